@@ -0,0 +1,45 @@
+-- Create dispute status enum
+CREATE TYPE dispute_status AS ENUM ('open', 'under_review', 'won', 'lost');
+
+-- Create disputes table
+CREATE TABLE IF NOT EXISTS disputes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id),
+    transaction_id UUID REFERENCES transactions(id),
+    payment_id UUID REFERENCES payments(id),
+    customer_account_id UUID NOT NULL REFERENCES accounts(id),
+    counterparty_account_id UUID REFERENCES accounts(id),
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency VARCHAR(3) DEFAULT 'USD',
+    reason VARCHAR(100) NOT NULL,
+    description TEXT,
+    status dispute_status DEFAULT 'open',
+    held_amount BIGINT NOT NULL DEFAULT 0,
+    resolution_note TEXT,
+    opened_by UUID,
+    resolved_by UUID,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    CHECK ((transaction_id IS NULL) <> (payment_id IS NULL))
+);
+
+-- Create dispute_evidence table
+CREATE TABLE IF NOT EXISTS dispute_evidence (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    dispute_id UUID NOT NULL REFERENCES disputes(id) ON DELETE CASCADE,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    storage_key VARCHAR(500) NOT NULL,
+    description TEXT,
+    uploaded_by UUID,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_disputes_user_id ON disputes(user_id);
+CREATE INDEX IF NOT EXISTS idx_disputes_status ON disputes(status);
+CREATE INDEX IF NOT EXISTS idx_disputes_transaction_id ON disputes(transaction_id);
+CREATE INDEX IF NOT EXISTS idx_disputes_payment_id ON disputes(payment_id);
+CREATE INDEX IF NOT EXISTS idx_dispute_evidence_dispute_id ON dispute_evidence(dispute_id);
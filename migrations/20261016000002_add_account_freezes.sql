@@ -0,0 +1,20 @@
+-- Create freeze reason enum
+CREATE TYPE freeze_reason AS ENUM ('suspected_fraud', 'compliance_review', 'legal_order', 'customer_request', 'other');
+
+-- Add freeze state to accounts
+ALTER TABLE accounts
+    ADD COLUMN IF NOT EXISTS frozen_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS freeze_reason freeze_reason,
+    ADD COLUMN IF NOT EXISTS freeze_note TEXT,
+    ADD COLUMN IF NOT EXISTS frozen_by UUID;
+
+-- Add freeze state to virtual accounts
+ALTER TABLE virtual_accounts
+    ADD COLUMN IF NOT EXISTS frozen_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS freeze_reason freeze_reason,
+    ADD COLUMN IF NOT EXISTS freeze_note TEXT,
+    ADD COLUMN IF NOT EXISTS frozen_by UUID;
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_accounts_frozen_at ON accounts(frozen_at) WHERE frozen_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_virtual_accounts_frozen_at ON virtual_accounts(frozen_at) WHERE frozen_at IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Create KYC tier enum
+CREATE TYPE kyc_tier AS ENUM ('tier0', 'tier1', 'tier2');
+
+-- Store the computed tier on the user profile
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS kyc_tier kyc_tier NOT NULL DEFAULT 'tier0',
+    ADD COLUMN IF NOT EXISTS kyc_tier_updated_at TIMESTAMPTZ;
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_users_kyc_tier ON users(kyc_tier);
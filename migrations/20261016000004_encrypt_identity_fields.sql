@@ -0,0 +1,2 @@
+-- Encrypted envelopes are longer than the plaintext document numbers they replace
+ALTER TABLE identity_verifications ALTER COLUMN document_number TYPE TEXT;
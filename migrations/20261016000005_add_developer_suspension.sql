@@ -0,0 +1,12 @@
+-- Administrative suspension and deletion of developer accounts.
+-- Suspended developers cannot obtain or refresh access tokens; deleted
+-- developers are retained for audit purposes but hidden from listings.
+
+ALTER TABLE developers
+    ADD COLUMN IF NOT EXISTS suspended_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS suspension_reason TEXT,
+    ADD COLUMN IF NOT EXISTS suspended_by UUID REFERENCES developers(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_developers_suspended_at ON developers(suspended_at) WHERE suspended_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_developers_deleted_at ON developers(deleted_at) WHERE deleted_at IS NOT NULL;
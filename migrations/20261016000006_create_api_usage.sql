@@ -0,0 +1,15 @@
+-- Daily API usage aggregates for billing, one row per project, scope and endpoint.
+
+CREATE TABLE IF NOT EXISTS api_usage_daily (
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    developer_id UUID NOT NULL REFERENCES developers(id) ON DELETE CASCADE,
+    usage_date DATE NOT NULL,
+    scope TEXT NOT NULL,
+    endpoint TEXT NOT NULL,
+    request_count BIGINT NOT NULL DEFAULT 0,
+    error_count BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, usage_date, scope, endpoint)
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_usage_daily_usage_date ON api_usage_daily(usage_date);
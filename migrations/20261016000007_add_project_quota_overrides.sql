@@ -0,0 +1,7 @@
+-- Administrative overrides of the per-environment monthly API call quota.
+
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS monthly_quota_override BIGINT CHECK (monthly_quota_override >= 0),
+    ADD COLUMN IF NOT EXISTS quota_override_reason TEXT,
+    ADD COLUMN IF NOT EXISTS quota_override_by UUID REFERENCES developers(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS quota_override_at TIMESTAMPTZ;
@@ -0,0 +1,17 @@
+-- Signed income reports. Third parties validate a report by its verification
+-- code; the stored payload is re-signed on lookup to detect tampering.
+
+CREATE TABLE IF NOT EXISTS income_reports (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id),
+    verification_code VARCHAR(32) NOT NULL UNIQUE,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    report_data JSONB NOT NULL,
+    signature TEXT NOT NULL,
+    generated_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_income_reports_user_id ON income_reports(user_id);
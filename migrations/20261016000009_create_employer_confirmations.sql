@@ -0,0 +1,26 @@
+-- Employer confirmation of income verifications. The employer contact receives
+-- a single-use link; only a SHA-256 hash of its token is stored.
+
+CREATE TYPE employer_confirmation_status AS ENUM ('pending', 'confirmed', 'rejected', 'expired', 'cancelled');
+
+CREATE TABLE IF NOT EXISTS employer_confirmations (
+    id UUID PRIMARY KEY,
+    verification_id UUID NOT NULL REFERENCES income_verifications(id) ON DELETE CASCADE,
+    contact_name VARCHAR(255) NOT NULL,
+    contact_email VARCHAR(255) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    status employer_confirmation_status NOT NULL DEFAULT 'pending',
+    employment_confirmed BOOLEAN,
+    confirmed_job_title VARCHAR(255),
+    confirmed_annual_income BIGINT,
+    employer_comment TEXT,
+    requested_by UUID NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    responded_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_employer_confirmations_verification_id ON employer_confirmations(verification_id);
+CREATE INDEX IF NOT EXISTS idx_employer_confirmations_pending_expiry
+    ON employer_confirmations(expires_at) WHERE status = 'pending';
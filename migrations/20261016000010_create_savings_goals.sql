@@ -0,0 +1,45 @@
+-- Create savings goal enums
+CREATE TYPE savings_goal_status AS ENUM ('active', 'completed', 'closed');
+CREATE TYPE goal_allocation_rule AS ENUM ('none', 'percentage', 'fixed');
+CREATE TYPE goal_movement_type AS ENUM ('allocation', 'auto_allocation', 'release');
+
+-- Create savings_goals table. Each goal is a bucket of the account's balance;
+-- funds in locked goals are excluded from the spendable balance.
+CREATE TABLE IF NOT EXISTS savings_goals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    name VARCHAR(100) NOT NULL,
+    target_amount BIGINT NOT NULL CHECK (target_amount > 0),
+    balance BIGINT NOT NULL DEFAULT 0 CHECK (balance >= 0),
+    currency VARCHAR(3) DEFAULT 'USD',
+    target_date DATE,
+    locked BOOLEAN NOT NULL DEFAULT TRUE,
+    allocation_rule goal_allocation_rule NOT NULL DEFAULT 'none',
+    -- Basis points of each inbound credit for percentage rules, minor units for fixed rules
+    allocation_value BIGINT NOT NULL DEFAULT 0 CHECK (allocation_value >= 0),
+    status savings_goal_status NOT NULL DEFAULT 'active',
+    completed_at TIMESTAMPTZ,
+    closed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Create savings_goal_movements table
+CREATE TABLE IF NOT EXISTS savings_goal_movements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    goal_id UUID NOT NULL REFERENCES savings_goals(id) ON DELETE CASCADE,
+    movement_type goal_movement_type NOT NULL,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    balance_after BIGINT NOT NULL,
+    transaction_id UUID REFERENCES transactions(id),
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_savings_goals_account_id ON savings_goals(account_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_savings_goals_account_name
+    ON savings_goals(account_id, LOWER(name)) WHERE status <> 'closed';
+CREATE INDEX IF NOT EXISTS idx_savings_goal_movements_goal_id ON savings_goal_movements(goal_id);
+-- A credit is only auto-allocated to a goal once
+CREATE UNIQUE INDEX IF NOT EXISTS idx_savings_goal_movements_auto_allocation
+    ON savings_goal_movements(goal_id, transaction_id) WHERE movement_type = 'auto_allocation';
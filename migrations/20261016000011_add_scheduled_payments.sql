@@ -0,0 +1,12 @@
+-- Add a scheduled state for future-dated payments
+ALTER TYPE payment_status ADD VALUE IF NOT EXISTS 'scheduled' BEFORE 'pending';
+
+-- Add scheduling columns to payments
+ALTER TABLE payments
+    ADD COLUMN IF NOT EXISTS execute_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS execution_timezone VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS executed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS execution_error TEXT;
+
+-- Create index for the scheduler's due-payment scan
+CREATE INDEX IF NOT EXISTS idx_payments_execute_at ON payments(execute_at) WHERE execute_at IS NOT NULL;
@@ -0,0 +1,47 @@
+-- Create fee type enum
+CREATE TYPE fee_type AS ENUM ('flat', 'percentage', 'tiered');
+
+-- Create fee_schedules table. For each fee code the most specific active
+-- schedule matching a payment's project, method and currency applies.
+CREATE TABLE IF NOT EXISTS fee_schedules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    fee_code VARCHAR(50) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    project_id UUID REFERENCES projects(id) ON DELETE CASCADE,
+    payment_method payment_method,
+    currency VARCHAR(3),
+    fee_type fee_type NOT NULL,
+    flat_amount BIGINT NOT NULL DEFAULT 0 CHECK (flat_amount >= 0),
+    percentage_bps BIGINT NOT NULL DEFAULT 0 CHECK (percentage_bps >= 0),
+    tiers JSONB,
+    min_fee BIGINT CHECK (min_fee >= 0),
+    max_fee BIGINT CHECK (max_fee >= 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Create fee_postings table, the fee ledger kept separate from payment principal
+CREATE TABLE IF NOT EXISTS fee_postings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    payment_id UUID NOT NULL REFERENCES payments(id),
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    fee_schedule_id UUID REFERENCES fee_schedules(id),
+    fee_code VARCHAR(50) NOT NULL,
+    entry_type VARCHAR(20) NOT NULL CHECK (entry_type IN ('charge', 'reversal')),
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency VARCHAR(3) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Add fee columns to payments
+ALTER TABLE payments
+    ADD COLUMN IF NOT EXISTS project_id UUID REFERENCES projects(id),
+    ADD COLUMN IF NOT EXISTS fee_amount BIGINT NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS fee_breakdown JSONB;
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_fee_schedules_lookup ON fee_schedules(fee_code, is_active);
+CREATE INDEX IF NOT EXISTS idx_fee_postings_payment_id ON fee_postings(payment_id);
+CREATE INDEX IF NOT EXISTS idx_fee_postings_account_id ON fee_postings(account_id);
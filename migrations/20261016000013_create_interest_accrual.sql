@@ -0,0 +1,55 @@
+-- Interest capitalizations are posted as their own transaction type
+ALTER TYPE transaction_type ADD VALUE IF NOT EXISTS 'interest';
+
+-- Create interest_rates table. The rate for an account type and currency on a
+-- given day is the one with the latest effective_from on or before that day.
+CREATE TABLE IF NOT EXISTS interest_rates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_type VARCHAR(50) NOT NULL,
+    currency VARCHAR(3) NOT NULL DEFAULT 'USD',
+    annual_rate_bps BIGINT NOT NULL CHECK (annual_rate_bps BETWEEN 0 AND 10000),
+    -- Balances below this amount earn no interest
+    min_balance BIGINT NOT NULL DEFAULT 0 CHECK (min_balance >= 0),
+    effective_from DATE NOT NULL,
+    created_by UUID,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(account_type, currency, effective_from)
+);
+
+-- Create interest_capitalizations table. Accrued interest is paid into the
+-- account once a month; fractions of a minor unit carry over to the next month.
+CREATE TABLE IF NOT EXISTS interest_capitalizations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    -- Accruals for the period plus the previous carry, in millionths of a minor unit
+    accrued_micros BIGINT NOT NULL,
+    amount BIGINT NOT NULL CHECK (amount >= 0),
+    carried_micros BIGINT NOT NULL CHECK (carried_micros >= 0),
+    currency VARCHAR(3) DEFAULT 'USD',
+    transaction_id UUID REFERENCES transactions(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(account_id, period_end)
+);
+
+-- Create interest_accruals table, one posting per account per day
+CREATE TABLE IF NOT EXISTS interest_accruals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    accrual_date DATE NOT NULL,
+    balance BIGINT NOT NULL,
+    annual_rate_bps BIGINT NOT NULL,
+    -- Interest for the day in millionths of a minor unit
+    amount_micros BIGINT NOT NULL CHECK (amount_micros >= 0),
+    currency VARCHAR(3) DEFAULT 'USD',
+    capitalization_id UUID REFERENCES interest_capitalizations(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(account_id, accrual_date)
+);
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_interest_rates_lookup ON interest_rates(account_type, currency, effective_from DESC);
+CREATE INDEX IF NOT EXISTS idx_interest_accruals_uncapitalized ON interest_accruals(account_id, accrual_date)
+    WHERE capitalization_id IS NULL;
+CREATE INDEX IF NOT EXISTS idx_interest_capitalizations_account ON interest_capitalizations(account_id, period_end DESC);
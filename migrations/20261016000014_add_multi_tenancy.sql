@@ -0,0 +1,70 @@
+-- Migration: Multi-tenancy
+-- Organizations are the tenant boundary. They own developers and projects,
+-- and every top-level domain row records the tenant it belongs to.
+
+-- Create organizations table
+CREATE TABLE IF NOT EXISTS organizations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Give every existing developer an organization of their own, reusing the
+-- developer id so projects can be mapped without a lookup table
+INSERT INTO organizations (id, name, created_at, updated_at)
+SELECT id, COALESCE(NULLIF(company, ''), name), created_at, NOW()
+FROM developers
+ON CONFLICT (id) DO NOTHING;
+
+ALTER TABLE developers ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id);
+UPDATE developers SET organization_id = id WHERE organization_id IS NULL;
+ALTER TABLE developers ALTER COLUMN organization_id SET NOT NULL;
+
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS organization_id UUID REFERENCES organizations(id);
+UPDATE projects p SET organization_id = d.organization_id
+FROM developers d
+WHERE d.id = p.developer_id AND p.organization_id IS NULL;
+ALTER TABLE projects ALTER COLUMN organization_id SET NOT NULL;
+
+-- Tenant of each top-level domain row. Child rows (balances, evidence, goal
+-- movements, accruals, ...) are only reachable through their parent. Rows
+-- created before tenancy have no tenant and are not visible to tenant-scoped
+-- queries until they are assigned one.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+ALTER TABLE virtual_accounts ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+ALTER TABLE identity_verifications ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+ALTER TABLE income_verifications ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+ALTER TABLE disputes ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+ALTER TABLE savings_goals ADD COLUMN IF NOT EXISTS tenant_id UUID REFERENCES organizations(id);
+
+-- Payments created through a project already identify their tenant
+UPDATE payments pm SET tenant_id = p.organization_id
+FROM projects p
+WHERE p.id = pm.project_id AND pm.tenant_id IS NULL;
+
+-- Savings goals and disputes belong to the tenant of their account
+UPDATE savings_goals g SET tenant_id = a.tenant_id
+FROM accounts a
+WHERE a.id = g.account_id AND g.tenant_id IS NULL AND a.tenant_id IS NOT NULL;
+
+UPDATE disputes d SET tenant_id = a.tenant_id
+FROM accounts a
+WHERE a.id = d.customer_account_id AND d.tenant_id IS NULL AND a.tenant_id IS NOT NULL;
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_developers_organization_id ON developers(organization_id);
+CREATE INDEX IF NOT EXISTS idx_projects_organization_id ON projects(organization_id);
+CREATE INDEX IF NOT EXISTS idx_users_tenant_id ON users(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_accounts_tenant_id ON accounts(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_transactions_tenant_id ON transactions(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_payments_tenant_id ON payments(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_virtual_accounts_tenant_id ON virtual_accounts(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_identity_verifications_tenant_id ON identity_verifications(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_income_verifications_tenant_id ON income_verifications(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_disputes_tenant_id ON disputes(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_savings_goals_tenant_id ON savings_goals(tenant_id);
@@ -0,0 +1,42 @@
+-- Create organization role enum
+CREATE TYPE organization_role AS ENUM ('owner', 'admin', 'member');
+
+-- Create organization_members table. A developer's own organization
+-- (developers.organization_id) is where their new projects go by default;
+-- membership grants access to other organizations' projects.
+CREATE TABLE IF NOT EXISTS organization_members (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    developer_id UUID NOT NULL REFERENCES developers(id) ON DELETE CASCADE,
+    role organization_role NOT NULL DEFAULT 'member',
+    invited_by UUID REFERENCES developers(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (organization_id, developer_id)
+);
+
+-- Every developer owns the organization created for them
+INSERT INTO organization_members (organization_id, developer_id, role)
+SELECT organization_id, id, 'owner' FROM developers
+ON CONFLICT DO NOTHING;
+
+-- Create organization_invitations table. Only a hash of the emailed token is stored.
+CREATE TABLE IF NOT EXISTS organization_invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    role organization_role NOT NULL DEFAULT 'member' CHECK (role <> 'owner'),
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    invited_by UUID NOT NULL REFERENCES developers(id),
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_by UUID REFERENCES developers(id),
+    accepted_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_organization_members_developer_id ON organization_members(developer_id);
+CREATE INDEX IF NOT EXISTS idx_organization_invitations_organization_id ON organization_invitations(organization_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_organization_invitations_open
+    ON organization_invitations(organization_id, LOWER(email))
+    WHERE accepted_at IS NULL AND revoked_at IS NULL;
@@ -0,0 +1,15 @@
+-- Developers registered through the API only have a display name. Databases
+-- built from the auth plane migrations still require the older first and
+-- last name columns, which nothing writes any more.
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_schema = current_schema() AND table_name = 'developers' AND column_name = 'first_name'
+    ) THEN
+        ALTER TABLE developers
+            ALTER COLUMN first_name DROP NOT NULL,
+            ALTER COLUMN last_name DROP NOT NULL;
+    END IF;
+END $$;
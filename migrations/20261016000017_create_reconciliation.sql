@@ -0,0 +1,47 @@
+-- Reconciliation of internal transactions against settlement files from
+-- external rails. Each ingested file is a run; entries that do not reconcile
+-- are recorded as breaks for operations to resolve.
+
+CREATE TYPE settlement_format AS ENUM ('csv', 'mt940');
+CREATE TYPE reconciliation_break_type AS ENUM ('missing_internal', 'missing_settlement', 'amount_mismatch', 'date_mismatch');
+CREATE TYPE reconciliation_break_status AS ENUM ('open', 'resolved');
+CREATE TYPE reconciliation_resolution AS ENUM ('manually_matched', 'adjusted', 'written_off');
+
+CREATE TABLE IF NOT EXISTS reconciliation_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    file_name VARCHAR(255) NOT NULL,
+    format settlement_format NOT NULL,
+    -- SHA-256 of the file content; a file is only reconciled once
+    file_hash VARCHAR(64) NOT NULL UNIQUE,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    entry_count INTEGER NOT NULL,
+    matched_count INTEGER NOT NULL,
+    break_count INTEGER NOT NULL,
+    created_by UUID,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS reconciliation_breaks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    run_id UUID NOT NULL REFERENCES reconciliation_runs(id) ON DELETE CASCADE,
+    break_type reconciliation_break_type NOT NULL,
+    status reconciliation_break_status NOT NULL DEFAULT 'open',
+    reference VARCHAR(255),
+    transaction_id UUID REFERENCES transactions(id),
+    currency VARCHAR(3),
+    settlement_amount BIGINT,
+    internal_amount BIGINT,
+    settlement_date DATE,
+    internal_date DATE,
+    description TEXT,
+    resolution reconciliation_resolution,
+    resolution_note TEXT,
+    resolved_by UUID,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_reconciliation_runs_created_at ON reconciliation_runs(created_at);
+CREATE INDEX IF NOT EXISTS idx_reconciliation_breaks_run_id ON reconciliation_breaks(run_id);
+CREATE INDEX IF NOT EXISTS idx_reconciliation_breaks_status ON reconciliation_breaks(status);
@@ -0,0 +1,32 @@
+-- Ledger integrity checks. Each run recomputes balances from the postings in
+-- balance_history and records every invariant it finds broken.
+
+CREATE TYPE ledger_integrity_status AS ENUM ('passed', 'failed');
+CREATE TYPE ledger_discrepancy_type AS ENUM ('balance_mismatch', 'posting_arithmetic', 'unbalanced_transaction');
+
+CREATE TABLE IF NOT EXISTS ledger_integrity_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    status ledger_integrity_status NOT NULL,
+    accounts_checked INTEGER NOT NULL,
+    postings_checked INTEGER NOT NULL,
+    transactions_checked INTEGER NOT NULL,
+    discrepancy_count INTEGER NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ledger_discrepancies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    run_id UUID NOT NULL REFERENCES ledger_integrity_runs(id) ON DELETE CASCADE,
+    discrepancy_type ledger_discrepancy_type NOT NULL,
+    account_id UUID,
+    transaction_id UUID,
+    posting_id UUID,
+    expected BIGINT NOT NULL,
+    actual BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_integrity_runs_started_at ON ledger_integrity_runs(started_at);
+CREATE INDEX IF NOT EXISTS idx_ledger_discrepancies_run_id ON ledger_discrepancies(run_id);
+CREATE INDEX IF NOT EXISTS idx_balance_history_transaction_id ON balance_history(transaction_id);
@@ -0,0 +1,77 @@
+-- General ledger. Internal GL accounts form the chart of accounts; fees,
+-- interest and suspense amounts are booked to them as balanced journals whose
+-- accounts are chosen by posting rules.
+
+CREATE TYPE gl_account_type AS ENUM ('asset', 'liability', 'income', 'expense');
+CREATE TYPE gl_posting_event AS ENUM ('fee_charged', 'fee_reversed', 'interest_capitalized');
+
+CREATE TABLE IF NOT EXISTS gl_accounts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(20) NOT NULL UNIQUE,
+    name VARCHAR(100) NOT NULL,
+    account_type gl_account_type NOT NULL,
+    description TEXT,
+    -- Seeded accounts the posting rules rely on; they cannot be deactivated
+    is_system BOOLEAN NOT NULL DEFAULT FALSE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by UUID,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- For each event the rule with a matching fee code applies, falling back to
+-- the event's default rule (no fee code)
+CREATE TABLE IF NOT EXISTS gl_posting_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event gl_posting_event NOT NULL,
+    fee_code VARCHAR(50),
+    debit_account_id UUID NOT NULL REFERENCES gl_accounts(id),
+    credit_account_id UUID NOT NULL REFERENCES gl_accounts(id),
+    created_by UUID,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    CHECK (debit_account_id <> credit_account_id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_gl_posting_rules_event_fee_code
+    ON gl_posting_rules(event, COALESCE(fee_code, ''));
+
+-- Journal lines. Each journal's debits equal its credits.
+CREATE TABLE IF NOT EXISTS gl_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    journal_id UUID NOT NULL,
+    gl_account_id UUID NOT NULL REFERENCES gl_accounts(id),
+    debit BIGINT NOT NULL DEFAULT 0 CHECK (debit >= 0),
+    credit BIGINT NOT NULL DEFAULT 0 CHECK (credit >= 0),
+    currency VARCHAR(3) NOT NULL,
+    event gl_posting_event NOT NULL,
+    -- The payment or transaction the journal books
+    source_id UUID NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    CHECK ((debit = 0) <> (credit = 0))
+);
+
+CREATE INDEX IF NOT EXISTS idx_gl_entries_journal_id ON gl_entries(journal_id);
+CREATE INDEX IF NOT EXISTS idx_gl_entries_account_created_at ON gl_entries(gl_account_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_gl_entries_source_id ON gl_entries(source_id);
+
+-- System accounts
+INSERT INTO gl_accounts (code, name, account_type, description, is_system) VALUES
+    ('1000', 'Settlement bank', 'asset', 'Funds held at settlement banks and rails', TRUE),
+    ('2000', 'Customer deposits', 'liability', 'Balances owed to account holders', TRUE),
+    ('2900', 'Suspense', 'liability', 'Amounts received or paid that are not yet identified', TRUE),
+    ('4000', 'Fee income', 'income', 'Fees charged on payments', TRUE),
+    ('5000', 'Interest expense', 'expense', 'Interest paid to account holders', TRUE)
+ON CONFLICT (code) DO NOTHING;
+
+-- Default posting rules
+INSERT INTO gl_posting_rules (event, debit_account_id, credit_account_id)
+SELECT rule.event::gl_posting_event, debit.id, credit.id
+FROM (VALUES
+    ('fee_charged', '2000', '4000'),
+    ('fee_reversed', '4000', '2000'),
+    ('interest_capitalized', '5000', '2000')
+) AS rule(event, debit_code, credit_code)
+JOIN gl_accounts debit ON debit.code = rule.debit_code
+JOIN gl_accounts credit ON credit.code = rule.credit_code
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,11 @@
+-- Payments post as pending: the payer's available balance is held at once
+-- and ledger balances move when the payment settles after the clearing delay
+-- of its payment method.
+ALTER TABLE payments
+    ADD COLUMN IF NOT EXISTS transaction_id UUID REFERENCES transactions(id),
+    ADD COLUMN IF NOT EXISTS expected_settlement_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS settled_at TIMESTAMPTZ;
+
+-- Create index for the settlement job's due-payment scan
+CREATE INDEX IF NOT EXISTS idx_payments_expected_settlement_at ON payments(expected_settlement_at)
+    WHERE expected_settlement_at IS NOT NULL AND settled_at IS NULL;
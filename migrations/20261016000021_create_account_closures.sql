@@ -0,0 +1,29 @@
+-- Create account closure enums
+CREATE TYPE account_closure_reason AS ENUM ('customer_request', 'switching_provider', 'consolidation', 'other');
+
+-- Closed accounts are kept, never deleted: they stop accepting money movement
+-- and their records are retained until the closure's retain_until date.
+ALTER TABLE accounts
+    ADD COLUMN IF NOT EXISTS closed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS closure_reason account_closure_reason;
+
+-- Create account_closures table
+CREATE TABLE IF NOT EXISTS account_closures (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID REFERENCES organizations(id),
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    reason account_closure_reason NOT NULL,
+    note TEXT,
+    -- Account the remaining balance was swept to, if it was not already zero
+    transfer_to_account_id UUID REFERENCES accounts(id),
+    transferred_amount BIGINT NOT NULL DEFAULT 0 CHECK (transferred_amount >= 0),
+    transaction_id UUID REFERENCES transactions(id),
+    requested_by UUID NOT NULL,
+    retain_until DATE NOT NULL,
+    closed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create indexes
+CREATE UNIQUE INDEX IF NOT EXISTS idx_account_closures_account_id ON account_closures(account_id);
+CREATE INDEX IF NOT EXISTS idx_account_closures_tenant_id ON account_closures(tenant_id);
+CREATE INDEX IF NOT EXISTS idx_account_closures_retain_until ON account_closures(retain_until);
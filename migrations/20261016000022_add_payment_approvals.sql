@@ -0,0 +1,22 @@
+-- Payments over the approval threshold wait for a second user to approve them
+ALTER TYPE payment_status ADD VALUE IF NOT EXISTS 'pending_approval' AFTER 'scheduled';
+
+CREATE TYPE payment_approval_decision AS ENUM ('approved', 'rejected');
+
+-- The maker of a payment, who may not also approve it
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS created_by UUID;
+
+-- Create payment_approvals table: the checker's decision on a payment
+CREATE TABLE IF NOT EXISTS payment_approvals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    payment_id UUID NOT NULL REFERENCES payments(id),
+    tenant_id UUID REFERENCES organizations(id),
+    decision payment_approval_decision NOT NULL,
+    decided_by UUID NOT NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- A payment is decided once
+CREATE UNIQUE INDEX IF NOT EXISTS idx_payment_approvals_payment_id ON payment_approvals(payment_id);
+
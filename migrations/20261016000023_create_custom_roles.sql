@@ -0,0 +1,24 @@
+-- Tenant-defined roles. Each role is a named set of "resource:action"
+-- permissions granted on top of a developer's built-in roles.
+CREATE TABLE IF NOT EXISTS custom_roles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    description TEXT,
+    permissions TEXT[] NOT NULL DEFAULT '{}',
+    created_by UUID REFERENCES developers(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (tenant_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS custom_role_assignments (
+    role_id UUID NOT NULL REFERENCES custom_roles(id) ON DELETE CASCADE,
+    developer_id UUID NOT NULL REFERENCES developers(id) ON DELETE CASCADE,
+    assigned_by UUID REFERENCES developers(id),
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (role_id, developer_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_custom_role_assignments_developer_id ON custom_role_assignments(developer_id);
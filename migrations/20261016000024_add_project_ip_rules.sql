@@ -0,0 +1,7 @@
+-- Networks a project's tokens may be used from, in CIDR notation. Denied
+-- networks always win; a non-empty allowlist refuses every other address.
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS ip_allowlist TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS ip_denylist TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS ip_rules_updated_by UUID REFERENCES developers(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS ip_rules_updated_at TIMESTAMPTZ;
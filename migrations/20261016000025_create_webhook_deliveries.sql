@@ -0,0 +1,51 @@
+-- Create webhook delivery enums
+CREATE TYPE webhook_delivery_status AS ENUM ('pending', 'delivered', 'dead_lettered');
+
+-- Outgoing webhooks are queued here and delivered by a background job, which
+-- retries failures with exponential backoff
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID REFERENCES organizations(id),
+    event_type VARCHAR(100) NOT NULL,
+    url TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    status webhook_delivery_status NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_status_code INTEGER,
+    last_error TEXT,
+    -- Dead letter this delivery was replayed from
+    replay_of UUID,
+    delivered_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Deliveries that exhausted their retries, kept until an administrator
+-- replays them
+CREATE TABLE IF NOT EXISTS webhook_dead_letters (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    delivery_id UUID NOT NULL UNIQUE REFERENCES webhook_deliveries(id),
+    tenant_id UUID REFERENCES organizations(id),
+    event_type VARCHAR(100) NOT NULL,
+    url TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_status_code INTEGER,
+    last_error TEXT,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    replayed_at TIMESTAMPTZ,
+    replayed_by UUID,
+    replay_delivery_id UUID REFERENCES webhook_deliveries(id)
+);
+
+ALTER TABLE webhook_deliveries
+    ADD CONSTRAINT webhook_deliveries_replay_of_fkey
+    FOREIGN KEY (replay_of) REFERENCES webhook_dead_letters(id);
+
+-- Create indexes
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due
+    ON webhook_deliveries(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_webhook_dead_letters_outstanding
+    ON webhook_dead_letters(failed_at) WHERE replayed_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_webhook_dead_letters_event_type ON webhook_dead_letters(event_type);
@@ -0,0 +1,27 @@
+-- Every domain event published on the event bus is journaled here, so
+-- integrators can page through the history their tenant's webhooks carried
+CREATE TABLE IF NOT EXISTS domain_events (
+    id UUID PRIMARY KEY,
+    -- Monotonic position used as the paging cursor
+    sequence BIGINT GENERATED ALWAYS AS IDENTITY UNIQUE,
+    event_type VARCHAR(100) NOT NULL,
+    tenant_id UUID REFERENCES organizations(id),
+    account_ids UUID[] NOT NULL DEFAULT '{}',
+    data JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_domain_events_tenant_sequence ON domain_events(tenant_id, sequence);
+CREATE INDEX IF NOT EXISTS idx_domain_events_tenant_occurred_at ON domain_events(tenant_id, occurred_at);
+
+-- Domain events are delivered to the webhook URL of each project in the tenant
+ALTER TABLE webhook_deliveries
+    ADD COLUMN IF NOT EXISTS project_id UUID REFERENCES projects(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS event_id UUID REFERENCES domain_events(id);
+
+ALTER TABLE webhook_dead_letters
+    ADD COLUMN IF NOT EXISTS project_id UUID REFERENCES projects(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS event_id UUID REFERENCES domain_events(id);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_event_id ON webhook_deliveries(event_id);
@@ -0,0 +1,22 @@
+-- Documents uploaded as evidence for income verifications (payslips, bank
+-- statements, employment letters). Files live in object storage; only the
+-- fields detected by the parsing pipeline are stored here, never the raw text.
+
+CREATE TYPE income_document_type AS ENUM ('payslip', 'bank_statement', 'employment_letter', 'tax_return', 'other');
+CREATE TYPE income_document_parse_status AS ENUM ('parsed', 'no_fields_found', 'no_text');
+
+CREATE TABLE IF NOT EXISTS income_documents (
+    id UUID PRIMARY KEY,
+    verification_id UUID NOT NULL REFERENCES income_verifications(id) ON DELETE CASCADE,
+    document_type income_document_type NOT NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    storage_key VARCHAR(512) NOT NULL,
+    parse_status income_document_parse_status NOT NULL,
+    extracted_fields JSONB,
+    uploaded_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_income_documents_verification_id ON income_documents(verification_id);
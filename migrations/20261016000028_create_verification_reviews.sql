@@ -0,0 +1,30 @@
+-- Manual review queue for identity and income verifications. A verification
+-- has at most one open (pending or claimed) review at a time; a reviewer
+-- claims it, inspects the evidence and approves or rejects it with a reason.
+
+CREATE TYPE verification_kind AS ENUM ('identity', 'income');
+CREATE TYPE verification_review_status AS ENUM ('pending', 'claimed', 'approved', 'rejected');
+
+CREATE TABLE IF NOT EXISTS verification_reviews (
+    id UUID PRIMARY KEY,
+    verification_kind verification_kind NOT NULL,
+    verification_id UUID NOT NULL,
+    subject_user_id UUID NOT NULL REFERENCES users(id),
+    status verification_review_status NOT NULL DEFAULT 'pending',
+    flag_reason TEXT NOT NULL,
+    -- NULL when the verification was flagged automatically
+    flagged_by UUID,
+    claimed_by UUID,
+    claimed_at TIMESTAMPTZ,
+    decided_by UUID,
+    decision_reason TEXT,
+    decided_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_verification_reviews_open
+    ON verification_reviews(verification_kind, verification_id) WHERE status IN ('pending', 'claimed');
+CREATE INDEX IF NOT EXISTS idx_verification_reviews_status ON verification_reviews(status, created_at);
+CREATE INDEX IF NOT EXISTS idx_verification_reviews_verification
+    ON verification_reviews(verification_kind, verification_id);
@@ -0,0 +1,44 @@
+-- User notifications. Each user chooses, per category, which channels
+-- (email, webhook, in-app) a notification is delivered on; in-app
+-- notifications are kept here as the user's notification feed.
+
+CREATE TYPE notification_category AS ENUM ('payments', 'security', 'account');
+CREATE TYPE notification_kind AS ENUM (
+    'payment_completed',
+    'payment_received',
+    'fraud_alert',
+    'login_alert',
+    'account_closed'
+);
+
+-- Users without a row for a category get the defaults for that category
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id UUID NOT NULL REFERENCES users(id),
+    category notification_category NOT NULL,
+    email_enabled BOOLEAN NOT NULL,
+    webhook_enabled BOOLEAN NOT NULL,
+    in_app_enabled BOOLEAN NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, category)
+);
+
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id),
+    tenant_id UUID REFERENCES organizations(id),
+    category notification_category NOT NULL,
+    kind notification_kind NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    data JSONB NOT NULL DEFAULT '{}',
+    -- What raised the notification (a domain event, a freeze), so it is
+    -- only recorded once
+    source_id UUID,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_notifications_user_source
+    ON notifications(user_id, kind, source_id) WHERE source_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_notifications_user_created_at ON notifications(user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread ON notifications(user_id) WHERE read_at IS NULL;
@@ -0,0 +1,31 @@
+-- Periodic reports developers subscribe to. A scheduler job renders each
+-- due report for the subscription's tenant and delivers it by email or to a
+-- webhook URL, then schedules the next run.
+
+CREATE TYPE scheduled_report_type AS ENUM (
+    'daily_transaction_summary',
+    'weekly_settlement',
+    'monthly_usage'
+);
+CREATE TYPE report_delivery_channel AS ENUM ('email', 'webhook');
+CREATE TYPE report_file_format AS ENUM ('csv', 'json');
+
+CREATE TABLE IF NOT EXISTS report_subscriptions (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES organizations(id),
+    developer_id UUID NOT NULL REFERENCES developers(id) ON DELETE CASCADE,
+    report_type scheduled_report_type NOT NULL,
+    channel report_delivery_channel NOT NULL,
+    -- Email address or webhook URL, depending on the channel
+    destination VARCHAR(2048) NOT NULL,
+    format report_file_format NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    next_run_at TIMESTAMPTZ NOT NULL,
+    last_run_at TIMESTAMPTZ,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_subscriptions_developer_id ON report_subscriptions(developer_id);
+CREATE INDEX IF NOT EXISTS idx_report_subscriptions_due ON report_subscriptions(next_run_at) WHERE is_active;
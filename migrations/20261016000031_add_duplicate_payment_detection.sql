@@ -0,0 +1,16 @@
+-- Duplicate payment detection. A payment with the same payer, amount,
+-- beneficiary and reference as one created within the project's window is
+-- flagged as a duplicate of it, or refused when the project blocks them.
+CREATE TYPE duplicate_payment_action AS ENUM ('off', 'flag', 'block');
+
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS duplicate_payment_action duplicate_payment_action NOT NULL DEFAULT 'flag',
+    ADD COLUMN IF NOT EXISTS duplicate_payment_window_minutes INTEGER NOT NULL DEFAULT 10
+        CHECK (duplicate_payment_window_minutes BETWEEN 1 AND 1440),
+    ADD COLUMN IF NOT EXISTS duplicate_rules_updated_by UUID REFERENCES developers(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS duplicate_rules_updated_at TIMESTAMPTZ;
+
+-- Earlier payment this one was flagged as a duplicate of
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS duplicate_of UUID REFERENCES payments(id);
+
+CREATE INDEX IF NOT EXISTS idx_payments_duplicate_lookup ON payments(from_account_id, amount, created_at);
@@ -0,0 +1,8 @@
+-- Language API messages are returned in when a request does not ask for a
+-- supported one through Accept-Language
+CREATE TYPE api_locale AS ENUM ('en', 'fr');
+
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS default_locale api_locale NOT NULL DEFAULT 'en',
+    ADD COLUMN IF NOT EXISTS locale_updated_by UUID REFERENCES developers(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS locale_updated_at TIMESTAMPTZ;
@@ -0,0 +1,10 @@
+-- Virtual accounts hold no balance row of their own: credits paid into one
+-- land on its parent account, with the posting attributed to the virtual
+-- account so its activity and balance can be derived from the ledger.
+ALTER TABLE balance_history ADD COLUMN IF NOT EXISTS virtual_account_id UUID REFERENCES virtual_accounts(id);
+
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS to_virtual_account_id UUID REFERENCES virtual_accounts(id);
+
+CREATE INDEX IF NOT EXISTS idx_balance_history_virtual_account
+    ON balance_history(virtual_account_id, created_at)
+    WHERE virtual_account_id IS NOT NULL;
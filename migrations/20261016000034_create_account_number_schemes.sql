@@ -0,0 +1,26 @@
+-- Per-project account number schemes, optionally per currency
+CREATE TYPE account_number_scheme_kind AS ENUM ('prefix_check_digit', 'bin_range', 'iban');
+
+CREATE TABLE IF NOT EXISTS account_number_schemes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    currency VARCHAR(3),
+    kind account_number_scheme_kind NOT NULL,
+    prefix VARCHAR(6),
+    bin_start VARCHAR(8),
+    bin_end VARCHAR(8),
+    country_code VARCHAR(2),
+    bank_code VARCHAR(8),
+    length INTEGER,
+    created_by UUID REFERENCES developers(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One scheme per currency, plus one default, per project
+CREATE UNIQUE INDEX IF NOT EXISTS idx_account_number_schemes_project_currency
+    ON account_number_schemes (project_id, COALESCE(currency, ''));
+
+-- IBANs run to 34 characters
+ALTER TABLE accounts ALTER COLUMN account_number TYPE VARCHAR(34);
+ALTER TABLE virtual_accounts ALTER COLUMN account_number TYPE VARCHAR(34);
@@ -0,0 +1,29 @@
+-- Credit report pulls from a credit bureau, completed by the bureau's callback
+CREATE TYPE credit_check_status AS ENUM ('pending', 'completed', 'failed');
+
+CREATE TABLE IF NOT EXISTS credit_checks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider VARCHAR(50) NOT NULL,
+    provider_reference VARCHAR(255),
+    status credit_check_status NOT NULL DEFAULT 'pending',
+    purpose VARCHAR(100) NOT NULL,
+    consent_reference VARCHAR(255) NOT NULL,
+    consent_granted_at TIMESTAMPTZ NOT NULL,
+    report JSONB,
+    failure_reason TEXT,
+    requested_by UUID NOT NULL,
+    completed_at TIMESTAMPTZ,
+    -- The report is removed once this passes; the record of the pull stays
+    retain_until TIMESTAMPTZ,
+    purged_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_credit_checks_user ON credit_checks (user_id, created_at DESC);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_credit_checks_provider_reference
+    ON credit_checks (provider, provider_reference) WHERE provider_reference IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_credit_checks_retention
+    ON credit_checks (retain_until) WHERE report IS NOT NULL;
@@ -0,0 +1,58 @@
+-- Merchant details resolved from raw transaction descriptions
+CREATE TYPE merchant_category AS ENUM (
+    'groceries', 'dining', 'transport', 'shopping', 'utilities', 'entertainment',
+    'travel', 'health', 'subscriptions', 'cash', 'fuel', 'other'
+);
+CREATE TYPE enrichment_status AS ENUM ('matched', 'unmatched');
+
+-- Lookup table for the rules provider: a description containing the pattern
+-- belongs to the merchant. Higher priority wins, then the longer pattern.
+CREATE TABLE IF NOT EXISTS merchant_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    pattern VARCHAR(100) NOT NULL UNIQUE,
+    merchant_name VARCHAR(255) NOT NULL,
+    domain VARCHAR(255),
+    logo_url TEXT,
+    category merchant_category NOT NULL,
+    city VARCHAR(100),
+    country VARCHAR(2),
+    priority INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO merchant_rules (pattern, merchant_name, domain, category) VALUES
+    ('AMZN', 'Amazon', 'amazon.com', 'shopping'),
+    ('AMAZON', 'Amazon', 'amazon.com', 'shopping'),
+    ('APPLE.COM/BILL', 'Apple', 'apple.com', 'subscriptions'),
+    ('NETFLIX', 'Netflix', 'netflix.com', 'subscriptions'),
+    ('SPOTIFY', 'Spotify', 'spotify.com', 'subscriptions'),
+    ('UBER EATS', 'Uber Eats', 'ubereats.com', 'dining'),
+    ('UBER', 'Uber', 'uber.com', 'transport'),
+    ('LYFT', 'Lyft', 'lyft.com', 'transport'),
+    ('STARBUCKS', 'Starbucks', 'starbucks.com', 'dining'),
+    ('MCDONALD', 'McDonald''s', 'mcdonalds.com', 'dining'),
+    ('WALMART', 'Walmart', 'walmart.com', 'groceries'),
+    ('WAL-MART', 'Walmart', 'walmart.com', 'groceries'),
+    ('TESCO', 'Tesco', 'tesco.com', 'groceries'),
+    ('SAINSBURY', 'Sainsbury''s', 'sainsburys.co.uk', 'groceries'),
+    ('WHOLEFDS', 'Whole Foods Market', 'wholefoodsmarket.com', 'groceries'),
+    ('SHELL', 'Shell', 'shell.com', 'fuel'),
+    ('CHEVRON', 'Chevron', 'chevron.com', 'fuel'),
+    ('AIRBNB', 'Airbnb', 'airbnb.com', 'travel'),
+    ('RYANAIR', 'Ryanair', 'ryanair.com', 'travel'),
+    ('CVS', 'CVS Pharmacy', 'cvs.com', 'health'),
+    ('ATM WITHDRAWAL', 'Cash withdrawal', NULL, 'cash')
+ON CONFLICT (pattern) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS transaction_enrichments (
+    transaction_id UUID PRIMARY KEY REFERENCES transactions(id) ON DELETE CASCADE,
+    status enrichment_status NOT NULL,
+    merchant_name VARCHAR(255),
+    logo_url TEXT,
+    category merchant_category,
+    city VARCHAR(100),
+    region VARCHAR(100),
+    country VARCHAR(2),
+    provider VARCHAR(50) NOT NULL,
+    enriched_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,30 @@
+-- Opt-in capture of sampled API traffic per project, for integrators
+-- debugging signature and validation failures
+CREATE TABLE IF NOT EXISTS project_capture_settings (
+    project_id UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    sample_rate DOUBLE PRECISION NOT NULL DEFAULT 1.0 CHECK (sample_rate > 0 AND sample_rate <= 1),
+    retention_hours INTEGER NOT NULL DEFAULT 24 CHECK (retention_hours BETWEEN 1 AND 168),
+    updated_by UUID,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Captured requests and responses, redacted before they are stored
+CREATE TABLE IF NOT EXISTS debug_captures (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    method VARCHAR(10) NOT NULL,
+    path TEXT NOT NULL,
+    query TEXT,
+    status_code INTEGER NOT NULL,
+    request_headers JSONB NOT NULL DEFAULT '{}',
+    request_body TEXT,
+    response_headers JSONB NOT NULL DEFAULT '{}',
+    response_body TEXT,
+    duration_ms BIGINT NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_debug_captures_project ON debug_captures(project_id, captured_at DESC);
+CREATE INDEX IF NOT EXISTS idx_debug_captures_expires ON debug_captures(expires_at);
@@ -0,0 +1,25 @@
+-- Feature flags for gradual rollouts. A disabled flag is off everywhere;
+-- otherwise a tenant or project target decides, then the rollout
+-- percentage of projects (or tenants, for calls without a project).
+CREATE TYPE feature_flag_target AS ENUM ('tenant', 'project');
+
+CREATE TABLE IF NOT EXISTS feature_flags (
+    key VARCHAR(100) PRIMARY KEY,
+    description TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    rollout_percentage INTEGER NOT NULL DEFAULT 0 CHECK (rollout_percentage BETWEEN 0 AND 100),
+    created_by UUID,
+    updated_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS feature_flag_targets (
+    flag_key VARCHAR(100) NOT NULL REFERENCES feature_flags(key) ON DELETE CASCADE,
+    target_type feature_flag_target NOT NULL,
+    target_id UUID NOT NULL,
+    enabled BOOLEAN NOT NULL,
+    created_by UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (flag_key, target_type, target_id)
+);
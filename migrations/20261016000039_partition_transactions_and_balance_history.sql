@@ -0,0 +1,207 @@
+-- Partition transactions and balance_history by month of created_at, so
+-- queries bounded by date only scan the months they ask for and old months
+-- can later be detached whole. Keys on a partitioned table must include
+-- created_at, so transaction ids and references stay globally unique through
+-- transaction_keys, which the tables pointing at a transaction now reference.
+
+-- Create the monthly partition of `parent` holding `month`, moving any rows
+-- for that month out of the default partition first. Returns whether a
+-- partition was created.
+CREATE OR REPLACE FUNCTION create_monthly_partition(parent TEXT, month DATE)
+RETURNS BOOLEAN AS $$
+DECLARE
+    start_date DATE := date_trunc('month', month)::DATE;
+    partition_name TEXT := format('%s_%s', parent, to_char(start_date, 'YYYY_MM'));
+    lower_bound TIMESTAMPTZ := start_date::TIMESTAMP AT TIME ZONE 'UTC';
+    upper_bound TIMESTAMPTZ := (start_date + INTERVAL '1 month')::TIMESTAMP AT TIME ZONE 'UTC';
+BEGIN
+    IF to_regclass(partition_name) IS NOT NULL THEN
+        RETURN FALSE;
+    END IF;
+
+    EXECUTE format('CREATE TABLE %I (LIKE %I INCLUDING DEFAULTS INCLUDING CONSTRAINTS)', partition_name, parent);
+    EXECUTE format(
+        'WITH moved AS (DELETE FROM %I WHERE created_at >= %L AND created_at < %L RETURNING *)
+         INSERT INTO %I SELECT * FROM moved',
+        parent || '_default', lower_bound, upper_bound, partition_name
+    );
+    EXECUTE format(
+        'ALTER TABLE %I ATTACH PARTITION %I FOR VALUES FROM (%L) TO (%L)',
+        parent, partition_name, lower_bound, upper_bound
+    );
+    RETURN TRUE;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Make sure `parent` has partitions from the current month (UTC) through
+-- `months_ahead` months later. Returns how many partitions were created.
+CREATE OR REPLACE FUNCTION ensure_monthly_partitions(parent TEXT, months_ahead INTEGER)
+RETURNS INTEGER AS $$
+DECLARE
+    current_month DATE := date_trunc('month', NOW() AT TIME ZONE 'UTC')::DATE;
+    created INTEGER := 0;
+BEGIN
+    FOR offset_months IN 0..months_ahead LOOP
+        IF create_monthly_partition(parent, (current_month + make_interval(months => offset_months))::DATE) THEN
+            created := created + 1;
+        END IF;
+    END LOOP;
+    RETURN created;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Ids and references of every transaction, unique across all partitions
+CREATE TABLE IF NOT EXISTS transaction_keys (
+    id UUID PRIMARY KEY,
+    reference VARCHAR(255) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL
+);
+
+INSERT INTO transaction_keys (id, reference, created_at)
+SELECT id, reference, COALESCE(created_at, NOW()) FROM transactions;
+
+ALTER TABLE disputes DROP CONSTRAINT IF EXISTS disputes_transaction_id_fkey;
+ALTER TABLE savings_goal_movements DROP CONSTRAINT IF EXISTS savings_goal_movements_transaction_id_fkey;
+ALTER TABLE interest_capitalizations DROP CONSTRAINT IF EXISTS interest_capitalizations_transaction_id_fkey;
+ALTER TABLE reconciliation_breaks DROP CONSTRAINT IF EXISTS reconciliation_breaks_transaction_id_fkey;
+ALTER TABLE payments DROP CONSTRAINT IF EXISTS payments_transaction_id_fkey;
+ALTER TABLE account_closures DROP CONSTRAINT IF EXISTS account_closures_transaction_id_fkey;
+ALTER TABLE transaction_enrichments DROP CONSTRAINT IF EXISTS transaction_enrichments_transaction_id_fkey;
+
+ALTER TABLE disputes
+    ADD CONSTRAINT disputes_transaction_id_fkey FOREIGN KEY (transaction_id) REFERENCES transaction_keys(id);
+ALTER TABLE savings_goal_movements
+    ADD CONSTRAINT savings_goal_movements_transaction_id_fkey FOREIGN KEY (transaction_id) REFERENCES transaction_keys(id);
+ALTER TABLE interest_capitalizations
+    ADD CONSTRAINT interest_capitalizations_transaction_id_fkey FOREIGN KEY (transaction_id) REFERENCES transaction_keys(id);
+ALTER TABLE reconciliation_breaks
+    ADD CONSTRAINT reconciliation_breaks_transaction_id_fkey FOREIGN KEY (transaction_id) REFERENCES transaction_keys(id);
+ALTER TABLE payments
+    ADD CONSTRAINT payments_transaction_id_fkey FOREIGN KEY (transaction_id) REFERENCES transaction_keys(id);
+ALTER TABLE account_closures
+    ADD CONSTRAINT account_closures_transaction_id_fkey FOREIGN KEY (transaction_id) REFERENCES transaction_keys(id);
+ALTER TABLE transaction_enrichments
+    ADD CONSTRAINT transaction_enrichments_transaction_id_fkey FOREIGN KEY (transaction_id)
+    REFERENCES transaction_keys(id) ON DELETE CASCADE;
+
+-- Transactions
+CREATE TABLE transactions_partitioned (
+    id UUID NOT NULL DEFAULT gen_random_uuid(),
+    from_account_id UUID,
+    to_account_id UUID,
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency VARCHAR(3) DEFAULT 'USD',
+    transaction_type transaction_type NOT NULL,
+    status transaction_status DEFAULT 'pending',
+    reference VARCHAR(255) NOT NULL,
+    description TEXT,
+    metadata JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    tenant_id UUID
+) PARTITION BY RANGE (created_at);
+
+ALTER TABLE transactions RENAME TO transactions_unpartitioned;
+ALTER TABLE transactions_partitioned RENAME TO transactions;
+CREATE TABLE transactions_default PARTITION OF transactions DEFAULT;
+
+DO $$
+DECLARE
+    month DATE;
+BEGIN
+    SELECT date_trunc('month', MIN(created_at) AT TIME ZONE 'UTC')::DATE INTO month FROM transactions_unpartitioned;
+    WHILE month < date_trunc('month', NOW() AT TIME ZONE 'UTC')::DATE LOOP
+        PERFORM create_monthly_partition('transactions', month);
+        month := (month + INTERVAL '1 month')::DATE;
+    END LOOP;
+    PERFORM ensure_monthly_partitions('transactions', 3);
+END;
+$$;
+
+INSERT INTO transactions (id, from_account_id, to_account_id, amount, currency, transaction_type, status,
+                          reference, description, metadata, created_at, updated_at, tenant_id)
+SELECT id, from_account_id, to_account_id, amount, currency, transaction_type, status,
+       reference, description, metadata, COALESCE(created_at, NOW()), updated_at, tenant_id
+FROM transactions_unpartitioned;
+
+DROP TABLE transactions_unpartitioned;
+
+ALTER TABLE transactions ADD CONSTRAINT transactions_pkey PRIMARY KEY (id, created_at);
+ALTER TABLE transactions ADD CONSTRAINT transactions_from_account_id_fkey
+    FOREIGN KEY (from_account_id) REFERENCES accounts(id);
+ALTER TABLE transactions ADD CONSTRAINT transactions_to_account_id_fkey
+    FOREIGN KEY (to_account_id) REFERENCES accounts(id);
+ALTER TABLE transactions ADD CONSTRAINT transactions_tenant_id_fkey
+    FOREIGN KEY (tenant_id) REFERENCES organizations(id);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_from_account ON transactions(from_account_id);
+CREATE INDEX IF NOT EXISTS idx_transactions_to_account ON transactions(to_account_id);
+CREATE INDEX IF NOT EXISTS idx_transactions_reference ON transactions(reference);
+CREATE INDEX IF NOT EXISTS idx_transactions_status ON transactions(status);
+CREATE INDEX IF NOT EXISTS idx_transactions_created_at ON transactions(created_at);
+CREATE INDEX IF NOT EXISTS idx_transactions_tenant_id ON transactions(tenant_id);
+
+-- Every new transaction claims its id and reference; a duplicate reference
+-- still fails the insert with a unique violation
+CREATE OR REPLACE FUNCTION claim_transaction_key()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO transaction_keys (id, reference, created_at) VALUES (NEW.id, NEW.reference, NEW.created_at);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER transactions_claim_key
+    BEFORE INSERT ON transactions
+    FOR EACH ROW EXECUTE FUNCTION claim_transaction_key();
+
+-- Balance history
+CREATE TABLE balance_history_partitioned (
+    id UUID NOT NULL DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL,
+    balance_before BIGINT NOT NULL,
+    balance_after BIGINT NOT NULL,
+    amount_changed BIGINT NOT NULL,
+    transaction_id UUID,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    virtual_account_id UUID
+) PARTITION BY RANGE (created_at);
+
+ALTER TABLE balance_history RENAME TO balance_history_unpartitioned;
+ALTER TABLE balance_history_partitioned RENAME TO balance_history;
+CREATE TABLE balance_history_default PARTITION OF balance_history DEFAULT;
+
+DO $$
+DECLARE
+    month DATE;
+BEGIN
+    SELECT date_trunc('month', MIN(created_at) AT TIME ZONE 'UTC')::DATE INTO month FROM balance_history_unpartitioned;
+    WHILE month < date_trunc('month', NOW() AT TIME ZONE 'UTC')::DATE LOOP
+        PERFORM create_monthly_partition('balance_history', month);
+        month := (month + INTERVAL '1 month')::DATE;
+    END LOOP;
+    PERFORM ensure_monthly_partitions('balance_history', 3);
+END;
+$$;
+
+INSERT INTO balance_history (id, account_id, balance_before, balance_after, amount_changed,
+                             transaction_id, description, created_at, virtual_account_id)
+SELECT id, account_id, balance_before, balance_after, amount_changed,
+       transaction_id, description, COALESCE(created_at, NOW()), virtual_account_id
+FROM balance_history_unpartitioned;
+
+DROP TABLE balance_history_unpartitioned;
+
+ALTER TABLE balance_history ADD CONSTRAINT balance_history_pkey PRIMARY KEY (id, created_at);
+ALTER TABLE balance_history ADD CONSTRAINT balance_history_account_id_fkey
+    FOREIGN KEY (account_id) REFERENCES accounts(id);
+ALTER TABLE balance_history ADD CONSTRAINT balance_history_virtual_account_id_fkey
+    FOREIGN KEY (virtual_account_id) REFERENCES virtual_accounts(id);
+
+CREATE INDEX IF NOT EXISTS idx_balance_history_account_id ON balance_history(account_id);
+CREATE INDEX IF NOT EXISTS idx_balance_history_created_at ON balance_history(created_at);
+CREATE INDEX IF NOT EXISTS idx_balance_history_transaction_id ON balance_history(transaction_id);
+CREATE INDEX IF NOT EXISTS idx_balance_history_virtual_account
+    ON balance_history(virtual_account_id, created_at)
+    WHERE virtual_account_id IS NOT NULL;
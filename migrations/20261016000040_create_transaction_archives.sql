@@ -0,0 +1,28 @@
+-- Transactions past the archive age are exported to object storage and
+-- removed from the transactions table. Each archive holds one tenant's
+-- transactions from a span of time; transaction_keys keeps the id and
+-- reference of every archived transaction and points at its archive.
+CREATE TABLE IF NOT EXISTS transaction_archives (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID REFERENCES organizations(id),
+    -- Creation time of the earliest transaction in the archive
+    period_start TIMESTAMPTZ NOT NULL,
+    -- Just after the creation time of the latest one
+    period_end TIMESTAMPTZ NOT NULL,
+    storage_key TEXT NOT NULL,
+    format VARCHAR(16) NOT NULL DEFAULT 'csv',
+    record_count INTEGER NOT NULL,
+    -- SHA-256 of the stored object, hex encoded
+    checksum VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (period_start < period_end)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_archives_tenant_period
+    ON transaction_archives(tenant_id, period_start, period_end);
+
+ALTER TABLE transaction_keys ADD COLUMN IF NOT EXISTS archive_id UUID REFERENCES transaction_archives(id);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_keys_archive_id
+    ON transaction_keys(archive_id)
+    WHERE archive_id IS NOT NULL;
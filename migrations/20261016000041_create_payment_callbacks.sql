@@ -0,0 +1,30 @@
+-- Callbacks payment rails post about the payments they carry. Every verified
+-- callback is kept with its raw body for audit, once per event id at the rail;
+-- redelivered events find the stored row and change nothing.
+CREATE TYPE payment_callback_outcome AS ENUM ('received', 'applied', 'ignored', 'unmatched');
+
+CREATE TABLE IF NOT EXISTS payment_callbacks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    provider VARCHAR(32) NOT NULL,
+    event_id VARCHAR(255) NOT NULL,
+    payment_id UUID REFERENCES payments(id) ON DELETE SET NULL,
+    -- Status as the rail named it
+    reported_status VARCHAR(64),
+    -- 'received' until the callback has been processed
+    outcome payment_callback_outcome NOT NULL DEFAULT 'received',
+    detail TEXT,
+    raw_body TEXT NOT NULL,
+    signature TEXT,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    processed_at TIMESTAMPTZ,
+    UNIQUE (provider, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_payment_callbacks_payment_id
+    ON payment_callbacks(payment_id)
+    WHERE payment_id IS NOT NULL;
+
+-- Rails name payments by their own id once they have seen them
+CREATE INDEX IF NOT EXISTS idx_payments_external_reference
+    ON payments(external_reference)
+    WHERE external_reference IS NOT NULL;
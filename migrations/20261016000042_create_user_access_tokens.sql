@@ -0,0 +1,23 @@
+-- Access tokens a project obtains for one of its tenant's customers. They
+-- carry the user they act for and reach only that user's data.
+ALTER TABLE oauth_tokens ADD COLUMN IF NOT EXISTS user_id UUID REFERENCES users(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_oauth_tokens_user_id
+    ON oauth_tokens(user_id)
+    WHERE user_id IS NOT NULL;
+
+-- One-time codes customers log in with. Only a hash of the code is kept;
+-- a code is spent by its first correct use or after too many wrong ones.
+CREATE TABLE IF NOT EXISTS user_login_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    code_hash VARCHAR(64) NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_login_codes_user_project
+    ON user_login_codes(user_id, project_id, created_at DESC);
@@ -0,0 +1,4 @@
+-- When and how the end user behind a user token authenticated, kept so a
+-- refreshed token does not count as a fresh login for step-up checks
+ALTER TABLE oauth_tokens ADD COLUMN IF NOT EXISTS auth_time TIMESTAMPTZ;
+ALTER TABLE oauth_tokens ADD COLUMN IF NOT EXISTS acr VARCHAR(16);
@@ -0,0 +1,22 @@
+-- Devices end users have bound to their login. Each holds an Ed25519 key
+-- pair and signs sensitive calls with it; only the public key is stored.
+CREATE TABLE IF NOT EXISTS user_devices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    public_key VARCHAR(64) NOT NULL,
+    -- Timestamp of the last accepted assertion, in milliseconds; each
+    -- assertion must be newer so a captured one cannot be replayed
+    last_assertion_ms BIGINT,
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_devices_user_id
+    ON user_devices(user_id)
+    WHERE revoked_at IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_devices_public_key
+    ON user_devices(public_key)
+    WHERE revoked_at IS NULL;
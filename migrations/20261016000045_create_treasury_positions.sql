@@ -0,0 +1,29 @@
+-- Treasury positions. Money moved in and out of customer accounts is
+-- accumulated per business date and currency as domain events arrive;
+-- snapshots record the positions at a point in time. The end-of-day
+-- snapshot freezes its business date, and later flows roll into the next one.
+
+CREATE TABLE IF NOT EXISTS treasury_flows (
+    business_date DATE NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    inflow BIGINT NOT NULL DEFAULT 0,
+    outflow BIGINT NOT NULL DEFAULT 0,
+    movement_count BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (business_date, currency)
+);
+
+CREATE TABLE IF NOT EXISTS treasury_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    business_date DATE NOT NULL,
+    is_end_of_day BOOLEAN NOT NULL DEFAULT FALSE,
+    currencies JSONB NOT NULL,
+    gl_accounts JSONB NOT NULL,
+    taken_by UUID,
+    taken_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_treasury_snapshots_end_of_day
+    ON treasury_snapshots(business_date) WHERE is_end_of_day;
+CREATE INDEX IF NOT EXISTS idx_treasury_snapshots_business_date
+    ON treasury_snapshots(business_date, taken_at);
@@ -0,0 +1,34 @@
+-- Regulatory transaction reports. Each report lists the reportable payments
+-- of one jurisdiction over a period, rendered in the jurisdiction's file
+-- format, and tracks its submission to the regulator.
+
+CREATE TYPE regulatory_report_type AS ENUM ('large_transactions', 'suspicious_activity');
+CREATE TYPE regulatory_report_format AS ENUM ('csv', 'xml');
+CREATE TYPE regulatory_submission_status AS ENUM ('generated', 'submitted', 'acknowledged', 'rejected');
+
+CREATE TABLE IF NOT EXISTS regulatory_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    jurisdiction VARCHAR(10) NOT NULL,
+    report_type regulatory_report_type NOT NULL,
+    format regulatory_report_format NOT NULL,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+    transaction_count INTEGER NOT NULL,
+    -- The rendered file, kept as generated
+    content TEXT NOT NULL,
+    status regulatory_submission_status NOT NULL DEFAULT 'generated',
+    -- The regulator's reference for the filing
+    submission_reference VARCHAR(100),
+    status_note TEXT,
+    generated_by UUID NOT NULL,
+    generated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    submitted_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (period_start <= period_end)
+);
+
+CREATE INDEX IF NOT EXISTS idx_regulatory_reports_jurisdiction
+    ON regulatory_reports(jurisdiction, report_type, period_start);
+CREATE INDEX IF NOT EXISTS idx_regulatory_reports_status ON regulatory_reports(status);
+CREATE INDEX IF NOT EXISTS idx_payments_executed_or_created_at
+    ON payments((COALESCE(executed_at, created_at)));
@@ -0,0 +1,27 @@
+-- Key store for per-user encryption of personal data. Each tenant has a
+-- master key, wrapped by a configured encryption key; each user's data key is
+-- derived from their tenant's master key with a salt of their own. Erasing a
+-- user's personal data destroys the salt (crypto-shredding).
+
+CREATE TABLE IF NOT EXISTS tenant_master_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- NULL for the key shared by users outside any tenant
+    tenant_id UUID REFERENCES organizations(id),
+    -- Configured encryption key the master key is wrapped by
+    key_id VARCHAR(100) NOT NULL,
+    wrapped_key BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tenant_master_keys_tenant
+    ON tenant_master_keys ((COALESCE(tenant_id, '00000000-0000-0000-0000-000000000000'::UUID)));
+
+CREATE TABLE IF NOT EXISTS user_data_keys (
+    user_id UUID PRIMARY KEY REFERENCES users(id),
+    -- NULL only for users shredded before a key was ever created
+    tenant_key_id UUID REFERENCES tenant_master_keys(id),
+    salt BYTEA,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    shredded_at TIMESTAMPTZ,
+    CHECK ((salt IS NULL) = (shredded_at IS NOT NULL))
+);
@@ -0,0 +1,24 @@
+-- Heads of the hash-chained audit streams, recorded periodically so that
+-- deleting or rewriting audit events up to an anchored head is detected.
+-- Anchors are append-only.
+
+CREATE TABLE IF NOT EXISTS audit_chain_anchors (
+    stream_id UUID NOT NULL,
+    sequence BIGINT NOT NULL,
+    head_hash CHAR(64) NOT NULL,
+    anchored_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (stream_id, sequence)
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_chain_anchors_anchored_at ON audit_chain_anchors(anchored_at);
+
+CREATE OR REPLACE FUNCTION reject_audit_chain_anchor_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_chain_anchors is append-only';
+END;
+$$ language 'plpgsql';
+
+CREATE TRIGGER audit_chain_anchors_append_only
+    BEFORE UPDATE OR DELETE ON audit_chain_anchors
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_chain_anchor_change();
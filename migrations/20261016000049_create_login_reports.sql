@@ -0,0 +1,11 @@
+-- Logins developers reported as not theirs. The logins themselves live in
+-- the audit log; a report raises a security alert once.
+
+CREATE TABLE IF NOT EXISTS login_reports (
+    event_id UUID PRIMARY KEY,
+    developer_id UUID NOT NULL REFERENCES developers(id) ON DELETE CASCADE,
+    note TEXT,
+    reported_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_reports_developer_id ON login_reports(developer_id);
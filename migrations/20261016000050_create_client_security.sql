@@ -0,0 +1,13 @@
+-- Failed secret attempts per OAuth client id, so a client is locked out
+-- after repeated wrong secrets whichever addresses they come from
+
+CREATE TABLE IF NOT EXISTS client_security (
+    client_id VARCHAR(255) PRIMARY KEY,
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    last_failed_attempt TIMESTAMPTZ,
+    locked_until TIMESTAMPTZ,
+    lock_reason TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_client_security_locked_until ON client_security(locked_until) WHERE locked_until IS NOT NULL;
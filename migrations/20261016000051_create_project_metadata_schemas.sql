@@ -0,0 +1,14 @@
+-- JSON Schemas projects hold their payment and transaction metadata to
+CREATE TYPE metadata_schema_target AS ENUM ('payment', 'transaction');
+
+CREATE TABLE IF NOT EXISTS project_metadata_schemas (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    target metadata_schema_target NOT NULL,
+    schema JSONB NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    created_by UUID REFERENCES developers(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (project_id, target)
+);
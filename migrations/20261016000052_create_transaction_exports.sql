@@ -0,0 +1,29 @@
+-- Asynchronous transaction exports written to object storage
+CREATE TYPE transaction_export_status AS ENUM ('queued', 'running', 'completed', 'failed', 'expired');
+
+CREATE TABLE IF NOT EXISTS transaction_exports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL,
+    project_id UUID REFERENCES projects(id) ON DELETE SET NULL,
+    requested_by UUID,
+    filter JSONB NOT NULL DEFAULT '{}',
+    format report_file_format NOT NULL,
+    status transaction_export_status NOT NULL DEFAULT 'queued',
+    notify_email TEXT,
+    webhook_url TEXT,
+    storage_key TEXT,
+    row_count BIGINT,
+    size_bytes BIGINT,
+    error TEXT,
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_exports_status ON transaction_exports (status, created_at);
+CREATE INDEX IF NOT EXISTS idx_transaction_exports_tenant ON transaction_exports (tenant_id, created_at DESC);
+
+-- Exports page through a tenant's transactions in (created_at, id) order
+CREATE INDEX IF NOT EXISTS idx_transactions_tenant_created ON transactions (tenant_id, created_at, id);
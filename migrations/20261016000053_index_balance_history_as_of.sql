@@ -0,0 +1,6 @@
+-- Balance-as-of lookups sum one account's postings on one side of a time.
+-- Keyed by account then time, and carrying the amount, each partition
+-- answers them from the index alone; partition pruning skips the months on
+-- the other side.
+CREATE INDEX IF NOT EXISTS idx_balance_history_account_created
+    ON balance_history (account_id, created_at) INCLUDE (amount_changed);
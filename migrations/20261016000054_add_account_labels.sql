@@ -0,0 +1,11 @@
+-- Nicknames, labels and custom metadata users give their accounts
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS nickname TEXT;
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS labels TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS metadata JSONB;
+
+ALTER TABLE virtual_accounts ADD COLUMN IF NOT EXISTS nickname TEXT;
+ALTER TABLE virtual_accounts ADD COLUMN IF NOT EXISTS labels TEXT[] NOT NULL DEFAULT '{}';
+
+-- Label lookups within a user's accounts
+CREATE INDEX IF NOT EXISTS idx_accounts_labels ON accounts USING GIN (labels);
+CREATE INDEX IF NOT EXISTS idx_virtual_accounts_labels ON virtual_accounts USING GIN (labels);
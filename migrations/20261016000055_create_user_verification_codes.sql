@@ -0,0 +1,25 @@
+-- Whether the customer has proved they receive texts at their phone number;
+-- is_verified says the same of their email address.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS phone_verified BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TYPE verification_channel AS ENUM ('email', 'phone');
+
+-- One-time codes sent to prove a customer owns an email address or phone
+-- number. Only a hash of the code is kept; a code is spent by its first
+-- correct use or after too many wrong ones. Rows are kept to rate-limit how
+-- often codes are sent.
+CREATE TABLE IF NOT EXISTS user_verification_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    channel verification_channel NOT NULL,
+    -- The address or number the code was sent to
+    destination VARCHAR(255) NOT NULL,
+    code_hash VARCHAR(64) NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_verification_codes_user_channel
+    ON user_verification_codes(user_id, channel, created_at DESC);
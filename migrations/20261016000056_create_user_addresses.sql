@@ -0,0 +1,24 @@
+-- A customer's residential address, one per user. The street, city, region
+-- and postal code are encrypted under the user's data key, so erasing the
+-- user's data erases the address too; the country is kept in the clear for
+-- reporting. Changing the address resets its verification.
+CREATE TYPE address_verification_status AS ENUM ('unverified', 'verified', 'failed');
+
+CREATE TABLE IF NOT EXISTS user_addresses (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    address JSONB NOT NULL,
+    country CHAR(2) NOT NULL,
+    verification_status address_verification_status NOT NULL DEFAULT 'unverified',
+    provider VARCHAR(50),
+    provider_reference VARCHAR(255),
+    -- Why the provider could not verify the address
+    failure_reason TEXT,
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Face enrollment is recorded as an identity verification of type 'face';
+-- the KYC status looks verifications up by user and type
+CREATE INDEX IF NOT EXISTS idx_identity_verifications_user_type
+    ON identity_verifications(user_id, verification_type);
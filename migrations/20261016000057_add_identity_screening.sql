@@ -0,0 +1,19 @@
+-- Politically-exposed-person and adverse-media screening of the person behind
+-- an identity verification. The latest result is kept on the verification:
+-- 'potential_match' puts it in front of a reviewer, and approving the review
+-- dismisses the matches so re-screening only flags matches not seen before.
+CREATE TYPE screening_status AS ENUM ('clear', 'potential_match', 'dismissed');
+
+ALTER TABLE identity_verifications
+    ADD COLUMN IF NOT EXISTS screening_status screening_status,
+    ADD COLUMN IF NOT EXISTS screening_matches JSONB NOT NULL DEFAULT '[]',
+    -- 0 (no matches) to 100, from how strong the matches are
+    ADD COLUMN IF NOT EXISTS screening_risk_score SMALLINT,
+    ADD COLUMN IF NOT EXISTS screening_provider VARCHAR(50),
+    ADD COLUMN IF NOT EXISTS screening_reference VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS screened_at TIMESTAMPTZ;
+
+-- The re-screening job walks verifications screened longest ago first
+CREATE INDEX IF NOT EXISTS idx_identity_verifications_screened_at
+    ON identity_verifications(screened_at NULLS FIRST)
+    WHERE status IN ('pending', 'in_progress', 'completed');
@@ -0,0 +1,47 @@
+-- Multi-step identity verification sessions. The client creates a session
+-- once, uploads each step (ID document, selfie, liveness video) in order and
+-- asks for the decision, which records the outcome as identity
+-- verifications. A user has at most one active session, which is resumed
+-- until it is decided or expires.
+CREATE TYPE verification_session_status AS ENUM ('active', 'in_review', 'approved', 'rejected', 'expired');
+CREATE TYPE verification_session_step AS ENUM ('document', 'selfie', 'liveness');
+CREATE TYPE verification_step_status AS ENUM ('pending', 'submitted', 'passed', 'failed');
+
+CREATE TABLE IF NOT EXISTS verification_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    document_type VARCHAR(100) NOT NULL,
+    -- Read off the document step; encrypted under the user's data key
+    document_number TEXT,
+    status verification_session_status NOT NULL DEFAULT 'active',
+    decision_reason TEXT,
+    identity_verification_id UUID REFERENCES identity_verifications(id),
+    face_verification_id UUID REFERENCES identity_verifications(id),
+    created_by UUID NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    decided_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_verification_sessions_active_user
+    ON verification_sessions(user_id) WHERE status = 'active';
+CREATE INDEX IF NOT EXISTS idx_verification_sessions_expiry
+    ON verification_sessions(expires_at) WHERE status = 'active';
+
+CREATE TABLE IF NOT EXISTS verification_session_steps (
+    session_id UUID NOT NULL REFERENCES verification_sessions(id) ON DELETE CASCADE,
+    step verification_session_step NOT NULL,
+    -- Steps are completed in this order
+    position SMALLINT NOT NULL,
+    status verification_step_status NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- The latest upload; removed when the session expires
+    storage_key TEXT,
+    content_type VARCHAR(100),
+    provider_reference VARCHAR(255),
+    failure_reason TEXT,
+    submitted_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, step)
+);
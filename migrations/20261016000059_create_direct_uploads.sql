@@ -0,0 +1,24 @@
+-- Direct uploads: the API issues a short-lived signed link scoped to one
+-- user, purpose and content type; the client uploads the file straight to
+-- storage and references the upload id in verification requests instead of
+-- sending the file through the API.
+CREATE TYPE direct_upload_purpose AS ENUM ('identity_document', 'selfie', 'liveness_video');
+CREATE TYPE direct_upload_status AS ENUM ('pending', 'consumed', 'expired');
+
+CREATE TABLE IF NOT EXISTS direct_uploads (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose direct_upload_purpose NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    storage_key TEXT NOT NULL,
+    status direct_upload_status NOT NULL DEFAULT 'pending',
+    created_by UUID NOT NULL,
+    -- The signed upload link stops working at this time
+    link_expires_at TIMESTAMPTZ NOT NULL,
+    -- Uploads not referenced by this time are deleted
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_direct_uploads_expiry ON direct_uploads(expires_at) WHERE status = 'pending';
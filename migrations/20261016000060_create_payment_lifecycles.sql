@@ -0,0 +1,19 @@
+-- Payment lifecycles for analytics: one row per payment, kept up to date
+-- from payment.status_changed events. The provider is the rail that last
+-- called back about the payment; payments no rail reported on are internal.
+
+CREATE TABLE IF NOT EXISTS payment_lifecycles (
+    payment_id UUID PRIMARY KEY,
+    provider VARCHAR(50),
+    payment_method payment_method NOT NULL,
+    currency VARCHAR(3) NOT NULL,
+    amount BIGINT NOT NULL,
+    status payment_status NOT NULL,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL,
+    -- When the payment completed, failed or was cancelled
+    finished_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_payment_lifecycles_created_at ON payment_lifecycles(created_at);
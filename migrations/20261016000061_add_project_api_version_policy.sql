@@ -0,0 +1,11 @@
+-- Versions of the API, each served under /api/{version}
+CREATE TYPE api_version AS ENUM ('v1', 'v2');
+
+-- Which versions a project still calls: older versions than its minimum are
+-- refused, and its own sunset date replaces the configured one of deprecated
+-- versions
+ALTER TABLE projects
+    ADD COLUMN IF NOT EXISTS min_api_version api_version NOT NULL DEFAULT 'v1',
+    ADD COLUMN IF NOT EXISTS api_sunset_on DATE,
+    ADD COLUMN IF NOT EXISTS api_versions_updated_by UUID REFERENCES developers(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS api_versions_updated_at TIMESTAMPTZ;
@@ -0,0 +1,40 @@
+-- Payment intents: a payment priced by the server, fees included, that the
+-- client confirms with the intent's confirmation token before it is made.
+-- The confirmed intent's amount and fees are the ones charged.
+CREATE TYPE payment_intent_status AS ENUM (
+    'requires_confirmation',
+    'processing',
+    'failed',
+    'cancelled',
+    'expired'
+);
+
+CREATE TABLE IF NOT EXISTS payment_intents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID REFERENCES organizations(id),
+    project_id UUID,
+    created_by UUID NOT NULL,
+    from_account_id UUID NOT NULL REFERENCES accounts(id),
+    to_account_id UUID REFERENCES accounts(id),
+    to_virtual_account_id UUID REFERENCES virtual_accounts(id),
+    amount BIGINT NOT NULL CHECK (amount > 0),
+    currency VARCHAR(3) NOT NULL,
+    payment_method payment_method NOT NULL,
+    description TEXT,
+    recipient_info JSONB,
+    metadata JSONB,
+    fee_amount BIGINT NOT NULL DEFAULT 0,
+    fee_breakdown JSONB NOT NULL,
+    status payment_intent_status NOT NULL DEFAULT 'requires_confirmation',
+    -- SHA-256 of the confirmation token; the token is only shown once
+    confirmation_token_hash VARCHAR(64) NOT NULL,
+    -- The payment made when the intent was confirmed
+    payment_id UUID REFERENCES payments(id),
+    failure_reason TEXT,
+    expires_at TIMESTAMPTZ NOT NULL,
+    confirmed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_payment_intents_tenant_id ON payment_intents(tenant_id);
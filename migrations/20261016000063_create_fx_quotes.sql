@@ -0,0 +1,24 @@
+-- FX quotes: the conversion of one amount at a rate locked until the quote
+-- expires. A quote is used at most once, by the transfer or payment intent
+-- that references it.
+CREATE TABLE IF NOT EXISTS fx_quotes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tenant_id UUID NOT NULL REFERENCES organizations(id),
+    project_id UUID,
+    source_currency VARCHAR(3) NOT NULL,
+    target_currency VARCHAR(3) NOT NULL,
+    source_amount BIGINT NOT NULL CHECK (source_amount > 0),
+    target_amount BIGINT NOT NULL CHECK (target_amount >= 0),
+    -- Target currency per unit of source currency, in parts per billion
+    rate_ppb BIGINT NOT NULL CHECK (rate_ppb > 0),
+    -- The expired quote this one priced again at the current rate
+    requoted_from UUID REFERENCES fx_quotes(id),
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_fx_quotes_tenant_id ON fx_quotes(tenant_id);
+
+-- The quote converting a payment intent's amount, used when it was created
+ALTER TABLE payment_intents ADD COLUMN IF NOT EXISTS fx_quote_id UUID REFERENCES fx_quotes(id);
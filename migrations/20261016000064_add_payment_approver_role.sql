@@ -0,0 +1,5 @@
+-- Payment approvers hold their own role so dual control can be granted
+-- without admin rights.
+ALTER TABLE user_roles DROP CONSTRAINT IF EXISTS user_roles_role_check;
+ALTER TABLE user_roles ADD CONSTRAINT user_roles_role_check
+    CHECK (role IN ('super_admin', 'admin', 'developer', 'read_only', 'support', 'auditor', 'payment_approver'));
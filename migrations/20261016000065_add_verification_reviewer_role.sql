@@ -0,0 +1,6 @@
+-- Verification reviewers decide identity and income verifications flagged for
+-- manual review.
+ALTER TABLE user_roles DROP CONSTRAINT IF EXISTS user_roles_role_check;
+ALTER TABLE user_roles ADD CONSTRAINT user_roles_role_check
+    CHECK (role IN ('super_admin', 'admin', 'developer', 'read_only', 'support', 'auditor', 'payment_approver',
+                    'verification_reviewer'));
@@ -0,0 +1,10 @@
+-- Transfers are charged fees as well as payments; their fee postings point at
+-- the transfer's transaction instead. transactions is partitioned, so the
+-- column carries no foreign key.
+ALTER TABLE fee_postings ALTER COLUMN payment_id DROP NOT NULL;
+ALTER TABLE fee_postings ADD COLUMN IF NOT EXISTS transaction_id UUID;
+ALTER TABLE fee_postings DROP CONSTRAINT IF EXISTS fee_postings_source_check;
+ALTER TABLE fee_postings ADD CONSTRAINT fee_postings_source_check
+    CHECK (num_nonnulls(payment_id, transaction_id) = 1);
+
+CREATE INDEX IF NOT EXISTS idx_fee_postings_transaction_id ON fee_postings(transaction_id);
@@ -0,0 +1,10 @@
+-- A payment converted with an FX quote credits its payee the quote's target
+-- amount, in the target currency. The intent keeps the conversion it was
+-- priced with, and the payment it makes the conversion settled.
+ALTER TABLE payment_intents
+    ADD COLUMN IF NOT EXISTS target_amount BIGINT,
+    ADD COLUMN IF NOT EXISTS target_currency VARCHAR(3);
+
+ALTER TABLE payments
+    ADD COLUMN IF NOT EXISTS target_amount BIGINT,
+    ADD COLUMN IF NOT EXISTS target_currency VARCHAR(3);
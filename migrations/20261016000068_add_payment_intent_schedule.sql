@@ -0,0 +1,5 @@
+-- A payment intent may schedule the payment it makes; the execution time is
+-- resolved when the intent is priced, in the time zone it was asked in.
+ALTER TABLE payment_intents
+    ADD COLUMN IF NOT EXISTS execute_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS execution_timezone VARCHAR(64);
@@ -0,0 +1,3 @@
+-- A payment intent may ask to make its payment even though the project blocks
+-- it as a duplicate of a recent payment.
+ALTER TABLE payment_intents ADD COLUMN IF NOT EXISTS force_override BOOLEAN NOT NULL DEFAULT FALSE;
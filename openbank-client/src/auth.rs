@@ -0,0 +1,35 @@
+use openbank::auth::model::{
+    CreateProjectRequest, DeveloperResponse, MeResponse, ProjectResponse, RegisterDeveloperRequest, ScopesResponse,
+};
+use uuid::Uuid;
+use crate::client::OpenBankClient;
+use crate::error::ClientResult;
+
+impl OpenBankClient {
+    /// Register a developer account
+    pub async fn register_developer(&self, request: &RegisterDeveloperRequest) -> ClientResult<DeveloperResponse> {
+        self.send_public(self.http().post(self.url("/auth/developers")).json(request))
+            .await
+    }
+
+    /// Create a project and its client credentials
+    pub async fn create_project(
+        &self,
+        developer_id: Uuid,
+        request: &CreateProjectRequest,
+    ) -> ClientResult<ProjectResponse> {
+        let url = self.url(&format!("/auth/developers/{}/projects", developer_id));
+        self.send_public(self.http().post(url).json(request)).await
+    }
+
+    /// Describe the client's access token
+    pub async fn me(&self) -> ClientResult<MeResponse> {
+        let url = self.url("/auth/me");
+        self.send(|| self.http().get(&url), None).await
+    }
+
+    /// List the scopes a project can request
+    pub async fn available_scopes(&self) -> ClientResult<ScopesResponse> {
+        self.send_public(self.http().get(self.url("/auth/scopes"))).await
+    }
+}
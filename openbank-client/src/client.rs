@@ -0,0 +1,172 @@
+use std::sync::Arc;
+use openbank::core::response::{ApiResponse, ErrorResponse};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use crate::error::{ClientError, ClientResult};
+use crate::idempotency::{IdempotencyKey, IDEMPOTENCY_KEY_HEADER};
+use crate::token::{ClientCredentials, TokenManager};
+
+/// Retries of an idempotent write after a connection failure, by default
+const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// Async client for the OpenBank API.
+///
+/// Cloning is cheap; clones share the connection pool and access token.
+#[derive(Clone)]
+pub struct OpenBankClient {
+    http: reqwest::Client,
+    base_url: String,
+    tokens: Option<Arc<TokenManager>>,
+    max_retries: u32,
+}
+
+impl OpenBankClient {
+    /// A client without credentials, for the public endpoints
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            tokens: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+
+    /// Authenticate as a project; tokens are obtained and renewed automatically
+    pub fn with_credentials(mut self, credentials: ClientCredentials) -> Self {
+        self.tokens = Some(Arc::new(TokenManager::new(credentials)));
+        self
+    }
+
+    /// Use a preconfigured HTTP client (timeouts, proxies, TLS settings)
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// How often a write carrying an idempotency key is retried after a
+    /// connection failure or timeout
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// The current access token, obtaining one if needed
+    pub async fn access_token(&self) -> ClientResult<String> {
+        self.token_manager()?.access_token(&self.http, &self.base_url).await
+    }
+
+    pub(crate) fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    pub(crate) fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn token_manager(&self) -> ClientResult<&TokenManager> {
+        self.tokens
+            .as_deref()
+            .ok_or_else(|| ClientError::Authentication("No client credentials configured".to_string()))
+    }
+
+    /// Send a request without authentication and read the `data` of the envelope
+    pub(crate) async fn send_public<T: DeserializeOwned>(&self, request: RequestBuilder) -> ClientResult<T> {
+        read_data(request.send().await?).await
+    }
+
+    /// Send an authenticated request and read the `data` of the envelope
+    pub(crate) async fn send<T, F>(&self, build: F, idempotency_key: Option<&IdempotencyKey>) -> ClientResult<T>
+    where
+        T: DeserializeOwned,
+        F: Fn() -> RequestBuilder,
+    {
+        read_data(self.send_authorized(build, idempotency_key).await?).await
+    }
+
+    /// Send an authenticated request and return the raw body, for endpoints
+    /// that do not respond with JSON
+    pub(crate) async fn send_for_bytes<F>(&self, build: F) -> ClientResult<Vec<u8>>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let response = self.send_authorized(build, None).await?;
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Send with a bearer token. A 401 renews the token and retries once;
+    /// requests with an idempotency key are also retried after connection
+    /// failures, reusing the key.
+    async fn send_authorized<F>(&self, build: F, idempotency_key: Option<&IdempotencyKey>) -> ClientResult<Response>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let tokens = self.token_manager()?;
+        let mut token = tokens.access_token(&self.http, &self.base_url).await?;
+        let mut renewed = false;
+        let mut attempt = 0;
+
+        loop {
+            let mut request = build().bearer_auth(&token);
+            if let Some(key) = idempotency_key {
+                request = request.header(IDEMPOTENCY_KEY_HEADER, key.as_str());
+            }
+
+            match request.send().await {
+                Ok(response) if response.status() == StatusCode::UNAUTHORIZED && !renewed => {
+                    // The token may have been revoked before it expired
+                    tokens.invalidate(&token).await;
+                    token = tokens.access_token(&self.http, &self.base_url).await?;
+                    renewed = true;
+                }
+                Ok(response) => return Ok(response),
+                Err(err)
+                    if idempotency_key.is_some()
+                        && attempt < self.max_retries
+                        && (err.is_connect() || err.is_timeout()) =>
+                {
+                    attempt += 1;
+                    tracing::warn!("Retrying idempotent request (attempt {}): {}", attempt, err);
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+/// Read the `data` of a response envelope, or the error it describes
+pub(crate) async fn read_data<T: DeserializeOwned>(response: Response) -> ClientResult<T> {
+    if !response.status().is_success() {
+        return Err(api_error(response).await);
+    }
+
+    let body = response.bytes().await?;
+    let envelope: ApiResponse<T> = serde_json::from_slice(&body)
+        .map_err(|e| ClientError::InvalidResponse(format!("Unexpected response body: {}", e)))?;
+    envelope
+        .data
+        .ok_or_else(|| ClientError::InvalidResponse(format!("Response has no data: {}", envelope.message)))
+}
+
+async fn api_error(response: Response) -> ClientError {
+    let status = response.status();
+    let envelope = match response.bytes().await {
+        Ok(body) => serde_json::from_slice::<ApiResponse<ErrorResponse>>(&body).ok(),
+        Err(err) => return err.into(),
+    };
+
+    match envelope {
+        Some(envelope) => ClientError::Api {
+            status,
+            message: envelope.message,
+            error: envelope.data.map(Box::new),
+        },
+        None => ClientError::Api {
+            status,
+            message: status.canonical_reason().unwrap_or("Unknown error").to_string(),
+            error: None,
+        },
+    }
+}
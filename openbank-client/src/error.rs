@@ -0,0 +1,37 @@
+use openbank::core::response::ErrorResponse;
+use reqwest::StatusCode;
+
+/// Errors returned by the client
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The API answered with an error status
+    #[error("API error ({status}): {message}")]
+    Api {
+        status: StatusCode,
+        message: String,
+        /// Error details from the response envelope, when the body had one
+        error: Option<Box<ErrorResponse>>,
+    },
+
+    #[error("Authentication error: {0}")]
+    Authentication(String),
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+}
+
+impl ClientError {
+    /// HTTP status of an API error
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::Api { status, .. } => Some(*status),
+            ClientError::Http(err) => err.status(),
+            _ => None,
+        }
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
@@ -0,0 +1,42 @@
+use std::fmt;
+use uuid::Uuid;
+
+/// Header carrying the idempotency key of a write request
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Identifies one logical write, such as a payment.
+///
+/// Send the same key when retrying a request so the server can tell the
+/// retry apart from a new operation. The client reuses the key for its own
+/// retries after connection failures.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    /// A new random key
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    /// A key derived from the caller's own identifier for the operation, such
+    /// as an order or invoice ID, so that every attempt maps to the same key
+    pub fn from_reference(namespace: &str, reference: &str) -> Self {
+        Self(format!("{}:{}", namespace, reference))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for IdempotencyKey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,17 @@
+use openbank::identity::model::{VerificationRequest, VerificationResponse};
+use uuid::Uuid;
+use crate::client::OpenBankClient;
+use crate::error::ClientResult;
+
+impl OpenBankClient {
+    /// Start an identity verification
+    pub async fn initiate_verification(&self, request: &VerificationRequest) -> ClientResult<VerificationResponse> {
+        let url = self.url("/api/v1/identity/verify");
+        self.send(|| self.http().post(&url).json(request), None).await
+    }
+
+    pub async fn verification_status(&self, id: Uuid) -> ClientResult<VerificationResponse> {
+        let url = self.url(&format!("/api/v1/identity/verify/status/{}", id));
+        self.send(|| self.http().get(&url), None).await
+    }
+}
@@ -0,0 +1,41 @@
+//! Typed async client for the OpenBank API.
+//!
+//! Requests and responses use the service's own model types, re-exported
+//! from [`models`]. Authenticated calls obtain and renew access tokens with
+//! the project's client credentials; writes that move money take an
+//! [`IdempotencyKey`].
+
+mod auth;
+mod client;
+mod error;
+mod idempotency;
+mod identity;
+mod payments;
+mod token;
+mod transactions;
+
+pub use client::OpenBankClient;
+pub use error::{ClientError, ClientResult};
+pub use idempotency::{IdempotencyKey, IDEMPOTENCY_KEY_HEADER};
+pub use token::ClientCredentials;
+
+/// Request and response models shared with the service
+pub mod models {
+    pub use openbank::auth::model::{
+        CreateProjectRequest, DeveloperResponse, MeResponse, ProjectEnvironment, ProjectResponse,
+        RegisterDeveloperRequest, ScopeInfo, ScopeSetsInfo, ScopesResponse,
+    };
+    pub use openbank::core::qr::{QrFormat, QrQuery};
+    pub use openbank::core::response::ErrorResponse;
+    pub use openbank::fees::model::{FeeBreakdown, FeeLine, FeeType};
+    pub use openbank::identity::model::{VerificationRequest, VerificationResponse, VerificationStatus};
+    pub use openbank::payments::model::{
+        CreatePaymentRequest, ExecuteAt, PaymentMethod, PaymentResponse, PaymentStatus,
+    };
+    pub use openbank::shared::types::{
+        AccountId, Amount, Currency, PaginatedResponse, PaginationParams, TransactionId, UserId,
+    };
+    pub use openbank::transactions::model::{
+        CreateTransactionRequest, TransactionResponse, TransactionStatus, TransactionType, TransferRequest,
+    };
+}
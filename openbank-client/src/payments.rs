@@ -0,0 +1,49 @@
+use openbank::core::qr::QrQuery;
+use openbank::payments::model::{CreatePaymentRequest, PaymentResponse, ValidateAccountQuery};
+use openbank::shared::bank_details::AccountValidation;
+use openbank::shared::types::{PaginatedResponse, PaginationParams};
+use uuid::Uuid;
+use crate::client::OpenBankClient;
+use crate::error::ClientResult;
+use crate::idempotency::IdempotencyKey;
+
+impl OpenBankClient {
+    /// Create a payment. Retrying with the same key does not pay twice.
+    pub async fn create_payment(
+        &self,
+        request: &CreatePaymentRequest,
+        idempotency_key: &IdempotencyKey,
+    ) -> ClientResult<PaymentResponse> {
+        let url = self.url("/api/v1/payments");
+        self.send(|| self.http().post(&url).json(request), Some(idempotency_key))
+            .await
+    }
+
+    pub async fn list_payments(&self, pagination: &PaginationParams) -> ClientResult<PaginatedResponse<PaymentResponse>> {
+        let url = self.url("/api/v1/payments");
+        self.send(|| self.http().get(&url).query(pagination), None).await
+    }
+
+    pub async fn get_payment(&self, id: Uuid) -> ClientResult<PaymentResponse> {
+        let url = self.url(&format!("/api/v1/payments/{}", id));
+        self.send(|| self.http().get(&url), None).await
+    }
+
+    /// Cancel a payment before it executes
+    pub async fn cancel_payment(&self, id: Uuid) -> ClientResult<PaymentResponse> {
+        let url = self.url(&format!("/api/v1/payments/{}/cancel", id));
+        self.send(|| self.http().post(&url), None).await
+    }
+
+    /// Check an IBAN or local account number before paying it
+    pub async fn validate_account(&self, query: &ValidateAccountQuery) -> ClientResult<AccountValidation> {
+        let url = self.url("/api/v1/payments/validate-account");
+        self.send(|| self.http().get(&url).query(query), None).await
+    }
+
+    /// Render a QR code for a pending payment, as PNG or SVG bytes
+    pub async fn payment_qr(&self, id: Uuid, query: &QrQuery) -> ClientResult<Vec<u8>> {
+        let url = self.url(&format!("/api/v1/payments/{}/qr", id));
+        self.send_for_bytes(|| self.http().get(&url).query(query)).await
+    }
+}
@@ -0,0 +1,154 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use openbank::auth::model::{JwtClaims, RefreshTokenRequest, TokenRequest, TokenResponse};
+use tokio::sync::Mutex;
+use crate::client::read_data;
+use crate::error::{ClientError, ClientResult};
+
+/// Tokens are renewed this long before they expire, so a request never
+/// starts with a token that lapses in flight
+const REFRESH_MARGIN_SECONDS: i64 = 60;
+
+/// OAuth2 client credentials of a project
+#[derive(Debug, Clone)]
+pub struct ClientCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+    /// Space separated scopes to request; defaults to all of the project's scopes
+    pub scope: Option<String>,
+}
+
+impl ClientCredentials {
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            scope: None,
+        }
+    }
+
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedToken {
+    access_token: String,
+    jti: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl CachedToken {
+    fn from_response(response: TokenResponse) -> ClientResult<Self> {
+        let claims = decode_claims(&response.access_token)?;
+        Ok(Self {
+            jti: claims.jti,
+            expires_at: Utc::now() + Duration::seconds(response.expires_in),
+            access_token: response.access_token,
+        })
+    }
+
+    fn is_fresh(&self) -> bool {
+        self.expires_at - Duration::seconds(REFRESH_MARGIN_SECONDS) > Utc::now()
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+/// Read the claims of an access token. The signature is the server's
+/// concern; the client only needs the token ID to refresh it.
+fn decode_claims(token: &str) -> ClientResult<JwtClaims> {
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| ClientError::InvalidResponse("Access token is not a JWT".to_string()))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|e| ClientError::InvalidResponse(format!("Invalid access token payload: {}", e)))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| ClientError::InvalidResponse(format!("Invalid access token claims: {}", e)))
+}
+
+/// Obtains access tokens with the client credentials grant and renews them
+/// before they expire. Concurrent callers wait for a single renewal.
+pub(crate) struct TokenManager {
+    credentials: ClientCredentials,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+impl TokenManager {
+    pub(crate) fn new(credentials: ClientCredentials) -> Self {
+        Self {
+            credentials,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// A valid access token, renewed first if it is about to expire
+    pub(crate) async fn access_token(&self, http: &reqwest::Client, base_url: &str) -> ClientResult<String> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref().filter(|token| token.is_fresh()) {
+            return Ok(token.access_token.clone());
+        }
+
+        // The refresh endpoint only accepts tokens that have not expired yet
+        let renewed = match cached.take().filter(|token| !token.is_expired()) {
+            Some(expiring) => match self.refresh(http, base_url, &expiring.jti).await {
+                Ok(token) => token,
+                Err(err) => {
+                    tracing::warn!("Token refresh failed, requesting a new token: {}", err);
+                    self.request_token(http, base_url).await?
+                }
+            },
+            None => self.request_token(http, base_url).await?,
+        };
+
+        let access_token = renewed.access_token.clone();
+        *cached = Some(renewed);
+        Ok(access_token)
+    }
+
+    /// Forget a token the server rejected, unless it was already replaced
+    pub(crate) async fn invalidate(&self, rejected: &str) {
+        let mut cached = self.cached.lock().await;
+        if cached.as_ref().is_some_and(|token| token.access_token == rejected) {
+            *cached = None;
+        }
+    }
+
+    async fn request_token(&self, http: &reqwest::Client, base_url: &str) -> ClientResult<CachedToken> {
+        let request = TokenRequest {
+            grant_type: "client_credentials".to_string(),
+            client_id: self.credentials.client_id.clone(),
+            client_secret: self.credentials.client_secret.clone(),
+            scope: self.credentials.scope.clone(),
+            username: None,
+            password: None,
+            otp: None,
+        };
+        let response = http
+            .post(format!("{}/auth/token", base_url))
+            .json(&request)
+            .send()
+            .await?;
+        CachedToken::from_response(read_data(response).await?)
+    }
+
+    async fn refresh(&self, http: &reqwest::Client, base_url: &str, jti: &str) -> ClientResult<CachedToken> {
+        let request = RefreshTokenRequest {
+            client_id: self.credentials.client_id.clone(),
+            client_secret: self.credentials.client_secret.clone(),
+            jti: jti.to_string(),
+        };
+        let response = http
+            .post(format!("{}/auth/token/refresh", base_url))
+            .json(&request)
+            .send()
+            .await?;
+        CachedToken::from_response(read_data(response).await?)
+    }
+}
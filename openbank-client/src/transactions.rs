@@ -0,0 +1,42 @@
+use openbank::shared::types::{PaginatedResponse, PaginationParams, TransactionId};
+use openbank::transactions::model::{CreateTransactionRequest, TransactionResponse, TransferRequest};
+use crate::client::OpenBankClient;
+use crate::error::ClientResult;
+use crate::idempotency::IdempotencyKey;
+
+impl OpenBankClient {
+    /// Record a transaction. Retrying with the same key does not record it twice.
+    pub async fn create_transaction(
+        &self,
+        request: &CreateTransactionRequest,
+        idempotency_key: &IdempotencyKey,
+    ) -> ClientResult<TransactionResponse> {
+        let url = self.url("/api/v1/transactions");
+        self.send(|| self.http().post(&url).json(request), Some(idempotency_key))
+            .await
+    }
+
+    pub async fn list_transactions(
+        &self,
+        pagination: &PaginationParams,
+    ) -> ClientResult<PaginatedResponse<TransactionResponse>> {
+        let url = self.url("/api/v1/transactions");
+        self.send(|| self.http().get(&url).query(pagination), None).await
+    }
+
+    pub async fn get_transaction(&self, id: TransactionId) -> ClientResult<TransactionResponse> {
+        let url = self.url(&format!("/api/v1/transactions/{}", id));
+        self.send(|| self.http().get(&url), None).await
+    }
+
+    /// Move funds between accounts. Retrying with the same key does not move them twice.
+    pub async fn transfer(
+        &self,
+        request: &TransferRequest,
+        idempotency_key: &IdempotencyKey,
+    ) -> ClientResult<TransactionResponse> {
+        let url = self.url("/api/v1/transactions/transfer");
+        self.send(|| self.http().post(&url).json(request), Some(idempotency_key))
+            .await
+    }
+}
@@ -0,0 +1,37 @@
+use openbank::core::config::Config;
+
+/// Secret the test configuration signs access tokens with
+pub const TEST_JWT_SECRET: &str = "openbank-test-jwt-secret";
+
+/// 32 byte key, base64 encoded, for the test envelope cipher
+const TEST_ENCRYPTION_KEY: &str = "b3BlbmJhbmstdGVzdC1lbmNyeXB0aW9uLWtleS0zMmI=";
+
+/// Configuration for tests: the defaults, with anything that would reach
+/// outside the test process replaced.
+///
+/// Environment variables still apply, so a test run can point at a
+/// particular Postgres or MongoDB; secrets, keys and providers are fixed.
+pub fn test_config() -> Config {
+    let mut config = Config::from_env().expect("Failed to load test configuration");
+
+    config.jwt_secret = TEST_JWT_SECRET.to_string();
+    config.encryption_keys = format!("test:{}", TEST_ENCRYPTION_KEY);
+    config.encryption_active_key_id = "test".to_string();
+    config.income_report_signing_key = "openbank-test-report-signing-key".to_string();
+    config.secrets_provider = "env".to_string();
+    config.mail_provider = "log".to_string();
+    config.alert_sink = "log".to_string();
+    config.credit_bureau_provider = "none".to_string();
+    config.merchant_enrichment_provider = "rules".to_string();
+    config.verification_expiry_webhook_url = None;
+    config.account_closure_webhook_url = None;
+    config.storage_local_root = std::env::temp_dir()
+        .join("openbank-test-storage")
+        .to_string_lossy()
+        .into_owned();
+    config.swagger_ui_enabled = false;
+    // Payments execute straight away whatever the time of day
+    config.payment_cutoff_bank_transfer = None;
+
+    config
+}
@@ -0,0 +1,112 @@
+use sqlx::migrate::Migrator;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Executor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Environment variable naming the Postgres server database tests run against
+pub const TEST_DATABASE_URL: &str = "TEST_DATABASE_URL";
+
+static MIGRATOR: Migrator = sqlx::migrate!("../migrations");
+
+/// Versions below this are the legacy numbered migrations (`001_`, `004_`)
+const LEGACY_VERSION_LIMIT: i64 = 1_000;
+
+/// The auth plane migration databases received the legacy numbered
+/// migrations after; they build on the tables it creates
+const LEGACY_MIGRATIONS_AFTER: i64 = 20250929000002;
+
+/// A Postgres schema private to one test, with every migration applied.
+///
+/// The pool's connections only see this schema, so tests run in parallel
+/// against one server without seeing each other's rows.
+pub struct TestDatabase {
+    pool: PgPool,
+    admin: PgPool,
+    schema: String,
+}
+
+impl TestDatabase {
+    /// Create a schema on the `TEST_DATABASE_URL` server, or `None` when the
+    /// variable is not set so the calling test can skip itself
+    pub async fn create() -> Option<Self> {
+        match std::env::var(TEST_DATABASE_URL) {
+            Ok(url) => Some(Self::create_at(&url).await),
+            Err(_) => {
+                eprintln!("{} is not set, skipping database test", TEST_DATABASE_URL);
+                None
+            }
+        }
+    }
+
+    /// Create a schema on the given server
+    pub async fn create_at(url: &str) -> Self {
+        let schema = format!("test_{}", Uuid::new_v4().simple());
+
+        let admin = PgPoolOptions::new()
+            .max_connections(1)
+            .connect(url)
+            .await
+            .expect("Failed to connect to the test database");
+        admin
+            .execute(format!("CREATE SCHEMA {}", schema).as_str())
+            .await
+            .expect("Failed to create test schema");
+
+        let search_path = format!("SET search_path TO {}, public", schema);
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .after_connect(move |conn, _| {
+                let search_path = search_path.clone();
+                Box::pin(async move {
+                    conn.execute(search_path.as_str()).await?;
+                    Ok(())
+                })
+            })
+            .connect(url)
+            .await
+            .expect("Failed to connect to the test schema");
+
+        migrate(&pool).await;
+
+        Self { pool, admin, schema }
+    }
+
+    pub fn pool(&self) -> PgPool {
+        self.pool.clone()
+    }
+
+    /// A transaction in the test schema that is rolled back when dropped,
+    /// for tests that only need a connection
+    pub async fn begin(&self) -> Transaction<'static, Postgres> {
+        self.pool.begin().await.expect("Failed to start test transaction")
+    }
+
+    /// Drop the schema and everything in it. A test that panics never gets
+    /// here, leaving its schema behind for inspection.
+    pub async fn cleanup(self) {
+        self.pool.close().await;
+        self.admin
+            .execute(format!("DROP SCHEMA {} CASCADE", self.schema).as_str())
+            .await
+            .expect("Failed to drop test schema");
+    }
+}
+
+/// Apply every migration in version order, with the legacy numbered ones
+/// where databases received them
+async fn migrate(pool: &PgPool) {
+    let mut migrations: Vec<_> = MIGRATOR.iter().collect();
+    migrations.sort_by_key(|migration| {
+        if migration.version < LEGACY_VERSION_LIMIT {
+            (LEGACY_MIGRATIONS_AFTER, migration.version)
+        } else {
+            (migration.version, 0)
+        }
+    });
+
+    for migration in migrations {
+        pool.execute(&*migration.sql).await.unwrap_or_else(|e| {
+            panic!("Failed to apply migration {} {}: {}", migration.version, migration.description, e)
+        });
+    }
+}
@@ -0,0 +1,151 @@
+use openbank::auth::login_history::LoginContext;
+use openbank::auth::model::{
+    CreateProjectRequest, DeveloperResponse, ProjectEnvironment, ProjectResponse,
+    RegisterDeveloperRequest, TokenRequest, TokenResponse,
+};
+use openbank::auth::repository::AuthRepository;
+use openbank::auth::service::AuthService;
+use openbank::core::config::Config;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// A project with the credentials it was issued
+pub struct SeededProject {
+    pub developer: DeveloperResponse,
+    pub project: ProjectResponse,
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+/// A user and the account seeded for them
+pub struct SeededAccount {
+    pub user_id: Uuid,
+    pub id: Uuid,
+}
+
+/// Seeds developers, projects and tokens through the auth service, so they
+/// look exactly like ones created through the API, and bank accounts
+/// directly in the database
+pub struct Seeder {
+    pool: PgPool,
+    auth: AuthService,
+}
+
+impl Seeder {
+    pub fn new(pool: PgPool, config: &Config) -> Self {
+        Self {
+            auth: AuthService::new(AuthRepository::new(pool.clone()), config.jwt_secret.clone()),
+            pool,
+        }
+    }
+
+    /// A developer with a unique email and their personal organization
+    pub async fn developer(&self) -> DeveloperResponse {
+        let suffix = Uuid::new_v4().simple().to_string();
+        self.auth
+            .register_developer(RegisterDeveloperRequest {
+                name: "Test Developer".to_string(),
+                email: format!("developer-{}@example.com", &suffix[..12]),
+                company: None,
+                title: None,
+                password: "correct-horse-battery".to_string(),
+            })
+            .await
+            .expect("Failed to seed developer")
+    }
+
+    /// A development project with the given scopes, owned by a new developer
+    pub async fn project(&self, scopes: &[&str]) -> SeededProject {
+        let developer = self.developer().await;
+        self.project_for(developer, scopes).await
+    }
+
+    /// A development project with the given scopes in the developer's organization
+    pub async fn project_for(&self, developer: DeveloperResponse, scopes: &[&str]) -> SeededProject {
+        let project = self
+            .auth
+            .create_project(
+                developer.id,
+                CreateProjectRequest {
+                    name: "Test Project".to_string(),
+                    description: None,
+                    environment: ProjectEnvironment::Development,
+                    redirect_uris: Vec::new(),
+                    scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+                    organization_id: None,
+                    webhook_url: None,
+                },
+            )
+            .await
+            .expect("Failed to seed project");
+
+        // The response carries the only copy of the secret as `id:secret`
+        let (client_id, client_secret) = project
+            .client_id
+            .split_once(':')
+            .map(|(id, secret)| (id.to_string(), secret.to_string()))
+            .expect("Project response has no client secret");
+
+        SeededProject {
+            developer,
+            project,
+            client_id,
+            client_secret,
+        }
+    }
+
+    /// An access token for the project with all of its scopes
+    pub async fn token(&self, project: &SeededProject) -> TokenResponse {
+        self.auth
+            .handle_client_credentials_flow(
+                TokenRequest {
+                    grant_type: "client_credentials".to_string(),
+                    client_id: project.client_id.clone(),
+                    client_secret: project.client_secret.clone(),
+                    scope: None,
+                    username: None,
+                    password: None,
+                    otp: None,
+                },
+                &LoginContext::default(),
+            )
+            .await
+            .expect("Failed to issue token")
+    }
+
+    /// A checking account of a new user, in the tenant if one is given,
+    /// holding `balance` in `currency` as both its available and ledger balance
+    pub async fn account(&self, tenant_id: Option<Uuid>, currency: &str, balance: i64) -> SeededAccount {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, first_name, last_name, tenant_id)
+             VALUES ($1, 'x', 'Test', 'User', $2) RETURNING id",
+        )
+        .bind(format!("{}@example.com", Uuid::new_v4()))
+        .bind(tenant_id)
+        .fetch_one(&self.pool)
+        .await
+        .expect("Failed to seed user");
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO accounts (user_id, account_number, account_name, account_type, currency, tenant_id)
+             VALUES ($1, $2, 'Checking', 'checking', $3, $4) RETURNING id",
+        )
+        .bind(user_id)
+        .bind(&Uuid::new_v4().simple().to_string()[..20])
+        .bind(currency)
+        .bind(tenant_id)
+        .fetch_one(&self.pool)
+        .await
+        .expect("Failed to seed account");
+        sqlx::query(
+            "INSERT INTO balances (account_id, available_balance, ledger_balance, currency) VALUES ($1, $2, $2, $3)",
+        )
+        .bind(id)
+        .bind(balance)
+        .bind(currency)
+        .execute(&self.pool)
+        .await
+        .expect("Failed to seed balance");
+
+        SeededAccount { user_id, id }
+    }
+}
@@ -0,0 +1,16 @@
+use std::io::Cursor;
+use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+use openbank::shared::constants::MIN_IMAGE_DIMENSION;
+
+/// A valid image of the given type, of the smallest size identity uploads
+/// accept, for tests submitting photos
+pub fn test_image(content_type: &str) -> Vec<u8> {
+    let format = ImageFormat::from_mime_type(content_type).expect("Unsupported test image type");
+    let image = RgbImage::from_fn(MIN_IMAGE_DIMENSION, MIN_IMAGE_DIMENSION, |x, y| Rgb([x as u8, y as u8, 128]));
+
+    let mut buffer = Cursor::new(Vec::new());
+    DynamicImage::ImageRgb8(image)
+        .write_to(&mut buffer, format)
+        .expect("Failed to encode test image");
+    buffer.into_inner()
+}
@@ -0,0 +1,19 @@
+//! Fixtures and factories for OpenBank integration tests.
+//!
+//! [`TestStateBuilder`] assembles an `AppState` with in-memory audit logging
+//! and fakes that need no running services; [`TestDatabase`] gives each test
+//! its own migrated Postgres schema; [`Seeder`] creates developers, projects
+//! and access tokens through the real auth service, and funded accounts;
+//! [`test_image`] encodes photos identity uploads accept.
+
+mod config;
+mod database;
+mod fixtures;
+mod images;
+mod state;
+
+pub use config::test_config;
+pub use database::{TestDatabase, TEST_DATABASE_URL};
+pub use fixtures::{SeededAccount, SeededProject, Seeder};
+pub use images::test_image;
+pub use state::TestStateBuilder;
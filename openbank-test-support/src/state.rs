@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use openbank::core::audit::AuditLogger;
+use openbank::core::circuit_breaker::{CircuitBreakerSettings, CircuitBreakers};
+use openbank::core::config::Config;
+use openbank::core::http_client::HttpClients;
+use openbank::core::mailer::{LogMailer, Mailer};
+use openbank::core::storage::{LocalStorage, Storage};
+use openbank::core::AppState;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+use crate::config::test_config;
+
+/// Builds an `AppState` for tests.
+///
+/// By default audit events are kept in memory, email goes to the log and no
+/// database connection is opened until a query runs, so handlers that do not
+/// touch Postgres or MongoDB work without either running.
+pub struct TestStateBuilder {
+    config: Config,
+    postgres: Option<PgPool>,
+    audit_logger: AuditLogger,
+    storage: Option<Arc<dyn Storage>>,
+    mailer: Arc<dyn Mailer>,
+}
+
+impl TestStateBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: test_config(),
+            postgres: None,
+            audit_logger: AuditLogger::in_memory(),
+            storage: None,
+            mailer: Arc::new(LogMailer),
+        }
+    }
+
+    /// Adjust the configuration
+    pub fn config(mut self, configure: impl FnOnce(&mut Config)) -> Self {
+        configure(&mut self.config);
+        self
+    }
+
+    /// Use a real pool, typically [`TestDatabase::pool`](crate::TestDatabase::pool)
+    pub fn postgres(mut self, pool: PgPool) -> Self {
+        self.postgres = Some(pool);
+        self
+    }
+
+    /// Share an audit logger with the test to inspect the events it records
+    pub fn audit_logger(mut self, audit_logger: AuditLogger) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    pub fn storage(mut self, storage: Arc<dyn Storage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    pub fn mailer(mut self, mailer: Arc<dyn Mailer>) -> Self {
+        self.mailer = mailer;
+        self
+    }
+
+    pub async fn build(self) -> AppState {
+        let config = self.config;
+        let postgres = match self.postgres {
+            Some(pool) => pool,
+            None => PgPoolOptions::new()
+                .connect_lazy(&config.database_url)
+                .expect("Invalid DATABASE_URL"),
+        };
+        // The driver connects on the first operation, so this needs no server
+        let mongodb = mongodb::Client::with_uri_str(&config.mongodb_url)
+            .await
+            .expect("Invalid MONGODB_URL");
+        let storage = self
+            .storage
+            .unwrap_or_else(|| Arc::new(LocalStorage::new(config.storage_local_root.clone())));
+
+        let circuit_breakers = CircuitBreakers::new(CircuitBreakerSettings::from_config(&config));
+        let http_clients = HttpClients::from_config(&config).expect("Invalid outbound HTTP settings");
+
+        AppState::new(
+            config,
+            postgres,
+            mongodb,
+            self.audit_logger,
+            storage,
+            self.mailer,
+            circuit_breakers,
+            http_clients,
+        )
+        .expect("Failed to build test state")
+    }
+}
+
+impl Default for TestStateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,88 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::account_controls::{repository::AccountControlRepository, service::AccountFreezeGuard};
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::transactions::repository::TransactionRepository;
+use crate::webhooks::repository::WebhookRepository;
+use super::model::{AccountClosure, CloseAccountRequest, ClosureSettings};
+use super::repository::AccountClosureRepository;
+use super::service::AccountClosureService;
+
+fn account_closure_service(state: &AppState) -> AccountClosureService {
+    AccountClosureService::new(
+        AccountClosureRepository::new(state.postgres.clone()),
+        TransactionRepository::new(state.postgres.clone()),
+        AccountFreezeGuard::new(
+            AccountControlRepository::new(state.postgres.clone()),
+            state.config.frozen_accounts_allow_credits,
+        ),
+        state.audit_logger.clone(),
+        state.event_bus.clone(),
+        WebhookRepository::new(state.postgres.clone()),
+        ClosureSettings::from_config(&state.config),
+    )
+}
+
+/// Close an account, sweeping any remaining balance to another account
+#[utoipa::path(
+    post,
+    path = "/api/v1/account-closures",
+    tag = "account-closures",
+    request_body = CloseAccountRequest,
+    responses(
+        (status = 201, description = "Account closed", body = AccountClosure),
+        (status = 400, description = "Account has a balance and no transfer account, or cannot be debited"),
+        (status = 404, description = "Account not found"),
+        (status = 409, description = "Account is already closed, has open payments, goal funds or held funds")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn close_account(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<CloseAccountRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<AccountClosure>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let closure = account_closure_service(&state)
+        .close_account(request, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Account closed successfully", closure)),
+    ))
+}
+
+/// Get an account closure record
+#[utoipa::path(
+    get,
+    path = "/api/v1/account-closures/{id}",
+    tag = "account-closures",
+    params(("id" = Uuid, Path, description = "Account closure ID")),
+    responses(
+        (status = 200, description = "Account closure", body = AccountClosure),
+        (status = 404, description = "Account closure not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_account_closure(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<AccountClosure>>> {
+    let closure = account_closure_service(&state).get_closure(id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Account closure retrieved successfully", closure)))
+}
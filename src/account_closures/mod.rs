@@ -0,0 +1,13 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(controller::close_account))
+        .route("/:id", get(controller::get_account_closure))
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::core::config::Config;
+use crate::shared::types::{AccountId, Amount, TenantId, TransactionId};
+
+/// Why an account was closed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "account_closure_reason", rename_all = "snake_case")]
+pub enum ClosureReason {
+    CustomerRequest,
+    SwitchingProvider,
+    Consolidation,
+    Other,
+}
+
+/// Record of an account's closure, kept until `retain_until`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AccountClosure {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub tenant_id: Option<TenantId>,
+    pub account_id: AccountId,
+    pub reason: ClosureReason,
+    pub note: Option<String>,
+    /// Account the remaining balance was swept to
+    pub transfer_to_account_id: Option<AccountId>,
+    pub transferred_amount: Amount,
+    /// Transfer that swept the remaining balance
+    pub transaction_id: Option<TransactionId>,
+    pub requested_by: Uuid,
+    /// Records of the account are retained at least until this date
+    pub retain_until: NaiveDate,
+    pub closed_at: DateTime<Utc>,
+}
+
+/// Close account request. An account with a balance can only be closed by
+/// sweeping it to another open account.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CloseAccountRequest {
+    pub account_id: AccountId,
+    pub reason: ClosureReason,
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+    pub transfer_to_account_id: Option<AccountId>,
+}
+
+/// Configured retention and notification for closures
+#[derive(Debug, Clone)]
+pub struct ClosureSettings {
+    pub retention_days: i64,
+    /// Receives an `account.closed` notification for every closure
+    pub webhook_url: Option<String>,
+}
+
+impl ClosureSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            retention_days: config.closed_account_retention_days,
+            webhook_url: config.account_closure_webhook_url.clone(),
+        }
+    }
+}
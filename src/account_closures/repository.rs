@@ -0,0 +1,214 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::constants::TRANSACTION_REF_PREFIX;
+use crate::shared::types::{AccountId, Amount, Currency, TenantId};
+use crate::transactions::model::{TransactionStatus, TransactionType};
+use super::model::AccountClosure;
+
+const CLOSURE_COLUMNS: &str = "id, tenant_id, account_id, reason, note, transfer_to_account_id, transferred_amount,
+    transaction_id, requested_by, retain_until, closed_at";
+
+pub struct AccountClosureRepository {
+    pool: PgPool,
+}
+
+impl AccountClosureRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_id(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<AccountClosure>> {
+        let closure = sqlx::query_as::<_, AccountClosure>(&format!(
+            "SELECT {CLOSURE_COLUMNS} FROM account_closures WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(closure)
+    }
+
+    /// Payments that could still move money in or out of the account
+    pub async fn count_open_payments(&self, account_id: AccountId) -> AppResult<i64> {
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM payments
+             WHERE (from_account_id = $1 OR to_account_id = $1)
+               AND status IN ('scheduled', 'pending', 'processing')",
+        )
+        .bind(account_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Funds still set aside in the account's savings goals
+    pub async fn open_goal_balance(&self, account_id: AccountId) -> AppResult<Amount> {
+        let balance = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(balance), 0)::BIGINT FROM savings_goals
+             WHERE account_id = $1 AND status <> 'closed'",
+        )
+        .bind(account_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(balance)
+    }
+
+    /// Close the account, first sweeping any remaining balance to
+    /// `transfer_to_account_id`. Returns `None` if the account was closed in
+    /// the meantime.
+    pub async fn close(&self, closure: &AccountClosure) -> AppResult<Option<AccountClosure>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let open = sqlx::query_scalar::<_, bool>(
+            "SELECT closed_at IS NULL FROM accounts WHERE id = $1 FOR UPDATE",
+        )
+        .bind(closure.account_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Account not found".to_string()))?;
+        if !open {
+            return Ok(None);
+        }
+
+        let balance = sqlx::query_as::<_, (Amount, Amount, Currency)>(
+            "SELECT COALESCE(available_balance, 0), COALESCE(ledger_balance, 0), currency
+             FROM balances WHERE account_id = $1 FOR UPDATE",
+        )
+        .bind(closure.account_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let mut transferred_amount = 0;
+        let mut transaction_id = None;
+        if let Some((available, ledger, currency)) = balance {
+            if available != ledger {
+                return Err(AppError::Conflict("Account has funds on hold".to_string()));
+            }
+            if ledger < 0 {
+                return Err(AppError::Conflict("Account is overdrawn".to_string()));
+            }
+            if ledger > 0 {
+                let Some(to_account_id) = closure.transfer_to_account_id else {
+                    return Err(AppError::BadRequest(
+                        "Account balance must be zero or transferred to another account".to_string(),
+                    ));
+                };
+                transaction_id = Some(
+                    sweep(&mut tx, closure.account_id, to_account_id, ledger, &currency, closure.tenant_id).await?,
+                );
+                transferred_amount = ledger;
+            }
+        }
+
+        sqlx::query(
+            "UPDATE accounts SET closed_at = $1, closure_reason = $2, is_active = FALSE, updated_at = NOW()
+             WHERE id = $3",
+        )
+        .bind(closure.closed_at)
+        .bind(closure.reason)
+        .bind(closure.account_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let created = sqlx::query_as::<_, AccountClosure>(&format!(
+            "INSERT INTO account_closures ({CLOSURE_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING {CLOSURE_COLUMNS}"
+        ))
+        .bind(closure.id)
+        .bind(closure.tenant_id)
+        .bind(closure.account_id)
+        .bind(closure.reason)
+        .bind(&closure.note)
+        .bind(closure.transfer_to_account_id.filter(|_| transferred_amount > 0))
+        .bind(transferred_amount)
+        .bind(transaction_id)
+        .bind(closure.requested_by)
+        .bind(closure.retain_until)
+        .bind(closure.closed_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(created))
+    }
+}
+
+/// Move the whole balance of a closing account to another account as a
+/// completed transfer, posted on both balances
+async fn sweep(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    from_account_id: AccountId,
+    to_account_id: AccountId,
+    amount: Amount,
+    currency: &str,
+    tenant_id: Option<TenantId>,
+) -> AppResult<Uuid> {
+    let transaction_id = Uuid::new_v4();
+    let description = format!("Closing balance of account {}", from_account_id);
+
+    sqlx::query(
+        "INSERT INTO transactions (id, from_account_id, to_account_id, amount, currency, transaction_type, status, reference, description, tenant_id)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+    )
+    .bind(transaction_id)
+    .bind(from_account_id)
+    .bind(to_account_id)
+    .bind(amount)
+    .bind(currency)
+    .bind(TransactionType::Transfer)
+    .bind(TransactionStatus::Completed)
+    .bind(format!("{}_{}", TRANSACTION_REF_PREFIX, transaction_id))
+    .bind(&description)
+    .bind(tenant_id)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE balances SET available_balance = 0, ledger_balance = 0, updated_at = NOW()
+         WHERE account_id = $1",
+    )
+    .bind(from_account_id)
+    .execute(&mut **tx)
+    .await?;
+
+    let to_ledger = sqlx::query_scalar::<_, Amount>(
+        "INSERT INTO balances (account_id, available_balance, ledger_balance, currency)
+         VALUES ($1, $2, $2, $3)
+         ON CONFLICT (account_id) DO UPDATE
+         SET available_balance = balances.available_balance + EXCLUDED.available_balance,
+             ledger_balance = balances.ledger_balance + EXCLUDED.ledger_balance,
+             updated_at = NOW()
+         RETURNING ledger_balance",
+    )
+    .bind(to_account_id)
+    .bind(amount)
+    .bind(currency)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    for (account_id, balance_before, amount_changed) in [
+        (from_account_id, amount, -amount),
+        (to_account_id, to_ledger - amount, amount),
+    ] {
+        sqlx::query(
+            "INSERT INTO balance_history (account_id, balance_before, balance_after, amount_changed, transaction_id, description)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(account_id)
+        .bind(balance_before)
+        .bind(balance_before + amount_changed)
+        .bind(amount_changed)
+        .bind(transaction_id)
+        .bind(&description)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(transaction_id)
+}
@@ -0,0 +1,158 @@
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use crate::account_controls::{model::AccountKind, service::AccountFreezeGuard};
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::events::{DomainEvent, DomainEventType, EventBus};
+use crate::shared::types::{AccountId, TenantId};
+use crate::transactions::repository::TransactionRepository;
+use crate::webhooks::repository::WebhookRepository;
+use super::model::{AccountClosure, CloseAccountRequest, ClosureSettings};
+use super::repository::AccountClosureRepository;
+
+pub struct AccountClosureService {
+    repository: AccountClosureRepository,
+    transaction_repository: TransactionRepository,
+    freeze_guard: AccountFreezeGuard,
+    audit_logger: AuditLogger,
+    event_bus: EventBus,
+    webhooks: WebhookRepository,
+    settings: ClosureSettings,
+}
+
+impl AccountClosureService {
+    pub fn new(
+        repository: AccountClosureRepository,
+        transaction_repository: TransactionRepository,
+        freeze_guard: AccountFreezeGuard,
+        audit_logger: AuditLogger,
+        event_bus: EventBus,
+        webhooks: WebhookRepository,
+        settings: ClosureSettings,
+    ) -> Self {
+        Self {
+            repository,
+            transaction_repository,
+            freeze_guard,
+            audit_logger,
+            event_bus,
+            webhooks,
+            settings,
+        }
+    }
+
+    /// Close an account once nothing can move money through it any more.
+    /// A remaining balance is swept to `transfer_to_account_id`; afterwards
+    /// the account rejects debits and credits and its records are kept for
+    /// the configured retention period.
+    pub async fn close_account(
+        &self,
+        request: CloseAccountRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<AccountClosure> {
+        self.ensure_account_in_tenant(request.account_id, tenant_id).await?;
+        // Frozen or already closed accounts cannot be drained or closed
+        self.freeze_guard.ensure_can_debit(request.account_id).await?;
+
+        if let Some(to_account_id) = request.transfer_to_account_id {
+            if to_account_id == request.account_id {
+                return Err(AppError::Validation(
+                    "Cannot transfer the closing balance to the account being closed".to_string(),
+                ));
+            }
+            self.ensure_account_in_tenant(to_account_id, tenant_id).await?;
+            self.freeze_guard
+                .ensure_can_credit(AccountKind::Account, to_account_id)
+                .await?;
+        }
+
+        if self.repository.count_open_payments(request.account_id).await? > 0 {
+            return Err(AppError::Conflict(
+                "Account has scheduled or pending payments; cancel or settle them first".to_string(),
+            ));
+        }
+        if self.repository.open_goal_balance(request.account_id).await? > 0 {
+            return Err(AppError::Conflict(
+                "Account has funds in savings goals; release them first".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let closure = AccountClosure {
+            id: Uuid::new_v4(),
+            tenant_id: Some(tenant_id),
+            account_id: request.account_id,
+            reason: request.reason,
+            note: request.note,
+            transfer_to_account_id: request.transfer_to_account_id,
+            transferred_amount: 0,
+            transaction_id: None,
+            requested_by: actor_id,
+            retain_until: (now + Duration::days(self.settings.retention_days)).date_naive(),
+            closed_at: now,
+        };
+        let closure = self
+            .repository
+            .close(&closure)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Account is already closed".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::AccountClosed)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("account:{}", closure.account_id))
+            .action("close".to_string())
+            .metadata("reason".to_string(), json!(closure.reason))
+            .metadata("transferred_amount".to_string(), json!(closure.transferred_amount))
+            .metadata("transfer_to_account_id".to_string(), json!(closure.transfer_to_account_id))
+            .metadata("retain_until".to_string(), json!(closure.retain_until))
+            .compliance_tag("ACCOUNT_CLOSURE".to_string());
+        self.audit_logger.log(event).await;
+
+        let account_ids = std::iter::once(closure.account_id)
+            .chain(closure.transfer_to_account_id)
+            .collect();
+        self.event_bus.publish(DomainEvent::new(
+            DomainEventType::AccountClosed,
+            closure.tenant_id,
+            account_ids,
+            json!(closure),
+        ));
+
+        if let Some(url) = &self.settings.webhook_url {
+            let event_type = DomainEventType::AccountClosed.as_str();
+            let payload = json!({
+                "event": event_type,
+                "closure_id": closure.id,
+                "account_id": closure.account_id,
+                "reason": closure.reason,
+                "transferred_amount": closure.transferred_amount,
+                "transfer_to_account_id": closure.transfer_to_account_id,
+                "retain_until": closure.retain_until,
+                "closed_at": closure.closed_at,
+            });
+            // The closure stands even if its notification cannot be queued
+            if let Err(e) = self.webhooks.enqueue(closure.tenant_id, event_type, url, &payload).await {
+                tracing::error!("Failed to queue account closure webhook for {}: {}", closure.account_id, e);
+            }
+        }
+
+        Ok(closure)
+    }
+
+    pub async fn get_closure(&self, id: Uuid, tenant_id: TenantId) -> AppResult<AccountClosure> {
+        self.repository
+            .find_by_id(id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account closure not found".to_string()))
+    }
+
+    async fn ensure_account_in_tenant(&self, account_id: AccountId, tenant_id: TenantId) -> AppResult<()> {
+        if !self.transaction_repository.account_in_tenant(account_id, tenant_id).await? {
+            return Err(AppError::NotFound("Account not found".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::notifications::controller::notification_service;
+use super::model::{AccountFreezeResponse, AccountKind, FreezeAccountRequest};
+use super::repository::AccountControlRepository;
+use super::service::AccountControlService;
+
+fn account_control_service(state: &AppState) -> AccountControlService {
+    AccountControlService::new(
+        AccountControlRepository::new(state.postgres.clone()),
+        notification_service(state),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Get the freeze state of an account
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/accounts/{id}/freeze",
+    tag = "account-controls",
+    params(("id" = Uuid, Path, description = "Account ID")),
+    responses(
+        (status = 200, description = "Freeze state", body = AccountFreezeResponse),
+        (status = 403, description = "Caller lacks the freeze permission"),
+        (status = 404, description = "Account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_account_freeze(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    get_freeze(state, claims.developer_id, ip, AccountKind::Account, id).await
+}
+
+/// Freeze an account
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/accounts/{id}/freeze",
+    tag = "account-controls",
+    params(("id" = Uuid, Path, description = "Account ID")),
+    request_body = FreezeAccountRequest,
+    responses(
+        (status = 200, description = "Account frozen", body = AccountFreezeResponse),
+        (status = 403, description = "Caller lacks the freeze permission"),
+        (status = 404, description = "Account not found"),
+        (status = 409, description = "Account is already frozen")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn freeze_account(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<FreezeAccountRequest>,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    freeze(state, claims.developer_id, ip, AccountKind::Account, id, request).await
+}
+
+/// Unfreeze an account
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/accounts/{id}/unfreeze",
+    tag = "account-controls",
+    params(("id" = Uuid, Path, description = "Account ID")),
+    responses(
+        (status = 200, description = "Account unfrozen", body = AccountFreezeResponse),
+        (status = 403, description = "Caller lacks the freeze permission"),
+        (status = 404, description = "Account not found"),
+        (status = 409, description = "Account is not frozen")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unfreeze_account(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    unfreeze(state, claims.developer_id, ip, AccountKind::Account, id).await
+}
+
+/// Get the freeze state of a virtual account
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/virtual-accounts/{id}/freeze",
+    tag = "account-controls",
+    params(("id" = Uuid, Path, description = "Virtual account ID")),
+    responses(
+        (status = 200, description = "Freeze state", body = AccountFreezeResponse),
+        (status = 403, description = "Caller lacks the freeze permission"),
+        (status = 404, description = "Virtual account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_virtual_account_freeze(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    get_freeze(state, claims.developer_id, ip, AccountKind::VirtualAccount, id).await
+}
+
+/// Freeze a virtual account
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/virtual-accounts/{id}/freeze",
+    tag = "account-controls",
+    params(("id" = Uuid, Path, description = "Virtual account ID")),
+    request_body = FreezeAccountRequest,
+    responses(
+        (status = 200, description = "Virtual account frozen", body = AccountFreezeResponse),
+        (status = 403, description = "Caller lacks the freeze permission"),
+        (status = 404, description = "Virtual account not found"),
+        (status = 409, description = "Virtual account is already frozen")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn freeze_virtual_account(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<FreezeAccountRequest>,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    freeze(state, claims.developer_id, ip, AccountKind::VirtualAccount, id, request).await
+}
+
+/// Unfreeze a virtual account
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/virtual-accounts/{id}/unfreeze",
+    tag = "account-controls",
+    params(("id" = Uuid, Path, description = "Virtual account ID")),
+    responses(
+        (status = 200, description = "Virtual account unfrozen", body = AccountFreezeResponse),
+        (status = 403, description = "Caller lacks the freeze permission"),
+        (status = 404, description = "Virtual account not found"),
+        (status = 409, description = "Virtual account is not frozen")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn unfreeze_virtual_account(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    unfreeze(state, claims.developer_id, ip, AccountKind::VirtualAccount, id).await
+}
+
+async fn get_freeze(
+    state: AppState,
+    actor_id: Uuid,
+    ip: String,
+    kind: AccountKind,
+    id: Uuid,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    state
+        .authorize(actor_id, permissions::freeze_accounts(), ip, format!("{}:{}", kind.label(), id))
+        .await?;
+
+    let freeze = account_control_service(&state).get_freeze_state(kind, id).await?;
+    Ok(Json(ApiResponse::success("Freeze state retrieved successfully", freeze)))
+}
+
+async fn freeze(
+    state: AppState,
+    actor_id: Uuid,
+    ip: String,
+    kind: AccountKind,
+    id: Uuid,
+    request: FreezeAccountRequest,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(actor_id, permissions::freeze_accounts(), ip, format!("{}:{}", kind.label(), id))
+        .await?;
+
+    let freeze = account_control_service(&state)
+        .freeze(kind, id, request, actor_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Account frozen successfully", freeze)))
+}
+
+async fn unfreeze(
+    state: AppState,
+    actor_id: Uuid,
+    ip: String,
+    kind: AccountKind,
+    id: Uuid,
+) -> AppResult<Json<ApiResponse<AccountFreezeResponse>>> {
+    state
+        .authorize(actor_id, permissions::freeze_accounts(), ip, format!("{}:{}", kind.label(), id))
+        .await?;
+
+    let freeze = account_control_service(&state).unfreeze(kind, id, actor_id).await?;
+    Ok(Json(ApiResponse::success("Account unfrozen successfully", freeze)))
+}
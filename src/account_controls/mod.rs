@@ -0,0 +1,17 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/accounts/:id/freeze", get(controller::get_account_freeze))
+        .route("/accounts/:id/freeze", post(controller::freeze_account))
+        .route("/accounts/:id/unfreeze", post(controller::unfreeze_account))
+        .route("/virtual-accounts/:id/freeze", get(controller::get_virtual_account_freeze))
+        .route("/virtual-accounts/:id/freeze", post(controller::freeze_virtual_account))
+        .route("/virtual-accounts/:id/unfreeze", post(controller::unfreeze_virtual_account))
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Reason code recorded when an account is frozen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "freeze_reason", rename_all = "snake_case")]
+pub enum FreezeReason {
+    SuspectedFraud,
+    ComplianceReview,
+    LegalOrder,
+    CustomerRequest,
+    Other,
+}
+
+/// Kind of account an administrative control is applied to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccountKind {
+    Account,
+    VirtualAccount,
+}
+
+impl AccountKind {
+    pub fn table(&self) -> &'static str {
+        match self {
+            AccountKind::Account => "accounts",
+            AccountKind::VirtualAccount => "virtual_accounts",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AccountKind::Account => "account",
+            AccountKind::VirtualAccount => "virtual_account",
+        }
+    }
+}
+
+/// Freeze state of an account or virtual account
+#[derive(Debug, Clone, FromRow)]
+pub struct AccountFreezeState {
+    pub id: Uuid,
+    pub frozen_at: Option<DateTime<Utc>>,
+    pub freeze_reason: Option<FreezeReason>,
+    pub freeze_note: Option<String>,
+    pub frozen_by: Option<Uuid>,
+}
+
+impl AccountFreezeState {
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_at.is_some()
+    }
+}
+
+/// Freeze account request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct FreezeAccountRequest {
+    pub reason: FreezeReason,
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+/// Freeze state response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountFreezeResponse {
+    pub account_id: Uuid,
+    pub is_frozen: bool,
+    pub freeze_reason: Option<FreezeReason>,
+    pub freeze_note: Option<String>,
+    pub frozen_by: Option<Uuid>,
+    pub frozen_at: Option<DateTime<Utc>>,
+}
+
+impl From<AccountFreezeState> for AccountFreezeResponse {
+    fn from(state: AccountFreezeState) -> Self {
+        Self {
+            account_id: state.id,
+            is_frozen: state.is_frozen(),
+            freeze_reason: state.freeze_reason,
+            freeze_note: state.freeze_note,
+            frozen_by: state.frozen_by,
+            frozen_at: state.frozen_at,
+        }
+    }
+}
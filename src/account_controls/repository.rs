@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use super::model::{AccountFreezeState, AccountKind, FreezeReason};
+
+const FREEZE_COLUMNS: &str = "id, frozen_at, freeze_reason, freeze_note, frozen_by";
+
+pub struct AccountControlRepository {
+    pool: PgPool,
+}
+
+impl AccountControlRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the freeze state of an account or virtual account
+    pub async fn find_freeze_state(
+        &self,
+        kind: AccountKind,
+        id: Uuid,
+    ) -> AppResult<Option<AccountFreezeState>> {
+        let state = sqlx::query_as::<_, AccountFreezeState>(&format!(
+            "SELECT {FREEZE_COLUMNS} FROM {} WHERE id = $1",
+            kind.table()
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(state)
+    }
+
+    /// When an account was closed, if it has been
+    pub async fn find_closed_at(&self, account_id: Uuid) -> AppResult<Option<DateTime<Utc>>> {
+        let closed_at = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT closed_at FROM accounts WHERE id = $1",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .flatten();
+
+        Ok(closed_at)
+    }
+
+    /// User who owns an account or virtual account
+    pub async fn find_owner(&self, kind: AccountKind, id: Uuid) -> AppResult<Option<Uuid>> {
+        let owner = sqlx::query_scalar(&format!("SELECT user_id FROM {} WHERE id = $1", kind.table()))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(owner)
+    }
+
+    /// Mark an account as frozen
+    pub async fn freeze(
+        &self,
+        kind: AccountKind,
+        id: Uuid,
+        reason: FreezeReason,
+        note: Option<String>,
+        actor_id: Uuid,
+    ) -> AppResult<AccountFreezeState> {
+        let state = sqlx::query_as::<_, AccountFreezeState>(&format!(
+            "UPDATE {}
+             SET frozen_at = NOW(), freeze_reason = $1, freeze_note = $2, frozen_by = $3, updated_at = NOW()
+             WHERE id = $4
+             RETURNING {FREEZE_COLUMNS}",
+            kind.table()
+        ))
+        .bind(reason)
+        .bind(note)
+        .bind(actor_id)
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(state)
+    }
+
+    /// Clear an account's freeze
+    pub async fn unfreeze(&self, kind: AccountKind, id: Uuid) -> AppResult<AccountFreezeState> {
+        let state = sqlx::query_as::<_, AccountFreezeState>(&format!(
+            "UPDATE {}
+             SET frozen_at = NULL, freeze_reason = NULL, freeze_note = NULL, frozen_by = NULL, updated_at = NOW()
+             WHERE id = $1
+             RETURNING {FREEZE_COLUMNS}",
+            kind.table()
+        ))
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(state)
+    }
+}
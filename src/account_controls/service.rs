@@ -0,0 +1,160 @@
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::notifications::service::NotificationService;
+use crate::shared::types::AccountId;
+use super::model::{AccountFreezeResponse, AccountFreezeState, AccountKind, FreezeAccountRequest, FreezeReason};
+use super::repository::AccountControlRepository;
+
+pub struct AccountControlService {
+    repository: AccountControlRepository,
+    notifications: NotificationService,
+    audit_logger: AuditLogger,
+}
+
+impl AccountControlService {
+    pub fn new(
+        repository: AccountControlRepository,
+        notifications: NotificationService,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            notifications,
+            audit_logger,
+        }
+    }
+
+    /// Get the freeze state of an account
+    pub async fn get_freeze_state(&self, kind: AccountKind, id: Uuid) -> AppResult<AccountFreezeResponse> {
+        let state = find_state(&self.repository, kind, id).await?;
+        Ok(AccountFreezeResponse::from(state))
+    }
+
+    /// Freeze an account so it can no longer be debited. The owner of an
+    /// account frozen for suspected fraud is sent a fraud alert.
+    pub async fn freeze(
+        &self,
+        kind: AccountKind,
+        id: Uuid,
+        request: FreezeAccountRequest,
+        actor_id: Uuid,
+    ) -> AppResult<AccountFreezeResponse> {
+        let current = find_state(&self.repository, kind, id).await?;
+        if current.is_frozen() {
+            return Err(AppError::Conflict("Account is already frozen".to_string()));
+        }
+
+        let state = self
+            .repository
+            .freeze(kind, id, request.reason, request.note, actor_id)
+            .await?;
+
+        let event = AuditEvent::new(AuditEventType::AccountFrozen)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("{}:{}", kind.label(), id))
+            .action("freeze".to_string())
+            .metadata("reason".to_string(), serde_json::json!(state.freeze_reason))
+            .compliance_tag("ACCOUNT_CONTROLS".to_string());
+        self.audit_logger.log(event).await;
+
+        if state.freeze_reason == Some(FreezeReason::SuspectedFraud) {
+            // The freeze stands even if the alert cannot be delivered
+            if let Err(e) = self.send_fraud_alert(kind, id).await {
+                tracing::error!("Failed to send fraud alert for {} {}: {}", kind.label(), id, e);
+            }
+        }
+
+        Ok(AccountFreezeResponse::from(state))
+    }
+
+    async fn send_fraud_alert(&self, kind: AccountKind, id: Uuid) -> AppResult<()> {
+        if let Some(owner) = self.repository.find_owner(kind, id).await? {
+            self.notifications.notify_fraud_alert(owner, id).await?;
+        }
+        Ok(())
+    }
+
+    /// Lift a freeze from an account
+    pub async fn unfreeze(&self, kind: AccountKind, id: Uuid, actor_id: Uuid) -> AppResult<AccountFreezeResponse> {
+        let current = find_state(&self.repository, kind, id).await?;
+        if !current.is_frozen() {
+            return Err(AppError::Conflict("Account is not frozen".to_string()));
+        }
+
+        let state = self.repository.unfreeze(kind, id).await?;
+
+        let event = AuditEvent::new(AuditEventType::AccountUnfrozen)
+            .user_id(actor_id)
+            .resource(format!("{}:{}", kind.label(), id))
+            .action("unfreeze".to_string())
+            .metadata("previous_reason".to_string(), serde_json::json!(current.freeze_reason))
+            .compliance_tag("ACCOUNT_CONTROLS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(AccountFreezeResponse::from(state))
+    }
+}
+
+/// Enforces account freezes and closures on money movement
+pub struct AccountFreezeGuard {
+    repository: AccountControlRepository,
+    allow_credits: bool,
+}
+
+impl AccountFreezeGuard {
+    pub fn new(repository: AccountControlRepository, allow_credits: bool) -> Self {
+        Self {
+            repository,
+            allow_credits,
+        }
+    }
+
+    /// Reject debits from a frozen or closed account
+    pub async fn ensure_can_debit(&self, account_id: AccountId) -> AppResult<()> {
+        let state = find_state(&self.repository, AccountKind::Account, account_id).await?;
+        self.ensure_open(account_id).await?;
+        if state.is_frozen() {
+            return Err(AppError::BadRequest(format!(
+                "Account {} is frozen and cannot be debited",
+                account_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject credits to a closed account, and to a frozen one unless
+    /// configured to allow them
+    pub async fn ensure_can_credit(&self, kind: AccountKind, account_id: Uuid) -> AppResult<()> {
+        let state = find_state(&self.repository, kind, account_id).await?;
+        if kind == AccountKind::Account {
+            self.ensure_open(account_id).await?;
+        }
+        if state.is_frozen() && !self.allow_credits {
+            return Err(AppError::BadRequest(format!(
+                "Account {} is frozen and cannot receive funds",
+                account_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn ensure_open(&self, account_id: AccountId) -> AppResult<()> {
+        if self.repository.find_closed_at(account_id).await?.is_some() {
+            return Err(AppError::BadRequest(format!("Account {} is closed", account_id)));
+        }
+        Ok(())
+    }
+}
+
+async fn find_state(
+    repository: &AccountControlRepository,
+    kind: AccountKind,
+    id: Uuid,
+) -> AppResult<AccountFreezeState> {
+    repository
+        .find_freeze_state(kind, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Account not found".to_string()))
+}
@@ -0,0 +1,125 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{
+    AccountNumberScheme, AccountNumberValidation, SetAccountNumberSchemeRequest, ValidateAccountNumberRequest,
+};
+use super::repository::AccountNumberRepository;
+use super::service::{default_scheme, AccountNumberSchemeService};
+
+fn account_number_scheme_service(state: &AppState) -> AccountNumberSchemeService {
+    AccountNumberSchemeService::new(
+        AccountNumberRepository::new(state.postgres.clone()),
+        default_scheme(&state.config),
+        state.audit_logger.clone(),
+    )
+}
+
+/// List the calling project's account number schemes
+#[utoipa::path(
+    get,
+    path = "/api/v1/account-numbers/schemes",
+    tag = "account-numbers",
+    responses(
+        (status = 200, description = "Account number schemes, the default first", body = [AccountNumberScheme])
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_account_number_schemes(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+) -> AppResult<Json<ApiResponse<Vec<AccountNumberScheme>>>> {
+    let schemes = account_number_scheme_service(&state)
+        .list_schemes(claims.project_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Account number schemes retrieved successfully", schemes)))
+}
+
+/// Set the scheme the calling project's new account numbers are issued in,
+/// for a currency or by default
+#[utoipa::path(
+    put,
+    path = "/api/v1/account-numbers/schemes",
+    tag = "account-numbers",
+    request_body = SetAccountNumberSchemeRequest,
+    responses(
+        (status = 200, description = "Account number scheme set", body = AccountNumberScheme),
+        (status = 400, description = "Invalid scheme")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_account_number_scheme(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<SetAccountNumberSchemeRequest>,
+) -> AppResult<Json<ApiResponse<AccountNumberScheme>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let scheme = account_number_scheme_service(&state)
+        .set_scheme(claims.project_id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Account number scheme set successfully", scheme)))
+}
+
+/// Remove one of the calling project's schemes, so its currency falls back
+/// to the project's default
+#[utoipa::path(
+    delete,
+    path = "/api/v1/account-numbers/schemes/{id}",
+    tag = "account-numbers",
+    params(("id" = Uuid, Path, description = "Account number scheme ID")),
+    responses(
+        (status = 200, description = "Account number scheme deleted"),
+        (status = 404, description = "Account number scheme not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_account_number_scheme(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    account_number_scheme_service(&state)
+        .delete_scheme(id, claims.project_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Account number scheme deleted successfully", ())))
+}
+
+/// Check an account number against the calling project's scheme
+#[utoipa::path(
+    post,
+    path = "/api/v1/account-numbers/validate",
+    tag = "account-numbers",
+    request_body = ValidateAccountNumberRequest,
+    responses(
+        (status = 200, description = "Validation result", body = AccountNumberValidation),
+        (status = 400, description = "Invalid request")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn validate_account_number(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<ValidateAccountNumberRequest>,
+) -> AppResult<Json<ApiResponse<AccountNumberValidation>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let validation = account_number_scheme_service(&state)
+        .validate(claims.project_id, request)
+        .await?;
+    Ok(Json(ApiResponse::success("Account number validated successfully", validation)))
+}
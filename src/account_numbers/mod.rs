@@ -0,0 +1,18 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod scheme;
+pub mod service;
+
+use axum::{routing::{delete, get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/schemes",
+            get(controller::get_account_number_schemes).put(controller::set_account_number_scheme),
+        )
+        .route("/schemes/:id", delete(controller::delete_account_number_scheme))
+        .route("/validate", post(controller::validate_account_number))
+}
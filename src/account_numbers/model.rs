@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::Currency;
+
+/// Most attempts at a number that is not already taken before giving up
+pub const MAX_GENERATION_ATTEMPTS: usize = 10;
+
+/// How a scheme lays out account numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "account_number_scheme_kind", rename_all = "snake_case")]
+pub enum SchemeKind {
+    /// A fixed prefix, then `length` digits ending in a Luhn check digit
+    PrefixCheckDigit,
+    /// `length` digits starting with an issuer number between `bin_start`
+    /// and `bin_end`, ending in a Luhn check digit
+    BinRange,
+    /// An IBAN for `country_code` under `bank_code`
+    Iban,
+}
+
+/// The settings a scheme is built from, as stored or requested
+#[derive(Debug, Clone)]
+pub struct SchemeSettings {
+    pub kind: SchemeKind,
+    pub prefix: Option<String>,
+    pub bin_start: Option<String>,
+    pub bin_end: Option<String>,
+    pub country_code: Option<String>,
+    pub bank_code: Option<String>,
+    pub length: Option<i32>,
+}
+
+/// A project's account number scheme, for one currency or as its default
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AccountNumberScheme {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Currency the scheme applies to; the project's default when absent
+    pub currency: Option<Currency>,
+    pub kind: SchemeKind,
+    pub prefix: Option<String>,
+    pub bin_start: Option<String>,
+    pub bin_end: Option<String>,
+    pub country_code: Option<String>,
+    pub bank_code: Option<String>,
+    pub length: Option<i32>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AccountNumberScheme {
+    pub fn settings(&self) -> SchemeSettings {
+        SchemeSettings {
+            kind: self.kind,
+            prefix: self.prefix.clone(),
+            bin_start: self.bin_start.clone(),
+            bin_end: self.bin_end.clone(),
+            country_code: self.country_code.clone(),
+            bank_code: self.bank_code.clone(),
+            length: self.length,
+        }
+    }
+}
+
+/// Choose the scheme for a currency, or the project's default scheme.
+/// Only the fields the kind uses are read.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct SetAccountNumberSchemeRequest {
+    #[validate(length(equal = 3))]
+    pub currency: Option<Currency>,
+    pub kind: SchemeKind,
+    #[validate(length(max = 6))]
+    pub prefix: Option<String>,
+    pub bin_start: Option<String>,
+    pub bin_end: Option<String>,
+    #[validate(length(equal = 2))]
+    pub country_code: Option<String>,
+    pub bank_code: Option<String>,
+    #[validate(range(min = 1, max = 34))]
+    pub length: Option<i32>,
+}
+
+impl SetAccountNumberSchemeRequest {
+    pub fn settings(&self) -> SchemeSettings {
+        SchemeSettings {
+            kind: self.kind,
+            prefix: self.prefix.clone(),
+            bin_start: self.bin_start.clone(),
+            bin_end: self.bin_end.clone(),
+            country_code: self.country_code.clone(),
+            bank_code: self.bank_code.clone(),
+            length: self.length,
+        }
+    }
+}
+
+/// Check an account number against the project's scheme
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct ValidateAccountNumberRequest {
+    #[validate(length(min = 1, max = 64))]
+    pub account_number: String,
+    /// Currency whose scheme to check against; the default scheme otherwise
+    #[validate(length(equal = 3))]
+    pub currency: Option<Currency>,
+}
+
+/// Whether an account number fits the project's scheme, and if not why
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccountNumberValidation {
+    /// The number without spaces or dashes, upper-cased
+    pub account_number: String,
+    pub valid: bool,
+    /// Scheme the number was checked against
+    pub kind: SchemeKind,
+    /// For IBANs, the country and bank they were issued under
+    pub country_code: Option<String>,
+    pub bank_code: Option<String>,
+    pub issues: Vec<String>,
+    /// Whether an account already holds the number
+    pub in_use: bool,
+}
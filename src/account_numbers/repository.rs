@@ -0,0 +1,113 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use super::model::AccountNumberScheme;
+
+const SCHEME_COLUMNS: &str = "id, project_id, currency, kind, prefix, bin_start, bin_end, country_code, bank_code,
+    length, created_by, created_at, updated_at";
+
+pub struct AccountNumberRepository {
+    pool: PgPool,
+}
+
+impl AccountNumberRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_schemes(&self, project_id: Uuid) -> AppResult<Vec<AccountNumberScheme>> {
+        let schemes = sqlx::query_as::<_, AccountNumberScheme>(&format!(
+            "SELECT {SCHEME_COLUMNS} FROM account_number_schemes
+             WHERE project_id = $1
+             ORDER BY currency NULLS FIRST"
+        ))
+        .bind(project_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(schemes)
+    }
+
+    /// The scheme for a currency, falling back to the project's default
+    pub async fn find_applicable_scheme(
+        &self,
+        project_id: Uuid,
+        currency: Option<&str>,
+    ) -> AppResult<Option<AccountNumberScheme>> {
+        let scheme = sqlx::query_as::<_, AccountNumberScheme>(&format!(
+            "SELECT {SCHEME_COLUMNS} FROM account_number_schemes
+             WHERE project_id = $1 AND (currency IS NULL OR currency = $2)
+             ORDER BY currency NULLS LAST
+             LIMIT 1"
+        ))
+        .bind(project_id)
+        .bind(currency)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(scheme)
+    }
+
+    /// Create the scheme for its project and currency, or replace it
+    pub async fn upsert_scheme(&self, scheme: &AccountNumberScheme) -> AppResult<AccountNumberScheme> {
+        let saved = sqlx::query_as::<_, AccountNumberScheme>(&format!(
+            "INSERT INTO account_number_schemes ({SCHEME_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             ON CONFLICT (project_id, COALESCE(currency, '')) DO UPDATE SET
+                 kind = EXCLUDED.kind,
+                 prefix = EXCLUDED.prefix,
+                 bin_start = EXCLUDED.bin_start,
+                 bin_end = EXCLUDED.bin_end,
+                 country_code = EXCLUDED.country_code,
+                 bank_code = EXCLUDED.bank_code,
+                 length = EXCLUDED.length,
+                 created_by = EXCLUDED.created_by,
+                 updated_at = EXCLUDED.updated_at
+             RETURNING {SCHEME_COLUMNS}"
+        ))
+        .bind(scheme.id)
+        .bind(scheme.project_id)
+        .bind(&scheme.currency)
+        .bind(scheme.kind)
+        .bind(&scheme.prefix)
+        .bind(&scheme.bin_start)
+        .bind(&scheme.bin_end)
+        .bind(&scheme.country_code)
+        .bind(&scheme.bank_code)
+        .bind(scheme.length)
+        .bind(scheme.created_by)
+        .bind(scheme.created_at)
+        .bind(scheme.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(saved)
+    }
+
+    /// Remove a project's scheme, returning it if it existed
+    pub async fn delete_scheme(&self, id: Uuid, project_id: Uuid) -> AppResult<Option<AccountNumberScheme>> {
+        let deleted = sqlx::query_as::<_, AccountNumberScheme>(&format!(
+            "DELETE FROM account_number_schemes WHERE id = $1 AND project_id = $2
+             RETURNING {SCHEME_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(deleted)
+    }
+
+    /// Whether an account or virtual account already holds the number
+    pub async fn number_in_use(&self, account_number: &str) -> AppResult<bool> {
+        let in_use: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM accounts WHERE account_number = $1)
+                 OR EXISTS (SELECT 1 FROM virtual_accounts WHERE account_number = $1)",
+        )
+        .bind(account_number)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(in_use)
+    }
+}
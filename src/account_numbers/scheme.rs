@@ -0,0 +1,283 @@
+use rand::Rng;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::bank_details::{iban_check_digits, iban_country, normalize, validate_iban, IbanCountry, IBAN_COUNTRIES};
+use super::model::{SchemeKind, SchemeSettings};
+
+/// Longest number the account tables hold
+pub const MAX_ACCOUNT_NUMBER_LENGTH: usize = 34;
+
+/// How account numbers are laid out for a project and currency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumberScheme {
+    /// A fixed prefix, then digits ending in a Luhn check digit
+    PrefixCheckDigit { prefix: String, length: usize },
+    /// Digits starting with an issuer number from a bank's range and ending
+    /// in a Luhn check digit
+    BinRange { bin_start: u64, bin_end: u64, bin_length: usize, length: usize },
+    /// An IBAN for the country, under one bank code
+    Iban { country_code: String, bank_code: String },
+}
+
+impl NumberScheme {
+    /// Build a scheme from stored or requested settings, rejecting layouts
+    /// that cannot produce valid numbers
+    pub fn from_settings(settings: &SchemeSettings) -> AppResult<Self> {
+        let invalid = |message: &str| AppError::Validation(message.to_string());
+        let length = settings.length.map(|length| length.max(0) as usize);
+
+        match settings.kind {
+            SchemeKind::PrefixCheckDigit => {
+                let prefix = settings.prefix.as_deref().unwrap_or_default().trim().to_ascii_uppercase();
+                if prefix.len() > 6 || !prefix.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err(invalid("Prefix must be up to 6 letters or digits"));
+                }
+                let length = length.ok_or_else(|| invalid("A prefix scheme needs a length"))?;
+                if length < 6 || prefix.len() + length > MAX_ACCOUNT_NUMBER_LENGTH {
+                    return Err(invalid("Length must be at least 6 digits and fit the account number"));
+                }
+                Ok(NumberScheme::PrefixCheckDigit { prefix, length })
+            }
+            SchemeKind::BinRange => {
+                let (Some(start), Some(end)) = (settings.bin_start.as_deref(), settings.bin_end.as_deref()) else {
+                    return Err(invalid("A BIN range scheme needs bin_start and bin_end"));
+                };
+                let is_bin = |bin: &str| (4..=8).contains(&bin.len()) && bin.chars().all(|c| c.is_ascii_digit());
+                if !is_bin(start) || !is_bin(end) || start.len() != end.len() {
+                    return Err(invalid("BIN range bounds must be 4 to 8 digits of equal length"));
+                }
+                let (bin_start, bin_end) = (start.parse::<u64>().unwrap(), end.parse::<u64>().unwrap());
+                if bin_start > bin_end {
+                    return Err(invalid("bin_start must not be above bin_end"));
+                }
+                let bin_length = start.len();
+                let length = length.ok_or_else(|| invalid("A BIN range scheme needs a length"))?;
+                if length < bin_length + 2 || length > 19 {
+                    return Err(invalid("Length must leave room after the BIN and be at most 19 digits"));
+                }
+                Ok(NumberScheme::BinRange { bin_start, bin_end, bin_length, length })
+            }
+            SchemeKind::Iban => {
+                let country_code = settings.country_code.as_deref().unwrap_or_default().trim().to_ascii_uppercase();
+                let country = iban_country(&country_code).ok_or_else(|| {
+                    let supported: Vec<_> = IBAN_COUNTRIES.iter().map(|country| country.code).collect();
+                    AppError::Validation(format!(
+                        "IBANs can be issued for {} only",
+                        supported.join(", ")
+                    ))
+                })?;
+                let bank_code = settings.bank_code.as_deref().unwrap_or_default().trim().to_ascii_uppercase();
+                if !bank_code_matches(country, &bank_code) {
+                    return Err(AppError::Validation(format!(
+                        "{} bank codes are {} {}",
+                        country.code,
+                        country.bank_code_length,
+                        if country.bank_code_alphabetic { "letters" } else { "digits" }
+                    )));
+                }
+                Ok(NumberScheme::Iban { country_code, bank_code })
+            }
+        }
+    }
+
+    pub fn kind(&self) -> SchemeKind {
+        match self {
+            NumberScheme::PrefixCheckDigit { .. } => SchemeKind::PrefixCheckDigit,
+            NumberScheme::BinRange { .. } => SchemeKind::BinRange,
+            NumberScheme::Iban { .. } => SchemeKind::Iban,
+        }
+    }
+
+    /// A new random number in this scheme
+    pub fn generate<R: Rng>(&self, rng: &mut R) -> String {
+        match self {
+            NumberScheme::PrefixCheckDigit { prefix, length } => {
+                format!("{}{}", prefix, with_check_digit(random_digits(rng, length - 1)))
+            }
+            NumberScheme::BinRange { bin_start, bin_end, bin_length, length } => {
+                let bin = format!("{:0width$}", rng.gen_range(*bin_start..=*bin_end), width = bin_length);
+                with_check_digit(format!("{}{}", bin, random_digits(rng, length - bin_length - 1)))
+            }
+            NumberScheme::Iban { country_code, bank_code } => {
+                let country = iban_country(country_code).expect("scheme countries are supported");
+                let account = random_digits(rng, country.length - 4 - bank_code.len());
+                let bban = format!("{}{}", bank_code, account);
+                format!("{}{}{}", country_code, iban_check_digits(country_code, &bban), bban)
+            }
+        }
+    }
+
+    /// Reasons `number` does not fit this scheme; empty when it does
+    pub fn validate(&self, number: &str) -> Vec<String> {
+        let number = normalize(number);
+        match self {
+            NumberScheme::PrefixCheckDigit { prefix, length } => {
+                let Some(digits) = number.strip_prefix(prefix.as_str()) else {
+                    return vec![format!("Account numbers start with '{}'", prefix)];
+                };
+                check_digits(digits, *length)
+            }
+            NumberScheme::BinRange { bin_start, bin_end, bin_length, length } => {
+                let mut issues = check_digits(&number, *length);
+                if issues.is_empty() {
+                    let bin: u64 = number[..*bin_length].parse().unwrap_or_default();
+                    if bin < *bin_start || bin > *bin_end {
+                        issues.push("Issuer number is outside the bank's range".to_string());
+                    }
+                }
+                issues
+            }
+            NumberScheme::Iban { country_code, bank_code } => {
+                let mut issues = validate_iban(&number);
+                if issues.is_empty() {
+                    if !number.starts_with(country_code.as_str()) {
+                        issues.push(format!("IBAN is not a {} IBAN", country_code));
+                    } else if !number[4..].starts_with(bank_code.as_str()) {
+                        issues.push(format!("IBAN is not for bank {}", bank_code));
+                    }
+                }
+                issues
+            }
+        }
+    }
+}
+
+fn bank_code_matches(country: &IbanCountry, bank_code: &str) -> bool {
+    bank_code.len() == country.bank_code_length
+        && bank_code.chars().all(|c| {
+            if country.bank_code_alphabetic {
+                c.is_ascii_uppercase()
+            } else {
+                c.is_ascii_digit()
+            }
+        })
+}
+
+fn check_digits(digits: &str, length: usize) -> Vec<String> {
+    if digits.len() != length || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return vec![format!("Account numbers have {} digits", length)];
+    }
+    if !luhn_valid(digits) {
+        return vec!["Check digit does not match".to_string()];
+    }
+    Vec::new()
+}
+
+fn random_digits<R: Rng>(rng: &mut R, count: usize) -> String {
+    (0..count).map(|_| char::from(b'0' + rng.gen_range(0..10))).collect()
+}
+
+fn with_check_digit(digits: String) -> String {
+    let check = luhn_check_digit(&digits);
+    format!("{}{}", digits, check)
+}
+
+/// The Luhn digit to append to `digits`
+pub fn luhn_check_digit(digits: &str) -> u32 {
+    (10 - luhn_sum(digits, true) % 10) % 10
+}
+
+/// Whether `digits` ends in a valid Luhn check digit
+pub fn luhn_valid(digits: &str) -> bool {
+    luhn_sum(digits, false).is_multiple_of(10)
+}
+
+/// Luhn sum, doubling every second digit from the right; `pending` when
+/// the check digit is still to be appended
+fn luhn_sum(digits: &str, pending: bool) -> u32 {
+    digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, digit)| {
+            if (i % 2 == 0) == pending {
+                let doubled = digit * 2;
+                if doubled > 9 { doubled - 9 } else { doubled }
+            } else {
+                digit
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(kind: SchemeKind) -> SchemeSettings {
+        SchemeSettings {
+            kind,
+            prefix: None,
+            bin_start: None,
+            bin_end: None,
+            country_code: None,
+            bank_code: None,
+            length: None,
+        }
+    }
+
+    #[test]
+    fn luhn_digits_round_trip() {
+        assert_eq!(luhn_check_digit("7992739871"), 3);
+        assert!(luhn_valid("79927398713"));
+        assert!(!luhn_valid("79927398714"));
+    }
+
+    #[test]
+    fn generated_numbers_validate_against_their_scheme() {
+        let mut rng = rand::thread_rng();
+        let schemes = [
+            SchemeSettings { prefix: Some("va".to_string()), length: Some(10), ..settings(SchemeKind::PrefixCheckDigit) },
+            SchemeSettings {
+                bin_start: Some("400000".to_string()),
+                bin_end: Some("400099".to_string()),
+                length: Some(16),
+                ..settings(SchemeKind::BinRange)
+            },
+            SchemeSettings {
+                country_code: Some("GB".to_string()),
+                bank_code: Some("OPEN".to_string()),
+                ..settings(SchemeKind::Iban)
+            },
+        ];
+
+        for settings in &schemes {
+            let scheme = NumberScheme::from_settings(settings).unwrap();
+            for _ in 0..20 {
+                let number = scheme.generate(&mut rng);
+                assert!(scheme.validate(&number).is_empty(), "{} should fit {:?}", number, scheme);
+            }
+        }
+
+        let prefix = NumberScheme::from_settings(&schemes[0]).unwrap();
+        assert!(!prefix.validate("XX123456789").is_empty());
+        let iban = NumberScheme::from_settings(&schemes[2]).unwrap();
+        assert!(!iban.validate("GB82WEST12345698765432").is_empty());
+    }
+
+    #[test]
+    fn unusable_settings_are_rejected() {
+        let bad = [
+            SchemeSettings { prefix: Some("TOOLONGX".to_string()), length: Some(10), ..settings(SchemeKind::PrefixCheckDigit) },
+            SchemeSettings {
+                bin_start: Some("4999".to_string()),
+                bin_end: Some("4000".to_string()),
+                length: Some(16),
+                ..settings(SchemeKind::BinRange)
+            },
+            SchemeSettings {
+                country_code: Some("FR".to_string()),
+                bank_code: Some("30006".to_string()),
+                ..settings(SchemeKind::Iban)
+            },
+            SchemeSettings {
+                country_code: Some("DE".to_string()),
+                bank_code: Some("WEST".to_string()),
+                ..settings(SchemeKind::Iban)
+            },
+        ];
+        for settings in &bad {
+            assert!(NumberScheme::from_settings(settings).is_err(), "{:?} should be rejected", settings);
+        }
+    }
+}
@@ -1,15 +1,18 @@
+use super::login_history::LoginContext;
 use super::model::*;
 use super::service::AuthService;
 use crate::core::error::AppError;
-use crate::core::extractors::ApiJson;
+use crate::core::extractors::{ApiJson, ClientIp};
 use crate::core::response::ApiResponse;
+use crate::shared::types::{PaginatedResponse, PaginationParams};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
     response::Json,
     routing::{get, post},
     Router,
 };
+use uuid::Uuid;
 use validator::Validate;
 
 pub fn routes(auth_service: AuthService) -> Router {
@@ -17,21 +20,32 @@ pub fn routes(auth_service: AuthService) -> Router {
         .route("/developers", post(register_developer))
         .route("/token", post(oauth_token))
         .route("/token/refresh", post(refresh_token))
+        .route("/users/login-code", post(request_login_code))
         .route("/developers/:developer_id/projects", post(create_project))
         .route("/me", get(get_me))
+        .route("/me/logins", get(list_logins))
+        .route("/me/logins/:event_id/report", post(report_login))
         .route("/scopes", get(get_available_scopes))
         .with_state(auth_service)
 }
 
+/// Register a developer account
+#[utoipa::path(
+    post,
+    path = "/auth/developers",
+    tag = "auth",
+    request_body = RegisterDeveloperRequest,
+    responses(
+        (status = 201, description = "Developer registered", body = DeveloperResponse),
+        (status = 400, description = "Invalid request or email already registered")
+    )
+)]
 pub async fn register_developer(
     State(service): State<AuthService>,
     ApiJson(request): ApiJson<RegisterDeveloperRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<DeveloperResponse>>), AppError> {
     if let Err(validation_errors) = request.validate() {
-        return Err(AppError::Validation(format!(
-            "Invalid request data: {:?}",
-            validation_errors
-        )));
+        return Err(AppError::InvalidFields(validation_errors));
     }
 
     match service.register_developer(request).await {
@@ -46,18 +60,31 @@ pub async fn register_developer(
     }
 }
 
+/// Exchange project credentials for an access token (client credentials
+/// grant), or for a token acting for an end user (password and otp grants)
+#[utoipa::path(
+    post,
+    path = "/auth/token",
+    tag = "auth",
+    request_body = TokenRequest,
+    responses(
+        (status = 200, description = "Access token issued", body = TokenResponse),
+        (status = 400, description = "Invalid grant, missing credentials or scopes"),
+        (status = 401, description = "Invalid client or user credentials")
+    )
+)]
 pub async fn oauth_token(
     State(service): State<AuthService>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
     ApiJson(request): ApiJson<TokenRequest>,
 ) -> Result<Json<ApiResponse<TokenResponse>>, AppError> {
     if let Err(validation_errors) = request.validate() {
-        return Err(AppError::Validation(format!(
-            "Invalid request data: {:?}",
-            validation_errors
-        )));
+        return Err(AppError::InvalidFields(validation_errors));
     }
 
-    match service.handle_client_credentials_flow(request).await {
+    let context = LoginContext::from_headers(ip, &headers);
+    match service.issue_token(request, &context).await {
         Ok(token) => Ok(Json(ApiResponse::success(
             "Access token generated successfully",
             token,
@@ -66,18 +93,29 @@ pub async fn oauth_token(
     }
 }
 
+/// Refresh an access token
+#[utoipa::path(
+    post,
+    path = "/auth/token/refresh",
+    tag = "auth",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Access token refreshed", body = TokenResponse),
+        (status = 401, description = "Invalid client credentials or token")
+    )
+)]
 pub async fn refresh_token(
     State(service): State<AuthService>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
     ApiJson(request): ApiJson<RefreshTokenRequest>,
 ) -> Result<Json<ApiResponse<TokenResponse>>, AppError> {
     if let Err(validation_errors) = request.validate() {
-        return Err(AppError::Validation(format!(
-            "Invalid request data: {:?}",
-            validation_errors
-        )));
+        return Err(AppError::InvalidFields(validation_errors));
     }
 
-    match service.refresh_access_token(request).await {
+    let context = LoginContext::from_headers(ip, &headers);
+    match service.refresh_access_token(request, &context).await {
         Ok(token) => Ok(Json(ApiResponse::success(
             "Access token refreshed successfully",
             token,
@@ -86,16 +124,53 @@ pub async fn refresh_token(
     }
 }
 
+/// Email an end user a one-time code for the otp grant. The answer is the
+/// same whether or not the user exists.
+#[utoipa::path(
+    post,
+    path = "/auth/users/login-code",
+    tag = "auth",
+    request_body = LoginCodeRequest,
+    responses(
+        (status = 200, description = "Login code sent if the user exists"),
+        (status = 401, description = "Invalid client credentials")
+    )
+)]
+pub async fn request_login_code(
+    State(service): State<AuthService>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+    ApiJson(request): ApiJson<LoginCodeRequest>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let context = LoginContext::from_headers(ip, &headers);
+    service.request_login_code(request, &context).await?;
+    Ok(Json(ApiResponse::success("Login code sent", ())))
+}
+
+/// Create a project and its client credentials
+#[utoipa::path(
+    post,
+    path = "/auth/developers/{developer_id}/projects",
+    tag = "auth",
+    params(("developer_id" = Uuid, Path, description = "Developer ID")),
+    request_body = CreateProjectRequest,
+    responses(
+        (status = 201, description = "Project created; client_id carries the client secret once", body = ProjectResponse),
+        (status = 400, description = "Invalid request or scopes"),
+        (status = 403, description = "Not an owner or admin of the organization")
+    )
+)]
 pub async fn create_project(
     State(service): State<AuthService>,
-    Path(developer_id): Path<uuid::Uuid>,
+    Path(developer_id): Path<Uuid>,
     ApiJson(request): ApiJson<CreateProjectRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<ProjectResponse>>), AppError> {
     if let Err(validation_errors) = request.validate() {
-        return Err(AppError::Validation(format!(
-            "Invalid request data: {:?}",
-            validation_errors
-        )));
+        return Err(AppError::InvalidFields(validation_errors));
     }
 
     match service.create_project(developer_id, request).await {
@@ -110,26 +185,22 @@ pub async fn create_project(
     }
 }
 
+/// Describe the caller's access token
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    tag = "auth",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Token details", body = MeResponse),
+        (status = 401, description = "Missing, invalid or expired token")
+    )
+)]
 pub async fn get_me(
     State(service): State<AuthService>,
-    headers: axum::http::HeaderMap,
+    headers: HeaderMap,
 ) -> Result<Json<ApiResponse<MeResponse>>, AppError> {
-    // Extract Authorization header
-    let auth_header = headers
-        .get("authorization")
-        .ok_or_else(|| AppError::Authentication("Missing Authorization header".to_string()))?
-        .to_str()
-        .map_err(|_| AppError::Authentication("Invalid Authorization header".to_string()))?;
-
-    // Check if it starts with "Bearer "
-    if !auth_header.starts_with("Bearer ") {
-        return Err(AppError::Authentication(
-            "Authorization header must start with 'Bearer '".to_string(),
-        ));
-    }
-
-    // Extract the token
-    let token = auth_header.strip_prefix("Bearer ").unwrap();
+    let token = bearer_token(&headers)?;
 
     // Verify the token using the service
     match service.verify_access_token(token).await {
@@ -141,6 +212,87 @@ pub async fn get_me(
     }
 }
 
+/// The caller's recent logins with their projects' credentials, successful
+/// and refused, newest first
+#[utoipa::path(
+    get,
+    path = "/auth/me/logins",
+    tag = "auth",
+    params(PaginationParams),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Page of logins", body = PaginatedLogins),
+        (status = 401, description = "Missing, invalid or expired token")
+    )
+)]
+pub async fn list_logins(
+    State(service): State<AuthService>,
+    headers: HeaderMap,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<ApiResponse<PaginatedResponse<LoginRecord>>>, AppError> {
+    let me = service.verify_access_token(bearer_token(&headers)?).await?;
+
+    let logins = service
+        .login_history(me.developer_id, pagination.page, pagination.limit)
+        .await?;
+    Ok(Json(ApiResponse::success("Logins retrieved successfully", logins)))
+}
+
+/// Report one of the caller's logins as not theirs, alerting the security
+/// team
+#[utoipa::path(
+    post,
+    path = "/auth/me/logins/{event_id}/report",
+    tag = "auth",
+    params(("event_id" = Uuid, Path, description = "Login ID")),
+    request_body = ReportLoginRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Login reported", body = LoginReport),
+        (status = 401, description = "Missing, invalid or expired token"),
+        (status = 404, description = "Login not found"),
+        (status = 409, description = "The login was already reported")
+    )
+)]
+pub async fn report_login(
+    State(service): State<AuthService>,
+    ClientIp(ip): ClientIp,
+    headers: HeaderMap,
+    Path(event_id): Path<Uuid>,
+    ApiJson(request): ApiJson<ReportLoginRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<LoginReport>>), AppError> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let me = service.verify_access_token(bearer_token(&headers)?).await?;
+
+    let report = service.report_login(me.developer_id, event_id, request, ip).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Login reported, our security team has been alerted", report)),
+    ))
+}
+
+/// The bearer token of the Authorization header
+fn bearer_token(headers: &HeaderMap) -> Result<&str, AppError> {
+    let auth_header = headers
+        .get("authorization")
+        .ok_or_else(|| AppError::Authentication("Missing Authorization header".to_string()))?
+        .to_str()
+        .map_err(|_| AppError::Authentication("Invalid Authorization header".to_string()))?;
+
+    auth_header.strip_prefix("Bearer ").ok_or_else(|| {
+        AppError::Authentication("Authorization header must start with 'Bearer '".to_string())
+    })
+}
+
+/// List the scopes a project can request
+#[utoipa::path(
+    get,
+    path = "/auth/scopes",
+    tag = "auth",
+    responses((status = 200, description = "Available scopes", body = ScopesResponse))
+)]
 pub async fn get_available_scopes() -> Json<ApiResponse<ScopesResponse>> {
     use crate::auth::scopes;
 
@@ -0,0 +1,177 @@
+use axum::http::HeaderMap;
+use crate::core::audit::{AuditEvent, AuditEventType};
+use super::model::LoginRecord;
+
+/// Audit events that make up a developer's login history
+pub const LOGIN_EVENT_TYPES: [AuditEventType; 2] = [AuditEventType::LoginSuccess, AuditEventType::LoginFailure];
+
+/// Headers edge proxies put the client's country in, most specific first
+const COUNTRY_HEADERS: [&str; 3] = ["cf-ipcountry", "cloudfront-viewer-country", "x-country-code"];
+
+/// Where a login came from
+#[derive(Debug, Clone, Default)]
+pub struct LoginContext {
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+    /// ISO 3166 alpha-2 country, when the edge proxy geolocated the client
+    pub country: Option<String>,
+}
+
+impl LoginContext {
+    pub fn from_headers(ip_address: String, headers: &HeaderMap) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let country = COUNTRY_HEADERS
+            .iter()
+            .filter_map(|name| header(name))
+            .map(str::to_uppercase)
+            // Cloudflare sends XX for unknown and T1 for Tor
+            .find(|code| code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic()) && code != "XX");
+
+        Self {
+            ip_address,
+            user_agent: header("user-agent").map(str::to_string),
+            country,
+        }
+    }
+}
+
+/// A short description of the client behind a user agent, e.g. "Chrome on
+/// macOS" or "curl 8.4.0"
+pub fn describe_device(user_agent: &str) -> Option<String> {
+    let user_agent = user_agent.trim();
+    if user_agent.is_empty() {
+        return None;
+    }
+
+    // Tools and client libraries name themselves first, as name/version
+    const CLIENTS: [(&str, &str); 8] = [
+        ("curl/", "curl"),
+        ("wget/", "Wget"),
+        ("postmanruntime/", "Postman"),
+        ("insomnia/", "Insomnia"),
+        ("python-requests/", "Python requests"),
+        ("axios/", "axios"),
+        ("okhttp/", "OkHttp"),
+        ("go-http-client/", "Go HTTP client"),
+    ];
+    let lower = user_agent.to_lowercase();
+    for (prefix, name) in CLIENTS {
+        if let Some(rest) = lower.strip_prefix(prefix) {
+            let version = rest.split_whitespace().next().unwrap_or_default();
+            return Some(if version.is_empty() { name.to_string() } else { format!("{} {}", name, version) });
+        }
+    }
+
+    // Browsers: later entries are only checked when earlier ones miss, as
+    // Edge and Opera also claim to be Chrome, and Chrome to be Safari
+    const BROWSERS: [(&str, &str); 5] = [
+        ("edg/", "Edge"),
+        ("opr/", "Opera"),
+        ("firefox/", "Firefox"),
+        ("chrome/", "Chrome"),
+        ("safari/", "Safari"),
+    ];
+    const SYSTEMS: [(&str, &str); 6] = [
+        ("android", "Android"),
+        ("iphone", "iOS"),
+        ("ipad", "iPadOS"),
+        ("windows", "Windows"),
+        ("mac os x", "macOS"),
+        ("linux", "Linux"),
+    ];
+    let browser = BROWSERS.iter().find(|(marker, _)| lower.contains(marker)).map(|(_, name)| *name);
+    let system = SYSTEMS.iter().find(|(marker, _)| lower.contains(marker)).map(|(_, name)| *name);
+
+    match (browser, system) {
+        (Some(browser), Some(system)) => Some(format!("{} on {}", browser, system)),
+        (Some(browser), None) => Some(browser.to_string()),
+        (None, Some(system)) => Some(system.to_string()),
+        // Unknown clients are shown by their product token
+        (None, None) => user_agent.split_whitespace().next().map(str::to_string),
+    }
+}
+
+/// A login as listed to the developer
+pub fn login_record(event: &AuditEvent, reported: bool) -> LoginRecord {
+    let metadata_str = |key: &str| event.metadata.get(key).and_then(|value| value.as_str()).map(str::to_string);
+
+    LoginRecord {
+        id: event.id,
+        occurred_at: event.timestamp,
+        success: event.success,
+        failure_reason: event.error_message.clone(),
+        project_id: event.project_id,
+        ip_address: event.ip_address.clone(),
+        user_agent: event.user_agent.clone().filter(|agent| !agent.is_empty()),
+        country: metadata_str("country"),
+        device: event.user_agent.as_deref().and_then(describe_device),
+        reported,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use uuid::Uuid;
+
+    #[test]
+    fn describes_browsers_and_tools() {
+        let chrome = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 \
+                      (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+        let edge = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+                    (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.2210.91";
+        let safari = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 \
+                      (KHTML, like Gecko) Version/17.2 Mobile/15E148 Safari/604.1";
+
+        assert_eq!(describe_device(chrome).as_deref(), Some("Chrome on macOS"));
+        assert_eq!(describe_device(edge).as_deref(), Some("Edge on Windows"));
+        assert_eq!(describe_device(safari).as_deref(), Some("Safari on iOS"));
+        assert_eq!(describe_device("curl/8.4.0").as_deref(), Some("curl 8.4.0"));
+        assert_eq!(describe_device("python-requests/2.31.0").as_deref(), Some("Python requests 2.31.0"));
+        assert_eq!(describe_device("openbank-sdk/1.2").as_deref(), Some("openbank-sdk/1.2"));
+        assert_eq!(describe_device("  "), None);
+    }
+
+    #[test]
+    fn reads_the_country_from_edge_proxy_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("user-agent", HeaderValue::from_static("curl/8.4.0"));
+        headers.insert("cf-ipcountry", HeaderValue::from_static("XX"));
+        headers.insert("x-country-code", HeaderValue::from_static("gb"));
+
+        let context = LoginContext::from_headers("203.0.113.7".to_string(), &headers);
+        assert_eq!(context.country.as_deref(), Some("GB"));
+        assert_eq!(context.user_agent.as_deref(), Some("curl/8.4.0"));
+
+        let context = LoginContext::from_headers("203.0.113.7".to_string(), &HeaderMap::new());
+        assert_eq!(context.country, None);
+        assert_eq!(context.user_agent, None);
+    }
+
+    #[test]
+    fn lists_logins_from_audit_events() {
+        let project_id = Uuid::new_v4();
+        let event = AuditEvent::new(AuditEventType::LoginFailure)
+            .project_id(project_id)
+            .ip_address("203.0.113.7".to_string())
+            .user_agent("curl/8.4.0".to_string())
+            .error("Invalid client credentials".to_string())
+            .metadata("country".to_string(), serde_json::json!("GB"));
+
+        let record = login_record(&event, true);
+        assert_eq!(record.id, event.id);
+        assert!(!record.success);
+        assert_eq!(record.failure_reason.as_deref(), Some("Invalid client credentials"));
+        assert_eq!(record.project_id, Some(project_id));
+        assert_eq!(record.country.as_deref(), Some("GB"));
+        assert_eq!(record.device.as_deref(), Some("curl 8.4.0"));
+        assert!(record.reported);
+    }
+}
@@ -1,9 +1,9 @@
 use crate::auth::model::JwtClaims;
-use crate::core::error::AppError;
+use crate::core::{error::AppError, AppState};
 use axum::{
     async_trait,
-    extract::{FromRequestParts, Request},
-    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    extract::{FromRef, FromRequestParts, Request},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap, StatusCode},
     middleware::Next,
     response::Response,
 };
@@ -15,37 +15,53 @@ pub struct JwtToken(pub JwtClaims);
 #[async_trait]
 impl<S> FromRequestParts<S> for JwtToken
 where
+    AppState: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = AppError;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        // Extract Authorization header
-        let auth_header = parts
-            .headers
-            .get(AUTHORIZATION)
-            .ok_or_else(|| AppError::Authentication("Missing Authorization header".to_string()))?
-            .to_str()
-            .map_err(|_| AppError::Authentication("Invalid Authorization header".to_string()))?;
-
-        // Check if it starts with "Bearer "
-        if !auth_header.starts_with("Bearer ") {
-            return Err(AppError::Authentication(
-                "Authorization header must start with 'Bearer '".to_string(),
-            ));
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        // Claims may already have been decoded by jwt_auth_middleware
+        if let Some(claims) = parts.extensions.get::<JwtClaims>() {
+            return Ok(JwtToken(claims.clone()));
         }
 
-        // Extract the token
-        let _token = auth_header.strip_prefix("Bearer ").unwrap();
+        let app_state = AppState::from_ref(state);
+        let claims = decode_bearer_claims(&parts.headers, &app_state.config.jwt_secret)?;
 
-        // This is a placeholder - we need access to the JWT secret
-        // We'll handle this with dependency injection in the middleware
-        Err(AppError::Authentication(
-            "Token verification requires middleware setup".to_string(),
-        ))
+        parts.extensions.insert(claims.clone());
+        Ok(JwtToken(claims))
     }
 }
 
+/// Decode and validate the bearer token in the Authorization header
+pub fn decode_bearer_claims(headers: &HeaderMap, jwt_secret: &str) -> Result<JwtClaims, AppError> {
+    // Extract Authorization header
+    let auth_header = headers
+        .get(AUTHORIZATION)
+        .ok_or_else(|| AppError::Authentication("Missing Authorization header".to_string()))?
+        .to_str()
+        .map_err(|_| AppError::Authentication("Invalid Authorization header".to_string()))?;
+
+    // Check if it starts with "Bearer "
+    let token = auth_header.strip_prefix("Bearer ").ok_or_else(|| {
+        AppError::Authentication("Authorization header must start with 'Bearer '".to_string())
+    })?;
+
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.set_audience(&["openbank-api"]);
+    validation.set_issuer(&["openbank-auth"]);
+
+    let token_data = decode::<JwtClaims>(
+        token,
+        &DecodingKey::from_secret(jwt_secret.as_ref()),
+        &validation,
+    )
+    .map_err(|e| AppError::Authentication(format!("Invalid token: {}", e)))?;
+
+    Ok(token_data.claims)
+}
+
 /// JWT Authentication middleware
 pub async fn jwt_auth_middleware(
     mut req: Request,
@@ -1,9 +1,11 @@
 pub mod controller;
+pub mod login_history;
 pub mod middleware;
 pub mod model;
 pub mod repository;
 pub mod scopes;
 pub mod service;
+pub mod step_up;
 
 use crate::auth::service::AuthService;
 use axum::Router;
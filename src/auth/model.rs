@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use utoipa::ToSchema;
 use validator::Validate;
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -11,12 +12,13 @@ pub struct Developer {
     pub email: String,
     pub company: Option<String>,
     pub title: Option<String>,
+    pub organization_id: Uuid,
     pub password_hash: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ProjectEnvironment {
     Development,
@@ -28,6 +30,11 @@ impl sqlx::Type<sqlx::Postgres> for ProjectEnvironment {
     fn type_info() -> sqlx::postgres::PgTypeInfo {
         <&str as sqlx::Type<sqlx::Postgres>>::type_info()
     }
+
+    // The column is TEXT or VARCHAR depending on which migrations built the database
+    fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+        <&str as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+    }
 }
 
 impl sqlx::Encode<'_, sqlx::Postgres> for ProjectEnvironment {
@@ -57,6 +64,7 @@ impl<'r> sqlx::Decode<'r, sqlx::Postgres> for ProjectEnvironment {
 pub struct Project {
     pub id: Uuid,
     pub developer_id: Uuid,
+    pub organization_id: Uuid,
     pub name: String,
     pub description: Option<String>,
     pub environment: ProjectEnvironment,
@@ -64,6 +72,7 @@ pub struct Project {
     pub client_secret_hash: String,
     pub redirect_uris: Vec<String>,
     pub scopes: Vec<String>,
+    pub webhook_url: Option<String>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -80,9 +89,31 @@ pub struct OAuthToken {
     pub expires_at: DateTime<Utc>,
     pub jti: String,
     pub created_at: DateTime<Utc>,
+    /// End user the token acts for; project tokens have none
+    pub user_id: Option<Uuid>,
+    /// When the end user last logged in; kept across refreshes
+    pub auth_time: Option<DateTime<Utc>>,
+    /// How the end user last logged in
+    pub acr: Option<String>,
+}
+
+/// How and when the end user behind a user token logged in
+#[derive(Debug, Clone)]
+pub struct UserLogin {
+    pub user_id: Uuid,
+    pub auth_time: DateTime<Utc>,
+    pub acr: String,
+}
+
+/// A customer of the project's tenant who can log in for a user token
+#[derive(Debug, Clone, FromRow)]
+pub struct TenantUser {
+    pub id: Uuid,
+    pub email: String,
+    pub password_hash: String,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct RegisterDeveloperRequest {
     #[validate(length(min = 2, max = 100))]
     pub name: String,
@@ -96,7 +127,7 @@ pub struct RegisterDeveloperRequest {
     pub password: String,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateProjectRequest {
     #[validate(length(min = 2, max = 100))]
     pub name: String,
@@ -104,47 +135,80 @@ pub struct CreateProjectRequest {
     pub environment: ProjectEnvironment,
     pub redirect_uris: Vec<String>,
     pub scopes: Vec<String>,
+    /// Organization that owns the project; defaults to the developer's own.
+    /// The developer must be an owner or admin of it.
+    #[serde(default)]
+    pub organization_id: Option<Uuid>,
+    /// Receives the tenant's domain events the project's scopes cover
+    #[serde(default)]
+    #[validate(url)]
+    pub webhook_url: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+/// Token request. `client_credentials` issues a project token; `password`
+/// and `otp` issue a token acting for the end user named by `username`,
+/// who logs in with their password or a one-time code sent to them.
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct TokenRequest {
     pub grant_type: String,
     pub client_id: String,
     pub client_secret: String,
     pub scope: Option<String>,
+    /// End user's email, for the `password` and `otp` grants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// End user's password, for the `password` grant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// One-time code sent to the end user, for the `otp` grant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otp: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+/// Send an end user a one-time code to log in with
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct LoginCodeRequest {
+    pub client_id: String,
+    pub client_secret: String,
+    /// End user's email; the code is sent there
+    #[validate(email)]
+    pub username: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct RefreshTokenRequest {
     pub client_id: String,
     pub client_secret: String,
     pub jti: String, // Token identifier to refresh
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DeveloperResponse {
     pub id: Uuid,
     pub name: String,
     pub email: String,
     pub company: Option<String>,
     pub title: Option<String>,
+    pub organization_id: Uuid,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProjectResponse {
     pub id: Uuid,
+    pub organization_id: Uuid,
     pub name: String,
     pub description: Option<String>,
     pub environment: ProjectEnvironment,
     pub client_id: String,
     pub redirect_uris: Vec<String>,
     pub scopes: Vec<String>,
+    pub webhook_url: Option<String>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TokenResponse {
     pub access_token: String,
     pub token_type: String,
@@ -152,10 +216,13 @@ pub struct TokenResponse {
     pub scope: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct MeResponse {
     pub developer_id: Uuid,
     pub project_id: Uuid,
+    pub tenant_id: Uuid,
+    /// End user the token acts for; absent for project tokens
+    pub user_id: Option<Uuid>,
     pub scopes: Vec<String>,
     pub expires_at: DateTime<Utc>,
 }
@@ -170,7 +237,19 @@ pub struct JwtClaims {
     pub jti: String,
     pub developer_id: Uuid,
     pub project_id: Uuid,
+    /// Organization that owns the project; all data access is scoped to it
+    pub tenant_id: Uuid,
     pub scopes: Vec<String>,
+    /// End user the token acts for. User tokens reach only that user's
+    /// accounts; project tokens have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<Uuid>,
+    /// Unix time the end user last logged in, for step-up checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_time: Option<i64>,
+    /// How the end user last logged in: `pwd` or `otp`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acr: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -188,6 +267,7 @@ impl From<Developer> for DeveloperResponse {
             email: developer.email,
             company: developer.company,
             title: developer.title,
+            organization_id: developer.organization_id,
             created_at: developer.created_at,
         }
     }
@@ -197,25 +277,27 @@ impl From<Project> for ProjectResponse {
     fn from(project: Project) -> Self {
         Self {
             id: project.id,
+            organization_id: project.organization_id,
             name: project.name,
             description: project.description,
             environment: project.environment,
             client_id: project.client_id,
             redirect_uris: project.redirect_uris,
             scopes: project.scopes,
+            webhook_url: project.webhook_url,
             is_active: project.is_active,
             created_at: project.created_at,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ScopeInfo {
     pub scope: String,
     pub description: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ScopeSetsInfo {
     pub basic: Vec<String>,
     pub banking_app: Vec<String>,
@@ -225,8 +307,45 @@ pub struct ScopeSetsInfo {
     pub full_access: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ScopesResponse {
     pub scopes: Vec<ScopeInfo>,
     pub scope_sets: ScopeSetsInfo,
 }
+
+/// A token grant with a developer's project credentials, as recorded in the
+/// audit log
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LoginRecord {
+    /// Audit event of the login, to report it by
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub success: bool,
+    /// Why the login was refused
+    pub failure_reason: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+    /// ISO 3166 country of the IP address, as reported by the edge proxy
+    pub country: Option<String>,
+    /// Browser, client library or tool the login came from
+    pub device: Option<String>,
+    /// Whether the developer reported the login as not theirs
+    pub reported: bool,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReportLoginRequest {
+    /// What looked wrong about the login
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+/// A login a developer reported as not theirs
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct LoginReport {
+    pub event_id: Uuid,
+    pub developer_id: Uuid,
+    pub note: Option<String>,
+    pub reported_at: DateTime<Utc>,
+}
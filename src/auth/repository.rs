@@ -1,5 +1,8 @@
-use crate::auth::model::{Developer, OAuthToken, Project, ProjectEnvironment};
+use crate::auth::model::{CreateProjectRequest, Developer, LoginReport, OAuthToken, Project, TenantUser};
+use crate::core::deadline;
 use crate::core::error::AppResult;
+use crate::core::security::ClientSecurity;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -23,28 +26,53 @@ impl AuthRepository {
     ) -> AppResult<Developer> {
         let id = Uuid::new_v4();
         let now = chrono::Utc::now();
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        // Every developer starts in an organization of their own
+        let organization_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO organizations (id, name, is_active, created_at, updated_at) VALUES ($1, $2, TRUE, $3, $4)"
+        )
+        .bind(organization_id)
+        .bind(company.filter(|company| !company.is_empty()).unwrap_or(name))
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
 
         let developer = sqlx::query_as::<_, Developer>(
-            "INSERT INTO developers (id, name, email, company, title, password_hash, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING id, name, email, company, title, password_hash, created_at, updated_at"
+            "INSERT INTO developers (id, name, email, company, title, organization_id, password_hash, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING id, name, email, company, title, organization_id, password_hash, created_at, updated_at"
         )
         .bind(id)
         .bind(name)
         .bind(email)
         .bind(company)
         .bind(title)
+        .bind(organization_id)
         .bind(password_hash)
         .bind(now)
         .bind(now)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| crate::core::error::AppError::Database(e))?;
 
+        sqlx::query(
+            "INSERT INTO organization_members (organization_id, developer_id, role, created_at, updated_at) VALUES ($1, $2, 'owner', $3, $4)"
+        )
+        .bind(organization_id)
+        .bind(id)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
         Ok(developer)
     }
 
     pub async fn find_developer_by_email(&self, email: &str) -> AppResult<Option<Developer>> {
         let developer = sqlx::query_as::<_, Developer>(
-            "SELECT id, name, email, company, title, password_hash, created_at, updated_at FROM developers WHERE email = $1"
+            "SELECT id, name, email, company, title, organization_id, password_hash, created_at, updated_at FROM developers WHERE email = $1"
         )
         .bind(email)
         .fetch_optional(&self.pool)
@@ -56,7 +84,7 @@ impl AuthRepository {
 
     pub async fn find_developer_by_id(&self, id: Uuid) -> AppResult<Option<Developer>> {
         let developer = sqlx::query_as::<_, Developer>(
-            "SELECT id, name, email, password_hash, created_at, updated_at FROM developers WHERE id = $1"
+            "SELECT id, name, email, company, title, organization_id, password_hash, created_at, updated_at FROM developers WHERE id = $1"
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -66,32 +94,42 @@ impl AuthRepository {
         Ok(developer)
     }
 
+    /// Whether a developer has been suspended or deleted by an administrator
+    pub async fn is_developer_blocked(&self, id: Uuid) -> AppResult<bool> {
+        let blocked = sqlx::query_scalar::<_, bool>(
+            "SELECT suspended_at IS NOT NULL OR deleted_at IS NOT NULL FROM developers WHERE id = $1"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(blocked.unwrap_or(true))
+    }
+
     pub async fn create_project(
         &self,
         developer_id: Uuid,
-        name: &str,
-        description: &str,
-        environment: ProjectEnvironment,
+        request: &CreateProjectRequest,
         client_id: &str,
         client_secret_hash: &str,
-        redirect_uris: &[String],
-        scopes: &[String],
     ) -> AppResult<Project> {
         let id = Uuid::new_v4();
         let now = chrono::Utc::now();
 
         let project = sqlx::query_as::<_, Project>(
-            "INSERT INTO projects (id, developer_id, name, description, environment, client_id, client_secret_hash, redirect_uris, scopes, is_active, created_at, updated_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING id, developer_id, name, description, environment, client_id, client_secret_hash, redirect_uris, scopes, is_active, created_at, updated_at"
+            "INSERT INTO projects (id, developer_id, organization_id, name, description, environment, client_id, client_secret_hash, redirect_uris, scopes, webhook_url, is_active, created_at, updated_at) VALUES ($1, $2, COALESCE($3, (SELECT organization_id FROM developers WHERE id = $2)), $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) RETURNING id, developer_id, organization_id, name, description, environment, client_id, client_secret_hash, redirect_uris, scopes, webhook_url, is_active, created_at, updated_at"
         )
         .bind(id)
         .bind(developer_id)
-        .bind(name)
-        .bind(description)
-        .bind(environment)
+        .bind(request.organization_id)
+        .bind(&request.name)
+        .bind(request.description.as_deref().unwrap_or(""))
+        .bind(request.environment.clone())
         .bind(client_id)
         .bind(client_secret_hash)
-        .bind(redirect_uris)
-        .bind(scopes)
+        .bind(&request.redirect_uris)
+        .bind(&request.scopes)
+        .bind(&request.webhook_url)
         .bind(true)
         .bind(now)
         .bind(now)
@@ -102,9 +140,22 @@ impl AuthRepository {
         Ok(project)
     }
 
+    /// Whether a developer may manage projects in an organization
+    pub async fn can_manage_organization(&self, organization_id: Uuid, developer_id: Uuid) -> AppResult<bool> {
+        let can_manage = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM organization_members WHERE organization_id = $1 AND developer_id = $2 AND role IN ('owner', 'admin'))"
+        )
+        .bind(organization_id)
+        .bind(developer_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(can_manage)
+    }
+
     pub async fn find_project_by_client_id(&self, client_id: &str) -> AppResult<Option<Project>> {
         let project = sqlx::query_as::<_, Project>(
-            "SELECT id, developer_id, name, description, environment, client_id, client_secret_hash, redirect_uris, scopes, is_active, created_at, updated_at FROM projects WHERE client_id = $1"
+            "SELECT id, developer_id, organization_id, name, description, environment, client_id, client_secret_hash, redirect_uris, scopes, webhook_url, is_active, created_at, updated_at FROM projects WHERE client_id = $1"
         )
         .bind(client_id)
         .fetch_optional(&self.pool)
@@ -116,7 +167,7 @@ impl AuthRepository {
 
     pub async fn store_oauth_token(&self, token: &OAuthToken) -> AppResult<()> {
         sqlx::query(
-            "INSERT INTO oauth_tokens (id, project_id, developer_id, access_token_hash, token_type, scopes, expires_at, jti, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+            "INSERT INTO oauth_tokens (id, project_id, developer_id, access_token_hash, token_type, scopes, expires_at, jti, created_at, user_id, auth_time, acr) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"
         )
         .bind(token.id)
         .bind(token.project_id)
@@ -127,6 +178,9 @@ impl AuthRepository {
         .bind(token.expires_at)
         .bind(&token.jti)
         .bind(token.created_at)
+        .bind(token.user_id)
+        .bind(token.auth_time)
+        .bind(&token.acr)
         .execute(&self.pool)
         .await
         .map_err(|e| crate::core::error::AppError::Database(e))?;
@@ -136,7 +190,7 @@ impl AuthRepository {
 
     pub async fn find_oauth_token_by_jti(&self, jti: &str) -> AppResult<Option<OAuthToken>> {
         let token = sqlx::query_as::<_, OAuthToken>(
-            "SELECT id, project_id, developer_id, access_token_hash, token_type, scopes, expires_at, jti, created_at FROM oauth_tokens WHERE jti = $1"
+            "SELECT id, project_id, developer_id, access_token_hash, token_type, scopes, expires_at, jti, created_at, user_id, auth_time, acr FROM oauth_tokens WHERE jti = $1"
         )
         .bind(jti)
         .fetch_optional(&self.pool)
@@ -159,4 +213,169 @@ impl AuthRepository {
 
         Ok(())
     }
+
+    /// An active customer of the tenant, by email
+    pub async fn find_tenant_user(&self, tenant_id: Uuid, email: &str) -> AppResult<Option<TenantUser>> {
+        let user = sqlx::query_as::<_, TenantUser>(
+            "SELECT id, email, password_hash FROM users
+             WHERE tenant_id = $1 AND LOWER(email) = LOWER($2) AND is_active = true",
+        )
+        .bind(tenant_id)
+        .bind(email)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    /// Store a login code, replacing any the user still had open for the project
+    pub async fn create_login_code(
+        &self,
+        user_id: Uuid,
+        project_id: Uuid,
+        code_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE user_login_codes SET consumed_at = NOW()
+             WHERE user_id = $1 AND project_id = $2 AND consumed_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(project_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO user_login_codes (user_id, project_id, code_hash, expires_at) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(user_id)
+        .bind(project_id)
+        .bind(code_hash)
+        .bind(expires_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Count an attempt against the user's open login code, spending it when
+    /// `code_hash` matches. Returns whether it matched; codes that expired or
+    /// ran out of attempts never do.
+    pub async fn redeem_login_code(
+        &self,
+        user_id: Uuid,
+        project_id: Uuid,
+        code_hash: &str,
+        max_attempts: i32,
+    ) -> AppResult<bool> {
+        let redeemed = sqlx::query_scalar::<_, bool>(
+            "UPDATE user_login_codes
+             SET attempts = attempts + 1,
+                 consumed_at = CASE WHEN code_hash = $3 OR attempts + 1 >= $4 THEN NOW() END
+             WHERE id = (
+                 SELECT id FROM user_login_codes
+                 WHERE user_id = $1 AND project_id = $2 AND consumed_at IS NULL AND expires_at > NOW()
+                 ORDER BY created_at DESC
+                 LIMIT 1
+                 FOR UPDATE
+             )
+             RETURNING code_hash = $3",
+        )
+        .bind(user_id)
+        .bind(project_id)
+        .bind(code_hash)
+        .bind(max_attempts)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(redeemed.unwrap_or(false))
+    }
+
+    /// Record a login reported as not the developer's. Returns `None` if it
+    /// was already reported.
+    pub async fn create_login_report(
+        &self,
+        event_id: Uuid,
+        developer_id: Uuid,
+        note: Option<&str>,
+    ) -> AppResult<Option<LoginReport>> {
+        let report = sqlx::query_as::<_, LoginReport>(
+            "INSERT INTO login_reports (event_id, developer_id, note)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (event_id) DO NOTHING
+             RETURNING event_id, developer_id, note, reported_at",
+        )
+        .bind(event_id)
+        .bind(developer_id)
+        .bind(note)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(report)
+    }
+
+    /// Which of the logins were reported as not the developer's
+    pub async fn reported_logins(&self, developer_id: Uuid, event_ids: &[Uuid]) -> AppResult<Vec<Uuid>> {
+        let reported = sqlx::query_scalar(
+            "SELECT event_id FROM login_reports WHERE developer_id = $1 AND event_id = ANY($2)",
+        )
+        .bind(developer_id)
+        .bind(event_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reported)
+    }
+
+    pub async fn find_client_security(&self, client_id: &str) -> AppResult<Option<ClientSecurity>> {
+        let security = sqlx::query_as::<_, ClientSecurity>(
+            "SELECT client_id, failed_attempts, last_failed_attempt, locked_until, lock_reason, updated_at
+             FROM client_security WHERE client_id = $1",
+        )
+        .bind(client_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(security)
+    }
+
+    /// Change a client's failed attempt tracking under a row lock, so
+    /// concurrent attempts all count
+    pub async fn update_client_security<R>(
+        &self,
+        client_id: &str,
+        update: impl FnOnce(&mut ClientSecurity) -> R,
+    ) -> AppResult<R> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("INSERT INTO client_security (client_id) VALUES ($1) ON CONFLICT (client_id) DO NOTHING")
+            .bind(client_id)
+            .execute(&mut *tx)
+            .await?;
+        let mut security = sqlx::query_as::<_, ClientSecurity>(
+            "SELECT client_id, failed_attempts, last_failed_attempt, locked_until, lock_reason, updated_at
+             FROM client_security WHERE client_id = $1 FOR UPDATE",
+        )
+        .bind(client_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let result = update(&mut security);
+        sqlx::query(
+            "UPDATE client_security SET
+                 failed_attempts = $2, last_failed_attempt = $3, locked_until = $4, lock_reason = $5, updated_at = $6
+             WHERE client_id = $1",
+        )
+        .bind(client_id)
+        .bind(security.failed_attempts)
+        .bind(security.last_failed_attempt)
+        .bind(security.locked_until)
+        .bind(security.lock_reason)
+        .bind(security.updated_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(result)
+    }
 }
@@ -1,7 +1,11 @@
 /// OpenBank API Scopes
 /// 
 /// This module defines all available scopes for the OpenBank API.
-/// Scopes are based on the actual banking modules.
+/// Scopes are based on the actual banking modules, each split into a read
+/// and a write scope. The module-wide scopes are kept for existing projects
+/// and tokens and grant both.
+use axum::http::Method;
+use crate::core::versioning;
 
 // Core Banking Modules
 pub const IDENTITY: &str = "identity";
@@ -10,6 +14,111 @@ pub const PAYMENTS: &str = "payments";
 pub const TRANSACTIONS: &str = "transactions";
 pub const USER_DATA: &str = "user-data";
 pub const VIRTUAL_ACCOUNTS: &str = "virtual-accounts";
+pub const DISPUTES: &str = "disputes";
+
+// Read and write scopes per module
+pub const IDENTITY_READ: &str = "identity:read";
+pub const IDENTITY_WRITE: &str = "identity:write";
+pub const INCOME_READ: &str = "income:read";
+pub const INCOME_WRITE: &str = "income:write";
+pub const PAYMENTS_READ: &str = "payments:read";
+pub const PAYMENTS_WRITE: &str = "payments:write";
+pub const TRANSACTIONS_READ: &str = "transactions:read";
+pub const TRANSACTIONS_WRITE: &str = "transactions:write";
+pub const USER_DATA_READ: &str = "user-data:read";
+pub const USER_DATA_WRITE: &str = "user-data:write";
+pub const VIRTUAL_ACCOUNTS_READ: &str = "virtual-accounts:read";
+pub const VIRTUAL_ACCOUNTS_WRITE: &str = "virtual-accounts:write";
+pub const DISPUTES_READ: &str = "disputes:read";
+pub const DISPUTES_WRITE: &str = "disputes:write";
+
+/// Modules guarded by scopes, named as their routes are under `/api/v1`
+pub const MODULES: &[&str] = &[
+    IDENTITY,
+    INCOME,
+    PAYMENTS,
+    TRANSACTIONS,
+    USER_DATA,
+    VIRTUAL_ACCOUNTS,
+    DISPUTES,
+];
+
+/// Modules tokens acting for an end user may reach, each limited to the
+/// user's own data
+pub const USER_TOKEN_MODULES: &[&str] = &[USER_DATA, TRANSACTIONS];
+
+/// Whether a scope may be granted to a token acting for an end user
+pub fn is_user_scope(scope: &str) -> bool {
+    let module = scope.split_once(':').map_or(scope, |(module, _)| module);
+    USER_TOKEN_MODULES.contains(&module)
+}
+
+/// Whether a call reads or changes a module's data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+impl Access {
+    /// GET, HEAD and OPTIONS read; every other method writes
+    pub fn for_method(method: &Method) -> Self {
+        if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            Access::Read
+        } else {
+            Access::Write
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Access::Read => "read",
+            Access::Write => "write",
+        }
+    }
+}
+
+/// The scope granting `access` to a module, e.g. `payments:write`
+pub fn module_scope(module: &str, access: Access) -> String {
+    format!("{}:{}", module, access.as_str())
+}
+
+/// Whether the scopes grant `access` to a module, either through its read or
+/// write scope or through the legacy module-wide scope, which expands to both
+pub fn grants(scopes: &[String], module: &str, access: Access) -> bool {
+    scopes
+        .iter()
+        .any(|scope| scope == module || scope.split_once(':') == Some((module, access.as_str())))
+}
+
+/// Whether the scopes grant everything `scope` does. A module-wide scope
+/// is covered by holding both the module's read and write scopes.
+pub fn covers(scopes: &[String], scope: &str) -> bool {
+    match scope.split_once(':') {
+        Some((module, "read")) => grants(scopes, module, Access::Read),
+        Some((module, "write")) => grants(scopes, module, Access::Write),
+        Some(_) => false,
+        None => {
+            scopes.iter().any(|held| held == scope)
+                || (grants(scopes, scope, Access::Read) && grants(scopes, scope, Access::Write))
+        }
+    }
+}
+
+/// Custom methods that only read, though sent with POST to carry a body
+const READ_ONLY_METHODS: &[&str] = &[":batchGet"];
+
+/// The module and access a request needs a scope for: the module its path is
+/// under, below its API version, and the access its method implies. Paths
+/// outside the scoped modules need none.
+pub fn required_scope(method: &Method, path: &str) -> Option<(&'static str, Access)> {
+    let segment = versioning::unversioned(path)?.strip_prefix('/')?.split('/').next()?;
+    let module = MODULES.iter().find(|module| **module == segment)?;
+    if READ_ONLY_METHODS.iter().any(|custom| path.ends_with(custom)) {
+        return Some((*module, Access::Read));
+    }
+    Some((*module, Access::for_method(method)))
+}
 
 /// Default scope sets for different project types
 pub struct ScopeSets;
@@ -67,26 +176,41 @@ impl ScopeSets {
             TRANSACTIONS.to_string(),
             USER_DATA.to_string(),
             VIRTUAL_ACCOUNTS.to_string(),
+            DISPUTES.to_string(),
         ]
     }
 }
 
 /// Validates if a scope is valid
 pub fn is_valid_scope(scope: &str) -> bool {
-    matches!(scope,
-        IDENTITY | INCOME | PAYMENTS | TRANSACTIONS | USER_DATA | VIRTUAL_ACCOUNTS
-    )
+    get_scope_description(scope).is_some()
 }
 
-/// Get all available scopes
+/// Get all available scopes, the read and write scopes first and the legacy
+/// module-wide scopes after them
 pub fn all_scopes() -> Vec<String> {
     vec![
+        IDENTITY_READ.to_string(),
+        IDENTITY_WRITE.to_string(),
+        INCOME_READ.to_string(),
+        INCOME_WRITE.to_string(),
+        PAYMENTS_READ.to_string(),
+        PAYMENTS_WRITE.to_string(),
+        TRANSACTIONS_READ.to_string(),
+        TRANSACTIONS_WRITE.to_string(),
+        USER_DATA_READ.to_string(),
+        USER_DATA_WRITE.to_string(),
+        VIRTUAL_ACCOUNTS_READ.to_string(),
+        VIRTUAL_ACCOUNTS_WRITE.to_string(),
+        DISPUTES_READ.to_string(),
+        DISPUTES_WRITE.to_string(),
         IDENTITY.to_string(),
         INCOME.to_string(),
         PAYMENTS.to_string(),
         TRANSACTIONS.to_string(),
         USER_DATA.to_string(),
         VIRTUAL_ACCOUNTS.to_string(),
+        DISPUTES.to_string(),
     ]
 }
 
@@ -99,6 +223,58 @@ pub fn get_scope_description(scope: &str) -> Option<&'static str> {
         TRANSACTIONS => Some("Access to transaction management and history features"),
         USER_DATA => Some("Access to user profile and account data features"),
         VIRTUAL_ACCOUNTS => Some("Access to virtual account creation and management features"),
+        DISPUTES => Some("Access to transaction and payment dispute management features"),
+        IDENTITY_READ => Some("Read identity verifications and their results"),
+        IDENTITY_WRITE => Some("Start and update identity verifications"),
+        INCOME_READ => Some("Read income verifications and credit checks"),
+        INCOME_WRITE => Some("Request income verifications and credit checks"),
+        PAYMENTS_READ => Some("Read payments and their status"),
+        PAYMENTS_WRITE => Some("Create, approve and cancel payments"),
+        TRANSACTIONS_READ => Some("Read transactions, statements and history"),
+        TRANSACTIONS_WRITE => Some("Create and categorize transactions"),
+        USER_DATA_READ => Some("Read user profiles and account data"),
+        USER_DATA_WRITE => Some("Update user profiles and account data"),
+        VIRTUAL_ACCOUNTS_READ => Some("Read virtual accounts and their balances"),
+        VIRTUAL_ACCOUNTS_WRITE => Some("Create, update and close virtual accounts"),
+        DISPUTES_READ => Some("Read disputes and their status"),
+        DISPUTES_WRITE => Some("Open disputes and add evidence"),
         _ => None,
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owned(scopes: &[&str]) -> Vec<String> {
+        scopes.iter().map(|scope| scope.to_string()).collect()
+    }
+
+    #[test]
+    fn legacy_scopes_grant_read_and_write() {
+        let legacy = owned(&[PAYMENTS]);
+        assert!(grants(&legacy, PAYMENTS, Access::Read));
+        assert!(grants(&legacy, PAYMENTS, Access::Write));
+        assert!(!grants(&legacy, TRANSACTIONS, Access::Read));
+
+        let read_only = owned(&[PAYMENTS_READ]);
+        assert!(grants(&read_only, PAYMENTS, Access::Read));
+        assert!(!grants(&read_only, PAYMENTS, Access::Write));
+        assert!(covers(&legacy, PAYMENTS_WRITE));
+        assert!(!covers(&read_only, PAYMENTS));
+        assert!(covers(&owned(&[PAYMENTS_READ, PAYMENTS_WRITE]), PAYMENTS));
+    }
+
+    #[test]
+    fn methods_map_to_read_or_write() {
+        assert_eq!(required_scope(&Method::GET, "/api/v1/payments/:id"), Some((PAYMENTS, Access::Read)));
+        assert_eq!(required_scope(&Method::POST, "/api/v1/user-data/accounts"), Some((USER_DATA, Access::Write)));
+        assert_eq!(required_scope(&Method::POST, "/api/v1/user-data/balances:batchGet"), Some((USER_DATA, Access::Read)));
+        assert_eq!(required_scope(&Method::DELETE, "/api/v1/virtual-accounts/:id"), Some((VIRTUAL_ACCOUNTS, Access::Write)));
+        assert_eq!(required_scope(&Method::GET, "/api/v1/goals"), None);
+        assert_eq!(required_scope(&Method::GET, "/api/v2/payments/:id"), Some((PAYMENTS, Access::Read)));
+        assert_eq!(required_scope(&Method::POST, "/graphql"), None);
+        assert!(is_valid_scope(DISPUTES_READ));
+        assert!(!is_valid_scope("disputes:delete"));
+    }
 }
\ No newline at end of file
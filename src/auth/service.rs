@@ -1,18 +1,38 @@
+use super::login_history::{login_record, LoginContext, LOGIN_EVENT_TYPES};
 use super::model::*;
 use super::repository::AuthRepository;
 use super::scopes;
+use super::step_up::{ACR_OTP, ACR_PASSWORD};
+use crate::core::alerts::{Alert, AlertSink, LogAlertSink};
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::crypto::{hex, hmac_sha256};
 use crate::core::error::{AppError, AppResult};
+use crate::core::mailer::{EmailMessage, LogMailer, Mailer};
+use crate::core::security::{AccountSecurityService, ClientSecurity, SecurityAction, SecurityConfig};
+use crate::shared::types::PaginatedResponse;
 use bcrypt::{hash, verify, DEFAULT_COST};
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use rand::{distributions::Alphanumeric, Rng};
 use sha2::{Digest, Sha256};
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// Lifetime of user tokens, shorter than project tokens as they are held on
+/// customers' devices
+const USER_TOKEN_TTL_SECONDS: i64 = 3600;
+const LOGIN_CODE_TTL_MINUTES: i64 = 10;
+/// Wrong codes after which a login code is spent
+const LOGIN_CODE_MAX_ATTEMPTS: i32 = 5;
+
 #[derive(Clone)]
 pub struct AuthService {
     pub repository: AuthRepository,
     pub jwt_secret: String,
+    mailer: Arc<dyn Mailer>,
+    audit_logger: AuditLogger,
+    alert_sink: Arc<dyn AlertSink>,
+    security_service: AccountSecurityService,
 }
 
 impl AuthService {
@@ -20,9 +40,39 @@ impl AuthService {
         Self {
             repository,
             jwt_secret,
+            mailer: Arc::new(LogMailer),
+            audit_logger: AuditLogger::in_memory(),
+            alert_sink: Arc::new(LogAlertSink),
+            security_service: AccountSecurityService::new(SecurityConfig::default()),
         }
     }
 
+    /// Deliver end users' login codes through `mailer` instead of the log
+    pub fn with_mailer(mut self, mailer: Arc<dyn Mailer>) -> Self {
+        self.mailer = mailer;
+        self
+    }
+
+    /// Record developers' logins in `audit_logger` instead of in memory
+    pub fn with_audit_logger(mut self, audit_logger: AuditLogger) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Lock out clients by the thresholds of `security_service`, following
+    /// its reconfigurations, instead of the defaults
+    pub fn with_security_service(mut self, security_service: AccountSecurityService) -> Self {
+        self.security_service = security_service;
+        self
+    }
+
+    /// Raise logins reported as not the developer's through `alert_sink`
+    /// instead of the log
+    pub fn with_alert_sink(mut self, alert_sink: Arc<dyn AlertSink>) -> Self {
+        self.alert_sink = alert_sink;
+        self
+    }
+
     pub async fn register_developer(
         &self,
         request: RegisterDeveloperRequest,
@@ -59,6 +109,19 @@ impl AuthService {
         // Validate requested scopes
         self.validate_project_scopes(&request.scopes)?;
 
+        // Only owners and admins may add projects to an organization
+        if let Some(organization_id) = request.organization_id {
+            if !self
+                .repository
+                .can_manage_organization(organization_id, developer_id)
+                .await?
+            {
+                return Err(AppError::Authorization(
+                    "Only organization owners and admins can create projects".to_string(),
+                ));
+            }
+        }
+
         let client_id = self.generate_client_id();
         let client_secret = self.generate_client_secret();
         let client_secret_hash = hash(&client_secret, DEFAULT_COST)
@@ -66,16 +129,7 @@ impl AuthService {
 
         let project = self
             .repository
-            .create_project(
-                developer_id,
-                &request.name,
-                request.description.as_deref().unwrap_or(""),
-                request.environment,
-                &client_id,
-                &client_secret_hash,
-                &request.redirect_uris,
-                &request.scopes,
-            )
+            .create_project(developer_id, &request, &client_id, &client_secret_hash)
             .await?;
 
         let mut response = ProjectResponse::from(project);
@@ -83,27 +137,27 @@ impl AuthService {
         Ok(response)
     }
 
+    /// The client credentials grant, a developer's login. Grants with a
+    /// known client id are recorded in the developer's login history.
     pub async fn handle_client_credentials_flow(
         &self,
         request: TokenRequest,
+        context: &LoginContext,
     ) -> AppResult<TokenResponse> {
         if request.grant_type != "client_credentials" {
             return Err(AppError::Validation("Invalid grant type".to_string()));
         }
 
-        let project = self
-            .repository
-            .find_project_by_client_id(&request.client_id)
-            .await?
-            .ok_or_else(|| AppError::Authentication("Invalid client credentials".to_string()))?;
-
-        if !verify(&request.client_secret, &project.client_secret_hash)
-            .map_err(|_| AppError::Internal("Failed to verify client secret".to_string()))?
+        let project = match self
+            .authenticate_project(&request.client_id, &request.client_secret, context)
+            .await
         {
-            return Err(AppError::Authentication(
-                "Invalid client credentials".to_string(),
-            ));
-        }
+            Ok(project) => project,
+            Err(error) => {
+                self.record_failed_login(&request.client_id, context, &error).await;
+                return Err(error);
+            }
+        };
 
         let requested_scopes = request
             .scope
@@ -117,9 +171,10 @@ impl AuthService {
             }
         }
 
-        // Ensure requested scopes are a subset of project scopes
+        // Ensure requested scopes are covered by the project scopes; a legacy
+        // module scope covers the module's read and write scopes
         for scope in &requested_scopes {
-            if !project.scopes.contains(scope) {
+            if !scopes::covers(&project.scopes, scope) {
                 return Err(AppError::Validation(format!(
                     "Scope '{}' not authorized for this project",
                     scope
@@ -127,82 +182,140 @@ impl AuthService {
             }
         }
 
-        let scopes = requested_scopes;
-
         // Environment-based token expiration for better developer experience
-        let expires_at = match project.environment {
-            ProjectEnvironment::Development => Utc::now() + Duration::hours(24), // 24 hours for development
-            ProjectEnvironment::Staging => Utc::now() + Duration::hours(8), // 8 hours for staging
-            ProjectEnvironment::Production => Utc::now() + Duration::hours(4), // 4 hours for production
-        };
-
         let expires_in_seconds = match project.environment {
             ProjectEnvironment::Development => 24 * 3600, // 24 hours
             ProjectEnvironment::Staging => 8 * 3600,      // 8 hours
             ProjectEnvironment::Production => 4 * 3600,   // 4 hours
         };
 
-        let jti = Uuid::new_v4().to_string();
+        let token = self
+            .mint_token(&project, requested_scopes, None, expires_in_seconds)
+            .await?;
+        let event = login_event(AuditEventType::LoginSuccess, &project, context).risk_score(0);
+        self.audit_logger.log(event).await;
+        Ok(token)
+    }
 
-        let claims = JwtClaims {
-            iss: "openbank-auth".to_string(),
-            aud: "openbank-api".to_string(),
-            sub: project.developer_id.to_string(),
-            exp: expires_at.timestamp(),
-            iat: Utc::now().timestamp(),
-            jti: jti.clone(),
-            developer_id: project.developer_id,
-            project_id: project.id,
-            scopes: scopes.clone(),
+    /// Record a refused login against the developer owning the client id;
+    /// unknown client ids have no one to record it for
+    async fn record_failed_login(&self, client_id: &str, context: &LoginContext, error: &AppError) {
+        if !matches!(error, AppError::Authentication(_) | AppError::Authorization(_)) {
+            return;
+        }
+        let project = match self.repository.find_project_by_client_id(client_id).await {
+            Ok(Some(project)) => project,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to look up the project of a refused login");
+                return;
+            }
         };
 
-        let token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.jwt_secret.as_ref()),
-        )
-        .map_err(|_| AppError::Internal("Failed to generate token".to_string()))?;
+        let event = login_event(AuditEventType::LoginFailure, &project, context)
+            .error(error.to_string())
+            .severity(AuditSeverity::Warning)
+            .risk_score(30);
+        self.audit_logger.log(event).await;
+    }
 
-        let oauth_token = OAuthToken {
-            id: Uuid::new_v4(),
-            project_id: project.id,
-            developer_id: project.developer_id,
-            access_token_hash: self.hash_secret(&token),
-            token_type: "Bearer".to_string(),
-            scopes: scopes.clone(),
-            expires_at,
-            jti,
-            created_at: Utc::now(),
-        };
+    /// The developer's logins, newest first
+    pub async fn login_history(
+        &self,
+        developer_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<PaginatedResponse<LoginRecord>> {
+        let limit = limit.clamp(1, 100);
+        let page = page.max(1);
+
+        let (events, total) = self
+            .audit_logger
+            .user_events(developer_id, &LOGIN_EVENT_TYPES, (page as u64 - 1) * limit as u64, limit as i64)
+            .await?;
+        let event_ids: Vec<Uuid> = events.iter().map(|event| event.id).collect();
+        let reported = self.repository.reported_logins(developer_id, &event_ids).await?;
+
+        Ok(PaginatedResponse {
+            data: events
+                .iter()
+                .map(|event| login_record(event, reported.contains(&event.id)))
+                .collect(),
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit as u64) as u32,
+        })
+    }
 
-        self.repository.store_oauth_token(&oauth_token).await?;
+    /// Report one of the developer's logins as not theirs, raising a
+    /// security alert. A login can be reported once.
+    pub async fn report_login(
+        &self,
+        developer_id: Uuid,
+        event_id: Uuid,
+        request: ReportLoginRequest,
+        ip_address: String,
+    ) -> AppResult<LoginReport> {
+        let login = self
+            .audit_logger
+            .find_event(event_id)
+            .await?
+            .filter(|event| event.user_id == Some(developer_id))
+            .filter(|event| matches!(event.event_type, AuditEventType::LoginSuccess | AuditEventType::LoginFailure))
+            .ok_or_else(|| AppError::NotFound("Login not found".to_string()))?;
 
-        Ok(TokenResponse {
-            access_token: token,
-            token_type: "Bearer".to_string(),
-            expires_in: expires_in_seconds,
-            scope: scopes.join(" "),
-        })
+        let report = self
+            .repository
+            .create_login_report(event_id, developer_id, request.note.as_deref())
+            .await?
+            .ok_or_else(|| AppError::Conflict("The login was already reported".to_string()))?;
+
+        let record = login_record(&login, true);
+        let event = AuditEvent::new(AuditEventType::SuspiciousActivity)
+            .severity(AuditSeverity::Critical)
+            .user_id(developer_id)
+            .ip_address(ip_address)
+            .resource(format!("login:{}", event_id))
+            .action("report_login".to_string())
+            .metadata("login_ip_address".to_string(), serde_json::json!(record.ip_address))
+            .metadata("login_country".to_string(), serde_json::json!(record.country))
+            .metadata("login_project_id".to_string(), serde_json::json!(record.project_id))
+            .metadata("note".to_string(), serde_json::json!(report.note))
+            .risk_score(90)
+            .compliance_tag("SECURITY".to_string());
+        self.audit_logger.log(event).await;
+
+        let alert = Alert {
+            title: "Login reported as not the developer's".to_string(),
+            message: format!(
+                "Developer {} reported a {} login from {} at {} as not theirs",
+                developer_id,
+                if record.success { "successful" } else { "failed" },
+                record.ip_address,
+                record.occurred_at.to_rfc3339()
+            ),
+            details: serde_json::json!({ "developer_id": developer_id, "login": record, "note": report.note }),
+            raised_at: report.reported_at,
+        };
+        // The report is kept and audited either way; a lost alert must not
+        // stop the developer from reporting
+        if let Err(e) = self.alert_sink.send(&alert).await {
+            tracing::error!(error = %e, event_id = %event_id, "Failed to raise the reported login alert");
+        }
+
+        Ok(report)
     }
 
     pub async fn refresh_access_token(
         &self,
         request: RefreshTokenRequest,
+        context: &LoginContext,
     ) -> AppResult<TokenResponse> {
         // Verify client credentials
         let project = self
-            .repository
-            .find_project_by_client_id(&request.client_id)
-            .await?
-            .ok_or_else(|| AppError::Authentication("Invalid client credentials".to_string()))?;
-
-        if !verify(&request.client_secret, &project.client_secret_hash)
-            .map_err(|_| AppError::Internal("Failed to verify client secret".to_string()))?
-        {
-            return Err(AppError::Authentication(
-                "Invalid client credentials".to_string(),
-            ));
-        }
+            .authenticate_project(&request.client_id, &request.client_secret, context)
+            .await?;
 
         // Get existing token by JTI
         let existing_token = self
@@ -223,31 +336,190 @@ impl AuthService {
             return Err(AppError::Authentication("Token has expired".to_string()));
         }
 
-        // Generate new token with same scopes but extended expiration
-        let expires_at = match project.environment {
-            ProjectEnvironment::Development => Utc::now() + Duration::hours(24),
-            ProjectEnvironment::Staging => Utc::now() + Duration::hours(8),
-            ProjectEnvironment::Production => Utc::now() + Duration::hours(4),
+        // Generate new token with same scopes and user but extended expiration
+        let expires_in_seconds = match (existing_token.user_id, &project.environment) {
+            (Some(_), _) => USER_TOKEN_TTL_SECONDS,
+            (None, ProjectEnvironment::Development) => 24 * 3600,
+            (None, ProjectEnvironment::Staging) => 8 * 3600,
+            (None, ProjectEnvironment::Production) => 4 * 3600,
         };
 
-        let expires_in_seconds = match project.environment {
-            ProjectEnvironment::Development => 24 * 3600,
-            ProjectEnvironment::Staging => 8 * 3600,
-            ProjectEnvironment::Production => 4 * 3600,
+        // A refresh is not a new login, so the user's login time carries over
+        let login = existing_token.user_id.map(|user_id| UserLogin {
+            user_id,
+            auth_time: existing_token.auth_time.unwrap_or(existing_token.created_at),
+            acr: existing_token.acr.clone().unwrap_or_else(|| ACR_PASSWORD.to_string()),
+        });
+        let response = self
+            .mint_token(&project, existing_token.scopes.clone(), login, expires_in_seconds)
+            .await?;
+
+        // Revoke the token that was refreshed
+        self.repository.revoke_oauth_token(&request.jti).await?;
+
+        Ok(response)
+    }
+
+    /// Issue a token for any supported grant
+    pub async fn issue_token(&self, request: TokenRequest, context: &LoginContext) -> AppResult<TokenResponse> {
+        match request.grant_type.as_str() {
+            "password" | "otp" => self.handle_user_flow(request, context).await,
+            _ => self.handle_client_credentials_flow(request, context).await,
+        }
+    }
+
+    /// Password and one-time code grants: a token acting for one of the
+    /// tenant's customers, limited to the modules end users reach
+    pub async fn handle_user_flow(&self, request: TokenRequest, context: &LoginContext) -> AppResult<TokenResponse> {
+        let required = |value: &Option<String>, field: &str| {
+            value.clone().filter(|value| !value.is_empty()).ok_or_else(|| {
+                AppError::Validation(format!("{} is required for the {} grant", field, request.grant_type))
+            })
+        };
+        let username = required(&request.username, "username")?;
+        let secret = match request.grant_type.as_str() {
+            "password" => required(&request.password, "password")?,
+            "otp" => required(&request.otp, "otp")?,
+            _ => return Err(AppError::Validation("Invalid grant type".to_string())),
+        };
+
+        let project = self
+            .authenticate_project(&request.client_id, &request.client_secret, context)
+            .await?;
+
+        let user = self
+            .repository
+            .find_tenant_user(project.organization_id, &username)
+            .await?;
+        let authenticated = match user {
+            Some(user) if request.grant_type == "password" => {
+                // Legacy rows may hold hashes bcrypt cannot read; they never match
+                verify(&secret, &user.password_hash).unwrap_or(false).then_some(user)
+            }
+            Some(user) => self
+                .repository
+                .redeem_login_code(
+                    user.id,
+                    project.id,
+                    &self.login_code_hash(user.id, &secret),
+                    LOGIN_CODE_MAX_ATTEMPTS,
+                )
+                .await?
+                .then_some(user),
+            None => None,
+        };
+        let user = authenticated.ok_or_else(|| AppError::Authentication("Invalid user credentials".to_string()))?;
+
+        let requested_scopes: Vec<String> = match request.scope {
+            Some(scope) => scope.split_whitespace().map(String::from).collect(),
+            None => project
+                .scopes
+                .iter()
+                .filter(|scope| scopes::is_user_scope(scope))
+                .cloned()
+                .collect(),
+        };
+        for scope in &requested_scopes {
+            if !scopes::is_valid_scope(scope) {
+                return Err(AppError::Validation(format!("Invalid scope: {}", scope)));
+            }
+            if !scopes::is_user_scope(scope) {
+                return Err(AppError::Validation(format!(
+                    "Scope '{}' cannot be granted to user tokens",
+                    scope
+                )));
+            }
+            if !scopes::covers(&project.scopes, scope) {
+                return Err(AppError::Validation(format!(
+                    "Scope '{}' not authorized for this project",
+                    scope
+                )));
+            }
+        }
+        if requested_scopes.is_empty() {
+            return Err(AppError::Validation(
+                "The project has no scopes user tokens can be granted".to_string(),
+            ));
+        }
+
+        let login = UserLogin {
+            user_id: user.id,
+            auth_time: Utc::now(),
+            acr: if request.grant_type == "otp" { ACR_OTP } else { ACR_PASSWORD }.to_string(),
+        };
+        self.mint_token(&project, requested_scopes, Some(login), USER_TOKEN_TTL_SECONDS)
+            .await
+    }
+
+    /// Send one of the tenant's customers a one-time code to log in with.
+    /// Unknown users get the same answer and no code, so the call does not
+    /// reveal who is a customer.
+    pub async fn request_login_code(&self, request: LoginCodeRequest, context: &LoginContext) -> AppResult<()> {
+        let project = self
+            .authenticate_project(&request.client_id, &request.client_secret, context)
+            .await?;
+
+        let Some(user) = self
+            .repository
+            .find_tenant_user(project.organization_id, &request.username)
+            .await?
+        else {
+            return Ok(());
         };
 
-        let new_jti = Uuid::new_v4().to_string();
+        let code = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+        self.repository
+            .create_login_code(
+                user.id,
+                project.id,
+                &self.login_code_hash(user.id, &code),
+                Utc::now() + Duration::minutes(LOGIN_CODE_TTL_MINUTES),
+            )
+            .await?;
+
+        self.mailer
+            .send(EmailMessage {
+                to: user.email,
+                subject: format!("Your {} login code", project.name),
+                body: format!(
+                    "Your login code is {}. It expires in {} minutes.\n\n\
+                     If you did not try to log in, you can ignore this email.",
+                    code, LOGIN_CODE_TTL_MINUTES
+                ),
+            })
+            .await
+    }
+
+    /// Sign and store a token for the project, acting for the logged in user
+    /// when given
+    async fn mint_token(
+        &self,
+        project: &Project,
+        scopes: Vec<String>,
+        login: Option<UserLogin>,
+        expires_in_seconds: i64,
+    ) -> AppResult<TokenResponse> {
+        let user_id = login.as_ref().map(|login| login.user_id);
+        let auth_time = login.as_ref().map(|login| login.auth_time);
+        let acr = login.map(|login| login.acr);
+        let now = Utc::now();
+        let expires_at = now + Duration::seconds(expires_in_seconds);
+        let jti = Uuid::new_v4().to_string();
 
         let claims = JwtClaims {
             iss: "openbank-auth".to_string(),
             aud: "openbank-api".to_string(),
-            sub: project.developer_id.to_string(),
+            sub: user_id.unwrap_or(project.developer_id).to_string(),
             exp: expires_at.timestamp(),
-            iat: Utc::now().timestamp(),
-            jti: new_jti.clone(),
+            iat: now.timestamp(),
+            jti: jti.clone(),
             developer_id: project.developer_id,
             project_id: project.id,
-            scopes: existing_token.scopes.clone(),
+            tenant_id: project.organization_id,
+            scopes: scopes.clone(),
+            user_id,
+            auth_time: auth_time.map(|auth_time| auth_time.timestamp()),
+            acr: acr.clone(),
         };
 
         let token = encode(
@@ -257,27 +529,28 @@ impl AuthService {
         )
         .map_err(|_| AppError::Internal("Failed to generate token".to_string()))?;
 
-        let new_token = OAuthToken {
+        let oauth_token = OAuthToken {
             id: Uuid::new_v4(),
             project_id: project.id,
             developer_id: project.developer_id,
             access_token_hash: self.hash_secret(&token),
             token_type: "Bearer".to_string(),
-            scopes: existing_token.scopes.clone(),
+            scopes: scopes.clone(),
             expires_at,
-            jti: new_jti,
-            created_at: Utc::now(),
+            jti,
+            created_at: now,
+            user_id,
+            auth_time,
+            acr,
         };
 
-        // Store new token and optionally revoke old one
-        self.repository.store_oauth_token(&new_token).await?;
-        self.repository.revoke_oauth_token(&request.jti).await?;
+        self.repository.store_oauth_token(&oauth_token).await?;
 
         Ok(TokenResponse {
             access_token: token,
             token_type: "Bearer".to_string(),
             expires_in: expires_in_seconds,
-            scope: existing_token.scopes.join(" "),
+            scope: scopes.join(" "),
         })
     }
 
@@ -312,11 +585,85 @@ impl AuthService {
         Ok(MeResponse {
             developer_id: oauth_token.developer_id,
             project_id: oauth_token.project_id,
+            tenant_id: token_data.claims.tenant_id,
+            user_id: oauth_token.user_id,
             scopes: oauth_token.scopes,
             expires_at: oauth_token.expires_at,
         })
     }
 
+    /// The project behind a client id and secret, if its developer may still
+    /// be issued tokens. Wrong secrets are counted per client id, and a
+    /// client with too many is locked out for a while, whatever the secret.
+    async fn authenticate_project(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+        context: &LoginContext,
+    ) -> AppResult<Project> {
+        let project = self
+            .repository
+            .find_project_by_client_id(client_id)
+            .await?
+            .ok_or_else(|| AppError::Authentication("Invalid client credentials".to_string()))?;
+
+        let security = self.repository.find_client_security(client_id).await?;
+        if let Some(remaining) = security.as_ref().and_then(ClientSecurity::lock_remaining) {
+            return Err(AppError::Authentication(format!(
+                "Client temporarily locked after repeated failed attempts, try again in {} minutes",
+                remaining.num_minutes() + 1
+            )));
+        }
+
+        if !verify(client_secret, &project.client_secret_hash)
+            .map_err(|_| AppError::Internal("Failed to verify client secret".to_string()))?
+        {
+            let action = self
+                .repository
+                .update_client_security(client_id, |security| {
+                    self.security_service.record_failed_client_attempt(security)
+                })
+                .await??;
+            if let SecurityAction::AccountLocked { duration, reason } = action {
+                let event = AuditEvent::new(AuditEventType::AccountLocked)
+                    .severity(AuditSeverity::Warning)
+                    .user_id(project.developer_id)
+                    .project_id(project.id)
+                    .ip_address(context.ip_address.clone())
+                    .resource(format!("oauth_client:{}", client_id))
+                    .action("lock_client".to_string())
+                    .metadata("reason".to_string(), serde_json::json!(reason))
+                    .metadata("lock_minutes".to_string(), serde_json::json!(duration.num_minutes()))
+                    .risk_score(60)
+                    .compliance_tag("SECURITY".to_string());
+                self.audit_logger.log(event).await;
+            }
+            return Err(AppError::Authentication(
+                "Invalid client credentials".to_string(),
+            ));
+        }
+        if security.is_some_and(|security| security.failed_attempts > 0) {
+            self.repository
+                .update_client_security(client_id, |security| {
+                    self.security_service.record_successful_client_auth(security)
+                })
+                .await?;
+        }
+
+        self.ensure_developer_active(project.developer_id).await?;
+        Ok(project)
+    }
+
+    /// Reject token issuance for developers suspended or deleted by an administrator
+    async fn ensure_developer_active(&self, developer_id: Uuid) -> AppResult<()> {
+        if self.repository.is_developer_blocked(developer_id).await? {
+            return Err(AppError::Authorization(
+                "Developer account is suspended".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     fn generate_client_id(&self) -> String {
         format!("ck_{}", self.generate_random_string(32))
     }
@@ -333,6 +680,12 @@ impl AuthService {
             .collect()
     }
 
+    /// Login codes are short, so they are keyed with the signing secret and
+    /// the user rather than hashed alone
+    fn login_code_hash(&self, user_id: Uuid, code: &str) -> String {
+        hex(&hmac_sha256(self.jwt_secret.as_bytes(), format!("{}:{}", user_id, code).as_bytes()))
+    }
+
     fn hash_secret(&self, secret: &str) -> String {
         let mut hasher = Sha256::new();
         hasher.update(secret.as_bytes());
@@ -362,3 +715,22 @@ impl AuthService {
         Ok(())
     }
 }
+
+/// A developer's login with a project's credentials
+fn login_event(event_type: AuditEventType, project: &Project, context: &LoginContext) -> AuditEvent {
+    let mut event = AuditEvent::new(event_type)
+        .user_id(project.developer_id)
+        .project_id(project.id)
+        .ip_address(context.ip_address.clone())
+        .resource(format!("project:{}", project.id))
+        .action("client_credentials".to_string())
+        .compliance_tag("SOC2".to_string())
+        .compliance_tag("AUTHENTICATION".to_string());
+    if let Some(user_agent) = &context.user_agent {
+        event = event.user_agent(user_agent.clone());
+    }
+    if let Some(country) = &context.country {
+        event = event.metadata("country".to_string(), serde_json::json!(country));
+    }
+    event
+}
@@ -0,0 +1,161 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::{AccountId, Amount};
+use super::model::JwtClaims;
+
+/// `acr` of a login with the user's password
+pub const ACR_PASSWORD: &str = "pwd";
+/// `acr` of a login with a one-time code sent to the user, the strong method
+pub const ACR_OTP: &str = "otp";
+
+/// How recent and strong a login high-risk actions need
+#[derive(Debug, Clone, Copy)]
+pub struct StepUpSettings {
+    /// Seconds a one-time code login counts as recent
+    pub max_age_seconds: i64,
+    /// Transfers of at least this amount need a recent login; 0 challenges every transfer
+    pub transfer_threshold: Amount,
+}
+
+impl StepUpSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_age_seconds: config.step_up_max_age_seconds,
+            transfer_threshold: config.step_up_transfer_threshold,
+        }
+    }
+}
+
+/// Actions an end user must have logged in for recently
+#[derive(Debug, Clone, Copy)]
+pub enum SensitiveAction {
+    AddBeneficiary,
+    RegisterDevice,
+    Transfer { from_account_id: AccountId, amount: Amount },
+}
+
+impl SensitiveAction {
+    fn name(&self) -> &'static str {
+        match self {
+            SensitiveAction::AddBeneficiary => "add_beneficiary",
+            SensitiveAction::RegisterDevice => "register_device",
+            SensitiveAction::Transfer { .. } => "transfer",
+        }
+    }
+}
+
+/// Why the token's login is not good enough for a sensitive action, or
+/// `None` if it is
+pub fn challenge(
+    settings: &StepUpSettings,
+    action: &SensitiveAction,
+    acr: Option<&str>,
+    auth_time: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<&'static str> {
+    if let SensitiveAction::Transfer { amount, .. } = action {
+        if *amount < settings.transfer_threshold {
+            return None;
+        }
+    }
+
+    match (acr, auth_time) {
+        (Some(ACR_OTP), Some(auth_time)) if now - auth_time <= Duration::seconds(settings.max_age_seconds) => None,
+        (Some(ACR_OTP), Some(_)) => Some("Login is too old for this action"),
+        _ => Some("This action needs a login with a one-time code"),
+    }
+}
+
+/// Demands a fresh one-time code login from end users before high-risk
+/// actions. Project tokens act for no user and are not challenged.
+pub struct StepUpPolicy {
+    settings: StepUpSettings,
+    audit_logger: AuditLogger,
+}
+
+impl StepUpPolicy {
+    pub fn new(settings: StepUpSettings, audit_logger: AuditLogger) -> Self {
+        Self {
+            settings,
+            audit_logger,
+        }
+    }
+
+    /// Fail with `StepUpRequired` unless the token's user logged in recently
+    /// enough, with a strong enough method, for `action`
+    pub async fn require(&self, claims: &JwtClaims, action: SensitiveAction) -> AppResult<()> {
+        let Some(user_id) = claims.user_id else {
+            return Ok(());
+        };
+
+        let auth_time = claims
+            .auth_time
+            .and_then(|auth_time| Utc.timestamp_opt(auth_time, 0).single());
+        let Some(reason) = challenge(&self.settings, &action, claims.acr.as_deref(), auth_time, Utc::now())
+        else {
+            return Ok(());
+        };
+
+        let mut event = AuditEvent::new(AuditEventType::StepUpChallenged)
+            .severity(AuditSeverity::Warning)
+            .user_id(user_id)
+            .project_id(claims.project_id)
+            .action(action.name().to_string())
+            .metadata("reason".to_string(), serde_json::json!(reason))
+            .metadata("acr".to_string(), serde_json::json!(claims.acr))
+            .metadata("auth_time".to_string(), serde_json::json!(claims.auth_time))
+            .compliance_tag("SCA".to_string());
+        if let SensitiveAction::Transfer { from_account_id, amount } = action {
+            event = event
+                .resource(format!("account:{}", from_account_id))
+                .metadata("amount".to_string(), serde_json::json!(amount));
+        }
+        self.audit_logger.log(event).await;
+
+        Err(AppError::StepUpRequired {
+            reason: reason.to_string(),
+            acr: ACR_OTP,
+            max_age_seconds: self.settings.max_age_seconds,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    const SETTINGS: StepUpSettings = StepUpSettings {
+        max_age_seconds: 300,
+        transfer_threshold: 100_000,
+    };
+
+    fn transfer(amount: Amount) -> SensitiveAction {
+        SensitiveAction::Transfer {
+            from_account_id: Uuid::nil(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn small_transfers_are_not_challenged() {
+        let now = Utc::now();
+        assert_eq!(challenge(&SETTINGS, &transfer(99_999), Some(ACR_PASSWORD), Some(now), now), None);
+        assert!(challenge(&SETTINGS, &transfer(100_000), Some(ACR_PASSWORD), Some(now), now).is_some());
+    }
+
+    #[test]
+    fn needs_a_recent_one_time_code_login() {
+        let now = Utc::now();
+        let action = SensitiveAction::AddBeneficiary;
+        assert_eq!(challenge(&SETTINGS, &action, Some(ACR_OTP), Some(now - Duration::seconds(300)), now), None);
+        assert_eq!(
+            challenge(&SETTINGS, &action, Some(ACR_OTP), Some(now - Duration::seconds(301)), now),
+            Some("Login is too old for this action")
+        );
+        assert!(challenge(&SETTINGS, &action, Some(ACR_PASSWORD), Some(now), now).is_some());
+        assert!(challenge(&SETTINGS, &action, None, None, now).is_some());
+    }
+}
@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::{PaginatedResponse, PaginationParams};
+use super::model::{
+    CaptureFilter, DebugCapture, DebugCaptureSummary, ProjectCaptureSettings, UpdateCaptureSettingsRequest,
+};
+use super::service::capture_service;
+
+/// Get a project's debug capture settings
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/projects/{id}/capture-settings",
+    tag = "captures",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Debug capture settings", body = ProjectCaptureSettings),
+        (status = 403, description = "Caller lacks the project management permission"),
+        (status = 404, description = "Project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_capture_settings(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<ProjectCaptureSettings>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, format!("project:{}", id))
+        .await?;
+
+    let settings = capture_service(&state).get_settings(id).await?;
+    Ok(Json(ApiResponse::success("Debug capture settings retrieved successfully", settings)))
+}
+
+/// Turn sampled request and response capture on or off for a project
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/projects/{id}/capture-settings",
+    tag = "captures",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = UpdateCaptureSettingsRequest,
+    responses(
+        (status = 200, description = "Debug capture settings updated", body = ProjectCaptureSettings),
+        (status = 400, description = "Invalid sample rate or retention"),
+        (status = 403, description = "Caller lacks the project management permission"),
+        (status = 404, description = "Project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_capture_settings(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<UpdateCaptureSettingsRequest>,
+) -> AppResult<Json<ApiResponse<ProjectCaptureSettings>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, format!("project:{}", id))
+        .await?;
+
+    let settings = capture_service(&state)
+        .update_settings(id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Debug capture settings updated successfully", settings)))
+}
+
+/// List a project's unexpired debug captures, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/projects/{id}/captures",
+    tag = "captures",
+    params(("id" = Uuid, Path, description = "Project ID"), CaptureFilter, PaginationParams),
+    responses(
+        (status = 200, description = "Page of captures", body = PaginatedDebugCaptures),
+        (status = 403, description = "Caller lacks the project management permission"),
+        (status = 404, description = "Project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_captures(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    Query(filter): Query<CaptureFilter>,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<PaginatedResponse<DebugCaptureSummary>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, format!("project:{}", id))
+        .await?;
+
+    let captures = capture_service(&state)
+        .list_captures(id, filter, pagination.page, pagination.limit)
+        .await?;
+    Ok(Json(ApiResponse::success("Debug captures retrieved successfully", captures)))
+}
+
+/// Get a captured request and response
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/projects/{id}/captures/{capture_id}",
+    tag = "captures",
+    params(
+        ("id" = Uuid, Path, description = "Project ID"),
+        ("capture_id" = Uuid, Path, description = "Capture ID")
+    ),
+    responses(
+        (status = 200, description = "Captured request and response, redacted", body = DebugCapture),
+        (status = 403, description = "Caller lacks the project management permission"),
+        (status = 404, description = "Capture not found or expired")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_capture(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path((id, capture_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<Json<ApiResponse<DebugCapture>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, format!("project:{}", id))
+        .await?;
+
+    let capture = capture_service(&state)
+        .get_capture(id, capture_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Debug capture retrieved successfully", capture)))
+}
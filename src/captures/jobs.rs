@@ -0,0 +1,29 @@
+use crate::core::AppState;
+use super::service::capture_service;
+
+const PURGE_JOB: &str = "debug_capture_purge";
+
+/// Periodically delete debug captures past their retention
+pub fn spawn_purge_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.debug_capture_purge_interval_seconds);
+    state.job_monitor.register(PURGE_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match capture_service(&state).purge_expired().await {
+                Ok(count) => {
+                    state.job_monitor.record_success(PURGE_JOB);
+                    if count > 0 {
+                        tracing::info!("Purged {} expired debug captures", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(PURGE_JOB, e.to_string());
+                    tracing::error!("Debug capture purge job failed: {}", e);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,18 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::get, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/:id/capture-settings",
+            get(controller::get_capture_settings).put(controller::update_capture_settings),
+        )
+        .route("/projects/:id/captures", get(controller::list_captures))
+        .route("/projects/:id/captures/:capture_id", get(controller::get_capture))
+}
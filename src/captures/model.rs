@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::core::capture::CaptureSettings;
+
+/// A project's debug capture settings as stored
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct ProjectCaptureSettings {
+    pub project_id: Uuid,
+    pub enabled: bool,
+    /// Share of requests captured, between 0 (exclusive) and 1
+    pub sample_rate: f64,
+    /// Hours a capture is kept before it is purged
+    pub retention_hours: i32,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProjectCaptureSettings {
+    pub fn settings(&self) -> CaptureSettings {
+        CaptureSettings {
+            enabled: self.enabled,
+            sample_rate: self.sample_rate,
+            retention_hours: self.retention_hours,
+        }
+    }
+}
+
+/// Turn debug capture on or off for a project
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateCaptureSettingsRequest {
+    pub enabled: bool,
+    /// Share of requests captured; defaults to every request
+    #[validate(range(exclusive_min = 0.0, max = 1.0))]
+    pub sample_rate: Option<f64>,
+    /// Hours captures are kept, up to a week; defaults to 24
+    #[validate(range(min = 1, max = 168))]
+    pub retention_hours: Option<i32>,
+}
+
+/// A request and response captured for debugging, redacted
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct DebugCapture {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub status_code: i32,
+    #[schema(value_type = Object)]
+    pub request_headers: serde_json::Value,
+    pub request_body: Option<String>,
+    #[schema(value_type = Object)]
+    pub response_headers: serde_json::Value,
+    pub response_body: Option<String>,
+    pub duration_ms: i64,
+    pub captured_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A capture as listed, without headers and bodies
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct DebugCaptureSummary {
+    pub id: Uuid,
+    pub method: String,
+    pub path: String,
+    pub status_code: i32,
+    pub duration_ms: i64,
+    pub captured_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A capture about to be stored
+#[derive(Debug, Clone)]
+pub struct NewDebugCapture {
+    pub project_id: Uuid,
+    pub method: String,
+    pub path: String,
+    pub query: Option<String>,
+    pub status_code: i32,
+    pub request_headers: serde_json::Value,
+    pub request_body: Option<String>,
+    pub response_headers: serde_json::Value,
+    pub response_body: Option<String>,
+    pub duration_ms: i64,
+    pub retention_hours: i32,
+}
+
+/// Filters for listing captures
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CaptureFilter {
+    /// Only captures answered with this status code
+    pub status_code: Option<i32>,
+    /// Only failed calls, those answered with a 4xx or 5xx status
+    #[serde(default)]
+    pub errors_only: bool,
+}
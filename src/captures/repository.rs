@@ -0,0 +1,160 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use super::model::{CaptureFilter, DebugCapture, DebugCaptureSummary, NewDebugCapture, ProjectCaptureSettings};
+
+const CAPTURE_COLUMNS: &str = "id, project_id, method, path, query, status_code, request_headers, request_body,
+     response_headers, response_body, duration_ms, captured_at, expires_at";
+
+pub struct CaptureRepository {
+    pool: PgPool,
+}
+
+impl CaptureRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn project_exists(&self, project_id: Uuid) -> AppResult<bool> {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1)")
+            .bind(project_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(exists)
+    }
+
+    pub async fn find_settings(&self, project_id: Uuid) -> AppResult<Option<ProjectCaptureSettings>> {
+        let settings = sqlx::query_as::<_, ProjectCaptureSettings>(
+            "SELECT project_id, enabled, sample_rate, retention_hours, updated_by, updated_at
+             FROM project_capture_settings WHERE project_id = $1",
+        )
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(settings)
+    }
+
+    pub async fn save_settings(
+        &self,
+        project_id: Uuid,
+        enabled: bool,
+        sample_rate: f64,
+        retention_hours: i32,
+        updated_by: Uuid,
+    ) -> AppResult<ProjectCaptureSettings> {
+        let settings = sqlx::query_as::<_, ProjectCaptureSettings>(
+            "INSERT INTO project_capture_settings (project_id, enabled, sample_rate, retention_hours, updated_by)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (project_id) DO UPDATE
+             SET enabled = EXCLUDED.enabled,
+                 sample_rate = EXCLUDED.sample_rate,
+                 retention_hours = EXCLUDED.retention_hours,
+                 updated_by = EXCLUDED.updated_by,
+                 updated_at = NOW()
+             RETURNING project_id, enabled, sample_rate, retention_hours, updated_by, updated_at",
+        )
+        .bind(project_id)
+        .bind(enabled)
+        .bind(sample_rate)
+        .bind(retention_hours)
+        .bind(updated_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(settings)
+    }
+
+    pub async fn insert_capture(&self, capture: &NewDebugCapture) -> AppResult<Uuid> {
+        let id = sqlx::query_scalar(
+            "INSERT INTO debug_captures
+                 (project_id, method, path, query, status_code, request_headers, request_body,
+                  response_headers, response_body, duration_ms, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW() + make_interval(hours => $11))
+             RETURNING id",
+        )
+        .bind(capture.project_id)
+        .bind(&capture.method)
+        .bind(&capture.path)
+        .bind(&capture.query)
+        .bind(capture.status_code)
+        .bind(&capture.request_headers)
+        .bind(&capture.request_body)
+        .bind(&capture.response_headers)
+        .bind(&capture.response_body)
+        .bind(capture.duration_ms)
+        .bind(capture.retention_hours)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Unexpired captures for a project, newest first
+    pub async fn find_captures(
+        &self,
+        project_id: Uuid,
+        filter: &CaptureFilter,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<Vec<DebugCaptureSummary>> {
+        let offset = (page.saturating_sub(1) * limit) as i64;
+        let captures = sqlx::query_as::<_, DebugCaptureSummary>(
+            "SELECT id, method, path, status_code, duration_ms, captured_at, expires_at
+             FROM debug_captures
+             WHERE project_id = $1 AND expires_at > NOW()
+               AND ($2::INTEGER IS NULL OR status_code = $2)
+               AND (NOT $3 OR status_code >= 400)
+             ORDER BY captured_at DESC
+             LIMIT $4 OFFSET $5",
+        )
+        .bind(project_id)
+        .bind(filter.status_code)
+        .bind(filter.errors_only)
+        .bind(limit as i64)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(captures)
+    }
+
+    pub async fn count_captures(&self, project_id: Uuid, filter: &CaptureFilter) -> AppResult<i64> {
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM debug_captures
+             WHERE project_id = $1 AND expires_at > NOW()
+               AND ($2::INTEGER IS NULL OR status_code = $2)
+               AND (NOT $3 OR status_code >= 400)",
+        )
+        .bind(project_id)
+        .bind(filter.status_code)
+        .bind(filter.errors_only)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn find_capture(&self, project_id: Uuid, capture_id: Uuid) -> AppResult<Option<DebugCapture>> {
+        let capture = sqlx::query_as::<_, DebugCapture>(&format!(
+            "SELECT {CAPTURE_COLUMNS} FROM debug_captures
+             WHERE id = $1 AND project_id = $2 AND expires_at > NOW()"
+        ))
+        .bind(capture_id)
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(capture)
+    }
+
+    /// Delete captures past their retention, returning how many went
+    pub async fn purge_expired(&self) -> AppResult<u64> {
+        let result = sqlx::query("DELETE FROM debug_captures WHERE expires_at <= NOW()")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
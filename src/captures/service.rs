@@ -0,0 +1,164 @@
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::capture::{CaptureSettings, CaptureSettingsCache};
+use crate::core::error::{AppError, AppResult};
+use crate::core::AppState;
+use crate::shared::constants::MAX_PAGE_LIMIT;
+use crate::shared::types::PaginatedResponse;
+use super::model::{
+    CaptureFilter, DebugCapture, DebugCaptureSummary, NewDebugCapture, ProjectCaptureSettings,
+    UpdateCaptureSettingsRequest,
+};
+use super::repository::CaptureRepository;
+
+/// Retention for captures when a project does not choose one
+const DEFAULT_RETENTION_HOURS: i32 = 24;
+
+pub struct CaptureService {
+    repository: CaptureRepository,
+    cache: CaptureSettingsCache,
+    audit_logger: AuditLogger,
+}
+
+pub fn capture_service(state: &AppState) -> CaptureService {
+    CaptureService::new(
+        CaptureRepository::new(state.postgres.clone()),
+        state.capture_settings_cache.clone(),
+        state.audit_logger.clone(),
+    )
+}
+
+impl CaptureService {
+    pub fn new(repository: CaptureRepository, cache: CaptureSettingsCache, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            cache,
+            audit_logger,
+        }
+    }
+
+    /// Capture settings for sampling a request; projects that never opted
+    /// in are not captured
+    pub async fn settings_for(&self, project_id: Uuid) -> AppResult<CaptureSettings> {
+        if let Some(settings) = self.cache.get(project_id) {
+            return Ok(settings);
+        }
+
+        let settings = self
+            .repository
+            .find_settings(project_id)
+            .await?
+            .map(|stored| stored.settings())
+            .unwrap_or_else(CaptureSettings::disabled);
+        self.cache.store(project_id, settings);
+        Ok(settings)
+    }
+
+    pub async fn get_settings(&self, project_id: Uuid) -> AppResult<ProjectCaptureSettings> {
+        self.ensure_project(project_id).await?;
+        let settings = self.repository.find_settings(project_id).await?;
+
+        Ok(settings.unwrap_or_else(|| {
+            let disabled = CaptureSettings::disabled();
+            ProjectCaptureSettings {
+                project_id,
+                enabled: disabled.enabled,
+                sample_rate: disabled.sample_rate,
+                retention_hours: disabled.retention_hours,
+                updated_by: None,
+                updated_at: chrono::Utc::now(),
+            }
+        }))
+    }
+
+    pub async fn update_settings(
+        &self,
+        project_id: Uuid,
+        request: UpdateCaptureSettingsRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ProjectCaptureSettings> {
+        self.ensure_project(project_id).await?;
+        let previous = self.repository.find_settings(project_id).await?;
+
+        let settings = self
+            .repository
+            .save_settings(
+                project_id,
+                request.enabled,
+                request.sample_rate.unwrap_or(1.0),
+                request.retention_hours.unwrap_or(DEFAULT_RETENTION_HOURS),
+                actor_id,
+            )
+            .await?;
+        self.cache.invalidate(project_id);
+
+        let event = AuditEvent::new(AuditEventType::ProjectDebugCaptureChanged)
+            .user_id(actor_id)
+            .resource(format!("project:{}", project_id))
+            .action(if settings.enabled { "enable_capture" } else { "disable_capture" }.to_string())
+            .metadata("was_enabled".to_string(), serde_json::json!(previous.is_some_and(|p| p.enabled)))
+            .metadata("sample_rate".to_string(), serde_json::json!(settings.sample_rate))
+            .metadata("retention_hours".to_string(), serde_json::json!(settings.retention_hours))
+            .compliance_tag("PRIVACY".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(settings)
+    }
+
+    pub async fn list_captures(
+        &self,
+        project_id: Uuid,
+        filter: CaptureFilter,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<PaginatedResponse<DebugCaptureSummary>> {
+        let limit = limit.clamp(1, MAX_PAGE_LIMIT);
+        let page = page.max(1);
+
+        self.ensure_project(project_id).await?;
+        let captures = self.repository.find_captures(project_id, &filter, page, limit).await?;
+        let total = self.repository.count_captures(project_id, &filter).await?.max(0) as u64;
+
+        Ok(PaginatedResponse {
+            data: captures,
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit as u64) as u32,
+        })
+    }
+
+    /// A capture in full; viewing one is audited, as it holds request data
+    pub async fn get_capture(&self, project_id: Uuid, capture_id: Uuid, actor_id: Uuid) -> AppResult<DebugCapture> {
+        let capture = self
+            .repository
+            .find_capture(project_id, capture_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Debug capture not found".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::DebugCaptureViewed)
+            .user_id(actor_id)
+            .resource(format!("project:{}", project_id))
+            .action("view_capture".to_string())
+            .metadata("capture_id".to_string(), serde_json::json!(capture_id))
+            .compliance_tag("PRIVACY".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(capture)
+    }
+
+    pub async fn record(&self, capture: NewDebugCapture) -> AppResult<Uuid> {
+        self.repository.insert_capture(&capture).await
+    }
+
+    pub async fn purge_expired(&self) -> AppResult<u64> {
+        self.repository.purge_expired().await
+    }
+
+    async fn ensure_project(&self, project_id: Uuid) -> AppResult<()> {
+        if !self.repository.project_exists(project_id).await? {
+            return Err(AppError::NotFound("Project not found".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use crate::core::mailer::{EmailMessage, Mailer};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// A security alert for the operations team
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub title: String,
+    pub message: String,
+    pub details: serde_json::Value,
+    pub raised_at: DateTime<Utc>,
+}
+
+/// Delivery channel for security alerts
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    async fn send(&self, alert: &Alert) -> AppResult<()>;
+}
+
+/// Writes alerts to the log only
+pub struct LogAlertSink;
+
+#[async_trait]
+impl AlertSink for LogAlertSink {
+    async fn send(&self, alert: &Alert) -> AppResult<()> {
+        tracing::warn!(details = %alert.details, "Security alert: {}: {}", alert.title, alert.message);
+        Ok(())
+    }
+}
+
+/// Posts the alert as JSON to a webhook
+pub struct WebhookAlertSink {
+    client: HttpClient,
+    url: String,
+}
+
+impl WebhookAlertSink {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            url,
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookAlertSink {
+    async fn send(&self, alert: &Alert) -> AppResult<()> {
+        post_json(&self.client, &self.url, &serde_json::json!(alert)).await
+    }
+}
+
+/// Posts the alert to a Slack incoming webhook
+pub struct SlackAlertSink {
+    client: HttpClient,
+    url: String,
+}
+
+impl SlackAlertSink {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            url,
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl AlertSink for SlackAlertSink {
+    async fn send(&self, alert: &Alert) -> AppResult<()> {
+        let payload = serde_json::json!({
+            "text": format!(":rotating_light: *{}*\n{}", alert.title, alert.message),
+        });
+        post_json(&self.client, &self.url, &payload).await
+    }
+}
+
+/// Emails the alert through the configured mailer
+pub struct EmailAlertSink {
+    mailer: Arc<dyn Mailer>,
+    to: String,
+}
+
+impl EmailAlertSink {
+    pub fn new(mailer: Arc<dyn Mailer>, to: String) -> Self {
+        Self { mailer, to }
+    }
+}
+
+#[async_trait]
+impl AlertSink for EmailAlertSink {
+    async fn send(&self, alert: &Alert) -> AppResult<()> {
+        let details = serde_json::to_string_pretty(&alert.details).unwrap_or_default();
+        self.mailer
+            .send(EmailMessage {
+                to: self.to.clone(),
+                subject: format!("Security alert: {}", alert.title),
+                body: format!(
+                    "{}\n\nRaised at {}\n\n{}",
+                    alert.message,
+                    alert.raised_at.to_rfc3339(),
+                    details
+                ),
+            })
+            .await
+    }
+}
+
+async fn post_json(client: &HttpClient, url: &str, payload: &serde_json::Value) -> AppResult<()> {
+    let response = client
+        .send(client.post(url).json(payload))
+        .await
+        .map_err(|e| AppError::ExternalService(format!("Alert delivery failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalService(format!(
+            "Alert sink returned {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+/// Name of the alert webhooks' outbound client
+const ALERTS_CLIENT: &str = "alerts";
+
+/// Build the alert sink selected by `ALERT_SINK`
+pub fn from_config(config: &Config, mailer: Arc<dyn Mailer>, clients: &HttpClients) -> AppResult<Arc<dyn AlertSink>> {
+    let missing = |name: &str| {
+        AppError::Internal(format!("{} is required for the {} alert sink", name, config.alert_sink))
+    };
+    match config.alert_sink.as_str() {
+        "log" => Ok(Arc::new(LogAlertSink)),
+        "webhook" => Ok(Arc::new(
+            WebhookAlertSink::new(config.alert_webhook_url.clone().ok_or_else(|| missing("ALERT_WEBHOOK_URL"))?)
+                .with_http_client(clients.get(ALERTS_CLIENT)),
+        )),
+        "slack" => Ok(Arc::new(
+            SlackAlertSink::new(config.alert_webhook_url.clone().ok_or_else(|| missing("ALERT_WEBHOOK_URL"))?)
+                .with_http_client(clients.get(ALERTS_CLIENT)),
+        )),
+        "email" => Ok(Arc::new(EmailAlertSink::new(
+            mailer,
+            config.alert_email.clone().ok_or_else(|| missing("ALERT_EMAIL"))?,
+        ))),
+        other => Err(AppError::Internal(format!("Unknown alert sink '{}'", other))),
+    }
+}
@@ -0,0 +1,352 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::core::alerts::{Alert, AlertSink};
+use crate::core::audit::{AuditEvent, AuditEventType, AuditSeverity};
+use crate::core::config::Config;
+use crate::core::AppState;
+
+const ANOMALY_DETECTION_JOB: &str = "anomaly_detection";
+
+/// Audit events read per pass; a backlog is worked through over later passes
+const AUDIT_BATCH_SIZE: i64 = 5_000;
+
+/// Patterns watched for in the audit stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyRule {
+    /// Failed logins from one IP address
+    FailedLogins,
+    /// Scope violations by one project
+    ScopeViolations,
+    /// Denied requests (RBAC, tenancy, IP rules) from one IP address
+    AccessDenied,
+}
+
+impl AnomalyRule {
+    /// The rule an event counts towards, and the source it is counted against
+    fn classify(event: &AuditEvent) -> Option<(AnomalyRule, String)> {
+        let ip_source = || Some(format!("ip:{}", event.ip_address)).filter(|_| !event.ip_address.is_empty());
+        let source = match event.event_type {
+            AuditEventType::LoginFailure => (AnomalyRule::FailedLogins, ip_source()?),
+            AuditEventType::LoginAttempt if !event.success => (AnomalyRule::FailedLogins, ip_source()?),
+            AuditEventType::ScopeViolation => {
+                let source = event
+                    .project_id
+                    .map(|project_id| format!("project:{}", project_id))
+                    .or_else(ip_source)?;
+                (AnomalyRule::ScopeViolations, source)
+            }
+            AuditEventType::AccessDenied
+            | AuditEventType::CrossTenantAccessDenied
+            | AuditEventType::IpAccessBlocked => (AnomalyRule::AccessDenied, ip_source()?),
+            _ => return None,
+        };
+        Some(source)
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            AnomalyRule::FailedLogins => "failed logins",
+            AnomalyRule::ScopeViolations => "scope violations",
+            AnomalyRule::AccessDenied => "denied requests",
+        }
+    }
+}
+
+/// Rolling window and per-rule thresholds
+#[derive(Debug, Clone)]
+pub struct DetectionSettings {
+    pub window: Duration,
+    pub failed_login_threshold: usize,
+    pub scope_violation_threshold: usize,
+    pub access_denied_threshold: usize,
+}
+
+impl DetectionSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            window: Duration::seconds(config.anomaly_window_seconds),
+            failed_login_threshold: config.anomaly_failed_login_threshold,
+            scope_violation_threshold: config.anomaly_scope_violation_threshold,
+            access_denied_threshold: config.anomaly_access_denied_threshold,
+        }
+    }
+
+    fn threshold(&self, rule: AnomalyRule) -> usize {
+        match rule {
+            AnomalyRule::FailedLogins => self.failed_login_threshold,
+            AnomalyRule::ScopeViolations => self.scope_violation_threshold,
+            AnomalyRule::AccessDenied => self.access_denied_threshold,
+        }
+    }
+}
+
+/// A source that crossed a rule's threshold within the window
+#[derive(Debug, Clone, Serialize)]
+pub struct Anomaly {
+    pub rule: AnomalyRule,
+    /// `ip:<address>` or `project:<id>`
+    pub source: String,
+    pub count: usize,
+    pub threshold: usize,
+    pub window_seconds: i64,
+    /// Events per minute over the window
+    pub rate_per_minute: f64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+struct SourceWindow {
+    count: usize,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+struct Observation {
+    event_id: Uuid,
+    at: DateTime<Utc>,
+    rule: AnomalyRule,
+    source: String,
+}
+
+/// Counts matching audit events per source over a rolling window. A source
+/// is reported once when it reaches the threshold, and again only after a
+/// full window without a report.
+pub struct AnomalyDetector {
+    settings: DetectionSettings,
+    observations: Vec<Observation>,
+    seen: HashSet<Uuid>,
+    reported: HashMap<(AnomalyRule, String), DateTime<Utc>>,
+}
+
+impl AnomalyDetector {
+    pub fn new(settings: DetectionSettings) -> Self {
+        Self {
+            settings,
+            observations: Vec::new(),
+            seen: HashSet::new(),
+            reported: HashMap::new(),
+        }
+    }
+
+    /// Add newly read events and return the anomalies they complete
+    pub fn observe(&mut self, events: &[AuditEvent], now: DateTime<Utc>) -> Vec<Anomaly> {
+        let window_start = now - self.settings.window;
+
+        for event in events {
+            if event.timestamp <= window_start || self.seen.contains(&event.id) {
+                continue;
+            }
+            if let Some((rule, source)) = AnomalyRule::classify(event) {
+                self.seen.insert(event.id);
+                self.observations.push(Observation {
+                    event_id: event.id,
+                    at: event.timestamp,
+                    rule,
+                    source,
+                });
+            }
+        }
+
+        let seen = &mut self.seen;
+        self.observations.retain(|observation| {
+            let keep = observation.at > window_start;
+            if !keep {
+                seen.remove(&observation.event_id);
+            }
+            keep
+        });
+        self.reported.retain(|_, reported_at| *reported_at > window_start);
+
+        let mut windows: HashMap<(AnomalyRule, &str), SourceWindow> = HashMap::new();
+        for observation in &self.observations {
+            let window = windows
+                .entry((observation.rule, observation.source.as_str()))
+                .or_insert(SourceWindow {
+                    count: 0,
+                    first_seen: observation.at,
+                    last_seen: observation.at,
+                });
+            window.count += 1;
+            window.first_seen = window.first_seen.min(observation.at);
+            window.last_seen = window.last_seen.max(observation.at);
+        }
+
+        let window_minutes = (self.settings.window.num_seconds().max(1) as f64) / 60.0;
+        let mut anomalies = Vec::new();
+        for ((rule, source), window) in windows {
+            let threshold = self.settings.threshold(rule);
+            if threshold == 0 || window.count < threshold || self.reported.contains_key(&(rule, source.to_string())) {
+                continue;
+            }
+            anomalies.push(Anomaly {
+                rule,
+                source: source.to_string(),
+                count: window.count,
+                threshold,
+                window_seconds: self.settings.window.num_seconds(),
+                rate_per_minute: window.count as f64 / window_minutes,
+                first_seen: window.first_seen,
+                last_seen: window.last_seen,
+            });
+        }
+
+        for anomaly in &anomalies {
+            self.reported.insert((anomaly.rule, anomaly.source.clone()), now);
+        }
+        anomalies.sort_by_key(|anomaly| anomaly.first_seen);
+        anomalies
+    }
+}
+
+/// Tail the audit stream for anomalies while security alerts are enabled.
+/// Each anomaly is recorded as a suspicious activity event and sent to the
+/// alert sink.
+pub fn spawn_anomaly_detection_job(state: AppState, alert_sink: Arc<dyn AlertSink>) {
+    if !state.config.security_alerts_enabled {
+        return;
+    }
+
+    let period = std::time::Duration::from_secs(state.config.anomaly_detection_interval_seconds);
+    state.job_monitor.register(ANOMALY_DETECTION_JOB, period);
+
+    tokio::spawn(async move {
+        let mut detector = AnomalyDetector::new(DetectionSettings::from_config(&state.config));
+        let mut cursor = Utc::now();
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let events = match state.audit_logger.events_since(cursor, AUDIT_BATCH_SIZE).await {
+                Ok(events) => events,
+                Err(e) => {
+                    state.job_monitor.record_failure(ANOMALY_DETECTION_JOB, e.to_string());
+                    tracing::error!("Anomaly detection could not read the audit stream: {}", e);
+                    continue;
+                }
+            };
+            if let Some(last) = events.last() {
+                cursor = cursor.max(last.timestamp);
+            }
+
+            for anomaly in detector.observe(&events, Utc::now()) {
+                report(&state, alert_sink.as_ref(), &anomaly).await;
+            }
+            state.job_monitor.record_success(ANOMALY_DETECTION_JOB);
+        }
+    });
+}
+
+async fn report(state: &AppState, alert_sink: &dyn AlertSink, anomaly: &Anomaly) {
+    let message = format!(
+        "{} {} from {} in the last {} seconds (threshold {})",
+        anomaly.count,
+        anomaly.rule.description(),
+        anomaly.source,
+        anomaly.window_seconds,
+        anomaly.threshold
+    );
+    tracing::warn!("Anomaly detected: {}", message);
+
+    let mut event = AuditEvent::new(AuditEventType::SuspiciousActivity)
+        .severity(AuditSeverity::Critical)
+        .resource(anomaly.source.clone())
+        .action("detect".to_string())
+        .success(false)
+        .error(message.clone())
+        .metadata("anomaly".to_string(), serde_json::json!(anomaly))
+        .risk_score(80)
+        .compliance_tag("SECURITY".to_string());
+    if let Some(ip) = anomaly.source.strip_prefix("ip:") {
+        event = event.ip_address(ip.to_string());
+    }
+    if let Some(project_id) = anomaly.source.strip_prefix("project:").and_then(|id| id.parse().ok()) {
+        event = event.project_id(project_id);
+    }
+    state.audit_logger.log(event).await;
+
+    let alert = Alert {
+        title: format!("Unusual {}", anomaly.rule.description()),
+        message,
+        details: serde_json::json!(anomaly),
+        raised_at: Utc::now(),
+    };
+    if let Err(e) = alert_sink.send(&alert).await {
+        tracing::error!("Failed to deliver security alert: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> DetectionSettings {
+        DetectionSettings {
+            window: Duration::minutes(5),
+            failed_login_threshold: 3,
+            scope_violation_threshold: 2,
+            access_denied_threshold: 5,
+        }
+    }
+
+    fn failed_login(ip: &str, at: DateTime<Utc>) -> AuditEvent {
+        let mut event = AuditEvent::new(AuditEventType::LoginFailure).ip_address(ip.to_string());
+        event.timestamp = at;
+        event
+    }
+
+    #[test]
+    fn reports_a_source_once_per_window() {
+        let now = Utc::now();
+        let mut detector = AnomalyDetector::new(settings());
+
+        let first = vec![failed_login("10.0.0.1", now), failed_login("10.0.0.1", now)];
+        assert!(detector.observe(&first, now).is_empty());
+        // Events read again are not counted twice
+        assert!(detector.observe(&first, now).is_empty());
+
+        let anomalies = detector.observe(&[failed_login("10.0.0.1", now), failed_login("10.0.0.2", now)], now);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].rule, AnomalyRule::FailedLogins);
+        assert_eq!(anomalies[0].source, "ip:10.0.0.1");
+        assert_eq!(anomalies[0].count, 3);
+
+        assert!(detector.observe(&[failed_login("10.0.0.1", now)], now).is_empty());
+    }
+
+    #[test]
+    fn events_leave_the_window() {
+        let now = Utc::now();
+        let mut detector = AnomalyDetector::new(settings());
+
+        let old = now - Duration::minutes(10);
+        let events = vec![failed_login("10.0.0.1", old), failed_login("10.0.0.1", old)];
+        assert!(detector.observe(&events, now).is_empty());
+        assert!(detector.observe(&[failed_login("10.0.0.1", now)], now).is_empty());
+
+        // A report is repeated once the previous one has left the window
+        let burst: Vec<_> = (0..3).map(|_| failed_login("10.0.0.1", now)).collect();
+        assert_eq!(detector.observe(&burst, now).len(), 1);
+        let later = now + Duration::minutes(6);
+        let burst: Vec<_> = (0..3).map(|_| failed_login("10.0.0.1", later)).collect();
+        assert_eq!(detector.observe(&burst, later).len(), 1);
+    }
+
+    #[test]
+    fn scope_violations_count_per_project() {
+        let now = Utc::now();
+        let mut detector = AnomalyDetector::new(settings());
+        let project_id = Uuid::new_v4();
+
+        let violation = |ip: &str| {
+            AuditEvent::new(AuditEventType::ScopeViolation)
+                .project_id(project_id)
+                .ip_address(ip.to_string())
+        };
+        let anomalies = detector.observe(&[violation("10.0.0.1"), violation("10.0.0.2")], now);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].source, format!("project:{}", project_id));
+    }
+}
@@ -1,10 +1,18 @@
 use chrono::{DateTime, Utc};
-use mongodb::{Client, Collection};
+use mongodb::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tracing::{error, info};
 use uuid::Uuid;
+use crate::core::audit_chain::{AuditChain, ChainLink};
+use crate::core::audit_writer::{AuditWriter, AuditWriterSettings, AuditWriterStats};
+use crate::core::circuit_breaker::CircuitBreaker;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::core::siem::{SiemForwarder, SiemForwarderStats};
+use crate::core::AppState;
 
 /// Audit event types for authentication and authorization
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,12 +28,19 @@ pub enum AuditEventType {
     TokenRevoked,
     TokenValidated,
     TokenExpired,
+    StepUpChallenged,
 
     // Authorization Events
     AccessGranted,
     AccessDenied,
     ScopeValidated,
     ScopeViolation,
+    CustomRoleCreated,
+    CustomRoleUpdated,
+    CustomRoleDeleted,
+    CustomRoleAssigned,
+    CustomRoleRevoked,
+    SystemRoleGranted,
 
     // Account Management
     DeveloperRegistered,
@@ -33,6 +48,14 @@ pub enum AuditEventType {
     ProjectCreated,
     ProjectUpdated,
     ProjectDeactivated,
+    ProjectIpRulesChanged,
+    ProjectDuplicatePaymentRulesChanged,
+    ProjectLocaleChanged,
+    ProjectApiVersionPolicyChanged,
+    ProjectDebugCaptureChanged,
+    DebugCaptureViewed,
+    AccountNumberSchemeChanged,
+    MetadataSchemaChanged,
 
     // Security Events
     RateLimitExceeded,
@@ -42,6 +65,133 @@ pub enum AuditEventType {
     PasswordChanged,
     MfaEnabled,
     MfaDisabled,
+    IpAccessBlocked,
+    DeviceRegistered,
+    DeviceRevoked,
+    DeviceAssertionRejected,
+    ContactVerificationRequested,
+    ContactVerified,
+    ContactVerificationFailed,
+
+    // Payment Events
+    PaymentApproved,
+    PaymentRejected,
+    PaymentCallbackReceived,
+    PaymentCallbackRejected,
+
+    // Dispute Events
+    DisputeOpened,
+    DisputeEvidenceAdded,
+    DisputeStatusChanged,
+
+    // Account Control Events
+    AccountFrozen,
+    AccountUnfrozen,
+    AccountClosed,
+
+    // Income Verification Events
+    EmployerConfirmationRequested,
+    EmployerConfirmationReceived,
+    EmployerConfirmationExpired,
+    IncomeVerificationInitiated,
+    IncomeDocumentUploaded,
+    AffordabilityAssessed,
+    CreditCheckRequested,
+    CreditCheckCompleted,
+    CreditReportPurged,
+
+    // Address Events
+    AddressUpdated,
+    AddressVerified,
+    AddressVerificationFailed,
+
+    // Screening Events
+    ScreeningCompleted,
+    ScreeningMatchFound,
+
+    // Verification Session Events
+    VerificationSessionCreated,
+    VerificationSessionStepSubmitted,
+    VerificationSessionDecided,
+    VerificationSessionExpired,
+
+    // Direct Upload Events
+    DirectUploadIssued,
+
+    // Verification Review Events
+    VerificationFlaggedForReview,
+    VerificationReviewClaimed,
+    VerificationReviewReleased,
+    VerificationEvidenceViewed,
+    VerificationReviewDecided,
+
+    // Notification Events
+    NotificationPreferencesUpdated,
+
+    // Report Subscription Events
+    ReportSubscriptionCreated,
+    ReportSubscriptionUpdated,
+    ReportSubscriptionDeleted,
+
+    // Savings Goal Events
+    SavingsGoalCreated,
+    SavingsGoalUpdated,
+    SavingsGoalFundsMoved,
+    SavingsGoalClosed,
+
+    // Fee Events
+    FeeScheduleChanged,
+
+    // Tenancy Events
+    CrossTenantAccessDenied,
+
+    // Organization Events
+    OrganizationCreated,
+    OrganizationMemberInvited,
+    OrganizationInvitationRevoked,
+    OrganizationInvitationAccepted,
+    OrganizationMemberRoleChanged,
+    OrganizationMemberRemoved,
+
+    // Interest Events
+    InterestRateChanged,
+    InterestCapitalized,
+
+    // Reconciliation Events
+    ReconciliationRunCompleted,
+    ReconciliationBreakResolved,
+
+    // Ledger Events
+    LedgerIntegrityChecked,
+    GlAccountCreated,
+    GlPostingRuleChanged,
+
+    // Treasury Events
+    TreasurySnapshotTaken,
+    TreasuryPositionsFrozen,
+
+    // Audit Chain Events
+    AuditChainVerified,
+
+    // Regulatory Reporting Events
+    RegulatoryReportGenerated,
+    RegulatoryReportDownloaded,
+    RegulatoryReportStatusChanged,
+
+    // Usage Events
+    QuotaExceeded,
+    QuotaOverridden,
+
+    // Webhook Events
+    WebhookDeadLettered,
+    WebhookReplayed,
+    EventRedelivered,
+
+    // Feature Flag Events
+    FeatureFlagCreated,
+    FeatureFlagUpdated,
+    FeatureFlagDeleted,
+    FeatureFlagTargetChanged,
 
     // System Events
     ConfigurationChanged,
@@ -53,6 +203,8 @@ pub enum AuditEventType {
     DataDeleted,
     ConsentGranted,
     ConsentRevoked,
+    KycTierChanged,
+    VerificationExpired,
 }
 
 /// Audit event severity levels
@@ -121,6 +273,10 @@ pub struct AuditEvent {
 
     /// Risk score (0-100)
     pub risk_score: Option<u8>,
+
+    /// Place in the logger's hash chain, set as the event is logged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainLink>,
 }
 
 impl AuditEvent {
@@ -144,6 +300,7 @@ impl AuditEvent {
             changes: None,
             compliance_tags: Vec::new(),
             risk_score: None,
+            chain: None,
         }
     }
 
@@ -216,22 +373,96 @@ impl AuditEvent {
     }
 }
 
-/// Audit logger service
+/// Where audit events are stored
+#[derive(Clone)]
+enum AuditSink {
+    Mongo(AuditWriter),
+    /// Kept in memory, for tests and local tooling without MongoDB
+    Memory(Arc<Mutex<Vec<AuditEvent>>>),
+}
+
+/// Audit logger service. Every event logged is hash-chained to the one
+/// before it in the logger's stream.
 #[derive(Clone)]
 pub struct AuditLogger {
-    collection: Collection<AuditEvent>,
+    sink: AuditSink,
+    chain: Arc<Mutex<AuditChain>>,
+    forwarder: Option<SiemForwarder>,
 }
 
 impl AuditLogger {
-    pub fn new(mongodb_client: Client) -> Self {
+    /// A logger writing to MongoDB from a background task; needs a Tokio
+    /// runtime
+    pub fn new(mongodb_client: Client, breaker: CircuitBreaker, settings: AuditWriterSettings) -> Self {
         let db = mongodb_client.database("openbank_audit");
         let collection = db.collection::<AuditEvent>("audit_events");
 
-        Self { collection }
+        Self {
+            sink: AuditSink::Mongo(AuditWriter::spawn(collection, breaker, settings)),
+            chain: Arc::new(Mutex::new(AuditChain::new())),
+            forwarder: None,
+        }
+    }
+
+    /// A logger that keeps events in memory instead of MongoDB
+    pub fn in_memory() -> Self {
+        Self {
+            sink: AuditSink::Memory(Arc::new(Mutex::new(Vec::new()))),
+            chain: Arc::new(Mutex::new(AuditChain::new())),
+            forwarder: None,
+        }
+    }
+
+    /// Also forward events to a SIEM
+    pub fn with_forwarder(mut self, forwarder: SiemForwarder) -> Self {
+        self.forwarder = Some(forwarder);
+        self
+    }
+
+    /// How SIEM forwarding is keeping up; `None` when it is off
+    pub fn forwarder_stats(&self) -> Option<SiemForwarderStats> {
+        self.forwarder.as_ref().map(SiemForwarder::stats)
+    }
+
+    /// Events recorded by an in-memory logger, oldest first; empty for MongoDB
+    pub fn recorded_events(&self) -> Vec<AuditEvent> {
+        match &self.sink {
+            AuditSink::Mongo(_) => Vec::new(),
+            AuditSink::Memory(events) => events.lock().unwrap().clone(),
+        }
+    }
+
+    /// How the background writer is keeping up; `None` for an in-memory
+    /// logger
+    pub fn writer_stats(&self) -> Option<AuditWriterStats> {
+        match &self.sink {
+            AuditSink::Mongo(writer) => Some(writer.stats()),
+            AuditSink::Memory(_) => None,
+        }
+    }
+
+    /// Write events spilled to disk during a MongoDB outage, returning how
+    /// many were written
+    pub async fn replay_spilled(&self) -> AppResult<usize> {
+        match &self.sink {
+            AuditSink::Mongo(writer) => writer.replay_spilled().await,
+            AuditSink::Memory(_) => Ok(0),
+        }
+    }
+
+    /// The stream this logger chains its events in
+    pub fn chain_stream_id(&self) -> Uuid {
+        self.chain.lock().unwrap().stream_id()
+    }
+
+    /// The link of the last event logged, if any
+    pub fn chain_head(&self) -> Option<ChainLink> {
+        self.chain.lock().unwrap().head().cloned()
     }
 
     /// Log an audit event
-    pub async fn log(&self, event: AuditEvent) {
+    pub async fn log(&self, mut event: AuditEvent) {
+        self.chain.lock().unwrap().link(&mut event);
         info!(
             event_id = %event.id,
             event_type = ?event.event_type,
@@ -239,18 +470,13 @@ impl AuditLogger {
             success = event.success,
             "Audit event logged"
         );
+        if let Some(forwarder) = &self.forwarder {
+            forwarder.submit(&event);
+        }
 
-        match self.collection.insert_one(&event, None).await {
-            Ok(_) => {
-                info!(event_id = %event.id, "Audit event stored in database");
-            }
-            Err(e) => {
-                error!(
-                    event_id = %event.id,
-                    error = %e,
-                    "Failed to store audit event in database"
-                );
-            }
+        match &self.sink {
+            AuditSink::Mongo(writer) => writer.submit(event).await,
+            AuditSink::Memory(events) => events.lock().unwrap().push(event),
         }
     }
 
@@ -378,7 +604,25 @@ impl AuditLogger {
         compliance_tag: Option<String>,
         user_id: Option<Uuid>,
     ) -> Result<Vec<AuditEvent>, mongodb::error::Error> {
-        use mongodb::bson::doc;
+        use mongodb::{bson::doc, options::FindOptions};
+
+        let collection = match &self.sink {
+            AuditSink::Mongo(writer) => writer.collection(),
+            AuditSink::Memory(events) => {
+                let events = events.lock().unwrap();
+                return Ok(events
+                    .iter()
+                    .filter(|event| event.timestamp >= start_date && event.timestamp <= end_date)
+                    .filter(|event| {
+                        compliance_tag
+                            .as_ref()
+                            .is_none_or(|tag| event.compliance_tags.contains(tag))
+                    })
+                    .filter(|event| user_id.is_none_or(|id| event.user_id == Some(id)))
+                    .cloned()
+                    .collect());
+            }
+        };
 
         let mut filter = doc! {
             "timestamp": {
@@ -395,7 +639,174 @@ impl AuditLogger {
             filter.insert("user_id", id.to_string());
         }
 
-        let mut cursor = self.collection.find(filter, None).await?;
+        // Let MongoDB stop the query once the request's deadline passes
+        let options = FindOptions::builder().max_time(deadline::remaining()).build();
+        let mut cursor = collection.find(filter, options).await?;
+        let mut results = Vec::new();
+        while cursor.advance().await? {
+            results.push(cursor.deserialize_current()?);
+        }
+        Ok(results)
+    }
+
+    /// Events recorded from `since` on, oldest first, at most `limit`. Used to
+    /// tail the audit stream; stored timestamps compare as RFC 3339 text, so
+    /// earlier events from the same second may be returned and callers should
+    /// dedupe by id.
+    pub async fn events_since(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, mongodb::error::Error> {
+        use mongodb::{bson::doc, options::FindOptions};
+
+        let collection = match &self.sink {
+            AuditSink::Mongo(writer) => writer.collection(),
+            AuditSink::Memory(events) => {
+                let events = events.lock().unwrap();
+                return Ok(events
+                    .iter()
+                    .filter(|event| event.timestamp > since)
+                    .take(usize::try_from(limit).unwrap_or(0))
+                    .cloned()
+                    .collect());
+            }
+        };
+
+        let filter = doc! {
+            // Every timestamp within the same second sorts after this prefix
+            "timestamp": { "$gte": since.format("%Y-%m-%dT%H:%M:%S").to_string() }
+        };
+        let options = FindOptions::builder().sort(doc! { "timestamp": 1 }).limit(limit).build();
+
+        let mut cursor = collection.find(filter, options).await?;
+        let mut results = Vec::new();
+        while cursor.advance().await? {
+            results.push(cursor.deserialize_current()?);
+        }
+        Ok(results)
+    }
+
+    /// A user's events of the given types, newest first, skipping `skip` and
+    /// returning at most `limit`, with how many there are in all
+    pub async fn user_events(
+        &self,
+        user_id: Uuid,
+        event_types: &[AuditEventType],
+        skip: u64,
+        limit: i64,
+    ) -> Result<(Vec<AuditEvent>, u64), mongodb::error::Error> {
+        use mongodb::{bson::doc, options::{CountOptions, FindOptions}};
+
+        let type_names: Vec<String> = event_types
+            .iter()
+            .filter_map(|event_type| serde_json::to_value(event_type).ok())
+            .filter_map(|value| value.as_str().map(str::to_string))
+            .collect();
+        let collection = match &self.sink {
+            AuditSink::Mongo(writer) => writer.collection(),
+            AuditSink::Memory(events) => {
+                let events = events.lock().unwrap();
+                let matching: Vec<&AuditEvent> = events
+                    .iter()
+                    .rev()
+                    .filter(|event| event.user_id == Some(user_id))
+                    .filter(|event| {
+                        serde_json::to_value(&event.event_type)
+                            .ok()
+                            .and_then(|value| value.as_str().map(|name| type_names.iter().any(|t| t == name)))
+                            .unwrap_or(false)
+                    })
+                    .collect();
+                let total = matching.len() as u64;
+                return Ok((
+                    matching
+                        .into_iter()
+                        .skip(usize::try_from(skip).unwrap_or(usize::MAX))
+                        .take(usize::try_from(limit).unwrap_or(0))
+                        .cloned()
+                        .collect(),
+                    total,
+                ));
+            }
+        };
+
+        let filter = doc! {
+            "user_id": user_id.to_string(),
+            "event_type": { "$in": type_names }
+        };
+        let total = collection
+            .count_documents(
+                filter.clone(),
+                CountOptions::builder().max_time(deadline::remaining()).build(),
+            )
+            .await?;
+        let options = FindOptions::builder()
+            .sort(doc! { "timestamp": -1 })
+            .skip(skip)
+            .limit(limit)
+            .max_time(deadline::remaining())
+            .build();
+
+        let mut cursor = collection.find(filter, options).await?;
+        let mut results = Vec::new();
+        while cursor.advance().await? {
+            results.push(cursor.deserialize_current()?);
+        }
+        Ok((results, total))
+    }
+
+    /// An event by its id
+    pub async fn find_event(&self, id: Uuid) -> Result<Option<AuditEvent>, mongodb::error::Error> {
+        use mongodb::bson::doc;
+
+        match &self.sink {
+            AuditSink::Mongo(writer) => writer.collection().find_one(doc! { "id": id.to_string() }, None).await,
+            AuditSink::Memory(events) => Ok(events.lock().unwrap().iter().find(|event| event.id == id).cloned()),
+        }
+    }
+
+    /// A stream's events after `after_sequence`, in sequence order, at most
+    /// `limit`
+    pub async fn chain_events(
+        &self,
+        stream_id: Uuid,
+        after_sequence: i64,
+        limit: i64,
+    ) -> Result<Vec<AuditEvent>, mongodb::error::Error> {
+        use mongodb::{bson::doc, options::FindOptions};
+
+        let collection = match &self.sink {
+            AuditSink::Mongo(writer) => writer.collection(),
+            AuditSink::Memory(events) => {
+                let events = events.lock().unwrap();
+                let mut chained: Vec<AuditEvent> = events
+                    .iter()
+                    .filter(|event| {
+                        event
+                            .chain
+                            .as_ref()
+                            .is_some_and(|link| link.stream_id == stream_id && link.sequence > after_sequence)
+                    })
+                    .cloned()
+                    .collect();
+                chained.sort_by_key(|event| event.chain.as_ref().map(|link| link.sequence));
+                chained.truncate(usize::try_from(limit).unwrap_or(0));
+                return Ok(chained);
+            }
+        };
+
+        let filter = doc! {
+            "chain.stream_id": stream_id.to_string(),
+            "chain.sequence": { "$gt": after_sequence }
+        };
+        let options = FindOptions::builder()
+            .sort(doc! { "chain.sequence": 1 })
+            .limit(limit)
+            .max_time(deadline::remaining())
+            .build();
+
+        let mut cursor = collection.find(filter, options).await?;
         let mut results = Vec::new();
         while cursor.advance().await? {
             results.push(cursor.deserialize_current()?);
@@ -404,6 +815,33 @@ impl AuditLogger {
     }
 }
 
+const AUDIT_REPLAY_JOB: &str = "audit_spill_replay";
+
+/// Periodically write audit events spilled to disk during a MongoDB outage
+pub fn spawn_audit_replay_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.audit_spill_replay_interval_seconds);
+    state.job_monitor.register(AUDIT_REPLAY_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match state.audit_logger.replay_spilled().await {
+                Ok(count) => {
+                    state.job_monitor.record_success(AUDIT_REPLAY_JOB);
+                    if count > 0 {
+                        info!("Replayed {} spilled audit events", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(AUDIT_REPLAY_JOB, e.to_string());
+                    error!("Audit spill replay failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
 /// Middleware to extract request context for audit logging
 pub fn extract_audit_context(req: &axum::extract::Request) -> AuditContext {
     let ip = req
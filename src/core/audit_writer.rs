@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use mongodb::Collection;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::Mutex;
+use tracing::{error, warn};
+use crate::core::audit::AuditEvent;
+use crate::core::circuit_breaker::CircuitBreaker;
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+
+#[derive(Debug, Clone)]
+pub struct AuditWriterSettings {
+    /// Events waiting for the writer before new ones go straight to disk
+    pub queue_capacity: usize,
+    /// Most events written to MongoDB in one insert
+    pub batch_size: usize,
+    /// JSON-lines file holding events MongoDB could not take
+    pub spill_path: PathBuf,
+}
+
+impl AuditWriterSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            queue_capacity: config.audit_queue_capacity.max(1),
+            batch_size: config.audit_batch_size.max(1),
+            spill_path: PathBuf::from(&config.audit_spill_path),
+        }
+    }
+}
+
+/// How the writer is keeping up, for health checks
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditWriterStats {
+    pub queued: usize,
+    pub queue_capacity: usize,
+    pub written: u64,
+    pub spilled: u64,
+    /// Events spilled straight to disk because the queue was full
+    pub backpressured: u64,
+    pub replayed: u64,
+    /// Size of the spill file still to be replayed
+    pub spill_bytes: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    written: AtomicU64,
+    spilled: AtomicU64,
+    backpressured: AtomicU64,
+    replayed: AtomicU64,
+}
+
+/// What the background writer and the loggers share
+struct Shared {
+    collection: Collection<AuditEvent>,
+    breaker: CircuitBreaker,
+    spill_path: PathBuf,
+    /// Serializes appends to the spill file with its replay
+    spill_lock: Mutex<()>,
+    batch_size: usize,
+    counters: Counters,
+}
+
+impl Shared {
+    /// Insert a batch, spilling it when MongoDB is unavailable
+    async fn write(&self, batch: Vec<AuditEvent>) {
+        if !self.breaker.try_acquire() {
+            self.spill(batch).await;
+            return;
+        }
+
+        match self.collection.insert_many(&batch, None).await {
+            Ok(_) => {
+                self.breaker.record_success();
+                self.counters.written.fetch_add(batch.len() as u64, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.breaker.record_failure();
+                error!(error = %e, count = batch.len(), "Failed to store audit events, spilling them to disk");
+                self.spill(batch).await;
+            }
+        }
+    }
+
+    async fn spill(&self, events: Vec<AuditEvent>) {
+        let _guard = self.spill_lock.lock().await;
+        match append_lines(&self.spill_path, &events).await {
+            Ok(()) => {
+                self.counters.spilled.fetch_add(events.len() as u64, Ordering::Relaxed);
+            }
+            Err(e) => {
+                // Last resort: the log keeps what neither store could
+                for event in &events {
+                    error!(
+                        error = %e,
+                        event = %serde_json::to_string(event).unwrap_or_default(),
+                        "Audit event could not be stored or spilled"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Writes audit events to MongoDB from a background task, so logging never
+/// waits on MongoDB. Events are batched off a bounded queue; when the queue
+/// is full or MongoDB is unavailable they are appended to a spill file on
+/// disk and replayed once it recovers.
+#[derive(Clone)]
+pub struct AuditWriter {
+    sender: mpsc::Sender<AuditEvent>,
+    queue_capacity: usize,
+    shared: Arc<Shared>,
+}
+
+impl AuditWriter {
+    /// Start the writer task; needs a Tokio runtime
+    pub fn spawn(collection: Collection<AuditEvent>, breaker: CircuitBreaker, settings: AuditWriterSettings) -> Self {
+        let (sender, receiver) = mpsc::channel(settings.queue_capacity);
+        let shared = Arc::new(Shared {
+            collection,
+            breaker,
+            spill_path: settings.spill_path,
+            spill_lock: Mutex::new(()),
+            batch_size: settings.batch_size,
+            counters: Counters::default(),
+        });
+        tokio::spawn(run(shared.clone(), receiver));
+
+        Self {
+            sender,
+            queue_capacity: settings.queue_capacity,
+            shared,
+        }
+    }
+
+    pub fn collection(&self) -> &Collection<AuditEvent> {
+        &self.shared.collection
+    }
+
+    /// Queue an event, spilling it at once when the queue is full
+    pub async fn submit(&self, event: AuditEvent) {
+        match self.sender.try_send(event) {
+            Ok(()) => {}
+            Err(TrySendError::Full(event)) => {
+                self.shared.counters.backpressured.fetch_add(1, Ordering::Relaxed);
+                warn!(event_id = %event.id, "Audit queue full, spilling event to disk");
+                self.shared.spill(vec![event]).await;
+            }
+            Err(TrySendError::Closed(event)) => self.shared.spill(vec![event]).await,
+        }
+    }
+
+    /// Write spilled events to MongoDB, oldest first, returning how many
+    /// were written. Events stay spilled while the breaker is open; after a
+    /// failed insert only the events not yet written stay.
+    pub async fn replay_spilled(&self) -> AppResult<usize> {
+        let shared = &self.shared;
+        let _guard = shared.spill_lock.lock().await;
+        let data = match tokio::fs::read(&shared.spill_path).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(AppError::Internal(format!("Failed to read audit spill file: {}", e))),
+        };
+
+        let events: Vec<AuditEvent> = data
+            .split(|byte| *byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .filter_map(|line| match serde_json::from_slice(line) {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    error!(error = %e, line = %String::from_utf8_lossy(line), "Skipping unreadable spilled audit event");
+                    None
+                }
+            })
+            .collect();
+        if events.is_empty() {
+            remove_spill(&shared.spill_path).await?;
+            return Ok(0);
+        }
+        if !shared.breaker.try_acquire() {
+            return Ok(0);
+        }
+
+        let mut written = 0;
+        for chunk in events.chunks(shared.batch_size) {
+            if let Err(e) = shared.collection.insert_many(chunk, None).await {
+                shared.breaker.record_failure();
+                rewrite_lines(&shared.spill_path, &events[written..])
+                    .await
+                    .map_err(|e| AppError::Internal(format!("Failed to rewrite audit spill file: {}", e)))?;
+                shared.counters.replayed.fetch_add(written as u64, Ordering::Relaxed);
+                return Err(e.into());
+            }
+            written += chunk.len();
+        }
+        shared.breaker.record_success();
+        remove_spill(&shared.spill_path).await?;
+        shared.counters.replayed.fetch_add(written as u64, Ordering::Relaxed);
+        Ok(written)
+    }
+
+    pub fn stats(&self) -> AuditWriterStats {
+        let counters = &self.shared.counters;
+        AuditWriterStats {
+            queued: self.queue_capacity - self.sender.capacity(),
+            queue_capacity: self.queue_capacity,
+            written: counters.written.load(Ordering::Relaxed),
+            spilled: counters.spilled.load(Ordering::Relaxed),
+            backpressured: counters.backpressured.load(Ordering::Relaxed),
+            replayed: counters.replayed.load(Ordering::Relaxed),
+            spill_bytes: std::fs::metadata(&self.shared.spill_path)
+                .map(|metadata| metadata.len())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Take whatever is queued, up to a batch, and write it
+async fn run(shared: Arc<Shared>, mut receiver: mpsc::Receiver<AuditEvent>) {
+    let mut batch = Vec::with_capacity(shared.batch_size);
+    while receiver.recv_many(&mut batch, shared.batch_size).await > 0 {
+        shared.write(std::mem::take(&mut batch)).await;
+    }
+}
+
+fn encode_lines(events: &[AuditEvent]) -> std::io::Result<Vec<u8>> {
+    let mut lines = Vec::new();
+    for event in events {
+        serde_json::to_writer(&mut lines, event)?;
+        lines.push(b'\n');
+    }
+    Ok(lines)
+}
+
+async fn append_lines(path: &Path, events: &[AuditEvent]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(path).await?;
+    file.write_all(&encode_lines(events)?).await?;
+    file.sync_data().await
+}
+
+/// Replace the spill file's contents, through a rename so a crash leaves
+/// either the old or the new file
+async fn rewrite_lines(path: &Path, events: &[AuditEvent]) -> std::io::Result<()> {
+    let staging = path.with_extension("tmp");
+    let mut file = tokio::fs::File::create(&staging).await?;
+    file.write_all(&encode_lines(events)?).await?;
+    file.sync_data().await?;
+    tokio::fs::rename(&staging, path).await
+}
+
+async fn remove_spill(path: &Path) -> AppResult<()> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(AppError::Internal(format!("Failed to remove audit spill file: {}", e))),
+    }
+}
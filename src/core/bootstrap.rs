@@ -0,0 +1,169 @@
+use chrono::Utc;
+use serde_json::json;
+use tracing::info;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType};
+use crate::core::error::{AppError, AppResult};
+use crate::core::AppState;
+
+/// System roles the bootstrap admin holds
+pub const BOOTSTRAP_ROLES: &[&str] = &["super_admin"];
+
+/// Shortest password accepted for the bootstrap admin, as for registration
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// What a bootstrap run found or created
+#[derive(Debug, Clone)]
+pub struct BootstrapReport {
+    pub admin_id: Uuid,
+    pub organization_id: Uuid,
+    /// Whether the admin and their organization were created on this run
+    pub admin_created: bool,
+    /// Roles granted on this run; roles already held are not listed
+    pub roles_granted: Vec<String>,
+}
+
+/// Create the first admin developer, their organization and their system
+/// roles when `BOOTSTRAP_ENABLED` is set. Safe to run on every start: an
+/// existing admin is left as is, apart from regaining any missing role.
+pub async fn run(state: &AppState) -> AppResult<Option<BootstrapReport>> {
+    let config = &state.config;
+    if !config.bootstrap_enabled {
+        return Ok(None);
+    }
+    let email = config
+        .bootstrap_admin_email
+        .as_deref()
+        .filter(|email| !email.is_empty())
+        .ok_or_else(|| AppError::Validation("BOOTSTRAP_ADMIN_EMAIL must be set when bootstrapping".to_string()))?;
+
+    let mut tx = state.postgres.begin().await?;
+    // Replicas starting together would otherwise race to create the admin
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('openbank_bootstrap'))")
+        .execute(&mut *tx)
+        .await?;
+
+    let existing: Option<(Uuid, Uuid)> =
+        sqlx::query_as("SELECT id, organization_id FROM developers WHERE email = $1")
+            .bind(email)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+    let (admin_id, organization_id, admin_created) = match existing {
+        Some((admin_id, organization_id)) => (admin_id, organization_id, false),
+        None => {
+            let password = config
+                .bootstrap_admin_password
+                .as_deref()
+                .filter(|password| password.len() >= MIN_PASSWORD_LENGTH)
+                .ok_or_else(|| {
+                    AppError::Validation(format!(
+                        "BOOTSTRAP_ADMIN_PASSWORD of at least {} characters is needed to create the bootstrap admin",
+                        MIN_PASSWORD_LENGTH
+                    ))
+                })?;
+            let password_hash = bcrypt::hash(password, config.bcrypt_cost)
+                .map_err(|_| AppError::Internal("Failed to hash password".to_string()))?;
+
+            let admin_id = Uuid::new_v4();
+            let organization_id = Uuid::new_v4();
+            let now = Utc::now();
+            sqlx::query(
+                "INSERT INTO organizations (id, name, is_active, created_at, updated_at) VALUES ($1, $2, TRUE, $3, $3)",
+            )
+            .bind(organization_id)
+            .bind(&config.bootstrap_organization_name)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                "INSERT INTO developers (id, name, email, organization_id, password_hash, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $6)",
+            )
+            .bind(admin_id)
+            .bind(&config.bootstrap_admin_name)
+            .bind(email)
+            .bind(organization_id)
+            .bind(&password_hash)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                "INSERT INTO organization_members (organization_id, developer_id, role, created_at, updated_at)
+                 VALUES ($1, $2, 'owner', $3, $3)",
+            )
+            .bind(organization_id)
+            .bind(admin_id)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+
+            (admin_id, organization_id, true)
+        }
+    };
+
+    // Insert missing roles and revive expired ones; untouched rows return nothing
+    let mut roles_granted = Vec::new();
+    for role in BOOTSTRAP_ROLES {
+        let granted: Option<(Uuid,)> = sqlx::query_as(
+            "INSERT INTO user_roles (developer_id, role) VALUES ($1, $2)
+             ON CONFLICT (developer_id, role) DO UPDATE SET expires_at = NULL
+             WHERE user_roles.expires_at IS NOT NULL
+             RETURNING id",
+        )
+        .bind(admin_id)
+        .bind(role)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if granted.is_some() {
+            roles_granted.push(role.to_string());
+        }
+    }
+
+    tx.commit().await?;
+
+    let report = BootstrapReport {
+        admin_id,
+        organization_id,
+        admin_created,
+        roles_granted,
+    };
+    log_report(state, email, &report).await;
+    Ok(Some(report))
+}
+
+async fn log_report(state: &AppState, email: &str, report: &BootstrapReport) {
+    if report.admin_created {
+        info!(
+            admin_id = %report.admin_id,
+            organization_id = %report.organization_id,
+            "Bootstrap created admin {} and organization {}",
+            email,
+            state.config.bootstrap_organization_name
+        );
+        let organization = AuditEvent::new(AuditEventType::OrganizationCreated)
+            .resource(format!("organization:{}", report.organization_id))
+            .action("bootstrap".to_string());
+        state.audit_logger.log(organization).await;
+        let developer = AuditEvent::new(AuditEventType::DeveloperRegistered)
+            .user_id(report.admin_id)
+            .resource(format!("developer:{}", report.admin_id))
+            .action("bootstrap".to_string());
+        state.audit_logger.log(developer).await;
+    }
+
+    if !report.roles_granted.is_empty() {
+        info!(admin_id = %report.admin_id, roles = ?report.roles_granted, "Bootstrap granted system roles");
+        let roles = AuditEvent::new(AuditEventType::SystemRoleGranted)
+            .user_id(report.admin_id)
+            .resource(format!("developer:{}", report.admin_id))
+            .action("bootstrap".to_string())
+            .metadata("roles".to_string(), json!(report.roles_granted))
+            .compliance_tag("RBAC".to_string());
+        state.audit_logger.log(roles).await;
+    }
+
+    if !report.admin_created && report.roles_granted.is_empty() {
+        info!(admin_id = %report.admin_id, "Bootstrap admin already in place, nothing created");
+    }
+}
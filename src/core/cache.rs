@@ -0,0 +1,72 @@
+//! Short-lived per-project caches for settings read on every request
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+struct Cached<T> {
+    value: T,
+    loaded_at: Instant,
+}
+
+/// A project's settings kept in memory for `ttl` after they are loaded, so
+/// the middleware consulting them does not query the database on every
+/// request. Changing the settings invalidates the project's entry; other
+/// instances pick the change up once their entry expires.
+#[derive(Debug, Clone)]
+pub struct ProjectCache<T> {
+    entries: Arc<Mutex<HashMap<Uuid, Cached<T>>>>,
+    ttl: Duration,
+}
+
+impl<T: Clone> ProjectCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    pub fn get(&self, project_id: Uuid) -> Option<T> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(&project_id)
+            .filter(|cached| cached.loaded_at.elapsed() < self.ttl)
+            .map(|cached| cached.value.clone())
+    }
+
+    pub fn store(&self, project_id: Uuid, value: T) {
+        let cached = Cached {
+            value,
+            loaded_at: Instant::now(),
+        };
+        self.entries.lock().unwrap().insert(project_id, cached);
+    }
+
+    pub fn invalidate(&self, project_id: Uuid) {
+        self.entries.lock().unwrap().remove(&project_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_expire_and_can_be_invalidated() {
+        let project_id = Uuid::new_v4();
+
+        let cache = ProjectCache::new(Duration::from_secs(60));
+        assert_eq!(cache.get(project_id), None);
+        cache.store(project_id, 7);
+        assert_eq!(cache.get(project_id), Some(7));
+        cache.invalidate(project_id);
+        assert_eq!(cache.get(project_id), None);
+
+        let expired = ProjectCache::new(Duration::ZERO);
+        expired.store(project_id, 7);
+        assert_eq!(expired.get(project_id), None);
+    }
+}
@@ -0,0 +1,216 @@
+use axum::http::HeaderMap;
+use serde_json::{Map, Value};
+use crate::core::cache::ProjectCache;
+
+/// Placeholder stored in place of a redacted value
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers carrying credentials; their values are never captured
+const SECRET_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-client-secret",
+];
+
+/// Field names that are personal data or secrets wherever they appear,
+/// compared lowercased with `_` and `-` removed
+const SENSITIVE_FIELDS: &[&str] = &["pin", "cvv", "cvc", "ssn", "iban", "dob", "email", "phone", "address"];
+
+/// Fragments marking a field as sensitive, as in `client_secret` or
+/// `account_number`
+const SENSITIVE_FRAGMENTS: &[&str] = &[
+    "password",
+    "secret",
+    "token",
+    "accountnumber",
+    "cardnumber",
+    "taxid",
+    "nationalid",
+    "dateofbirth",
+    "firstname",
+    "lastname",
+    "holdername",
+];
+
+/// A project's debug capture settings as the middleware needs them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptureSettings {
+    pub enabled: bool,
+    /// Share of requests captured, in (0, 1]
+    pub sample_rate: f64,
+    pub retention_hours: i32,
+}
+
+impl CaptureSettings {
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            sample_rate: 1.0,
+            retention_hours: 24,
+        }
+    }
+
+    /// Whether to capture the request at hand
+    pub fn sample(&self) -> bool {
+        self.enabled && rand::random::<f64>() < self.sample_rate
+    }
+}
+
+/// Per-project capture settings, cached for sampling
+pub type CaptureSettingsCache = ProjectCache<CaptureSettings>;
+
+/// Headers as a JSON object, credentials redacted
+pub fn redact_headers(headers: &HeaderMap) -> Value {
+    let mut redacted = Map::new();
+    for (name, value) in headers {
+        let value = if SECRET_HEADERS.contains(&name.as_str()) {
+            REDACTED.to_string()
+        } else {
+            String::from_utf8_lossy(value.as_bytes()).into_owned()
+        };
+        redacted.insert(name.as_str().to_string(), Value::String(value));
+    }
+    Value::Object(redacted)
+}
+
+/// A query or form-encoded string with the values of sensitive keys redacted
+pub fn redact_query(query: &str) -> String {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if is_sensitive_field(key) => format!("{}={}", key, REDACTED),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// A body as stored in a capture. JSON and form bodies are redacted; other
+/// content is described rather than stored, and long bodies are cut at
+/// `max_bytes`.
+pub fn redact_body(content_type: Option<&str>, body: &[u8], max_bytes: usize) -> Option<String> {
+    if body.is_empty() {
+        return None;
+    }
+    let content_type = content_type.unwrap_or_default();
+
+    let text = if content_type.starts_with("application/json") || content_type.contains("+json") {
+        match serde_json::from_slice::<Value>(body) {
+            Ok(mut value) => {
+                redact_json(&mut value);
+                value.to_string()
+            }
+            // Kept as sent, since a malformed body is often what is being debugged
+            Err(_) => String::from_utf8_lossy(body).into_owned(),
+        }
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        redact_query(&String::from_utf8_lossy(body))
+    } else if content_type.starts_with("text/") {
+        String::from_utf8_lossy(body).into_owned()
+    } else {
+        let kind = if content_type.is_empty() { "unknown content" } else { content_type };
+        return Some(format!("[{} bytes of {} not captured]", body.len(), kind));
+    };
+
+    Some(truncate(text, max_bytes))
+}
+
+/// Redact sensitive fields and card-number-like strings throughout a JSON value
+pub fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                if is_sensitive_field(key) && !field.is_null() {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        Value::String(text) if looks_like_card_number(text) => *text = REDACTED.to_string(),
+        _ => {}
+    }
+}
+
+fn is_sensitive_field(key: &str) -> bool {
+    let normalized: String = key
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+    SENSITIVE_FIELDS.contains(&normalized.as_str())
+        || SENSITIVE_FRAGMENTS.iter().any(|fragment| normalized.contains(fragment))
+}
+
+/// 13 to 19 digits, optionally grouped by spaces or dashes
+fn looks_like_card_number(text: &str) -> bool {
+    let digits = text.chars().filter(char::is_ascii_digit).count();
+    (13..=19).contains(&digits) && text.chars().all(|c| c.is_ascii_digit() || c == ' ' || c == '-')
+}
+
+fn truncate(mut text: String, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let total = text.len();
+    text.truncate(end);
+    text.push_str(&format!("... [truncated, {} bytes]", total));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    #[test]
+    fn credentials_and_personal_data_are_redacted() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer abc"));
+        headers.insert("x-signature", HeaderValue::from_static("t=1,v1=ff"));
+        let headers = redact_headers(&headers);
+        assert_eq!(headers["authorization"], REDACTED);
+        assert_eq!(headers["x-signature"], "t=1,v1=ff");
+
+        let body = json!({
+            "amount": 1200,
+            "client_secret": "s3cret",
+            "recipient": {"firstName": "Ada", "account_number": "12345678", "email": null},
+            "cards": [{"number": "4111 1111 1111 1111", "expiry": "12/30"}]
+        });
+        let captured = redact_body(Some("application/json"), body.to_string().as_bytes(), 4096).unwrap();
+        let captured: Value = serde_json::from_str(&captured).unwrap();
+        assert_eq!(captured["amount"], 1200);
+        assert_eq!(captured["client_secret"], REDACTED);
+        assert_eq!(captured["recipient"]["firstName"], REDACTED);
+        assert_eq!(captured["recipient"]["account_number"], REDACTED);
+        assert!(captured["recipient"]["email"].is_null());
+        assert_eq!(captured["cards"][0]["number"], REDACTED);
+        assert_eq!(captured["cards"][0]["expiry"], "12/30");
+
+        assert_eq!(redact_query("limit=5&access_token=abc&pin"), "limit=5&access_token=[REDACTED]&pin");
+    }
+
+    #[test]
+    fn bodies_are_described_or_truncated() {
+        assert_eq!(redact_body(Some("application/json"), b"", 10), None);
+        assert_eq!(
+            redact_body(Some("image/png"), &[0u8; 42], 10).unwrap(),
+            "[42 bytes of image/png not captured]"
+        );
+        assert_eq!(redact_body(Some("application/json"), b"{\"amount\":", 64).unwrap(), "{\"amount\":");
+        assert_eq!(
+            redact_body(Some("text/plain"), "héllo world".as_bytes(), 2).unwrap(),
+            "h... [truncated, 12 bytes]"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+
+/// Where a circuit breaker stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls go through and their outcomes are counted
+    Closed,
+    /// The dependency is treated as down and calls fail immediately
+    Open,
+    /// After the open period a few trial calls decide whether to close
+    HalfOpen,
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerSettings {
+    /// Share of failed calls in the window that opens the circuit
+    pub failure_rate_threshold: f64,
+    /// Calls needed in the window before the failure rate counts
+    pub minimum_calls: usize,
+    pub window: Duration,
+    /// How long the circuit stays open before trial calls
+    pub open_duration: Duration,
+    /// Successful trial calls needed to close the circuit again
+    pub half_open_calls: u32,
+}
+
+impl CircuitBreakerSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            failure_rate_threshold: config.circuit_breaker_failure_rate,
+            minimum_calls: config.circuit_breaker_minimum_calls,
+            window: Duration::from_secs(config.circuit_breaker_window_seconds),
+            open_duration: Duration::from_secs(config.circuit_breaker_open_seconds),
+            half_open_calls: config.circuit_breaker_half_open_calls,
+        }
+    }
+}
+
+impl Default for CircuitBreakerSettings {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            minimum_calls: 10,
+            window: Duration::from_secs(60),
+            open_duration: Duration::from_secs(30),
+            half_open_calls: 3,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: CircuitState,
+    /// Outcomes in the window while closed, `true` for a failure
+    outcomes: VecDeque<(Instant, bool)>,
+    opened_at: Option<Instant>,
+    /// Trial calls started and succeeded while half-open
+    trials_started: u32,
+    trials_succeeded: u32,
+}
+
+/// Stops calling a dependency that keeps failing, so requests fail fast
+/// instead of each waiting on it. Clones share the same circuit.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    name: &'static str,
+    settings: CircuitBreakerSettings,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &'static str, settings: CircuitBreakerSettings) -> Self {
+        Self {
+            name,
+            settings,
+            inner: Arc::new(Mutex::new(Inner {
+                state: CircuitState::Closed,
+                outcomes: VecDeque::new(),
+                opened_at: None,
+                trials_started: 0,
+                trials_succeeded: 0,
+            })),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let mut inner = self.inner.lock().unwrap();
+        self.refresh(&mut inner, Instant::now());
+        inner.state
+    }
+
+    /// Whether a call may go ahead now. Each admitted call must be followed
+    /// by `record_success` or `record_failure`.
+    pub fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        self.refresh(&mut inner, Instant::now());
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen if inner.trials_started < self.settings.half_open_calls => {
+                inner.trials_started += 1;
+                true
+            }
+            CircuitState::HalfOpen => false,
+        }
+    }
+
+    pub fn record_success(&self) {
+        self.record(false, Instant::now());
+    }
+
+    pub fn record_failure(&self) {
+        self.record(true, Instant::now());
+    }
+
+    /// Run a call through the breaker. Dependency failures, meaning
+    /// `ExternalService` and `Timeout` errors, count against it; other
+    /// errors are answers from a working dependency.
+    pub async fn call<F, T>(&self, future: F) -> AppResult<T>
+    where
+        F: Future<Output = AppResult<T>>,
+    {
+        if !self.try_acquire() {
+            return Err(AppError::ExternalService(format!(
+                "{} is unavailable; calls are paused after repeated failures",
+                self.name
+            )));
+        }
+
+        let result = future.await;
+        match &result {
+            Err(AppError::ExternalService(_)) | Err(AppError::Timeout(_)) => self.record_failure(),
+            _ => self.record_success(),
+        }
+        result
+    }
+
+    fn record(&self, failed: bool, now: Instant) {
+        let mut inner = self.inner.lock().unwrap();
+        self.refresh(&mut inner, now);
+        match inner.state {
+            CircuitState::Closed => {
+                inner.outcomes.push_back((now, failed));
+                self.prune(&mut inner, now);
+                let calls = inner.outcomes.len();
+                let failures = inner.outcomes.iter().filter(|(_, failed)| *failed).count();
+                if calls >= self.settings.minimum_calls
+                    && failures as f64 / calls as f64 >= self.settings.failure_rate_threshold
+                {
+                    self.open(&mut inner, now);
+                }
+            }
+            CircuitState::HalfOpen if failed => self.open(&mut inner, now),
+            CircuitState::HalfOpen => {
+                inner.trials_succeeded += 1;
+                if inner.trials_succeeded >= self.settings.half_open_calls {
+                    tracing::info!(breaker = self.name, "Circuit closed");
+                    inner.state = CircuitState::Closed;
+                    inner.opened_at = None;
+                }
+            }
+            // A call admitted before the circuit opened
+            CircuitState::Open => {}
+        }
+    }
+
+    fn open(&self, inner: &mut Inner, now: Instant) {
+        tracing::warn!(breaker = self.name, "Circuit opened after repeated failures");
+        inner.state = CircuitState::Open;
+        inner.opened_at = Some(now);
+        inner.outcomes.clear();
+    }
+
+    /// Move an open circuit whose open period has passed to half-open
+    fn refresh(&self, inner: &mut Inner, now: Instant) {
+        if inner.state == CircuitState::Open
+            && inner
+                .opened_at
+                .is_some_and(|opened_at| now.duration_since(opened_at) >= self.settings.open_duration)
+        {
+            inner.state = CircuitState::HalfOpen;
+            inner.trials_started = 0;
+            inner.trials_succeeded = 0;
+        }
+    }
+
+    fn prune(&self, inner: &mut Inner, now: Instant) {
+        while inner
+            .outcomes
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > self.settings.window)
+        {
+            inner.outcomes.pop_front();
+        }
+    }
+}
+
+/// The breakers of the external providers, one per dependency, shared
+/// across requests
+#[derive(Debug, Clone)]
+pub struct CircuitBreakers {
+    settings: CircuitBreakerSettings,
+    breakers: Arc<Mutex<BTreeMap<&'static str, CircuitBreaker>>>,
+}
+
+impl CircuitBreakers {
+    pub fn new(settings: CircuitBreakerSettings) -> Self {
+        Self {
+            settings,
+            breakers: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// The breaker for a dependency, created closed on first use. Adapters
+    /// take theirs through `with_circuit_breaker`, so every instance built for
+    /// the same provider trips and recovers together.
+    pub fn get(&self, name: &'static str) -> CircuitBreaker {
+        self.breakers
+            .lock()
+            .unwrap()
+            .entry(name)
+            .or_insert_with(|| CircuitBreaker::new(name, self.settings.clone()))
+            .clone()
+    }
+
+    /// The state of each breaker, by dependency name
+    pub fn snapshot(&self) -> BTreeMap<&'static str, CircuitState> {
+        self.breakers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, breaker)| (*name, breaker.state()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(open_duration: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(
+            "test",
+            CircuitBreakerSettings {
+                failure_rate_threshold: 0.5,
+                minimum_calls: 4,
+                window: Duration::from_secs(60),
+                open_duration,
+                half_open_calls: 2,
+            },
+        )
+    }
+
+    #[test]
+    fn opens_once_the_failure_rate_crosses_the_threshold() {
+        let breaker = breaker(Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_failure();
+        // Too few calls to judge yet
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.try_acquire());
+    }
+
+    #[test]
+    fn trial_calls_close_or_reopen_the_circuit() {
+        let breaker = breaker(Duration::ZERO);
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // Only as many trials as needed to decide
+        assert!(breaker.try_acquire());
+        assert!(breaker.try_acquire());
+        assert!(!breaker.try_acquire());
+        breaker.record_success();
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        for _ in 0..4 {
+            breaker.record_failure();
+        }
+        assert!(breaker.try_acquire());
+        breaker.record_failure();
+        assert!(breaker.try_acquire(), "a failed trial reopens, then half-opens after the zero wait");
+    }
+
+    #[tokio::test]
+    async fn only_dependency_failures_count() {
+        let breaker = breaker(Duration::from_secs(60));
+        for _ in 0..4 {
+            let result: AppResult<()> = breaker.call(async { Err(AppError::NotFound("missing".to_string())) }).await;
+            assert!(matches!(result, Err(AppError::NotFound(_))));
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        for _ in 0..4 {
+            let _ = breaker.call(async { Err::<(), _>(AppError::ExternalService("down".to_string())) }).await;
+        }
+        let result = breaker.call(async { Ok(1) }).await;
+        assert!(matches!(result, Err(AppError::ExternalService(message)) if message.contains("unavailable")));
+    }
+}
@@ -0,0 +1,155 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::convert::Infallible;
+use crate::core::crypto::hex;
+use crate::core::error::{AppError, AppResult};
+use crate::core::response::ApiResponse;
+
+/// Strong entity tag of a response's data: a digest of its JSON, so it
+/// changes whenever anything a client sees in the data changes
+pub fn etag<T: Serialize>(data: &T) -> String {
+    let json = serde_json::to_vec(data).unwrap_or_default();
+    format!("\"{}\"", hex(&Sha256::digest(&json)[..16]))
+}
+
+/// The conditional headers of a request
+#[derive(Debug, Clone, Default)]
+pub struct Preconditions {
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+}
+
+impl Preconditions {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            if_match: header(header::IF_MATCH),
+            if_none_match: header(header::IF_NONE_MATCH),
+        }
+    }
+
+    /// Whether the client already holds the representation tagged `etag`;
+    /// If-None-Match compares tags weakly
+    pub fn not_modified(&self, etag: &str) -> bool {
+        self.if_none_match
+            .as_deref()
+            .is_some_and(|tags| listed(tags, |tag| opaque(tag) == opaque(etag)))
+    }
+
+    /// Refuse a change when If-Match names none of the resource's current
+    /// tag. If-Match compares tags strongly, so weak tags never match.
+    pub fn ensure_match(&self, etag: &str) -> AppResult<()> {
+        match &self.if_match {
+            Some(tags) if !listed(tags, |tag| tag == etag) => Err(AppError::PreconditionFailed(
+                "The resource has changed since it was read".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the change must be made against the representation read
+    pub fn is_conditional(&self) -> bool {
+        self.if_match.is_some()
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Preconditions {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+/// Whether a tag list is `*` or holds a tag satisfying `matches`
+fn listed(tags: &str, matches: impl Fn(&str) -> bool) -> bool {
+    tags.trim() == "*" || tags.split(',').map(str::trim).any(matches)
+}
+
+fn opaque(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+/// A successful response carrying the ETag of its data
+pub fn tagged<T: Serialize>(message: &str, data: T) -> Response {
+    let etag = etag(&data);
+    let mut response = Json(ApiResponse::success(message, data)).into_response();
+    insert_validators(&mut response, &etag);
+    response
+}
+
+/// A successful response for a read, or 304 Not Modified without a body
+/// when the client's copy is current
+pub fn respond<T: Serialize>(preconditions: &Preconditions, message: &str, data: T) -> Response {
+    let etag = etag(&data);
+    if !preconditions.not_modified(&etag) {
+        return tagged(message, data);
+    }
+
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    insert_validators(&mut response, &etag);
+    response
+}
+
+/// Caches may keep the data for the caller only, and revalidate it first
+fn insert_validators(response: &mut Response, etag: &str) {
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, value);
+    }
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("private, no-cache"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preconditions(if_match: Option<&str>, if_none_match: Option<&str>) -> Preconditions {
+        Preconditions {
+            if_match: if_match.map(str::to_string),
+            if_none_match: if_none_match.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn unchanged_data_is_not_sent_again() {
+        let tag = etag(&serde_json::json!({ "balance": 100 }));
+        assert_ne!(tag, etag(&serde_json::json!({ "balance": 101 })));
+
+        assert!(preconditions(None, Some(&tag)).not_modified(&tag));
+        assert!(preconditions(None, Some(&format!("\"other\", W/{}", tag))).not_modified(&tag));
+        assert!(preconditions(None, Some("*")).not_modified(&tag));
+        assert!(!preconditions(None, Some("\"other\"")).not_modified(&tag));
+        assert!(!preconditions(None, None).not_modified(&tag));
+
+        let response = respond(&preconditions(None, Some(&tag)), "Balance retrieved successfully", serde_json::json!({ "balance": 100 }));
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], tag.as_str());
+    }
+
+    #[test]
+    fn changes_need_the_current_tag() {
+        let tag = etag(&"account");
+        assert!(preconditions(None, None).ensure_match(&tag).is_ok());
+        assert!(preconditions(Some(&tag), None).ensure_match(&tag).is_ok());
+        assert!(preconditions(Some("*"), None).ensure_match(&tag).is_ok());
+        assert!(matches!(
+            preconditions(Some("\"stale\""), None).ensure_match(&tag),
+            Err(AppError::PreconditionFailed(_))
+        ));
+        // Weak tags never match for changes
+        assert!(preconditions(Some(&format!("W/{}", tag)), None).ensure_match(&tag).is_err());
+    }
+}
@@ -0,0 +1,482 @@
+use crate::core::error::{AppError, AppResult};
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD}, Engine};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use ring::hkdf;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Prefix marking a value as an encrypted envelope
+const ENVELOPE_PREFIX: &str = "enc:v1";
+
+/// Prefix marking a value as encrypted under its owner's data key
+const USER_ENVELOPE_PREFIX: &str = "enc:u1";
+
+/// HKDF context binding derived keys to their purpose
+const USER_KEY_INFO: &[u8] = b"openbank user data key v1";
+
+/// Length in bytes of the random salt a user's data key is derived with
+const USER_KEY_SALT_LEN: usize = 32;
+
+/// AES-GCM nonce length in bytes
+const NONCE_LEN: usize = 12;
+
+/// A data encryption key generated by a key provider
+pub struct DataKey {
+    /// Identifier of the master key that wrapped this data key
+    pub key_id: String,
+    pub plaintext: Vec<u8>,
+    pub wrapped: Vec<u8>,
+}
+
+/// Source of master keys used to wrap per-value data keys.
+///
+/// Implementations may be backed by a cloud KMS; the local provider keeps
+/// master keys in configuration.
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// Generate a new data key wrapped by the currently active master key
+    async fn generate_data_key(&self) -> AppResult<DataKey>;
+
+    /// Unwrap a data key previously wrapped by the given master key
+    async fn unwrap_data_key(&self, key_id: &str, wrapped: &[u8]) -> AppResult<Vec<u8>>;
+}
+
+/// Key provider holding AES-256 master keys in memory.
+///
+/// Several keys may be configured so values wrapped by a retired key remain
+/// readable after rotation; new values always use the active key.
+pub struct LocalKeyProvider {
+    active_key_id: String,
+    keys: HashMap<String, Key<Aes256Gcm>>,
+}
+
+impl LocalKeyProvider {
+    /// Build from a `key_id:base64_key` comma-separated list
+    pub fn from_config(keys: &str, active_key_id: &str) -> AppResult<Self> {
+        let mut parsed = HashMap::new();
+        for entry in keys.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let (key_id, encoded) = entry.split_once(':').ok_or_else(|| {
+                AppError::Internal("Encryption keys must be formatted as key_id:base64_key".to_string())
+            })?;
+            let bytes = STANDARD
+                .decode(encoded)
+                .map_err(|_| AppError::Internal(format!("Encryption key '{}' is not valid base64", key_id)))?;
+            if bytes.len() != 32 {
+                return Err(AppError::Internal(format!(
+                    "Encryption key '{}' must be 32 bytes",
+                    key_id
+                )));
+            }
+            parsed.insert(key_id.to_string(), *Key::<Aes256Gcm>::from_slice(&bytes));
+        }
+
+        if !parsed.contains_key(active_key_id) {
+            return Err(AppError::Internal(format!(
+                "Active encryption key '{}' is not configured",
+                active_key_id
+            )));
+        }
+
+        Ok(Self {
+            active_key_id: active_key_id.to_string(),
+            keys: parsed,
+        })
+    }
+
+    fn master_key(&self, key_id: &str) -> AppResult<&Key<Aes256Gcm>> {
+        self.keys
+            .get(key_id)
+            .ok_or_else(|| AppError::Internal(format!("Unknown encryption key '{}'", key_id)))
+    }
+}
+
+#[async_trait]
+impl KeyProvider for LocalKeyProvider {
+    async fn generate_data_key(&self) -> AppResult<DataKey> {
+        let plaintext = Aes256Gcm::generate_key(OsRng).to_vec();
+        let wrapped = seal(self.master_key(&self.active_key_id)?, &plaintext)?;
+
+        Ok(DataKey {
+            key_id: self.active_key_id.clone(),
+            plaintext,
+            wrapped,
+        })
+    }
+
+    async fn unwrap_data_key(&self, key_id: &str, wrapped: &[u8]) -> AppResult<Vec<u8>> {
+        open(self.master_key(key_id)?, wrapped)
+    }
+}
+
+/// Envelope encryption for sensitive fields persisted by repositories.
+///
+/// Each value is encrypted with its own data key, which is stored wrapped by a
+/// master key alongside the ciphertext:
+/// `enc:v1:<key_id>:<wrapped_data_key>:<ciphertext>`.
+#[derive(Clone)]
+pub struct EnvelopeCipher {
+    provider: Arc<dyn KeyProvider>,
+}
+
+impl EnvelopeCipher {
+    pub fn new(provider: Arc<dyn KeyProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Encrypt a string into an envelope
+    pub async fn encrypt_str(&self, plaintext: &str) -> AppResult<String> {
+        let data_key = self.provider.generate_data_key().await?;
+        let ciphertext = seal(Key::<Aes256Gcm>::from_slice(&data_key.plaintext), plaintext.as_bytes())?;
+
+        Ok(format!(
+            "{}:{}:{}:{}",
+            ENVELOPE_PREFIX,
+            data_key.key_id,
+            STANDARD.encode(data_key.wrapped),
+            STANDARD.encode(ciphertext)
+        ))
+    }
+
+    /// Decrypt an envelope. Values written before encryption was enabled are
+    /// returned unchanged.
+    pub async fn decrypt_str(&self, value: &str) -> AppResult<String> {
+        let Some(envelope) = value.strip_prefix(ENVELOPE_PREFIX).and_then(|rest| rest.strip_prefix(':')) else {
+            return Ok(value.to_string());
+        };
+
+        let mut parts = envelope.splitn(3, ':');
+        let (Some(key_id), Some(wrapped), Some(ciphertext)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(AppError::Internal("Malformed encrypted value".to_string()));
+        };
+        let wrapped = STANDARD
+            .decode(wrapped)
+            .map_err(|_| AppError::Internal("Malformed encrypted value".to_string()))?;
+        let ciphertext = STANDARD
+            .decode(ciphertext)
+            .map_err(|_| AppError::Internal("Malformed encrypted value".to_string()))?;
+
+        let data_key = self.provider.unwrap_data_key(key_id, &wrapped).await?;
+        let plaintext = open(Key::<Aes256Gcm>::from_slice(&data_key), &ciphertext)?;
+
+        String::from_utf8(plaintext).map_err(|_| AppError::Internal("Decrypted value is not valid UTF-8".to_string()))
+    }
+
+    /// Encrypt a JSON value, storing the envelope as a JSON string
+    pub async fn encrypt_json(&self, value: &serde_json::Value) -> AppResult<serde_json::Value> {
+        let envelope = self.encrypt_str(&value.to_string()).await?;
+        Ok(serde_json::Value::String(envelope))
+    }
+
+    /// Decrypt a JSON value produced by `encrypt_json`; plaintext JSON is returned unchanged
+    pub async fn decrypt_json(&self, value: serde_json::Value) -> AppResult<serde_json::Value> {
+        match value {
+            serde_json::Value::String(envelope) if envelope.starts_with(ENVELOPE_PREFIX) => {
+                let decrypted = self.decrypt_str(&envelope).await?;
+                serde_json::from_str(&decrypted)
+                    .map_err(|e| AppError::Internal(format!("Decrypted value is not valid JSON: {}", e)))
+            }
+            other => Ok(other),
+        }
+    }
+}
+
+/// Where a user's data key stands in the key store
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UserKeyRecord {
+    pub user_id: Uuid,
+    pub tenant_key_id: Option<Uuid>,
+    /// Random salt the key is derived with; destroyed when the key is shredded
+    pub salt: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+    pub shredded_at: Option<DateTime<Utc>>,
+}
+
+/// Per-tenant master keys and per-user data keys, kept in Postgres.
+///
+/// Each tenant has a random master key, stored wrapped by the key provider.
+/// A user's data key is derived from their tenant's master key with HKDF and
+/// a random salt held for that user alone. Shredding a user's key destroys
+/// the salt, so nothing encrypted under it can be decrypted again.
+#[derive(Clone)]
+pub struct KeyStore {
+    pool: PgPool,
+    provider: Arc<dyn KeyProvider>,
+}
+
+impl KeyStore {
+    pub fn new(pool: PgPool, provider: Arc<dyn KeyProvider>) -> Self {
+        Self { pool, provider }
+    }
+
+    /// The user's data key, created on first use. `None` once shredded.
+    pub async fn user_key(&self, user_id: Uuid) -> AppResult<Option<Key<Aes256Gcm>>> {
+        if self.find_user_key_record(user_id).await?.is_none() {
+            self.create_user_key(user_id).await?;
+        }
+        self.find_user_key(user_id).await
+    }
+
+    /// The user's existing data key, without creating one. `None` once
+    /// shredded.
+    pub async fn find_user_key(&self, user_id: Uuid) -> AppResult<Option<Key<Aes256Gcm>>> {
+        let record = self
+            .find_user_key_record(user_id)
+            .await?
+            .ok_or_else(|| AppError::Internal(format!("No data key exists for user {}", user_id)))?;
+        let (Some(tenant_key_id), Some(salt)) = (record.tenant_key_id, record.salt) else {
+            return Ok(None);
+        };
+
+        let (key_id, wrapped): (String, Vec<u8>) =
+            sqlx::query_as("SELECT key_id, wrapped_key FROM tenant_master_keys WHERE id = $1")
+                .bind(tenant_key_id)
+                .fetch_one(&self.pool)
+                .await?;
+        let tenant_key = self.provider.unwrap_data_key(&key_id, &wrapped).await?;
+
+        Ok(Some(derive_user_key(&tenant_key, &salt, user_id)))
+    }
+
+    pub async fn find_user_key_record(&self, user_id: Uuid) -> AppResult<Option<UserKeyRecord>> {
+        let record = sqlx::query_as::<_, UserKeyRecord>(
+            "SELECT user_id, tenant_key_id, salt, created_at, shredded_at FROM user_data_keys WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Destroy the user's data key. Users without a key are recorded as
+    /// shredded so none is created for them later. Returns `None` if the key
+    /// was already shredded.
+    pub async fn shred_user_key(&self, user_id: Uuid) -> AppResult<Option<UserKeyRecord>> {
+        let record = sqlx::query_as::<_, UserKeyRecord>(
+            "INSERT INTO user_data_keys (user_id, tenant_key_id, salt, shredded_at)
+             VALUES ($1, NULL, NULL, NOW())
+             ON CONFLICT (user_id) DO UPDATE SET salt = NULL, shredded_at = NOW()
+                 WHERE user_data_keys.shredded_at IS NULL
+             RETURNING user_id, tenant_key_id, salt, created_at, shredded_at",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    async fn create_user_key(&self, user_id: Uuid) -> AppResult<()> {
+        let tenant_id: Option<Option<Uuid>> = sqlx::query_scalar("SELECT tenant_id FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        let tenant_id = tenant_id.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        let tenant_key_id = self.tenant_key_id(tenant_id).await?;
+
+        let mut salt = [0u8; USER_KEY_SALT_LEN];
+        ring::rand::SecureRandom::fill(&ring::rand::SystemRandom::new(), &mut salt)
+            .map_err(|_| AppError::Internal("Failed to generate a data key salt".to_string()))?;
+        sqlx::query(
+            "INSERT INTO user_data_keys (user_id, tenant_key_id, salt) VALUES ($1, $2, $3)
+             ON CONFLICT (user_id) DO NOTHING",
+        )
+        .bind(user_id)
+        .bind(tenant_key_id)
+        .bind(salt.as_slice())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The tenant's master key, generated on first use. Users outside any
+    /// tenant share one.
+    async fn tenant_key_id(&self, tenant_id: Option<Uuid>) -> AppResult<Uuid> {
+        let existing = self.find_tenant_key_id(tenant_id).await?;
+        if let Some(id) = existing {
+            return Ok(id);
+        }
+
+        let master_key = self.provider.generate_data_key().await?;
+        sqlx::query(
+            "INSERT INTO tenant_master_keys (tenant_id, key_id, wrapped_key) VALUES ($1, $2, $3)
+             ON CONFLICT ((COALESCE(tenant_id, '00000000-0000-0000-0000-000000000000'::UUID))) DO NOTHING",
+        )
+        .bind(tenant_id)
+        .bind(&master_key.key_id)
+        .bind(&master_key.wrapped)
+        .execute(&self.pool)
+        .await?;
+
+        self.find_tenant_key_id(tenant_id)
+            .await?
+            .ok_or_else(|| AppError::Internal("Tenant master key was not stored".to_string()))
+    }
+
+    async fn find_tenant_key_id(&self, tenant_id: Option<Uuid>) -> AppResult<Option<Uuid>> {
+        let id = sqlx::query_scalar("SELECT id FROM tenant_master_keys WHERE tenant_id IS NOT DISTINCT FROM $1")
+            .bind(tenant_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(id)
+    }
+}
+
+/// Encryption of a user's personal data under their own data key, so that
+/// shredding the key erases it. Values written in the per-value envelope
+/// format before are still read.
+#[derive(Clone)]
+pub struct UserCipher {
+    key_store: KeyStore,
+    envelope: EnvelopeCipher,
+}
+
+impl UserCipher {
+    pub fn new(key_store: KeyStore, envelope: EnvelopeCipher) -> Self {
+        Self { key_store, envelope }
+    }
+
+    pub fn key_store(&self) -> &KeyStore {
+        &self.key_store
+    }
+
+    /// Encrypt a string under the user's data key
+    pub async fn encrypt_str(&self, user_id: Uuid, plaintext: &str) -> AppResult<String> {
+        let key = self
+            .key_store
+            .user_key(user_id)
+            .await?
+            .ok_or_else(|| AppError::Conflict("The user's personal data has been erased".to_string()))?;
+        let ciphertext = seal(&key, plaintext.as_bytes())?;
+
+        Ok(format!("{}:{}", USER_ENVELOPE_PREFIX, STANDARD.encode(ciphertext)))
+    }
+
+    /// Decrypt a value of the user's. Returns `None` if it was encrypted
+    /// under a key that has since been shredded.
+    pub async fn decrypt_str(&self, user_id: Uuid, value: &str) -> AppResult<Option<String>> {
+        let Some(ciphertext) = value.strip_prefix(USER_ENVELOPE_PREFIX).and_then(|rest| rest.strip_prefix(':')) else {
+            return self.envelope.decrypt_str(value).await.map(Some);
+        };
+        let ciphertext = STANDARD
+            .decode(ciphertext)
+            .map_err(|_| AppError::Internal("Malformed encrypted value".to_string()))?;
+
+        let Some(key) = self.key_store.find_user_key(user_id).await? else {
+            return Ok(None);
+        };
+        let plaintext = open(&key, &ciphertext)?;
+
+        String::from_utf8(plaintext)
+            .map(Some)
+            .map_err(|_| AppError::Internal("Decrypted value is not valid UTF-8".to_string()))
+    }
+
+    /// Encrypt a JSON value, storing the ciphertext as a JSON string
+    pub async fn encrypt_json(&self, user_id: Uuid, value: &serde_json::Value) -> AppResult<serde_json::Value> {
+        let encrypted = self.encrypt_str(user_id, &value.to_string()).await?;
+        Ok(serde_json::Value::String(encrypted))
+    }
+
+    /// Decrypt a JSON value produced by `encrypt_json`; plaintext JSON is
+    /// returned unchanged. Returns `None` once the user's key is shredded.
+    pub async fn decrypt_json(&self, user_id: Uuid, value: serde_json::Value) -> AppResult<Option<serde_json::Value>> {
+        match value {
+            serde_json::Value::String(encrypted) if encrypted.starts_with(USER_ENVELOPE_PREFIX) => {
+                let Some(decrypted) = self.decrypt_str(user_id, &encrypted).await? else {
+                    return Ok(None);
+                };
+                serde_json::from_str(&decrypted)
+                    .map(Some)
+                    .map_err(|e| AppError::Internal(format!("Decrypted value is not valid JSON: {}", e)))
+            }
+            other => self.envelope.decrypt_json(other).await.map(Some),
+        }
+    }
+}
+
+/// Derive a user's data key from their tenant's master key with HKDF-SHA256
+fn derive_user_key(tenant_key: &[u8], salt: &[u8], user_id: Uuid) -> Key<Aes256Gcm> {
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(tenant_key);
+    let info = [USER_KEY_INFO, user_id.as_bytes().as_slice()];
+    let mut key = [0u8; 32];
+    prk.expand(&info, hkdf::HKDF_SHA256)
+        .and_then(|okm| okm.fill(&mut key))
+        .expect("HKDF-SHA256 yields 32 byte keys");
+    *Key::<Aes256Gcm>::from_slice(&key)
+}
+
+/// Encrypt with AES-256-GCM, prefixing the random nonce to the ciphertext
+fn seal(key: &Key<Aes256Gcm>, plaintext: &[u8]) -> AppResult<Vec<u8>> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = Aes256Gcm::new(key)
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| AppError::Internal("Encryption failed".to_string()))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Decrypt a nonce-prefixed AES-256-GCM ciphertext
+fn open(key: &Key<Aes256Gcm>, sealed: &[u8]) -> AppResult<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return Err(AppError::Internal("Malformed encrypted value".to_string()));
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+    Aes256Gcm::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AppError::Internal("Decryption failed".to_string()))
+}
+
+/// HMAC-SHA256 of `data` under `key`
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Whether `tag` is the HMAC-SHA256 of `data` under `key`, compared in constant time
+pub fn verify_hmac_sha256(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.verify_slice(tag).is_ok()
+}
+
+/// AWS Signature Version 4 signing key for one day, region and service
+pub fn sigv4_signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let date_key = hmac_sha256(format!("AWS4{}", secret_access_key).as_bytes(), date.as_bytes());
+    let region_key = hmac_sha256(&date_key, region.as_bytes());
+    let service_key = hmac_sha256(&region_key, service.as_bytes());
+    hmac_sha256(&service_key, b"aws4_request")
+}
+
+/// Lowercase hex encoding
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Random URL-safe token for links and confirmations sent to a client
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// SHA-256 of a token, hex encoded, as it is stored and looked up
+pub fn hash_token(token: &str) -> String {
+    hex(&Sha256::digest(token.as_bytes()))
+}
@@ -1,7 +1,22 @@
 use mongodb::{options::ClientOptions, Client as MongoClient};
-use sqlx::{postgres::PgPoolOptions, PgPool};
+use rand::Rng;
+use sqlx::{postgres::PgPoolOptions, PgPool, Postgres, Transaction};
+use std::future::Future;
 use std::time::Duration;
-use tracing::info;
+use tracing::{info, warn};
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::AccountId;
+
+/// Times a transaction is attempted before a serialization failure or
+/// deadlock is returned to the caller
+pub const MAX_TRANSACTION_ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry; it doubles on each further one
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(10);
+
+/// SQLSTATEs Postgres reports when a transaction lost a race and is safe to
+/// run again: serialization_failure and deadlock_detected
+const RETRYABLE_SQLSTATES: &[&str] = &["40001", "40P01"];
 
 /// Initialize PostgreSQL connection pool
 pub async fn init_postgres(database_url: &str) -> Result<PgPool, sqlx::Error> {
@@ -35,6 +50,63 @@ pub async fn init_postgres_safe(database_url: &str) -> Result<PgPool, Box<dyn st
     }
 }
 
+/// Whether an error is a serialization failure or deadlock, after which the
+/// whole transaction can be retried
+pub fn is_retryable(error: &AppError) -> bool {
+    match error {
+        AppError::Database(sqlx::Error::Database(db)) => db
+            .code()
+            .is_some_and(|code| RETRYABLE_SQLSTATES.contains(&code.as_ref())),
+        _ => false,
+    }
+}
+
+/// Run a transaction, running it again after a serialization failure or
+/// deadlock. Each retry waits a random share of an exponentially growing
+/// delay, so transactions that collided do not collide again in lockstep.
+/// `transaction` must begin and commit its own transaction.
+pub async fn retry_transaction<T, F, Fut>(mut transaction: F) -> AppResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = AppResult<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match transaction().await {
+            Err(error) if attempt < MAX_TRANSACTION_ATTEMPTS && is_retryable(&error) => {
+                let ceiling = RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                let delay = ceiling.mul_f64(rand::thread_rng().gen_range(0.5..=1.0));
+                warn!(attempt, delay_ms = delay.as_millis() as u64, "Retrying transaction: {}", error);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Lock the balance rows of the given accounts and return their available
+/// balances. Rows are always locked in account id order, so transactions
+/// moving money between the same accounts in opposite directions wait for
+/// each other instead of deadlocking. Accounts without a balance row are
+/// left out.
+pub async fn lock_balances(
+    tx: &mut Transaction<'static, Postgres>,
+    account_ids: &[AccountId],
+) -> AppResult<Vec<(AccountId, i64)>> {
+    let balances = sqlx::query_as::<_, (AccountId, i64)>(
+        "SELECT account_id, COALESCE(available_balance, 0) FROM balances
+         WHERE account_id = ANY($1)
+         ORDER BY account_id
+         FOR UPDATE",
+    )
+    .bind(account_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(balances)
+}
+
 /// Initialize MongoDB client
 pub async fn init_mongodb(mongodb_url: &str) -> Result<MongoClient, mongodb::error::Error> {
     info!("Connecting to MongoDB...");
@@ -51,3 +123,85 @@ pub async fn init_mongodb(mongodb_url: &str) -> Result<MongoClient, mongodb::err
     info!("MongoDB connection established successfully");
     Ok(client)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug)]
+    struct PostgresError(&'static str);
+
+    impl std::fmt::Display for PostgresError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "SQLSTATE {}", self.0)
+        }
+    }
+
+    impl std::error::Error for PostgresError {}
+
+    impl sqlx::error::DatabaseError for PostgresError {
+        fn message(&self) -> &str {
+            "transaction lost a race"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(self.0))
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    fn postgres_error(code: &'static str) -> AppError {
+        AppError::Database(sqlx::Error::Database(Box::new(PostgresError(code))))
+    }
+
+    #[tokio::test]
+    async fn lost_races_are_retried_until_the_limit() {
+        let attempts = AtomicU32::new(0);
+        let result = retry_transaction(|| async {
+            match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(postgres_error("40P01")),
+                1 => Err(postgres_error("40001")),
+                _ => Ok("committed"),
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), "committed");
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        let attempts = AtomicU32::new(0);
+        let result: AppResult<()> = retry_transaction(|| async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(postgres_error("40001"))
+        })
+        .await;
+        assert!(is_retryable(&result.unwrap_err()));
+        assert_eq!(attempts.load(Ordering::SeqCst), MAX_TRANSACTION_ATTEMPTS);
+
+        // Other errors are returned straight away
+        let attempts = AtomicU32::new(0);
+        let result: AppResult<()> = retry_transaction(|| async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(postgres_error("23505"))
+        })
+        .await;
+        assert!(!is_retryable(&result.unwrap_err()));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,197 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+use crate::core::error::{AppError, AppResult};
+use crate::core::versioning;
+
+tokio::task_local! {
+    static CURRENT: RequestDeadline;
+}
+
+/// The point by which the current request must be answered. The deadline
+/// middleware sets it for the handler's task, so repositories and
+/// downstream clients can read it without it being passed through every
+/// call.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestDeadline {
+    expires_at: Instant,
+    budget: Duration,
+}
+
+impl RequestDeadline {
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            expires_at: Instant::now() + budget,
+            budget,
+        }
+    }
+
+    /// The deadline of the request being served, if there is one
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|deadline| *deadline).ok()
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.expires_at.saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Run `future` with this as the current deadline
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+}
+
+/// Time left for the request being served; `None` outside a request, as in
+/// background jobs
+pub fn remaining() -> Option<Duration> {
+    RequestDeadline::current().map(|deadline| deadline.remaining())
+}
+
+/// Give up on `future` when the current request's deadline passes. Outside
+/// a request it runs to completion.
+pub async fn bounded<F, T>(future: F) -> AppResult<T>
+where
+    F: Future<Output = AppResult<T>>,
+{
+    let Some(remaining) = remaining() else {
+        return future.await;
+    };
+    tokio::time::timeout(remaining, future)
+        .await
+        .map_err(|_| AppError::Timeout("Request deadline exceeded".to_string()))?
+}
+
+/// Start a Postgres transaction whose statements Postgres itself cancels
+/// once the current request's deadline passes, so abandoned requests do not
+/// leave queries running and holding locks
+pub async fn begin(pool: &sqlx::PgPool) -> AppResult<sqlx::Transaction<'static, sqlx::Postgres>> {
+    let mut tx = pool.begin().await?;
+    if let Some(remaining) = remaining() {
+        if remaining.is_zero() {
+            return Err(AppError::Timeout("Request deadline exceeded".to_string()));
+        }
+        // SET does not take bind parameters; the value is a plain integer
+        sqlx::query(&format!("SET LOCAL statement_timeout = {}", remaining.as_millis().max(1)))
+            .execute(&mut *tx)
+            .await?;
+    }
+    Ok(tx)
+}
+
+/// Limit outgoing HTTP calls to the time the current request has left
+pub trait WithDeadline {
+    fn with_deadline(self) -> Self;
+}
+
+impl WithDeadline for reqwest::RequestBuilder {
+    fn with_deadline(self) -> Self {
+        match remaining() {
+            Some(remaining) => self.timeout(remaining),
+            None => self,
+        }
+    }
+}
+
+/// Timeout budgets per route: a default, and overrides for path prefixes
+/// where the longest matching prefix wins. A budget of zero means no
+/// deadline, for long-lived routes such as event streams. A prefix under an
+/// API version covers the same routes under every version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeoutBudgets {
+    default: Option<Duration>,
+    routes: Vec<(String, Option<Duration>)>,
+}
+
+impl TimeoutBudgets {
+    /// Read `REQUEST_TIMEOUT_SECONDS` and the comma-separated
+    /// `prefix=seconds` entries of `REQUEST_TIMEOUT_ROUTES`
+    pub fn from_config(default_seconds: u64, routes: &str) -> AppResult<Self> {
+        let mut parsed = Vec::new();
+        for entry in routes.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let invalid = || {
+                AppError::Internal(format!("Request timeout route '{}' must be formatted as /path=seconds", entry))
+            };
+            let (prefix, seconds) = entry.split_once('=').ok_or_else(invalid)?;
+            let prefix = prefix.trim().trim_end_matches('/');
+            if !prefix.starts_with('/') {
+                return Err(invalid());
+            }
+            let seconds: u64 = seconds.trim().parse().map_err(|_| invalid())?;
+            parsed.push((prefix.to_string(), budget(seconds)));
+        }
+        parsed.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+        Ok(Self {
+            default: budget(default_seconds),
+            routes: parsed,
+        })
+    }
+
+    /// The budget for a request path, `None` when it has no deadline
+    pub fn for_path(&self, path: &str) -> Option<Duration> {
+        self.routes
+            .iter()
+            .find(|(prefix, _)| {
+                let (path, prefix) = match (versioning::unversioned(path), versioning::unversioned(prefix)) {
+                    (Some(path), Some(prefix)) => (path, prefix),
+                    _ => (path, prefix.as_str()),
+                };
+                path.strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .map_or(self.default, |(_, budget)| *budget)
+    }
+}
+
+fn budget(seconds: u64) -> Option<Duration> {
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_longest_matching_prefix_sets_the_budget() {
+        let budgets = TimeoutBudgets::from_config(
+            30,
+            "/api/v1/stream=0, /api/v1/reconciliation=120, /api/v1/reconciliation/runs/=5",
+        )
+        .unwrap();
+
+        assert_eq!(budgets.for_path("/api/v1/payments"), Some(Duration::from_secs(30)));
+        assert_eq!(budgets.for_path("/api/v1/stream/events"), None);
+        assert_eq!(budgets.for_path("/api/v1/reconciliation"), Some(Duration::from_secs(120)));
+        assert_eq!(budgets.for_path("/api/v1/reconciliation/runs/1"), Some(Duration::from_secs(5)));
+        // Prefixes match whole segments only
+        assert_eq!(budgets.for_path("/api/v1/streams"), Some(Duration::from_secs(30)));
+        assert_eq!(budgets.for_path("/api/v2/stream/events"), None);
+
+        assert!(TimeoutBudgets::from_config(30, "/api/v1/stream").is_err());
+        assert!(TimeoutBudgets::from_config(30, "api/v1/stream=5").is_err());
+        assert!(TimeoutBudgets::from_config(30, "/api/v1/stream=soon").is_err());
+    }
+
+    #[tokio::test]
+    async fn work_past_the_deadline_is_abandoned() {
+        assert!(RequestDeadline::current().is_none());
+        assert_eq!(bounded(async { Ok(1) }).await.unwrap(), 1);
+
+        let deadline = RequestDeadline::new(Duration::from_millis(20));
+        let slow = deadline.scope(bounded(async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        }));
+        assert!(matches!(slow.await, Err(AppError::Timeout(_))));
+
+        let quick = deadline.scope(async { RequestDeadline::current().map(|deadline| deadline.budget()) });
+        assert_eq!(quick.await, Some(Duration::from_millis(20)));
+    }
+}
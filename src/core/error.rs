@@ -1,9 +1,12 @@
+use super::i18n::validation_details;
 use super::response::{ApiResponse, ErrorResponse};
 use axum::{
-    http::StatusCode,
+    http::{header::{RETRY_AFTER, WWW_AUTHENTICATE}, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use uuid::Uuid;
+use validator::ValidationErrors;
 
 /// Application-wide error type
 #[derive(Debug, thiserror::Error)]
@@ -17,26 +20,63 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    /// A request body failed its field rules; the failures are returned
+    /// per field so clients can show them next to their inputs
+    #[error("Validation error: {0}")]
+    InvalidFields(ValidationErrors),
+
     #[error("Authentication error: {0}")]
     Authentication(String),
 
     #[error("Authorization error: {0}")]
     Authorization(String),
 
+    /// The action needs a more recent or stronger login than the token
+    /// carries; the client should log the user in again as described
+    #[error("Step-up authentication required: {reason}")]
+    StepUpRequired {
+        reason: String,
+        /// Authentication method the new login must use
+        acr: &'static str,
+        /// How old the new login may be when the action is retried
+        max_age_seconds: i64,
+    },
+
     #[error("Not found: {0}")]
     NotFound(String),
 
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// The resource no longer matches the tag the client sent in If-Match
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    /// The payment repeats one created moments ago; carries that payment's ID
+    #[error("Duplicate of payment {0}")]
+    DuplicatePayment(Uuid),
+
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    /// The caller asked too often; it may try again after the given delay
+    #[error("Too many requests: {reason}")]
+    TooManyRequests { reason: String, retry_after_seconds: i64 },
+
     #[error("Internal server error: {0}")]
     Internal(String),
 
     #[error("External service error: {0}")]
     ExternalService(String),
+
+    /// The request ran past its timeout budget
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
+    /// The request was made under an API version no longer served to the
+    /// caller; the client should move to a later version
+    #[error("API version retired: {0}")]
+    ApiVersionRetired(String),
 }
 
 impl IntoResponse for AppError {
@@ -54,6 +94,10 @@ impl IntoResponse for AppError {
                 tracing::warn!("Validation error: {}", msg);
                 (StatusCode::BAD_REQUEST, "Validation error")
             }
+            AppError::InvalidFields(ref errors) => {
+                tracing::warn!("Validation error: {}", errors);
+                (StatusCode::BAD_REQUEST, "Validation error")
+            }
             AppError::Authentication(ref msg) => {
                 tracing::warn!("Authentication error: {}", msg);
                 (StatusCode::UNAUTHORIZED, "Authentication error")
@@ -62,6 +106,10 @@ impl IntoResponse for AppError {
                 tracing::warn!("Authorization error: {}", msg);
                 (StatusCode::FORBIDDEN, "Authorization error")
             }
+            AppError::StepUpRequired { ref reason, .. } => {
+                tracing::info!("Step-up authentication required: {}", reason);
+                (StatusCode::UNAUTHORIZED, "Step-up authentication required")
+            }
             AppError::NotFound(ref msg) => {
                 tracing::info!("Not found: {}", msg);
                 (StatusCode::NOT_FOUND, "Not found")
@@ -70,10 +118,22 @@ impl IntoResponse for AppError {
                 tracing::warn!("Conflict: {}", msg);
                 (StatusCode::CONFLICT, "Conflict")
             }
+            AppError::PreconditionFailed(ref msg) => {
+                tracing::info!("Precondition failed: {}", msg);
+                (StatusCode::PRECONDITION_FAILED, "Precondition failed")
+            }
+            AppError::DuplicatePayment(matched_payment_id) => {
+                tracing::warn!("Duplicate of payment {}", matched_payment_id);
+                (StatusCode::CONFLICT, "Possible duplicate payment")
+            }
             AppError::BadRequest(ref msg) => {
                 tracing::warn!("Bad request: {}", msg);
                 (StatusCode::BAD_REQUEST, "Bad request")
             }
+            AppError::TooManyRequests { ref reason, .. } => {
+                tracing::warn!("Too many requests: {}", reason);
+                (StatusCode::TOO_MANY_REQUESTS, "Too many requests")
+            }
             AppError::Internal(ref msg) => {
                 tracing::error!("Internal error: {}", msg);
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
@@ -82,25 +142,104 @@ impl IntoResponse for AppError {
                 tracing::error!("External service error: {}", msg);
                 (StatusCode::BAD_GATEWAY, "External service error")
             }
+            AppError::Timeout(ref msg) => {
+                tracing::warn!("Timeout: {}", msg);
+                (StatusCode::GATEWAY_TIMEOUT, "Request timed out")
+            }
+            AppError::ApiVersionRetired(ref msg) => {
+                tracing::info!("API version retired: {}", msg);
+                (StatusCode::GONE, "API version retired")
+            }
         };
 
         let error_code = match &self {
             AppError::Database(_) => "DATABASE_ERROR",
             AppError::MongoDB(_) => "MONGODB_ERROR",
-            AppError::Validation(_) => "VALIDATION_ERROR",
+            AppError::Validation(_) | AppError::InvalidFields(_) => "VALIDATION_ERROR",
             AppError::Authentication(_) => "AUTHENTICATION_ERROR",
             AppError::Authorization(_) => "AUTHORIZATION_ERROR",
+            AppError::StepUpRequired { .. } => "STEP_UP_REQUIRED",
             AppError::NotFound(_) => "NOT_FOUND",
             AppError::Conflict(_) => "CONFLICT",
+            AppError::PreconditionFailed(_) => "PRECONDITION_FAILED",
+            AppError::DuplicatePayment(_) => "DUPLICATE_PAYMENT",
             AppError::BadRequest(_) => "BAD_REQUEST",
+            AppError::TooManyRequests { .. } => "RATE_LIMITED",
             AppError::Internal(_) => "INTERNAL_ERROR",
             AppError::ExternalService(_) => "EXTERNAL_SERVICE_ERROR",
+            AppError::Timeout(_) => "DEADLINE_EXCEEDED",
+            AppError::ApiVersionRetired(_) => "API_VERSION_RETIRED",
         };
 
-        let response =
-            ApiResponse::<ErrorResponse>::error("Request failed", error_code, error_message);
+        let response = match &self {
+            // Tell the client which payment it repeated and how to go ahead anyway
+            AppError::DuplicatePayment(matched_payment_id) => ApiResponse::<ErrorResponse>::error_with_details(
+                "Request failed",
+                error_code,
+                error_message,
+                serde_json::json!({
+                    "matched_payment_id": matched_payment_id,
+                    "override": "Resubmit with force_override set to true to create the payment anyway",
+                }),
+            ),
+            AppError::StepUpRequired { reason, acr, max_age_seconds } => {
+                ApiResponse::<ErrorResponse>::error_with_details(
+                    "Request failed",
+                    error_code,
+                    error_message,
+                    serde_json::json!({
+                        "reason": reason,
+                        "acr_values": acr,
+                        "max_age": max_age_seconds,
+                    }),
+                )
+            }
+            AppError::TooManyRequests { reason, retry_after_seconds } => {
+                ApiResponse::<ErrorResponse>::error_with_details(
+                    "Request failed",
+                    error_code,
+                    error_message,
+                    serde_json::json!({
+                        "reason": reason,
+                        "retry_after": retry_after_seconds,
+                    }),
+                )
+            }
+            AppError::ApiVersionRetired(reason) => ApiResponse::<ErrorResponse>::error_with_details(
+                "Request failed",
+                error_code,
+                error_message,
+                serde_json::json!({ "reason": reason }),
+            ),
+            AppError::InvalidFields(errors) => ApiResponse::<ErrorResponse>::error_with_details(
+                "Request failed",
+                error_code,
+                error_message,
+                validation_details(errors),
+            ),
+            _ => ApiResponse::<ErrorResponse>::error("Request failed", error_code, error_message),
+        };
+
+        // Challenge as RFC 9470 describes, so OAuth clients know to log in again
+        let challenge = match &self {
+            AppError::StepUpRequired { acr, max_age_seconds, .. } => HeaderValue::from_str(&format!(
+                "Bearer error=\"insufficient_user_authentication\", acr_values=\"{}\", max_age={}",
+                acr, max_age_seconds
+            ))
+            .ok(),
+            _ => None,
+        };
 
-        (status, Json(response)).into_response()
+        let mut response = (status, Json(response)).into_response();
+        if let Some(challenge) = challenge {
+            response.headers_mut().insert(WWW_AUTHENTICATE, challenge);
+        }
+        if let AppError::TooManyRequests { retry_after_seconds, .. } = &self {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from((*retry_after_seconds).max(0) as u64));
+        }
+        response
     }
 }
 
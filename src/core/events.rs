@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{broadcast, mpsc};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::auth::scopes;
+use crate::shared::types::{AccountId, Amount, Currency, TenantId};
+
+/// Kinds of domain events published on the event bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub enum DomainEventType {
+    #[serde(rename = "transaction.created")]
+    TransactionCreated,
+    #[serde(rename = "payment.status_changed")]
+    PaymentStatusChanged,
+    #[serde(rename = "balance.updated")]
+    BalanceUpdated,
+    #[serde(rename = "account.closed")]
+    AccountClosed,
+    #[serde(rename = "notification.created")]
+    NotificationCreated,
+    #[serde(rename = "credit_check.completed")]
+    CreditCheckCompleted,
+}
+
+impl DomainEventType {
+    pub const ALL: [DomainEventType; 6] = [
+        DomainEventType::TransactionCreated,
+        DomainEventType::PaymentStatusChanged,
+        DomainEventType::BalanceUpdated,
+        DomainEventType::AccountClosed,
+        DomainEventType::NotificationCreated,
+        DomainEventType::CreditCheckCompleted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DomainEventType::TransactionCreated => "transaction.created",
+            DomainEventType::PaymentStatusChanged => "payment.status_changed",
+            DomainEventType::BalanceUpdated => "balance.updated",
+            DomainEventType::AccountClosed => "account.closed",
+            DomainEventType::NotificationCreated => "notification.created",
+            DomainEventType::CreditCheckCompleted => "credit_check.completed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event_type| event_type.as_str() == value)
+    }
+
+    /// Module whose read scope a token needs to receive events of this type
+    pub fn required_scope(&self) -> &'static str {
+        match self {
+            DomainEventType::TransactionCreated => scopes::TRANSACTIONS,
+            DomainEventType::PaymentStatusChanged => scopes::PAYMENTS,
+            DomainEventType::BalanceUpdated
+            | DomainEventType::AccountClosed
+            | DomainEventType::NotificationCreated => scopes::USER_DATA,
+            DomainEventType::CreditCheckCompleted => scopes::INCOME,
+        }
+    }
+}
+
+/// Something that happened to an account, as seen by real-time subscribers
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DomainEvent {
+    pub id: Uuid,
+    pub event_type: DomainEventType,
+    /// Events without a tenant are never delivered to subscribers
+    #[serde(skip)]
+    pub tenant_id: Option<TenantId>,
+    /// Accounts the event concerns
+    pub account_ids: Vec<AccountId>,
+    pub data: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl DomainEvent {
+    pub fn new(
+        event_type: DomainEventType,
+        tenant_id: Option<TenantId>,
+        account_ids: Vec<AccountId>,
+        data: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type,
+            tenant_id,
+            account_ids,
+            data,
+            occurred_at: Utc::now(),
+        }
+    }
+}
+
+/// An account's balance after a change, as carried by `balance.updated`
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct BalanceSnapshot {
+    #[serde(skip)]
+    pub tenant_id: Option<TenantId>,
+    pub account_id: AccountId,
+    pub available_balance: Amount,
+    pub ledger_balance: Amount,
+    pub currency: Currency,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<BalanceSnapshot> for DomainEvent {
+    fn from(snapshot: BalanceSnapshot) -> Self {
+        DomainEvent::new(
+            DomainEventType::BalanceUpdated,
+            snapshot.tenant_id,
+            vec![snapshot.account_id],
+            serde_json::json!(snapshot),
+        )
+    }
+}
+
+/// In-process publish/subscribe bus for domain events.
+///
+/// Delivery to subscribers is best effort: events published with no
+/// subscribers are dropped, and a subscriber that falls more than the buffer
+/// size behind misses the oldest events. The journal, when attached,
+/// receives every event.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Arc<DomainEvent>>,
+    journal: Arc<OnceLock<mpsc::UnboundedSender<Arc<DomainEvent>>>>,
+}
+
+impl EventBus {
+    pub fn new(buffer_size: usize) -> Self {
+        let (sender, _) = broadcast::channel(buffer_size.max(1));
+        Self {
+            sender,
+            journal: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn publish(&self, event: DomainEvent) {
+        let event = Arc::new(event);
+        if let Some(journal) = self.journal.get() {
+            // An error only means the journal has shut down
+            let _ = journal.send(event.clone());
+        }
+        // An error only means nobody is subscribed right now
+        let _ = self.sender.send(event);
+    }
+
+    /// Receive every event published from now on, without loss. Only one
+    /// journal can be attached; later calls return `None`.
+    pub fn attach_journal(&self) -> Option<mpsc::UnboundedReceiver<Arc<DomainEvent>>> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.journal.set(sender).ok()?;
+        Some(receiver)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<DomainEvent>> {
+        self.sender.subscribe()
+    }
+}
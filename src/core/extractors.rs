@@ -1,7 +1,8 @@
 use crate::core::error::AppError;
 use axum::{
     async_trait,
-    extract::{FromRequest, Request},
+    extract::{FromRequest, FromRequestParts, Request},
+    http::request::Parts,
     Json,
 };
 use serde::de::DeserializeOwned;
@@ -57,3 +58,35 @@ impl<T> std::ops::DerefMut for ApiJson<T> {
         &mut self.0
     }
 }
+
+/// Client IP address taken from proxy headers (X-Forwarded-For, then X-Real-IP)
+pub struct ClientIp(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let forwarded = parts
+            .headers
+            .get("x-forwarded-for")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|value| value.split(',').next())
+            .map(|ip| ip.trim().to_string());
+
+        let ip = forwarded
+            .or_else(|| {
+                parts
+                    .headers
+                    .get("x-real-ip")
+                    .and_then(|h| h.to_str().ok())
+                    .map(|s| s.to_string())
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Ok(ClientIp(ip))
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+
+/// Features rolled out behind a flag. Services ask about these rather than
+/// raw flag keys, so a misspelt key cannot quietly read as a missing flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// Confirmation of payee for accounts at other banks, through the payee
+    /// directory provider
+    ExternalPayeeDirectory,
+}
+
+impl Feature {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Feature::ExternalPayeeDirectory => "external_payee_directory",
+        }
+    }
+
+    /// Whether the feature is on while its flag has not been created, so
+    /// features that predate their flag keep working
+    pub fn default_enabled(&self) -> bool {
+        match self {
+            Feature::ExternalPayeeDirectory => true,
+        }
+    }
+}
+
+/// What a flag target names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "feature_flag_target", rename_all = "snake_case")]
+pub enum FlagTargetType {
+    Tenant,
+    Project,
+}
+
+/// A tenant or project a flag is explicitly turned on or off for
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct FlagTarget {
+    pub target_type: FlagTargetType,
+    pub target_id: Uuid,
+    pub enabled: bool,
+}
+
+/// How a flag decides, as stored
+#[derive(Debug, Clone)]
+pub struct FlagRule {
+    /// Off everywhere when false, whatever the targets say
+    pub enabled: bool,
+    pub rollout_percentage: i32,
+    pub targets: Vec<FlagTarget>,
+}
+
+/// Who a flag is evaluated for
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlagContext {
+    pub tenant_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+}
+
+impl FlagContext {
+    pub fn new(tenant_id: Option<Uuid>, project_id: Option<Uuid>) -> Self {
+        Self { tenant_id, project_id }
+    }
+
+    pub fn tenant(tenant_id: Uuid) -> Self {
+        Self::new(Some(tenant_id), None)
+    }
+}
+
+impl FlagRule {
+    /// A project target wins over a tenant target, which wins over the
+    /// rollout. The rollout buckets the project, else the tenant, so each
+    /// one stays on the same side as the percentage grows.
+    pub fn evaluate(&self, key: &str, context: &FlagContext) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let target = |target_type: FlagTargetType, id: Option<Uuid>| {
+            id.and_then(|id| {
+                self.targets
+                    .iter()
+                    .find(|target| target.target_type == target_type && target.target_id == id)
+                    .map(|target| target.enabled)
+            })
+        };
+        if let Some(enabled) = target(FlagTargetType::Project, context.project_id)
+            .or_else(|| target(FlagTargetType::Tenant, context.tenant_id))
+        {
+            return enabled;
+        }
+
+        match context.project_id.or(context.tenant_id) {
+            Some(subject) => (rollout_bucket(key, subject) as i32) < self.rollout_percentage,
+            None => self.rollout_percentage >= 100,
+        }
+    }
+}
+
+/// A stable bucket in 0..100 for a subject under a flag; hashing the key in
+/// means different flags roll out to different subjects first
+fn rollout_bucket(key: &str, subject: Uuid) -> u32 {
+    let digest = Sha256::new()
+        .chain_update(key.as_bytes())
+        .chain_update(subject.as_bytes())
+        .finalize();
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) % 100
+}
+
+#[derive(Debug, Clone)]
+struct CachedRules {
+    rules: Arc<HashMap<String, FlagRule>>,
+    loaded_at: Instant,
+}
+
+/// Feature flags stored in Postgres, with every rule cached in memory for a
+/// short time so checking a flag rarely costs a query
+#[derive(Debug, Clone)]
+pub struct FeatureFlags {
+    pool: PgPool,
+    cache: Arc<Mutex<Option<CachedRules>>>,
+    ttl: Duration,
+}
+
+impl FeatureFlags {
+    pub fn new(pool: PgPool, ttl: Duration) -> Self {
+        Self {
+            pool,
+            cache: Arc::new(Mutex::new(None)),
+            ttl,
+        }
+    }
+
+    /// Whether a feature is on for the caller. When the flags cannot be
+    /// loaded the last loaded rules apply, else the feature's default.
+    pub async fn is_enabled(&self, feature: Feature, context: FlagContext) -> bool {
+        let rules = match self.rules().await {
+            Ok(rules) => rules,
+            Err(e) => {
+                warn!(flag = feature.key(), "Failed to load feature flags: {}", e);
+                match self.cache.lock().unwrap().as_ref() {
+                    Some(cached) => cached.rules.clone(),
+                    None => return feature.default_enabled(),
+                }
+            }
+        };
+
+        rules
+            .get(feature.key())
+            .map_or(feature.default_enabled(), |rule| rule.evaluate(feature.key(), &context))
+    }
+
+    /// Evaluate any stored flag by key; `None` when it does not exist
+    pub async fn evaluate(&self, key: &str, context: FlagContext) -> AppResult<Option<bool>> {
+        let rules = self.rules().await?;
+        Ok(rules.get(key).map(|rule| rule.evaluate(key, &context)))
+    }
+
+    /// Drop the cached rules so a change applies on the next check
+    pub fn invalidate(&self) {
+        *self.cache.lock().unwrap() = None;
+    }
+
+    async fn rules(&self) -> AppResult<Arc<HashMap<String, FlagRule>>> {
+        if let Some(cached) = self.cache.lock().unwrap().as_ref() {
+            if cached.loaded_at.elapsed() < self.ttl {
+                return Ok(cached.rules.clone());
+            }
+        }
+
+        let rules = Arc::new(self.load().await?);
+        *self.cache.lock().unwrap() = Some(CachedRules {
+            rules: rules.clone(),
+            loaded_at: Instant::now(),
+        });
+        Ok(rules)
+    }
+
+    async fn load(&self) -> AppResult<HashMap<String, FlagRule>> {
+        let flags: Vec<(String, bool, i32)> =
+            sqlx::query_as("SELECT key, enabled, rollout_percentage FROM feature_flags")
+                .fetch_all(&self.pool)
+                .await?;
+        let targets: Vec<(String, FlagTargetType, Uuid, bool)> =
+            sqlx::query_as("SELECT flag_key, target_type, target_id, enabled FROM feature_flag_targets")
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut rules: HashMap<String, FlagRule> = flags
+            .into_iter()
+            .map(|(key, enabled, rollout_percentage)| {
+                let rule = FlagRule {
+                    enabled,
+                    rollout_percentage,
+                    targets: Vec::new(),
+                };
+                (key, rule)
+            })
+            .collect();
+        for (key, target_type, target_id, enabled) in targets {
+            if let Some(rule) = rules.get_mut(&key) {
+                rule.targets.push(FlagTarget {
+                    target_type,
+                    target_id,
+                    enabled,
+                });
+            }
+        }
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(rollout_percentage: i32, targets: Vec<FlagTarget>) -> FlagRule {
+        FlagRule {
+            enabled: true,
+            rollout_percentage,
+            targets,
+        }
+    }
+
+    #[test]
+    fn targets_win_over_the_rollout() {
+        let tenant = Uuid::new_v4();
+        let project = Uuid::new_v4();
+        let flag = rule(
+            0,
+            vec![
+                FlagTarget { target_type: FlagTargetType::Tenant, target_id: tenant, enabled: true },
+                FlagTarget { target_type: FlagTargetType::Project, target_id: project, enabled: false },
+            ],
+        );
+
+        assert!(flag.evaluate("new_rules", &FlagContext::tenant(tenant)));
+        assert!(flag.evaluate("new_rules", &FlagContext::new(Some(tenant), Some(Uuid::new_v4()))));
+        assert!(!flag.evaluate("new_rules", &FlagContext::new(Some(tenant), Some(project))));
+        assert!(!flag.evaluate("new_rules", &FlagContext::tenant(Uuid::new_v4())));
+
+        let killed = FlagRule { enabled: false, ..flag };
+        assert!(!killed.evaluate("new_rules", &FlagContext::tenant(tenant)));
+    }
+
+    #[test]
+    fn rollouts_are_stable_and_grow_monotonically() {
+        let subjects: Vec<Uuid> = (0..1000).map(|_| Uuid::new_v4()).collect();
+        let on_at = |percentage: i32| -> Vec<bool> {
+            let flag = rule(percentage, Vec::new());
+            subjects
+                .iter()
+                .map(|subject| flag.evaluate("new_rules", &FlagContext::new(None, Some(*subject))))
+                .collect()
+        };
+
+        let quarter = on_at(25);
+        let half = on_at(50);
+        assert_eq!(quarter, on_at(25));
+        assert!(quarter.iter().zip(&half).all(|(quarter, half)| !quarter || *half));
+        let share = half.iter().filter(|on| **on).count();
+        assert!((400..600).contains(&share), "about half of the projects, got {}", share);
+
+        assert!(on_at(100).iter().all(|on| *on));
+        assert!(on_at(0).iter().all(|on| !on));
+        // Without a subject only a full rollout is on
+        assert!(!rule(99, Vec::new()).evaluate("new_rules", &FlagContext::default()));
+        assert!(rule(100, Vec::new()).evaluate("new_rules", &FlagContext::default()));
+    }
+}
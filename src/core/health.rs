@@ -0,0 +1,253 @@
+use crate::core::circuit_breaker::CircuitState;
+use crate::core::AppState;
+use crate::webhooks::repository::WebhookRepository;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// How long a single dependency check may take before it is reported down
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Health of a single component
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentStatus {
+    Up,
+    Degraded,
+    Down,
+}
+
+/// Overall service health
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverallStatus {
+    /// All components are up
+    Healthy,
+    /// A non-critical component is degraded or down
+    Degraded,
+    /// A critical component is down
+    Unhealthy,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComponentHealth {
+    pub status: ComponentStatus,
+    /// Whether the service cannot operate without this component
+    pub critical: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    pub details: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub status: OverallStatus,
+    pub service: String,
+    pub version: String,
+    pub timestamp: String,
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+/// Run all dependency checks and aggregate them into a report
+pub async fn check(state: &AppState) -> HealthReport {
+    let (postgres, mongodb, webhooks) =
+        tokio::join!(check_postgres(state), check_mongodb(state), check_webhooks(state));
+
+    let mut components = BTreeMap::new();
+    components.insert("postgres".to_string(), postgres);
+    components.insert("mongodb".to_string(), mongodb);
+    components.insert("rate_limiter".to_string(), check_rate_limiter(state));
+    components.insert("jobs".to_string(), check_jobs(state));
+    components.insert("webhooks".to_string(), webhooks);
+    components.insert("circuit_breakers".to_string(), check_circuit_breakers(state));
+    components.insert("audit_writer".to_string(), check_audit_writer(state));
+    components.insert("replay_cache".to_string(), check_replay_cache(state));
+    if let Some(siem) = check_siem_forwarder(state) {
+        components.insert("siem_forwarder".to_string(), siem);
+    }
+
+    let status = if components
+        .values()
+        .any(|component| component.critical && component.status == ComponentStatus::Down)
+    {
+        OverallStatus::Unhealthy
+    } else if components
+        .values()
+        .any(|component| component.status != ComponentStatus::Up)
+    {
+        OverallStatus::Degraded
+    } else {
+        OverallStatus::Healthy
+    };
+
+    HealthReport {
+        status,
+        service: "openBank".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        timestamp: Utc::now().to_rfc3339(),
+        components,
+    }
+}
+
+async fn check_postgres(state: &AppState) -> ComponentHealth {
+    let pool = &state.postgres;
+    let pool_stats = json!({
+        "size": pool.size(),
+        "idle": pool.num_idle(),
+        "max_connections": pool.options().get_max_connections(),
+    });
+
+    let (result, latency_ms) = timed(sqlx::query("SELECT 1").execute(pool)).await;
+    match result {
+        Some(Ok(_)) => ComponentHealth {
+            status: ComponentStatus::Up,
+            critical: true,
+            latency_ms: Some(latency_ms),
+            details: json!({ "pool": pool_stats }),
+        },
+        Some(Err(e)) => down(true, latency_ms, e.to_string(), Some(pool_stats)),
+        None => down(true, latency_ms, "timed out".to_string(), Some(pool_stats)),
+    }
+}
+
+async fn check_mongodb(state: &AppState) -> ComponentHealth {
+    let admin = state.mongodb.database("admin");
+    let ping = admin.run_command(mongodb::bson::doc! { "ping": 1 }, None);
+
+    let (result, latency_ms) = timed(ping).await;
+    match result {
+        Some(Ok(_)) => ComponentHealth {
+            status: ComponentStatus::Up,
+            critical: false,
+            latency_ms: Some(latency_ms),
+            details: json!({}),
+        },
+        Some(Err(e)) => down(false, latency_ms, e.to_string(), None),
+        None => down(false, latency_ms, "timed out".to_string(), None),
+    }
+}
+
+fn check_rate_limiter(state: &AppState) -> ComponentHealth {
+    let (tracked_clients, blocked_clients) = state.rate_limiter.client_counts();
+
+    ComponentHealth {
+        status: ComponentStatus::Up,
+        critical: false,
+        latency_ms: None,
+        details: json!({
+            "tracked_clients": tracked_clients,
+            "blocked_clients": blocked_clients,
+        }),
+    }
+}
+
+fn check_jobs(state: &AppState) -> ComponentHealth {
+    let now = Utc::now();
+    let jobs = state.job_monitor.snapshot();
+    let unhealthy = jobs
+        .iter()
+        .any(|job| job.consecutive_failures > 0 || job.is_overdue(now));
+
+    ComponentHealth {
+        status: if unhealthy { ComponentStatus::Degraded } else { ComponentStatus::Up },
+        critical: false,
+        latency_ms: None,
+        details: json!({ "jobs": jobs }),
+    }
+}
+
+/// Dependencies behind an open or half-open breaker degrade the service
+fn check_circuit_breakers(state: &AppState) -> ComponentHealth {
+    let breakers = state.circuit_breakers.snapshot();
+    let tripped = breakers.values().any(|breaker| *breaker != CircuitState::Closed);
+
+    ComponentHealth {
+        status: if tripped { ComponentStatus::Degraded } else { ComponentStatus::Up },
+        critical: false,
+        latency_ms: None,
+        details: json!({ "breakers": breakers }),
+    }
+}
+
+/// Audit events waiting on disk for MongoDB degrade the service
+fn check_audit_writer(state: &AppState) -> ComponentHealth {
+    let stats = state.audit_logger.writer_stats();
+    let spilling = stats.as_ref().is_some_and(|stats| stats.spill_bytes > 0);
+
+    ComponentHealth {
+        status: if spilling { ComponentStatus::Degraded } else { ComponentStatus::Up },
+        critical: false,
+        latency_ms: None,
+        details: json!({ "writer": stats }),
+    }
+}
+
+/// A nonce store that keeps failing degrades the service, as signed
+/// requests are refused while it is down
+fn check_replay_cache(state: &AppState) -> ComponentHealth {
+    let stats = state.replay_cache.stats();
+
+    ComponentHealth {
+        status: if stats.consecutive_store_errors > 0 { ComponentStatus::Degraded } else { ComponentStatus::Up },
+        critical: false,
+        latency_ms: None,
+        details: json!({ "replays": stats }),
+    }
+}
+
+/// Batches the SIEM keeps refusing degrade the service; `None` when
+/// forwarding is off
+fn check_siem_forwarder(state: &AppState) -> Option<ComponentHealth> {
+    let stats = state.audit_logger.forwarder_stats()?;
+
+    Some(ComponentHealth {
+        status: if stats.consecutive_failures > 0 { ComponentStatus::Degraded } else { ComponentStatus::Up },
+        critical: false,
+        latency_ms: None,
+        details: json!({ "forwarder": stats }),
+    })
+}
+
+/// Webhook queue depth; dead letters waiting for replay degrade the service
+async fn check_webhooks(state: &AppState) -> ComponentHealth {
+    let repository = WebhookRepository::new(state.postgres.clone());
+
+    let (result, latency_ms) = timed(repository.queue_stats()).await;
+    match result {
+        Some(Ok(stats)) => ComponentHealth {
+            status: if stats.dead_letter_depth > 0 { ComponentStatus::Degraded } else { ComponentStatus::Up },
+            critical: false,
+            latency_ms: Some(latency_ms),
+            details: json!({
+                "dead_letter_depth": stats.dead_letter_depth,
+                "oldest_dead_letter_at": stats.oldest_dead_letter_at,
+                "pending_deliveries": stats.pending_deliveries,
+            }),
+        },
+        Some(Err(e)) => down(false, latency_ms, e.to_string(), None),
+        None => down(false, latency_ms, "timed out".to_string(), None),
+    }
+}
+
+async fn timed<F: Future>(future: F) -> (Option<F::Output>, u64) {
+    let started = Instant::now();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, future).await.ok();
+    (result, started.elapsed().as_millis() as u64)
+}
+
+fn down(critical: bool, latency_ms: u64, error: String, extra: Option<serde_json::Value>) -> ComponentHealth {
+    let mut details = json!({ "error": error });
+    if let Some(extra) = extra {
+        details["pool"] = extra;
+    }
+
+    ComponentHealth {
+        status: ComponentStatus::Down,
+        critical,
+        latency_ms: Some(latency_ms),
+        details,
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use validator::{ValidationError, ValidationErrors};
+use crate::core::cache::ProjectCache;
+use crate::core::error::AppError;
+
+/// Languages API messages are available in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "api_locale", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    En,
+    Fr,
+}
+
+/// The language messages are written in; responses in it are not rewritten
+pub const SOURCE_LOCALE: Locale = Locale::En;
+
+impl Locale {
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = AppError;
+
+    /// Accepts a language tag, ignoring its region (`fr-CA` is `fr`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.trim().split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Ok(Locale::En),
+            "fr" => Ok(Locale::Fr),
+            _ => Err(AppError::Validation(format!("Unsupported locale '{}'", s))),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// The supported locale the client prefers most in an `Accept-Language`
+/// header, or `None` when it accepts none of them (or anything, via `*`)
+pub fn negotiate(accept_language: &str) -> Option<Locale> {
+    let mut ranges: Vec<(&str, f32)> = accept_language
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map(|q| q.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+    // Stable, so equally weighted ranges keep the client's order
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges
+        .into_iter()
+        .take_while(|(tag, _)| *tag != "*")
+        .find_map(|(tag, _)| tag.parse().ok())
+}
+
+/// A locale's message catalog: source messages to their translation, and
+/// templates for validation failures keyed by validator code
+#[derive(Debug, Default, Deserialize)]
+struct Catalog {
+    #[serde(default)]
+    messages: HashMap<String, String>,
+    #[serde(default)]
+    validation: HashMap<String, String>,
+}
+
+fn catalog(locale: Locale) -> &'static Catalog {
+    static EN: OnceLock<Catalog> = OnceLock::new();
+    static FR: OnceLock<Catalog> = OnceLock::new();
+
+    let (cell, source) = match locale {
+        Locale::En => (&EN, include_str!("../../locales/en.json")),
+        Locale::Fr => (&FR, include_str!("../../locales/fr.json")),
+    };
+    cell.get_or_init(|| serde_json::from_str(source).expect("message catalog is valid JSON"))
+}
+
+/// Translate a source message, falling back to the message itself
+pub fn translate(locale: Locale, message: &str) -> String {
+    catalog(locale)
+        .messages
+        .get(message)
+        .cloned()
+        .unwrap_or_else(|| message.to_string())
+}
+
+/// Message for a failed validation rule, filled in with the rule's bounds
+pub fn validation_message(locale: Locale, code: &str, params: &serde_json::Map<String, Value>) -> String {
+    let key = match code {
+        "length" | "range" if params.contains_key("equal") => format!("{}.equal", code),
+        "length" | "range" => match (params.contains_key("min"), params.contains_key("max")) {
+            (true, true) => code.to_string(),
+            (true, false) => format!("{}.min", code),
+            (false, true) => format!("{}.max", code),
+            (false, false) => "invalid".to_string(),
+        },
+        _ => code.to_string(),
+    };
+
+    let templates = &catalog(locale).validation;
+    let template = templates
+        .get(&key)
+        .or_else(|| templates.get("invalid"))
+        .cloned()
+        .unwrap_or_else(|| "Is invalid".to_string());
+
+    params.iter().fold(template, |message, (name, value)| {
+        let value = match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        message.replace(&format!("{{{}}}", name), &value)
+    })
+}
+
+/// Per-field validation failures in the source locale, as returned in an
+/// error's details. The submitted value is left out, since it may be a
+/// secret.
+pub fn validation_details(errors: &ValidationErrors) -> Value {
+    let mut fields: Vec<_> = errors.field_errors().into_iter().collect();
+    fields.sort_by_key(|(field, _)| *field);
+
+    let fields: serde_json::Map<String, Value> = fields
+        .into_iter()
+        .map(|(field, errors)| {
+            let errors = errors.iter().map(field_error).collect();
+            (field.to_string(), Value::Array(errors))
+        })
+        .collect();
+    serde_json::json!({ "fields": fields })
+}
+
+fn field_error(error: &ValidationError) -> Value {
+    let params: serde_json::Map<String, Value> = error
+        .params
+        .iter()
+        .filter(|(name, _)| name.as_ref() != "value")
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect();
+    let message = validation_message(SOURCE_LOCALE, &error.code, &params);
+    serde_json::json!({ "code": error.code, "message": message, "params": params })
+}
+
+/// Rewrite an API response body's human-readable text into `locale`: the
+/// message, an error's message and any per-field validation messages
+pub fn localize_body(locale: Locale, body: &mut Value) {
+    if let Some(Value::String(message)) = body.get_mut("message") {
+        *message = translate(locale, message);
+    }
+
+    let Some(data) = body.get_mut("data") else {
+        return;
+    };
+    if let Some(Value::String(message)) = data.get_mut("error_message") {
+        *message = translate(locale, message);
+    }
+    let Some(Value::Object(fields)) = data.pointer_mut("/details/fields") else {
+        return;
+    };
+    for error in fields.values_mut().filter_map(Value::as_array_mut).flatten() {
+        let code = error.get("code").and_then(Value::as_str).unwrap_or("invalid").to_string();
+        let params = error.get("params").and_then(Value::as_object).cloned().unwrap_or_default();
+        error["message"] = Value::String(validation_message(locale, &code, &params));
+    }
+}
+
+/// Project default locales, cached for negotiation
+pub type ProjectLocaleCache = ProjectCache<Locale>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct Signup {
+        #[validate(email)]
+        email: String,
+        #[validate(length(min = 8))]
+        password: String,
+        #[validate(range(min = 1, max = 10))]
+        seats: i32,
+    }
+
+    #[test]
+    fn negotiation_follows_quality_and_order() {
+        assert_eq!(negotiate("fr-CA,fr;q=0.9,en;q=0.8"), Some(Locale::Fr));
+        assert_eq!(negotiate("de-DE, en;q=0.5, fr;q=0.7"), Some(Locale::Fr));
+        assert_eq!(negotiate("en-GB, fr"), Some(Locale::En));
+        assert_eq!(negotiate("fr;q=0, en;q=0.1"), Some(Locale::En));
+        assert_eq!(negotiate("de, *;q=0.5"), None);
+        assert_eq!(negotiate(""), None);
+    }
+
+    #[test]
+    fn messages_fall_back_to_the_source_text() {
+        assert_eq!(translate(Locale::Fr, "Not found"), "Introuvable");
+        assert_eq!(translate(Locale::Fr, "Something new"), "Something new");
+        assert_eq!(translate(Locale::En, "Not found"), "Not found");
+    }
+
+    #[test]
+    fn validation_details_are_localized_with_their_bounds() {
+        let errors = Signup {
+            email: "nope".to_string(),
+            password: "short".to_string(),
+            seats: 0,
+        }
+        .validate()
+        .unwrap_err();
+
+        let details = validation_details(&errors);
+        assert_eq!(details["fields"]["password"][0]["message"], "Length must be at least 8");
+        assert_eq!(details["fields"]["seats"][0]["message"], "Must be between 1 and 10");
+        assert!(details["fields"]["password"][0]["params"].get("value").is_none());
+
+        let mut body = serde_json::json!({
+            "status": "error",
+            "message": "Request failed",
+            "data": { "error_code": "VALIDATION_ERROR", "error_message": "Validation error", "details": details },
+        });
+        localize_body(Locale::Fr, &mut body);
+        assert_eq!(body["message"], "La requête a échoué");
+        assert_eq!(body["data"]["error_message"], "Erreur de validation");
+        assert_eq!(body["data"]["details"]["fields"]["email"][0]["message"], "Doit être une adresse e-mail valide");
+        assert_eq!(
+            body["data"]["details"]["fields"]["password"][0]["message"],
+            "La longueur doit être d'au moins 8"
+        );
+    }
+}
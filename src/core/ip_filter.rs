@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use crate::core::cache::ProjectCache;
+use crate::core::error::AppError;
+
+/// An IPv4 or IPv6 network in CIDR notation. A bare address is a network
+/// of that single address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.address, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix)).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix)).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AppError::Validation(format!("Invalid IP network '{}'", s));
+        let (address, prefix) = match s.trim().split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (s.trim(), None),
+        };
+
+        let address: IpAddr = address.parse().map_err(|_| invalid())?;
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse::<u8>().map_err(|_| invalid())?,
+            None => max_prefix,
+        };
+        if prefix > max_prefix {
+            return Err(invalid());
+        }
+
+        Ok(Self { address, prefix })
+    }
+}
+
+impl TryFrom<String> for IpNetwork {
+    type Error = AppError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<IpNetwork> for String {
+    fn from(network: IpNetwork) -> Self {
+        network.to_string()
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
+
+/// Why a client address was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpRejection {
+    /// The address falls in a denied network
+    Denied(IpNetwork),
+    /// The project has an allowlist and the address is not on it
+    NotAllowed,
+    /// The client address could not be determined while an allowlist applies
+    Unknown,
+}
+
+/// A project's network restrictions. Denied networks always win; when any
+/// networks are allowed, every other address is refused.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IpPolicy {
+    pub allow: Vec<IpNetwork>,
+    pub deny: Vec<IpNetwork>,
+}
+
+impl IpPolicy {
+    pub fn is_unrestricted(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Check a client address as reported by the proxy (`None` when absent
+    /// or unparseable)
+    pub fn check(&self, ip: Option<IpAddr>) -> Result<(), IpRejection> {
+        let Some(ip) = ip else {
+            return if self.allow.is_empty() {
+                Ok(())
+            } else {
+                Err(IpRejection::Unknown)
+            };
+        };
+
+        if let Some(network) = self.deny.iter().find(|network| network.contains(ip)) {
+            return Err(IpRejection::Denied(*network));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|network| network.contains(ip)) {
+            return Err(IpRejection::NotAllowed);
+        }
+        Ok(())
+    }
+}
+
+/// The address a request came from. That is the connecting peer, unless
+/// the peer is a trusted proxy: then it is the rightmost `X-Forwarded-For`
+/// hop not added by a trusted proxy. Hops left of it were written by the
+/// client and are never believed. `None` when the peer is unknown or a
+/// forwarded hop is unparseable.
+pub fn client_ip(peer: Option<IpAddr>, forwarded_for: &str, trusted_proxies: &[IpNetwork]) -> Option<IpAddr> {
+    let trusted = |ip: IpAddr| trusted_proxies.iter().any(|network| network.contains(ip));
+    let mut client = peer?;
+    if !trusted(client) {
+        return Some(client);
+    }
+    for hop in forwarded_for.rsplit(',').map(str::trim).filter(|hop| !hop.is_empty()) {
+        client = hop.parse().ok()?;
+        if !trusted(client) {
+            break;
+        }
+    }
+    Some(client)
+}
+
+/// Project IP policies, cached for enforcement
+pub type IpPolicyCache = ProjectCache<Arc<IpPolicy>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn networks_match_their_prefix() {
+        let network: IpNetwork = "10.1.0.0/16".parse().unwrap();
+        assert!(network.contains("10.1.200.3".parse().unwrap()));
+        assert!(!network.contains("10.2.0.1".parse().unwrap()));
+        assert!(!network.contains("::1".parse().unwrap()));
+
+        let single: IpNetwork = "2001:db8::1".parse().unwrap();
+        assert_eq!(single.to_string(), "2001:db8::1/128");
+        assert!(single.contains("2001:db8::1".parse().unwrap()));
+        assert!("0.0.0.0/0".parse::<IpNetwork>().unwrap().contains("8.8.8.8".parse().unwrap()));
+
+        for invalid in ["10.0.0.0/33", "10.0.0/8", "example.com", "::/129"] {
+            assert!(invalid.parse::<IpNetwork>().is_err(), "{} should not parse", invalid);
+        }
+    }
+
+    #[test]
+    fn forwarded_addresses_are_only_believed_from_trusted_proxies() {
+        let proxies: Vec<IpNetwork> = vec!["10.0.0.0/8".parse().unwrap()];
+
+        // A direct caller cannot claim another address
+        assert_eq!(client_ip(ip("198.51.100.7"), "203.0.113.5", &proxies), ip("198.51.100.7"));
+        // A trusted proxy reports the caller
+        assert_eq!(client_ip(ip("10.0.0.1"), "203.0.113.5", &proxies), ip("203.0.113.5"));
+        // Hops the caller wrote left of the proxies' own are ignored
+        assert_eq!(
+            client_ip(ip("10.0.0.1"), "203.0.113.5, 198.51.100.7, 10.0.0.2", &proxies),
+            ip("198.51.100.7")
+        );
+        assert_eq!(client_ip(ip("10.0.0.1"), "", &proxies), ip("10.0.0.1"));
+        assert_eq!(client_ip(ip("10.0.0.1"), "garbage", &proxies), None);
+        assert_eq!(client_ip(None, "203.0.113.5", &proxies), None);
+        assert_eq!(client_ip(ip("10.0.0.1"), "203.0.113.5", &[]), ip("10.0.0.1"));
+    }
+
+    #[test]
+    fn deny_wins_over_allow() {
+        let policy = IpPolicy {
+            allow: vec!["192.168.0.0/16".parse().unwrap()],
+            deny: vec!["192.168.13.0/24".parse().unwrap()],
+        };
+        assert_eq!(policy.check(ip("192.168.1.10")), Ok(()));
+        assert!(matches!(policy.check(ip("192.168.13.7")), Err(IpRejection::Denied(_))));
+        assert_eq!(policy.check(ip("203.0.113.5")), Err(IpRejection::NotAllowed));
+        assert_eq!(policy.check(None), Err(IpRejection::Unknown));
+
+        let deny_only = IpPolicy {
+            allow: vec![],
+            deny: vec!["203.0.113.0/24".parse().unwrap()],
+        };
+        assert_eq!(deny_only.check(ip("198.51.100.1")), Ok(()));
+        assert_eq!(deny_only.check(None), Ok(()));
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Last known state of a background job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    pub name: String,
+    pub interval_seconds: u64,
+    pub registered_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+}
+
+impl JobStatus {
+    /// A job is overdue when it has not run within two of its intervals
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        let reference = self.last_run_at.unwrap_or(self.registered_at);
+        let allowed = chrono::Duration::seconds(self.interval_seconds.saturating_mul(2) as i64);
+        now - reference > allowed
+    }
+}
+
+/// Tracks background job runs so their health can be reported. Each job
+/// registers under a fixed name, the `*_JOB` constant in its module, and
+/// reports its runs under that same name.
+#[derive(Debug, Clone, Default)]
+pub struct JobMonitor {
+    jobs: Arc<Mutex<HashMap<String, JobStatus>>>,
+}
+
+impl JobMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, name: &str, interval: Duration) {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.insert(
+            name.to_string(),
+            JobStatus {
+                name: name.to_string(),
+                interval_seconds: interval.as_secs(),
+                registered_at: Utc::now(),
+                last_run_at: None,
+                last_success_at: None,
+                last_error: None,
+                consecutive_failures: 0,
+            },
+        );
+    }
+
+    pub fn record_success(&self, name: &str) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.get_mut(name) {
+            let now = Utc::now();
+            job.last_run_at = Some(now);
+            job.last_success_at = Some(now);
+            job.last_error = None;
+            job.consecutive_failures = 0;
+        }
+    }
+
+    pub fn record_failure(&self, name: &str, error: String) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.get_mut(name) {
+            job.last_run_at = Some(Utc::now());
+            job.last_error = Some(error);
+            job.consecutive_failures += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> Vec<JobStatus> {
+        let jobs = self.jobs.lock().unwrap();
+        let mut statuses: Vec<JobStatus> = jobs.values().cloned().collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+}
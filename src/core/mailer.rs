@@ -0,0 +1,107 @@
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// An outgoing plain-text email
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Delivery channel for transactional email
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: EmailMessage) -> AppResult<()>;
+}
+
+/// Writes emails to the log instead of delivering them (local development)
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, message: EmailMessage) -> AppResult<()> {
+        tracing::info!(
+            to = %message.to,
+            subject = %message.subject,
+            "Email not delivered (log mailer):\n{}",
+            message.body
+        );
+        Ok(())
+    }
+}
+
+/// Delivers email through an HTTP email API accepting
+/// `{from, to, subject, text}` JSON with a bearer API key
+pub struct HttpMailer {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    from: String,
+}
+
+impl HttpMailer {
+    pub fn new(api_url: String, api_key: String, from: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            from,
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl Mailer for HttpMailer {
+    async fn send(&self, message: EmailMessage) -> AppResult<()> {
+        let payload = serde_json::json!({
+            "from": self.from,
+            "to": message.to,
+            "subject": message.subject,
+            "text": message.body,
+        });
+
+        let request = self.client.post(&self.api_url).bearer_auth(&self.api_key).json(&payload);
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Email request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Email provider returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Name of the email API's outbound client
+const MAIL_CLIENT: &str = "mail";
+
+/// Build the mailer selected by `MAIL_PROVIDER`
+pub fn from_config(config: &Config, clients: &HttpClients) -> AppResult<Arc<dyn Mailer>> {
+    match config.mail_provider.as_str() {
+        "log" => Ok(Arc::new(LogMailer)),
+        "http" => {
+            let missing = |name: &str| AppError::Internal(format!("{} is required for the http mail provider", name));
+            Ok(Arc::new(HttpMailer::new(
+                config.mail_api_url.clone().ok_or_else(|| missing("MAIL_API_URL"))?,
+                config.mail_api_key.clone().ok_or_else(|| missing("MAIL_API_KEY"))?,
+                config.mail_from.clone(),
+            )
+            .with_http_client(clients.get(MAIL_CLIENT))))
+        }
+        other => Err(AppError::Internal(format!("Unknown mail provider '{}'", other))),
+    }
+}
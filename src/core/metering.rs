@@ -0,0 +1,166 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// One daily usage bucket: calls by a project to an endpoint under a scope
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UsageKey {
+    pub project_id: Uuid,
+    pub developer_id: Uuid,
+    pub usage_date: NaiveDate,
+    pub scope: String,
+    /// Method and route template, e.g. `GET /api/v1/payments/:id`
+    pub endpoint: String,
+}
+
+/// Counts accumulated for a usage bucket
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UsageCount {
+    pub requests: i64,
+    pub errors: i64,
+}
+
+/// In-memory API call counter.
+///
+/// Counts are buffered per bucket and periodically drained into Postgres by
+/// the usage flush job, keeping metering off the request's critical path.
+#[derive(Debug, Clone, Default)]
+pub struct UsageMeter {
+    buckets: Arc<Mutex<HashMap<UsageKey, UsageCount>>>,
+}
+
+impl UsageMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one call against a bucket
+    pub fn record(&self, key: UsageKey, is_error: bool) {
+        let mut buckets = self.buckets.lock().unwrap();
+        let count = buckets.entry(key).or_default();
+        count.requests += 1;
+        if is_error {
+            count.errors += 1;
+        }
+    }
+
+    /// Take all buffered counts, leaving the buffer empty
+    pub fn drain(&self) -> Vec<(UsageKey, UsageCount)> {
+        let mut buckets = self.buckets.lock().unwrap();
+        buckets.drain().collect()
+    }
+
+    /// Buffered, not yet flushed, calls by a project on or after a date
+    pub fn pending_requests(&self, project_id: Uuid, since: NaiveDate) -> i64 {
+        let buckets = self.buckets.lock().unwrap();
+        buckets
+            .iter()
+            .filter(|(key, _)| key.project_id == project_id && key.usage_date >= since)
+            .map(|(_, count)| count.requests)
+            .sum()
+    }
+
+    /// Put counts back after a failed flush so they are retried on the next run
+    pub fn restore(&self, entries: Vec<(UsageKey, UsageCount)>) {
+        let mut buckets = self.buckets.lock().unwrap();
+        for (key, restored) in entries {
+            let count = buckets.entry(key).or_default();
+            count.requests += restored.requests;
+            count.errors += restored.errors;
+        }
+    }
+}
+
+/// A project's position against its monthly quota
+#[derive(Debug, Clone)]
+pub struct QuotaState {
+    /// Monthly call limit; `None` means unlimited
+    pub limit: Option<i64>,
+    pub used: i64,
+    pub period_start: NaiveDate,
+    pub resets_at: DateTime<Utc>,
+    /// Whether the overrun has already been audited this period
+    pub exceeded_reported: bool,
+    loaded_at: Instant,
+}
+
+impl QuotaState {
+    pub fn new(limit: Option<i64>, used: i64, period_start: NaiveDate, resets_at: DateTime<Utc>) -> Self {
+        Self {
+            limit,
+            used,
+            period_start,
+            resets_at,
+            exceeded_reported: false,
+            loaded_at: Instant::now(),
+        }
+    }
+
+    pub fn remaining(&self) -> Option<i64> {
+        self.limit.map(|limit| (limit - self.used).max(0))
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == Some(0)
+    }
+}
+
+/// Short-lived cache of project quota positions.
+///
+/// Entries are reloaded from the usage aggregates once older than `ttl`, and
+/// advanced locally for each admitted call in between.
+#[derive(Debug, Clone)]
+pub struct QuotaCache {
+    entries: Arc<Mutex<HashMap<Uuid, QuotaState>>>,
+    ttl: Duration,
+}
+
+impl QuotaCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    /// Get a cached position if it is fresh and for the given period
+    pub fn get(&self, project_id: Uuid, period_start: NaiveDate) -> Option<QuotaState> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(&project_id)
+            .filter(|state| state.period_start == period_start && state.loaded_at.elapsed() < self.ttl)
+            .cloned()
+    }
+
+    pub fn store(&self, project_id: Uuid, state: QuotaState) {
+        let mut entries = self.entries.lock().unwrap();
+        let exceeded_reported = entries
+            .get(&project_id)
+            .is_some_and(|previous| previous.period_start == state.period_start && previous.exceeded_reported);
+        entries.insert(project_id, QuotaState { exceeded_reported, ..state });
+    }
+
+    /// Count an admitted call against a cached position
+    pub fn consume(&self, project_id: Uuid) {
+        if let Some(state) = self.entries.lock().unwrap().get_mut(&project_id) {
+            state.used += 1;
+        }
+    }
+
+    /// Mark the overrun as reported, returning whether this is the first report
+    pub fn report_exceeded(&self, project_id: Uuid) -> bool {
+        match self.entries.lock().unwrap().get_mut(&project_id) {
+            Some(state) if !state.exceeded_reported => {
+                state.exceeded_reported = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn invalidate(&self, project_id: Uuid) {
+        self.entries.lock().unwrap().remove(&project_id);
+    }
+}
@@ -1,15 +1,30 @@
 use axum::{
-    extract::{Request, State},
+    body::{to_bytes, Body},
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{header, HeaderName, HeaderValue},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use std::net::SocketAddr;
 use std::time::Instant;
 use tracing::{info, warn};
+use crate::auth::{middleware::decode_bearer_claims, model::JwtClaims, scopes};
 use crate::core::{
     AppState,
     audit::{AuditEvent, AuditEventType, AuditSeverity, extract_audit_context},
+    capture,
+    deadline::RequestDeadline,
+    error::AppError,
+    i18n::{self, Locale, SOURCE_LOCALE},
+    ip_filter::{self, IpRejection},
+    metering::UsageKey,
     rate_limit::RateLimitError,
+    rbac::{self, PermissionContext},
+    versioning::{self, ApiVersion, ProjectVersionRules, VersionStatus},
 };
+use crate::captures::{model::NewDebugCapture, service::capture_service};
+use crate::organizations::service::{organization_service, TENANT_HEADER};
+use crate::usage::quota::quota_service;
 
 /// Combined security middleware that handles rate limiting, audit logging, and monitoring
 pub async fn security_middleware(
@@ -110,18 +125,80 @@ pub async fn security_middleware(
         }
     }
 
-    // 2. Add request ID for tracing
+    // 2. Project IP restrictions, for requests made with a project's token
+    if let Some(claims) = request_claims(&req, &app_state) {
+        let policy = match organization_service(&app_state).ip_policy(claims.project_id).await {
+            Ok(policy) => policy,
+            Err(e) => {
+                // Fail closed: the restriction cannot be checked
+                warn!(project_id = %claims.project_id, "Failed to load project IP rules: {}", e);
+                return Ok(e.into_response());
+            }
+        };
+
+        // The caller writes X-Forwarded-For, so only hops added by our own
+        // proxies count
+        let peer = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let forwarded_for = req
+            .headers()
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        let client_ip = ip_filter::client_ip(peer, &forwarded_for, &app_state.config.trusted_proxies);
+        if let Err(rejection) = policy.check(client_ip) {
+            let reason = match &rejection {
+                IpRejection::Denied(network) => format!("Address is in denied network {}", network),
+                IpRejection::NotAllowed => "Address is not in the project's allowlist".to_string(),
+                IpRejection::Unknown => "Client address could not be determined".to_string(),
+            };
+            let blocked_ip = client_ip.map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+            warn!(
+                ip = blocked_ip,
+                project_id = %claims.project_id,
+                "Request blocked by project IP rules: {}", reason
+            );
+
+            let event = AuditEvent::new(AuditEventType::IpAccessBlocked)
+                .severity(AuditSeverity::Warning)
+                .user_id(claims.developer_id)
+                .project_id(claims.project_id)
+                .ip_address(blocked_ip)
+                .metadata("forwarded_for".to_string(), serde_json::json!(audit_context.ip_address))
+                .user_agent(audit_context.user_agent.clone().unwrap_or_default())
+                .resource(audit_context.uri.clone())
+                .action(audit_context.method.clone())
+                .success(false)
+                .error(reason)
+                .risk_score(50)
+                .compliance_tag("SECURITY".to_string());
+            app_state.audit_logger.log(event).await;
+
+            let error = AppError::Authorization(
+                "Access from this IP address is not allowed for this project".to_string(),
+            );
+            return Ok(error.into_response());
+        }
+
+        req.extensions_mut().insert(claims);
+    }
+
+    // 3. Add request ID for tracing
     let request_id = uuid::Uuid::new_v4().to_string();
     req.headers_mut().insert(
         "x-request-id",
         request_id.clone().parse().unwrap(),
     );
 
-    // 3. Process request
+    // 4. Process request
     let response = next.run(req).await;
     let duration = start_time.elapsed();
 
-        // 4. Log successful request completion
+    // 5. Log successful request completion
     let end_time = std::time::Instant::now();
     let duration = end_time - start_time;
     let success = response.status().is_success();
@@ -147,7 +224,7 @@ pub async fn security_middleware(
 
     app_state.audit_logger.log(event).await;
 
-    // 5. Add security headers to response
+    // 6. Add security headers to response
     let mut response = response;
     let headers = response.headers_mut();
     
@@ -209,26 +286,62 @@ pub async fn auth_security_middleware(
     Ok(response)
 }
 
-/// RBAC middleware for checking permissions
+/// RBAC middleware enforcing the route permission table. Authenticated
+/// callers on a privileged route need the mapped permission through one of
+/// their roles; requests without a token are left for the handler to reject.
 pub async fn rbac_middleware(
     State(app_state): State<AppState>,
-    req: Request,
+    mut req: Request,
     next: Next,
 ) -> Result<Response, axum::http::StatusCode> {
-    // Extract JWT claims (if present)
-    // Check if user has required permissions for the endpoint
-    // This would be endpoint-specific and require integration with JWT middleware
-    
     let audit_context = extract_audit_context(&req);
     let is_api = req.uri().path().starts_with("/api");
     let resource_path = req.uri().path().to_string();
-    
-    // For now, pass through - full RBAC integration requires JWT token extraction
+
+    let required = rbac::required_permission(req.method(), &resource_path);
+    let claims = required.as_ref().and_then(|_| request_claims(&req, &app_state));
+
+    if let (Some(permission), Some(claims)) = (&required, &claims) {
+        let user_id = claims.developer_id;
+        if let Err(e) = app_state.rbac_service.load_user_roles(&app_state.postgres, user_id).await {
+            warn!(user_id = %user_id, "Failed to load roles, denying request: {}", e);
+            return Ok(e.into_response());
+        }
+
+        let context = PermissionContext::new(user_id, audit_context.ip_address.clone());
+        if let Err(e) = app_state.rbac_service.authorize(user_id, permission.clone(), context) {
+            let event = AuditEvent::new(AuditEventType::AccessDenied)
+                .severity(AuditSeverity::Warning)
+                .user_id(user_id)
+                .ip_address(audit_context.ip_address.clone())
+                .user_agent(audit_context.user_agent.clone().unwrap_or_default())
+                .resource(resource_path)
+                .action(audit_context.method.clone())
+                .success(false)
+                .error(e.to_string())
+                .metadata(
+                    "permission".to_string(),
+                    serde_json::json!(permission.to_string()),
+                )
+                .risk_score(25)
+                .compliance_tag("RBAC".to_string())
+                .compliance_tag("AUTHORIZATION".to_string());
+            app_state.audit_logger.log(event).await;
+
+            return Ok(e.into_response());
+        }
+    }
+
+    // Share the decoded claims with later layers
+    if let Some(claims) = claims {
+        req.extensions_mut().insert(claims);
+    }
+
     let response = next.run(req).await;
-    
+
     // Log authorization events for protected endpoints
     if is_api {
-        let event = AuditEvent::new(AuditEventType::AccessGranted)
+        let mut event = AuditEvent::new(AuditEventType::AccessGranted)
             .severity(AuditSeverity::Info)
             .ip_address(audit_context.ip_address.clone())
             .user_agent(audit_context.user_agent.clone().unwrap_or_default())
@@ -237,9 +350,458 @@ pub async fn rbac_middleware(
             .success(response.status().is_success())
             .compliance_tag("RBAC".to_string())
             .compliance_tag("AUTHORIZATION".to_string());
+        if let Some(permission) = required {
+            event = event.metadata(
+                "permission".to_string(),
+                serde_json::json!(permission.to_string()),
+            );
+        }
 
         app_state.audit_logger.log(event).await;
     }
-    
+
+    Ok(response)
+}
+
+/// Usage metering middleware counting authenticated API calls per project,
+/// scope and endpoint. Applied as a route layer so the matched route
+/// template is available.
+pub async fn usage_metering_middleware(
+    State(app_state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let claims = request_claims(&req, &app_state);
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let method = req.method().clone();
+
+    // Share the decoded claims with the JwtToken extractor
+    if let Some(claims) = &claims {
+        req.extensions_mut().insert(claims.clone());
+    }
+
+    let response = next.run(req).await;
+
+    if let (Some(claims), Some(route)) = (claims, route) {
+        let scope = versioning::unversioned(&route)
+            .unwrap_or(&route)
+            .strip_prefix('/')
+            .and_then(|rest| rest.split('/').next())
+            .unwrap_or("other")
+            .to_string();
+        let status = response.status();
+
+        app_state.usage_meter.record(
+            UsageKey {
+                project_id: claims.project_id,
+                developer_id: claims.developer_id,
+                usage_date: chrono::Utc::now().date_naive(),
+                scope,
+                endpoint: format!("{} {}", method, route),
+            },
+            status.is_client_error() || status.is_server_error(),
+        );
+    }
+
+    Ok(response)
+}
+
+/// Scope enforcement for authenticated API calls. Routes under a module need
+/// the module's read scope for GET, HEAD and OPTIONS and its write scope for
+/// every other method; legacy module-wide scopes grant both. Tokens acting
+/// for an end user reach only the modules serving end users. Applied as a
+/// route layer so the matched route template is available.
+pub async fn scope_middleware(
+    State(app_state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let Some(claims) = request_claims(&req, &app_state) else {
+        return Ok(next.run(req).await);
+    };
+    let required = req
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|route| scopes::required_scope(req.method(), route.as_str()));
+
+    let user_module = required.is_some_and(|(module, _)| scopes::USER_TOKEN_MODULES.contains(&module));
+    let denial = match required {
+        _ if claims.user_id.is_some() && !user_module => {
+            Some((None, "User tokens cannot access this endpoint".to_string()))
+        }
+        Some((module, access)) if !scopes::grants(&claims.scopes, module, access) => {
+            let scope = scopes::module_scope(module, access);
+            let message = format!("Token is missing the '{}' scope", scope);
+            Some((Some(scope), message))
+        }
+        _ => None,
+    };
+
+    if let Some((scope, message)) = denial {
+        let audit_context = extract_audit_context(&req);
+        let event = AuditEvent::new(AuditEventType::AccessDenied)
+            .severity(AuditSeverity::Warning)
+            .user_id(claims.developer_id)
+            .ip_address(audit_context.ip_address.clone())
+            .resource(req.uri().path().to_string())
+            .action(audit_context.method.clone())
+            .success(false)
+            .metadata("scope".to_string(), serde_json::json!(scope))
+            .metadata("project_id".to_string(), serde_json::json!(claims.project_id))
+            .metadata("end_user_id".to_string(), serde_json::json!(claims.user_id))
+            .compliance_tag("AUTHORIZATION".to_string());
+        app_state.audit_logger.log(event).await;
+
+        return Ok(AppError::Authorization(message).into_response());
+    }
+
+    req.extensions_mut().insert(claims);
+    Ok(next.run(req).await)
+}
+
+/// Monthly quota enforcement for authenticated API calls. Runs outside usage
+/// metering so rejected calls are not billed.
+pub async fn quota_middleware(
+    State(app_state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let Some(claims) = request_claims(&req, &app_state) else {
+        return Ok(next.run(req).await);
+    };
+    let project_id = claims.project_id;
+    req.extensions_mut().insert(claims);
+
+    let check = match quota_service(&app_state).admit(project_id).await {
+        Ok(check) => check,
+        Err(e) => {
+            // Fail open: a metering outage must not take the API down
+            warn!(project_id = %project_id, error = %e, "Quota check failed, allowing request");
+            return Ok(next.run(req).await);
+        }
+    };
+
+    let mut response = if check.allowed {
+        next.run(req).await
+    } else {
+        warn!(project_id = %project_id, limit = ?check.limit, "Monthly quota exceeded");
+        Response::builder()
+            .status(axum::http::StatusCode::TOO_MANY_REQUESTS)
+            .body("Monthly API quota exceeded. Please contact support or wait for the quota to reset.".into())
+            .unwrap()
+    };
+
+    if let Some(limit) = check.limit {
+        let headers = response.headers_mut();
+        headers.insert("X-Quota-Limit", limit.to_string().parse().unwrap());
+        headers.insert(
+            "X-Quota-Remaining",
+            check.remaining.unwrap_or(0).to_string().parse().unwrap(),
+        );
+        headers.insert("X-Quota-Reset", check.resets_at.timestamp().to_string().parse().unwrap());
+    }
+
+    Ok(response)
+}
+
+/// Tenant isolation for authenticated API calls: the token's tenant must
+/// still own its project and match any tenant the client asks for
+pub async fn tenant_isolation_middleware(
+    State(app_state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let Some(claims) = request_claims(&req, &app_state) else {
+        return Ok(next.run(req).await);
+    };
+    let requested = req
+        .headers()
+        .get(TENANT_HEADER)
+        .map(|value| value.to_str().unwrap_or_default().to_string());
+
+    if let Err(e) = organization_service(&app_state)
+        .ensure_tenant_access(&claims, requested.as_deref())
+        .await
+    {
+        let audit_context = extract_audit_context(&req);
+        let event = AuditEvent::new(AuditEventType::CrossTenantAccessDenied)
+            .severity(AuditSeverity::Warning)
+            .user_id(claims.developer_id)
+            .ip_address(audit_context.ip_address.clone())
+            .resource(req.uri().path().to_string())
+            .action(audit_context.method.clone())
+            .success(false)
+            .metadata("tenant_id".to_string(), serde_json::json!(claims.tenant_id))
+            .metadata("requested_tenant".to_string(), serde_json::json!(requested))
+            .compliance_tag("TENANCY".to_string());
+        app_state.audit_logger.log(event).await;
+
+        warn!(project_id = %claims.project_id, tenant_id = %claims.tenant_id, "Tenant access rejected: {}", e);
+        return Ok(e.into_response());
+    }
+
+    req.extensions_mut().insert(claims);
+    Ok(next.run(req).await)
+}
+
+/// Localization of API messages. The locale is the first supported one in
+/// the Accept-Language header, else the calling project's default, else the
+/// configured default; JSON responses are rewritten into it.
+pub async fn localization_middleware(
+    State(app_state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let requested = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(i18n::negotiate);
+    let locale = match requested {
+        Some(locale) => locale,
+        None => {
+            let claims = request_claims(&req, &app_state);
+            project_locale(claims, &app_state).await.unwrap_or(app_state.config.default_locale)
+        }
+    };
+
+    let mut response = next.run(req).await;
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_LANGUAGE, HeaderValue::from_static(locale.code()));
+    headers.append(header::VARY, HeaderValue::from_static("Accept-Language"));
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if locale == SOURCE_LOCALE || !is_json {
+        return Ok(response);
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to read response body for localization: {}", e);
+            return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(mut body) => {
+            i18n::localize_body(locale, &mut body);
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Body::from(serde_json::to_vec(&body).unwrap_or_else(|_| bytes.to_vec()))
+        }
+        Err(_) => Body::from(bytes),
+    };
+
+    Ok(Response::from_parts(parts, body))
+}
+
+/// Default locale of the project whose token made the request
+async fn project_locale(claims: Option<JwtClaims>, app_state: &AppState) -> Option<Locale> {
+    let claims = claims?;
+    match organization_service(app_state).default_locale(claims.project_id).await {
+        Ok(locale) => locale,
+        Err(e) => {
+            warn!(project_id = %claims.project_id, "Failed to load project locale: {}", e);
+            None
+        }
+    }
+}
+
+/// API version policy. Requests under `/api/{version}` carry their version
+/// to the handlers; versions the calling project is no longer served are
+/// refused with 410, and deprecated ones are answered with `Deprecation`,
+/// `Sunset` and `Link` headers.
+pub async fn api_version_middleware(
+    State(app_state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let Some((version, _)) = ApiVersion::from_path(req.uri().path()) else {
+        return Ok(next.run(req).await);
+    };
+    req.extensions_mut().insert(version);
+
+    let rules = project_version_rules(request_claims(&req, &app_state), &app_state).await;
+    let today = chrono::Utc::now().date_naive();
+    let (deprecated_on, sunset_on) = match app_state.version_policy.status(version, rules.as_ref(), today) {
+        VersionStatus::Current => return Ok(next.run(req).await),
+        VersionStatus::Retired(reason) => return Ok(AppError::ApiVersionRetired(reason).into_response()),
+        VersionStatus::Deprecated { deprecated_on, sunset_on } => (deprecated_on, sunset_on),
+    };
+
+    let mut response = next.run(req).await;
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&versioning::deprecation_header(deprecated_on)) {
+        headers.insert(HeaderName::from_static("deprecation"), value);
+    }
+    if let Some(value) = sunset_on.and_then(|date| HeaderValue::from_str(&versioning::sunset_header(date)).ok()) {
+        headers.insert(HeaderName::from_static("sunset"), value);
+    }
+    let link = app_state
+        .version_policy
+        .info_url()
+        .and_then(|url| HeaderValue::from_str(&format!("<{}>; rel=\"deprecation\"; type=\"text/html\"", url)).ok());
+    if let Some(link) = link {
+        headers.append(header::LINK, link);
+    }
+
+    Ok(response)
+}
+
+/// API versions the project whose token made the request is served
+async fn project_version_rules(claims: Option<JwtClaims>, app_state: &AppState) -> Option<ProjectVersionRules> {
+    let claims = claims?;
+    match organization_service(app_state).version_rules(claims.project_id).await {
+        Ok(rules) => rules,
+        Err(e) => {
+            warn!(project_id = %claims.project_id, "Failed to load project API versions: {}", e);
+            None
+        }
+    }
+}
+
+/// JWT claims for the request, reusing claims decoded by an earlier layer
+fn request_claims(req: &Request, app_state: &AppState) -> Option<JwtClaims> {
+    if let Some(claims) = req.extensions().get::<JwtClaims>() {
+        return Some(claims.clone());
+    }
+    decode_bearer_claims(req.headers(), &app_state.config.jwt_secret).ok()
+}
+
+/// Per-route timeout budgets. The handler runs with a `RequestDeadline`
+/// in its extensions and task, so repository and downstream calls stop when
+/// the budget is spent; a request still running then is answered with 504.
+pub async fn deadline_middleware(
+    State(app_state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let Some(budget) = app_state.timeout_budgets.for_path(req.uri().path()) else {
+        return Ok(next.run(req).await);
+    };
+    let deadline = RequestDeadline::new(budget);
+    req.extensions_mut().insert(deadline);
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+
+    match tokio::time::timeout(budget, deadline.scope(next.run(req))).await {
+        Ok(response) => Ok(response),
+        Err(_) => {
+            warn!(method = %method, path = %path, budget_ms = budget.as_millis() as u64, "Request deadline exceeded");
+            Ok(AppError::Timeout(format!("{} {} took longer than {:?}", method, path, budget)).into_response())
+        }
+    }
+}
+
+/// Largest request body buffered for a capture, matching the default body
+/// limit of the JSON extractors
+const MAX_CAPTURED_REQUEST_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Debug capture for projects that opted in: a sample of their requests and
+/// responses is stored, redacted, so integrators can see what the API
+/// received and what it answered. Runs outside the security layers so
+/// rejected calls are captured too.
+pub async fn debug_capture_middleware(
+    State(app_state): State<AppState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, axum::http::StatusCode> {
+    let Some(claims) = request_claims(&req, &app_state) else {
+        return Ok(next.run(req).await);
+    };
+    let project_id = claims.project_id;
+    req.extensions_mut().insert(claims);
+
+    let settings = match capture_service(&app_state).settings_for(project_id).await {
+        Ok(settings) => settings,
+        Err(e) => {
+            warn!(project_id = %project_id, "Failed to load debug capture settings: {}", e);
+            return Ok(next.run(req).await);
+        }
+    };
+    if !settings.sample() || !is_capturable(req.headers()) {
+        return Ok(next.run(req).await);
+    }
+
+    let started = Instant::now();
+    let max_body_bytes = app_state.config.debug_capture_max_body_bytes;
+    let (parts, body) = req.into_parts();
+    let request_bytes = match to_bytes(body, MAX_CAPTURED_REQUEST_BYTES as usize).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("Failed to read request body for debug capture: {}", e);
+            return Err(axum::http::StatusCode::BAD_REQUEST);
+        }
+    };
+    let mut capture = NewDebugCapture {
+        project_id,
+        method: parts.method.to_string(),
+        path: parts.uri.path().to_string(),
+        query: parts.uri.query().map(capture::redact_query),
+        status_code: 0,
+        request_headers: capture::redact_headers(&parts.headers),
+        request_body: capture::redact_body(content_type(&parts.headers), &request_bytes, max_body_bytes),
+        response_headers: serde_json::json!({}),
+        response_body: None,
+        duration_ms: 0,
+        retention_hours: settings.retention_hours,
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(request_bytes))).await;
+    let (parts, body) = response.into_parts();
+    capture.status_code = parts.status.as_u16() as i32;
+    capture.response_headers = capture::redact_headers(&parts.headers);
+
+    let response = if is_capturable(&parts.headers) {
+        let bytes = match to_bytes(body, usize::MAX).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to read response body for debug capture: {}", e);
+                return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
+        capture.response_body = capture::redact_body(content_type(&parts.headers), &bytes, max_body_bytes);
+        Response::from_parts(parts, Body::from(bytes))
+    } else {
+        capture.response_body = content_type(&parts.headers).map(|kind| format!("[{} not captured]", kind));
+        Response::from_parts(parts, body)
+    };
+    capture.duration_ms = started.elapsed().as_millis() as i64;
+
+    // Stored off the request path; a lost capture only costs debugging detail
+    tokio::spawn(async move {
+        if let Err(e) = capture_service(&app_state).record(capture).await {
+            warn!(project_id = %project_id, "Failed to store debug capture: {}", e);
+        }
+    });
+
     Ok(response)
-}
\ No newline at end of file
+}
+
+/// Whether a message's body can be buffered for a capture: not a stream, a
+/// file, or a request body too large to hold
+fn is_capturable(headers: &axum::http::HeaderMap) -> bool {
+    let streamed = content_type(headers).is_some_and(|kind| {
+        kind.starts_with("text/event-stream")
+            || kind.starts_with("multipart/")
+            || kind.starts_with("application/octet-stream")
+    });
+    let length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    let unbounded = length.is_none() && headers.contains_key(header::TRANSFER_ENCODING);
+
+    !streamed && !unbounded && length.unwrap_or(0) <= MAX_CAPTURED_REQUEST_BYTES
+}
+
+fn content_type(headers: &axum::http::HeaderMap) -> Option<&str> {
+    headers.get(header::CONTENT_TYPE).and_then(|value| value.to_str().ok())
+}
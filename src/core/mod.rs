@@ -1,20 +1,76 @@
+pub mod alerts;
+pub mod anomaly;
 pub mod audit;
+pub mod audit_chain;
+pub mod audit_writer;
+pub mod bootstrap;
+pub mod cache;
+pub mod capture;
+pub mod circuit_breaker;
+pub mod conditional;
 pub mod config;
+pub mod crypto;
 pub mod database;
+pub mod deadline;
 pub mod error;
+pub mod events;
 pub mod extractors;
+pub mod feature_flags;
+pub mod health;
+pub mod http_client;
+pub mod i18n;
+pub mod ip_filter;
+pub mod jobs;
+pub mod live_config;
+pub mod mailer;
+pub mod metering;
 pub mod middleware;
+pub mod openapi;
+pub mod partitions;
+pub mod pdf;
+pub mod qr;
 pub mod rate_limit;
 pub mod rbac;
+pub mod replay;
 pub mod response;
+pub mod secrets;
 pub mod security;
+pub mod siem;
+pub mod sms;
+pub mod storage;
+pub mod versioning;
 
 use crate::core::{
-    audit::AuditLogger, rate_limit::RateLimiter, rbac::RbacService,
-    security::AccountSecurityService,
+    audit::AuditLogger,
+    capture::CaptureSettingsCache,
+    circuit_breaker::CircuitBreakers,
+    config::Config,
+    crypto::{EnvelopeCipher, KeyProvider, KeyStore, LocalKeyProvider, UserCipher},
+    deadline::TimeoutBudgets,
+    error::AppResult,
+    events::EventBus,
+    feature_flags::FeatureFlags,
+    http_client::HttpClients,
+    i18n::ProjectLocaleCache,
+    ip_filter::IpPolicyCache,
+    jobs::JobMonitor,
+    live_config::LiveConfig,
+    mailer::Mailer,
+    metering::{QuotaCache, UsageMeter},
+    qr::QrRenderer,
+    rate_limit::{RateLimitConfig, RateLimiter},
+    rbac::{Permission, PermissionContext, RbacService},
+    replay::ReplayCache,
+    security::{AccountSecurityService, SecurityConfig},
+    sms::SmsSender,
+    storage::Storage,
+    versioning::{ProjectVersionRulesCache, VersionPolicy},
 };
 use mongodb::Client as MongoClient;
 use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -25,4 +81,122 @@ pub struct AppState {
     pub security_service: AccountSecurityService,
     pub rbac_service: RbacService,
     pub rate_limiter: RateLimiter,
+    pub qr_renderer: QrRenderer,
+    pub storage: Arc<dyn Storage>,
+    pub cipher: EnvelopeCipher,
+    pub user_cipher: UserCipher,
+    pub job_monitor: JobMonitor,
+    pub usage_meter: UsageMeter,
+    pub quota_cache: QuotaCache,
+    pub ip_policy_cache: IpPolicyCache,
+    pub project_locale_cache: ProjectLocaleCache,
+    pub project_version_cache: ProjectVersionRulesCache,
+    pub version_policy: VersionPolicy,
+    pub capture_settings_cache: CaptureSettingsCache,
+    pub feature_flags: FeatureFlags,
+    pub mailer: Arc<dyn Mailer>,
+    pub sms_sender: Arc<dyn SmsSender>,
+    pub event_bus: EventBus,
+    pub timeout_budgets: TimeoutBudgets,
+    pub circuit_breakers: CircuitBreakers,
+    pub http_clients: HttpClients,
+    pub live_config: LiveConfig,
+    pub replay_cache: ReplayCache,
+}
+
+impl AppState {
+    /// Build the shared state around connections, adapters, the breakers
+    /// guarding them and the outbound HTTP clients chosen by the caller,
+    /// configuring the in-process services from `config`
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config: Config,
+        postgres: PgPool,
+        mongodb: MongoClient,
+        audit_logger: AuditLogger,
+        storage: Arc<dyn Storage>,
+        mailer: Arc<dyn Mailer>,
+        circuit_breakers: CircuitBreakers,
+        http_clients: HttpClients,
+    ) -> AppResult<Self> {
+        let security_service = AccountSecurityService::new(SecurityConfig::from_config(&config));
+        let rate_limiter = RateLimiter::new(RateLimitConfig::from_config(&config));
+        let qr_renderer = QrRenderer::new(
+            config.qr_default_size,
+            config.qr_max_size,
+            config.qr_logo_path.clone(),
+        );
+        let key_provider: Arc<dyn KeyProvider> = Arc::new(LocalKeyProvider::from_config(
+            &config.encryption_keys,
+            &config.encryption_active_key_id,
+        )?);
+        let cipher = EnvelopeCipher::new(key_provider.clone());
+        let user_cipher = UserCipher::new(KeyStore::new(postgres.clone(), key_provider), cipher.clone());
+        let timeout_budgets =
+            TimeoutBudgets::from_config(config.request_timeout_seconds, &config.request_timeout_routes)?;
+
+        let sms_sender = sms::from_config(&config, &http_clients)?;
+
+        let feature_flags = FeatureFlags::new(
+            postgres.clone(),
+            Duration::from_secs(config.feature_flag_cache_ttl_seconds),
+        );
+
+        Ok(Self {
+            postgres,
+            mongodb,
+            audit_logger,
+            security_service,
+            rbac_service: RbacService::new(),
+            rate_limiter,
+            qr_renderer,
+            storage,
+            cipher,
+            user_cipher,
+            job_monitor: JobMonitor::new(),
+            usage_meter: UsageMeter::new(),
+            quota_cache: QuotaCache::new(Duration::from_secs(config.quota_cache_ttl_seconds)),
+            ip_policy_cache: IpPolicyCache::new(Duration::from_secs(config.ip_policy_cache_ttl_seconds)),
+            project_locale_cache: ProjectLocaleCache::new(Duration::from_secs(config.project_locale_cache_ttl_seconds)),
+            project_version_cache: ProjectVersionRulesCache::new(Duration::from_secs(
+                config.project_api_version_cache_ttl_seconds,
+            )),
+            version_policy: VersionPolicy::from_config(&config),
+            capture_settings_cache: CaptureSettingsCache::new(Duration::from_secs(
+                config.debug_capture_settings_cache_ttl_seconds,
+            )),
+            feature_flags,
+            mailer,
+            sms_sender,
+            event_bus: EventBus::new(config.event_stream_buffer_size),
+            timeout_budgets,
+            circuit_breakers,
+            http_clients,
+            live_config: LiveConfig::new(config.clone()),
+            replay_cache: ReplayCache::from_config(&config)?,
+            config,
+        })
+    }
+
+    /// Authorize an administrative action against the caller's persisted roles,
+    /// recording an access-denied audit event when the check fails
+    pub async fn authorize(
+        &self,
+        user_id: Uuid,
+        permission: Permission,
+        ip_address: String,
+        resource: String,
+    ) -> AppResult<()> {
+        self.rbac_service.load_user_roles(&self.postgres, user_id).await?;
+
+        let context = PermissionContext::new(user_id, ip_address.clone());
+        if let Err(error) = self.rbac_service.authorize(user_id, permission, context) {
+            self.audit_logger
+                .log_access_denied(resource, error.to_string(), ip_address)
+                .await;
+            return Err(error);
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,817 @@
+use std::sync::OnceLock;
+use axum::{
+    response::{Html, Json},
+    routing::get,
+    Router,
+};
+use utoipa::openapi::{
+    security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Content, KnownFormat, ObjectBuilder, OpenApi as OpenApiDocument, Ref, RefOr, Schema, SchemaFormat, SchemaType,
+};
+use utoipa::{Modify, OpenApi};
+use crate::core::config::Config;
+use crate::core::AppState;
+
+/// Paths whose successful responses are not wrapped in the `ApiResponse` envelope
+const UNWRAPPED_PATHS: [&str; 2] = ["/graphql", "/api/v1/stream"];
+
+/// OpenAPI description of the public API.
+///
+/// Handlers declare the `data` type of their responses; the envelope is
+/// added by [`ResponseEnvelope`] so the spec always matches `ApiResponse`.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "OpenBank API",
+        description = "Modular open banking API. Successful responses are wrapped in the standard envelope \
+                       `{status, message, data, meta}`; errors carry an `ErrorResponse` in `data`."
+    ),
+    paths(
+        crate::auth::controller::register_developer,
+        crate::auth::controller::oauth_token,
+        crate::auth::controller::refresh_token,
+        crate::auth::controller::request_login_code,
+        crate::auth::controller::create_project,
+        crate::auth::controller::get_me,
+        crate::auth::controller::list_logins,
+        crate::auth::controller::report_login,
+        crate::auth::controller::get_available_scopes,
+        crate::organizations::controller::list_organizations,
+        crate::organizations::controller::create_organization,
+        crate::organizations::controller::get_current_organization,
+        crate::organizations::controller::list_members,
+        crate::organizations::controller::update_member_role,
+        crate::organizations::controller::remove_member,
+        crate::organizations::controller::list_invitations,
+        crate::organizations::controller::create_invitation,
+        crate::organizations::controller::revoke_invitation,
+        crate::organizations::controller::get_invitation,
+        crate::organizations::controller::accept_invitation,
+        crate::organizations::controller::list_projects,
+        crate::organizations::controller::get_project_ip_rules,
+        crate::organizations::controller::set_project_ip_rules,
+        crate::organizations::controller::get_project_duplicate_rules,
+        crate::organizations::controller::set_project_duplicate_rules,
+        crate::organizations::controller::get_project_locale,
+        crate::organizations::controller::set_project_locale,
+        crate::organizations::controller::get_project_api_version_policy,
+        crate::organizations::controller::set_project_api_version_policy,
+        crate::payments::controller::verify_payee,
+        crate::payments::controller::validate_account,
+        crate::payments::controller::list_pending_approvals,
+        crate::payments::controller::approve_payment,
+        crate::payments::controller::payment_callback,
+        crate::payments::controller::cancel_payment,
+        crate::payments::controller::get_payment_qr,
+        crate::payments::controller::create_payment_intent,
+        crate::payments::controller::get_payment_intent,
+        crate::payments::controller::confirm_payment_intent,
+        crate::payments::controller::cancel_payment_intent,
+        crate::fees::controller::preview_fees,
+        crate::fees::controller::list_fee_schedules,
+        crate::fees::controller::create_fee_schedule,
+        crate::fees::controller::update_fee_schedule,
+        crate::fx::controller::create_quote,
+        crate::fx::controller::get_quote,
+        crate::disputes::controller::open_dispute,
+        crate::disputes::controller::get_disputes,
+        crate::disputes::controller::get_dispute_by_id,
+        crate::disputes::controller::upload_evidence,
+        crate::disputes::controller::download_evidence,
+        crate::disputes::controller::update_dispute_status,
+        crate::goals::controller::create_goal,
+        crate::goals::controller::get_goals,
+        crate::goals::controller::get_goal_by_id,
+        crate::goals::controller::update_goal,
+        crate::goals::controller::close_goal,
+        crate::goals::controller::allocate_funds,
+        crate::goals::controller::release_funds,
+        crate::goals::controller::get_goal_movements,
+        crate::goals::controller::get_account_goal_balance,
+        crate::virtual_accounts::controller::get_virtual_account_transactions,
+        crate::virtual_accounts::controller::get_virtual_account_balance,
+        crate::virtual_accounts::controller::update_virtual_account_details,
+        crate::account_numbers::controller::get_account_number_schemes,
+        crate::account_numbers::controller::set_account_number_scheme,
+        crate::account_numbers::controller::delete_account_number_scheme,
+        crate::account_numbers::controller::validate_account_number,
+        crate::metadata_schemas::controller::list_metadata_schemas,
+        crate::metadata_schemas::controller::get_metadata_schema,
+        crate::metadata_schemas::controller::set_metadata_schema,
+        crate::metadata_schemas::controller::delete_metadata_schema,
+        crate::metadata_schemas::controller::validate_metadata,
+        crate::account_closures::controller::close_account,
+        crate::account_closures::controller::get_account_closure,
+        crate::interest::controller::get_accrued_interest,
+        crate::interest::controller::list_interest_rates,
+        crate::interest::controller::create_interest_rate,
+        crate::reconciliation::controller::ingest_settlement,
+        crate::reconciliation::controller::list_runs,
+        crate::reconciliation::controller::get_run,
+        crate::reconciliation::controller::list_breaks,
+        crate::reconciliation::controller::resolve_break,
+        crate::income::controller::initiate_income_verification,
+        crate::income::controller::upload_income_documents,
+        crate::income::controller::get_income_verification_status,
+        crate::income::controller::request_employer_confirmation,
+        crate::income::controller::list_employer_confirmations,
+        crate::income::controller::get_employer_confirmation,
+        crate::income::controller::submit_employer_confirmation,
+        crate::income::controller::get_income_report,
+        crate::income::controller::verify_income_report,
+        crate::income::controller::get_affordability,
+        crate::income::controller::initiate_credit_check,
+        crate::income::controller::get_credit_check,
+        crate::income::controller::purge_credit_report,
+        crate::income::controller::credit_check_callback,
+        crate::kyc::controller::get_user_tier,
+        crate::kyc::controller::refresh_user_tier,
+        crate::identity::controller::get_address,
+        crate::identity::controller::set_address,
+        crate::identity::controller::verify_address,
+        crate::identity::controller::get_kyc_status,
+        crate::identity::controller::screen_verification,
+        crate::identity::controller::check_face_quality,
+        crate::verification_sessions::controller::create_session,
+        crate::verification_sessions::controller::get_session,
+        crate::verification_sessions::controller::submit_step,
+        crate::verification_sessions::controller::complete_session,
+        crate::account_controls::controller::get_account_freeze,
+        crate::account_controls::controller::freeze_account,
+        crate::account_controls::controller::unfreeze_account,
+        crate::account_controls::controller::get_virtual_account_freeze,
+        crate::account_controls::controller::freeze_virtual_account,
+        crate::account_controls::controller::unfreeze_virtual_account,
+        crate::developers::controller::list_developers,
+        crate::developers::controller::get_developer,
+        crate::developers::controller::suspend_developer,
+        crate::developers::controller::reinstate_developer,
+        crate::developers::controller::delete_developer,
+        crate::data_erasure::controller::get_erasure,
+        crate::data_erasure::controller::erase_user_data,
+        crate::ledger::controller::list_integrity_runs,
+        crate::ledger::controller::get_integrity_run,
+        crate::general_ledger::controller::list_gl_accounts,
+        crate::general_ledger::controller::create_gl_account,
+        crate::general_ledger::controller::list_posting_rules,
+        crate::general_ledger::controller::set_posting_rule,
+        crate::general_ledger::controller::get_trial_balance,
+        crate::treasury::controller::get_positions,
+        crate::treasury::controller::list_position_snapshots,
+        crate::treasury::controller::take_position_snapshot,
+        crate::treasury::controller::freeze_positions,
+        crate::payment_analytics::controller::get_payment_analytics,
+        crate::regulatory_reports::controller::list_jurisdictions,
+        crate::regulatory_reports::controller::list_reportable,
+        crate::regulatory_reports::controller::generate_report,
+        crate::regulatory_reports::controller::list_reports,
+        crate::regulatory_reports::controller::get_report,
+        crate::regulatory_reports::controller::download_report,
+        crate::regulatory_reports::controller::update_submission,
+        crate::roles::controller::list_roles,
+        crate::roles::controller::create_role,
+        crate::roles::controller::get_role,
+        crate::roles::controller::update_role,
+        crate::roles::controller::delete_role,
+        crate::roles::controller::list_assignments,
+        crate::roles::controller::assign_role,
+        crate::roles::controller::revoke_role,
+        crate::feature_flags::controller::list_feature_flags,
+        crate::feature_flags::controller::create_feature_flag,
+        crate::feature_flags::controller::get_feature_flag,
+        crate::feature_flags::controller::update_feature_flag,
+        crate::feature_flags::controller::delete_feature_flag,
+        crate::feature_flags::controller::evaluate_feature_flag,
+        crate::feature_flags::controller::set_feature_flag_target,
+        crate::feature_flags::controller::remove_feature_flag_target,
+        crate::core::live_config::get_effective_config,
+        crate::core::live_config::reload_effective_config,
+        crate::core::audit_chain::get_audit_chain,
+        crate::core::audit_chain::verify_audit_chain,
+        crate::usage::controller::get_project_usage,
+        crate::usage::controller::get_project_quota,
+        crate::usage::controller::override_project_quota,
+        crate::usage::controller::clear_project_quota_override,
+        crate::usage::controller::export_billing,
+        crate::captures::controller::get_capture_settings,
+        crate::captures::controller::update_capture_settings,
+        crate::captures::controller::list_captures,
+        crate::captures::controller::get_capture,
+        crate::webhooks::controller::list_dead_letters,
+        crate::webhooks::controller::get_queue_stats,
+        crate::webhooks::controller::get_dead_letter,
+        crate::webhooks::controller::replay_dead_letter,
+        crate::webhooks::controller::replay_dead_letters,
+        crate::user_data::controller::get_balance,
+        crate::user_data::controller::update_account_details,
+        crate::user_data::controller::batch_get_balances,
+        crate::user_data::controller::get_balance_history,
+        crate::user_data::controller::get_user_profile,
+        crate::user_data::controller::get_user_accounts,
+        crate::notifications::controller::get_notifications,
+        crate::notifications::controller::mark_notification_read,
+        crate::notifications::controller::mark_all_notifications_read,
+        crate::notifications::controller::get_notification_preferences,
+        crate::notifications::controller::update_notification_preferences,
+        crate::devices::controller::register_device,
+        crate::devices::controller::list_devices,
+        crate::devices::controller::revoke_device,
+        crate::verification::controller::get_verification_status,
+        crate::verification::controller::request_verification_code,
+        crate::verification::controller::confirm_verification_code,
+        crate::reviews::controller::list_reviews,
+        crate::reviews::controller::flag_verification,
+        crate::reviews::controller::get_review,
+        crate::reviews::controller::claim_review,
+        crate::reviews::controller::release_review,
+        crate::reviews::controller::decide_review,
+        crate::scheduled_reports::controller::create_report_subscription,
+        crate::scheduled_reports::controller::get_report_subscriptions,
+        crate::scheduled_reports::controller::get_report_subscription,
+        crate::scheduled_reports::controller::update_report_subscription,
+        crate::scheduled_reports::controller::delete_report_subscription,
+        crate::transactions::controller::transfer_funds,
+        crate::transactions::controller::preview_transfer,
+        crate::transactions::controller::get_statement,
+        crate::transactions::controller::export_transactions,
+        crate::transactions::controller::get_transaction_export,
+        crate::stream::controller::stream_events,
+        crate::events::controller::list_events,
+        crate::events::controller::redeliver_event,
+        crate::core::storage::download_signed_object,
+        crate::core::storage::upload_signed_object,
+        crate::uploads::controller::create_upload,
+        crate::graphql::controller::execute,
+    ),
+    components(schemas(
+        crate::core::response::ResponseStatus,
+        crate::core::response::ErrorResponse,
+        crate::core::events::DomainEventType,
+        crate::core::events::DomainEvent,
+        crate::events::model::EventPage,
+        crate::core::qr::QrFormat,
+        crate::shared::types::PaginatedDebugCaptures,
+        crate::shared::types::PaginatedDevelopers,
+        crate::shared::types::PaginatedLogins,
+        crate::shared::types::PaginatedDeadLetters,
+        crate::shared::types::PaginatedGoalMovements,
+        crate::shared::types::PaginatedReviews,
+        crate::shared::types::PaginatedVirtualAccountPostings,
+        crate::virtual_accounts::model::VirtualAccountPosting,
+        crate::virtual_accounts::model::VirtualAccountBalance,
+        crate::virtual_accounts::model::VirtualAccountResponse,
+        crate::virtual_accounts::model::VirtualAccountStatus,
+        crate::account_numbers::model::SchemeKind,
+        crate::account_numbers::model::AccountNumberScheme,
+        crate::account_numbers::model::SetAccountNumberSchemeRequest,
+        crate::account_numbers::model::ValidateAccountNumberRequest,
+        crate::account_numbers::model::AccountNumberValidation,
+        crate::metadata_schemas::model::MetadataTarget,
+        crate::metadata_schemas::model::MetadataSchema,
+        crate::metadata_schemas::model::SetMetadataSchemaRequest,
+        crate::metadata_schemas::model::ValidateMetadataRequest,
+        crate::metadata_schemas::model::MetadataViolation,
+        crate::metadata_schemas::model::MetadataValidation,
+        crate::auth::model::ProjectEnvironment,
+        crate::auth::model::RegisterDeveloperRequest,
+        crate::auth::model::CreateProjectRequest,
+        crate::auth::model::TokenRequest,
+        crate::auth::model::RefreshTokenRequest,
+        crate::auth::model::LoginCodeRequest,
+        crate::auth::model::DeveloperResponse,
+        crate::auth::model::ProjectResponse,
+        crate::auth::model::TokenResponse,
+        crate::auth::model::MeResponse,
+        crate::auth::model::LoginRecord,
+        crate::auth::model::ReportLoginRequest,
+        crate::auth::model::LoginReport,
+        crate::auth::model::ScopeInfo,
+        crate::auth::model::ScopeSetsInfo,
+        crate::auth::model::ScopesResponse,
+        crate::organizations::model::Organization,
+        crate::organizations::model::OrganizationRole,
+        crate::organizations::model::OrganizationMember,
+        crate::organizations::model::InvitationStatus,
+        crate::organizations::model::OrganizationProject,
+        crate::organizations::model::CreateOrganizationRequest,
+        crate::organizations::model::CreateInvitationRequest,
+        crate::organizations::model::UpdateMemberRoleRequest,
+        crate::organizations::model::InvitationResponse,
+        crate::organizations::model::InvitationDetails,
+        crate::organizations::model::ProjectIpRules,
+        crate::organizations::model::SetProjectIpRulesRequest,
+        crate::organizations::model::ProjectDuplicatePaymentRules,
+        crate::organizations::model::SetDuplicatePaymentRulesRequest,
+        crate::organizations::model::ProjectLocale,
+        crate::organizations::model::SetProjectLocaleRequest,
+        crate::core::i18n::Locale,
+        crate::organizations::model::ProjectApiVersionPolicy,
+        crate::organizations::model::SetProjectApiVersionPolicyRequest,
+        crate::core::versioning::ApiVersion,
+        crate::payments::model::DuplicatePaymentAction,
+        crate::payments::model::PaymentStatus,
+        crate::payments::model::PaymentMethod,
+        crate::payments::model::PaymentResponse,
+        crate::payments::model::PayeeMatch,
+        crate::payments::model::VerifyPayeeRequest,
+        crate::shared::bank_details::AccountFormat,
+        crate::shared::bank_details::BicDetails,
+        crate::shared::bank_details::AccountValidation,
+        crate::payments::model::PayeeVerificationResponse,
+        crate::payments::model::ApprovalDecision,
+        crate::payments::model::PaymentApprovalRequest,
+        crate::payments::model::PaymentCallbackOutcome,
+        crate::payments::model::PaymentCallbackResponse,
+        crate::payments::model::PaymentIntentStatus,
+        crate::payments::model::CreatePaymentIntentRequest,
+        crate::payments::model::ConfirmPaymentIntentRequest,
+        crate::payments::model::PaymentIntentResponse,
+        crate::fees::model::FeeType,
+        crate::fees::model::FeeTier,
+        crate::fees::model::FeeSchedule,
+        crate::fees::model::FeeLine,
+        crate::fees::model::FeeBreakdown,
+        crate::fees::model::CreateFeeScheduleRequest,
+        crate::fees::model::UpdateFeeScheduleRequest,
+        crate::fees::model::FeePreviewRequest,
+        crate::fees::model::FeePreviewResponse,
+        crate::fx::model::CreateFxQuoteRequest,
+        crate::fx::model::FxQuoteResponse,
+        crate::fx::model::FxConversion,
+        crate::disputes::model::DisputeStatus,
+        crate::disputes::model::OpenDisputeRequest,
+        crate::disputes::model::UploadEvidenceRequest,
+        crate::disputes::model::UpdateDisputeStatusRequest,
+        crate::disputes::model::DisputeEvidenceResponse,
+        crate::disputes::model::DisputeResponse,
+        crate::goals::model::SavingsGoalStatus,
+        crate::goals::model::GoalAllocationRule,
+        crate::goals::model::GoalMovementType,
+        crate::goals::model::GoalMovement,
+        crate::goals::model::GoalBalanceSummary,
+        crate::goals::model::CreateGoalRequest,
+        crate::goals::model::UpdateGoalRequest,
+        crate::goals::model::GoalFundsRequest,
+        crate::goals::model::GoalResponse,
+        crate::account_closures::model::ClosureReason,
+        crate::account_closures::model::AccountClosure,
+        crate::account_closures::model::CloseAccountRequest,
+        crate::interest::model::InterestRate,
+        crate::interest::model::InterestCapitalization,
+        crate::interest::model::CreateInterestRateRequest,
+        crate::interest::model::AccruedInterestResponse,
+        crate::reconciliation::model::SettlementFormat,
+        crate::reconciliation::model::BreakType,
+        crate::reconciliation::model::BreakStatus,
+        crate::reconciliation::model::BreakResolution,
+        crate::reconciliation::model::ReconciliationRun,
+        crate::reconciliation::model::ReconciliationBreak,
+        crate::reconciliation::model::IngestSettlementRequest,
+        crate::reconciliation::model::ReconciliationRunResponse,
+        crate::reconciliation::model::ResolveBreakRequest,
+        crate::income::model::IncomeVerificationStatus,
+        crate::income::model::IncomeVerificationRequest,
+        crate::income::model::IncomeVerificationResponse,
+        crate::income::model::IncomeDocumentType,
+        crate::income::model::DocumentParseStatus,
+        crate::income::model::PayFrequency,
+        crate::income::model::ExtractedIncomeFields,
+        crate::income::model::IncomeDocumentResponse,
+        crate::income::model::ReportFormat,
+        crate::income::model::VerifiedIncome,
+        crate::income::model::MonthlyIncome,
+        crate::income::model::IncomeAnalytics,
+        crate::income::model::IncomeReportData,
+        crate::income::model::IncomeReportResponse,
+        crate::income::model::ReportValidity,
+        crate::income::model::ReportVerificationResponse,
+        crate::income::model::IncomeSource,
+        crate::income::model::LendingBand,
+        crate::income::model::FactorImpact,
+        crate::income::model::AffordabilityFactor,
+        crate::income::model::AffordabilityAssessment,
+        crate::income::model::CreditCheckStatus,
+        crate::income::model::CreditCheckConsent,
+        crate::income::model::CreditCheckRequest,
+        crate::income::model::CreditAccountStatus,
+        crate::income::model::CreditAccount,
+        crate::income::model::CreditReport,
+        crate::income::model::CreditCheckResponse,
+        crate::income::model::EmployerConfirmationStatus,
+        crate::income::model::RequestEmployerConfirmationRequest,
+        crate::income::model::EmployerConfirmationSubmission,
+        crate::income::model::EmployerConfirmationResponse,
+        crate::income::model::EmployerConfirmationDetails,
+        crate::kyc::model::KycTier,
+        crate::kyc::model::TierLimits,
+        crate::kyc::model::KycTierResponse,
+        crate::kyc::model::AccountLimitPosition,
+        crate::identity::model::PostalAddress,
+        crate::identity::model::AddressVerificationStatus,
+        crate::identity::model::UserAddressResponse,
+        crate::identity::model::KycStep,
+        crate::identity::model::KycStepStatus,
+        crate::identity::model::KycStepState,
+        crate::identity::model::KycStatusResponse,
+        crate::identity::model::ScreeningStatus,
+        crate::identity::model::ScreeningCategory,
+        crate::identity::model::ScreeningMatch,
+        crate::identity::model::VerificationScreening,
+        crate::identity::model::FaceQualityFeedback,
+        crate::identity::model::QualityScores,
+        crate::identity::model::FaceQualityResponse,
+        crate::verification_sessions::model::VerificationSessionStatus,
+        crate::verification_sessions::model::SessionStep,
+        crate::verification_sessions::model::StepStatus,
+        crate::verification_sessions::model::CreateVerificationSessionRequest,
+        crate::verification_sessions::model::SessionStepResponse,
+        crate::verification_sessions::model::VerificationSessionResponse,
+        crate::verification_sessions::model::StepUploadReference,
+        crate::uploads::model::UploadPurpose,
+        crate::uploads::model::CreateUploadRequest,
+        crate::uploads::model::UploadTokenResponse,
+        crate::account_controls::model::FreezeReason,
+        crate::account_controls::model::FreezeAccountRequest,
+        crate::account_controls::model::AccountFreezeResponse,
+        crate::developers::model::DeveloperStatus,
+        crate::developers::model::SuspendDeveloperRequest,
+        crate::developers::model::ManagedDeveloperResponse,
+        crate::data_erasure::model::EraseUserDataRequest,
+        crate::data_erasure::model::UserDataErasure,
+        crate::ledger::model::IntegrityStatus,
+        crate::ledger::model::DiscrepancyType,
+        crate::ledger::model::IntegrityRun,
+        crate::ledger::model::Discrepancy,
+        crate::ledger::model::IntegrityRunResponse,
+        crate::general_ledger::model::GlAccountType,
+        crate::general_ledger::model::PostingEvent,
+        crate::general_ledger::model::GlAccount,
+        crate::general_ledger::model::PostingRule,
+        crate::general_ledger::model::TrialBalanceLine,
+        crate::general_ledger::model::TrialBalance,
+        crate::general_ledger::model::CreateGlAccountRequest,
+        crate::general_ledger::model::SetPostingRuleRequest,
+        crate::treasury::model::CurrencyPosition,
+        crate::treasury::model::GlPosition,
+        crate::treasury::model::TreasuryPositions,
+        crate::treasury::model::FreezePositionsRequest,
+        crate::payment_analytics::model::PaymentErrorCount,
+        crate::payment_analytics::model::PaymentBreakdown,
+        crate::payment_analytics::model::PaymentAnalytics,
+        crate::regulatory_reports::model::RegulatoryReportType,
+        crate::regulatory_reports::model::RegulatoryReportFormat,
+        crate::regulatory_reports::model::SubmissionStatus,
+        crate::regulatory_reports::model::Jurisdiction,
+        crate::regulatory_reports::model::ReportableReason,
+        crate::regulatory_reports::model::ReportableTransaction,
+        crate::regulatory_reports::model::RegulatoryReport,
+        crate::regulatory_reports::model::GenerateRegulatoryReportRequest,
+        crate::regulatory_reports::model::UpdateSubmissionRequest,
+        crate::roles::model::CustomRole,
+        crate::roles::model::CustomRoleRequest,
+        crate::roles::model::RoleAssignment,
+        crate::roles::model::AssignRoleRequest,
+        crate::core::feature_flags::FlagTargetType,
+        crate::core::feature_flags::FlagTarget,
+        crate::feature_flags::model::FeatureFlagResponse,
+        crate::feature_flags::model::CreateFeatureFlagRequest,
+        crate::feature_flags::model::UpdateFeatureFlagRequest,
+        crate::feature_flags::model::SetFlagTargetRequest,
+        crate::feature_flags::model::FlagEvaluation,
+        crate::core::live_config::EffectiveConfig,
+        crate::core::live_config::ConfigReload,
+        crate::core::audit_chain::ChainLink,
+        crate::core::audit_chain::ChainAnchor,
+        crate::core::audit_chain::ChainFinding,
+        crate::core::audit_chain::ChainVerification,
+        crate::core::audit_chain::AuditChainStatus,
+        crate::usage::model::DailyUsage,
+        crate::usage::model::EndpointUsage,
+        crate::usage::model::ExportFormat,
+        crate::usage::model::QuotaOverrideRequest,
+        crate::usage::model::QuotaStatusResponse,
+        crate::usage::model::ProjectUsageResponse,
+        crate::usage::model::BillingExport,
+        crate::captures::model::ProjectCaptureSettings,
+        crate::captures::model::UpdateCaptureSettingsRequest,
+        crate::captures::model::DebugCapture,
+        crate::captures::model::DebugCaptureSummary,
+        crate::webhooks::model::WebhookDeliveryStatus,
+        crate::webhooks::model::WebhookDelivery,
+        crate::webhooks::model::WebhookDeadLetter,
+        crate::webhooks::model::ReplayDeadLettersRequest,
+        crate::webhooks::model::ReplayDeadLettersResponse,
+        crate::webhooks::model::DeadLetterCount,
+        crate::webhooks::model::WebhookQueueStats,
+        crate::notifications::model::NotificationCategory,
+        crate::notifications::model::NotificationKind,
+        crate::notifications::model::ChannelPreferences,
+        crate::notifications::model::CategoryPreferences,
+        crate::notifications::model::NotificationPreferencesResponse,
+        crate::notifications::model::CategoryPreferencesUpdate,
+        crate::notifications::model::UpdateNotificationPreferencesRequest,
+        crate::notifications::model::Notification,
+        crate::notifications::model::NotificationFeed,
+        crate::notifications::model::MarkAllReadRequest,
+        crate::notifications::model::MarkAllReadResponse,
+        crate::devices::model::RegisterDeviceRequest,
+        crate::devices::model::DeviceResponse,
+        crate::devices::model::DeviceRisk,
+        crate::verification::model::VerificationChannel,
+        crate::verification::model::VerificationStatus,
+        crate::verification::model::RequestVerificationCodeRequest,
+        crate::verification::model::ConfirmVerificationCodeRequest,
+        crate::verification::model::VerificationCodeSent,
+        crate::identity::model::VerificationStatus,
+        crate::reviews::model::VerificationKind,
+        crate::reviews::model::ReviewStatus,
+        crate::reviews::model::ReviewDecision,
+        crate::reviews::model::VerificationReview,
+        crate::reviews::model::FlagVerificationRequest,
+        crate::reviews::model::ReviewDecisionRequest,
+        crate::reviews::model::IdentityEvidence,
+        crate::reviews::model::EvidenceDocumentLink,
+        crate::reviews::model::IncomeEvidence,
+        crate::reviews::model::ReviewEvidenceResponse,
+        crate::transactions::model::TransferRequest,
+        crate::transactions::model::BalancePreview,
+        crate::transactions::model::TransferPreview,
+        crate::transactions::model::AccountStatement,
+        crate::transactions::model::TransactionRecord,
+        crate::transactions::model::TransactionExportFilter,
+        crate::transactions::model::ExportTransactionsRequest,
+        crate::transactions::model::TransactionExportStatus,
+        crate::transactions::model::TransactionExport,
+        crate::transactions::model::TransactionExportResponse,
+        crate::scheduled_reports::model::ScheduledReportType,
+        crate::scheduled_reports::model::ReportDeliveryChannel,
+        crate::scheduled_reports::model::ReportFileFormat,
+        crate::scheduled_reports::model::ReportSubscription,
+        crate::scheduled_reports::model::CreateReportSubscriptionRequest,
+        crate::scheduled_reports::model::UpdateReportSubscriptionRequest,
+        crate::user_data::model::BalanceResponse,
+        crate::user_data::model::BalanceAsOfResponse,
+        crate::user_data::model::AccountBalance,
+        crate::user_data::model::BalanceHistory,
+        crate::user_data::model::BatchGetBalancesRequest,
+        crate::user_data::model::BatchBalanceResult,
+        crate::user_data::model::BalanceError,
+        crate::user_data::model::UserProfileResponse,
+        crate::user_data::model::UserAccountResponse,
+        crate::shared::account_labels::UpdateAccountDetailsRequest,
+        crate::shared::account_labels::AccountLabels,
+    )),
+    modifiers(&SharedTypes, &BearerAuth, &ResponseEnvelope),
+    tags(
+        (name = "auth", description = "Developer registration, projects and OAuth2 tokens"),
+        (name = "organizations", description = "Organizations, members and invitations"),
+        (name = "payments", description = "Payments"),
+        (name = "transactions", description = "Transfers, transfer previews, statements and exports"),
+        (name = "fees", description = "Fee schedules and previews"),
+        (name = "fx", description = "Currency conversion quotes with locked rates"),
+        (name = "disputes", description = "Transaction and payment disputes"),
+        (name = "goals", description = "Savings goals"),
+        (name = "virtual-accounts", description = "Virtual account activity and balances"),
+        (name = "account-numbers", description = "Account number schemes and validation"),
+        (name = "metadata-schemas", description = "JSON Schemas for payment and transaction metadata"),
+        (name = "account-closures", description = "Account closure"),
+        (name = "interest", description = "Interest rates and accruals"),
+        (name = "reconciliation", description = "Settlement file reconciliation and breaks"),
+        (name = "income", description = "Income verification, document parsing, reports and employer confirmation"),
+        (name = "kyc", description = "KYC tiers and limits"),
+        (name = "identity", description = "Customers' addresses, PEP and adverse-media screening, and how complete their KYC profiles are"),
+        (name = "user-data", description = "Balances, profile and accounts of the end user a token acts for"),
+        (name = "notifications", description = "In-app notification feed and channel preferences"),
+        (name = "devices", description = "Devices end users have bound to sign sensitive calls with"),
+        (name = "verification", description = "One-time codes end users verify their email address and phone number with"),
+        (name = "reviews", description = "Manual review queue for identity and income verifications"),
+        (name = "account-controls", description = "Administrative account freezes"),
+        (name = "developers", description = "Developer administration"),
+        (name = "data-erasure", description = "Erasing a user's personal data by shredding their data key"),
+        (name = "ledger", description = "Ledger integrity checks"),
+        (name = "general-ledger", description = "Chart of accounts, posting rules and trial balance"),
+        (name = "treasury", description = "Intraday liquidity positions and end-of-day freezing"),
+        (name = "analytics", description = "Payment success rates and latency per provider"),
+        (name = "regulatory-reports", description = "Large transaction and suspicious activity reports for regulators"),
+        (name = "roles", description = "Custom roles built from granular permissions"),
+        (name = "feature-flags", description = "Feature flags with tenant and project targets and percentage rollouts"),
+        (name = "configuration", description = "The running configuration and reloading it without a restart"),
+        (name = "audit", description = "Hash-chained audit streams, their anchors and verification"),
+        (name = "usage", description = "API usage, quotas and billing export"),
+        (name = "captures", description = "Sampled request and response captures for debugging integrations"),
+        (name = "webhooks", description = "Webhook dead-letter queue and replay"),
+        (name = "report-subscriptions", description = "Scheduled report subscriptions delivered by email or webhook"),
+        (name = "stream", description = "Real-time event stream (server-sent events)"),
+        (name = "events", description = "Event history and webhook redelivery"),
+        (name = "storage", description = "Signed download and upload links for stored documents"),
+        (name = "uploads", description = "Short-lived links for uploading images and videos directly to storage"),
+        (name = "graphql", description = "Read-only GraphQL endpoint"),
+    )
+)]
+pub struct ApiDoc;
+
+/// The generated specification, built once
+pub fn spec() -> &'static OpenApiDocument {
+    static SPEC: OnceLock<OpenApiDocument> = OnceLock::new();
+    SPEC.get_or_init(ApiDoc::openapi)
+}
+
+/// Routes serving the specification, and Swagger UI when enabled
+pub fn routes(config: &Config) -> Router<AppState> {
+    let router = Router::new().route("/api-docs/openapi.json", get(openapi_json));
+    if !config.swagger_ui_enabled {
+        return router;
+    }
+
+    let page = swagger_ui_page(&config.swagger_ui_assets_url);
+    router.route("/swagger-ui", get(move || async move { Html(page) }))
+}
+
+async fn openapi_json() -> Json<&'static OpenApiDocument> {
+    Json(spec())
+}
+
+fn swagger_ui_page(assets_url: &str) -> String {
+    let assets_url = assets_url.trim_end_matches('/');
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>OpenBank API</title>
+  <link rel="stylesheet" href="{assets_url}/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="{assets_url}/swagger-ui-bundle.js"></script>
+  <script>
+    window.ui = SwaggerUIBundle({{ url: "/api-docs/openapi.json", dom_id: "#swagger-ui" }});
+  </script>
+</body>
+</html>"##
+    )
+}
+
+/// Schemas for the type aliases in `shared::types`, which the derive macros
+/// reference by name
+struct SharedTypes;
+
+impl Modify for SharedTypes {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        let uuid = |description: &str| {
+            ObjectBuilder::new()
+                .schema_type(SchemaType::String)
+                .format(Some(SchemaFormat::KnownFormat(KnownFormat::Uuid)))
+                .description(Some(description))
+                .build()
+        };
+
+        components.schemas.insert("AccountId".to_string(), uuid("Account ID").into());
+        components.schemas.insert("UserId".to_string(), uuid("User ID").into());
+        components.schemas.insert("TransactionId".to_string(), uuid("Transaction ID").into());
+        components.schemas.insert("TenantId".to_string(), uuid("Tenant (organization) ID").into());
+        components.schemas.insert(
+            "Amount".to_string(),
+            ObjectBuilder::new()
+                .schema_type(SchemaType::Integer)
+                .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int64)))
+                .description(Some("Amount in minor units (cents)"))
+                .build()
+                .into(),
+        );
+        components.schemas.insert(
+            "Currency".to_string(),
+            ObjectBuilder::new()
+                .schema_type(SchemaType::String)
+                .description(Some("ISO 4217 currency code"))
+                .min_length(Some(3))
+                .max_length(Some(3))
+                .build()
+                .into(),
+        );
+    }
+}
+
+/// Bearer token security scheme used by authenticated endpoints
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Wraps declared response bodies in the `ApiResponse` envelope, and gives
+/// error responses the envelope with an `ErrorResponse`
+struct ResponseEnvelope;
+
+impl ResponseEnvelope {
+    fn envelope(data: Option<RefOr<Schema>>) -> RefOr<Schema> {
+        let mut envelope = ObjectBuilder::new()
+            .property("status", Ref::from_schema_name("ResponseStatus"))
+            .required("status")
+            .property("message", ObjectBuilder::new().schema_type(SchemaType::String))
+            .required("message");
+        if let Some(data) = data {
+            envelope = envelope.property("data", data);
+        }
+        envelope
+            .property(
+                "meta",
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Object)
+                    .description(Some("Additional metadata")),
+            )
+            .build()
+            .into()
+    }
+}
+
+impl Modify for ResponseEnvelope {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            let unwrapped = UNWRAPPED_PATHS.contains(&path.as_str());
+            for operation in item.operations.values_mut() {
+                for (status, response) in operation.responses.responses.iter_mut() {
+                    let RefOr::T(response) = response else {
+                        continue;
+                    };
+
+                    if status.starts_with('2') {
+                        if unwrapped {
+                            continue;
+                        }
+                        // Non-JSON bodies (file downloads, images) are sent as is
+                        let data = match response.content.get("application/json") {
+                            Some(content) => Some(content.schema.clone()),
+                            None if response.content.is_empty() => None,
+                            None => continue,
+                        };
+                        response
+                            .content
+                            .insert("application/json".to_string(), Content::new(Self::envelope(data)));
+                    } else if response.content.is_empty() {
+                        let error = Ref::from_schema_name("ErrorResponse").into();
+                        response
+                            .content
+                            .insert("application/json".to_string(), Content::new(Self::envelope(Some(error))));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect_refs(value: &serde_json::Value, refs: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    match (key.as_str(), value) {
+                        ("$ref", serde_json::Value::String(reference)) => refs.push(reference.clone()),
+                        _ => collect_refs(value, refs),
+                    }
+                }
+            }
+            serde_json::Value::Array(values) => values.iter().for_each(|value| collect_refs(value, refs)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_schema_references_resolve() {
+        let document = serde_json::to_value(spec()).unwrap();
+        let mut refs = Vec::new();
+        collect_refs(&document, &mut refs);
+
+        let schemas = &document["components"]["schemas"];
+        for reference in refs {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            assert!(schemas.get(name).is_some(), "unresolved schema reference {}", reference);
+        }
+    }
+
+    #[test]
+    fn test_responses_use_envelope() {
+        let document = serde_json::to_value(spec()).unwrap();
+        let response = &document["paths"]["/api/v1/goals/{id}"]["get"]["responses"];
+
+        let success = &response["200"]["content"]["application/json"]["schema"];
+        assert_eq!(success["properties"]["data"]["$ref"], "#/components/schemas/GoalResponse");
+        let error = &response["404"]["content"]["application/json"]["schema"];
+        assert_eq!(error["properties"]["data"]["$ref"], "#/components/schemas/ErrorResponse");
+    }
+}
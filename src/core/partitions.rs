@@ -0,0 +1,50 @@
+use sqlx::PgPool;
+use tracing::info;
+use crate::core::error::AppResult;
+use crate::core::AppState;
+
+/// Tables partitioned by month of `created_at`
+pub const PARTITIONED_TABLES: &[&str] = &["transactions", "balance_history"];
+
+const PARTITION_MAINTENANCE_JOB: &str = "partition_maintenance";
+
+/// Create any missing monthly partitions of the partitioned tables, from the
+/// current month through `months_ahead` months later. Returns how many were
+/// created. Rows that had landed in a table's default partition for one of
+/// those months move into the new partition.
+pub async fn ensure_partitions(pool: &PgPool, months_ahead: u32) -> AppResult<i32> {
+    let mut created = 0;
+    for table in PARTITIONED_TABLES {
+        let count: i32 = sqlx::query_scalar("SELECT ensure_monthly_partitions($1, $2)")
+            .bind(table)
+            .bind(months_ahead as i32)
+            .fetch_one(pool)
+            .await?;
+        if count > 0 {
+            info!(table = table, partitions = count, "Created monthly partitions");
+        }
+        created += count;
+    }
+    Ok(created)
+}
+
+/// Keep partitions ready ahead of the months rows will be written in, so new
+/// rows never fall into the default partition
+pub fn spawn_partition_maintenance_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.partition_maintenance_interval_seconds);
+    state.job_monitor.register(PARTITION_MAINTENANCE_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match ensure_partitions(&state.postgres, state.config.partition_months_ahead).await {
+                Ok(_) => state.job_monitor.record_success(PARTITION_MAINTENANCE_JOB),
+                Err(e) => {
+                    state.job_monitor.record_failure(PARTITION_MAINTENANCE_JOB, e.to_string());
+                    tracing::error!("Partition maintenance job failed: {}", e);
+                }
+            }
+        }
+    });
+}
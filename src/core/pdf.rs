@@ -0,0 +1,151 @@
+/// Page size (US Letter) and layout in PDF points
+const PAGE_WIDTH: u32 = 612;
+const PAGE_HEIGHT: u32 = 792;
+const MARGIN: u32 = 56;
+const BODY_SIZE: u32 = 10;
+const HEADING_SIZE: u32 = 13;
+const LINE_SPACING: u32 = 4;
+
+enum PdfLine {
+    Heading(String),
+    Text(String),
+    Blank,
+}
+
+impl PdfLine {
+    fn height(&self) -> u32 {
+        match self {
+            PdfLine::Heading(_) => HEADING_SIZE + LINE_SPACING * 2,
+            PdfLine::Text(_) | PdfLine::Blank => BODY_SIZE + LINE_SPACING,
+        }
+    }
+}
+
+/// Minimal text-only PDF writer for generated documents such as reports.
+///
+/// Lines are laid out top to bottom in Helvetica and flow onto new pages as
+/// needed. Characters outside printable ASCII are replaced with `?`.
+pub struct PdfDocument {
+    title: String,
+    lines: Vec<PdfLine>,
+}
+
+impl PdfDocument {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn heading(&mut self, text: impl Into<String>) -> &mut Self {
+        self.lines.push(PdfLine::Heading(text.into()));
+        self
+    }
+
+    pub fn text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.lines.push(PdfLine::Text(text.into()));
+        self
+    }
+
+    pub fn blank(&mut self) -> &mut Self {
+        self.lines.push(PdfLine::Blank);
+        self
+    }
+
+    /// Render the document to PDF bytes
+    pub fn render(&self) -> Vec<u8> {
+        let pages = self.page_streams();
+
+        // Object layout: 1 catalog, 2 page tree, 3 regular font, 4 bold font,
+        // 5 info, then a page object and a content stream per page
+        let page_ids: Vec<usize> = (0..pages.len()).map(|index| 6 + index * 2).collect();
+        let mut objects = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+                pages.len()
+            ),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+            format!("<< /Title ({}) /Producer (OpenBank) >>", escape(&self.title)),
+        ];
+        for (page_id, stream) in page_ids.iter().zip(&pages) {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_id + 1
+            ));
+            objects.push(format!("<< /Length {} >>\nstream\n{}\nendstream", stream.len(), stream));
+        }
+
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+        }
+
+        let xref_offset = pdf.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            xref.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        pdf.extend_from_slice(xref.as_bytes());
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+
+    /// Lay lines out into one content stream per page
+    fn page_streams(&self) -> Vec<String> {
+        let mut pages = Vec::new();
+        let mut stream = String::new();
+        let mut y = PAGE_HEIGHT - MARGIN;
+
+        for line in &self.lines {
+            if y < MARGIN + line.height() {
+                pages.push(std::mem::take(&mut stream));
+                y = PAGE_HEIGHT - MARGIN;
+            }
+            y -= line.height();
+
+            let (font, size, text) = match line {
+                PdfLine::Heading(text) => ("F2", HEADING_SIZE, text),
+                PdfLine::Text(text) => ("F1", BODY_SIZE, text),
+                PdfLine::Blank => continue,
+            };
+            stream.push_str(&format!(
+                "BT /{} {} Tf {} {} Td ({}) Tj ET\n",
+                font,
+                size,
+                MARGIN,
+                y,
+                escape(text)
+            ));
+        }
+
+        pages.push(stream);
+        pages
+    }
+}
+
+/// Escape a string for a PDF literal string
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '(' | ')' | '\\' => format!("\\{}", c),
+            ' '..='~' => c.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,165 @@
+use crate::core::error::{AppError, AppResult};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{imageops, DynamicImage, ImageFormat, Rgba};
+use qrcode::{render::svg, EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use utoipa::{IntoParams, ToSchema};
+
+/// Quiet zone (in modules) the renderer adds around normal QR codes
+const QUIET_ZONE_MODULES: u32 = 4;
+
+/// Fraction of the QR code width covered by an embedded logo.
+/// Level H error correction tolerates ~30% damage, so 1/5 of the width is safe.
+const LOGO_SCALE_DIVISOR: u32 = 5;
+
+/// Output format for rendered QR codes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl QrFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            QrFormat::Png => "image/png",
+            QrFormat::Svg => "image/svg+xml",
+        }
+    }
+}
+
+/// Query parameters accepted by QR endpoints
+#[derive(Debug, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QrQuery {
+    #[serde(default)]
+    pub format: QrFormat,
+    pub size: Option<u32>,
+    #[serde(default)]
+    pub logo: bool,
+}
+
+/// Resolved rendering options
+#[derive(Debug, Clone)]
+pub struct QrOptions {
+    pub format: QrFormat,
+    pub size: u32,
+    pub logo: Option<Vec<u8>>,
+}
+
+/// QR code renderer configured from application settings
+#[derive(Debug, Clone)]
+pub struct QrRenderer {
+    default_size: u32,
+    max_size: u32,
+    logo_path: Option<String>,
+}
+
+impl QrRenderer {
+    pub fn new(default_size: u32, max_size: u32, logo_path: Option<String>) -> Self {
+        Self {
+            default_size,
+            max_size,
+            logo_path,
+        }
+    }
+
+    /// Resolve request parameters into rendering options, loading the logo if requested
+    pub async fn options(&self, query: &QrQuery) -> AppResult<QrOptions> {
+        let size = query.size.unwrap_or(self.default_size);
+        if size < 64 || size > self.max_size {
+            return Err(AppError::Validation(format!(
+                "QR size must be between 64 and {} pixels",
+                self.max_size
+            )));
+        }
+
+        let logo = if query.logo {
+            let path = self.logo_path.as_ref().ok_or_else(|| {
+                AppError::BadRequest("No QR logo is configured for this deployment".to_string())
+            })?;
+            let bytes = tokio::fs::read(path)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to read QR logo: {}", e)))?;
+            Some(bytes)
+        } else {
+            None
+        };
+
+        Ok(QrOptions {
+            format: query.format,
+            size,
+            logo,
+        })
+    }
+
+    /// Render a payload as a QR code, returning the content type and encoded bytes
+    pub fn render(&self, payload: &str, options: &QrOptions) -> AppResult<(&'static str, Vec<u8>)> {
+        // Use the highest error correction level when part of the code is covered by a logo
+        let ec_level = if options.logo.is_some() { EcLevel::H } else { EcLevel::M };
+        let code = QrCode::with_error_correction_level(payload.as_bytes(), ec_level)
+            .map_err(|e| AppError::Internal(format!("Failed to encode QR code: {}", e)))?;
+
+        let bytes = match options.format {
+            QrFormat::Png => render_png(&code, options)?,
+            QrFormat::Svg => render_svg(&code, options).into_bytes(),
+        };
+
+        Ok((options.format.content_type(), bytes))
+    }
+}
+
+fn render_png(code: &QrCode, options: &QrOptions) -> AppResult<Vec<u8>> {
+    let mut image = code
+        .render::<Rgba<u8>>()
+        .min_dimensions(options.size, options.size)
+        .build();
+
+    if let Some(logo_bytes) = &options.logo {
+        let logo = image::load_from_memory(logo_bytes)
+            .map_err(|e| AppError::Internal(format!("Invalid QR logo image: {}", e)))?;
+        let logo_size = image.width() / LOGO_SCALE_DIVISOR;
+        let logo = logo
+            .resize(logo_size, logo_size, imageops::FilterType::Triangle)
+            .to_rgba8();
+        let x = (image.width() - logo.width()) / 2;
+        let y = (image.height() - logo.height()) / 2;
+        imageops::overlay(&mut image, &logo, x as i64, y as i64);
+    }
+
+    let mut buffer = Vec::new();
+    DynamicImage::ImageRgba8(image)
+        .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+        .map_err(|e| AppError::Internal(format!("Failed to encode PNG: {}", e)))?;
+
+    Ok(buffer)
+}
+
+fn render_svg(code: &QrCode, options: &QrOptions) -> String {
+    let svg = code
+        .render::<svg::Color>()
+        .min_dimensions(options.size, options.size)
+        .build();
+
+    let Some(logo_bytes) = &options.logo else {
+        return svg;
+    };
+
+    // Mirror the renderer's sizing so the logo lands in the exact centre
+    let modules = code.width() as u32 + 2 * QUIET_ZONE_MODULES;
+    let rendered = modules * options.size.div_ceil(modules);
+    let logo_size = rendered / LOGO_SCALE_DIVISOR;
+    let offset = (rendered - logo_size) / 2;
+    let logo = format!(
+        r#"<image x="{offset}" y="{offset}" width="{logo_size}" height="{logo_size}" href="data:image/png;base64,{}"/>"#,
+        STANDARD.encode(logo_bytes)
+    );
+
+    match svg.rfind("</svg>") {
+        Some(end) => format!("{}{}{}", &svg[..end], logo, &svg[end..]),
+        None => svg,
+    }
+}
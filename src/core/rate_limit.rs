@@ -6,11 +6,12 @@ use axum::{
 use std::{
     collections::HashMap,
     net::SocketAddr,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, RwLock},
     time::{Duration, Instant},
 };
 
 use tracing::{info, warn};
+use crate::core::config::Config;
 
 /// Rate limiting configuration
 #[derive(Debug, Clone)]
@@ -20,6 +21,16 @@ pub struct RateLimitConfig {
     pub window_size: Duration,
 }
 
+impl RateLimitConfig {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            requests_per_minute: config.rate_limit_requests_per_minute as u32,
+            burst_size: config.rate_limit_burst_size,
+            window_size: Duration::from_secs(config.rate_limit_window_seconds),
+        }
+    }
+}
+
 impl Default for RateLimitConfig {
     fn default() -> Self {
         Self {
@@ -40,19 +51,29 @@ struct RateLimitState {
 /// In-memory rate limiter (use Redis in production for distributed systems)
 #[derive(Debug, Clone)]
 pub struct RateLimiter {
-    config: RateLimitConfig,
+    config: Arc<RwLock<RateLimitConfig>>,
     states: Arc<Mutex<HashMap<String, RateLimitState>>>,
 }
 
 impl RateLimiter {
     pub fn new(config: RateLimitConfig) -> Self {
         Self {
-            config,
+            config: Arc::new(RwLock::new(config)),
             states: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
+    pub fn config(&self) -> RateLimitConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Apply new limits to every clone; tracked clients are kept
+    pub fn reconfigure(&self, config: RateLimitConfig) {
+        *self.config.write().unwrap() = config;
+    }
+
     pub fn check_rate_limit(&self, ip: &str) -> Result<(), RateLimitError> {
+        let config = self.config();
         let mut states = self.states.lock().unwrap();
         let now = Instant::now();
 
@@ -75,10 +96,10 @@ impl RateLimiter {
         // Clean old requests outside the window
         state
             .requests
-            .retain(|&request_time| now.duration_since(request_time) < self.config.window_size);
+            .retain(|&request_time| now.duration_since(request_time) < config.window_size);
 
         // Check if exceeding rate limit
-        if state.requests.len() >= self.config.requests_per_minute as usize {
+        if state.requests.len() >= config.requests_per_minute as usize {
             let block_duration = Duration::from_secs(300); // 5 minutes
             state.blocked_until = Some(now + block_duration);
 
@@ -90,7 +111,7 @@ impl RateLimiter {
 
             return Err(RateLimitError::ExceededLimit {
                 requests_made: state.requests.len() as u32,
-                limit: self.config.requests_per_minute,
+                limit: config.requests_per_minute,
                 retry_after: block_duration,
             });
         }
@@ -102,10 +123,10 @@ impl RateLimiter {
             .filter(|&&request_time| now.duration_since(request_time) < Duration::from_secs(10))
             .count();
 
-        if recent_requests >= self.config.burst_size as usize {
+        if recent_requests >= config.burst_size as usize {
             return Err(RateLimitError::BurstExceeded {
                 burst_count: recent_requests as u32,
-                burst_limit: self.config.burst_size,
+                burst_limit: config.burst_size,
             });
         }
 
@@ -121,8 +142,21 @@ impl RateLimiter {
         Ok(())
     }
 
+    /// Number of clients currently tracked and currently blocked
+    pub fn client_counts(&self) -> (usize, usize) {
+        let states = self.states.lock().unwrap();
+        let now = Instant::now();
+        let blocked = states
+            .values()
+            .filter(|state| state.blocked_until.is_some_and(|until| until > now))
+            .count();
+
+        (states.len(), blocked)
+    }
+
     /// Clean up expired entries (call periodically)
     pub fn cleanup_expired(&self) {
+        let window_size = self.config().window_size;
         let mut states = self.states.lock().unwrap();
         let now = Instant::now();
 
@@ -133,7 +167,7 @@ impl RateLimiter {
                 || state
                     .requests
                     .iter()
-                    .any(|&request_time| now.duration_since(request_time) < self.config.window_size)
+                    .any(|&request_time| now.duration_since(request_time) < window_size)
         });
     }
 }
@@ -1,7 +1,12 @@
+use axum::http::Method;
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 use std::collections::{HashMap, HashSet, BTreeMap};
+use std::fmt;
+use std::str::FromStr;
 use uuid::Uuid;
 use crate::core::error::{AppError, AppResult};
+use crate::core::versioning;
 
 /// System roles with hierarchical permissions
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -19,6 +24,28 @@ pub enum Role {
     Support,
     /// Auditor - access to audit logs and compliance reports
     Auditor,
+    /// Payment approver - second user approving high-value payments
+    PaymentApprover,
+    /// Verification reviewer - decides identity and income verifications flagged for manual review
+    VerificationReviewer,
+}
+
+impl FromStr for Role {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "super_admin" => Ok(Role::SuperAdmin),
+            "admin" => Ok(Role::Admin),
+            "developer" => Ok(Role::Developer),
+            "read_only" => Ok(Role::ReadOnly),
+            "support" => Ok(Role::Support),
+            "auditor" => Ok(Role::Auditor),
+            "payment_approver" => Ok(Role::PaymentApprover),
+            "verification_reviewer" => Ok(Role::VerificationReviewer),
+            _ => Err(AppError::Validation(format!("Unknown role: {}", s))),
+        }
+    }
 }
 
 impl Role {
@@ -31,11 +58,15 @@ impl Role {
                 Role::ReadOnly,
                 Role::Support,
                 Role::Auditor,
+                Role::PaymentApprover,
+                Role::VerificationReviewer,
             ],
             Role::Admin => vec![Role::Developer, Role::ReadOnly, Role::Support],
             Role::Developer => vec![Role::ReadOnly],
             Role::Support => vec![Role::ReadOnly],
             Role::Auditor => vec![Role::ReadOnly],
+            Role::PaymentApprover => vec![Role::ReadOnly],
+            Role::VerificationReviewer => vec![Role::ReadOnly],
             Role::ReadOnly => vec![],
         }
     }
@@ -70,15 +101,26 @@ impl Role {
                 permissions.insert(Permission::new("system", "manage"));
                 permissions.insert(Permission::new("users", "delete"));
                 permissions.insert(Permission::new("developers", "suspend"));
+                permissions.insert(Permission::new("developers", "delete"));
                 permissions.insert(Permission::new("audit", "configure"));
             }
             Role::Admin => {
                 permissions.insert(Permission::new("developers", "create"));
                 permissions.insert(Permission::new("developers", "update"));
                 permissions.insert(Permission::new("developers", "read"));
+                permissions.insert(Permission::new("developers", "manage"));
                 permissions.insert(Permission::new("projects", "manage"));
                 permissions.insert(Permission::new("audit", "read"));
                 permissions.insert(Permission::new("system", "monitor"));
+                permissions.insert(Permission::new("accounts", "freeze"));
+                permissions.insert(Permission::new("fees", "manage"));
+                permissions.insert(Permission::new("interest", "manage"));
+                permissions.insert(Permission::new("reconciliation", "manage"));
+                permissions.insert(Permission::new("general_ledger", "manage"));
+                permissions.insert(Permission::new("treasury", "manage"));
+                permissions.insert(Permission::new("roles", "manage"));
+                permissions.insert(Permission::new("webhooks", "manage"));
+                permissions.insert(Permission::new("feature_flags", "manage"));
             }
             Role::Developer => {
                 permissions.insert(Permission::new("projects", "create"));
@@ -94,6 +136,7 @@ impl Role {
                 permissions.insert(Permission::new("projects", "read"));
                 permissions.insert(Permission::new("tokens", "read"));
                 permissions.insert(Permission::new("support", "assist"));
+                permissions.insert(Permission::new("disputes", "review"));
             }
             Role::Auditor => {
                 permissions.insert(Permission::new("audit", "read"));
@@ -101,6 +144,12 @@ impl Role {
                 permissions.insert(Permission::new("logs", "read"));
                 permissions.insert(Permission::new("security", "monitor"));
             }
+            Role::PaymentApprover => {
+                permissions.insert(Permission::new("payments", "approve"));
+            }
+            Role::VerificationReviewer => {
+                permissions.insert(Permission::new("verifications", "review"));
+            }
             Role::ReadOnly => {
                 permissions.insert(Permission::new("profile", "read_own"));
                 permissions.insert(Permission::new("projects", "read_own"));
@@ -120,6 +169,30 @@ pub struct Permission {
     pub conditions: Option<std::collections::BTreeMap<String, String>>,
 }
 
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.resource, self.action)
+    }
+}
+
+/// Parses the `resource:action` form used by custom roles
+impl FromStr for Permission {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let valid = |part: &str| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        };
+        match s.split_once(':') {
+            Some((resource, action)) if valid(resource) && valid(action) => Ok(Permission::new(resource, action)),
+            _ => Err(AppError::Validation(format!(
+                "Invalid permission '{}', expected resource:action",
+                s
+            ))),
+        }
+    }
+}
+
 impl Permission {
     pub fn new(resource: &str, action: &str) -> Self {
         Self {
@@ -308,6 +381,12 @@ pub struct RbacService {
     user_roles: std::sync::Arc<std::sync::Mutex<HashMap<Uuid, UserRoles>>>,
 }
 
+impl Default for RbacService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RbacService {
     pub fn new() -> Self {
         Self {
@@ -335,6 +414,48 @@ impl RbacService {
         Ok(())
     }
 
+    /// Load a user's active role assignments from the `user_roles` table,
+    /// plus the permissions of custom roles assigned in organizations they
+    /// still belong to, replacing whatever is cached in memory
+    pub async fn load_user_roles(&self, pool: &PgPool, user_id: Uuid) -> AppResult<()> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT role FROM user_roles
+             WHERE developer_id = $1 AND (expires_at IS NULL OR expires_at > NOW())",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        let custom_permissions: Vec<(String,)> = sqlx::query_as(
+            "SELECT DISTINCT UNNEST(r.permissions)
+             FROM custom_role_assignments a
+             JOIN custom_roles r ON r.id = a.role_id
+             JOIN organization_members m ON m.organization_id = r.tenant_id AND m.developer_id = a.developer_id
+             WHERE a.developer_id = $1 AND (a.expires_at IS NULL OR a.expires_at > NOW())",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut loaded = UserRoles::new(user_id, Role::ReadOnly);
+        for (role,) in rows {
+            match role.parse::<Role>() {
+                Ok(role) => loaded.add_role(role),
+                Err(_) => tracing::warn!(user_id = %user_id, role = role, "Ignoring unknown role"),
+            }
+        }
+        for (permission,) in custom_permissions {
+            match permission.parse::<Permission>() {
+                Ok(permission) => loaded.add_permission(permission),
+                Err(_) => tracing::warn!(user_id = %user_id, permission = permission, "Ignoring invalid permission"),
+            }
+        }
+
+        let mut user_roles = self.user_roles.lock().unwrap();
+        user_roles.insert(user_id, loaded);
+        Ok(())
+    }
+
     /// Check if user has permission
     pub fn check_permission(
         &self,
@@ -387,6 +508,86 @@ impl RbacService {
     }
 }
 
+/// A group of routes guarded by a single permission.
+///
+/// Patterns are request paths below the API version, split on `/`: `:name`
+/// matches any one segment and a trailing `*` matches zero or more remaining
+/// segments.
+pub struct RoutePermission {
+    /// `None` guards every method
+    pub method: Option<Method>,
+    pub pattern: &'static str,
+    pub permission: fn() -> Permission,
+}
+
+impl RoutePermission {
+    const fn any(pattern: &'static str, permission: fn() -> Permission) -> Self {
+        Self { method: None, pattern, permission }
+    }
+
+    const fn only(method: Method, pattern: &'static str, permission: fn() -> Permission) -> Self {
+        Self { method: Some(method), pattern, permission }
+    }
+
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        if self.method.as_ref().is_some_and(|expected| expected != method) {
+            return false;
+        }
+
+        let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+        for expected in self.pattern.split('/').filter(|segment| !segment.is_empty()) {
+            if expected == "*" {
+                return true;
+            }
+            match segments.next() {
+                Some(segment) if expected.starts_with(':') || expected == segment => {}
+                _ => return false,
+            }
+        }
+        segments.next().is_none()
+    }
+}
+
+/// Privileged routes and the permission each requires. The first matching
+/// entry wins, so narrower entries come before the broader ones they refine.
+pub const ROUTE_PERMISSIONS: &[RoutePermission] = &[
+    RoutePermission::only(Method::DELETE, "/admin/developers/:id", permissions::delete_developers),
+    RoutePermission::any("/admin/developers/*", permissions::manage_developers),
+    RoutePermission::any("/admin/users/:id/erasure", permissions::delete_users),
+    RoutePermission::any("/admin/accounts/:id/*", permissions::freeze_accounts),
+    RoutePermission::any("/admin/virtual-accounts/:id/*", permissions::freeze_accounts),
+    RoutePermission::any("/admin/ledger/*", permissions::monitor_system),
+    RoutePermission::any("/admin/gl/*", permissions::manage_general_ledger),
+    RoutePermission::any("/admin/treasury/*", permissions::manage_treasury),
+    RoutePermission::any("/admin/analytics/*", permissions::monitor_system),
+    RoutePermission::any("/admin/regulatory-reports/*", permissions::report_compliance),
+    RoutePermission::any("/admin/projects/:id/*", permissions::manage_projects),
+    RoutePermission::any("/admin/usage/*", permissions::manage_projects),
+    RoutePermission::any("/admin/roles/*", permissions::manage_roles),
+    RoutePermission::any("/admin/feature-flags/*", permissions::manage_feature_flags),
+    RoutePermission::only(Method::POST, "/admin/config/reload", permissions::system_admin),
+    RoutePermission::any("/admin/config", permissions::monitor_system),
+    RoutePermission::any("/admin/audit/*", permissions::read_audit_logs),
+    RoutePermission::any("/fees/schedules/*", permissions::manage_fees),
+    RoutePermission::any("/interest/rates", permissions::manage_interest_rates),
+    RoutePermission::any("/reconciliation/*", permissions::manage_reconciliation),
+    RoutePermission::any("/webhooks/*", permissions::manage_webhooks),
+    RoutePermission::only(Method::GET, "/payments/approvals", permissions::approve_payments),
+    RoutePermission::only(Method::POST, "/payments/:id/approve", permissions::approve_payments),
+    RoutePermission::only(Method::POST, "/disputes/:id/status", permissions::review_disputes),
+    RoutePermission::any("/reviews/*", permissions::review_verifications),
+];
+
+/// The permission a request needs, if its route is privileged. Routes are
+/// guarded alike under every API version.
+pub fn required_permission(method: &Method, path: &str) -> Option<Permission> {
+    let path = versioning::unversioned(path)?;
+    ROUTE_PERMISSIONS
+        .iter()
+        .find(|route| route.matches(method, path))
+        .map(|route| (route.permission)())
+}
+
 /// Convenience macros for common permissions
 pub mod permissions {
     use super::Permission;
@@ -415,9 +616,73 @@ pub mod permissions {
         Permission::new("developers", "manage")
     }
 
+    pub fn delete_developers() -> Permission {
+        Permission::new("developers", "delete")
+    }
+
+    pub fn delete_users() -> Permission {
+        Permission::new("users", "delete")
+    }
+
     pub fn system_admin() -> Permission {
         Permission::new("system", "manage")
     }
+
+    pub fn monitor_system() -> Permission {
+        Permission::new("system", "monitor")
+    }
+
+    pub fn review_disputes() -> Permission {
+        Permission::new("disputes", "review")
+    }
+
+    pub fn freeze_accounts() -> Permission {
+        Permission::new("accounts", "freeze")
+    }
+
+    pub fn manage_fees() -> Permission {
+        Permission::new("fees", "manage")
+    }
+
+    pub fn manage_interest_rates() -> Permission {
+        Permission::new("interest", "manage")
+    }
+
+    pub fn manage_reconciliation() -> Permission {
+        Permission::new("reconciliation", "manage")
+    }
+
+    pub fn manage_general_ledger() -> Permission {
+        Permission::new("general_ledger", "manage")
+    }
+
+    pub fn manage_treasury() -> Permission {
+        Permission::new("treasury", "manage")
+    }
+
+    pub fn report_compliance() -> Permission {
+        Permission::new("compliance", "report")
+    }
+
+    pub fn approve_payments() -> Permission {
+        Permission::new("payments", "approve")
+    }
+
+    pub fn review_verifications() -> Permission {
+        Permission::new("verifications", "review")
+    }
+
+    pub fn manage_roles() -> Permission {
+        Permission::new("roles", "manage")
+    }
+
+    pub fn manage_webhooks() -> Permission {
+        Permission::new("webhooks", "manage")
+    }
+
+    pub fn manage_feature_flags() -> Permission {
+        Permission::new("feature_flags", "manage")
+    }
 }
 
 #[cfg(test)]
@@ -460,4 +725,45 @@ mod tests {
         
         assert!(user_roles.has_permission(&permission, &context));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_route_permissions() {
+        let approve = Uuid::new_v4();
+        assert_eq!(
+            required_permission(&Method::POST, &format!("/api/v1/payments/{}/approve", approve)),
+            Some(permissions::approve_payments())
+        );
+        assert_eq!(required_permission(&Method::GET, "/api/v1/payments/approvals"), Some(permissions::approve_payments()));
+        assert_eq!(required_permission(&Method::GET, &format!("/api/v1/payments/{}", approve)), None);
+        assert_eq!(required_permission(&Method::POST, "/api/v1/payments/"), None);
+
+        // Narrower entries take precedence over the prefix they refine
+        let developer = format!("/api/v1/admin/developers/{}", Uuid::new_v4());
+        assert_eq!(required_permission(&Method::DELETE, &developer), Some(permissions::delete_developers()));
+        assert_eq!(required_permission(&Method::GET, &developer), Some(permissions::manage_developers()));
+        assert_eq!(required_permission(&Method::GET, "/api/v1/admin/developers"), Some(permissions::manage_developers()));
+
+        assert_eq!(required_permission(&Method::POST, "/api/v1/interest/rates"), Some(permissions::manage_interest_rates()));
+        assert_eq!(required_permission(&Method::GET, "/api/v1/interest/accounts/x/accrued"), None);
+        assert_eq!(required_permission(&Method::POST, "/api/v1/fees/preview"), None);
+
+        let review = format!("/api/v1/reviews/{}/decision", Uuid::new_v4());
+        assert_eq!(required_permission(&Method::POST, &review), Some(permissions::review_verifications()));
+        assert_eq!(required_permission(&Method::GET, "/api/v1/reviews"), Some(permissions::review_verifications()));
+
+        // Every version is guarded alike; paths outside the API are not
+        assert_eq!(required_permission(&Method::GET, "/api/v2/reviews"), Some(permissions::review_verifications()));
+        assert_eq!(required_permission(&Method::GET, "/reviews"), None);
+    }
+
+    #[test]
+    fn test_permission_round_trip() {
+        let permission: Permission = "payments:approve".parse().unwrap();
+        assert_eq!(permission, permissions::approve_payments());
+        assert_eq!(permission.to_string(), "payments:approve");
+
+        for invalid in ["payments", "payments:", ":approve", "Payments:approve", "a:b:c"] {
+            assert!(invalid.parse::<Permission>().is_err(), "{} should not parse", invalid);
+        }
+    }
+}
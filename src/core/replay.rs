@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::aio::ConnectionManager;
+use serde::Serialize;
+use tokio::sync::OnceCell;
+use tracing::warn;
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+
+/// Prefix of the nonce keys kept in Redis
+const REDIS_KEY_PREFIX: &str = "openbank:nonce:";
+
+/// Entries the in-memory store holds before it sweeps out expired ones
+const MEMORY_SWEEP_THRESHOLD: usize = 1024;
+
+/// Where seen nonces are remembered
+#[async_trait]
+pub trait NonceStore: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Claim `key` for `ttl`, false when it is already claimed
+    async fn claim(&self, key: &str, ttl: Duration) -> AppResult<bool>;
+}
+
+/// Nonces seen by this instance only; enough for a single node
+#[derive(Default)]
+pub struct MemoryNonceStore {
+    entries: Mutex<MemoryEntries>,
+}
+
+#[derive(Default)]
+struct MemoryEntries {
+    expiries: HashMap<String, Instant>,
+    next_sweep: usize,
+}
+
+impl MemoryNonceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn claim_at(&self, key: &str, ttl: Duration, now: Instant) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.expiries.get(key).is_some_and(|expires| *expires > now) {
+            return false;
+        }
+
+        if entries.expiries.len() >= entries.next_sweep {
+            entries.expiries.retain(|_, expires| *expires > now);
+            entries.next_sweep = (entries.expiries.len() * 2).max(MEMORY_SWEEP_THRESHOLD);
+        }
+        entries.expiries.insert(key.to_string(), now + ttl);
+        true
+    }
+}
+
+#[async_trait]
+impl NonceStore for MemoryNonceStore {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn claim(&self, key: &str, ttl: Duration) -> AppResult<bool> {
+        Ok(self.claim_at(key, ttl, Instant::now()))
+    }
+}
+
+/// Nonces shared by every instance through Redis, claimed with `SET NX PX`
+/// so two instances racing on the same nonce cannot both accept it
+pub struct RedisNonceStore {
+    client: redis::Client,
+    connection: OnceCell<ConnectionManager>,
+}
+
+impl RedisNonceStore {
+    /// Connects on first use, so an unreachable Redis fails requests rather
+    /// than startup
+    pub fn new(url: &str) -> AppResult<Self> {
+        let client = redis::Client::open(url)
+            .map_err(|e| AppError::Internal(format!("Invalid REDIS_URL: {}", e)))?;
+        Ok(Self {
+            client,
+            connection: OnceCell::new(),
+        })
+    }
+
+    async fn connection(&self) -> AppResult<ConnectionManager> {
+        let connection = self
+            .connection
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Redis unavailable: {}", e)))?;
+        Ok(connection.clone())
+    }
+}
+
+#[async_trait]
+impl NonceStore for RedisNonceStore {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn claim(&self, key: &str, ttl: Duration) -> AppResult<bool> {
+        let mut connection = self.connection().await?;
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(format!("{}{}", REDIS_KEY_PREFIX, key))
+            .arg(1)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis().max(1) as u64)
+            .query_async(&mut connection)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Redis unavailable: {}", e)))?;
+        Ok(reply.is_some())
+    }
+}
+
+/// Replay counters, as reported by the health check
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayCacheStats {
+    pub backend: &'static str,
+    pub window_seconds: i64,
+    pub accepted: u64,
+    /// Nonces seen before within the window
+    pub replays_rejected: u64,
+    /// Timestamps outside the window
+    pub stale_rejected: u64,
+    pub store_errors: u64,
+    /// Store failures since the last successful claim
+    pub consecutive_store_errors: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    accepted: AtomicU64,
+    replays_rejected: AtomicU64,
+    stale_rejected: AtomicU64,
+    store_errors: AtomicU64,
+    consecutive_store_errors: AtomicU64,
+}
+
+/// Rejects signed requests whose nonce was already used or whose timestamp
+/// is outside the validity window.
+///
+/// A nonce only has to be remembered while its timestamp would still be
+/// accepted, so each is kept until `timestamp + window`. Nonces are scoped
+/// (by rail, signing key, ...) so unrelated signers cannot collide. When
+/// the store is unreachable requests are refused rather than let through.
+#[derive(Clone)]
+pub struct ReplayCache {
+    store: Arc<dyn NonceStore>,
+    window_seconds: i64,
+    counters: Arc<Counters>,
+}
+
+impl ReplayCache {
+    pub fn new(store: Arc<dyn NonceStore>, window_seconds: i64) -> Self {
+        Self {
+            store,
+            window_seconds: window_seconds.max(1),
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> AppResult<Self> {
+        let store: Arc<dyn NonceStore> = match config.replay_cache_backend.as_str() {
+            "memory" => Arc::new(MemoryNonceStore::new()),
+            "redis" => {
+                let url = config.redis_url.as_deref().ok_or_else(|| {
+                    AppError::Internal("REDIS_URL is required when REPLAY_CACHE_BACKEND is redis".to_string())
+                })?;
+                Arc::new(RedisNonceStore::new(url)?)
+            }
+            other => {
+                return Err(AppError::Internal(format!("Unknown REPLAY_CACHE_BACKEND '{}'", other)));
+            }
+        };
+        Ok(Self::new(store, config.replay_window_seconds))
+    }
+
+    /// Accept `nonce` signed at `timestamp` (unix seconds) once within
+    /// `scope`
+    pub async fn check(&self, scope: &str, nonce: &str, timestamp: i64, now: DateTime<Utc>) -> AppResult<()> {
+        let age = now.timestamp() - timestamp;
+        if age.abs() > self.window_seconds {
+            self.counters.stale_rejected.fetch_add(1, Ordering::Relaxed);
+            return Err(AppError::Authentication(
+                "Request timestamp is outside the allowed window".to_string(),
+            ));
+        }
+
+        let ttl = Duration::from_secs((self.window_seconds - age).max(1) as u64);
+        let key = format!("{}:{}", scope, nonce);
+        match self.store.claim(&key, ttl).await {
+            Ok(true) => {
+                self.counters.consecutive_store_errors.store(0, Ordering::Relaxed);
+                self.counters.accepted.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Ok(false) => {
+                self.counters.consecutive_store_errors.store(0, Ordering::Relaxed);
+                self.counters.replays_rejected.fetch_add(1, Ordering::Relaxed);
+                warn!(scope, "Rejected replayed signed request");
+                Err(AppError::Authentication("Request has already been used".to_string()))
+            }
+            Err(e) => {
+                self.counters.store_errors.fetch_add(1, Ordering::Relaxed);
+                self.counters.consecutive_store_errors.fetch_add(1, Ordering::Relaxed);
+                warn!(scope, error = %e, "Replay cache unavailable, refusing signed request");
+                Err(e)
+            }
+        }
+    }
+
+    pub fn stats(&self) -> ReplayCacheStats {
+        ReplayCacheStats {
+            backend: self.store.name(),
+            window_seconds: self.window_seconds,
+            accepted: self.counters.accepted.load(Ordering::Relaxed),
+            replays_rejected: self.counters.replays_rejected.load(Ordering::Relaxed),
+            stale_rejected: self.counters.stale_rejected.load(Ordering::Relaxed),
+            store_errors: self.counters.store_errors.load(Ordering::Relaxed),
+            consecutive_store_errors: self.counters.consecutive_store_errors.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FailingStore;
+
+    #[async_trait]
+    impl NonceStore for FailingStore {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn claim(&self, _key: &str, _ttl: Duration) -> AppResult<bool> {
+            Err(AppError::ExternalService("Redis unavailable".to_string()))
+        }
+    }
+
+    #[test]
+    fn memory_store_forgets_nonces_once_they_expire() {
+        let store = MemoryNonceStore::new();
+        let now = Instant::now();
+        assert!(store.claim_at("a", Duration::from_secs(10), now));
+        assert!(!store.claim_at("a", Duration::from_secs(10), now + Duration::from_secs(5)));
+        assert!(store.claim_at("b", Duration::from_secs(10), now));
+        assert!(store.claim_at("a", Duration::from_secs(10), now + Duration::from_secs(11)));
+    }
+
+    #[tokio::test]
+    async fn rejects_reused_nonces_and_stale_timestamps() {
+        let cache = ReplayCache::new(Arc::new(MemoryNonceStore::new()), 300);
+        let now = Utc::now();
+
+        cache.check("stripe", "n1", now.timestamp(), now).await.unwrap();
+        assert!(matches!(
+            cache.check("stripe", "n1", now.timestamp(), now).await,
+            Err(AppError::Authentication(_))
+        ));
+        // Scopes keep unrelated signers apart
+        cache.check("generic", "n1", now.timestamp(), now).await.unwrap();
+        assert!(cache.check("stripe", "n2", now.timestamp() - 301, now).await.is_err());
+
+        let stats = cache.stats();
+        assert_eq!(stats.backend, "memory");
+        assert_eq!(stats.accepted, 2);
+        assert_eq!(stats.replays_rejected, 1);
+        assert_eq!(stats.stale_rejected, 1);
+    }
+
+    #[tokio::test]
+    async fn refuses_requests_when_the_store_is_down() {
+        let cache = ReplayCache::new(Arc::new(FailingStore), 300);
+        let now = Utc::now();
+
+        assert!(cache.check("stripe", "n1", now.timestamp(), now).await.is_err());
+        let stats = cache.stats();
+        assert_eq!(stats.store_errors, 1);
+        assert_eq!(stats.consecutive_store_errors, 1);
+    }
+}
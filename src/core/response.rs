@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use utoipa::ToSchema;
 
 /// Standard API response wrapper for all OpenBank endpoints
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,7 +18,7 @@ pub struct ApiResponse<T> {
 }
 
 /// Standard error response for API errors
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
     /// Error code for programmatic handling
     pub error_code: String,
@@ -29,7 +30,7 @@ pub struct ErrorResponse {
 }
 
 /// Response status enumeration
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ResponseStatus {
     Success,
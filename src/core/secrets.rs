@@ -0,0 +1,296 @@
+use crate::core::config::Config;
+use crate::core::crypto::{hex, hmac_sha256, sigv4_signing_key};
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use async_trait::async_trait;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Name of the secrets providers' outbound client
+const SECRETS_CLIENT: &str = "secrets";
+
+/// Source of secrets such as the JWT signing key and database credentials
+#[async_trait]
+pub trait SecretsProvider: Send + Sync {
+    /// Fetch a secret by name, returning `None` when the provider does not hold it
+    async fn get_secret(&self, name: &str) -> AppResult<Option<String>>;
+}
+
+/// Reads secrets from environment variables (local development)
+pub struct EnvSecretsProvider;
+
+#[async_trait]
+impl SecretsProvider for EnvSecretsProvider {
+    async fn get_secret(&self, name: &str) -> AppResult<Option<String>> {
+        Ok(std::env::var(name).ok())
+    }
+}
+
+/// Reads secrets from a HashiCorp Vault KV v2 secret, one key per secret name
+pub struct VaultSecretsProvider {
+    client: HttpClient,
+    address: String,
+    token: String,
+    mount: String,
+    path: String,
+}
+
+impl VaultSecretsProvider {
+    pub fn new(address: String, token: String, mount: String, path: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            address: address.trim_end_matches('/').to_string(),
+            token,
+            mount,
+            path,
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for VaultSecretsProvider {
+    async fn get_secret(&self, name: &str) -> AppResult<Option<String>> {
+        let url = format!("{}/v1/{}/data/{}", self.address, self.mount, self.path);
+        let response = self
+            .client
+            .send(self.client.get(&url).header("X-Vault-Token", &self.token))
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Vault request failed: {}", e)))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Vault returned {} for {}",
+                response.status(),
+                self.path
+            )));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Invalid Vault response: {}", e)))?;
+
+        Ok(body["data"]["data"][name].as_str().map(str::to_string))
+    }
+}
+
+/// Reads secrets from an AWS Secrets Manager secret holding a JSON object,
+/// one key per secret name
+pub struct AwsSecretsManagerProvider {
+    client: HttpClient,
+    region: String,
+    secret_id: String,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl AwsSecretsManagerProvider {
+    pub fn new(
+        region: String,
+        secret_id: String,
+        access_key_id: String,
+        secret_access_key: String,
+        session_token: Option<String>,
+    ) -> Self {
+        Self {
+            client: HttpClient::default(),
+            region,
+            secret_id,
+            access_key_id,
+            secret_access_key,
+            session_token,
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Build the SigV4 headers for a GetSecretValue call
+    fn signed_headers(&self, body: &str) -> Vec<(&'static str, String)> {
+        let host = format!("secretsmanager.{}.amazonaws.com", self.region);
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let target = "secretsmanager.GetSecretValue".to_string();
+        let content_type = "application/x-amz-json-1.1".to_string();
+
+        let mut headers = vec![
+            ("content-type", content_type),
+            ("host", host),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(token) = &self.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+        headers.push(("x-amz-target", target));
+
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
+        let signed_header_names = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "POST\n/\n\n{}\n{}\n{}",
+            canonical_headers,
+            signed_header_names,
+            hex(&Sha256::digest(body.as_bytes()))
+        );
+
+        let scope = format!("{}/{}/secretsmanager/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = sigv4_signing_key(&self.secret_access_key, &date, &self.region, "secretsmanager");
+        let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        headers.push((
+            "authorization",
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                self.access_key_id, scope, signed_header_names, signature
+            ),
+        ));
+        headers.retain(|(name, _)| *name != "host");
+        headers
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for AwsSecretsManagerProvider {
+    async fn get_secret(&self, name: &str) -> AppResult<Option<String>> {
+        let body = serde_json::json!({ "SecretId": self.secret_id }).to_string();
+        let url = format!("https://secretsmanager.{}.amazonaws.com/", self.region);
+
+        let mut request = self.client.post(&url).body(body.clone());
+        for (header, value) in self.signed_headers(&body) {
+            request = request.header(header, value);
+        }
+
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Secrets Manager request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Secrets Manager returned {} for {}",
+                response.status(),
+                self.secret_id
+            )));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Invalid Secrets Manager response: {}", e)))?;
+        let Some(secret_string) = body["SecretString"].as_str() else {
+            return Ok(None);
+        };
+        let values: serde_json::Value = serde_json::from_str(secret_string).map_err(|_| {
+            AppError::ExternalService(format!("Secret {} is not a JSON object", self.secret_id))
+        })?;
+
+        Ok(values[name].as_str().map(str::to_string))
+    }
+}
+
+/// Caching front for a secrets provider.
+///
+/// Secrets are fetched lazily on first use and cached for `ttl`, so values
+/// rotated in the backing store are picked up once the cache entry expires.
+/// Names the provider does not hold fall back to environment variables.
+#[derive(Clone)]
+pub struct SecretsManager {
+    provider: Arc<dyn SecretsProvider>,
+    ttl: Duration,
+    cache: Arc<Mutex<HashMap<String, (String, Instant)>>>,
+}
+
+impl SecretsManager {
+    pub fn new(provider: Arc<dyn SecretsProvider>, ttl: Duration) -> Self {
+        Self {
+            provider,
+            ttl,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Build the manager for the provider selected by `SECRETS_PROVIDER`
+    pub fn from_config(config: &Config, clients: &HttpClients) -> AppResult<Self> {
+        let missing = |name: &str| {
+            AppError::Internal(format!(
+                "{} is required for the {} secrets provider",
+                name, config.secrets_provider
+            ))
+        };
+
+        let provider: Arc<dyn SecretsProvider> = match config.secrets_provider.as_str() {
+            "env" => Arc::new(EnvSecretsProvider),
+            "vault" => Arc::new(VaultSecretsProvider::new(
+                config.vault_address.clone().ok_or_else(|| missing("VAULT_ADDR"))?,
+                config.vault_token.clone().ok_or_else(|| missing("VAULT_TOKEN"))?,
+                config.vault_mount.clone(),
+                config.vault_secret_path.clone(),
+            )
+            .with_http_client(clients.get(SECRETS_CLIENT))),
+            "aws" => Arc::new(AwsSecretsManagerProvider::new(
+                config.aws_region.clone(),
+                config.aws_secret_id.clone().ok_or_else(|| missing("AWS_SECRET_ID"))?,
+                std::env::var("AWS_ACCESS_KEY_ID").map_err(|_| missing("AWS_ACCESS_KEY_ID"))?,
+                std::env::var("AWS_SECRET_ACCESS_KEY").map_err(|_| missing("AWS_SECRET_ACCESS_KEY"))?,
+                std::env::var("AWS_SESSION_TOKEN").ok(),
+            )
+            .with_http_client(clients.get(SECRETS_CLIENT))),
+            other => {
+                return Err(AppError::Internal(format!("Unknown secrets provider '{}'", other)));
+            }
+        };
+
+        Ok(Self::new(provider, Duration::from_secs(config.secrets_cache_ttl_seconds)))
+    }
+
+    /// Get a secret, consulting the cache, then the provider, then the environment
+    pub async fn get(&self, name: &str) -> AppResult<Option<String>> {
+        if let Some((value, fetched_at)) = self.cache.lock().unwrap().get(name) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(Some(value.clone()));
+            }
+        }
+
+        let value = match self.provider.get_secret(name).await? {
+            Some(value) => Some(value),
+            None => std::env::var(name).ok(),
+        };
+
+        if let Some(value) = &value {
+            self.cache
+                .lock()
+                .unwrap()
+                .insert(name.to_string(), (value.clone(), Instant::now()));
+        }
+
+        Ok(value)
+    }
+}
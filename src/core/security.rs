@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use crate::core::config::Config;
 use crate::core::error::AppResult;
 
 /// Account security tracking model
@@ -102,6 +104,43 @@ impl AccountSecurity {
     }
 }
 
+/// Failed secret attempts against an OAuth client, tracked per client id so
+/// secrets cannot be sprayed from many addresses
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ClientSecurity {
+    pub client_id: String,
+    pub failed_attempts: i32,
+    pub last_failed_attempt: Option<DateTime<Utc>>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub lock_reason: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ClientSecurity {
+    pub fn new(client_id: String) -> Self {
+        Self {
+            client_id,
+            failed_attempts: 0,
+            last_failed_attempt: None,
+            locked_until: None,
+            lock_reason: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Check if the client is currently locked
+    pub fn is_locked(&self) -> bool {
+        self.locked_until.is_some_and(|locked_until| Utc::now() < locked_until)
+    }
+
+    /// Get remaining lock time
+    pub fn lock_remaining(&self) -> Option<Duration> {
+        self.locked_until
+            .map(|locked_until| locked_until - Utc::now())
+            .filter(|remaining| *remaining > Duration::zero())
+    }
+}
+
 /// Security event for tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityEvent {
@@ -129,17 +168,36 @@ pub struct SecurityConfig {
     pub max_failed_attempts: i32,
     pub lockout_duration_minutes: i64,
     pub progressive_lockout: bool,
+    pub client_max_failed_attempts: i32,
+    pub client_lockout_duration_minutes: i64,
     pub suspicious_activity_threshold: i32,
     pub password_history_count: usize,
     pub require_password_change_days: i64,
 }
 
+impl SecurityConfig {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_failed_attempts: config.max_failed_attempts,
+            lockout_duration_minutes: config.account_lockout_duration_minutes,
+            progressive_lockout: config.progressive_lockout_enabled,
+            client_max_failed_attempts: config.client_max_failed_attempts,
+            client_lockout_duration_minutes: config.client_lockout_duration_minutes,
+            suspicious_activity_threshold: config.suspicious_activity_threshold,
+            password_history_count: config.password_history_count,
+            require_password_change_days: config.require_password_change_days,
+        }
+    }
+}
+
 impl Default for SecurityConfig {
     fn default() -> Self {
         Self {
             max_failed_attempts: 5,
             lockout_duration_minutes: 30,
             progressive_lockout: true,
+            client_max_failed_attempts: 10,
+            client_lockout_duration_minutes: 15,
             suspicious_activity_threshold: 50,
             password_history_count: 12,
             require_password_change_days: 90,
@@ -150,12 +208,24 @@ impl Default for SecurityConfig {
 /// Account security service
 #[derive(Clone)]
 pub struct AccountSecurityService {
-    config: SecurityConfig,
+    config: Arc<RwLock<SecurityConfig>>,
 }
 
 impl AccountSecurityService {
     pub fn new(config: SecurityConfig) -> Self {
-        Self { config }
+        Self {
+            config: Arc::new(RwLock::new(config)),
+        }
+    }
+
+    pub fn config(&self) -> SecurityConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Apply new thresholds to every clone; they take effect from the next
+    /// attempt
+    pub fn reconfigure(&self, config: SecurityConfig) {
+        *self.config.write().unwrap() = config;
     }
 
     /// Record a failed login attempt
@@ -164,6 +234,7 @@ impl AccountSecurityService {
         security: &mut AccountSecurity,
         ip_address: String,
     ) -> AppResult<SecurityAction> {
+        let config = self.config();
         security.failed_attempts += 1;
         security.last_failed_attempt = Some(Utc::now());
         security.updated_at = Utc::now();
@@ -175,16 +246,12 @@ impl AccountSecurityService {
         }
 
         // Determine if account should be locked
-        if security.failed_attempts >= self.config.max_failed_attempts {
-            let lock_duration = if self.config.progressive_lockout {
-                // Progressive lockout: increase duration with each lockout
-                let base_minutes = self.config.lockout_duration_minutes;
-                let multiplier = (security.failed_attempts - self.config.max_failed_attempts) + 1;
-                Duration::minutes(base_minutes * multiplier as i64)
-            } else {
-                Duration::minutes(self.config.lockout_duration_minutes)
-            };
-
+        if let Some(lock_duration) = lock_duration(
+            security.failed_attempts,
+            config.max_failed_attempts,
+            config.lockout_duration_minutes,
+            config.progressive_lockout,
+        ) {
             security.locked_until = Some(Utc::now() + lock_duration);
             security.lock_reason = Some(format!(
                 "Account locked due to {} consecutive failed login attempts",
@@ -199,7 +266,7 @@ impl AccountSecurityService {
 
         Ok(SecurityAction::IncrementFailures {
             current_count: security.failed_attempts,
-            max_attempts: self.config.max_failed_attempts,
+            max_attempts: config.max_failed_attempts,
         })
     }
 
@@ -224,6 +291,47 @@ impl AccountSecurityService {
         Ok(())
     }
 
+    /// Record a wrong secret for an OAuth client, locking the client once
+    /// the failures reach the client threshold
+    pub fn record_failed_client_attempt(&self, security: &mut ClientSecurity) -> AppResult<SecurityAction> {
+        let config = self.config();
+        security.failed_attempts += 1;
+        security.last_failed_attempt = Some(Utc::now());
+        security.updated_at = Utc::now();
+
+        if let Some(lock_duration) = lock_duration(
+            security.failed_attempts,
+            config.client_max_failed_attempts,
+            config.client_lockout_duration_minutes,
+            config.progressive_lockout,
+        ) {
+            security.locked_until = Some(Utc::now() + lock_duration);
+            security.lock_reason = Some(format!(
+                "Client locked due to {} consecutive failed authentication attempts",
+                security.failed_attempts
+            ));
+
+            return Ok(SecurityAction::AccountLocked {
+                duration: lock_duration,
+                reason: security.lock_reason.clone().unwrap(),
+            });
+        }
+
+        Ok(SecurityAction::IncrementFailures {
+            current_count: security.failed_attempts,
+            max_attempts: config.client_max_failed_attempts,
+        })
+    }
+
+    /// Record a successful client authentication, clearing its failures
+    pub fn record_successful_client_auth(&self, security: &mut ClientSecurity) {
+        security.failed_attempts = 0;
+        security.last_failed_attempt = None;
+        security.locked_until = None;
+        security.lock_reason = None;
+        security.updated_at = Utc::now();
+    }
+
     /// Check if password can be changed (not in history)
     pub fn can_use_password(&self, security: &AccountSecurity, password_hash: &str) -> bool {
         !security.password_history_hashes.contains(&password_hash.to_string())
@@ -241,7 +349,7 @@ impl AccountSecurityService {
         }
 
         // Keep only configured number of password history
-        security.password_history_hashes.truncate(self.config.password_history_count);
+        security.password_history_hashes.truncate(self.config().password_history_count);
 
         security.password_last_changed = Utc::now();
         security.updated_at = Utc::now();
@@ -307,6 +415,16 @@ impl AccountSecurityService {
     }
 }
 
+/// How long to lock after `failed_attempts` failures, if at all. Progressive
+/// lockout lengthens the lock with each failure past the threshold.
+fn lock_duration(failed_attempts: i32, max_attempts: i32, base_minutes: i64, progressive: bool) -> Option<Duration> {
+    if failed_attempts < max_attempts {
+        return None;
+    }
+    let multiplier = if progressive { (failed_attempts - max_attempts) as i64 + 1 } else { 1 };
+    Some(Duration::minutes(base_minutes * multiplier))
+}
+
 /// Actions taken by security service
 #[derive(Debug, Clone)]
 pub enum SecurityAction {
@@ -403,4 +521,53 @@ impl PasswordPolicy {
             Err(errors)
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(progressive: bool) -> AccountSecurityService {
+        AccountSecurityService::new(SecurityConfig {
+            client_max_failed_attempts: 3,
+            client_lockout_duration_minutes: 10,
+            progressive_lockout: progressive,
+            ..SecurityConfig::default()
+        })
+    }
+
+    #[test]
+    fn locks_clients_after_repeated_failures() {
+        let service = service(true);
+        let mut security = ClientSecurity::new("client".to_string());
+
+        for _ in 0..2 {
+            let action = service.record_failed_client_attempt(&mut security).unwrap();
+            assert!(matches!(action, SecurityAction::IncrementFailures { max_attempts: 3, .. }));
+        }
+        assert!(!security.is_locked());
+
+        let action = service.record_failed_client_attempt(&mut security).unwrap();
+        assert!(matches!(action, SecurityAction::AccountLocked { duration, .. } if duration == Duration::minutes(10)));
+        assert!(security.is_locked());
+
+        // Each failure after the lock runs out locks for longer
+        let action = service.record_failed_client_attempt(&mut security).unwrap();
+        assert!(matches!(action, SecurityAction::AccountLocked { duration, .. } if duration == Duration::minutes(20)));
+    }
+
+    #[test]
+    fn success_clears_client_failures() {
+        let service = service(false);
+        let mut security = ClientSecurity::new("client".to_string());
+        for _ in 0..4 {
+            service.record_failed_client_attempt(&mut security).unwrap();
+        }
+        assert_eq!(security.lock_remaining().map(|remaining| remaining.num_minutes()), Some(9));
+
+        service.record_successful_client_auth(&mut security);
+        assert_eq!(security.failed_attempts, 0);
+        assert!(!security.is_locked());
+        assert!(security.lock_remaining().is_none());
+    }
+}
@@ -0,0 +1,818 @@
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::Mutex;
+use tracing::{error, warn};
+use crate::core::audit::{AuditEvent, AuditEventType, AuditSeverity};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients, RetryPolicy};
+
+/// ECS version the forwarded documents follow
+pub const ECS_VERSION: &str = "8.11.0";
+
+/// Name of the SIEM's outbound client
+const SIEM_CLIENT: &str = "siem";
+
+/// Syslog facility 13, "log audit"
+const SYSLOG_FACILITY: u8 = 13;
+
+#[derive(Debug, Clone)]
+pub struct SiemSettings {
+    /// Least severe events forwarded
+    pub min_severity: AuditSeverity,
+    /// Events waiting for the forwarder before new ones are dropped
+    pub queue_capacity: usize,
+    /// Most events shipped in one request
+    pub batch_size: usize,
+    /// How long a batch waits to fill up before it is shipped
+    pub flush_interval: Duration,
+    /// Attempts at shipping a batch before it is dropped
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubling for each one after
+    pub retry: RetryPolicy,
+}
+
+impl SiemSettings {
+    pub fn from_config(config: &Config) -> AppResult<Self> {
+        let min_severity = parse_severity(&config.siem_min_severity).ok_or_else(|| {
+            AppError::Internal(format!("Unknown SIEM_MIN_SEVERITY '{}'", config.siem_min_severity))
+        })?;
+        let max_attempts = config.siem_max_attempts.max(1);
+
+        Ok(Self {
+            min_severity,
+            queue_capacity: config.siem_queue_capacity.max(1),
+            batch_size: config.siem_batch_size.max(1),
+            flush_interval: Duration::from_millis(config.siem_flush_interval_ms),
+            max_attempts,
+            retry: RetryPolicy {
+                max_retries: max_attempts - 1,
+                base_delay: Duration::from_millis(config.siem_retry_base_delay_ms),
+            },
+        })
+    }
+}
+
+fn parse_severity(value: &str) -> Option<AuditSeverity> {
+    match value.to_lowercase().as_str() {
+        "info" => Some(AuditSeverity::Info),
+        "warning" => Some(AuditSeverity::Warning),
+        "error" => Some(AuditSeverity::Error),
+        "critical" => Some(AuditSeverity::Critical),
+        _ => None,
+    }
+}
+
+/// Orders severities, least severe first
+fn severity_rank(severity: &AuditSeverity) -> u8 {
+    match severity {
+        AuditSeverity::Info => 0,
+        AuditSeverity::Warning => 1,
+        AuditSeverity::Error => 2,
+        AuditSeverity::Critical => 3,
+    }
+}
+
+fn severity_name(severity: &AuditSeverity) -> &'static str {
+    match severity {
+        AuditSeverity::Info => "info",
+        AuditSeverity::Warning => "warning",
+        AuditSeverity::Error => "error",
+        AuditSeverity::Critical => "critical",
+    }
+}
+
+/// Numeric severity on the scale Elastic's detection rules use
+fn ecs_severity(severity: &AuditSeverity) -> u8 {
+    match severity {
+        AuditSeverity::Info => 21,
+        AuditSeverity::Warning => 47,
+        AuditSeverity::Error => 73,
+        AuditSeverity::Critical => 99,
+    }
+}
+
+/// RFC 5424 severity code
+fn syslog_severity(severity: &AuditSeverity) -> u8 {
+    match severity {
+        AuditSeverity::Info => 6,
+        AuditSeverity::Warning => 4,
+        AuditSeverity::Error => 3,
+        AuditSeverity::Critical => 2,
+    }
+}
+
+/// ECS `event.category` and `event.type` of an event type, where one fits
+fn ecs_categorization(event_type: &AuditEventType, success: bool) -> (&'static [&'static str], &'static str) {
+    use AuditEventType::*;
+    match event_type {
+        LoginAttempt | LoginSuccess | LoginFailure | StepUpChallenged => (&["authentication"], "start"),
+        Logout | TokenRevoked | TokenExpired => (&["authentication", "session"], "end"),
+        TokenGenerated | TokenRefreshed | TokenValidated | MfaEnabled | MfaDisabled | PasswordChanged => {
+            (&["authentication"], "info")
+        }
+        AccessGranted | AccessDenied | ScopeValidated | ScopeViolation => {
+            (&["iam"], if success { "allowed" } else { "denied" })
+        }
+        CustomRoleCreated | CustomRoleUpdated | CustomRoleDeleted | CustomRoleAssigned | CustomRoleRevoked
+        | SystemRoleGranted | AccountLocked | AccountUnlocked => (&["iam"], "change"),
+        RateLimitExceeded | SuspiciousActivity | IpAccessBlocked | DeviceAssertionRejected => {
+            (&["intrusion_detection"], "denied")
+        }
+        ConfigurationChanged | FeatureFlagCreated | FeatureFlagUpdated | FeatureFlagDeleted
+        | FeatureFlagTargetChanged => (&["configuration"], "change"),
+        DatabaseAccess => (&["database"], "access"),
+        _ => (&[], "info"),
+    }
+}
+
+/// Map an audit event to an ECS document. Fields without an ECS home are
+/// kept under `openbank.audit`.
+pub fn to_ecs(event: &AuditEvent) -> Value {
+    let event_type = serde_json::to_value(&event.event_type)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+    let (categories, kind) = ecs_categorization(&event.event_type, event.success);
+
+    let mut ecs_event = json!({
+        "id": event.id,
+        "kind": "event",
+        "action": event_type,
+        "dataset": "openbank.audit",
+        "module": "openbank",
+        "outcome": if event.success { "success" } else { "failure" },
+        "severity": ecs_severity(&event.severity),
+        "created": event.timestamp,
+    });
+    if !categories.is_empty() {
+        ecs_event["category"] = json!(categories);
+        ecs_event["type"] = json!([kind]);
+    }
+    if let Some(risk_score) = event.risk_score {
+        ecs_event["risk_score"] = json!(risk_score);
+    }
+
+    let mut document = json!({
+        "@timestamp": event.timestamp,
+        "ecs": { "version": ECS_VERSION },
+        "service": { "name": "openbank" },
+        "event": ecs_event,
+        "log": { "level": severity_name(&event.severity) },
+        "message": match (&event.resource, &event.error_message) {
+            (_, Some(error)) => format!("{}: {}", event_type, error),
+            (Some(resource), None) => format!("{} on {}", event_type, resource),
+            (None, None) => event_type.clone(),
+        },
+    });
+    if let Some(user_id) = event.user_id {
+        document["user"] = json!({ "id": user_id });
+    }
+    // `source.ip` is mapped as an address; anything else would be rejected
+    if let Ok(ip) = event.ip_address.parse::<IpAddr>() {
+        document["source"] = json!({ "ip": ip.to_string() });
+    }
+    if let Some(user_agent) = &event.user_agent {
+        document["user_agent"] = json!({ "original": user_agent });
+    }
+    if let Some(request_id) = &event.request_id {
+        document["http"] = json!({ "request": { "id": request_id } });
+    }
+    if let Some(error) = &event.error_message {
+        document["error"] = json!({ "message": error });
+    }
+    if !event.compliance_tags.is_empty() {
+        document["tags"] = json!(event.compliance_tags);
+    }
+    if let Some(project_id) = event.project_id {
+        document["labels"] = json!({ "project_id": project_id.to_string() });
+    }
+
+    let mut audit = Map::new();
+    let optional = [
+        ("resource", event.resource.as_ref().map(|value| json!(value))),
+        ("action", event.action.as_ref().map(|value| json!(value))),
+        ("project_id", event.project_id.map(|value| json!(value))),
+        ("session_id", event.session_id.as_ref().map(|value| json!(value))),
+        ("changes", event.changes.clone()),
+        ("chain", event.chain.as_ref().map(|value| json!(value))),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value {
+            audit.insert(key.to_string(), value);
+        }
+    }
+    if !event.metadata.is_empty() {
+        audit.insert("metadata".to_string(), json!(event.metadata));
+    }
+    document["openbank"] = json!({ "audit": audit });
+
+    document
+}
+
+/// A forwarded event: the ECS document and what the transports need besides it
+#[derive(Debug, Clone)]
+pub struct SiemEvent {
+    pub id: String,
+    pub severity: AuditSeverity,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub document: Value,
+}
+
+impl SiemEvent {
+    pub fn from_audit(event: &AuditEvent) -> Self {
+        Self {
+            id: event.id.to_string(),
+            severity: event.severity.clone(),
+            timestamp: event.timestamp,
+            document: to_ecs(event),
+        }
+    }
+}
+
+/// Where forwarded events are shipped
+#[async_trait]
+pub trait SiemSink: Send + Sync {
+    /// Ship a batch; on error the whole batch is shipped again, so sinks
+    /// must tolerate events they already took
+    async fn ship(&self, events: &[SiemEvent]) -> AppResult<()>;
+}
+
+/// An RFC 5424 syslog message carrying the ECS document
+pub fn syslog_message(event: &SiemEvent, hostname: &str) -> String {
+    format!(
+        "<{}>1 {} {} openbank {} audit - {}",
+        SYSLOG_FACILITY * 8 + syslog_severity(&event.severity),
+        event.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+        hostname,
+        std::process::id(),
+        event.document
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyslogTransport {
+    Tcp,
+    Udp,
+}
+
+/// Sends RFC 5424 messages over TCP, with RFC 6587 octet counting, or UDP,
+/// one datagram per event
+pub struct SyslogSink {
+    transport: SyslogTransport,
+    address: String,
+    hostname: String,
+    /// The TCP connection, reopened after a failed send
+    connection: Mutex<Option<TcpStream>>,
+}
+
+impl SyslogSink {
+    /// `endpoint` is `tcp://host:port` or `udp://host:port`
+    pub fn new(endpoint: &str) -> AppResult<Self> {
+        let (transport, address) = match endpoint.split_once("://") {
+            Some(("tcp", address)) => (SyslogTransport::Tcp, address),
+            Some(("udp", address)) => (SyslogTransport::Udp, address),
+            _ => {
+                return Err(AppError::Internal(format!(
+                    "SIEM_ENDPOINT '{}' must be tcp://host:port or udp://host:port for syslog",
+                    endpoint
+                )))
+            }
+        };
+        Ok(Self {
+            transport,
+            address: address.to_string(),
+            hostname: std::env::var("HOSTNAME").unwrap_or_else(|_| "-".to_string()),
+            connection: Mutex::new(None),
+        })
+    }
+
+    async fn ship_tcp(&self, events: &[SiemEvent]) -> std::io::Result<()> {
+        let mut frames = Vec::new();
+        for event in events {
+            let message = syslog_message(event, &self.hostname);
+            frames.extend_from_slice(format!("{} {}", message.len(), message).as_bytes());
+        }
+
+        let mut connection = self.connection.lock().await;
+        if connection.is_none() {
+            *connection = Some(TcpStream::connect(&self.address).await?);
+        }
+        let stream = connection.as_mut().expect("connection was just opened");
+        let result = async {
+            stream.write_all(&frames).await?;
+            stream.flush().await
+        }
+        .await;
+        if result.is_err() {
+            *connection = None;
+        }
+        result
+    }
+
+    async fn ship_udp(&self, events: &[SiemEvent]) -> std::io::Result<()> {
+        let socket = UdpSocket::bind("0.0.0.0:0").await?;
+        socket.connect(&self.address).await?;
+        for event in events {
+            socket.send(syslog_message(event, &self.hostname).as_bytes()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SiemSink for SyslogSink {
+    async fn ship(&self, events: &[SiemEvent]) -> AppResult<()> {
+        let result = match self.transport {
+            SyslogTransport::Tcp => self.ship_tcp(events).await,
+            SyslogTransport::Udp => self.ship_udp(events).await,
+        };
+        result.map_err(|e| AppError::ExternalService(format!("Syslog delivery failed: {}", e)))
+    }
+}
+
+/// The body of a Splunk HEC request: one event object after another
+pub fn splunk_hec_body(events: &[SiemEvent]) -> String {
+    events
+        .iter()
+        .map(|event| {
+            json!({
+                "time": event.timestamp.timestamp_millis() as f64 / 1000.0,
+                "source": "openbank",
+                "sourcetype": "_json",
+                "event": event.document,
+            })
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Posts events to a Splunk HTTP Event Collector
+pub struct SplunkHecSink {
+    client: HttpClient,
+    url: String,
+    token: String,
+}
+
+impl SplunkHecSink {
+    pub fn new(url: String, token: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            url,
+            token,
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl SiemSink for SplunkHecSink {
+    async fn ship(&self, events: &[SiemEvent]) -> AppResult<()> {
+        let request = self
+            .client
+            .post(&self.url)
+            .header("Authorization", format!("Splunk {}", self.token))
+            .header("Content-Type", "application/json")
+            .body(splunk_hec_body(events));
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Splunk HEC delivery failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Splunk HEC returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The body of an Elasticsearch bulk request. Each event is created under
+/// its own id, so a batch shipped again does not index it twice.
+pub fn elastic_bulk_body(events: &[SiemEvent], index: &str) -> String {
+    let mut body = String::new();
+    for event in events {
+        body.push_str(&json!({ "create": { "_index": index, "_id": event.id } }).to_string());
+        body.push('\n');
+        body.push_str(&event.document.to_string());
+        body.push('\n');
+    }
+    body
+}
+
+/// Why items of a bulk response failed. Conflicts are events an earlier
+/// attempt already indexed.
+pub fn elastic_bulk_failures(response: &Value) -> Vec<String> {
+    if response["errors"].as_bool() != Some(true) {
+        return Vec::new();
+    }
+    response["items"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_object()?.values().next())
+                .filter(|result| result["status"].as_u64().is_some_and(|status| status >= 300 && status != 409))
+                .map(|result| {
+                    result["error"]["reason"]
+                        .as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| result["error"].to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Indexes events through the Elasticsearch bulk API
+pub struct ElasticSink {
+    client: HttpClient,
+    url: String,
+    api_key: Option<String>,
+    index: String,
+}
+
+impl ElasticSink {
+    pub fn new(url: String, api_key: Option<String>, index: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            url,
+            api_key,
+            index,
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl SiemSink for ElasticSink {
+    async fn ship(&self, events: &[SiemEvent]) -> AppResult<()> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Content-Type", "application/x-ndjson")
+            .body(elastic_bulk_body(events, &self.index));
+        if let Some(api_key) = &self.api_key {
+            request = request.header("Authorization", format!("ApiKey {}", api_key));
+        }
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Elasticsearch delivery failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Elasticsearch returned {}",
+                response.status()
+            )));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Unreadable Elasticsearch response: {}", e)))?;
+        let failures = elastic_bulk_failures(&body);
+        if let Some(first) = failures.first() {
+            return Err(AppError::ExternalService(format!(
+                "Elasticsearch rejected {} of {} events: {}",
+                failures.len(),
+                events.len(),
+                first
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How the forwarder is keeping up, for health checks
+#[derive(Debug, Clone, Serialize)]
+pub struct SiemForwarderStats {
+    pub queued: usize,
+    pub queue_capacity: usize,
+    pub forwarded: u64,
+    /// Events dropped because the queue was full or every attempt failed
+    pub dropped: u64,
+    /// Batches in a row that could not be shipped
+    pub consecutive_failures: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    forwarded: AtomicU64,
+    dropped: AtomicU64,
+    consecutive_failures: AtomicU64,
+}
+
+/// Ships audit events at or above a severity to a SIEM from a background
+/// task, so logging never waits on the SIEM. Events are batched off a
+/// bounded queue; a batch that cannot be shipped is retried with doubling
+/// delays and dropped after the last attempt. MongoDB stays the record.
+#[derive(Clone)]
+pub struct SiemForwarder {
+    sender: mpsc::Sender<SiemEvent>,
+    min_severity: u8,
+    queue_capacity: usize,
+    counters: Arc<Counters>,
+}
+
+impl SiemForwarder {
+    /// Start the forwarder task; needs a Tokio runtime
+    pub fn spawn(sink: Arc<dyn SiemSink>, settings: SiemSettings) -> Self {
+        let (sender, receiver) = mpsc::channel(settings.queue_capacity);
+        let counters = Arc::new(Counters::default());
+        let forwarder = Self {
+            sender,
+            min_severity: severity_rank(&settings.min_severity),
+            queue_capacity: settings.queue_capacity,
+            counters: counters.clone(),
+        };
+        tokio::spawn(run(sink, settings, counters, receiver));
+        forwarder
+    }
+
+    /// Queue an event if it is severe enough, dropping it when the queue is
+    /// full
+    pub fn submit(&self, event: &AuditEvent) {
+        if severity_rank(&event.severity) < self.min_severity {
+            return;
+        }
+        match self.sender.try_send(SiemEvent::from_audit(event)) {
+            Ok(()) => {}
+            Err(TrySendError::Full(event)) => {
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+                warn!(event_id = %event.id, "SIEM queue full, event not forwarded");
+            }
+            Err(TrySendError::Closed(_)) => {
+                self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn stats(&self) -> SiemForwarderStats {
+        SiemForwarderStats {
+            queued: self.queue_capacity - self.sender.capacity(),
+            queue_capacity: self.queue_capacity,
+            forwarded: self.counters.forwarded.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+            consecutive_failures: self.counters.consecutive_failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Wait for an event, give the batch the flush interval to fill up, then
+/// ship it
+async fn run(
+    sink: Arc<dyn SiemSink>,
+    settings: SiemSettings,
+    counters: Arc<Counters>,
+    mut receiver: mpsc::Receiver<SiemEvent>,
+) {
+    let mut batch = Vec::with_capacity(settings.batch_size);
+    while receiver.recv_many(&mut batch, settings.batch_size).await > 0 {
+        let deadline = tokio::time::Instant::now() + settings.flush_interval;
+        while batch.len() < settings.batch_size {
+            let remaining = settings.batch_size - batch.len();
+            match tokio::time::timeout_at(deadline, receiver.recv_many(&mut batch, remaining)).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        ship(sink.as_ref(), &settings, &counters, std::mem::take(&mut batch)).await;
+    }
+}
+
+async fn ship(sink: &dyn SiemSink, settings: &SiemSettings, counters: &Counters, batch: Vec<SiemEvent>) {
+    for attempt in 1..=settings.max_attempts {
+        match sink.ship(&batch).await {
+            Ok(()) => {
+                counters.forwarded.fetch_add(batch.len() as u64, Ordering::Relaxed);
+                counters.consecutive_failures.store(0, Ordering::Relaxed);
+                return;
+            }
+            Err(e) if attempt < settings.max_attempts => {
+                warn!(error = %e, attempt, count = batch.len(), "Failed to forward audit events to the SIEM, retrying");
+                tokio::time::sleep(settings.retry.delay(attempt - 1)).await;
+            }
+            Err(e) => {
+                error!(error = %e, attempt, count = batch.len(), "Failed to forward audit events to the SIEM, dropping them");
+            }
+        }
+    }
+    counters.dropped.fetch_add(batch.len() as u64, Ordering::Relaxed);
+    counters.consecutive_failures.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Build the forwarder selected by `SIEM_FORWARDER`; `None` when forwarding
+/// is off
+pub fn from_config(config: &Config, clients: &HttpClients) -> AppResult<Option<SiemForwarder>> {
+    let missing = |name: &str| {
+        AppError::Internal(format!("{} is required for the {} SIEM forwarder", name, config.siem_forwarder))
+    };
+    let endpoint = || config.siem_endpoint.clone().ok_or_else(|| missing("SIEM_ENDPOINT"));
+    // Batches are retried here, with the forwarder's own policy
+    let client = || clients.get(SIEM_CLIENT).without_retries();
+
+    let sink: Arc<dyn SiemSink> = match config.siem_forwarder.as_str() {
+        "none" => return Ok(None),
+        "syslog" => Arc::new(SyslogSink::new(&endpoint()?)?),
+        "splunk" => Arc::new(
+            SplunkHecSink::new(endpoint()?, config.siem_token.clone().ok_or_else(|| missing("SIEM_TOKEN"))?)
+                .with_http_client(client()),
+        ),
+        "elastic" => Arc::new(
+            ElasticSink::new(endpoint()?, config.siem_token.clone(), config.siem_elastic_index.clone())
+                .with_http_client(client()),
+        ),
+        other => return Err(AppError::Internal(format!("Unknown SIEM forwarder '{}'", other))),
+    };
+    Ok(Some(SiemForwarder::spawn(sink, SiemSettings::from_config(config)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+    use uuid::Uuid;
+
+    fn failed_login() -> AuditEvent {
+        AuditEvent::new(AuditEventType::LoginFailure)
+            .user_id(Uuid::nil())
+            .ip_address("203.0.113.7".to_string())
+            .error("bad password".to_string())
+            .severity(AuditSeverity::Warning)
+            .risk_score(30)
+            .compliance_tag("SOC2".to_string())
+    }
+
+    #[test]
+    fn maps_audit_events_to_ecs() {
+        let event = failed_login();
+        let document = to_ecs(&event);
+
+        assert_eq!(document["ecs"]["version"], ECS_VERSION);
+        assert_eq!(document["event"]["id"], json!(event.id));
+        assert_eq!(document["event"]["action"], "login_failure");
+        assert_eq!(document["event"]["category"], json!(["authentication"]));
+        assert_eq!(document["event"]["type"], json!(["start"]));
+        assert_eq!(document["event"]["outcome"], "failure");
+        assert_eq!(document["event"]["severity"], 47);
+        assert_eq!(document["event"]["risk_score"], 30);
+        assert_eq!(document["log"]["level"], "warning");
+        assert_eq!(document["source"]["ip"], "203.0.113.7");
+        assert_eq!(document["user"]["id"], json!(Uuid::nil()));
+        assert_eq!(document["error"]["message"], "bad password");
+        assert_eq!(document["message"], "login_failure: bad password");
+        assert_eq!(document["tags"], json!(["SOC2"]));
+    }
+
+    #[test]
+    fn keeps_fields_without_an_ecs_home_under_openbank() {
+        let event = AuditEvent::new(AuditEventType::PaymentRejected)
+            .resource("payment:1".to_string())
+            .action("reject".to_string())
+            .metadata("amount".to_string(), json!(100));
+        let document = to_ecs(&event);
+
+        assert!(document["event"].get("category").is_none());
+        assert!(document.get("source").is_none());
+        assert_eq!(document["openbank"]["audit"]["resource"], "payment:1");
+        assert_eq!(document["openbank"]["audit"]["action"], "reject");
+        assert_eq!(document["openbank"]["audit"]["metadata"]["amount"], 100);
+        assert_eq!(document["message"], "payment_rejected on payment:1");
+    }
+
+    #[test]
+    fn syslog_messages_follow_rfc_5424() {
+        let event = SiemEvent::from_audit(&failed_login());
+        let message = syslog_message(&event, "api-1");
+
+        // facility 13 (log audit) * 8 + severity 4 (warning)
+        assert!(message.starts_with("<108>1 "));
+        assert!(message.contains(" api-1 openbank "));
+        assert!(message.ends_with(&event.document.to_string()));
+    }
+
+    #[test]
+    fn bulk_bodies_create_each_event_under_its_id() {
+        let events = vec![SiemEvent::from_audit(&failed_login()), SiemEvent::from_audit(&failed_login())];
+        let body = elastic_bulk_body(&events, "logs-openbank.audit-default");
+        let lines: Vec<Value> = body.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0]["create"]["_id"], events[0].id);
+        assert_eq!(lines[0]["create"]["_index"], "logs-openbank.audit-default");
+        assert_eq!(lines[3], events[1].document);
+    }
+
+    #[test]
+    fn bulk_conflicts_are_not_failures() {
+        let response = json!({
+            "errors": true,
+            "items": [
+                { "create": { "status": 201 } },
+                { "create": { "status": 409, "error": { "reason": "version conflict" } } },
+                { "create": { "status": 400, "error": { "reason": "mapper_parsing_exception" } } },
+            ]
+        });
+        assert_eq!(elastic_bulk_failures(&response), vec!["mapper_parsing_exception".to_string()]);
+        assert!(elastic_bulk_failures(&json!({ "errors": false, "items": [] })).is_empty());
+    }
+
+    #[test]
+    fn hec_bodies_wrap_each_document() {
+        let events = vec![SiemEvent::from_audit(&failed_login())];
+        let body: Value = serde_json::from_str(&splunk_hec_body(&events)).unwrap();
+
+        assert_eq!(body["sourcetype"], "_json");
+        assert_eq!(body["event"], events[0].document);
+    }
+
+    struct FlakySink {
+        failures_left: StdMutex<u32>,
+        shipped: StdMutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl SiemSink for FlakySink {
+        async fn ship(&self, events: &[SiemEvent]) -> AppResult<()> {
+            let mut failures_left = self.failures_left.lock().unwrap();
+            if *failures_left > 0 {
+                *failures_left -= 1;
+                return Err(AppError::ExternalService("unavailable".to_string()));
+            }
+            self.shipped.lock().unwrap().push(events.len());
+            Ok(())
+        }
+    }
+
+    fn settings(max_attempts: u32) -> SiemSettings {
+        SiemSettings {
+            min_severity: AuditSeverity::Warning,
+            queue_capacity: 100,
+            batch_size: 10,
+            flush_interval: Duration::from_millis(20),
+            max_attempts,
+            retry: RetryPolicy {
+                max_retries: max_attempts - 1,
+                base_delay: Duration::from_millis(1),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn batches_severe_events_and_retries_failed_batches() {
+        let sink = Arc::new(FlakySink {
+            failures_left: StdMutex::new(2),
+            shipped: StdMutex::new(Vec::new()),
+        });
+        let forwarder = SiemForwarder::spawn(sink.clone(), settings(3));
+
+        for _ in 0..3 {
+            forwarder.submit(&failed_login());
+        }
+        forwarder.submit(&AuditEvent::new(AuditEventType::LoginSuccess));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(*sink.shipped.lock().unwrap(), vec![3]);
+        let stats = forwarder.stats();
+        assert_eq!(stats.forwarded, 3);
+        assert_eq!(stats.dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn drops_batches_after_the_last_attempt() {
+        let sink = Arc::new(FlakySink {
+            failures_left: StdMutex::new(u32::MAX),
+            shipped: StdMutex::new(Vec::new()),
+        });
+        let forwarder = SiemForwarder::spawn(sink, settings(2));
+
+        forwarder.submit(&failed_login());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let stats = forwarder.stats();
+        assert_eq!(stats.forwarded, 0);
+        assert_eq!(stats.dropped, 1);
+        assert_eq!(stats.consecutive_failures, 1);
+    }
+}
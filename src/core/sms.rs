@@ -0,0 +1,102 @@
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// An outgoing text message
+#[derive(Debug, Clone)]
+pub struct SmsMessage {
+    /// E.164 phone number
+    pub to: String,
+    pub body: String,
+}
+
+/// Delivery channel for transactional text messages
+#[async_trait]
+pub trait SmsSender: Send + Sync {
+    async fn send(&self, message: SmsMessage) -> AppResult<()>;
+}
+
+/// Writes text messages to the log instead of delivering them (local
+/// development)
+pub struct LogSmsSender;
+
+#[async_trait]
+impl SmsSender for LogSmsSender {
+    async fn send(&self, message: SmsMessage) -> AppResult<()> {
+        tracing::info!(to = %message.to, "Text message not delivered (log sender):\n{}", message.body);
+        Ok(())
+    }
+}
+
+/// Delivers text messages through an HTTP SMS API accepting
+/// `{from, to, text}` JSON with a bearer API key
+pub struct HttpSmsSender {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    from: String,
+}
+
+impl HttpSmsSender {
+    pub fn new(api_url: String, api_key: String, from: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            from,
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl SmsSender for HttpSmsSender {
+    async fn send(&self, message: SmsMessage) -> AppResult<()> {
+        let payload = serde_json::json!({
+            "from": self.from,
+            "to": message.to,
+            "text": message.body,
+        });
+
+        let request = self.client.post(&self.api_url).bearer_auth(&self.api_key).json(&payload);
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("SMS request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "SMS provider returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Name of the SMS API's outbound client
+const SMS_CLIENT: &str = "sms";
+
+/// Build the text message sender selected by `SMS_PROVIDER`
+pub fn from_config(config: &Config, clients: &HttpClients) -> AppResult<Arc<dyn SmsSender>> {
+    match config.sms_provider.as_str() {
+        "log" => Ok(Arc::new(LogSmsSender)),
+        "http" => {
+            let missing = |name: &str| AppError::Internal(format!("{} is required for the http SMS provider", name));
+            Ok(Arc::new(HttpSmsSender::new(
+                config.sms_api_url.clone().ok_or_else(|| missing("SMS_API_URL"))?,
+                config.sms_api_key.clone().ok_or_else(|| missing("SMS_API_KEY"))?,
+                config.sms_from.clone(),
+            )
+            .with_http_client(clients.get(SMS_CLIENT))))
+        }
+        other => Err(AppError::Internal(format!("Unknown SMS provider '{}'", other))),
+    }
+}
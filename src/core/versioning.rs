@@ -0,0 +1,254 @@
+use axum::{
+    extract::FromRequestParts,
+    http::request::Parts,
+    Router,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use sqlx::FromRow;
+use std::convert::Infallible;
+use std::str::FromStr;
+use utoipa::ToSchema;
+use crate::core::config::Config;
+use crate::core::cache::ProjectCache;
+use crate::core::error::AppError;
+
+/// Versions of the API, each served under `/api/{version}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "api_version", rename_all = "lowercase")]
+pub enum ApiVersion {
+    V1,
+    /// Enums in payment bodies are snake_case, as everywhere else
+    V2,
+}
+
+impl ApiVersion {
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+        }
+    }
+
+    /// The version a path is under and the rest of the path after it,
+    /// e.g. `v2` and `/payments` for `/api/v2/payments`
+    pub fn from_path(path: &str) -> Option<(ApiVersion, &str)> {
+        let rest = path.strip_prefix("/api/")?;
+        let (version, rest) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+        Some((version.parse().ok()?, rest))
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ApiVersion::ALL
+            .into_iter()
+            .find(|version| version.as_str() == value)
+            .ok_or_else(|| AppError::Validation(format!("Unknown API version '{}'", value)))
+    }
+}
+
+/// The version a request was made under; requests outside the versioned
+/// API count as v1
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ApiVersion {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<ApiVersion>().copied().unwrap_or(ApiVersion::V1))
+    }
+}
+
+/// A path below its API version, e.g. `/payments/:id` for
+/// `/api/v1/payments/:id`; `None` outside the versioned API
+pub fn unversioned(path: &str) -> Option<&str> {
+    ApiVersion::from_path(path).map(|(_, rest)| rest)
+}
+
+/// Serve the API's routes under every version
+pub fn nest_versions<S: Clone + Send + Sync + 'static>(api: Router<S>) -> Router<S> {
+    ApiVersion::ALL.into_iter().fold(Router::new(), |router, version| {
+        router.nest(&format!("/api/{}", version.as_str()), api.clone())
+    })
+}
+
+/// A response body whose shape differs between API versions. Bodies are
+/// written as v1 returns them and mapped to the shape of later versions.
+pub trait VersionedBody: Serialize {
+    fn for_version(&self, version: ApiVersion) -> serde_json::Value;
+}
+
+impl<T: VersionedBody> VersionedBody for Vec<T> {
+    fn for_version(&self, version: ApiVersion) -> serde_json::Value {
+        serde_json::Value::Array(self.iter().map(|item| item.for_version(version)).collect())
+    }
+}
+
+/// A body serialized in the shape of the request's API version
+pub struct Versioned<T>(pub ApiVersion, pub T);
+
+impl<T: VersionedBody> Serialize for Versioned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.1.for_version(self.0).serialize(serializer)
+    }
+}
+
+/// A version that is on its way out: served with `Deprecation` headers from
+/// `deprecated_on`, and refused from `sunset_on`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionDeprecation {
+    pub version: ApiVersion,
+    pub deprecated_on: NaiveDate,
+    pub sunset_on: Option<NaiveDate>,
+}
+
+/// What a project has chosen about the versions it calls
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct ProjectVersionRules {
+    /// Older versions are refused for the project
+    pub min_api_version: ApiVersion,
+    /// When the project stops being served deprecated versions, instead of
+    /// their configured sunset
+    pub api_sunset_on: Option<NaiveDate>,
+}
+
+/// Whether a request may be served under its version
+#[derive(Debug, Clone, PartialEq)]
+pub enum VersionStatus {
+    Current,
+    /// Served, with the dates for the `Deprecation` and `Sunset` headers
+    Deprecated {
+        deprecated_on: NaiveDate,
+        sunset_on: Option<NaiveDate>,
+    },
+    /// Refused, for the given reason
+    Retired(String),
+}
+
+/// Deprecation dates of each version and where to read about them
+#[derive(Debug, Clone, Default)]
+pub struct VersionPolicy {
+    deprecations: Vec<VersionDeprecation>,
+    info_url: Option<String>,
+}
+
+impl VersionPolicy {
+    pub fn new(deprecations: Vec<VersionDeprecation>, info_url: Option<String>) -> Self {
+        Self { deprecations, info_url }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.api_version_deprecations.clone(),
+            config.api_deprecation_info_url.clone(),
+        )
+    }
+
+    /// Page describing the deprecation, for the `Link` header
+    pub fn info_url(&self) -> Option<&str> {
+        self.info_url.as_deref()
+    }
+
+    /// Whether `version` is served on `today` to a project with `rules`
+    pub fn status(&self, version: ApiVersion, rules: Option<&ProjectVersionRules>, today: NaiveDate) -> VersionStatus {
+        if let Some(rules) = rules.filter(|rules| version < rules.min_api_version) {
+            return VersionStatus::Retired(format!(
+                "This project requires API {} or later",
+                rules.min_api_version.as_str()
+            ));
+        }
+        let Some(deprecation) = self.deprecations.iter().find(|deprecation| deprecation.version == version) else {
+            return VersionStatus::Current;
+        };
+
+        let sunset_on = rules.and_then(|rules| rules.api_sunset_on).or(deprecation.sunset_on);
+        if sunset_on.is_some_and(|sunset_on| sunset_on <= today) {
+            return VersionStatus::Retired(format!("API {} is no longer served", version.as_str()));
+        }
+        if deprecation.deprecated_on <= today {
+            return VersionStatus::Deprecated {
+                deprecated_on: deprecation.deprecated_on,
+                sunset_on,
+            };
+        }
+        VersionStatus::Current
+    }
+}
+
+/// `Deprecation` header value: the deprecation time as a structured field
+/// date (RFC 9745)
+pub fn deprecation_header(deprecated_on: NaiveDate) -> String {
+    format!("@{}", start_of(deprecated_on).timestamp())
+}
+
+/// `Sunset` header value: the sunset time as an HTTP date (RFC 8594)
+pub fn sunset_header(sunset_on: NaiveDate) -> String {
+    start_of(sunset_on).format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+fn start_of(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Per-project version rules, cached for the version check
+pub type ProjectVersionRulesCache = ProjectCache<ProjectVersionRules>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn paths_split_into_version_and_rest() {
+        assert_eq!(ApiVersion::from_path("/api/v2/payments/1"), Some((ApiVersion::V2, "/payments/1")));
+        assert_eq!(ApiVersion::from_path("/api/v1"), Some((ApiVersion::V1, "")));
+        assert_eq!(ApiVersion::from_path("/api/v9/payments"), None);
+        assert_eq!(unversioned("/graphql"), None);
+    }
+
+    #[test]
+    fn deprecated_versions_are_served_until_their_sunset() {
+        let policy = VersionPolicy::new(
+            vec![VersionDeprecation {
+                version: ApiVersion::V1,
+                deprecated_on: date("2027-01-01"),
+                sunset_on: Some(date("2027-07-01")),
+            }],
+            None,
+        );
+
+        assert_eq!(policy.status(ApiVersion::V1, None, date("2026-12-31")), VersionStatus::Current);
+        assert_eq!(
+            policy.status(ApiVersion::V1, None, date("2027-03-01")),
+            VersionStatus::Deprecated { deprecated_on: date("2027-01-01"), sunset_on: Some(date("2027-07-01")) }
+        );
+        assert!(matches!(policy.status(ApiVersion::V1, None, date("2027-07-01")), VersionStatus::Retired(_)));
+        assert_eq!(policy.status(ApiVersion::V2, None, date("2027-07-01")), VersionStatus::Current);
+
+        // A project may be given longer, or opt out of old versions early
+        let extended = ProjectVersionRules { min_api_version: ApiVersion::V1, api_sunset_on: Some(date("2027-10-01")) };
+        assert!(matches!(
+            policy.status(ApiVersion::V1, Some(&extended), date("2027-08-01")),
+            VersionStatus::Deprecated { .. }
+        ));
+        let migrated = ProjectVersionRules { min_api_version: ApiVersion::V2, api_sunset_on: None };
+        assert!(matches!(policy.status(ApiVersion::V1, Some(&migrated), date("2026-01-01")), VersionStatus::Retired(_)));
+    }
+
+    #[test]
+    fn headers_carry_the_dates() {
+        assert_eq!(deprecation_header(date("2027-01-01")), "@1798761600");
+        assert_eq!(sunset_header(date("2027-07-01")), "Thu, 01 Jul 2027 00:00:00 GMT");
+    }
+}
@@ -0,0 +1,89 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::identity::repository::IdentityRepository;
+use super::model::{EraseUserDataRequest, UserDataErasure};
+use super::repository::DataErasureRepository;
+use super::service::DataErasureService;
+
+fn data_erasure_service(state: &AppState) -> DataErasureService {
+    DataErasureService::new(
+        DataErasureRepository::new(state.postgres.clone()),
+        state.user_cipher.key_store().clone(),
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Get whether a user's personal data has been erased
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/users/{id}/erasure",
+    tag = "data-erasure",
+    params(("id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Erasure state", body = UserDataErasure),
+        (status = 403, description = "Caller lacks the user deletion permission"),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_erasure(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<UserDataErasure>>> {
+    state
+        .authorize(claims.developer_id, permissions::delete_users(), ip, format!("user:{}", id))
+        .await?;
+
+    let erasure = data_erasure_service(&state).get(id).await?;
+    Ok(Json(ApiResponse::success("Erasure state retrieved successfully", erasure)))
+}
+
+/// Erase a user's personal data by shredding their data key
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/{id}/erasure",
+    tag = "data-erasure",
+    params(("id" = Uuid, Path, description = "User ID")),
+    request_body = EraseUserDataRequest,
+    responses(
+        (status = 200, description = "Personal data erased", body = UserDataErasure),
+        (status = 403, description = "Caller lacks the user deletion permission"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "Personal data already erased")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn erase_user_data(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<EraseUserDataRequest>,
+) -> AppResult<Json<ApiResponse<UserDataErasure>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    state
+        .authorize(claims.developer_id, permissions::delete_users(), ip, format!("user:{}", id))
+        .await?;
+
+    let erasure = data_erasure_service(&state)
+        .erase(id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Personal data erased successfully", erasure)))
+}
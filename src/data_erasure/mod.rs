@@ -0,0 +1,14 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::get, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/users/:id/erasure",
+        get(controller::get_erasure).post(controller::erase_user_data),
+    )
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::core::crypto::UserKeyRecord;
+
+/// Erase a user's personal data by shredding their data key
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EraseUserDataRequest {
+    /// Why the data is erased, e.g. the reference of the user's request
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
+
+/// Whether a user's personal data has been erased
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserDataErasure {
+    pub user_id: Uuid,
+    pub erased: bool,
+    /// When the user's data key was shredded
+    pub erased_at: Option<DateTime<Utc>>,
+}
+
+impl UserDataErasure {
+    pub fn new(user_id: Uuid, record: Option<UserKeyRecord>) -> Self {
+        let erased_at = record.and_then(|record| record.shredded_at);
+        Self {
+            user_id,
+            erased: erased_at.is_some(),
+            erased_at,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use sqlx::PgPool;
+use crate::core::error::AppResult;
+use crate::shared::types::UserId;
+
+pub struct DataErasureRepository {
+    pool: PgPool,
+}
+
+impl DataErasureRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn user_exists(&self, user_id: UserId) -> AppResult<bool> {
+        let exists = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM users WHERE id = $1)")
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(exists)
+    }
+}
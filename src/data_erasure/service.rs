@@ -0,0 +1,74 @@
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::crypto::KeyStore;
+use crate::core::error::{AppError, AppResult};
+use crate::identity::repository::IdentityRepository;
+use crate::shared::types::UserId;
+use super::model::{EraseUserDataRequest, UserDataErasure};
+use super::repository::DataErasureRepository;
+
+pub struct DataErasureService {
+    repository: DataErasureRepository,
+    key_store: KeyStore,
+    identity_repository: IdentityRepository,
+    audit_logger: AuditLogger,
+}
+
+impl DataErasureService {
+    pub fn new(
+        repository: DataErasureRepository,
+        key_store: KeyStore,
+        identity_repository: IdentityRepository,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            key_store,
+            identity_repository,
+            audit_logger,
+        }
+    }
+
+    pub async fn get(&self, user_id: UserId) -> AppResult<UserDataErasure> {
+        self.ensure_user(user_id).await?;
+        let record = self.key_store.find_user_key_record(user_id).await?;
+        Ok(UserDataErasure::new(user_id, record))
+    }
+
+    /// Erase the user's personal data by shredding their data key. Values
+    /// written before per-user keys are cleared, as shredding cannot reach
+    /// them. Records the user's transactions depend on are kept.
+    pub async fn erase(
+        &self,
+        user_id: UserId,
+        request: EraseUserDataRequest,
+        actor_id: Uuid,
+    ) -> AppResult<UserDataErasure> {
+        self.ensure_user(user_id).await?;
+        let record = self
+            .key_store
+            .shred_user_key(user_id)
+            .await?
+            .ok_or_else(|| AppError::Conflict("The user's personal data has already been erased".to_string()))?;
+        let cleared = self.identity_repository.clear_unshreddable(user_id).await?;
+
+        let event = AuditEvent::new(AuditEventType::DataDeleted)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("user:{}", user_id))
+            .action("crypto_shred".to_string())
+            .metadata("reason".to_string(), serde_json::json!(request.reason))
+            .metadata("cleared_unshreddable_values".to_string(), serde_json::json!(cleared))
+            .compliance_tag("GDPR".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(UserDataErasure::new(user_id, Some(record)))
+    }
+
+    async fn ensure_user(&self, user_id: UserId) -> AppResult<()> {
+        if !self.repository.user_exists(user_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::{PaginatedResponse, PaginationParams};
+use super::model::{DeveloperFilter, ManagedDeveloperResponse, SuspendDeveloperRequest};
+use super::repository::DeveloperRepository;
+use super::service::DeveloperAdminService;
+
+fn developer_admin_service(state: &AppState) -> DeveloperAdminService {
+    DeveloperAdminService::new(
+        DeveloperRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// List developers with optional search, company and status filters
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/developers",
+    tag = "developers",
+    params(DeveloperFilter, PaginationParams),
+    responses(
+        (status = 200, description = "Page of developers", body = PaginatedDevelopers),
+        (status = 403, description = "Caller lacks the developer management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_developers(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(filter): Query<DeveloperFilter>,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<PaginatedResponse<ManagedDeveloperResponse>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_developers(), ip, "developers".to_string())
+        .await?;
+
+    let developers = developer_admin_service(&state)
+        .list_developers(filter, pagination.page, pagination.limit)
+        .await?;
+
+    Ok(Json(ApiResponse::success("Developers retrieved successfully", developers)))
+}
+
+/// Get a developer by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/developers/{id}",
+    tag = "developers",
+    params(("id" = Uuid, Path, description = "Developer ID")),
+    responses(
+        (status = 200, description = "Developer", body = ManagedDeveloperResponse),
+        (status = 403, description = "Caller lacks the developer management permission"),
+        (status = 404, description = "Developer not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_developer(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<ManagedDeveloperResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_developers(), ip, format!("developer:{}", id))
+        .await?;
+
+    let developer = developer_admin_service(&state).get_developer(id).await?;
+    Ok(Json(ApiResponse::success("Developer retrieved successfully", developer)))
+}
+
+/// Suspend a developer
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/developers/{id}/suspend",
+    tag = "developers",
+    params(("id" = Uuid, Path, description = "Developer ID")),
+    request_body = SuspendDeveloperRequest,
+    responses(
+        (status = 200, description = "Developer suspended", body = ManagedDeveloperResponse),
+        (status = 403, description = "Caller lacks the developer management permission"),
+        (status = 404, description = "Developer not found"),
+        (status = 409, description = "Developer is already suspended")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn suspend_developer(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<SuspendDeveloperRequest>,
+) -> AppResult<Json<ApiResponse<ManagedDeveloperResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_developers(), ip, format!("developer:{}", id))
+        .await?;
+
+    let developer = developer_admin_service(&state)
+        .suspend(id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Developer suspended successfully", developer)))
+}
+
+/// Reinstate a suspended developer
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/developers/{id}/reinstate",
+    tag = "developers",
+    params(("id" = Uuid, Path, description = "Developer ID")),
+    responses(
+        (status = 200, description = "Developer reinstated", body = ManagedDeveloperResponse),
+        (status = 403, description = "Caller lacks the developer management permission"),
+        (status = 404, description = "Developer not found"),
+        (status = 409, description = "Developer is not suspended")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn reinstate_developer(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<ManagedDeveloperResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_developers(), ip, format!("developer:{}", id))
+        .await?;
+
+    let developer = developer_admin_service(&state)
+        .reinstate(id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Developer reinstated successfully", developer)))
+}
+
+/// Delete a developer (super admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/developers/{id}",
+    tag = "developers",
+    params(("id" = Uuid, Path, description = "Developer ID")),
+    responses(
+        (status = 200, description = "Developer deleted", body = ManagedDeveloperResponse),
+        (status = 403, description = "Caller lacks the developer deletion permission"),
+        (status = 404, description = "Developer not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_developer(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<ManagedDeveloperResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::delete_developers(), ip, format!("developer:{}", id))
+        .await?;
+
+    let developer = developer_admin_service(&state)
+        .delete(id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Developer deleted successfully", developer)))
+}
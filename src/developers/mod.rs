@@ -0,0 +1,16 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{delete, get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/developers", get(controller::list_developers))
+        .route("/developers/:id", get(controller::get_developer))
+        .route("/developers/:id", delete(controller::delete_developer))
+        .route("/developers/:id/suspend", post(controller::suspend_developer))
+        .route("/developers/:id/reinstate", post(controller::reinstate_developer))
+}
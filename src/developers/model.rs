@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Administrative status of a developer account
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DeveloperStatus {
+    Active,
+    Suspended,
+    Deleted,
+}
+
+impl DeveloperStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeveloperStatus::Active => "active",
+            DeveloperStatus::Suspended => "suspended",
+            DeveloperStatus::Deleted => "deleted",
+        }
+    }
+}
+
+/// Developer account as seen by platform administrators
+#[derive(Debug, Clone, FromRow)]
+pub struct ManagedDeveloper {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub company: Option<String>,
+    pub title: Option<String>,
+    pub suspended_at: Option<DateTime<Utc>>,
+    pub suspension_reason: Option<String>,
+    pub suspended_by: Option<Uuid>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub project_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ManagedDeveloper {
+    pub fn status(&self) -> DeveloperStatus {
+        if self.deleted_at.is_some() {
+            DeveloperStatus::Deleted
+        } else if self.suspended_at.is_some() {
+            DeveloperStatus::Suspended
+        } else {
+            DeveloperStatus::Active
+        }
+    }
+}
+
+/// Filters for listing developers
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeveloperFilter {
+    /// Case-insensitive match against name or email
+    pub search: Option<String>,
+    pub company: Option<String>,
+    /// Defaults to all non-deleted developers
+    pub status: Option<DeveloperStatus>,
+}
+
+/// Suspend developer request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SuspendDeveloperRequest {
+    #[validate(length(min = 1, max = 1000))]
+    pub reason: String,
+}
+
+/// Developer response for administrators
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ManagedDeveloperResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub company: Option<String>,
+    pub title: Option<String>,
+    pub status: DeveloperStatus,
+    pub suspended_at: Option<DateTime<Utc>>,
+    pub suspension_reason: Option<String>,
+    pub suspended_by: Option<Uuid>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub project_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ManagedDeveloper> for ManagedDeveloperResponse {
+    fn from(developer: ManagedDeveloper) -> Self {
+        Self {
+            status: developer.status(),
+            id: developer.id,
+            name: developer.name,
+            email: developer.email,
+            company: developer.company,
+            title: developer.title,
+            suspended_at: developer.suspended_at,
+            suspension_reason: developer.suspension_reason,
+            suspended_by: developer.suspended_by,
+            deleted_at: developer.deleted_at,
+            project_count: developer.project_count,
+            created_at: developer.created_at,
+            updated_at: developer.updated_at,
+        }
+    }
+}
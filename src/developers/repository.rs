@@ -0,0 +1,140 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use super::model::{DeveloperFilter, ManagedDeveloper};
+
+const DEVELOPER_COLUMNS: &str = "d.id, d.name, d.email, d.company, d.title, d.suspended_at, d.suspension_reason, \
+     d.suspended_by, d.deleted_at, \
+     (SELECT COUNT(*) FROM projects p WHERE p.developer_id = d.id) AS project_count, \
+     d.created_at, d.updated_at";
+
+const DEVELOPER_FILTER: &str = "($1::TEXT IS NULL OR d.name ILIKE '%' || $1 || '%' OR d.email ILIKE '%' || $1 || '%')
+     AND ($2::TEXT IS NULL OR d.company ILIKE '%' || $2 || '%')
+     AND CASE $3::TEXT
+         WHEN 'active' THEN d.suspended_at IS NULL AND d.deleted_at IS NULL
+         WHEN 'suspended' THEN d.suspended_at IS NOT NULL AND d.deleted_at IS NULL
+         WHEN 'deleted' THEN d.deleted_at IS NOT NULL
+         ELSE d.deleted_at IS NULL
+     END";
+
+pub struct DeveloperRepository {
+    pool: PgPool,
+}
+
+impl DeveloperRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// List developers matching the filter, newest first
+    pub async fn list(&self, filter: &DeveloperFilter, page: u32, limit: u32) -> AppResult<Vec<ManagedDeveloper>> {
+        let offset = (page.saturating_sub(1) * limit) as i64;
+
+        let developers = sqlx::query_as::<_, ManagedDeveloper>(&format!(
+            "SELECT {DEVELOPER_COLUMNS} FROM developers d
+             WHERE {DEVELOPER_FILTER}
+             ORDER BY d.created_at DESC
+             LIMIT $4 OFFSET $5"
+        ))
+        .bind(&filter.search)
+        .bind(&filter.company)
+        .bind(filter.status.map(|status| status.as_str()))
+        .bind(limit as i64)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(developers)
+    }
+
+    /// Count developers matching the filter
+    pub async fn count(&self, filter: &DeveloperFilter) -> AppResult<i64> {
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM developers d WHERE {DEVELOPER_FILTER}"
+        ))
+        .bind(&filter.search)
+        .bind(&filter.company)
+        .bind(filter.status.map(|status| status.as_str()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(total)
+    }
+
+    /// Find a developer by ID, including deleted developers
+    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<ManagedDeveloper>> {
+        let developer = sqlx::query_as::<_, ManagedDeveloper>(&format!(
+            "SELECT {DEVELOPER_COLUMNS} FROM developers d WHERE d.id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(developer)
+    }
+
+    /// Suspend a developer and revoke their outstanding access tokens
+    pub async fn suspend(&self, id: Uuid, reason: &str, actor_id: Uuid) -> AppResult<u64> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        sqlx::query(
+            "UPDATE developers
+             SET suspended_at = NOW(), suspension_reason = $1, suspended_by = $2, updated_at = NOW()
+             WHERE id = $3",
+        )
+        .bind(reason)
+        .bind(actor_id)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        let revoked = sqlx::query("DELETE FROM oauth_tokens WHERE developer_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        tx.commit().await?;
+        Ok(revoked)
+    }
+
+    /// Lift a developer's suspension
+    pub async fn reinstate(&self, id: Uuid) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE developers
+             SET suspended_at = NULL, suspension_reason = NULL, suspended_by = NULL, updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Soft-delete a developer, deactivating their projects and revoking their tokens.
+    /// The row is kept so audit trails and foreign keys remain intact.
+    pub async fn soft_delete(&self, id: Uuid) -> AppResult<u64> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        sqlx::query("UPDATE developers SET deleted_at = NOW(), updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("UPDATE projects SET is_active = false, updated_at = NOW() WHERE developer_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        let revoked = sqlx::query("DELETE FROM oauth_tokens WHERE developer_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        tx.commit().await?;
+        Ok(revoked)
+    }
+}
@@ -0,0 +1,146 @@
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::PaginatedResponse;
+use super::model::{
+    DeveloperFilter, DeveloperStatus, ManagedDeveloper, ManagedDeveloperResponse, SuspendDeveloperRequest,
+};
+use super::repository::DeveloperRepository;
+
+pub struct DeveloperAdminService {
+    repository: DeveloperRepository,
+    audit_logger: AuditLogger,
+}
+
+impl DeveloperAdminService {
+    pub fn new(repository: DeveloperRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            audit_logger,
+        }
+    }
+
+    /// List developers matching the filter
+    pub async fn list_developers(
+        &self,
+        filter: DeveloperFilter,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<PaginatedResponse<ManagedDeveloperResponse>> {
+        let limit = limit.clamp(1, 100);
+        let page = page.max(1);
+
+        let developers = self.repository.list(&filter, page, limit).await?;
+        let total = self.repository.count(&filter).await?.max(0) as u64;
+
+        Ok(PaginatedResponse {
+            data: developers.into_iter().map(ManagedDeveloperResponse::from).collect(),
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit as u64) as u32,
+        })
+    }
+
+    /// Get a developer by ID
+    pub async fn get_developer(&self, id: Uuid) -> AppResult<ManagedDeveloperResponse> {
+        let developer = self.find_developer(id).await?;
+        Ok(ManagedDeveloperResponse::from(developer))
+    }
+
+    /// Suspend a developer, blocking token issuance and revoking existing tokens
+    pub async fn suspend(
+        &self,
+        id: Uuid,
+        request: SuspendDeveloperRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ManagedDeveloperResponse> {
+        if id == actor_id {
+            return Err(AppError::BadRequest("You cannot suspend your own account".to_string()));
+        }
+
+        let developer = self.find_developer(id).await?;
+        match developer.status() {
+            DeveloperStatus::Suspended => {
+                return Err(AppError::Conflict("Developer is already suspended".to_string()))
+            }
+            DeveloperStatus::Deleted => return Err(AppError::NotFound("Developer not found".to_string())),
+            DeveloperStatus::Active => {}
+        }
+
+        let revoked_tokens = self.repository.suspend(id, &request.reason, actor_id).await?;
+
+        let event = AuditEvent::new(AuditEventType::DeveloperUpdated)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("developer:{}", id))
+            .action("suspend".to_string())
+            .metadata("reason".to_string(), serde_json::json!(request.reason))
+            .metadata("revoked_tokens".to_string(), serde_json::json!(revoked_tokens))
+            .compliance_tag("DEVELOPER_ADMIN".to_string());
+        self.audit_logger.log(event).await;
+
+        self.get_developer(id).await
+    }
+
+    /// Lift a developer's suspension
+    pub async fn reinstate(&self, id: Uuid, actor_id: Uuid) -> AppResult<ManagedDeveloperResponse> {
+        let developer = self.find_developer(id).await?;
+        match developer.status() {
+            DeveloperStatus::Active => {
+                return Err(AppError::Conflict("Developer is not suspended".to_string()))
+            }
+            DeveloperStatus::Deleted => return Err(AppError::NotFound("Developer not found".to_string())),
+            DeveloperStatus::Suspended => {}
+        }
+
+        self.repository.reinstate(id).await?;
+
+        let event = AuditEvent::new(AuditEventType::DeveloperUpdated)
+            .user_id(actor_id)
+            .resource(format!("developer:{}", id))
+            .action("reinstate".to_string())
+            .metadata(
+                "suspension_reason".to_string(),
+                serde_json::json!(developer.suspension_reason),
+            )
+            .compliance_tag("DEVELOPER_ADMIN".to_string());
+        self.audit_logger.log(event).await;
+
+        self.get_developer(id).await
+    }
+
+    /// Delete a developer, deactivating their projects and revoking their tokens
+    pub async fn delete(&self, id: Uuid, actor_id: Uuid) -> AppResult<ManagedDeveloperResponse> {
+        if id == actor_id {
+            return Err(AppError::BadRequest("You cannot delete your own account".to_string()));
+        }
+
+        let developer = self.find_developer(id).await?;
+        if developer.status() == DeveloperStatus::Deleted {
+            return Err(AppError::NotFound("Developer not found".to_string()));
+        }
+
+        let revoked_tokens = self.repository.soft_delete(id).await?;
+
+        let event = AuditEvent::new(AuditEventType::DeveloperUpdated)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("developer:{}", id))
+            .action("delete".to_string())
+            .metadata("email".to_string(), serde_json::json!(developer.email))
+            .metadata("project_count".to_string(), serde_json::json!(developer.project_count))
+            .metadata("revoked_tokens".to_string(), serde_json::json!(revoked_tokens))
+            .compliance_tag("DEVELOPER_ADMIN".to_string());
+        self.audit_logger.log(event).await;
+
+        self.get_developer(id).await
+    }
+
+    async fn find_developer(&self, id: Uuid) -> AppResult<ManagedDeveloper> {
+        self.repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Developer not found".to_string()))
+    }
+}
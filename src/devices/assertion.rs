@@ -0,0 +1,121 @@
+use axum::http::HeaderMap;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::core::crypto::hex;
+use crate::core::error::{AppError, AppResult};
+
+/// Device that signed the call
+pub const DEVICE_ID_HEADER: &str = "x-device-id";
+/// Unix time of the signature in milliseconds; must grow with every call
+pub const DEVICE_TIMESTAMP_HEADER: &str = "x-device-timestamp";
+/// Base64 Ed25519 signature of the signing input
+pub const DEVICE_SIGNATURE_HEADER: &str = "x-device-signature";
+
+/// Length of a raw Ed25519 public key
+const PUBLIC_KEY_LEN: usize = 32;
+
+/// A device's signature over one call, as sent in the device headers
+#[derive(Debug, Clone)]
+pub struct DeviceAssertion {
+    pub device_id: Uuid,
+    pub timestamp_ms: i64,
+    pub signature: Vec<u8>,
+}
+
+impl DeviceAssertion {
+    /// The assertion in `headers`, or `None` if the call is not signed
+    pub fn from_headers(headers: &HeaderMap) -> AppResult<Option<Self>> {
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+        let Some(device_id) = header(DEVICE_ID_HEADER) else {
+            return Ok(None);
+        };
+        let invalid = |what: &str| AppError::Authentication(format!("Invalid device assertion: {}", what));
+
+        let device_id = Uuid::parse_str(device_id).map_err(|_| invalid("malformed device id"))?;
+        let timestamp_ms = header(DEVICE_TIMESTAMP_HEADER)
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| invalid("missing or malformed timestamp"))?;
+        let signature = header(DEVICE_SIGNATURE_HEADER)
+            .and_then(|value| STANDARD.decode(value).ok())
+            .ok_or_else(|| invalid("missing or malformed signature"))?;
+
+        Ok(Some(Self {
+            device_id,
+            timestamp_ms,
+            signature,
+        }))
+    }
+}
+
+/// What a device signs: the method, path with query, timestamp and the hex
+/// SHA-256 of the body, one per line
+pub fn signing_input(method: &str, path_and_query: &str, timestamp_ms: i64, body: &[u8]) -> Vec<u8> {
+    format!(
+        "{}\n{}\n{}\n{}",
+        method.to_uppercase(),
+        path_and_query,
+        timestamp_ms,
+        hex(&Sha256::digest(body))
+    )
+    .into_bytes()
+}
+
+/// Check a registered public key is a base64 raw Ed25519 key
+pub fn decode_public_key(public_key: &str) -> AppResult<Vec<u8>> {
+    STANDARD
+        .decode(public_key.trim())
+        .ok()
+        .filter(|key| key.len() == PUBLIC_KEY_LEN)
+        .ok_or_else(|| AppError::Validation("public_key must be a base64 32-byte Ed25519 key".to_string()))
+}
+
+/// Whether `signature` is the key's signature of `message`
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(message, signature)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn key_pair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn signatures_cover_the_whole_call() {
+        let key = key_pair();
+        let public_key = decode_public_key(&STANDARD.encode(key.public_key().as_ref())).unwrap();
+        let message = signing_input("post", "/api/v1/transactions/transfer", 1_700_000_000_000, b"{\"amount\":100}");
+        let signature = key.sign(&message);
+
+        assert!(verify(&public_key, &message, signature.as_ref()));
+        let tampered = signing_input("post", "/api/v1/transactions/transfer", 1_700_000_000_000, b"{\"amount\":900}");
+        assert!(!verify(&public_key, &tampered, signature.as_ref()));
+        assert!(!verify(key_pair().public_key().as_ref(), &message, signature.as_ref()));
+    }
+
+    #[test]
+    fn reads_assertions_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(DeviceAssertion::from_headers(&headers).unwrap().is_none());
+
+        let device_id = Uuid::new_v4();
+        headers.insert(DEVICE_ID_HEADER, device_id.to_string().parse().unwrap());
+        assert!(DeviceAssertion::from_headers(&headers).is_err());
+
+        headers.insert(DEVICE_TIMESTAMP_HEADER, "1700000000000".parse().unwrap());
+        headers.insert(DEVICE_SIGNATURE_HEADER, STANDARD.encode([7u8; 64]).parse().unwrap());
+        let assertion = DeviceAssertion::from_headers(&headers).unwrap().unwrap();
+        assert_eq!(assertion.device_id, device_id);
+        assert_eq!(assertion.timestamp_ms, 1_700_000_000_000);
+        assert!(decode_public_key("c2hvcnQ=").is_err());
+    }
+}
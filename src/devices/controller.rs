@@ -0,0 +1,111 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::auth::model::JwtClaims;
+use crate::auth::step_up::{SensitiveAction, StepUpPolicy, StepUpSettings};
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::UserId;
+use super::model::{DeviceResponse, DeviceSettings, RegisterDeviceRequest};
+use super::repository::DeviceRepository;
+use super::service::DeviceService;
+
+fn device_service(state: &AppState) -> DeviceService {
+    DeviceService::new(
+        DeviceRepository::new(state.postgres.clone()),
+        DeviceSettings::from_config(&state.config),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Devices belong to an end user; project tokens have none
+fn end_user(claims: &JwtClaims) -> AppResult<UserId> {
+    claims
+        .user_id
+        .ok_or_else(|| AppError::Authorization("This endpoint requires a user access token".to_string()))
+}
+
+/// Bind a device to the user by the public key it generated. Needs a
+/// recent one-time code login, as a bound device can sign payments.
+#[utoipa::path(
+    post,
+    path = "/api/v1/user-data/devices",
+    tag = "devices",
+    request_body = RegisterDeviceRequest,
+    responses(
+        (status = 200, description = "Device bound", body = DeviceResponse),
+        (status = 400, description = "Invalid public key"),
+        (status = 401, description = "The user must log in again with a one-time code; see the WWW-Authenticate header"),
+        (status = 403, description = "Not a user access token"),
+        (status = 409, description = "Key already bound to a device")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn register_device(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<RegisterDeviceRequest>,
+) -> AppResult<Json<ApiResponse<DeviceResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let user_id = end_user(&claims)?;
+
+    StepUpPolicy::new(StepUpSettings::from_config(&state.config), state.audit_logger.clone())
+        .require(&claims, SensitiveAction::RegisterDevice)
+        .await?;
+
+    let device = device_service(&state).register(user_id, request).await?;
+    Ok(Json(ApiResponse::success("Device registered successfully", device)))
+}
+
+/// List the user's trusted devices
+#[utoipa::path(
+    get,
+    path = "/api/v1/user-data/devices",
+    tag = "devices",
+    responses(
+        (status = 200, description = "The user's bound devices, most recent first", body = [DeviceResponse]),
+        (status = 403, description = "Not a user access token")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_devices(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+) -> AppResult<Json<ApiResponse<Vec<DeviceResponse>>>> {
+    let user_id = end_user(&claims)?;
+    let devices = device_service(&state).list(user_id).await?;
+    Ok(Json(ApiResponse::success("Devices retrieved successfully", devices)))
+}
+
+/// Revoke one of the user's devices, e.g. when it is lost
+#[utoipa::path(
+    delete,
+    path = "/api/v1/user-data/devices/{id}",
+    tag = "devices",
+    params(("id" = Uuid, Path, description = "Device ID")),
+    responses(
+        (status = 200, description = "Device revoked"),
+        (status = 403, description = "Not a user access token"),
+        (status = 404, description = "Device not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn revoke_device(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    let user_id = end_user(&claims)?;
+    device_service(&state).revoke(user_id, id).await?;
+    Ok(Json(ApiResponse::success("Device revoked successfully", ())))
+}
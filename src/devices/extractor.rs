@@ -0,0 +1,65 @@
+use axum::{
+    async_trait,
+    body::{Body, Bytes},
+    extract::{FromRef, FromRequest, FromRequestParts, OriginalUri, Request},
+};
+use serde::de::DeserializeOwned;
+use crate::auth::middleware::JwtToken;
+use crate::core::{error::AppError, extractors::ApiJson, AppState};
+use super::assertion::DeviceAssertion;
+use super::model::{DeviceRisk, DeviceSettings};
+use super::repository::DeviceRepository;
+use super::service::{DeviceService, SignedCall};
+
+/// JSON body of a sensitive call, with the device assertion on it verified.
+/// End users sign such calls with a registered device; see `assertion`.
+pub struct DeviceSigned<T> {
+    /// `None` for project tokens, which act for no device
+    pub risk: Option<DeviceRisk>,
+    pub body: T,
+}
+
+#[async_trait]
+impl<T, S> FromRequest<S> for DeviceSigned<T>
+where
+    T: DeserializeOwned + Send,
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let (mut parts, body) = req.into_parts();
+        let JwtToken(claims) = JwtToken::from_request_parts(&mut parts, state).await?;
+        let assertion = DeviceAssertion::from_headers(&parts.headers)?;
+        // Routers are nested, so the path the device signed is the original one
+        let OriginalUri(uri) = OriginalUri::from_request_parts(&mut parts, state)
+            .await
+            .map_err(|_| AppError::Internal("Request URI unavailable".to_string()))?;
+        let method = parts.method.clone();
+
+        let bytes = Bytes::from_request(Request::from_parts(parts.clone(), body), state)
+            .await
+            .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?;
+        let ApiJson(parsed) = ApiJson::<T>::from_request(Request::from_parts(parts, Body::from(bytes.clone())), state).await?;
+
+        let app_state = AppState::from_ref(state);
+        let risk = DeviceService::new(
+            DeviceRepository::new(app_state.postgres.clone()),
+            DeviceSettings::from_config(&app_state.config),
+            app_state.audit_logger.clone(),
+        )
+        .assess(
+            &claims,
+            assertion,
+            SignedCall {
+                method: method.as_str(),
+                path_and_query: uri.path_and_query().map_or(uri.path(), |path| path.as_str()),
+                body: &bytes,
+            },
+        )
+        .await?;
+
+        Ok(DeviceSigned { risk, body: parsed })
+    }
+}
@@ -0,0 +1,15 @@
+pub mod assertion;
+pub mod controller;
+pub mod extractor;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{delete, get}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(controller::list_devices).post(controller::register_device))
+        .route("/:id", delete(controller::revoke_device))
+}
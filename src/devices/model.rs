@@ -0,0 +1,97 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::core::config::Config;
+use crate::shared::types::UserId;
+
+/// A device an end user has bound to their login
+#[derive(Debug, Clone, FromRow)]
+pub struct UserDevice {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub name: String,
+    /// Base64 raw Ed25519 public key
+    pub public_key: String,
+    pub last_assertion_ms: Option<i64>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Bind a device by the public half of a key pair it generated
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RegisterDeviceRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Base64 raw 32-byte Ed25519 public key
+    #[validate(length(min = 1, max = 64))]
+    pub public_key: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeviceResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub public_key: String,
+    /// Bound long enough ago to count as trusted
+    pub trusted: bool,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DeviceResponse {
+    pub fn new(device: UserDevice, settings: &DeviceSettings, now: DateTime<Utc>) -> Self {
+        Self {
+            trusted: settings.risk(&device, now) == DeviceRisk::Trusted,
+            id: device.id,
+            name: device.name,
+            public_key: device.public_key,
+            last_used_at: device.last_used_at,
+            created_at: device.created_at,
+        }
+    }
+}
+
+/// How far a call can be tied to a device the user has trusted for a while
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceRisk {
+    /// Signed by a device bound long enough ago
+    Trusted,
+    /// Signed by a recently bound device, as when an attacker binds their own
+    New,
+    /// Not signed by any device
+    Unbound,
+}
+
+/// Device binding policy
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceSettings {
+    /// Reject end users' sensitive calls that carry no device assertion
+    pub binding_required: bool,
+    /// How far an assertion's timestamp may be from the server's clock
+    pub max_skew: Duration,
+    /// How long a device must be bound before it is trusted
+    pub trust_after: Duration,
+}
+
+impl DeviceSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            binding_required: config.device_binding_required,
+            max_skew: Duration::seconds(config.device_assertion_max_skew_seconds),
+            trust_after: Duration::hours(config.device_trust_after_hours),
+        }
+    }
+
+    pub fn risk(&self, device: &UserDevice, now: DateTime<Utc>) -> DeviceRisk {
+        if now - device.created_at >= self.trust_after {
+            DeviceRisk::Trusted
+        } else {
+            DeviceRisk::New
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::shared::types::UserId;
+use super::model::UserDevice;
+
+const DEVICE_COLUMNS: &str =
+    "id, user_id, name, public_key, last_assertion_ms, last_used_at, revoked_at, created_at";
+
+#[derive(Clone)]
+pub struct DeviceRepository {
+    pool: PgPool,
+}
+
+impl DeviceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, user_id: UserId, name: &str, public_key: &str) -> AppResult<UserDevice> {
+        let device = sqlx::query_as::<_, UserDevice>(&format!(
+            "INSERT INTO user_devices (user_id, name, public_key) VALUES ($1, $2, $3) RETURNING {}",
+            DEVICE_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(name)
+        .bind(public_key)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(device)
+    }
+
+    /// Whether an active device already holds the key
+    pub async fn public_key_in_use(&self, public_key: &str) -> AppResult<bool> {
+        let in_use = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM user_devices WHERE public_key = $1 AND revoked_at IS NULL)",
+        )
+        .bind(public_key)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(in_use)
+    }
+
+    /// The user's active devices, most recently bound first
+    pub async fn find_active(&self, user_id: UserId) -> AppResult<Vec<UserDevice>> {
+        let devices = sqlx::query_as::<_, UserDevice>(&format!(
+            "SELECT {} FROM user_devices WHERE user_id = $1 AND revoked_at IS NULL ORDER BY created_at DESC",
+            DEVICE_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(devices)
+    }
+
+    /// One of the user's active devices
+    pub async fn find_active_for_user(&self, device_id: Uuid, user_id: UserId) -> AppResult<Option<UserDevice>> {
+        let device = sqlx::query_as::<_, UserDevice>(&format!(
+            "SELECT {} FROM user_devices WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+            DEVICE_COLUMNS
+        ))
+        .bind(device_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(device)
+    }
+
+    /// Revoke one of the user's active devices; `None` if there was none
+    pub async fn revoke(&self, device_id: Uuid, user_id: UserId) -> AppResult<Option<UserDevice>> {
+        let device = sqlx::query_as::<_, UserDevice>(&format!(
+            "UPDATE user_devices SET revoked_at = NOW()
+             WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+             RETURNING {}",
+            DEVICE_COLUMNS
+        ))
+        .bind(device_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(device)
+    }
+
+    /// Record an assertion as used. Fails (returns false) unless it is newer
+    /// than every assertion accepted from the device before.
+    pub async fn advance_assertion(&self, device_id: Uuid, timestamp_ms: i64) -> AppResult<bool> {
+        let result = sqlx::query(
+            "UPDATE user_devices SET last_assertion_ms = $2, last_used_at = NOW()
+             WHERE id = $1 AND revoked_at IS NULL
+               AND (last_assertion_ms IS NULL OR last_assertion_ms < $2)",
+        )
+        .bind(device_id)
+        .bind(timestamp_ms)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::Utc;
+use uuid::Uuid;
+use crate::auth::model::JwtClaims;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::UserId;
+use super::assertion::{self, DeviceAssertion};
+use super::model::{DeviceResponse, DeviceRisk, DeviceSettings, RegisterDeviceRequest};
+use super::repository::DeviceRepository;
+
+/// The signed call a device assertion is checked against
+pub struct SignedCall<'a> {
+    pub method: &'a str,
+    pub path_and_query: &'a str,
+    pub body: &'a [u8],
+}
+
+/// Registry of the devices end users have bound, and verification of the
+/// assertions they sign calls with
+pub struct DeviceService {
+    repository: DeviceRepository,
+    settings: DeviceSettings,
+    audit_logger: AuditLogger,
+}
+
+impl DeviceService {
+    pub fn new(repository: DeviceRepository, settings: DeviceSettings, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            settings,
+            audit_logger,
+        }
+    }
+
+    /// Bind a device to the user by its public key
+    pub async fn register(&self, user_id: UserId, request: RegisterDeviceRequest) -> AppResult<DeviceResponse> {
+        assertion::decode_public_key(&request.public_key)?;
+        let public_key = request.public_key.trim();
+        if self.repository.public_key_in_use(public_key).await? {
+            return Err(AppError::Conflict("This key is already bound to a device".to_string()));
+        }
+
+        let device = self.repository.create(user_id, request.name.trim(), public_key).await?;
+
+        let event = AuditEvent::new(AuditEventType::DeviceRegistered)
+            .user_id(user_id)
+            .resource(format!("device:{}", device.id))
+            .action("register_device".to_string())
+            .metadata("name".to_string(), serde_json::json!(device.name))
+            .compliance_tag("SECURITY".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(DeviceResponse::new(device, &self.settings, Utc::now()))
+    }
+
+    /// The user's active devices
+    pub async fn list(&self, user_id: UserId) -> AppResult<Vec<DeviceResponse>> {
+        let now = Utc::now();
+        let devices = self.repository.find_active(user_id).await?;
+        Ok(devices
+            .into_iter()
+            .map(|device| DeviceResponse::new(device, &self.settings, now))
+            .collect())
+    }
+
+    /// Unbind one of the user's devices; its assertions are refused from now on
+    pub async fn revoke(&self, user_id: UserId, device_id: Uuid) -> AppResult<()> {
+        let device = self
+            .repository
+            .revoke(device_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Device not found".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::DeviceRevoked)
+            .severity(AuditSeverity::Warning)
+            .user_id(user_id)
+            .resource(format!("device:{}", device.id))
+            .action("revoke_device".to_string())
+            .compliance_tag("SECURITY".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(())
+    }
+
+    /// Verify the device assertion on an end user's call and rate the
+    /// device it came from. Project tokens act for no device and get `None`;
+    /// unsigned calls are `Unbound` unless binding is required.
+    pub async fn assess(
+        &self,
+        claims: &JwtClaims,
+        assertion: Option<DeviceAssertion>,
+        call: SignedCall<'_>,
+    ) -> AppResult<Option<DeviceRisk>> {
+        let Some(user_id) = claims.user_id else {
+            return Ok(None);
+        };
+        let Some(assertion) = assertion else {
+            if self.settings.binding_required {
+                return Err(self.reject(user_id, None, "This call must be signed by a registered device").await);
+            }
+            return Ok(Some(DeviceRisk::Unbound));
+        };
+
+        let device_id = assertion.device_id;
+        let Some(device) = self.repository.find_active_for_user(device_id, user_id).await? else {
+            return Err(self.reject(user_id, Some(device_id), "Unknown or revoked device").await);
+        };
+
+        let now = Utc::now();
+        if (now.timestamp_millis() - assertion.timestamp_ms).abs() > self.settings.max_skew.num_milliseconds() {
+            return Err(self.reject(user_id, Some(device_id), "Device assertion has expired").await);
+        }
+
+        let public_key = assertion::decode_public_key(&device.public_key)?;
+        let message = assertion::signing_input(call.method, call.path_and_query, assertion.timestamp_ms, call.body);
+        if !assertion::verify(&public_key, &message, &assertion.signature) {
+            return Err(self.reject(user_id, Some(device_id), "Invalid device signature").await);
+        }
+
+        if !self.repository.advance_assertion(device_id, assertion.timestamp_ms).await? {
+            return Err(self.reject(user_id, Some(device_id), "Device assertion was already used").await);
+        }
+
+        Ok(Some(self.settings.risk(&device, now)))
+    }
+
+    async fn reject(&self, user_id: UserId, device_id: Option<Uuid>, reason: &str) -> AppError {
+        let mut event = AuditEvent::new(AuditEventType::DeviceAssertionRejected)
+            .severity(AuditSeverity::Warning)
+            .user_id(user_id)
+            .action("verify_device_assertion".to_string())
+            .metadata("reason".to_string(), serde_json::json!(reason))
+            .compliance_tag("SECURITY".to_string());
+        if let Some(device_id) = device_id {
+            event = event.resource(format!("device:{}", device_id));
+        }
+        self.audit_logger.log(event).await;
+
+        AppError::Authentication(reason.to_string())
+    }
+}
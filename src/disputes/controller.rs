@@ -0,0 +1,220 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::{PaginationParams, UserId};
+use super::model::{
+    DisputeEvidenceResponse, DisputeResponse, OpenDisputeRequest, UpdateDisputeStatusRequest,
+    UploadEvidenceRequest,
+};
+use super::repository::DisputeRepository;
+use super::service::DisputeService;
+
+/// Query parameters for listing disputes
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListDisputesQuery {
+    pub user_id: UserId,
+}
+
+fn dispute_service(state: &AppState) -> DisputeService {
+    DisputeService::new(
+        DisputeRepository::new(state.postgres.clone()),
+        state.storage.clone(),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Open a dispute against a transaction or payment
+#[utoipa::path(
+    post,
+    path = "/api/v1/disputes",
+    tag = "disputes",
+    request_body = OpenDisputeRequest,
+    responses(
+        (status = 201, description = "Dispute opened", body = DisputeResponse),
+        (status = 400, description = "The transaction or payment cannot be disputed"),
+        (status = 404, description = "Transaction or payment not found"),
+        (status = 409, description = "An open dispute already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn open_dispute(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<OpenDisputeRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<DisputeResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let dispute = dispute_service(&state)
+        .open_dispute(request, claims.tenant_id, claims.developer_id)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Dispute opened successfully", dispute)),
+    ))
+}
+
+/// List disputes for a user
+#[utoipa::path(
+    get,
+    path = "/api/v1/disputes",
+    tag = "disputes",
+    params(ListDisputesQuery, PaginationParams),
+    responses(
+        (status = 200, description = "Disputes for the user", body = [DisputeResponse])
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_disputes(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<ListDisputesQuery>,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<Vec<DisputeResponse>>>> {
+    let disputes = dispute_service(&state)
+        .get_user_disputes(query.user_id, claims.tenant_id, pagination.page, pagination.limit)
+        .await?;
+
+    Ok(Json(ApiResponse::success("Disputes retrieved successfully", disputes)))
+}
+
+/// Get dispute by ID
+#[utoipa::path(
+    get,
+    path = "/api/v1/disputes/{id}",
+    tag = "disputes",
+    params(("id" = Uuid, Path, description = "Dispute ID")),
+    responses(
+        (status = 200, description = "Dispute", body = DisputeResponse),
+        (status = 404, description = "Dispute not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_dispute_by_id(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<DisputeResponse>>> {
+    let dispute = dispute_service(&state).get_dispute(id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Dispute retrieved successfully", dispute)))
+}
+
+/// Upload evidence for a dispute
+#[utoipa::path(
+    post,
+    path = "/api/v1/disputes/{id}/evidence",
+    tag = "disputes",
+    params(("id" = Uuid, Path, description = "Dispute ID")),
+    request_body = UploadEvidenceRequest,
+    responses(
+        (status = 201, description = "Evidence uploaded", body = DisputeEvidenceResponse),
+        (status = 400, description = "Dispute is closed"),
+        (status = 404, description = "Dispute not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload_evidence(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<UploadEvidenceRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<DisputeEvidenceResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let evidence = dispute_service(&state)
+        .upload_evidence(id, claims.tenant_id, request, claims.developer_id)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Evidence uploaded successfully", evidence)),
+    ))
+}
+
+/// Download a piece of dispute evidence
+#[utoipa::path(
+    get,
+    path = "/api/v1/disputes/{id}/evidence/{evidence_id}",
+    tag = "disputes",
+    params(
+        ("id" = Uuid, Path, description = "Dispute ID"),
+        ("evidence_id" = Uuid, Path, description = "Evidence ID")
+    ),
+    responses(
+        (status = 200, description = "Evidence file with its original content type", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 404, description = "Dispute or evidence not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn download_evidence(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((id, evidence_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<Response> {
+    let (content_type, content) = dispute_service(&state)
+        .download_evidence(id, evidence_id, claims.tenant_id)
+        .await?;
+
+    Ok(([(header::CONTENT_TYPE, content_type)], content).into_response())
+}
+
+/// Progress a dispute's review status (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/disputes/{id}/status",
+    tag = "disputes",
+    params(("id" = Uuid, Path, description = "Dispute ID")),
+    request_body = UpdateDisputeStatusRequest,
+    responses(
+        (status = 200, description = "Dispute status updated", body = DisputeResponse),
+        (status = 400, description = "Status transition not allowed"),
+        (status = 403, description = "Caller lacks the dispute review permission"),
+        (status = 404, description = "Dispute not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_dispute_status(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<UpdateDisputeStatusRequest>,
+) -> AppResult<Json<ApiResponse<DisputeResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(
+            claims.developer_id,
+            permissions::review_disputes(),
+            ip,
+            format!("dispute:{}", id),
+        )
+        .await?;
+
+    let dispute = dispute_service(&state)
+        .update_status(id, request, claims.developer_id)
+        .await?;
+
+    Ok(Json(ApiResponse::success("Dispute status updated successfully", dispute)))
+}
@@ -0,0 +1,23 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{extract::DefaultBodyLimit, routing::{get, post}, Router};
+use crate::core::AppState;
+use crate::shared::constants::MAX_DISPUTE_EVIDENCE_SIZE;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(controller::open_dispute))
+        .route("/", get(controller::get_disputes))
+        .route("/:id", get(controller::get_dispute_by_id))
+        .route("/:id/status", post(controller::update_dispute_status))
+        .route(
+            "/:id/evidence",
+            // Base64 inflates uploads by roughly a third
+            post(controller::upload_evidence)
+                .layer(DefaultBodyLimit::max(MAX_DISPUTE_EVIDENCE_SIZE * 2)),
+        )
+        .route("/:id/evidence/:evidence_id", get(controller::download_evidence))
+}
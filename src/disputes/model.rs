@@ -0,0 +1,181 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::{AccountId, Amount, Currency, TenantId, TransactionId, UserId};
+
+/// Dispute status enum
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "dispute_status", rename_all = "snake_case")]
+pub enum DisputeStatus {
+    Open,
+    UnderReview,
+    Won,
+    Lost,
+}
+
+impl DisputeStatus {
+    /// Whether a dispute may move from this status to `next`
+    pub fn can_transition_to(&self, next: &DisputeStatus) -> bool {
+        matches!(
+            (self, next),
+            (DisputeStatus::Open, DisputeStatus::UnderReview)
+                | (DisputeStatus::UnderReview, DisputeStatus::Won)
+                | (DisputeStatus::UnderReview, DisputeStatus::Lost)
+        )
+    }
+
+    pub fn is_final(&self) -> bool {
+        matches!(self, DisputeStatus::Won | DisputeStatus::Lost)
+    }
+}
+
+/// Dispute model for database
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Dispute {
+    pub id: Uuid,
+    pub tenant_id: Option<TenantId>,
+    pub user_id: UserId,
+    pub transaction_id: Option<TransactionId>,
+    pub payment_id: Option<Uuid>,
+    pub customer_account_id: AccountId,
+    pub counterparty_account_id: Option<AccountId>,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub reason: String,
+    pub description: Option<String>,
+    pub status: DisputeStatus,
+    pub held_amount: Amount,
+    pub resolution_note: Option<String>,
+    pub opened_by: Option<Uuid>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Evidence attached to a dispute
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DisputeEvidence {
+    pub id: Uuid,
+    pub dispute_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub description: Option<String>,
+    pub uploaded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The money movement a dispute is raised against
+#[derive(Debug, Clone, FromRow)]
+pub struct DisputedFunds {
+    pub from_account_id: Option<AccountId>,
+    pub to_account_id: Option<AccountId>,
+    pub amount: Amount,
+    pub currency: Currency,
+}
+
+/// Open dispute request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct OpenDisputeRequest {
+    pub user_id: UserId,
+    pub transaction_id: Option<TransactionId>,
+    pub payment_id: Option<Uuid>,
+    #[validate(length(min = 1, max = 100))]
+    pub reason: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+    /// Provisionally hold the disputed amount on the counterparty account
+    #[serde(default)]
+    pub hold_funds: bool,
+}
+
+/// Evidence upload request (file content is base64 encoded)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UploadEvidenceRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub file_name: String,
+    #[validate(length(min = 1, max = 100))]
+    pub content_type: String,
+    #[validate(length(min = 1))]
+    pub content_base64: String,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+}
+
+/// Admin status transition request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateDisputeStatusRequest {
+    pub status: DisputeStatus,
+    #[validate(length(max = 2000))]
+    pub resolution_note: Option<String>,
+}
+
+/// Evidence response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DisputeEvidenceResponse {
+    pub id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DisputeEvidence> for DisputeEvidenceResponse {
+    fn from(evidence: DisputeEvidence) -> Self {
+        Self {
+            id: evidence.id,
+            file_name: evidence.file_name,
+            content_type: evidence.content_type,
+            size_bytes: evidence.size_bytes,
+            description: evidence.description,
+            created_at: evidence.created_at,
+        }
+    }
+}
+
+/// Dispute response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DisputeResponse {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub transaction_id: Option<TransactionId>,
+    pub payment_id: Option<Uuid>,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub reason: String,
+    pub description: Option<String>,
+    pub status: DisputeStatus,
+    pub held_amount: Amount,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub evidence: Vec<DisputeEvidenceResponse>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DisputeResponse {
+    pub fn new(dispute: Dispute, evidence: Vec<DisputeEvidence>) -> Self {
+        Self {
+            id: dispute.id,
+            user_id: dispute.user_id,
+            transaction_id: dispute.transaction_id,
+            payment_id: dispute.payment_id,
+            amount: dispute.amount,
+            currency: dispute.currency,
+            reason: dispute.reason,
+            description: dispute.description,
+            status: dispute.status,
+            held_amount: dispute.held_amount,
+            resolution_note: dispute.resolution_note,
+            resolved_at: dispute.resolved_at,
+            evidence: evidence.into_iter().map(DisputeEvidenceResponse::from).collect(),
+            created_at: dispute.created_at,
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::shared::types::{TenantId, UserId};
+use super::model::{Dispute, DisputeEvidence, DisputeStatus, DisputedFunds};
+
+const DISPUTE_COLUMNS: &str = "id, tenant_id, user_id, transaction_id, payment_id, customer_account_id,
+    counterparty_account_id, amount, currency, reason, description, status, held_amount,
+    resolution_note, opened_by, resolved_by, resolved_at, created_at, updated_at";
+
+const EVIDENCE_COLUMNS: &str =
+    "id, dispute_id, file_name, content_type, size_bytes, storage_key, description, uploaded_by, created_at";
+
+pub struct DisputeRepository {
+    pool: PgPool,
+}
+
+impl DisputeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Look up the accounts and amount moved by a tenant's transaction
+    pub async fn find_transaction_funds(
+        &self,
+        transaction_id: Uuid,
+        tenant_id: TenantId,
+    ) -> AppResult<Option<DisputedFunds>> {
+        let funds = sqlx::query_as::<_, DisputedFunds>(
+            "SELECT from_account_id, to_account_id, amount, currency FROM transactions
+             WHERE id = $1 AND tenant_id = $2",
+        )
+        .bind(transaction_id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(funds)
+    }
+
+    /// Look up the accounts and amount moved by a tenant's payment
+    pub async fn find_payment_funds(&self, payment_id: Uuid, tenant_id: TenantId) -> AppResult<Option<DisputedFunds>> {
+        let funds = sqlx::query_as::<_, DisputedFunds>(
+            "SELECT from_account_id, to_account_id, amount, currency FROM payments
+             WHERE id = $1 AND tenant_id = $2",
+        )
+        .bind(payment_id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(funds)
+    }
+
+    /// Check whether an unresolved dispute already exists for the transaction or payment
+    pub async fn has_active_dispute(
+        &self,
+        transaction_id: Option<Uuid>,
+        payment_id: Option<Uuid>,
+    ) -> AppResult<bool> {
+        let (exists,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS(
+                SELECT 1 FROM disputes
+                WHERE (transaction_id = $1 OR payment_id = $2)
+                  AND status IN ('open', 'under_review')
+            )",
+        )
+        .bind(transaction_id)
+        .bind(payment_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Insert a dispute, placing a provisional hold on the counterparty balance if requested
+    pub async fn create(&self, dispute: &Dispute) -> AppResult<Dispute> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let created = sqlx::query_as::<_, Dispute>(&format!(
+            "INSERT INTO disputes ({DISPUTE_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+             RETURNING {DISPUTE_COLUMNS}"
+        ))
+        .bind(dispute.id)
+        .bind(dispute.tenant_id)
+        .bind(dispute.user_id)
+        .bind(dispute.transaction_id)
+        .bind(dispute.payment_id)
+        .bind(dispute.customer_account_id)
+        .bind(dispute.counterparty_account_id)
+        .bind(dispute.amount)
+        .bind(&dispute.currency)
+        .bind(&dispute.reason)
+        .bind(&dispute.description)
+        .bind(&dispute.status)
+        .bind(dispute.held_amount)
+        .bind(&dispute.resolution_note)
+        .bind(dispute.opened_by)
+        .bind(dispute.resolved_by)
+        .bind(dispute.resolved_at)
+        .bind(dispute.created_at)
+        .bind(dispute.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let (Some(counterparty), true) = (dispute.counterparty_account_id, dispute.held_amount > 0) {
+            sqlx::query(
+                "UPDATE balances SET available_balance = available_balance - $1, updated_at = NOW()
+                 WHERE account_id = $2",
+            )
+            .bind(dispute.held_amount)
+            .bind(counterparty)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Dispute>> {
+        let dispute = sqlx::query_as::<_, Dispute>(&format!(
+            "SELECT {DISPUTE_COLUMNS} FROM disputes WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(dispute)
+    }
+
+    /// Find a dispute belonging to a tenant
+    pub async fn find_by_id_for_tenant(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<Dispute>> {
+        let dispute = sqlx::query_as::<_, Dispute>(&format!(
+            "SELECT {DISPUTE_COLUMNS} FROM disputes WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(dispute)
+    }
+
+    pub async fn find_by_user_id(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<Vec<Dispute>> {
+        let offset = (page.max(1) - 1) * limit;
+
+        let disputes = sqlx::query_as::<_, Dispute>(&format!(
+            "SELECT {DISPUTE_COLUMNS} FROM disputes WHERE user_id = $1 AND tenant_id = $2
+             ORDER BY created_at DESC LIMIT $3 OFFSET $4"
+        ))
+        .bind(user_id)
+        .bind(tenant_id)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(disputes)
+    }
+
+    /// Move a dispute to a new status, settling any provisional hold when it is resolved
+    pub async fn update_status(
+        &self,
+        dispute: &Dispute,
+        status: DisputeStatus,
+        resolution_note: Option<String>,
+        actor_id: Uuid,
+    ) -> AppResult<Dispute> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let resolved = status.is_final();
+        let updated = sqlx::query_as::<_, Dispute>(&format!(
+            "UPDATE disputes
+             SET status = $1,
+                 resolution_note = COALESCE($2, resolution_note),
+                 resolved_by = CASE WHEN $3 THEN $4 ELSE resolved_by END,
+                 resolved_at = CASE WHEN $3 THEN NOW() ELSE resolved_at END,
+                 updated_at = NOW()
+             WHERE id = $5
+             RETURNING {DISPUTE_COLUMNS}"
+        ))
+        .bind(&status)
+        .bind(resolution_note)
+        .bind(resolved)
+        .bind(actor_id)
+        .bind(dispute.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let (Some(counterparty), true) = (dispute.counterparty_account_id, dispute.held_amount > 0) {
+            match status {
+                // Customer won: the held funds leave the counterparty and are credited back
+                DisputeStatus::Won => {
+                    sqlx::query(
+                        "UPDATE balances SET ledger_balance = ledger_balance - $1, updated_at = NOW()
+                         WHERE account_id = $2",
+                    )
+                    .bind(dispute.held_amount)
+                    .bind(counterparty)
+                    .execute(&mut *tx)
+                    .await?;
+
+                    sqlx::query(
+                        "UPDATE balances
+                         SET available_balance = available_balance + $1,
+                             ledger_balance = ledger_balance + $1,
+                             updated_at = NOW()
+                         WHERE account_id = $2",
+                    )
+                    .bind(dispute.held_amount)
+                    .bind(dispute.customer_account_id)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                // Customer lost: release the hold back to the counterparty
+                DisputeStatus::Lost => {
+                    sqlx::query(
+                        "UPDATE balances SET available_balance = available_balance + $1, updated_at = NOW()
+                         WHERE account_id = $2",
+                    )
+                    .bind(dispute.held_amount)
+                    .bind(counterparty)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                DisputeStatus::Open | DisputeStatus::UnderReview => {}
+            }
+        }
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    pub async fn add_evidence(&self, evidence: &DisputeEvidence) -> AppResult<DisputeEvidence> {
+        let created = sqlx::query_as::<_, DisputeEvidence>(&format!(
+            "INSERT INTO dispute_evidence ({EVIDENCE_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             RETURNING {EVIDENCE_COLUMNS}"
+        ))
+        .bind(evidence.id)
+        .bind(evidence.dispute_id)
+        .bind(&evidence.file_name)
+        .bind(&evidence.content_type)
+        .bind(evidence.size_bytes)
+        .bind(&evidence.storage_key)
+        .bind(&evidence.description)
+        .bind(evidence.uploaded_by)
+        .bind(evidence.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn find_evidence(&self, dispute_id: Uuid) -> AppResult<Vec<DisputeEvidence>> {
+        let evidence = sqlx::query_as::<_, DisputeEvidence>(&format!(
+            "SELECT {EVIDENCE_COLUMNS} FROM dispute_evidence WHERE dispute_id = $1 ORDER BY created_at"
+        ))
+        .bind(dispute_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(evidence)
+    }
+
+    pub async fn find_evidence_by_id(
+        &self,
+        dispute_id: Uuid,
+        evidence_id: Uuid,
+    ) -> AppResult<Option<DisputeEvidence>> {
+        let evidence = sqlx::query_as::<_, DisputeEvidence>(&format!(
+            "SELECT {EVIDENCE_COLUMNS} FROM dispute_evidence WHERE dispute_id = $1 AND id = $2"
+        ))
+        .bind(dispute_id)
+        .bind(evidence_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(evidence)
+    }
+}
@@ -0,0 +1,270 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::storage::Storage;
+use crate::shared::constants::{MAX_DISPUTE_EVIDENCE_SIZE, SUPPORTED_EVIDENCE_TYPES};
+use crate::shared::types::{TenantId, UserId};
+use super::model::{
+    Dispute, DisputeEvidence, DisputeEvidenceResponse, DisputeResponse, DisputeStatus,
+    OpenDisputeRequest, UpdateDisputeStatusRequest, UploadEvidenceRequest,
+};
+use super::repository::DisputeRepository;
+
+pub struct DisputeService {
+    repository: DisputeRepository,
+    storage: Arc<dyn Storage>,
+    audit_logger: AuditLogger,
+}
+
+impl DisputeService {
+    pub fn new(repository: DisputeRepository, storage: Arc<dyn Storage>, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            storage,
+            audit_logger,
+        }
+    }
+
+    /// Open a dispute against a transaction or payment
+    pub async fn open_dispute(
+        &self,
+        request: OpenDisputeRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<DisputeResponse> {
+        let funds = match (request.transaction_id, request.payment_id) {
+            (Some(transaction_id), None) => self
+                .repository
+                .find_transaction_funds(transaction_id, tenant_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?,
+            (None, Some(payment_id)) => self
+                .repository
+                .find_payment_funds(payment_id, tenant_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Payment not found".to_string()))?,
+            _ => {
+                return Err(AppError::Validation(
+                    "Exactly one of transaction_id or payment_id must be provided".to_string(),
+                ))
+            }
+        };
+
+        let customer_account_id = funds.from_account_id.ok_or_else(|| {
+            AppError::BadRequest("Only outgoing transactions can be disputed".to_string())
+        })?;
+
+        if self
+            .repository
+            .has_active_dispute(request.transaction_id, request.payment_id)
+            .await?
+        {
+            return Err(AppError::Conflict(
+                "An unresolved dispute already exists for this transaction".to_string(),
+            ));
+        }
+
+        let held_amount = if request.hold_funds && funds.to_account_id.is_some() {
+            funds.amount
+        } else {
+            0
+        };
+
+        let now = Utc::now();
+        let dispute = Dispute {
+            id: Uuid::new_v4(),
+            tenant_id: Some(tenant_id),
+            user_id: request.user_id,
+            transaction_id: request.transaction_id,
+            payment_id: request.payment_id,
+            customer_account_id,
+            counterparty_account_id: funds.to_account_id,
+            amount: funds.amount,
+            currency: funds.currency,
+            reason: request.reason,
+            description: request.description,
+            status: DisputeStatus::Open,
+            held_amount,
+            resolution_note: None,
+            opened_by: Some(actor_id),
+            resolved_by: None,
+            resolved_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let created = self.repository.create(&dispute).await?;
+
+        let event = AuditEvent::new(AuditEventType::DisputeOpened)
+            .user_id(actor_id)
+            .resource(format!("dispute:{}", created.id))
+            .action("open".to_string())
+            .metadata("user_id".to_string(), serde_json::json!(created.user_id))
+            .metadata("amount".to_string(), serde_json::json!(created.amount))
+            .metadata("held_amount".to_string(), serde_json::json!(created.held_amount))
+            .compliance_tag("DISPUTES".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(DisputeResponse::new(created, Vec::new()))
+    }
+
+    /// Get a dispute with its evidence
+    pub async fn get_dispute(&self, dispute_id: Uuid, tenant_id: TenantId) -> AppResult<DisputeResponse> {
+        let dispute = self.find_tenant_dispute(dispute_id, tenant_id).await?;
+        let evidence = self.repository.find_evidence(dispute_id).await?;
+        Ok(DisputeResponse::new(dispute, evidence))
+    }
+
+    /// List disputes raised by a user
+    pub async fn get_user_disputes(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<Vec<DisputeResponse>> {
+        let disputes = self
+            .repository
+            .find_by_user_id(user_id, tenant_id, page, limit)
+            .await?;
+        Ok(disputes
+            .into_iter()
+            .map(|dispute| DisputeResponse::new(dispute, Vec::new()))
+            .collect())
+    }
+
+    /// Upload a piece of evidence to an unresolved dispute
+    pub async fn upload_evidence(
+        &self,
+        dispute_id: Uuid,
+        tenant_id: TenantId,
+        request: UploadEvidenceRequest,
+        actor_id: Uuid,
+    ) -> AppResult<DisputeEvidenceResponse> {
+        let dispute = self.find_tenant_dispute(dispute_id, tenant_id).await?;
+        if dispute.status.is_final() {
+            return Err(AppError::BadRequest(
+                "Evidence cannot be added to a resolved dispute".to_string(),
+            ));
+        }
+
+        if !SUPPORTED_EVIDENCE_TYPES.contains(&request.content_type.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Unsupported evidence type '{}'. Supported types: {}",
+                request.content_type,
+                SUPPORTED_EVIDENCE_TYPES.join(", ")
+            )));
+        }
+
+        let content = STANDARD
+            .decode(request.content_base64.as_bytes())
+            .map_err(|_| AppError::Validation("Evidence content is not valid base64".to_string()))?;
+        if content.len() > MAX_DISPUTE_EVIDENCE_SIZE {
+            return Err(AppError::Validation(format!(
+                "Evidence exceeds the maximum size of {} bytes",
+                MAX_DISPUTE_EVIDENCE_SIZE
+            )));
+        }
+
+        let evidence_id = Uuid::new_v4();
+        let storage_key = format!("disputes/{}/{}", dispute_id, evidence_id);
+        let size_bytes = content.len() as i64;
+        self.storage.put(&storage_key, content).await?;
+
+        let evidence = DisputeEvidence {
+            id: evidence_id,
+            dispute_id,
+            file_name: request.file_name,
+            content_type: request.content_type,
+            size_bytes,
+            storage_key,
+            description: request.description,
+            uploaded_by: Some(actor_id),
+            created_at: Utc::now(),
+        };
+        let created = self.repository.add_evidence(&evidence).await?;
+
+        let event = AuditEvent::new(AuditEventType::DisputeEvidenceAdded)
+            .user_id(actor_id)
+            .resource(format!("dispute:{}", dispute_id))
+            .action("upload_evidence".to_string())
+            .metadata("evidence_id".to_string(), serde_json::json!(created.id))
+            .metadata("size_bytes".to_string(), serde_json::json!(created.size_bytes))
+            .compliance_tag("DISPUTES".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(DisputeEvidenceResponse::from(created))
+    }
+
+    /// Download a piece of evidence, returning its content type and bytes
+    pub async fn download_evidence(
+        &self,
+        dispute_id: Uuid,
+        evidence_id: Uuid,
+        tenant_id: TenantId,
+    ) -> AppResult<(String, Vec<u8>)> {
+        self.find_tenant_dispute(dispute_id, tenant_id).await?;
+        let evidence = self
+            .repository
+            .find_evidence_by_id(dispute_id, evidence_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Evidence not found".to_string()))?;
+
+        let content = self.storage.get(&evidence.storage_key).await?;
+        Ok((evidence.content_type, content))
+    }
+
+    /// Progress a dispute through its review workflow (platform admin only,
+    /// so not limited to one tenant)
+    pub async fn update_status(
+        &self,
+        dispute_id: Uuid,
+        request: UpdateDisputeStatusRequest,
+        actor_id: Uuid,
+    ) -> AppResult<DisputeResponse> {
+        let dispute = self.find_dispute(dispute_id).await?;
+        if !dispute.status.can_transition_to(&request.status) {
+            return Err(AppError::BadRequest(format!(
+                "Cannot move dispute from {:?} to {:?}",
+                dispute.status, request.status
+            )));
+        }
+
+        let previous_status = dispute.status.clone();
+        let updated = self
+            .repository
+            .update_status(&dispute, request.status, request.resolution_note, actor_id)
+            .await?;
+
+        let event = AuditEvent::new(AuditEventType::DisputeStatusChanged)
+            .severity(if updated.status.is_final() { AuditSeverity::Warning } else { AuditSeverity::Info })
+            .user_id(actor_id)
+            .resource(format!("dispute:{}", dispute_id))
+            .action("update_status".to_string())
+            .metadata("from".to_string(), serde_json::json!(previous_status))
+            .metadata("to".to_string(), serde_json::json!(updated.status))
+            .metadata("held_amount".to_string(), serde_json::json!(updated.held_amount))
+            .compliance_tag("DISPUTES".to_string());
+        self.audit_logger.log(event).await;
+
+        let evidence = self.repository.find_evidence(dispute_id).await?;
+        Ok(DisputeResponse::new(updated, evidence))
+    }
+
+    async fn find_dispute(&self, dispute_id: Uuid) -> AppResult<Dispute> {
+        self.repository
+            .find_by_id(dispute_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Dispute not found".to_string()))
+    }
+
+    async fn find_tenant_dispute(&self, dispute_id: Uuid, tenant_id: TenantId) -> AppResult<Dispute> {
+        self.repository
+            .find_by_id_for_tenant(dispute_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Dispute not found".to_string()))
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use crate::auth::middleware::JwtToken;
+use crate::core::{error::AppResult, response::ApiResponse, AppState};
+use crate::stream::model::permitted_event_types;
+use crate::webhooks::model::WebhookDelivery;
+use crate::webhooks::repository::WebhookRepository;
+use super::model::{EventHistoryQuery, EventPage};
+use super::repository::EventRepository;
+use super::service::EventService;
+
+pub(crate) fn event_service(state: &AppState) -> EventService {
+    EventService::new(
+        EventRepository::new(state.postgres.clone()),
+        WebhookRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Page through the tenant's past events, oldest first. Event types the
+/// token's scopes do not cover are never returned.
+#[utoipa::path(
+    get,
+    path = "/api/v1/events",
+    tag = "events",
+    params(EventHistoryQuery),
+    responses(
+        (status = 200, description = "Page of events", body = EventPage),
+        (status = 400, description = "Unknown event type, invalid cursor or time range"),
+        (status = 403, description = "Token lacks the scope an event type needs")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_events(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<EventHistoryQuery>,
+) -> AppResult<Json<ApiResponse<EventPage>>> {
+    let event_types = permitted_event_types(&claims, query.events.as_deref())?;
+
+    let page = event_service(&state)
+        .list_events(claims.tenant_id, &event_types, query)
+        .await?;
+    Ok(Json(ApiResponse::success("Events retrieved successfully", page)))
+}
+
+/// Send an event to the project's webhook URL again
+#[utoipa::path(
+    post,
+    path = "/api/v1/events/{id}/redeliver",
+    tag = "events",
+    params(("id" = Uuid, Path, description = "Event ID")),
+    responses(
+        (status = 202, description = "Delivery queued", body = WebhookDelivery),
+        (status = 400, description = "Project has no webhook URL"),
+        (status = 404, description = "Event not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn redeliver_event(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<(StatusCode, Json<ApiResponse<WebhookDelivery>>)> {
+    let permitted = permitted_event_types(&claims, None)?;
+
+    let delivery = event_service(&state)
+        .redeliver(id, claims.tenant_id, claims.project_id, &permitted, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiResponse::success("Event queued for redelivery", delivery)),
+    ))
+}
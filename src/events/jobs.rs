@@ -0,0 +1,20 @@
+use crate::core::AppState;
+use super::controller::event_service;
+
+/// Journal every domain event published on the bus and queue it for project
+/// webhooks. Runs for the life of the process.
+pub fn spawn_journal_job(state: AppState) {
+    let Some(mut events) = state.event_bus.attach_journal() else {
+        tracing::warn!("Domain event journal is already attached");
+        return;
+    };
+
+    tokio::spawn(async move {
+        let service = event_service(&state);
+        while let Some(event) = events.recv().await {
+            if let Err(e) = service.record(&event).await {
+                tracing::error!(event_id = %event.id, "Failed to journal {} event: {}", event.event_type.as_str(), e);
+            }
+        }
+    });
+}
@@ -0,0 +1,14 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(controller::list_events))
+        .route("/:id/redeliver", post(controller::redeliver_event))
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use crate::core::events::{DomainEvent, DomainEventType};
+use crate::shared::types::{AccountId, TenantId};
+
+/// Events returned per page unless the caller asks for another size
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// Largest page a caller may ask for
+pub const MAX_PAGE_SIZE: u32 = 200;
+
+/// Query parameters for the event history
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventHistoryQuery {
+    /// Comma-separated event types; defaults to every type the token's scopes allow
+    pub events: Option<String>,
+    /// Only events that occurred at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only events that occurred before this time
+    pub to: Option<DateTime<Utc>>,
+    /// `next_cursor` of the previous page; omit to start from the oldest event
+    pub cursor: Option<String>,
+    /// Page size, at most 200 (default 50)
+    pub limit: Option<u32>,
+}
+
+/// A journaled domain event
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredEvent {
+    pub id: Uuid,
+    pub sequence: i64,
+    pub event_type: String,
+    pub tenant_id: Option<TenantId>,
+    pub account_ids: Vec<AccountId>,
+    pub data: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl StoredEvent {
+    /// The event as published; `None` for types this build no longer knows
+    pub fn into_event(self) -> Option<DomainEvent> {
+        Some(DomainEvent {
+            id: self.id,
+            event_type: DomainEventType::parse(&self.event_type)?,
+            tenant_id: self.tenant_id,
+            account_ids: self.account_ids,
+            data: self.data,
+            occurred_at: self.occurred_at,
+        })
+    }
+}
+
+/// A page of historical events, oldest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EventPage {
+    pub data: Vec<DomainEvent>,
+    /// Pass as `cursor` to continue after this page. Also returned for the
+    /// last page, so it can be polled for newer events.
+    pub next_cursor: Option<String>,
+    /// Whether more events matched than fit on this page
+    pub has_more: bool,
+}
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::core::events::DomainEvent;
+use crate::shared::types::TenantId;
+use super::model::StoredEvent;
+
+const EVENT_COLUMNS: &str = "id, sequence, event_type, tenant_id, account_ids, data, occurred_at";
+
+/// Bounds for a page of a tenant's events
+pub struct EventWindow<'a> {
+    pub event_types: &'a [&'static str],
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Only events after this sequence number
+    pub after: i64,
+}
+
+pub struct EventRepository {
+    pool: PgPool,
+}
+
+impl EventRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Journal a published event. Returns false when it was already recorded.
+    pub async fn record(&self, event: &DomainEvent) -> AppResult<bool> {
+        let recorded = sqlx::query(
+            "INSERT INTO domain_events (id, event_type, tenant_id, account_ids, data, occurred_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (id) DO NOTHING",
+        )
+        .bind(event.id)
+        .bind(event.event_type.as_str())
+        .bind(event.tenant_id)
+        .bind(&event.account_ids)
+        .bind(&event.data)
+        .bind(event.occurred_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(recorded.rows_affected() > 0)
+    }
+
+    pub async fn find_page(
+        &self,
+        tenant_id: TenantId,
+        window: &EventWindow<'_>,
+        limit: i64,
+    ) -> AppResult<Vec<StoredEvent>> {
+        let events = sqlx::query_as::<_, StoredEvent>(&format!(
+            "SELECT {EVENT_COLUMNS} FROM domain_events
+             WHERE tenant_id = $1
+               AND sequence > $2
+               AND event_type = ANY($3)
+               AND ($4::TIMESTAMPTZ IS NULL OR occurred_at >= $4)
+               AND ($5::TIMESTAMPTZ IS NULL OR occurred_at < $5)
+             ORDER BY sequence
+             LIMIT $6"
+        ))
+        .bind(tenant_id)
+        .bind(window.after)
+        .bind(window.event_types)
+        .bind(window.from)
+        .bind(window.to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<StoredEvent>> {
+        let event = sqlx::query_as::<_, StoredEvent>(&format!(
+            "SELECT {EVENT_COLUMNS} FROM domain_events WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(event)
+    }
+
+    /// The project's webhook URL, if it has one
+    pub async fn find_project_webhook_url(&self, project_id: Uuid) -> AppResult<Option<String>> {
+        let url: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT webhook_url FROM projects WHERE id = $1 AND COALESCE(webhook_url, '') <> ''",
+        )
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(url.flatten())
+    }
+}
@@ -0,0 +1,122 @@
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::events::{DomainEvent, DomainEventType};
+use crate::shared::types::TenantId;
+use crate::webhooks::model::WebhookDelivery;
+use crate::webhooks::repository::WebhookRepository;
+use super::model::{EventHistoryQuery, EventPage, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use super::repository::{EventRepository, EventWindow};
+
+pub struct EventService {
+    repository: EventRepository,
+    webhooks: WebhookRepository,
+    audit_logger: AuditLogger,
+}
+
+impl EventService {
+    pub fn new(repository: EventRepository, webhooks: WebhookRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            webhooks,
+            audit_logger,
+        }
+    }
+
+    /// Journal a published event and queue it for the tenant's project webhooks
+    pub async fn record(&self, event: &DomainEvent) -> AppResult<()> {
+        let recorded = self.repository.record(event).await?;
+        if recorded && event.tenant_id.is_some() {
+            self.webhooks.enqueue_for_projects(event, &json!(event)).await?;
+        }
+        Ok(())
+    }
+
+    /// A page of the tenant's events of the given types, after the cursor
+    pub async fn list_events(
+        &self,
+        tenant_id: TenantId,
+        event_types: &[DomainEventType],
+        query: EventHistoryQuery,
+    ) -> AppResult<EventPage> {
+        let after = match query.cursor.as_deref() {
+            Some(cursor) => cursor
+                .parse::<i64>()
+                .ok()
+                .filter(|sequence| *sequence >= 0)
+                .ok_or_else(|| AppError::Validation("Invalid cursor".to_string()))?,
+            None => 0,
+        };
+        if let (Some(from), Some(to)) = (query.from, query.to) {
+            if from >= to {
+                return Err(AppError::Validation("'from' must be before 'to'".to_string()));
+            }
+        }
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE) as usize;
+
+        let event_types: Vec<&'static str> = event_types.iter().map(DomainEventType::as_str).collect();
+        let window = EventWindow {
+            event_types: &event_types,
+            from: query.from,
+            to: query.to,
+            after,
+        };
+        // One extra row tells whether another page follows
+        let mut events = self.repository.find_page(tenant_id, &window, limit as i64 + 1).await?;
+        let has_more = events.len() > limit;
+        events.truncate(limit);
+
+        let next_cursor = events
+            .last()
+            .map(|event| event.sequence)
+            .or(query.cursor.is_some().then_some(after))
+            .map(|sequence| sequence.to_string());
+        Ok(EventPage {
+            data: events.into_iter().filter_map(|event| event.into_event()).collect(),
+            next_cursor,
+            has_more,
+        })
+    }
+
+    /// Queue an event for delivery to the project's webhook URL again
+    pub async fn redeliver(
+        &self,
+        event_id: Uuid,
+        tenant_id: TenantId,
+        project_id: Uuid,
+        permitted: &[DomainEventType],
+        actor_id: Uuid,
+    ) -> AppResult<WebhookDelivery> {
+        let event = self
+            .repository
+            .find_by_id(event_id, tenant_id)
+            .await?
+            .and_then(|event| event.into_event())
+            .filter(|event| permitted.contains(&event.event_type))
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))?;
+
+        let url = self
+            .repository
+            .find_project_webhook_url(project_id)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Project has no webhook URL".to_string()))?;
+
+        let delivery = self
+            .webhooks
+            .enqueue_for_project(project_id, &event, &url, &json!(event))
+            .await?;
+
+        let audit = AuditEvent::new(AuditEventType::EventRedelivered)
+            .severity(AuditSeverity::Info)
+            .user_id(actor_id)
+            .project_id(project_id)
+            .resource(format!("domain_event:{}", event.id))
+            .action("redeliver".to_string())
+            .metadata("event_type".to_string(), json!(event.event_type.as_str()))
+            .metadata("delivery_id".to_string(), json!(delivery.id));
+        self.audit_logger.log(audit).await;
+
+        Ok(delivery)
+    }
+}
@@ -0,0 +1,270 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    feature_flags::{FlagContext, FlagTargetType},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{
+    CreateFeatureFlagRequest, EvaluateFlagQuery, FeatureFlagResponse, FlagEvaluation, SetFlagTargetRequest,
+    UpdateFeatureFlagRequest,
+};
+use super::service::feature_flag_service;
+
+/// List feature flags and their targets (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/feature-flags",
+    tag = "feature-flags",
+    responses(
+        (status = 200, description = "Feature flags", body = [FeatureFlagResponse]),
+        (status = 403, description = "Caller lacks the feature flag management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_feature_flags(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+) -> AppResult<Json<ApiResponse<Vec<FeatureFlagResponse>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_feature_flags(), ip, "feature_flags".to_string())
+        .await?;
+
+    let flags = feature_flag_service(&state).list_flags().await?;
+    Ok(Json(ApiResponse::success("Feature flags retrieved successfully", flags)))
+}
+
+/// Create a feature flag (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/feature-flags",
+    tag = "feature-flags",
+    request_body = CreateFeatureFlagRequest,
+    responses(
+        (status = 201, description = "Feature flag created", body = FeatureFlagResponse),
+        (status = 400, description = "Invalid key or rollout percentage"),
+        (status = 403, description = "Caller lacks the feature flag management permission"),
+        (status = 409, description = "A flag with the key already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_feature_flag(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<CreateFeatureFlagRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<FeatureFlagResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_feature_flags(), ip, "feature_flags".to_string())
+        .await?;
+
+    let flag = feature_flag_service(&state)
+        .create_flag(request, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Feature flag created successfully", flag)),
+    ))
+}
+
+/// Get a feature flag (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/feature-flags/{key}",
+    tag = "feature-flags",
+    params(("key" = String, Path, description = "Flag key")),
+    responses(
+        (status = 200, description = "Feature flag", body = FeatureFlagResponse),
+        (status = 403, description = "Caller lacks the feature flag management permission"),
+        (status = 404, description = "Feature flag not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_feature_flag(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(key): Path<String>,
+) -> AppResult<Json<ApiResponse<FeatureFlagResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_feature_flags(), ip, format!("feature_flag:{}", key))
+        .await?;
+
+    let flag = feature_flag_service(&state).get_flag(&key).await?;
+    Ok(Json(ApiResponse::success("Feature flag retrieved successfully", flag)))
+}
+
+/// Change a feature flag's description, switch or rollout (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/feature-flags/{key}",
+    tag = "feature-flags",
+    params(("key" = String, Path, description = "Flag key")),
+    request_body = UpdateFeatureFlagRequest,
+    responses(
+        (status = 200, description = "Feature flag updated", body = FeatureFlagResponse),
+        (status = 400, description = "Invalid rollout percentage"),
+        (status = 403, description = "Caller lacks the feature flag management permission"),
+        (status = 404, description = "Feature flag not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_feature_flag(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(key): Path<String>,
+    ApiJson(request): ApiJson<UpdateFeatureFlagRequest>,
+) -> AppResult<Json<ApiResponse<FeatureFlagResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_feature_flags(), ip, format!("feature_flag:{}", key))
+        .await?;
+
+    let flag = feature_flag_service(&state)
+        .update_flag(&key, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Feature flag updated successfully", flag)))
+}
+
+/// Delete a feature flag and its targets (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/feature-flags/{key}",
+    tag = "feature-flags",
+    params(("key" = String, Path, description = "Flag key")),
+    responses(
+        (status = 200, description = "Feature flag deleted"),
+        (status = 403, description = "Caller lacks the feature flag management permission"),
+        (status = 404, description = "Feature flag not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_feature_flag(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(key): Path<String>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_feature_flags(), ip, format!("feature_flag:{}", key))
+        .await?;
+
+    feature_flag_service(&state).delete_flag(&key, claims.developer_id).await?;
+    Ok(Json(ApiResponse::success_no_data("Feature flag deleted successfully")))
+}
+
+/// Evaluate a feature flag for a tenant and project (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/feature-flags/{key}/evaluate",
+    tag = "feature-flags",
+    params(("key" = String, Path, description = "Flag key"), EvaluateFlagQuery),
+    responses(
+        (status = 200, description = "The flag's value for the tenant and project", body = FlagEvaluation),
+        (status = 403, description = "Caller lacks the feature flag management permission"),
+        (status = 404, description = "Feature flag not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn evaluate_feature_flag(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(key): Path<String>,
+    Query(query): Query<EvaluateFlagQuery>,
+) -> AppResult<Json<ApiResponse<FlagEvaluation>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_feature_flags(), ip, format!("feature_flag:{}", key))
+        .await?;
+
+    let evaluation = feature_flag_service(&state)
+        .evaluate(&key, FlagContext::new(query.tenant_id, query.project_id))
+        .await?;
+    Ok(Json(ApiResponse::success("Feature flag evaluated successfully", evaluation)))
+}
+
+/// Turn a feature flag on or off for one tenant or project (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/feature-flags/{key}/targets/{target_type}/{target_id}",
+    tag = "feature-flags",
+    params(
+        ("key" = String, Path, description = "Flag key"),
+        ("target_type" = FlagTargetType, Path, description = "tenant or project"),
+        ("target_id" = Uuid, Path, description = "Organization or project ID")
+    ),
+    request_body = SetFlagTargetRequest,
+    responses(
+        (status = 200, description = "Target set", body = FeatureFlagResponse),
+        (status = 403, description = "Caller lacks the feature flag management permission"),
+        (status = 404, description = "Feature flag, organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_feature_flag_target(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path((key, target_type, target_id)): Path<(String, FlagTargetType, Uuid)>,
+    ApiJson(request): ApiJson<SetFlagTargetRequest>,
+) -> AppResult<Json<ApiResponse<FeatureFlagResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_feature_flags(), ip, format!("feature_flag:{}", key))
+        .await?;
+
+    let flag = feature_flag_service(&state)
+        .set_target(&key, target_type, target_id, request.enabled, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Feature flag target set successfully", flag)))
+}
+
+/// Remove a tenant or project target, leaving it to the rollout (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/feature-flags/{key}/targets/{target_type}/{target_id}",
+    tag = "feature-flags",
+    params(
+        ("key" = String, Path, description = "Flag key"),
+        ("target_type" = FlagTargetType, Path, description = "tenant or project"),
+        ("target_id" = Uuid, Path, description = "Organization or project ID")
+    ),
+    responses(
+        (status = 200, description = "Target removed", body = FeatureFlagResponse),
+        (status = 403, description = "Caller lacks the feature flag management permission"),
+        (status = 404, description = "Feature flag or target not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove_feature_flag_target(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path((key, target_type, target_id)): Path<(String, FlagTargetType, Uuid)>,
+) -> AppResult<Json<ApiResponse<FeatureFlagResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_feature_flags(), ip, format!("feature_flag:{}", key))
+        .await?;
+
+    let flag = feature_flag_service(&state)
+        .remove_target(&key, target_type, target_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Feature flag target removed successfully", flag)))
+}
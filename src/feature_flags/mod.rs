@@ -0,0 +1,26 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, put}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/feature-flags",
+            get(controller::list_feature_flags).post(controller::create_feature_flag),
+        )
+        .route(
+            "/feature-flags/:key",
+            get(controller::get_feature_flag)
+                .put(controller::update_feature_flag)
+                .delete(controller::delete_feature_flag),
+        )
+        .route("/feature-flags/:key/evaluate", get(controller::evaluate_feature_flag))
+        .route(
+            "/feature-flags/:key/targets/:target_type/:target_id",
+            put(controller::set_feature_flag_target).delete(controller::remove_feature_flag_target),
+        )
+}
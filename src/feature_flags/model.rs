@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::core::feature_flags::FlagTarget;
+
+/// A feature flag as stored
+#[derive(Debug, Clone, FromRow)]
+pub struct FeatureFlag {
+    pub key: String,
+    pub description: Option<String>,
+    pub enabled: bool,
+    pub rollout_percentage: i32,
+    pub created_by: Option<Uuid>,
+    pub updated_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A feature flag with its tenant and project targets
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeatureFlagResponse {
+    pub key: String,
+    pub description: Option<String>,
+    /// Off everywhere when false, whatever the targets say
+    pub enabled: bool,
+    /// Share of projects, or tenants for calls without a project, the flag
+    /// is on for when no target applies
+    pub rollout_percentage: i32,
+    pub targets: Vec<FlagTarget>,
+    pub created_by: Option<Uuid>,
+    pub updated_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FeatureFlagResponse {
+    pub fn new(flag: FeatureFlag, targets: Vec<FlagTarget>) -> Self {
+        Self {
+            key: flag.key,
+            description: flag.description,
+            enabled: flag.enabled,
+            rollout_percentage: flag.rollout_percentage,
+            targets,
+            created_by: flag.created_by,
+            updated_by: flag.updated_by,
+            created_at: flag.created_at,
+            updated_at: flag.updated_at,
+        }
+    }
+}
+
+/// Create a feature flag
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateFeatureFlagRequest {
+    /// Lowercase letters, digits, underscores and dots, e.g. `fraud.velocity_v2`
+    #[validate(length(min = 1, max = 100))]
+    pub key: String,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 100))]
+    pub rollout_percentage: i32,
+}
+
+/// Change a feature flag; fields left out keep their value
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateFeatureFlagRequest {
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+    #[validate(range(min = 0, max = 100))]
+    pub rollout_percentage: Option<i32>,
+}
+
+/// Turn a flag on or off for one tenant or project
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetFlagTargetRequest {
+    pub enabled: bool,
+}
+
+/// Who to evaluate a flag for
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EvaluateFlagQuery {
+    pub tenant_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+}
+
+/// A flag's value for a tenant and project
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FlagEvaluation {
+    pub key: String,
+    pub tenant_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    pub enabled: bool,
+}
@@ -0,0 +1,165 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::core::feature_flags::{FlagTarget, FlagTargetType};
+use super::model::FeatureFlag;
+
+const FLAG_COLUMNS: &str =
+    "key, description, enabled, rollout_percentage, created_by, updated_by, created_at, updated_at";
+
+pub struct FeatureFlagRepository {
+    pool: PgPool,
+}
+
+impl FeatureFlagRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self) -> AppResult<Vec<FeatureFlag>> {
+        let flags = sqlx::query_as::<_, FeatureFlag>(&format!(
+            "SELECT {} FROM feature_flags ORDER BY key",
+            FLAG_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(flags)
+    }
+
+    pub async fn find(&self, key: &str) -> AppResult<Option<FeatureFlag>> {
+        let flag = sqlx::query_as::<_, FeatureFlag>(&format!(
+            "SELECT {} FROM feature_flags WHERE key = $1",
+            FLAG_COLUMNS
+        ))
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(flag)
+    }
+
+    /// Insert a flag, returning `None` when the key is taken
+    pub async fn create(
+        &self,
+        key: &str,
+        description: Option<&str>,
+        enabled: bool,
+        rollout_percentage: i32,
+        created_by: Uuid,
+    ) -> AppResult<Option<FeatureFlag>> {
+        let flag = sqlx::query_as::<_, FeatureFlag>(&format!(
+            "INSERT INTO feature_flags (key, description, enabled, rollout_percentage, created_by, updated_by)
+             VALUES ($1, $2, $3, $4, $5, $5)
+             ON CONFLICT (key) DO NOTHING
+             RETURNING {}",
+            FLAG_COLUMNS
+        ))
+        .bind(key)
+        .bind(description)
+        .bind(enabled)
+        .bind(rollout_percentage)
+        .bind(created_by)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(flag)
+    }
+
+    pub async fn update(
+        &self,
+        key: &str,
+        description: Option<&str>,
+        enabled: bool,
+        rollout_percentage: i32,
+        updated_by: Uuid,
+    ) -> AppResult<Option<FeatureFlag>> {
+        let flag = sqlx::query_as::<_, FeatureFlag>(&format!(
+            "UPDATE feature_flags
+             SET description = $2, enabled = $3, rollout_percentage = $4, updated_by = $5, updated_at = NOW()
+             WHERE key = $1
+             RETURNING {}",
+            FLAG_COLUMNS
+        ))
+        .bind(key)
+        .bind(description)
+        .bind(enabled)
+        .bind(rollout_percentage)
+        .bind(updated_by)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(flag)
+    }
+
+    pub async fn delete(&self, key: &str) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM feature_flags WHERE key = $1")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn find_targets(&self, key: &str) -> AppResult<Vec<FlagTarget>> {
+        let targets = sqlx::query_as::<_, FlagTarget>(
+            "SELECT target_type, target_id, enabled FROM feature_flag_targets
+             WHERE flag_key = $1
+             ORDER BY target_type, created_at",
+        )
+        .bind(key)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(targets)
+    }
+
+    pub async fn set_target(
+        &self,
+        key: &str,
+        target_type: FlagTargetType,
+        target_id: Uuid,
+        enabled: bool,
+        created_by: Uuid,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "INSERT INTO feature_flag_targets (flag_key, target_type, target_id, enabled, created_by)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (flag_key, target_type, target_id) DO UPDATE
+             SET enabled = EXCLUDED.enabled, created_by = EXCLUDED.created_by, created_at = NOW()",
+        )
+        .bind(key)
+        .bind(target_type)
+        .bind(target_id)
+        .bind(enabled)
+        .bind(created_by)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_target(&self, key: &str, target_type: FlagTargetType, target_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query(
+            "DELETE FROM feature_flag_targets WHERE flag_key = $1 AND target_type = $2 AND target_id = $3",
+        )
+        .bind(key)
+        .bind(target_type)
+        .bind(target_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether the tenant or project a target names exists
+    pub async fn target_exists(&self, target_type: FlagTargetType, target_id: Uuid) -> AppResult<bool> {
+        let query = match target_type {
+            FlagTargetType::Tenant => "SELECT EXISTS(SELECT 1 FROM organizations WHERE id = $1)",
+            FlagTargetType::Project => "SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1)",
+        };
+        let exists: bool = sqlx::query_scalar(query).bind(target_id).fetch_one(&self.pool).await?;
+
+        Ok(exists)
+    }
+}
@@ -0,0 +1,217 @@
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::core::feature_flags::{FeatureFlags, FlagContext, FlagTargetType};
+use crate::core::AppState;
+use super::model::{
+    CreateFeatureFlagRequest, FeatureFlag, FeatureFlagResponse, FlagEvaluation, UpdateFeatureFlagRequest,
+};
+use super::repository::FeatureFlagRepository;
+
+pub struct FeatureFlagService {
+    repository: FeatureFlagRepository,
+    flags: FeatureFlags,
+    audit_logger: AuditLogger,
+}
+
+pub fn feature_flag_service(state: &AppState) -> FeatureFlagService {
+    FeatureFlagService::new(
+        FeatureFlagRepository::new(state.postgres.clone()),
+        state.feature_flags.clone(),
+        state.audit_logger.clone(),
+    )
+}
+
+impl FeatureFlagService {
+    pub fn new(repository: FeatureFlagRepository, flags: FeatureFlags, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            flags,
+            audit_logger,
+        }
+    }
+
+    pub async fn list_flags(&self) -> AppResult<Vec<FeatureFlagResponse>> {
+        let mut flags = Vec::new();
+        for flag in self.repository.list().await? {
+            let targets = self.repository.find_targets(&flag.key).await?;
+            flags.push(FeatureFlagResponse::new(flag, targets));
+        }
+        Ok(flags)
+    }
+
+    pub async fn get_flag(&self, key: &str) -> AppResult<FeatureFlagResponse> {
+        let flag = self.find_flag(key).await?;
+        self.respond(flag).await
+    }
+
+    pub async fn create_flag(&self, request: CreateFeatureFlagRequest, actor_id: Uuid) -> AppResult<FeatureFlagResponse> {
+        if !request
+            .key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.')
+        {
+            return Err(AppError::Validation(
+                "Flag keys may only contain lowercase letters, digits, underscores and dots".to_string(),
+            ));
+        }
+
+        let flag = self
+            .repository
+            .create(
+                &request.key,
+                request.description.as_deref(),
+                request.enabled,
+                request.rollout_percentage,
+                actor_id,
+            )
+            .await?
+            .ok_or_else(|| AppError::Conflict(format!("Feature flag '{}' already exists", request.key)))?;
+        self.flags.invalidate();
+
+        let event = self
+            .event(AuditEventType::FeatureFlagCreated, actor_id, &flag.key, "create")
+            .metadata("enabled".to_string(), json!(flag.enabled))
+            .metadata("rollout_percentage".to_string(), json!(flag.rollout_percentage));
+        self.audit_logger.log(event).await;
+
+        self.respond(flag).await
+    }
+
+    pub async fn update_flag(
+        &self,
+        key: &str,
+        request: UpdateFeatureFlagRequest,
+        actor_id: Uuid,
+    ) -> AppResult<FeatureFlagResponse> {
+        let previous = self.find_flag(key).await?;
+        let flag = self
+            .repository
+            .update(
+                key,
+                request.description.as_deref().or(previous.description.as_deref()),
+                request.enabled.unwrap_or(previous.enabled),
+                request.rollout_percentage.unwrap_or(previous.rollout_percentage),
+                actor_id,
+            )
+            .await?
+            .ok_or_else(|| AppError::NotFound("Feature flag not found".to_string()))?;
+        self.flags.invalidate();
+
+        let event = self
+            .event(AuditEventType::FeatureFlagUpdated, actor_id, key, "update")
+            .metadata(
+                "from".to_string(),
+                json!({"enabled": previous.enabled, "rollout_percentage": previous.rollout_percentage}),
+            )
+            .metadata(
+                "to".to_string(),
+                json!({"enabled": flag.enabled, "rollout_percentage": flag.rollout_percentage}),
+            );
+        self.audit_logger.log(event).await;
+
+        self.respond(flag).await
+    }
+
+    pub async fn delete_flag(&self, key: &str, actor_id: Uuid) -> AppResult<()> {
+        if !self.repository.delete(key).await? {
+            return Err(AppError::NotFound("Feature flag not found".to_string()));
+        }
+        self.flags.invalidate();
+
+        let event = self.event(AuditEventType::FeatureFlagDeleted, actor_id, key, "delete");
+        self.audit_logger.log(event).await;
+        Ok(())
+    }
+
+    /// Turn a flag on or off for one tenant or project, whatever the rollout
+    pub async fn set_target(
+        &self,
+        key: &str,
+        target_type: FlagTargetType,
+        target_id: Uuid,
+        enabled: bool,
+        actor_id: Uuid,
+    ) -> AppResult<FeatureFlagResponse> {
+        let flag = self.find_flag(key).await?;
+        if !self.repository.target_exists(target_type, target_id).await? {
+            return Err(AppError::NotFound(match target_type {
+                FlagTargetType::Tenant => "Organization not found".to_string(),
+                FlagTargetType::Project => "Project not found".to_string(),
+            }));
+        }
+
+        self.repository
+            .set_target(key, target_type, target_id, enabled, actor_id)
+            .await?;
+        self.flags.invalidate();
+
+        let event = self
+            .event(AuditEventType::FeatureFlagTargetChanged, actor_id, key, "set_target")
+            .metadata("target_type".to_string(), json!(target_type))
+            .metadata("target_id".to_string(), json!(target_id))
+            .metadata("enabled".to_string(), json!(enabled));
+        self.audit_logger.log(event).await;
+
+        self.respond(flag).await
+    }
+
+    pub async fn remove_target(
+        &self,
+        key: &str,
+        target_type: FlagTargetType,
+        target_id: Uuid,
+        actor_id: Uuid,
+    ) -> AppResult<FeatureFlagResponse> {
+        let flag = self.find_flag(key).await?;
+        if !self.repository.remove_target(key, target_type, target_id).await? {
+            return Err(AppError::NotFound("Feature flag target not found".to_string()));
+        }
+        self.flags.invalidate();
+
+        let event = self
+            .event(AuditEventType::FeatureFlagTargetChanged, actor_id, key, "remove_target")
+            .metadata("target_type".to_string(), json!(target_type))
+            .metadata("target_id".to_string(), json!(target_id));
+        self.audit_logger.log(event).await;
+
+        self.respond(flag).await
+    }
+
+    /// A flag's value for a tenant and project, as services see it
+    pub async fn evaluate(&self, key: &str, context: FlagContext) -> AppResult<FlagEvaluation> {
+        let enabled = self
+            .flags
+            .evaluate(key, context)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Feature flag not found".to_string()))?;
+
+        Ok(FlagEvaluation {
+            key: key.to_string(),
+            tenant_id: context.tenant_id,
+            project_id: context.project_id,
+            enabled,
+        })
+    }
+
+    async fn find_flag(&self, key: &str) -> AppResult<FeatureFlag> {
+        self.repository
+            .find(key)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Feature flag not found".to_string()))
+    }
+
+    async fn respond(&self, flag: FeatureFlag) -> AppResult<FeatureFlagResponse> {
+        let targets = self.repository.find_targets(&flag.key).await?;
+        Ok(FeatureFlagResponse::new(flag, targets))
+    }
+
+    fn event(&self, event_type: AuditEventType, actor_id: Uuid, key: &str, action: &str) -> AuditEvent {
+        AuditEvent::new(event_type)
+            .user_id(actor_id)
+            .resource(format!("feature_flag:{}", key))
+            .action(action.to_string())
+            .compliance_tag("CHANGE_MANAGEMENT".to_string())
+    }
+}
@@ -0,0 +1,153 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{
+    CreateFeeScheduleRequest, FeePreviewRequest, FeePreviewResponse, FeeSchedule, FeeScheduleFilter,
+    UpdateFeeScheduleRequest,
+};
+use super::repository::FeeRepository;
+use super::service::{FeeEngine, FeeScheduleService};
+
+fn fee_schedule_service(state: &AppState) -> FeeScheduleService {
+    FeeScheduleService::new(
+        FeeRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Preview the fees a payment would incur for the calling project
+#[utoipa::path(
+    post,
+    path = "/api/v1/fees/preview",
+    tag = "fees",
+    request_body = FeePreviewRequest,
+    responses(
+        (status = 200, description = "Fees the payment would incur", body = FeePreviewResponse),
+        (status = 400, description = "Invalid request")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn preview_fees(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<FeePreviewRequest>,
+) -> AppResult<Json<ApiResponse<FeePreviewResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let preview = FeeEngine::new(FeeRepository::new(state.postgres.clone()))
+        .preview(claims.project_id, request)
+        .await?;
+    Ok(Json(ApiResponse::success("Fee preview calculated successfully", preview)))
+}
+
+/// List fee schedules (admin)
+#[utoipa::path(
+    get,
+    path = "/api/v1/fees/schedules",
+    tag = "fees",
+    params(FeeScheduleFilter),
+    responses(
+        (status = 200, description = "Fee schedules", body = [FeeSchedule]),
+        (status = 403, description = "Caller lacks the fee management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_fee_schedules(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(filter): Query<FeeScheduleFilter>,
+) -> AppResult<Json<ApiResponse<Vec<FeeSchedule>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_fees(), ip, "fee_schedules".to_string())
+        .await?;
+
+    let schedules = fee_schedule_service(&state).list_schedules(filter).await?;
+    Ok(Json(ApiResponse::success("Fee schedules retrieved successfully", schedules)))
+}
+
+/// Create a fee schedule (admin)
+#[utoipa::path(
+    post,
+    path = "/api/v1/fees/schedules",
+    tag = "fees",
+    request_body = CreateFeeScheduleRequest,
+    responses(
+        (status = 201, description = "Fee schedule created", body = FeeSchedule),
+        (status = 400, description = "Invalid fee schedule"),
+        (status = 403, description = "Caller lacks the fee management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_fee_schedule(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<CreateFeeScheduleRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<FeeSchedule>>)> {
+    state
+        .authorize(claims.developer_id, permissions::manage_fees(), ip, "fee_schedules".to_string())
+        .await?;
+
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let schedule = fee_schedule_service(&state)
+        .create_schedule(request, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Fee schedule created successfully", schedule)),
+    ))
+}
+
+/// Update a fee schedule (admin)
+#[utoipa::path(
+    put,
+    path = "/api/v1/fees/schedules/{id}",
+    tag = "fees",
+    params(("id" = Uuid, Path, description = "Fee schedule ID")),
+    request_body = UpdateFeeScheduleRequest,
+    responses(
+        (status = 200, description = "Fee schedule updated", body = FeeSchedule),
+        (status = 400, description = "Invalid fee schedule"),
+        (status = 403, description = "Caller lacks the fee management permission"),
+        (status = 404, description = "Fee schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_fee_schedule(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<UpdateFeeScheduleRequest>,
+) -> AppResult<Json<ApiResponse<FeeSchedule>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_fees(), ip, format!("fee_schedule:{}", id))
+        .await?;
+
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let schedule = fee_schedule_service(&state)
+        .update_schedule(id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Fee schedule updated successfully", schedule)))
+}
@@ -0,0 +1,17 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post, put}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/preview", post(controller::preview_fees))
+        .route(
+            "/schedules",
+            get(controller::list_fee_schedules).post(controller::create_fee_schedule),
+        )
+        .route("/schedules/:id", put(controller::update_fee_schedule))
+}
@@ -0,0 +1,176 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::payments::model::PaymentMethod;
+use crate::shared::types::{Amount, Currency};
+
+/// Basis points representing 100%
+pub const FULL_PERCENTAGE_BPS: i64 = 10_000;
+
+/// How a fee schedule computes its fee
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "fee_type", rename_all = "snake_case")]
+pub enum FeeType {
+    /// A fixed amount per payment
+    Flat,
+    /// Basis points of the payment amount
+    Percentage,
+    /// Flat and percentage components chosen by payment amount band
+    Tiered,
+}
+
+/// One amount band of a tiered fee schedule
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeeTier {
+    /// Upper bound of the band (inclusive); `None` for the last band
+    pub up_to: Option<Amount>,
+    #[serde(default)]
+    pub flat_amount: Amount,
+    #[serde(default)]
+    pub percentage_bps: i64,
+}
+
+/// Fee schedule model for database
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct FeeSchedule {
+    pub id: Uuid,
+    /// Fees with the same code replace each other by specificity; different codes stack
+    pub fee_code: String,
+    pub name: String,
+    pub project_id: Option<Uuid>,
+    pub payment_method: Option<PaymentMethod>,
+    pub currency: Option<Currency>,
+    pub fee_type: FeeType,
+    pub flat_amount: Amount,
+    pub percentage_bps: i64,
+    #[schema(value_type = Option<Vec<FeeTier>>)]
+    pub tiers: Option<Json<Vec<FeeTier>>>,
+    pub min_fee: Option<Amount>,
+    pub max_fee: Option<Amount>,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FeeSchedule {
+    /// Fee charged on a payment of `amount`, within the schedule's bounds
+    pub fn fee_for(&self, amount: Amount) -> Amount {
+        let fee = match self.fee_type {
+            FeeType::Flat => self.flat_amount,
+            FeeType::Percentage => percentage_of(amount, self.percentage_bps),
+            FeeType::Tiered => self
+                .tiers
+                .as_ref()
+                .and_then(|tiers| {
+                    tiers
+                        .iter()
+                        .find(|tier| tier.up_to.is_none_or(|up_to| amount <= up_to))
+                })
+                .map(|tier| tier.flat_amount + percentage_of(amount, tier.percentage_bps))
+                .unwrap_or(0),
+        };
+
+        let fee = self.min_fee.map_or(fee, |min_fee| fee.max(min_fee));
+        self.max_fee.map_or(fee, |max_fee| fee.min(max_fee))
+    }
+}
+
+/// Basis points of an amount, rounded half up
+fn percentage_of(amount: Amount, bps: i64) -> Amount {
+    (amount * bps + FULL_PERCENTAGE_BPS / 2) / FULL_PERCENTAGE_BPS
+}
+
+/// A single fee applied to a payment
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeeLine {
+    pub fee_code: String,
+    pub name: String,
+    pub fee_schedule_id: Uuid,
+    pub fee_type: FeeType,
+    pub amount: Amount,
+}
+
+/// Fees charged on a payment or transfer, in addition to its principal
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeeBreakdown {
+    pub total: Amount,
+    pub currency: Currency,
+    pub lines: Vec<FeeLine>,
+}
+
+/// Query parameters for listing fee schedules
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeeScheduleFilter {
+    pub project_id: Option<Uuid>,
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+/// Create fee schedule request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateFeeScheduleRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub fee_code: String,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    /// Limit the schedule to one project; omit for the platform default
+    pub project_id: Option<Uuid>,
+    pub payment_method: Option<PaymentMethod>,
+    #[validate(length(equal = 3))]
+    pub currency: Option<Currency>,
+    pub fee_type: FeeType,
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub flat_amount: Amount,
+    #[serde(default)]
+    #[validate(range(min = 0, max = 10000))]
+    pub percentage_bps: i64,
+    pub tiers: Option<Vec<FeeTier>>,
+    #[validate(range(min = 0))]
+    pub min_fee: Option<Amount>,
+    #[validate(range(min = 0))]
+    pub max_fee: Option<Amount>,
+}
+
+/// Update fee schedule request. A schedule's code, scope and type are fixed;
+/// create a new schedule to change them.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateFeeScheduleRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    #[validate(range(min = 0))]
+    pub flat_amount: Option<Amount>,
+    #[validate(range(min = 0, max = 10000))]
+    pub percentage_bps: Option<i64>,
+    pub tiers: Option<Vec<FeeTier>>,
+    #[validate(range(min = 0))]
+    pub min_fee: Option<Amount>,
+    #[validate(range(min = 0))]
+    pub max_fee: Option<Amount>,
+    pub is_active: Option<bool>,
+}
+
+/// Fee preview request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct FeePreviewRequest {
+    #[validate(range(min = 1))]
+    pub amount: Amount,
+    #[validate(length(equal = 3))]
+    pub currency: Currency,
+    pub payment_method: PaymentMethod,
+}
+
+/// Fee preview response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeePreviewResponse {
+    pub amount: Amount,
+    pub fees: FeeBreakdown,
+    /// Principal plus fees debited from the paying account
+    pub total_debit: Amount,
+}
@@ -0,0 +1,240 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::general_ledger::model::PostingEvent;
+use crate::general_ledger::repository::{post_journal, JournalRequest};
+use crate::payments::model::PaymentMethod;
+use crate::shared::types::{AccountId, Amount, Currency, TransactionId};
+use super::model::{FeeBreakdown, FeeSchedule, FeeScheduleFilter};
+
+const SCHEDULE_COLUMNS: &str = "id, fee_code, name, project_id, payment_method, currency, fee_type, flat_amount,
+    percentage_bps, tiers, min_fee, max_fee, is_active, created_by, created_at, updated_at";
+
+pub struct FeeRepository {
+    pool: PgPool,
+}
+
+impl FeeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_schedule(&self, schedule: &FeeSchedule) -> AppResult<FeeSchedule> {
+        let created = sqlx::query_as::<_, FeeSchedule>(&format!(
+            "INSERT INTO fee_schedules ({SCHEDULE_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+             RETURNING {SCHEDULE_COLUMNS}"
+        ))
+        .bind(schedule.id)
+        .bind(&schedule.fee_code)
+        .bind(&schedule.name)
+        .bind(schedule.project_id)
+        .bind(&schedule.payment_method)
+        .bind(&schedule.currency)
+        .bind(schedule.fee_type)
+        .bind(schedule.flat_amount)
+        .bind(schedule.percentage_bps)
+        .bind(&schedule.tiers)
+        .bind(schedule.min_fee)
+        .bind(schedule.max_fee)
+        .bind(schedule.is_active)
+        .bind(schedule.created_by)
+        .bind(schedule.created_at)
+        .bind(schedule.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn find_schedule_by_id(&self, id: Uuid) -> AppResult<Option<FeeSchedule>> {
+        let schedule = sqlx::query_as::<_, FeeSchedule>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM fee_schedules WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(schedule)
+    }
+
+    pub async fn list_schedules(&self, filter: &FeeScheduleFilter) -> AppResult<Vec<FeeSchedule>> {
+        let schedules = sqlx::query_as::<_, FeeSchedule>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM fee_schedules
+             WHERE ($1::UUID IS NULL OR project_id = $1) AND ($2 OR is_active)
+             ORDER BY fee_code, created_at"
+        ))
+        .bind(filter.project_id)
+        .bind(filter.include_inactive)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(schedules)
+    }
+
+    pub async fn update_schedule(&self, schedule: &FeeSchedule) -> AppResult<FeeSchedule> {
+        let updated = sqlx::query_as::<_, FeeSchedule>(&format!(
+            "UPDATE fee_schedules
+             SET name = $1, flat_amount = $2, percentage_bps = $3, tiers = $4, min_fee = $5, max_fee = $6,
+                 is_active = $7, updated_at = NOW()
+             WHERE id = $8
+             RETURNING {SCHEDULE_COLUMNS}"
+        ))
+        .bind(&schedule.name)
+        .bind(schedule.flat_amount)
+        .bind(schedule.percentage_bps)
+        .bind(&schedule.tiers)
+        .bind(schedule.min_fee)
+        .bind(schedule.max_fee)
+        .bind(schedule.is_active)
+        .bind(schedule.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(updated)
+    }
+
+    /// Active schedules matching a payment, grouped by fee code with the most
+    /// specific schedule (project, then method, then currency) first
+    pub async fn find_applicable_schedules(
+        &self,
+        project_id: Option<Uuid>,
+        payment_method: &PaymentMethod,
+        currency: &str,
+    ) -> AppResult<Vec<FeeSchedule>> {
+        let schedules = sqlx::query_as::<_, FeeSchedule>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM fee_schedules
+             WHERE is_active
+               AND (project_id IS NULL OR project_id = $1)
+               AND (payment_method IS NULL OR payment_method = $2)
+               AND (currency IS NULL OR currency = $3)
+             ORDER BY fee_code,
+                      (project_id IS NOT NULL) DESC,
+                      (payment_method IS NOT NULL) DESC,
+                      (currency IS NOT NULL) DESC,
+                      created_at DESC"
+        ))
+        .bind(project_id)
+        .bind(payment_method)
+        .bind(currency)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(schedules)
+    }
+
+    /// Record the fee charges for an executed payment in the fee ledger and
+    /// book them to the general ledger
+    pub async fn post_charges(
+        &self,
+        payment_id: Uuid,
+        account_id: AccountId,
+        breakdown: &FeeBreakdown,
+    ) -> AppResult<()> {
+        let mut tx = deadline::begin(&self.pool).await?;
+        insert_charges(&mut tx, ChargedOn::Payment(payment_id), account_id, breakdown).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Reverse a payment's posted fee charges, once
+    pub async fn post_reversals(&self, payment_id: Uuid) -> AppResult<u64> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let reversals = sqlx::query_as::<_, (String, Amount, Currency)>(
+            "INSERT INTO fee_postings (id, payment_id, account_id, fee_schedule_id, fee_code, entry_type, amount, currency)
+             SELECT gen_random_uuid(), payment_id, account_id, fee_schedule_id, fee_code, 'reversal', amount, currency
+             FROM fee_postings
+             WHERE payment_id = $1 AND entry_type = 'charge'
+               AND NOT EXISTS (
+                   SELECT 1 FROM fee_postings WHERE payment_id = $1 AND entry_type = 'reversal'
+               )
+             RETURNING fee_code, amount, currency",
+        )
+        .bind(payment_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for (fee_code, amount, currency) in &reversals {
+            post_journal(
+                &mut tx,
+                JournalRequest {
+                    event: PostingEvent::FeeReversed,
+                    fee_code: Some(fee_code),
+                    amount: *amount,
+                    currency,
+                    source_id: payment_id,
+                    description: format!("Reversal of {} on payment {}", fee_code, payment_id),
+                },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(reversals.len() as u64)
+    }
+}
+
+/// What a fee charge was made on
+#[derive(Clone, Copy)]
+enum ChargedOn {
+    Payment(Uuid),
+    Transfer(TransactionId),
+}
+
+/// Record the fee charges for a transfer on the caller's connection, so they
+/// are booked only if the transfer they were charged on commits
+pub async fn post_transfer_charges_in(
+    conn: &mut PgConnection,
+    transaction_id: TransactionId,
+    account_id: AccountId,
+    breakdown: &FeeBreakdown,
+) -> AppResult<()> {
+    insert_charges(conn, ChargedOn::Transfer(transaction_id), account_id, breakdown).await
+}
+
+async fn insert_charges(
+    conn: &mut PgConnection,
+    charged_on: ChargedOn,
+    account_id: AccountId,
+    breakdown: &FeeBreakdown,
+) -> AppResult<()> {
+    let (payment_id, transaction_id, source_id, label) = match charged_on {
+        ChargedOn::Payment(id) => (Some(id), None, id, "payment"),
+        ChargedOn::Transfer(id) => (None, Some(id), id, "transfer"),
+    };
+
+    for line in breakdown.lines.iter().filter(|line| line.amount > 0) {
+        sqlx::query(
+            "INSERT INTO fee_postings
+                (id, payment_id, transaction_id, account_id, fee_schedule_id, fee_code, entry_type, amount, currency)
+             VALUES ($1, $2, $3, $4, $5, $6, 'charge', $7, $8)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(payment_id)
+        .bind(transaction_id)
+        .bind(account_id)
+        .bind(line.fee_schedule_id)
+        .bind(&line.fee_code)
+        .bind(line.amount)
+        .bind(&breakdown.currency)
+        .execute(&mut *conn)
+        .await?;
+
+        post_journal(
+            &mut *conn,
+            JournalRequest {
+                event: PostingEvent::FeeCharged,
+                fee_code: Some(&line.fee_code),
+                amount: line.amount,
+                currency: &breakdown.currency,
+                source_id,
+                description: format!("{} on {} {}", line.name, label, source_id),
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
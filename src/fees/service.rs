@@ -0,0 +1,242 @@
+use chrono::Utc;
+use sqlx::types::Json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::payments::model::PaymentMethod;
+use crate::shared::types::{AccountId, Amount};
+use super::model::{
+    CreateFeeScheduleRequest, FeeBreakdown, FeeLine, FeePreviewRequest, FeePreviewResponse, FeeSchedule,
+    FeeScheduleFilter, FeeTier, FeeType, UpdateFeeScheduleRequest, FULL_PERCENTAGE_BPS,
+};
+use super::repository::FeeRepository;
+
+/// Calculates payment fees from the configured schedules and posts them to
+/// the fee ledger
+pub struct FeeEngine {
+    repository: FeeRepository,
+}
+
+impl FeeEngine {
+    pub fn new(repository: FeeRepository) -> Self {
+        Self { repository }
+    }
+
+    /// Fees for a payment. For each fee code the most specific matching
+    /// schedule applies; different fee codes add up.
+    pub async fn calculate(
+        &self,
+        project_id: Option<Uuid>,
+        payment_method: &PaymentMethod,
+        currency: &str,
+        amount: Amount,
+    ) -> AppResult<FeeBreakdown> {
+        let schedules = self
+            .repository
+            .find_applicable_schedules(project_id, payment_method, currency)
+            .await?;
+
+        let mut lines: Vec<FeeLine> = Vec::new();
+        for schedule in schedules {
+            if lines.iter().any(|line| line.fee_code == schedule.fee_code) {
+                continue;
+            }
+            lines.push(FeeLine {
+                amount: schedule.fee_for(amount),
+                fee_code: schedule.fee_code,
+                name: schedule.name,
+                fee_schedule_id: schedule.id,
+                fee_type: schedule.fee_type,
+            });
+        }
+
+        Ok(FeeBreakdown {
+            total: lines.iter().map(|line| line.amount).sum(),
+            currency: currency.to_string(),
+            lines,
+        })
+    }
+
+    /// Preview the fees a payment would incur
+    pub async fn preview(&self, project_id: Uuid, request: FeePreviewRequest) -> AppResult<FeePreviewResponse> {
+        let fees = self
+            .calculate(Some(project_id), &request.payment_method, &request.currency, request.amount)
+            .await?;
+
+        Ok(FeePreviewResponse {
+            amount: request.amount,
+            total_debit: request.amount + fees.total,
+            fees,
+        })
+    }
+
+    /// Post a payment's fees to the fee ledger when the payment executes
+    pub async fn post_charges(&self, payment_id: Uuid, account_id: AccountId, fees: &FeeBreakdown) -> AppResult<()> {
+        if fees.total == 0 {
+            return Ok(());
+        }
+        self.repository.post_charges(payment_id, account_id, fees).await
+    }
+
+    /// Reverse any fees posted for a payment that will not go ahead
+    pub async fn reverse_charges(&self, payment_id: Uuid) -> AppResult<()> {
+        let reversed = self.repository.post_reversals(payment_id).await?;
+        if reversed > 0 {
+            tracing::info!(payment_id = %payment_id, "Reversed {} fee postings", reversed);
+        }
+        Ok(())
+    }
+}
+
+/// Admin management of fee schedules
+pub struct FeeScheduleService {
+    repository: FeeRepository,
+    audit_logger: AuditLogger,
+}
+
+impl FeeScheduleService {
+    pub fn new(repository: FeeRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            audit_logger,
+        }
+    }
+
+    /// List fee schedules
+    pub async fn list_schedules(&self, filter: FeeScheduleFilter) -> AppResult<Vec<FeeSchedule>> {
+        self.repository.list_schedules(&filter).await
+    }
+
+    /// Create a fee schedule
+    pub async fn create_schedule(&self, request: CreateFeeScheduleRequest, actor_id: Uuid) -> AppResult<FeeSchedule> {
+        let now = Utc::now();
+        let schedule = FeeSchedule {
+            id: Uuid::new_v4(),
+            fee_code: request.fee_code.trim().to_lowercase(),
+            name: request.name,
+            project_id: request.project_id,
+            payment_method: request.payment_method,
+            currency: request.currency.map(|currency| currency.to_uppercase()),
+            fee_type: request.fee_type,
+            flat_amount: request.flat_amount,
+            percentage_bps: request.percentage_bps,
+            tiers: request.tiers.map(Json),
+            min_fee: request.min_fee,
+            max_fee: request.max_fee,
+            is_active: true,
+            created_by: Some(actor_id),
+            created_at: now,
+            updated_at: now,
+        };
+        validate_schedule(&schedule)?;
+
+        let schedule = self.repository.create_schedule(&schedule).await?;
+        self.log_change(&schedule, "create", actor_id).await;
+        Ok(schedule)
+    }
+
+    /// Update a fee schedule's amounts, bounds or active flag
+    pub async fn update_schedule(
+        &self,
+        id: Uuid,
+        request: UpdateFeeScheduleRequest,
+        actor_id: Uuid,
+    ) -> AppResult<FeeSchedule> {
+        let mut schedule = self
+            .repository
+            .find_schedule_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Fee schedule not found".to_string()))?;
+
+        if let Some(name) = request.name {
+            schedule.name = name;
+        }
+        if let Some(flat_amount) = request.flat_amount {
+            schedule.flat_amount = flat_amount;
+        }
+        if let Some(percentage_bps) = request.percentage_bps {
+            schedule.percentage_bps = percentage_bps;
+        }
+        if let Some(tiers) = request.tiers {
+            schedule.tiers = Some(Json(tiers));
+        }
+        if request.min_fee.is_some() {
+            schedule.min_fee = request.min_fee;
+        }
+        if request.max_fee.is_some() {
+            schedule.max_fee = request.max_fee;
+        }
+        if let Some(is_active) = request.is_active {
+            schedule.is_active = is_active;
+        }
+        validate_schedule(&schedule)?;
+
+        let schedule = self.repository.update_schedule(&schedule).await?;
+        self.log_change(&schedule, "update", actor_id).await;
+        Ok(schedule)
+    }
+
+    async fn log_change(&self, schedule: &FeeSchedule, action: &str, actor_id: Uuid) {
+        let event = AuditEvent::new(AuditEventType::FeeScheduleChanged)
+            .user_id(actor_id)
+            .resource(format!("fee_schedule:{}", schedule.id))
+            .action(action.to_string())
+            .metadata("fee_code".to_string(), serde_json::json!(schedule.fee_code))
+            .metadata("fee_type".to_string(), serde_json::json!(schedule.fee_type))
+            .metadata("is_active".to_string(), serde_json::json!(schedule.is_active))
+            .compliance_tag("FEES".to_string());
+        self.audit_logger.log(event).await;
+    }
+}
+
+fn validate_schedule(schedule: &FeeSchedule) -> AppResult<()> {
+    let invalid = |message: &str| Err(AppError::Validation(message.to_string()));
+
+    match schedule.fee_type {
+        FeeType::Flat if schedule.flat_amount <= 0 => {
+            return invalid("Flat fee schedules require a positive flat_amount");
+        }
+        FeeType::Percentage if !(1..=FULL_PERCENTAGE_BPS).contains(&schedule.percentage_bps) => {
+            return invalid("Percentage fee schedules require percentage_bps between 1 and 10000");
+        }
+        FeeType::Tiered => validate_tiers(schedule.tiers.as_deref().map(Vec::as_slice).unwrap_or_default())?,
+        _ => {}
+    }
+
+    if let (Some(min_fee), Some(max_fee)) = (schedule.min_fee, schedule.max_fee) {
+        if min_fee > max_fee {
+            return invalid("min_fee cannot exceed max_fee");
+        }
+    }
+    Ok(())
+}
+
+/// Tiers must have ascending upper bounds, with only the last band open-ended
+fn validate_tiers(tiers: &[FeeTier]) -> AppResult<()> {
+    let invalid = |message: &str| Err(AppError::Validation(message.to_string()));
+
+    let Some((last, bounded)) = tiers.split_last() else {
+        return invalid("Tiered fee schedules require at least one tier");
+    };
+    if last.up_to.is_some() {
+        return invalid("The last fee tier must have no upper bound");
+    }
+
+    let mut previous: Option<Amount> = None;
+    for tier in bounded {
+        let Some(up_to) = tier.up_to else {
+            return invalid("Only the last fee tier may omit its upper bound");
+        };
+        if previous.is_some_and(|previous| up_to <= previous) {
+            return invalid("Fee tier upper bounds must be ascending");
+        }
+        previous = Some(up_to);
+    }
+
+    if tiers.iter().any(|tier| {
+        tier.flat_amount < 0 || !(0..=FULL_PERCENTAGE_BPS).contains(&tier.percentage_bps)
+    }) {
+        return invalid("Fee tiers require non-negative amounts and percentage_bps up to 10000");
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{CreateFxQuoteRequest, FxQuoteResponse};
+use super::rates;
+use super::repository::FxRepository;
+use super::service::FxQuoteService;
+
+pub(crate) fn fx_quote_service(state: &AppState) -> AppResult<FxQuoteService> {
+    Ok(FxQuoteService::new(
+        FxRepository::new(state.postgres.clone()),
+        rates::from_config(&state.config, &state.circuit_breakers, &state.http_clients)?,
+        chrono::Duration::seconds(state.config.fx_quote_ttl_seconds),
+    ))
+}
+
+/// Lock a conversion rate for an amount. Transfers and payment intents
+/// referencing the quote before it expires convert at its rate.
+#[utoipa::path(
+    post,
+    path = "/api/v1/fx/quotes",
+    tag = "fx",
+    request_body = CreateFxQuoteRequest,
+    responses(
+        (status = 201, description = "Rate locked until the quote expires", body = FxQuoteResponse),
+        (status = 400, description = "Invalid request, or no rate is available for the pair"),
+        (status = 502, description = "Rate source unavailable")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_quote(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<CreateFxQuoteRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<FxQuoteResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let quote = fx_quote_service(&state)?
+        .create_quote(claims.tenant_id, Some(claims.project_id), request)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("FX quote created successfully", FxQuoteResponse::from(quote))),
+    ))
+}
+
+/// Get an FX quote
+#[utoipa::path(
+    get,
+    path = "/api/v1/fx/quotes/{id}",
+    tag = "fx",
+    params(("id" = Uuid, Path, description = "FX quote ID")),
+    responses(
+        (status = 200, description = "The quote, and when it was used", body = FxQuoteResponse),
+        (status = 404, description = "FX quote not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_quote(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<FxQuoteResponse>>> {
+    let quote = fx_quote_service(&state)?.get(id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("FX quote retrieved successfully", FxQuoteResponse::from(quote))))
+}
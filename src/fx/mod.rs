@@ -0,0 +1,14 @@
+pub mod controller;
+pub mod model;
+pub mod rates;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/quotes", post(controller::create_quote))
+        .route("/quotes/:id", get(controller::get_quote))
+}
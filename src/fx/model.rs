@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::{Amount, Currency, TenantId};
+use super::rates::format_rate;
+
+/// A conversion of one amount at a rate locked until the quote expires.
+/// Each quote is used at most once.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FxQuote {
+    pub id: Uuid,
+    pub tenant_id: TenantId,
+    pub project_id: Option<Uuid>,
+    pub source_currency: Currency,
+    pub target_currency: Currency,
+    pub source_amount: Amount,
+    pub target_amount: Amount,
+    /// Target currency per unit of source currency, in parts per billion
+    pub rate_ppb: i64,
+    /// The expired quote this one priced again
+    pub requoted_from: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FxQuote {
+    /// Why the quote cannot price a conversion of `amount` from `currency`,
+    /// if it cannot
+    pub fn mismatch(&self, currency: &str, amount: Amount) -> Option<String> {
+        (!self.source_currency.eq_ignore_ascii_case(currency) || self.source_amount != amount).then(|| {
+            format!(
+                "FX quote is for {} {}, not {} {}",
+                self.source_amount,
+                self.source_currency,
+                amount,
+                currency.to_uppercase()
+            )
+        })
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Create FX quote request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateFxQuoteRequest {
+    #[validate(length(equal = 3))]
+    pub source_currency: Currency,
+    #[validate(length(equal = 3))]
+    pub target_currency: Currency,
+    /// Amount to convert, in the source currency
+    #[validate(range(min = 1))]
+    pub source_amount: Amount,
+}
+
+/// FX quote response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FxQuoteResponse {
+    pub id: Uuid,
+    pub source_currency: Currency,
+    pub target_currency: Currency,
+    pub source_amount: Amount,
+    /// Amount the source amount converts to, rounded half up
+    pub target_amount: Amount,
+    /// Target currency per unit of source currency, as a decimal
+    pub rate: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requoted_from: Option<Uuid>,
+    /// The rate is guaranteed until then
+    pub expires_at: DateTime<Utc>,
+    /// When a transfer or payment intent used the quote
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<FxQuote> for FxQuoteResponse {
+    fn from(quote: FxQuote) -> Self {
+        Self {
+            id: quote.id,
+            source_currency: quote.source_currency,
+            target_currency: quote.target_currency,
+            source_amount: quote.source_amount,
+            target_amount: quote.target_amount,
+            rate: format_rate(quote.rate_ppb),
+            requoted_from: quote.requoted_from,
+            expires_at: quote.expires_at,
+            used_at: quote.used_at,
+            created_at: quote.created_at,
+        }
+    }
+}
+
+/// The conversion a quote priced, as applied to a transfer
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FxConversion {
+    pub quote_id: Uuid,
+    pub target_currency: Currency,
+    pub target_amount: Amount,
+    pub rate: String,
+}
+
+impl From<&FxQuote> for FxConversion {
+    fn from(quote: &FxQuote) -> Self {
+        Self {
+            quote_id: quote.id,
+            target_currency: quote.target_currency.clone(),
+            target_amount: quote.target_amount,
+            rate: format_rate(quote.rate_ppb),
+        }
+    }
+}
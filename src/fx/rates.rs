@@ -0,0 +1,243 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::core::circuit_breaker::{CircuitBreaker, CircuitBreakerSettings, CircuitBreakers};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use crate::shared::types::Amount;
+
+/// Rates are kept in parts per billion: target currency units per
+/// 1,000,000,000 units of the source currency
+pub const RATE_SCALE: i64 = 1_000_000_000;
+
+/// Decimal places of a rate at `RATE_SCALE`
+const RATE_DECIMALS: usize = 9;
+
+/// Quotes rates between currencies
+#[async_trait]
+pub trait RateSource: Send + Sync {
+    /// The rate converting `source` into `target` in parts per billion, or
+    /// `None` if the pair is not quoted
+    async fn rate(&self, source: &str, target: &str) -> AppResult<Option<i64>>;
+}
+
+/// Used when no rate source is configured: currencies cannot be converted
+pub struct UnavailableRateSource;
+
+#[async_trait]
+impl RateSource for UnavailableRateSource {
+    async fn rate(&self, _source: &str, _target: &str) -> AppResult<Option<i64>> {
+        Err(AppError::BadRequest("Currency conversion is not available".to_string()))
+    }
+}
+
+/// Fixed rates from configuration. A pair configured one way is quoted the
+/// other way at the inverse rate.
+pub struct StaticRateSource {
+    rates: HashMap<(String, String), i64>,
+}
+
+impl StaticRateSource {
+    /// Parse comma-separated `SOURCE/TARGET=RATE` entries, e.g. `EUR/USD=1.0857`
+    pub fn parse(value: &str) -> AppResult<Self> {
+        let mut rates = HashMap::new();
+        for entry in value.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let invalid = || AppError::Internal(format!("Invalid FX_RATES entry '{}', expected SOURCE/TARGET=RATE", entry));
+            let (pair, rate) = entry.split_once('=').ok_or_else(invalid)?;
+            let (source, target) = pair.split_once('/').ok_or_else(invalid)?;
+            let rate = parse_rate(rate.trim()).ok_or_else(invalid)?;
+            rates.insert((source.trim().to_uppercase(), target.trim().to_uppercase()), rate);
+        }
+        Ok(Self { rates })
+    }
+}
+
+#[async_trait]
+impl RateSource for StaticRateSource {
+    async fn rate(&self, source: &str, target: &str) -> AppResult<Option<i64>> {
+        if let Some(rate) = self.rates.get(&(source.to_string(), target.to_string())) {
+            return Ok(Some(*rate));
+        }
+        Ok(self
+            .rates
+            .get(&(target.to_string(), source.to_string()))
+            .map(|rate| inverse(*rate)))
+    }
+}
+
+/// Name of the rate source's circuit breaker
+const FX_RATES_BREAKER: &str = "fx_rates";
+
+/// Queries an HTTP rate API with `GET ?source=EUR&target=USD` and a bearer
+/// API key, answering `{rate}` as a decimal string or number, or 404 for
+/// pairs it does not quote
+pub struct HttpRateSource {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    breaker: CircuitBreaker,
+}
+
+impl HttpRateSource {
+    pub fn new(api_url: String, api_key: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            breaker: CircuitBreaker::new(FX_RATES_BREAKER, CircuitBreakerSettings::default()),
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    async fn lookup(&self, source: &str, target: &str) -> AppResult<Option<i64>> {
+        let request = self
+            .client
+            .get(&self.api_url)
+            .bearer_auth(&self.api_key)
+            .query(&[("source", source), ("target", target)]);
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("FX rate request failed: {}", e)))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!("FX rate source returned {}", response.status())));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Invalid FX rate response: {}", e)))?;
+        let rate = match &body["rate"] {
+            serde_json::Value::String(rate) => parse_rate(rate),
+            serde_json::Value::Number(rate) => parse_rate(&rate.to_string()),
+            _ => None,
+        };
+        rate.map(Some)
+            .ok_or_else(|| AppError::ExternalService("Invalid FX rate response: no rate".to_string()))
+    }
+}
+
+#[async_trait]
+impl RateSource for HttpRateSource {
+    async fn rate(&self, source: &str, target: &str) -> AppResult<Option<i64>> {
+        self.breaker.call(self.lookup(source, target)).await
+    }
+}
+
+/// Build the rate source selected by `FX_RATES_PROVIDER`
+pub fn from_config(
+    config: &Config,
+    breakers: &CircuitBreakers,
+    clients: &HttpClients,
+) -> AppResult<Arc<dyn RateSource>> {
+    match config.fx_rates_provider.as_str() {
+        "none" => Ok(Arc::new(UnavailableRateSource)),
+        "static" => Ok(Arc::new(StaticRateSource::parse(&config.fx_rates)?)),
+        "http" => {
+            let missing = |name: &str| AppError::Internal(format!("{} is required for the http rate source", name));
+            Ok(Arc::new(
+                HttpRateSource::new(
+                    config.fx_rates_api_url.clone().ok_or_else(|| missing("FX_RATES_API_URL"))?,
+                    config.fx_rates_api_key.clone().ok_or_else(|| missing("FX_RATES_API_KEY"))?,
+                )
+                .with_circuit_breaker(breakers.get(FX_RATES_BREAKER))
+                .with_http_client(clients.get(FX_RATES_BREAKER)),
+            ))
+        }
+        other => Err(AppError::Internal(format!("Unknown FX rate provider '{}'", other))),
+    }
+}
+
+/// Parse a positive decimal rate such as `1.0857` into parts per billion.
+/// Digits past the ninth decimal place are dropped.
+pub fn parse_rate(value: &str) -> Option<i64> {
+    let (units, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if units.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !units.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let units: i64 = if units.is_empty() { 0 } else { units.parse().ok()? };
+    let fraction: String = fraction.chars().chain(std::iter::repeat('0')).take(RATE_DECIMALS).collect();
+    let rate = units.checked_mul(RATE_SCALE)?.checked_add(fraction.parse().ok()?)?;
+    (rate > 0).then_some(rate)
+}
+
+/// A rate in parts per billion as a decimal, without trailing zeros
+pub fn format_rate(rate: i64) -> String {
+    let fraction = format!("{:09}", rate % RATE_SCALE);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        (rate / RATE_SCALE).to_string()
+    } else {
+        format!("{}.{}", rate / RATE_SCALE, fraction)
+    }
+}
+
+/// `amount` converted at `rate`, rounded half up
+pub fn convert(amount: Amount, rate: i64) -> Amount {
+    let scale = RATE_SCALE as i128;
+    ((amount as i128 * rate as i128 + scale / 2) / scale) as Amount
+}
+
+/// The rate quoting a pair the other way round, rounded half up
+fn inverse(rate: i64) -> i64 {
+    let scale = RATE_SCALE as i128;
+    ((scale * scale + rate as i128 / 2) / rate as i128) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_round_trip_as_decimals() {
+        assert_eq!(parse_rate("1.0857"), Some(1_085_700_000));
+        assert_eq!(parse_rate("150"), Some(150 * RATE_SCALE));
+        assert_eq!(parse_rate(".5"), Some(500_000_000));
+        assert_eq!(parse_rate("0.0066612345678"), Some(6_661_234));
+        assert_eq!(parse_rate("0"), None);
+        assert_eq!(parse_rate("-1.2"), None);
+        assert_eq!(parse_rate("1.2.3"), None);
+        assert_eq!(parse_rate(""), None);
+
+        assert_eq!(format_rate(1_085_700_000), "1.0857");
+        assert_eq!(format_rate(150 * RATE_SCALE), "150");
+        assert_eq!(format_rate(6_661_234), "0.006661234");
+    }
+
+    #[test]
+    fn amounts_convert_rounding_half_up() {
+        assert_eq!(convert(10_000, 1_085_700_000), 10_857);
+        assert_eq!(convert(1, 1_500_000_000), 2);
+        assert_eq!(convert(1, 1_499_999_999), 1);
+    }
+
+    #[tokio::test]
+    async fn static_rates_are_quoted_both_ways() {
+        let rates = StaticRateSource::parse(" eur/usd=1.25 , GBP/USD=1.27").unwrap();
+        assert_eq!(rates.rate("EUR", "USD").await.unwrap(), Some(1_250_000_000));
+        assert_eq!(rates.rate("USD", "EUR").await.unwrap(), Some(800_000_000));
+        assert_eq!(rates.rate("EUR", "GBP").await.unwrap(), None);
+
+        assert!(StaticRateSource::parse("EUR-USD=1.25").is_err());
+        assert!(StaticRateSource::parse("EUR/USD=abc").is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::shared::types::TenantId;
+use super::model::FxQuote;
+
+const QUOTE_COLUMNS: &str = "id, tenant_id, project_id, source_currency, target_currency, source_amount,
+    target_amount, rate_ppb, requoted_from, expires_at, used_at, created_at";
+
+pub struct FxRepository {
+    pool: PgPool,
+}
+
+impl FxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_quote(&self, quote: &FxQuote) -> AppResult<FxQuote> {
+        let created = sqlx::query_as::<_, FxQuote>(&format!(
+            "INSERT INTO fx_quotes ({QUOTE_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+             RETURNING {QUOTE_COLUMNS}"
+        ))
+        .bind(quote.id)
+        .bind(quote.tenant_id)
+        .bind(quote.project_id)
+        .bind(&quote.source_currency)
+        .bind(&quote.target_currency)
+        .bind(quote.source_amount)
+        .bind(quote.target_amount)
+        .bind(quote.rate_ppb)
+        .bind(quote.requoted_from)
+        .bind(quote.expires_at)
+        .bind(quote.used_at)
+        .bind(quote.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    /// Find a quote belonging to a tenant
+    pub async fn find_quote_for_tenant(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<FxQuote>> {
+        let quote = sqlx::query_as::<_, FxQuote>(&format!(
+            "SELECT {QUOTE_COLUMNS} FROM fx_quotes WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(quote)
+    }
+
+    /// Mark a quote used, if it is unused and unexpired; `None` otherwise
+    pub async fn use_quote(&self, id: Uuid) -> AppResult<Option<FxQuote>> {
+        let quote = sqlx::query_as::<_, FxQuote>(&use_quote_query())
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(quote)
+    }
+
+    /// Make a used quote available again, until it expires
+    pub async fn release_quote(&self, id: Uuid) -> AppResult<()> {
+        sqlx::query("UPDATE fx_quotes SET used_at = NULL WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Mark a quote used within the transaction converting its amount, so the
+/// quote is only used up if that transaction commits; `None` if it is used
+/// or expired
+pub async fn use_quote_in(tx: &mut Transaction<'static, Postgres>, id: Uuid) -> AppResult<Option<FxQuote>> {
+    let quote = sqlx::query_as::<_, FxQuote>(&use_quote_query())
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+    Ok(quote)
+}
+
+fn use_quote_query() -> String {
+    format!(
+        "UPDATE fx_quotes SET used_at = NOW()
+         WHERE id = $1 AND used_at IS NULL AND expires_at > NOW()
+         RETURNING {QUOTE_COLUMNS}"
+    )
+}
@@ -0,0 +1,142 @@
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::{Amount, TenantId};
+use super::model::{CreateFxQuoteRequest, FxQuote};
+use super::rates::{convert, RateSource};
+use super::repository::FxRepository;
+
+/// Why a quote that looked usable could not be used up
+pub const QUOTE_TAKEN: &str = "FX quote was used or expired in the meantime";
+
+/// Locks conversion rates in quotes, and checks them for the transfers and
+/// payment intents that reference them.
+///
+/// A quote prices exactly one amount of its source currency, and only until
+/// it expires. Callers may ask for an expired quote to be priced again at
+/// the current rate instead of being refused; the new quote records the one
+/// it replaced.
+pub struct FxQuoteService {
+    repository: FxRepository,
+    rates: Arc<dyn RateSource>,
+    ttl: Duration,
+}
+
+impl FxQuoteService {
+    pub fn new(repository: FxRepository, rates: Arc<dyn RateSource>, ttl: Duration) -> Self {
+        Self { repository, rates, ttl }
+    }
+
+    pub async fn create_quote(
+        &self,
+        tenant_id: TenantId,
+        project_id: Option<Uuid>,
+        request: CreateFxQuoteRequest,
+    ) -> AppResult<FxQuote> {
+        let quote = self.price(tenant_id, project_id, &request).await?;
+        self.repository.create_quote(&quote).await
+    }
+
+    pub async fn get(&self, quote_id: Uuid, tenant_id: TenantId) -> AppResult<FxQuote> {
+        self.repository
+            .find_quote_for_tenant(quote_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("FX quote not found".to_string()))
+    }
+
+    /// Check that a quote converts `amount` of `currency` and is still
+    /// usable, without using it up. With `requote`, an expired quote is
+    /// replaced by a new one at the current rate, which is returned instead.
+    pub async fn resolve(
+        &self,
+        quote_id: Uuid,
+        tenant_id: TenantId,
+        currency: &str,
+        amount: Amount,
+        requote: bool,
+    ) -> AppResult<FxQuote> {
+        let quote = self.get(quote_id, tenant_id).await?;
+        if let Some(mismatch) = quote.mismatch(currency, amount) {
+            return Err(AppError::Validation(mismatch));
+        }
+        if quote.used_at.is_some() {
+            return Err(AppError::Conflict("FX quote has already been used".to_string()));
+        }
+        if quote.is_expired(Utc::now()) {
+            if !requote {
+                return Err(AppError::Conflict("FX quote has expired".to_string()));
+            }
+            return self.requote(&quote).await;
+        }
+        Ok(quote)
+    }
+
+    /// Resolve a quote as above and use it up at once
+    pub async fn redeem(
+        &self,
+        quote_id: Uuid,
+        tenant_id: TenantId,
+        currency: &str,
+        amount: Amount,
+        requote: bool,
+    ) -> AppResult<FxQuote> {
+        let quote = self.resolve(quote_id, tenant_id, currency, amount, requote).await?;
+        self.repository
+            .use_quote(quote.id)
+            .await?
+            .ok_or_else(|| AppError::Conflict(QUOTE_TAKEN.to_string()))
+    }
+
+    /// Make a redeemed quote available again, when what used it failed
+    pub async fn release(&self, quote: &FxQuote) -> AppResult<()> {
+        self.repository.release_quote(quote.id).await
+    }
+
+    /// Price an expired quote's conversion again
+    async fn requote(&self, expired: &FxQuote) -> AppResult<FxQuote> {
+        let request = CreateFxQuoteRequest {
+            source_currency: expired.source_currency.clone(),
+            target_currency: expired.target_currency.clone(),
+            source_amount: expired.source_amount,
+        };
+        let quote = self.price(expired.tenant_id, expired.project_id, &request).await?;
+        let quote = FxQuote {
+            requoted_from: Some(expired.id),
+            ..quote
+        };
+        self.repository.create_quote(&quote).await
+    }
+
+    async fn price(
+        &self,
+        tenant_id: TenantId,
+        project_id: Option<Uuid>,
+        request: &CreateFxQuoteRequest,
+    ) -> AppResult<FxQuote> {
+        let source = request.source_currency.to_uppercase();
+        let target = request.target_currency.to_uppercase();
+        if source == target {
+            return Err(AppError::Validation("Source and target currencies must differ".to_string()));
+        }
+        let rate = self.rates.rate(&source, &target).await?.ok_or_else(|| {
+            AppError::BadRequest(format!("No exchange rate is available from {} to {}", source, target))
+        })?;
+
+        let now = Utc::now();
+        Ok(FxQuote {
+            id: Uuid::new_v4(),
+            tenant_id,
+            project_id,
+            source_currency: source,
+            target_currency: target,
+            source_amount: request.source_amount,
+            target_amount: convert(request.source_amount, rate),
+            rate_ppb: rate,
+            requoted_from: None,
+            expires_at: now + self.ttl,
+            used_at: None,
+            created_at: now,
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{
+    CreateGlAccountRequest, GlAccount, PostingRule, SetPostingRuleRequest, TrialBalance, TrialBalanceQuery,
+};
+use super::repository::GeneralLedgerRepository;
+use super::service::GeneralLedgerService;
+
+fn general_ledger_service(state: &AppState) -> GeneralLedgerService {
+    GeneralLedgerService::new(
+        GeneralLedgerRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// List the chart of accounts (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/gl/accounts",
+    tag = "general-ledger",
+    responses(
+        (status = 200, description = "GL accounts", body = [GlAccount]),
+        (status = 403, description = "Caller lacks the general ledger permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_gl_accounts(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+) -> AppResult<Json<ApiResponse<Vec<GlAccount>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_general_ledger(), ip, "gl_accounts".to_string())
+        .await?;
+
+    let accounts = general_ledger_service(&state).list_accounts().await?;
+    Ok(Json(ApiResponse::success("GL accounts retrieved successfully", accounts)))
+}
+
+/// Add an account to the chart of accounts (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/gl/accounts",
+    tag = "general-ledger",
+    request_body = CreateGlAccountRequest,
+    responses(
+        (status = 201, description = "GL account created", body = GlAccount),
+        (status = 403, description = "Caller lacks the general ledger permission"),
+        (status = 409, description = "An account with the code already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_gl_account(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<CreateGlAccountRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<GlAccount>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_general_ledger(), ip, "gl_accounts".to_string())
+        .await?;
+
+    let account = general_ledger_service(&state)
+        .create_account(request, claims.developer_id)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("GL account created successfully", account)),
+    ))
+}
+
+/// List posting rules (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/gl/posting-rules",
+    tag = "general-ledger",
+    responses(
+        (status = 200, description = "Posting rules", body = [PostingRule]),
+        (status = 403, description = "Caller lacks the general ledger permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_posting_rules(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+) -> AppResult<Json<ApiResponse<Vec<PostingRule>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_general_ledger(), ip, "gl_posting_rules".to_string())
+        .await?;
+
+    let rules = general_ledger_service(&state).list_rules().await?;
+    Ok(Json(ApiResponse::success("Posting rules retrieved successfully", rules)))
+}
+
+/// Set the GL accounts an event is booked to (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/gl/posting-rules",
+    tag = "general-ledger",
+    request_body = SetPostingRuleRequest,
+    responses(
+        (status = 200, description = "Posting rule set", body = PostingRule),
+        (status = 403, description = "Caller lacks the general ledger permission"),
+        (status = 404, description = "GL account not found or inactive")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_posting_rule(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<SetPostingRuleRequest>,
+) -> AppResult<Json<ApiResponse<PostingRule>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_general_ledger(), ip, "gl_posting_rules".to_string())
+        .await?;
+
+    let rule = general_ledger_service(&state)
+        .set_rule(request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Posting rule set successfully", rule)))
+}
+
+/// Trial balance of the general ledger (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/gl/trial-balance",
+    tag = "general-ledger",
+    params(TrialBalanceQuery),
+    responses(
+        (status = 200, description = "Trial balance", body = TrialBalance),
+        (status = 403, description = "Caller lacks the general ledger permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_trial_balance(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<TrialBalanceQuery>,
+) -> AppResult<Json<ApiResponse<TrialBalance>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_general_ledger(), ip, "gl_trial_balance".to_string())
+        .await?;
+
+    let trial_balance = general_ledger_service(&state)
+        .trial_balance(query.as_of, query.currency)
+        .await?;
+    Ok(Json(ApiResponse::success("Trial balance retrieved successfully", trial_balance)))
+}
@@ -0,0 +1,16 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post, put}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/gl/accounts", get(controller::list_gl_accounts))
+        .route("/gl/accounts", post(controller::create_gl_account))
+        .route("/gl/posting-rules", get(controller::list_posting_rules))
+        .route("/gl/posting-rules", put(controller::set_posting_rule))
+        .route("/gl/trial-balance", get(controller::get_trial_balance))
+}
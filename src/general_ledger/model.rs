@@ -0,0 +1,120 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::{Amount, Currency};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "gl_account_type", rename_all = "snake_case")]
+pub enum GlAccountType {
+    Asset,
+    Liability,
+    Income,
+    Expense,
+}
+
+impl GlAccountType {
+    /// Whether the account's balance grows with debits
+    pub fn is_debit_normal(&self) -> bool {
+        matches!(self, GlAccountType::Asset | GlAccountType::Expense)
+    }
+}
+
+/// Business events booked to the general ledger
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "gl_posting_event", rename_all = "snake_case")]
+pub enum PostingEvent {
+    FeeCharged,
+    FeeReversed,
+    InterestCapitalized,
+}
+
+/// An account in the chart of accounts
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct GlAccount {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub account_type: GlAccountType,
+    pub description: Option<String>,
+    pub is_system: bool,
+    pub is_active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Which GL accounts an event is booked to
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PostingRule {
+    pub id: Uuid,
+    pub event: PostingEvent,
+    /// Fee code the rule is limited to; the default rule for the event has none
+    pub fee_code: Option<String>,
+    pub debit_account_code: String,
+    pub credit_account_code: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One line of the trial balance
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TrialBalanceLine {
+    pub code: String,
+    pub name: String,
+    pub account_type: GlAccountType,
+    pub currency: Currency,
+    pub total_debits: Amount,
+    pub total_credits: Amount,
+    /// Net balance on the account's normal side
+    pub balance: Amount,
+}
+
+/// Debit and credit totals of every GL account up to a date
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrialBalance {
+    /// Entries up to the end of this day are included
+    pub as_of: NaiveDate,
+    pub lines: Vec<TrialBalanceLine>,
+    pub total_debits: Amount,
+    pub total_credits: Amount,
+    /// Whether debits equal credits in every currency
+    pub balanced: bool,
+}
+
+/// Query parameters for the trial balance
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TrialBalanceQuery {
+    /// Defaults to today
+    pub as_of: Option<NaiveDate>,
+    pub currency: Option<Currency>,
+}
+
+/// Create GL account request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateGlAccountRequest {
+    #[validate(length(min = 1, max = 20))]
+    pub code: String,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    pub account_type: GlAccountType,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
+
+/// Set the posting rule for an event, optionally limited to one fee code
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetPostingRuleRequest {
+    pub event: PostingEvent,
+    #[validate(length(min = 1, max = 50))]
+    pub fee_code: Option<String>,
+    #[validate(length(min = 1, max = 20))]
+    pub debit_account_code: String,
+    #[validate(length(min = 1, max = 20))]
+    pub credit_account_code: String,
+}
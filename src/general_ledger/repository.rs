@@ -0,0 +1,193 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::Amount;
+use super::model::{GlAccount, PostingEvent, PostingRule, TrialBalanceLine};
+
+const ACCOUNT_COLUMNS: &str =
+    "id, code, name, account_type, description, is_system, is_active, created_by, created_at";
+
+const RULE_SELECT: &str = "SELECT r.id, r.event, r.fee_code, debit.code AS debit_account_code,
+        credit.code AS credit_account_code, r.created_by, r.created_at, r.updated_at
+    FROM gl_posting_rules r
+    JOIN gl_accounts debit ON debit.id = r.debit_account_id
+    JOIN gl_accounts credit ON credit.id = r.credit_account_id";
+
+/// What a journal books, before it is routed to GL accounts
+pub struct JournalRequest<'a> {
+    pub event: PostingEvent,
+    pub fee_code: Option<&'a str>,
+    pub amount: Amount,
+    pub currency: &'a str,
+    pub source_id: Uuid,
+    pub description: String,
+}
+
+/// Book a balanced journal on the caller's connection, so it commits or rolls
+/// back together with the movement it records. The posting rule for the event
+/// (and fee code, if any) chooses the debit and credit accounts.
+pub async fn post_journal(conn: &mut PgConnection, journal: JournalRequest<'_>) -> AppResult<()> {
+    if journal.amount == 0 {
+        return Ok(());
+    }
+
+    let accounts = sqlx::query_as::<_, (Uuid, Uuid)>(
+        "SELECT debit_account_id, credit_account_id FROM gl_posting_rules
+         WHERE event = $1 AND (fee_code = $2 OR fee_code IS NULL)
+         ORDER BY fee_code IS NULL
+         LIMIT 1",
+    )
+    .bind(journal.event)
+    .bind(journal.fee_code)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some((debit_account_id, credit_account_id)) = accounts else {
+        return Err(AppError::Internal(format!("No posting rule for {:?}", journal.event)));
+    };
+
+    let journal_id = Uuid::new_v4();
+    for (account_id, debit, credit) in [
+        (debit_account_id, journal.amount, 0),
+        (credit_account_id, 0, journal.amount),
+    ] {
+        sqlx::query(
+            "INSERT INTO gl_entries (journal_id, gl_account_id, debit, credit, currency, event, source_id, description)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        )
+        .bind(journal_id)
+        .bind(account_id)
+        .bind(debit)
+        .bind(credit)
+        .bind(journal.currency)
+        .bind(journal.event)
+        .bind(journal.source_id)
+        .bind(&journal.description)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub struct GeneralLedgerRepository {
+    pool: PgPool,
+}
+
+impl GeneralLedgerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_accounts(&self) -> AppResult<Vec<GlAccount>> {
+        let accounts = sqlx::query_as::<_, GlAccount>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM gl_accounts ORDER BY code"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    pub async fn find_account_by_code(&self, code: &str) -> AppResult<Option<GlAccount>> {
+        let account = sqlx::query_as::<_, GlAccount>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM gl_accounts WHERE code = $1"
+        ))
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(account)
+    }
+
+    pub async fn create_account(&self, account: &GlAccount) -> AppResult<GlAccount> {
+        let created = sqlx::query_as::<_, GlAccount>(&format!(
+            "INSERT INTO gl_accounts ({ACCOUNT_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             RETURNING {ACCOUNT_COLUMNS}"
+        ))
+        .bind(account.id)
+        .bind(&account.code)
+        .bind(&account.name)
+        .bind(account.account_type)
+        .bind(&account.description)
+        .bind(account.is_system)
+        .bind(account.is_active)
+        .bind(account.created_by)
+        .bind(account.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn list_rules(&self) -> AppResult<Vec<PostingRule>> {
+        let rules = sqlx::query_as::<_, PostingRule>(&format!(
+            "{RULE_SELECT} ORDER BY r.event, r.fee_code NULLS FIRST"
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    /// Create or replace the rule for an event and fee code
+    pub async fn upsert_rule(
+        &self,
+        event: PostingEvent,
+        fee_code: Option<&str>,
+        debit_account_id: Uuid,
+        credit_account_id: Uuid,
+        actor_id: Uuid,
+    ) -> AppResult<PostingRule> {
+        let rule_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO gl_posting_rules (event, fee_code, debit_account_id, credit_account_id, created_by)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (event, COALESCE(fee_code, ''))
+             DO UPDATE SET debit_account_id = EXCLUDED.debit_account_id,
+                           credit_account_id = EXCLUDED.credit_account_id,
+                           updated_at = NOW()
+             RETURNING id",
+        )
+        .bind(event)
+        .bind(fee_code)
+        .bind(debit_account_id)
+        .bind(credit_account_id)
+        .bind(actor_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let rule = sqlx::query_as::<_, PostingRule>(&format!("{RULE_SELECT} WHERE r.id = $1"))
+            .bind(rule_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(rule)
+    }
+
+    /// Debit and credit totals per account and currency for entries before
+    /// `until`. The balance is left for the caller to put on the normal side.
+    pub async fn trial_balance(
+        &self,
+        until: DateTime<Utc>,
+        currency: Option<&str>,
+    ) -> AppResult<Vec<TrialBalanceLine>> {
+        let lines = sqlx::query_as::<_, TrialBalanceLine>(
+            "SELECT a.code, a.name, a.account_type, e.currency,
+                    SUM(e.debit)::BIGINT AS total_debits,
+                    SUM(e.credit)::BIGINT AS total_credits,
+                    0::BIGINT AS balance
+             FROM gl_entries e
+             JOIN gl_accounts a ON a.id = e.gl_account_id
+             WHERE e.created_at < $1 AND ($2::VARCHAR IS NULL OR e.currency = $2)
+             GROUP BY a.code, a.name, a.account_type, e.currency
+             ORDER BY e.currency, a.code",
+        )
+        .bind(until)
+        .bind(currency)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(lines)
+    }
+}
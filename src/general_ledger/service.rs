@@ -0,0 +1,127 @@
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::Amount;
+use super::model::{CreateGlAccountRequest, GlAccount, PostingRule, SetPostingRuleRequest, TrialBalance};
+use super::repository::GeneralLedgerRepository;
+
+pub struct GeneralLedgerService {
+    repository: GeneralLedgerRepository,
+    audit_logger: AuditLogger,
+}
+
+impl GeneralLedgerService {
+    pub fn new(repository: GeneralLedgerRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            audit_logger,
+        }
+    }
+
+    /// The chart of accounts
+    pub async fn list_accounts(&self) -> AppResult<Vec<GlAccount>> {
+        self.repository.list_accounts().await
+    }
+
+    pub async fn create_account(&self, request: CreateGlAccountRequest, actor_id: Uuid) -> AppResult<GlAccount> {
+        if self.repository.find_account_by_code(&request.code).await?.is_some() {
+            return Err(AppError::Conflict(format!("GL account {} already exists", request.code)));
+        }
+
+        let account = GlAccount {
+            id: Uuid::new_v4(),
+            code: request.code,
+            name: request.name,
+            account_type: request.account_type,
+            description: request.description,
+            is_system: false,
+            is_active: true,
+            created_by: Some(actor_id),
+            created_at: Utc::now(),
+        };
+        let created = self.repository.create_account(&account).await?;
+
+        let event = AuditEvent::new(AuditEventType::GlAccountCreated)
+            .user_id(actor_id)
+            .resource(format!("gl_account:{}", created.code))
+            .action("create".to_string())
+            .metadata("account_type".to_string(), serde_json::json!(created.account_type))
+            .compliance_tag("GENERAL_LEDGER".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(created)
+    }
+
+    pub async fn list_rules(&self) -> AppResult<Vec<PostingRule>> {
+        self.repository.list_rules().await
+    }
+
+    /// Route an event (or one fee code) to a pair of active GL accounts
+    pub async fn set_rule(&self, request: SetPostingRuleRequest, actor_id: Uuid) -> AppResult<PostingRule> {
+        if request.debit_account_code == request.credit_account_code {
+            return Err(AppError::Validation(
+                "Debit and credit accounts must be different".to_string(),
+            ));
+        }
+        let debit = self.find_active_account(&request.debit_account_code).await?;
+        let credit = self.find_active_account(&request.credit_account_code).await?;
+
+        let rule = self
+            .repository
+            .upsert_rule(request.event, request.fee_code.as_deref(), debit.id, credit.id, actor_id)
+            .await?;
+
+        let event = AuditEvent::new(AuditEventType::GlPostingRuleChanged)
+            .user_id(actor_id)
+            .resource(format!("gl_posting_rule:{}", rule.id))
+            .action("set".to_string())
+            .metadata("event".to_string(), serde_json::json!(rule.event))
+            .metadata("fee_code".to_string(), serde_json::json!(rule.fee_code))
+            .metadata("debit_account_code".to_string(), serde_json::json!(rule.debit_account_code))
+            .metadata("credit_account_code".to_string(), serde_json::json!(rule.credit_account_code))
+            .compliance_tag("GENERAL_LEDGER".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(rule)
+    }
+
+    /// Totals of every GL account for entries up to the end of `as_of`
+    pub async fn trial_balance(&self, as_of: Option<NaiveDate>, currency: Option<String>) -> AppResult<TrialBalance> {
+        let as_of = as_of.unwrap_or_else(|| Utc::now().date_naive());
+        let until = (as_of + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+        let mut lines = self
+            .repository
+            .trial_balance(until, currency.map(|code| code.to_uppercase()).as_deref())
+            .await?;
+
+        let mut per_currency: BTreeMap<String, (Amount, Amount)> = BTreeMap::new();
+        for line in &mut lines {
+            line.balance = if line.account_type.is_debit_normal() {
+                line.total_debits - line.total_credits
+            } else {
+                line.total_credits - line.total_debits
+            };
+            let totals = per_currency.entry(line.currency.clone()).or_default();
+            totals.0 += line.total_debits;
+            totals.1 += line.total_credits;
+        }
+
+        Ok(TrialBalance {
+            as_of,
+            total_debits: lines.iter().map(|line| line.total_debits).sum(),
+            total_credits: lines.iter().map(|line| line.total_credits).sum(),
+            balanced: per_currency.values().all(|(debits, credits)| debits == credits),
+            lines,
+        })
+    }
+
+    async fn find_active_account(&self, code: &str) -> AppResult<GlAccount> {
+        self.repository
+            .find_account_by_code(code)
+            .await?
+            .filter(|account| account.is_active)
+            .ok_or_else(|| AppError::NotFound(format!("GL account {} not found", code)))
+    }
+}
@@ -0,0 +1,258 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::{AccountId, PaginatedResponse, PaginationParams};
+use crate::transactions::repository::TransactionRepository;
+use super::model::{
+    CreateGoalRequest, GoalBalanceSummary, GoalFundsRequest, GoalMovement, GoalResponse, ListGoalsQuery,
+    UpdateGoalRequest,
+};
+use super::repository::GoalRepository;
+use super::service::GoalService;
+
+fn goal_service(state: &AppState) -> GoalService {
+    GoalService::new(
+        GoalRepository::new(state.postgres.clone()),
+        TransactionRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Create a savings goal on an account
+#[utoipa::path(
+    post,
+    path = "/api/v1/goals",
+    tag = "goals",
+    request_body = CreateGoalRequest,
+    responses(
+        (status = 201, description = "Savings goal created", body = GoalResponse),
+        (status = 400, description = "Invalid goal"),
+        (status = 404, description = "Account not found"),
+        (status = 409, description = "A savings goal with this name already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_goal(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<CreateGoalRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<GoalResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let goal = goal_service(&state).create_goal(request, claims.tenant_id, claims.developer_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Savings goal created successfully", goal)),
+    ))
+}
+
+/// List savings goals on an account
+#[utoipa::path(
+    get,
+    path = "/api/v1/goals",
+    tag = "goals",
+    params(ListGoalsQuery),
+    responses(
+        (status = 200, description = "Savings goals on the account", body = [GoalResponse]),
+        (status = 404, description = "Account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_goals(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<ListGoalsQuery>,
+) -> AppResult<Json<ApiResponse<Vec<GoalResponse>>>> {
+    let goals = goal_service(&state).list_goals(query, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Savings goals retrieved successfully", goals)))
+}
+
+/// Get a savings goal with its progress
+#[utoipa::path(
+    get,
+    path = "/api/v1/goals/{id}",
+    tag = "goals",
+    params(("id" = Uuid, Path, description = "Savings goal ID")),
+    responses(
+        (status = 200, description = "Savings goal", body = GoalResponse),
+        (status = 404, description = "Savings goal not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_goal_by_id(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<GoalResponse>>> {
+    let goal = goal_service(&state).get_goal(id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Savings goal retrieved successfully", goal)))
+}
+
+/// Update a savings goal's settings
+#[utoipa::path(
+    patch,
+    path = "/api/v1/goals/{id}",
+    tag = "goals",
+    params(("id" = Uuid, Path, description = "Savings goal ID")),
+    request_body = UpdateGoalRequest,
+    responses(
+        (status = 200, description = "Savings goal updated", body = GoalResponse),
+        (status = 400, description = "Invalid update or goal is closed"),
+        (status = 404, description = "Savings goal not found"),
+        (status = 409, description = "A savings goal with this name already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_goal(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<UpdateGoalRequest>,
+) -> AppResult<Json<ApiResponse<GoalResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let goal = goal_service(&state).update_goal(id, claims.tenant_id, request, claims.developer_id).await?;
+    Ok(Json(ApiResponse::success("Savings goal updated successfully", goal)))
+}
+
+/// Close a savings goal, releasing its funds
+#[utoipa::path(
+    delete,
+    path = "/api/v1/goals/{id}",
+    tag = "goals",
+    params(("id" = Uuid, Path, description = "Savings goal ID")),
+    responses(
+        (status = 200, description = "Savings goal closed", body = GoalResponse),
+        (status = 400, description = "Savings goal is closed"),
+        (status = 404, description = "Savings goal not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn close_goal(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<GoalResponse>>> {
+    let goal = goal_service(&state).close_goal(id, claims.tenant_id, claims.developer_id).await?;
+    Ok(Json(ApiResponse::success("Savings goal closed successfully", goal)))
+}
+
+/// Move unallocated funds into a savings goal
+#[utoipa::path(
+    post,
+    path = "/api/v1/goals/{id}/allocate",
+    tag = "goals",
+    params(("id" = Uuid, Path, description = "Savings goal ID")),
+    request_body = GoalFundsRequest,
+    responses(
+        (status = 200, description = "Funds allocated", body = GoalResponse),
+        (status = 400, description = "Insufficient unallocated funds or goal is closed"),
+        (status = 404, description = "Savings goal not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn allocate_funds(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<GoalFundsRequest>,
+) -> AppResult<Json<ApiResponse<GoalResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let goal = goal_service(&state)
+        .allocate(id, claims.tenant_id, request.amount, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Funds allocated successfully", goal)))
+}
+
+/// Release funds from a savings goal
+#[utoipa::path(
+    post,
+    path = "/api/v1/goals/{id}/release",
+    tag = "goals",
+    params(("id" = Uuid, Path, description = "Savings goal ID")),
+    request_body = GoalFundsRequest,
+    responses(
+        (status = 200, description = "Funds released", body = GoalResponse),
+        (status = 400, description = "Amount exceeds the goal balance or goal is closed"),
+        (status = 404, description = "Savings goal not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn release_funds(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<GoalFundsRequest>,
+) -> AppResult<Json<ApiResponse<GoalResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let goal = goal_service(&state)
+        .release(id, claims.tenant_id, request.amount, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Funds released successfully", goal)))
+}
+
+/// List funds movements for a savings goal
+#[utoipa::path(
+    get,
+    path = "/api/v1/goals/{id}/movements",
+    tag = "goals",
+    params(("id" = Uuid, Path, description = "Savings goal ID"), PaginationParams),
+    responses(
+        (status = 200, description = "Page of funds movements", body = PaginatedGoalMovements),
+        (status = 404, description = "Savings goal not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_goal_movements(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<PaginatedResponse<GoalMovement>>>> {
+    let movements = goal_service(&state)
+        .get_movements(id, claims.tenant_id, pagination.page, pagination.limit)
+        .await?;
+    Ok(Json(ApiResponse::success("Savings goal movements retrieved successfully", movements)))
+}
+
+/// Get an account's balance split into goal buckets and spendable funds
+#[utoipa::path(
+    get,
+    path = "/api/v1/goals/accounts/{account_id}/balance",
+    tag = "goals",
+    params(("account_id" = Uuid, Path, description = "Account ID")),
+    responses(
+        (status = 200, description = "Balance split into goal buckets and spendable funds", body = GoalBalanceSummary),
+        (status = 404, description = "Account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_account_goal_balance(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(account_id): Path<AccountId>,
+) -> AppResult<Json<ApiResponse<GoalBalanceSummary>>> {
+    let summary = goal_service(&state).get_balance_summary(account_id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Account balance retrieved successfully", summary)))
+}
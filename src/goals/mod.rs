@@ -0,0 +1,22 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(controller::create_goal).get(controller::get_goals))
+        .route(
+            "/:id",
+            get(controller::get_goal_by_id)
+                .patch(controller::update_goal)
+                .delete(controller::close_goal),
+        )
+        .route("/:id/allocate", post(controller::allocate_funds))
+        .route("/:id/release", post(controller::release_funds))
+        .route("/:id/movements", get(controller::get_goal_movements))
+        .route("/accounts/:account_id/balance", get(controller::get_account_goal_balance))
+}
@@ -0,0 +1,236 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::{AccountId, Amount, Currency, TenantId, TransactionId};
+
+/// Basis points representing 100%
+pub const FULL_PERCENTAGE_BPS: i64 = 10_000;
+
+/// Savings goal status enum
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "savings_goal_status", rename_all = "snake_case")]
+pub enum SavingsGoalStatus {
+    Active,
+    Completed,
+    Closed,
+}
+
+/// How a goal is funded automatically from inbound credits
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "goal_allocation_rule", rename_all = "snake_case")]
+pub enum GoalAllocationRule {
+    #[default]
+    None,
+    /// `allocation_value` basis points of each credit
+    Percentage,
+    /// `allocation_value` minor units of each credit
+    Fixed,
+}
+
+/// Goal balance movement type
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "goal_movement_type", rename_all = "snake_case")]
+pub enum GoalMovementType {
+    Allocation,
+    AutoAllocation,
+    Release,
+}
+
+/// Savings goal model for database
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SavingsGoal {
+    pub id: Uuid,
+    pub tenant_id: Option<TenantId>,
+    pub account_id: AccountId,
+    pub name: String,
+    pub target_amount: Amount,
+    pub balance: Amount,
+    pub currency: Currency,
+    pub target_date: Option<NaiveDate>,
+    pub locked: bool,
+    pub allocation_rule: GoalAllocationRule,
+    pub allocation_value: i64,
+    pub status: SavingsGoalStatus,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SavingsGoal {
+    pub fn remaining_amount(&self) -> Amount {
+        (self.target_amount - self.balance).max(0)
+    }
+
+    /// Amount the allocation rule assigns from an inbound credit, capped at
+    /// what the goal still needs
+    pub fn auto_allocation_for(&self, credit_amount: Amount) -> Amount {
+        let amount = match self.allocation_rule {
+            GoalAllocationRule::None => 0,
+            GoalAllocationRule::Percentage => credit_amount * self.allocation_value / FULL_PERCENTAGE_BPS,
+            GoalAllocationRule::Fixed => self.allocation_value.min(credit_amount),
+        };
+        amount.min(self.remaining_amount())
+    }
+}
+
+/// A movement of funds into or out of a goal
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GoalMovement {
+    pub id: Uuid,
+    pub goal_id: Uuid,
+    pub movement_type: GoalMovementType,
+    pub amount: Amount,
+    pub balance_after: Amount,
+    pub transaction_id: Option<TransactionId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Account balance split into goal buckets
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GoalBalanceSummary {
+    pub account_id: AccountId,
+    pub available_balance: Amount,
+    /// Funds held in any open goal
+    pub allocated_balance: Amount,
+    /// Funds held in locked goals, which cannot be spent
+    pub locked_balance: Amount,
+    /// Funds not assigned to any goal
+    pub unallocated_balance: Amount,
+    /// Available balance less locked goal funds
+    pub spendable_balance: Amount,
+    pub currency: Currency,
+}
+
+impl GoalBalanceSummary {
+    pub fn new(account_id: AccountId, available: Amount, allocated: Amount, locked: Amount, currency: Currency) -> Self {
+        Self {
+            account_id,
+            available_balance: available,
+            allocated_balance: allocated,
+            locked_balance: locked,
+            unallocated_balance: available - allocated,
+            spendable_balance: available - locked,
+            currency,
+        }
+    }
+}
+
+/// Query parameters for listing goals
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListGoalsQuery {
+    pub account_id: AccountId,
+    #[serde(default)]
+    pub include_closed: bool,
+}
+
+/// Create savings goal request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateGoalRequest {
+    pub account_id: AccountId,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(range(min = 1))]
+    pub target_amount: Amount,
+    pub target_date: Option<NaiveDate>,
+    /// Exclude the goal's funds from the spendable balance
+    #[serde(default = "default_locked")]
+    pub locked: bool,
+    #[serde(default)]
+    pub allocation_rule: GoalAllocationRule,
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub allocation_value: i64,
+}
+
+fn default_locked() -> bool {
+    true
+}
+
+/// Update savings goal request; omitted fields are left unchanged
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateGoalRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    #[validate(range(min = 1))]
+    pub target_amount: Option<Amount>,
+    pub target_date: Option<NaiveDate>,
+    pub locked: Option<bool>,
+    pub allocation_rule: Option<GoalAllocationRule>,
+    #[validate(range(min = 0))]
+    pub allocation_value: Option<i64>,
+}
+
+/// Move funds into or out of a goal
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct GoalFundsRequest {
+    #[validate(range(min = 1))]
+    pub amount: Amount,
+}
+
+/// Savings goal response with progress
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GoalResponse {
+    pub id: Uuid,
+    pub account_id: AccountId,
+    pub name: String,
+    pub target_amount: Amount,
+    pub balance: Amount,
+    pub remaining_amount: Amount,
+    /// Progress towards the target, 0 to 100
+    pub progress_percent: f64,
+    /// Monthly contribution needed to reach the target by its date
+    pub monthly_contribution_needed: Option<Amount>,
+    pub currency: Currency,
+    pub target_date: Option<NaiveDate>,
+    pub locked: bool,
+    pub allocation_rule: GoalAllocationRule,
+    pub allocation_value: i64,
+    pub status: SavingsGoalStatus,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SavingsGoal> for GoalResponse {
+    fn from(goal: SavingsGoal) -> Self {
+        let remaining_amount = goal.remaining_amount();
+        let progress_percent =
+            ((goal.balance as f64 / goal.target_amount as f64) * 10_000.0).round().min(10_000.0) / 100.0;
+        let monthly_contribution_needed = goal
+            .target_date
+            .filter(|_| remaining_amount > 0 && goal.status == SavingsGoalStatus::Active)
+            .map(|target_date| {
+                let today = Utc::now().date_naive();
+                let months = (target_date - today).num_days().max(0) / 30 + 1;
+                (remaining_amount + months - 1) / months
+            });
+
+        Self {
+            id: goal.id,
+            account_id: goal.account_id,
+            name: goal.name,
+            target_amount: goal.target_amount,
+            balance: goal.balance,
+            remaining_amount,
+            progress_percent,
+            monthly_contribution_needed,
+            currency: goal.currency,
+            target_date: goal.target_date,
+            locked: goal.locked,
+            allocation_rule: goal.allocation_rule,
+            allocation_value: goal.allocation_value,
+            status: goal.status,
+            completed_at: goal.completed_at,
+            closed_at: goal.closed_at,
+            created_at: goal.created_at,
+        }
+    }
+}
@@ -0,0 +1,382 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::shared::types::{AccountId, Amount, Currency, TenantId, TransactionId};
+use super::model::{GoalBalanceSummary, GoalMovement, GoalMovementType, SavingsGoal, SavingsGoalStatus};
+
+const GOAL_COLUMNS: &str = "id, tenant_id, account_id, name, target_amount, balance, currency, target_date, locked,
+    allocation_rule, allocation_value, status, completed_at, closed_at, created_at, updated_at";
+
+const MOVEMENT_COLUMNS: &str = "id, goal_id, movement_type, amount, balance_after, transaction_id, created_at";
+
+pub struct GoalRepository {
+    pool: PgPool,
+}
+
+impl GoalRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Account balance split into goal buckets
+    pub async fn find_balance_summary(&self, account_id: AccountId) -> AppResult<Option<GoalBalanceSummary>> {
+        let row = sqlx::query_as::<_, (Amount, Amount, Amount, Currency)>(
+            "SELECT COALESCE(b.available_balance, 0),
+                    COALESCE(SUM(g.balance), 0)::BIGINT,
+                    COALESCE(SUM(g.balance) FILTER (WHERE g.locked), 0)::BIGINT,
+                    COALESCE(b.currency, 'USD')
+             FROM balances b
+             LEFT JOIN savings_goals g ON g.account_id = b.account_id AND g.status <> 'closed'
+             WHERE b.account_id = $1
+             GROUP BY b.account_id, b.available_balance, b.currency",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|(available, allocated, locked, currency)| {
+            GoalBalanceSummary::new(account_id, available, allocated, locked, currency)
+        }))
+    }
+
+    pub async fn create(&self, goal: &SavingsGoal) -> AppResult<SavingsGoal> {
+        let created = sqlx::query_as::<_, SavingsGoal>(&format!(
+            "INSERT INTO savings_goals ({GOAL_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+             RETURNING {GOAL_COLUMNS}"
+        ))
+        .bind(goal.id)
+        .bind(goal.tenant_id)
+        .bind(goal.account_id)
+        .bind(&goal.name)
+        .bind(goal.target_amount)
+        .bind(goal.balance)
+        .bind(&goal.currency)
+        .bind(goal.target_date)
+        .bind(goal.locked)
+        .bind(goal.allocation_rule)
+        .bind(goal.allocation_value)
+        .bind(goal.status)
+        .bind(goal.completed_at)
+        .bind(goal.closed_at)
+        .bind(goal.created_at)
+        .bind(goal.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<SavingsGoal>> {
+        let goal = sqlx::query_as::<_, SavingsGoal>(&format!(
+            "SELECT {GOAL_COLUMNS} FROM savings_goals WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(goal)
+    }
+
+    /// Goals on an account in creation order, which is also the order inbound
+    /// credits are allocated in
+    pub async fn find_by_account_id(&self, account_id: AccountId, include_closed: bool) -> AppResult<Vec<SavingsGoal>> {
+        let goals = sqlx::query_as::<_, SavingsGoal>(&format!(
+            "SELECT {GOAL_COLUMNS} FROM savings_goals
+             WHERE account_id = $1 AND ($2 OR status <> 'closed')
+             ORDER BY created_at"
+        ))
+        .bind(account_id)
+        .bind(include_closed)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(goals)
+    }
+
+    /// Whether an open goal on the account already uses the name
+    pub async fn name_in_use(&self, account_id: AccountId, name: &str, exclude_id: Option<Uuid>) -> AppResult<bool> {
+        let exists = sqlx::query_scalar(
+            "SELECT EXISTS(
+                 SELECT 1 FROM savings_goals
+                 WHERE account_id = $1 AND LOWER(name) = LOWER($2) AND status <> 'closed'
+                   AND ($3::UUID IS NULL OR id <> $3)
+             )",
+        )
+        .bind(account_id)
+        .bind(name)
+        .bind(exclude_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Save edited goal settings, re-evaluating completion against the new target
+    pub async fn update_settings(&self, goal: &SavingsGoal) -> AppResult<SavingsGoal> {
+        let updated = sqlx::query_as::<_, SavingsGoal>(&format!(
+            "UPDATE savings_goals
+             SET name = $1, target_amount = $2, target_date = $3, locked = $4,
+                 allocation_rule = $5, allocation_value = $6,
+                 status = CASE
+                     WHEN balance >= $2 THEN 'completed'::savings_goal_status
+                     ELSE 'active'::savings_goal_status
+                 END,
+                 completed_at = CASE WHEN balance >= $2 THEN COALESCE(completed_at, NOW()) END,
+                 updated_at = NOW()
+             WHERE id = $7
+             RETURNING {GOAL_COLUMNS}"
+        ))
+        .bind(&goal.name)
+        .bind(goal.target_amount)
+        .bind(goal.target_date)
+        .bind(goal.locked)
+        .bind(goal.allocation_rule)
+        .bind(goal.allocation_value)
+        .bind(goal.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(updated)
+    }
+
+    /// Move funds between the account's unallocated balance and a goal.
+    /// Returns `None` when the account's unallocated funds (for allocations)
+    /// or the goal's balance (for releases) do not cover the amount.
+    pub async fn move_funds(
+        &self,
+        goal: &SavingsGoal,
+        movement_type: GoalMovementType,
+        amount: Amount,
+    ) -> AppResult<Option<SavingsGoal>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let unallocated = lock_unallocated_balance(&mut tx, goal.account_id).await?;
+        let covered = match movement_type {
+            GoalMovementType::Release => true,
+            GoalMovementType::Allocation | GoalMovementType::AutoAllocation => unallocated >= amount,
+        };
+        if !covered {
+            return Ok(None);
+        }
+
+        let updated = apply_movement(&mut tx, goal.id, movement_type, amount, None).await?;
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    /// Allocate an inbound credit to the account's goals according to their
+    /// rules, in goal creation order and never beyond unallocated funds.
+    /// A credit that was already allocated is skipped.
+    pub async fn allocate_credit(
+        &self,
+        account_id: AccountId,
+        credit_amount: Amount,
+        transaction_id: TransactionId,
+    ) -> AppResult<Vec<SavingsGoal>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let mut unallocated = lock_unallocated_balance(&mut tx, account_id).await?;
+
+        let already_allocated: bool = sqlx::query_scalar(
+            "SELECT EXISTS(
+                 SELECT 1 FROM savings_goal_movements m
+                 JOIN savings_goals g ON g.id = m.goal_id
+                 WHERE g.account_id = $1 AND m.transaction_id = $2 AND m.movement_type = 'auto_allocation'
+             )",
+        )
+        .bind(account_id)
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if already_allocated {
+            return Ok(Vec::new());
+        }
+
+        let goals = sqlx::query_as::<_, SavingsGoal>(&format!(
+            "SELECT {GOAL_COLUMNS} FROM savings_goals
+             WHERE account_id = $1 AND status = 'active' AND allocation_rule <> 'none'
+             ORDER BY created_at
+             FOR UPDATE"
+        ))
+        .bind(account_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut funded = Vec::new();
+        for goal in goals {
+            let amount = goal.auto_allocation_for(credit_amount).min(unallocated);
+            if amount <= 0 {
+                continue;
+            }
+            if let Some(updated) = apply_movement(
+                &mut tx,
+                goal.id,
+                GoalMovementType::AutoAllocation,
+                amount,
+                Some(transaction_id),
+            )
+            .await?
+            {
+                unallocated -= amount;
+                funded.push(updated);
+            }
+        }
+
+        tx.commit().await?;
+        Ok(funded)
+    }
+
+    /// Close a goal, releasing any remaining funds back to the account
+    pub async fn close(&self, goal: &SavingsGoal) -> AppResult<SavingsGoal> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let closed = sqlx::query_as::<_, SavingsGoal>(&format!(
+            "UPDATE savings_goals SET balance = 0, status = $1, closed_at = NOW(), updated_at = NOW()
+             WHERE id = $2
+             RETURNING {GOAL_COLUMNS}"
+        ))
+        .bind(SavingsGoalStatus::Closed)
+        .bind(goal.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if goal.balance > 0 {
+            insert_movement(&mut tx, goal.id, GoalMovementType::Release, goal.balance, 0, None).await?;
+        }
+
+        tx.commit().await?;
+        Ok(closed)
+    }
+
+    pub async fn find_movements(&self, goal_id: Uuid, page: u32, limit: u32) -> AppResult<Vec<GoalMovement>> {
+        let offset = (page.saturating_sub(1) * limit) as i64;
+        let movements = sqlx::query_as::<_, GoalMovement>(&format!(
+            "SELECT {MOVEMENT_COLUMNS} FROM savings_goal_movements
+             WHERE goal_id = $1
+             ORDER BY created_at DESC
+             LIMIT $2 OFFSET $3"
+        ))
+        .bind(goal_id)
+        .bind(limit as i64)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(movements)
+    }
+
+    pub async fn count_movements(&self, goal_id: Uuid) -> AppResult<i64> {
+        let count = sqlx::query_scalar("SELECT COUNT(*) FROM savings_goal_movements WHERE goal_id = $1")
+            .bind(goal_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count)
+    }
+}
+
+/// Funds held in the account's locked goals, read in the caller's
+/// transaction. Goal movements lock the account's balance row first, so
+/// with that row locked the sum cannot change before the caller commits.
+pub async fn locked_goal_balance_in(tx: &mut Transaction<'_, Postgres>, account_id: AccountId) -> AppResult<Amount> {
+    let locked = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(balance), 0)::BIGINT FROM savings_goals
+         WHERE account_id = $1 AND locked AND status <> 'closed'",
+    )
+    .bind(account_id)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(locked)
+}
+
+/// Lock the account's balance row for the rest of the transaction and return
+/// the funds not yet assigned to an open goal
+async fn lock_unallocated_balance(tx: &mut Transaction<'_, Postgres>, account_id: AccountId) -> AppResult<Amount> {
+    let available: Option<Amount> = sqlx::query_scalar(
+        "SELECT COALESCE(available_balance, 0) FROM balances WHERE account_id = $1 FOR UPDATE",
+    )
+    .bind(account_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let allocated: Amount = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(balance), 0)::BIGINT FROM savings_goals
+         WHERE account_id = $1 AND status <> 'closed'",
+    )
+    .bind(account_id)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(available.unwrap_or(0) - allocated)
+}
+
+/// Adjust a goal's balance, keep its completion status in step with the
+/// target and record the movement. Returns `None` if a release exceeds the
+/// goal's balance.
+async fn apply_movement(
+    tx: &mut Transaction<'_, Postgres>,
+    goal_id: Uuid,
+    movement_type: GoalMovementType,
+    amount: Amount,
+    transaction_id: Option<TransactionId>,
+) -> AppResult<Option<SavingsGoal>> {
+    let delta = match movement_type {
+        GoalMovementType::Release => -amount,
+        GoalMovementType::Allocation | GoalMovementType::AutoAllocation => amount,
+    };
+
+    let updated = sqlx::query_as::<_, SavingsGoal>(&format!(
+        "UPDATE savings_goals
+         SET balance = balance + $1,
+             status = CASE
+                 WHEN status = 'closed' THEN status
+                 WHEN balance + $1 >= target_amount THEN 'completed'::savings_goal_status
+                 ELSE 'active'::savings_goal_status
+             END,
+             completed_at = CASE
+                 WHEN balance + $1 >= target_amount THEN COALESCE(completed_at, NOW())
+             END,
+             updated_at = NOW()
+         WHERE id = $2 AND balance + $1 >= 0
+         RETURNING {GOAL_COLUMNS}"
+    ))
+    .bind(delta)
+    .bind(goal_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let Some(updated) = updated else {
+        return Ok(None);
+    };
+
+    insert_movement(tx, goal_id, movement_type, amount, updated.balance, transaction_id).await?;
+    Ok(Some(updated))
+}
+
+async fn insert_movement(
+    tx: &mut Transaction<'_, Postgres>,
+    goal_id: Uuid,
+    movement_type: GoalMovementType,
+    amount: Amount,
+    balance_after: Amount,
+    transaction_id: Option<TransactionId>,
+) -> AppResult<()> {
+    sqlx::query(
+        "INSERT INTO savings_goal_movements (id, goal_id, movement_type, amount, balance_after, transaction_id)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(goal_id)
+    .bind(movement_type)
+    .bind(amount)
+    .bind(balance_after)
+    .bind(transaction_id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,345 @@
+use chrono::Utc;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::shared::constants::{MAX_GOALS_PER_ACCOUNT, MAX_PAGE_LIMIT};
+use crate::transactions::repository::TransactionRepository;
+use crate::shared::types::{AccountId, Amount, PaginatedResponse, TenantId, TransactionId};
+use super::model::{
+    CreateGoalRequest, GoalAllocationRule, GoalBalanceSummary, GoalMovement, GoalMovementType, GoalResponse,
+    ListGoalsQuery, SavingsGoal, SavingsGoalStatus, UpdateGoalRequest, FULL_PERCENTAGE_BPS,
+};
+use super::repository::GoalRepository;
+
+pub struct GoalService {
+    repository: GoalRepository,
+    transaction_repository: TransactionRepository,
+    audit_logger: AuditLogger,
+}
+
+impl GoalService {
+    pub fn new(repository: GoalRepository, transaction_repository: TransactionRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            transaction_repository,
+            audit_logger,
+        }
+    }
+
+    /// Create a savings goal on an account
+    pub async fn create_goal(
+        &self,
+        request: CreateGoalRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<GoalResponse> {
+        self.ensure_account_in_tenant(request.account_id, tenant_id).await?;
+        let summary = find_summary(&self.repository, request.account_id).await?;
+        validate_allocation_rule(request.allocation_rule, request.allocation_value)?;
+        if let Some(target_date) = request.target_date {
+            if target_date <= Utc::now().date_naive() {
+                return Err(AppError::Validation("Target date must be in the future".to_string()));
+            }
+        }
+
+        let open_goals = self.repository.find_by_account_id(request.account_id, false).await?;
+        if open_goals.len() >= MAX_GOALS_PER_ACCOUNT {
+            return Err(AppError::BadRequest(format!(
+                "An account can have at most {} open savings goals",
+                MAX_GOALS_PER_ACCOUNT
+            )));
+        }
+        if self.repository.name_in_use(request.account_id, &request.name, None).await? {
+            return Err(AppError::Conflict("A savings goal with this name already exists".to_string()));
+        }
+
+        let now = Utc::now();
+        let goal = SavingsGoal {
+            id: Uuid::new_v4(),
+            tenant_id: Some(tenant_id),
+            account_id: request.account_id,
+            name: request.name,
+            target_amount: request.target_amount,
+            balance: 0,
+            currency: summary.currency,
+            target_date: request.target_date,
+            locked: request.locked,
+            allocation_rule: request.allocation_rule,
+            allocation_value: request.allocation_value,
+            status: SavingsGoalStatus::Active,
+            completed_at: None,
+            closed_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let goal = self.repository.create(&goal).await?;
+
+        let event = AuditEvent::new(AuditEventType::SavingsGoalCreated)
+            .user_id(actor_id)
+            .resource(format!("savings_goal:{}", goal.id))
+            .action("create".to_string())
+            .metadata("account_id".to_string(), serde_json::json!(goal.account_id))
+            .metadata("target_amount".to_string(), serde_json::json!(goal.target_amount))
+            .compliance_tag("SAVINGS_GOALS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(GoalResponse::from(goal))
+    }
+
+    /// List goals on an account
+    pub async fn list_goals(&self, query: ListGoalsQuery, tenant_id: TenantId) -> AppResult<Vec<GoalResponse>> {
+        self.ensure_account_in_tenant(query.account_id, tenant_id).await?;
+        let goals = self
+            .repository
+            .find_by_account_id(query.account_id, query.include_closed)
+            .await?;
+        Ok(goals.into_iter().map(GoalResponse::from).collect())
+    }
+
+    /// Get a goal with its progress
+    pub async fn get_goal(&self, id: Uuid, tenant_id: TenantId) -> AppResult<GoalResponse> {
+        let goal = self.find_goal(id, tenant_id).await?;
+        Ok(GoalResponse::from(goal))
+    }
+
+    /// Change a goal's name, target, lock or allocation rule
+    pub async fn update_goal(
+        &self,
+        id: Uuid,
+        tenant_id: TenantId,
+        request: UpdateGoalRequest,
+        actor_id: Uuid,
+    ) -> AppResult<GoalResponse> {
+        let mut goal = self.find_open_goal(id, tenant_id).await?;
+
+        if let Some(name) = request.name {
+            if self.repository.name_in_use(goal.account_id, &name, Some(goal.id)).await? {
+                return Err(AppError::Conflict("A savings goal with this name already exists".to_string()));
+            }
+            goal.name = name;
+        }
+        if let Some(target_amount) = request.target_amount {
+            goal.target_amount = target_amount;
+        }
+        if let Some(target_date) = request.target_date {
+            goal.target_date = Some(target_date);
+        }
+        if let Some(locked) = request.locked {
+            goal.locked = locked;
+        }
+        if let Some(allocation_rule) = request.allocation_rule {
+            goal.allocation_rule = allocation_rule;
+            if allocation_rule == GoalAllocationRule::None {
+                goal.allocation_value = 0;
+            }
+        }
+        if let Some(allocation_value) = request.allocation_value {
+            goal.allocation_value = allocation_value;
+        }
+        validate_allocation_rule(goal.allocation_rule, goal.allocation_value)?;
+
+        let goal = self.repository.update_settings(&goal).await?;
+
+        let event = AuditEvent::new(AuditEventType::SavingsGoalUpdated)
+            .user_id(actor_id)
+            .resource(format!("savings_goal:{}", goal.id))
+            .action("update".to_string())
+            .metadata("locked".to_string(), serde_json::json!(goal.locked))
+            .metadata("allocation_rule".to_string(), serde_json::json!(goal.allocation_rule))
+            .compliance_tag("SAVINGS_GOALS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(GoalResponse::from(goal))
+    }
+
+    /// Move unallocated account funds into a goal
+    pub async fn allocate(&self, id: Uuid, tenant_id: TenantId, amount: Amount, actor_id: Uuid) -> AppResult<GoalResponse> {
+        let goal = self.find_open_goal(id, tenant_id).await?;
+        let updated = self
+            .repository
+            .move_funds(&goal, GoalMovementType::Allocation, amount)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Insufficient unallocated funds on the account".to_string()))?;
+
+        self.log_funds_moved(&updated, GoalMovementType::Allocation, amount, actor_id).await;
+        Ok(GoalResponse::from(updated))
+    }
+
+    /// Move funds out of a goal back to the account's unallocated balance
+    pub async fn release(&self, id: Uuid, tenant_id: TenantId, amount: Amount, actor_id: Uuid) -> AppResult<GoalResponse> {
+        let goal = self.find_open_goal(id, tenant_id).await?;
+        let updated = self
+            .repository
+            .move_funds(&goal, GoalMovementType::Release, amount)
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Amount exceeds the goal balance".to_string()))?;
+
+        self.log_funds_moved(&updated, GoalMovementType::Release, amount, actor_id).await;
+        Ok(GoalResponse::from(updated))
+    }
+
+    /// Close a goal, releasing its funds back to the account
+    pub async fn close_goal(&self, id: Uuid, tenant_id: TenantId, actor_id: Uuid) -> AppResult<GoalResponse> {
+        let goal = self.find_open_goal(id, tenant_id).await?;
+        let closed = self.repository.close(&goal).await?;
+
+        let event = AuditEvent::new(AuditEventType::SavingsGoalClosed)
+            .user_id(actor_id)
+            .resource(format!("savings_goal:{}", closed.id))
+            .action("close".to_string())
+            .metadata("released_amount".to_string(), serde_json::json!(goal.balance))
+            .compliance_tag("SAVINGS_GOALS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(GoalResponse::from(closed))
+    }
+
+    /// Funds movements into and out of a goal, newest first
+    pub async fn get_movements(
+        &self,
+        id: Uuid,
+        tenant_id: TenantId,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<PaginatedResponse<GoalMovement>> {
+        let limit = limit.clamp(1, MAX_PAGE_LIMIT);
+        let page = page.max(1);
+
+        self.find_goal(id, tenant_id).await?;
+        let movements = self.repository.find_movements(id, page, limit).await?;
+        let total = self.repository.count_movements(id).await?.max(0) as u64;
+
+        Ok(PaginatedResponse {
+            data: movements,
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit as u64) as u32,
+        })
+    }
+
+    /// Account balance split into goal buckets and spendable funds
+    pub async fn get_balance_summary(&self, account_id: AccountId, tenant_id: TenantId) -> AppResult<GoalBalanceSummary> {
+        self.ensure_account_in_tenant(account_id, tenant_id).await?;
+        find_summary(&self.repository, account_id).await
+    }
+
+    async fn ensure_account_in_tenant(&self, account_id: AccountId, tenant_id: TenantId) -> AppResult<()> {
+        if !self.transaction_repository.account_in_tenant(account_id, tenant_id).await? {
+            return Err(AppError::NotFound("Account not found".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn find_goal(&self, id: Uuid, tenant_id: TenantId) -> AppResult<SavingsGoal> {
+        self.repository
+            .find_by_id(id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Savings goal not found".to_string()))
+    }
+
+    async fn find_open_goal(&self, id: Uuid, tenant_id: TenantId) -> AppResult<SavingsGoal> {
+        let goal = self.find_goal(id, tenant_id).await?;
+        if goal.status == SavingsGoalStatus::Closed {
+            return Err(AppError::BadRequest("Savings goal is closed".to_string()));
+        }
+        Ok(goal)
+    }
+
+    async fn log_funds_moved(
+        &self,
+        goal: &SavingsGoal,
+        movement_type: GoalMovementType,
+        amount: Amount,
+        actor_id: Uuid,
+    ) {
+        let event = AuditEvent::new(AuditEventType::SavingsGoalFundsMoved)
+            .user_id(actor_id)
+            .resource(format!("savings_goal:{}", goal.id))
+            .action(
+                match movement_type {
+                    GoalMovementType::Release => "release",
+                    GoalMovementType::Allocation | GoalMovementType::AutoAllocation => "allocate",
+                }
+                .to_string(),
+            )
+            .metadata("amount".to_string(), serde_json::json!(amount))
+            .metadata("balance_after".to_string(), serde_json::json!(goal.balance))
+            .compliance_tag("SAVINGS_GOALS".to_string());
+        self.audit_logger.log(event).await;
+    }
+}
+
+/// Keeps money movement consistent with savings goal buckets: debits may not
+/// touch funds locked in goals, and inbound credits feed goal allocation rules
+pub struct GoalBalanceGuard {
+    repository: GoalRepository,
+}
+
+impl GoalBalanceGuard {
+    pub fn new(repository: GoalRepository) -> Self {
+        Self { repository }
+    }
+
+    /// The account's balance split into goal buckets
+    pub async fn balance_summary(&self, account_id: AccountId) -> AppResult<GoalBalanceSummary> {
+        find_summary(&self.repository, account_id).await
+    }
+
+    /// Reject debits larger than the account's spendable balance
+    pub async fn ensure_spendable(&self, account_id: AccountId, amount: Amount) -> AppResult<()> {
+        let summary = find_summary(&self.repository, account_id).await?;
+        if amount > summary.spendable_balance {
+            return Err(AppError::BadRequest(format!(
+                "Insufficient spendable balance: {} of the available balance is locked in savings goals",
+                summary.locked_balance
+            )));
+        }
+        Ok(())
+    }
+
+    /// Apply goal allocation rules to a completed inbound credit
+    pub async fn allocate_inbound_credit(
+        &self,
+        account_id: AccountId,
+        amount: Amount,
+        transaction_id: TransactionId,
+    ) -> AppResult<()> {
+        let funded = self
+            .repository
+            .allocate_credit(account_id, amount, transaction_id)
+            .await?;
+        for goal in &funded {
+            tracing::info!(
+                goal_id = %goal.id,
+                transaction_id = %transaction_id,
+                balance = goal.balance,
+                "Allocated inbound credit to savings goal"
+            );
+        }
+        Ok(())
+    }
+}
+
+async fn find_summary(repository: &GoalRepository, account_id: AccountId) -> AppResult<GoalBalanceSummary> {
+    repository
+        .find_balance_summary(account_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Account not found".to_string()))
+}
+
+fn validate_allocation_rule(rule: GoalAllocationRule, value: i64) -> AppResult<()> {
+    let valid = match rule {
+        GoalAllocationRule::None => value == 0,
+        GoalAllocationRule::Percentage => (1..=FULL_PERCENTAGE_BPS).contains(&value),
+        GoalAllocationRule::Fixed => value > 0,
+    };
+    if !valid {
+        return Err(AppError::Validation(
+            "allocation_value must be 1-10000 basis points for percentage rules, a positive amount for fixed rules, \
+             and 0 when there is no rule"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use async_graphql::dataloader::DataLoader;
+use axum::{extract::State, response::Json};
+use crate::auth::middleware::JwtToken;
+use crate::core::{extractors::ApiJson, AppState};
+use super::loaders::{AccountLoader, BalanceLoader};
+use super::repository::ReadModelRepository;
+use super::schema::schema;
+
+/// Execute a read-only GraphQL query for the caller's tenant. Responses use
+/// the GraphQL response format rather than the REST envelope.
+#[utoipa::path(
+    post,
+    path = "/graphql",
+    tag = "graphql",
+    request_body(
+        content = Object,
+        description = "GraphQL request with `query`, and optionally `variables` and `operationName`"
+    ),
+    responses(
+        (status = 200, description = "GraphQL response with `data` and `errors`", body = Object)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn execute(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    // Loaders are per request so cached rows never cross tenants
+    let repository = ReadModelRepository::new(state.postgres.clone(), claims.tenant_id);
+    let request = request
+        .data(DataLoader::new(AccountLoader::new(repository.clone()), tokio::spawn))
+        .data(DataLoader::new(BalanceLoader::new(repository.clone()), tokio::spawn))
+        .data(repository)
+        .data(claims);
+
+    Json(schema().execute(request).await)
+}
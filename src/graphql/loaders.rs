@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use async_graphql::dataloader::Loader;
+use crate::shared::types::AccountId;
+use super::model::{AccountNode, BalanceNode};
+use super::repository::ReadModelRepository;
+use super::schema::graphql_error;
+
+/// Batches account lookups made while resolving one request, so a list of
+/// transactions resolves its accounts with a single query
+pub struct AccountLoader {
+    repository: ReadModelRepository,
+}
+
+impl AccountLoader {
+    pub fn new(repository: ReadModelRepository) -> Self {
+        Self { repository }
+    }
+}
+
+impl Loader<AccountId> for AccountLoader {
+    type Value = AccountNode;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[AccountId]) -> Result<HashMap<AccountId, AccountNode>, Self::Error> {
+        let accounts = self
+            .repository
+            .find_accounts_by_ids(keys)
+            .await
+            .map_err(graphql_error)?;
+        Ok(accounts.into_iter().map(|account| (account.id, account)).collect())
+    }
+}
+
+/// Batches balance lookups by account
+pub struct BalanceLoader {
+    repository: ReadModelRepository,
+}
+
+impl BalanceLoader {
+    pub fn new(repository: ReadModelRepository) -> Self {
+        Self { repository }
+    }
+}
+
+impl Loader<AccountId> for BalanceLoader {
+    type Value = BalanceNode;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[AccountId]) -> Result<HashMap<AccountId, BalanceNode>, Self::Error> {
+        let balances = self
+            .repository
+            .find_balances_by_account_ids(keys)
+            .await
+            .map_err(graphql_error)?;
+        Ok(balances.into_iter().map(|balance| (balance.account_id, balance)).collect())
+    }
+}
@@ -0,0 +1,12 @@
+pub mod controller;
+pub mod loaders;
+pub mod model;
+pub mod repository;
+pub mod schema;
+
+use axum::{routing::post, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/", post(controller::execute))
+}
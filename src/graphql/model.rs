@@ -0,0 +1,132 @@
+use async_graphql::{InputObject, OutputType, SimpleObject};
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+use crate::shared::types::{AccountId, Amount, Currency, TransactionId, UserId};
+
+/// Largest page a list field returns
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Account read model
+#[derive(Debug, Clone, SimpleObject, FromRow)]
+#[graphql(name = "Account", complex)]
+pub struct AccountNode {
+    pub id: AccountId,
+    pub user_id: UserId,
+    pub account_number: String,
+    pub account_name: String,
+    pub account_type: String,
+    pub currency: Currency,
+    pub is_active: bool,
+    pub frozen_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Balance read model
+#[derive(Debug, Clone, SimpleObject, FromRow)]
+#[graphql(name = "Balance")]
+pub struct BalanceNode {
+    pub account_id: AccountId,
+    pub available_balance: Amount,
+    pub ledger_balance: Amount,
+    pub currency: Currency,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Transaction read model
+#[derive(Debug, Clone, SimpleObject, FromRow)]
+#[graphql(name = "Transaction", complex)]
+pub struct TransactionNode {
+    pub id: TransactionId,
+    pub from_account_id: Option<AccountId>,
+    pub to_account_id: Option<AccountId>,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub transaction_type: String,
+    pub status: String,
+    pub reference: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Payment read model
+#[derive(Debug, Clone, SimpleObject, FromRow)]
+#[graphql(name = "Payment", complex)]
+pub struct PaymentNode {
+    pub id: Uuid,
+    pub from_account_id: AccountId,
+    pub to_account_id: Option<AccountId>,
+    pub amount: Amount,
+    pub fee_amount: Amount,
+    pub currency: Currency,
+    pub payment_method: String,
+    pub status: String,
+    pub reference: String,
+    pub description: Option<String>,
+    pub execute_at: Option<DateTime<Utc>>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One page of a list field
+#[derive(Debug, SimpleObject)]
+#[graphql(concrete(name = "AccountPage", params(AccountNode)))]
+#[graphql(concrete(name = "TransactionPage", params(TransactionNode)))]
+#[graphql(concrete(name = "PaymentPage", params(PaymentNode)))]
+pub struct Page<T: OutputType> {
+    pub nodes: Vec<T>,
+    pub page: u32,
+    pub limit: u32,
+    pub total: i64,
+    pub has_next_page: bool,
+}
+
+impl<T: OutputType> Page<T> {
+    pub fn new(nodes: Vec<T>, page: u32, limit: u32, total: i64) -> Self {
+        Self {
+            has_next_page: (page as i64) * (limit as i64) < total,
+            nodes,
+            page,
+            limit,
+            total,
+        }
+    }
+}
+
+/// Clamp client-supplied paging arguments
+pub fn page_bounds(page: Option<u32>, limit: Option<u32>) -> (u32, u32) {
+    (page.unwrap_or(1).max(1), limit.unwrap_or(20).clamp(1, MAX_PAGE_SIZE))
+}
+
+/// Account list filter
+#[derive(Debug, Default, InputObject)]
+pub struct AccountFilter {
+    pub user_id: Option<UserId>,
+    pub account_type: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+/// Transaction list filter
+#[derive(Debug, Default, InputObject)]
+pub struct TransactionFilter {
+    /// Transactions where the account is the sender or the recipient
+    pub account_id: Option<AccountId>,
+    pub transaction_type: Option<String>,
+    pub status: Option<String>,
+    pub currency: Option<Currency>,
+    pub min_amount: Option<Amount>,
+    pub max_amount: Option<Amount>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+/// Payment list filter
+#[derive(Debug, Default, InputObject)]
+pub struct PaymentFilter {
+    /// Payments where the account is the payer or the payee
+    pub account_id: Option<AccountId>,
+    pub status: Option<String>,
+    pub payment_method: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,224 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::shared::types::{AccountId, TenantId};
+use super::model::{
+    AccountFilter, AccountNode, BalanceNode, PaymentFilter, PaymentNode, TransactionFilter, TransactionNode,
+};
+
+const ACCOUNT_COLUMNS: &str =
+    "a.id, a.user_id, a.account_number, a.account_name, a.account_type, COALESCE(a.currency, 'USD') AS currency,
+     COALESCE(a.is_active, TRUE) AS is_active, a.frozen_at, a.created_at";
+
+const ACCOUNT_FILTER: &str = "a.tenant_id = $1
+     AND ($2::UUID IS NULL OR a.user_id = $2)
+     AND ($3::TEXT IS NULL OR a.account_type = $3)
+     AND ($4::BOOLEAN IS NULL OR a.is_active = $4)";
+
+const TRANSACTION_COLUMNS: &str = "t.id, t.from_account_id, t.to_account_id, t.amount, t.currency,
+     t.transaction_type::TEXT AS transaction_type, t.status::TEXT AS status, t.reference, t.description, t.created_at";
+
+const TRANSACTION_FILTER: &str = "t.tenant_id = $1
+     AND ($2::UUID IS NULL OR t.from_account_id = $2 OR t.to_account_id = $2)
+     AND ($3::TEXT IS NULL OR t.transaction_type::TEXT = $3)
+     AND ($4::TEXT IS NULL OR t.status::TEXT = $4)
+     AND ($5::TEXT IS NULL OR t.currency = $5)
+     AND ($6::BIGINT IS NULL OR t.amount >= $6)
+     AND ($7::BIGINT IS NULL OR t.amount <= $7)
+     AND t.created_at >= COALESCE($8::TIMESTAMPTZ, '-infinity')
+     AND t.created_at < COALESCE($9::TIMESTAMPTZ, 'infinity')";
+
+const PAYMENT_COLUMNS: &str = "p.id, p.from_account_id, p.to_account_id, p.amount, p.fee_amount, p.currency,
+     p.payment_method::TEXT AS payment_method, p.status::TEXT AS status, p.reference, p.description,
+     p.execute_at, p.executed_at, p.created_at";
+
+const PAYMENT_FILTER: &str = "p.tenant_id = $1
+     AND ($2::UUID IS NULL OR p.from_account_id = $2 OR p.to_account_id = $2)
+     AND ($3::TEXT IS NULL OR p.status::TEXT = $3)
+     AND ($4::TEXT IS NULL OR p.payment_method::TEXT = $4)
+     AND ($5::TIMESTAMPTZ IS NULL OR p.created_at >= $5)
+     AND ($6::TIMESTAMPTZ IS NULL OR p.created_at < $6)";
+
+/// Read-only queries behind the GraphQL schema. Every query is limited to
+/// the tenant the repository was created for.
+#[derive(Clone)]
+pub struct ReadModelRepository {
+    pool: PgPool,
+    tenant_id: TenantId,
+}
+
+impl ReadModelRepository {
+    pub fn new(pool: PgPool, tenant_id: TenantId) -> Self {
+        Self { pool, tenant_id }
+    }
+
+    pub async fn find_accounts_by_ids(&self, ids: &[AccountId]) -> AppResult<Vec<AccountNode>> {
+        let accounts = sqlx::query_as::<_, AccountNode>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts a WHERE a.tenant_id = $1 AND a.id = ANY($2)"
+        ))
+        .bind(self.tenant_id)
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    pub async fn list_accounts(&self, filter: &AccountFilter, page: u32, limit: u32) -> AppResult<Vec<AccountNode>> {
+        let offset = (page.saturating_sub(1) * limit) as i64;
+
+        let accounts = sqlx::query_as::<_, AccountNode>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts a
+             WHERE {ACCOUNT_FILTER}
+             ORDER BY a.created_at DESC
+             LIMIT $5 OFFSET $6"
+        ))
+        .bind(self.tenant_id)
+        .bind(filter.user_id)
+        .bind(&filter.account_type)
+        .bind(filter.is_active)
+        .bind(limit as i64)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    pub async fn count_accounts(&self, filter: &AccountFilter) -> AppResult<i64> {
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM accounts a WHERE {ACCOUNT_FILTER}"))
+            .bind(self.tenant_id)
+            .bind(filter.user_id)
+            .bind(&filter.account_type)
+            .bind(filter.is_active)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(total)
+    }
+
+    pub async fn find_balances_by_account_ids(&self, account_ids: &[AccountId]) -> AppResult<Vec<BalanceNode>> {
+        let balances = sqlx::query_as::<_, BalanceNode>(
+            "SELECT b.account_id, b.available_balance, b.ledger_balance, b.currency, b.updated_at
+             FROM balances b JOIN accounts a ON a.id = b.account_id
+             WHERE a.tenant_id = $1 AND b.account_id = ANY($2)",
+        )
+        .bind(self.tenant_id)
+        .bind(account_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(balances)
+    }
+
+    pub async fn find_transaction(&self, id: Uuid) -> AppResult<Option<TransactionNode>> {
+        let transaction = sqlx::query_as::<_, TransactionNode>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions t WHERE t.tenant_id = $1 AND t.id = $2"
+        ))
+        .bind(self.tenant_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(transaction)
+    }
+
+    pub async fn list_transactions(
+        &self,
+        filter: &TransactionFilter,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<Vec<TransactionNode>> {
+        let offset = (page.saturating_sub(1) * limit) as i64;
+
+        let transactions = sqlx::query_as::<_, TransactionNode>(&format!(
+            "SELECT {TRANSACTION_COLUMNS} FROM transactions t
+             WHERE {TRANSACTION_FILTER}
+             ORDER BY t.created_at DESC
+             LIMIT $10 OFFSET $11"
+        ))
+        .bind(self.tenant_id)
+        .bind(filter.account_id)
+        .bind(&filter.transaction_type)
+        .bind(&filter.status)
+        .bind(&filter.currency)
+        .bind(filter.min_amount)
+        .bind(filter.max_amount)
+        .bind(filter.created_after)
+        .bind(filter.created_before)
+        .bind(limit as i64)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(transactions)
+    }
+
+    pub async fn count_transactions(&self, filter: &TransactionFilter) -> AppResult<i64> {
+        let total: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM transactions t WHERE {TRANSACTION_FILTER}"
+        ))
+        .bind(self.tenant_id)
+        .bind(filter.account_id)
+        .bind(&filter.transaction_type)
+        .bind(&filter.status)
+        .bind(&filter.currency)
+        .bind(filter.min_amount)
+        .bind(filter.max_amount)
+        .bind(filter.created_after)
+        .bind(filter.created_before)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(total)
+    }
+
+    pub async fn find_payment(&self, id: Uuid) -> AppResult<Option<PaymentNode>> {
+        let payment = sqlx::query_as::<_, PaymentNode>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments p WHERE p.tenant_id = $1 AND p.id = $2"
+        ))
+        .bind(self.tenant_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payment)
+    }
+
+    pub async fn list_payments(&self, filter: &PaymentFilter, page: u32, limit: u32) -> AppResult<Vec<PaymentNode>> {
+        let offset = (page.saturating_sub(1) * limit) as i64;
+
+        let payments = sqlx::query_as::<_, PaymentNode>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments p
+             WHERE {PAYMENT_FILTER}
+             ORDER BY p.created_at DESC
+             LIMIT $7 OFFSET $8"
+        ))
+        .bind(self.tenant_id)
+        .bind(filter.account_id)
+        .bind(&filter.status)
+        .bind(&filter.payment_method)
+        .bind(filter.created_after)
+        .bind(filter.created_before)
+        .bind(limit as i64)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(payments)
+    }
+
+    pub async fn count_payments(&self, filter: &PaymentFilter) -> AppResult<i64> {
+        let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM payments p WHERE {PAYMENT_FILTER}"))
+            .bind(self.tenant_id)
+            .bind(filter.account_id)
+            .bind(&filter.status)
+            .bind(&filter.payment_method)
+            .bind(filter.created_after)
+            .bind(filter.created_before)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(total)
+    }
+}
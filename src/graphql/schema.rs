@@ -0,0 +1,244 @@
+use std::sync::OnceLock;
+use async_graphql::{
+    dataloader::DataLoader, ComplexObject, Context, EmptyMutation, EmptySubscription, Guard, Object, Result, Schema,
+};
+use uuid::Uuid;
+use crate::auth::model::JwtClaims;
+use crate::auth::scopes::{self, Access};
+use crate::core::error::AppError;
+use crate::shared::types::AccountId;
+use super::loaders::{AccountLoader, BalanceLoader};
+use super::model::{
+    page_bounds, AccountFilter, AccountNode, BalanceNode, Page, PaymentFilter, PaymentNode, TransactionFilter,
+    TransactionNode,
+};
+use super::repository::ReadModelRepository;
+
+pub type ReadModelSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Deepest selection a query may nest
+const MAX_QUERY_DEPTH: usize = 8;
+
+/// Upper bound on the number of fields a query may resolve
+const MAX_QUERY_COMPLEXITY: usize = 500;
+
+static SCHEMA: OnceLock<ReadModelSchema> = OnceLock::new();
+
+/// The read-only schema. It holds no per-request data; the caller's claims,
+/// repository and dataloaders are attached to each request.
+pub fn schema() -> &'static ReadModelSchema {
+    SCHEMA.get_or_init(|| {
+        Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .limit_depth(MAX_QUERY_DEPTH)
+            .limit_complexity(MAX_QUERY_COMPLEXITY)
+            .finish()
+    })
+}
+
+/// Field guard requiring the token to carry read access to a module
+pub struct ScopeGuard {
+    scope: &'static str,
+}
+
+impl ScopeGuard {
+    pub fn new(scope: &'static str) -> Self {
+        Self { scope }
+    }
+}
+
+impl Guard for ScopeGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        let claims = ctx.data::<JwtClaims>()?;
+        if scopes::grants(&claims.scopes, self.scope, Access::Read) {
+            Ok(())
+        } else {
+            Err(format!("Token is missing the '{}' scope", scopes::module_scope(self.scope, Access::Read)).into())
+        }
+    }
+}
+
+/// Convert a service error into a GraphQL error without exposing internals
+pub fn graphql_error(error: AppError) -> async_graphql::Error {
+    match error {
+        AppError::Database(err) => {
+            tracing::error!("Database error: {}", err);
+            async_graphql::Error::new("Database error")
+        }
+        AppError::MongoDB(err) => {
+            tracing::error!("MongoDB error: {}", err);
+            async_graphql::Error::new("MongoDB error")
+        }
+        AppError::Internal(msg) => {
+            tracing::error!("Internal error: {}", msg);
+            async_graphql::Error::new("Internal server error")
+        }
+        other => async_graphql::Error::new(other.to_string()),
+    }
+}
+
+fn repository<'a>(ctx: &Context<'a>) -> Result<&'a ReadModelRepository> {
+    ctx.data::<ReadModelRepository>()
+}
+
+async fn list_transactions(
+    ctx: &Context<'_>,
+    filter: TransactionFilter,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> Result<Page<TransactionNode>> {
+    let (page, limit) = page_bounds(page, limit);
+    let repository = repository(ctx)?;
+    let transactions = repository
+        .list_transactions(&filter, page, limit)
+        .await
+        .map_err(graphql_error)?;
+    let total = repository.count_transactions(&filter).await.map_err(graphql_error)?;
+    Ok(Page::new(transactions, page, limit, total))
+}
+
+async fn list_payments(
+    ctx: &Context<'_>,
+    filter: PaymentFilter,
+    page: Option<u32>,
+    limit: Option<u32>,
+) -> Result<Page<PaymentNode>> {
+    let (page, limit) = page_bounds(page, limit);
+    let repository = repository(ctx)?;
+    let payments = repository
+        .list_payments(&filter, page, limit)
+        .await
+        .map_err(graphql_error)?;
+    let total = repository.count_payments(&filter).await.map_err(graphql_error)?;
+    Ok(Page::new(payments, page, limit, total))
+}
+
+async fn load_account(ctx: &Context<'_>, id: Option<AccountId>) -> Result<Option<AccountNode>> {
+    match id {
+        Some(id) => ctx.data::<DataLoader<AccountLoader>>()?.load_one(id).await,
+        None => Ok(None),
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// An account in the caller's tenant
+    #[graphql(guard = "ScopeGuard::new(scopes::USER_DATA)")]
+    async fn account(&self, ctx: &Context<'_>, id: AccountId) -> Result<Option<AccountNode>> {
+        load_account(ctx, Some(id)).await
+    }
+
+    /// Accounts in the caller's tenant, newest first
+    #[graphql(guard = "ScopeGuard::new(scopes::USER_DATA)")]
+    async fn accounts(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<AccountFilter>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Page<AccountNode>> {
+        let filter = filter.unwrap_or_default();
+        let (page, limit) = page_bounds(page, limit);
+        let repository = repository(ctx)?;
+        let accounts = repository
+            .list_accounts(&filter, page, limit)
+            .await
+            .map_err(graphql_error)?;
+        let total = repository.count_accounts(&filter).await.map_err(graphql_error)?;
+        Ok(Page::new(accounts, page, limit, total))
+    }
+
+    #[graphql(guard = "ScopeGuard::new(scopes::TRANSACTIONS)")]
+    async fn transaction(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<TransactionNode>> {
+        repository(ctx)?.find_transaction(id).await.map_err(graphql_error)
+    }
+
+    /// Transactions in the caller's tenant, newest first
+    #[graphql(guard = "ScopeGuard::new(scopes::TRANSACTIONS)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionFilter>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Page<TransactionNode>> {
+        list_transactions(ctx, filter.unwrap_or_default(), page, limit).await
+    }
+
+    #[graphql(guard = "ScopeGuard::new(scopes::PAYMENTS)")]
+    async fn payment(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<PaymentNode>> {
+        repository(ctx)?.find_payment(id).await.map_err(graphql_error)
+    }
+
+    /// Payments in the caller's tenant, newest first
+    #[graphql(guard = "ScopeGuard::new(scopes::PAYMENTS)")]
+    async fn payments(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<PaymentFilter>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Page<PaymentNode>> {
+        list_payments(ctx, filter.unwrap_or_default(), page, limit).await
+    }
+}
+
+#[ComplexObject]
+impl AccountNode {
+    #[graphql(guard = "ScopeGuard::new(scopes::USER_DATA)")]
+    async fn balance(&self, ctx: &Context<'_>) -> Result<Option<BalanceNode>> {
+        ctx.data::<DataLoader<BalanceLoader>>()?.load_one(self.id).await
+    }
+
+    /// Transactions sent or received by the account, newest first
+    #[graphql(guard = "ScopeGuard::new(scopes::TRANSACTIONS)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Page<TransactionNode>> {
+        let filter = TransactionFilter {
+            account_id: Some(self.id),
+            ..Default::default()
+        };
+        list_transactions(ctx, filter, page, limit).await
+    }
+
+    /// Payments made or received by the account, newest first
+    #[graphql(guard = "ScopeGuard::new(scopes::PAYMENTS)")]
+    async fn payments(&self, ctx: &Context<'_>, page: Option<u32>, limit: Option<u32>) -> Result<Page<PaymentNode>> {
+        let filter = PaymentFilter {
+            account_id: Some(self.id),
+            ..Default::default()
+        };
+        list_payments(ctx, filter, page, limit).await
+    }
+}
+
+#[ComplexObject]
+impl TransactionNode {
+    #[graphql(name = "fromAccount", guard = "ScopeGuard::new(scopes::USER_DATA)")]
+    async fn sending_account(&self, ctx: &Context<'_>) -> Result<Option<AccountNode>> {
+        load_account(ctx, self.from_account_id).await
+    }
+
+    #[graphql(name = "toAccount", guard = "ScopeGuard::new(scopes::USER_DATA)")]
+    async fn receiving_account(&self, ctx: &Context<'_>) -> Result<Option<AccountNode>> {
+        load_account(ctx, self.to_account_id).await
+    }
+}
+
+#[ComplexObject]
+impl PaymentNode {
+    #[graphql(name = "fromAccount", guard = "ScopeGuard::new(scopes::USER_DATA)")]
+    async fn sending_account(&self, ctx: &Context<'_>) -> Result<Option<AccountNode>> {
+        load_account(ctx, Some(self.from_account_id)).await
+    }
+
+    #[graphql(name = "toAccount", guard = "ScopeGuard::new(scopes::USER_DATA)")]
+    async fn receiving_account(&self, ctx: &Context<'_>) -> Result<Option<AccountNode>> {
+        load_account(ctx, self.to_account_id).await
+    }
+}
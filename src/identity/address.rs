@@ -0,0 +1,267 @@
+use super::model::PostalAddress;
+use crate::core::circuit_breaker::{CircuitBreaker, CircuitBreakerSettings, CircuitBreakers};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use crate::shared::types::UserId;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// What an address provider found
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddressCheck {
+    /// The user is known to live at the address
+    pub verified: bool,
+    /// The provider's reference for the check
+    pub reference: Option<String>,
+    /// Why the address could not be verified
+    pub reason: Option<String>,
+}
+
+/// Confirms a user lives at an address, e.g. against electoral rolls,
+/// utility records or credit files
+#[async_trait]
+pub trait AddressVerifier: Send + Sync {
+    /// Name stored with each check, matching `ADDRESS_VERIFIER_PROVIDER`
+    fn name(&self) -> &'static str;
+
+    async fn verify(
+        &self,
+        user_id: UserId,
+        first_name: &str,
+        last_name: &str,
+        address: &PostalAddress,
+    ) -> AppResult<AddressCheck>;
+}
+
+/// Used when no provider is configured: addresses cannot be verified
+pub struct UnavailableAddressVerifier;
+
+#[async_trait]
+impl AddressVerifier for UnavailableAddressVerifier {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    async fn verify(
+        &self,
+        _user_id: UserId,
+        _first_name: &str,
+        _last_name: &str,
+        _address: &PostalAddress,
+    ) -> AppResult<AddressCheck> {
+        Err(AppError::BadRequest(
+            "Address verification is not available".to_string(),
+        ))
+    }
+}
+
+/// Name of the address provider's circuit breaker
+const ADDRESS_VERIFIER_BREAKER: &str = "address_verifier";
+
+/// Talks to an HTTP address API taking `{reference, first_name, last_name,
+/// address}` JSON with a bearer API key and answering `{verified, reference,
+/// reason}`
+pub struct HttpAddressVerifier {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    breaker: CircuitBreaker,
+}
+
+impl HttpAddressVerifier {
+    pub fn new(api_url: String, api_key: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            breaker: CircuitBreaker::new(
+                ADDRESS_VERIFIER_BREAKER,
+                CircuitBreakerSettings::default(),
+            ),
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    async fn send(
+        &self,
+        user_id: UserId,
+        first_name: &str,
+        last_name: &str,
+        address: &PostalAddress,
+    ) -> AppResult<AddressCheck> {
+        let request = self
+            .client
+            .post(format!(
+                "{}/verifications",
+                self.api_url.trim_end_matches('/')
+            ))
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({
+                "reference": user_id,
+                "first_name": first_name,
+                "last_name": last_name,
+                "address": address,
+            }));
+        let response = self.client.send(request).await.map_err(|e| {
+            AppError::ExternalService(format!("Address verification request failed: {}", e))
+        })?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Address provider returned {}",
+                response.status()
+            )));
+        }
+
+        let body: HttpCheck = response.json().await.map_err(|e| {
+            AppError::ExternalService(format!("Invalid address provider response: {}", e))
+        })?;
+        Ok(AddressCheck {
+            verified: body.verified,
+            reference: body.reference,
+            reason: body.reason,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpCheck {
+    verified: bool,
+    reference: Option<String>,
+    reason: Option<String>,
+}
+
+#[async_trait]
+impl AddressVerifier for HttpAddressVerifier {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn verify(
+        &self,
+        user_id: UserId,
+        first_name: &str,
+        last_name: &str,
+        address: &PostalAddress,
+    ) -> AppResult<AddressCheck> {
+        self.breaker
+            .call(self.send(user_id, first_name, last_name, address))
+            .await
+    }
+}
+
+/// Build the address provider selected by `ADDRESS_VERIFIER_PROVIDER`
+pub fn from_config(
+    config: &Config,
+    breakers: &CircuitBreakers,
+    clients: &HttpClients,
+) -> AppResult<Arc<dyn AddressVerifier>> {
+    match config.address_verifier_provider.as_str() {
+        "none" => Ok(Arc::new(UnavailableAddressVerifier)),
+        "http" => {
+            let missing = |name: &str| {
+                AppError::Internal(format!(
+                    "{} is required for the http address provider",
+                    name
+                ))
+            };
+            Ok(Arc::new(
+                HttpAddressVerifier::new(
+                    config
+                        .address_verifier_api_url
+                        .clone()
+                        .ok_or_else(|| missing("ADDRESS_VERIFIER_API_URL"))?,
+                    config
+                        .address_verifier_api_key
+                        .clone()
+                        .ok_or_else(|| missing("ADDRESS_VERIFIER_API_KEY"))?,
+                )
+                .with_circuit_breaker(breakers.get(ADDRESS_VERIFIER_BREAKER))
+                .with_http_client(clients.get(ADDRESS_VERIFIER_BREAKER)),
+            ))
+        }
+        other => Err(AppError::Internal(format!(
+            "Unknown address provider '{}'",
+            other
+        ))),
+    }
+}
+
+/// Trim the fields, drop empty optional ones and upper-case the country,
+/// rejecting country codes that are not two letters
+pub fn normalize_address(address: PostalAddress) -> AppResult<PostalAddress> {
+    let optional = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let address = PostalAddress {
+        line1: address.line1.trim().to_string(),
+        line2: optional(address.line2),
+        city: address.city.trim().to_string(),
+        region: optional(address.region),
+        postal_code: address.postal_code.trim().to_uppercase(),
+        country: address.country.trim().to_uppercase(),
+    };
+    if address.line1.is_empty() || address.city.is_empty() || address.postal_code.is_empty() {
+        return Err(AppError::Validation(
+            "An address needs a street line, city and postal code".to_string(),
+        ));
+    }
+    if address.country.len() != 2 || !address.country.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(AppError::Validation(
+            "Country must be an ISO 3166-1 alpha-2 code".to_string(),
+        ));
+    }
+    Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address() -> PostalAddress {
+        PostalAddress {
+            line1: " 10 Downing Street ".to_string(),
+            line2: Some("  ".to_string()),
+            city: "London".to_string(),
+            region: None,
+            postal_code: "sw1a 2aa".to_string(),
+            country: "gb".to_string(),
+        }
+    }
+
+    #[test]
+    fn addresses_are_trimmed_and_upper_cased() {
+        let normalized = normalize_address(address()).unwrap();
+        assert_eq!(normalized.line1, "10 Downing Street");
+        assert_eq!(normalized.line2, None);
+        assert_eq!(normalized.postal_code, "SW1A 2AA");
+        assert_eq!(normalized.country, "GB");
+    }
+
+    #[test]
+    fn rejects_blank_lines_and_unknown_country_forms() {
+        assert!(normalize_address(PostalAddress {
+            city: " ".to_string(),
+            ..address()
+        })
+        .is_err());
+        assert!(normalize_address(PostalAddress {
+            country: "G1".to_string(),
+            ..address()
+        })
+        .is_err());
+    }
+}
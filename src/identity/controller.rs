@@ -1,6 +1,38 @@
-use axum::{extract::State, response::Json};
+use axum::{extract::{Multipart, Path, State}, response::Json};
 use serde_json::{json, Value};
-use crate::core::{error::AppResult, AppState};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{error::{AppError, AppResult}, extractors::ApiJson, response::ApiResponse, AppState};
+use crate::kyc::repository::KycRepository;
+use crate::reviews::{repository::ReviewRepository, service::ReviewQueue};
+use crate::shared::types::UserId;
+use super::{address, images};
+use super::model::{FaceQualityResponse, KycStatusResponse, PostalAddress, UserAddressResponse, VerificationScreening};
+use super::repository::IdentityRepository;
+use super::screening::{self, ScreeningSettings};
+use super::service::{KycProfileService, ScreeningService};
+
+fn kyc_profile_service(state: &AppState) -> AppResult<KycProfileService> {
+    Ok(KycProfileService::new(
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        KycRepository::new(state.postgres.clone()),
+        address::from_config(&state.config, &state.circuit_breakers, &state.http_clients)?,
+        state.audit_logger.clone(),
+    ))
+}
+
+fn screening_service(state: &AppState) -> AppResult<ScreeningService> {
+    let provider = screening::from_config(&state.config, &state.circuit_breakers, &state.http_clients)?
+        .ok_or_else(|| AppError::BadRequest("Screening is not enabled".to_string()))?;
+    Ok(ScreeningService::new(
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        provider,
+        ScreeningSettings::from_config(&state.config),
+        ReviewQueue::new(ReviewRepository::new(state.postgres.clone()), state.audit_logger.clone()),
+        state.audit_logger.clone(),
+    ))
+}
 
 /// Initiate identity verification process
 pub async fn initiate_verification(
@@ -39,4 +71,175 @@ pub async fn complete_verification(
         "message": "Complete identity verification endpoint - TODO: Implement",
         "status": "placeholder"
     })))
-}
\ No newline at end of file
+}
+
+/// Get a user's address and whether it is verified
+#[utoipa::path(
+    get,
+    path = "/api/v1/identity/users/{user_id}/address",
+    tag = "identity",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The user's address", body = UserAddressResponse),
+        (status = 404, description = "User or address not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_address(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(user_id): Path<UserId>,
+) -> AppResult<Json<ApiResponse<UserAddressResponse>>> {
+    let address = kyc_profile_service(&state)?
+        .get_address(user_id, claims.tenant_id, claims.user_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Address retrieved successfully", address)))
+}
+
+/// Set a user's address. A changed address has to be verified again.
+#[utoipa::path(
+    put,
+    path = "/api/v1/identity/users/{user_id}/address",
+    tag = "identity",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = PostalAddress,
+    responses(
+        (status = 200, description = "Address stored", body = UserAddressResponse),
+        (status = 400, description = "Invalid address"),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_address(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(user_id): Path<UserId>,
+    ApiJson(request): ApiJson<PostalAddress>,
+) -> AppResult<Json<ApiResponse<UserAddressResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let address = kyc_profile_service(&state)?
+        .set_address(
+            user_id,
+            claims.tenant_id,
+            claims.user_id,
+            claims.user_id.unwrap_or(claims.developer_id),
+            request,
+        )
+        .await?;
+    Ok(Json(ApiResponse::success("Address updated successfully", address)))
+}
+
+/// Check with the address provider that the user lives at their address
+#[utoipa::path(
+    post,
+    path = "/api/v1/identity/users/{user_id}/address/verify",
+    tag = "identity",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Provider's finding recorded", body = UserAddressResponse),
+        (status = 400, description = "Address verification is not available"),
+        (status = 404, description = "User or address not found"),
+        (status = 409, description = "Already verified, or the user's data was erased"),
+        (status = 502, description = "The address provider failed")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn verify_address(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(user_id): Path<UserId>,
+) -> AppResult<Json<ApiResponse<UserAddressResponse>>> {
+    let address = kyc_profile_service(&state)?
+        .verify_address(user_id, claims.tenant_id, claims.user_id, claims.user_id.unwrap_or(claims.developer_id))
+        .await?;
+    Ok(Json(ApiResponse::success("Address verification completed", address)))
+}
+
+/// Summarize how complete a user's KYC profile is, listing the onboarding
+/// steps still to do
+#[utoipa::path(
+    get,
+    path = "/api/v1/identity/kyc-status/{user_id}",
+    tag = "identity",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Each onboarding step and the ones left", body = KycStatusResponse),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_kyc_status(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(user_id): Path<UserId>,
+) -> AppResult<Json<ApiResponse<KycStatusResponse>>> {
+    let status = kyc_profile_service(&state)?
+        .kyc_status(user_id, claims.tenant_id, claims.user_id)
+        .await?;
+    Ok(Json(ApiResponse::success("KYC status retrieved successfully", status)))
+}
+
+
+/// Screen the person behind a verification against PEP lists and adverse
+/// media now. Matches not dismissed before queue it for manual review.
+#[utoipa::path(
+    post,
+    path = "/api/v1/identity/verify/{id}/screening",
+    tag = "identity",
+    params(("id" = Uuid, Path, description = "Identity verification ID")),
+    responses(
+        (status = 200, description = "Screening recorded", body = VerificationScreening),
+        (status = 400, description = "Screening is not enabled"),
+        (status = 404, description = "Verification not found"),
+        (status = 409, description = "The verification failed or expired"),
+        (status = 502, description = "The screening provider failed")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn screen_verification(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(verification_id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<VerificationScreening>>> {
+    let screening = screening_service(&state)?
+        .screen_verification(verification_id, claims.tenant_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Verification screened successfully", screening)))
+}
+
+/// Check a face photo's lighting and focus so an app can ask the user to
+/// retake it before submitting a verification. Nothing is stored or matched.
+#[utoipa::path(
+    post,
+    path = "/api/v1/identity/face/quality",
+    tag = "identity",
+    request_body(content = Vec<u8>, content_type = "multipart/form-data", description = "The photo as the `file` part"),
+    responses(
+        (status = 200, description = "Quality scores and what to fix", body = FaceQualityResponse),
+        (status = 400, description = "Missing, unsupported or invalid image")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn check_face_quality(
+    JwtToken(_claims): JwtToken,
+    mut multipart: Multipart,
+) -> AppResult<Json<ApiResponse<FaceQualityResponse>>> {
+    let invalid = |error: axum::extract::multipart::MultipartError| {
+        AppError::BadRequest(format!("Invalid multipart upload: {}", error.body_text()))
+    };
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        if field.name() == Some("file") {
+            let content_type = field.content_type().unwrap_or("application/octet-stream").to_string();
+            file = Some((content_type, field.bytes().await.map_err(invalid)?));
+        }
+    }
+    let (content_type, content) = file.ok_or_else(|| AppError::Validation("Missing file part 'file'".to_string()))?;
+
+    let quality = tokio::task::spawn_blocking(move || images::assess_quality(&content_type, &content))
+        .await
+        .map_err(|e| AppError::Internal(format!("Image processing failed: {}", e)))??;
+    Ok(Json(ApiResponse::success("Face quality checked successfully", quality)))
+}
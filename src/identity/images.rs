@@ -0,0 +1,268 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::metadata::Orientation;
+use image::{DynamicImage, GrayImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::constants::{MAX_IMAGE_DIMENSION, MAX_IMAGE_SIZE, MIN_IMAGE_DIMENSION, SUPPORTED_IMAGE_FORMATS};
+use super::model::{FaceQualityFeedback, FaceQualityResponse, QualityScores};
+
+/// Quality JPEG images are re-encoded at
+const JPEG_QUALITY: u8 = 90;
+
+/// Bytes a decoded image may allocate; an RGBA image of the largest
+/// dimensions accepted
+const MAX_DECODED_BYTES: u64 = MAX_IMAGE_DIMENSION as u64 * MAX_IMAGE_DIMENSION as u64 * 4;
+
+/// Longest side images are scaled down to before their quality is measured,
+/// so scores do not depend on the camera's resolution
+const QUALITY_SAMPLE_SIZE: u32 = 640;
+
+/// Brightness below which a photo is too dark to read a face in
+const MIN_BRIGHTNESS: f64 = 0.25;
+
+/// Brightness above which a photo is washed out
+const MAX_BRIGHTNESS: f64 = 0.85;
+
+/// Laplacian variance of a sample scoring full sharpness
+const SHARP_LAPLACIAN_VARIANCE: f64 = 500.0;
+
+/// Sharpness below which a photo is blurred
+const MIN_SHARPNESS: f64 = 0.2;
+
+/// An identity image ready for checks and storage: upright, and re-encoded
+/// without any of the metadata of the original
+#[derive(Debug)]
+pub struct NormalizedImage {
+    pub content: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    /// The EXIF orientation the image was rotated out of
+    pub orientation: Orientation,
+}
+
+pub fn is_image(content_type: &str) -> bool {
+    SUPPORTED_IMAGE_FORMATS.contains(&content_type)
+}
+
+/// Validate an identity image against its declared content type and the
+/// size and dimension limits, apply its EXIF orientation and strip its
+/// metadata (EXIF, GPS position, camera details) by re-encoding it
+pub fn normalize(content_type: &str, content: &[u8]) -> AppResult<NormalizedImage> {
+    let (format, image, orientation) = decode(content_type, content)?;
+
+    let mut buffer = Vec::new();
+    let encoded = match format {
+        ImageFormat::Jpeg => image.write_with_encoder(JpegEncoder::new_with_quality(&mut buffer, JPEG_QUALITY)),
+        _ => image.write_to(&mut Cursor::new(&mut buffer), format),
+    };
+    encoded.map_err(|e| AppError::Internal(format!("Failed to encode image: {}", e)))?;
+
+    Ok(NormalizedImage {
+        content: buffer,
+        width: image.width(),
+        height: image.height(),
+        orientation,
+    })
+}
+
+/// Measure how well a face photo is lit and focused and say what to fix
+/// before it is submitted. Nothing is stored or matched.
+pub fn assess_quality(content_type: &str, content: &[u8]) -> AppResult<FaceQualityResponse> {
+    let (_, image, _) = decode(content_type, content)?;
+    let (width, height) = (image.width(), image.height());
+    let sample = if width.max(height) > QUALITY_SAMPLE_SIZE {
+        image.thumbnail(QUALITY_SAMPLE_SIZE, QUALITY_SAMPLE_SIZE)
+    } else {
+        image
+    }
+    .to_luma8();
+
+    let pixels = sample.as_raw();
+    let brightness = pixels.iter().map(|&p| p as f64).sum::<f64>() / pixels.len() as f64 / 255.0;
+    let sharpness = (laplacian_variance(&sample) / SHARP_LAPLACIAN_VARIANCE).min(1.0);
+
+    let mut feedback = Vec::new();
+    if brightness < MIN_BRIGHTNESS {
+        feedback.push(FaceQualityFeedback::TooDark);
+    } else if brightness > MAX_BRIGHTNESS {
+        feedback.push(FaceQualityFeedback::TooBright);
+    }
+    if sharpness < MIN_SHARPNESS {
+        feedback.push(FaceQualityFeedback::Blurred);
+    }
+
+    Ok(FaceQualityResponse {
+        acceptable: feedback.is_empty(),
+        scores: QualityScores { brightness, sharpness },
+        feedback,
+        width,
+        height,
+    })
+}
+
+/// Variance of the 4-neighbour Laplacian; low when the image has no edges
+fn laplacian_variance(image: &GrayImage) -> f64 {
+    let (width, height) = image.dimensions();
+    let at = |x: u32, y: u32| image.get_pixel(x, y).0[0] as f64;
+    let (mut sum, mut sum_squares, mut count) = (0.0, 0.0, 0.0);
+    for y in 1..height.saturating_sub(1) {
+        for x in 1..width.saturating_sub(1) {
+            let laplacian = 4.0 * at(x, y) - at(x - 1, y) - at(x + 1, y) - at(x, y - 1) - at(x, y + 1);
+            sum += laplacian;
+            sum_squares += laplacian * laplacian;
+            count += 1.0;
+        }
+    }
+    if count == 0.0 {
+        return 0.0;
+    }
+    let mean = sum / count;
+    sum_squares / count - mean * mean
+}
+
+/// Decode an identity image after checking it against its declared content
+/// type and the size and dimension limits, and turn it upright
+fn decode(content_type: &str, content: &[u8]) -> AppResult<(ImageFormat, DynamicImage, Orientation)> {
+    let format = match content_type {
+        "image/jpeg" => ImageFormat::Jpeg,
+        "image/png" => ImageFormat::Png,
+        _ => {
+            return Err(AppError::Validation(format!(
+                "Unsupported image type '{}'. Supported types: {}",
+                content_type,
+                SUPPORTED_IMAGE_FORMATS.join(", ")
+            )))
+        }
+    };
+    if content.is_empty() {
+        return Err(AppError::Validation("The image is empty".to_string()));
+    }
+    if content.len() > MAX_IMAGE_SIZE {
+        return Err(AppError::Validation(format!(
+            "Image exceeds the maximum size of {} bytes",
+            MAX_IMAGE_SIZE
+        )));
+    }
+    if image::guess_format(content).ok() != Some(format) {
+        return Err(AppError::Validation(format!(
+            "The file is not a valid {} image",
+            content_type
+        )));
+    }
+
+    let invalid = |e: image::ImageError| AppError::Validation(format!("The image could not be read: {}", e));
+    let mut reader = ImageReader::with_format(Cursor::new(content), format);
+    let mut limits = Limits::default();
+    limits.max_alloc = Some(MAX_DECODED_BYTES);
+    reader.limits(limits);
+    let mut decoder = reader.into_decoder().map_err(invalid)?;
+
+    // Checked before decoding so oversized images are never expanded
+    let (width, height) = decoder.dimensions();
+    if width.min(height) < MIN_IMAGE_DIMENSION {
+        return Err(AppError::Validation(format!(
+            "Image is {}x{} pixels; both sides must be at least {} pixels",
+            width, height, MIN_IMAGE_DIMENSION
+        )));
+    }
+    if width.max(height) > MAX_IMAGE_DIMENSION {
+        return Err(AppError::Validation(format!(
+            "Image is {}x{} pixels; neither side may exceed {} pixels",
+            width, height, MAX_IMAGE_DIMENSION
+        )));
+    }
+
+    // Unreadable EXIF leaves the image as it is rather than rejecting it
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+    Ok((format, image, orientation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::png::PngEncoder;
+    use image::{ImageEncoder, RgbImage};
+
+    /// A big-endian TIFF block holding only the orientation tag
+    fn exif_orientation(value: u8) -> Vec<u8> {
+        let mut exif = b"MM\0\x2a\0\0\0\x08\0\x01".to_vec();
+        exif.extend_from_slice(&[0x01, 0x12, 0x00, 0x03, 0, 0, 0, 1, 0, value, 0, 0]);
+        exif.extend_from_slice(&[0, 0, 0, 0]);
+        exif
+    }
+
+    fn encode(format: ImageFormat, width: u32, height: u32, exif: Option<Vec<u8>>) -> Vec<u8> {
+        let image = RgbImage::from_fn(width, height, |x, _| image::Rgb([(x % 256) as u8, 0, 0]));
+        let mut buffer = Vec::new();
+        let exif = exif.unwrap_or_default();
+        let (raw, color) = (image.as_raw(), image::ExtendedColorType::Rgb8);
+        match format {
+            ImageFormat::Jpeg => {
+                let mut encoder = JpegEncoder::new(&mut buffer);
+                encoder.set_exif_metadata(exif).unwrap();
+                encoder.write_image(raw, width, height, color).unwrap();
+            }
+            _ => {
+                let mut encoder = PngEncoder::new(&mut buffer);
+                encoder.set_exif_metadata(exif).unwrap();
+                encoder.write_image(raw, width, height, color).unwrap();
+            }
+        }
+        buffer
+    }
+
+    #[test]
+    fn rotated_images_are_turned_upright_and_stripped() {
+        for (format, content_type) in [(ImageFormat::Jpeg, "image/jpeg"), (ImageFormat::Png, "image/png")] {
+            let content = encode(format, 300, 200, Some(exif_orientation(6)));
+            let normalized = normalize(content_type, &content).unwrap();
+
+            assert_eq!((normalized.width, normalized.height), (200, 300));
+            assert_eq!(normalized.orientation, Orientation::Rotate90);
+            let mut decoder = ImageReader::with_format(Cursor::new(&normalized.content), format)
+                .into_decoder()
+                .unwrap();
+            assert_eq!(decoder.exif_metadata().unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn images_must_match_their_type_and_limits() {
+        let png = encode(ImageFormat::Png, 300, 200, None);
+        assert!(normalize("image/png", &png).is_ok());
+        assert!(matches!(normalize("image/jpeg", &png), Err(AppError::Validation(_))));
+        assert!(matches!(normalize("image/gif", &png), Err(AppError::Validation(_))));
+        assert!(matches!(normalize("image/png", b"not an image"), Err(AppError::Validation(_))));
+
+        let small = encode(ImageFormat::Png, 300, MIN_IMAGE_DIMENSION - 1, None);
+        assert!(matches!(normalize("image/png", &small), Err(AppError::Validation(_))));
+        let wide = encode(ImageFormat::Png, MAX_IMAGE_DIMENSION + 1, 200, None);
+        assert!(matches!(normalize("image/png", &wide), Err(AppError::Validation(_))));
+    }
+
+    fn png(image: RgbImage) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        DynamicImage::ImageRgb8(image)
+            .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+            .unwrap();
+        buffer
+    }
+
+    #[test]
+    fn quality_feedback_flags_dark_and_blurred_photos() {
+        let dark = png(RgbImage::from_pixel(300, 300, image::Rgb([20, 20, 20])));
+        let quality = assess_quality("image/png", &dark).unwrap();
+        assert!(!quality.acceptable);
+        assert_eq!(quality.feedback, vec![FaceQualityFeedback::TooDark, FaceQualityFeedback::Blurred]);
+
+        let checkerboard = png(RgbImage::from_fn(300, 300, |x, y| {
+            let value = if (x / 8 + y / 8) % 2 == 0 { 40 } else { 215 };
+            image::Rgb([value, value, value])
+        }));
+        let quality = assess_quality("image/png", &checkerboard).unwrap();
+        assert!(quality.acceptable, "{:?}", quality);
+        assert_eq!(quality.scores.sharpness, 1.0);
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditSeverity};
+use crate::core::error::AppResult;
+use crate::core::AppState;
+use crate::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use crate::reviews::{repository::ReviewRepository, service::ReviewQueue};
+use crate::webhooks::repository::WebhookRepository;
+use super::model::IdentityVerification;
+use super::repository::IdentityRepository;
+use super::screening::{self, ScreeningSettings};
+use super::service::ScreeningService;
+
+const EXPIRY_JOB: &str = "identity_verification_expiry";
+
+const RESCREENING_JOB: &str = "identity_rescreening";
+
+/// Periodically expire identity verifications older than the configured validity
+pub fn spawn_expiry_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.verification_expiry_check_interval_seconds);
+    state.job_monitor.register(EXPIRY_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match expire_stale_verifications(&state).await {
+                Ok(count) => {
+                    state.job_monitor.record_success(EXPIRY_JOB);
+                    if count > 0 {
+                        tracing::info!("Expired {} identity verifications", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(EXPIRY_JOB, e.to_string());
+                    tracing::error!("Verification expiry job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+async fn expire_stale_verifications(state: &AppState) -> AppResult<usize> {
+    let cutoff = Utc::now() - Duration::days(state.config.verification_validity_days);
+    let expired = IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone())
+        .expire_completed_before(cutoff)
+        .await?;
+
+    let kyc_policy = KycPolicyService::new(
+        KycRepository::new(state.postgres.clone()),
+        KycLimits::from_config(&state.config),
+        state.audit_logger.clone(),
+    );
+
+    for verification in &expired {
+        let event = AuditEvent::new(AuditEventType::VerificationExpired)
+            .severity(AuditSeverity::Warning)
+            .user_id(verification.user_id)
+            .resource(format!("identity_verification:{}", verification.id))
+            .action("expire".to_string())
+            .metadata("completed_at".to_string(), json!(verification.completed_at))
+            .compliance_tag("KYC".to_string());
+        state.audit_logger.log(event).await;
+
+        // Expired identity checks no longer count towards the user's tier
+        kyc_policy.refresh_user_tier(verification.user_id, Uuid::nil()).await?;
+
+        if let Some(url) = &state.config.verification_expiry_webhook_url {
+            queue_expiry_webhook(state, url, verification).await;
+        }
+    }
+
+    Ok(expired.len())
+}
+
+async fn queue_expiry_webhook(
+    state: &AppState,
+    url: &str,
+    verification: &IdentityVerification,
+) {
+    let event_type = "identity_verification.expired";
+    let payload = json!({
+        "event": event_type,
+        "verification_id": verification.id,
+        "user_id": verification.user_id,
+        "verification_type": verification.verification_type,
+        "completed_at": verification.completed_at,
+        "expired_at": Utc::now(),
+    });
+
+    if let Err(e) = WebhookRepository::new(state.postgres.clone())
+        .enqueue(None, event_type, url, &payload)
+        .await
+    {
+        tracing::error!("Failed to queue verification expiry webhook for {}: {}", verification.id, e);
+    }
+}
+
+/// Periodically screen verifications again against PEP lists and adverse
+/// media, so people who become exposed after onboarding are caught
+pub fn spawn_rescreening_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.screening_rescreen_interval_seconds);
+    state.job_monitor.register(RESCREENING_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match rescreen_verifications(&state).await {
+                Ok(count) => {
+                    state.job_monitor.record_success(RESCREENING_JOB);
+                    if count > 0 {
+                        tracing::info!("Re-screened {} identity verifications", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(RESCREENING_JOB, e.to_string());
+                    tracing::error!("Identity re-screening job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Run one batch; a no-op while screening is turned off
+async fn rescreen_verifications(state: &AppState) -> AppResult<usize> {
+    let Some(provider) = screening::from_config(&state.config, &state.circuit_breakers, &state.http_clients)? else {
+        return Ok(0);
+    };
+    ScreeningService::new(
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        provider,
+        ScreeningSettings::from_config(&state.config),
+        ReviewQueue::new(ReviewRepository::new(state.postgres.clone()), state.audit_logger.clone()),
+        state.audit_logger.clone(),
+    )
+    .rescreen_due()
+    .await
+}
@@ -1,14 +1,30 @@
+pub mod address;
 pub mod controller;
+pub mod images;
+pub mod jobs;
 pub mod model;
 pub mod repository;
+pub mod screening;
 pub mod service;
 
-use axum::{routing::{get, post}, Router};
+use axum::{extract::DefaultBodyLimit, routing::{get, post}, Router};
 use crate::core::AppState;
+use crate::shared::constants::MAX_IMAGE_SIZE;
+
+/// Room for one image plus the multipart framing
+const FACE_QUALITY_BODY_LIMIT: usize = MAX_IMAGE_SIZE + 64 * 1024;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/verify", post(controller::initiate_verification))
         .route("/verify/status/:id", get(controller::get_verification_status))
         .route("/verify/complete", post(controller::complete_verification))
+        .route("/verify/:id/screening", post(controller::screen_verification))
+        .route("/users/:user_id/address", get(controller::get_address).put(controller::set_address))
+        .route("/users/:user_id/address/verify", post(controller::verify_address))
+        .route("/kyc-status/:user_id", get(controller::get_kyc_status))
+        .route(
+            "/face/quality",
+            post(controller::check_face_quality).layer(DefaultBodyLimit::max(FACE_QUALITY_BODY_LIMIT)),
+        )
 }
\ No newline at end of file
@@ -1,13 +1,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{types::Json, FromRow};
+use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
+use crate::kyc::model::KycTier;
 use crate::shared::types::UserId;
 
 /// Identity verification status
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "verification_status", rename_all = "lowercase")]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "verification_status", rename_all = "snake_case")]
 pub enum VerificationStatus {
     Pending,
     InProgress,
@@ -34,7 +37,7 @@ pub struct IdentityVerification {
 }
 
 /// Identity verification request
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct VerificationRequest {
     pub verification_type: String,
     pub document_type: String,
@@ -43,7 +46,7 @@ pub struct VerificationRequest {
 }
 
 /// Verification response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct VerificationResponse {
     pub id: Uuid,
     pub status: VerificationStatus,
@@ -62,4 +65,332 @@ impl From<IdentityVerification> for VerificationResponse {
             completed_at: verification.completed_at,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Outcome of screening the person behind a verification against PEP and
+/// adverse-media lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "screening_status", rename_all = "snake_case")]
+pub enum ScreeningStatus {
+    /// Nothing on any list
+    Clear,
+    /// Matches waiting for a reviewer
+    PotentialMatch,
+    /// A reviewer approved the verification despite the matches
+    Dismissed,
+}
+
+/// Kind of list a screening match came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningCategory {
+    /// Politically exposed person, or their family or close associate
+    Pep,
+    /// Negative news coverage, e.g. of fraud or financial crime
+    AdverseMedia,
+}
+
+/// A list entry the screened person may be
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ScreeningMatch {
+    pub category: ScreeningCategory,
+    /// Name on the list entry
+    pub name: String,
+    /// How closely the entry matches the person, from 0 to 1
+    pub score: f64,
+    /// List or publication the entry comes from
+    pub source: Option<String>,
+    /// Position held, or what the coverage is about
+    pub details: Option<String>,
+}
+
+/// The latest screening of a verification
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct VerificationScreening {
+    pub verification_id: Uuid,
+    /// Absent until the verification is first screened
+    pub status: Option<ScreeningStatus>,
+    #[schema(value_type = Vec<ScreeningMatch>)]
+    pub matches: Json<Vec<ScreeningMatch>>,
+    /// Contribution of the matches to the user's risk, from 0 to 100
+    pub risk_score: Option<i16>,
+    pub provider: Option<String>,
+    pub reference: Option<String>,
+    pub screened_at: Option<DateTime<Utc>>,
+}
+
+/// Identity verifications of this type record a face enrollment (a selfie
+/// matched to the ID document and checked for liveness)
+pub const FACE_VERIFICATION_TYPE: &str = "face";
+
+/// A postal address
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
+pub struct PostalAddress {
+    #[validate(length(min = 1, max = 200))]
+    pub line1: String,
+    #[validate(length(max = 200))]
+    pub line2: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub city: String,
+    /// State, province or county, where the country has them
+    #[validate(length(max = 100))]
+    pub region: Option<String>,
+    #[validate(length(min = 1, max = 20))]
+    pub postal_code: String,
+    /// ISO 3166-1 alpha-2 country code
+    #[validate(length(equal = 2))]
+    pub country: String,
+}
+
+/// Whether an address provider confirmed the user lives at their address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "address_verification_status", rename_all = "snake_case")]
+pub enum AddressVerificationStatus {
+    Unverified,
+    Verified,
+    Failed,
+}
+
+/// A user's stored address; `address` holds the encrypted `PostalAddress`
+#[derive(Debug, Clone, FromRow)]
+pub struct UserAddress {
+    pub user_id: UserId,
+    pub address: serde_json::Value,
+    pub country: String,
+    pub verification_status: AddressVerificationStatus,
+    pub provider: Option<String>,
+    pub provider_reference: Option<String>,
+    pub failure_reason: Option<String>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A user's address and its verification
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserAddressResponse {
+    pub user_id: UserId,
+    /// Absent once the user's personal data has been erased
+    pub address: Option<PostalAddress>,
+    pub verification_status: AddressVerificationStatus,
+    pub provider: Option<String>,
+    pub failure_reason: Option<String>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One step of KYC onboarding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KycStep {
+    EmailVerified,
+    PhoneVerified,
+    IdVerified,
+    FaceEnrolled,
+    AddressVerified,
+    IncomeVerified,
+}
+
+/// How far a user is through a KYC step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KycStepStatus {
+    /// Not started
+    Missing,
+    /// Started, waiting on the user or a provider
+    Pending,
+    Completed,
+    /// Tried and failed; the user may try again
+    Failed,
+    /// Completed once but has lapsed and must be redone
+    Expired,
+}
+
+impl KycStepStatus {
+    /// Read the status of a verification record (`pending`, `in_progress`,
+    /// `completed`, `failed`, `expired`); no record is `Missing`
+    pub fn from_verification(status: Option<&str>) -> Self {
+        match status {
+            None => KycStepStatus::Missing,
+            Some("completed") => KycStepStatus::Completed,
+            Some("failed") => KycStepStatus::Failed,
+            Some("expired") => KycStepStatus::Expired,
+            Some(_) => KycStepStatus::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct KycStepState {
+    pub step: KycStep,
+    pub status: KycStepStatus,
+}
+
+/// The verification outcomes a KYC status is built from
+#[derive(Debug, Clone, Default, FromRow)]
+pub struct KycFacts {
+    pub email_verified: bool,
+    pub phone_verified: bool,
+    /// Status of the user's most relevant ID document verification
+    pub identity_status: Option<String>,
+    pub face_status: Option<String>,
+    pub address_status: Option<AddressVerificationStatus>,
+    pub income_status: Option<String>,
+}
+
+/// How complete a user's KYC profile is
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KycStatusResponse {
+    pub user_id: UserId,
+    pub kyc_tier: KycTier,
+    /// Every step is completed
+    pub complete: bool,
+    pub steps: Vec<KycStepState>,
+    /// The steps not completed, in onboarding order
+    pub missing_steps: Vec<KycStep>,
+}
+
+impl KycStatusResponse {
+    pub fn new(user_id: UserId, kyc_tier: KycTier, facts: &KycFacts) -> Self {
+        let flag = |verified: bool| {
+            if verified {
+                KycStepStatus::Completed
+            } else {
+                KycStepStatus::Missing
+            }
+        };
+        let address = match facts.address_status {
+            None => KycStepStatus::Missing,
+            Some(AddressVerificationStatus::Unverified) => KycStepStatus::Pending,
+            Some(AddressVerificationStatus::Verified) => KycStepStatus::Completed,
+            Some(AddressVerificationStatus::Failed) => KycStepStatus::Failed,
+        };
+        let steps = vec![
+            KycStepState {
+                step: KycStep::EmailVerified,
+                status: flag(facts.email_verified),
+            },
+            KycStepState {
+                step: KycStep::PhoneVerified,
+                status: flag(facts.phone_verified),
+            },
+            KycStepState {
+                step: KycStep::IdVerified,
+                status: KycStepStatus::from_verification(facts.identity_status.as_deref()),
+            },
+            KycStepState {
+                step: KycStep::FaceEnrolled,
+                status: KycStepStatus::from_verification(facts.face_status.as_deref()),
+            },
+            KycStepState {
+                step: KycStep::AddressVerified,
+                status: address,
+            },
+            KycStepState {
+                step: KycStep::IncomeVerified,
+                status: KycStepStatus::from_verification(facts.income_status.as_deref()),
+            },
+        ];
+        let missing_steps: Vec<KycStep> = steps
+            .iter()
+            .filter(|state| state.status != KycStepStatus::Completed)
+            .map(|state| state.step)
+            .collect();
+
+        Self {
+            user_id,
+            kyc_tier,
+            complete: missing_steps.is_empty(),
+            steps,
+            missing_steps,
+        }
+    }
+}
+
+/// Something about a face photo the user should fix by retaking it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FaceQualityFeedback {
+    TooDark,
+    TooBright,
+    Blurred,
+}
+
+/// Measurements of a face photo, each from 0 to 1
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct QualityScores {
+    /// Mean luminance
+    pub brightness: f64,
+    /// Edge contrast; 1 is sharp
+    pub sharpness: f64,
+}
+
+/// Whether a face photo is good enough to submit, and what to fix if not
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FaceQualityResponse {
+    /// No feedback to act on
+    pub acceptable: bool,
+    pub scores: QualityScores,
+    pub feedback: Vec<FaceQualityFeedback>,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_steps_list_everything_not_completed() {
+        let facts = KycFacts {
+            email_verified: true,
+            phone_verified: false,
+            identity_status: Some("completed".to_string()),
+            face_status: Some("in_progress".to_string()),
+            address_status: Some(AddressVerificationStatus::Failed),
+            income_status: None,
+        };
+        let status = KycStatusResponse::new(Uuid::new_v4(), KycTier::Tier1, &facts);
+
+        assert!(!status.complete);
+        assert_eq!(
+            status.missing_steps,
+            vec![
+                KycStep::PhoneVerified,
+                KycStep::FaceEnrolled,
+                KycStep::AddressVerified,
+                KycStep::IncomeVerified
+            ]
+        );
+        let statuses: Vec<KycStepStatus> = status.steps.iter().map(|state| state.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                KycStepStatus::Completed,
+                KycStepStatus::Missing,
+                KycStepStatus::Completed,
+                KycStepStatus::Pending,
+                KycStepStatus::Failed,
+                KycStepStatus::Missing,
+            ]
+        );
+        assert_eq!(serde_json::to_value(status.missing_steps[0]).unwrap(), "phone_verified");
+    }
+
+    #[test]
+    fn a_profile_with_every_step_completed_is_complete() {
+        let facts = KycFacts {
+            email_verified: true,
+            phone_verified: true,
+            identity_status: Some("completed".to_string()),
+            face_status: Some("completed".to_string()),
+            address_status: Some(AddressVerificationStatus::Verified),
+            income_status: Some("completed".to_string()),
+        };
+        let status = KycStatusResponse::new(Uuid::new_v4(), KycTier::Tier2, &facts);
+        assert!(status.complete);
+        assert!(status.missing_steps.is_empty());
+    }
+}
@@ -1,23 +1,67 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgPool};
 use uuid::Uuid;
-use crate::core::error::AppResult;
-use crate::shared::{traits::Repository, types::UserId};
-use super::model::{IdentityVerification, VerificationStatus};
+use crate::core::crypto::UserCipher;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::{traits::Repository, types::{TenantId, UserId}};
+use super::address::AddressCheck;
+use super::model::{
+    AddressVerificationStatus, IdentityVerification, KycFacts, PostalAddress, ScreeningMatch, ScreeningStatus,
+    UserAddress, VerificationScreening, VerificationStatus, FACE_VERIFICATION_TYPE,
+};
 
+const VERIFICATION_COLUMNS: &str = "id, user_id, verification_type, status, document_type, document_number,
+    verification_data, provider, provider_reference, completed_at, created_at, updated_at";
+
+const SCREENING_COLUMNS: &str = "id AS verification_id, screening_status AS status, screening_matches AS matches,
+    screening_risk_score AS risk_score, screening_provider AS provider, screening_reference AS reference, screened_at";
+
+const ADDRESS_COLUMNS: &str =
+    "user_id, address, country, verification_status, provider, provider_reference,
+    failure_reason, verified_at, created_at, updated_at";
+
+/// Identity verification persistence. `document_number` and `verification_data`
+/// are encrypted under the user's data key before they are written and
+/// decrypted as they are read; once the key is shredded they read as empty.
+#[derive(Clone)]
 pub struct IdentityRepository {
     pool: PgPool,
+    cipher: UserCipher,
 }
 
 impl IdentityRepository {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool, cipher: UserCipher) -> Self {
+        Self { pool, cipher }
     }
 
     /// Find verifications by user ID
-    pub async fn find_by_user_id(&self, _user_id: UserId) -> AppResult<Vec<IdentityVerification>> {
-        // TODO: Implement database query
-        Ok(Vec::new())
+    pub async fn find_by_user_id(&self, user_id: UserId) -> AppResult<Vec<IdentityVerification>> {
+        let verifications = sqlx::query_as::<_, IdentityVerification>(&format!(
+            "SELECT {VERIFICATION_COLUMNS} FROM identity_verifications
+             WHERE user_id = $1 ORDER BY created_at DESC"
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.decrypt_all(verifications).await
+    }
+
+    /// Mark completed verifications finished before `cutoff` as expired,
+    /// returning the verifications that were expired
+    pub async fn expire_completed_before(&self, cutoff: DateTime<Utc>) -> AppResult<Vec<IdentityVerification>> {
+        let expired = sqlx::query_as::<_, IdentityVerification>(&format!(
+            "UPDATE identity_verifications
+             SET status = 'expired', updated_at = NOW()
+             WHERE status = 'completed' AND completed_at < $1
+             RETURNING {VERIFICATION_COLUMNS}"
+        ))
+        .bind(cutoff)
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.decrypt_all(expired).await
     }
 
     /// Update verification status
@@ -29,18 +73,317 @@ impl IdentityRepository {
         // TODO: Implement status update
         Ok(())
     }
+
+    /// Clear the user's values that are not encrypted under their data key,
+    /// written before per-user keys, which shredding the key cannot erase
+    pub async fn clear_unshreddable(&self, user_id: UserId) -> AppResult<u64> {
+        let result = sqlx::query(
+            "UPDATE identity_verifications SET
+                 document_number = CASE WHEN document_number LIKE 'enc:u1:%' THEN document_number END,
+                 verification_data = CASE WHEN verification_data #>> '{}' LIKE 'enc:u1:%' THEN verification_data END,
+                 updated_at = NOW()
+             WHERE user_id = $1
+               AND (document_number NOT LIKE 'enc:u1:%' OR verification_data #>> '{}' NOT LIKE 'enc:u1:%')",
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Whether the user belongs to the tenant
+    pub async fn user_in_tenant(&self, user_id: UserId, tenant_id: TenantId) -> AppResult<bool> {
+        let exists = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM users WHERE id = $1 AND tenant_id = $2)")
+            .bind(user_id)
+            .bind(tenant_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(exists)
+    }
+
+    /// The user's first and last name
+    pub async fn find_user_name(&self, user_id: UserId) -> AppResult<Option<(String, String)>> {
+        let name = sqlx::query_as("SELECT first_name, last_name FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(name)
+    }
+
+    /// The user's address, decrypted; the address reads as `None` once the
+    /// user's data key is shredded
+    pub async fn find_address(&self, user_id: UserId) -> AppResult<Option<(UserAddress, Option<PostalAddress>)>> {
+        let address = sqlx::query_as::<_, UserAddress>(&format!(
+            "SELECT {ADDRESS_COLUMNS} FROM user_addresses WHERE user_id = $1"
+        ))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match address {
+            Some(address) => {
+                let postal = self.decrypt_address(&address).await?;
+                Ok(Some((address, postal)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Store the user's address, resetting its verification
+    pub async fn upsert_address(&self, user_id: UserId, address: &PostalAddress) -> AppResult<UserAddress> {
+        let plaintext = serde_json::to_value(address)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize address: {}", e)))?;
+        let encrypted = self.cipher.encrypt_json(user_id, &plaintext).await?;
+
+        let stored = sqlx::query_as::<_, UserAddress>(&format!(
+            "INSERT INTO user_addresses (user_id, address, country)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (user_id) DO UPDATE SET
+                 address = EXCLUDED.address,
+                 country = EXCLUDED.country,
+                 verification_status = 'unverified',
+                 provider = NULL,
+                 provider_reference = NULL,
+                 failure_reason = NULL,
+                 verified_at = NULL,
+                 updated_at = NOW()
+             RETURNING {ADDRESS_COLUMNS}"
+        ))
+        .bind(user_id)
+        .bind(encrypted)
+        .bind(&address.country)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(stored)
+    }
+
+    /// Record what the address provider found
+    pub async fn record_address_check(
+        &self,
+        user_id: UserId,
+        provider: &str,
+        check: &AddressCheck,
+    ) -> AppResult<UserAddress> {
+        let status = if check.verified {
+            AddressVerificationStatus::Verified
+        } else {
+            AddressVerificationStatus::Failed
+        };
+        let stored = sqlx::query_as::<_, UserAddress>(&format!(
+            "UPDATE user_addresses SET
+                 verification_status = $2,
+                 provider = $3,
+                 provider_reference = $4,
+                 failure_reason = $5,
+                 verified_at = CASE WHEN $2 = 'verified'::address_verification_status THEN NOW() END,
+                 updated_at = NOW()
+             WHERE user_id = $1
+             RETURNING {ADDRESS_COLUMNS}"
+        ))
+        .bind(user_id)
+        .bind(status)
+        .bind(provider)
+        .bind(&check.reference)
+        .bind(&check.reason)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(stored)
+    }
+
+    /// The verification outcomes the user's KYC status is built from. For
+    /// each kind of verification a completed one counts first, then the
+    /// newest.
+    pub async fn find_kyc_facts(&self, user_id: UserId) -> AppResult<Option<KycFacts>> {
+        let facts = sqlx::query_as::<_, KycFacts>(
+            "SELECT
+                 COALESCE(u.is_verified, FALSE) AS email_verified,
+                 u.phone_verified,
+                 (SELECT COALESCE(v.status::TEXT, 'pending') FROM identity_verifications v
+                  WHERE v.user_id = u.id AND v.verification_type <> $2
+                  ORDER BY v.status = 'completed' DESC NULLS LAST, v.created_at DESC LIMIT 1) AS identity_status,
+                 (SELECT COALESCE(v.status::TEXT, 'pending') FROM identity_verifications v
+                  WHERE v.user_id = u.id AND v.verification_type = $2
+                  ORDER BY v.status = 'completed' DESC NULLS LAST, v.created_at DESC LIMIT 1) AS face_status,
+                 (SELECT a.verification_status FROM user_addresses a WHERE a.user_id = u.id) AS address_status,
+                 (SELECT COALESCE(i.status::TEXT, 'pending') FROM income_verifications i
+                  WHERE i.user_id = u.id
+                  ORDER BY i.status = 'completed' DESC NULLS LAST, i.created_at DESC LIMIT 1) AS income_status
+             FROM users u
+             WHERE u.id = $1",
+        )
+        .bind(user_id)
+        .bind(FACE_VERIFICATION_TYPE)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(facts)
+    }
+
+    /// The verification's latest screening
+    pub async fn find_screening(&self, verification_id: Uuid) -> AppResult<Option<VerificationScreening>> {
+        let screening = sqlx::query_as::<_, VerificationScreening>(&format!(
+            "SELECT {SCREENING_COLUMNS} FROM identity_verifications WHERE id = $1"
+        ))
+        .bind(verification_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(screening)
+    }
+
+    /// Replace the verification's screening with a new one
+    pub async fn record_screening(
+        &self,
+        verification_id: Uuid,
+        status: ScreeningStatus,
+        matches: &[ScreeningMatch],
+        risk_score: i16,
+        provider: &str,
+        reference: Option<&str>,
+    ) -> AppResult<VerificationScreening> {
+        let screening = sqlx::query_as::<_, VerificationScreening>(&format!(
+            "UPDATE identity_verifications SET
+                 screening_status = $2,
+                 screening_matches = $3,
+                 screening_risk_score = $4,
+                 screening_provider = $5,
+                 screening_reference = $6,
+                 screened_at = NOW(),
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING {SCREENING_COLUMNS}"
+        ))
+        .bind(verification_id)
+        .bind(status)
+        .bind(Json(matches))
+        .bind(risk_score)
+        .bind(provider)
+        .bind(reference)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(screening)
+    }
+
+    /// Open and completed ID document verifications never screened or last
+    /// screened before `screened_before`, longest ago first
+    pub async fn find_due_for_screening(
+        &self,
+        screened_before: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<IdentityVerification>> {
+        let due = sqlx::query_as::<_, IdentityVerification>(&format!(
+            "SELECT {VERIFICATION_COLUMNS} FROM identity_verifications
+             WHERE status IN ('pending', 'in_progress', 'completed')
+               AND verification_type <> $1
+               AND (screened_at IS NULL OR screened_at < $2)
+             ORDER BY screened_at NULLS FIRST, created_at
+             LIMIT $3"
+        ))
+        .bind(FACE_VERIFICATION_TYPE)
+        .bind(screened_before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        self.decrypt_all(due).await
+    }
+
+    /// Country of the user's address, kept in the clear
+    pub async fn find_address_country(&self, user_id: UserId) -> AppResult<Option<String>> {
+        let country = sqlx::query_scalar("SELECT country FROM user_addresses WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(country)
+    }
+
+    async fn decrypt_address(&self, address: &UserAddress) -> AppResult<Option<PostalAddress>> {
+        let Some(plaintext) = self.cipher.decrypt_json(address.user_id, address.address.clone()).await? else {
+            return Ok(None);
+        };
+        let postal = serde_json::from_value(plaintext)
+            .map_err(|e| AppError::Internal(format!("Stored address for user {} is invalid: {}", address.user_id, e)))?;
+        Ok(Some(postal))
+    }
+
+    async fn encrypt(&self, mut verification: IdentityVerification) -> AppResult<IdentityVerification> {
+        let user_id = verification.user_id;
+        if let Some(document_number) = &verification.document_number {
+            verification.document_number = Some(self.cipher.encrypt_str(user_id, document_number).await?);
+        }
+        if let Some(data) = &verification.verification_data {
+            verification.verification_data = Some(self.cipher.encrypt_json(user_id, data).await?);
+        }
+        Ok(verification)
+    }
+
+    async fn decrypt(&self, mut verification: IdentityVerification) -> AppResult<IdentityVerification> {
+        let user_id = verification.user_id;
+        if let Some(document_number) = verification.document_number.take() {
+            verification.document_number = self.cipher.decrypt_str(user_id, &document_number).await?;
+        }
+        if let Some(data) = verification.verification_data.take() {
+            verification.verification_data = self.cipher.decrypt_json(user_id, data).await?;
+        }
+        Ok(verification)
+    }
+
+    async fn decrypt_all(&self, verifications: Vec<IdentityVerification>) -> AppResult<Vec<IdentityVerification>> {
+        let mut decrypted = Vec::with_capacity(verifications.len());
+        for verification in verifications {
+            decrypted.push(self.decrypt(verification).await?);
+        }
+        Ok(decrypted)
+    }
 }
 
 #[async_trait]
 impl Repository<IdentityVerification, Uuid> for IdentityRepository {
     async fn create(&self, verification: IdentityVerification) -> AppResult<IdentityVerification> {
-        // TODO: Implement verification creation
-        Ok(verification)
+        let encrypted = self.encrypt(verification).await?;
+
+        let created = sqlx::query_as::<_, IdentityVerification>(&format!(
+            "INSERT INTO identity_verifications ({VERIFICATION_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+             RETURNING {VERIFICATION_COLUMNS}"
+        ))
+        .bind(encrypted.id)
+        .bind(encrypted.user_id)
+        .bind(&encrypted.verification_type)
+        .bind(&encrypted.status)
+        .bind(&encrypted.document_type)
+        .bind(&encrypted.document_number)
+        .bind(&encrypted.verification_data)
+        .bind(&encrypted.provider)
+        .bind(&encrypted.provider_reference)
+        .bind(encrypted.completed_at)
+        .bind(encrypted.created_at)
+        .bind(encrypted.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        self.decrypt(created).await
     }
 
-    async fn find_by_id(&self, _id: Uuid) -> AppResult<Option<IdentityVerification>> {
-        // TODO: Implement find by ID
-        Ok(None)
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<IdentityVerification>> {
+        let verification = sqlx::query_as::<_, IdentityVerification>(&format!(
+            "SELECT {VERIFICATION_COLUMNS} FROM identity_verifications WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match verification {
+            Some(verification) => Ok(Some(self.decrypt(verification).await?)),
+            None => Ok(None),
+        }
     }
 
     async fn update(&self, _id: Uuid, verification: IdentityVerification) -> AppResult<IdentityVerification> {
@@ -57,4 +400,4 @@ impl Repository<IdentityVerification, Uuid> for IdentityRepository {
         // TODO: Implement paginated listing
         Ok(Vec::new())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,318 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::core::circuit_breaker::{CircuitBreaker, CircuitBreakerSettings, CircuitBreakers};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use super::model::{ScreeningCategory, ScreeningMatch, ScreeningStatus, VerificationScreening};
+
+/// The person a verification is for, as sent to the screening provider
+#[derive(Debug, Clone)]
+pub struct ScreeningSubject {
+    pub verification_id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    /// From the verification's data, when the document carried it
+    pub date_of_birth: Option<String>,
+    /// ISO 3166-1 alpha-2 country of the user's address
+    pub country: Option<String>,
+}
+
+/// What a screening provider found
+#[derive(Debug, Clone, Default)]
+pub struct ScreeningOutcome {
+    /// The provider's reference for the screening
+    pub reference: Option<String>,
+    pub matches: Vec<ScreeningMatch>,
+}
+
+/// Screens people against politically-exposed-person lists and adverse media
+#[async_trait]
+pub trait ScreeningProvider: Send + Sync {
+    /// Name stored with each screening, matching `SCREENING_PROVIDER`
+    fn name(&self) -> &'static str;
+
+    async fn screen(&self, subject: &ScreeningSubject, categories: &[ScreeningCategory]) -> AppResult<ScreeningOutcome>;
+}
+
+/// Name of the screening provider's circuit breaker
+const SCREENING_BREAKER: &str = "screening";
+
+/// Talks to an HTTP screening API taking `{reference, first_name, last_name,
+/// date_of_birth, country, categories}` JSON with a bearer API key and
+/// answering `{reference, matches: [{category, name, score, source, details}]}`
+pub struct HttpScreeningProvider {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    breaker: CircuitBreaker,
+}
+
+impl HttpScreeningProvider {
+    pub fn new(api_url: String, api_key: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            breaker: CircuitBreaker::new(SCREENING_BREAKER, CircuitBreakerSettings::default()),
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    async fn send(&self, subject: &ScreeningSubject, categories: &[ScreeningCategory]) -> AppResult<ScreeningOutcome> {
+        let request = self
+            .client
+            .post(format!("{}/screenings", self.api_url.trim_end_matches('/')))
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({
+                "reference": subject.verification_id,
+                "first_name": subject.first_name,
+                "last_name": subject.last_name,
+                "date_of_birth": subject.date_of_birth,
+                "country": subject.country,
+                "categories": categories,
+            }));
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Screening request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Screening provider returned {}",
+                response.status()
+            )));
+        }
+
+        let body: HttpScreening = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Invalid screening provider response: {}", e)))?;
+        Ok(ScreeningOutcome {
+            reference: body.reference,
+            matches: body.matches,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpScreening {
+    reference: Option<String>,
+    #[serde(default)]
+    matches: Vec<ScreeningMatch>,
+}
+
+#[async_trait]
+impl ScreeningProvider for HttpScreeningProvider {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn screen(
+        &self,
+        subject: &ScreeningSubject,
+        categories: &[ScreeningCategory],
+    ) -> AppResult<ScreeningOutcome> {
+        self.breaker.call(self.send(subject, categories)).await
+    }
+}
+
+/// Build the screening provider selected by `SCREENING_PROVIDER`; `None`
+/// when screening is turned off
+pub fn from_config(
+    config: &Config,
+    breakers: &CircuitBreakers,
+    clients: &HttpClients,
+) -> AppResult<Option<Arc<dyn ScreeningProvider>>> {
+    match config.screening_provider.as_str() {
+        "none" => Ok(None),
+        "http" => {
+            let missing =
+                |name: &str| AppError::Internal(format!("{} is required for the http screening provider", name));
+            Ok(Some(Arc::new(
+                HttpScreeningProvider::new(
+                    config.screening_api_url.clone().ok_or_else(|| missing("SCREENING_API_URL"))?,
+                    config.screening_api_key.clone().ok_or_else(|| missing("SCREENING_API_KEY"))?,
+                )
+                .with_circuit_breaker(breakers.get(SCREENING_BREAKER))
+                .with_http_client(clients.get(SCREENING_BREAKER)),
+            )))
+        }
+        other => Err(AppError::Internal(format!("Unknown screening provider '{}'", other))),
+    }
+}
+
+/// How verifications are screened and re-screened
+#[derive(Debug, Clone)]
+pub struct ScreeningSettings {
+    /// Matches scoring below this are dropped as noise
+    pub match_threshold: f64,
+    /// Also screen adverse media, not just PEP lists
+    pub adverse_media: bool,
+    /// How old a screening gets before the verification is screened again
+    pub rescreen_after: Duration,
+    /// Most verifications re-screened per run
+    pub rescreen_batch_size: i64,
+}
+
+impl ScreeningSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            match_threshold: config.screening_match_threshold,
+            adverse_media: config.screening_adverse_media,
+            rescreen_after: Duration::days(config.screening_rescreen_days),
+            rescreen_batch_size: config.screening_rescreen_batch_size,
+        }
+    }
+
+    /// The lists to screen against
+    pub fn categories(&self) -> Vec<ScreeningCategory> {
+        let mut categories = vec![ScreeningCategory::Pep];
+        if self.adverse_media {
+            categories.push(ScreeningCategory::AdverseMedia);
+        }
+        categories
+    }
+
+    /// The provider's matches worth a reviewer's time, strongest first
+    pub fn relevant_matches(&self, matches: Vec<ScreeningMatch>) -> Vec<ScreeningMatch> {
+        let categories = self.categories();
+        let mut relevant: Vec<ScreeningMatch> = matches
+            .into_iter()
+            .filter(|m| categories.contains(&m.category) && m.score >= self.match_threshold)
+            .collect();
+        relevant.sort_by(|a, b| b.score.total_cmp(&a.score));
+        relevant
+    }
+}
+
+/// What the matches add to the user's risk, from 0 to 100: up to 60 for the
+/// strongest PEP match and up to 40 for the strongest adverse-media match
+pub fn screening_risk_score(matches: &[ScreeningMatch]) -> i16 {
+    let strongest = |category: ScreeningCategory| {
+        matches
+            .iter()
+            .filter(|m| m.category == category)
+            .map(|m| m.score.clamp(0.0, 1.0))
+            .fold(0.0, f64::max)
+    };
+    let score = 60.0 * strongest(ScreeningCategory::Pep) + 40.0 * strongest(ScreeningCategory::AdverseMedia);
+    score.round().min(100.0) as i16
+}
+
+/// Status of a new screening given the verification's previous one. Matches
+/// a reviewer already dismissed stay dismissed; any match not seen before
+/// needs a reviewer again.
+pub fn screening_status(previous: Option<&VerificationScreening>, matches: &[ScreeningMatch]) -> ScreeningStatus {
+    if matches.is_empty() {
+        return ScreeningStatus::Clear;
+    }
+    match previous {
+        Some(previous) if previous.status == Some(ScreeningStatus::Dismissed) => {
+            let seen = |m: &ScreeningMatch| {
+                previous
+                    .matches
+                    .iter()
+                    .any(|p| p.category == m.category && p.name.eq_ignore_ascii_case(&m.name))
+            };
+            if matches.iter().all(seen) {
+                ScreeningStatus::Dismissed
+            } else {
+                ScreeningStatus::PotentialMatch
+            }
+        }
+        _ => ScreeningStatus::PotentialMatch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::types::Json;
+
+    fn settings() -> ScreeningSettings {
+        ScreeningSettings {
+            match_threshold: 0.8,
+            adverse_media: false,
+            rescreen_after: Duration::days(30),
+            rescreen_batch_size: 100,
+        }
+    }
+
+    fn hit(category: ScreeningCategory, name: &str, score: f64) -> ScreeningMatch {
+        ScreeningMatch {
+            category,
+            name: name.to_string(),
+            score,
+            source: None,
+            details: None,
+        }
+    }
+
+    #[test]
+    fn weak_matches_and_unscreened_lists_are_dropped() {
+        let matches = vec![
+            hit(ScreeningCategory::Pep, "Jane Doe", 0.82),
+            hit(ScreeningCategory::Pep, "Janet Doe", 0.5),
+            hit(ScreeningCategory::AdverseMedia, "Jane Doe", 0.95),
+            hit(ScreeningCategory::Pep, "J. Doe", 0.9),
+        ];
+
+        let relevant = settings().relevant_matches(matches.clone());
+        let names: Vec<&str> = relevant.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["J. Doe", "Jane Doe"]);
+
+        let with_media = ScreeningSettings { adverse_media: true, ..settings() }.relevant_matches(matches);
+        assert_eq!(with_media.len(), 3);
+        assert_eq!(with_media[0].category, ScreeningCategory::AdverseMedia);
+    }
+
+    #[test]
+    fn risk_scores_weigh_the_strongest_match_of_each_list() {
+        assert_eq!(screening_risk_score(&[]), 0);
+        let peps = [hit(ScreeningCategory::Pep, "a", 0.9), hit(ScreeningCategory::Pep, "b", 0.8)];
+        assert_eq!(screening_risk_score(&peps), 54);
+        let both = [hit(ScreeningCategory::Pep, "a", 1.0), hit(ScreeningCategory::AdverseMedia, "a", 1.0)];
+        assert_eq!(screening_risk_score(&both), 100);
+    }
+
+    #[test]
+    fn dismissed_matches_are_not_flagged_again() {
+        let dismissed = VerificationScreening {
+            verification_id: Uuid::new_v4(),
+            status: Some(ScreeningStatus::Dismissed),
+            matches: Json(vec![hit(ScreeningCategory::Pep, "Jane Doe", 0.9)]),
+            risk_score: Some(54),
+            provider: Some("http".to_string()),
+            reference: None,
+            screened_at: None,
+        };
+
+        assert_eq!(screening_status(Some(&dismissed), &[]), ScreeningStatus::Clear);
+        assert_eq!(
+            screening_status(Some(&dismissed), &[hit(ScreeningCategory::Pep, "JANE DOE", 0.95)]),
+            ScreeningStatus::Dismissed
+        );
+        assert_eq!(
+            screening_status(Some(&dismissed), &[hit(ScreeningCategory::AdverseMedia, "Jane Doe", 0.95)]),
+            ScreeningStatus::PotentialMatch
+        );
+        assert_eq!(
+            screening_status(None, &[hit(ScreeningCategory::Pep, "Jane Doe", 0.9)]),
+            ScreeningStatus::PotentialMatch
+        );
+    }
+}
@@ -1,19 +1,40 @@
+use std::sync::Arc;
 use uuid::Uuid;
 use chrono::Utc;
+use serde_json::json;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
 use crate::core::error::{AppError, AppResult};
-use crate::shared::{traits::Repository, types::UserId};
+use crate::kyc::repository::KycRepository;
+use crate::reviews::{model::VerificationKind, service::ReviewQueue};
+use crate::shared::{traits::Repository, types::{TenantId, UserId}};
+use super::address::{normalize_address, AddressVerifier};
 use super::model::{
-    IdentityVerification, VerificationRequest, VerificationResponse, VerificationStatus
+    AddressVerificationStatus, IdentityVerification, KycStatusResponse, PostalAddress, ScreeningStatus,
+    UserAddress, UserAddressResponse, VerificationRequest, VerificationResponse, VerificationScreening,
+    VerificationStatus, FACE_VERIFICATION_TYPE
 };
 use super::repository::IdentityRepository;
+use super::screening::{
+    screening_risk_score, screening_status, ScreeningProvider, ScreeningSettings, ScreeningSubject,
+};
 
 pub struct IdentityService {
     repository: IdentityRepository,
+    screening: Option<ScreeningService>,
 }
 
 impl IdentityService {
     pub fn new(repository: IdentityRepository) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            screening: None,
+        }
+    }
+
+    /// Screen new verifications against PEP lists and adverse media
+    pub fn with_screening(mut self, screening: ScreeningService) -> Self {
+        self.screening = Some(screening);
+        self
     }
 
     /// Initiate identity verification
@@ -40,6 +61,15 @@ impl IdentityService {
         };
 
         let created_verification = self.repository.create(verification).await?;
+
+        // A provider outage must not block the verification; the
+        // re-screening job picks up verifications never screened
+        if let Some(screening) = &self.screening {
+            if let Err(e) = screening.screen(&created_verification).await {
+                tracing::warn!("Screening of verification {} failed: {}", created_verification.id, e);
+            }
+        }
+
         Ok(VerificationResponse::from(created_verification))
     }
 
@@ -56,4 +86,301 @@ impl IdentityService {
         let verifications = self.repository.find_by_user_id(user_id).await?;
         Ok(verifications.into_iter().map(VerificationResponse::from).collect())
     }
-}
\ No newline at end of file
+}
+
+/// Screens the people behind identity verifications against PEP lists and
+/// adverse media, queueing verifications with matches for manual review
+pub struct ScreeningService {
+    repository: IdentityRepository,
+    provider: Arc<dyn ScreeningProvider>,
+    settings: ScreeningSettings,
+    review_queue: ReviewQueue,
+    audit_logger: AuditLogger,
+}
+
+impl ScreeningService {
+    pub fn new(
+        repository: IdentityRepository,
+        provider: Arc<dyn ScreeningProvider>,
+        settings: ScreeningSettings,
+        review_queue: ReviewQueue,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            provider,
+            settings,
+            review_queue,
+            audit_logger,
+        }
+    }
+
+    /// Screen a verification of a user in the tenant now
+    pub async fn screen_verification(
+        &self,
+        verification_id: Uuid,
+        tenant_id: TenantId,
+    ) -> AppResult<VerificationScreening> {
+        let verification = self
+            .repository
+            .find_by_id(verification_id)
+            .await?
+            .filter(|verification| verification.verification_type != FACE_VERIFICATION_TYPE)
+            .ok_or_else(|| AppError::NotFound("Verification not found".to_string()))?;
+        if !self.repository.user_in_tenant(verification.user_id, tenant_id).await? {
+            return Err(AppError::NotFound("Verification not found".to_string()));
+        }
+        if matches!(verification.status, VerificationStatus::Failed | VerificationStatus::Expired) {
+            return Err(AppError::Conflict("Failed and expired verifications are not screened".to_string()));
+        }
+
+        self.screen(&verification).await
+    }
+
+    /// Screen the person behind the verification and store the result.
+    /// Matches not dismissed by a reviewer before queue the verification
+    /// for review.
+    pub async fn screen(&self, verification: &IdentityVerification) -> AppResult<VerificationScreening> {
+        let (first_name, last_name) = self
+            .repository
+            .find_user_name(verification.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        let subject = ScreeningSubject {
+            verification_id: verification.id,
+            first_name,
+            last_name,
+            date_of_birth: verification
+                .verification_data
+                .as_ref()
+                .and_then(|data| data.get("date_of_birth"))
+                .and_then(|value| value.as_str())
+                .map(str::to_string),
+            country: self.repository.find_address_country(verification.user_id).await?,
+        };
+
+        let outcome = self.provider.screen(&subject, &self.settings.categories()).await?;
+        let matches = self.settings.relevant_matches(outcome.matches);
+        let previous = self.repository.find_screening(verification.id).await?;
+        let status = screening_status(previous.as_ref(), &matches);
+        let risk_score = screening_risk_score(&matches);
+        let screening = self
+            .repository
+            .record_screening(
+                verification.id,
+                status,
+                &matches,
+                risk_score,
+                self.provider.name(),
+                outcome.reference.as_deref(),
+            )
+            .await?;
+
+        let event = if status == ScreeningStatus::PotentialMatch {
+            AuditEvent::new(AuditEventType::ScreeningMatchFound).severity(AuditSeverity::Warning)
+        } else {
+            AuditEvent::new(AuditEventType::ScreeningCompleted)
+        }
+        .user_id(verification.user_id)
+        .resource(format!("identity_verification:{}", verification.id))
+        .action("screen".to_string())
+        .risk_score(risk_score.clamp(0, 100) as u8)
+        .metadata("status".to_string(), json!(status))
+        .metadata("match_count".to_string(), json!(matches.len()))
+        .metadata("provider".to_string(), json!(self.provider.name()))
+        .compliance_tag("KYC".to_string())
+        .compliance_tag("AML".to_string());
+        self.audit_logger.log(event).await;
+
+        if status == ScreeningStatus::PotentialMatch {
+            self.review_queue
+                .flag(
+                    VerificationKind::Identity,
+                    verification.id,
+                    verification.user_id,
+                    format!("Screening found {} potential PEP or adverse media match(es)", matches.len()),
+                    None,
+                )
+                .await?;
+        }
+
+        Ok(screening)
+    }
+
+    /// Screen a batch of verifications whose last screening is older than
+    /// the re-screening interval. Returns how many were screened.
+    pub async fn rescreen_due(&self) -> AppResult<usize> {
+        let due = self
+            .repository
+            .find_due_for_screening(Utc::now() - self.settings.rescreen_after, self.settings.rescreen_batch_size)
+            .await?;
+
+        let mut screened = 0;
+        for verification in &due {
+            match self.screen(verification).await {
+                Ok(_) => screened += 1,
+                Err(e) => tracing::warn!("Re-screening of verification {} failed: {}", verification.id, e),
+            }
+        }
+        Ok(screened)
+    }
+}
+
+/// A user's address and how complete their KYC profile is
+pub struct KycProfileService {
+    repository: IdentityRepository,
+    kyc_repository: KycRepository,
+    address_verifier: Arc<dyn AddressVerifier>,
+    audit_logger: AuditLogger,
+}
+
+impl KycProfileService {
+    pub fn new(
+        repository: IdentityRepository,
+        kyc_repository: KycRepository,
+        address_verifier: Arc<dyn AddressVerifier>,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            kyc_repository,
+            address_verifier,
+            audit_logger,
+        }
+    }
+
+    /// The user's address. User tokens reach only their own user.
+    pub async fn get_address(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+    ) -> AppResult<UserAddressResponse> {
+        self.ensure_user_access(user_id, tenant_id, caller).await?;
+        let (address, postal) = self
+            .repository
+            .find_address(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Address not found".to_string()))?;
+        Ok(address_response(address, postal))
+    }
+
+    /// Store the user's address. A changed address has to be verified again.
+    pub async fn set_address(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+        actor_id: Uuid,
+        address: PostalAddress,
+    ) -> AppResult<UserAddressResponse> {
+        self.ensure_user_access(user_id, tenant_id, caller).await?;
+        let address = normalize_address(address)?;
+
+        if let Some((stored, Some(postal))) = self.repository.find_address(user_id).await? {
+            if postal == address {
+                return Ok(address_response(stored, Some(postal)));
+            }
+        }
+        let stored = self.repository.upsert_address(user_id, &address).await?;
+
+        let event = AuditEvent::new(AuditEventType::AddressUpdated)
+            .user_id(actor_id)
+            .resource(format!("user:{}", user_id))
+            .action("set_address".to_string())
+            .metadata("country".to_string(), json!(address.country))
+            .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(address_response(stored, Some(address)))
+    }
+
+    /// Ask the address provider to confirm the user lives at their address
+    pub async fn verify_address(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+        actor_id: Uuid,
+    ) -> AppResult<UserAddressResponse> {
+        self.ensure_user_access(user_id, tenant_id, caller).await?;
+        let (stored, postal) = self
+            .repository
+            .find_address(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Address not found".to_string()))?;
+        let postal = postal.ok_or_else(|| AppError::Conflict("The user's personal data has been erased".to_string()))?;
+        if stored.verification_status == AddressVerificationStatus::Verified {
+            return Err(AppError::Conflict("The address is already verified".to_string()));
+        }
+        let (first_name, last_name) = self
+            .repository
+            .find_user_name(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let check = self.address_verifier.verify(user_id, &first_name, &last_name, &postal).await?;
+        let stored = self
+            .repository
+            .record_address_check(user_id, self.address_verifier.name(), &check)
+            .await?;
+
+        let event = if check.verified {
+            AuditEvent::new(AuditEventType::AddressVerified)
+        } else {
+            AuditEvent::new(AuditEventType::AddressVerificationFailed)
+                .severity(AuditSeverity::Warning)
+                .success(false)
+        }
+        .user_id(actor_id)
+        .resource(format!("user:{}", user_id))
+        .action("verify_address".to_string())
+        .metadata("provider".to_string(), json!(self.address_verifier.name()))
+        .metadata("reason".to_string(), json!(check.reason))
+        .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(address_response(stored, Some(postal)))
+    }
+
+    /// Which onboarding steps the user has completed and which are left
+    pub async fn kyc_status(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+    ) -> AppResult<KycStatusResponse> {
+        self.ensure_user_access(user_id, tenant_id, caller).await?;
+        let facts = self
+            .repository
+            .find_kyc_facts(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        let (tier, _) = self
+            .kyc_repository
+            .find_user_tier(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        Ok(KycStatusResponse::new(user_id, tier, &facts))
+    }
+
+    async fn ensure_user_access(&self, user_id: UserId, tenant_id: TenantId, caller: Option<UserId>) -> AppResult<()> {
+        let foreign_user = caller.is_some_and(|caller| caller != user_id);
+        if foreign_user || !self.repository.user_in_tenant(user_id, tenant_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        Ok(())
+    }
+}
+
+fn address_response(address: UserAddress, postal: Option<PostalAddress>) -> UserAddressResponse {
+    UserAddressResponse {
+        user_id: address.user_id,
+        address: postal,
+        verification_status: address.verification_status,
+        provider: address.provider,
+        failure_reason: address.failure_reason,
+        verified_at: address.verified_at,
+        updated_at: address.updated_at,
+    }
+}
@@ -0,0 +1,336 @@
+use chrono::{Duration, Months, NaiveDate, Utc};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::identity::repository::IdentityRepository;
+use crate::shared::types::{Amount, Currency, TenantId, UserId};
+use super::model::{
+    AffordabilityAssessment, AffordabilityFactor, AffordabilityQuery, FactorImpact, IncomeSource,
+    IncomeVerificationStatus, LendingBand,
+};
+use super::report::{report_period, start_of_day};
+use super::repository::IncomeRepository;
+
+/// Share of monthly income that obligations, a new repayment included, may take
+const MAX_DEBT_TO_INCOME: f64 = 0.40;
+
+/// Highest debt-to-income and lowest income stability for each band, best first
+const BANDS: [(LendingBand, f64, u32); 3] = [
+    (LendingBand::Prime, 0.30, 70),
+    (LendingBand::NearPrime, 0.40, 50),
+    (LendingBand::Subprime, 0.50, 30),
+];
+
+/// One month of a user's account activity in the assessed currency
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MonthlyActivity {
+    pub inflow: Amount,
+    pub outflow: Amount,
+    /// Part of the outflow paid to recurring payees
+    pub recurring: Amount,
+}
+
+/// Assesses whether a user can afford new lending from their verified income
+/// and account activity
+pub struct AffordabilityService {
+    repository: IncomeRepository,
+    identity_repository: IdentityRepository,
+    audit_logger: AuditLogger,
+}
+
+impl AffordabilityService {
+    pub fn new(repository: IncomeRepository, identity_repository: IdentityRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            identity_repository,
+            audit_logger,
+        }
+    }
+
+    /// Assess a tenant's user over the last `query.months` full months
+    pub async fn assess(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+        query: AffordabilityQuery,
+        actor_id: Uuid,
+    ) -> AppResult<AffordabilityAssessment> {
+        if !self.identity_repository.user_in_tenant(user_id, tenant_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+
+        let (period_start, period_end) = report_period(query.months)?;
+        let (from, until) = (start_of_day(period_start), start_of_day(period_end + Duration::days(1)));
+        let inflows = self.repository.find_monthly_inflows(user_id, from, until).await?;
+        let outflows = self
+            .repository
+            .find_monthly_outflows(user_id, from, until, i64::from(query.months.div_ceil(2)).max(2))
+            .await?;
+        let verifications: Vec<_> = self
+            .repository
+            .find_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .filter(|verification| {
+                matches!(verification.status, IncomeVerificationStatus::Completed)
+                    && verification.annual_income.is_some()
+            })
+            .collect();
+
+        // Assess the requested currency, else the one most income arrives in
+        let mut inflow_totals: Vec<(&Currency, Amount)> = Vec::new();
+        for inflow in &inflows {
+            match inflow_totals.iter_mut().find(|(currency, _)| **currency == inflow.currency) {
+                Some((_, total)) => *total += inflow.total,
+                None => inflow_totals.push((&inflow.currency, inflow.total)),
+            }
+        }
+        let currency = query
+            .currency
+            .map(|currency| currency.to_uppercase())
+            .or_else(|| inflow_totals.iter().max_by_key(|(_, total)| *total).map(|(currency, _)| (*currency).clone()))
+            .or_else(|| verifications.first().map(|verification| verification.currency.clone()))
+            .ok_or_else(|| AppError::NotFound("No income or account activity to assess".to_string()))?;
+
+        let activity: Vec<MonthlyActivity> = (0..query.months)
+            .map(|offset| {
+                let month = period_start + Months::new(offset);
+                MonthlyActivity {
+                    inflow: inflows
+                        .iter()
+                        .filter(|inflow| inflow.month == month && inflow.currency == currency)
+                        .map(|inflow| inflow.total)
+                        .sum(),
+                    outflow: outflows
+                        .iter()
+                        .filter(|outflow| outflow.month == month && outflow.currency == currency)
+                        .map(|outflow| outflow.total)
+                        .sum(),
+                    recurring: outflows
+                        .iter()
+                        .filter(|outflow| outflow.month == month && outflow.currency == currency)
+                        .map(|outflow| outflow.recurring)
+                        .sum(),
+                }
+            })
+            .collect();
+        let verified_monthly_income = verifications
+            .iter()
+            .find(|verification| verification.currency == currency)
+            .and_then(|verification| verification.annual_income)
+            .map(|annual_income| annual_income / 12);
+
+        let assessment = assess(user_id, currency, period_start, period_end, &activity, verified_monthly_income);
+
+        let event = AuditEvent::new(AuditEventType::AffordabilityAssessed)
+            .user_id(actor_id)
+            .resource(format!("user:{}", user_id))
+            .action("assess".to_string())
+            .metadata("months".to_string(), serde_json::json!(query.months))
+            .metadata("currency".to_string(), serde_json::json!(assessment.currency))
+            .metadata("lending_band".to_string(), serde_json::json!(assessment.lending_band))
+            .compliance_tag("INCOME".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(assessment)
+    }
+}
+
+/// Assess monthly activity, one entry per month of the period. Verified
+/// monthly income is preferred over average inflows when there is one.
+pub fn assess(
+    user_id: UserId,
+    currency: Currency,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    activity: &[MonthlyActivity],
+    verified_monthly_income: Option<Amount>,
+) -> AffordabilityAssessment {
+    let months = activity.len().max(1) as i64;
+    let average = |amount: fn(&MonthlyActivity) -> Amount| activity.iter().map(amount).sum::<Amount>() / months;
+    let average_inflow = average(|month| month.inflow);
+    let average_monthly_outflow = average(|month| month.outflow);
+    let monthly_obligations = average(|month| month.recurring);
+
+    let (monthly_income, income_source) = match verified_monthly_income {
+        Some(income) => (income, IncomeSource::Verified),
+        None => (average_inflow, IncomeSource::Observed),
+    };
+
+    // Coefficient of variation of monthly inflows; no inflow at all is as
+    // unstable as income gets
+    let mean = average_inflow as f64;
+    let income_variation = if mean > 0.0 {
+        let variance = activity.iter().map(|month| (month.inflow as f64 - mean).powi(2)).sum::<f64>() / months as f64;
+        variance.sqrt() / mean
+    } else {
+        1.0
+    };
+    let income_stability = ((1.0 - income_variation).clamp(0.0, 1.0) * 100.0).round() as u32;
+    let months_with_income = activity.iter().filter(|month| month.inflow > 0).count() as u32;
+
+    let debt_to_income = if monthly_income > 0 {
+        monthly_obligations as f64 / monthly_income as f64
+    } else {
+        0.0
+    };
+
+    let lending_band = if monthly_income <= 0 {
+        LendingBand::Decline
+    } else {
+        BANDS
+            .iter()
+            .find(|(_, max_debt_to_income, min_stability)| {
+                debt_to_income <= *max_debt_to_income && income_stability >= *min_stability
+            })
+            .map(|(band, _, _)| *band)
+            .unwrap_or(LendingBand::Decline)
+    };
+
+    let max_monthly_repayment = if lending_band == LendingBand::Decline {
+        0
+    } else {
+        let within_limit = (monthly_income as f64 * MAX_DEBT_TO_INCOME) as Amount - monthly_obligations;
+        let left_over = monthly_income - average_monthly_outflow;
+        within_limit.min(left_over).max(0)
+    };
+
+    let spending_ratio = if monthly_income > 0 {
+        average_monthly_outflow as f64 / monthly_income as f64
+    } else {
+        0.0
+    };
+    let factors = vec![
+        AffordabilityFactor {
+            code: "income_source".to_string(),
+            value: monthly_income as f64,
+            impact: match (income_source, monthly_income > 0) {
+                (_, false) => FactorImpact::Negative,
+                (IncomeSource::Verified, true) => FactorImpact::Positive,
+                (IncomeSource::Observed, true) => FactorImpact::Neutral,
+            },
+            description: match income_source {
+                IncomeSource::Verified => "Monthly income from a completed income verification".to_string(),
+                IncomeSource::Observed => "Monthly income averaged from account inflows; no verified income".to_string(),
+            },
+        },
+        AffordabilityFactor {
+            code: "debt_to_income".to_string(),
+            value: round(debt_to_income),
+            impact: impact(debt_to_income <= 0.30, debt_to_income > MAX_DEBT_TO_INCOME),
+            description: format!(
+                "Recurring payments take {:.0}% of monthly income; at most {:.0}% is affordable",
+                debt_to_income * 100.0,
+                MAX_DEBT_TO_INCOME * 100.0
+            ),
+        },
+        AffordabilityFactor {
+            code: "income_stability".to_string(),
+            value: f64::from(income_stability),
+            impact: impact(income_stability >= 70, income_stability < 50),
+            description: format!(
+                "Monthly inflows vary by {:.0}% from their average",
+                income_variation * 100.0
+            ),
+        },
+        AffordabilityFactor {
+            code: "income_gaps".to_string(),
+            value: f64::from(activity.len() as u32 - months_with_income),
+            impact: impact(months_with_income as usize == activity.len(), months_with_income * 2 < activity.len() as u32),
+            description: format!("Income arrived in {} of {} months", months_with_income, activity.len()),
+        },
+        AffordabilityFactor {
+            code: "spending_ratio".to_string(),
+            value: round(spending_ratio),
+            impact: impact(spending_ratio <= 0.70, spending_ratio > 1.0),
+            description: format!("Spending averages {:.0}% of monthly income", spending_ratio * 100.0),
+        },
+    ];
+
+    AffordabilityAssessment {
+        user_id,
+        currency,
+        period_start,
+        period_end,
+        months_in_period: activity.len() as u32,
+        monthly_income,
+        income_source,
+        average_monthly_outflow,
+        monthly_obligations,
+        debt_to_income: round(debt_to_income),
+        income_stability,
+        income_variation: round(income_variation),
+        months_with_income,
+        lending_band,
+        max_monthly_repayment,
+        factors,
+        assessed_at: Utc::now(),
+    }
+}
+
+fn impact(positive: bool, negative: bool) -> FactorImpact {
+    if negative {
+        FactorImpact::Negative
+    } else if positive {
+        FactorImpact::Positive
+    } else {
+        FactorImpact::Neutral
+    }
+}
+
+/// Round a ratio to four decimal places
+fn round(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn months(inflows: &[Amount], outflow: Amount, recurring: Amount) -> Vec<MonthlyActivity> {
+        inflows.iter().map(|&inflow| MonthlyActivity { inflow, outflow, recurring }).collect()
+    }
+
+    fn assess_activity(activity: &[MonthlyActivity], verified: Option<Amount>) -> AffordabilityAssessment {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 6, 30).unwrap();
+        assess(Uuid::nil(), "USD".to_string(), start, end, activity, verified)
+    }
+
+    #[test]
+    fn steady_income_with_low_obligations_is_prime() {
+        let assessment = assess_activity(&months(&[500_000; 6], 300_000, 100_000), None);
+        assert_eq!(assessment.income_source, IncomeSource::Observed);
+        assert_eq!(assessment.income_stability, 100);
+        assert_eq!(assessment.debt_to_income, 0.2);
+        assert_eq!(assessment.lending_band, LendingBand::Prime);
+        // 40% of income less obligations, within what is left after spending
+        assert_eq!(assessment.max_monthly_repayment, 100_000);
+        assert!(assessment.factors.iter().all(|factor| factor.impact != FactorImpact::Negative));
+    }
+
+    #[test]
+    fn irregular_income_lowers_the_band() {
+        let assessment = assess_activity(&months(&[900_000, 0, 600_000, 0, 300_000, 0], 100_000, 50_000), None);
+        assert_eq!(assessment.months_with_income, 3);
+        assert!(assessment.income_stability < 30);
+        assert_eq!(assessment.lending_band, LendingBand::Decline);
+        assert_eq!(assessment.max_monthly_repayment, 0);
+        let gaps = assessment.factors.iter().find(|factor| factor.code == "income_gaps").unwrap();
+        assert_eq!(gaps.value, 3.0);
+
+        // Verified income does not hide the variation in what arrives
+        let inflows = [500_000, 450_000, 500_000, 300_000, 500_000, 500_000];
+        let verified = assess_activity(&months(&inflows, 200_000, 175_000), Some(500_000));
+        assert_eq!(verified.income_source, IncomeSource::Verified);
+        assert_eq!(verified.lending_band, LendingBand::NearPrime);
+    }
+
+    #[test]
+    fn no_income_is_declined() {
+        let assessment = assess_activity(&months(&[0; 6], 50_000, 0), None);
+        assert_eq!(assessment.lending_band, LendingBand::Decline);
+        assert_eq!(assessment.debt_to_income, 0.0);
+        assert_eq!(assessment.factors[0].impact, FactorImpact::Negative);
+    }
+}
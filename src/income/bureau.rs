@@ -0,0 +1,359 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::core::circuit_breaker::{CircuitBreaker, CircuitBreakerSettings, CircuitBreakers};
+use crate::core::config::Config;
+use crate::core::crypto::verify_hmac_sha256;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use crate::shared::types::{Amount, Currency};
+use super::model::{CreditAccount, CreditAccountStatus, CreditReport, CreditSubject};
+
+/// A credit report pull to send to a bureau
+#[derive(Debug, Clone)]
+pub struct CreditReportRequest {
+    pub check_id: Uuid,
+    pub subject: CreditSubject,
+    pub purpose: String,
+    /// Where the bureau posts the result
+    pub callback_url: String,
+}
+
+/// What a bureau's completion callback says about a pull
+#[derive(Debug, Clone)]
+pub enum BureauOutcome {
+    Completed(CreditReport),
+    Failed(String),
+}
+
+/// A bureau's completion callback, read into the bureau's reference for the
+/// pull and its outcome
+#[derive(Debug, Clone)]
+pub struct BureauCallback {
+    pub reference: String,
+    pub outcome: BureauOutcome,
+}
+
+/// Pulls credit reports from a credit bureau. Pulls are asynchronous: the
+/// bureau accepts a request, then posts the report to the callback URL.
+#[async_trait]
+pub trait CreditBureau: Send + Sync {
+    /// Name stored with each check, matching `CREDIT_BUREAU_PROVIDER`
+    fn name(&self) -> &'static str;
+
+    /// Ask for a report, returning the bureau's reference for the pull
+    async fn request_report(&self, request: &CreditReportRequest) -> AppResult<String>;
+
+    /// Whether a callback body carries the bureau's signature
+    fn verify_callback(&self, signature: Option<&str>, body: &[u8]) -> bool;
+
+    /// Read a verified callback body
+    fn parse_callback(&self, body: &[u8]) -> AppResult<BureauCallback>;
+}
+
+/// Used when no bureau is configured: credit checks cannot be made
+pub struct UnavailableCreditBureau;
+
+#[async_trait]
+impl CreditBureau for UnavailableCreditBureau {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    async fn request_report(&self, _request: &CreditReportRequest) -> AppResult<String> {
+        Err(AppError::BadRequest("Credit checks are not available".to_string()))
+    }
+
+    fn verify_callback(&self, _signature: Option<&str>, _body: &[u8]) -> bool {
+        false
+    }
+
+    fn parse_callback(&self, _body: &[u8]) -> AppResult<BureauCallback> {
+        Err(AppError::BadRequest("Credit checks are not available".to_string()))
+    }
+}
+
+/// Name of the credit bureau's circuit breaker
+const CREDIT_BUREAU_BREAKER: &str = "credit_bureau";
+
+/// Talks to an HTTP bureau API taking `{reference, subject, purpose,
+/// callback_url}` JSON with a bearer API key and answering `{reference}`.
+/// Callbacks are signed with a hex HMAC-SHA256 of the body under the shared
+/// webhook secret.
+pub struct HttpCreditBureau {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    webhook_secret: String,
+    breaker: CircuitBreaker,
+}
+
+impl HttpCreditBureau {
+    pub fn new(api_url: String, api_key: String, webhook_secret: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            webhook_secret,
+            breaker: CircuitBreaker::new(CREDIT_BUREAU_BREAKER, CircuitBreakerSettings::default()),
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    async fn send_report_request(&self, request: &CreditReportRequest) -> AppResult<String> {
+        let http_request = self
+            .client
+            .post(format!("{}/reports", self.api_url.trim_end_matches('/')))
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({
+                "reference": request.check_id,
+                "subject": {
+                    "first_name": request.subject.first_name,
+                    "last_name": request.subject.last_name,
+                    "email": request.subject.email,
+                },
+                "purpose": request.purpose,
+                "callback_url": request.callback_url,
+            }));
+        let response = self
+            .client
+            .send(http_request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Credit bureau request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!("Credit bureau returned {}", response.status())));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Invalid credit bureau response: {}", e)))?;
+        body["reference"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| AppError::ExternalService("Credit bureau response has no reference".to_string()))
+    }
+}
+
+#[async_trait]
+impl CreditBureau for HttpCreditBureau {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn request_report(&self, request: &CreditReportRequest) -> AppResult<String> {
+        self.breaker.call(self.send_report_request(request)).await
+    }
+
+    fn verify_callback(&self, signature: Option<&str>, body: &[u8]) -> bool {
+        signature
+            .and_then(decode_hex)
+            .is_some_and(|signature| verify_hmac_sha256(self.webhook_secret.as_bytes(), body, &signature))
+    }
+
+    fn parse_callback(&self, body: &[u8]) -> AppResult<BureauCallback> {
+        let callback: HttpCallback = serde_json::from_slice(body)
+            .map_err(|e| AppError::BadRequest(format!("Invalid credit bureau callback: {}", e)))?;
+
+        let outcome = match (callback.status.as_str(), callback.report) {
+            ("completed", Some(report)) => BureauOutcome::Completed(normalize_report(report)),
+            ("completed", None) => return Err(AppError::BadRequest("Completed callback has no report".to_string())),
+            ("failed", _) => BureauOutcome::Failed(callback.reason.unwrap_or_else(|| "Bureau gave no reason".to_string())),
+            (other, _) => return Err(AppError::BadRequest(format!("Unknown callback status '{}'", other))),
+        };
+        Ok(BureauCallback {
+            reference: callback.reference,
+            outcome,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpCallback {
+    reference: String,
+    status: String,
+    reason: Option<String>,
+    report: Option<BureauReport>,
+}
+
+/// A report as the HTTP bureau sends it
+#[derive(Debug, Deserialize)]
+pub struct BureauReport {
+    pub score: Option<i32>,
+    pub score_model: Option<String>,
+    pub currency: Currency,
+    #[serde(default)]
+    pub tradelines: Vec<BureauTradeline>,
+    #[serde(default)]
+    pub inquiries_12m: u32,
+    #[serde(default)]
+    pub public_records: u32,
+    pub generated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BureauTradeline {
+    #[serde(rename = "type")]
+    pub account_type: String,
+    pub status: String,
+    pub balance: Amount,
+    pub limit: Option<Amount>,
+    #[serde(default)]
+    pub monthly_payment: Amount,
+    pub opened_on: Option<NaiveDate>,
+}
+
+/// Build the normalized report, with totals over the accounts
+pub fn normalize_report(report: BureauReport) -> CreditReport {
+    let accounts: Vec<CreditAccount> = report
+        .tradelines
+        .into_iter()
+        .map(|tradeline| CreditAccount {
+            status: account_status(&tradeline.status),
+            account_type: tradeline.account_type.to_lowercase(),
+            balance: tradeline.balance,
+            credit_limit: tradeline.limit,
+            monthly_payment: tradeline.monthly_payment,
+            opened_on: tradeline.opened_on,
+        })
+        .collect();
+
+    let open: Vec<&CreditAccount> = accounts
+        .iter()
+        .filter(|account| account.status != CreditAccountStatus::Closed)
+        .collect();
+    let (limited_balance, total_limit) = open
+        .iter()
+        .filter_map(|account| account.credit_limit.filter(|limit| *limit > 0).map(|limit| (account.balance, limit)))
+        .fold((0, 0), |(balance, total), (account_balance, limit)| (balance + account_balance, total + limit));
+
+    CreditReport {
+        score: report.score,
+        score_model: report.score_model,
+        currency: report.currency.to_uppercase(),
+        total_balance: open.iter().map(|account| account.balance).sum(),
+        total_monthly_payments: open.iter().map(|account| account.monthly_payment).sum(),
+        credit_utilization: (total_limit > 0)
+            .then(|| (limited_balance as f64 / total_limit as f64 * 10_000.0).round() / 10_000.0),
+        delinquent_accounts: accounts
+            .iter()
+            .filter(|account| matches!(account.status, CreditAccountStatus::Delinquent | CreditAccountStatus::Default))
+            .count() as u32,
+        recent_inquiries: report.inquiries_12m,
+        public_records: report.public_records,
+        generated_at: report.generated_at.unwrap_or_else(Utc::now),
+        accounts,
+    }
+}
+
+fn account_status(status: &str) -> CreditAccountStatus {
+    match status.to_lowercase().as_str() {
+        "closed" | "paid" => CreditAccountStatus::Closed,
+        "default" | "charged_off" | "collection" => CreditAccountStatus::Default,
+        "delinquent" | "late" | "past_due" => CreditAccountStatus::Delinquent,
+        _ => CreditAccountStatus::Current,
+    }
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| value.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
+/// Build the credit bureau selected by `CREDIT_BUREAU_PROVIDER`
+pub fn from_config(
+    config: &Config,
+    breakers: &CircuitBreakers,
+    clients: &HttpClients,
+) -> AppResult<Arc<dyn CreditBureau>> {
+    match config.credit_bureau_provider.as_str() {
+        "none" => Ok(Arc::new(UnavailableCreditBureau)),
+        "http" => {
+            let missing = |name: &str| AppError::Internal(format!("{} is required for the http credit bureau", name));
+            Ok(Arc::new(HttpCreditBureau::new(
+                config.credit_bureau_api_url.clone().ok_or_else(|| missing("CREDIT_BUREAU_API_URL"))?,
+                config.credit_bureau_api_key.clone().ok_or_else(|| missing("CREDIT_BUREAU_API_KEY"))?,
+                config
+                    .credit_bureau_webhook_secret
+                    .clone()
+                    .ok_or_else(|| missing("CREDIT_BUREAU_WEBHOOK_SECRET"))?,
+            )
+            .with_circuit_breaker(breakers.get(CREDIT_BUREAU_BREAKER))
+            .with_http_client(clients.get(CREDIT_BUREAU_BREAKER))))
+        }
+        other => Err(AppError::Internal(format!("Unknown credit bureau provider '{}'", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::crypto::{hex, hmac_sha256};
+
+    fn bureau() -> HttpCreditBureau {
+        HttpCreditBureau::new("https://bureau.example.com".to_string(), "key".to_string(), "secret".to_string())
+    }
+
+    #[test]
+    fn callbacks_must_carry_the_bureau_signature() {
+        let body = br#"{"reference":"r1","status":"failed"}"#;
+        let signature = hex(&hmac_sha256(b"secret", body));
+        assert!(bureau().verify_callback(Some(&signature), body));
+        assert!(!bureau().verify_callback(Some(&signature), br#"{"reference":"r2","status":"failed"}"#));
+        assert!(!bureau().verify_callback(Some("zz"), body));
+        assert!(!bureau().verify_callback(None, body));
+    }
+
+    #[test]
+    fn reports_are_normalized_with_totals() {
+        let body = br#"{
+            "reference": "r1",
+            "status": "completed",
+            "report": {
+                "score": 712,
+                "score_model": "FICO8",
+                "currency": "usd",
+                "tradelines": [
+                    {"type": "CREDIT_CARD", "status": "current", "balance": 150000, "limit": 500000, "monthly_payment": 5000},
+                    {"type": "loan", "status": "late", "balance": 800000, "monthly_payment": 25000},
+                    {"type": "credit_card", "status": "closed", "balance": 0, "limit": 300000}
+                ],
+                "inquiries_12m": 2
+            }
+        }"#;
+
+        let callback = bureau().parse_callback(body).unwrap();
+        assert_eq!(callback.reference, "r1");
+        let BureauOutcome::Completed(report) = callback.outcome else {
+            panic!("expected a report");
+        };
+        assert_eq!(report.currency, "USD");
+        assert_eq!(report.accounts[0].account_type, "credit_card");
+        assert_eq!(report.total_balance, 950_000);
+        assert_eq!(report.total_monthly_payments, 30_000);
+        assert_eq!(report.credit_utilization, Some(0.3));
+        assert_eq!(report.delinquent_accounts, 1);
+        assert_eq!(report.recent_inquiries, 2);
+
+        let failed = bureau().parse_callback(br#"{"reference":"r2","status":"failed","reason":"No file"}"#).unwrap();
+        assert!(matches!(failed.outcome, BureauOutcome::Failed(reason) if reason == "No file"));
+        assert!(bureau().parse_callback(br#"{"reference":"r3","status":"completed"}"#).is_err());
+    }
+}
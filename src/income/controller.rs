@@ -1,42 +1,575 @@
-use axum::{extract::State, response::Json};
-use serde_json::{json, Value};
-use crate::core::{error::AppResult, AppState};
+use std::collections::HashMap;
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Multipart, Path, Query, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::identity::repository::IdentityRepository;
+use crate::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use crate::reviews::{repository::ReviewRepository, service::ReviewQueue};
+use crate::shared::constants::MAX_INCOME_DOCUMENTS_PER_UPLOAD;
+use uuid::Uuid;
+use super::affordability::AffordabilityService;
+use super::bureau;
+use super::credit_check::CreditCheckService;
+use super::employer::EmployerConfirmationService;
+use super::model::{
+    AffordabilityAssessment, AffordabilityQuery, CreditCheckRequest, CreditCheckResponse, CreditCheckSettings,
+    DocumentUpload, EmployerConfirmationDetails, EmployerConfirmationResponse, EmployerConfirmationSubmission,
+    IncomeDocumentResponse, IncomeDocumentType, IncomeReportQuery, IncomeVerificationRequest,
+    IncomeVerificationResponse, ReportFormat, ReportVerificationResponse, RequestEmployerConfirmationRequest,
+};
+use super::repository::IncomeRepository;
+use super::report::IncomeReportService;
+use super::service::IncomeService;
 
-/// Initiate income verification process
+fn income_service(state: &AppState) -> IncomeService {
+    IncomeService::new(
+        IncomeRepository::new(state.postgres.clone()),
+        state.storage.clone(),
+        ReviewQueue::new(ReviewRepository::new(state.postgres.clone()), state.audit_logger.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+fn income_report_service(state: &AppState) -> IncomeReportService {
+    IncomeReportService::new(
+        IncomeRepository::new(state.postgres.clone()),
+        state.config.income_report_signing_key.clone(),
+        state.config.income_report_validity_days,
+        state.config.public_base_url.clone(),
+        state.audit_logger.clone(),
+    )
+}
+
+fn affordability_service(state: &AppState) -> AffordabilityService {
+    AffordabilityService::new(
+        IncomeRepository::new(state.postgres.clone()),
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Header carrying a bureau's signature over a callback body
+const BUREAU_SIGNATURE_HEADER: &str = "x-bureau-signature";
+
+fn credit_check_service(state: &AppState) -> AppResult<CreditCheckService> {
+    Ok(CreditCheckService::new(
+        IncomeRepository::new(state.postgres.clone()),
+        bureau::from_config(&state.config, &state.circuit_breakers, &state.http_clients)?,
+        state.audit_logger.clone(),
+        state.event_bus.clone(),
+        CreditCheckSettings::from_config(&state.config),
+    ))
+}
+
+fn employer_confirmation_service(state: &AppState) -> EmployerConfirmationService {
+    EmployerConfirmationService::new(
+        IncomeRepository::new(state.postgres.clone()),
+        state.mailer.clone(),
+        state.audit_logger.clone(),
+        KycPolicyService::new(
+            KycRepository::new(state.postgres.clone()),
+            KycLimits::from_config(&state.config),
+            state.audit_logger.clone(),
+        ),
+        state.config.employer_confirmation_validity_hours,
+        state.config.public_base_url.clone(),
+    )
+}
+
+/// Initiate an income verification. Accepts a JSON body, or a multipart
+/// form carrying the same fields as text parts plus up to five `document`
+/// file parts (payslips, statements) that are stored and parsed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/income/verify",
+    tag = "income",
+    request_body(
+        content = IncomeVerificationRequest,
+        description = "JSON, or multipart/form-data with the same fields and `document` file parts"
+    ),
+    responses(
+        (status = 201, description = "Income verification initiated", body = IncomeVerificationResponse),
+        (status = 400, description = "Invalid request or unsupported document"),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn initiate_income_verification(
-    State(_state): State<AppState>,
-    // TODO: Add request body for income verification data
-) -> AppResult<Json<Value>> {
-    // TODO: Implement income verification initiation logic
-    
-    Ok(Json(json!({
-        "message": "Initiate income verification endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
-}
-
-/// Get income verification status
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    request: Request,
+) -> AppResult<(StatusCode, Json<ApiResponse<IncomeVerificationResponse>>)> {
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("multipart/form-data"));
+
+    let (verification_request, uploads) = if is_multipart {
+        let multipart = Multipart::from_request(request, &state)
+            .await
+            .map_err(|rejection| AppError::BadRequest(rejection.body_text()))?;
+        let (fields, uploads) = read_multipart(multipart).await?;
+        (verification_request_from_fields(&fields)?, uploads)
+    } else {
+        let ApiJson(verification_request) = ApiJson::from_request(request, &state).await?;
+        (verification_request, Vec::new())
+    };
+
+    if let Err(validation_errors) = verification_request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let verification = income_service(&state)
+        .initiate_verification(verification_request, uploads, claims.developer_id)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Income verification initiated successfully", verification)),
+    ))
+}
+
+/// Upload further documents for an income verification (multipart
+/// `document` file parts and an optional `document_type` field)
+#[utoipa::path(
+    post,
+    path = "/api/v1/income/verify/{id}/documents",
+    tag = "income",
+    params(("id" = Uuid, Path, description = "Income verification ID")),
+    request_body(content = Vec<u8>, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Documents stored and parsed", body = [IncomeDocumentResponse]),
+        (status = 400, description = "Unsupported document or verification already decided"),
+        (status = 404, description = "Income verification not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload_income_documents(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(verification_id): Path<Uuid>,
+    multipart: Multipart,
+) -> AppResult<(StatusCode, Json<ApiResponse<Vec<IncomeDocumentResponse>>>)> {
+    let (fields, uploads) = read_multipart(multipart).await?;
+    let document_type = document_type_from_fields(&fields)?;
+
+    let documents = income_service(&state)
+        .add_documents(verification_id, document_type, uploads, claims.developer_id)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Income documents uploaded successfully", documents)),
+    ))
+}
+
+/// Get income verification status, with uploaded documents and the fields
+/// detected in them
+#[utoipa::path(
+    get,
+    path = "/api/v1/income/verify/status/{id}",
+    tag = "income",
+    params(("id" = Uuid, Path, description = "Income verification ID")),
+    responses(
+        (status = 200, description = "Income verification", body = IncomeVerificationResponse),
+        (status = 404, description = "Income verification not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_income_verification_status(
-    State(_state): State<AppState>,
-    // TODO: Add path parameter for verification ID
-) -> AppResult<Json<Value>> {
-    // TODO: Implement income verification status retrieval
-    
-    Ok(Json(json!({
-        "message": "Get income verification status endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
-}
-
-/// Get income report
+    State(state): State<AppState>,
+    JwtToken(_claims): JwtToken,
+    Path(verification_id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<IncomeVerificationResponse>>> {
+    let verification = income_service(&state).get_verification_status(verification_id).await?;
+    Ok(Json(ApiResponse::success("Income verification retrieved successfully", verification)))
+}
+
+/// Split a multipart upload into its text fields and `document` files
+async fn read_multipart(mut multipart: Multipart) -> AppResult<(HashMap<String, String>, Vec<DocumentUpload>)> {
+    let invalid = |error: axum::extract::multipart::MultipartError| {
+        AppError::BadRequest(format!("Invalid multipart upload: {}", error.body_text()))
+    };
+    let mut fields = HashMap::new();
+    let mut uploads = Vec::new();
+
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "document" {
+            if uploads.len() == MAX_INCOME_DOCUMENTS_PER_UPLOAD {
+                return Err(AppError::Validation(format!(
+                    "At most {} documents can be uploaded at once",
+                    MAX_INCOME_DOCUMENTS_PER_UPLOAD
+                )));
+            }
+            let file_name = field.file_name().unwrap_or("document").to_string();
+            let content_type = field.content_type().unwrap_or("application/octet-stream").to_string();
+            let content = field.bytes().await.map_err(invalid)?;
+            uploads.push(DocumentUpload {
+                file_name,
+                content_type,
+                content: content.to_vec(),
+            });
+        } else {
+            let value = field.text().await.map_err(invalid)?;
+            fields.insert(name, value);
+        }
+    }
+
+    Ok((fields, uploads))
+}
+
+/// Build a verification request from multipart text fields
+fn verification_request_from_fields(fields: &HashMap<String, String>) -> AppResult<IncomeVerificationRequest> {
+    let required = |name: &str| {
+        fields
+            .get(name)
+            .map(|value| value.trim().to_string())
+            .ok_or_else(|| AppError::Validation(format!("Missing field '{}'", name)))
+    };
+    let optional = |name: &str| {
+        fields
+            .get(name)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    Ok(IncomeVerificationRequest {
+        user_id: required("user_id")?
+            .parse()
+            .map_err(|_| AppError::Validation("Field 'user_id' must be a UUID".to_string()))?,
+        verification_type: required("verification_type")?,
+        employer_name: optional("employer_name"),
+        job_title: optional("job_title"),
+        expected_annual_income: optional("expected_annual_income")
+            .map(|value| value.parse())
+            .transpose()
+            .map_err(|_| AppError::Validation("Field 'expected_annual_income' must be an integer".to_string()))?,
+        currency: required("currency")?,
+        document_type: document_type_from_fields(fields)?,
+        additional_data: optional("additional_data")
+            .map(|value| serde_json::from_str(&value))
+            .transpose()
+            .map_err(|_| AppError::Validation("Field 'additional_data' must be JSON".to_string()))?,
+    })
+}
+
+fn document_type_from_fields(fields: &HashMap<String, String>) -> AppResult<IncomeDocumentType> {
+    match fields.get("document_type").map(|value| value.trim()) {
+        None | Some("") => Ok(IncomeDocumentType::default()),
+        Some(value) => serde_json::from_value(serde_json::Value::String(value.to_string()))
+            .map_err(|_| AppError::Validation(format!("Unknown document type '{}'", value))),
+    }
+}
+
+/// Generate a signed income report as JSON or PDF
+#[utoipa::path(
+    get,
+    path = "/api/v1/income/report",
+    tag = "income",
+    params(IncomeReportQuery),
+    responses(
+        (status = 200, description = "Signed income report", content(
+            ("application/json" = IncomeReportResponse),
+            ("application/pdf" = Vec<u8>)
+        )),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_income_report(
-    State(_state): State<AppState>,
-    // TODO: Add query parameters for date range and filters
-) -> AppResult<Json<Value>> {
-    // TODO: Implement income report generation
-    
-    Ok(Json(json!({
-        "message": "Get income report endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
-}
\ No newline at end of file
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<IncomeReportQuery>,
+) -> AppResult<Response> {
+    if let Err(validation_errors) = query.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let service = income_report_service(&state);
+    let report = service
+        .generate_report(query.user_id, query.months, claims.developer_id)
+        .await?;
+
+    match query.format {
+        ReportFormat::Json => Ok(Json(ApiResponse::success("Income report generated successfully", report))
+            .into_response()),
+        ReportFormat::Pdf => {
+            let disposition = format!(
+                "attachment; filename=\"income-report-{}.pdf\"",
+                report.verification_code
+            );
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "application/pdf".to_string()),
+                    (header::CONTENT_DISPOSITION, disposition),
+                    (header::HeaderName::from_static("x-report-verification-code"), report.verification_code.clone()),
+                ],
+                service.render_pdf(&report),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// Assess a user's affordability for lending: debt-to-income, income
+/// stability and a recommended lending band, with the factors behind them
+#[utoipa::path(
+    get,
+    path = "/api/v1/income/affordability/{user_id}",
+    tag = "income",
+    params(("user_id" = Uuid, Path, description = "User ID"), AffordabilityQuery),
+    responses(
+        (status = 200, description = "Affordability assessment", body = AffordabilityAssessment),
+        (status = 400, description = "Invalid query"),
+        (status = 404, description = "User not found or no activity to assess")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_affordability(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<AffordabilityQuery>,
+) -> AppResult<Json<ApiResponse<AffordabilityAssessment>>> {
+    if let Err(validation_errors) = query.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let assessment = affordability_service(&state)
+        .assess(user_id, claims.tenant_id, query, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Affordability assessed successfully", assessment)))
+}
+
+/// Validate an income report by its verification code (public)
+#[utoipa::path(
+    get,
+    path = "/api/v1/income/report/verify/{code}",
+    tag = "income",
+    params(("code" = String, Path, description = "Report verification code")),
+    responses(
+        (status = 200, description = "Report validity and signed content", body = ReportVerificationResponse),
+        (status = 404, description = "Report not found")
+    )
+)]
+pub async fn verify_income_report(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> AppResult<Json<ApiResponse<ReportVerificationResponse>>> {
+    let verification = income_report_service(&state).verify_report(&code).await?;
+    Ok(Json(ApiResponse::success("Income report verification completed", verification)))
+}
+
+/// Email an employer contact a link to confirm employment and income
+#[utoipa::path(
+    post,
+    path = "/api/v1/income/verify/{id}/employer-confirmation",
+    tag = "income",
+    params(("id" = Uuid, Path, description = "Income verification ID")),
+    request_body = RequestEmployerConfirmationRequest,
+    responses(
+        (status = 200, description = "Confirmation request emailed to the employer", body = EmployerConfirmationResponse),
+        (status = 400, description = "Income verification has already been decided"),
+        (status = 404, description = "Income verification not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn request_employer_confirmation(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(verification_id): Path<Uuid>,
+    ApiJson(request): ApiJson<RequestEmployerConfirmationRequest>,
+) -> AppResult<Json<ApiResponse<EmployerConfirmationResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let confirmation = employer_confirmation_service(&state)
+        .request_confirmation(verification_id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Employer confirmation requested successfully", confirmation)))
+}
+
+/// List employer confirmation requests for an income verification
+#[utoipa::path(
+    get,
+    path = "/api/v1/income/verify/{id}/employer-confirmation",
+    tag = "income",
+    params(("id" = Uuid, Path, description = "Income verification ID")),
+    responses(
+        (status = 200, description = "Employer confirmation requests", body = [EmployerConfirmationResponse]),
+        (status = 404, description = "Income verification not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_employer_confirmations(
+    State(state): State<AppState>,
+    JwtToken(_claims): JwtToken,
+    Path(verification_id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<Vec<EmployerConfirmationResponse>>>> {
+    let confirmations = employer_confirmation_service(&state)
+        .list_confirmations(verification_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Employer confirmations retrieved successfully", confirmations)))
+}
+
+/// Show the employer what they are asked to confirm (public, token-authenticated)
+#[utoipa::path(
+    get,
+    path = "/api/v1/income/employer-confirmations/{token}",
+    tag = "income",
+    params(("token" = String, Path, description = "Confirmation token from the email link")),
+    responses(
+        (status = 200, description = "What the employer is asked to confirm", body = EmployerConfirmationDetails),
+        (status = 400, description = "The confirmation link has expired"),
+        (status = 404, description = "Confirmation request not found")
+    )
+)]
+pub async fn get_employer_confirmation(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> AppResult<Json<ApiResponse<EmployerConfirmationDetails>>> {
+    let details = employer_confirmation_service(&state).get_details(&token).await?;
+    Ok(Json(ApiResponse::success("Employer confirmation retrieved successfully", details)))
+}
+
+/// Record the employer's response (public, token-authenticated)
+#[utoipa::path(
+    post,
+    path = "/api/v1/income/employer-confirmations/{token}",
+    tag = "income",
+    params(("token" = String, Path, description = "Confirmation token from the email link")),
+    request_body = EmployerConfirmationSubmission,
+    responses(
+        (status = 200, description = "Employer response recorded", body = EmployerConfirmationResponse),
+        (status = 400, description = "The confirmation link has expired or is no longer open"),
+        (status = 404, description = "Confirmation request not found")
+    )
+)]
+pub async fn submit_employer_confirmation(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    ApiJson(submission): ApiJson<EmployerConfirmationSubmission>,
+) -> AppResult<Json<ApiResponse<EmployerConfirmationResponse>>> {
+    if let Err(validation_errors) = submission.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let confirmation = employer_confirmation_service(&state).submit(&token, submission).await?;
+    Ok(Json(ApiResponse::success("Employer response recorded successfully", confirmation)))
+}
+
+/// Pull a user's credit report with their consent. The bureau answers
+/// through the completion callback; poll the check or wait for the
+/// `credit_check.completed` webhook.
+#[utoipa::path(
+    post,
+    path = "/api/v1/income/credit-check",
+    tag = "income",
+    request_body = CreditCheckRequest,
+    responses(
+        (status = 202, description = "Credit report requested", body = CreditCheckResponse),
+        (status = 400, description = "Missing or stale consent, or credit checks are not available"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "A credit check is already in progress for this user"),
+        (status = 502, description = "Credit bureau unavailable")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn initiate_credit_check(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<CreditCheckRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<CreditCheckResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let check = credit_check_service(&state)?
+        .initiate(request, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiResponse::success("Credit check requested successfully", check)),
+    ))
+}
+
+/// Get a credit check and, until it is purged, its report
+#[utoipa::path(
+    get,
+    path = "/api/v1/income/credit-check/{id}",
+    tag = "income",
+    params(("id" = Uuid, Path, description = "Credit check ID")),
+    responses(
+        (status = 200, description = "Credit check", body = CreditCheckResponse),
+        (status = 404, description = "Credit check not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_credit_check(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<CreditCheckResponse>>> {
+    let check = credit_check_service(&state)?.get_check(id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Credit check retrieved successfully", check)))
+}
+
+/// Purge a credit report before its retention period ends, keeping the
+/// record of the pull
+#[utoipa::path(
+    delete,
+    path = "/api/v1/income/credit-check/{id}",
+    tag = "income",
+    params(("id" = Uuid, Path, description = "Credit check ID")),
+    responses(
+        (status = 200, description = "Credit report purged", body = CreditCheckResponse),
+        (status = 404, description = "Credit check not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn purge_credit_report(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<CreditCheckResponse>>> {
+    let check = credit_check_service(&state)?
+        .purge_report(id, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Credit report purged successfully", check)))
+}
+
+/// Receive a credit bureau's completion callback (public, signature-authenticated)
+#[utoipa::path(
+    post,
+    path = "/api/v1/income/credit-check/callback",
+    tag = "income",
+    params(("X-Bureau-Signature" = String, Header, description = "Hex HMAC-SHA256 of the body")),
+    responses(
+        (status = 200, description = "Callback recorded"),
+        (status = 401, description = "Invalid callback signature"),
+        (status = 404, description = "Credit check not found")
+    )
+)]
+pub async fn credit_check_callback(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<Json<ApiResponse<()>>> {
+    let signature = headers.get(BUREAU_SIGNATURE_HEADER).and_then(|value| value.to_str().ok());
+    credit_check_service(&state)?.handle_callback(signature, &body).await?;
+    Ok(Json(ApiResponse::success("Credit check callback recorded", ())))
+}
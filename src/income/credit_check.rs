@@ -0,0 +1,205 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::events::{DomainEvent, DomainEventType, EventBus};
+use crate::shared::types::TenantId;
+use super::bureau::{BureauOutcome, CreditBureau, CreditReportRequest};
+use super::model::{CreditCheck, CreditCheckRequest, CreditCheckResponse, CreditCheckSettings, CreditCheckStatus};
+use super::repository::IncomeRepository;
+
+/// Allowance for clock differences when consent is dated just now
+const CONSENT_CLOCK_SKEW_MINUTES: i64 = 5;
+
+/// Pulls credit reports from the configured bureau with the user's consent.
+///
+/// A pull stays pending until the bureau posts its result to the callback,
+/// which then notifies the tenant's webhooks with `credit_check.completed`.
+/// Reports are purged once their retention period passes; the record of the
+/// pull and its consent stays.
+pub struct CreditCheckService {
+    repository: IncomeRepository,
+    bureau: Arc<dyn CreditBureau>,
+    audit_logger: AuditLogger,
+    event_bus: EventBus,
+    settings: CreditCheckSettings,
+}
+
+impl CreditCheckService {
+    pub fn new(
+        repository: IncomeRepository,
+        bureau: Arc<dyn CreditBureau>,
+        audit_logger: AuditLogger,
+        event_bus: EventBus,
+        settings: CreditCheckSettings,
+    ) -> Self {
+        Self {
+            repository,
+            bureau,
+            audit_logger,
+            event_bus,
+            settings,
+        }
+    }
+
+    /// Check the consent and ask the bureau for the user's report
+    pub async fn initiate(
+        &self,
+        request: CreditCheckRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<CreditCheckResponse> {
+        let now = Utc::now();
+        let consent = &request.consent;
+        if !consent.granted {
+            return Err(AppError::Validation("The user has not consented to a credit check".to_string()));
+        }
+        if consent.granted_at > now + Duration::minutes(CONSENT_CLOCK_SKEW_MINUTES) {
+            return Err(AppError::Validation("Consent cannot be dated in the future".to_string()));
+        }
+        if consent.granted_at < now - Duration::days(self.settings.consent_max_age_days) {
+            return Err(AppError::Validation(format!(
+                "Consent is more than {} days old; ask the user again",
+                self.settings.consent_max_age_days
+            )));
+        }
+
+        let subject = self
+            .repository
+            .find_credit_subject(request.user_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let check = CreditCheck {
+            id: Uuid::new_v4(),
+            tenant_id,
+            user_id: request.user_id,
+            provider: self.bureau.name().to_string(),
+            provider_reference: None,
+            status: CreditCheckStatus::Pending,
+            purpose: request.purpose,
+            consent_reference: request.consent.reference,
+            consent_granted_at: request.consent.granted_at,
+            report: None,
+            failure_reason: None,
+            requested_by: actor_id,
+            completed_at: None,
+            retain_until: None,
+            purged_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let check = self.repository.create_credit_check(&check).await?.ok_or_else(|| {
+            AppError::Conflict("A credit check is already in progress for this user".to_string())
+        })?;
+
+        let bureau_request = CreditReportRequest {
+            check_id: check.id,
+            subject,
+            purpose: check.purpose.clone(),
+            callback_url: self.settings.callback_url.clone(),
+        };
+        let check = match self.bureau.request_report(&bureau_request).await {
+            Ok(reference) => self.repository.set_credit_check_reference(check.id, &reference).await?,
+            Err(e) => {
+                self.repository.fail_credit_check(check.id, &e.to_string()).await?;
+                return Err(e);
+            }
+        };
+
+        let event = AuditEvent::new(AuditEventType::CreditCheckRequested)
+            .user_id(actor_id)
+            .resource(format!("credit_check:{}", check.id))
+            .action("request".to_string())
+            .metadata("subject_user_id".to_string(), json!(check.user_id))
+            .metadata("provider".to_string(), json!(check.provider))
+            .metadata("purpose".to_string(), json!(check.purpose))
+            .metadata("consent_reference".to_string(), json!(check.consent_reference))
+            .metadata("consent_granted_at".to_string(), json!(check.consent_granted_at))
+            .compliance_tag("CREDIT_CHECK".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(check.into())
+    }
+
+    /// Record a bureau's completion callback. Repeated callbacks for a pull
+    /// that is already decided change nothing.
+    pub async fn handle_callback(&self, signature: Option<&str>, body: &[u8]) -> AppResult<()> {
+        if !self.bureau.verify_callback(signature, body) {
+            return Err(AppError::Authentication("Invalid callback signature".to_string()));
+        }
+        let callback = self.bureau.parse_callback(body)?;
+        let check = self
+            .repository
+            .find_credit_check_by_reference(self.bureau.name(), &callback.reference)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Credit check not found".to_string()))?;
+
+        let decided = match &callback.outcome {
+            BureauOutcome::Completed(report) => {
+                let retain_until = Utc::now() + Duration::days(self.settings.retention_days);
+                self.repository.complete_credit_check(check.id, report, retain_until).await?
+            }
+            BureauOutcome::Failed(reason) => self.repository.fail_credit_check(check.id, reason).await?,
+        };
+        let Some(check) = decided else {
+            return Ok(());
+        };
+
+        let score = check.report.as_ref().and_then(|report| report.score);
+        let event = AuditEvent::new(AuditEventType::CreditCheckCompleted)
+            .resource(format!("credit_check:{}", check.id))
+            .action("complete".to_string())
+            .metadata("status".to_string(), json!(check.status))
+            .metadata("failure_reason".to_string(), json!(check.failure_reason))
+            .metadata("retain_until".to_string(), json!(check.retain_until))
+            .compliance_tag("CREDIT_CHECK".to_string());
+        self.audit_logger.log(event).await;
+
+        // The report itself stays out of webhooks; subscribers fetch it
+        self.event_bus.publish(DomainEvent::new(
+            DomainEventType::CreditCheckCompleted,
+            Some(check.tenant_id),
+            vec![],
+            json!({
+                "credit_check_id": check.id,
+                "user_id": check.user_id,
+                "status": check.status,
+                "score": score,
+                "completed_at": check.completed_at,
+            }),
+        ));
+
+        Ok(())
+    }
+
+    pub async fn get_check(&self, id: Uuid, tenant_id: TenantId) -> AppResult<CreditCheckResponse> {
+        self.repository
+            .find_credit_check(id, tenant_id)
+            .await?
+            .map(CreditCheckResponse::from)
+            .ok_or_else(|| AppError::NotFound("Credit check not found".to_string()))
+    }
+
+    /// Remove a report before its retention period ends
+    pub async fn purge_report(&self, id: Uuid, tenant_id: TenantId, actor_id: Uuid) -> AppResult<CreditCheckResponse> {
+        let check = self
+            .repository
+            .purge_credit_report(id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Credit check not found".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::CreditReportPurged)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("credit_check:{}", check.id))
+            .action("purge".to_string())
+            .metadata("retain_until".to_string(), json!(check.retain_until))
+            .compliance_tag("CREDIT_CHECK".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(check.into())
+    }
+}
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::crypto::{generate_token, hash_token};
+use crate::core::error::{AppError, AppResult};
+use crate::core::mailer::{EmailMessage, Mailer};
+use crate::kyc::service::KycPolicyService;
+use crate::shared::traits::Repository;
+use super::model::{
+    EmployerConfirmation, EmployerConfirmationDetails, EmployerConfirmationResponse,
+    EmployerConfirmationStatus, EmployerConfirmationSubmission, IncomeVerification,
+    IncomeVerificationStatus, RequestEmployerConfirmationRequest,
+};
+use super::repository::IncomeRepository;
+
+/// Confirms employment and income directly with an employer's HR contact.
+///
+/// The contact receives a single-use link carrying a random token; only the
+/// token's hash is stored. Their answer completes or fails the verification.
+pub struct EmployerConfirmationService {
+    repository: IncomeRepository,
+    mailer: Arc<dyn Mailer>,
+    audit_logger: AuditLogger,
+    kyc_policy: KycPolicyService,
+    validity_hours: i64,
+    public_base_url: String,
+}
+
+impl EmployerConfirmationService {
+    pub fn new(
+        repository: IncomeRepository,
+        mailer: Arc<dyn Mailer>,
+        audit_logger: AuditLogger,
+        kyc_policy: KycPolicyService,
+        validity_hours: i64,
+        public_base_url: String,
+    ) -> Self {
+        Self {
+            repository,
+            mailer,
+            audit_logger,
+            kyc_policy,
+            validity_hours,
+            public_base_url,
+        }
+    }
+
+    /// Email a confirmation link to the employer contact. Any earlier link for
+    /// the same verification stops working.
+    pub async fn request_confirmation(
+        &self,
+        verification_id: Uuid,
+        request: RequestEmployerConfirmationRequest,
+        actor_id: Uuid,
+    ) -> AppResult<EmployerConfirmationResponse> {
+        let verification = self.find_verification(verification_id).await?;
+        if matches!(
+            verification.status,
+            IncomeVerificationStatus::Completed | IncomeVerificationStatus::Failed
+        ) {
+            return Err(AppError::BadRequest(
+                "Income verification has already been decided".to_string(),
+            ));
+        }
+
+        let token = generate_token();
+        let now = Utc::now();
+        let confirmation = EmployerConfirmation {
+            id: Uuid::new_v4(),
+            verification_id,
+            contact_name: request.contact_name,
+            contact_email: request.contact_email,
+            token_hash: hash_token(&token),
+            status: EmployerConfirmationStatus::Pending,
+            employment_confirmed: None,
+            confirmed_job_title: None,
+            confirmed_annual_income: None,
+            employer_comment: None,
+            requested_by: actor_id,
+            expires_at: now + Duration::hours(self.validity_hours),
+            responded_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let confirmation = self.repository.create_employer_confirmation(&confirmation).await?;
+
+        let employee_name = self
+            .repository
+            .find_user_name(verification.user_id)
+            .await?
+            .unwrap_or_else(|| "an employee".to_string());
+        self.mailer
+            .send(EmailMessage {
+                to: confirmation.contact_email.clone(),
+                subject: format!("Employment confirmation request for {}", employee_name),
+                body: format!(
+                    "Hello {},\n\n\
+                     {} has asked us to confirm their employment{}.\n\n\
+                     Please review and respond using the link below:\n{}\n\n\
+                     This link expires on {}.\n",
+                    confirmation.contact_name,
+                    employee_name,
+                    verification
+                        .employer_name
+                        .as_deref()
+                        .map(|employer| format!(" at {}", employer))
+                        .unwrap_or_default(),
+                    self.confirmation_url(&token),
+                    confirmation.expires_at.format("%Y-%m-%d %H:%M UTC"),
+                ),
+            })
+            .await?;
+
+        let event = AuditEvent::new(AuditEventType::EmployerConfirmationRequested)
+            .user_id(actor_id)
+            .resource(format!("income_verification:{}", verification_id))
+            .action("request_employer_confirmation".to_string())
+            .metadata("confirmation_id".to_string(), serde_json::json!(confirmation.id))
+            .metadata("subject_user_id".to_string(), serde_json::json!(verification.user_id))
+            .compliance_tag("INCOME".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(confirmation.into())
+    }
+
+    /// Confirmation requests sent for a verification
+    pub async fn list_confirmations(&self, verification_id: Uuid) -> AppResult<Vec<EmployerConfirmationResponse>> {
+        self.find_verification(verification_id).await?;
+        let confirmations = self.repository.find_employer_confirmations(verification_id).await?;
+        Ok(confirmations.into_iter().map(EmployerConfirmationResponse::from).collect())
+    }
+
+    /// What the employer is asked to confirm, looked up by link token
+    pub async fn get_details(&self, token: &str) -> AppResult<EmployerConfirmationDetails> {
+        let confirmation = self.find_by_token(token).await?;
+        let verification = self.find_verification(confirmation.verification_id).await?;
+        let employee_name = self
+            .repository
+            .find_user_name(verification.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        Ok(EmployerConfirmationDetails {
+            employee_name,
+            employer_name: verification.employer_name,
+            job_title: verification.job_title,
+            stated_annual_income: verification.annual_income,
+            currency: verification.currency,
+            status: confirmation.status,
+            expires_at: confirmation.expires_at,
+        })
+    }
+
+    /// Record the employer's answer and decide the verification
+    pub async fn submit(
+        &self,
+        token: &str,
+        submission: EmployerConfirmationSubmission,
+    ) -> AppResult<EmployerConfirmationResponse> {
+        let mut confirmation = self.find_by_token(token).await?;
+        if !matches!(confirmation.status, EmployerConfirmationStatus::Pending) {
+            return Err(AppError::BadRequest(
+                "This confirmation request is no longer open".to_string(),
+            ));
+        }
+        if confirmation.expires_at < Utc::now() {
+            return Err(AppError::BadRequest("This confirmation link has expired".to_string()));
+        }
+
+        let verification_status = if submission.employed {
+            confirmation.status = EmployerConfirmationStatus::Confirmed;
+            confirmation.confirmed_job_title = submission.job_title;
+            confirmation.confirmed_annual_income = submission.annual_income;
+            IncomeVerificationStatus::Completed
+        } else {
+            confirmation.status = EmployerConfirmationStatus::Rejected;
+            IncomeVerificationStatus::Failed
+        };
+        confirmation.employment_confirmed = Some(submission.employed);
+        confirmation.employer_comment = submission.comment;
+
+        let confirmation = self
+            .repository
+            .record_employer_response(&confirmation, verification_status)
+            .await?;
+        let verification = self.find_verification(confirmation.verification_id).await?;
+
+        let event = AuditEvent::new(AuditEventType::EmployerConfirmationReceived)
+            .severity(if submission.employed {
+                AuditSeverity::Info
+            } else {
+                AuditSeverity::Warning
+            })
+            .user_id(verification.user_id)
+            .resource(format!("income_verification:{}", verification.id))
+            .action("employer_response".to_string())
+            .metadata("confirmation_id".to_string(), serde_json::json!(confirmation.id))
+            .metadata("employed".to_string(), serde_json::json!(submission.employed))
+            .compliance_tag("INCOME".to_string());
+        self.audit_logger.log(event).await;
+
+        // The verification outcome may change the user's KYC tier
+        self.kyc_policy.refresh_user_tier(verification.user_id, Uuid::nil()).await?;
+
+        Ok(confirmation.into())
+    }
+
+    async fn find_verification(&self, verification_id: Uuid) -> AppResult<IncomeVerification> {
+        self.repository
+            .find_by_id(verification_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Income verification not found".to_string()))
+    }
+
+    async fn find_by_token(&self, token: &str) -> AppResult<EmployerConfirmation> {
+        let confirmation = self
+            .repository
+            .find_employer_confirmation_by_token_hash(&hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Confirmation request not found".to_string()))?;
+
+        if matches!(confirmation.status, EmployerConfirmationStatus::Expired) {
+            return Err(AppError::BadRequest("This confirmation link has expired".to_string()));
+        }
+        Ok(confirmation)
+    }
+
+    fn confirmation_url(&self, token: &str) -> String {
+        format!(
+            "{}/api/v1/income/employer-confirmations/{}",
+            self.public_base_url.trim_end_matches('/'),
+            token
+        )
+    }
+}
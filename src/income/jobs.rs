@@ -0,0 +1,96 @@
+use chrono::Utc;
+use serde_json::json;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditSeverity};
+use crate::core::error::AppResult;
+use crate::core::AppState;
+use super::repository::IncomeRepository;
+
+const EMPLOYER_CONFIRMATION_EXPIRY_JOB: &str = "employer_confirmation_expiry";
+
+const CREDIT_REPORT_PURGE_JOB: &str = "credit_report_purge";
+
+/// Periodically expire employer confirmation links that were never answered
+pub fn spawn_employer_confirmation_expiry_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.employer_confirmation_expiry_check_interval_seconds);
+    state.job_monitor.register(EMPLOYER_CONFIRMATION_EXPIRY_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match expire_employer_confirmations(&state).await {
+                Ok(count) => {
+                    state.job_monitor.record_success(EMPLOYER_CONFIRMATION_EXPIRY_JOB);
+                    if count > 0 {
+                        tracing::info!("Expired {} employer confirmation requests", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(EMPLOYER_CONFIRMATION_EXPIRY_JOB, e.to_string());
+                    tracing::error!("Employer confirmation expiry job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+async fn expire_employer_confirmations(state: &AppState) -> AppResult<usize> {
+    let expired = IncomeRepository::new(state.postgres.clone())
+        .expire_employer_confirmations(Utc::now())
+        .await?;
+
+    for confirmation in &expired {
+        let event = AuditEvent::new(AuditEventType::EmployerConfirmationExpired)
+            .severity(AuditSeverity::Warning)
+            .resource(format!("income_verification:{}", confirmation.verification_id))
+            .action("expire".to_string())
+            .metadata("confirmation_id".to_string(), json!(confirmation.id))
+            .metadata("expires_at".to_string(), json!(confirmation.expires_at))
+            .compliance_tag("INCOME".to_string());
+        state.audit_logger.log(event).await;
+    }
+
+    Ok(expired.len())
+}
+
+/// Periodically purge credit reports held past their retention period
+pub fn spawn_credit_report_purge_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.credit_report_purge_interval_seconds);
+    state.job_monitor.register(CREDIT_REPORT_PURGE_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match purge_expired_credit_reports(&state).await {
+                Ok(count) => {
+                    state.job_monitor.record_success(CREDIT_REPORT_PURGE_JOB);
+                    if count > 0 {
+                        tracing::info!("Purged {} expired credit reports", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(CREDIT_REPORT_PURGE_JOB, e.to_string());
+                    tracing::error!("Credit report purge job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+async fn purge_expired_credit_reports(state: &AppState) -> AppResult<usize> {
+    let purged = IncomeRepository::new(state.postgres.clone())
+        .purge_expired_credit_reports(Utc::now())
+        .await?;
+
+    for check in &purged {
+        let event = AuditEvent::new(AuditEventType::CreditReportPurged)
+            .resource(format!("credit_check:{}", check.id))
+            .action("expire".to_string())
+            .metadata("retain_until".to_string(), json!(check.retain_until))
+            .compliance_tag("CREDIT_CHECK".to_string());
+        state.audit_logger.log(event).await;
+    }
+
+    Ok(purged.len())
+}
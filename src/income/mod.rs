@@ -1,14 +1,48 @@
+pub mod affordability;
+pub mod bureau;
 pub mod controller;
+pub mod credit_check;
+pub mod employer;
+pub mod jobs;
 pub mod model;
+pub mod parser;
+pub mod report;
 pub mod repository;
 pub mod service;
 
-use axum::{routing::{get, post}, Router};
+use axum::{extract::DefaultBodyLimit, routing::{get, post}, Router};
 use crate::core::AppState;
+use crate::shared::constants::{MAX_INCOME_DOCUMENTS_PER_UPLOAD, MAX_INCOME_DOCUMENT_SIZE};
+
+/// Room for every document of one upload plus the multipart framing and text fields
+const UPLOAD_BODY_LIMIT: usize = MAX_INCOME_DOCUMENT_SIZE * MAX_INCOME_DOCUMENTS_PER_UPLOAD + 64 * 1024;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
-        .route("/verify", post(controller::initiate_income_verification))
+        .route(
+            "/verify",
+            post(controller::initiate_income_verification).layer(DefaultBodyLimit::max(UPLOAD_BODY_LIMIT)),
+        )
+        .route(
+            "/verify/:id/documents",
+            post(controller::upload_income_documents).layer(DefaultBodyLimit::max(UPLOAD_BODY_LIMIT)),
+        )
         .route("/verify/status/:id", get(controller::get_income_verification_status))
+        .route(
+            "/verify/:id/employer-confirmation",
+            post(controller::request_employer_confirmation).get(controller::list_employer_confirmations),
+        )
+        .route(
+            "/employer-confirmations/:token",
+            get(controller::get_employer_confirmation).post(controller::submit_employer_confirmation),
+        )
         .route("/report", get(controller::get_income_report))
+        .route("/report/verify/:code", get(controller::verify_income_report))
+        .route("/affordability/:user_id", get(controller::get_affordability))
+        .route("/credit-check", post(controller::initiate_credit_check))
+        .route("/credit-check/callback", post(controller::credit_check_callback))
+        .route(
+            "/credit-check/:id",
+            get(controller::get_credit_check).delete(controller::purge_credit_report),
+        )
 }
\ No newline at end of file
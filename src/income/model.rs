@@ -1,13 +1,16 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{types::Json, FromRow};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
+use crate::core::config::Config;
 use crate::shared::types::{UserId, Amount, Currency};
 
 /// Income verification status
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "income_verification_status", rename_all = "lowercase")]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "income_verification_status", rename_all = "snake_case")]
 pub enum IncomeVerificationStatus {
     Pending,
     InProgress,
@@ -35,43 +38,696 @@ pub struct IncomeVerification {
     pub updated_at: DateTime<Utc>,
 }
 
-/// Income verification request
-#[derive(Debug, Deserialize, Validate)]
+/// Income verification request. Sent as JSON, or as the text fields of a
+/// multipart upload alongside `document` file parts.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct IncomeVerificationRequest {
+    pub user_id: UserId,
+    #[validate(length(min = 1, max = 100))]
     pub verification_type: String,
-    pub employer_name: String,
-    pub job_title: String,
-    pub expected_annual_income: Amount,
+    #[validate(length(min = 1, max = 255))]
+    pub employer_name: Option<String>,
+    #[validate(length(min = 1, max = 255))]
+    pub job_title: Option<String>,
+    #[validate(range(min = 0))]
+    pub expected_annual_income: Option<Amount>,
+    #[validate(length(equal = 3))]
     pub currency: Currency,
+    /// Document type applied to files uploaded with the request
+    #[serde(default)]
+    pub document_type: IncomeDocumentType,
     pub additional_data: Option<serde_json::Value>,
 }
 
 /// Income verification response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct IncomeVerificationResponse {
     pub id: Uuid,
+    pub user_id: UserId,
     pub status: IncomeVerificationStatus,
     pub verification_type: String,
     pub employer_name: Option<String>,
     pub job_title: Option<String>,
     pub annual_income: Option<Amount>,
     pub currency: Currency,
+    /// Fields detected in the latest parsed document, awaiting reviewer confirmation
+    pub extracted_fields: Option<ExtractedIncomeFields>,
+    pub documents: Vec<IncomeDocumentResponse>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
 
 impl From<IncomeVerification> for IncomeVerificationResponse {
     fn from(verification: IncomeVerification) -> Self {
+        let extracted_fields = verification
+            .verification_data
+            .as_ref()
+            .and_then(|data| data.get("extracted_fields"))
+            .and_then(|fields| serde_json::from_value(fields.clone()).ok());
+
         Self {
             id: verification.id,
+            user_id: verification.user_id,
             status: verification.status,
             verification_type: verification.verification_type,
             employer_name: verification.employer_name,
             job_title: verification.job_title,
             annual_income: verification.annual_income,
             currency: verification.currency,
+            extracted_fields,
+            documents: Vec::new(),
             created_at: verification.created_at,
             completed_at: verification.completed_at,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Kind of document uploaded as income evidence
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "income_document_type", rename_all = "snake_case")]
+pub enum IncomeDocumentType {
+    #[default]
+    Payslip,
+    BankStatement,
+    EmploymentLetter,
+    TaxReturn,
+    Other,
+}
+
+/// Outcome of running the parsing pipeline over a document
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "income_document_parse_status", rename_all = "snake_case")]
+pub enum DocumentParseStatus {
+    Parsed,
+    /// Text was extracted but no income fields were recognised
+    NoFieldsFound,
+    /// The document has no text layer (an image or scanned PDF)
+    NoText,
+}
+
+/// How often the detected pay is paid
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayFrequency {
+    Weekly,
+    Biweekly,
+    SemiMonthly,
+    Monthly,
+    Annual,
+}
+
+impl PayFrequency {
+    pub fn periods_per_year(&self) -> i64 {
+        match self {
+            PayFrequency::Weekly => 52,
+            PayFrequency::Biweekly => 26,
+            PayFrequency::SemiMonthly => 24,
+            PayFrequency::Monthly => 12,
+            PayFrequency::Annual => 1,
+        }
+    }
+}
+
+/// Income fields detected in a document. They are unconfirmed until a
+/// reviewer checks them against the document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExtractedIncomeFields {
+    pub employer_name: Option<String>,
+    pub gross_pay: Option<Amount>,
+    pub net_pay: Option<Amount>,
+    pub currency: Option<Currency>,
+    pub pay_date: Option<NaiveDate>,
+    pub pay_frequency: Option<PayFrequency>,
+    /// Gross pay multiplied by the pay periods in a year
+    pub estimated_annual_income: Option<Amount>,
+}
+
+impl ExtractedIncomeFields {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// A document stored as evidence for an income verification
+#[derive(Debug, Clone, FromRow)]
+pub struct IncomeDocument {
+    pub id: Uuid,
+    pub verification_id: Uuid,
+    pub document_type: IncomeDocumentType,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+    pub parse_status: DocumentParseStatus,
+    pub extracted_fields: Option<Json<ExtractedIncomeFields>>,
+    pub uploaded_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A file received in a multipart upload
+#[derive(Debug)]
+pub struct DocumentUpload {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+/// Income document response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IncomeDocumentResponse {
+    pub id: Uuid,
+    pub verification_id: Uuid,
+    pub document_type: IncomeDocumentType,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub parse_status: DocumentParseStatus,
+    pub extracted_fields: Option<ExtractedIncomeFields>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<IncomeDocument> for IncomeDocumentResponse {
+    fn from(document: IncomeDocument) -> Self {
+        Self {
+            id: document.id,
+            verification_id: document.verification_id,
+            document_type: document.document_type,
+            file_name: document.file_name,
+            content_type: document.content_type,
+            size_bytes: document.size_bytes,
+            parse_status: document.parse_status,
+            extracted_fields: document.extracted_fields.map(|fields| fields.0),
+            created_at: document.created_at,
+        }
+    }
+}
+/// Income report output format
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Pdf,
+}
+
+/// Income report query parameters
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IncomeReportQuery {
+    pub user_id: UserId,
+    /// Number of full months of account inflows to analyse
+    #[serde(default = "default_report_months")]
+    #[validate(range(min = 1, max = 36))]
+    pub months: u32,
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+fn default_report_months() -> u32 {
+    12
+}
+
+/// Inflows into a user's accounts for one month and currency
+#[derive(Debug, Clone, FromRow)]
+pub struct MonthlyInflow {
+    pub month: NaiveDate,
+    pub currency: Currency,
+    pub total: Amount,
+    pub transaction_count: i64,
+}
+
+/// A completed income verification included in a report
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct VerifiedIncome {
+    pub verification_id: Uuid,
+    pub verification_type: String,
+    pub employer_name: Option<String>,
+    pub job_title: Option<String>,
+    pub annual_income: Option<Amount>,
+    pub currency: Currency,
+    pub provider: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<IncomeVerification> for VerifiedIncome {
+    fn from(verification: IncomeVerification) -> Self {
+        Self {
+            verification_id: verification.id,
+            verification_type: verification.verification_type,
+            employer_name: verification.employer_name,
+            job_title: verification.job_title,
+            annual_income: verification.annual_income,
+            currency: verification.currency,
+            provider: verification.provider,
+            completed_at: verification.completed_at,
+        }
+    }
+}
+
+/// One month of inflows in a report
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MonthlyIncome {
+    pub month: String,
+    pub total: Amount,
+    pub transaction_count: i64,
+}
+
+/// Income analytics for one currency over the report period
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IncomeAnalytics {
+    pub currency: Currency,
+    pub total_inflow: Amount,
+    /// Total inflow divided by the months in the period, rounded down
+    pub average_monthly_inflow: Amount,
+    pub months_with_income: u32,
+    pub monthly: Vec<MonthlyIncome>,
+}
+
+/// Signed content of an income report
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IncomeReportData {
+    pub report_id: Uuid,
+    pub verification_code: String,
+    pub user_id: UserId,
+    pub subject_name: String,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub months_in_period: u32,
+    pub generated_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub verified_income: Vec<VerifiedIncome>,
+    pub income_analytics: Vec<IncomeAnalytics>,
+}
+
+/// Stored income report
+#[derive(Debug, Clone, FromRow)]
+pub struct IncomeReport {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub verification_code: String,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub report_data: serde_json::Value,
+    pub signature: String,
+    pub generated_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Generated income report response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IncomeReportResponse {
+    pub verification_code: String,
+    pub verification_url: String,
+    pub signature: String,
+    pub signature_algorithm: String,
+    pub report: IncomeReportData,
+}
+
+/// Outcome of validating a report by its verification code
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportValidity {
+    Valid,
+    Expired,
+    /// The stored content no longer matches its signature
+    Invalid,
+}
+
+/// Public report verification response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReportVerificationResponse {
+    pub verification_code: String,
+    pub validity: ReportValidity,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub signature: String,
+    pub report: Option<serde_json::Value>,
+}
+
+/// Affordability query parameters
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AffordabilityQuery {
+    /// Number of full months of account activity to analyse
+    #[serde(default = "default_affordability_months")]
+    #[validate(range(min = 3, max = 24))]
+    pub months: u32,
+    /// Currency to assess; by default the one with the most income
+    #[validate(length(equal = 3))]
+    pub currency: Option<Currency>,
+}
+
+fn default_affordability_months() -> u32 {
+    6
+}
+
+/// Outflows from a user's accounts for one month and currency
+#[derive(Debug, Clone, FromRow)]
+pub struct MonthlyOutflow {
+    pub month: NaiveDate,
+    pub currency: Currency,
+    pub total: Amount,
+    /// Part of the total paid to payees the user pays most months
+    pub recurring: Amount,
+}
+
+/// Where the monthly income an assessment is based on comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IncomeSource {
+    /// A completed income verification's annual income
+    Verified,
+    /// Average inflows into the user's accounts
+    Observed,
+}
+
+/// Recommended lending band, from lowest to highest risk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LendingBand {
+    Prime,
+    NearPrime,
+    Subprime,
+    Decline,
+}
+
+/// Whether a factor helps or hurts the assessment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FactorImpact {
+    Positive,
+    Neutral,
+    Negative,
+}
+
+/// One input to an affordability assessment and how it counted
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AffordabilityFactor {
+    pub code: String,
+    pub value: f64,
+    pub impact: FactorImpact,
+    pub description: String,
+}
+
+/// Affordability signal for lenders, with the factors behind it
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AffordabilityAssessment {
+    pub user_id: UserId,
+    pub currency: Currency,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub months_in_period: u32,
+    pub monthly_income: Amount,
+    pub income_source: IncomeSource,
+    pub average_monthly_outflow: Amount,
+    /// Average monthly payments to recurring payees, treated as existing
+    /// obligations
+    pub monthly_obligations: Amount,
+    /// Monthly obligations divided by monthly income
+    pub debt_to_income: f64,
+    /// 0 to 100; 100 is the same income every month
+    pub income_stability: u32,
+    /// Standard deviation of monthly inflows divided by their mean
+    pub income_variation: f64,
+    pub months_with_income: u32,
+    pub lending_band: LendingBand,
+    /// Largest new monthly repayment that keeps obligations within limits
+    /// and is covered by income left after current spending
+    pub max_monthly_repayment: Amount,
+    pub factors: Vec<AffordabilityFactor>,
+    pub assessed_at: DateTime<Utc>,
+}
+
+/// Status of an employer's confirmation of an income verification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "employer_confirmation_status", rename_all = "snake_case")]
+pub enum EmployerConfirmationStatus {
+    Pending,
+    Confirmed,
+    Rejected,
+    Expired,
+    Cancelled,
+}
+
+/// Employer confirmation request sent to an HR contact
+#[derive(Debug, Clone, FromRow)]
+pub struct EmployerConfirmation {
+    pub id: Uuid,
+    pub verification_id: Uuid,
+    pub contact_name: String,
+    pub contact_email: String,
+    pub token_hash: String,
+    pub status: EmployerConfirmationStatus,
+    pub employment_confirmed: Option<bool>,
+    pub confirmed_job_title: Option<String>,
+    pub confirmed_annual_income: Option<Amount>,
+    pub employer_comment: Option<String>,
+    pub requested_by: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request employer confirmation for an income verification
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RequestEmployerConfirmationRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub contact_name: String,
+    #[validate(email)]
+    pub contact_email: String,
+}
+
+/// Employer's response submitted through the confirmation link
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EmployerConfirmationSubmission {
+    /// Whether the person is employed as stated
+    pub employed: bool,
+    #[validate(length(min = 1, max = 255))]
+    pub job_title: Option<String>,
+    #[validate(range(min = 0))]
+    pub annual_income: Option<Amount>,
+    #[validate(length(max = 2000))]
+    pub comment: Option<String>,
+}
+
+/// Employer confirmation response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EmployerConfirmationResponse {
+    pub id: Uuid,
+    pub verification_id: Uuid,
+    pub contact_name: String,
+    pub contact_email: String,
+    pub status: EmployerConfirmationStatus,
+    pub employment_confirmed: Option<bool>,
+    pub confirmed_job_title: Option<String>,
+    pub confirmed_annual_income: Option<Amount>,
+    pub employer_comment: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<EmployerConfirmation> for EmployerConfirmationResponse {
+    fn from(confirmation: EmployerConfirmation) -> Self {
+        Self {
+            id: confirmation.id,
+            verification_id: confirmation.verification_id,
+            contact_name: confirmation.contact_name,
+            contact_email: confirmation.contact_email,
+            status: confirmation.status,
+            employment_confirmed: confirmation.employment_confirmed,
+            confirmed_job_title: confirmation.confirmed_job_title,
+            confirmed_annual_income: confirmation.confirmed_annual_income,
+            employer_comment: confirmation.employer_comment,
+            expires_at: confirmation.expires_at,
+            responded_at: confirmation.responded_at,
+            created_at: confirmation.created_at,
+        }
+    }
+}
+
+/// What the employer is asked to confirm, shown on the public confirmation link
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EmployerConfirmationDetails {
+    pub employee_name: String,
+    pub employer_name: Option<String>,
+    pub job_title: Option<String>,
+    pub stated_annual_income: Option<Amount>,
+    pub currency: Currency,
+    pub status: EmployerConfirmationStatus,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Status of a credit report pull
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "credit_check_status", rename_all = "snake_case")]
+pub enum CreditCheckStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// Consent and retention rules for credit checks
+#[derive(Debug, Clone)]
+pub struct CreditCheckSettings {
+    /// Consent older than this must be given again
+    pub consent_max_age_days: i64,
+    /// How long a report is kept after it arrives
+    pub retention_days: i64,
+    /// Where bureaus post completed pulls
+    pub callback_url: String,
+}
+
+impl CreditCheckSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            consent_max_age_days: config.credit_check_consent_max_age_days,
+            retention_days: config.credit_report_retention_days,
+            callback_url: format!(
+                "{}/api/v1/income/credit-check/callback",
+                config.public_base_url.trim_end_matches('/')
+            ),
+        }
+    }
+}
+
+/// The user's consent to a credit report pull, as recorded by the caller
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CreditCheckConsent {
+    /// Must be true; a pull without consent is refused
+    pub granted: bool,
+    /// When the user gave consent
+    pub granted_at: DateTime<Utc>,
+    /// The caller's reference for the consent record, such as a signed form ID
+    #[validate(length(min = 1, max = 255))]
+    pub reference: String,
+}
+
+/// Request to pull a user's credit report
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreditCheckRequest {
+    pub user_id: UserId,
+    /// Why the report is needed, such as `loan_application`
+    #[validate(length(min = 1, max = 100))]
+    pub purpose: String,
+    #[validate(nested)]
+    pub consent: CreditCheckConsent,
+}
+
+/// Standing of one credit account on a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CreditAccountStatus {
+    Current,
+    Delinquent,
+    Default,
+    Closed,
+}
+
+/// A credit account (tradeline) on a report
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreditAccount {
+    /// Kind of credit, such as `mortgage`, `credit_card` or `loan`
+    pub account_type: String,
+    pub status: CreditAccountStatus,
+    pub balance: Amount,
+    pub credit_limit: Option<Amount>,
+    pub monthly_payment: Amount,
+    pub opened_on: Option<NaiveDate>,
+}
+
+/// A credit report in the same form whichever bureau supplied it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreditReport {
+    pub score: Option<i32>,
+    /// Scoring model behind the score, such as `FICO8`
+    pub score_model: Option<String>,
+    pub currency: Currency,
+    pub accounts: Vec<CreditAccount>,
+    /// Balances of accounts that are not closed
+    pub total_balance: Amount,
+    pub total_monthly_payments: Amount,
+    /// Balances over limits of accounts with a limit, 0 to 1
+    pub credit_utilization: Option<f64>,
+    pub delinquent_accounts: u32,
+    /// Hard inquiries in the last twelve months
+    pub recent_inquiries: u32,
+    pub public_records: u32,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Who a credit report is pulled for
+#[derive(Debug, Clone, FromRow)]
+pub struct CreditSubject {
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+}
+
+/// A credit report pull and, until it is purged, the report
+#[derive(Debug, Clone, FromRow)]
+pub struct CreditCheck {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub user_id: UserId,
+    pub provider: String,
+    pub provider_reference: Option<String>,
+    pub status: CreditCheckStatus,
+    pub purpose: String,
+    pub consent_reference: String,
+    pub consent_granted_at: DateTime<Utc>,
+    pub report: Option<Json<CreditReport>>,
+    pub failure_reason: Option<String>,
+    pub requested_by: Uuid,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub retain_until: Option<DateTime<Utc>>,
+    pub purged_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Credit check response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreditCheckResponse {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub provider: String,
+    pub status: CreditCheckStatus,
+    pub purpose: String,
+    pub consent_reference: String,
+    pub consent_granted_at: DateTime<Utc>,
+    /// Absent until the bureau answers, and again once purged
+    pub report: Option<CreditReport>,
+    pub failure_reason: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the report will be purged
+    pub retain_until: Option<DateTime<Utc>>,
+    pub purged_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<CreditCheck> for CreditCheckResponse {
+    fn from(check: CreditCheck) -> Self {
+        Self {
+            id: check.id,
+            user_id: check.user_id,
+            provider: check.provider,
+            status: check.status,
+            purpose: check.purpose,
+            consent_reference: check.consent_reference,
+            consent_granted_at: check.consent_granted_at,
+            report: check.report.map(|report| report.0),
+            failure_reason: check.failure_reason,
+            completed_at: check.completed_at,
+            retain_until: check.retain_until,
+            purged_at: check.purged_at,
+            created_at: check.created_at,
+        }
+    }
+}
@@ -0,0 +1,539 @@
+use std::io::Read;
+use chrono::NaiveDate;
+use flate2::read::ZlibDecoder;
+use crate::shared::types::{Amount, Currency};
+use super::model::{DocumentParseStatus, ExtractedIncomeFields, PayFrequency};
+
+/// Largest decompressed PDF stream the text extractor will inflate
+const MAX_INFLATED_STREAM_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Currency codes recognised next to an amount
+const CURRENCY_CODES: &[&str] = &["USD", "EUR", "GBP", "NGN", "CAD", "AUD", "ZAR", "KES", "GHS", "INR"];
+
+const EMPLOYER_LABELS: &[&str] = &["employer name", "employer", "company name", "company"];
+const GROSS_PAY_LABELS: &[&str] = &[
+    "gross pay", "gross earnings", "gross salary", "gross income", "total gross", "total earnings",
+];
+const NET_PAY_LABELS: &[&str] = &["net pay", "net salary", "take home pay", "take-home pay", "net amount"];
+const PAY_DATE_LABELS: &[&str] = &["pay date", "payment date", "date paid", "paid on"];
+const FREQUENCY_LABELS: &[&str] = &["pay frequency", "frequency", "pay period"];
+
+/// Run the parsing pipeline over an uploaded document: extract its text,
+/// then detect income fields in it
+pub fn parse_document(content_type: &str, content: &[u8]) -> (DocumentParseStatus, Option<ExtractedIncomeFields>) {
+    let Some(text) = extract_text(content_type, content) else {
+        return (DocumentParseStatus::NoText, None);
+    };
+
+    let fields = detect_fields(&text);
+    if fields.is_empty() {
+        (DocumentParseStatus::NoFieldsFound, None)
+    } else {
+        (DocumentParseStatus::Parsed, Some(fields))
+    }
+}
+
+/// Pull readable text out of a document. Returns `None` for documents
+/// without a text layer, such as images and scanned PDFs, which a reviewer
+/// has to read instead.
+pub fn extract_text(content_type: &str, content: &[u8]) -> Option<String> {
+    let text = match content_type {
+        "text/plain" => String::from_utf8_lossy(content).into_owned(),
+        "application/pdf" => extract_pdf_text(content),
+        _ => return None,
+    };
+    Some(text).filter(|text| !text.trim().is_empty())
+}
+
+/// Detect employer, pay amounts, pay date and pay frequency in document text
+pub fn detect_fields(text: &str) -> ExtractedIncomeFields {
+    let mut fields = ExtractedIncomeFields::default();
+
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        if fields.employer_name.is_none() {
+            fields.employer_name = labelled_value(line, EMPLOYER_LABELS, true)
+                .filter(|value| value.chars().any(|c| c.is_alphabetic()))
+                .map(str::to_string);
+        }
+        if fields.gross_pay.is_none() {
+            if let Some((amount, currency)) = labelled_value(line, GROSS_PAY_LABELS, false).and_then(find_amount) {
+                fields.gross_pay = Some(amount);
+                fields.currency = fields.currency.take().or(currency);
+            }
+        }
+        if fields.net_pay.is_none() {
+            if let Some((amount, currency)) = labelled_value(line, NET_PAY_LABELS, false).and_then(find_amount) {
+                fields.net_pay = Some(amount);
+                fields.currency = fields.currency.take().or(currency);
+            }
+        }
+        if fields.pay_date.is_none() {
+            fields.pay_date = labelled_value(line, PAY_DATE_LABELS, false).and_then(find_date);
+        }
+        if fields.pay_frequency.is_none() {
+            fields.pay_frequency = labelled_value(line, FREQUENCY_LABELS, false).and_then(find_frequency);
+        }
+    }
+
+    // Fall back to a frequency mentioned anywhere, e.g. "Monthly payslip"
+    if fields.pay_frequency.is_none() && !fields.is_empty() {
+        fields.pay_frequency = find_frequency(text);
+    }
+    fields.estimated_annual_income = fields
+        .gross_pay
+        .zip(fields.pay_frequency)
+        .and_then(|(gross, frequency)| gross.checked_mul(frequency.periods_per_year()));
+
+    fields
+}
+
+/// The text after the first of `labels` found in the line, without the
+/// separating colon. Labels match case-insensitively.
+fn labelled_value<'a>(line: &'a str, labels: &[&str], require_colon: bool) -> Option<&'a str> {
+    let lower = line.to_lowercase();
+    // Lowercasing can change byte offsets outside ASCII
+    if lower.len() != line.len() {
+        return None;
+    }
+
+    labels.iter().find_map(|label| {
+        let start = lower.find(label)?;
+        let at_word_start = start == 0 || !lower.as_bytes()[start - 1].is_ascii_alphanumeric();
+        let rest = &line[start + label.len()..];
+        let at_word_end = !rest.starts_with(|c: char| c.is_ascii_alphanumeric());
+        if !at_word_start || !at_word_end {
+            return None;
+        }
+
+        let value = rest.trim_start();
+        let value = match value.strip_prefix(':') {
+            Some(value) => value,
+            None if require_colon => return None,
+            None => value,
+        };
+        Some(value.trim()).filter(|value| !value.is_empty())
+    })
+}
+
+/// The first amount in the text, in minor units, with its currency when a
+/// symbol or code accompanies it. Bare numbers without a decimal part are
+/// skipped since they are more often years or counts.
+fn find_amount(text: &str) -> Option<(Amount, Option<Currency>)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    words.iter().enumerate().find_map(|(index, word)| {
+        let (symbol_currency, number) = strip_currency_symbol(word.trim_end_matches([',', ';']));
+        let has_fraction = number.rsplit_once('.').is_some_and(|(_, fraction)| fraction.len() == 2);
+        if symbol_currency.is_none() && !has_fraction {
+            return None;
+        }
+        let amount = parse_money(number)?;
+
+        let code = |position: Option<usize>| {
+            position
+                .and_then(|position| words.get(position))
+                .map(|word| word.trim_matches(|c: char| !c.is_ascii_alphabetic()))
+                .filter(|word| CURRENCY_CODES.contains(word))
+                .map(str::to_string)
+        };
+        let currency = symbol_currency
+            .map(str::to_string)
+            .or_else(|| code(index.checked_sub(1)))
+            .or_else(|| code(Some(index + 1)));
+        Some((amount, currency))
+    })
+}
+
+fn strip_currency_symbol(word: &str) -> (Option<&'static str>, &str) {
+    for (symbol, code) in [("$", "USD"), ("€", "EUR"), ("£", "GBP"), ("₦", "NGN")] {
+        if let Some(number) = word.strip_prefix(symbol) {
+            return (Some(code), number);
+        }
+    }
+    if let Some(code) = CURRENCY_CODES.iter().find(|code| word.starts_with(*code)) {
+        if word.len() > code.len() {
+            return (Some(code), &word[code.len()..]);
+        }
+    }
+    (None, word)
+}
+
+/// `1,234.56` style amounts to minor units
+fn parse_money(value: &str) -> Option<Amount> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let whole: String = whole.chars().filter(|c| *c != ',').collect();
+    if whole.is_empty()
+        || fraction.len() > 2
+        || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let whole: Amount = whole.parse().ok()?;
+    let fraction: Amount = format!("{:0<2}", fraction).parse().ok()?;
+    whole.checked_mul(100)?.checked_add(fraction)
+}
+
+/// The first date in the text, trying runs of up to three words so that
+/// `15 Oct 2026` and `October 15, 2026` are recognised
+fn find_date(text: &str) -> Option<NaiveDate> {
+    const FORMATS: &[&str] = &[
+        "%Y-%m-%d", "%d/%m/%Y", "%m/%d/%Y", "%d.%m.%Y", "%d-%m-%Y", "%d %b %Y", "%d %B %Y", "%b %d, %Y",
+        "%B %d, %Y",
+    ];
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    (0..words.len()).find_map(|start| {
+        (1..=3).filter(|len| start + len <= words.len()).find_map(|len| {
+            let candidate = words[start..start + len].join(" ");
+            let candidate = candidate.trim_end_matches(['.', ';']);
+            FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(candidate, format).ok())
+        })
+    })
+}
+
+fn find_frequency(text: &str) -> Option<PayFrequency> {
+    let lower = text.to_lowercase();
+    // Longer names first so "bi-weekly" is not read as "weekly"
+    let patterns = [
+        ("semi-monthly", PayFrequency::SemiMonthly),
+        ("semimonthly", PayFrequency::SemiMonthly),
+        ("bi-weekly", PayFrequency::Biweekly),
+        ("biweekly", PayFrequency::Biweekly),
+        ("fortnightly", PayFrequency::Biweekly),
+        ("weekly", PayFrequency::Weekly),
+        ("monthly", PayFrequency::Monthly),
+        ("annually", PayFrequency::Annual),
+        ("annual", PayFrequency::Annual),
+        ("yearly", PayFrequency::Annual),
+    ];
+
+    patterns
+        .iter()
+        .filter_map(|(pattern, frequency)| lower.find(pattern).map(|position| (position, *frequency)))
+        .min_by_key(|(position, _)| *position)
+        .map(|(_, frequency)| frequency)
+}
+
+/// Text shown by the content streams of a PDF. Uncompressed and
+/// Flate-compressed streams are read; streams with other filters (usually
+/// images) are skipped. Strings are decoded as Latin-1, which covers the
+/// standard fonts payroll systems emit; embedded CID fonts yield no text.
+fn extract_pdf_text(content: &[u8]) -> String {
+    let mut text = String::new();
+    let mut rest = content;
+
+    while let Some(keyword) = find_bytes(rest, b"stream") {
+        let dictionary = &rest[..keyword];
+        let dictionary = rfind_bytes(dictionary, b"obj").map_or(dictionary, |start| &dictionary[start..]);
+
+        let mut body_start = keyword + b"stream".len();
+        if rest[body_start..].starts_with(b"\r\n") {
+            body_start += 2;
+        } else if rest[body_start..].starts_with(b"\n") {
+            body_start += 1;
+        }
+        let Some(body_len) = find_bytes(&rest[body_start..], b"endstream") else {
+            break;
+        };
+        let body = &rest[body_start..body_start + body_len];
+
+        let decoded = if find_bytes(dictionary, b"/Filter").is_none() {
+            Some(body.to_vec())
+        } else if find_bytes(dictionary, b"/FlateDecode").is_some() {
+            inflate(body)
+        } else {
+            None
+        };
+        if let Some(decoded) = decoded {
+            text.push_str(&content_stream_text(&decoded));
+        }
+
+        rest = &rest[body_start + body_len + b"endstream".len()..];
+    }
+
+    text
+}
+
+fn inflate(data: &[u8]) -> Option<Vec<u8>> {
+    let mut inflated = Vec::new();
+    ZlibDecoder::new(data)
+        .take(MAX_INFLATED_STREAM_SIZE)
+        .read_to_end(&mut inflated)
+        .ok()?;
+    Some(inflated)
+}
+
+/// Text shown between `BT` and `ET` in a content stream, one output line per
+/// line of text on the page
+fn content_stream_text(data: &[u8]) -> String {
+    let mut text = String::new();
+    let mut line = String::new();
+    // Strings seen since the last text-showing operator
+    let mut pending = String::new();
+    let mut operands: Vec<f64> = Vec::new();
+    let mut in_text = false;
+    let mut in_array = false;
+    let mut last_matrix_y: Option<f64> = None;
+    let mut i = 0;
+
+    let flush = |text: &mut String, line: &mut String| {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() {
+            text.push_str(trimmed);
+            text.push('\n');
+        }
+        line.clear();
+    };
+
+    while i < data.len() {
+        match data[i] {
+            b'(' => {
+                let (string, next) = read_literal_string(data, i + 1);
+                pending.push_str(&string);
+                i = next;
+            }
+            b'<' if data.get(i + 1) == Some(&b'<') => i += 2,
+            b'>' if data.get(i + 1) == Some(&b'>') => i += 2,
+            b'<' => {
+                let end = data[i..].iter().position(|b| *b == b'>').map_or(data.len(), |end| i + end);
+                pending.push_str(&decode_hex_string(&data[i + 1..end]));
+                i = end + 1;
+            }
+            b'[' => {
+                in_array = true;
+                i += 1;
+            }
+            b']' => {
+                in_array = false;
+                i += 1;
+            }
+            b'%' => {
+                while i < data.len() && data[i] != b'\n' && data[i] != b'\r' {
+                    i += 1;
+                }
+            }
+            b'/' => {
+                i += 1;
+                while i < data.len() && is_regular(data[i]) {
+                    i += 1;
+                }
+            }
+            b'0'..=b'9' | b'-' | b'+' | b'.' => {
+                let start = i;
+                i += 1;
+                while i < data.len() && matches!(data[i], b'0'..=b'9' | b'.') {
+                    i += 1;
+                }
+                let number = std::str::from_utf8(&data[start..i]).ok().and_then(|n| n.parse::<f64>().ok());
+                if let Some(number) = number {
+                    // Large negative kerning inside a TJ array separates words
+                    if in_array && number < -200.0 {
+                        pending.push(' ');
+                    }
+                    operands.push(number);
+                }
+            }
+            byte if is_regular(byte) => {
+                let start = i;
+                while i < data.len() && is_regular(data[i]) {
+                    i += 1;
+                }
+                match &data[start..i] {
+                    b"BT" => in_text = true,
+                    b"ET" => {
+                        line.push_str(&pending);
+                        flush(&mut text, &mut line);
+                        in_text = false;
+                    }
+                    b"Tj" | b"TJ" if in_text => line.push_str(&pending),
+                    b"'" | b"\"" if in_text => {
+                        flush(&mut text, &mut line);
+                        line.push_str(&pending);
+                    }
+                    b"Td" | b"TD" if in_text => {
+                        let moves_down = operands.last().is_some_and(|ty| ty.abs() > f64::EPSILON);
+                        if moves_down {
+                            flush(&mut text, &mut line);
+                        } else {
+                            line.push(' ');
+                        }
+                    }
+                    b"T*" if in_text => flush(&mut text, &mut line),
+                    b"Tm" if in_text => {
+                        let y = operands.last().copied();
+                        if last_matrix_y.is_some_and(|last| Some(last) != y) {
+                            flush(&mut text, &mut line);
+                        } else {
+                            line.push(' ');
+                        }
+                        last_matrix_y = y;
+                    }
+                    _ => {}
+                }
+                pending.clear();
+                operands.clear();
+            }
+            _ => i += 1,
+        }
+    }
+
+    flush(&mut text, &mut line);
+    text
+}
+
+/// Bytes that make up names, numbers and operators
+fn is_regular(byte: u8) -> bool {
+    !byte.is_ascii_whitespace() && !b"()<>[]{}/%".contains(&byte)
+}
+
+/// A `(...)` string starting just after the opening parenthesis. Returns the
+/// decoded string and the position after the closing parenthesis.
+fn read_literal_string(data: &[u8], mut i: usize) -> (String, usize) {
+    let mut string = String::new();
+    let mut depth = 1;
+
+    while i < data.len() {
+        let byte = data[i];
+        i += 1;
+        match byte {
+            b'\\' => {
+                let Some(&escaped) = data.get(i) else { break };
+                i += 1;
+                match escaped {
+                    b'n' => string.push('\n'),
+                    b'r' => string.push('\r'),
+                    b't' => string.push('\t'),
+                    b'b' | b'f' => {}
+                    b'0'..=b'7' => {
+                        let mut value = u32::from(escaped - b'0');
+                        for _ in 0..2 {
+                            match data.get(i) {
+                                Some(digit @ b'0'..=b'7') => {
+                                    value = value * 8 + u32::from(digit - b'0');
+                                    i += 1;
+                                }
+                                _ => break,
+                            }
+                        }
+                        string.push(char::from_u32(value & 0xFF).unwrap_or('?'));
+                    }
+                    // Line continuation
+                    b'\r' | b'\n' => {
+                        if escaped == b'\r' && data.get(i) == Some(&b'\n') {
+                            i += 1;
+                        }
+                    }
+                    other => string.push(other as char),
+                }
+            }
+            b'(' => {
+                depth += 1;
+                string.push('(');
+            }
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                string.push(')');
+            }
+            other => string.push(other as char),
+        }
+    }
+
+    (string, i)
+}
+
+fn decode_hex_string(hex: &[u8]) -> String {
+    let digits: Vec<u8> = hex
+        .iter()
+        .filter_map(|byte| (*byte as char).to_digit(16).map(|digit| digit as u8))
+        .collect();
+    digits
+        .chunks(2)
+        .map(|pair| ((pair[0] << 4) | pair.get(1).copied().unwrap_or(0)) as char)
+        .collect()
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn rfind_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use flate2::{write::ZlibEncoder, Compression};
+    use super::*;
+
+    #[test]
+    fn test_detect_payslip_fields() {
+        let text = "ACME Payroll Services\n\
+                    Employer: Acme Widgets Ltd\n\
+                    Employee: Jane Doe\n\
+                    Pay Date: 31/10/2026\n\
+                    Pay Frequency: Monthly\n\
+                    Gross Pay $4,250.00 YTD $42,500.00\n\
+                    Net Pay: 3,187.50 USD\n";
+        let fields = detect_fields(text);
+
+        assert_eq!(fields.employer_name.as_deref(), Some("Acme Widgets Ltd"));
+        assert_eq!(fields.gross_pay, Some(425_000));
+        assert_eq!(fields.net_pay, Some(318_750));
+        assert_eq!(fields.currency.as_deref(), Some("USD"));
+        assert_eq!(fields.pay_date, NaiveDate::from_ymd_opt(2026, 10, 31));
+        assert_eq!(fields.pay_frequency, Some(PayFrequency::Monthly));
+        assert_eq!(fields.estimated_annual_income, Some(5_100_000));
+    }
+
+    #[test]
+    fn test_detect_fields_skips_bare_numbers_and_unlabelled_text() {
+        let fields = detect_fields("Employer ID 2026\nGross pay 2026 period 1,200.50\nBi-weekly");
+        assert_eq!(fields.employer_name, None);
+        assert_eq!(fields.gross_pay, Some(120_050));
+        assert_eq!(fields.pay_frequency, Some(PayFrequency::Biweekly));
+
+        assert!(detect_fields("Dear customer, thank you for banking with us").is_empty());
+    }
+
+    #[test]
+    fn test_extract_text_from_compressed_pdf() {
+        let content_stream = b"BT /F1 10 Tf 56 720 Td (Employer: Acme Widgets Ltd) Tj \
+                               0 -14 Td [(Gross Pay) -300 (\\2434,250.00)] TJ \
+                               0 -14 Td (Pay Date: 15 Oct 2026) Tj ET";
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content_stream).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut pdf = b"%PDF-1.4\n4 0 obj\n<< /Length 0 /Filter /FlateDecode >>\nstream\n".to_vec();
+        pdf.extend_from_slice(&compressed);
+        pdf.extend_from_slice(b"\nendstream\nendobj\n5 0 obj\n<< /Subtype /Image /Filter /DCTDecode >>\nstream\n");
+        pdf.extend_from_slice(&[0xFF, 0xD8, b'(', b'x', b')', b' ', b'T', b'j']);
+        pdf.extend_from_slice(b"\nendstream\nendobj\n%%EOF");
+
+        let text = extract_text("application/pdf", &pdf).unwrap();
+        assert_eq!(text, "Employer: Acme Widgets Ltd\nGross Pay \u{a3}4,250.00\nPay Date: 15 Oct 2026\n");
+
+        let (status, fields) = parse_document("application/pdf", &pdf);
+        assert_eq!(status, DocumentParseStatus::Parsed);
+        let fields = fields.unwrap();
+        assert_eq!(fields.employer_name.as_deref(), Some("Acme Widgets Ltd"));
+        assert_eq!(fields.gross_pay, Some(425_000));
+        assert_eq!(fields.pay_date, NaiveDate::from_ymd_opt(2026, 10, 15));
+    }
+
+    #[test]
+    fn test_images_have_no_text() {
+        assert_eq!(parse_document("image/png", b"\x89PNG"), (DocumentParseStatus::NoText, None));
+        assert_eq!(
+            parse_document("text/plain", b"Nothing useful here"),
+            (DocumentParseStatus::NoFieldsFound, None)
+        );
+    }
+}
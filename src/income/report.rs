@@ -0,0 +1,333 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc};
+use rand::Rng;
+use serde_json::Value;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::crypto::{hex, hmac_sha256};
+use crate::core::error::{AppError, AppResult};
+use crate::core::pdf::PdfDocument;
+use crate::shared::types::UserId;
+use super::model::{
+    IncomeAnalytics, IncomeReport, IncomeReportData, IncomeReportResponse, IncomeVerificationStatus,
+    MonthlyIncome, MonthlyInflow, ReportValidity, ReportVerificationResponse, VerifiedIncome,
+};
+use super::repository::IncomeRepository;
+
+/// Signature scheme used for income reports
+const SIGNATURE_ALGORITHM: &str = "HMAC-SHA256";
+
+/// Verification code alphabet, without easily confused characters
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Builds signed income reports and validates them by verification code
+pub struct IncomeReportService {
+    repository: IncomeRepository,
+    signing_key: String,
+    validity_days: i64,
+    public_base_url: String,
+    audit_logger: AuditLogger,
+}
+
+impl IncomeReportService {
+    pub fn new(
+        repository: IncomeRepository,
+        signing_key: String,
+        validity_days: i64,
+        public_base_url: String,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            signing_key,
+            validity_days,
+            public_base_url,
+            audit_logger,
+        }
+    }
+
+    /// Generate and store a signed report covering the last `months` full months
+    pub async fn generate_report(
+        &self,
+        user_id: UserId,
+        months: u32,
+        actor_id: Uuid,
+    ) -> AppResult<IncomeReportResponse> {
+        let subject_name = self
+            .repository
+            .find_user_name(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let verified_income: Vec<VerifiedIncome> = self
+            .repository
+            .find_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .filter(|verification| matches!(verification.status, IncomeVerificationStatus::Completed))
+            .map(VerifiedIncome::from)
+            .collect();
+
+        let (period_start, period_end) = report_period(months)?;
+        let inflows = self
+            .repository
+            .find_monthly_inflows(
+                user_id,
+                start_of_day(period_start),
+                start_of_day(period_end + Duration::days(1)),
+            )
+            .await?;
+
+        let now = Utc::now();
+        let data = IncomeReportData {
+            report_id: Uuid::new_v4(),
+            verification_code: generate_verification_code(),
+            user_id,
+            subject_name,
+            period_start,
+            period_end,
+            months_in_period: months,
+            generated_at: now,
+            expires_at: now + Duration::days(self.validity_days),
+            verified_income,
+            income_analytics: summarize_inflows(inflows, months),
+        };
+
+        let report_data = serde_json::to_value(&data)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize income report: {}", e)))?;
+        let signature = self.sign(&report_data);
+
+        let report = IncomeReport {
+            id: data.report_id,
+            user_id,
+            verification_code: data.verification_code.clone(),
+            period_start,
+            period_end,
+            report_data,
+            signature: signature.clone(),
+            generated_by: actor_id,
+            created_at: now,
+            expires_at: data.expires_at,
+        };
+        self.repository.create_report(&report).await?;
+
+        let event = AuditEvent::new(AuditEventType::DataExported)
+            .user_id(actor_id)
+            .resource(format!("income_report:{}", report.id))
+            .action("generate".to_string())
+            .metadata("subject_user_id".to_string(), serde_json::json!(user_id))
+            .metadata("months".to_string(), serde_json::json!(months))
+            .compliance_tag("INCOME".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(IncomeReportResponse {
+            verification_url: self.verification_url(&data.verification_code),
+            verification_code: data.verification_code.clone(),
+            signature,
+            signature_algorithm: SIGNATURE_ALGORITHM.to_string(),
+            report: data,
+        })
+    }
+
+    /// Validate a report by its verification code. Report content is only
+    /// disclosed while the report is valid.
+    pub async fn verify_report(&self, code: &str) -> AppResult<ReportVerificationResponse> {
+        let report = self
+            .repository
+            .find_report_by_code(&code.trim().to_uppercase())
+            .await?
+            .ok_or_else(|| AppError::NotFound("Report not found".to_string()))?;
+
+        let validity = if self.sign(&report.report_data) != report.signature {
+            ReportValidity::Invalid
+        } else if report.expires_at < Utc::now() {
+            ReportValidity::Expired
+        } else {
+            ReportValidity::Valid
+        };
+
+        Ok(ReportVerificationResponse {
+            verification_code: report.verification_code,
+            report: (validity == ReportValidity::Valid).then_some(report.report_data),
+            validity,
+            issued_at: report.created_at,
+            expires_at: report.expires_at,
+            signature: report.signature,
+        })
+    }
+
+    /// Render a generated report as a PDF document
+    pub fn render_pdf(&self, response: &IncomeReportResponse) -> Vec<u8> {
+        let report = &response.report;
+        let mut pdf = PdfDocument::new(format!("Income Report {}", report.verification_code));
+
+        pdf.heading("OpenBank Income Report")
+            .text(format!("Subject: {}", report.subject_name))
+            .text(format!("User ID: {}", report.user_id))
+            .text(format!("Period: {} to {}", report.period_start, report.period_end))
+            .text(format!("Generated: {}", report.generated_at.format("%Y-%m-%d %H:%M UTC")))
+            .text(format!("Valid until: {}", report.expires_at.format("%Y-%m-%d")))
+            .blank();
+
+        pdf.heading("Verified Income");
+        if report.verified_income.is_empty() {
+            pdf.text("No completed income verifications.");
+        }
+        for income in &report.verified_income {
+            pdf.text(format!(
+                "{} - {} ({})",
+                income.employer_name.as_deref().unwrap_or("Unknown employer"),
+                income.job_title.as_deref().unwrap_or("Unknown role"),
+                income.verification_type
+            ))
+            .text(format!(
+                "    Annual income: {}    Verified: {}",
+                income
+                    .annual_income
+                    .map(|amount| format_amount(amount, &income.currency))
+                    .unwrap_or_else(|| "n/a".to_string()),
+                income
+                    .completed_at
+                    .map(|at| at.format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "n/a".to_string())
+            ));
+        }
+        pdf.blank();
+
+        pdf.heading("Account Inflows");
+        if report.income_analytics.is_empty() {
+            pdf.text("No qualifying inflows during the period.");
+        }
+        for analytics in &report.income_analytics {
+            pdf.text(format!(
+                "{}: total {}, monthly average {}, {} of {} months with income",
+                analytics.currency,
+                format_amount(analytics.total_inflow, &analytics.currency),
+                format_amount(analytics.average_monthly_inflow, &analytics.currency),
+                analytics.months_with_income,
+                report.months_in_period
+            ));
+            for month in &analytics.monthly {
+                pdf.text(format!(
+                    "    {}  {}  ({} transactions)",
+                    month.month,
+                    format_amount(month.total, &analytics.currency),
+                    month.transaction_count
+                ));
+            }
+        }
+        pdf.blank();
+
+        pdf.heading("Verification")
+            .text(format!("Verification code: {}", report.verification_code))
+            .text(format!("Verify at: {}", response.verification_url))
+            .text(format!("Signature ({}): {}", response.signature_algorithm, response.signature));
+
+        pdf.render()
+    }
+
+    fn verification_url(&self, code: &str) -> String {
+        format!(
+            "{}/api/v1/income/report/verify/{}",
+            self.public_base_url.trim_end_matches('/'),
+            code
+        )
+    }
+
+    /// Sign the canonical JSON form of the report content
+    fn sign(&self, report_data: &Value) -> String {
+        hex(&hmac_sha256(
+            self.signing_key.as_bytes(),
+            canonical_json(report_data).as_bytes(),
+        ))
+    }
+}
+
+/// The last `months` full calendar months, as inclusive start and end dates
+pub(crate) fn report_period(months: u32) -> AppResult<(NaiveDate, NaiveDate)> {
+    let current_month = Utc::now().date_naive().with_day(1).expect("first day of month is valid");
+    let period_end = current_month
+        .pred_opt()
+        .ok_or_else(|| AppError::Internal("Report period out of range".to_string()))?;
+    let period_start = current_month
+        .checked_sub_months(Months::new(months))
+        .ok_or_else(|| AppError::Internal("Report period out of range".to_string()))?;
+    Ok((period_start, period_end))
+}
+
+pub(crate) fn start_of_day(date: NaiveDate) -> chrono::DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).expect("midnight is valid").and_utc()
+}
+
+/// Group monthly inflows by currency
+fn summarize_inflows(inflows: Vec<MonthlyInflow>, months: u32) -> Vec<IncomeAnalytics> {
+    let mut analytics: Vec<IncomeAnalytics> = Vec::new();
+    for inflow in inflows {
+        let index = match analytics.iter().position(|entry| entry.currency == inflow.currency) {
+            Some(index) => index,
+            None => {
+                analytics.push(IncomeAnalytics {
+                    currency: inflow.currency.clone(),
+                    total_inflow: 0,
+                    average_monthly_inflow: 0,
+                    months_with_income: 0,
+                    monthly: Vec::new(),
+                });
+                analytics.len() - 1
+            }
+        };
+
+        let entry = &mut analytics[index];
+        entry.total_inflow += inflow.total;
+        entry.months_with_income += 1;
+        entry.monthly.push(MonthlyIncome {
+            month: inflow.month.format("%Y-%m").to_string(),
+            total: inflow.total,
+            transaction_count: inflow.transaction_count,
+        });
+    }
+
+    for entry in &mut analytics {
+        entry.average_monthly_inflow = entry.total_inflow / i64::from(months.max(1));
+    }
+    analytics
+}
+
+/// Random code such as `K7QP-2MZX-R4TD-9HWA`
+fn generate_verification_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..4)
+        .map(|_| {
+            (0..4)
+                .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Serialize JSON with object keys sorted so the signature does not depend on
+/// key order, which Postgres JSONB does not preserve
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|key| format!("{}:{}", Value::String(key.clone()), canonical_json(&map[key])))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            format!("[{}]", items.iter().map(canonical_json).collect::<Vec<_>>().join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Format minor units as a decimal amount with currency code
+fn format_amount(amount: i64, currency: &str) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    let amount = amount.unsigned_abs();
+    format!("{}{}.{:02} {}", sign, amount / 100, amount % 100, currency)
+}
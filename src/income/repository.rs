@@ -1,9 +1,32 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgPool};
 use uuid::Uuid;
+use crate::core::deadline;
 use crate::core::error::AppResult;
-use crate::shared::{traits::Repository, types::UserId};
-use super::model::{IncomeVerification, IncomeVerificationStatus};
+use crate::shared::{traits::Repository, types::{TenantId, UserId}};
+use super::model::{
+    CreditCheck, CreditReport, CreditSubject, EmployerConfirmation, IncomeDocument, IncomeReport,
+    IncomeVerification, IncomeVerificationStatus, MonthlyInflow, MonthlyOutflow,
+};
+
+const VERIFICATION_COLUMNS: &str = "id, user_id, verification_type, status, employer_name, job_title,
+    annual_income, currency, verification_data, provider, provider_reference, completed_at, created_at,
+    updated_at";
+
+const CONFIRMATION_COLUMNS: &str = "id, verification_id, contact_name, contact_email, token_hash, status,
+    employment_confirmed, confirmed_job_title, confirmed_annual_income, employer_comment, requested_by,
+    expires_at, responded_at, created_at, updated_at";
+
+const DOCUMENT_COLUMNS: &str = "id, verification_id, document_type, file_name, content_type, size_bytes,
+    storage_key, parse_status, extracted_fields, uploaded_by, created_at";
+
+const CREDIT_CHECK_COLUMNS: &str = "id, tenant_id, user_id, provider, provider_reference, status, purpose,
+    consent_reference, consent_granted_at, report, failure_reason, requested_by, completed_at, retain_until,
+    purged_at, created_at, updated_at";
+
+const REPORT_COLUMNS: &str = "id, user_id, verification_code, period_start, period_end, report_data, signature,
+    generated_by, created_at, expires_at";
 
 pub struct IncomeRepository {
     pool: PgPool,
@@ -15,9 +38,358 @@ impl IncomeRepository {
     }
 
     /// Find income verifications by user ID
-    pub async fn find_by_user_id(&self, _user_id: UserId) -> AppResult<Vec<IncomeVerification>> {
-        // TODO: Implement database query
-        Ok(Vec::new())
+    pub async fn find_by_user_id(&self, user_id: UserId) -> AppResult<Vec<IncomeVerification>> {
+        let verifications = sqlx::query_as::<_, IncomeVerification>(&format!(
+            "SELECT {VERIFICATION_COLUMNS} FROM income_verifications
+             WHERE user_id = $1 ORDER BY created_at DESC"
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(verifications)
+    }
+
+    /// Get the user's full name
+    pub async fn find_user_name(&self, user_id: UserId) -> AppResult<Option<String>> {
+        let name = sqlx::query_scalar("SELECT first_name || ' ' || last_name FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(name)
+    }
+
+    /// Sum completed inflows into the user's accounts per month and currency,
+    /// excluding transfers between the user's own accounts
+    pub async fn find_monthly_inflows(
+        &self,
+        user_id: UserId,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> AppResult<Vec<MonthlyInflow>> {
+        let inflows = sqlx::query_as::<_, MonthlyInflow>(
+            "SELECT date_trunc('month', t.created_at)::DATE AS month, t.currency,
+                    SUM(t.amount)::BIGINT AS total, COUNT(*) AS transaction_count
+             FROM transactions t
+             JOIN accounts a ON a.id = t.to_account_id
+             LEFT JOIN accounts source ON source.id = t.from_account_id
+             WHERE a.user_id = $1
+               AND t.status = 'completed'
+               AND t.transaction_type IN ('deposit', 'transfer', 'payment')
+               AND (source.user_id IS NULL OR source.user_id <> $1)
+               AND t.created_at >= $2 AND t.created_at < $3
+             GROUP BY 1, 2
+             ORDER BY 1, 2",
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(inflows)
+    }
+
+    /// Sum completed outflows from the user's accounts per month and
+    /// currency, excluding transfers between the user's own accounts. Outflows
+    /// to a payee paid in at least `recurring_months` of the months are also
+    /// summed as recurring.
+    pub async fn find_monthly_outflows(
+        &self,
+        user_id: UserId,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+        recurring_months: i64,
+    ) -> AppResult<Vec<MonthlyOutflow>> {
+        let outflows = sqlx::query_as::<_, MonthlyOutflow>(
+            "WITH outflows AS (
+                 SELECT date_trunc('month', t.created_at)::DATE AS month, t.currency, t.amount,
+                        COALESCE(t.to_account_id::TEXT, NULLIF(t.description, ''), t.transaction_type::TEXT) AS payee
+                 FROM transactions t
+                 JOIN accounts a ON a.id = t.from_account_id
+                 LEFT JOIN accounts destination ON destination.id = t.to_account_id
+                 WHERE a.user_id = $1
+                   AND t.status = 'completed'
+                   AND t.transaction_type IN ('withdrawal', 'transfer', 'payment')
+                   AND (destination.user_id IS NULL OR destination.user_id <> $1)
+                   AND t.created_at >= $2 AND t.created_at < $3
+             ),
+             recurring AS (
+                 SELECT currency, payee FROM outflows
+                 GROUP BY 1, 2
+                 HAVING COUNT(DISTINCT month) >= $4
+             )
+             SELECT o.month, o.currency, SUM(o.amount)::BIGINT AS total,
+                    COALESCE(SUM(o.amount) FILTER (WHERE r.payee IS NOT NULL), 0)::BIGINT AS recurring
+             FROM outflows o
+             LEFT JOIN recurring r ON r.currency = o.currency AND r.payee = o.payee
+             GROUP BY 1, 2
+             ORDER BY 1, 2",
+        )
+        .bind(user_id)
+        .bind(from)
+        .bind(until)
+        .bind(recurring_months)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(outflows)
+    }
+
+    /// Store a generated income report
+    pub async fn create_report(&self, report: &IncomeReport) -> AppResult<IncomeReport> {
+        let created = sqlx::query_as::<_, IncomeReport>(&format!(
+            "INSERT INTO income_reports ({REPORT_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             RETURNING {REPORT_COLUMNS}"
+        ))
+        .bind(report.id)
+        .bind(report.user_id)
+        .bind(&report.verification_code)
+        .bind(report.period_start)
+        .bind(report.period_end)
+        .bind(&report.report_data)
+        .bind(&report.signature)
+        .bind(report.generated_by)
+        .bind(report.created_at)
+        .bind(report.expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    /// Find an income report by its verification code
+    pub async fn find_report_by_code(&self, code: &str) -> AppResult<Option<IncomeReport>> {
+        let report = sqlx::query_as::<_, IncomeReport>(&format!(
+            "SELECT {REPORT_COLUMNS} FROM income_reports WHERE verification_code = $1"
+        ))
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(report)
+    }
+
+    /// Store a new employer confirmation request, cancelling any still pending
+    /// for the verification and moving the verification into progress
+    pub async fn create_employer_confirmation(
+        &self,
+        confirmation: &EmployerConfirmation,
+    ) -> AppResult<EmployerConfirmation> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        sqlx::query(
+            "UPDATE employer_confirmations SET status = 'cancelled', updated_at = NOW()
+             WHERE verification_id = $1 AND status = 'pending'",
+        )
+        .bind(confirmation.verification_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let created = sqlx::query_as::<_, EmployerConfirmation>(&format!(
+            "INSERT INTO employer_confirmations ({CONFIRMATION_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+             RETURNING {CONFIRMATION_COLUMNS}"
+        ))
+        .bind(confirmation.id)
+        .bind(confirmation.verification_id)
+        .bind(&confirmation.contact_name)
+        .bind(&confirmation.contact_email)
+        .bind(&confirmation.token_hash)
+        .bind(&confirmation.status)
+        .bind(confirmation.employment_confirmed)
+        .bind(&confirmation.confirmed_job_title)
+        .bind(confirmation.confirmed_annual_income)
+        .bind(&confirmation.employer_comment)
+        .bind(confirmation.requested_by)
+        .bind(confirmation.expires_at)
+        .bind(confirmation.responded_at)
+        .bind(confirmation.created_at)
+        .bind(confirmation.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE income_verifications SET status = 'in_progress', updated_at = NOW() WHERE id = $1",
+        )
+        .bind(confirmation.verification_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    /// Employer confirmations for a verification, newest first
+    pub async fn find_employer_confirmations(&self, verification_id: Uuid) -> AppResult<Vec<EmployerConfirmation>> {
+        let confirmations = sqlx::query_as::<_, EmployerConfirmation>(&format!(
+            "SELECT {CONFIRMATION_COLUMNS} FROM employer_confirmations
+             WHERE verification_id = $1 ORDER BY created_at DESC"
+        ))
+        .bind(verification_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(confirmations)
+    }
+
+    pub async fn find_employer_confirmation_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> AppResult<Option<EmployerConfirmation>> {
+        let confirmation = sqlx::query_as::<_, EmployerConfirmation>(&format!(
+            "SELECT {CONFIRMATION_COLUMNS} FROM employer_confirmations WHERE token_hash = $1"
+        ))
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(confirmation)
+    }
+
+    /// Record the employer's response and move the verification to its outcome.
+    /// Confirmed figures replace the applicant's stated job title and income.
+    pub async fn record_employer_response(
+        &self,
+        confirmation: &EmployerConfirmation,
+        verification_status: IncomeVerificationStatus,
+    ) -> AppResult<EmployerConfirmation> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let updated = sqlx::query_as::<_, EmployerConfirmation>(&format!(
+            "UPDATE employer_confirmations
+             SET status = $1, employment_confirmed = $2, confirmed_job_title = $3,
+                 confirmed_annual_income = $4, employer_comment = $5, responded_at = NOW(), updated_at = NOW()
+             WHERE id = $6 AND status = 'pending'
+             RETURNING {CONFIRMATION_COLUMNS}"
+        ))
+        .bind(&confirmation.status)
+        .bind(confirmation.employment_confirmed)
+        .bind(&confirmation.confirmed_job_title)
+        .bind(confirmation.confirmed_annual_income)
+        .bind(&confirmation.employer_comment)
+        .bind(confirmation.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE income_verifications
+             SET status = $1,
+                 job_title = COALESCE($2, job_title),
+                 annual_income = COALESCE($3, annual_income),
+                 completed_at = CASE WHEN $1 = 'completed'::income_verification_status THEN NOW() ELSE completed_at END,
+                 updated_at = NOW()
+             WHERE id = $4",
+        )
+        .bind(verification_status)
+        .bind(&updated.confirmed_job_title)
+        .bind(updated.confirmed_annual_income)
+        .bind(updated.verification_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    /// Expire pending employer confirmations past their deadline. Verifications
+    /// left waiting on no other confirmation are expired too. Returns the
+    /// expired confirmations.
+    pub async fn expire_employer_confirmations(&self, now: DateTime<Utc>) -> AppResult<Vec<EmployerConfirmation>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let expired = sqlx::query_as::<_, EmployerConfirmation>(&format!(
+            "UPDATE employer_confirmations SET status = 'expired', updated_at = NOW()
+             WHERE status = 'pending' AND expires_at < $1
+             RETURNING {CONFIRMATION_COLUMNS}"
+        ))
+        .bind(now)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let verification_ids: Vec<Uuid> = expired.iter().map(|confirmation| confirmation.verification_id).collect();
+        sqlx::query(
+            "UPDATE income_verifications v SET status = 'expired', updated_at = NOW()
+             WHERE v.id = ANY($1) AND v.status = 'in_progress'
+               AND NOT EXISTS (
+                   SELECT 1 FROM employer_confirmations c
+                   WHERE c.verification_id = v.id AND c.status = 'pending'
+               )",
+        )
+        .bind(&verification_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(expired)
+    }
+
+    /// Store an uploaded document. Fields detected in it are attached to the
+    /// verification for reviewer confirmation, replacing those of earlier
+    /// documents, and a pending verification moves into progress.
+    pub async fn create_document(&self, document: &IncomeDocument) -> AppResult<IncomeDocument> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let created = sqlx::query_as::<_, IncomeDocument>(&format!(
+            "INSERT INTO income_documents ({DOCUMENT_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING {DOCUMENT_COLUMNS}"
+        ))
+        .bind(document.id)
+        .bind(document.verification_id)
+        .bind(document.document_type)
+        .bind(&document.file_name)
+        .bind(&document.content_type)
+        .bind(document.size_bytes)
+        .bind(&document.storage_key)
+        .bind(document.parse_status)
+        .bind(&document.extracted_fields)
+        .bind(document.uploaded_by)
+        .bind(document.created_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(fields) = &created.extracted_fields {
+            sqlx::query(
+                "UPDATE income_verifications
+                 SET verification_data = COALESCE(verification_data, '{}'::jsonb)
+                         || jsonb_build_object('extracted_fields', $1::jsonb, 'extracted_from_document_id', $2::uuid,
+                                               'extracted_fields_confirmed', false),
+                     updated_at = NOW()
+                 WHERE id = $3",
+            )
+            .bind(fields)
+            .bind(created.id)
+            .bind(created.verification_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query(
+            "UPDATE income_verifications SET status = 'in_progress', updated_at = NOW()
+             WHERE id = $1 AND status = 'pending'",
+        )
+        .bind(created.verification_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    /// Documents uploaded for a verification, oldest first
+    pub async fn find_documents(&self, verification_id: Uuid) -> AppResult<Vec<IncomeDocument>> {
+        let documents = sqlx::query_as::<_, IncomeDocument>(&format!(
+            "SELECT {DOCUMENT_COLUMNS} FROM income_documents
+             WHERE verification_id = $1 ORDER BY created_at"
+        ))
+        .bind(verification_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(documents)
     }
 
     /// Update verification status
@@ -29,18 +401,203 @@ impl IncomeRepository {
         // TODO: Implement status update
         Ok(())
     }
+
+    /// Name and email of a tenant's user, for a credit report pull
+    pub async fn find_credit_subject(&self, user_id: UserId, tenant_id: TenantId) -> AppResult<Option<CreditSubject>> {
+        let subject = sqlx::query_as::<_, CreditSubject>(
+            "SELECT first_name, last_name, email FROM users WHERE id = $1 AND tenant_id = $2",
+        )
+        .bind(user_id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(subject)
+    }
+
+    /// Store a new credit check, unless one is already pending for the user
+    pub async fn create_credit_check(&self, check: &CreditCheck) -> AppResult<Option<CreditCheck>> {
+        let created = sqlx::query_as::<_, CreditCheck>(&format!(
+            "INSERT INTO credit_checks ({CREDIT_CHECK_COLUMNS})
+             SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17
+             WHERE NOT EXISTS (SELECT 1 FROM credit_checks WHERE user_id = $3 AND status = 'pending')
+             RETURNING {CREDIT_CHECK_COLUMNS}"
+        ))
+        .bind(check.id)
+        .bind(check.tenant_id)
+        .bind(check.user_id)
+        .bind(&check.provider)
+        .bind(&check.provider_reference)
+        .bind(&check.status)
+        .bind(&check.purpose)
+        .bind(&check.consent_reference)
+        .bind(check.consent_granted_at)
+        .bind(&check.report)
+        .bind(&check.failure_reason)
+        .bind(check.requested_by)
+        .bind(check.completed_at)
+        .bind(check.retain_until)
+        .bind(check.purged_at)
+        .bind(check.created_at)
+        .bind(check.updated_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn find_credit_check(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<CreditCheck>> {
+        let check = sqlx::query_as::<_, CreditCheck>(&format!(
+            "SELECT {CREDIT_CHECK_COLUMNS} FROM credit_checks WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(check)
+    }
+
+    /// Find a credit check by the reference the bureau gave the pull
+    pub async fn find_credit_check_by_reference(
+        &self,
+        provider: &str,
+        provider_reference: &str,
+    ) -> AppResult<Option<CreditCheck>> {
+        let check = sqlx::query_as::<_, CreditCheck>(&format!(
+            "SELECT {CREDIT_CHECK_COLUMNS} FROM credit_checks WHERE provider = $1 AND provider_reference = $2"
+        ))
+        .bind(provider)
+        .bind(provider_reference)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(check)
+    }
+
+    /// Record the bureau's reference for a pull it accepted
+    pub async fn set_credit_check_reference(&self, id: Uuid, provider_reference: &str) -> AppResult<CreditCheck> {
+        let check = sqlx::query_as::<_, CreditCheck>(&format!(
+            "UPDATE credit_checks SET provider_reference = $2, updated_at = NOW()
+             WHERE id = $1
+             RETURNING {CREDIT_CHECK_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(provider_reference)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(check)
+    }
+
+    /// Store the report of a pending check; `None` if the check was already
+    /// decided
+    pub async fn complete_credit_check(
+        &self,
+        id: Uuid,
+        report: &CreditReport,
+        retain_until: DateTime<Utc>,
+    ) -> AppResult<Option<CreditCheck>> {
+        let check = sqlx::query_as::<_, CreditCheck>(&format!(
+            "UPDATE credit_checks
+             SET status = 'completed', report = $2, completed_at = NOW(), retain_until = $3, updated_at = NOW()
+             WHERE id = $1 AND status = 'pending'
+             RETURNING {CREDIT_CHECK_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(Json(report))
+        .bind(retain_until)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(check)
+    }
+
+    /// Fail a pending check; `None` if the check was already decided
+    pub async fn fail_credit_check(&self, id: Uuid, reason: &str) -> AppResult<Option<CreditCheck>> {
+        let check = sqlx::query_as::<_, CreditCheck>(&format!(
+            "UPDATE credit_checks
+             SET status = 'failed', failure_reason = $2, completed_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = 'pending'
+             RETURNING {CREDIT_CHECK_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(reason)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(check)
+    }
+
+    /// Remove the report of one of the tenant's checks, keeping the record of
+    /// the pull
+    pub async fn purge_credit_report(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<CreditCheck>> {
+        let check = sqlx::query_as::<_, CreditCheck>(&format!(
+            "UPDATE credit_checks
+             SET report = NULL, purged_at = COALESCE(purged_at, NOW()), updated_at = NOW()
+             WHERE id = $1 AND tenant_id = $2
+             RETURNING {CREDIT_CHECK_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(check)
+    }
+
+    /// Remove reports held past their retention period
+    pub async fn purge_expired_credit_reports(&self, now: DateTime<Utc>) -> AppResult<Vec<CreditCheck>> {
+        let purged = sqlx::query_as::<_, CreditCheck>(&format!(
+            "UPDATE credit_checks SET report = NULL, purged_at = $1, updated_at = NOW()
+             WHERE report IS NOT NULL AND retain_until <= $1
+             RETURNING {CREDIT_CHECK_COLUMNS}"
+        ))
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(purged)
+    }
 }
 
 #[async_trait]
 impl Repository<IncomeVerification, Uuid> for IncomeRepository {
     async fn create(&self, verification: IncomeVerification) -> AppResult<IncomeVerification> {
-        // TODO: Implement verification creation
-        Ok(verification)
+        let created = sqlx::query_as::<_, IncomeVerification>(&format!(
+            "INSERT INTO income_verifications ({VERIFICATION_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+             RETURNING {VERIFICATION_COLUMNS}"
+        ))
+        .bind(verification.id)
+        .bind(verification.user_id)
+        .bind(&verification.verification_type)
+        .bind(&verification.status)
+        .bind(&verification.employer_name)
+        .bind(&verification.job_title)
+        .bind(verification.annual_income)
+        .bind(&verification.currency)
+        .bind(&verification.verification_data)
+        .bind(&verification.provider)
+        .bind(&verification.provider_reference)
+        .bind(verification.completed_at)
+        .bind(verification.created_at)
+        .bind(verification.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
     }
 
-    async fn find_by_id(&self, _id: Uuid) -> AppResult<Option<IncomeVerification>> {
-        // TODO: Implement find by ID
-        Ok(None)
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<IncomeVerification>> {
+        let verification = sqlx::query_as::<_, IncomeVerification>(&format!(
+            "SELECT {VERIFICATION_COLUMNS} FROM income_verifications WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(verification)
     }
 
     async fn update(&self, _id: Uuid, verification: IncomeVerification) -> AppResult<IncomeVerification> {
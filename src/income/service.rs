@@ -1,38 +1,67 @@
+use std::sync::Arc;
 use uuid::Uuid;
 use chrono::Utc;
+use sqlx::types::Json;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
 use crate::core::error::{AppError, AppResult};
+use crate::core::storage::Storage;
+use crate::reviews::{model::VerificationKind, service::ReviewQueue};
+use crate::shared::constants::{MAX_INCOME_DOCUMENT_SIZE, SUPPORTED_INCOME_DOCUMENT_TYPES};
 use crate::shared::{traits::Repository, types::UserId};
 use super::model::{
-    IncomeVerification, IncomeVerificationRequest, IncomeVerificationResponse, IncomeVerificationStatus
+    DocumentParseStatus, DocumentUpload, IncomeDocument, IncomeDocumentResponse, IncomeDocumentType, IncomeVerification,
+    IncomeVerificationRequest, IncomeVerificationResponse, IncomeVerificationStatus,
 };
+use super::parser;
 use super::repository::IncomeRepository;
 
 pub struct IncomeService {
     repository: IncomeRepository,
+    storage: Arc<dyn Storage>,
+    review_queue: ReviewQueue,
+    audit_logger: AuditLogger,
 }
 
 impl IncomeService {
-    pub fn new(repository: IncomeRepository) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: IncomeRepository,
+        storage: Arc<dyn Storage>,
+        review_queue: ReviewQueue,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            storage,
+            review_queue,
+            audit_logger,
+        }
     }
 
-    /// Initiate income verification
+    /// Initiate income verification, storing and parsing any documents
+    /// uploaded with the request
     pub async fn initiate_verification(
         &self,
-        user_id: UserId,
         request: IncomeVerificationRequest,
+        uploads: Vec<DocumentUpload>,
+        actor_id: Uuid,
     ) -> AppResult<IncomeVerificationResponse> {
-        // TODO: Implement income verification initiation logic
+        for upload in &uploads {
+            check_upload(upload)?;
+        }
+        if self.repository.find_user_name(request.user_id).await?.is_none() {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+
         let now = Utc::now();
         let verification = IncomeVerification {
             id: Uuid::new_v4(),
-            user_id,
+            user_id: request.user_id,
             verification_type: request.verification_type,
             status: IncomeVerificationStatus::Pending,
-            employer_name: Some(request.employer_name),
-            job_title: Some(request.job_title),
-            annual_income: Some(request.expected_annual_income),
-            currency: request.currency,
+            employer_name: request.employer_name,
+            job_title: request.job_title,
+            annual_income: request.expected_annual_income,
+            currency: request.currency.to_uppercase(),
             verification_data: request.additional_data,
             provider: None,
             provider_reference: None,
@@ -40,17 +69,65 @@ impl IncomeService {
             created_at: now,
             updated_at: now,
         };
-
         let created_verification = self.repository.create(verification).await?;
-        Ok(IncomeVerificationResponse::from(created_verification))
+
+        let event = AuditEvent::new(AuditEventType::IncomeVerificationInitiated)
+            .user_id(actor_id)
+            .resource(format!("income_verification:{}", created_verification.id))
+            .action("initiate_income_verification".to_string())
+            .metadata("subject_user_id".to_string(), serde_json::json!(created_verification.user_id))
+            .metadata("document_count".to_string(), serde_json::json!(uploads.len()))
+            .compliance_tag("INCOME".to_string());
+        self.audit_logger.log(event).await;
+
+        for upload in uploads {
+            self.store_document(&created_verification, request.document_type, upload, actor_id)
+                .await?;
+        }
+
+        self.get_verification_status(created_verification.id).await
     }
 
-    /// Get verification status
+    /// Add documents to an undecided verification
+    pub async fn add_documents(
+        &self,
+        verification_id: Uuid,
+        document_type: IncomeDocumentType,
+        uploads: Vec<DocumentUpload>,
+        actor_id: Uuid,
+    ) -> AppResult<Vec<IncomeDocumentResponse>> {
+        if uploads.is_empty() {
+            return Err(AppError::Validation("At least one document is required".to_string()));
+        }
+        for upload in &uploads {
+            check_upload(upload)?;
+        }
+
+        let verification = self.find_verification(verification_id).await?;
+        if matches!(
+            verification.status,
+            IncomeVerificationStatus::Completed | IncomeVerificationStatus::Failed
+        ) {
+            return Err(AppError::BadRequest(
+                "Income verification has already been decided".to_string(),
+            ));
+        }
+
+        let mut documents = Vec::with_capacity(uploads.len());
+        for upload in uploads {
+            documents.push(self.store_document(&verification, document_type, upload, actor_id).await?);
+        }
+        Ok(documents)
+    }
+
+    /// Get verification status, with its documents and the fields detected in them
     pub async fn get_verification_status(&self, verification_id: Uuid) -> AppResult<IncomeVerificationResponse> {
-        let verification = self.repository.find_by_id(verification_id).await?
-            .ok_or_else(|| AppError::NotFound("Income verification not found".to_string()))?;
+        let verification = self.find_verification(verification_id).await?;
+        let documents = self.repository.find_documents(verification_id).await?;
 
-        Ok(IncomeVerificationResponse::from(verification))
+        let mut response = IncomeVerificationResponse::from(verification);
+        response.documents = documents.into_iter().map(IncomeDocumentResponse::from).collect();
+        Ok(response)
     }
 
     /// Get verifications for user
@@ -58,4 +135,100 @@ impl IncomeService {
         let verifications = self.repository.find_by_user_id(user_id).await?;
         Ok(verifications.into_iter().map(IncomeVerificationResponse::from).collect())
     }
-}
\ No newline at end of file
+
+    /// Store a document, run the parsing pipeline over it and record the
+    /// result against the verification. Fields read from documents are only
+    /// trusted once a reviewer confirms them, so the verification is queued
+    /// for review.
+    async fn store_document(
+        &self,
+        verification: &IncomeVerification,
+        document_type: IncomeDocumentType,
+        upload: DocumentUpload,
+        actor_id: Uuid,
+    ) -> AppResult<IncomeDocumentResponse> {
+        let document_id = Uuid::new_v4();
+        let storage_key = format!("income/{}/{}", verification.id, document_id);
+        let size_bytes = upload.content.len() as i64;
+
+        let content_type = upload.content_type.clone();
+        let content = upload.content;
+        let (parse_status, extracted_fields, content) = tokio::task::spawn_blocking(move || {
+            let (status, fields) = parser::parse_document(&content_type, &content);
+            (status, fields, content)
+        })
+        .await
+        .map_err(|e| AppError::Internal(format!("Document parsing failed: {}", e)))?;
+
+        self.storage.put(&storage_key, content).await?;
+
+        let document = IncomeDocument {
+            id: document_id,
+            verification_id: verification.id,
+            document_type,
+            file_name: upload.file_name,
+            content_type: upload.content_type,
+            size_bytes,
+            storage_key,
+            parse_status,
+            extracted_fields: extracted_fields.map(Json),
+            uploaded_by: actor_id,
+            created_at: Utc::now(),
+        };
+        let created = self.repository.create_document(&document).await?;
+
+        let event = AuditEvent::new(AuditEventType::IncomeDocumentUploaded)
+            .user_id(actor_id)
+            .resource(format!("income_verification:{}", verification.id))
+            .action("upload_income_document".to_string())
+            .metadata("document_id".to_string(), serde_json::json!(created.id))
+            .metadata("document_type".to_string(), serde_json::json!(created.document_type))
+            .metadata("parse_status".to_string(), serde_json::json!(created.parse_status))
+            .metadata("size_bytes".to_string(), serde_json::json!(created.size_bytes))
+            .compliance_tag("INCOME".to_string());
+        self.audit_logger.log(event).await;
+
+        let reason = match created.parse_status {
+            DocumentParseStatus::NoText => "Uploaded document has no readable text and needs manual review",
+            _ => "Fields extracted from uploaded documents await reviewer confirmation",
+        };
+        self.review_queue
+            .flag(
+                VerificationKind::Income,
+                verification.id,
+                verification.user_id,
+                reason.to_string(),
+                None,
+            )
+            .await?;
+
+        Ok(IncomeDocumentResponse::from(created))
+    }
+
+    async fn find_verification(&self, verification_id: Uuid) -> AppResult<IncomeVerification> {
+        self.repository
+            .find_by_id(verification_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Income verification not found".to_string()))
+    }
+}
+
+fn check_upload(upload: &DocumentUpload) -> AppResult<()> {
+    if !SUPPORTED_INCOME_DOCUMENT_TYPES.contains(&upload.content_type.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Unsupported document type '{}'. Supported types: {}",
+            upload.content_type,
+            SUPPORTED_INCOME_DOCUMENT_TYPES.join(", ")
+        )));
+    }
+    if upload.content.is_empty() {
+        return Err(AppError::Validation(format!("Document '{}' is empty", upload.file_name)));
+    }
+    if upload.content.len() > MAX_INCOME_DOCUMENT_SIZE {
+        return Err(AppError::Validation(format!(
+            "Document exceeds the maximum size of {} bytes",
+            MAX_INCOME_DOCUMENT_SIZE
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::AccountId;
+use super::model::{AccruedInterestResponse, CreateInterestRateRequest, InterestRate, InterestRateFilter};
+use super::repository::InterestRepository;
+use super::service::InterestService;
+
+pub(crate) fn interest_service(state: &AppState) -> InterestService {
+    InterestService::new(
+        InterestRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+        state.event_bus.clone(),
+    )
+}
+
+/// Get interest accrued on an account since its last capitalization
+#[utoipa::path(
+    get,
+    path = "/api/v1/interest/accounts/{account_id}/accrued",
+    tag = "interest",
+    params(("account_id" = Uuid, Path, description = "Account ID")),
+    responses(
+        (status = 200, description = "Interest accrued since the last capitalization", body = AccruedInterestResponse),
+        (status = 404, description = "Account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_accrued_interest(
+    State(state): State<AppState>,
+    JwtToken(_claims): JwtToken,
+    Path(account_id): Path<AccountId>,
+) -> AppResult<Json<ApiResponse<AccruedInterestResponse>>> {
+    let accrued = interest_service(&state).get_accrued_interest(account_id).await?;
+    Ok(Json(ApiResponse::success("Accrued interest retrieved successfully", accrued)))
+}
+
+/// List interest rates (admin)
+#[utoipa::path(
+    get,
+    path = "/api/v1/interest/rates",
+    tag = "interest",
+    params(InterestRateFilter),
+    responses(
+        (status = 200, description = "Interest rates", body = [InterestRate]),
+        (status = 403, description = "Caller lacks the interest rate management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_interest_rates(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(filter): Query<InterestRateFilter>,
+) -> AppResult<Json<ApiResponse<Vec<InterestRate>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_interest_rates(), ip, "interest_rates".to_string())
+        .await?;
+
+    let rates = interest_service(&state).list_rates(filter).await?;
+    Ok(Json(ApiResponse::success("Interest rates retrieved successfully", rates)))
+}
+
+/// Set an interest rate for an account type (admin)
+#[utoipa::path(
+    post,
+    path = "/api/v1/interest/rates",
+    tag = "interest",
+    request_body = CreateInterestRateRequest,
+    responses(
+        (status = 201, description = "Interest rate created", body = InterestRate),
+        (status = 400, description = "Invalid rate or effective date in the past"),
+        (status = 403, description = "Caller lacks the interest rate management permission"),
+        (status = 409, description = "A rate already takes effect on that date")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_interest_rate(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<CreateInterestRateRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<InterestRate>>)> {
+    state
+        .authorize(claims.developer_id, permissions::manage_interest_rates(), ip, "interest_rates".to_string())
+        .await?;
+
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let rate = interest_service(&state)
+        .create_rate(request, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Interest rate created successfully", rate)),
+    ))
+}
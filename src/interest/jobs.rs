@@ -0,0 +1,48 @@
+use chrono::Utc;
+use crate::core::error::AppResult;
+use crate::core::AppState;
+use super::controller::interest_service;
+
+const INTEREST_ACCRUAL_JOB: &str = "interest_accrual";
+
+/// Periodically accrue interest for completed days and capitalize the
+/// previous month's accruals once it has ended. Both steps are idempotent,
+/// so the job can run more often than daily.
+pub fn spawn_interest_accrual_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.interest_accrual_check_interval_seconds);
+    state.job_monitor.register(INTEREST_ACCRUAL_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match run_accrual(&state).await {
+                Ok((accrued, capitalized)) => {
+                    state.job_monitor.record_success(INTEREST_ACCRUAL_JOB);
+                    if accrued > 0 || capitalized > 0 {
+                        tracing::info!(
+                            "Posted {} interest accruals and {} capitalizations",
+                            accrued,
+                            capitalized
+                        );
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(INTEREST_ACCRUAL_JOB, e.to_string());
+                    tracing::error!("Interest accrual job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+async fn run_accrual(state: &AppState) -> AppResult<(u64, usize)> {
+    let service = interest_service(state);
+    let today = Utc::now().date_naive();
+
+    let accrued = service
+        .accrue_completed_days(today, state.config.interest_accrual_max_catch_up_days)
+        .await?;
+    let capitalized = service.capitalize_previous_months(today).await?;
+    Ok((accrued, capitalized))
+}
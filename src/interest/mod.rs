@@ -0,0 +1,17 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::get, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/rates",
+            get(controller::list_interest_rates).post(controller::create_interest_rate),
+        )
+        .route("/accounts/:account_id/accrued", get(controller::get_accrued_interest))
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::{AccountId, Amount, Currency, TransactionId};
+
+/// Accruals are kept in millionths of a minor unit so that small daily
+/// amounts are not lost to rounding
+pub const MICROS_PER_MINOR_UNIT: i64 = 1_000_000;
+
+/// Interest rate for an account type, effective from a date
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct InterestRate {
+    pub id: Uuid,
+    pub account_type: String,
+    pub currency: Currency,
+    pub annual_rate_bps: i64,
+    pub min_balance: Amount,
+    pub effective_from: NaiveDate,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Accrued interest paid into an account for a period
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct InterestCapitalization {
+    pub id: Uuid,
+    pub account_id: AccountId,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub accrued_micros: i64,
+    pub amount: Amount,
+    pub carried_micros: i64,
+    pub currency: Currency,
+    pub transaction_id: Option<TransactionId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Uncapitalized interest on an account, as summed by the repository
+#[derive(Debug, Clone, FromRow)]
+pub struct AccruedInterestTotals {
+    pub accrued_micros: i64,
+    pub first_accrual_date: Option<NaiveDate>,
+    pub last_accrual_date: Option<NaiveDate>,
+}
+
+/// Days in the year containing `date`, for actual/actual daily rates
+pub fn days_in_year(date: NaiveDate) -> i64 {
+    if NaiveDate::from_ymd_opt(date.year(), 2, 29).is_some() {
+        366
+    } else {
+        365
+    }
+}
+
+/// First day of the month containing `date`
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// Query parameters for listing interest rates
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct InterestRateFilter {
+    pub account_type: Option<String>,
+    pub currency: Option<Currency>,
+}
+
+/// Create interest rate request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateInterestRateRequest {
+    #[validate(length(min = 1, max = 50))]
+    pub account_type: String,
+    #[validate(length(equal = 3))]
+    pub currency: Currency,
+    #[validate(range(min = 0, max = 10000))]
+    pub annual_rate_bps: i64,
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub min_balance: Amount,
+    /// Defaults to today; a rate cannot take effect in the past
+    pub effective_from: Option<NaiveDate>,
+}
+
+/// Interest accrued on an account since its last capitalization
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccruedInterestResponse {
+    pub account_id: AccountId,
+    pub currency: Currency,
+    /// Rate that applies to the account today, if any
+    pub annual_rate_bps: Option<i64>,
+    /// Accrued interest in minor units, rounded down
+    pub accrued_amount: Amount,
+    /// Accrued interest in millionths of a minor unit, including the carry from the last capitalization
+    pub accrued_micros: i64,
+    pub accrued_from: Option<NaiveDate>,
+    pub accrued_to: Option<NaiveDate>,
+    pub last_capitalization: Option<InterestCapitalization>,
+}
@@ -0,0 +1,353 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::core::events::BalanceSnapshot;
+use crate::general_ledger::model::PostingEvent;
+use crate::general_ledger::repository::{post_journal, JournalRequest};
+use crate::shared::constants::TRANSACTION_REF_PREFIX;
+use crate::shared::types::{AccountId, Amount, Currency};
+use crate::transactions::model::{TransactionStatus, TransactionType};
+use super::model::{
+    AccruedInterestTotals, InterestCapitalization, InterestRate, InterestRateFilter, MICROS_PER_MINOR_UNIT,
+};
+
+const RATE_COLUMNS: &str =
+    "id, account_type, currency, annual_rate_bps, min_balance, effective_from, created_by, created_at";
+
+const CAPITALIZATION_COLUMNS: &str = "id, account_id, period_start, period_end, accrued_micros, amount,
+    carried_micros, currency, transaction_id, created_at";
+
+pub struct InterestRepository {
+    pool: PgPool,
+}
+
+impl InterestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_rate(&self, rate: &InterestRate) -> AppResult<InterestRate> {
+        let created = sqlx::query_as::<_, InterestRate>(&format!(
+            "INSERT INTO interest_rates ({RATE_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {RATE_COLUMNS}"
+        ))
+        .bind(rate.id)
+        .bind(&rate.account_type)
+        .bind(&rate.currency)
+        .bind(rate.annual_rate_bps)
+        .bind(rate.min_balance)
+        .bind(rate.effective_from)
+        .bind(rate.created_by)
+        .bind(rate.created_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn rate_exists(&self, account_type: &str, currency: &str, effective_from: NaiveDate) -> AppResult<bool> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(
+                 SELECT 1 FROM interest_rates
+                 WHERE account_type = $1 AND currency = $2 AND effective_from = $3
+             )",
+        )
+        .bind(account_type)
+        .bind(currency)
+        .bind(effective_from)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    pub async fn list_rates(&self, filter: &InterestRateFilter) -> AppResult<Vec<InterestRate>> {
+        let rates = sqlx::query_as::<_, InterestRate>(&format!(
+            "SELECT {RATE_COLUMNS} FROM interest_rates
+             WHERE ($1::VARCHAR IS NULL OR account_type = $1) AND ($2::VARCHAR IS NULL OR currency = $2)
+             ORDER BY account_type, currency, effective_from DESC"
+        ))
+        .bind(&filter.account_type)
+        .bind(&filter.currency)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rates)
+    }
+
+    /// Currency of an account's balance, if the account exists
+    pub async fn find_account_currency(&self, account_id: AccountId) -> AppResult<Option<Currency>> {
+        let currency = sqlx::query_scalar::<_, Currency>(
+            "SELECT COALESCE(b.currency, a.currency, 'USD')
+             FROM accounts a LEFT JOIN balances b ON b.account_id = a.id
+             WHERE a.id = $1",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(currency)
+    }
+
+    /// The rate that applies to an account on a given day
+    pub async fn find_rate_for_account(&self, account_id: AccountId, date: NaiveDate) -> AppResult<Option<InterestRate>> {
+        let rate = sqlx::query_as::<_, InterestRate>(&format!(
+            "SELECT {RATE_COLUMNS} FROM interest_rates
+             WHERE (account_type, currency) = (
+                 SELECT a.account_type, COALESCE(b.currency, a.currency, 'USD')
+                 FROM accounts a LEFT JOIN balances b ON b.account_id = a.id
+                 WHERE a.id = $1
+             )
+               AND effective_from <= $2
+             ORDER BY effective_from DESC
+             LIMIT 1"
+        ))
+        .bind(account_id)
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rate)
+    }
+
+    pub async fn find_latest_accrual_date(&self) -> AppResult<Option<NaiveDate>> {
+        let date = sqlx::query_scalar::<_, Option<NaiveDate>>("SELECT MAX(accrual_date) FROM interest_accruals")
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(date)
+    }
+
+    /// Post a day's accrual for every active account with a positive rate and
+    /// a ledger balance at or above the rate's minimum. Accounts already
+    /// accrued for the day are skipped.
+    pub async fn accrue_for_date(&self, accrual_date: NaiveDate, days_in_year: i64) -> AppResult<u64> {
+        let result = sqlx::query(
+            "INSERT INTO interest_accruals (id, account_id, accrual_date, balance, annual_rate_bps, amount_micros, currency)
+             SELECT gen_random_uuid(), a.id, $1, b.ledger_balance, r.annual_rate_bps,
+                    FLOOR(b.ledger_balance::NUMERIC * r.annual_rate_bps * $3 / (10000 * $2))::BIGINT,
+                    b.currency
+             FROM accounts a
+             JOIN balances b ON b.account_id = a.id
+             JOIN LATERAL (
+                 SELECT annual_rate_bps, min_balance FROM interest_rates
+                 WHERE account_type = a.account_type AND currency = b.currency AND effective_from <= $1
+                 ORDER BY effective_from DESC
+                 LIMIT 1
+             ) r ON TRUE
+             WHERE a.is_active
+               AND r.annual_rate_bps > 0
+               AND b.ledger_balance > 0
+               AND b.ledger_balance >= r.min_balance
+             ON CONFLICT (account_id, accrual_date) DO NOTHING",
+        )
+        .bind(accrual_date)
+        .bind(days_in_year)
+        .bind(MICROS_PER_MINOR_UNIT)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Accounts with uncapitalized accruals on or before `period_end`
+    pub async fn find_accounts_due_capitalization(&self, period_end: NaiveDate) -> AppResult<Vec<AccountId>> {
+        let accounts = sqlx::query_scalar::<_, AccountId>(
+            "SELECT DISTINCT account_id FROM interest_accruals
+             WHERE capitalization_id IS NULL AND accrual_date <= $1",
+        )
+        .bind(period_end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    /// Pay an account's accruals up to `period_end` into its balance. Whole
+    /// minor units are credited as an interest transaction and the remainder
+    /// is carried to the next capitalization. Returns `None` when there is
+    /// nothing left to capitalize.
+    pub async fn capitalize(
+        &self,
+        account_id: AccountId,
+        period_end: NaiveDate,
+    ) -> AppResult<Option<InterestCapitalization>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        // Lock the balance so concurrent runs capitalize each period once
+        let balance = sqlx::query_as::<_, (Amount, Currency)>(
+            "SELECT ledger_balance, currency FROM balances WHERE account_id = $1 FOR UPDATE",
+        )
+        .bind(account_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some((ledger_balance, currency)) = balance else {
+            return Ok(None);
+        };
+
+        let totals = sqlx::query_as::<_, AccruedInterestTotals>(
+            "SELECT COALESCE(SUM(amount_micros), 0)::BIGINT AS accrued_micros,
+                    MIN(accrual_date) AS first_accrual_date,
+                    MAX(accrual_date) AS last_accrual_date
+             FROM interest_accruals
+             WHERE account_id = $1 AND capitalization_id IS NULL AND accrual_date <= $2",
+        )
+        .bind(account_id)
+        .bind(period_end)
+        .fetch_one(&mut *tx)
+        .await?;
+        let Some(period_start) = totals.first_accrual_date else {
+            return Ok(None);
+        };
+
+        let previous_carry = sqlx::query_scalar::<_, i64>(
+            "SELECT carried_micros FROM interest_capitalizations
+             WHERE account_id = $1 ORDER BY period_end DESC LIMIT 1",
+        )
+        .bind(account_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .unwrap_or(0);
+
+        let accrued_micros = totals.accrued_micros + previous_carry;
+        let amount = accrued_micros / MICROS_PER_MINOR_UNIT;
+        let carried_micros = accrued_micros % MICROS_PER_MINOR_UNIT;
+
+        let transaction_id = if amount > 0 {
+            let transaction_id = Uuid::new_v4();
+            let description = format!("Interest {} to {}", period_start, period_end);
+
+            sqlx::query(
+                "INSERT INTO transactions (id, to_account_id, amount, currency, transaction_type, status, reference, description, tenant_id)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, (SELECT tenant_id FROM accounts WHERE id = $2))",
+            )
+            .bind(transaction_id)
+            .bind(account_id)
+            .bind(amount)
+            .bind(&currency)
+            .bind(TransactionType::Interest)
+            .bind(TransactionStatus::Completed)
+            .bind(format!("{}_{}", TRANSACTION_REF_PREFIX, transaction_id))
+            .bind(&description)
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                "UPDATE balances
+                 SET available_balance = available_balance + $1,
+                     ledger_balance = ledger_balance + $1,
+                     updated_at = NOW()
+                 WHERE account_id = $2",
+            )
+            .bind(amount)
+            .bind(account_id)
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                "INSERT INTO balance_history (account_id, balance_before, balance_after, amount_changed, transaction_id, description)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(account_id)
+            .bind(ledger_balance)
+            .bind(ledger_balance + amount)
+            .bind(amount)
+            .bind(transaction_id)
+            .bind(&description)
+            .execute(&mut *tx)
+            .await?;
+
+            post_journal(
+                &mut tx,
+                JournalRequest {
+                    event: PostingEvent::InterestCapitalized,
+                    fee_code: None,
+                    amount,
+                    currency: &currency,
+                    source_id: transaction_id,
+                    description,
+                },
+            )
+            .await?;
+
+            Some(transaction_id)
+        } else {
+            None
+        };
+
+        let capitalization = sqlx::query_as::<_, InterestCapitalization>(&format!(
+            "INSERT INTO interest_capitalizations ({CAPITALIZATION_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
+             RETURNING {CAPITALIZATION_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(account_id)
+        .bind(period_start)
+        .bind(period_end)
+        .bind(accrued_micros)
+        .bind(amount)
+        .bind(carried_micros)
+        .bind(&currency)
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE interest_accruals SET capitalization_id = $1
+             WHERE account_id = $2 AND capitalization_id IS NULL AND accrual_date <= $3",
+        )
+        .bind(capitalization.id)
+        .bind(account_id)
+        .bind(period_end)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(capitalization))
+    }
+
+    /// Accruals on an account that have not been capitalized yet
+    pub async fn find_uncapitalized_totals(&self, account_id: AccountId) -> AppResult<AccruedInterestTotals> {
+        let totals = sqlx::query_as::<_, AccruedInterestTotals>(
+            "SELECT COALESCE(SUM(amount_micros), 0)::BIGINT AS accrued_micros,
+                    MIN(accrual_date) AS first_accrual_date,
+                    MAX(accrual_date) AS last_accrual_date
+             FROM interest_accruals
+             WHERE account_id = $1 AND capitalization_id IS NULL",
+        )
+        .bind(account_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    pub async fn find_last_capitalization(&self, account_id: AccountId) -> AppResult<Option<InterestCapitalization>> {
+        let capitalization = sqlx::query_as::<_, InterestCapitalization>(&format!(
+            "SELECT {CAPITALIZATION_COLUMNS} FROM interest_capitalizations
+             WHERE account_id = $1 ORDER BY period_end DESC LIMIT 1"
+        ))
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(capitalization)
+    }
+
+    /// An account's tenant and current balance
+    pub async fn find_balance_snapshot(&self, account_id: AccountId) -> AppResult<Option<BalanceSnapshot>> {
+        let snapshot = sqlx::query_as::<_, BalanceSnapshot>(
+            "SELECT a.tenant_id, b.account_id, b.available_balance, b.ledger_balance, b.currency, b.updated_at
+             FROM balances b JOIN accounts a ON a.id = b.account_id
+             WHERE b.account_id = $1",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+}
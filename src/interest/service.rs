@@ -0,0 +1,194 @@
+use chrono::{Duration, NaiveDate, Utc};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::core::events::{DomainEvent, DomainEventType, EventBus};
+use crate::shared::types::{AccountId, TransactionId};
+use crate::transactions::model::{TransactionStatus, TransactionType};
+use super::model::{
+    days_in_year, month_start, AccruedInterestResponse, CreateInterestRateRequest, InterestCapitalization, InterestRate,
+    InterestRateFilter, MICROS_PER_MINOR_UNIT,
+};
+use super::repository::InterestRepository;
+
+pub struct InterestService {
+    repository: InterestRepository,
+    audit_logger: AuditLogger,
+    event_bus: EventBus,
+}
+
+impl InterestService {
+    pub fn new(repository: InterestRepository, audit_logger: AuditLogger, event_bus: EventBus) -> Self {
+        Self {
+            repository,
+            audit_logger,
+            event_bus,
+        }
+    }
+
+    /// List configured interest rates, newest first per account type
+    pub async fn list_rates(&self, filter: InterestRateFilter) -> AppResult<Vec<InterestRate>> {
+        self.repository.list_rates(&filter).await
+    }
+
+    /// Set the rate for an account type from a date. Earlier rates stay in
+    /// place for the days before it.
+    pub async fn create_rate(&self, request: CreateInterestRateRequest, actor_id: Uuid) -> AppResult<InterestRate> {
+        let today = Utc::now().date_naive();
+        let effective_from = request.effective_from.unwrap_or(today);
+        if effective_from < today {
+            return Err(AppError::Validation("effective_from cannot be in the past".to_string()));
+        }
+
+        let account_type = request.account_type.trim().to_lowercase();
+        let currency = request.currency.to_uppercase();
+        if self
+            .repository
+            .rate_exists(&account_type, &currency, effective_from)
+            .await?
+        {
+            return Err(AppError::Conflict(
+                "A rate for this account type and currency already takes effect on that date".to_string(),
+            ));
+        }
+
+        let rate = InterestRate {
+            id: Uuid::new_v4(),
+            account_type,
+            currency,
+            annual_rate_bps: request.annual_rate_bps,
+            min_balance: request.min_balance,
+            effective_from,
+            created_by: Some(actor_id),
+            created_at: Utc::now(),
+        };
+        let rate = self.repository.create_rate(&rate).await?;
+
+        let event = AuditEvent::new(AuditEventType::InterestRateChanged)
+            .user_id(actor_id)
+            .resource(format!("interest_rate:{}", rate.id))
+            .action("create".to_string())
+            .metadata("account_type".to_string(), serde_json::json!(rate.account_type))
+            .metadata("annual_rate_bps".to_string(), serde_json::json!(rate.annual_rate_bps))
+            .metadata("effective_from".to_string(), serde_json::json!(rate.effective_from))
+            .compliance_tag("INTEREST".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(rate)
+    }
+
+    /// Interest accrued on an account since its last capitalization
+    pub async fn get_accrued_interest(&self, account_id: AccountId) -> AppResult<AccruedInterestResponse> {
+        let currency = self
+            .repository
+            .find_account_currency(account_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found".to_string()))?;
+
+        let rate = self
+            .repository
+            .find_rate_for_account(account_id, Utc::now().date_naive())
+            .await?;
+        let totals = self.repository.find_uncapitalized_totals(account_id).await?;
+        let last_capitalization = self.repository.find_last_capitalization(account_id).await?;
+
+        let accrued_micros = totals.accrued_micros
+            + last_capitalization
+                .as_ref()
+                .map_or(0, |capitalization| capitalization.carried_micros);
+
+        Ok(AccruedInterestResponse {
+            account_id,
+            currency,
+            annual_rate_bps: rate.map(|rate| rate.annual_rate_bps),
+            accrued_amount: accrued_micros / MICROS_PER_MINOR_UNIT,
+            accrued_micros,
+            accrued_from: totals.first_accrual_date,
+            accrued_to: totals.last_accrual_date,
+            last_capitalization,
+        })
+    }
+
+    /// Accrue interest for each completed day not yet accrued, going back at
+    /// most `max_catch_up_days`. Accruals use the ledger balance at the time
+    /// of the run.
+    pub async fn accrue_completed_days(&self, today: NaiveDate, max_catch_up_days: i64) -> AppResult<u64> {
+        let yesterday = today - Duration::days(1);
+        let earliest = today - Duration::days(max_catch_up_days.max(1));
+        let mut date = self
+            .repository
+            .find_latest_accrual_date()
+            .await?
+            .map_or(yesterday, |latest| latest + Duration::days(1))
+            .max(earliest);
+
+        let mut posted = 0;
+        while date <= yesterday {
+            posted += self
+                .repository
+                .accrue_for_date(date, days_in_year(date))
+                .await?;
+            date += Duration::days(1);
+        }
+        Ok(posted)
+    }
+
+    /// Capitalize accruals from months before the current one
+    pub async fn capitalize_previous_months(&self, today: NaiveDate) -> AppResult<usize> {
+        let period_end = month_start(today) - Duration::days(1);
+        let accounts = self
+            .repository
+            .find_accounts_due_capitalization(period_end)
+            .await?;
+
+        let mut capitalized = 0;
+        for account_id in accounts {
+            let Some(capitalization) = self.repository.capitalize(account_id, period_end).await? else {
+                continue;
+            };
+            capitalized += 1;
+
+            let event = AuditEvent::new(AuditEventType::InterestCapitalized)
+                .resource(format!("account:{}", account_id))
+                .action("capitalize".to_string())
+                .metadata("amount".to_string(), serde_json::json!(capitalization.amount))
+                .metadata("period_start".to_string(), serde_json::json!(capitalization.period_start))
+                .metadata("period_end".to_string(), serde_json::json!(capitalization.period_end))
+                .metadata("transaction_id".to_string(), serde_json::json!(capitalization.transaction_id))
+                .compliance_tag("INTEREST".to_string());
+            self.audit_logger.log(event).await;
+
+            if let Some(transaction_id) = capitalization.transaction_id {
+                self.publish_credit(transaction_id, &capitalization).await?;
+            }
+        }
+        Ok(capitalized)
+    }
+
+    /// Tell real-time subscribers about the interest transaction and the new balance
+    async fn publish_credit(&self, transaction_id: TransactionId, capitalization: &InterestCapitalization) -> AppResult<()> {
+        let Some(snapshot) = self
+            .repository
+            .find_balance_snapshot(capitalization.account_id)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        self.event_bus.publish(DomainEvent::new(
+            DomainEventType::TransactionCreated,
+            snapshot.tenant_id,
+            vec![capitalization.account_id],
+            serde_json::json!({
+                "id": transaction_id,
+                "to_account_id": capitalization.account_id,
+                "amount": capitalization.amount,
+                "currency": capitalization.currency,
+                "transaction_type": TransactionType::Interest,
+                "status": TransactionStatus::Completed,
+            }),
+        ));
+        self.event_bus.publish(snapshot.into());
+        Ok(())
+    }
+}
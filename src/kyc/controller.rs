@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use crate::auth::middleware::JwtToken;
+use crate::core::{error::AppResult, response::ApiResponse, AppState};
+use crate::shared::types::UserId;
+use super::model::{KycLimits, KycTierResponse};
+use super::repository::KycRepository;
+use super::service::KycPolicyService;
+
+fn kyc_policy_service(state: &AppState) -> KycPolicyService {
+    KycPolicyService::new(
+        KycRepository::new(state.postgres.clone()),
+        KycLimits::from_config(&state.config),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Get a user's KYC tier and limits
+#[utoipa::path(
+    get,
+    path = "/api/v1/kyc/users/{user_id}/tier",
+    tag = "kyc",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "KYC tier and limits", body = KycTierResponse),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_user_tier(
+    State(state): State<AppState>,
+    _token: JwtToken,
+    Path(user_id): Path<UserId>,
+) -> AppResult<Json<ApiResponse<KycTierResponse>>> {
+    let tier = kyc_policy_service(&state).get_user_tier(user_id).await?;
+    Ok(Json(ApiResponse::success("KYC tier retrieved successfully", tier)))
+}
+
+/// Recompute a user's KYC tier from their verification outcomes
+#[utoipa::path(
+    post,
+    path = "/api/v1/kyc/users/{user_id}/tier/refresh",
+    tag = "kyc",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "KYC tier recomputed", body = KycTierResponse),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn refresh_user_tier(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(user_id): Path<UserId>,
+) -> AppResult<Json<ApiResponse<KycTierResponse>>> {
+    let tier = kyc_policy_service(&state)
+        .refresh_user_tier(user_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("KYC tier refreshed successfully", tier)))
+}
@@ -0,0 +1,13 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/users/:user_id/tier", get(controller::get_user_tier))
+        .route("/users/:user_id/tier/refresh", post(controller::refresh_user_tier))
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::core::config::Config;
+use crate::shared::types::{Amount, UserId};
+
+/// KYC tier derived from a user's verification outcomes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "kyc_tier", rename_all = "lowercase")]
+pub enum KycTier {
+    /// Unverified
+    Tier0,
+    /// Identity verified
+    Tier1,
+    /// Identity and income verified
+    Tier2,
+}
+
+impl KycTier {
+    /// Compute the tier from completed verification outcomes
+    pub fn from_verifications(identity_verified: bool, income_verified: bool) -> Self {
+        match (identity_verified, income_verified) {
+            (true, true) => KycTier::Tier2,
+            (true, false) => KycTier::Tier1,
+            _ => KycTier::Tier0,
+        }
+    }
+}
+
+/// Money movement limits applied to a tier
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+pub struct TierLimits {
+    pub single_transaction_limit: Amount,
+    pub daily_limit: Amount,
+}
+
+/// An account owner's tier limits and how much of today's limit is used
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+pub struct AccountLimitPosition {
+    #[serde(skip)]
+    pub user_id: UserId,
+    pub tier: KycTier,
+    pub limits: TierLimits,
+    /// Outgoing transactions and payments since midnight UTC
+    pub daily_used: Amount,
+}
+
+/// Configured limits for every tier
+#[derive(Debug, Clone)]
+pub struct KycLimits {
+    tier0: TierLimits,
+    tier1: TierLimits,
+    tier2: TierLimits,
+}
+
+impl KycLimits {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            tier0: TierLimits {
+                single_transaction_limit: config.kyc_tier0_single_limit,
+                daily_limit: config.kyc_tier0_daily_limit,
+            },
+            tier1: TierLimits {
+                single_transaction_limit: config.kyc_tier1_single_limit,
+                daily_limit: config.kyc_tier1_daily_limit,
+            },
+            tier2: TierLimits {
+                single_transaction_limit: config.kyc_tier2_single_limit,
+                daily_limit: config.kyc_tier2_daily_limit,
+            },
+        }
+    }
+
+    pub fn for_tier(&self, tier: KycTier) -> TierLimits {
+        match tier {
+            KycTier::Tier0 => self.tier0,
+            KycTier::Tier1 => self.tier1,
+            KycTier::Tier2 => self.tier2,
+        }
+    }
+}
+
+/// KYC tier response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KycTierResponse {
+    pub user_id: UserId,
+    pub tier: KycTier,
+    pub limits: TierLimits,
+    /// The user's identity verification has expired and must be redone
+    pub reverification_required: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use crate::core::error::AppResult;
+use crate::shared::types::{AccountId, Amount, UserId};
+use super::model::KycTier;
+
+pub struct KycRepository {
+    pool: PgPool,
+}
+
+impl KycRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Check whether the user has completed identity and income verifications
+    pub async fn find_verification_outcomes(&self, user_id: UserId) -> AppResult<(bool, bool)> {
+        let outcomes: (bool, bool) = sqlx::query_as(
+            "SELECT
+                EXISTS(SELECT 1 FROM identity_verifications WHERE user_id = $1 AND status = 'completed'),
+                EXISTS(SELECT 1 FROM income_verifications WHERE user_id = $1 AND status = 'completed')",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(outcomes)
+    }
+
+    /// Get the tier currently stored on the user profile
+    pub async fn find_user_tier(
+        &self,
+        user_id: UserId,
+    ) -> AppResult<Option<(KycTier, Option<DateTime<Utc>>)>> {
+        let tier = sqlx::query_as("SELECT kyc_tier, kyc_tier_updated_at FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tier)
+    }
+
+    /// Get the owner and tier of the user owning an account
+    pub async fn find_account_owner_tier(&self, account_id: AccountId) -> AppResult<Option<(UserId, KycTier)>> {
+        let owner = sqlx::query_as(
+            "SELECT u.id, u.kyc_tier FROM accounts a JOIN users u ON u.id = a.user_id WHERE a.id = $1",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(owner)
+    }
+
+    /// Check whether the user's identity verification has expired without being renewed
+    pub async fn has_lapsed_identity_verification(&self, user_id: UserId) -> AppResult<bool> {
+        let (lapsed,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS(SELECT 1 FROM identity_verifications WHERE user_id = $1 AND status = 'expired')
+                AND NOT EXISTS(SELECT 1 FROM identity_verifications WHERE user_id = $1 AND status = 'completed')",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(lapsed)
+    }
+
+    /// Store a newly computed tier on the user profile
+    pub async fn update_user_tier(&self, user_id: UserId, tier: KycTier) -> AppResult<DateTime<Utc>> {
+        let (updated_at,): (DateTime<Utc>,) = sqlx::query_as(
+            "UPDATE users SET kyc_tier = $1, kyc_tier_updated_at = NOW(), updated_at = NOW()
+             WHERE id = $2
+             RETURNING kyc_tier_updated_at",
+        )
+        .bind(tier)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(updated_at)
+    }
+
+    /// Total outgoing transactions and payments from an account since midnight UTC
+    pub async fn find_daily_outflow(&self, account_id: AccountId) -> AppResult<Amount> {
+        let (total,): (Option<i64>,) = sqlx::query_as(
+            "SELECT (
+                COALESCE((SELECT SUM(amount) FROM transactions
+                          WHERE from_account_id = $1
+                            AND status IN ('pending', 'completed')
+                            AND created_at >= date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'), 0)
+              + COALESCE((SELECT SUM(amount) FROM payments
+                          WHERE from_account_id = $1
+                            AND status IN ('pending', 'processing', 'completed')
+                            AND created_at >= date_trunc('day', NOW() AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'), 0)
+            )::BIGINT",
+        )
+        .bind(account_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(total.unwrap_or(0))
+    }
+}
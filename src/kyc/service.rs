@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::{AccountId, Amount, UserId};
+use super::model::{AccountLimitPosition, KycLimits, KycTier, KycTierResponse};
+use super::repository::KycRepository;
+
+/// Central policy for KYC tiers and the limits attached to them
+pub struct KycPolicyService {
+    repository: KycRepository,
+    limits: KycLimits,
+    audit_logger: AuditLogger,
+}
+
+impl KycPolicyService {
+    pub fn new(repository: KycRepository, limits: KycLimits, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            limits,
+            audit_logger,
+        }
+    }
+
+    /// Get the user's stored tier and its limits
+    pub async fn get_user_tier(&self, user_id: UserId) -> AppResult<KycTierResponse> {
+        let (tier, updated_at) = self
+            .repository
+            .find_user_tier(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        self.tier_response(user_id, tier, updated_at).await
+    }
+
+    /// Recompute the user's tier from their verification outcomes and store it
+    pub async fn refresh_user_tier(&self, user_id: UserId, actor_id: Uuid) -> AppResult<KycTierResponse> {
+        let (current, updated_at) = self
+            .repository
+            .find_user_tier(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let (identity_verified, income_verified) =
+            self.repository.find_verification_outcomes(user_id).await?;
+        let tier = KycTier::from_verifications(identity_verified, income_verified);
+
+        if tier == current {
+            return self.tier_response(user_id, tier, updated_at).await;
+        }
+
+        let updated_at = self.repository.update_user_tier(user_id, tier).await?;
+
+        let event = AuditEvent::new(AuditEventType::KycTierChanged)
+            .user_id(actor_id)
+            .resource(format!("user:{}", user_id))
+            .action("refresh_kyc_tier".to_string())
+            .metadata("from".to_string(), serde_json::json!(current))
+            .metadata("to".to_string(), serde_json::json!(tier))
+            .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        self.tier_response(user_id, tier, Some(updated_at)).await
+    }
+
+    /// The account owner's tier limits and today's outflow from the account
+    pub async fn limit_position(&self, account_id: AccountId) -> AppResult<AccountLimitPosition> {
+        let (user_id, tier) = self
+            .repository
+            .find_account_owner_tier(account_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found".to_string()))?;
+
+        Ok(AccountLimitPosition {
+            user_id,
+            tier,
+            limits: self.limits.for_tier(tier),
+            daily_used: self.repository.find_daily_outflow(account_id).await?,
+        })
+    }
+
+    /// Reject a debit that would exceed the account owner's tier limits
+    pub async fn ensure_within_limits(&self, account_id: AccountId, amount: Amount) -> AppResult<()> {
+        let AccountLimitPosition {
+            user_id,
+            tier,
+            limits,
+            daily_used: outflow,
+        } = self.limit_position(account_id).await?;
+        let violation = if amount > limits.single_transaction_limit {
+            format!(
+                "Amount exceeds the {:?} single transaction limit of {}",
+                tier, limits.single_transaction_limit
+            )
+        } else if outflow + amount > limits.daily_limit {
+            format!(
+                "Amount exceeds the {:?} daily limit of {} ({} already used today)",
+                tier, limits.daily_limit, outflow
+            )
+        } else {
+            return Ok(());
+        };
+
+        // Point users whose verification lapsed back at re-verification
+        if self.repository.has_lapsed_identity_verification(user_id).await? {
+            return Err(AppError::Authorization(format!(
+                "{}. Identity verification has expired; re-verify to restore higher limits",
+                violation
+            )));
+        }
+
+        Err(AppError::BadRequest(violation))
+    }
+
+    async fn tier_response(
+        &self,
+        user_id: UserId,
+        tier: KycTier,
+        updated_at: Option<DateTime<Utc>>,
+    ) -> AppResult<KycTierResponse> {
+        let reverification_required = self.repository.has_lapsed_identity_verification(user_id).await?;
+
+        Ok(KycTierResponse {
+            user_id,
+            tier,
+            limits: self.limits.for_tier(tier),
+            reverification_required,
+            updated_at,
+        })
+    }
+}
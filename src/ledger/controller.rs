@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use uuid::Uuid;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::AppResult,
+    extractors::ClientIp,
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::PaginationParams;
+use super::model::{IntegrityRun, IntegrityRunResponse};
+use super::repository::LedgerRepository;
+use super::service::LedgerService;
+
+pub(crate) fn ledger_service(state: &AppState) -> LedgerService {
+    LedgerService::new(
+        LedgerRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+        state.mailer.clone(),
+        state.config.ledger_integrity_alert_email.clone(),
+    )
+}
+
+/// List ledger integrity check results, newest first (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/ledger/integrity",
+    tag = "ledger",
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Ledger integrity runs", body = [IntegrityRun]),
+        (status = 403, description = "Caller lacks the system monitoring permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_integrity_runs(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<Vec<IntegrityRun>>>> {
+    state
+        .authorize(claims.developer_id, permissions::monitor_system(), ip, "ledger_integrity".to_string())
+        .await?;
+
+    let runs = ledger_service(&state)
+        .list_runs(pagination.page, pagination.limit)
+        .await?;
+    Ok(Json(ApiResponse::success("Ledger integrity runs retrieved successfully", runs)))
+}
+
+/// Get a ledger integrity run with its discrepancies (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/ledger/integrity/{id}",
+    tag = "ledger",
+    params(("id" = Uuid, Path, description = "Ledger integrity run ID")),
+    responses(
+        (status = 200, description = "Ledger integrity run", body = IntegrityRunResponse),
+        (status = 403, description = "Caller lacks the system monitoring permission"),
+        (status = 404, description = "Ledger integrity run not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_integrity_run(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<IntegrityRunResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::monitor_system(), ip, format!("ledger_integrity_run:{}", id))
+        .await?;
+
+    let run = ledger_service(&state).get_run(id).await?;
+    Ok(Json(ApiResponse::success("Ledger integrity run retrieved successfully", run)))
+}
@@ -0,0 +1,25 @@
+use crate::core::AppState;
+use super::controller::ledger_service;
+
+const INTEGRITY_CHECK_JOB: &str = "ledger_integrity_check";
+
+/// Periodically verify the ledger. Discrepancies are alerted by the service;
+/// the job itself only fails when the check cannot run.
+pub fn spawn_integrity_check_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.ledger_integrity_check_interval_seconds);
+    state.job_monitor.register(INTEGRITY_CHECK_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match ledger_service(&state).run_integrity_check().await {
+                Ok(_) => state.job_monitor.record_success(INTEGRITY_CHECK_JOB),
+                Err(e) => {
+                    state.job_monitor.record_failure(INTEGRITY_CHECK_JOB, e.to_string());
+                    tracing::error!("Ledger integrity job failed: {}", e);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,14 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::get, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/ledger/integrity", get(controller::list_integrity_runs))
+        .route("/ledger/integrity/:id", get(controller::get_integrity_run))
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::shared::types::{AccountId, Amount, TransactionId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "ledger_integrity_status", rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    Passed,
+    Failed,
+}
+
+/// Which ledger invariant a discrepancy breaks
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "ledger_discrepancy_type", rename_all = "snake_case")]
+pub enum DiscrepancyType {
+    /// The stored ledger balance differs from the sum of the account's postings
+    BalanceMismatch,
+    /// A posting's balance after is not its balance before plus the amount
+    PostingArithmetic,
+    /// A transaction's postings do not net to zero against its external leg
+    UnbalancedTransaction,
+}
+
+/// One integrity check over the whole ledger
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct IntegrityRun {
+    pub id: Uuid,
+    pub status: IntegrityStatus,
+    pub accounts_checked: i32,
+    pub postings_checked: i32,
+    pub transactions_checked: i32,
+    pub discrepancy_count: i32,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// A broken invariant found by a run
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Discrepancy {
+    pub id: Uuid,
+    pub run_id: Uuid,
+    pub discrepancy_type: DiscrepancyType,
+    pub account_id: Option<AccountId>,
+    pub transaction_id: Option<TransactionId>,
+    /// The balance_history row, for posting arithmetic discrepancies
+    pub posting_id: Option<Uuid>,
+    /// Value implied by the postings, in minor units
+    pub expected: Amount,
+    /// Value actually stored, in minor units
+    pub actual: Amount,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Row counts covered by a run
+#[derive(Debug, Clone, Copy, Default, FromRow)]
+pub struct LedgerCoverage {
+    pub accounts: i64,
+    pub postings: i64,
+    pub transactions: i64,
+}
+
+/// An integrity run with its discrepancies
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IntegrityRunResponse {
+    #[serde(flatten)]
+    pub run: IntegrityRun,
+    pub discrepancies: Vec<Discrepancy>,
+}
@@ -0,0 +1,191 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use super::model::{Discrepancy, IntegrityRun, LedgerCoverage};
+
+const RUN_COLUMNS: &str = "id, status, accounts_checked, postings_checked, transactions_checked,
+    discrepancy_count, started_at, completed_at";
+
+const DISCREPANCY_COLUMNS: &str =
+    "id, run_id, discrepancy_type, account_id, transaction_id, posting_id, expected, actual, created_at";
+
+pub struct LedgerRepository {
+    pool: PgPool,
+}
+
+impl LedgerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Check every ledger invariant against one consistent snapshot.
+    ///
+    /// Postings are the rows of balance_history. A transaction's postings must
+    /// net to zero once its external leg is counted: a transfer moves money
+    /// between two balances, while deposits, withdrawals and interest settle
+    /// against the outside world, so only their internal leg is posted.
+    pub async fn check(&self, run_id: Uuid) -> AppResult<(LedgerCoverage, Vec<Discrepancy>)> {
+        let mut tx = deadline::begin(&self.pool).await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let coverage = sqlx::query_as::<_, LedgerCoverage>(
+            "SELECT (SELECT COUNT(*) FROM balances) AS accounts,
+                    (SELECT COUNT(*) FROM balance_history) AS postings,
+                    (SELECT COUNT(DISTINCT transaction_id) FROM balance_history) AS transactions",
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut discrepancies = sqlx::query_as::<_, Discrepancy>(
+            "SELECT gen_random_uuid() AS id, $1 AS run_id,
+                    'balance_mismatch'::ledger_discrepancy_type AS discrepancy_type,
+                    b.account_id, NULL::UUID AS transaction_id, NULL::UUID AS posting_id,
+                    COALESCE(p.total, 0)::BIGINT AS expected, COALESCE(b.ledger_balance, 0) AS actual,
+                    NOW() AS created_at
+             FROM balances b
+             LEFT JOIN (
+                 SELECT account_id, SUM(amount_changed) AS total FROM balance_history GROUP BY account_id
+             ) p ON p.account_id = b.account_id
+             WHERE COALESCE(b.ledger_balance, 0) <> COALESCE(p.total, 0)
+             ORDER BY b.account_id",
+        )
+        .bind(run_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        discrepancies.extend(
+            sqlx::query_as::<_, Discrepancy>(
+                "SELECT gen_random_uuid() AS id, $1 AS run_id,
+                        'posting_arithmetic'::ledger_discrepancy_type AS discrepancy_type,
+                        account_id, transaction_id, id AS posting_id,
+                        balance_before + amount_changed AS expected, balance_after AS actual,
+                        NOW() AS created_at
+                 FROM balance_history
+                 WHERE balance_after <> balance_before + amount_changed
+                 ORDER BY created_at",
+            )
+            .bind(run_id)
+            .fetch_all(&mut *tx)
+            .await?,
+        );
+
+        // Only completed transactions may move money; anything posted for a
+        // pending, failed or cancelled one is expected to net to zero. Archived
+        // transactions are no longer in the table to compare against.
+        discrepancies.extend(
+            sqlx::query_as::<_, Discrepancy>(
+                "WITH posted AS (
+                     SELECT transaction_id, SUM(amount_changed)::BIGINT AS total
+                     FROM balance_history
+                     WHERE transaction_id IS NOT NULL
+                       AND transaction_id NOT IN (SELECT id FROM transaction_keys WHERE archive_id IS NOT NULL)
+                     GROUP BY transaction_id
+                 ),
+                 implied AS (
+                     SELECT posted.transaction_id, posted.total,
+                            CASE WHEN t.id IS NULL OR t.status <> 'completed' THEN 0
+                                 ELSE (CASE WHEN t.to_account_id IS NOT NULL THEN t.amount ELSE 0 END)
+                                    - (CASE WHEN t.from_account_id IS NOT NULL THEN t.amount ELSE 0 END)
+                            END AS expected
+                     FROM posted LEFT JOIN transactions t ON t.id = posted.transaction_id
+                 )
+                 SELECT gen_random_uuid() AS id, $1 AS run_id,
+                        'unbalanced_transaction'::ledger_discrepancy_type AS discrepancy_type,
+                        NULL::UUID AS account_id, transaction_id, NULL::UUID AS posting_id,
+                        expected, total AS actual, NOW() AS created_at
+                 FROM implied
+                 WHERE total <> expected
+                 ORDER BY transaction_id",
+            )
+            .bind(run_id)
+            .fetch_all(&mut *tx)
+            .await?,
+        );
+
+        tx.commit().await?;
+        Ok((coverage, discrepancies))
+    }
+
+    /// Store a finished run with its discrepancies
+    pub async fn save_run(&self, run: &IntegrityRun, discrepancies: &[Discrepancy]) -> AppResult<IntegrityRun> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let saved = sqlx::query_as::<_, IntegrityRun>(&format!(
+            "INSERT INTO ledger_integrity_runs ({RUN_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {RUN_COLUMNS}"
+        ))
+        .bind(run.id)
+        .bind(run.status)
+        .bind(run.accounts_checked)
+        .bind(run.postings_checked)
+        .bind(run.transactions_checked)
+        .bind(run.discrepancy_count)
+        .bind(run.started_at)
+        .bind(run.completed_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for discrepancy in discrepancies {
+            sqlx::query(&format!(
+                "INSERT INTO ledger_discrepancies ({DISCREPANCY_COLUMNS})
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+            ))
+            .bind(discrepancy.id)
+            .bind(discrepancy.run_id)
+            .bind(discrepancy.discrepancy_type)
+            .bind(discrepancy.account_id)
+            .bind(discrepancy.transaction_id)
+            .bind(discrepancy.posting_id)
+            .bind(discrepancy.expected)
+            .bind(discrepancy.actual)
+            .bind(discrepancy.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(saved)
+    }
+
+    pub async fn find_runs(&self, page: u32, limit: u32) -> AppResult<Vec<IntegrityRun>> {
+        let offset = (page.max(1) - 1) * limit;
+
+        let runs = sqlx::query_as::<_, IntegrityRun>(&format!(
+            "SELECT {RUN_COLUMNS} FROM ledger_integrity_runs
+             ORDER BY started_at DESC LIMIT $1 OFFSET $2"
+        ))
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(runs)
+    }
+
+    pub async fn find_run(&self, run_id: Uuid) -> AppResult<Option<IntegrityRun>> {
+        let run = sqlx::query_as::<_, IntegrityRun>(&format!(
+            "SELECT {RUN_COLUMNS} FROM ledger_integrity_runs WHERE id = $1"
+        ))
+        .bind(run_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    pub async fn find_discrepancies(&self, run_id: Uuid) -> AppResult<Vec<Discrepancy>> {
+        let discrepancies = sqlx::query_as::<_, Discrepancy>(&format!(
+            "SELECT {DISCREPANCY_COLUMNS} FROM ledger_discrepancies
+             WHERE run_id = $1 ORDER BY discrepancy_type, created_at"
+        ))
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(discrepancies)
+    }
+}
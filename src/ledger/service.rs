@@ -0,0 +1,141 @@
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::mailer::{EmailMessage, Mailer};
+use super::model::{Discrepancy, DiscrepancyType, IntegrityRun, IntegrityRunResponse, IntegrityStatus};
+use super::repository::LedgerRepository;
+
+/// Discrepancies listed in an alert email; the rest are only in the run report
+const ALERT_DISCREPANCY_LIMIT: usize = 20;
+
+pub struct LedgerService {
+    repository: LedgerRepository,
+    audit_logger: AuditLogger,
+    mailer: Arc<dyn Mailer>,
+    alert_email: Option<String>,
+}
+
+impl LedgerService {
+    pub fn new(
+        repository: LedgerRepository,
+        audit_logger: AuditLogger,
+        mailer: Arc<dyn Mailer>,
+        alert_email: Option<String>,
+    ) -> Self {
+        Self {
+            repository,
+            audit_logger,
+            mailer,
+            alert_email,
+        }
+    }
+
+    /// Recompute balances from postings, record what does not add up and
+    /// raise an alert when anything is found
+    pub async fn run_integrity_check(&self) -> AppResult<IntegrityRunResponse> {
+        let run_id = Uuid::new_v4();
+        let started_at = Utc::now();
+        let (coverage, discrepancies) = self.repository.check(run_id).await?;
+
+        let run = IntegrityRun {
+            id: run_id,
+            status: if discrepancies.is_empty() { IntegrityStatus::Passed } else { IntegrityStatus::Failed },
+            accounts_checked: coverage.accounts as i32,
+            postings_checked: coverage.postings as i32,
+            transactions_checked: coverage.transactions as i32,
+            discrepancy_count: discrepancies.len() as i32,
+            started_at,
+            completed_at: Utc::now(),
+        };
+        let run = self.repository.save_run(&run, &discrepancies).await?;
+
+        let failed = run.status == IntegrityStatus::Failed;
+        let event = AuditEvent::new(AuditEventType::LedgerIntegrityChecked)
+            .severity(if failed { AuditSeverity::Critical } else { AuditSeverity::Info })
+            .resource(format!("ledger_integrity_run:{}", run.id))
+            .action("check".to_string())
+            .success(!failed)
+            .metadata("accounts_checked".to_string(), serde_json::json!(run.accounts_checked))
+            .metadata("postings_checked".to_string(), serde_json::json!(run.postings_checked))
+            .metadata("discrepancy_count".to_string(), serde_json::json!(run.discrepancy_count))
+            .compliance_tag("LEDGER_INTEGRITY".to_string());
+        self.audit_logger.log(event).await;
+
+        if failed {
+            tracing::error!(
+                "Ledger integrity check {} found {} discrepancies",
+                run.id,
+                run.discrepancy_count
+            );
+            self.send_alert(&run, &discrepancies).await;
+        }
+
+        Ok(IntegrityRunResponse { run, discrepancies })
+    }
+
+    pub async fn list_runs(&self, page: u32, limit: u32) -> AppResult<Vec<IntegrityRun>> {
+        self.repository.find_runs(page, limit).await
+    }
+
+    /// Get a run with its discrepancies
+    pub async fn get_run(&self, run_id: Uuid) -> AppResult<IntegrityRunResponse> {
+        let run = self
+            .repository
+            .find_run(run_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Ledger integrity run not found".to_string()))?;
+        let discrepancies = self.repository.find_discrepancies(run_id).await?;
+        Ok(IntegrityRunResponse { run, discrepancies })
+    }
+
+    /// Email the configured operations address; delivery failures are logged
+    /// so they never fail the check itself
+    async fn send_alert(&self, run: &IntegrityRun, discrepancies: &[Discrepancy]) {
+        let Some(to) = self.alert_email.clone() else {
+            return;
+        };
+
+        let mut lines: Vec<String> = discrepancies
+            .iter()
+            .take(ALERT_DISCREPANCY_LIMIT)
+            .map(describe)
+            .collect();
+        if discrepancies.len() > ALERT_DISCREPANCY_LIMIT {
+            lines.push(format!("... and {} more", discrepancies.len() - ALERT_DISCREPANCY_LIMIT));
+        }
+
+        let message = EmailMessage {
+            to,
+            subject: format!("Ledger integrity check failed: {} discrepancies", run.discrepancy_count),
+            body: format!(
+                "The ledger integrity check {} completed at {} found {} discrepancies \
+                 across {} accounts and {} postings:\n\n{}\n",
+                run.id,
+                run.completed_at.format("%Y-%m-%d %H:%M UTC"),
+                run.discrepancy_count,
+                run.accounts_checked,
+                run.postings_checked,
+                lines.join("\n"),
+            ),
+        };
+        if let Err(e) = self.mailer.send(message).await {
+            tracing::error!("Failed to send ledger integrity alert: {}", e);
+        }
+    }
+}
+
+fn describe(discrepancy: &Discrepancy) -> String {
+    let subject = match discrepancy.discrepancy_type {
+        DiscrepancyType::BalanceMismatch => format!("account {} balance", discrepancy.account_id.unwrap_or_default()),
+        DiscrepancyType::PostingArithmetic => format!("posting {}", discrepancy.posting_id.unwrap_or_default()),
+        DiscrepancyType::UnbalancedTransaction => {
+            format!("transaction {}", discrepancy.transaction_id.unwrap_or_default())
+        }
+    };
+    format!(
+        "- {}: expected {}, found {}",
+        subject, discrepancy.expected, discrepancy.actual
+    )
+}
@@ -0,0 +1,48 @@
+//! OpenBank service library. The server binary is built on these modules,
+//! and the client SDK reuses their request and response models.
+
+pub mod core;
+pub mod shared;
+
+// Module declarations
+pub mod account_closures;
+pub mod account_controls;
+pub mod account_numbers;
+pub mod auth;
+pub mod captures;
+pub mod data_erasure;
+pub mod developers;
+pub mod devices;
+pub mod disputes;
+pub mod events;
+pub mod feature_flags;
+pub mod fees;
+pub mod fx;
+pub mod general_ledger;
+pub mod goals;
+pub mod graphql;
+pub mod identity;
+pub mod income;
+pub mod interest;
+pub mod kyc;
+pub mod ledger;
+pub mod metadata_schemas;
+pub mod notifications;
+pub mod organizations;
+pub mod payment_analytics;
+pub mod payments;
+pub mod reconciliation;
+pub mod regulatory_reports;
+pub mod reviews;
+pub mod roles;
+pub mod scheduled_reports;
+pub mod stream;
+pub mod transactions;
+pub mod treasury;
+pub mod uploads;
+pub mod usage;
+pub mod user_data;
+pub mod verification;
+pub mod verification_sessions;
+pub mod virtual_accounts;
+pub mod webhooks;
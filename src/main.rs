@@ -1,20 +1,15 @@
-use axum::{response::Json, routing::get, Router};
-use serde::{Deserialize, Serialize};
+use axum::{extract::State, http::StatusCode, response::Json, routing::get, Router};
+use std::net::SocketAddr;
 use tower_http::cors::CorsLayer;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-mod core;
-mod shared;
-
-// Module declarations
-mod auth;
-mod identity;
-mod income;
-mod payments;
-mod transactions;
-mod user_data;
-mod virtual_accounts;
+use openbank::{
+    account_closures, account_controls, account_numbers, auth, captures, core, data_erasure, developers, disputes, events,
+    feature_flags, fees, fx, general_ledger, goals, graphql, identity, income, interest, kyc, ledger, metadata_schemas,
+    notifications, organizations, payment_analytics, payments, reconciliation, regulatory_reports, reviews, roles, scheduled_reports, stream,
+    transactions, treasury, uploads, usage, user_data, verification_sessions, virtual_accounts, webhooks,
+};
 
 use core::config::Config;
 use core::database::init_mongodb;
@@ -31,7 +26,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .init();
 
     // Load configuration
-    let config = Config::from_env().map_err(|e| e as Box<dyn std::error::Error>)?;
+    let mut config = Config::from_env().map_err(|e| e as Box<dyn std::error::Error>)?;
+    let http_clients = core::http_client::HttpClients::from_config(&config)?;
+    let secrets = core::secrets::SecretsManager::from_config(&config, &http_clients)?;
+    config.apply_secrets(&secrets).await?;
     info!("Configuration loaded successfully");
 
     // Initialize databases (skip migrations for testing)
@@ -86,59 +84,157 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     // Initialize Security Services
-    let audit_logger = core::audit::AuditLogger::new(audit_mongodb_client);
-    let security_config = core::security::SecurityConfig {
-        max_failed_attempts: config.max_failed_attempts,
-        lockout_duration_minutes: config.account_lockout_duration_minutes,
-        progressive_lockout: config.progressive_lockout_enabled,
-        suspicious_activity_threshold: config.suspicious_activity_threshold,
-        password_history_count: config.password_history_count,
-        require_password_change_days: config.require_password_change_days,
-    };
-    let security_service = core::security::AccountSecurityService::new(security_config);
-    let rbac_service = core::rbac::RbacService::new();
-    let rate_limit_config = core::rate_limit::RateLimitConfig {
-        requests_per_minute: config.rate_limit_requests_per_minute as u32,
-        burst_size: config.rate_limit_burst_size,
-        window_size: std::time::Duration::from_secs(config.rate_limit_window_seconds),
+    let circuit_breakers =
+        core::circuit_breaker::CircuitBreakers::new(core::circuit_breaker::CircuitBreakerSettings::from_config(&config));
+    let audit_logger = core::audit::AuditLogger::new(
+        audit_mongodb_client,
+        circuit_breakers.get("audit_store"),
+        core::audit_writer::AuditWriterSettings::from_config(&config),
+    );
+    let audit_logger = match core::siem::from_config(&config, &http_clients)? {
+        Some(forwarder) => audit_logger.with_forwarder(forwarder),
+        None => audit_logger,
     };
-    let rate_limiter = core::rate_limit::RateLimiter::new(rate_limit_config);
+    let storage = core::storage::from_config(&config, &circuit_breakers, &http_clients)?;
+    let mailer = core::mailer::from_config(&config, &http_clients)?;
+    let alert_sink = core::alerts::from_config(&config, mailer.clone(), &http_clients)?;
 
-    info!("Security services initialized");
+    // Create AppState with all services
+    let app_state = core::AppState::new(
+        config.clone(),
+        postgres_pool,
+        mongodb_client,
+        audit_logger,
+        storage,
+        mailer,
+        circuit_breakers,
+        http_clients,
+    )?;
 
     // Create Auth service for OAuth2 API-as-a-Service
     let auth_service = auth::service::AuthService::new(
-        auth::repository::AuthRepository::new(postgres_pool.clone()),
+        auth::repository::AuthRepository::new(app_state.postgres.clone()),
         config.jwt_secret.clone(),
-    );
+    )
+    .with_mailer(app_state.mailer.clone())
+    .with_audit_logger(app_state.audit_logger.clone())
+    .with_alert_sink(alert_sink.clone())
+    .with_security_service(app_state.security_service.clone());
 
-    // Create AppState with all services
-    let app_state = core::AppState {
-        postgres: postgres_pool,
-        mongodb: mongodb_client,
-        config: config.clone(),
-        audit_logger,
-        security_service,
-        rbac_service,
-        rate_limiter,
-    };
+    info!("Security services initialized");
+
+    // Create the first admin when asked to; a no-op once they exist
+    core::bootstrap::run(&app_state).await?;
+
+    // Start background jobs; the journal first, so it sees every event
+    events::jobs::spawn_journal_job(app_state.clone());
+    core::audit::spawn_audit_replay_job(app_state.clone());
+    core::audit_chain::spawn_audit_anchor_job(app_state.clone());
+    core::audit_chain::spawn_audit_verification_job(app_state.clone(), alert_sink.clone());
+    identity::jobs::spawn_expiry_job(app_state.clone());
+    identity::jobs::spawn_rescreening_job(app_state.clone());
+    verification_sessions::jobs::spawn_session_expiry_job(app_state.clone());
+    uploads::jobs::spawn_upload_cleanup_job(app_state.clone());
+    usage::jobs::spawn_flush_job(app_state.clone());
+    captures::jobs::spawn_purge_job(app_state.clone());
+    income::jobs::spawn_employer_confirmation_expiry_job(app_state.clone());
+    income::jobs::spawn_credit_report_purge_job(app_state.clone());
+    transactions::jobs::spawn_enrichment_job(app_state.clone());
+    transactions::jobs::spawn_archive_job(app_state.clone());
+    transactions::jobs::spawn_export_job(app_state.clone());
+    payments::jobs::spawn_scheduled_payment_job(app_state.clone());
+    payments::jobs::spawn_settlement_job(app_state.clone());
+    payment_analytics::jobs::spawn_lifecycle_job(app_state.clone());
+    interest::jobs::spawn_interest_accrual_job(app_state.clone());
+    ledger::jobs::spawn_integrity_check_job(app_state.clone());
+    webhooks::jobs::spawn_delivery_job(app_state.clone());
+    notifications::jobs::spawn_notification_job(app_state.clone());
+    treasury::jobs::spawn_position_job(app_state.clone());
+    scheduled_reports::jobs::spawn_report_scheduler_job(app_state.clone());
+    core::anomaly::spawn_anomaly_detection_job(app_state.clone(), alert_sink);
+    core::live_config::spawn_config_watcher(app_state.clone());
+    core::partitions::spawn_partition_maintenance_job(app_state.clone());
 
     // Build our application with routes and security middleware
     let fintech_app = Router::new()
         .route("/health", get(health_check))
-        // Legacy fintech routes (with state)
-        .nest("/api/v1/user-data", user_data::routes())
-        .nest("/api/v1/identity", identity::routes())
-        .nest("/api/v1/income", income::routes())
-        .nest("/api/v1/payments", payments::routes())
-        .nest("/api/v1/transactions", transactions::routes())
-        .nest("/api/v1/virtual-accounts", virtual_accounts::routes())
+        .merge(core::openapi::routes(&config))
+        .nest("/graphql", graphql::routes())
+        // Fintech routes, served under every API version
+        .merge(core::versioning::nest_versions(
+            Router::new()
+                .nest("/user-data", user_data::routes())
+                .nest("/identity", identity::routes())
+                .nest("/identity/sessions", verification_sessions::routes())
+                .nest("/income", income::routes())
+                .nest("/reviews", reviews::routes())
+                .nest("/payments", payments::routes())
+                .nest("/transactions", transactions::routes())
+                .nest("/virtual-accounts", virtual_accounts::routes())
+                .nest("/account-numbers", account_numbers::routes())
+                .nest("/metadata-schemas", metadata_schemas::routes())
+                .nest("/disputes", disputes::routes())
+                .nest("/goals", goals::routes())
+                .nest("/account-closures", account_closures::routes())
+                .nest("/fees", fees::routes())
+                .nest("/fx", fx::routes())
+                .nest("/interest", interest::routes())
+                .nest("/reconciliation", reconciliation::routes())
+                .nest("/organizations", organizations::routes())
+                .nest("/stream", stream::routes())
+                .nest("/events", events::routes())
+                .nest("/webhooks", webhooks::routes())
+                .nest("/report-subscriptions", scheduled_reports::routes())
+                .nest("/storage", core::storage::routes())
+                .nest("/uploads", uploads::routes())
+                .nest(
+                    "/admin",
+                    account_controls::routes()
+                        .merge(developers::routes())
+                        .merge(data_erasure::routes())
+                        .merge(ledger::routes())
+                        .merge(general_ledger::routes())
+                        .merge(treasury::routes())
+                        .merge(payment_analytics::routes())
+                        .merge(regulatory_reports::routes())
+                        .merge(roles::routes())
+                        .merge(feature_flags::routes())
+                        .merge(usage::routes())
+                        .merge(captures::routes())
+                        .merge(core::live_config::routes())
+                        .merge(core::audit_chain::routes()),
+                )
+                .nest("/kyc", kyc::routes())
+        ))
+        // Usage metering needs the matched route, so it runs after routing
+        .route_layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            core::middleware::usage_metering_middleware,
+        ))
+        .route_layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            core::middleware::quota_middleware,
+        ))
+        // Scope checks run before quota so calls the token may not make are not billed
+        .route_layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            core::middleware::scope_middleware,
+        ))
+        // Tenant checks run first so calls for another tenant are neither billed nor served
+        .route_layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            core::middleware::tenant_isolation_middleware,
+        ))
         .with_state(app_state.clone());
 
     // Merge OAuth2 routes (no state) with fintech routes (with state)
     let app = fintech_app
         .merge(auth::routes(auth_service.clone()))
         // Security middleware layers (applied in reverse order)
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            core::middleware::api_version_middleware,
+        ))
         .layer(axum::middleware::from_fn_with_state(
             app_state.clone(),
             core::middleware::rbac_middleware,
@@ -151,6 +247,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             app_state.clone(),
             core::middleware::security_middleware,
         ))
+        // Outside the security layers so captures include rejected calls
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            core::middleware::debug_capture_middleware,
+        ))
+        // Inside localization so timeout errors are localized too
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            core::middleware::deadline_middleware,
+        ))
+        // Outermost, so every JSON response is localized, errors included
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            core::middleware::localization_middleware,
+        ))
         .layer(CorsLayer::permissive());
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080")
@@ -158,29 +269,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
     info!("Server starting on http://127.0.0.1:8080");
 
-    axum::serve(listener, app)
+    // The peer address decides project IP rules unless a trusted proxy forwards
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
 
     Ok(())
 }
 
-#[derive(Serialize, Deserialize)]
-struct HealthData {
-    service: String,
-    version: String,
-    timestamp: String,
-}
+async fn health_check(
+    State(state): State<core::AppState>,
+) -> (StatusCode, Json<core::response::ApiResponse<core::health::HealthReport>>) {
+    let report = core::health::check(&state).await;
 
-async fn health_check() -> Json<core::response::ApiResponse<HealthData>> {
-    let health_data = HealthData {
-        service: "openBank".to_string(),
-        version: "0.1.0".to_string(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
+    let (status_code, message) = match report.status {
+        core::health::OverallStatus::Healthy => (StatusCode::OK, "Service is healthy and operational"),
+        core::health::OverallStatus::Degraded => (StatusCode::OK, "Service is operational with degraded components"),
+        core::health::OverallStatus::Unhealthy => (StatusCode::SERVICE_UNAVAILABLE, "Service is unhealthy"),
     };
 
-    Json(core::response::ApiResponse::success(
-        "Service is healthy and operational",
-        health_data,
-    ))
+    (status_code, Json(core::response::ApiResponse::success(message, report)))
 }
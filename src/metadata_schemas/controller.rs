@@ -0,0 +1,139 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::AppResult,
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{
+    MetadataSchema, MetadataTarget, MetadataValidation, SetMetadataSchemaRequest, ValidateMetadataRequest,
+};
+use super::repository::MetadataSchemaRepository;
+use super::service::MetadataSchemaService;
+
+fn metadata_schema_service(state: &AppState) -> MetadataSchemaService {
+    MetadataSchemaService::new(
+        MetadataSchemaRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// List the calling project's metadata schemas
+#[utoipa::path(
+    get,
+    path = "/api/v1/metadata-schemas",
+    tag = "metadata-schemas",
+    responses(
+        (status = 200, description = "Metadata schemas, one per target at most", body = [MetadataSchema])
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_metadata_schemas(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+) -> AppResult<Json<ApiResponse<Vec<MetadataSchema>>>> {
+    let schemas = metadata_schema_service(&state).list_schemas(claims.project_id).await?;
+    Ok(Json(ApiResponse::success("Metadata schemas retrieved successfully", schemas)))
+}
+
+/// Get the calling project's schema for payment or transaction metadata
+#[utoipa::path(
+    get,
+    path = "/api/v1/metadata-schemas/{target}",
+    tag = "metadata-schemas",
+    params(("target" = MetadataTarget, Path, description = "payment or transaction")),
+    responses(
+        (status = 200, description = "Metadata schema", body = MetadataSchema),
+        (status = 404, description = "Metadata schema not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_metadata_schema(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(target): Path<MetadataTarget>,
+) -> AppResult<Json<ApiResponse<MetadataSchema>>> {
+    let schema = metadata_schema_service(&state)
+        .get_schema(claims.project_id, target)
+        .await?;
+    Ok(Json(ApiResponse::success("Metadata schema retrieved successfully", schema)))
+}
+
+/// Register or replace the JSON Schema the calling project's payment or
+/// transaction metadata must match from now on
+#[utoipa::path(
+    put,
+    path = "/api/v1/metadata-schemas/{target}",
+    tag = "metadata-schemas",
+    params(("target" = MetadataTarget, Path, description = "payment or transaction")),
+    request_body = SetMetadataSchemaRequest,
+    responses(
+        (status = 200, description = "Metadata schema set", body = MetadataSchema),
+        (status = 400, description = "Not a valid JSON Schema, too large or referencing other documents")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_metadata_schema(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(target): Path<MetadataTarget>,
+    ApiJson(request): ApiJson<SetMetadataSchemaRequest>,
+) -> AppResult<Json<ApiResponse<MetadataSchema>>> {
+    let schema = metadata_schema_service(&state)
+        .set_schema(claims.project_id, target, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Metadata schema set successfully", schema)))
+}
+
+/// Remove the calling project's schema for a target, so its metadata is
+/// free-form again
+#[utoipa::path(
+    delete,
+    path = "/api/v1/metadata-schemas/{target}",
+    tag = "metadata-schemas",
+    params(("target" = MetadataTarget, Path, description = "payment or transaction")),
+    responses(
+        (status = 200, description = "Metadata schema deleted"),
+        (status = 404, description = "Metadata schema not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_metadata_schema(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(target): Path<MetadataTarget>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    metadata_schema_service(&state)
+        .delete_schema(claims.project_id, target, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Metadata schema deleted successfully", ())))
+}
+
+/// Check metadata against the calling project's schema without creating a
+/// payment or transaction
+#[utoipa::path(
+    post,
+    path = "/api/v1/metadata-schemas/{target}/validate",
+    tag = "metadata-schemas",
+    params(("target" = MetadataTarget, Path, description = "payment or transaction")),
+    request_body = ValidateMetadataRequest,
+    responses(
+        (status = 200, description = "Validation result, with each violation", body = MetadataValidation)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn validate_metadata(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(target): Path<MetadataTarget>,
+    ApiJson(request): ApiJson<ValidateMetadataRequest>,
+) -> AppResult<Json<ApiResponse<MetadataValidation>>> {
+    let validation = metadata_schema_service(&state)
+        .validate(claims.project_id, target, request.metadata.as_ref())
+        .await?;
+    Ok(Json(ApiResponse::success("Metadata validated", validation)))
+}
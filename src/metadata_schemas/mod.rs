@@ -0,0 +1,20 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod schema;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(controller::list_metadata_schemas))
+        .route(
+            "/:target",
+            get(controller::get_metadata_schema)
+                .put(controller::set_metadata_schema)
+                .delete(controller::delete_metadata_schema),
+        )
+        .route("/:target/validate", post(controller::validate_metadata))
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a metadata schema applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "metadata_schema_target", rename_all = "snake_case")]
+pub enum MetadataTarget {
+    Payment,
+    Transaction,
+}
+
+/// A JSON Schema the metadata of a project's payments or transactions must
+/// match
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct MetadataSchema {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub target: MetadataTarget,
+    #[schema(value_type = Object)]
+    pub schema: Value,
+    /// Starts at 1 and goes up each time the schema is replaced
+    pub version: i32,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Register or replace the schema for a target. Drafts 4, 6, 7, 2019-09
+/// and 2020-12 are accepted, chosen by `$schema` and defaulting to draft 7;
+/// `$ref`s must point inside the schema.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SetMetadataSchemaRequest {
+    #[schema(value_type = Object)]
+    pub schema: Value,
+}
+
+/// Metadata to check against a project's schema without creating anything
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ValidateMetadataRequest {
+    #[schema(value_type = Option<Object>)]
+    pub metadata: Option<Value>,
+}
+
+/// One way metadata breaks its schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct MetadataViolation {
+    /// JSON Pointer to the offending value; empty for the metadata itself
+    pub path: String,
+    /// Schema keyword that failed, e.g. `required` or `type`
+    pub keyword: String,
+    pub message: String,
+}
+
+/// Whether metadata matches a project's schema, and how it does not
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MetadataValidation {
+    pub target: MetadataTarget,
+    pub valid: bool,
+    /// Version of the schema checked against; absent when the project has
+    /// none, in which case any metadata is valid
+    pub schema_version: Option<i32>,
+    pub violations: Vec<MetadataViolation>,
+}
@@ -0,0 +1,83 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use super::model::{MetadataSchema, MetadataTarget};
+
+const SCHEMA_COLUMNS: &str = "id, project_id, target, schema, version, created_by, created_at, updated_at";
+
+pub struct MetadataSchemaRepository {
+    pool: PgPool,
+}
+
+impl MetadataSchemaRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_schemas(&self, project_id: Uuid) -> AppResult<Vec<MetadataSchema>> {
+        let schemas = sqlx::query_as::<_, MetadataSchema>(&format!(
+            "SELECT {SCHEMA_COLUMNS} FROM project_metadata_schemas
+             WHERE project_id = $1
+             ORDER BY target"
+        ))
+        .bind(project_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(schemas)
+    }
+
+    pub async fn find_schema(&self, project_id: Uuid, target: MetadataTarget) -> AppResult<Option<MetadataSchema>> {
+        let schema = sqlx::query_as::<_, MetadataSchema>(&format!(
+            "SELECT {SCHEMA_COLUMNS} FROM project_metadata_schemas
+             WHERE project_id = $1 AND target = $2"
+        ))
+        .bind(project_id)
+        .bind(target)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(schema)
+    }
+
+    /// Register the target's schema, or replace it under the next version
+    pub async fn upsert_schema(
+        &self,
+        project_id: Uuid,
+        target: MetadataTarget,
+        schema: &serde_json::Value,
+        created_by: Uuid,
+    ) -> AppResult<MetadataSchema> {
+        let saved = sqlx::query_as::<_, MetadataSchema>(&format!(
+            "INSERT INTO project_metadata_schemas (project_id, target, schema, created_by)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (project_id, target) DO UPDATE SET
+                 schema = EXCLUDED.schema,
+                 version = project_metadata_schemas.version + 1,
+                 created_by = EXCLUDED.created_by,
+                 updated_at = NOW()
+             RETURNING {SCHEMA_COLUMNS}"
+        ))
+        .bind(project_id)
+        .bind(target)
+        .bind(schema)
+        .bind(created_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(saved)
+    }
+
+    pub async fn delete_schema(&self, project_id: Uuid, target: MetadataTarget) -> AppResult<Option<MetadataSchema>> {
+        let deleted = sqlx::query_as::<_, MetadataSchema>(&format!(
+            "DELETE FROM project_metadata_schemas WHERE project_id = $1 AND target = $2
+             RETURNING {SCHEMA_COLUMNS}"
+        ))
+        .bind(project_id)
+        .bind(target)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,152 @@
+use jsonschema::paths::{JSONPointer, PathChunk};
+use jsonschema::JSONSchema;
+use serde_json::{Map, Value};
+use validator::{ValidationError, ValidationErrors};
+use crate::core::error::{AppError, AppResult};
+use super::model::MetadataViolation;
+
+/// Largest schema a project may register, serialized
+pub const MAX_SCHEMA_BYTES: usize = 64 * 1024;
+
+/// Most violations reported for one document
+pub const MAX_VIOLATIONS: usize = 20;
+
+/// Compile a schema a project asks to register, rejecting ones that are not
+/// valid JSON Schema, too large, or that reference other documents
+pub fn compile(schema: &Value) -> AppResult<JSONSchema> {
+    let invalid = |message: String| AppError::Validation(format!("Invalid metadata schema: {}", message));
+
+    if !schema.is_object() {
+        return Err(invalid("must be a JSON object".to_string()));
+    }
+    if schema.to_string().len() > MAX_SCHEMA_BYTES {
+        return Err(invalid(format!("must be at most {} bytes", MAX_SCHEMA_BYTES)));
+    }
+    if let Some(reference) = external_reference(schema) {
+        return Err(invalid(format!("$ref '{}' must point inside the schema", reference)));
+    }
+    JSONSchema::options()
+        .compile(schema)
+        .map_err(|e| invalid(e.to_string()))
+}
+
+/// How `metadata` breaks the schema, at most `MAX_VIOLATIONS` of them.
+/// Absent metadata is checked as an empty object, so required keys cannot
+/// be skipped by leaving it out.
+pub fn violations(schema: &JSONSchema, metadata: Option<&Value>) -> Vec<MetadataViolation> {
+    let empty = Value::Object(Map::new());
+    let metadata = metadata.unwrap_or(&empty);
+
+    let found = match schema.validate(metadata) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .take(MAX_VIOLATIONS)
+            .map(|error| MetadataViolation {
+                path: error.instance_path.to_string(),
+                keyword: keyword(&error.schema_path),
+                message: error.to_string(),
+            })
+            .collect(),
+    };
+    found
+}
+
+/// Violations as a field error on `metadata`, one entry per violation
+pub fn violations_error(violations: &[MetadataViolation]) -> AppError {
+    let mut errors = ValidationErrors::new();
+    for violation in violations {
+        let mut error = ValidationError::new("metadata_schema");
+        error.add_param("path".into(), &violation.path);
+        error.add_param("keyword".into(), &violation.keyword);
+        error.add_param("reason".into(), &violation.message);
+        errors.add("metadata", error);
+    }
+    AppError::InvalidFields(errors)
+}
+
+/// The keyword a schema path ends in
+fn keyword(schema_path: &JSONPointer) -> String {
+    match schema_path.last() {
+        Some(PathChunk::Keyword(keyword)) => keyword.to_string(),
+        Some(PathChunk::Property(keyword)) => keyword.to_string(),
+        _ => "schema".to_string(),
+    }
+}
+
+/// The first `$ref` that leaves the document, if any
+fn external_reference(schema: &Value) -> Option<&str> {
+    match schema {
+        Value::Object(object) => {
+            let reference = object
+                .get("$ref")
+                .and_then(Value::as_str)
+                .filter(|reference| !reference.starts_with('#'));
+            reference.or_else(|| object.values().find_map(external_reference))
+        }
+        Value::Array(items) => items.iter().find_map(external_reference),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn order_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["order_id"],
+            "properties": {
+                "order_id": {"type": "string", "pattern": "^ORD-[0-9]+$"},
+                "channel": {"enum": ["web", "mobile"]},
+                "items": {"type": "array", "items": {"$ref": "#/$defs/item"}}
+            },
+            "$defs": {"item": {"type": "object", "required": ["sku"]}}
+        })
+    }
+
+    #[test]
+    fn reports_where_and_how_metadata_breaks_the_schema() {
+        let schema = compile(&order_schema()).unwrap();
+
+        assert!(violations(&schema, Some(&json!({"order_id": "ORD-1", "items": [{"sku": "A"}]}))).is_empty());
+
+        let found = violations(&schema, Some(&json!({"order_id": 7, "channel": "fax", "items": [{}]})));
+        let mut found: Vec<_> = found.iter().map(|v| (v.path.as_str(), v.keyword.as_str())).collect();
+        found.sort();
+        assert_eq!(found, vec![("/channel", "enum"), ("/items/0", "required"), ("/order_id", "type")]);
+
+        // Leaving metadata out does not get around required keys
+        let missing = violations(&schema, None);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].path, "");
+        assert_eq!(missing[0].keyword, "required");
+    }
+
+    #[test]
+    fn rejects_schemas_that_cannot_be_registered() {
+        assert!(matches!(compile(&json!(true)), Err(AppError::Validation(_))));
+        assert!(compile(&json!({"type": "no-such-type"})).is_err());
+        assert!(compile(&json!({"properties": {"a": {"$ref": "https://example.com/a.json"}}})).is_err());
+        let huge = json!({"description": "x".repeat(MAX_SCHEMA_BYTES)});
+        assert!(compile(&huge).is_err());
+    }
+
+    #[test]
+    fn violations_become_metadata_field_errors() {
+        let violation = MetadataViolation {
+            path: "/order_id".to_string(),
+            keyword: "type".to_string(),
+            message: "7 is not of type \"string\"".to_string(),
+        };
+        let AppError::InvalidFields(errors) = violations_error(&[violation]) else {
+            panic!("expected field errors");
+        };
+        let fields = errors.field_errors();
+        let error = &fields["metadata"][0];
+        assert_eq!(error.code, "metadata_schema");
+        assert_eq!(error.params["path"], json!("/order_id"));
+        assert_eq!(error.params["keyword"], json!("type"));
+    }
+}
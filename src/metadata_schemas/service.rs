@@ -0,0 +1,130 @@
+use serde_json::Value;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use super::model::{MetadataSchema, MetadataTarget, MetadataValidation, MetadataViolation, SetMetadataSchemaRequest};
+use super::repository::MetadataSchemaRepository;
+use super::schema::{compile, violations, violations_error};
+
+/// How metadata breaks the project's schema for the target, with the
+/// version checked against; no schema means nothing to break
+async fn check(
+    repository: &MetadataSchemaRepository,
+    project_id: Uuid,
+    target: MetadataTarget,
+    metadata: Option<&Value>,
+) -> AppResult<(Option<i32>, Vec<MetadataViolation>)> {
+    let Some(stored) = repository.find_schema(project_id, target).await? else {
+        return Ok((None, Vec::new()));
+    };
+    // Schemas are checked when they are registered
+    let schema = compile(&stored.schema)
+        .map_err(|e| AppError::Internal(format!("Stored metadata schema {} does not compile: {}", stored.id, e)))?;
+    Ok((Some(stored.version), violations(&schema, metadata)))
+}
+
+/// Rejects payments and transactions whose metadata does not match their
+/// project's schema
+pub struct MetadataSchemaGuard {
+    repository: MetadataSchemaRepository,
+}
+
+impl MetadataSchemaGuard {
+    pub fn new(repository: MetadataSchemaRepository) -> Self {
+        Self { repository }
+    }
+
+    /// Fails with a field error on `metadata` per violation
+    pub async fn ensure_valid(&self, project_id: Uuid, target: MetadataTarget, metadata: Option<&Value>) -> AppResult<()> {
+        let (_, violations) = check(&self.repository, project_id, target, metadata).await?;
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations_error(&violations))
+        }
+    }
+}
+
+/// Project management of metadata schemas, and checks of metadata against
+/// them
+pub struct MetadataSchemaService {
+    repository: MetadataSchemaRepository,
+    audit_logger: AuditLogger,
+}
+
+impl MetadataSchemaService {
+    pub fn new(repository: MetadataSchemaRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            audit_logger,
+        }
+    }
+
+    pub async fn list_schemas(&self, project_id: Uuid) -> AppResult<Vec<MetadataSchema>> {
+        self.repository.find_schemas(project_id).await
+    }
+
+    pub async fn get_schema(&self, project_id: Uuid, target: MetadataTarget) -> AppResult<MetadataSchema> {
+        self.repository
+            .find_schema(project_id, target)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Metadata schema not found".to_string()))
+    }
+
+    /// Register or replace the target's schema. Metadata already stored is
+    /// not checked again.
+    pub async fn set_schema(
+        &self,
+        project_id: Uuid,
+        target: MetadataTarget,
+        request: SetMetadataSchemaRequest,
+        actor_id: Uuid,
+    ) -> AppResult<MetadataSchema> {
+        compile(&request.schema)?;
+
+        let schema = self
+            .repository
+            .upsert_schema(project_id, target, &request.schema, actor_id)
+            .await?;
+        self.log_change(&schema, "set", actor_id).await;
+        Ok(schema)
+    }
+
+    pub async fn delete_schema(&self, project_id: Uuid, target: MetadataTarget, actor_id: Uuid) -> AppResult<()> {
+        let schema = self
+            .repository
+            .delete_schema(project_id, target)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Metadata schema not found".to_string()))?;
+        self.log_change(&schema, "delete", actor_id).await;
+        Ok(())
+    }
+
+    /// Check metadata the way creating a payment or transaction would
+    pub async fn validate(
+        &self,
+        project_id: Uuid,
+        target: MetadataTarget,
+        metadata: Option<&Value>,
+    ) -> AppResult<MetadataValidation> {
+        let (schema_version, violations) = check(&self.repository, project_id, target, metadata).await?;
+
+        Ok(MetadataValidation {
+            target,
+            valid: violations.is_empty(),
+            schema_version,
+            violations,
+        })
+    }
+
+    async fn log_change(&self, schema: &MetadataSchema, action: &str, actor_id: Uuid) {
+        let event = AuditEvent::new(AuditEventType::MetadataSchemaChanged)
+            .user_id(actor_id)
+            .project_id(schema.project_id)
+            .resource(format!("metadata_schema:{}", schema.id))
+            .action(action.to_string())
+            .metadata("target".to_string(), serde_json::json!(schema.target))
+            .metadata("version".to_string(), serde_json::json!(schema.version));
+        self.audit_logger.log(event).await;
+    }
+}
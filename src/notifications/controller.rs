@@ -0,0 +1,153 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::identity::repository::IdentityRepository;
+use crate::shared::types::PaginationParams;
+use super::model::{
+    MarkAllReadRequest, MarkAllReadResponse, Notification, NotificationFeed, NotificationFilter,
+    NotificationPreferencesResponse, NotificationUserQuery, UpdateNotificationPreferencesRequest,
+};
+use super::repository::NotificationRepository;
+use super::service::NotificationService;
+
+pub(crate) fn notification_service(state: &AppState) -> NotificationService {
+    NotificationService::new(
+        NotificationRepository::new(state.postgres.clone()),
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        state.mailer.clone(),
+        state.event_bus.clone(),
+        state.audit_logger.clone(),
+    )
+}
+
+/// List a user's in-app notifications, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/user-data/notifications",
+    tag = "notifications",
+    params(NotificationFilter, PaginationParams),
+    responses(
+        (status = 200, description = "Page of notifications with the unread count", body = NotificationFeed),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_notifications(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(filter): Query<NotificationFilter>,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<NotificationFeed>>> {
+    let feed = notification_service(&state)
+        .get_feed(filter, claims.tenant_id, pagination.page, pagination.limit)
+        .await?;
+    Ok(Json(ApiResponse::success("Notifications retrieved successfully", feed)))
+}
+
+/// Mark a notification as read
+#[utoipa::path(
+    post,
+    path = "/api/v1/user-data/notifications/{id}/read",
+    tag = "notifications",
+    params(("id" = Uuid, Path, description = "Notification ID"), NotificationUserQuery),
+    responses(
+        (status = 200, description = "Notification marked as read", body = Notification),
+        (status = 404, description = "Notification not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn mark_notification_read(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    Query(query): Query<NotificationUserQuery>,
+) -> AppResult<Json<ApiResponse<Notification>>> {
+    let notification = notification_service(&state)
+        .mark_read(id, query.user_id, claims.tenant_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Notification marked as read", notification)))
+}
+
+/// Mark all of a user's notifications as read
+#[utoipa::path(
+    post,
+    path = "/api/v1/user-data/notifications/read-all",
+    tag = "notifications",
+    request_body = MarkAllReadRequest,
+    responses(
+        (status = 200, description = "Notifications marked as read", body = MarkAllReadResponse),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn mark_all_notifications_read(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<MarkAllReadRequest>,
+) -> AppResult<Json<ApiResponse<MarkAllReadResponse>>> {
+    let marked = notification_service(&state)
+        .mark_all_read(request.user_id, claims.tenant_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Notifications marked as read", marked)))
+}
+
+/// Get a user's notification channel preferences
+#[utoipa::path(
+    get,
+    path = "/api/v1/user-data/notifications/preferences",
+    tag = "notifications",
+    params(NotificationUserQuery),
+    responses(
+        (status = 200, description = "Channel preferences for every category", body = NotificationPreferencesResponse),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_notification_preferences(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<NotificationUserQuery>,
+) -> AppResult<Json<ApiResponse<NotificationPreferencesResponse>>> {
+    let preferences = notification_service(&state)
+        .get_preferences(query.user_id, claims.tenant_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Notification preferences retrieved successfully", preferences)))
+}
+
+/// Update a user's notification channel preferences
+#[utoipa::path(
+    put,
+    path = "/api/v1/user-data/notifications/preferences",
+    tag = "notifications",
+    request_body = UpdateNotificationPreferencesRequest,
+    responses(
+        (status = 200, description = "Updated channel preferences", body = NotificationPreferencesResponse),
+        (status = 400, description = "Invalid preferences"),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_notification_preferences(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<UpdateNotificationPreferencesRequest>,
+) -> AppResult<Json<ApiResponse<NotificationPreferencesResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let preferences = notification_service(&state)
+        .update_preferences(request, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Notification preferences updated successfully", preferences)))
+}
@@ -0,0 +1,26 @@
+use tokio::sync::broadcast::error::RecvError;
+use crate::core::AppState;
+use super::controller::notification_service;
+
+/// Notify users of domain events that concern their accounts. Runs for the
+/// life of the process; events published while it lags behind are skipped.
+pub fn spawn_notification_job(state: AppState) {
+    let mut events = state.event_bus.subscribe();
+
+    tokio::spawn(async move {
+        let service = notification_service(&state);
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if let Err(e) = service.handle_event(&event).await {
+                        tracing::error!(event_id = %event.id, "Failed to notify users of {} event: {}", event.event_type.as_str(), e);
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Notification job fell behind and skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
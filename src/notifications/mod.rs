@@ -0,0 +1,19 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(controller::get_notifications))
+        .route("/read-all", post(controller::mark_all_notifications_read))
+        .route("/:id/read", post(controller::mark_notification_read))
+        .route(
+            "/preferences",
+            get(controller::get_notification_preferences).put(controller::update_notification_preferences),
+        )
+}
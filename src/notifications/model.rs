@@ -0,0 +1,210 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::{TenantId, UserId};
+
+/// Group of notifications a user sets channel preferences for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "notification_category", rename_all = "snake_case")]
+pub enum NotificationCategory {
+    Payments,
+    /// Fraud and login alerts
+    Security,
+    Account,
+}
+
+impl NotificationCategory {
+    pub const ALL: [NotificationCategory; 3] = [
+        NotificationCategory::Payments,
+        NotificationCategory::Security,
+        NotificationCategory::Account,
+    ];
+}
+
+/// What a notification is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "notification_kind", rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A payment from one of the user's accounts completed
+    PaymentCompleted,
+    /// A payment into one of the user's accounts completed
+    PaymentReceived,
+    /// One of the user's accounts was frozen for suspected fraud
+    FraudAlert,
+    LoginAlert,
+    AccountClosed,
+}
+
+impl NotificationKind {
+    pub fn category(&self) -> NotificationCategory {
+        match self {
+            NotificationKind::PaymentCompleted | NotificationKind::PaymentReceived => NotificationCategory::Payments,
+            NotificationKind::FraudAlert | NotificationKind::LoginAlert => NotificationCategory::Security,
+            NotificationKind::AccountClosed => NotificationCategory::Account,
+        }
+    }
+}
+
+/// Channels a category of notifications is delivered on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+pub struct ChannelPreferences {
+    pub email: bool,
+    /// Delivered to the tenant's project webhooks as `notification.created`
+    pub webhook: bool,
+    /// Shown in the user's notification feed
+    pub in_app: bool,
+}
+
+impl ChannelPreferences {
+    /// Channels used until the user chooses otherwise. Payment notifications
+    /// are not emailed by default; everything else goes everywhere.
+    pub fn default_for(category: NotificationCategory) -> Self {
+        Self {
+            email: category != NotificationCategory::Payments,
+            webhook: true,
+            in_app: true,
+        }
+    }
+}
+
+/// Stored channel preferences for one category
+#[derive(Debug, Clone, FromRow)]
+pub struct NotificationPreference {
+    pub user_id: UserId,
+    pub category: NotificationCategory,
+    pub email_enabled: bool,
+    pub webhook_enabled: bool,
+    pub in_app_enabled: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl NotificationPreference {
+    pub fn channels(&self) -> ChannelPreferences {
+        ChannelPreferences {
+            email: self.email_enabled,
+            webhook: self.webhook_enabled,
+            in_app: self.in_app_enabled,
+        }
+    }
+}
+
+/// Channel preferences for one category, with defaults filled in
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CategoryPreferences {
+    pub category: NotificationCategory,
+    pub channels: ChannelPreferences,
+    /// Absent while the category still uses the defaults
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A user's channel preferences for every category
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPreferencesResponse {
+    pub user_id: UserId,
+    pub preferences: Vec<CategoryPreferences>,
+}
+
+/// Channels to change for one category; omitted channels keep their setting
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategoryPreferencesUpdate {
+    pub category: NotificationCategory,
+    pub email: Option<bool>,
+    pub webhook: Option<bool>,
+    pub in_app: Option<bool>,
+}
+
+/// Update a user's notification preferences
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub user_id: UserId,
+    #[validate(length(min = 1, max = 3))]
+    pub preferences: Vec<CategoryPreferencesUpdate>,
+}
+
+/// An in-app notification
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: UserId,
+    #[serde(skip)]
+    pub tenant_id: Option<TenantId>,
+    pub category: NotificationCategory,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: String,
+    pub data: serde_json::Value,
+    #[serde(skip)]
+    pub source_id: Option<Uuid>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A notification to deliver to a user on the channels they have enabled
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub user_id: UserId,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: String,
+    pub data: serde_json::Value,
+    /// Accounts the notification concerns, carried by the webhook event
+    pub account_ids: Vec<Uuid>,
+    /// What raised the notification; a source is notified once per user and kind
+    pub source_id: Option<Uuid>,
+}
+
+/// Where a notification is delivered
+#[derive(Debug, Clone, FromRow)]
+pub struct Recipient {
+    pub user_id: UserId,
+    pub tenant_id: Option<TenantId>,
+    pub email: String,
+    pub first_name: String,
+}
+
+/// Query parameters identifying the user whose notifications are read
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationUserQuery {
+    pub user_id: UserId,
+}
+
+/// Query parameters for the notification feed
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationFilter {
+    pub user_id: UserId,
+    pub category: Option<NotificationCategory>,
+    /// Only notifications not yet marked as read
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+/// A page of the notification feed
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationFeed {
+    pub data: Vec<Notification>,
+    pub page: u32,
+    pub limit: u32,
+    pub total: u64,
+    pub total_pages: u32,
+    /// Unread notifications across the whole feed
+    pub unread_count: u64,
+}
+
+/// Mark every notification of a user as read
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MarkAllReadRequest {
+    pub user_id: UserId,
+}
+
+/// How many notifications were marked as read
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MarkAllReadResponse {
+    pub marked: u64,
+}
@@ -0,0 +1,196 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::shared::types::{AccountId, TenantId, UserId};
+use super::model::{
+    ChannelPreferences, Notification, NotificationCategory, NotificationFilter, NotificationPreference, Recipient,
+};
+
+const NOTIFICATION_COLUMNS: &str =
+    "id, user_id, tenant_id, category, kind, title, body, data, source_id, read_at, created_at";
+
+const NOTIFICATION_FILTER: &str = "user_id = $1 AND tenant_id = $2
+    AND ($3::notification_category IS NULL OR category = $3)
+    AND (NOT $4 OR read_at IS NULL)";
+
+#[derive(Clone)]
+pub struct NotificationRepository {
+    pool: PgPool,
+}
+
+impl NotificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_recipient(&self, user_id: UserId) -> AppResult<Option<Recipient>> {
+        let recipient = sqlx::query_as::<_, Recipient>(
+            "SELECT id AS user_id, tenant_id, email, first_name FROM users WHERE id = $1 AND is_active = true",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(recipient)
+    }
+
+    /// Owners of the given accounts, as (account, user) pairs
+    pub async fn find_account_owners(&self, account_ids: &[AccountId]) -> AppResult<Vec<(AccountId, UserId)>> {
+        let owners = sqlx::query_as("SELECT id, user_id FROM accounts WHERE id = ANY($1)")
+            .bind(account_ids)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(owners)
+    }
+
+    pub async fn find_preferences(&self, user_id: UserId) -> AppResult<Vec<NotificationPreference>> {
+        let preferences = sqlx::query_as::<_, NotificationPreference>(
+            "SELECT user_id, category, email_enabled, webhook_enabled, in_app_enabled, updated_at
+             FROM notification_preferences WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(preferences)
+    }
+
+    pub async fn upsert_preference(
+        &self,
+        user_id: UserId,
+        category: NotificationCategory,
+        channels: ChannelPreferences,
+    ) -> AppResult<NotificationPreference> {
+        let preference = sqlx::query_as::<_, NotificationPreference>(
+            "INSERT INTO notification_preferences (user_id, category, email_enabled, webhook_enabled, in_app_enabled)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (user_id, category) DO UPDATE
+             SET email_enabled = $3, webhook_enabled = $4, in_app_enabled = $5, updated_at = NOW()
+             RETURNING user_id, category, email_enabled, webhook_enabled, in_app_enabled, updated_at",
+        )
+        .bind(user_id)
+        .bind(category)
+        .bind(channels.email)
+        .bind(channels.webhook)
+        .bind(channels.in_app)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(preference)
+    }
+
+    /// Record an in-app notification. Returns `None` if the user has already
+    /// been notified of this kind for the same source.
+    pub async fn create(&self, notification: &Notification) -> AppResult<Option<Notification>> {
+        let created = sqlx::query_as::<_, Notification>(&format!(
+            "INSERT INTO notifications ({NOTIFICATION_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             ON CONFLICT (user_id, kind, source_id) WHERE source_id IS NOT NULL DO NOTHING
+             RETURNING {NOTIFICATION_COLUMNS}"
+        ))
+        .bind(notification.id)
+        .bind(notification.user_id)
+        .bind(notification.tenant_id)
+        .bind(notification.category)
+        .bind(notification.kind)
+        .bind(&notification.title)
+        .bind(&notification.body)
+        .bind(&notification.data)
+        .bind(notification.source_id)
+        .bind(notification.read_at)
+        .bind(notification.created_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    /// The user's notifications, newest first
+    pub async fn find_feed(
+        &self,
+        filter: &NotificationFilter,
+        tenant_id: TenantId,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<Vec<Notification>> {
+        let offset = (page.max(1) - 1) * limit;
+
+        let notifications = sqlx::query_as::<_, Notification>(&format!(
+            "SELECT {NOTIFICATION_COLUMNS} FROM notifications
+             WHERE {NOTIFICATION_FILTER}
+             ORDER BY created_at DESC, id
+             LIMIT $5 OFFSET $6"
+        ))
+        .bind(filter.user_id)
+        .bind(tenant_id)
+        .bind(filter.category)
+        .bind(filter.unread_only)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(notifications)
+    }
+
+    pub async fn count_feed(&self, filter: &NotificationFilter, tenant_id: TenantId) -> AppResult<i64> {
+        let count: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM notifications WHERE {NOTIFICATION_FILTER}"
+        ))
+        .bind(filter.user_id)
+        .bind(tenant_id)
+        .bind(filter.category)
+        .bind(filter.unread_only)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn count_unread(&self, user_id: UserId, tenant_id: TenantId) -> AppResult<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND tenant_id = $2 AND read_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(tenant_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Mark a notification as read, keeping the time it was first read
+    pub async fn mark_read(
+        &self,
+        id: Uuid,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> AppResult<Option<Notification>> {
+        let notification = sqlx::query_as::<_, Notification>(&format!(
+            "UPDATE notifications SET read_at = COALESCE(read_at, NOW())
+             WHERE id = $1 AND user_id = $2 AND tenant_id = $3
+             RETURNING {NOTIFICATION_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(notification)
+    }
+
+    pub async fn mark_all_read(&self, user_id: UserId, tenant_id: TenantId) -> AppResult<u64> {
+        let result = sqlx::query(
+            "UPDATE notifications SET read_at = NOW()
+             WHERE user_id = $1 AND tenant_id = $2 AND read_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(tenant_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
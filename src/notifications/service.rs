@@ -0,0 +1,380 @@
+use std::sync::Arc;
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::core::events::{DomainEvent, DomainEventType, EventBus};
+use crate::core::mailer::{EmailMessage, Mailer};
+use crate::identity::repository::IdentityRepository;
+use crate::payments::model::{PaymentResponse, PaymentStatus};
+use crate::shared::types::{AccountId, TenantId, UserId};
+use super::model::{
+    CategoryPreferences, ChannelPreferences, MarkAllReadResponse, NewNotification, Notification,
+    NotificationCategory, NotificationFeed, NotificationFilter, NotificationKind, NotificationPreferencesResponse,
+    UpdateNotificationPreferencesRequest,
+};
+use super::repository::NotificationRepository;
+
+/// The parts of an `account.closed` event a notification needs
+#[derive(Deserialize)]
+struct ClosedAccount {
+    account_id: AccountId,
+}
+
+/// Delivers notifications to users on the channels they have chosen, and
+/// serves the in-app notification feed
+#[derive(Clone)]
+pub struct NotificationService {
+    repository: NotificationRepository,
+    identity_repository: IdentityRepository,
+    mailer: Arc<dyn Mailer>,
+    event_bus: EventBus,
+    audit_logger: AuditLogger,
+}
+
+impl NotificationService {
+    pub fn new(
+        repository: NotificationRepository,
+        identity_repository: IdentityRepository,
+        mailer: Arc<dyn Mailer>,
+        event_bus: EventBus,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            identity_repository,
+            mailer,
+            event_bus,
+            audit_logger,
+        }
+    }
+
+    /// Deliver a notification. Returns the in-app notification when one was
+    /// recorded; a notification already recorded for the same source is not
+    /// delivered again.
+    pub async fn dispatch(&self, notification: NewNotification) -> AppResult<Option<Notification>> {
+        let Some(recipient) = self.repository.find_recipient(notification.user_id).await? else {
+            return Ok(None);
+        };
+        let category = notification.kind.category();
+        let channels = self
+            .repository
+            .find_preferences(recipient.user_id)
+            .await?
+            .into_iter()
+            .find(|preference| preference.category == category)
+            .map(|preference| preference.channels())
+            .unwrap_or_else(|| ChannelPreferences::default_for(category));
+
+        let notification_id = Uuid::new_v4();
+        let mut created = None;
+        if channels.in_app {
+            let record = Notification {
+                id: notification_id,
+                user_id: recipient.user_id,
+                tenant_id: recipient.tenant_id,
+                category,
+                kind: notification.kind,
+                title: notification.title.clone(),
+                body: notification.body.clone(),
+                data: notification.data.clone(),
+                source_id: notification.source_id,
+                read_at: None,
+                created_at: Utc::now(),
+            };
+            created = self.repository.create(&record).await?;
+            if created.is_none() {
+                return Ok(None);
+            }
+        }
+
+        if channels.email {
+            let message = EmailMessage {
+                to: recipient.email.clone(),
+                subject: notification.title.clone(),
+                body: format!("Hi {},\n\n{}\n", recipient.first_name, notification.body),
+            };
+            // Other channels still deliver if the email cannot be sent
+            if let Err(e) = self.mailer.send(message).await {
+                tracing::error!(user_id = %recipient.user_id, "Failed to email {:?} notification: {}", notification.kind, e);
+            }
+        }
+
+        if channels.webhook && recipient.tenant_id.is_some() {
+            self.event_bus.publish(DomainEvent::new(
+                DomainEventType::NotificationCreated,
+                recipient.tenant_id,
+                notification.account_ids,
+                json!({
+                    "notification_id": notification_id,
+                    "user_id": recipient.user_id,
+                    "category": category,
+                    "kind": notification.kind,
+                    "title": notification.title,
+                    "body": notification.body,
+                    "data": notification.data,
+                }),
+            ));
+        }
+
+        Ok(created)
+    }
+
+    /// Turn a domain event into notifications for the owners of the accounts
+    /// it concerns
+    pub async fn handle_event(&self, event: &DomainEvent) -> AppResult<()> {
+        match event.event_type {
+            DomainEventType::PaymentStatusChanged => {
+                let Ok(payment) = serde_json::from_value::<PaymentResponse>(event.data.clone()) else {
+                    return Ok(());
+                };
+                if matches!(payment.status, PaymentStatus::Completed) {
+                    self.notify_payment_completed(&payment).await?;
+                }
+            }
+            DomainEventType::AccountClosed => {
+                let Ok(closed) = serde_json::from_value::<ClosedAccount>(event.data.clone()) else {
+                    return Ok(());
+                };
+                for (account_id, user_id) in self.repository.find_account_owners(&[closed.account_id]).await? {
+                    self.dispatch(NewNotification {
+                        user_id,
+                        kind: NotificationKind::AccountClosed,
+                        title: "Your account has been closed".to_string(),
+                        body: "One of your accounts has been closed. Any remaining balance was moved to the account you chose.".to_string(),
+                        data: event.data.clone(),
+                        account_ids: vec![account_id],
+                        source_id: Some(event.id),
+                    })
+                    .await?;
+                }
+            }
+            DomainEventType::TransactionCreated
+            | DomainEventType::BalanceUpdated
+            | DomainEventType::NotificationCreated
+            | DomainEventType::CreditCheckCompleted => {}
+        }
+        Ok(())
+    }
+
+    /// Alert the owner of an account that it was frozen for suspected fraud
+    pub async fn notify_fraud_alert(&self, user_id: UserId, account_id: Uuid) -> AppResult<()> {
+        self.dispatch(NewNotification {
+            user_id,
+            kind: NotificationKind::FraudAlert,
+            title: "Suspicious activity on your account".to_string(),
+            body: "We have frozen one of your accounts because of suspected fraud. Outgoing payments are blocked until we have reviewed the activity.".to_string(),
+            data: json!({ "account_id": account_id }),
+            account_ids: vec![account_id],
+            source_id: None,
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Tell a user about a sign-in to their account, so they can react if it
+    /// was not them
+    pub async fn notify_login_alert(
+        &self,
+        user_id: UserId,
+        ip_address: &str,
+        user_agent: Option<&str>,
+    ) -> AppResult<()> {
+        self.dispatch(NewNotification {
+            user_id,
+            kind: NotificationKind::LoginAlert,
+            title: "New sign-in to your account".to_string(),
+            body: format!(
+                "Your account was signed in to from {}. If this was not you, contact support straight away.",
+                ip_address
+            ),
+            data: json!({ "ip_address": ip_address, "user_agent": user_agent }),
+            account_ids: Vec::new(),
+            source_id: None,
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Channel preferences for every category, with defaults for categories
+    /// the user has not set
+    pub async fn get_preferences(
+        &self,
+        user_id: UserId,
+        tenant_id: TenantId,
+    ) -> AppResult<NotificationPreferencesResponse> {
+        self.ensure_user_in_tenant(user_id, tenant_id).await?;
+        let stored = self.repository.find_preferences(user_id).await?;
+
+        let preferences = NotificationCategory::ALL
+            .into_iter()
+            .map(|category| {
+                let preference = stored.iter().find(|preference| preference.category == category);
+                CategoryPreferences {
+                    category,
+                    channels: preference
+                        .map(|preference| preference.channels())
+                        .unwrap_or_else(|| ChannelPreferences::default_for(category)),
+                    updated_at: preference.map(|preference| preference.updated_at),
+                }
+            })
+            .collect();
+
+        Ok(NotificationPreferencesResponse { user_id, preferences })
+    }
+
+    /// Change the channels of one or more categories. Security alerts always
+    /// stay in the notification feed.
+    pub async fn update_preferences(
+        &self,
+        request: UpdateNotificationPreferencesRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<NotificationPreferencesResponse> {
+        let current = self.get_preferences(request.user_id, tenant_id).await?;
+
+        let mut updated = Vec::with_capacity(request.preferences.len());
+        for update in &request.preferences {
+            if request.preferences.iter().filter(|other| other.category == update.category).count() > 1 {
+                return Err(AppError::Validation(format!(
+                    "Category {:?} is listed more than once",
+                    update.category
+                )));
+            }
+            if update.category == NotificationCategory::Security && update.in_app == Some(false) {
+                return Err(AppError::Validation(
+                    "In-app delivery cannot be turned off for security alerts".to_string(),
+                ));
+            }
+
+            let existing = current
+                .preferences
+                .iter()
+                .find(|preference| preference.category == update.category)
+                .map(|preference| preference.channels)
+                .unwrap_or_else(|| ChannelPreferences::default_for(update.category));
+            updated.push((
+                update.category,
+                ChannelPreferences {
+                    email: update.email.unwrap_or(existing.email),
+                    webhook: update.webhook.unwrap_or(existing.webhook),
+                    in_app: update.in_app.unwrap_or(existing.in_app),
+                },
+            ));
+        }
+
+        for (category, channels) in &updated {
+            self.repository
+                .upsert_preference(request.user_id, *category, *channels)
+                .await?;
+        }
+
+        let event = AuditEvent::new(AuditEventType::NotificationPreferencesUpdated)
+            .user_id(actor_id)
+            .resource(format!("user:{}", request.user_id))
+            .action("update_notification_preferences".to_string())
+            .metadata("preferences".to_string(), json!(updated
+                .iter()
+                .map(|(category, channels)| json!({ "category": category, "channels": channels }))
+                .collect::<Vec<_>>()))
+            .compliance_tag("NOTIFICATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        self.get_preferences(request.user_id, tenant_id).await
+    }
+
+    /// A page of the user's notification feed, newest first
+    pub async fn get_feed(
+        &self,
+        filter: NotificationFilter,
+        tenant_id: TenantId,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<NotificationFeed> {
+        self.ensure_user_in_tenant(filter.user_id, tenant_id).await?;
+        let limit = limit.clamp(1, 100);
+        let page = page.max(1);
+
+        let notifications = self.repository.find_feed(&filter, tenant_id, page, limit).await?;
+        let total = self.repository.count_feed(&filter, tenant_id).await?.max(0) as u64;
+        let unread_count = self.repository.count_unread(filter.user_id, tenant_id).await?.max(0) as u64;
+
+        Ok(NotificationFeed {
+            data: notifications,
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit as u64) as u32,
+            unread_count,
+        })
+    }
+
+    pub async fn mark_read(&self, id: Uuid, user_id: UserId, tenant_id: TenantId) -> AppResult<Notification> {
+        self.repository
+            .mark_read(id, user_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Notification not found".to_string()))
+    }
+
+    pub async fn mark_all_read(&self, user_id: UserId, tenant_id: TenantId) -> AppResult<MarkAllReadResponse> {
+        self.ensure_user_in_tenant(user_id, tenant_id).await?;
+        let marked = self.repository.mark_all_read(user_id, tenant_id).await?;
+        Ok(MarkAllReadResponse { marked })
+    }
+
+    async fn notify_payment_completed(&self, payment: &PaymentResponse) -> AppResult<()> {
+        let account_ids: Vec<AccountId> = std::iter::once(payment.from_account_id)
+            .chain(payment.to_account_id)
+            .collect();
+        let amount = format_amount(payment.amount, &payment.currency);
+        let data = json!({
+            "payment_id": payment.id,
+            "amount": payment.amount,
+            "currency": payment.currency,
+            "reference": payment.reference,
+        });
+
+        for (account_id, user_id) in self.repository.find_account_owners(&account_ids).await? {
+            let (kind, title, body) = if account_id == payment.from_account_id {
+                (
+                    NotificationKind::PaymentCompleted,
+                    "Payment sent",
+                    format!("Your payment of {} ({}) has completed.", amount, payment.reference),
+                )
+            } else {
+                (
+                    NotificationKind::PaymentReceived,
+                    "Payment received",
+                    format!("You received {} ({}).", amount, payment.reference),
+                )
+            };
+            self.dispatch(NewNotification {
+                user_id,
+                kind,
+                title: title.to_string(),
+                body,
+                data: data.clone(),
+                account_ids: vec![account_id],
+                source_id: Some(payment.id),
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn ensure_user_in_tenant(&self, user_id: UserId, tenant_id: TenantId) -> AppResult<()> {
+        if !self.identity_repository.user_in_tenant(user_id, tenant_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Format minor units as a decimal amount with currency code
+fn format_amount(amount: i64, currency: &str) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+    let amount = amount.unsigned_abs();
+    format!("{}{}.{:02} {}", sign, amount / 100, amount % 100, currency)
+}
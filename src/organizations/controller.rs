@@ -0,0 +1,528 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::TenantId;
+use super::members::organization_member_service;
+use super::model::{
+    CreateInvitationRequest, CreateOrganizationRequest, InvitationDetails, InvitationResponse, Organization,
+    OrganizationMember, OrganizationProject, ProjectApiVersionPolicy, ProjectDuplicatePaymentRules, ProjectIpRules,
+    ProjectLocale, SetDuplicatePaymentRulesRequest, SetProjectApiVersionPolicyRequest, SetProjectIpRulesRequest,
+    SetProjectLocaleRequest, UpdateMemberRoleRequest,
+};
+use super::service::organization_service;
+
+/// Get the organization the caller's token acts for
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/current",
+    tag = "organizations",
+    responses(
+        (status = 200, description = "Organization the token acts for", body = Organization),
+        (status = 404, description = "Organization not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_current_organization(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+) -> AppResult<Json<ApiResponse<Organization>>> {
+    let organization = organization_service(&state)
+        .get_organization(claims.tenant_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Organization retrieved successfully", organization)))
+}
+
+/// List the organizations the calling developer belongs to
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations",
+    tag = "organizations",
+    responses(
+        (status = 200, description = "Organizations the developer belongs to", body = [Organization])
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_organizations(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+) -> AppResult<Json<ApiResponse<Vec<Organization>>>> {
+    let organizations = organization_member_service(&state)
+        .list_organizations(claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Organizations retrieved successfully", organizations)))
+}
+
+/// Create an organization owned by the calling developer
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations",
+    tag = "organizations",
+    request_body = CreateOrganizationRequest,
+    responses(
+        (status = 201, description = "Organization created", body = Organization),
+        (status = 400, description = "Invalid request")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_organization(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<CreateOrganizationRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<Organization>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let organization = organization_member_service(&state)
+        .create_organization(request, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Organization created successfully", organization)),
+    ))
+}
+
+/// List an organization's members
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/members",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID")),
+    responses(
+        (status = 200, description = "Organization members", body = [OrganizationMember]),
+        (status = 403, description = "Caller is not a member"),
+        (status = 404, description = "Organization not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_members(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(organization_id): Path<TenantId>,
+) -> AppResult<Json<ApiResponse<Vec<OrganizationMember>>>> {
+    let members = organization_member_service(&state)
+        .list_members(organization_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Members retrieved successfully", members)))
+}
+
+/// Change a member's role (owners and admins)
+#[utoipa::path(
+    patch,
+    path = "/api/v1/organizations/{organization_id}/members/{developer_id}",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("developer_id" = Uuid, Path, description = "Member's developer ID")),
+    request_body = UpdateMemberRoleRequest,
+    responses(
+        (status = 200, description = "Member role changed", body = OrganizationMember),
+        (status = 400, description = "The organization would be left without an owner"),
+        (status = 403, description = "Caller cannot manage members or change ownership"),
+        (status = 404, description = "Member not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_member_role(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, developer_id)): Path<(TenantId, Uuid)>,
+    ApiJson(request): ApiJson<UpdateMemberRoleRequest>,
+) -> AppResult<Json<ApiResponse<OrganizationMember>>> {
+    let member = organization_member_service(&state)
+        .update_member_role(organization_id, developer_id, request.role, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Member role updated successfully", member)))
+}
+
+/// Remove a member, or leave the organization
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/{organization_id}/members/{developer_id}",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("developer_id" = Uuid, Path, description = "Member's developer ID")),
+    responses(
+        (status = 200, description = "Member removed"),
+        (status = 400, description = "The organization would be left without an owner"),
+        (status = 403, description = "Caller cannot manage members or remove an owner"),
+        (status = 404, description = "Member not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove_member(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, developer_id)): Path<(TenantId, Uuid)>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    organization_member_service(&state)
+        .remove_member(organization_id, developer_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success_no_data("Member removed successfully")))
+}
+
+/// List invitations sent for an organization (owners and admins)
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/invitations",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID")),
+    responses(
+        (status = 200, description = "Invitations sent for the organization", body = [InvitationResponse]),
+        (status = 403, description = "Caller cannot manage members")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_invitations(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(organization_id): Path<TenantId>,
+) -> AppResult<Json<ApiResponse<Vec<InvitationResponse>>>> {
+    let invitations = organization_member_service(&state)
+        .list_invitations(organization_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Invitations retrieved successfully", invitations)))
+}
+
+/// Invite a developer by email (owners and admins)
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/{organization_id}/invitations",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID")),
+    request_body = CreateInvitationRequest,
+    responses(
+        (status = 201, description = "Invitation sent", body = InvitationResponse),
+        (status = 400, description = "Invalid request; invitations cannot grant ownership"),
+        (status = 403, description = "Caller cannot manage members")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_invitation(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(organization_id): Path<TenantId>,
+    ApiJson(request): ApiJson<CreateInvitationRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<InvitationResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let invitation = organization_member_service(&state)
+        .invite(organization_id, request, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Invitation sent successfully", invitation)),
+    ))
+}
+
+/// Revoke an open invitation (owners and admins)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/{organization_id}/invitations/{invitation_id}",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("invitation_id" = Uuid, Path, description = "Invitation ID")),
+    responses(
+        (status = 200, description = "Invitation revoked"),
+        (status = 403, description = "Caller cannot manage members"),
+        (status = 404, description = "Open invitation not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn revoke_invitation(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, invitation_id)): Path<(TenantId, Uuid)>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    organization_member_service(&state)
+        .revoke_invitation(organization_id, invitation_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success_no_data("Invitation revoked successfully")))
+}
+
+/// Get what an invitation link is for. Public; the token is the credential.
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/invitations/{token}",
+    tag = "organizations",
+    params(("token" = String, Path, description = "Invitation token from the email link")),
+    responses(
+        (status = 200, description = "What the invitation is for", body = InvitationDetails),
+        (status = 404, description = "Invitation not found")
+    )
+)]
+pub async fn get_invitation(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> AppResult<Json<ApiResponse<InvitationDetails>>> {
+    let details = organization_member_service(&state).get_invitation(&token).await?;
+    Ok(Json(ApiResponse::success("Invitation retrieved successfully", details)))
+}
+
+/// Accept an invitation as the signed-in developer
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/invitations/{token}/accept",
+    tag = "organizations",
+    params(("token" = String, Path, description = "Invitation token from the email link")),
+    responses(
+        (status = 200, description = "Invitation accepted; the caller is now a member", body = OrganizationMember),
+        (status = 400, description = "Invitation has expired or is no longer valid"),
+        (status = 403, description = "Invitation was sent to a different email address"),
+        (status = 404, description = "Invitation not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn accept_invitation(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(token): Path<String>,
+) -> AppResult<Json<ApiResponse<OrganizationMember>>> {
+    let member = organization_member_service(&state)
+        .accept_invitation(&token, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Invitation accepted successfully", member)))
+}
+
+/// List the projects an organization owns (members only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/projects",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID")),
+    responses(
+        (status = 200, description = "Projects the organization owns", body = [OrganizationProject]),
+        (status = 403, description = "Caller is not a member")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_projects(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(organization_id): Path<TenantId>,
+) -> AppResult<Json<ApiResponse<Vec<OrganizationProject>>>> {
+    let projects = organization_member_service(&state)
+        .list_projects(organization_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Projects retrieved successfully", projects)))
+}
+
+/// Get the networks a project's tokens may be used from (members only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/projects/{project_id}/ip-rules",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("project_id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Project IP rules", body = ProjectIpRules),
+        (status = 404, description = "Organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_project_ip_rules(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, project_id)): Path<(TenantId, Uuid)>,
+) -> AppResult<Json<ApiResponse<ProjectIpRules>>> {
+    let rules = organization_member_service(&state)
+        .get_project_ip_rules(organization_id, project_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Project IP rules retrieved successfully", rules)))
+}
+
+/// Replace a project's IP allowlist and denylist (owners and admins)
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/{organization_id}/projects/{project_id}/ip-rules",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("project_id" = Uuid, Path, description = "Project ID")),
+    request_body = SetProjectIpRulesRequest,
+    responses(
+        (status = 200, description = "Project IP rules updated", body = ProjectIpRules),
+        (status = 400, description = "Invalid network"),
+        (status = 403, description = "Caller cannot manage the organization"),
+        (status = 404, description = "Organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_project_ip_rules(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, project_id)): Path<(TenantId, Uuid)>,
+    ApiJson(request): ApiJson<SetProjectIpRulesRequest>,
+) -> AppResult<Json<ApiResponse<ProjectIpRules>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let rules = organization_member_service(&state)
+        .set_project_ip_rules(organization_id, project_id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Project IP rules updated successfully", rules)))
+}
+
+/// Get how a project treats repeated payments (members only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/projects/{project_id}/duplicate-payment-rules",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("project_id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Project duplicate payment rules", body = ProjectDuplicatePaymentRules),
+        (status = 404, description = "Organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_project_duplicate_rules(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, project_id)): Path<(TenantId, Uuid)>,
+) -> AppResult<Json<ApiResponse<ProjectDuplicatePaymentRules>>> {
+    let rules = organization_member_service(&state)
+        .get_project_duplicate_rules(organization_id, project_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Duplicate payment rules retrieved successfully", rules)))
+}
+
+/// Set whether a project's repeated payments are flagged, blocked or let
+/// through, and the detection window (owners and admins)
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/{organization_id}/projects/{project_id}/duplicate-payment-rules",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("project_id" = Uuid, Path, description = "Project ID")),
+    request_body = SetDuplicatePaymentRulesRequest,
+    responses(
+        (status = 200, description = "Duplicate payment rules updated", body = ProjectDuplicatePaymentRules),
+        (status = 400, description = "Invalid window"),
+        (status = 403, description = "Caller cannot manage the organization"),
+        (status = 404, description = "Organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_project_duplicate_rules(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, project_id)): Path<(TenantId, Uuid)>,
+    ApiJson(request): ApiJson<SetDuplicatePaymentRulesRequest>,
+) -> AppResult<Json<ApiResponse<ProjectDuplicatePaymentRules>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let rules = organization_member_service(&state)
+        .set_project_duplicate_rules(organization_id, project_id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Duplicate payment rules updated successfully", rules)))
+}
+
+/// Get the language a project's API messages default to (members only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/projects/{project_id}/locale",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("project_id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Project default locale", body = ProjectLocale),
+        (status = 404, description = "Organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_project_locale(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, project_id)): Path<(TenantId, Uuid)>,
+) -> AppResult<Json<ApiResponse<ProjectLocale>>> {
+    let locale = organization_member_service(&state)
+        .get_project_locale(organization_id, project_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Project locale retrieved successfully", locale)))
+}
+
+/// Set the language a project's API messages default to when a request's
+/// Accept-Language names no supported one (owners and admins)
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/{organization_id}/projects/{project_id}/locale",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("project_id" = Uuid, Path, description = "Project ID")),
+    request_body = SetProjectLocaleRequest,
+    responses(
+        (status = 200, description = "Project default locale updated", body = ProjectLocale),
+        (status = 400, description = "Unsupported locale"),
+        (status = 403, description = "Caller cannot manage the organization"),
+        (status = 404, description = "Organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_project_locale(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, project_id)): Path<(TenantId, Uuid)>,
+    ApiJson(request): ApiJson<SetProjectLocaleRequest>,
+) -> AppResult<Json<ApiResponse<ProjectLocale>>> {
+    let locale = organization_member_service(&state)
+        .set_project_locale(organization_id, project_id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Project locale updated successfully", locale)))
+}
+
+/// Get which API versions a project is served (members only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/projects/{project_id}/api-versions",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("project_id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Project API version policy", body = ProjectApiVersionPolicy),
+        (status = 404, description = "Organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_project_api_version_policy(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, project_id)): Path<(TenantId, Uuid)>,
+) -> AppResult<Json<ApiResponse<ProjectApiVersionPolicy>>> {
+    let policy = organization_member_service(&state)
+        .get_project_api_version_policy(organization_id, project_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Project API versions retrieved successfully", policy)))
+}
+
+/// Set the oldest API version a project is served and when deprecated
+/// versions stop being served to it (owners and admins)
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/{organization_id}/projects/{project_id}/api-versions",
+    tag = "organizations",
+    params(("organization_id" = Uuid, Path, description = "Organization ID"), ("project_id" = Uuid, Path, description = "Project ID")),
+    request_body = SetProjectApiVersionPolicyRequest,
+    responses(
+        (status = 200, description = "Project API version policy updated", body = ProjectApiVersionPolicy),
+        (status = 400, description = "Unknown API version"),
+        (status = 403, description = "Caller cannot manage the organization"),
+        (status = 404, description = "Organization or project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn set_project_api_version_policy(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((organization_id, project_id)): Path<(TenantId, Uuid)>,
+    ApiJson(request): ApiJson<SetProjectApiVersionPolicyRequest>,
+) -> AppResult<Json<ApiResponse<ProjectApiVersionPolicy>>> {
+    let policy = organization_member_service(&state)
+        .set_project_api_version_policy(organization_id, project_id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Project API versions updated successfully", policy)))
+}
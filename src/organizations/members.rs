@@ -0,0 +1,592 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::crypto::{generate_token, hash_token};
+use crate::core::error::{AppError, AppResult};
+use crate::core::i18n::ProjectLocaleCache;
+use crate::core::ip_filter::IpPolicyCache;
+use crate::core::mailer::{EmailMessage, Mailer};
+use crate::core::versioning::ProjectVersionRulesCache;
+use crate::core::AppState;
+use crate::shared::types::TenantId;
+use super::model::{
+    CreateInvitationRequest, CreateOrganizationRequest, InvitationDetails, InvitationResponse, InvitationStatus,
+    Organization, OrganizationInvitation, OrganizationMember, OrganizationProject, OrganizationRole,
+    ProjectApiVersionPolicy, ProjectDuplicatePaymentRules, ProjectIpRules, ProjectLocale,
+    SetDuplicatePaymentRulesRequest, SetProjectApiVersionPolicyRequest, SetProjectIpRulesRequest,
+    SetProjectLocaleRequest,
+};
+use super::repository::OrganizationRepository;
+use super::service::parse_networks;
+
+/// Manages who belongs to an organization on the developer dashboard.
+///
+/// Owners and admins invite developers by email and manage members; only an
+/// owner can grant or take away ownership, and an organization always keeps
+/// at least one owner. Invitation links carry a random token of which only
+/// the hash is stored.
+pub struct OrganizationMemberService {
+    repository: OrganizationRepository,
+    mailer: Arc<dyn Mailer>,
+    audit_logger: AuditLogger,
+    ip_policy_cache: IpPolicyCache,
+    locale_cache: ProjectLocaleCache,
+    version_cache: ProjectVersionRulesCache,
+    invitation_validity_hours: i64,
+    public_base_url: String,
+}
+
+impl OrganizationMemberService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: OrganizationRepository,
+        mailer: Arc<dyn Mailer>,
+        audit_logger: AuditLogger,
+        ip_policy_cache: IpPolicyCache,
+        locale_cache: ProjectLocaleCache,
+        version_cache: ProjectVersionRulesCache,
+        invitation_validity_hours: i64,
+        public_base_url: String,
+    ) -> Self {
+        Self {
+            repository,
+            mailer,
+            audit_logger,
+            ip_policy_cache,
+            locale_cache,
+            version_cache,
+            invitation_validity_hours,
+            public_base_url,
+        }
+    }
+
+    /// Create an organization owned by the calling developer
+    pub async fn create_organization(
+        &self,
+        request: CreateOrganizationRequest,
+        developer_id: Uuid,
+    ) -> AppResult<Organization> {
+        let organization = self.repository.create(request.name.trim(), developer_id).await?;
+
+        let event = AuditEvent::new(AuditEventType::OrganizationCreated)
+            .user_id(developer_id)
+            .resource(format!("organization:{}", organization.id))
+            .action("create".to_string())
+            .compliance_tag("ORGANIZATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(organization)
+    }
+
+    /// Organizations the developer is a member of
+    pub async fn list_organizations(&self, developer_id: Uuid) -> AppResult<Vec<Organization>> {
+        self.repository.list_for_developer(developer_id).await
+    }
+
+    pub async fn list_members(
+        &self,
+        organization_id: TenantId,
+        developer_id: Uuid,
+    ) -> AppResult<Vec<OrganizationMember>> {
+        self.require_member(organization_id, developer_id).await?;
+        self.repository.list_members(organization_id).await
+    }
+
+    /// Projects owned by the organization, visible to any member
+    pub async fn list_projects(
+        &self,
+        organization_id: TenantId,
+        developer_id: Uuid,
+    ) -> AppResult<Vec<OrganizationProject>> {
+        self.require_member(organization_id, developer_id).await?;
+        self.repository.list_projects(organization_id).await
+    }
+
+    /// A project's IP rules, visible to any member
+    pub async fn get_project_ip_rules(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        developer_id: Uuid,
+    ) -> AppResult<ProjectIpRules> {
+        self.require_member(organization_id, developer_id).await?;
+        self.repository
+            .find_organization_project_ip_rules(organization_id, project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))
+    }
+
+    /// Replace the networks a project's tokens may be used from (owners and
+    /// admins). Rules are stored in canonical CIDR form and apply once the
+    /// cached policy is dropped, immediately on this instance.
+    pub async fn set_project_ip_rules(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        request: SetProjectIpRulesRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ProjectIpRules> {
+        self.require_manager(organization_id, actor_id).await?;
+        let canonical = |networks: &[String]| -> AppResult<Vec<String>> {
+            let mut networks: Vec<String> = parse_networks(networks)?.iter().map(ToString::to_string).collect();
+            networks.sort();
+            networks.dedup();
+            Ok(networks)
+        };
+        let allow = canonical(&request.allow)?;
+        let deny = canonical(&request.deny)?;
+
+        let rules = self
+            .repository
+            .set_project_ip_rules(organization_id, project_id, &allow, &deny, actor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        self.ip_policy_cache.invalidate(project_id);
+
+        let event = AuditEvent::new(AuditEventType::ProjectIpRulesChanged)
+            .user_id(actor_id)
+            .project_id(project_id)
+            .resource(format!("project:{}", project_id))
+            .action("set_ip_rules".to_string())
+            .metadata("allow".to_string(), serde_json::json!(rules.allow))
+            .metadata("deny".to_string(), serde_json::json!(rules.deny))
+            .compliance_tag("ORGANIZATIONS".to_string())
+            .compliance_tag("SECURITY".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(rules)
+    }
+
+    /// A project's default locale, visible to any member
+    pub async fn get_project_locale(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        developer_id: Uuid,
+    ) -> AppResult<ProjectLocale> {
+        self.require_member(organization_id, developer_id).await?;
+        self.repository
+            .find_organization_project_locale(organization_id, project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))
+    }
+
+    /// Change the language a project's responses default to (owners and
+    /// admins). Requests naming a supported language still get it.
+    pub async fn set_project_locale(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        request: SetProjectLocaleRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ProjectLocale> {
+        self.require_manager(organization_id, actor_id).await?;
+        let locale = self
+            .repository
+            .set_project_locale(organization_id, project_id, request.default_locale, actor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        self.locale_cache.invalidate(project_id);
+
+        let event = AuditEvent::new(AuditEventType::ProjectLocaleChanged)
+            .user_id(actor_id)
+            .project_id(project_id)
+            .resource(format!("project:{}", project_id))
+            .action("set_locale".to_string())
+            .metadata("default_locale".to_string(), serde_json::json!(locale.default_locale))
+            .compliance_tag("ORGANIZATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(locale)
+    }
+
+    /// Which API versions a project is served, visible to any member
+    pub async fn get_project_api_version_policy(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        developer_id: Uuid,
+    ) -> AppResult<ProjectApiVersionPolicy> {
+        self.require_member(organization_id, developer_id).await?;
+        self.repository
+            .find_project_api_version_policy(organization_id, project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))
+    }
+
+    /// Change which API versions a project is served (owners and admins):
+    /// raising the minimum refuses older versions at once, and a sunset
+    /// date moves when deprecated versions stop being served to it
+    pub async fn set_project_api_version_policy(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        request: SetProjectApiVersionPolicyRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ProjectApiVersionPolicy> {
+        self.require_manager(organization_id, actor_id).await?;
+        let policy = self
+            .repository
+            .set_project_api_version_policy(
+                organization_id,
+                project_id,
+                request.min_api_version,
+                request.api_sunset_on,
+                actor_id,
+            )
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+        self.version_cache.invalidate(project_id);
+
+        let event = AuditEvent::new(AuditEventType::ProjectApiVersionPolicyChanged)
+            .user_id(actor_id)
+            .project_id(project_id)
+            .resource(format!("project:{}", project_id))
+            .action("set_api_versions".to_string())
+            .metadata("min_api_version".to_string(), serde_json::json!(policy.min_api_version))
+            .metadata("api_sunset_on".to_string(), serde_json::json!(policy.api_sunset_on))
+            .compliance_tag("ORGANIZATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(policy)
+    }
+
+    /// A project's duplicate payment rules, visible to any member
+    pub async fn get_project_duplicate_rules(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        developer_id: Uuid,
+    ) -> AppResult<ProjectDuplicatePaymentRules> {
+        self.require_member(organization_id, developer_id).await?;
+        self.repository
+            .find_project_duplicate_rules(organization_id, project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))
+    }
+
+    /// Choose whether a project's repeated payments are flagged, blocked or
+    /// let through, and how far back to look (owners and admins)
+    pub async fn set_project_duplicate_rules(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        request: SetDuplicatePaymentRulesRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ProjectDuplicatePaymentRules> {
+        self.require_manager(organization_id, actor_id).await?;
+        let rules = self
+            .repository
+            .set_project_duplicate_rules(organization_id, project_id, request.action, request.window_minutes, actor_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::ProjectDuplicatePaymentRulesChanged)
+            .user_id(actor_id)
+            .project_id(project_id)
+            .resource(format!("project:{}", project_id))
+            .action("set_duplicate_payment_rules".to_string())
+            .metadata("action".to_string(), serde_json::json!(rules.action))
+            .metadata("window_minutes".to_string(), serde_json::json!(rules.window_minutes))
+            .compliance_tag("ORGANIZATIONS".to_string())
+            .compliance_tag("PAYMENTS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(rules)
+    }
+
+    /// Change a member's role. Ownership can only be granted or taken away
+    /// by an owner.
+    pub async fn update_member_role(
+        &self,
+        organization_id: TenantId,
+        member_id: Uuid,
+        role: OrganizationRole,
+        actor_id: Uuid,
+    ) -> AppResult<OrganizationMember> {
+        let actor_role = self.require_manager(organization_id, actor_id).await?;
+        let member = self.find_member(organization_id, member_id).await?;
+
+        if (role == OrganizationRole::Owner || member.role == OrganizationRole::Owner)
+            && actor_role != OrganizationRole::Owner
+        {
+            return Err(AppError::Authorization(
+                "Only an owner can change ownership of the organization".to_string(),
+            ));
+        }
+        if !self
+            .repository
+            .update_member_role(organization_id, member_id, role)
+            .await?
+        {
+            return Err(AppError::BadRequest(
+                "An organization must keep at least one owner".to_string(),
+            ));
+        }
+
+        let event = AuditEvent::new(AuditEventType::OrganizationMemberRoleChanged)
+            .user_id(actor_id)
+            .resource(format!("organization:{}", organization_id))
+            .action("change_role".to_string())
+            .metadata("member_id".to_string(), serde_json::json!(member_id))
+            .metadata("previous_role".to_string(), serde_json::json!(member.role))
+            .metadata("role".to_string(), serde_json::json!(role))
+            .compliance_tag("ORGANIZATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        self.find_member(organization_id, member_id).await
+    }
+
+    /// Remove a member. Members may always leave; removing someone else
+    /// needs an owner or admin, and only an owner can remove an owner.
+    pub async fn remove_member(&self, organization_id: TenantId, member_id: Uuid, actor_id: Uuid) -> AppResult<()> {
+        let member = self.find_member(organization_id, member_id).await?;
+        if member_id != actor_id {
+            let actor_role = self.require_manager(organization_id, actor_id).await?;
+            if member.role == OrganizationRole::Owner && actor_role != OrganizationRole::Owner {
+                return Err(AppError::Authorization("Only an owner can remove an owner".to_string()));
+            }
+        }
+
+        if !self.repository.remove_member(organization_id, member_id).await? {
+            return Err(AppError::BadRequest(
+                "An organization must keep at least one owner".to_string(),
+            ));
+        }
+
+        let event = AuditEvent::new(AuditEventType::OrganizationMemberRemoved)
+            .user_id(actor_id)
+            .resource(format!("organization:{}", organization_id))
+            .action("remove_member".to_string())
+            .metadata("member_id".to_string(), serde_json::json!(member_id))
+            .metadata("role".to_string(), serde_json::json!(member.role))
+            .compliance_tag("ORGANIZATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(())
+    }
+
+    /// Email an invitation link. Any earlier open invitation for the same
+    /// address stops working.
+    pub async fn invite(
+        &self,
+        organization_id: TenantId,
+        request: CreateInvitationRequest,
+        actor_id: Uuid,
+    ) -> AppResult<InvitationResponse> {
+        self.require_manager(organization_id, actor_id).await?;
+        if request.role == OrganizationRole::Owner {
+            return Err(AppError::Validation(
+                "Invitations cannot grant ownership; promote the member after they join".to_string(),
+            ));
+        }
+        let organization = self.find_organization(organization_id).await?;
+
+        let token = generate_token();
+        let now = Utc::now();
+        let invitation = OrganizationInvitation {
+            id: Uuid::new_v4(),
+            organization_id,
+            email: request.email.trim().to_lowercase(),
+            role: request.role,
+            token_hash: hash_token(&token),
+            invited_by: actor_id,
+            expires_at: now + Duration::hours(self.invitation_validity_hours),
+            accepted_by: None,
+            accepted_at: None,
+            revoked_at: None,
+            created_at: now,
+        };
+        let invitation = self.repository.create_invitation(&invitation).await?;
+
+        self.mailer
+            .send(EmailMessage {
+                to: invitation.email.clone(),
+                subject: format!("You have been invited to join {}", organization.name),
+                body: format!(
+                    "Hello,\n\n\
+                     You have been invited to join {} on openBank as {}.\n\n\
+                     Sign in to your developer account and accept the invitation using the link below:\n{}\n\n\
+                     This link expires on {}.\n",
+                    organization.name,
+                    role_name(invitation.role),
+                    self.invitation_url(&token),
+                    invitation.expires_at.format("%Y-%m-%d %H:%M UTC"),
+                ),
+            })
+            .await?;
+
+        let event = AuditEvent::new(AuditEventType::OrganizationMemberInvited)
+            .user_id(actor_id)
+            .resource(format!("organization:{}", organization_id))
+            .action("invite".to_string())
+            .metadata("invitation_id".to_string(), serde_json::json!(invitation.id))
+            .metadata("role".to_string(), serde_json::json!(invitation.role))
+            .compliance_tag("ORGANIZATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(invitation.into())
+    }
+
+    pub async fn list_invitations(
+        &self,
+        organization_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<Vec<InvitationResponse>> {
+        self.require_manager(organization_id, actor_id).await?;
+        let invitations = self.repository.list_invitations(organization_id).await?;
+        Ok(invitations.into_iter().map(InvitationResponse::from).collect())
+    }
+
+    pub async fn revoke_invitation(
+        &self,
+        organization_id: TenantId,
+        invitation_id: Uuid,
+        actor_id: Uuid,
+    ) -> AppResult<()> {
+        self.require_manager(organization_id, actor_id).await?;
+        if !self
+            .repository
+            .revoke_invitation(organization_id, invitation_id)
+            .await?
+        {
+            return Err(AppError::NotFound("Open invitation not found".to_string()));
+        }
+
+        let event = AuditEvent::new(AuditEventType::OrganizationInvitationRevoked)
+            .user_id(actor_id)
+            .resource(format!("organization:{}", organization_id))
+            .action("revoke_invitation".to_string())
+            .metadata("invitation_id".to_string(), serde_json::json!(invitation_id))
+            .compliance_tag("ORGANIZATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(())
+    }
+
+    /// What an invitee is invited to, looked up by link token
+    pub async fn get_invitation(&self, token: &str) -> AppResult<InvitationDetails> {
+        let invitation = self.find_open_invitation(token).await?;
+        let organization = self.find_organization(invitation.organization_id).await?;
+
+        Ok(InvitationDetails {
+            organization_name: organization.name,
+            email: invitation.email,
+            role: invitation.role,
+            expires_at: invitation.expires_at,
+        })
+    }
+
+    /// Join the organization as the invited developer. The signed-in
+    /// developer's email must match the invited address.
+    pub async fn accept_invitation(
+        &self,
+        token: &str,
+        developer_id: Uuid,
+    ) -> AppResult<OrganizationMember> {
+        let invitation = self.find_open_invitation(token).await?;
+        let developer_email = self
+            .repository
+            .find_developer_email(developer_id)
+            .await?
+            .ok_or_else(|| AppError::Authentication("Developer not found".to_string()))?;
+        if !invitation.email.eq_ignore_ascii_case(developer_email.trim()) {
+            return Err(AppError::Authorization(
+                "This invitation was sent to a different email address".to_string(),
+            ));
+        }
+
+        if !self.repository.accept_invitation(&invitation, developer_id).await? {
+            return Err(AppError::BadRequest("This invitation is no longer valid".to_string()));
+        }
+
+        let event = AuditEvent::new(AuditEventType::OrganizationInvitationAccepted)
+            .user_id(developer_id)
+            .resource(format!("organization:{}", invitation.organization_id))
+            .action("accept_invitation".to_string())
+            .metadata("invitation_id".to_string(), serde_json::json!(invitation.id))
+            .metadata("role".to_string(), serde_json::json!(invitation.role))
+            .compliance_tag("ORGANIZATIONS".to_string());
+        self.audit_logger.log(event).await;
+
+        self.find_member(invitation.organization_id, developer_id).await
+    }
+
+    async fn find_organization(&self, organization_id: TenantId) -> AppResult<Organization> {
+        self.repository
+            .find_by_id(organization_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))
+    }
+
+    async fn find_member(&self, organization_id: TenantId, developer_id: Uuid) -> AppResult<OrganizationMember> {
+        self.repository
+            .find_member(organization_id, developer_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))
+    }
+
+    /// Non-members get not found so organization ids cannot be probed
+    async fn require_member(&self, organization_id: TenantId, developer_id: Uuid) -> AppResult<OrganizationRole> {
+        self.repository
+            .find_member_role(organization_id, developer_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))
+    }
+
+    async fn require_manager(&self, organization_id: TenantId, developer_id: Uuid) -> AppResult<OrganizationRole> {
+        let role = self.require_member(organization_id, developer_id).await?;
+        if !role.can_manage() {
+            return Err(AppError::Authorization(
+                "Only organization owners and admins can manage members".to_string(),
+            ));
+        }
+        Ok(role)
+    }
+
+    async fn find_open_invitation(&self, token: &str) -> AppResult<OrganizationInvitation> {
+        let invitation = self
+            .repository
+            .find_invitation_by_token_hash(&hash_token(token))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Invitation not found".to_string()))?;
+
+        match invitation.status() {
+            InvitationStatus::Pending => Ok(invitation),
+            InvitationStatus::Expired => Err(AppError::BadRequest("This invitation has expired".to_string())),
+            InvitationStatus::Accepted | InvitationStatus::Revoked => {
+                Err(AppError::BadRequest("This invitation is no longer valid".to_string()))
+            }
+        }
+    }
+
+    fn invitation_url(&self, token: &str) -> String {
+        format!(
+            "{}/api/v1/organizations/invitations/{}",
+            self.public_base_url.trim_end_matches('/'),
+            token
+        )
+    }
+}
+
+pub fn organization_member_service(state: &AppState) -> OrganizationMemberService {
+    OrganizationMemberService::new(
+        OrganizationRepository::new(state.postgres.clone()),
+        state.mailer.clone(),
+        state.audit_logger.clone(),
+        state.ip_policy_cache.clone(),
+        state.project_locale_cache.clone(),
+        state.project_version_cache.clone(),
+        state.config.organization_invitation_validity_hours,
+        state.config.public_base_url.clone(),
+    )
+}
+
+fn role_name(role: OrganizationRole) -> &'static str {
+    match role {
+        OrganizationRole::Owner => "an owner",
+        OrganizationRole::Admin => "an admin",
+        OrganizationRole::Member => "a member",
+    }
+}
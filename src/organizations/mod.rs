@@ -0,0 +1,52 @@
+pub mod controller;
+pub mod members;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{
+    routing::{delete, get, patch, post},
+    Router,
+};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(controller::list_organizations).post(controller::create_organization),
+        )
+        .route("/current", get(controller::get_current_organization))
+        .route("/invitations/:token", get(controller::get_invitation))
+        .route("/invitations/:token/accept", post(controller::accept_invitation))
+        .route("/:organization_id/members", get(controller::list_members))
+        .route(
+            "/:organization_id/members/:developer_id",
+            patch(controller::update_member_role).delete(controller::remove_member),
+        )
+        .route(
+            "/:organization_id/invitations",
+            get(controller::list_invitations).post(controller::create_invitation),
+        )
+        .route(
+            "/:organization_id/invitations/:invitation_id",
+            delete(controller::revoke_invitation),
+        )
+        .route("/:organization_id/projects", get(controller::list_projects))
+        .route(
+            "/:organization_id/projects/:project_id/ip-rules",
+            get(controller::get_project_ip_rules).put(controller::set_project_ip_rules),
+        )
+        .route(
+            "/:organization_id/projects/:project_id/duplicate-payment-rules",
+            get(controller::get_project_duplicate_rules).put(controller::set_project_duplicate_rules),
+        )
+        .route(
+            "/:organization_id/projects/:project_id/locale",
+            get(controller::get_project_locale).put(controller::set_project_locale),
+        )
+        .route(
+            "/:organization_id/projects/:project_id/api-versions",
+            get(controller::get_project_api_version_policy).put(controller::set_project_api_version_policy),
+        )
+}
@@ -0,0 +1,255 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::model::ProjectEnvironment;
+use crate::core::i18n::Locale;
+use crate::core::versioning::ApiVersion;
+use crate::payments::model::DuplicatePaymentAction;
+use crate::shared::types::TenantId;
+
+/// Organization model for database. Organizations are the tenant boundary.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Organization {
+    pub id: TenantId,
+    pub name: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The tenant a project currently belongs to
+#[derive(Debug, Clone, FromRow)]
+pub struct ProjectTenant {
+    pub organization_id: TenantId,
+    pub organization_active: bool,
+}
+
+/// A developer's role within an organization
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "organization_role", rename_all = "lowercase")]
+pub enum OrganizationRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+impl OrganizationRole {
+    /// Owners and admins manage members, invitations and projects
+    pub fn can_manage(&self) -> bool {
+        matches!(self, OrganizationRole::Owner | OrganizationRole::Admin)
+    }
+}
+
+/// Organization member with their developer profile
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct OrganizationMember {
+    pub organization_id: TenantId,
+    pub developer_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub role: OrganizationRole,
+    pub invited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Invitation for a developer to join an organization
+#[derive(Debug, Clone, FromRow)]
+pub struct OrganizationInvitation {
+    pub id: Uuid,
+    pub organization_id: TenantId,
+    pub email: String,
+    pub role: OrganizationRole,
+    pub token_hash: String,
+    pub invited_by: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_by: Option<Uuid>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OrganizationInvitation {
+    pub fn status(&self) -> InvitationStatus {
+        if self.accepted_at.is_some() {
+            InvitationStatus::Accepted
+        } else if self.revoked_at.is_some() {
+            InvitationStatus::Revoked
+        } else if self.expires_at <= Utc::now() {
+            InvitationStatus::Expired
+        } else {
+            InvitationStatus::Pending
+        }
+    }
+}
+
+/// Invitation status, derived from its timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum InvitationStatus {
+    Pending,
+    Accepted,
+    Revoked,
+    Expired,
+}
+
+/// A project owned by an organization
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct OrganizationProject {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub environment: ProjectEnvironment,
+    pub client_id: String,
+    pub developer_id: Uuid,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Create organization request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateOrganizationRequest {
+    #[validate(length(min = 2, max = 255))]
+    pub name: String,
+}
+
+/// Invite a developer by email
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateInvitationRequest {
+    #[validate(email)]
+    pub email: String,
+    #[serde(default = "default_invitation_role")]
+    pub role: OrganizationRole,
+}
+
+fn default_invitation_role() -> OrganizationRole {
+    OrganizationRole::Member
+}
+
+/// Change a member's role
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateMemberRoleRequest {
+    pub role: OrganizationRole,
+}
+
+/// Invitation response; never includes the token
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InvitationResponse {
+    pub id: Uuid,
+    pub organization_id: TenantId,
+    pub email: String,
+    pub role: OrganizationRole,
+    pub status: InvitationStatus,
+    pub invited_by: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OrganizationInvitation> for InvitationResponse {
+    fn from(invitation: OrganizationInvitation) -> Self {
+        Self {
+            status: invitation.status(),
+            id: invitation.id,
+            organization_id: invitation.organization_id,
+            email: invitation.email,
+            role: invitation.role,
+            invited_by: invitation.invited_by,
+            expires_at: invitation.expires_at,
+            accepted_at: invitation.accepted_at,
+            created_at: invitation.created_at,
+        }
+    }
+}
+
+/// What an invitee sees before accepting
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InvitationDetails {
+    pub organization_name: String,
+    pub email: String,
+    pub role: OrganizationRole,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Networks a project's tokens may be used from, in CIDR notation
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ProjectIpRules {
+    pub project_id: Uuid,
+    /// When non-empty, only these networks may use the project's tokens
+    pub allow: Vec<String>,
+    /// Networks always refused, even when also allowed
+    pub deny: Vec<String>,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Replace a project's IP rules. Empty lists remove the restriction.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetProjectIpRulesRequest {
+    /// Networks like `203.0.113.0/24` or single addresses like `2001:db8::1`
+    #[validate(length(max = 100))]
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[validate(length(max = 100))]
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+/// How a project treats payments that repeat a recent one with the same
+/// payer, amount, beneficiary and reference
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ProjectDuplicatePaymentRules {
+    pub project_id: Uuid,
+    pub action: DuplicatePaymentAction,
+    /// How far back to look for a matching payment
+    pub window_minutes: i32,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Replace a project's duplicate payment rules
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetDuplicatePaymentRulesRequest {
+    pub action: DuplicatePaymentAction,
+    #[validate(range(min = 1, max = 1440))]
+    pub window_minutes: i32,
+}
+
+/// Language a project's API messages are returned in when a request does
+/// not ask for a supported one
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ProjectLocale {
+    pub project_id: Uuid,
+    pub default_locale: Locale,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Change a project's default locale
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetProjectLocaleRequest {
+    pub default_locale: Locale,
+}
+
+/// Which API versions a project is still served
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ProjectApiVersionPolicy {
+    pub project_id: Uuid,
+    /// Requests under older versions are refused
+    pub min_api_version: ApiVersion,
+    /// When deprecated versions stop being served to the project, instead of
+    /// their configured sunset
+    pub api_sunset_on: Option<NaiveDate>,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Change which API versions a project is served
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetProjectApiVersionPolicyRequest {
+    pub min_api_version: ApiVersion,
+    pub api_sunset_on: Option<NaiveDate>,
+}
@@ -0,0 +1,550 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::shared::types::TenantId;
+use super::model::{
+    Organization, OrganizationInvitation, OrganizationMember, OrganizationProject, OrganizationRole,
+    ProjectApiVersionPolicy, ProjectDuplicatePaymentRules, ProjectIpRules, ProjectLocale, ProjectTenant,
+};
+use crate::core::i18n::Locale;
+use crate::core::versioning::{ApiVersion, ProjectVersionRules};
+use crate::payments::model::DuplicatePaymentAction;
+
+const ORGANIZATION_COLUMNS: &str = "id, name, is_active, created_at, updated_at";
+
+const MEMBER_COLUMNS: &str =
+    "m.organization_id, m.developer_id, d.name, d.email, m.role, m.invited_by, m.created_at";
+
+const IP_RULES_COLUMNS: &str = "id AS project_id, ip_allowlist AS allow, ip_denylist AS deny,
+    ip_rules_updated_by AS updated_by, ip_rules_updated_at AS updated_at";
+
+const DUPLICATE_RULES_COLUMNS: &str = "id AS project_id, duplicate_payment_action AS action,
+    duplicate_payment_window_minutes AS window_minutes, duplicate_rules_updated_by AS updated_by,
+    duplicate_rules_updated_at AS updated_at";
+
+const LOCALE_COLUMNS: &str = "id AS project_id, default_locale, locale_updated_by AS updated_by,
+    locale_updated_at AS updated_at";
+
+const API_VERSION_COLUMNS: &str = "id AS project_id, min_api_version, api_sunset_on,
+    api_versions_updated_by AS updated_by, api_versions_updated_at AS updated_at";
+
+const INVITATION_COLUMNS: &str = "id, organization_id, email, role, token_hash, invited_by, expires_at,
+    accepted_by, accepted_at, revoked_at, created_at";
+
+pub struct OrganizationRepository {
+    pool: PgPool,
+}
+
+impl OrganizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_id(&self, id: TenantId) -> AppResult<Option<Organization>> {
+        let organization = sqlx::query_as::<_, Organization>(&format!(
+            "SELECT {ORGANIZATION_COLUMNS} FROM organizations WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(organization)
+    }
+
+    pub async fn find_project_tenant(&self, project_id: Uuid) -> AppResult<Option<ProjectTenant>> {
+        let tenant = sqlx::query_as::<_, ProjectTenant>(
+            "SELECT p.organization_id, o.is_active AS organization_active
+             FROM projects p JOIN organizations o ON o.id = p.organization_id
+             WHERE p.id = $1",
+        )
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tenant)
+    }
+
+    /// Create an organization with its creator as owner
+    pub async fn create(&self, name: &str, owner_id: Uuid) -> AppResult<Organization> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let organization = sqlx::query_as::<_, Organization>(&format!(
+            "INSERT INTO organizations (id, name, is_active, created_at, updated_at)
+             VALUES ($1, $2, TRUE, NOW(), NOW())
+             RETURNING {ORGANIZATION_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(name)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO organization_members (organization_id, developer_id, role)
+             VALUES ($1, $2, 'owner')",
+        )
+        .bind(organization.id)
+        .bind(owner_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(organization)
+    }
+
+    /// A developer's role in an organization, if they are a member
+    pub async fn find_member_role(
+        &self,
+        organization_id: TenantId,
+        developer_id: Uuid,
+    ) -> AppResult<Option<OrganizationRole>> {
+        let role = sqlx::query_scalar::<_, OrganizationRole>(
+            "SELECT role FROM organization_members WHERE organization_id = $1 AND developer_id = $2",
+        )
+        .bind(organization_id)
+        .bind(developer_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(role)
+    }
+
+    pub async fn find_member(
+        &self,
+        organization_id: TenantId,
+        developer_id: Uuid,
+    ) -> AppResult<Option<OrganizationMember>> {
+        let member = sqlx::query_as::<_, OrganizationMember>(&format!(
+            "SELECT {MEMBER_COLUMNS} FROM organization_members m JOIN developers d ON d.id = m.developer_id
+             WHERE m.organization_id = $1 AND m.developer_id = $2"
+        ))
+        .bind(organization_id)
+        .bind(developer_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(member)
+    }
+
+    pub async fn list_members(&self, organization_id: TenantId) -> AppResult<Vec<OrganizationMember>> {
+        let members = sqlx::query_as::<_, OrganizationMember>(&format!(
+            "SELECT {MEMBER_COLUMNS} FROM organization_members m JOIN developers d ON d.id = m.developer_id
+             WHERE m.organization_id = $1
+             ORDER BY m.created_at"
+        ))
+        .bind(organization_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(members)
+    }
+
+    /// Organizations a developer belongs to
+    pub async fn list_for_developer(&self, developer_id: Uuid) -> AppResult<Vec<Organization>> {
+        let organizations = sqlx::query_as::<_, Organization>(
+            "SELECT o.id, o.name, o.is_active, o.created_at, o.updated_at
+             FROM organizations o JOIN organization_members m ON m.organization_id = o.id
+             WHERE m.developer_id = $1
+             ORDER BY o.name",
+        )
+        .bind(developer_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(organizations)
+    }
+
+    /// Change a member's role. Returns false when the member would be the
+    /// last owner removed, leaving the organization without one.
+    pub async fn update_member_role(
+        &self,
+        organization_id: TenantId,
+        developer_id: Uuid,
+        role: OrganizationRole,
+    ) -> AppResult<bool> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        // Lock the owners so two demotions cannot both pass the check
+        let owners = self.lock_owners(&mut tx, organization_id).await?;
+        if role != OrganizationRole::Owner && owners == [developer_id] {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            "UPDATE organization_members SET role = $1, updated_at = NOW()
+             WHERE organization_id = $2 AND developer_id = $3",
+        )
+        .bind(role)
+        .bind(organization_id)
+        .bind(developer_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Remove a member. Returns false when they are the last owner.
+    pub async fn remove_member(&self, organization_id: TenantId, developer_id: Uuid) -> AppResult<bool> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let owners = self.lock_owners(&mut tx, organization_id).await?;
+        if owners == [developer_id] {
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM organization_members WHERE organization_id = $1 AND developer_id = $2")
+            .bind(organization_id)
+            .bind(developer_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn lock_owners(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        organization_id: TenantId,
+    ) -> AppResult<Vec<Uuid>> {
+        let owners = sqlx::query_scalar::<_, Uuid>(
+            "SELECT developer_id FROM organization_members
+             WHERE organization_id = $1 AND role = 'owner'
+             FOR UPDATE",
+        )
+        .bind(organization_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(owners)
+    }
+
+    /// Store an invitation, revoking any open invitation for the same email
+    pub async fn create_invitation(&self, invitation: &OrganizationInvitation) -> AppResult<OrganizationInvitation> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        sqlx::query(
+            "UPDATE organization_invitations SET revoked_at = NOW()
+             WHERE organization_id = $1 AND LOWER(email) = LOWER($2)
+               AND accepted_at IS NULL AND revoked_at IS NULL",
+        )
+        .bind(invitation.organization_id)
+        .bind(&invitation.email)
+        .execute(&mut *tx)
+        .await?;
+
+        let created = sqlx::query_as::<_, OrganizationInvitation>(&format!(
+            "INSERT INTO organization_invitations ({INVITATION_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING {INVITATION_COLUMNS}"
+        ))
+        .bind(invitation.id)
+        .bind(invitation.organization_id)
+        .bind(&invitation.email)
+        .bind(invitation.role)
+        .bind(&invitation.token_hash)
+        .bind(invitation.invited_by)
+        .bind(invitation.expires_at)
+        .bind(invitation.accepted_by)
+        .bind(invitation.accepted_at)
+        .bind(invitation.revoked_at)
+        .bind(invitation.created_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    pub async fn list_invitations(&self, organization_id: TenantId) -> AppResult<Vec<OrganizationInvitation>> {
+        let invitations = sqlx::query_as::<_, OrganizationInvitation>(&format!(
+            "SELECT {INVITATION_COLUMNS} FROM organization_invitations
+             WHERE organization_id = $1
+             ORDER BY created_at DESC"
+        ))
+        .bind(organization_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(invitations)
+    }
+
+    pub async fn find_invitation_by_token_hash(&self, token_hash: &str) -> AppResult<Option<OrganizationInvitation>> {
+        let invitation = sqlx::query_as::<_, OrganizationInvitation>(&format!(
+            "SELECT {INVITATION_COLUMNS} FROM organization_invitations WHERE token_hash = $1"
+        ))
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(invitation)
+    }
+
+    /// Revoke an open invitation. Returns false when it was already accepted or revoked.
+    pub async fn revoke_invitation(&self, organization_id: TenantId, invitation_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query(
+            "UPDATE organization_invitations SET revoked_at = NOW()
+             WHERE id = $1 AND organization_id = $2 AND accepted_at IS NULL AND revoked_at IS NULL",
+        )
+        .bind(invitation_id)
+        .bind(organization_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Accept an open, unexpired invitation and add the developer as a
+    /// member. Returns false when the invitation can no longer be used.
+    pub async fn accept_invitation(&self, invitation: &OrganizationInvitation, developer_id: Uuid) -> AppResult<bool> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let result = sqlx::query(
+            "UPDATE organization_invitations SET accepted_by = $1, accepted_at = NOW()
+             WHERE id = $2 AND accepted_at IS NULL AND revoked_at IS NULL AND expires_at > NOW()",
+        )
+        .bind(developer_id)
+        .bind(invitation.id)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        // An existing member keeps their current role
+        sqlx::query(
+            "INSERT INTO organization_members (organization_id, developer_id, role, invited_by)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (organization_id, developer_id) DO NOTHING",
+        )
+        .bind(invitation.organization_id)
+        .bind(developer_id)
+        .bind(invitation.role)
+        .bind(invitation.invited_by)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn find_developer_email(&self, developer_id: Uuid) -> AppResult<Option<String>> {
+        let email = sqlx::query_scalar::<_, String>("SELECT email FROM developers WHERE id = $1")
+            .bind(developer_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(email)
+    }
+
+    pub async fn list_projects(&self, organization_id: TenantId) -> AppResult<Vec<OrganizationProject>> {
+        let projects = sqlx::query_as::<_, OrganizationProject>(
+            "SELECT id, name, description, environment, client_id, developer_id, is_active, created_at
+             FROM projects
+             WHERE organization_id = $1
+             ORDER BY created_at",
+        )
+        .bind(organization_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(projects)
+    }
+
+    pub async fn find_project_ip_rules(&self, project_id: Uuid) -> AppResult<Option<ProjectIpRules>> {
+        let rules = sqlx::query_as::<_, ProjectIpRules>(&format!(
+            "SELECT {IP_RULES_COLUMNS} FROM projects WHERE id = $1"
+        ))
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn find_organization_project_ip_rules(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+    ) -> AppResult<Option<ProjectIpRules>> {
+        let rules = sqlx::query_as::<_, ProjectIpRules>(&format!(
+            "SELECT {IP_RULES_COLUMNS} FROM projects WHERE id = $1 AND organization_id = $2"
+        ))
+        .bind(project_id)
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn set_project_ip_rules(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        allow: &[String],
+        deny: &[String],
+        updated_by: Uuid,
+    ) -> AppResult<Option<ProjectIpRules>> {
+        let rules = sqlx::query_as::<_, ProjectIpRules>(&format!(
+            "UPDATE projects
+             SET ip_allowlist = $3, ip_denylist = $4, ip_rules_updated_by = $5, ip_rules_updated_at = NOW()
+             WHERE id = $1 AND organization_id = $2
+             RETURNING {IP_RULES_COLUMNS}"
+        ))
+        .bind(project_id)
+        .bind(organization_id)
+        .bind(allow)
+        .bind(deny)
+        .bind(updated_by)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn find_project_duplicate_rules(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+    ) -> AppResult<Option<ProjectDuplicatePaymentRules>> {
+        let rules = sqlx::query_as::<_, ProjectDuplicatePaymentRules>(&format!(
+            "SELECT {DUPLICATE_RULES_COLUMNS} FROM projects WHERE id = $1 AND organization_id = $2"
+        ))
+        .bind(project_id)
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn set_project_duplicate_rules(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        action: DuplicatePaymentAction,
+        window_minutes: i32,
+        updated_by: Uuid,
+    ) -> AppResult<Option<ProjectDuplicatePaymentRules>> {
+        let rules = sqlx::query_as::<_, ProjectDuplicatePaymentRules>(&format!(
+            "UPDATE projects
+             SET duplicate_payment_action = $3, duplicate_payment_window_minutes = $4,
+                 duplicate_rules_updated_by = $5, duplicate_rules_updated_at = NOW()
+             WHERE id = $1 AND organization_id = $2
+             RETURNING {DUPLICATE_RULES_COLUMNS}"
+        ))
+        .bind(project_id)
+        .bind(organization_id)
+        .bind(action)
+        .bind(window_minutes)
+        .bind(updated_by)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn find_project_locale(&self, project_id: Uuid) -> AppResult<Option<Locale>> {
+        let locale = sqlx::query_scalar("SELECT default_locale FROM projects WHERE id = $1")
+            .bind(project_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(locale)
+    }
+
+    pub async fn find_organization_project_locale(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+    ) -> AppResult<Option<ProjectLocale>> {
+        let locale = sqlx::query_as::<_, ProjectLocale>(&format!(
+            "SELECT {LOCALE_COLUMNS} FROM projects WHERE id = $1 AND organization_id = $2"
+        ))
+        .bind(project_id)
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(locale)
+    }
+
+    pub async fn set_project_locale(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        default_locale: Locale,
+        updated_by: Uuid,
+    ) -> AppResult<Option<ProjectLocale>> {
+        let locale = sqlx::query_as::<_, ProjectLocale>(&format!(
+            "UPDATE projects
+             SET default_locale = $3, locale_updated_by = $4, locale_updated_at = NOW()
+             WHERE id = $1 AND organization_id = $2
+             RETURNING {LOCALE_COLUMNS}"
+        ))
+        .bind(project_id)
+        .bind(organization_id)
+        .bind(default_locale)
+        .bind(updated_by)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(locale)
+    }
+
+    pub async fn find_project_version_rules(&self, project_id: Uuid) -> AppResult<Option<ProjectVersionRules>> {
+        let rules = sqlx::query_as::<_, ProjectVersionRules>(
+            "SELECT min_api_version, api_sunset_on FROM projects WHERE id = $1",
+        )
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn find_project_api_version_policy(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+    ) -> AppResult<Option<ProjectApiVersionPolicy>> {
+        let policy = sqlx::query_as::<_, ProjectApiVersionPolicy>(&format!(
+            "SELECT {API_VERSION_COLUMNS} FROM projects WHERE id = $1 AND organization_id = $2"
+        ))
+        .bind(project_id)
+        .bind(organization_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(policy)
+    }
+
+    pub async fn set_project_api_version_policy(
+        &self,
+        organization_id: TenantId,
+        project_id: Uuid,
+        min_api_version: ApiVersion,
+        api_sunset_on: Option<NaiveDate>,
+        updated_by: Uuid,
+    ) -> AppResult<Option<ProjectApiVersionPolicy>> {
+        let policy = sqlx::query_as::<_, ProjectApiVersionPolicy>(&format!(
+            "UPDATE projects
+             SET min_api_version = $3, api_sunset_on = $4, api_versions_updated_by = $5,
+                 api_versions_updated_at = NOW()
+             WHERE id = $1 AND organization_id = $2
+             RETURNING {API_VERSION_COLUMNS}"
+        ))
+        .bind(project_id)
+        .bind(organization_id)
+        .bind(min_api_version)
+        .bind(api_sunset_on)
+        .bind(updated_by)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(policy)
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::auth::model::JwtClaims;
+use crate::core::error::{AppError, AppResult};
+use crate::core::i18n::{Locale, ProjectLocaleCache};
+use crate::core::ip_filter::{IpNetwork, IpPolicy, IpPolicyCache};
+use crate::core::versioning::{ProjectVersionRules, ProjectVersionRulesCache};
+use crate::core::AppState;
+use crate::shared::types::TenantId;
+use super::model::Organization;
+use super::repository::OrganizationRepository;
+
+/// Header a client may send to state which tenant it expects to act for
+pub const TENANT_HEADER: &str = "X-Tenant-ID";
+
+pub struct OrganizationService {
+    repository: OrganizationRepository,
+    ip_policy_cache: IpPolicyCache,
+    locale_cache: ProjectLocaleCache,
+    version_cache: ProjectVersionRulesCache,
+}
+
+impl OrganizationService {
+    pub fn new(
+        repository: OrganizationRepository,
+        ip_policy_cache: IpPolicyCache,
+        locale_cache: ProjectLocaleCache,
+        version_cache: ProjectVersionRulesCache,
+    ) -> Self {
+        Self {
+            repository,
+            ip_policy_cache,
+            locale_cache,
+            version_cache,
+        }
+    }
+
+    /// The organization a token acts for
+    pub async fn get_organization(&self, tenant_id: TenantId) -> AppResult<Organization> {
+        self.repository
+            .find_by_id(tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))
+    }
+
+    /// Reject a request whose token no longer matches its project's tenant,
+    /// whose organization is deactivated, or that asks to act for another
+    /// tenant through the tenant header
+    pub async fn ensure_tenant_access(&self, claims: &JwtClaims, requested: Option<&str>) -> AppResult<()> {
+        if let Some(requested) = requested {
+            if requested.parse::<TenantId>().ok() != Some(claims.tenant_id) {
+                return Err(AppError::Authorization("Cross-tenant access is not allowed".to_string()));
+            }
+        }
+
+        let tenant = self
+            .repository
+            .find_project_tenant(claims.project_id)
+            .await?
+            .ok_or_else(|| AppError::Authentication("Project no longer exists".to_string()))?;
+
+        if tenant.organization_id != claims.tenant_id {
+            return Err(AppError::Authorization("Cross-tenant access is not allowed".to_string()));
+        }
+        if !tenant.organization_active {
+            return Err(AppError::Authorization("Organization is deactivated".to_string()));
+        }
+        Ok(())
+    }
+
+    /// The networks a project's tokens may be used from
+    pub async fn ip_policy(&self, project_id: Uuid) -> AppResult<Arc<IpPolicy>> {
+        if let Some(policy) = self.ip_policy_cache.get(project_id) {
+            return Ok(policy);
+        }
+
+        let policy = match self.repository.find_project_ip_rules(project_id).await? {
+            Some(rules) => IpPolicy {
+                allow: parse_networks(&rules.allow)?,
+                deny: parse_networks(&rules.deny)?,
+            },
+            None => IpPolicy::default(),
+        };
+        let policy = Arc::new(policy);
+        self.ip_policy_cache.store(project_id, policy.clone());
+        Ok(policy)
+    }
+
+    /// The locale a project's responses default to, if the project exists
+    pub async fn default_locale(&self, project_id: Uuid) -> AppResult<Option<Locale>> {
+        if let Some(locale) = self.locale_cache.get(project_id) {
+            return Ok(Some(locale));
+        }
+
+        let locale = self.repository.find_project_locale(project_id).await?;
+        if let Some(locale) = locale {
+            self.locale_cache.store(project_id, locale);
+        }
+        Ok(locale)
+    }
+
+    /// The API versions a project has chosen to be served, if the project exists
+    pub async fn version_rules(&self, project_id: Uuid) -> AppResult<Option<ProjectVersionRules>> {
+        if let Some(rules) = self.version_cache.get(project_id) {
+            return Ok(Some(rules));
+        }
+
+        let rules = self.repository.find_project_version_rules(project_id).await?;
+        if let Some(rules) = &rules {
+            self.version_cache.store(project_id, rules.clone());
+        }
+        Ok(rules)
+    }
+}
+
+pub fn parse_networks(networks: &[String]) -> AppResult<Vec<IpNetwork>> {
+    networks.iter().map(|network| network.parse()).collect()
+}
+
+pub fn organization_service(state: &AppState) -> OrganizationService {
+    OrganizationService::new(
+        OrganizationRepository::new(state.postgres.clone()),
+        state.ip_policy_cache.clone(),
+        state.project_locale_cache.clone(),
+        state.project_version_cache.clone(),
+    )
+}
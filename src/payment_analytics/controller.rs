@@ -0,0 +1,47 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::AppResult,
+    extractors::ClientIp,
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{PaymentAnalytics, PaymentAnalyticsQuery};
+use super::repository::PaymentAnalyticsRepository;
+use super::service::PaymentAnalyticsService;
+
+pub(crate) fn payment_analytics_service(state: &AppState) -> PaymentAnalyticsService {
+    PaymentAnalyticsService::new(PaymentAnalyticsRepository::new(state.postgres.clone()))
+}
+
+/// Payment volumes, success rates, completion latency and failure reasons
+/// per provider, method and currency (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/analytics/payments",
+    tag = "analytics",
+    params(PaymentAnalyticsQuery),
+    responses(
+        (status = 200, description = "Payments created in the period, broken down", body = PaymentAnalytics),
+        (status = 400, description = "Invalid period"),
+        (status = 403, description = "Caller lacks the system monitoring permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_payment_analytics(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<PaymentAnalyticsQuery>,
+) -> AppResult<Json<ApiResponse<PaymentAnalytics>>> {
+    state
+        .authorize(claims.developer_id, permissions::monitor_system(), ip, "payment_analytics".to_string())
+        .await?;
+
+    let analytics = payment_analytics_service(&state).analytics(query).await?;
+    Ok(Json(ApiResponse::success("Payment analytics retrieved successfully", analytics)))
+}
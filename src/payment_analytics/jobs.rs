@@ -0,0 +1,27 @@
+use tokio::sync::broadcast::error::RecvError;
+use crate::core::AppState;
+use super::controller::payment_analytics_service;
+
+/// Keep payment lifecycles up to date with the status changes domain
+/// events report. Runs for the life of the process; events published while
+/// it lags behind are skipped and logged.
+pub fn spawn_lifecycle_job(state: AppState) {
+    let mut events = state.event_bus.subscribe();
+
+    tokio::spawn(async move {
+        let service = payment_analytics_service(&state);
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if let Err(e) = service.record(&event).await {
+                        tracing::error!(event_id = %event.id, "Failed to record payment lifecycle of {} event: {}", event.event_type.as_str(), e);
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Payment analytics job fell behind and skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
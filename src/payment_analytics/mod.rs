@@ -0,0 +1,12 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::get, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/analytics/payments", get(controller::get_payment_analytics))
+}
@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use crate::core::events::{DomainEvent, DomainEventType};
+use crate::payments::model::{PaymentMethod, PaymentStatus};
+use crate::shared::types::{Amount, Currency};
+
+/// Provider reported for payments no rail called back about
+pub const INTERNAL_PROVIDER: &str = "internal";
+
+/// Where a payment is in its life, as of its latest status change
+#[derive(Debug, Clone)]
+pub struct PaymentLifecycle {
+    pub payment_id: Uuid,
+    pub payment_method: PaymentMethod,
+    pub currency: Currency,
+    pub amount: Amount,
+    pub status: PaymentStatus,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Set once the payment completes, fails or is cancelled
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// The fields analytics needs from `payment.status_changed` payloads
+#[derive(Debug, Deserialize)]
+struct StatusChange {
+    id: Uuid,
+    payment_method: PaymentMethod,
+    currency: Currency,
+    amount: Amount,
+    status: PaymentStatus,
+    execution_error: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+/// The lifecycle a domain event moves a payment to, if it is a payment's
+/// status change. Refunds keep the time the payment completed.
+pub fn lifecycle(event: &DomainEvent) -> Option<PaymentLifecycle> {
+    if event.event_type != DomainEventType::PaymentStatusChanged {
+        return None;
+    }
+    let change = StatusChange::deserialize(&event.data).ok()?;
+    let finished = matches!(
+        change.status,
+        PaymentStatus::Completed | PaymentStatus::Failed | PaymentStatus::Cancelled | PaymentStatus::Refunded
+    );
+
+    Some(PaymentLifecycle {
+        payment_id: change.id,
+        payment_method: change.payment_method,
+        currency: change.currency.to_uppercase(),
+        amount: change.amount,
+        error: change.execution_error.filter(|_| matches!(change.status, PaymentStatus::Failed)),
+        status: change.status,
+        created_at: change.created_at,
+        finished_at: finished.then_some(event.occurred_at),
+    })
+}
+
+/// How often a failure reason came up
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PaymentErrorCount {
+    pub error: String,
+    pub count: i64,
+}
+
+/// Payments of one provider, method and currency
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PaymentBreakdown {
+    /// The rail that reported on the payments, or `internal`
+    pub provider: String,
+    pub payment_method: PaymentMethod,
+    pub currency: Currency,
+    pub payment_count: i64,
+    /// Sum of the amounts of every payment
+    pub volume: Amount,
+    /// Completed, including payments refunded since
+    pub succeeded: i64,
+    pub failed: i64,
+    pub cancelled: i64,
+    /// Not finished yet
+    pub in_progress: i64,
+    /// Share of finished payments, cancelled ones aside, that succeeded
+    pub success_rate: Option<f64>,
+    pub failure_rate: Option<f64>,
+    /// Seconds from creation to completion of the succeeded payments
+    pub average_latency_seconds: Option<f64>,
+    pub p95_latency_seconds: Option<f64>,
+    /// Failure reasons, most frequent first
+    #[sqlx(skip)]
+    pub errors: Vec<PaymentErrorCount>,
+}
+
+/// A failure reason of one provider, method and currency
+#[derive(Debug, Clone, FromRow)]
+pub struct GroupedErrorCount {
+    pub provider: String,
+    pub payment_method: PaymentMethod,
+    pub currency: Currency,
+    pub error: String,
+    pub count: i64,
+}
+
+/// Payment analytics over a period
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PaymentAnalytics {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub breakdown: Vec<PaymentBreakdown>,
+}
+
+/// Query parameters for payment analytics; payments are counted by when
+/// they were created
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PaymentAnalyticsQuery {
+    /// Defaults to seven days before `to`
+    pub from: Option<DateTime<Utc>>,
+    /// Defaults to now
+    pub to: Option<DateTime<Utc>>,
+    pub provider: Option<String>,
+    pub payment_method: Option<PaymentMethod>,
+    pub currency: Option<Currency>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_change(status: &str, error: Option<&str>) -> DomainEvent {
+        DomainEvent::new(
+            DomainEventType::PaymentStatusChanged,
+            None,
+            Vec::new(),
+            serde_json::json!({
+                "id": Uuid::new_v4(),
+                "payment_method": "BankTransfer",
+                "currency": "eur",
+                "amount": 500,
+                "status": status,
+                "execution_error": error,
+                "created_at": Utc::now(),
+            }),
+        )
+    }
+
+    #[test]
+    fn finished_payments_record_when_they_finished() {
+        let pending = lifecycle(&status_change("Pending", None)).unwrap();
+        assert!(pending.finished_at.is_none());
+        assert_eq!(pending.currency, "EUR");
+
+        let failed = status_change("Failed", Some("Insufficient funds"));
+        let lifecycle = lifecycle(&failed).unwrap();
+        assert_eq!(lifecycle.finished_at, Some(failed.occurred_at));
+        assert_eq!(lifecycle.error.as_deref(), Some("Insufficient funds"));
+    }
+
+    #[test]
+    fn other_events_are_not_lifecycles() {
+        let mut event = status_change("Completed", None);
+        event.event_type = DomainEventType::TransactionCreated;
+        assert!(lifecycle(&event).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use crate::core::error::AppResult;
+use crate::payments::model::PaymentMethod;
+use super::model::{GroupedErrorCount, PaymentBreakdown, PaymentLifecycle, INTERNAL_PROVIDER};
+
+/// Lifecycles in the period matching the optional provider, method and
+/// currency filters, with their provider resolved
+const FILTERED_LIFECYCLES: &str = "SELECT *, COALESCE(provider, $3) AS resolved_provider
+     FROM payment_lifecycles
+     WHERE created_at >= $1 AND created_at < $2
+       AND ($4::VARCHAR IS NULL OR COALESCE(provider, $3) = $4)
+       AND ($5::payment_method IS NULL OR payment_method = $5)
+       AND ($6::VARCHAR IS NULL OR currency = $6)";
+
+pub struct PaymentAnalyticsRepository {
+    pool: PgPool,
+}
+
+impl PaymentAnalyticsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Move a payment's lifecycle to its latest status, taking its provider
+    /// from the rail callbacks recorded for it. The time a payment finished
+    /// is kept once set.
+    pub async fn record(&self, lifecycle: &PaymentLifecycle) -> AppResult<()> {
+        sqlx::query(
+            "INSERT INTO payment_lifecycles
+                 (payment_id, provider, payment_method, currency, amount, status, error, created_at, finished_at)
+             VALUES ($1, (SELECT provider FROM payment_callbacks WHERE payment_id = $1 ORDER BY received_at DESC LIMIT 1),
+                     $2, $3, $4, $5, $6, $7, $8)
+             ON CONFLICT (payment_id) DO UPDATE SET
+                 provider = COALESCE(EXCLUDED.provider, payment_lifecycles.provider),
+                 status = EXCLUDED.status,
+                 error = COALESCE(EXCLUDED.error, payment_lifecycles.error),
+                 finished_at = COALESCE(payment_lifecycles.finished_at, EXCLUDED.finished_at),
+                 updated_at = NOW()",
+        )
+        .bind(lifecycle.payment_id)
+        .bind(&lifecycle.payment_method)
+        .bind(&lifecycle.currency)
+        .bind(lifecycle.amount)
+        .bind(&lifecycle.status)
+        .bind(&lifecycle.error)
+        .bind(lifecycle.created_at)
+        .bind(lifecycle.finished_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Counts, rates and latencies per provider, method and currency of the
+    /// payments created in a period
+    pub async fn breakdown(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        provider: Option<&str>,
+        payment_method: Option<&PaymentMethod>,
+        currency: Option<&str>,
+    ) -> AppResult<Vec<PaymentBreakdown>> {
+        let breakdown = sqlx::query_as::<_, PaymentBreakdown>(&format!(
+            "WITH lifecycles AS ({FILTERED_LIFECYCLES}),
+             groups AS (
+                 SELECT resolved_provider AS provider, payment_method, currency,
+                        COUNT(*) AS payment_count,
+                        COALESCE(SUM(amount), 0)::BIGINT AS volume,
+                        COUNT(*) FILTER (WHERE status IN ('completed', 'refunded')) AS succeeded,
+                        COUNT(*) FILTER (WHERE status = 'failed') AS failed,
+                        COUNT(*) FILTER (WHERE status = 'cancelled') AS cancelled,
+                        COUNT(*) FILTER (WHERE finished_at IS NULL) AS in_progress,
+                        AVG(EXTRACT(EPOCH FROM finished_at - created_at))
+                            FILTER (WHERE status IN ('completed', 'refunded'))::FLOAT8 AS average_latency_seconds,
+                        PERCENTILE_CONT(0.95) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM finished_at - created_at))
+                            FILTER (WHERE status IN ('completed', 'refunded'))::FLOAT8 AS p95_latency_seconds
+                 FROM lifecycles
+                 GROUP BY resolved_provider, payment_method, currency
+             )
+             SELECT provider, payment_method, currency, payment_count, volume, succeeded, failed, cancelled,
+                    in_progress,
+                    succeeded::FLOAT8 / NULLIF(succeeded + failed, 0) AS success_rate,
+                    failed::FLOAT8 / NULLIF(succeeded + failed, 0) AS failure_rate,
+                    average_latency_seconds, p95_latency_seconds
+             FROM groups
+             ORDER BY provider, payment_method, currency"
+        ))
+        .bind(from)
+        .bind(to)
+        .bind(INTERNAL_PROVIDER)
+        .bind(provider)
+        .bind(payment_method)
+        .bind(currency)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(breakdown)
+    }
+
+    /// How often each failure reason came up per provider, method and
+    /// currency among the payments created in a period
+    pub async fn error_counts(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        provider: Option<&str>,
+        payment_method: Option<&PaymentMethod>,
+        currency: Option<&str>,
+    ) -> AppResult<Vec<GroupedErrorCount>> {
+        let counts = sqlx::query_as::<_, GroupedErrorCount>(&format!(
+            "WITH lifecycles AS ({FILTERED_LIFECYCLES})
+             SELECT resolved_provider AS provider, payment_method, currency, error, COUNT(*) AS count
+             FROM lifecycles
+             WHERE status = 'failed' AND error IS NOT NULL
+             GROUP BY resolved_provider, payment_method, currency, error
+             ORDER BY count DESC, error"
+        ))
+        .bind(from)
+        .bind(to)
+        .bind(INTERNAL_PROVIDER)
+        .bind(provider)
+        .bind(payment_method)
+        .bind(currency)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::{Duration, Utc};
+use crate::core::error::{AppError, AppResult};
+use crate::core::events::DomainEvent;
+use super::model::{lifecycle, PaymentAnalytics, PaymentAnalyticsQuery, PaymentErrorCount};
+use super::repository::PaymentAnalyticsRepository;
+
+/// Period analytics cover when the query does not say
+const DEFAULT_PERIOD_DAYS: i64 = 7;
+
+/// Longest period one query may cover
+const MAX_PERIOD_DAYS: i64 = 366;
+
+pub struct PaymentAnalyticsService {
+    repository: PaymentAnalyticsRepository,
+}
+
+impl PaymentAnalyticsService {
+    pub fn new(repository: PaymentAnalyticsRepository) -> Self {
+        Self { repository }
+    }
+
+    /// Move the payment a domain event is about to its new status. Returns
+    /// whether the event was a payment's status change.
+    pub async fn record(&self, event: &DomainEvent) -> AppResult<bool> {
+        let Some(lifecycle) = lifecycle(event) else {
+            return Ok(false);
+        };
+        self.repository.record(&lifecycle).await?;
+        Ok(true)
+    }
+
+    /// Volumes, success and failure rates, completion latency and failure
+    /// reasons per provider, method and currency
+    pub async fn analytics(&self, query: PaymentAnalyticsQuery) -> AppResult<PaymentAnalytics> {
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = query.from.unwrap_or(to - Duration::days(DEFAULT_PERIOD_DAYS));
+        if from >= to {
+            return Err(AppError::Validation("'from' must be before 'to'".to_string()));
+        }
+        if to - from > Duration::days(MAX_PERIOD_DAYS) {
+            return Err(AppError::Validation(format!(
+                "Analytics cover at most {} days",
+                MAX_PERIOD_DAYS
+            )));
+        }
+        let provider = query.provider.as_deref();
+        let payment_method = query.payment_method.as_ref();
+        let currency = query.currency.map(|code| code.to_uppercase());
+
+        let mut breakdown = self
+            .repository
+            .breakdown(from, to, provider, payment_method, currency.as_deref())
+            .await?;
+        let errors = self
+            .repository
+            .error_counts(from, to, provider, payment_method, currency.as_deref())
+            .await?;
+        for count in errors {
+            let group = breakdown.iter_mut().find(|group| {
+                group.provider == count.provider
+                    && group.payment_method == count.payment_method
+                    && group.currency == count.currency
+            });
+            if let Some(group) = group {
+                group.errors.push(PaymentErrorCount {
+                    error: count.error,
+                    count: count.count,
+                });
+            }
+        }
+
+        Ok(PaymentAnalytics { from, to, breakdown })
+    }
+}
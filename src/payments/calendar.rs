@@ -0,0 +1,178 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use std::collections::HashSet;
+use crate::core::config::Config;
+use super::model::PaymentMethod;
+
+/// Holidays applying to every currency and country
+const ALL_MARKETS: &str = "*";
+
+/// Latest time of day (UTC) each method accepts payments for same-day
+/// processing. Methods without a cut-off run around the clock and are not
+/// bound to banking days.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CutOffTimes {
+    bank_transfer: Option<NaiveTime>,
+    card: Option<NaiveTime>,
+    wallet: Option<NaiveTime>,
+    crypto: Option<NaiveTime>,
+}
+
+impl CutOffTimes {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            bank_transfer: config.payment_cutoff_bank_transfer,
+            card: config.payment_cutoff_card,
+            wallet: config.payment_cutoff_wallet,
+            crypto: config.payment_cutoff_crypto,
+        }
+    }
+
+    pub fn for_method(&self, method: &PaymentMethod) -> Option<NaiveTime> {
+        match method {
+            PaymentMethod::BankTransfer => self.bank_transfer,
+            PaymentMethod::Card => self.card,
+            PaymentMethod::Wallet => self.wallet,
+            PaymentMethod::Crypto => self.crypto,
+        }
+    }
+}
+
+/// The market a payment is processed in: its currency and, for payments
+/// to other banks, the recipient's country
+#[derive(Debug, Clone, Copy)]
+pub struct Market<'a> {
+    pub currency: &'a str,
+    pub country: Option<&'a str>,
+}
+
+/// Banking days and processing windows. Weekends are never banking days;
+/// holidays close the markets they are configured for.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessingCalendar {
+    cutoffs: CutOffTimes,
+    /// (currency, country or `*`, date), upper case
+    holidays: HashSet<(String, NaiveDate)>,
+}
+
+impl ProcessingCalendar {
+    pub fn new(cutoffs: CutOffTimes, holidays: &[(String, NaiveDate)]) -> Self {
+        Self {
+            cutoffs,
+            holidays: holidays
+                .iter()
+                .map(|(market, date)| (market.to_uppercase(), *date))
+                .collect(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(CutOffTimes::from_config(config), &config.payment_holidays)
+    }
+
+    pub fn is_banking_day(&self, date: NaiveDate, market: Market<'_>) -> bool {
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        let closed = |scope: &str| self.holidays.contains(&(scope.to_uppercase(), date));
+        !(closed(ALL_MARKETS) || closed(market.currency) || market.country.is_some_and(closed))
+    }
+
+    /// When a payment due at `at` can be processed: `at` itself if that is
+    /// before the method's cut-off on a banking day, otherwise the start of
+    /// the next banking day
+    pub fn next_window(&self, method: &PaymentMethod, at: DateTime<Utc>, market: Market<'_>) -> DateTime<Utc> {
+        let Some(cutoff) = self.cutoffs.for_method(method) else {
+            return at;
+        };
+        if self.is_banking_day(at.date_naive(), market) && at.time() < cutoff {
+            return at;
+        }
+        let mut date = at.date_naive() + Duration::days(1);
+        while !self.is_banking_day(date, market) {
+            date += Duration::days(1);
+        }
+        date.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    /// When a payment processed at `from` settles. Clearing of methods with
+    /// a cut-off only runs on banking days, so closed days do not count
+    /// towards the delay.
+    pub fn settlement_time(
+        &self,
+        method: &PaymentMethod,
+        from: DateTime<Utc>,
+        delay: Duration,
+        market: Market<'_>,
+    ) -> DateTime<Utc> {
+        if self.cutoffs.for_method(method).is_none() || delay <= Duration::zero() {
+            return from + delay;
+        }
+
+        let mut at = from;
+        let mut remaining = delay;
+        loop {
+            let next_day = (at.date_naive() + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+            if self.is_banking_day(at.date_naive(), market) {
+                if at + remaining <= next_day {
+                    return at + remaining;
+                }
+                remaining -= next_day - at;
+            }
+            at = next_day;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const EUR: Market<'static> = Market {
+        currency: "EUR",
+        country: Some("DE"),
+    };
+
+    fn calendar() -> ProcessingCalendar {
+        let cutoffs = CutOffTimes {
+            bank_transfer: NaiveTime::from_hms_opt(16, 0, 0),
+            ..CutOffTimes::default()
+        };
+        // Friday 25 December 2026 is closed for euro payments
+        ProcessingCalendar::new(cutoffs, &[("eur".to_string(), NaiveDate::from_ymd_opt(2026, 12, 25).unwrap())])
+    }
+
+    fn utc(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 12, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn payments_past_cut_off_wait_for_the_next_banking_day() {
+        let calendar = calendar();
+        let method = PaymentMethod::BankTransfer;
+
+        assert_eq!(calendar.next_window(&method, utc(23, 15), EUR), utc(23, 15));
+        assert_eq!(calendar.next_window(&method, utc(23, 16), EUR), utc(24, 0));
+        // Thursday evening skips the holiday and the weekend
+        assert_eq!(calendar.next_window(&method, utc(24, 17), EUR), utc(28, 0));
+        // Other markets are open on the euro holiday
+        let gbp = Market { currency: "GBP", country: Some("GB") };
+        assert_eq!(calendar.next_window(&method, utc(24, 17), gbp), utc(25, 0));
+        // Cards have no cut-off
+        assert_eq!(calendar.next_window(&PaymentMethod::Card, utc(26, 12), EUR), utc(26, 12));
+    }
+
+    #[test]
+    fn clearing_counts_banking_days_only() {
+        let calendar = calendar();
+        let method = PaymentMethod::BankTransfer;
+
+        assert_eq!(calendar.settlement_time(&method, utc(22, 10), Duration::hours(24), EUR), utc(23, 10));
+        // Thursday 10:00 plus 24 banking hours lands on Monday 10:00
+        assert_eq!(calendar.settlement_time(&method, utc(24, 10), Duration::hours(24), EUR), utc(28, 10));
+        assert_eq!(
+            calendar.settlement_time(&PaymentMethod::Card, utc(24, 10), Duration::hours(48), EUR),
+            utc(26, 10)
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::replay::ReplayCache;
+use super::model::{PaymentCallbackOutcome, PaymentCallbackResponse};
+use super::rails::{PaymentRail, PaymentRails, RailCallback, RailStatus};
+use super::repository::PaymentRepository;
+use super::service::PaymentService;
+
+/// Applies the status changes payment rails report through their callbacks.
+///
+/// Each verified callback is stored with its raw body before anything else
+/// happens, once per event id at the rail, so redelivered events are
+/// acknowledged with the outcome of the first delivery. A rail accepting a
+/// posted payment moves it to processing; settling or rejecting it settles
+/// or fails it. Statuses a payment is already past are recorded and ignored.
+/// Rails with timestamped signatures also have each delivery's nonce checked
+/// against the replay cache, so a captured request cannot be sent again.
+pub struct PaymentCallbackService {
+    payments: PaymentService,
+    repository: PaymentRepository,
+    rails: PaymentRails,
+    replay_cache: ReplayCache,
+    audit_logger: AuditLogger,
+}
+
+impl PaymentCallbackService {
+    pub fn new(
+        payments: PaymentService,
+        repository: PaymentRepository,
+        rails: PaymentRails,
+        replay_cache: ReplayCache,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            payments,
+            repository,
+            rails,
+            replay_cache,
+            audit_logger,
+        }
+    }
+
+    /// The rail calling back under `provider`. Rails without a callback
+    /// secret configured are unknown.
+    pub fn rail(&self, provider: &str) -> AppResult<Arc<dyn PaymentRail>> {
+        self.rails
+            .get(provider)
+            .ok_or_else(|| AppError::NotFound("Payment provider not found".to_string()))
+    }
+
+    pub async fn handle(
+        &self,
+        rail: &dyn PaymentRail,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> AppResult<PaymentCallbackResponse> {
+        let now = Utc::now();
+        if !rail.verify(signature, body, now) {
+            self.log_rejection(rail, "invalid_signature").await;
+            return Err(AppError::Authentication("Invalid callback signature".to_string()));
+        }
+        if let Some((nonce, timestamp)) = rail.replay_nonce(signature, body) {
+            let checked = self.replay_cache.check(rail.name(), &nonce, timestamp, now).await;
+            if let Err(AppError::Authentication(_)) = &checked {
+                self.log_rejection(rail, "replayed").await;
+            }
+            checked?;
+        }
+        let callback = rail.parse(body)?;
+        let raw_body = std::str::from_utf8(body)
+            .map_err(|_| AppError::BadRequest("Payment callback body must be UTF-8".to_string()))?;
+
+        let (stored, processed) = self
+            .repository
+            .record_callback(rail.name(), &callback.event_id, raw_body, signature)
+            .await?;
+        if processed {
+            return Ok(PaymentCallbackResponse::new(stored, true));
+        }
+
+        let (payment_id, outcome, detail) = self.apply(&callback).await?;
+        let completed = self
+            .repository
+            .complete_callback(stored.id, payment_id, callback.status.as_str(), outcome, detail.as_deref())
+            .await?;
+
+        let mut event = AuditEvent::new(AuditEventType::PaymentCallbackReceived)
+            .resource(format!("payment_rail:{}", rail.name()))
+            .action("callback".to_string())
+            .metadata("callback_id".to_string(), json!(completed.id))
+            .metadata("event_id".to_string(), json!(completed.event_id))
+            .metadata("reported_status".to_string(), json!(completed.reported_status))
+            .metadata("outcome".to_string(), json!(completed.outcome))
+            .metadata("detail".to_string(), json!(completed.detail))
+            .compliance_tag("PAYMENT_CALLBACK".to_string());
+        if let Some(payment_id) = completed.payment_id {
+            event = event.metadata("payment_id".to_string(), json!(payment_id));
+        }
+        self.audit_logger.log(event).await;
+
+        Ok(PaymentCallbackResponse::new(completed, false))
+    }
+
+    async fn log_rejection(&self, rail: &dyn PaymentRail, reason: &str) {
+        let event = AuditEvent::new(AuditEventType::PaymentCallbackRejected)
+            .severity(AuditSeverity::Warning)
+            .resource(format!("payment_rail:{}", rail.name()))
+            .action("callback".to_string())
+            .metadata("reason".to_string(), json!(reason))
+            .compliance_tag("PAYMENT_CALLBACK".to_string());
+        self.audit_logger.log(event).await;
+    }
+
+    /// Move the payment the callback names to the reported status, returning
+    /// the payment, what came of it and why
+    async fn apply(
+        &self,
+        callback: &RailCallback,
+    ) -> AppResult<(Option<Uuid>, PaymentCallbackOutcome, Option<String>)> {
+        let payment = self
+            .repository
+            .find_by_references(callback.payment_reference.as_deref(), callback.provider_reference.as_deref())
+            .await?;
+        let Some(payment) = payment else {
+            return Ok((None, PaymentCallbackOutcome::Unmatched, None));
+        };
+
+        let changed = match &callback.status {
+            RailStatus::Processing => self
+                .payments
+                .mark_processing(payment.id, callback.provider_reference.as_deref())
+                .await?
+                .is_some(),
+            RailStatus::Settled => self.payments.settle_payment(payment.id).await?.is_some(),
+            RailStatus::Failed => {
+                let reason = callback.reason.as_deref().unwrap_or("Rejected by the payment provider");
+                self.payments.fail_posted(payment.id, reason).await?.is_some()
+            }
+            RailStatus::Other(status) => {
+                let detail = format!("Status '{}' does not change payments", status);
+                return Ok((Some(payment.id), PaymentCallbackOutcome::Ignored, Some(detail)));
+            }
+        };
+
+        if changed {
+            Ok((Some(payment.id), PaymentCallbackOutcome::Applied, callback.reason.clone()))
+        } else {
+            let detail = format!(
+                "Payment cannot move from {:?} to {}",
+                payment.status,
+                callback.status.as_str()
+            );
+            Ok((Some(payment.id), PaymentCallbackOutcome::Ignored, Some(detail)))
+        }
+    }
+}
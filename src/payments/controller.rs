@@ -1,6 +1,67 @@
-use axum::{extract::State, response::Json};
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
 use serde_json::{json, Value};
-use crate::core::{error::AppResult, AppState};
+use uuid::Uuid;
+use crate::account_controls::{
+    repository::AccountControlRepository, service::AccountFreezeGuard,
+};
+use crate::auth::{
+    middleware::JwtToken,
+    model::JwtClaims,
+    step_up::{StepUpPolicy, StepUpSettings},
+};
+use validator::Validate;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    qr::QrQuery,
+    rbac::permissions,
+    response::ApiResponse,
+    versioning::{ApiVersion, Versioned},
+    AppState,
+};
+use crate::fees::{repository::FeeRepository, service::FeeEngine};
+use crate::fx::controller::fx_quote_service;
+use crate::goals::{repository::GoalRepository, service::GoalBalanceGuard};
+use crate::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use crate::metadata_schemas::{repository::MetadataSchemaRepository, service::MetadataSchemaGuard};
+use crate::shared::bank_details::{self, AccountValidation};
+use crate::shared::types::AccountId;
+use crate::user_data::{repository::UserDataRepository, service::UserDataService};
+use super::callbacks::PaymentCallbackService;
+use super::intents::PaymentIntentService;
+use super::model::{
+    ConfirmPaymentIntentRequest, CreatePaymentIntentRequest, PayeeVerificationResponse, PaymentApprovalRequest,
+    PaymentCallbackResponse, PaymentIntentResponse, PaymentResponse, PaymentSettings, ValidateAccountQuery,
+    VerifyPayeeRequest,
+};
+use super::{payee, rails};
+use super::repository::PaymentRepository;
+use super::service::{PayeeVerificationService, PaymentApprovalService, PaymentService};
+
+pub(crate) fn payment_service(state: &AppState) -> PaymentService {
+    PaymentService::new(
+        PaymentRepository::new(state.postgres.clone()),
+        AccountFreezeGuard::new(
+            AccountControlRepository::new(state.postgres.clone()),
+            state.config.frozen_accounts_allow_credits,
+        ),
+        KycPolicyService::new(
+            KycRepository::new(state.postgres.clone()),
+            KycLimits::from_config(&state.config),
+            state.audit_logger.clone(),
+        ),
+        GoalBalanceGuard::new(GoalRepository::new(state.postgres.clone())),
+        FeeEngine::new(FeeRepository::new(state.postgres.clone())),
+        MetadataSchemaGuard::new(MetadataSchemaRepository::new(state.postgres.clone())),
+        state.event_bus.clone(),
+        PaymentSettings::from_config(&state.config),
+    )
+}
 
 /// Create a new payment
 pub async fn create_payment(
@@ -28,6 +89,68 @@ pub async fn get_payments(
     })))
 }
 
+/// Check a payee name against the destination account's registered holder
+#[utoipa::path(
+    post,
+    path = "/api/v1/payments/verify-payee",
+    tag = "payments",
+    request_body = VerifyPayeeRequest,
+    responses(
+        (status = 200, description = "Match, close match (with the registered name) or no match", body = PayeeVerificationResponse),
+        (status = 400, description = "External payees cannot be verified"),
+        (status = 404, description = "Payee account not found"),
+        (status = 502, description = "Payee directory unavailable")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn verify_payee(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<VerifyPayeeRequest>,
+) -> AppResult<Json<ApiResponse<PayeeVerificationResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let service = PayeeVerificationService::new(
+        PaymentRepository::new(state.postgres.clone()),
+        payee::from_config(&state.config, &state.circuit_breakers, &state.http_clients)?,
+        state.feature_flags.clone(),
+    );
+    let verification = service
+        .verify_payee(request, claims.tenant_id, claims.project_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Payee verified successfully", verification)))
+}
+
+/// Check an IBAN or local account number, and any BIC, and return them
+/// normalized with the bank and country they belong to
+#[utoipa::path(
+    get,
+    path = "/api/v1/payments/validate-account",
+    tag = "payments",
+    params(ValidateAccountQuery),
+    responses(
+        (status = 200, description = "Whether the details are valid, with issues and normalized details", body = AccountValidation),
+        (status = 400, description = "No account details given")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn validate_account(
+    JwtToken(_claims): JwtToken,
+    Query(query): Query<ValidateAccountQuery>,
+) -> AppResult<Json<ApiResponse<AccountValidation>>> {
+    if let Err(validation_errors) = query.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let details = query.details();
+    if details.is_empty() {
+        return Err(AppError::BadRequest("Provide an iban or an account_number".to_string()));
+    }
+
+    Ok(Json(ApiResponse::success("Account details validated", bank_details::validate_account(&details))))
+}
+
 /// Get payment by ID
 pub async fn get_payment_by_id(
     State(_state): State<AppState>,
@@ -41,15 +164,327 @@ pub async fn get_payment_by_id(
     })))
 }
 
-/// Cancel payment
+/// Cancel a payment before it executes
+#[utoipa::path(
+    post,
+    path = "/api/v1/payments/{id}/cancel",
+    tag = "payments",
+    params(("id" = Uuid, Path, description = "Payment ID")),
+    responses(
+        (status = 200, description = "Payment cancelled", body = PaymentResponse),
+        (status = 400, description = "Only scheduled, pending approval or pending payments can be cancelled"),
+        (status = 404, description = "Payment not found"),
+        (status = 409, description = "Payment was executed before it could be cancelled")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn cancel_payment(
-    State(_state): State<AppState>,
-    // TODO: Add path parameter for payment ID
-) -> AppResult<Json<Value>> {
-    // TODO: Implement payment cancellation logic
-    
-    Ok(Json(json!({
-        "message": "Cancel payment endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
-}
\ No newline at end of file
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    version: ApiVersion,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<Versioned<PaymentResponse>>>> {
+    let payment = payment_service(&state).cancel_payment(id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Payment cancelled successfully", Versioned(version, payment))))
+}
+
+/// Render a QR code encoding the payment details
+#[utoipa::path(
+    get,
+    path = "/api/v1/payments/{id}/qr",
+    tag = "payments",
+    params(("id" = Uuid, Path, description = "Payment ID"), QrQuery),
+    responses(
+        (status = 200, description = "QR code image", content(
+            ("image/png" = Vec<u8>),
+            ("image/svg+xml" = String)
+        )),
+        (status = 400, description = "Only pending payments can be shared as a QR code"),
+        (status = 404, description = "Payment not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_payment_qr(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    Query(query): Query<QrQuery>,
+) -> AppResult<Response> {
+    let payload = payment_service(&state)
+        .get_payment_qr_payload(id, claims.tenant_id)
+        .await?;
+
+    let options = state.qr_renderer.options(&query).await?;
+    let (content_type, body) = state.qr_renderer.render(&payload, &options)?;
+
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
+/// List payments awaiting approval (approvers only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/payments/approvals",
+    tag = "payments",
+    responses(
+        (status = 200, description = "Payments awaiting approval", body = [PaymentResponse]),
+        (status = 403, description = "Caller lacks the payment approval permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_pending_approvals(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    version: ApiVersion,
+) -> AppResult<Json<ApiResponse<Versioned<Vec<PaymentResponse>>>>> {
+    state
+        .authorize(claims.developer_id, permissions::approve_payments(), ip, "payment_approvals".to_string())
+        .await?;
+
+    let payments = payment_approval_service(&state).list_pending(claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success(
+        "Payments awaiting approval retrieved successfully",
+        Versioned(version, payments),
+    )))
+}
+
+/// Approve or reject a payment awaiting approval (approvers only, never the
+/// payment's creator)
+#[utoipa::path(
+    post,
+    path = "/api/v1/payments/{id}/approve",
+    tag = "payments",
+    params(("id" = Uuid, Path, description = "Payment ID")),
+    request_body = PaymentApprovalRequest,
+    responses(
+        (status = 200, description = "Decision recorded", body = PaymentResponse),
+        (status = 400, description = "Approved payment failed the debit checks"),
+        (status = 403, description = "Caller lacks the approval permission or created the payment"),
+        (status = 404, description = "Payment not found"),
+        (status = 409, description = "Payment is not awaiting approval")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn approve_payment(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    version: ApiVersion,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<PaymentApprovalRequest>,
+) -> AppResult<Json<ApiResponse<Versioned<PaymentResponse>>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::approve_payments(), ip, format!("payment:{}", id))
+        .await?;
+
+    let payment = payment_approval_service(&state)
+        .decide(id, claims.tenant_id, claims.developer_id, request)
+        .await?;
+    Ok(Json(ApiResponse::success("Payment approval decision recorded", Versioned(version, payment))))
+}
+
+fn payment_approval_service(state: &AppState) -> PaymentApprovalService {
+    PaymentApprovalService::new(
+        payment_service(state),
+        PaymentRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+fn payment_intent_service(state: &AppState) -> PaymentIntentService {
+    PaymentIntentService::new(
+        payment_service(state),
+        PaymentRepository::new(state.postgres.clone()),
+        StepUpPolicy::new(StepUpSettings::from_config(&state.config), state.audit_logger.clone()),
+        state.audit_logger.clone(),
+        chrono::Duration::seconds(state.config.payment_intent_ttl_seconds),
+    )
+}
+
+/// User tokens may only pay out of the user's own accounts
+async fn ensure_payer_access(state: &AppState, claims: &JwtClaims, account_id: AccountId) -> AppResult<()> {
+    if claims.user_id.is_some() {
+        UserDataService::new(UserDataRepository::new(state.postgres.clone()))
+            .ensure_account_access(claims, account_id)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Price a payment, fees included, for the client to confirm. The
+/// confirmation token is only returned here.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payments/intents",
+    tag = "payments",
+    request_body = CreatePaymentIntentRequest,
+    responses(
+        (status = 201, description = "Intent awaiting confirmation, with its confirmation token", body = PaymentIntentResponse),
+        (status = 400, description = "Invalid request, or the FX quote is for another amount or currency"),
+        (status = 404, description = "Account or FX quote not found, or not the user's own account"),
+        (status = 409, description = "The FX quote was already used, or has expired and requote was not asked for")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_payment_intent(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    version: ApiVersion,
+    ApiJson(request): ApiJson<CreatePaymentIntentRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<Versioned<PaymentIntentResponse>>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    ensure_payer_access(&state, &claims, request.from_account_id).await?;
+
+    let quote = match request.quote_id {
+        Some(quote_id) => Some(
+            fx_quote_service(&state)?
+                .redeem(quote_id, claims.tenant_id, &request.currency, request.amount, request.requote)
+                .await?,
+        ),
+        None => None,
+    };
+    let created = payment_intent_service(&state).create(&claims, request, quote.as_ref()).await;
+    // The quote may be used again if the intent could not be created
+    if let (Err(_), Some(quote)) = (&created, &quote) {
+        fx_quote_service(&state)?.release(quote).await?;
+    }
+    let intent = created?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Payment intent created successfully", Versioned(version, intent))),
+    ))
+}
+
+/// Get a payment intent
+#[utoipa::path(
+    get,
+    path = "/api/v1/payments/intents/{id}",
+    tag = "payments",
+    params(("id" = Uuid, Path, description = "Payment intent ID")),
+    responses(
+        (status = 200, description = "The payment intent", body = PaymentIntentResponse),
+        (status = 404, description = "Payment intent not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_payment_intent(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    version: ApiVersion,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<Versioned<PaymentIntentResponse>>>> {
+    let intent = payment_intent_service(&state).get(id, claims.tenant_id).await?;
+    ensure_payer_access(&state, &claims, intent.from_account_id).await?;
+
+    Ok(Json(ApiResponse::success(
+        "Payment intent retrieved successfully",
+        Versioned(version, PaymentIntentResponse::from(intent)),
+    )))
+}
+
+/// Confirm a payment intent with its confirmation token, making the payment
+/// it priced. End users confirming large payments must have logged in
+/// recently with a one-time code.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payments/intents/{id}/confirm",
+    tag = "payments",
+    params(("id" = Uuid, Path, description = "Payment intent ID")),
+    request_body = ConfirmPaymentIntentRequest,
+    responses(
+        (status = 200, description = "Payment made; the intent is processing", body = PaymentIntentResponse),
+        (status = 400, description = "The payment failed its checks; the intent has failed"),
+        (status = 401, description = "Invalid confirmation token, or the user must log in again with a one-time code (see the WWW-Authenticate header)"),
+        (status = 404, description = "Payment intent not found"),
+        (status = 409, description = "The intent was already confirmed, cancelled or has expired, or the project blocks the payment as a duplicate and force_override was not set")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn confirm_payment_intent(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    version: ApiVersion,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<ConfirmPaymentIntentRequest>,
+) -> AppResult<Json<ApiResponse<Versioned<PaymentIntentResponse>>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let service = payment_intent_service(&state);
+    let intent = service.get(id, claims.tenant_id).await?;
+    ensure_payer_access(&state, &claims, intent.from_account_id).await?;
+
+    let intent = service.confirm(&claims, id, &request.confirmation_token).await?;
+    Ok(Json(ApiResponse::success("Payment intent confirmed successfully", Versioned(version, intent))))
+}
+
+/// Cancel a payment intent before it is confirmed
+#[utoipa::path(
+    post,
+    path = "/api/v1/payments/intents/{id}/cancel",
+    tag = "payments",
+    params(("id" = Uuid, Path, description = "Payment intent ID")),
+    responses(
+        (status = 200, description = "Payment intent cancelled", body = PaymentIntentResponse),
+        (status = 404, description = "Payment intent not found"),
+        (status = 409, description = "The intent was already confirmed, cancelled or has expired")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn cancel_payment_intent(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    version: ApiVersion,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<Versioned<PaymentIntentResponse>>>> {
+    let service = payment_intent_service(&state);
+    let intent = service.get(id, claims.tenant_id).await?;
+    ensure_payer_access(&state, &claims, intent.from_account_id).await?;
+
+    let intent = service.cancel(id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Payment intent cancelled successfully", Versioned(version, intent))))
+}
+
+/// Receive a payment rail's status callback (public, signature-authenticated).
+/// `generic` rails sign the body with a hex HMAC-SHA256 in
+/// `X-Callback-Signature`; `stripe` sends its usual `Stripe-Signature`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payments/callbacks/{provider}",
+    tag = "payments",
+    params(
+        ("provider" = String, Path, description = "Payment rail: generic or stripe"),
+        ("X-Callback-Signature" = Option<String>, Header, description = "Hex HMAC-SHA256 of the body (generic)"),
+        ("Stripe-Signature" = Option<String>, Header, description = "Timestamped signature (stripe)")
+    ),
+    responses(
+        (status = 200, description = "Callback recorded, with what came of it", body = PaymentCallbackResponse),
+        (status = 400, description = "Unreadable callback body"),
+        (status = 401, description = "Invalid or replayed callback signature"),
+        (status = 404, description = "Payment provider not found")
+    )
+)]
+pub async fn payment_callback(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<Json<ApiResponse<PaymentCallbackResponse>>> {
+    let service = PaymentCallbackService::new(
+        payment_service(&state),
+        PaymentRepository::new(state.postgres.clone()),
+        rails::from_config(&state.config),
+        state.replay_cache.clone(),
+        state.audit_logger.clone(),
+    );
+    let rail = service.rail(&provider)?;
+    let signature = headers.get(rail.signature_header()).and_then(|value| value.to_str().ok());
+    let callback = service.handle(rail.as_ref(), signature, &body).await?;
+    Ok(Json(ApiResponse::success("Payment callback recorded", callback)))
+}
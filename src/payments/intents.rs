@@ -0,0 +1,256 @@
+use chrono::{Duration, Utc};
+use sqlx::types::Json;
+use uuid::Uuid;
+use crate::auth::model::JwtClaims;
+use crate::auth::step_up::{SensitiveAction, StepUpPolicy};
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::crypto::{generate_token, hash_token};
+use crate::core::error::{AppError, AppResult};
+use crate::fx::model::FxQuote;
+use crate::shared::types::TenantId;
+use super::model::{
+    CreatePaymentIntentRequest, CreatePaymentRequest, ExecuteAt, PaymentIntent, PaymentIntentResponse,
+    PaymentIntentStatus, PaymentPrice,
+};
+use super::repository::PaymentRepository;
+use super::service::PaymentService;
+
+/// Two-step payments: the server prices a payment intent, fees included,
+/// and the client confirms it with the token it was given.
+///
+/// Confirmation takes no amounts, so the payment made is the one priced,
+/// at the fees quoted, whatever the client sends. End users confirming
+/// large intents must have logged in recently with a one-time code, like
+/// for transfers; a challenged confirmation leaves the intent to be
+/// confirmed again after the login. Each intent is confirmed at most once
+/// and only until it expires.
+pub struct PaymentIntentService {
+    payments: PaymentService,
+    repository: PaymentRepository,
+    step_up: StepUpPolicy,
+    audit_logger: AuditLogger,
+    ttl: Duration,
+}
+
+impl PaymentIntentService {
+    pub fn new(
+        payments: PaymentService,
+        repository: PaymentRepository,
+        step_up: StepUpPolicy,
+        audit_logger: AuditLogger,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            payments,
+            repository,
+            step_up,
+            audit_logger,
+            ttl,
+        }
+    }
+
+    /// Price a payment and hold the price until the intent expires. An
+    /// intent converted with an FX quote expires with the quote, and an
+    /// account it pays into must hold the quote's target currency. An
+    /// execution time is checked here and schedules the payment made on
+    /// confirmation. The confirmation token is only returned here.
+    pub async fn create(
+        &self,
+        claims: &JwtClaims,
+        request: CreatePaymentIntentRequest,
+        quote: Option<&FxQuote>,
+    ) -> AppResult<PaymentIntentResponse> {
+        if let Some(quote) = quote {
+            self.ensure_payee_holds(&request, &quote.target_currency).await?;
+        }
+        let schedule = match &request.execute_at {
+            Some(execute_at) => Some(
+                self.payments
+                    .resolve_execution_time(execute_at, request.timezone.as_deref())
+                    .await?,
+            ),
+            None => None,
+        };
+        let fees = self
+            .payments
+            .quote_fees(Some(claims.project_id), &request.payment_method, &request.currency, request.amount)
+            .await?;
+
+        let token = generate_token();
+        let now = Utc::now();
+        let expires_at = match quote {
+            Some(quote) => quote.expires_at.min(now + self.ttl),
+            None => now + self.ttl,
+        };
+        let intent = PaymentIntent {
+            id: Uuid::new_v4(),
+            tenant_id: Some(claims.tenant_id),
+            project_id: Some(claims.project_id),
+            created_by: claims.developer_id,
+            from_account_id: request.from_account_id,
+            to_account_id: request.to_account_id,
+            to_virtual_account_id: request.to_virtual_account_id,
+            amount: request.amount,
+            currency: request.currency,
+            payment_method: request.payment_method,
+            description: request.description,
+            recipient_info: request.recipient_info,
+            metadata: request.metadata,
+            fee_amount: fees.total,
+            fee_breakdown: Json(fees),
+            fx_quote_id: quote.map(|quote| quote.id),
+            target_amount: quote.map(|quote| quote.target_amount),
+            target_currency: quote.map(|quote| quote.target_currency.clone()),
+            execute_at: schedule.as_ref().map(|(execute_at, _)| *execute_at),
+            execution_timezone: schedule.map(|(_, timezone)| timezone),
+            force_override: request.force_override,
+            status: PaymentIntentStatus::RequiresConfirmation,
+            confirmation_token_hash: hash_token(&token),
+            payment_id: None,
+            failure_reason: None,
+            expires_at,
+            confirmed_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let intent = self.repository.create_intent(&intent).await?;
+
+        Ok(PaymentIntentResponse {
+            confirmation_token: Some(token),
+            ..PaymentIntentResponse::from(intent)
+        })
+    }
+
+    pub async fn get(&self, intent_id: Uuid, tenant_id: TenantId) -> AppResult<PaymentIntent> {
+        self.repository
+            .find_intent_for_tenant(intent_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Payment intent not found".to_string()))
+    }
+
+    /// Make the intent's payment, exactly as priced. A payment that cannot
+    /// be made fails the intent with the reason.
+    pub async fn confirm(&self, claims: &JwtClaims, intent_id: Uuid, token: &str) -> AppResult<PaymentIntentResponse> {
+        let intent = self.get(intent_id, claims.tenant_id).await?;
+        if hash_token(token) != intent.confirmation_token_hash {
+            let event = AuditEvent::new(AuditEventType::AccessDenied)
+                .severity(AuditSeverity::Warning)
+                .user_id(claims.user_id.unwrap_or(claims.developer_id))
+                .project_id(claims.project_id)
+                .resource(format!("payment_intent:{}", intent.id))
+                .action("confirm".to_string())
+                .metadata("reason".to_string(), serde_json::json!("invalid_confirmation_token"));
+            self.audit_logger.log(event).await;
+            return Err(AppError::Authentication("Invalid confirmation token".to_string()));
+        }
+        self.ensure_awaiting_confirmation(&intent).await?;
+
+        self.step_up
+            .require(
+                claims,
+                SensitiveAction::Transfer {
+                    from_account_id: intent.from_account_id,
+                    amount: intent.amount,
+                },
+            )
+            .await?;
+
+        let intent = self
+            .repository
+            .begin_intent_processing(intent.id)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Payment intent was confirmed or closed in the meantime".to_string()))?;
+        let request = CreatePaymentRequest {
+            to_account_id: intent.to_account_id,
+            to_virtual_account_id: intent.to_virtual_account_id,
+            amount: intent.amount,
+            currency: intent.currency.clone(),
+            payment_method: intent.payment_method.clone(),
+            description: intent.description.clone(),
+            recipient_info: intent.recipient_info.clone(),
+            metadata: intent.metadata.clone(),
+            execute_at: intent.execute_at.map(|execute_at| ExecuteAt::Instant(execute_at.fixed_offset())),
+            timezone: intent.execution_timezone.clone(),
+            force_override: intent.force_override,
+        };
+        let created = self
+            .payments
+            .create_priced_payment(
+                intent.from_account_id,
+                intent.project_id,
+                intent.tenant_id,
+                intent.created_by,
+                request,
+                PaymentPrice {
+                    fees: intent.fee_breakdown.0.clone(),
+                    target_amount: intent.target_amount,
+                    target_currency: intent.target_currency.clone(),
+                },
+            )
+            .await;
+
+        match created {
+            Ok(payment) => {
+                let intent = self.repository.attach_intent_payment(intent.id, payment.id).await?;
+                Ok(PaymentIntentResponse::from(intent))
+            }
+            Err(error) => {
+                self.repository.fail_intent(intent.id, &error.to_string()).await?;
+                Err(error)
+            }
+        }
+    }
+
+    /// Cancel an intent before it is confirmed
+    pub async fn cancel(&self, intent_id: Uuid, tenant_id: TenantId) -> AppResult<PaymentIntentResponse> {
+        let intent = self.get(intent_id, tenant_id).await?;
+        self.ensure_awaiting_confirmation(&intent).await?;
+
+        let cancelled = self
+            .repository
+            .close_intent(intent.id, PaymentIntentStatus::Cancelled)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Payment intent was confirmed or closed in the meantime".to_string()))?;
+        Ok(PaymentIntentResponse::from(cancelled))
+    }
+
+    /// Refuse a converted intent paying into an account, or virtual account,
+    /// held in another currency than the one the quote converts into
+    async fn ensure_payee_holds(&self, request: &CreatePaymentIntentRequest, currency: &str) -> AppResult<()> {
+        let held = match (request.to_virtual_account_id, request.to_account_id) {
+            (Some(virtual_account_id), _) => self
+                .repository
+                .find_virtual_account(virtual_account_id)
+                .await?
+                .map(|(_, currency, _)| currency)
+                .ok_or_else(|| AppError::NotFound("Virtual account not found".to_string()))?,
+            (None, Some(to_account_id)) => self
+                .repository
+                .find_account_currency(to_account_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Account not found".to_string()))?,
+            // External payees are paid in whatever currency the rail delivers
+            (None, None) => return Ok(()),
+        };
+        if held != currency {
+            return Err(AppError::Validation(format!(
+                "The payee account holds {}, but the FX quote converts into {}",
+                held, currency
+            )));
+        }
+        Ok(())
+    }
+
+    /// Refuse intents no longer awaiting confirmation, marking them expired
+    /// once past their expiry
+    async fn ensure_awaiting_confirmation(&self, intent: &PaymentIntent) -> AppResult<()> {
+        if intent.status != PaymentIntentStatus::RequiresConfirmation {
+            return Err(AppError::Conflict(format!("Payment intent is {}", intent.status.as_str())));
+        }
+        if intent.expires_at <= Utc::now() {
+            self.repository.close_intent(intent.id, PaymentIntentStatus::Expired).await?;
+            return Err(AppError::Conflict("Payment intent has expired".to_string()));
+        }
+        Ok(())
+    }
+}
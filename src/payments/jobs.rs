@@ -0,0 +1,102 @@
+use chrono::Utc;
+use crate::core::error::AppResult;
+use crate::core::AppState;
+use super::controller::payment_service;
+use super::model::PaymentStatus;
+use super::repository::PaymentRepository;
+
+const SCHEDULED_PAYMENT_JOB: &str = "scheduled_payment_executor";
+
+const PAYMENT_SETTLEMENT_JOB: &str = "payment_settlement";
+
+/// Periodically execute scheduled payments whose execution time has passed
+pub fn spawn_scheduled_payment_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.scheduled_payment_check_interval_seconds);
+    state.job_monitor.register(SCHEDULED_PAYMENT_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match execute_due_payments(&state).await {
+                Ok(count) => {
+                    state.job_monitor.record_success(SCHEDULED_PAYMENT_JOB);
+                    if count > 0 {
+                        tracing::info!("Executed {} scheduled payments", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(SCHEDULED_PAYMENT_JOB, e.to_string());
+                    tracing::error!("Scheduled payment job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+async fn execute_due_payments(state: &AppState) -> AppResult<usize> {
+    let due = PaymentRepository::new(state.postgres.clone())
+        .find_due_scheduled(Utc::now(), state.config.scheduled_payment_batch_size)
+        .await?;
+
+    let service = payment_service(state);
+    let mut executed = 0;
+    for payment in &due {
+        match service.execute_scheduled_payment(payment).await? {
+            Some(result) if result.execution_error.is_some() => {
+                tracing::warn!(
+                    payment_id = %payment.id,
+                    "Scheduled payment failed: {}",
+                    result.execution_error.unwrap_or_default()
+                );
+            }
+            // Past its cut-off; runs in the next processing window
+            Some(result) if matches!(result.status, PaymentStatus::Scheduled) => {}
+            Some(_) => executed += 1,
+            None => {}
+        }
+    }
+
+    Ok(executed)
+}
+
+/// Periodically settle pending payments whose clearing delay has passed
+pub fn spawn_settlement_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.payment_settlement_check_interval_seconds);
+    state.job_monitor.register(PAYMENT_SETTLEMENT_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match settle_due_payments(&state).await {
+                Ok(count) => {
+                    state.job_monitor.record_success(PAYMENT_SETTLEMENT_JOB);
+                    if count > 0 {
+                        tracing::info!("Settled {} payments", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(PAYMENT_SETTLEMENT_JOB, e.to_string());
+                    tracing::error!("Payment settlement job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+async fn settle_due_payments(state: &AppState) -> AppResult<usize> {
+    let due = PaymentRepository::new(state.postgres.clone())
+        .find_due_settlement(Utc::now(), state.config.payment_settlement_batch_size)
+        .await?;
+
+    let service = payment_service(state);
+    let mut settled = 0;
+    for payment in &due {
+        if service.settle_payment(payment.id).await?.is_some() {
+            settled += 1;
+        }
+    }
+
+    Ok(settled)
+}
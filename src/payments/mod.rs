@@ -1,5 +1,11 @@
+pub mod calendar;
+pub mod callbacks;
 pub mod controller;
+pub mod intents;
+pub mod jobs;
 pub mod model;
+pub mod payee;
+pub mod rails;
 pub mod repository;
 pub mod service;
 
@@ -10,6 +16,16 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", post(controller::create_payment))
         .route("/", get(controller::get_payments))
+        .route("/verify-payee", post(controller::verify_payee))
+        .route("/validate-account", get(controller::validate_account))
+        .route("/approvals", get(controller::list_pending_approvals))
+        .route("/intents", post(controller::create_payment_intent))
+        .route("/intents/:id", get(controller::get_payment_intent))
+        .route("/intents/:id/confirm", post(controller::confirm_payment_intent))
+        .route("/intents/:id/cancel", post(controller::cancel_payment_intent))
+        .route("/callbacks/:provider", post(controller::payment_callback))
         .route("/:id", get(controller::get_payment_by_id))
+        .route("/:id/approve", post(controller::approve_payment))
         .route("/:id/cancel", post(controller::cancel_payment))
+        .route("/:id/qr", get(controller::get_payment_qr))
 }
\ No newline at end of file
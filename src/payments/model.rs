@@ -1,14 +1,27 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{types::Json, FromRow};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
-use crate::shared::types::{AccountId, Amount, Currency};
+use crate::core::config::Config;
+use crate::core::versioning::{ApiVersion, VersionedBody};
+use crate::devices::model::DeviceRisk;
+use crate::fees::model::FeeBreakdown;
+use crate::shared::bank_details::AccountDetails;
+use crate::shared::types::{AccountId, Amount, Currency, TenantId};
+use super::calendar::ProcessingCalendar;
 
 /// Payment status enum
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "payment_status", rename_all = "lowercase")]
 pub enum PaymentStatus {
+    /// Future-dated, waiting for the scheduler to execute it
+    Scheduled,
+    /// Over the approval threshold, or made from an untrusted device,
+    /// waiting for a second user to approve it
+    #[sqlx(rename = "pending_approval")]
+    PendingApproval,
     Pending,
     Processing,
     Completed,
@@ -17,9 +30,24 @@ pub enum PaymentStatus {
     Refunded,
 }
 
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Scheduled => "scheduled",
+            PaymentStatus::PendingApproval => "pending_approval",
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Processing => "processing",
+            PaymentStatus::Completed => "completed",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::Cancelled => "cancelled",
+            PaymentStatus::Refunded => "refunded",
+        }
+    }
+}
+
 /// Payment method enum
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "payment_method", rename_all = "lowercase")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "payment_method", rename_all = "snake_case")]
 pub enum PaymentMethod {
     BankTransfer,
     Card,
@@ -27,6 +55,17 @@ pub enum PaymentMethod {
     Crypto,
 }
 
+impl PaymentMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethod::BankTransfer => "bank_transfer",
+            PaymentMethod::Card => "card",
+            PaymentMethod::Wallet => "wallet",
+            PaymentMethod::Crypto => "crypto",
+        }
+    }
+}
+
 /// Payment model for database
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Payment {
@@ -42,14 +81,138 @@ pub struct Payment {
     pub recipient_info: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
     pub external_reference: Option<String>,
+    /// Project the payment was created through, used to select fee schedules
+    pub project_id: Option<Uuid>,
+    pub tenant_id: Option<TenantId>,
+    /// Fees charged on top of the principal `amount`
+    pub fee_amount: Amount,
+    pub fee_breakdown: Option<Json<FeeBreakdown>>,
+    pub execute_at: Option<DateTime<Utc>>,
+    /// IANA time zone the execution time was requested in
+    pub execution_timezone: Option<String>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub execution_error: Option<String>,
+    /// Ledger transaction posted for the payment once it executes
+    pub transaction_id: Option<Uuid>,
+    /// When the payment clears and its funds move between ledger balances
+    pub expected_settlement_at: Option<DateTime<Utc>>,
+    pub settled_at: Option<DateTime<Utc>>,
+    /// User who created the payment, and may not approve it
+    pub created_by: Option<Uuid>,
+    /// Earlier payment this one was flagged as a duplicate of
+    pub duplicate_of: Option<Uuid>,
+    /// Virtual account of the payee the credit is attributed to
+    pub to_virtual_account_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Amount the payee is credited, when an FX quote converted the payment
+    pub target_amount: Option<Amount>,
+    pub target_currency: Option<Currency>,
+}
+
+/// What happens to a payment that repeats a recent one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "duplicate_payment_action", rename_all = "snake_case")]
+pub enum DuplicatePaymentAction {
+    /// No duplicate detection
+    Off,
+    /// Create the payment, marking which payment it duplicates
+    Flag,
+    /// Refuse the payment unless the caller forces it through
+    Block,
+}
+
+/// Duplicate detection applied to payments without a project, matching the
+/// defaults projects start with
+pub const DEFAULT_DUPLICATE_PAYMENT_ACTION: DuplicatePaymentAction = DuplicatePaymentAction::Flag;
+pub const DEFAULT_DUPLICATE_PAYMENT_WINDOW_MINUTES: i32 = 10;
+
+/// Configured scheduling window, approval threshold, clearing delays and
+/// processing calendar
+#[derive(Debug, Clone)]
+pub struct PaymentSettings {
+    /// How far ahead a payment may be scheduled
+    pub max_schedule_days: i64,
+    /// Payments of at least this amount need a second user's approval; 0 disables approvals
+    pub approval_threshold: Amount,
+    /// Payments of at least this amount from a new or unbound device need
+    /// approval too; 0 disables the hold
+    pub device_review_threshold: Amount,
+    pub clearing_delays: ClearingDelays,
+    pub calendar: ProcessingCalendar,
+}
+
+impl PaymentSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_schedule_days: config.scheduled_payment_max_days_ahead,
+            approval_threshold: config.payment_approval_threshold,
+            device_review_threshold: config.device_review_threshold,
+            clearing_delays: ClearingDelays::from_config(config),
+            calendar: ProcessingCalendar::from_config(config),
+        }
+    }
+
+    pub fn requires_approval(&self, amount: Amount) -> bool {
+        self.approval_threshold > 0 && amount >= self.approval_threshold
+    }
+
+    /// Whether a payment made from a device of the given risk must be
+    /// approved; `None` for payments no end user's device made
+    pub fn requires_device_review(&self, risk: Option<DeviceRisk>, amount: Amount) -> bool {
+        let untrusted = matches!(risk, Some(DeviceRisk::New | DeviceRisk::Unbound));
+        untrusted && self.device_review_threshold > 0 && amount >= self.device_review_threshold
+    }
+}
+
+/// How long each payment method takes to clear. Until then the payer's
+/// available balance is held and neither ledger balance moves.
+#[derive(Debug, Clone, Copy)]
+pub struct ClearingDelays {
+    bank_transfer: Duration,
+    card: Duration,
+    wallet: Duration,
+    crypto: Duration,
+}
+
+impl ClearingDelays {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            bank_transfer: Duration::hours(config.payment_clearing_hours_bank_transfer),
+            card: Duration::hours(config.payment_clearing_hours_card),
+            wallet: Duration::hours(config.payment_clearing_hours_wallet),
+            crypto: Duration::hours(config.payment_clearing_hours_crypto),
+        }
+    }
+
+    pub fn for_method(&self, method: &PaymentMethod) -> Duration {
+        match method {
+            PaymentMethod::BankTransfer => self.bank_transfer,
+            PaymentMethod::Card => self.card,
+            PaymentMethod::Wallet => self.wallet,
+            PaymentMethod::Crypto => self.crypto,
+        }
+    }
+}
+
+/// When a future-dated payment should run: either an instant with a UTC
+/// offset (`2026-11-01T09:00:00+01:00`) or a wall-clock time
+/// (`2026-11-01T09:00:00`) interpreted in the request's `timezone`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExecuteAt {
+    Instant(DateTime<FixedOffset>),
+    Local(NaiveDateTime),
 }
 
 /// Create payment request
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreatePaymentRequest {
     pub to_account_id: Option<AccountId>,
+    /// Pay into a virtual account; its parent is credited and the posting
+    /// attributed to it. `to_account_id` may be omitted.
+    pub to_virtual_account_id: Option<Uuid>,
     #[validate(range(min = 1))]
     pub amount: Amount,
     pub currency: Currency,
@@ -57,10 +220,28 @@ pub struct CreatePaymentRequest {
     pub description: Option<String>,
     pub recipient_info: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
+    /// Schedule the payment instead of executing it immediately
+    pub execute_at: Option<ExecuteAt>,
+    /// IANA time zone name (e.g. `Europe/London`), defaults to UTC
+    #[validate(length(min = 1, max = 64))]
+    pub timezone: Option<String>,
+    /// Create the payment even though the project blocks it as a duplicate
+    #[serde(default)]
+    pub force_override: bool,
+}
+
+/// What a payment was priced at: the fees charged on top of its amount and,
+/// when an FX quote converts it, the amount and currency the payee is credited
+#[derive(Debug, Clone)]
+pub struct PaymentPrice {
+    pub fees: FeeBreakdown,
+    pub target_amount: Option<Amount>,
+    pub target_currency: Option<Currency>,
 }
 
-/// Payment response
-#[derive(Debug, Serialize)]
+/// Payment response. API v2 returns `payment_method` and `status` in
+/// snake_case, e.g. `bank_transfer` and `pending_approval`.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PaymentResponse {
     pub id: Uuid,
     pub from_account_id: AccountId,
@@ -71,6 +252,20 @@ pub struct PaymentResponse {
     pub status: PaymentStatus,
     pub reference: String,
     pub description: Option<String>,
+    pub fee_amount: Amount,
+    pub fees: Option<FeeBreakdown>,
+    pub execute_at: Option<DateTime<Utc>>,
+    pub execution_timezone: Option<String>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub execution_error: Option<String>,
+    /// When the payment settles, counting banking days only for methods
+    /// with a cut-off; projected from `execute_at` for scheduled payments
+    pub expected_settlement_at: Option<DateTime<Utc>>,
+    pub settled_at: Option<DateTime<Utc>>,
+    /// Earlier payment with the same payer, amount, beneficiary and
+    /// reference this one was flagged as a duplicate of
+    pub duplicate_of: Option<Uuid>,
+    pub to_virtual_account_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -86,7 +281,370 @@ impl From<Payment> for PaymentResponse {
             status: payment.status,
             reference: payment.reference,
             description: payment.description,
+            fee_amount: payment.fee_amount,
+            fees: payment.fee_breakdown.map(|Json(fees)| fees),
+            execute_at: payment.execute_at,
+            execution_timezone: payment.execution_timezone,
+            executed_at: payment.executed_at,
+            execution_error: payment.execution_error,
+            expected_settlement_at: payment.expected_settlement_at,
+            settled_at: payment.settled_at,
+            duplicate_of: payment.duplicate_of,
+            to_virtual_account_id: payment.to_virtual_account_id,
             created_at: payment.created_at,
         }
     }
-}
\ No newline at end of file
+}
+
+impl VersionedBody for PaymentResponse {
+    fn for_version(&self, version: ApiVersion) -> serde_json::Value {
+        let mut body = serde_json::to_value(self).unwrap_or_default();
+        if version >= ApiVersion::V2 {
+            body["payment_method"] = self.payment_method.as_str().into();
+            body["status"] = self.status.as_str().into();
+        }
+        body
+    }
+}
+
+/// Where a payment intent is in its confirmation flow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "payment_intent_status", rename_all = "snake_case")]
+pub enum PaymentIntentStatus {
+    /// Priced, waiting for the client to confirm it with its token
+    RequiresConfirmation,
+    /// Confirmed; the payment was made and follows its own status
+    Processing,
+    /// Confirmed, but the payment could not be made
+    Failed,
+    Cancelled,
+    /// Not confirmed in time
+    Expired,
+}
+
+impl PaymentIntentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentIntentStatus::RequiresConfirmation => "requires_confirmation",
+            PaymentIntentStatus::Processing => "processing",
+            PaymentIntentStatus::Failed => "failed",
+            PaymentIntentStatus::Cancelled => "cancelled",
+            PaymentIntentStatus::Expired => "expired",
+        }
+    }
+}
+
+/// A payment priced by the server that is only made once the client
+/// confirms it, for exactly the amount and fees it was priced at
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PaymentIntent {
+    pub id: Uuid,
+    pub tenant_id: Option<TenantId>,
+    pub project_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub from_account_id: AccountId,
+    pub to_account_id: Option<AccountId>,
+    pub to_virtual_account_id: Option<Uuid>,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub payment_method: PaymentMethod,
+    pub description: Option<String>,
+    pub recipient_info: Option<serde_json::Value>,
+    pub metadata: Option<serde_json::Value>,
+    pub fee_amount: Amount,
+    pub fee_breakdown: Json<FeeBreakdown>,
+    /// The FX quote converting the amount; the intent expires with it
+    pub fx_quote_id: Option<Uuid>,
+    /// The quote's converted amount, credited to the payee
+    pub target_amount: Option<Amount>,
+    pub target_currency: Option<Currency>,
+    /// When the payment made on confirmation is scheduled to run
+    pub execute_at: Option<DateTime<Utc>>,
+    /// IANA time zone the execution time was requested in
+    pub execution_timezone: Option<String>,
+    /// Make the payment even though the project blocks it as a duplicate
+    pub force_override: bool,
+    pub status: PaymentIntentStatus,
+    #[serde(skip)]
+    pub confirmation_token_hash: String,
+    pub payment_id: Option<Uuid>,
+    pub failure_reason: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create payment intent request. Fees are priced by the server; the
+/// client only confirms them.
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreatePaymentIntentRequest {
+    pub from_account_id: AccountId,
+    pub to_account_id: Option<AccountId>,
+    /// Pay into a virtual account; `to_account_id` may be omitted
+    pub to_virtual_account_id: Option<Uuid>,
+    #[validate(range(min = 1))]
+    pub amount: Amount,
+    #[validate(length(equal = 3))]
+    pub currency: Currency,
+    pub payment_method: PaymentMethod,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    pub recipient_info: Option<serde_json::Value>,
+    pub metadata: Option<serde_json::Value>,
+    /// FX quote converting the amount, from `currency`, at a locked rate
+    pub quote_id: Option<Uuid>,
+    /// Price an expired quote again at the current rate rather than refuse it
+    #[serde(default)]
+    pub requote: bool,
+    /// Schedule the payment instead of executing it on confirmation: an
+    /// instant with a UTC offset, or a wall-clock time in `timezone`
+    #[schema(value_type = Option<String>, example = "2026-11-01T09:00:00")]
+    pub execute_at: Option<ExecuteAt>,
+    /// IANA time zone name (e.g. `Europe/London`), defaults to UTC
+    #[validate(length(min = 1, max = 64))]
+    pub timezone: Option<String>,
+    /// Make the payment on confirmation even though the project blocks it as
+    /// a duplicate of a recent payment
+    #[serde(default)]
+    pub force_override: bool,
+}
+
+/// Confirm payment intent request
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ConfirmPaymentIntentRequest {
+    /// Token returned when the intent was created
+    #[validate(length(min = 1, max = 128))]
+    pub confirmation_token: String,
+}
+
+/// Payment intent response. API v2 returns `payment_method` in snake_case.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PaymentIntentResponse {
+    pub id: Uuid,
+    pub status: PaymentIntentStatus,
+    pub from_account_id: AccountId,
+    pub to_account_id: Option<AccountId>,
+    pub to_virtual_account_id: Option<Uuid>,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub payment_method: PaymentMethod,
+    pub description: Option<String>,
+    pub fee_amount: Amount,
+    pub fees: FeeBreakdown,
+    /// Amount and fees together, as debited from the payer
+    pub total_amount: Amount,
+    /// The FX quote converting the amount, which may have replaced the
+    /// expired quote asked for
+    pub fx_quote_id: Option<Uuid>,
+    /// Amount the payee is credited, in `target_currency`, when an FX quote
+    /// converts the payment
+    pub target_amount: Option<Amount>,
+    pub target_currency: Option<Currency>,
+    /// When the payment is scheduled to run once the intent is confirmed
+    pub execute_at: Option<DateTime<Utc>>,
+    pub execution_timezone: Option<String>,
+    /// Only returned when the intent is created; the client confirms the
+    /// intent with it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// The payment made on confirmation
+    pub payment_id: Option<Uuid>,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<PaymentIntent> for PaymentIntentResponse {
+    fn from(intent: PaymentIntent) -> Self {
+        Self {
+            id: intent.id,
+            status: intent.status,
+            from_account_id: intent.from_account_id,
+            to_account_id: intent.to_account_id,
+            to_virtual_account_id: intent.to_virtual_account_id,
+            amount: intent.amount,
+            currency: intent.currency,
+            payment_method: intent.payment_method,
+            description: intent.description,
+            fee_amount: intent.fee_amount,
+            fees: intent.fee_breakdown.0,
+            total_amount: intent.amount + intent.fee_amount,
+            fx_quote_id: intent.fx_quote_id,
+            target_amount: intent.target_amount,
+            target_currency: intent.target_currency,
+            execute_at: intent.execute_at,
+            execution_timezone: intent.execution_timezone,
+            confirmation_token: None,
+            expires_at: intent.expires_at,
+            confirmed_at: intent.confirmed_at,
+            payment_id: intent.payment_id,
+            failure_reason: intent.failure_reason,
+            created_at: intent.created_at,
+        }
+    }
+}
+
+impl VersionedBody for PaymentIntentResponse {
+    fn for_version(&self, version: ApiVersion) -> serde_json::Value {
+        let mut body = serde_json::to_value(self).unwrap_or_default();
+        if version >= ApiVersion::V2 {
+            body["payment_method"] = self.payment_method.as_str().into();
+        }
+        body
+    }
+}
+
+/// How closely a name matches an account's registered holder
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayeeMatch {
+    Match,
+    CloseMatch,
+    NoMatch,
+}
+
+/// Confirmation of payee request. Internal accounts are identified by
+/// `account_id` or `account_number`; accounts at other banks by
+/// `account_number` together with `bank_code`.
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct VerifyPayeeRequest {
+    pub account_id: Option<AccountId>,
+    #[validate(length(min = 1, max = 34))]
+    pub account_number: Option<String>,
+    #[validate(length(min = 1, max = 11))]
+    pub bank_code: Option<String>,
+    /// Name the payer expects the account to be held in
+    #[validate(length(min = 1, max = 140))]
+    pub name: String,
+}
+
+/// Account details to validate: an IBAN, or a local account number with its
+/// bank code and country, optionally with the bank's BIC
+#[derive(Debug, Default, Serialize, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ValidateAccountQuery {
+    /// IBAN, with or without spaces
+    #[validate(length(min = 1, max = 42))]
+    pub iban: Option<String>,
+    #[validate(length(min = 8, max = 14))]
+    pub bic: Option<String>,
+    /// Local account number, or an IBAN
+    #[validate(length(min = 1, max = 42))]
+    pub account_number: Option<String>,
+    /// Sort code, routing number or other local bank code
+    #[validate(length(min = 1, max = 16))]
+    pub bank_code: Option<String>,
+    /// ISO 3166 country of a local account
+    #[validate(length(equal = 2))]
+    pub country_code: Option<String>,
+}
+
+impl ValidateAccountQuery {
+    pub fn details(&self) -> AccountDetails<'_> {
+        AccountDetails {
+            iban: self.iban.as_deref(),
+            bic: self.bic.as_deref(),
+            account_number: self.account_number.as_deref(),
+            bank_code: self.bank_code.as_deref(),
+            country_code: self.country_code.as_deref(),
+        }
+    }
+}
+
+/// Confirmation of payee result
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PayeeVerificationResponse {
+    pub result: PayeeMatch,
+    /// The registered holder name, only returned for a close match so the
+    /// payer can correct it
+    pub account_name: Option<String>,
+}
+
+/// A checker's decision on a payment awaiting approval
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "payment_approval_decision", rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Approved,
+    Rejected,
+}
+
+/// Approve or reject payment request
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct PaymentApprovalRequest {
+    pub decision: ApprovalDecision,
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+/// Recorded approval decision
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PaymentApproval {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    #[serde(skip)]
+    pub tenant_id: Option<TenantId>,
+    pub decision: ApprovalDecision,
+    pub decided_by: Uuid,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What came of a payment rail's callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "payment_callback_outcome", rename_all = "snake_case")]
+pub enum PaymentCallbackOutcome {
+    /// Stored but not processed yet
+    Received,
+    /// The payment moved to the reported status
+    Applied,
+    /// The payment was already past the reported status, or the status is
+    /// not one payments move to
+    Ignored,
+    /// No payment has the reference the rail named
+    Unmatched,
+}
+
+/// A callback from a payment rail, kept with its raw body for audit
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PaymentCallback {
+    pub id: Uuid,
+    pub provider: String,
+    /// The rail's id for the event; redeliveries repeat it
+    pub event_id: String,
+    pub payment_id: Option<Uuid>,
+    pub reported_status: Option<String>,
+    pub outcome: PaymentCallbackOutcome,
+    pub detail: Option<String>,
+    pub raw_body: String,
+    pub signature: Option<String>,
+    pub received_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+/// Acknowledgement of a payment rail's callback
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PaymentCallbackResponse {
+    pub callback_id: Uuid,
+    pub payment_id: Option<Uuid>,
+    pub outcome: PaymentCallbackOutcome,
+    pub detail: Option<String>,
+    /// The event was delivered before; nothing changed this time
+    pub duplicate: bool,
+}
+
+impl PaymentCallbackResponse {
+    pub fn new(callback: PaymentCallback, duplicate: bool) -> Self {
+        Self {
+            callback_id: callback.id,
+            payment_id: callback.payment_id,
+            outcome: callback.outcome,
+            detail: callback.detail,
+            duplicate,
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use crate::core::circuit_breaker::{CircuitBreaker, CircuitBreakerSettings, CircuitBreakers};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use super::model::PayeeMatch;
+
+/// Typo tolerance for a close match, as a share of the longer name
+const CLOSE_MATCH_SIMILARITY: f64 = 0.85;
+
+/// Titles ignored when comparing names
+const HONORIFICS: [&str; 7] = ["mr", "mrs", "ms", "miss", "mx", "dr", "prof"];
+
+/// An account held at another bank
+#[derive(Debug, Clone)]
+pub struct ExternalAccount {
+    pub bank_code: String,
+    pub account_number: String,
+}
+
+/// Looks up who holds an account at another bank
+#[async_trait]
+pub trait PayeeDirectory: Send + Sync {
+    /// The registered holder name, or `None` if the bank knows no such account
+    async fn holder_name(&self, account: &ExternalAccount) -> AppResult<Option<String>>;
+}
+
+/// Used when no directory is configured: external payees cannot be verified
+pub struct UnavailablePayeeDirectory;
+
+#[async_trait]
+impl PayeeDirectory for UnavailablePayeeDirectory {
+    async fn holder_name(&self, _account: &ExternalAccount) -> AppResult<Option<String>> {
+        Err(AppError::BadRequest(
+            "Verification of external payees is not available".to_string(),
+        ))
+    }
+}
+
+/// Name of the payee directory's circuit breaker
+const PAYEE_DIRECTORY_BREAKER: &str = "payee_directory";
+
+/// Queries an HTTP directory accepting `{bank_code, account_number}` JSON
+/// with a bearer API key and answering `{holder_name}`, or 404 for unknown
+/// accounts
+pub struct HttpPayeeDirectory {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    breaker: CircuitBreaker,
+}
+
+impl HttpPayeeDirectory {
+    pub fn new(api_url: String, api_key: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            breaker: CircuitBreaker::new(PAYEE_DIRECTORY_BREAKER, CircuitBreakerSettings::default()),
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    async fn lookup(&self, account: &ExternalAccount) -> AppResult<Option<String>> {
+        let request = self
+            .client
+            .post(&self.api_url)
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({
+                "bank_code": account.bank_code,
+                "account_number": account.account_number,
+            }));
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Payee directory request failed: {}", e)))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Payee directory returned {}",
+                response.status()
+            )));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Invalid payee directory response: {}", e)))?;
+        Ok(body["holder_name"].as_str().map(str::to_string))
+    }
+}
+
+#[async_trait]
+impl PayeeDirectory for HttpPayeeDirectory {
+    async fn holder_name(&self, account: &ExternalAccount) -> AppResult<Option<String>> {
+        self.breaker.call(self.lookup(account)).await
+    }
+}
+
+/// Build the payee directory selected by `PAYEE_DIRECTORY_PROVIDER`
+pub fn from_config(
+    config: &Config,
+    breakers: &CircuitBreakers,
+    clients: &HttpClients,
+) -> AppResult<Arc<dyn PayeeDirectory>> {
+    match config.payee_directory_provider.as_str() {
+        "none" => Ok(Arc::new(UnavailablePayeeDirectory)),
+        "http" => {
+            let missing =
+                |name: &str| AppError::Internal(format!("{} is required for the http payee directory", name));
+            Ok(Arc::new(HttpPayeeDirectory::new(
+                config.payee_directory_api_url.clone().ok_or_else(|| missing("PAYEE_DIRECTORY_API_URL"))?,
+                config.payee_directory_api_key.clone().ok_or_else(|| missing("PAYEE_DIRECTORY_API_KEY"))?,
+            )
+            .with_circuit_breaker(breakers.get(PAYEE_DIRECTORY_BREAKER))
+            .with_http_client(clients.get(PAYEE_DIRECTORY_BREAKER))))
+        }
+        other => Err(AppError::Internal(format!("Unknown payee directory provider '{}'", other))),
+    }
+}
+
+/// Compare the name a payer expects against the account's registered holder.
+///
+/// Case, punctuation and titles never matter. The same names in another
+/// order, initials in place of given names, or a small typo are a close
+/// match.
+pub fn match_name(provided: &str, registered: &str) -> PayeeMatch {
+    let provided = tokens(provided);
+    let registered = tokens(registered);
+    if provided.is_empty() || registered.is_empty() {
+        return PayeeMatch::NoMatch;
+    }
+    if provided == registered {
+        return PayeeMatch::Match;
+    }
+
+    let mut provided_sorted = provided.clone();
+    let mut registered_sorted = registered.clone();
+    provided_sorted.sort();
+    registered_sorted.sort();
+    if provided_sorted == registered_sorted
+        || initials_match(&provided, &registered)
+        || similarity(&provided.join(" "), &registered.join(" ")) >= CLOSE_MATCH_SIMILARITY
+    {
+        return PayeeMatch::CloseMatch;
+    }
+
+    PayeeMatch::NoMatch
+}
+
+fn tokens(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty() && !HONORIFICS.contains(token))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Same surname, with every given name either equal or abbreviated to its
+/// initial (`J Smith` for `John Smith`)
+fn initials_match(provided: &[String], registered: &[String]) -> bool {
+    let (Some((provided_last, provided_given)), Some((registered_last, registered_given))) =
+        (provided.split_last(), registered.split_last())
+    else {
+        return false;
+    };
+    if provided_last != registered_last || provided_given.len() != registered_given.len() {
+        return false;
+    }
+
+    provided_given.iter().zip(registered_given).all(|(provided, registered)| {
+        provided == registered
+            || (provided.chars().count() == 1 && registered.starts_with(provided.as_str()))
+            || (registered.chars().count() == 1 && provided.starts_with(registered.as_str()))
+    })
+}
+
+/// One minus the edit distance, relative to the longer string
+fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignores_case_punctuation_and_titles() {
+        assert_eq!(match_name("mr. john  SMITH", "John Smith"), PayeeMatch::Match);
+        assert_eq!(match_name("Anne-Marie O'Neil", "anne marie o neil"), PayeeMatch::Match);
+    }
+
+    #[test]
+    fn reordered_names_initials_and_typos_are_close() {
+        assert_eq!(match_name("Smith John", "John Smith"), PayeeMatch::CloseMatch);
+        assert_eq!(match_name("J Smith", "John Smith"), PayeeMatch::CloseMatch);
+        assert_eq!(match_name("John A Smith", "John Andrew Smith"), PayeeMatch::CloseMatch);
+        assert_eq!(match_name("Jonathan Smyth", "Jonathan Smith"), PayeeMatch::CloseMatch);
+    }
+
+    #[test]
+    fn different_people_do_not_match() {
+        assert_eq!(match_name("Jane Doe", "John Smith"), PayeeMatch::NoMatch);
+        assert_eq!(match_name("J Doe", "John Smith"), PayeeMatch::NoMatch);
+        assert_eq!(match_name("Smith", "John Smith"), PayeeMatch::NoMatch);
+        assert_eq!(match_name("Mr", "John Smith"), PayeeMatch::NoMatch);
+    }
+}
@@ -0,0 +1,339 @@
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use crate::core::config::Config;
+use crate::core::crypto::{hex, hmac_sha256, verify_hmac_sha256};
+use crate::core::error::{AppError, AppResult};
+
+/// How far a timestamped signature may be from our clock before the
+/// callback is treated as a replay
+const SIGNATURE_TOLERANCE_SECONDS: i64 = 300;
+
+/// What a rail reports about a payment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RailStatus {
+    /// Accepted and on its way; the payment can no longer be cancelled
+    Processing,
+    /// The funds have cleared
+    Settled,
+    /// Rejected or returned by the rail
+    Failed,
+    /// Anything else the rail sends, kept as it was named
+    Other(String),
+}
+
+impl RailStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RailStatus::Processing => "processing",
+            RailStatus::Settled => "settled",
+            RailStatus::Failed => "failed",
+            RailStatus::Other(status) => status,
+        }
+    }
+}
+
+/// A verified callback read into the rail's event id, the payment it is
+/// about and what happened to it
+#[derive(Debug, Clone)]
+pub struct RailCallback {
+    /// Unique per event at the rail; redelivered events repeat it
+    pub event_id: String,
+    /// Our reference for the payment, as handed to the rail
+    pub payment_reference: Option<String>,
+    /// The rail's own id for the payment
+    pub provider_reference: Option<String>,
+    pub status: RailStatus,
+    pub reason: Option<String>,
+}
+
+/// A payment rail that reports on payments through signed callbacks
+pub trait PaymentRail: Send + Sync {
+    /// Name the rail calls back under, as in `/payments/callbacks/{name}`
+    fn name(&self) -> &'static str;
+
+    /// Header carrying the rail's signature
+    fn signature_header(&self) -> &'static str;
+
+    /// Whether a callback body carries the rail's signature
+    fn verify(&self, signature: Option<&str>, body: &[u8], now: DateTime<Utc>) -> bool;
+
+    /// Read a verified callback body
+    fn parse(&self, body: &[u8]) -> AppResult<RailCallback>;
+
+    /// Nonce and unix timestamp of a verified callback, for rails whose
+    /// signatures are timestamped; used to refuse the same delivery twice
+    fn replay_nonce(&self, _signature: Option<&str>, _body: &[u8]) -> Option<(String, i64)> {
+        None
+    }
+}
+
+/// Rails signing callbacks with a hex HMAC-SHA256 of the body under a
+/// shared secret, posting `{event_id, reference, provider_reference, status,
+/// reason}` with `status` one of `processing`, `settled` or `failed`
+pub struct GenericRail {
+    secret: String,
+}
+
+impl GenericRail {
+    pub fn new(secret: String) -> Self {
+        Self { secret }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GenericCallback {
+    event_id: String,
+    reference: Option<String>,
+    provider_reference: Option<String>,
+    status: String,
+    reason: Option<String>,
+}
+
+impl PaymentRail for GenericRail {
+    fn name(&self) -> &'static str {
+        "generic"
+    }
+
+    fn signature_header(&self) -> &'static str {
+        "x-callback-signature"
+    }
+
+    fn verify(&self, signature: Option<&str>, body: &[u8], _now: DateTime<Utc>) -> bool {
+        signature
+            .and_then(decode_hex)
+            .is_some_and(|signature| verify_hmac_sha256(self.secret.as_bytes(), body, &signature))
+    }
+
+    fn parse(&self, body: &[u8]) -> AppResult<RailCallback> {
+        let callback: GenericCallback = serde_json::from_slice(body)
+            .map_err(|e| AppError::BadRequest(format!("Invalid payment callback: {}", e)))?;
+
+        let status = match callback.status.as_str() {
+            "processing" | "accepted" => RailStatus::Processing,
+            "settled" | "completed" => RailStatus::Settled,
+            "failed" | "rejected" | "returned" => RailStatus::Failed,
+            other => RailStatus::Other(other.to_string()),
+        };
+        Ok(RailCallback {
+            event_id: callback.event_id,
+            payment_reference: callback.reference,
+            provider_reference: callback.provider_reference,
+            status,
+            reason: callback.reason,
+        })
+    }
+}
+
+/// Stripe webhooks: `Stripe-Signature: t=<unix time>,v1=<hex>` signs
+/// `<t>.<body>`, and payment intent events name our reference in the
+/// intent's `metadata.reference`
+pub struct StripeRail {
+    secret: String,
+}
+
+impl StripeRail {
+    pub fn new(secret: String) -> Self {
+        Self { secret }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEvent {
+    id: String,
+    #[serde(rename = "type")]
+    event_type: String,
+    data: StripeEventData,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeEventData {
+    object: StripePaymentIntent,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripePaymentIntent {
+    id: Option<String>,
+    #[serde(default)]
+    metadata: serde_json::Map<String, serde_json::Value>,
+    last_payment_error: Option<StripeError>,
+    cancellation_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StripeError {
+    message: Option<String>,
+}
+
+impl PaymentRail for StripeRail {
+    fn name(&self) -> &'static str {
+        "stripe"
+    }
+
+    fn signature_header(&self) -> &'static str {
+        "stripe-signature"
+    }
+
+    fn verify(&self, signature: Option<&str>, body: &[u8], now: DateTime<Utc>) -> bool {
+        let Some((Some(timestamp), candidates)) = signature.map(parse_stripe_signature) else {
+            return false;
+        };
+        if (now.timestamp() - timestamp).abs() > SIGNATURE_TOLERANCE_SECONDS {
+            return false;
+        }
+
+        let signed = stripe_signed_payload(timestamp, body);
+        candidates
+            .iter()
+            .any(|candidate| verify_hmac_sha256(self.secret.as_bytes(), &signed, candidate))
+    }
+
+    /// The expected signature itself, so extra `v1` entries added to a
+    /// replayed header do not make it look new
+    fn replay_nonce(&self, signature: Option<&str>, body: &[u8]) -> Option<(String, i64)> {
+        let timestamp = parse_stripe_signature(signature?).0?;
+        let nonce = hex(&hmac_sha256(self.secret.as_bytes(), &stripe_signed_payload(timestamp, body)));
+        Some((nonce, timestamp))
+    }
+
+    fn parse(&self, body: &[u8]) -> AppResult<RailCallback> {
+        let event: StripeEvent = serde_json::from_slice(body)
+            .map_err(|e| AppError::BadRequest(format!("Invalid payment callback: {}", e)))?;
+        let intent = event.data.object;
+
+        let (status, reason) = match event.event_type.as_str() {
+            "payment_intent.processing" => (RailStatus::Processing, None),
+            "payment_intent.succeeded" => (RailStatus::Settled, None),
+            "payment_intent.payment_failed" => (
+                RailStatus::Failed,
+                intent.last_payment_error.and_then(|error| error.message),
+            ),
+            "payment_intent.canceled" => (RailStatus::Failed, intent.cancellation_reason),
+            other => (RailStatus::Other(other.to_string()), None),
+        };
+        Ok(RailCallback {
+            event_id: event.id,
+            payment_reference: intent
+                .metadata
+                .get("reference")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string),
+            provider_reference: intent.id,
+            status,
+            reason,
+        })
+    }
+}
+
+/// The rails callbacks are accepted from
+#[derive(Clone, Default)]
+pub struct PaymentRails {
+    rails: Vec<Arc<dyn PaymentRail>>,
+}
+
+impl PaymentRails {
+    pub fn new(rails: Vec<Arc<dyn PaymentRail>>) -> Self {
+        Self { rails }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn PaymentRail>> {
+        self.rails.iter().find(|rail| rail.name() == name).cloned()
+    }
+}
+
+/// The rails with a callback secret configured
+pub fn from_config(config: &Config) -> PaymentRails {
+    let mut rails: Vec<Arc<dyn PaymentRail>> = Vec::new();
+    if let Some(secret) = &config.payment_callback_secret {
+        rails.push(Arc::new(GenericRail::new(secret.clone())));
+    }
+    if let Some(secret) = &config.stripe_webhook_secret {
+        rails.push(Arc::new(StripeRail::new(secret.clone())));
+    }
+    PaymentRails::new(rails)
+}
+
+/// The `t` timestamp and decoded `v1` signatures of a Stripe-Signature
+fn parse_stripe_signature(signature: &str) -> (Option<i64>, Vec<Vec<u8>>) {
+    let mut timestamp = None;
+    let mut candidates = Vec::new();
+    for part in signature.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => candidates.extend(decode_hex(value)),
+            _ => {}
+        }
+    }
+    (timestamp, candidates)
+}
+
+fn stripe_signed_payload(timestamp: i64, body: &[u8]) -> Vec<u8> {
+    let mut signed = format!("{}.", timestamp).into_bytes();
+    signed.extend_from_slice(body);
+    signed
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| value.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn stripe_signature(secret: &str, body: &[u8], timestamp: i64) -> String {
+        let mut signed = format!("{}.", timestamp).into_bytes();
+        signed.extend_from_slice(body);
+        format!("t={},v1={}", timestamp, hex(&hmac_sha256(secret.as_bytes(), &signed)))
+    }
+
+    #[test]
+    fn generic_callbacks_must_carry_the_signature() {
+        let rail = GenericRail::new("secret".to_string());
+        let body = br#"{"event_id":"evt_1","reference":"PAY_1","status":"settled"}"#;
+        let signature = hex(&hmac_sha256(b"secret", body));
+        assert!(rail.verify(Some(&signature), body, Utc::now()));
+        assert!(!rail.verify(Some(&signature), br#"{"event_id":"evt_2"}"#, Utc::now()));
+        assert!(!rail.verify(None, body, Utc::now()));
+
+        let callback = rail.parse(body).unwrap();
+        assert_eq!(callback.event_id, "evt_1");
+        assert_eq!(callback.payment_reference.as_deref(), Some("PAY_1"));
+        assert_eq!(callback.status, RailStatus::Settled);
+        let returned = rail
+            .parse(br#"{"event_id":"evt_3","reference":"PAY_1","status":"returned","reason":"Account closed"}"#)
+            .unwrap();
+        assert_eq!(returned.status, RailStatus::Failed);
+        assert_eq!(returned.reason.as_deref(), Some("Account closed"));
+    }
+
+    #[test]
+    fn stripe_signatures_are_timestamped() {
+        let rail = StripeRail::new("whsec".to_string());
+        let body = br#"{"id":"evt_1","type":"payment_intent.payment_failed","data":{"object":{"id":"pi_1","metadata":{"reference":"PAY_1"},"last_payment_error":{"message":"Card declined"}}}}"#;
+        let now = Utc::now();
+        let signature = stripe_signature("whsec", body, now.timestamp());
+        assert!(rail.verify(Some(&signature), body, now));
+        // Replayed long after it was signed
+        assert!(!rail.verify(Some(&signature), body, now + Duration::minutes(10)));
+        assert!(!rail.verify(Some(&stripe_signature("other", body, now.timestamp())), body, now));
+        // Padding the header with more signatures keeps the same nonce
+        let (nonce, timestamp) = rail.replay_nonce(Some(&signature), body).unwrap();
+        assert_eq!(timestamp, now.timestamp());
+        let padded = format!("{},v1=00ff", signature);
+        assert_eq!(rail.replay_nonce(Some(&padded), body), Some((nonce, timestamp)));
+
+        let callback = rail.parse(body).unwrap();
+        assert_eq!(callback.payment_reference.as_deref(), Some("PAY_1"));
+        assert_eq!(callback.provider_reference.as_deref(), Some("pi_1"));
+        assert_eq!(callback.status, RailStatus::Failed);
+        assert_eq!(callback.reason.as_deref(), Some("Card declined"));
+    }
+}
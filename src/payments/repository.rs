@@ -1,9 +1,33 @@
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
-use crate::core::error::AppResult;
-use crate::shared::{traits::Repository, types::AccountId};
-use super::model::{Payment, PaymentStatus};
+use crate::core::{database::lock_balances, deadline};
+use crate::core::error::{AppError, AppResult};
+use crate::shared::{traits::Repository, types::{AccountId, Currency, TenantId}};
+use crate::transactions::model::{TransactionStatus, TransactionType};
+use crate::virtual_accounts::model::VirtualAccountStatus;
+use super::model::{
+    ApprovalDecision, CreatePaymentRequest, DuplicatePaymentAction, Payment, PaymentApproval, PaymentCallback,
+    PaymentCallbackOutcome, PaymentIntent, PaymentIntentStatus, PaymentStatus,
+};
+
+const PAYMENT_COLUMNS: &str = "id, from_account_id, to_account_id, amount, currency, payment_method, status,
+    reference, description, recipient_info, metadata, external_reference, project_id, tenant_id, fee_amount,
+    fee_breakdown, execute_at, execution_timezone, executed_at, execution_error, transaction_id,
+    expected_settlement_at, settled_at, created_by, duplicate_of, to_virtual_account_id, created_at, updated_at,
+    target_amount, target_currency";
+
+const APPROVAL_COLUMNS: &str = "id, payment_id, tenant_id, decision, decided_by, note, created_at";
+
+const INTENT_COLUMNS: &str = "id, tenant_id, project_id, created_by, from_account_id, to_account_id,
+    to_virtual_account_id, amount, currency, payment_method, description, recipient_info, metadata, fee_amount,
+    fee_breakdown, status, confirmation_token_hash, payment_id, failure_reason, expires_at, confirmed_at,
+    created_at, updated_at, fx_quote_id, target_amount, target_currency, execute_at, execution_timezone,
+    force_override";
+
+const CALLBACK_COLUMNS: &str = "id, provider, event_id, payment_id, reported_status, outcome, detail, raw_body,
+    signature, received_at, processed_at";
 
 pub struct PaymentRepository {
     pool: PgPool,
@@ -25,6 +49,738 @@ impl PaymentRepository {
         Ok(Vec::new())
     }
 
+    /// Find a payment belonging to a tenant
+    pub async fn find_by_id_for_tenant(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<Payment>> {
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payment)
+    }
+
+    /// Registered holder of an open account in the tenant, looked up by ID
+    /// or account number
+    pub async fn find_holder_name(
+        &self,
+        account_id: Option<AccountId>,
+        account_number: Option<&str>,
+        tenant_id: TenantId,
+    ) -> AppResult<Option<String>> {
+        let name = sqlx::query_scalar(
+            "SELECT u.first_name || ' ' || u.last_name
+             FROM accounts a
+             JOIN users u ON u.id = a.user_id
+             WHERE a.tenant_id = $1 AND a.closed_at IS NULL
+               AND ($2::UUID IS NULL OR a.id = $2)
+               AND ($3::VARCHAR IS NULL OR a.account_number = $3)",
+        )
+        .bind(tenant_id)
+        .bind(account_id)
+        .bind(account_number)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(name)
+    }
+
+    /// A project's duplicate payment action and detection window in minutes
+    pub async fn find_duplicate_rules(&self, project_id: Uuid) -> AppResult<Option<(DuplicatePaymentAction, i32)>> {
+        let rules = sqlx::query_as(
+            "SELECT duplicate_payment_action, duplicate_payment_window_minutes FROM projects WHERE id = $1",
+        )
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    /// Most recent live payment created since `since` from the same account
+    /// for the same amount, beneficiary and reference as the request
+    pub async fn find_recent_duplicate(
+        &self,
+        from_account_id: AccountId,
+        request: &CreatePaymentRequest,
+        since: DateTime<Utc>,
+    ) -> AppResult<Option<Uuid>> {
+        let matched = sqlx::query_scalar(
+            "SELECT id FROM payments
+             WHERE from_account_id = $1
+               AND to_account_id IS NOT DISTINCT FROM $2
+               AND recipient_info IS NOT DISTINCT FROM $3
+               AND amount = $4
+               AND currency = $5
+               AND description IS NOT DISTINCT FROM $6
+               AND status NOT IN ('failed', 'cancelled')
+               AND created_at >= $7
+             ORDER BY created_at DESC
+             LIMIT 1",
+        )
+        .bind(from_account_id)
+        .bind(request.to_account_id)
+        .bind(&request.recipient_info)
+        .bind(request.amount)
+        .bind(&request.currency)
+        .bind(&request.description)
+        .bind(since)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(matched)
+    }
+
+    /// Parent account, currency and status of a virtual account payments may
+    /// be made into
+    pub async fn find_virtual_account(
+        &self,
+        virtual_account_id: Uuid,
+    ) -> AppResult<Option<(AccountId, Currency, VirtualAccountStatus)>> {
+        let account = sqlx::query_as(
+            "SELECT parent_account_id, currency, status FROM virtual_accounts WHERE id = $1",
+        )
+        .bind(virtual_account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(account)
+    }
+
+    /// Currency of an account's balance, if the account exists
+    pub async fn find_account_currency(&self, account_id: AccountId) -> AppResult<Option<Currency>> {
+        let currency = sqlx::query_scalar::<_, Currency>(
+            "SELECT COALESCE(b.currency, a.currency, 'USD')
+             FROM accounts a LEFT JOIN balances b ON b.account_id = a.id
+             WHERE a.id = $1",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(currency)
+    }
+
+    /// Whether Postgres knows the IANA time zone name
+    pub async fn timezone_exists(&self, timezone: &str) -> AppResult<bool> {
+        let exists = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM pg_timezone_names WHERE name = $1)")
+            .bind(timezone)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(exists)
+    }
+
+    /// Convert a wall-clock time in an IANA time zone to an instant, using the
+    /// database's time zone rules so daylight saving changes are respected
+    pub async fn resolve_local_time(&self, local: NaiveDateTime, timezone: &str) -> AppResult<DateTime<Utc>> {
+        let instant = sqlx::query_scalar("SELECT ($1::TIMESTAMP AT TIME ZONE $2)")
+            .bind(local)
+            .bind(timezone)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(instant)
+    }
+
+    /// Scheduled payments due for execution, oldest first
+    pub async fn find_due_scheduled(&self, now: DateTime<Utc>, limit: i64) -> AppResult<Vec<Payment>> {
+        let payments = sqlx::query_as::<_, Payment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments
+             WHERE status = 'scheduled' AND execute_at <= $1
+             ORDER BY execute_at
+             LIMIT $2"
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(payments)
+    }
+
+    /// Release a scheduled payment for processing. Returns `None` if it is no
+    /// longer scheduled, e.g. because it was cancelled or already executed.
+    pub async fn mark_executed(&self, payment_id: Uuid) -> AppResult<Option<Payment>> {
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments SET status = 'pending', executed_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = 'scheduled'
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(payment_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payment)
+    }
+
+    /// Move a scheduled payment to a later execution time, such as the next
+    /// processing window. Returns `None` if it is no longer scheduled.
+    pub async fn reschedule(
+        &self,
+        payment_id: Uuid,
+        execute_at: DateTime<Utc>,
+        expected_settlement_at: DateTime<Utc>,
+    ) -> AppResult<Option<Payment>> {
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments SET execute_at = $1, execution_timezone = COALESCE(execution_timezone, 'UTC'),
+                 expected_settlement_at = $2, updated_at = NOW()
+             WHERE id = $3 AND status = 'scheduled'
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(execute_at)
+        .bind(expected_settlement_at)
+        .bind(payment_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payment)
+    }
+
+    /// Fail a scheduled payment that could not be executed
+    pub async fn mark_execution_failed(&self, payment_id: Uuid, error: &str) -> AppResult<Option<Payment>> {
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments SET status = 'failed', execution_error = $1, updated_at = NOW()
+             WHERE id = $2 AND status = 'scheduled'
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(error)
+        .bind(payment_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payment)
+    }
+
+    /// Cancel a payment that has not been executed yet. Returns `None` if it
+    /// moved on in the meantime. A pending payment's hold on the payer's
+    /// available balance is released.
+    pub async fn cancel(&self, payment_id: Uuid) -> AppResult<Option<Payment>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments SET status = 'cancelled', updated_at = NOW()
+             WHERE id = $1 AND status IN ('scheduled', 'pending_approval', 'pending')
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(payment_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(Payment { transaction_id: Some(transaction_id), amount, from_account_id, .. }) = &payment {
+            sqlx::query(
+                "UPDATE balances SET available_balance = available_balance + $1, updated_at = NOW()
+                 WHERE account_id = $2",
+            )
+            .bind(amount)
+            .bind(from_account_id)
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query("UPDATE transactions SET status = $1, updated_at = NOW() WHERE id = $2")
+                .bind(TransactionStatus::Cancelled)
+                .bind(transaction_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(payment)
+    }
+
+    /// Payments in the tenant waiting for a second user's approval, oldest first
+    pub async fn find_pending_approval(&self, tenant_id: TenantId) -> AppResult<Vec<Payment>> {
+        let payments = sqlx::query_as::<_, Payment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments
+             WHERE tenant_id = $1 AND status = 'pending_approval'
+             ORDER BY created_at"
+        ))
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(payments)
+    }
+
+    /// Record a checker's decision and move the payment on to `status`.
+    /// Returns `None` if the payment is no longer awaiting approval.
+    pub async fn decide_approval(
+        &self,
+        payment_id: Uuid,
+        decision: ApprovalDecision,
+        status: PaymentStatus,
+        decided_by: Uuid,
+        note: Option<&str>,
+    ) -> AppResult<Option<(Payment, PaymentApproval)>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments
+             SET status = $1,
+                 executed_at = CASE WHEN $1 = 'pending'::payment_status THEN NOW() ELSE executed_at END,
+                 updated_at = NOW()
+             WHERE id = $2 AND status = 'pending_approval'
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(status)
+        .bind(payment_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(payment) = payment else {
+            return Ok(None);
+        };
+
+        let approval = sqlx::query_as::<_, PaymentApproval>(&format!(
+            "INSERT INTO payment_approvals (payment_id, tenant_id, decision, decided_by, note)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {APPROVAL_COLUMNS}"
+        ))
+        .bind(payment.id)
+        .bind(payment.tenant_id)
+        .bind(decision)
+        .bind(decided_by)
+        .bind(note)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some((payment, approval)))
+    }
+
+    /// Post an executed payment as a pending transaction, holding the amount
+    /// on the payer's available balance until `expected_settlement_at`.
+    /// The payer's balance is locked and checked again, so payments racing
+    /// for the same funds cannot overdraw it; the loser is marked failed.
+    /// Returns `None` if the payment was already posted or is no longer pending.
+    pub async fn post_pending(
+        &self,
+        payment: &Payment,
+        expected_settlement_at: DateTime<Utc>,
+    ) -> AppResult<Option<Payment>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let locked = sqlx::query_as::<_, Payment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments
+             WHERE id = $1 AND status = 'pending' AND transaction_id IS NULL
+             FOR UPDATE"
+        ))
+        .bind(payment.id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(locked) = locked else {
+            return Ok(None);
+        };
+
+        let available = lock_balances(&mut tx, &[locked.from_account_id])
+            .await?
+            .first()
+            .map(|(_, available)| *available)
+            .ok_or_else(|| AppError::NotFound("Payer balance not found".to_string()))?;
+        if available < locked.amount {
+            let failed = sqlx::query_as::<_, Payment>(&format!(
+                "UPDATE payments SET status = 'failed', execution_error = 'Insufficient funds', updated_at = NOW()
+                 WHERE id = $1
+                 RETURNING {PAYMENT_COLUMNS}"
+            ))
+            .bind(locked.id)
+            .fetch_one(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            return Ok(Some(failed));
+        }
+
+        let transaction_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO transactions (id, from_account_id, to_account_id, amount, currency, transaction_type, status, reference, description, tenant_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        )
+        .bind(transaction_id)
+        .bind(locked.from_account_id)
+        .bind(locked.to_account_id)
+        .bind(locked.amount)
+        .bind(&locked.currency)
+        .bind(TransactionType::Payment)
+        .bind(TransactionStatus::Pending)
+        .bind(&locked.reference)
+        .bind(&locked.description)
+        .bind(locked.tenant_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let posted = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments SET transaction_id = $1, expected_settlement_at = $2, updated_at = NOW()
+             WHERE id = $3
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(transaction_id)
+        .bind(expected_settlement_at)
+        .bind(payment.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE balances SET available_balance = available_balance - $1, updated_at = NOW()
+             WHERE account_id = $2",
+        )
+        .bind(posted.amount)
+        .bind(posted.from_account_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(posted))
+    }
+
+    /// Posted payments whose clearing delay has passed, oldest first
+    pub async fn find_due_settlement(&self, now: DateTime<Utc>, limit: i64) -> AppResult<Vec<Payment>> {
+        let payments = sqlx::query_as::<_, Payment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments
+             WHERE status = 'pending' AND settled_at IS NULL AND expected_settlement_at <= $1
+             ORDER BY expected_settlement_at
+             LIMIT $2"
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(payments)
+    }
+
+    /// Settle a posted payment, pending or in a rail's hands: the held amount
+    /// leaves the payer's ledger balance and, for an internal payee, lands on
+    /// both of its balances, converted if the payment was priced with an FX
+    /// quote. Returns `None` if the payment was settled,
+    /// failed or cancelled in the meantime.
+    pub async fn settle(&self, payment_id: Uuid) -> AppResult<Option<Payment>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments SET status = 'completed', settled_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status IN ('pending', 'processing') AND transaction_id IS NOT NULL
+               AND settled_at IS NULL
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(payment_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(payment) = payment else {
+            return Ok(None);
+        };
+        let description = format!("Payment {}", payment.reference);
+        let account_ids: Vec<AccountId> = std::iter::once(payment.from_account_id)
+            .chain(payment.to_account_id)
+            .collect();
+        lock_balances(&mut tx, &account_ids).await?;
+
+        let payer_ledger = sqlx::query_scalar::<_, i64>(
+            "UPDATE balances SET ledger_balance = ledger_balance - $1, updated_at = NOW()
+             WHERE account_id = $2
+             RETURNING ledger_balance",
+        )
+        .bind(payment.amount)
+        .bind(payment.from_account_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Payer balance not found".to_string()))?;
+        let mut postings = vec![(payment.from_account_id, payer_ledger + payment.amount, -payment.amount, None)];
+
+        if let Some(to_account_id) = payment.to_account_id {
+            let credit = payment.target_amount.unwrap_or(payment.amount);
+            let payee_ledger = sqlx::query_scalar::<_, i64>(
+                "INSERT INTO balances (account_id, available_balance, ledger_balance, currency)
+                 VALUES ($1, $2, $2, $3)
+                 ON CONFLICT (account_id) DO UPDATE
+                 SET available_balance = balances.available_balance + EXCLUDED.available_balance,
+                     ledger_balance = balances.ledger_balance + EXCLUDED.ledger_balance,
+                     updated_at = NOW()
+                 RETURNING ledger_balance",
+            )
+            .bind(to_account_id)
+            .bind(credit)
+            .bind(payment.target_currency.as_ref().unwrap_or(&payment.currency))
+            .fetch_one(&mut *tx)
+            .await?;
+            postings.push((to_account_id, payee_ledger - credit, credit, payment.to_virtual_account_id));
+        }
+
+        for (account_id, balance_before, amount_changed, virtual_account_id) in postings {
+            sqlx::query(
+                "INSERT INTO balance_history
+                    (account_id, balance_before, balance_after, amount_changed, transaction_id, description, virtual_account_id)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            )
+            .bind(account_id)
+            .bind(balance_before)
+            .bind(balance_before + amount_changed)
+            .bind(amount_changed)
+            .bind(payment.transaction_id)
+            .bind(&description)
+            .bind(virtual_account_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query("UPDATE transactions SET status = $1, updated_at = NOW() WHERE id = $2")
+            .bind(TransactionStatus::Completed)
+            .bind(payment.transaction_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(payment))
+    }
+
+    /// Payment with the given reference, or failing that the given rail reference
+    pub async fn find_by_references(
+        &self,
+        reference: Option<&str>,
+        external_reference: Option<&str>,
+    ) -> AppResult<Option<Payment>> {
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments
+             WHERE reference = $1 OR external_reference = $2
+             ORDER BY reference = $1 DESC NULLS LAST
+             LIMIT 1"
+        ))
+        .bind(reference)
+        .bind(external_reference)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payment)
+    }
+
+    /// Hand a posted payment over to a rail. It can no longer be cancelled
+    /// and settles when the rail reports it, not when its clearing delay
+    /// passes. Returns `None` if it is no longer pending.
+    pub async fn mark_processing(&self, payment_id: Uuid, external_reference: Option<&str>) -> AppResult<Option<Payment>> {
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments
+             SET status = 'processing', external_reference = COALESCE(external_reference, $1), updated_at = NOW()
+             WHERE id = $2 AND status = 'pending' AND transaction_id IS NOT NULL AND settled_at IS NULL
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(external_reference)
+        .bind(payment_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payment)
+    }
+
+    /// Fail a posted payment the rail rejected or returned, releasing its
+    /// hold on the payer's available balance. Returns `None` if it settled,
+    /// failed or was cancelled in the meantime.
+    pub async fn fail_posted(&self, payment_id: Uuid, error: &str) -> AppResult<Option<Payment>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "UPDATE payments SET status = 'failed', execution_error = $1, updated_at = NOW()
+             WHERE id = $2 AND status IN ('pending', 'processing') AND transaction_id IS NOT NULL
+               AND settled_at IS NULL
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(error)
+        .bind(payment_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(payment) = payment else {
+            return Ok(None);
+        };
+        lock_balances(&mut tx, &[payment.from_account_id]).await?;
+
+        sqlx::query(
+            "UPDATE balances SET available_balance = available_balance + $1, updated_at = NOW()
+             WHERE account_id = $2",
+        )
+        .bind(payment.amount)
+        .bind(payment.from_account_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE transactions SET status = $1, updated_at = NOW() WHERE id = $2")
+            .bind(TransactionStatus::Failed)
+            .bind(payment.transaction_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(payment))
+    }
+
+    /// Store a rail's callback, once per event. Returns the stored callback
+    /// and whether it was already processed; an earlier delivery that never
+    /// finished processing is handed back to be processed again.
+    pub async fn record_callback(
+        &self,
+        provider: &str,
+        event_id: &str,
+        raw_body: &str,
+        signature: Option<&str>,
+    ) -> AppResult<(PaymentCallback, bool)> {
+        let callback = sqlx::query_as::<_, PaymentCallback>(&format!(
+            "INSERT INTO payment_callbacks (provider, event_id, raw_body, signature)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (provider, event_id) DO UPDATE SET provider = EXCLUDED.provider
+             RETURNING {CALLBACK_COLUMNS}"
+        ))
+        .bind(provider)
+        .bind(event_id)
+        .bind(raw_body)
+        .bind(signature)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let processed = callback.processed_at.is_some();
+        Ok((callback, processed))
+    }
+
+    /// Record what came of a callback
+    pub async fn complete_callback(
+        &self,
+        callback_id: Uuid,
+        payment_id: Option<Uuid>,
+        reported_status: &str,
+        outcome: PaymentCallbackOutcome,
+        detail: Option<&str>,
+    ) -> AppResult<PaymentCallback> {
+        let callback = sqlx::query_as::<_, PaymentCallback>(&format!(
+            "UPDATE payment_callbacks
+             SET payment_id = $1, reported_status = $2, outcome = $3, detail = $4, processed_at = NOW()
+             WHERE id = $5
+             RETURNING {CALLBACK_COLUMNS}"
+        ))
+        .bind(payment_id)
+        .bind(reported_status)
+        .bind(outcome)
+        .bind(detail)
+        .bind(callback_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(callback)
+    }
+
+    pub async fn create_intent(&self, intent: &PaymentIntent) -> AppResult<PaymentIntent> {
+        let created = sqlx::query_as::<_, PaymentIntent>(&format!(
+            "INSERT INTO payment_intents ({INTENT_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26,
+                     $27, $28, $29)
+             RETURNING {INTENT_COLUMNS}"
+        ))
+        .bind(intent.id)
+        .bind(intent.tenant_id)
+        .bind(intent.project_id)
+        .bind(intent.created_by)
+        .bind(intent.from_account_id)
+        .bind(intent.to_account_id)
+        .bind(intent.to_virtual_account_id)
+        .bind(intent.amount)
+        .bind(&intent.currency)
+        .bind(&intent.payment_method)
+        .bind(&intent.description)
+        .bind(&intent.recipient_info)
+        .bind(&intent.metadata)
+        .bind(intent.fee_amount)
+        .bind(&intent.fee_breakdown)
+        .bind(intent.status)
+        .bind(&intent.confirmation_token_hash)
+        .bind(intent.payment_id)
+        .bind(&intent.failure_reason)
+        .bind(intent.expires_at)
+        .bind(intent.confirmed_at)
+        .bind(intent.created_at)
+        .bind(intent.updated_at)
+        .bind(intent.fx_quote_id)
+        .bind(intent.target_amount)
+        .bind(&intent.target_currency)
+        .bind(intent.execute_at)
+        .bind(&intent.execution_timezone)
+        .bind(intent.force_override)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    /// Find a payment intent belonging to a tenant
+    pub async fn find_intent_for_tenant(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<PaymentIntent>> {
+        let intent = sqlx::query_as::<_, PaymentIntent>(&format!(
+            "SELECT {INTENT_COLUMNS} FROM payment_intents WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(intent)
+    }
+
+    /// Claim an unexpired intent awaiting confirmation for processing, so it
+    /// is confirmed once. Returns `None` if it was confirmed, cancelled or
+    /// expired in the meantime.
+    pub async fn begin_intent_processing(&self, id: Uuid) -> AppResult<Option<PaymentIntent>> {
+        let intent = sqlx::query_as::<_, PaymentIntent>(&format!(
+            "UPDATE payment_intents
+             SET status = 'processing', confirmed_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = 'requires_confirmation' AND expires_at > NOW()
+             RETURNING {INTENT_COLUMNS}"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(intent)
+    }
+
+    /// Record the payment made for a confirmed intent
+    pub async fn attach_intent_payment(&self, id: Uuid, payment_id: Uuid) -> AppResult<PaymentIntent> {
+        let intent = sqlx::query_as::<_, PaymentIntent>(&format!(
+            "UPDATE payment_intents SET payment_id = $2, updated_at = NOW()
+             WHERE id = $1
+             RETURNING {INTENT_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(payment_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(intent)
+    }
+
+    /// Fail a confirmed intent whose payment could not be made
+    pub async fn fail_intent(&self, id: Uuid, reason: &str) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE payment_intents SET status = 'failed', failure_reason = $2, updated_at = NOW()
+             WHERE id = $1 AND status = 'processing'",
+        )
+        .bind(id)
+        .bind(reason)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Close an intent awaiting confirmation as cancelled, or as expired once
+    /// past its expiry. Returns `None` if it no longer awaits confirmation.
+    pub async fn close_intent(&self, id: Uuid, status: PaymentIntentStatus) -> AppResult<Option<PaymentIntent>> {
+        let intent = sqlx::query_as::<_, PaymentIntent>(&format!(
+            "UPDATE payment_intents SET status = $2, updated_at = NOW()
+             WHERE id = $1 AND status = 'requires_confirmation'
+             RETURNING {INTENT_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(status)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(intent)
+    }
+
     /// Update payment status
     pub async fn update_status(
         &self,
@@ -39,13 +795,56 @@ impl PaymentRepository {
 #[async_trait]
 impl Repository<Payment, Uuid> for PaymentRepository {
     async fn create(&self, payment: Payment) -> AppResult<Payment> {
-        // TODO: Implement payment creation
-        Ok(payment)
+        let created = sqlx::query_as::<_, Payment>(&format!(
+            "INSERT INTO payments ({PAYMENT_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30)
+             RETURNING {PAYMENT_COLUMNS}"
+        ))
+        .bind(payment.id)
+        .bind(payment.from_account_id)
+        .bind(payment.to_account_id)
+        .bind(payment.amount)
+        .bind(&payment.currency)
+        .bind(&payment.payment_method)
+        .bind(&payment.status)
+        .bind(&payment.reference)
+        .bind(&payment.description)
+        .bind(&payment.recipient_info)
+        .bind(&payment.metadata)
+        .bind(&payment.external_reference)
+        .bind(payment.project_id)
+        .bind(payment.tenant_id)
+        .bind(payment.fee_amount)
+        .bind(&payment.fee_breakdown)
+        .bind(payment.execute_at)
+        .bind(&payment.execution_timezone)
+        .bind(payment.executed_at)
+        .bind(&payment.execution_error)
+        .bind(payment.transaction_id)
+        .bind(payment.expected_settlement_at)
+        .bind(payment.settled_at)
+        .bind(payment.created_by)
+        .bind(payment.duplicate_of)
+        .bind(payment.to_virtual_account_id)
+        .bind(payment.created_at)
+        .bind(payment.updated_at)
+        .bind(payment.target_amount)
+        .bind(&payment.target_currency)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
     }
 
-    async fn find_by_id(&self, _id: Uuid) -> AppResult<Option<Payment>> {
-        // TODO: Implement find by ID
-        Ok(None)
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Payment>> {
+        let payment = sqlx::query_as::<_, Payment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM payments WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(payment)
     }
 
     async fn update(&self, _id: Uuid, payment: Payment) -> AppResult<Payment> {
@@ -1,29 +1,188 @@
+use std::sync::Arc;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::types::Json;
+use crate::account_controls::{model::AccountKind, service::AccountFreezeGuard};
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::database::retry_transaction;
 use crate::core::error::{AppError, AppResult};
-use crate::shared::{traits::Repository, types::AccountId};
+use crate::core::events::{DomainEvent, DomainEventType, EventBus};
+use crate::core::feature_flags::{Feature, FeatureFlags, FlagContext};
+use crate::devices::model::DeviceRisk;
+use crate::fees::{model::FeeBreakdown, service::FeeEngine};
+use crate::goals::service::GoalBalanceGuard;
+use crate::kyc::service::KycPolicyService;
+use crate::metadata_schemas::{model::MetadataTarget, service::MetadataSchemaGuard};
+use crate::shared::bank_details::{self, looks_like_iban, normalize, parse_bic, validate_iban, AccountDetails};
+use crate::shared::{traits::Repository, types::{AccountId, Amount, TenantId}};
+use crate::virtual_accounts::model::VirtualAccountStatus;
+use super::calendar::Market;
 use super::model::{
-    Payment, PaymentResponse, CreatePaymentRequest, PaymentStatus
+    Payment, PaymentMethod, PaymentPrice, PaymentResponse, CreatePaymentRequest, PaymentStatus, ExecuteAt,
+    PaymentSettings, PayeeMatch, PayeeVerificationResponse, VerifyPayeeRequest, ApprovalDecision, PaymentApproval,
+    PaymentApprovalRequest, DuplicatePaymentAction, DEFAULT_DUPLICATE_PAYMENT_ACTION, DEFAULT_DUPLICATE_PAYMENT_WINDOW_MINUTES
 };
+use super::payee::{match_name, ExternalAccount, PayeeDirectory};
 use super::repository::PaymentRepository;
 
 pub struct PaymentService {
     repository: PaymentRepository,
+    freeze_guard: AccountFreezeGuard,
+    kyc_policy: KycPolicyService,
+    goal_guard: GoalBalanceGuard,
+    fee_engine: FeeEngine,
+    metadata_guard: MetadataSchemaGuard,
+    event_bus: EventBus,
+    settings: PaymentSettings,
+    device_risk: Option<DeviceRisk>,
 }
 
 impl PaymentService {
-    pub fn new(repository: PaymentRepository) -> Self {
-        Self { repository }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: PaymentRepository,
+        freeze_guard: AccountFreezeGuard,
+        kyc_policy: KycPolicyService,
+        goal_guard: GoalBalanceGuard,
+        fee_engine: FeeEngine,
+        metadata_guard: MetadataSchemaGuard,
+        event_bus: EventBus,
+        settings: PaymentSettings,
+    ) -> Self {
+        Self {
+            repository,
+            freeze_guard,
+            kyc_policy,
+            goal_guard,
+            fee_engine,
+            metadata_guard,
+            event_bus,
+            settings,
+            device_risk: None,
+        }
     }
 
-    /// Create a new payment
+    /// Take into account the device an end user made the payments from, as
+    /// verified from the call's device assertion
+    pub fn with_device_risk(mut self, risk: Option<DeviceRisk>) -> Self {
+        self.device_risk = risk;
+        self
+    }
+
+    /// Create a new payment, or schedule it when `execute_at` is set.
+    /// Payments due past their method's cut-off, or on a closed day, are
+    /// deferred to the next processing window. Fees are calculated up front
+    /// and posted when the payment executes, together with the pending
+    /// transaction that clears it. Payments over
+    /// the approval threshold, or from a new or unbound device, wait for
+    /// another user's approval first, and payments repeating a recent one
+    /// are flagged or blocked. Metadata must match the project's payment
+    /// metadata schema, if it has one.
     pub async fn create_payment(
         &self,
         from_account_id: AccountId,
+        project_id: Option<Uuid>,
+        tenant_id: Option<TenantId>,
+        created_by: Uuid,
         request: CreatePaymentRequest,
     ) -> AppResult<PaymentResponse> {
+        let fees = self
+            .quote_fees(project_id, &request.payment_method, &request.currency, request.amount)
+            .await?;
+        let price = PaymentPrice {
+            fees,
+            target_amount: None,
+            target_currency: None,
+        };
+        self.create_priced_payment(from_account_id, project_id, tenant_id, created_by, request, price)
+            .await
+    }
+
+    /// Fees a payment would be charged under the project's fee schedules
+    pub async fn quote_fees(
+        &self,
+        project_id: Option<Uuid>,
+        payment_method: &PaymentMethod,
+        currency: &str,
+        amount: Amount,
+    ) -> AppResult<FeeBreakdown> {
+        self.fee_engine.calculate(project_id, payment_method, currency, amount).await
+    }
+
+    /// Create a payment charging fees priced earlier, as for a confirmed
+    /// payment intent, rather than under the fee schedules of today. A
+    /// converted payment credits the payee the price's target amount.
+    pub async fn create_priced_payment(
+        &self,
+        from_account_id: AccountId,
+        project_id: Option<Uuid>,
+        tenant_id: Option<TenantId>,
+        created_by: Uuid,
+        mut request: CreatePaymentRequest,
+        price: PaymentPrice,
+    ) -> AppResult<PaymentResponse> {
+        let PaymentPrice {
+            fees,
+            target_amount,
+            target_currency,
+        } = price;
         // TODO: Implement payment creation logic
+        if let Some(project_id) = project_id {
+            self.metadata_guard
+                .ensure_valid(project_id, MetadataTarget::Payment, request.metadata.as_ref())
+                .await?;
+        }
+        if let Some(recipient_info) = request.recipient_info.as_mut() {
+            normalize_recipient_account(recipient_info)?;
+        }
+        if let Some(virtual_account_id) = request.to_virtual_account_id {
+            let credit_currency = target_currency.as_deref().unwrap_or(&request.currency);
+            request.to_account_id = Some(
+                self.resolve_virtual_account(virtual_account_id, request.to_account_id, credit_currency)
+                    .await?,
+            );
+        }
+        let schedule = match &request.execute_at {
+            Some(execute_at) => Some(self.resolve_execution_time(execute_at, request.timezone.as_deref()).await?),
+            None => None,
+        };
         let now = Utc::now();
+        let market = market(&request.currency, request.recipient_info.as_ref());
+        let due_at = schedule.as_ref().map_or(now, |(execute_at, _)| *execute_at);
+        let window = self.settings.calendar.next_window(&request.payment_method, due_at, market);
+        let duplicate_of = self.check_duplicate(from_account_id, project_id, &request).await?;
+
+        if schedule.is_some() {
+            // Limits and balances are checked again when the payment executes
+            self.freeze_guard.ensure_can_debit(from_account_id).await?;
+            if let Some(to_account_id) = request.to_account_id {
+                self.freeze_guard
+                    .ensure_can_credit(AccountKind::Account, to_account_id)
+                    .await?;
+            }
+        } else {
+            self.ensure_can_execute(from_account_id, request.to_account_id, request.amount + fees.total)
+                .await?;
+        }
+
+        let (status, execute_at, execution_timezone) = match schedule {
+            Some((_, timezone)) => (PaymentStatus::Scheduled, Some(window), Some(timezone)),
+            None if window > now => (PaymentStatus::Scheduled, Some(window), Some("UTC".to_string())),
+            None => (PaymentStatus::Pending, None, None),
+        };
+        let expected_settlement_at = execute_at.map(|execute_at| {
+            let delay = self.settings.clearing_delays.for_method(&request.payment_method);
+            self.settings
+                .calendar
+                .settlement_time(&request.payment_method, execute_at, delay, market)
+        });
+        let status = if self.settings.requires_approval(request.amount)
+            || self.settings.requires_device_review(self.device_risk, request.amount)
+        {
+            PaymentStatus::PendingApproval
+        } else {
+            status
+        };
         let payment = Payment {
             id: Uuid::new_v4(),
             from_account_id,
@@ -31,23 +190,371 @@ impl PaymentService {
             amount: request.amount,
             currency: request.currency,
             payment_method: request.payment_method,
-            status: PaymentStatus::Pending,
+            status,
             reference: format!("PAY_{}", Uuid::new_v4()),
             description: request.description,
             recipient_info: request.recipient_info,
             metadata: request.metadata,
             external_reference: None,
+            project_id,
+            tenant_id,
+            fee_amount: fees.total,
+            fee_breakdown: Some(Json(fees)),
+            execute_at,
+            execution_timezone,
+            executed_at: None,
+            execution_error: None,
+            transaction_id: None,
+            expected_settlement_at,
+            settled_at: None,
+            created_by: Some(created_by),
+            duplicate_of,
+            to_virtual_account_id: request.to_virtual_account_id,
             created_at: now,
             updated_at: now,
+            target_amount,
+            target_currency,
         };
 
-        let created_payment = self.repository.create(payment).await?;
+        let mut created_payment = self.repository.create(payment).await?;
+        if matches!(created_payment.status, PaymentStatus::Pending) {
+            self.post_fees(&created_payment).await?;
+            created_payment = self.post_for_clearing(created_payment).await?;
+        }
+        self.publish_status_change(&created_payment);
         Ok(PaymentResponse::from(created_payment))
     }
 
+    /// Execute a scheduled payment that has come due. Payments picked up
+    /// past their cut-off stay scheduled for the next processing window, and
+    /// payments failing the debit checks are marked failed; returns `None` if
+    /// the payment was cancelled or executed elsewhere in the meantime.
+    pub async fn execute_scheduled_payment(&self, payment: &Payment) -> AppResult<Option<PaymentResponse>> {
+        if let Some(deferred) = self.defer_to_next_window(payment).await? {
+            return Ok(deferred.map(PaymentResponse::from));
+        }
+
+        let checked = self
+            .ensure_can_execute(
+                payment.from_account_id,
+                payment.to_account_id,
+                payment.amount + payment.fee_amount,
+            )
+            .await;
+
+        let updated = match checked {
+            Ok(()) => {
+                match self.repository.mark_executed(payment.id).await? {
+                    Some(executed) => {
+                        self.post_fees(&executed).await?;
+                        Some(self.post_for_clearing(executed).await?)
+                    }
+                    None => None,
+                }
+            }
+            Err(
+                error @ (AppError::BadRequest(_)
+                | AppError::Validation(_)
+                | AppError::NotFound(_)
+                | AppError::Conflict(_)),
+            ) => {
+                self.repository
+                    .mark_execution_failed(payment.id, &error.to_string())
+                    .await?
+            }
+            Err(error) => return Err(error),
+        };
+
+        if let Some(updated) = &updated {
+            self.publish_status_change(updated);
+        }
+        Ok(updated.map(PaymentResponse::from))
+    }
+
+    /// Apply a checker's decision to a payment awaiting approval. An approved
+    /// payment is scheduled if its execution time is still ahead or it is
+    /// past its cut-off, otherwise it executes now and must pass the usual
+    /// debit checks; a rejected one is cancelled.
+    pub async fn decide_approval(
+        &self,
+        payment: &Payment,
+        decided_by: Uuid,
+        request: &PaymentApprovalRequest,
+    ) -> AppResult<(Payment, PaymentApproval)> {
+        let now = Utc::now();
+        let window = self.settings.calendar.next_window(&payment.payment_method, now, payment_market(payment));
+        let status = match request.decision {
+            ApprovalDecision::Rejected => PaymentStatus::Cancelled,
+            ApprovalDecision::Approved if payment.execute_at.is_some_and(|at| at > now) || window > now => {
+                PaymentStatus::Scheduled
+            }
+            ApprovalDecision::Approved => {
+                self.ensure_can_execute(
+                    payment.from_account_id,
+                    payment.to_account_id,
+                    payment.amount + payment.fee_amount,
+                )
+                .await?;
+                PaymentStatus::Pending
+            }
+        };
+
+        let (mut decided, approval) = self
+            .repository
+            .decide_approval(payment.id, request.decision, status, decided_by, request.note.as_deref())
+            .await?
+            .ok_or_else(|| AppError::Conflict("Payment is no longer awaiting approval".to_string()))?;
+        match decided.status {
+            PaymentStatus::Pending => {
+                self.post_fees(&decided).await?;
+                decided = self.post_for_clearing(decided).await?;
+            }
+            PaymentStatus::Scheduled if decided.execute_at.is_none_or(|at| at <= now) => {
+                if let Some(Some(deferred)) = self.defer_to_next_window(&decided).await? {
+                    decided = deferred;
+                }
+            }
+            _ => {}
+        }
+
+        self.publish_status_change(&decided);
+        Ok((decided, approval))
+    }
+
+    /// Settle a posted payment whose clearing delay has passed, or that its
+    /// rail reports settled. Returns `None` if it was settled or cancelled in
+    /// the meantime.
+    pub async fn settle_payment(&self, payment_id: Uuid) -> AppResult<Option<PaymentResponse>> {
+        let settled = retry_transaction(|| self.repository.settle(payment_id)).await?;
+        if let Some(settled) = &settled {
+            self.publish_status_change(settled);
+        }
+        Ok(settled.map(PaymentResponse::from))
+    }
+
+    /// Move a posted payment a rail has accepted to processing. Returns
+    /// `None` if it is no longer pending.
+    pub async fn mark_processing(&self, payment_id: Uuid, external_reference: Option<&str>) -> AppResult<Option<Payment>> {
+        let processing = self.repository.mark_processing(payment_id, external_reference).await?;
+        if let Some(processing) = &processing {
+            self.publish_status_change(processing);
+        }
+        Ok(processing)
+    }
+
+    /// Fail a posted payment a rail rejected, releasing the payer's funds and
+    /// reversing its fees. Returns `None` if it settled, failed or was
+    /// cancelled in the meantime.
+    pub async fn fail_posted(&self, payment_id: Uuid, error: &str) -> AppResult<Option<Payment>> {
+        let failed = retry_transaction(|| self.repository.fail_posted(payment_id, error)).await?;
+        if let Some(failed) = &failed {
+            self.fee_engine.reverse_charges(failed.id).await?;
+            self.publish_status_change(failed);
+        }
+        Ok(failed)
+    }
+
+    /// Post an executed payment as pending with the clearing delay of its
+    /// method, settling it straight away when the method has none. A payment
+    /// that lost the payer's funds to a concurrent one comes back failed,
+    /// with its fees reversed.
+    async fn post_for_clearing(&self, payment: Payment) -> AppResult<Payment> {
+        let delay = self.settings.clearing_delays.for_method(&payment.payment_method);
+        let settles_at = self.settlement_time(&payment, Utc::now());
+        let Some(posted) = retry_transaction(|| self.repository.post_pending(&payment, settles_at)).await? else {
+            return Ok(payment);
+        };
+
+        if matches!(posted.status, PaymentStatus::Failed) {
+            self.fee_engine.reverse_charges(posted.id).await?;
+            return Ok(posted);
+        }
+        if delay > Duration::zero() {
+            return Ok(posted);
+        }
+        Ok(retry_transaction(|| self.repository.settle(posted.id)).await?.unwrap_or(posted))
+    }
+
+    /// Keep a scheduled payment that is past its method's cut-off, or due on
+    /// a closed day, scheduled for the next processing window. Returns
+    /// `None` if it can run now, and `Some(None)` if it stopped being
+    /// scheduled in the meantime.
+    async fn defer_to_next_window(&self, payment: &Payment) -> AppResult<Option<Option<Payment>>> {
+        let now = Utc::now();
+        let window = self.settings.calendar.next_window(&payment.payment_method, now, payment_market(payment));
+        if window <= now {
+            return Ok(None);
+        }
+
+        let expected_settlement_at = self.settlement_time(payment, window);
+        let deferred = self.repository.reschedule(payment.id, window, expected_settlement_at).await?;
+        Ok(Some(deferred))
+    }
+
+    /// When a payment executing at `at` settles, given its method's
+    /// clearing delay and the processing calendar
+    fn settlement_time(&self, payment: &Payment, at: DateTime<Utc>) -> DateTime<Utc> {
+        let delay = self.settings.clearing_delays.for_method(&payment.payment_method);
+        self.settings
+            .calendar
+            .settlement_time(&payment.payment_method, at, delay, payment_market(payment))
+    }
+
+    /// Tell real-time subscribers about a payment's new status
+    fn publish_status_change(&self, payment: &Payment) {
+        let account_ids = std::iter::once(payment.from_account_id)
+            .chain(payment.to_account_id)
+            .collect();
+        self.event_bus.publish(DomainEvent::new(
+            DomainEventType::PaymentStatusChanged,
+            payment.tenant_id,
+            account_ids,
+            serde_json::json!(PaymentResponse::from(payment.clone())),
+        ));
+    }
+
+    async fn post_fees(&self, payment: &Payment) -> AppResult<()> {
+        match &payment.fee_breakdown {
+            Some(Json(fees)) => {
+                self.fee_engine
+                    .post_charges(payment.id, payment.from_account_id, fees)
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The parent account credited for a payment into a virtual account,
+    /// which must be active, unfrozen and in the currency it is credited in
+    async fn resolve_virtual_account(
+        &self,
+        virtual_account_id: Uuid,
+        to_account_id: Option<AccountId>,
+        credit_currency: &str,
+    ) -> AppResult<AccountId> {
+        let (parent_account_id, currency, status) = self
+            .repository
+            .find_virtual_account(virtual_account_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Virtual account not found".to_string()))?;
+
+        if !matches!(status, VirtualAccountStatus::Active) {
+            return Err(AppError::BadRequest("Virtual account is not active".to_string()));
+        }
+        if to_account_id.is_some_and(|to_account_id| to_account_id != parent_account_id) {
+            return Err(AppError::BadRequest(
+                "Virtual account does not belong to the payee account".to_string(),
+            ));
+        }
+        if currency != credit_currency {
+            return Err(AppError::BadRequest(format!(
+                "Virtual account is held in {}, not {}",
+                currency, credit_currency
+            )));
+        }
+        self.freeze_guard
+            .ensure_can_credit(AccountKind::VirtualAccount, virtual_account_id)
+            .await?;
+
+        Ok(parent_account_id)
+    }
+
+    /// Debit and credit checks an immediate payment must pass
+    async fn ensure_can_execute(
+        &self,
+        from_account_id: AccountId,
+        to_account_id: Option<AccountId>,
+        amount: Amount,
+    ) -> AppResult<()> {
+        self.freeze_guard.ensure_can_debit(from_account_id).await?;
+        self.kyc_policy
+            .ensure_within_limits(from_account_id, amount)
+            .await?;
+        self.goal_guard
+            .ensure_spendable(from_account_id, amount)
+            .await?;
+        if let Some(to_account_id) = to_account_id {
+            self.freeze_guard
+                .ensure_can_credit(AccountKind::Account, to_account_id)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Earlier payment the request repeats within the project's detection
+    /// window. Projects that block duplicates refuse the request unless it
+    /// is forced through; otherwise the new payment is only flagged.
+    async fn check_duplicate(
+        &self,
+        from_account_id: AccountId,
+        project_id: Option<Uuid>,
+        request: &CreatePaymentRequest,
+    ) -> AppResult<Option<Uuid>> {
+        let rules = match project_id {
+            Some(project_id) => self.repository.find_duplicate_rules(project_id).await?,
+            None => None,
+        };
+        let (action, window_minutes) =
+            rules.unwrap_or((DEFAULT_DUPLICATE_PAYMENT_ACTION, DEFAULT_DUPLICATE_PAYMENT_WINDOW_MINUTES));
+        if action == DuplicatePaymentAction::Off {
+            return Ok(None);
+        }
+
+        let since = Utc::now() - Duration::minutes(window_minutes as i64);
+        let Some(matched) = self
+            .repository
+            .find_recent_duplicate(from_account_id, request, since)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if action == DuplicatePaymentAction::Block && !request.force_override {
+            return Err(AppError::DuplicatePayment(matched));
+        }
+
+        tracing::warn!(
+            from_account_id = %from_account_id,
+            matched_payment_id = %matched,
+            forced = request.force_override,
+            "Payment flagged as a possible duplicate"
+        );
+        Ok(Some(matched))
+    }
+
+    /// Resolve the requested execution time to an instant and the time zone
+    /// it was expressed in
+    pub async fn resolve_execution_time(
+        &self,
+        execute_at: &ExecuteAt,
+        timezone: Option<&str>,
+    ) -> AppResult<(DateTime<Utc>, String)> {
+        let timezone = timezone.unwrap_or("UTC");
+        if !self.repository.timezone_exists(timezone).await? {
+            return Err(AppError::Validation(format!("Unknown time zone '{}'", timezone)));
+        }
+
+        let instant = match execute_at {
+            ExecuteAt::Instant(instant) => instant.with_timezone(&Utc),
+            ExecuteAt::Local(local) => self.repository.resolve_local_time(*local, timezone).await?,
+        };
+
+        let now = Utc::now();
+        if instant <= now {
+            return Err(AppError::Validation("execute_at must be in the future".to_string()));
+        }
+        if instant > now + Duration::days(self.settings.max_schedule_days) {
+            return Err(AppError::Validation(format!(
+                "execute_at must be within {} days",
+                self.settings.max_schedule_days
+            )));
+        }
+
+        Ok((instant, timezone.to_string()))
+    }
+
     /// Get payment by ID
-    pub async fn get_payment(&self, payment_id: Uuid) -> AppResult<PaymentResponse> {
-        let payment = self.repository.find_by_id(payment_id).await?
+    pub async fn get_payment(&self, payment_id: Uuid, tenant_id: TenantId) -> AppResult<PaymentResponse> {
+        let payment = self.repository.find_by_id_for_tenant(payment_id, tenant_id).await?
             .ok_or_else(|| AppError::NotFound("Payment not found".to_string()))?;
 
         Ok(PaymentResponse::from(payment))
@@ -64,8 +571,262 @@ impl PaymentService {
         Ok(payments.into_iter().map(PaymentResponse::from).collect())
     }
 
-    /// Cancel payment
-    pub async fn cancel_payment(&self, payment_id: Uuid) -> AppResult<()> {
-        self.repository.update_status(payment_id, PaymentStatus::Cancelled).await
+    /// Build the payment-details payload encoded into a payment's QR code
+    pub async fn get_payment_qr_payload(&self, payment_id: Uuid, tenant_id: TenantId) -> AppResult<String> {
+        let payment = self.repository.find_by_id_for_tenant(payment_id, tenant_id).await?
+            .ok_or_else(|| AppError::NotFound("Payment not found".to_string()))?;
+
+        if !matches!(payment.status, PaymentStatus::Pending) {
+            return Err(AppError::BadRequest("Only pending payments can be shared as a QR code".to_string()));
+        }
+
+        let amount = payment.amount.to_string();
+        let mut params = vec![
+            ("reference", payment.reference.clone()),
+            ("amount", amount),
+            ("currency", payment.currency.clone()),
+        ];
+        if let Some(to_account_id) = payment.to_account_id {
+            params.push(("to_account_id", to_account_id.to_string()));
+        }
+        if let Some(description) = &payment.description {
+            params.push(("description", description.clone()));
+        }
+
+        let uri = reqwest::Url::parse_with_params("openbank://pay", &params)
+            .map_err(|e| AppError::Internal(format!("Failed to build payment URI: {}", e)))?;
+
+        Ok(uri.to_string())
+    }
+
+    /// Cancel a payment that has not been executed yet, including scheduled
+    /// payments and pending payments that have not cleared
+    pub async fn cancel_payment(&self, payment_id: Uuid, tenant_id: TenantId) -> AppResult<PaymentResponse> {
+        let payment = self.repository.find_by_id_for_tenant(payment_id, tenant_id).await?
+            .ok_or_else(|| AppError::NotFound("Payment not found".to_string()))?;
+
+        if !matches!(
+            payment.status,
+            PaymentStatus::Scheduled | PaymentStatus::PendingApproval | PaymentStatus::Pending
+        ) {
+            return Err(AppError::BadRequest(
+                "Only scheduled, pending approval or pending payments can be cancelled".to_string(),
+            ));
+        }
+
+        let cancelled = retry_transaction(|| self.repository.cancel(payment_id)).await?
+            .ok_or_else(|| AppError::Conflict("Payment was executed before it could be cancelled".to_string()))?;
+        self.fee_engine.reverse_charges(payment_id).await?;
+        self.publish_status_change(&cancelled);
+        Ok(PaymentResponse::from(cancelled))
+    }
+}
+
+/// Confirmation of payee: checks the name a payer expects against the
+/// destination account's registered holder before money is sent
+pub struct PayeeVerificationService {
+    repository: PaymentRepository,
+    directory: Arc<dyn PayeeDirectory>,
+    feature_flags: FeatureFlags,
+}
+
+impl PayeeVerificationService {
+    pub fn new(repository: PaymentRepository, directory: Arc<dyn PayeeDirectory>, feature_flags: FeatureFlags) -> Self {
+        Self {
+            repository,
+            directory,
+            feature_flags,
+        }
+    }
+
+    /// Payees at other banks are looked up in the payee directory, for
+    /// projects the external directory feature is rolled out to
+    pub async fn verify_payee(
+        &self,
+        request: VerifyPayeeRequest,
+        tenant_id: TenantId,
+        project_id: Uuid,
+    ) -> AppResult<PayeeVerificationResponse> {
+        let registered = match (request.account_id, request.account_number, request.bank_code) {
+            (Some(account_id), None, None) => {
+                self.repository
+                    .find_holder_name(Some(account_id), None, tenant_id)
+                    .await?
+            }
+            (None, Some(account_number), None) => {
+                self.repository
+                    .find_holder_name(None, Some(&account_number), tenant_id)
+                    .await?
+            }
+            (None, Some(account_number), Some(bank_code)) => {
+                let normalized = normalize(&account_number);
+                let account_number = if looks_like_iban(&normalized) {
+                    let issues = validate_iban(&normalized);
+                    if !issues.is_empty() {
+                        return Err(AppError::Validation(issues.join("; ")));
+                    }
+                    normalized
+                } else {
+                    account_number
+                };
+                let context = FlagContext::new(Some(tenant_id), Some(project_id));
+                if !self.feature_flags.is_enabled(Feature::ExternalPayeeDirectory, context).await {
+                    return Err(AppError::Validation(
+                        "Verifying payees at other banks is not enabled for this project".to_string(),
+                    ));
+                }
+                self.directory
+                    .holder_name(&ExternalAccount { bank_code, account_number })
+                    .await?
+            }
+            _ => {
+                return Err(AppError::Validation(
+                    "Provide either account_id, or account_number with an optional bank_code".to_string(),
+                ))
+            }
+        };
+        let registered = registered.ok_or_else(|| AppError::NotFound("Payee account not found".to_string()))?;
+
+        let result = match_name(&request.name, &registered);
+        Ok(PayeeVerificationResponse {
+            result,
+            account_name: (result == PayeeMatch::CloseMatch).then_some(registered),
+        })
     }
-}
\ No newline at end of file
+}
+
+/// Dual control for high-value payments: a second user holding the approver
+/// role approves or rejects what another user created
+pub struct PaymentApprovalService {
+    payments: PaymentService,
+    repository: PaymentRepository,
+    audit_logger: AuditLogger,
+}
+
+impl PaymentApprovalService {
+    pub fn new(payments: PaymentService, repository: PaymentRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            payments,
+            repository,
+            audit_logger,
+        }
+    }
+
+    /// Payments in the tenant awaiting approval
+    pub async fn list_pending(&self, tenant_id: TenantId) -> AppResult<Vec<PaymentResponse>> {
+        let payments = self.repository.find_pending_approval(tenant_id).await?;
+        Ok(payments.into_iter().map(PaymentResponse::from).collect())
+    }
+
+    pub async fn decide(
+        &self,
+        payment_id: Uuid,
+        tenant_id: TenantId,
+        approver_id: Uuid,
+        request: PaymentApprovalRequest,
+    ) -> AppResult<PaymentResponse> {
+        let payment = self.repository.find_by_id_for_tenant(payment_id, tenant_id).await?
+            .ok_or_else(|| AppError::NotFound("Payment not found".to_string()))?;
+
+        if !matches!(payment.status, PaymentStatus::PendingApproval) {
+            return Err(AppError::Conflict("Payment is not awaiting approval".to_string()));
+        }
+        if payment.created_by == Some(approver_id) {
+            let event = AuditEvent::new(AuditEventType::AccessDenied)
+                .severity(AuditSeverity::Warning)
+                .user_id(approver_id)
+                .resource(format!("payment:{}", payment.id))
+                .action("approve".to_string())
+                .metadata("reason".to_string(), serde_json::json!("maker_cannot_approve"))
+                .compliance_tag("DUAL_CONTROL".to_string());
+            self.audit_logger.log(event).await;
+            return Err(AppError::Authorization(
+                "A payment must be approved by someone other than its creator".to_string(),
+            ));
+        }
+
+        let (decided, approval) = self.payments.decide_approval(&payment, approver_id, &request).await?;
+
+        let event_type = match approval.decision {
+            ApprovalDecision::Approved => AuditEventType::PaymentApproved,
+            ApprovalDecision::Rejected => AuditEventType::PaymentRejected,
+        };
+        let event = AuditEvent::new(event_type)
+            .user_id(approver_id)
+            .resource(format!("payment:{}", decided.id))
+            .action("decide".to_string())
+            .metadata("created_by".to_string(), serde_json::json!(decided.created_by))
+            .metadata("amount".to_string(), serde_json::json!(decided.amount))
+            .metadata("currency".to_string(), serde_json::json!(decided.currency))
+            .metadata("status".to_string(), serde_json::json!(decided.status))
+            .metadata("note".to_string(), serde_json::json!(approval.note))
+            .compliance_tag("DUAL_CONTROL".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(PaymentResponse::from(decided))
+    }
+}
+
+/// The market a payment is processed in: its currency and the recipient's
+/// country, if known
+fn market<'a>(currency: &'a str, recipient_info: Option<&'a serde_json::Value>) -> Market<'a> {
+    Market {
+        currency,
+        country: recipient_info
+            .and_then(|info| info.get("country_code"))
+            .and_then(serde_json::Value::as_str),
+    }
+}
+
+/// The market a stored payment is processed in
+fn payment_market(payment: &Payment) -> Market<'_> {
+    market(&payment.currency, payment.recipient_info.as_ref())
+}
+
+/// Check the bank details in a payment's recipient info and store them
+/// normalized. An account number on its own names an internal account, so
+/// it is only checked as an IBAN or together with a country.
+fn normalize_recipient_account(recipient_info: &mut serde_json::Value) -> AppResult<()> {
+    const FIELDS: [&str; 5] = ["iban", "bic", "account_number", "bank_code", "country_code"];
+    let Some(info) = recipient_info.as_object_mut() else {
+        return Ok(());
+    };
+    let field = |name: &str| info.get(name).and_then(serde_json::Value::as_str).map(str::to_string);
+    let (iban, bic, account_number, bank_code, country_code) = (
+        field("iban"),
+        field("bic"),
+        field("account_number"),
+        field("bank_code"),
+        field("country_code"),
+    );
+
+    let external = iban.is_some()
+        || country_code.is_some()
+        || account_number.as_deref().is_some_and(|number| looks_like_iban(&normalize(number)));
+    if external {
+        let validation = bank_details::validate_account(&AccountDetails {
+            iban: iban.as_deref(),
+            bic: bic.as_deref(),
+            account_number: account_number.as_deref(),
+            bank_code: bank_code.as_deref(),
+            country_code: country_code.as_deref(),
+        });
+        if !validation.valid {
+            return Err(AppError::Validation(format!(
+                "Invalid recipient account: {}",
+                validation.issues.join("; ")
+            )));
+        }
+    } else if let Some(bic) = &bic {
+        parse_bic(&normalize(bic)).map_err(|issue| AppError::Validation(format!("Invalid recipient BIC: {}", issue)))?;
+    } else {
+        return Ok(());
+    }
+
+    for name in FIELDS {
+        if let Some(serde_json::Value::String(value)) = info.get_mut(name) {
+            *value = normalize(value);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::PaginationParams;
+use super::matching::Tolerance;
+use super::model::{
+    BreakFilter, IngestSettlementRequest, ReconciliationBreak, ReconciliationRun,
+    ReconciliationRunResponse, ResolveBreakRequest,
+};
+use super::repository::ReconciliationRepository;
+use super::service::ReconciliationService;
+
+fn reconciliation_service(state: &AppState) -> ReconciliationService {
+    ReconciliationService::new(
+        ReconciliationRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+        Tolerance {
+            amount: state.config.reconciliation_amount_tolerance,
+            days: state.config.reconciliation_date_tolerance_days,
+        },
+    )
+}
+
+/// Reconcile a settlement file and produce its break report (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/reconciliation/runs",
+    tag = "reconciliation",
+    request_body = IngestSettlementRequest,
+    responses(
+        (status = 201, description = "Settlement file reconciled", body = ReconciliationRunResponse),
+        (status = 403, description = "Caller lacks the reconciliation permission"),
+        (status = 409, description = "Settlement file has already been reconciled"),
+        (status = 422, description = "Settlement file could not be parsed")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn ingest_settlement(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<IngestSettlementRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<ReconciliationRunResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_reconciliation(), ip, "reconciliation_runs".to_string())
+        .await?;
+
+    let run = reconciliation_service(&state)
+        .ingest(request, claims.developer_id)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Settlement file reconciled successfully", run)),
+    ))
+}
+
+/// List reconciliation runs, newest first (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/reconciliation/runs",
+    tag = "reconciliation",
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Reconciliation runs", body = [ReconciliationRun]),
+        (status = 403, description = "Caller lacks the reconciliation permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_runs(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<Vec<ReconciliationRun>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_reconciliation(), ip, "reconciliation_runs".to_string())
+        .await?;
+
+    let runs = reconciliation_service(&state)
+        .list_runs(pagination.page, pagination.limit)
+        .await?;
+    Ok(Json(ApiResponse::success("Reconciliation runs retrieved successfully", runs)))
+}
+
+/// Get a reconciliation run with its break report (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/reconciliation/runs/{id}",
+    tag = "reconciliation",
+    params(("id" = Uuid, Path, description = "Reconciliation run ID")),
+    responses(
+        (status = 200, description = "Reconciliation run", body = ReconciliationRunResponse),
+        (status = 403, description = "Caller lacks the reconciliation permission"),
+        (status = 404, description = "Reconciliation run not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_run(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<ReconciliationRunResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_reconciliation(), ip, format!("reconciliation_run:{}", id))
+        .await?;
+
+    let run = reconciliation_service(&state).get_run(id).await?;
+    Ok(Json(ApiResponse::success("Reconciliation run retrieved successfully", run)))
+}
+
+/// List the breaks of a reconciliation run (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/reconciliation/runs/{id}/breaks",
+    tag = "reconciliation",
+    params(("id" = Uuid, Path, description = "Reconciliation run ID"), BreakFilter),
+    responses(
+        (status = 200, description = "Reconciliation breaks", body = [ReconciliationBreak]),
+        (status = 403, description = "Caller lacks the reconciliation permission"),
+        (status = 404, description = "Reconciliation run not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_breaks(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    Query(filter): Query<BreakFilter>,
+) -> AppResult<Json<ApiResponse<Vec<ReconciliationBreak>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_reconciliation(), ip, format!("reconciliation_run:{}", id))
+        .await?;
+
+    let breaks = reconciliation_service(&state).list_breaks(id, filter).await?;
+    Ok(Json(ApiResponse::success("Reconciliation breaks retrieved successfully", breaks)))
+}
+
+/// Resolve a reconciliation break manually (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/reconciliation/breaks/{id}/resolve",
+    tag = "reconciliation",
+    params(("id" = Uuid, Path, description = "Reconciliation break ID")),
+    request_body = ResolveBreakRequest,
+    responses(
+        (status = 200, description = "Break resolved", body = ReconciliationBreak),
+        (status = 400, description = "Break is already resolved"),
+        (status = 403, description = "Caller lacks the reconciliation permission"),
+        (status = 404, description = "Break or matched transaction not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn resolve_break(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<ResolveBreakRequest>,
+) -> AppResult<Json<ApiResponse<ReconciliationBreak>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_reconciliation(), ip, format!("reconciliation_break:{}", id))
+        .await?;
+
+    let resolved = reconciliation_service(&state)
+        .resolve_break(id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Reconciliation break resolved successfully", resolved)))
+}
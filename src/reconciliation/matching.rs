@@ -0,0 +1,215 @@
+use chrono::NaiveDate;
+use crate::shared::types::Amount;
+use super::model::{BreakType, LedgerEntry, SettlementEntry};
+
+/// How far a settlement entry may drift from its internal transaction and
+/// still reconcile
+#[derive(Debug, Clone, Copy)]
+pub struct Tolerance {
+    /// Minor units
+    pub amount: Amount,
+    pub days: i64,
+}
+
+/// A break found while matching, before it is stored
+#[derive(Debug, Clone)]
+pub struct BreakCandidate {
+    pub break_type: BreakType,
+    pub reference: String,
+    pub settlement: Option<SettlementEntry>,
+    pub internal: Option<LedgerEntry>,
+}
+
+/// Outcome of matching a settlement file against the ledger
+#[derive(Debug, Default)]
+pub struct MatchOutcome {
+    pub matched: usize,
+    pub breaks: Vec<BreakCandidate>,
+}
+
+/// Match settlement entries against internal transactions.
+///
+/// An entry is paired with the transaction carrying its reference; a pair
+/// whose currency, amount or date differs beyond tolerance is a break. Entries
+/// without a reference match fall back to the single unpaired transaction
+/// with the same currency and an amount and date within tolerance. Internal
+/// transactions dated inside `period` that nothing paired with are reported
+/// as missing from the settlement.
+pub fn reconcile(
+    settlement: &[SettlementEntry],
+    ledger: &[LedgerEntry],
+    period: (NaiveDate, NaiveDate),
+    tolerance: Tolerance,
+) -> MatchOutcome {
+    let mut consumed = vec![false; ledger.len()];
+    let mut outcome = MatchOutcome::default();
+
+    let within_amount = |entry: &SettlementEntry, internal: &LedgerEntry| {
+        entry.currency == internal.currency && (entry.amount - internal.amount).abs() <= tolerance.amount
+    };
+    let within_days = |entry: &SettlementEntry, internal: &LedgerEntry| {
+        (entry.value_date - internal.created_at.date_naive()).num_days().abs() <= tolerance.days
+    };
+
+    for entry in settlement {
+        let by_reference = ledger
+            .iter()
+            .position(|internal| internal.reference == entry.reference);
+
+        let paired = match by_reference {
+            Some(index) if !consumed[index] => Some(index),
+            // The same reference appearing twice in a file settles nothing new
+            Some(_) => None,
+            None => {
+                let mut candidates = ledger.iter().enumerate().filter(|(index, internal)| {
+                    !consumed[*index] && within_amount(entry, internal) && within_days(entry, internal)
+                });
+                match (candidates.next(), candidates.next()) {
+                    (Some((index, _)), None) => Some(index),
+                    _ => None,
+                }
+            }
+        };
+
+        let Some(index) = paired else {
+            outcome.breaks.push(BreakCandidate {
+                break_type: BreakType::MissingInternal,
+                reference: entry.reference.clone(),
+                settlement: Some(entry.clone()),
+                internal: None,
+            });
+            continue;
+        };
+
+        consumed[index] = true;
+        let internal = &ledger[index];
+        let break_type = if !within_amount(entry, internal) {
+            Some(BreakType::AmountMismatch)
+        } else if !within_days(entry, internal) {
+            Some(BreakType::DateMismatch)
+        } else {
+            None
+        };
+
+        match break_type {
+            Some(break_type) => outcome.breaks.push(BreakCandidate {
+                break_type,
+                reference: entry.reference.clone(),
+                settlement: Some(entry.clone()),
+                internal: Some(internal.clone()),
+            }),
+            None => outcome.matched += 1,
+        }
+    }
+
+    let (start, end) = period;
+    for (internal, _) in ledger.iter().zip(&consumed).filter(|(_, consumed)| !**consumed) {
+        let date = internal.created_at.date_naive();
+        if date >= start && date <= end {
+            outcome.breaks.push(BreakCandidate {
+                break_type: BreakType::MissingSettlement,
+                reference: internal.reference.clone(),
+                settlement: None,
+                internal: Some(internal.clone()),
+            });
+        }
+    }
+
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn settled(reference: &str, amount: Amount, day: u32) -> SettlementEntry {
+        SettlementEntry {
+            reference: reference.to_string(),
+            amount,
+            currency: "USD".to_string(),
+            value_date: date(day),
+            description: None,
+        }
+    }
+
+    fn internal(reference: &str, amount: Amount, day: u32) -> LedgerEntry {
+        LedgerEntry {
+            id: Uuid::new_v4(),
+            reference: reference.to_string(),
+            amount,
+            currency: "USD".to_string(),
+            created_at: Utc.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    const TOLERANCE: Tolerance = Tolerance { amount: 0, days: 1 };
+
+    #[test]
+    fn test_matches_by_reference_within_tolerance() {
+        let outcome = reconcile(
+            &[settled("TXN-1", 1000, 15)],
+            &[internal("TXN-1", 1000, 14)],
+            (date(15), date(15)),
+            TOLERANCE,
+        );
+
+        assert_eq!(outcome.matched, 1);
+        assert!(outcome.breaks.is_empty());
+    }
+
+    #[test]
+    fn test_reports_mismatches_and_missing_entries() {
+        let outcome = reconcile(
+            &[
+                settled("TXN-1", 1001, 15),
+                settled("TXN-2", 500, 18),
+                settled("UNKNOWN", 700, 15),
+            ],
+            &[
+                internal("TXN-1", 1000, 15),
+                internal("TXN-2", 500, 15),
+                internal("TXN-3", 900, 16),
+                internal("TXN-4", 900, 10),
+            ],
+            (date(15), date(18)),
+            TOLERANCE,
+        );
+
+        let types: Vec<_> = outcome
+            .breaks
+            .iter()
+            .map(|candidate| (candidate.reference.as_str(), candidate.break_type))
+            .collect();
+        assert_eq!(outcome.matched, 0);
+        assert_eq!(
+            types,
+            vec![
+                ("TXN-1", BreakType::AmountMismatch),
+                ("TXN-2", BreakType::DateMismatch),
+                ("UNKNOWN", BreakType::MissingInternal),
+                // TXN-4 falls outside the settlement period
+                ("TXN-3", BreakType::MissingSettlement),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_unique_amount_and_date() {
+        let outcome = reconcile(
+            &[settled("BANK-REF", 2500, 15)],
+            &[internal("TXN-1", 2500, 15), internal("TXN-2", 900, 15)],
+            (date(15), date(15)),
+            TOLERANCE,
+        );
+
+        assert_eq!(outcome.matched, 1);
+        assert_eq!(outcome.breaks.len(), 1);
+        assert_eq!(outcome.breaks[0].reference, "TXN-2");
+    }
+}
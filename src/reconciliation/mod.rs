@@ -0,0 +1,24 @@
+pub mod controller;
+pub mod matching;
+pub mod model;
+pub mod parser;
+pub mod repository;
+pub mod service;
+
+use axum::{extract::DefaultBodyLimit, routing::{get, post}, Router};
+use crate::core::AppState;
+use crate::shared::constants::MAX_SETTLEMENT_FILE_SIZE;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/runs",
+            // Leave room for the JSON envelope around the file content
+            post(controller::ingest_settlement)
+                .layer(DefaultBodyLimit::max(MAX_SETTLEMENT_FILE_SIZE + 64 * 1024)),
+        )
+        .route("/runs", get(controller::list_runs))
+        .route("/runs/:id", get(controller::get_run))
+        .route("/runs/:id/breaks", get(controller::list_breaks))
+        .route("/breaks/:id/resolve", post(controller::resolve_break))
+}
@@ -0,0 +1,154 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::{Amount, Currency, TransactionId};
+
+/// Layout of a settlement file
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "settlement_format", rename_all = "snake_case")]
+pub enum SettlementFormat {
+    /// Header row naming `reference`, `amount`, `currency` and `value_date`
+    /// columns, plus an optional `description`
+    Csv,
+    /// SWIFT MT940 customer statement
+    Mt940,
+}
+
+/// Why an entry did not reconcile
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "reconciliation_break_type", rename_all = "snake_case")]
+pub enum BreakType {
+    /// Settled externally with no internal transaction
+    MissingInternal,
+    /// Completed internally but absent from the settlement file
+    MissingSettlement,
+    /// Same reference, amounts or currencies differ beyond tolerance
+    AmountMismatch,
+    /// Same reference, dates differ beyond tolerance
+    DateMismatch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "reconciliation_break_status", rename_all = "snake_case")]
+pub enum BreakStatus {
+    Open,
+    Resolved,
+}
+
+/// How operations settled a break
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "reconciliation_resolution", rename_all = "snake_case")]
+pub enum BreakResolution {
+    /// Paired with an internal transaction by hand
+    ManuallyMatched,
+    /// Corrected by an adjustment posted outside reconciliation
+    Adjusted,
+    /// Accepted as a loss or gain
+    WrittenOff,
+}
+
+/// One movement reported by a settlement file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettlementEntry {
+    pub reference: String,
+    /// Minor units, always positive
+    pub amount: Amount,
+    pub currency: Currency,
+    pub value_date: NaiveDate,
+    pub description: Option<String>,
+}
+
+/// An internal transaction as seen by reconciliation
+#[derive(Debug, Clone, FromRow)]
+pub struct LedgerEntry {
+    pub id: TransactionId,
+    pub reference: String,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One ingested settlement file
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReconciliationRun {
+    pub id: Uuid,
+    pub file_name: String,
+    pub format: SettlementFormat,
+    /// SHA-256 of the file content
+    pub file_hash: String,
+    /// First value date in the file
+    pub period_start: NaiveDate,
+    /// Last value date in the file
+    pub period_end: NaiveDate,
+    pub entry_count: i32,
+    pub matched_count: i32,
+    pub break_count: i32,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An entry that did not reconcile
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReconciliationBreak {
+    pub id: Uuid,
+    pub run_id: Uuid,
+    pub break_type: BreakType,
+    pub status: BreakStatus,
+    pub reference: Option<String>,
+    pub transaction_id: Option<TransactionId>,
+    pub currency: Option<Currency>,
+    pub settlement_amount: Option<Amount>,
+    pub internal_amount: Option<Amount>,
+    pub settlement_date: Option<NaiveDate>,
+    pub internal_date: Option<NaiveDate>,
+    pub description: Option<String>,
+    pub resolution: Option<BreakResolution>,
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Ingest settlement file request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct IngestSettlementRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub file_name: String,
+    pub format: SettlementFormat,
+    /// File content as text
+    #[validate(length(min = 1))]
+    pub content: String,
+}
+
+/// Result of reconciling a settlement file
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReconciliationRunResponse {
+    #[serde(flatten)]
+    pub run: ReconciliationRun,
+    pub breaks: Vec<ReconciliationBreak>,
+}
+
+/// Query parameters for listing a run's breaks
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BreakFilter {
+    pub status: Option<BreakStatus>,
+}
+
+/// Resolve break request
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResolveBreakRequest {
+    pub resolution: BreakResolution,
+    /// Internal transaction the break was matched to; required for
+    /// `manually_matched`
+    pub transaction_id: Option<TransactionId>,
+    #[validate(length(min = 1, max = 1000))]
+    pub note: String,
+}
@@ -0,0 +1,259 @@
+use chrono::NaiveDate;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::Amount;
+use super::model::{SettlementEntry, SettlementFormat};
+
+/// Parse a settlement file into its entries
+pub fn parse(format: SettlementFormat, content: &str) -> AppResult<Vec<SettlementEntry>> {
+    let entries = match format {
+        SettlementFormat::Csv => parse_csv(content)?,
+        SettlementFormat::Mt940 => parse_mt940(content)?,
+    };
+    if entries.is_empty() {
+        return Err(AppError::Validation("Settlement file has no entries".to_string()));
+    }
+    Ok(entries)
+}
+
+/// CSV with a header row. Amounts are decimal major units; the sign is
+/// ignored since debits and credits both settle.
+fn parse_csv(content: &str) -> AppResult<Vec<SettlementEntry>> {
+    let mut lines = content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = lines
+        .next()
+        .ok_or_else(|| AppError::Validation("Settlement file is empty".to_string()))?;
+    let header: Vec<String> = split_csv_line(header)
+        .iter()
+        .map(|name| name.trim().to_lowercase())
+        .collect();
+    let column = |names: &[&str]| header.iter().position(|name| names.contains(&name.as_str()));
+    let required = |names: &[&str]| {
+        column(names).ok_or_else(|| {
+            AppError::Validation(format!("Settlement file has no '{}' column", names[0]))
+        })
+    };
+
+    let reference = required(&["reference", "ref"])?;
+    let amount = required(&["amount"])?;
+    let currency = required(&["currency"])?;
+    let value_date = required(&["value_date", "date"])?;
+    let description = column(&["description", "narrative"]);
+
+    lines
+        .map(|(index, line)| {
+            let fields = split_csv_line(line);
+            let field = |position: usize| {
+                fields.get(position).map(|value| value.trim()).ok_or_else(|| {
+                    AppError::Validation(format!("Line {}: missing column {}", index + 1, position + 1))
+                })
+            };
+            let line_error = |message: String| AppError::Validation(format!("Line {}: {}", index + 1, message));
+
+            Ok(SettlementEntry {
+                reference: field(reference)?.to_string(),
+                amount: parse_amount(field(amount)?, '.').map_err(line_error)?,
+                currency: parse_currency(field(currency)?).map_err(line_error)?,
+                value_date: NaiveDate::parse_from_str(field(value_date)?, "%Y-%m-%d")
+                    .map_err(|_| line_error("value date must be YYYY-MM-DD".to_string()))?,
+                description: description
+                    .and_then(|position| fields.get(position))
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty()),
+            })
+        })
+        .collect()
+}
+
+/// Split one CSV line, honouring double-quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// MT940 statement. Each `:61:` statement line is an entry; the currency
+/// comes from the opening balance and the description from the `:86:` field
+/// that follows. The customer reference is used for matching, or the bank
+/// reference when the customer gave none.
+fn parse_mt940(content: &str) -> AppResult<Vec<SettlementEntry>> {
+    let mut entries: Vec<SettlementEntry> = Vec::new();
+    let mut currency: Option<String> = None;
+    let mut in_description = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim_end();
+        let line_error = |message: &str| AppError::Validation(format!("Line {}: {}", index + 1, message));
+
+        if let Some(balance) = line.strip_prefix(":60F:").or_else(|| line.strip_prefix(":60M:")) {
+            // D/C mark, YYMMDD, then the currency
+            let code = balance.get(7..10).ok_or_else(|| line_error("invalid opening balance"))?;
+            currency = Some(parse_currency(code).map_err(|e| line_error(&e))?);
+            in_description = false;
+        } else if let Some(statement) = line.strip_prefix(":61:") {
+            let currency = currency
+                .clone()
+                .ok_or_else(|| line_error("statement line before the opening balance"))?;
+            entries.push(parse_statement_line(statement, currency).map_err(|e| line_error(&e))?);
+            in_description = false;
+        } else if let Some(information) = line.strip_prefix(":86:") {
+            if let Some(entry) = entries.last_mut().filter(|entry| entry.description.is_none()) {
+                entry.description = Some(information.trim().to_string());
+                in_description = true;
+            }
+        } else if line.starts_with(':') || line.starts_with('-') {
+            in_description = false;
+        } else if in_description {
+            // Continuation of a multi-line :86: field
+            if let Some(description) = entries.last_mut().and_then(|entry| entry.description.as_mut()) {
+                description.push(' ');
+                description.push_str(line.trim());
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// `YYMMDD[MMDD](C|D|RC|RD)[funds code]amount N<type><reference>[//<bank reference>]`
+fn parse_statement_line(statement: &str, currency: String) -> Result<SettlementEntry, String> {
+    let value_date = statement
+        .get(..6)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%y%m%d").ok())
+        .ok_or("invalid value date")?;
+    let mut rest = &statement[6..];
+
+    // Optional entry date
+    if rest.len() >= 4 && rest[..4].chars().all(|c| c.is_ascii_digit()) {
+        rest = &rest[4..];
+    }
+    // Debit/credit mark, including reversals
+    rest = ["RC", "RD", "C", "D"]
+        .iter()
+        .find_map(|mark| rest.strip_prefix(mark))
+        .ok_or("missing debit/credit mark")?;
+    // Optional funds code
+    if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        rest = &rest[1..];
+    }
+
+    let amount_end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == ','))
+        .ok_or("missing transaction type")?;
+    let amount = parse_amount(&rest[..amount_end], ',')?;
+    // Transaction type identification code: one letter and three characters
+    let references = rest.get(amount_end + 4..).ok_or("missing transaction type")?;
+
+    let (customer, bank) = match references.split_once("//") {
+        Some((customer, bank)) => (customer.trim(), Some(bank.trim())),
+        None => (references.trim(), None),
+    };
+    let reference = match (customer, bank) {
+        ("NONREF", Some(bank)) | ("", Some(bank)) => bank,
+        (customer, _) => customer,
+    };
+    if reference.is_empty() || reference == "NONREF" {
+        return Err("statement line has no reference".to_string());
+    }
+
+    Ok(SettlementEntry {
+        reference: reference.to_string(),
+        amount,
+        currency,
+        value_date,
+        description: None,
+    })
+}
+
+/// Decimal major units to minor units, assuming two decimal places
+fn parse_amount(value: &str, decimal_separator: char) -> Result<Amount, String> {
+    let invalid = || format!("invalid amount '{}'", value);
+    let unsigned = value.trim().trim_start_matches(['-', '+']);
+    let (whole, fraction) = unsigned.split_once(decimal_separator).unwrap_or((unsigned, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if fraction.len() > 2 || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let whole: Amount = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| invalid())? };
+    let fraction: Amount = format!("{:0<2}", fraction).parse().map_err(|_| invalid())?;
+    whole
+        .checked_mul(100)
+        .and_then(|minor| minor.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+fn parse_currency(value: &str) -> Result<String, String> {
+    if value.len() == 3 && value.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(value.to_uppercase())
+    } else {
+        Err(format!("invalid currency '{}'", value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let content = "Reference,Amount,Currency,Value_Date,Description\n\
+                       TXN-1,125.50,usd,2026-10-14,\"Card settlement, batch 7\"\n\
+                       TXN-2,-3,USD,2026-10-15,\n";
+        let entries = parse(SettlementFormat::Csv, content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].reference, "TXN-1");
+        assert_eq!(entries[0].amount, 12550);
+        assert_eq!(entries[0].currency, "USD");
+        assert_eq!(entries[0].description.as_deref(), Some("Card settlement, batch 7"));
+        assert_eq!(entries[1].amount, 300);
+        assert_eq!(entries[1].description, None);
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_bad_amount() {
+        let content = "reference,amount,currency,value_date\nTXN-1,12.345,USD,2026-10-14\n";
+        assert!(parse(SettlementFormat::Csv, content).is_err());
+    }
+
+    #[test]
+    fn test_parse_mt940() {
+        let content = ":20:STMT-20261015\n\
+                       :25:12345678/0001\n\
+                       :28C:42/1\n\
+                       :60F:C261014EUR1000,00\n\
+                       :61:2610151015C250,00NTRFTXN-1//BANKREF1\n\
+                       :86:Salary October\n\
+                       ACME Ltd\n\
+                       :61:261015RD12,5NMSCNONREF//BANKREF2\n\
+                       :62F:C261015EUR1237,50\n\
+                       -";
+        let entries = parse(SettlementFormat::Mt940, content).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].reference, "TXN-1");
+        assert_eq!(entries[0].amount, 25000);
+        assert_eq!(entries[0].currency, "EUR");
+        assert_eq!(entries[0].value_date, NaiveDate::from_ymd_opt(2026, 10, 15).unwrap());
+        assert_eq!(entries[0].description.as_deref(), Some("Salary October ACME Ltd"));
+        assert_eq!(entries[1].reference, "BANKREF2");
+        assert_eq!(entries[1].amount, 1250);
+    }
+}
@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::shared::types::TransactionId;
+use super::model::{BreakResolution, BreakStatus, LedgerEntry, ReconciliationBreak, ReconciliationRun};
+
+const RUN_COLUMNS: &str = "id, file_name, format, file_hash, period_start, period_end, entry_count,
+    matched_count, break_count, created_by, created_at";
+
+const BREAK_COLUMNS: &str = "id, run_id, break_type, status, reference, transaction_id, currency,
+    settlement_amount, internal_amount, settlement_date, internal_date, description, resolution,
+    resolution_note, resolved_by, resolved_at, created_at";
+
+pub struct ReconciliationRepository {
+    pool: PgPool,
+}
+
+impl ReconciliationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Completed transactions that move money across the platform boundary,
+    /// created within the window
+    pub async fn find_ledger_entries(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AppResult<Vec<LedgerEntry>> {
+        let entries = sqlx::query_as::<_, LedgerEntry>(
+            "SELECT id, reference, amount, currency, created_at FROM transactions
+             WHERE status = 'completed'
+               AND transaction_type IN ('deposit', 'withdrawal', 'payment', 'refund')
+               AND created_at >= $1 AND created_at < $2
+             ORDER BY created_at",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    pub async fn transaction_exists(&self, transaction_id: TransactionId) -> AppResult<bool> {
+        let (exists,): (bool,) = sqlx::query_as("SELECT EXISTS(SELECT 1 FROM transactions WHERE id = $1)")
+            .bind(transaction_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(exists)
+    }
+
+    pub async fn run_exists_for_hash(&self, file_hash: &str) -> AppResult<bool> {
+        let (exists,): (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM reconciliation_runs WHERE file_hash = $1)")
+                .bind(file_hash)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(exists)
+    }
+
+    /// Insert a run together with its breaks
+    pub async fn create_run(
+        &self,
+        run: &ReconciliationRun,
+        breaks: &[ReconciliationBreak],
+    ) -> AppResult<(ReconciliationRun, Vec<ReconciliationBreak>)> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let created = sqlx::query_as::<_, ReconciliationRun>(&format!(
+            "INSERT INTO reconciliation_runs (id, file_name, format, file_hash, period_start, period_end,
+                 entry_count, matched_count, break_count, created_by, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             RETURNING {RUN_COLUMNS}"
+        ))
+        .bind(run.id)
+        .bind(&run.file_name)
+        .bind(run.format)
+        .bind(&run.file_hash)
+        .bind(run.period_start)
+        .bind(run.period_end)
+        .bind(run.entry_count)
+        .bind(run.matched_count)
+        .bind(run.break_count)
+        .bind(run.created_by)
+        .bind(run.created_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let mut created_breaks = Vec::with_capacity(breaks.len());
+        for item in breaks {
+            let created_break = sqlx::query_as::<_, ReconciliationBreak>(&format!(
+                "INSERT INTO reconciliation_breaks (id, run_id, break_type, status, reference, transaction_id,
+                     currency, settlement_amount, internal_amount, settlement_date, internal_date, description,
+                     created_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                 RETURNING {BREAK_COLUMNS}"
+            ))
+            .bind(item.id)
+            .bind(created.id)
+            .bind(item.break_type)
+            .bind(item.status)
+            .bind(&item.reference)
+            .bind(item.transaction_id)
+            .bind(&item.currency)
+            .bind(item.settlement_amount)
+            .bind(item.internal_amount)
+            .bind(item.settlement_date)
+            .bind(item.internal_date)
+            .bind(&item.description)
+            .bind(item.created_at)
+            .fetch_one(&mut *tx)
+            .await?;
+            created_breaks.push(created_break);
+        }
+
+        tx.commit().await?;
+        Ok((created, created_breaks))
+    }
+
+    pub async fn find_runs(&self, page: u32, limit: u32) -> AppResult<Vec<ReconciliationRun>> {
+        let offset = (page.max(1) - 1) * limit;
+
+        let runs = sqlx::query_as::<_, ReconciliationRun>(&format!(
+            "SELECT {RUN_COLUMNS} FROM reconciliation_runs
+             ORDER BY created_at DESC LIMIT $1 OFFSET $2"
+        ))
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(runs)
+    }
+
+    pub async fn find_run(&self, run_id: Uuid) -> AppResult<Option<ReconciliationRun>> {
+        let run = sqlx::query_as::<_, ReconciliationRun>(&format!(
+            "SELECT {RUN_COLUMNS} FROM reconciliation_runs WHERE id = $1"
+        ))
+        .bind(run_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(run)
+    }
+
+    /// Breaks of a run, optionally limited to one status
+    pub async fn find_breaks(
+        &self,
+        run_id: Uuid,
+        status: Option<BreakStatus>,
+    ) -> AppResult<Vec<ReconciliationBreak>> {
+        let breaks = sqlx::query_as::<_, ReconciliationBreak>(&format!(
+            "SELECT {BREAK_COLUMNS} FROM reconciliation_breaks
+             WHERE run_id = $1 AND ($2::reconciliation_break_status IS NULL OR status = $2)
+             ORDER BY created_at, id"
+        ))
+        .bind(run_id)
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(breaks)
+    }
+
+    pub async fn find_break(&self, break_id: Uuid) -> AppResult<Option<ReconciliationBreak>> {
+        let found = sqlx::query_as::<_, ReconciliationBreak>(&format!(
+            "SELECT {BREAK_COLUMNS} FROM reconciliation_breaks WHERE id = $1"
+        ))
+        .bind(break_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(found)
+    }
+
+    /// Resolve an open break; `None` if it was resolved concurrently
+    pub async fn resolve_break(
+        &self,
+        break_id: Uuid,
+        resolution: BreakResolution,
+        transaction_id: Option<TransactionId>,
+        note: &str,
+        actor_id: Uuid,
+    ) -> AppResult<Option<ReconciliationBreak>> {
+        let resolved = sqlx::query_as::<_, ReconciliationBreak>(&format!(
+            "UPDATE reconciliation_breaks
+             SET status = 'resolved',
+                 resolution = $1,
+                 transaction_id = COALESCE($2, transaction_id),
+                 resolution_note = $3,
+                 resolved_by = $4,
+                 resolved_at = NOW()
+             WHERE id = $5 AND status = 'open'
+             RETURNING {BREAK_COLUMNS}"
+        ))
+        .bind(resolution)
+        .bind(transaction_id)
+        .bind(note)
+        .bind(actor_id)
+        .bind(break_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(resolved)
+    }
+}
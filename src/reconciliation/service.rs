@@ -0,0 +1,191 @@
+use chrono::{Duration, NaiveTime, Utc};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::crypto::hex;
+use crate::core::error::{AppError, AppResult};
+use super::matching::{self, BreakCandidate, Tolerance};
+use super::model::{
+    BreakFilter, BreakResolution, BreakStatus, IngestSettlementRequest, ReconciliationBreak,
+    ReconciliationRun, ReconciliationRunResponse, ResolveBreakRequest,
+};
+use super::parser;
+use super::repository::ReconciliationRepository;
+
+pub struct ReconciliationService {
+    repository: ReconciliationRepository,
+    audit_logger: AuditLogger,
+    tolerance: Tolerance,
+}
+
+impl ReconciliationService {
+    pub fn new(repository: ReconciliationRepository, audit_logger: AuditLogger, tolerance: Tolerance) -> Self {
+        Self {
+            repository,
+            audit_logger,
+            tolerance,
+        }
+    }
+
+    /// Reconcile a settlement file against completed internal transactions
+    pub async fn ingest(
+        &self,
+        request: IngestSettlementRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ReconciliationRunResponse> {
+        let file_hash = hex(&Sha256::digest(request.content.as_bytes()));
+        if self.repository.run_exists_for_hash(&file_hash).await? {
+            return Err(AppError::Conflict("Settlement file has already been reconciled".to_string()));
+        }
+
+        let entries = parser::parse(request.format, &request.content)?;
+        let period_start = entries.iter().map(|entry| entry.value_date).min().unwrap_or_default();
+        let period_end = entries.iter().map(|entry| entry.value_date).max().unwrap_or_default();
+
+        // Widen the window so entries near the edges can still pair within tolerance
+        let from = (period_start - Duration::days(self.tolerance.days))
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        let to = (period_end + Duration::days(self.tolerance.days + 1))
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+        let ledger = self.repository.find_ledger_entries(from, to).await?;
+
+        let outcome = matching::reconcile(&entries, &ledger, (period_start, period_end), self.tolerance);
+
+        let now = Utc::now();
+        let run = ReconciliationRun {
+            id: Uuid::new_v4(),
+            file_name: request.file_name,
+            format: request.format,
+            file_hash,
+            period_start,
+            period_end,
+            entry_count: entries.len() as i32,
+            matched_count: outcome.matched as i32,
+            break_count: outcome.breaks.len() as i32,
+            created_by: Some(actor_id),
+            created_at: now,
+        };
+        let breaks: Vec<ReconciliationBreak> = outcome
+            .breaks
+            .into_iter()
+            .map(|candidate| new_break(run.id, candidate))
+            .collect();
+
+        let (run, breaks) = self.repository.create_run(&run, &breaks).await?;
+
+        let event = AuditEvent::new(AuditEventType::ReconciliationRunCompleted)
+            .severity(if breaks.is_empty() { AuditSeverity::Info } else { AuditSeverity::Warning })
+            .user_id(actor_id)
+            .resource(format!("reconciliation_run:{}", run.id))
+            .action("ingest".to_string())
+            .metadata("file_name".to_string(), serde_json::json!(run.file_name))
+            .metadata("entry_count".to_string(), serde_json::json!(run.entry_count))
+            .metadata("matched_count".to_string(), serde_json::json!(run.matched_count))
+            .metadata("break_count".to_string(), serde_json::json!(run.break_count))
+            .compliance_tag("RECONCILIATION".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(ReconciliationRunResponse { run, breaks })
+    }
+
+    pub async fn list_runs(&self, page: u32, limit: u32) -> AppResult<Vec<ReconciliationRun>> {
+        self.repository.find_runs(page, limit).await
+    }
+
+    /// Get a run with its break report
+    pub async fn get_run(&self, run_id: Uuid) -> AppResult<ReconciliationRunResponse> {
+        let run = self.find_run(run_id).await?;
+        let breaks = self.repository.find_breaks(run_id, None).await?;
+        Ok(ReconciliationRunResponse { run, breaks })
+    }
+
+    pub async fn list_breaks(&self, run_id: Uuid, filter: BreakFilter) -> AppResult<Vec<ReconciliationBreak>> {
+        self.find_run(run_id).await?;
+        self.repository.find_breaks(run_id, filter.status).await
+    }
+
+    /// Resolve an open break by hand
+    pub async fn resolve_break(
+        &self,
+        break_id: Uuid,
+        request: ResolveBreakRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ReconciliationBreak> {
+        let existing = self
+            .repository
+            .find_break(break_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Reconciliation break not found".to_string()))?;
+        if existing.status == BreakStatus::Resolved {
+            return Err(AppError::BadRequest("Reconciliation break is already resolved".to_string()));
+        }
+
+        match (request.resolution, request.transaction_id) {
+            (BreakResolution::ManuallyMatched, None) => {
+                return Err(AppError::Validation(
+                    "transaction_id is required to match a break manually".to_string(),
+                ))
+            }
+            (_, Some(transaction_id)) if !self.repository.transaction_exists(transaction_id).await? => {
+                return Err(AppError::NotFound("Transaction not found".to_string()));
+            }
+            _ => {}
+        }
+
+        let resolved = self
+            .repository
+            .resolve_break(break_id, request.resolution, request.transaction_id, &request.note, actor_id)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Reconciliation break is already resolved".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::ReconciliationBreakResolved)
+            .user_id(actor_id)
+            .resource(format!("reconciliation_break:{}", break_id))
+            .action("resolve".to_string())
+            .metadata("run_id".to_string(), serde_json::json!(resolved.run_id))
+            .metadata("break_type".to_string(), serde_json::json!(resolved.break_type))
+            .metadata("resolution".to_string(), serde_json::json!(resolved.resolution))
+            .metadata("transaction_id".to_string(), serde_json::json!(resolved.transaction_id))
+            .compliance_tag("RECONCILIATION".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(resolved)
+    }
+
+    async fn find_run(&self, run_id: Uuid) -> AppResult<ReconciliationRun> {
+        self.repository
+            .find_run(run_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Reconciliation run not found".to_string()))
+    }
+}
+
+fn new_break(run_id: Uuid, candidate: BreakCandidate) -> ReconciliationBreak {
+    let settlement = candidate.settlement;
+    let internal = candidate.internal;
+
+    ReconciliationBreak {
+        id: Uuid::new_v4(),
+        run_id,
+        break_type: candidate.break_type,
+        status: BreakStatus::Open,
+        reference: Some(candidate.reference),
+        transaction_id: internal.as_ref().map(|entry| entry.id),
+        currency: settlement
+            .as_ref()
+            .map(|entry| entry.currency.clone())
+            .or_else(|| internal.as_ref().map(|entry| entry.currency.clone())),
+        settlement_amount: settlement.as_ref().map(|entry| entry.amount),
+        internal_amount: internal.as_ref().map(|entry| entry.amount),
+        settlement_date: settlement.as_ref().map(|entry| entry.value_date),
+        internal_date: internal.as_ref().map(|entry| entry.created_at.date_naive()),
+        description: settlement.and_then(|entry| entry.description),
+        resolution: None,
+        resolution_note: None,
+        resolved_by: None,
+        resolved_at: None,
+        created_at: Utc::now(),
+    }
+}
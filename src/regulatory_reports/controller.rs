@@ -0,0 +1,253 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{
+    GenerateRegulatoryReportRequest, Jurisdiction, RegulatoryReport, RegulatoryReportQuery, RegulatoryReportSettings,
+    ReportableQuery, ReportableTransaction, UpdateSubmissionRequest,
+};
+use super::repository::RegulatoryReportRepository;
+use super::service::RegulatoryReportService;
+
+fn regulatory_report_service(state: &AppState) -> AppResult<RegulatoryReportService> {
+    Ok(RegulatoryReportService::new(
+        RegulatoryReportRepository::new(state.postgres.clone()),
+        RegulatoryReportSettings::from_config(&state.config)?,
+        state.audit_logger.clone(),
+    ))
+}
+
+/// List the configured reporting jurisdictions (auditors only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/regulatory-reports/jurisdictions",
+    tag = "regulatory-reports",
+    responses(
+        (status = 200, description = "Jurisdictions", body = [Jurisdiction]),
+        (status = 403, description = "Caller lacks the compliance reporting permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_jurisdictions(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+) -> AppResult<Json<ApiResponse<Vec<Jurisdiction>>>> {
+    state
+        .authorize(claims.developer_id, permissions::report_compliance(), ip, "regulatory_jurisdictions".to_string())
+        .await?;
+
+    let service = regulatory_report_service(&state)?;
+    Ok(Json(ApiResponse::success(
+        "Jurisdictions retrieved successfully",
+        service.jurisdictions().to_vec(),
+    )))
+}
+
+/// Preview the payments a report would list (auditors only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/regulatory-reports/reportable",
+    tag = "regulatory-reports",
+    params(ReportableQuery),
+    responses(
+        (status = 200, description = "Reportable payments", body = [ReportableTransaction]),
+        (status = 400, description = "Invalid period"),
+        (status = 403, description = "Caller lacks the compliance reporting permission"),
+        (status = 404, description = "Jurisdiction not configured")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_reportable(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<ReportableQuery>,
+) -> AppResult<Json<ApiResponse<Vec<ReportableTransaction>>>> {
+    state
+        .authorize(claims.developer_id, permissions::report_compliance(), ip, "regulatory_reportable".to_string())
+        .await?;
+
+    let transactions = regulatory_report_service(&state)?
+        .reportable(&query.jurisdiction, query.report_type, query.period_start, query.period_end)
+        .await?;
+    Ok(Json(ApiResponse::success("Reportable transactions retrieved successfully", transactions)))
+}
+
+/// Generate a report file for a jurisdiction and period (auditors only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/regulatory-reports",
+    tag = "regulatory-reports",
+    request_body = GenerateRegulatoryReportRequest,
+    responses(
+        (status = 201, description = "Report generated", body = RegulatoryReport),
+        (status = 400, description = "Invalid period"),
+        (status = 403, description = "Caller lacks the compliance reporting permission"),
+        (status = 404, description = "Jurisdiction not configured")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn generate_report(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<GenerateRegulatoryReportRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<RegulatoryReport>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    state
+        .authorize(claims.developer_id, permissions::report_compliance(), ip, "regulatory_reports".to_string())
+        .await?;
+
+    let report = regulatory_report_service(&state)?
+        .generate(request, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Regulatory report generated successfully", report)),
+    ))
+}
+
+/// List generated reports, most recent first (auditors only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/regulatory-reports",
+    tag = "regulatory-reports",
+    params(RegulatoryReportQuery),
+    responses(
+        (status = 200, description = "Reports", body = [RegulatoryReport]),
+        (status = 403, description = "Caller lacks the compliance reporting permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_reports(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<RegulatoryReportQuery>,
+) -> AppResult<Json<ApiResponse<Vec<RegulatoryReport>>>> {
+    state
+        .authorize(claims.developer_id, permissions::report_compliance(), ip, "regulatory_reports".to_string())
+        .await?;
+
+    let reports = regulatory_report_service(&state)?
+        .list(query.jurisdiction, query.status)
+        .await?;
+    Ok(Json(ApiResponse::success("Regulatory reports retrieved successfully", reports)))
+}
+
+/// Get a report and its submission status (auditors only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/regulatory-reports/{id}",
+    tag = "regulatory-reports",
+    params(("id" = Uuid, Path, description = "Report ID")),
+    responses(
+        (status = 200, description = "Report", body = RegulatoryReport),
+        (status = 403, description = "Caller lacks the compliance reporting permission"),
+        (status = 404, description = "Report not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_report(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<RegulatoryReport>>> {
+    state
+        .authorize(claims.developer_id, permissions::report_compliance(), ip, format!("regulatory_report:{}", id))
+        .await?;
+
+    let report = regulatory_report_service(&state)?.get(id).await?;
+    Ok(Json(ApiResponse::success("Regulatory report retrieved successfully", report)))
+}
+
+/// Download a report's file as generated (auditors only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/regulatory-reports/{id}/file",
+    tag = "regulatory-reports",
+    params(("id" = Uuid, Path, description = "Report ID")),
+    responses(
+        (status = 200, description = "The report file", content(
+            ("text/csv" = String),
+            ("application/xml" = String)
+        )),
+        (status = 403, description = "Caller lacks the compliance reporting permission"),
+        (status = 404, description = "Report not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn download_report(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Response> {
+    state
+        .authorize(claims.developer_id, permissions::report_compliance(), ip, format!("regulatory_report:{}", id))
+        .await?;
+
+    let report = regulatory_report_service(&state)?
+        .download(id, claims.developer_id)
+        .await?;
+    let disposition = format!("attachment; filename=\"{}\"", report.file_name());
+    Ok((
+        [
+            (header::CONTENT_TYPE, report.format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        report.content,
+    )
+        .into_response())
+}
+
+/// Record a report's submission or the regulator's response (auditors only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/regulatory-reports/{id}/submission",
+    tag = "regulatory-reports",
+    params(("id" = Uuid, Path, description = "Report ID")),
+    request_body = UpdateSubmissionRequest,
+    responses(
+        (status = 200, description = "Submission status updated", body = RegulatoryReport),
+        (status = 400, description = "Submission reference missing"),
+        (status = 403, description = "Caller lacks the compliance reporting permission"),
+        (status = 404, description = "Report not found"),
+        (status = 409, description = "The report cannot move to that status")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_submission(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<UpdateSubmissionRequest>,
+) -> AppResult<Json<ApiResponse<RegulatoryReport>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    state
+        .authorize(claims.developer_id, permissions::report_compliance(), ip, format!("regulatory_report:{}", id))
+        .await?;
+
+    let report = regulatory_report_service(&state)?
+        .update_submission(id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Submission status updated successfully", report)))
+}
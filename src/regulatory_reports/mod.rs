@@ -0,0 +1,20 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/regulatory-reports",
+            get(controller::list_reports).post(controller::generate_report),
+        )
+        .route("/regulatory-reports/jurisdictions", get(controller::list_jurisdictions))
+        .route("/regulatory-reports/reportable", get(controller::list_reportable))
+        .route("/regulatory-reports/:id", get(controller::get_report))
+        .route("/regulatory-reports/:id/file", get(controller::download_report))
+        .route("/regulatory-reports/:id/submission", post(controller::update_submission))
+}
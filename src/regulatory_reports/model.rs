@@ -0,0 +1,487 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::{csv::csv_field, types::{AccountId, Amount, Currency}};
+
+/// What a regulatory report covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "regulatory_report_type", rename_all = "snake_case")]
+pub enum RegulatoryReportType {
+    /// Payments at or above the jurisdiction's reporting threshold
+    LargeTransactions,
+    /// Payments flagged by fraud rules
+    SuspiciousActivity,
+}
+
+impl RegulatoryReportType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegulatoryReportType::LargeTransactions => "large_transactions",
+            RegulatoryReportType::SuspiciousActivity => "suspicious_activity",
+        }
+    }
+}
+
+/// File format a jurisdiction accepts reports in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "regulatory_report_format", rename_all = "snake_case")]
+pub enum RegulatoryReportFormat {
+    Csv,
+    Xml,
+}
+
+impl RegulatoryReportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            RegulatoryReportFormat::Csv => "text/csv; charset=utf-8",
+            RegulatoryReportFormat::Xml => "application/xml; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RegulatoryReportFormat::Csv => "csv",
+            RegulatoryReportFormat::Xml => "xml",
+        }
+    }
+}
+
+/// Where a report is in its submission to the regulator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "regulatory_submission_status", rename_all = "snake_case")]
+pub enum SubmissionStatus {
+    Generated,
+    Submitted,
+    Acknowledged,
+    /// Refused by the regulator; a corrected report is generated anew
+    Rejected,
+}
+
+impl SubmissionStatus {
+    /// Reports are submitted once, then acknowledged or rejected
+    pub fn can_move_to(&self, next: SubmissionStatus) -> bool {
+        matches!(
+            (self, next),
+            (SubmissionStatus::Generated, SubmissionStatus::Submitted)
+                | (SubmissionStatus::Submitted, SubmissionStatus::Acknowledged | SubmissionStatus::Rejected)
+        )
+    }
+}
+
+/// A reporting jurisdiction: the currency whose payments it covers, the
+/// file format it accepts and its large transaction threshold
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Jurisdiction {
+    pub code: String,
+    pub currency: Currency,
+    pub format: RegulatoryReportFormat,
+    pub large_transaction_threshold: Amount,
+}
+
+impl Jurisdiction {
+    /// Parse comma-separated `CODE:CURRENCY:FORMAT:THRESHOLD` entries, e.g.
+    /// `US:USD:xml:1000000`
+    pub fn parse_list(value: &str) -> Result<Vec<Jurisdiction>, String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let invalid = || format!("Invalid jurisdiction '{}', expected CODE:CURRENCY:FORMAT:THRESHOLD", entry);
+                let [code, currency, format, threshold] = entry.split(':').collect::<Vec<_>>()[..] else {
+                    return Err(invalid());
+                };
+                let format = match format.trim().to_lowercase().as_str() {
+                    "csv" => RegulatoryReportFormat::Csv,
+                    "xml" => RegulatoryReportFormat::Xml,
+                    _ => return Err(invalid()),
+                };
+                let large_transaction_threshold = threshold.trim().parse().map_err(|_| invalid())?;
+                Ok(Jurisdiction {
+                    code: code.trim().to_uppercase(),
+                    currency: currency.trim().to_uppercase(),
+                    format,
+                    large_transaction_threshold,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Configured reporting jurisdictions
+#[derive(Debug, Clone)]
+pub struct RegulatoryReportSettings {
+    pub jurisdictions: Vec<Jurisdiction>,
+}
+
+impl RegulatoryReportSettings {
+    pub fn from_config(config: &Config) -> AppResult<Self> {
+        let jurisdictions = Jurisdiction::parse_list(&config.regulatory_jurisdictions)
+            .map_err(|e| AppError::Internal(format!("REGULATORY_JURISDICTIONS: {}", e)))?;
+        Ok(Self { jurisdictions })
+    }
+
+    pub fn jurisdiction(&self, code: &str) -> AppResult<&Jurisdiction> {
+        self.jurisdictions
+            .iter()
+            .find(|jurisdiction| jurisdiction.code.eq_ignore_ascii_case(code))
+            .ok_or_else(|| AppError::NotFound(format!("Jurisdiction {} is not configured", code)))
+    }
+}
+
+/// Why a payment is reportable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportableReason {
+    /// At or above the large transaction threshold
+    LargeTransaction,
+    /// Flagged as a repeat of a recent payment
+    DuplicatePayment,
+    /// Made from an account frozen for suspected fraud
+    SuspectedFraudAccount,
+    /// One of several payments under the threshold that together reach it
+    /// within a day
+    Structuring,
+}
+
+impl ReportableReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportableReason::LargeTransaction => "large_transaction",
+            ReportableReason::DuplicatePayment => "duplicate_payment",
+            ReportableReason::SuspectedFraudAccount => "suspected_fraud_account",
+            ReportableReason::Structuring => "structuring",
+        }
+    }
+}
+
+/// A payment in a jurisdiction's period, with the rules it matched
+#[derive(Debug, Clone, FromRow)]
+pub struct ReportCandidate {
+    pub payment_id: Uuid,
+    pub reference: String,
+    pub from_account_id: AccountId,
+    pub to_account_id: Option<AccountId>,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub payment_method: String,
+    pub occurred_at: DateTime<Utc>,
+    pub large: bool,
+    pub duplicate: bool,
+    pub suspected_fraud_account: bool,
+    pub structuring: bool,
+}
+
+/// A payment a report lists
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReportableTransaction {
+    pub payment_id: Uuid,
+    pub reference: String,
+    pub from_account_id: AccountId,
+    pub to_account_id: Option<AccountId>,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub payment_method: String,
+    /// When the payment executed
+    pub occurred_at: DateTime<Utc>,
+    pub reasons: Vec<ReportableReason>,
+}
+
+impl ReportableTransaction {
+    /// The candidate as a line of a report of the given type, if that
+    /// report covers it
+    pub fn from_candidate(candidate: ReportCandidate, report_type: RegulatoryReportType) -> Option<Self> {
+        let rules = match report_type {
+            RegulatoryReportType::LargeTransactions => vec![(candidate.large, ReportableReason::LargeTransaction)],
+            RegulatoryReportType::SuspiciousActivity => vec![
+                (candidate.duplicate, ReportableReason::DuplicatePayment),
+                (candidate.suspected_fraud_account, ReportableReason::SuspectedFraudAccount),
+                (candidate.structuring, ReportableReason::Structuring),
+            ],
+        };
+        let reasons: Vec<ReportableReason> = rules
+            .into_iter()
+            .filter_map(|(matched, reason)| matched.then_some(reason))
+            .collect();
+        if reasons.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            payment_id: candidate.payment_id,
+            reference: candidate.reference,
+            from_account_id: candidate.from_account_id,
+            to_account_id: candidate.to_account_id,
+            amount: candidate.amount,
+            currency: candidate.currency,
+            payment_method: candidate.payment_method,
+            occurred_at: candidate.occurred_at,
+            reasons,
+        })
+    }
+}
+
+/// A report's heading, written into the file
+pub struct ReportHeader<'a> {
+    pub jurisdiction: &'a str,
+    pub report_type: RegulatoryReportType,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Render a report in a jurisdiction's format
+pub fn render(
+    format: RegulatoryReportFormat,
+    header: &ReportHeader<'_>,
+    transactions: &[ReportableTransaction],
+) -> String {
+    match format {
+        RegulatoryReportFormat::Csv => render_csv(header, transactions),
+        RegulatoryReportFormat::Xml => render_xml(header, transactions),
+    }
+}
+
+fn reasons(transaction: &ReportableTransaction) -> Vec<&'static str> {
+    transaction.reasons.iter().map(ReportableReason::as_str).collect()
+}
+
+fn render_csv(header: &ReportHeader<'_>, transactions: &[ReportableTransaction]) -> String {
+    let mut csv = String::from(
+        "jurisdiction,report_type,payment_id,reference,occurred_at,amount,currency,payment_method,from_account_id,to_account_id,reasons\n",
+    );
+    for transaction in transactions {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{}\n",
+            csv_field(header.jurisdiction),
+            header.report_type.as_str(),
+            transaction.payment_id,
+            csv_field(&transaction.reference),
+            transaction.occurred_at.to_rfc3339(),
+            transaction.amount,
+            csv_field(&transaction.currency),
+            csv_field(&transaction.payment_method),
+            transaction.from_account_id,
+            transaction.to_account_id.map(|id| id.to_string()).unwrap_or_default(),
+            reasons(transaction).join(";")
+        ));
+    }
+    csv
+}
+
+fn render_xml(header: &ReportHeader<'_>, transactions: &[ReportableTransaction]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<RegulatoryReport jurisdiction=\"{}\" type=\"{}\" periodStart=\"{}\" periodEnd=\"{}\" generatedAt=\"{}\" count=\"{}\">\n",
+        xml_escape(header.jurisdiction),
+        header.report_type.as_str(),
+        header.period_start,
+        header.period_end,
+        header.generated_at.to_rfc3339(),
+        transactions.len()
+    ));
+    for transaction in transactions {
+        xml.push_str(&format!(
+            "  <Transaction id=\"{}\" reference=\"{}\">\n",
+            transaction.payment_id,
+            xml_escape(&transaction.reference)
+        ));
+        xml.push_str(&format!("    <OccurredAt>{}</OccurredAt>\n", transaction.occurred_at.to_rfc3339()));
+        xml.push_str(&format!(
+            "    <Amount currency=\"{}\">{}</Amount>\n",
+            xml_escape(&transaction.currency),
+            transaction.amount
+        ));
+        xml.push_str(&format!("    <PaymentMethod>{}</PaymentMethod>\n", xml_escape(&transaction.payment_method)));
+        xml.push_str(&format!("    <FromAccount>{}</FromAccount>\n", transaction.from_account_id));
+        if let Some(to_account_id) = transaction.to_account_id {
+            xml.push_str(&format!("    <ToAccount>{}</ToAccount>\n", to_account_id));
+        }
+        xml.push_str("    <Reasons>\n");
+        for reason in reasons(transaction) {
+            xml.push_str(&format!("      <Reason>{}</Reason>\n", reason));
+        }
+        xml.push_str("    </Reasons>\n  </Transaction>\n");
+    }
+    xml.push_str("</RegulatoryReport>\n");
+    xml
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// A generated regulatory report and its submission status
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct RegulatoryReport {
+    pub id: Uuid,
+    pub jurisdiction: String,
+    pub report_type: RegulatoryReportType,
+    pub format: RegulatoryReportFormat,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub transaction_count: i32,
+    /// The rendered file; downloaded separately
+    #[serde(skip)]
+    pub content: String,
+    pub status: SubmissionStatus,
+    pub submission_reference: Option<String>,
+    pub status_note: Option<String>,
+    pub generated_by: Uuid,
+    pub generated_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RegulatoryReport {
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}-{}-{}-{}.{}",
+            self.jurisdiction.to_lowercase(),
+            self.report_type.as_str(),
+            self.period_start,
+            self.period_end,
+            self.format.extension()
+        )
+    }
+}
+
+/// Query parameters for previewing reportable payments
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReportableQuery {
+    pub jurisdiction: String,
+    pub report_type: RegulatoryReportType,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
+
+/// Query parameters for listing reports
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RegulatoryReportQuery {
+    pub jurisdiction: Option<String>,
+    pub status: Option<SubmissionStatus>,
+}
+
+/// Generate a report of a jurisdiction's reportable payments over a period
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct GenerateRegulatoryReportRequest {
+    #[validate(length(min = 1, max = 10))]
+    pub jurisdiction: String,
+    pub report_type: RegulatoryReportType,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
+
+/// Record a report's submission, or the regulator's response to it
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateSubmissionRequest {
+    pub status: SubmissionStatus,
+    /// The regulator's reference for the filing
+    #[validate(length(min = 1, max = 100))]
+    pub submission_reference: Option<String>,
+    #[validate(length(max = 1000))]
+    pub note: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn candidate() -> ReportCandidate {
+        ReportCandidate {
+            payment_id: Uuid::nil(),
+            reference: "PAY_<1>".to_string(),
+            from_account_id: Uuid::nil(),
+            to_account_id: None,
+            amount: 1_500_000,
+            currency: "USD".to_string(),
+            payment_method: "bank_transfer".to_string(),
+            occurred_at: Utc.with_ymd_and_hms(2026, 10, 1, 9, 30, 0).unwrap(),
+            large: true,
+            duplicate: false,
+            suspected_fraud_account: true,
+            structuring: false,
+        }
+    }
+
+    #[test]
+    fn parses_jurisdictions() {
+        let jurisdictions = Jurisdiction::parse_list("us:usd:XML:1000000, GB:GBP:csv:1000000").unwrap();
+        assert_eq!(jurisdictions.len(), 2);
+        assert_eq!(jurisdictions[0].code, "US");
+        assert_eq!(jurisdictions[0].format, RegulatoryReportFormat::Xml);
+        assert_eq!(jurisdictions[1].large_transaction_threshold, 1_000_000);
+        assert!(Jurisdiction::parse_list("US:USD:pdf:1000000").is_err());
+        assert!(Jurisdiction::parse_list("US:USD:csv").is_err());
+    }
+
+    #[test]
+    fn each_report_type_lists_its_own_reasons() {
+        let large = ReportableTransaction::from_candidate(candidate(), RegulatoryReportType::LargeTransactions).unwrap();
+        assert_eq!(large.reasons, vec![ReportableReason::LargeTransaction]);
+        let suspicious =
+            ReportableTransaction::from_candidate(candidate(), RegulatoryReportType::SuspiciousActivity).unwrap();
+        assert_eq!(suspicious.reasons, vec![ReportableReason::SuspectedFraudAccount]);
+
+        let unflagged = ReportCandidate {
+            suspected_fraud_account: false,
+            ..candidate()
+        };
+        assert!(ReportableTransaction::from_candidate(unflagged, RegulatoryReportType::SuspiciousActivity).is_none());
+    }
+
+    #[test]
+    fn renders_escaped_files() {
+        let header = ReportHeader {
+            jurisdiction: "US",
+            report_type: RegulatoryReportType::SuspiciousActivity,
+            period_start: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2026, 10, 31).unwrap(),
+            generated_at: Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap(),
+        };
+        let transactions = vec![ReportableTransaction::from_candidate(
+            ReportCandidate {
+                reference: "PAY,\"1\"".to_string(),
+                ..candidate()
+            },
+            RegulatoryReportType::SuspiciousActivity,
+        )
+        .unwrap()];
+
+        let csv = render(RegulatoryReportFormat::Csv, &header, &transactions);
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.contains(",\"PAY,\"\"1\"\"\",2026-10-01T09:30:00+00:00,1500000,USD,"));
+        assert!(csv.ends_with(",suspected_fraud_account\n"));
+
+        let xml = render(RegulatoryReportFormat::Xml, &header, &transactions);
+        assert!(xml.contains("type=\"suspicious_activity\""));
+        assert!(xml.contains("count=\"1\""));
+        assert!(xml.contains("reference=\"PAY,&quot;1&quot;\""));
+        assert!(xml.contains("<Reason>suspected_fraud_account</Reason>"));
+        assert!(!xml.contains("<ToAccount>"));
+    }
+
+    #[test]
+    fn submissions_move_forward_only() {
+        assert!(SubmissionStatus::Generated.can_move_to(SubmissionStatus::Submitted));
+        assert!(SubmissionStatus::Submitted.can_move_to(SubmissionStatus::Rejected));
+        assert!(!SubmissionStatus::Generated.can_move_to(SubmissionStatus::Acknowledged));
+        assert!(!SubmissionStatus::Acknowledged.can_move_to(SubmissionStatus::Submitted));
+    }
+}
@@ -0,0 +1,154 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use super::model::{RegulatoryReport, ReportCandidate, SubmissionStatus};
+
+const REPORT_COLUMNS: &str = "id, jurisdiction, report_type, format, period_start, period_end, transaction_count,
+    content, status, submission_reference, status_note, generated_by, generated_at, submitted_at, updated_at";
+
+/// Payments under the threshold a payer must make within a day of each
+/// other, together reaching it, to be flagged as structuring
+const STRUCTURING_MIN_PAYMENTS: i64 = 3;
+
+pub struct RegulatoryReportRepository {
+    pool: PgPool,
+}
+
+impl RegulatoryReportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Executed payments in a currency over `[from, until)`, with the rules
+    /// each matches. Payments count from when they executed.
+    pub async fn find_candidates(
+        &self,
+        currency: &str,
+        large_transaction_threshold: i64,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> AppResult<Vec<ReportCandidate>> {
+        let candidates = sqlx::query_as::<_, ReportCandidate>(
+            "SELECT p.id AS payment_id, p.reference, p.from_account_id, p.to_account_id, p.amount, p.currency,
+                    p.payment_method::TEXT AS payment_method,
+                    COALESCE(p.executed_at, p.created_at) AS occurred_at,
+                    p.amount >= $2 AS large,
+                    p.duplicate_of IS NOT NULL AS duplicate,
+                    COALESCE(a.freeze_reason = 'suspected_fraud', FALSE) AS suspected_fraud_account,
+                    (p.amount < $2 AND (
+                        SELECT COUNT(*) >= $5 AND COALESCE(SUM(q.amount), 0) >= $2
+                        FROM payments q
+                        WHERE q.from_account_id = p.from_account_id
+                          AND q.currency = p.currency
+                          AND q.amount < $2
+                          AND q.status IN ('pending', 'processing', 'completed')
+                          AND COALESCE(q.executed_at, q.created_at)
+                              BETWEEN COALESCE(p.executed_at, p.created_at) - INTERVAL '1 day'
+                                  AND COALESCE(p.executed_at, p.created_at) + INTERVAL '1 day'
+                    )) AS structuring
+             FROM payments p
+             LEFT JOIN accounts a ON a.id = p.from_account_id
+             WHERE p.currency = $1
+               AND p.status IN ('pending', 'processing', 'completed')
+               AND COALESCE(p.executed_at, p.created_at) >= $3
+               AND COALESCE(p.executed_at, p.created_at) < $4
+             ORDER BY occurred_at, p.id",
+        )
+        .bind(currency)
+        .bind(large_transaction_threshold)
+        .bind(from)
+        .bind(until)
+        .bind(STRUCTURING_MIN_PAYMENTS)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    pub async fn create(&self, report: &RegulatoryReport) -> AppResult<RegulatoryReport> {
+        let created = sqlx::query_as::<_, RegulatoryReport>(&format!(
+            "INSERT INTO regulatory_reports (id, jurisdiction, report_type, format, period_start, period_end,
+                 transaction_count, content, status, generated_by, generated_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)
+             RETURNING {REPORT_COLUMNS}"
+        ))
+        .bind(report.id)
+        .bind(&report.jurisdiction)
+        .bind(report.report_type)
+        .bind(report.format)
+        .bind(report.period_start)
+        .bind(report.period_end)
+        .bind(report.transaction_count)
+        .bind(&report.content)
+        .bind(report.status)
+        .bind(report.generated_by)
+        .bind(report.generated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn find(&self, id: Uuid) -> AppResult<Option<RegulatoryReport>> {
+        let report = sqlx::query_as::<_, RegulatoryReport>(&format!(
+            "SELECT {REPORT_COLUMNS} FROM regulatory_reports WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(report)
+    }
+
+    /// Reports, most recent first
+    pub async fn list(
+        &self,
+        jurisdiction: Option<&str>,
+        status: Option<SubmissionStatus>,
+    ) -> AppResult<Vec<RegulatoryReport>> {
+        let reports = sqlx::query_as::<_, RegulatoryReport>(&format!(
+            "SELECT {REPORT_COLUMNS} FROM regulatory_reports
+             WHERE ($1::VARCHAR IS NULL OR jurisdiction = $1)
+               AND ($2::regulatory_submission_status IS NULL OR status = $2)
+             ORDER BY generated_at DESC"
+        ))
+        .bind(jurisdiction)
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reports)
+    }
+
+    /// Move a report on from `current` to `status`. Returns `None` if its
+    /// status changed in the meantime.
+    pub async fn update_status(
+        &self,
+        id: Uuid,
+        current: SubmissionStatus,
+        status: SubmissionStatus,
+        submission_reference: Option<&str>,
+        note: Option<&str>,
+    ) -> AppResult<Option<RegulatoryReport>> {
+        let report = sqlx::query_as::<_, RegulatoryReport>(&format!(
+            "UPDATE regulatory_reports SET
+                 status = $1,
+                 submission_reference = COALESCE($2, submission_reference),
+                 status_note = $3,
+                 submitted_at = CASE WHEN $1 = 'submitted' THEN NOW() ELSE submitted_at END,
+                 updated_at = NOW()
+             WHERE id = $4 AND status = $5
+             RETURNING {REPORT_COLUMNS}"
+        ))
+        .bind(status)
+        .bind(submission_reference)
+        .bind(note)
+        .bind(id)
+        .bind(current)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,200 @@
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use super::model::{
+    render, GenerateRegulatoryReportRequest, Jurisdiction, RegulatoryReport, RegulatoryReportSettings,
+    RegulatoryReportType, ReportHeader, ReportableTransaction, SubmissionStatus, UpdateSubmissionRequest,
+};
+use super::repository::RegulatoryReportRepository;
+
+/// Longest period one report may cover
+const MAX_PERIOD_DAYS: i64 = 366;
+
+pub struct RegulatoryReportService {
+    repository: RegulatoryReportRepository,
+    settings: RegulatoryReportSettings,
+    audit_logger: AuditLogger,
+}
+
+impl RegulatoryReportService {
+    pub fn new(
+        repository: RegulatoryReportRepository,
+        settings: RegulatoryReportSettings,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            settings,
+            audit_logger,
+        }
+    }
+
+    pub fn jurisdictions(&self) -> &[Jurisdiction] {
+        &self.settings.jurisdictions
+    }
+
+    /// Payments a report of the jurisdiction over the period would list
+    pub async fn reportable(
+        &self,
+        jurisdiction: &str,
+        report_type: RegulatoryReportType,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> AppResult<Vec<ReportableTransaction>> {
+        let jurisdiction = self.settings.jurisdiction(jurisdiction)?;
+        if period_start > period_end {
+            return Err(AppError::Validation("period_start must not be after period_end".to_string()));
+        }
+        if period_end - period_start >= Duration::days(MAX_PERIOD_DAYS) {
+            return Err(AppError::Validation(format!(
+                "A report can cover at most {} days",
+                MAX_PERIOD_DAYS
+            )));
+        }
+
+        let day_start = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc();
+        let candidates = self
+            .repository
+            .find_candidates(
+                &jurisdiction.currency,
+                jurisdiction.large_transaction_threshold,
+                day_start(period_start),
+                day_start(period_end + Duration::days(1)),
+            )
+            .await?;
+        Ok(candidates
+            .into_iter()
+            .filter_map(|candidate| ReportableTransaction::from_candidate(candidate, report_type))
+            .collect())
+    }
+
+    /// Render the reportable payments of a period in the jurisdiction's
+    /// format and keep the file for submission
+    pub async fn generate(
+        &self,
+        request: GenerateRegulatoryReportRequest,
+        actor_id: Uuid,
+    ) -> AppResult<RegulatoryReport> {
+        let transactions = self
+            .reportable(&request.jurisdiction, request.report_type, request.period_start, request.period_end)
+            .await?;
+        let jurisdiction = self.settings.jurisdiction(&request.jurisdiction)?;
+
+        let now = Utc::now();
+        let header = ReportHeader {
+            jurisdiction: &jurisdiction.code,
+            report_type: request.report_type,
+            period_start: request.period_start,
+            period_end: request.period_end,
+            generated_at: now,
+        };
+        let report = RegulatoryReport {
+            id: Uuid::new_v4(),
+            jurisdiction: jurisdiction.code.clone(),
+            report_type: request.report_type,
+            format: jurisdiction.format,
+            period_start: request.period_start,
+            period_end: request.period_end,
+            transaction_count: transactions.len() as i32,
+            content: render(jurisdiction.format, &header, &transactions),
+            status: SubmissionStatus::Generated,
+            submission_reference: None,
+            status_note: None,
+            generated_by: actor_id,
+            generated_at: now,
+            submitted_at: None,
+            updated_at: now,
+        };
+        let created = self.repository.create(&report).await?;
+
+        let event = AuditEvent::new(AuditEventType::RegulatoryReportGenerated)
+            .user_id(actor_id)
+            .resource(format!("regulatory_report:{}", created.id))
+            .action("generate".to_string())
+            .metadata("jurisdiction".to_string(), serde_json::json!(created.jurisdiction))
+            .metadata("report_type".to_string(), serde_json::json!(created.report_type))
+            .metadata("transaction_count".to_string(), serde_json::json!(created.transaction_count))
+            .compliance_tag("REGULATORY_REPORTING".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(created)
+    }
+
+    pub async fn list(
+        &self,
+        jurisdiction: Option<String>,
+        status: Option<SubmissionStatus>,
+    ) -> AppResult<Vec<RegulatoryReport>> {
+        let jurisdiction = jurisdiction.map(|code| code.to_uppercase());
+        self.repository.list(jurisdiction.as_deref(), status).await
+    }
+
+    pub async fn get(&self, id: Uuid) -> AppResult<RegulatoryReport> {
+        self.repository
+            .find(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Regulatory report not found".to_string()))
+    }
+
+    /// The report's file. Every download is audited, as the file holds
+    /// customer data.
+    pub async fn download(&self, id: Uuid, actor_id: Uuid) -> AppResult<RegulatoryReport> {
+        let report = self.get(id).await?;
+
+        let event = AuditEvent::new(AuditEventType::RegulatoryReportDownloaded)
+            .user_id(actor_id)
+            .resource(format!("regulatory_report:{}", report.id))
+            .action("download".to_string())
+            .compliance_tag("REGULATORY_REPORTING".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(report)
+    }
+
+    /// Record the report's submission to the regulator, or the regulator's
+    /// acknowledgement or rejection of it
+    pub async fn update_submission(
+        &self,
+        id: Uuid,
+        request: UpdateSubmissionRequest,
+        actor_id: Uuid,
+    ) -> AppResult<RegulatoryReport> {
+        let report = self.get(id).await?;
+        if !report.status.can_move_to(request.status) {
+            return Err(AppError::Conflict(format!(
+                "A {:?} report cannot be marked {:?}",
+                report.status, request.status
+            )));
+        }
+        if request.status == SubmissionStatus::Submitted && request.submission_reference.is_none() {
+            return Err(AppError::Validation(
+                "submission_reference is required when submitting a report".to_string(),
+            ));
+        }
+
+        let updated = self
+            .repository
+            .update_status(
+                id,
+                report.status,
+                request.status,
+                request.submission_reference.as_deref(),
+                request.note.as_deref(),
+            )
+            .await?
+            .ok_or_else(|| AppError::Conflict("Report status changed in the meantime".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::RegulatoryReportStatusChanged)
+            .user_id(actor_id)
+            .resource(format!("regulatory_report:{}", updated.id))
+            .action("update_submission".to_string())
+            .metadata("from".to_string(), serde_json::json!(report.status))
+            .metadata("to".to_string(), serde_json::json!(updated.status))
+            .metadata("submission_reference".to_string(), serde_json::json!(updated.submission_reference))
+            .compliance_tag("REGULATORY_REPORTING".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(updated)
+    }
+}
@@ -0,0 +1,225 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::identity::repository::IdentityRepository;
+use crate::income::repository::IncomeRepository;
+use crate::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use crate::shared::types::{PaginatedResponse, PaginationParams};
+use super::model::{
+    FlagVerificationRequest, ReviewDecisionRequest, ReviewEvidenceResponse, ReviewFilter, VerificationReview,
+};
+use super::repository::ReviewRepository;
+use super::service::ReviewService;
+
+fn review_service(state: &AppState) -> ReviewService {
+    ReviewService::new(
+        ReviewRepository::new(state.postgres.clone()),
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        IncomeRepository::new(state.postgres.clone()),
+        state.storage.clone(),
+        KycPolicyService::new(
+            KycRepository::new(state.postgres.clone()),
+            KycLimits::from_config(&state.config),
+            state.audit_logger.clone(),
+        ),
+        state.audit_logger.clone(),
+    )
+}
+
+/// List the review queue, oldest first (reviewers only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/reviews",
+    tag = "reviews",
+    params(ReviewFilter, PaginationParams),
+    responses(
+        (status = 200, description = "Page of reviews", body = PaginatedReviews),
+        (status = 403, description = "Caller lacks the verification review permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_reviews(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(filter): Query<ReviewFilter>,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<PaginatedResponse<VerificationReview>>>> {
+    state
+        .authorize(claims.developer_id, permissions::review_verifications(), ip, "verification_reviews".to_string())
+        .await?;
+
+    let reviews = review_service(&state)
+        .list(filter, claims.developer_id, pagination.page, pagination.limit)
+        .await?;
+    Ok(Json(ApiResponse::success("Reviews retrieved successfully", reviews)))
+}
+
+/// Flag a verification for manual review (reviewers only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/reviews",
+    tag = "reviews",
+    request_body = FlagVerificationRequest,
+    responses(
+        (status = 201, description = "Verification queued for review, or its existing open review", body = VerificationReview),
+        (status = 400, description = "Verification has already been decided"),
+        (status = 403, description = "Caller lacks the verification review permission"),
+        (status = 404, description = "Verification not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn flag_verification(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<FlagVerificationRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<VerificationReview>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(
+            claims.developer_id,
+            permissions::review_verifications(),
+            ip,
+            format!("verification:{}", request.verification_id),
+        )
+        .await?;
+
+    let review = review_service(&state).flag(request, claims.developer_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Verification flagged for review", review)),
+    ))
+}
+
+/// Get a review with the evidence needed to decide it (reviewers only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/reviews/{id}",
+    tag = "reviews",
+    params(("id" = Uuid, Path, description = "Review ID")),
+    responses(
+        (status = 200, description = "Review and its evidence", body = ReviewEvidenceResponse),
+        (status = 403, description = "Caller lacks the verification review permission"),
+        (status = 404, description = "Review not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_review(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<ReviewEvidenceResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::review_verifications(), ip, format!("verification_review:{}", id))
+        .await?;
+
+    let evidence = review_service(&state).get_evidence(id, claims.developer_id).await?;
+    Ok(Json(ApiResponse::success("Review retrieved successfully", evidence)))
+}
+
+/// Claim a pending review (reviewers only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/reviews/{id}/claim",
+    tag = "reviews",
+    params(("id" = Uuid, Path, description = "Review ID")),
+    responses(
+        (status = 200, description = "Review claimed", body = VerificationReview),
+        (status = 403, description = "Caller lacks the verification review permission"),
+        (status = 404, description = "Review not found"),
+        (status = 409, description = "Review is claimed by another reviewer or already decided")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn claim_review(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<VerificationReview>>> {
+    state
+        .authorize(claims.developer_id, permissions::review_verifications(), ip, format!("verification_review:{}", id))
+        .await?;
+
+    let review = review_service(&state).claim(id, claims.developer_id).await?;
+    Ok(Json(ApiResponse::success("Review claimed successfully", review)))
+}
+
+/// Return a claimed review to the queue (reviewers only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/reviews/{id}/release",
+    tag = "reviews",
+    params(("id" = Uuid, Path, description = "Review ID")),
+    responses(
+        (status = 200, description = "Review released", body = VerificationReview),
+        (status = 403, description = "Caller lacks the verification review permission"),
+        (status = 404, description = "Review not found"),
+        (status = 409, description = "Review is not claimed by the caller")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn release_review(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<VerificationReview>>> {
+    state
+        .authorize(claims.developer_id, permissions::review_verifications(), ip, format!("verification_review:{}", id))
+        .await?;
+
+    let review = review_service(&state).release(id, claims.developer_id).await?;
+    Ok(Json(ApiResponse::success("Review released successfully", review)))
+}
+
+/// Approve or reject a claimed review (reviewers only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/reviews/{id}/decision",
+    tag = "reviews",
+    params(("id" = Uuid, Path, description = "Review ID")),
+    request_body = ReviewDecisionRequest,
+    responses(
+        (status = 200, description = "Review decided and verification updated", body = VerificationReview),
+        (status = 403, description = "Caller lacks the verification review permission"),
+        (status = 404, description = "Review not found"),
+        (status = 409, description = "Review is not claimed by the caller or already decided")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn decide_review(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<ReviewDecisionRequest>,
+) -> AppResult<Json<ApiResponse<VerificationReview>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::review_verifications(), ip, format!("verification_review:{}", id))
+        .await?;
+
+    let review = review_service(&state).decide(id, claims.developer_id, request).await?;
+    Ok(Json(ApiResponse::success("Review decided successfully", review)))
+}
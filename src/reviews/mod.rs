@@ -0,0 +1,16 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(controller::list_reviews).post(controller::flag_verification))
+        .route("/:id", get(controller::get_review))
+        .route("/:id/claim", post(controller::claim_review))
+        .route("/:id/release", post(controller::release_review))
+        .route("/:id/decision", post(controller::decide_review))
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::identity::model::{IdentityVerification, VerificationScreening, VerificationStatus};
+use crate::income::model::IncomeVerificationResponse;
+use crate::shared::types::UserId;
+
+/// Kind of verification a review decides
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "verification_kind", rename_all = "snake_case")]
+pub enum VerificationKind {
+    Identity,
+    Income,
+}
+
+/// Where a review is in the queue
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "verification_review_status", rename_all = "snake_case")]
+pub enum ReviewStatus {
+    /// Waiting for a reviewer to claim it
+    Pending,
+    Claimed,
+    Approved,
+    Rejected,
+}
+
+impl ReviewStatus {
+    pub fn is_open(&self) -> bool {
+        matches!(self, ReviewStatus::Pending | ReviewStatus::Claimed)
+    }
+}
+
+/// A verification flagged for manual review
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct VerificationReview {
+    pub id: Uuid,
+    pub verification_kind: VerificationKind,
+    pub verification_id: Uuid,
+    pub subject_user_id: UserId,
+    pub status: ReviewStatus,
+    pub flag_reason: String,
+    /// Who flagged the verification; absent when it was flagged automatically
+    pub flagged_by: Option<Uuid>,
+    pub claimed_by: Option<Uuid>,
+    pub claimed_at: Option<DateTime<Utc>>,
+    pub decided_by: Option<Uuid>,
+    pub decision_reason: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Query parameters for listing the review queue
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReviewFilter {
+    /// Defaults to open (pending and claimed) reviews
+    pub status: Option<ReviewStatus>,
+    pub kind: Option<VerificationKind>,
+    /// Only reviews claimed by the caller
+    #[serde(default)]
+    pub mine: bool,
+}
+
+/// Flag a verification for manual review
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct FlagVerificationRequest {
+    pub verification_kind: VerificationKind,
+    pub verification_id: Uuid,
+    #[validate(length(min = 1, max = 1000))]
+    pub reason: String,
+}
+
+/// A reviewer's decision on a claimed review
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Approve,
+    Reject,
+}
+
+/// Approve or reject a claimed review
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReviewDecisionRequest {
+    pub decision: ReviewDecision,
+    #[validate(length(min = 1, max = 2000))]
+    pub reason: String,
+}
+
+/// Identity verification details shown to a reviewer. The document number
+/// is masked to its last four characters.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IdentityEvidence {
+    pub status: VerificationStatus,
+    pub verification_type: String,
+    pub document_type: Option<String>,
+    pub document_number_masked: Option<String>,
+    pub provider: Option<String>,
+    pub provider_reference: Option<String>,
+    pub verification_data: Option<serde_json::Value>,
+    /// PEP and adverse-media matches found for the person
+    pub screening: Option<VerificationScreening>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<IdentityVerification> for IdentityEvidence {
+    fn from(verification: IdentityVerification) -> Self {
+        Self {
+            status: verification.status,
+            verification_type: verification.verification_type,
+            document_type: verification.document_type,
+            document_number_masked: verification.document_number.as_deref().map(mask_document_number),
+            provider: verification.provider,
+            provider_reference: verification.provider_reference,
+            verification_data: verification.verification_data,
+            screening: None,
+            created_at: verification.created_at,
+        }
+    }
+}
+
+/// A temporary link to an uploaded document
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EvidenceDocumentLink {
+    pub document_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub download_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Income verification details shown to a reviewer, with links to its documents
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IncomeEvidence {
+    pub verification: IncomeVerificationResponse,
+    pub document_links: Vec<EvidenceDocumentLink>,
+}
+
+/// A review together with the evidence needed to decide it
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewEvidenceResponse {
+    pub review: VerificationReview,
+    pub subject_name: Option<String>,
+    pub identity: Option<IdentityEvidence>,
+    pub income: Option<IncomeEvidence>,
+    /// Earlier decided reviews of the same verification, newest first
+    pub history: Vec<VerificationReview>,
+}
+
+/// All but the last four characters replaced with `*`. Numbers of four
+/// characters or fewer are masked entirely.
+pub fn mask_document_number(number: &str) -> String {
+    let chars: Vec<char> = number.chars().collect();
+    let masked = if chars.len() > 4 { chars.len() - 4 } else { chars.len() };
+    chars
+        .iter()
+        .enumerate()
+        .map(|(index, c)| if index < masked { '*' } else { *c })
+        .collect()
+}
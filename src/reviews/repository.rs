@@ -0,0 +1,248 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::{AppError, AppResult};
+use super::model::{ReviewDecision, ReviewFilter, VerificationKind, VerificationReview};
+
+const REVIEW_COLUMNS: &str = "id, verification_kind, verification_id, subject_user_id, status, flag_reason,
+    flagged_by, claimed_by, claimed_at, decided_by, decision_reason, decided_at, created_at, updated_at";
+
+const REVIEW_FILTER: &str = "(($1::verification_review_status IS NULL AND status IN ('pending', 'claimed')) OR status = $1)
+    AND ($2::verification_kind IS NULL OR verification_kind = $2)
+    AND ($3::UUID IS NULL OR claimed_by = $3)";
+
+#[derive(Clone)]
+pub struct ReviewRepository {
+    pool: PgPool,
+}
+
+impl ReviewRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add a review to the queue. Returns `None` if the verification already
+    /// has an open review.
+    pub async fn enqueue(&self, review: &VerificationReview) -> AppResult<Option<VerificationReview>> {
+        let created = sqlx::query_as::<_, VerificationReview>(&format!(
+            "INSERT INTO verification_reviews ({REVIEW_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+             ON CONFLICT (verification_kind, verification_id) WHERE status IN ('pending', 'claimed') DO NOTHING
+             RETURNING {REVIEW_COLUMNS}"
+        ))
+        .bind(review.id)
+        .bind(review.verification_kind)
+        .bind(review.verification_id)
+        .bind(review.subject_user_id)
+        .bind(review.status)
+        .bind(&review.flag_reason)
+        .bind(review.flagged_by)
+        .bind(review.claimed_by)
+        .bind(review.claimed_at)
+        .bind(review.decided_by)
+        .bind(&review.decision_reason)
+        .bind(review.decided_at)
+        .bind(review.created_at)
+        .bind(review.updated_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<VerificationReview>> {
+        let review = sqlx::query_as::<_, VerificationReview>(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM verification_reviews WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(review)
+    }
+
+    /// The pending or claimed review of a verification, if any
+    pub async fn find_open(
+        &self,
+        kind: VerificationKind,
+        verification_id: Uuid,
+    ) -> AppResult<Option<VerificationReview>> {
+        let review = sqlx::query_as::<_, VerificationReview>(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM verification_reviews
+             WHERE verification_kind = $1 AND verification_id = $2 AND status IN ('pending', 'claimed')"
+        ))
+        .bind(kind)
+        .bind(verification_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(review)
+    }
+
+    /// Decided reviews of a verification, newest first
+    pub async fn find_decided(&self, kind: VerificationKind, verification_id: Uuid) -> AppResult<Vec<VerificationReview>> {
+        let reviews = sqlx::query_as::<_, VerificationReview>(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM verification_reviews
+             WHERE verification_kind = $1 AND verification_id = $2 AND status IN ('approved', 'rejected')
+             ORDER BY decided_at DESC"
+        ))
+        .bind(kind)
+        .bind(verification_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reviews)
+    }
+
+    /// Reviews matching the filter, oldest first so the queue is worked in order
+    pub async fn find_reviews(
+        &self,
+        filter: &ReviewFilter,
+        claimed_by: Option<Uuid>,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<Vec<VerificationReview>> {
+        let offset = (page.max(1) - 1) * limit;
+
+        let reviews = sqlx::query_as::<_, VerificationReview>(&format!(
+            "SELECT {REVIEW_COLUMNS} FROM verification_reviews
+             WHERE {REVIEW_FILTER}
+             ORDER BY created_at, id
+             LIMIT $4 OFFSET $5"
+        ))
+        .bind(filter.status)
+        .bind(filter.kind)
+        .bind(claimed_by)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reviews)
+    }
+
+    pub async fn count_reviews(&self, filter: &ReviewFilter, claimed_by: Option<Uuid>) -> AppResult<i64> {
+        let count: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM verification_reviews WHERE {REVIEW_FILTER}"
+        ))
+        .bind(filter.status)
+        .bind(filter.kind)
+        .bind(claimed_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Claim a pending review. Returns `None` if it is not pending.
+    pub async fn claim(&self, id: Uuid, reviewer_id: Uuid) -> AppResult<Option<VerificationReview>> {
+        let claimed = sqlx::query_as::<_, VerificationReview>(&format!(
+            "UPDATE verification_reviews
+             SET status = 'claimed', claimed_by = $2, claimed_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = 'pending'
+             RETURNING {REVIEW_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(reviewer_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(claimed)
+    }
+
+    /// Return a claimed review to the queue. Returns `None` unless the
+    /// reviewer holds the claim.
+    pub async fn release(&self, id: Uuid, reviewer_id: Uuid) -> AppResult<Option<VerificationReview>> {
+        let released = sqlx::query_as::<_, VerificationReview>(&format!(
+            "UPDATE verification_reviews
+             SET status = 'pending', claimed_by = NULL, claimed_at = NULL, updated_at = NOW()
+             WHERE id = $1 AND status = 'claimed' AND claimed_by = $2
+             RETURNING {REVIEW_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(reviewer_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(released)
+    }
+
+    /// Record the reviewer's decision and apply it to the verification:
+    /// approval completes it, rejection fails it. Approving an income
+    /// verification also confirms the fields extracted from its documents.
+    /// Returns `None` unless the reviewer holds the claim.
+    pub async fn decide(
+        &self,
+        id: Uuid,
+        reviewer_id: Uuid,
+        decision: ReviewDecision,
+        reason: &str,
+    ) -> AppResult<Option<VerificationReview>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let (review_status, verification_status) = match decision {
+            ReviewDecision::Approve => ("approved", "completed"),
+            ReviewDecision::Reject => ("rejected", "failed"),
+        };
+
+        let Some(decided) = sqlx::query_as::<_, VerificationReview>(&format!(
+            "UPDATE verification_reviews
+             SET status = $3::verification_review_status, decided_by = $2, decision_reason = $4,
+                 decided_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = 'claimed' AND claimed_by = $2
+             RETURNING {REVIEW_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(reviewer_id)
+        .bind(review_status)
+        .bind(reason)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let updated = match decided.verification_kind {
+            // A completed verification is open to review while screening
+            // matches wait for a reviewer; approving dismisses them
+            VerificationKind::Identity => sqlx::query(
+                "UPDATE identity_verifications
+                 SET status = $1::verification_status,
+                     completed_at = CASE WHEN $1 = 'completed' THEN COALESCE(completed_at, NOW()) ELSE completed_at END,
+                     screening_status = CASE
+                         WHEN $1 = 'completed' AND screening_status = 'potential_match' THEN 'dismissed'
+                         ELSE screening_status
+                     END,
+                     updated_at = NOW()
+                 WHERE id = $2
+                   AND (status IN ('pending', 'in_progress')
+                        OR (status = 'completed' AND screening_status = 'potential_match'))",
+            ),
+            VerificationKind::Income => sqlx::query(
+                "UPDATE income_verifications
+                 SET status = $1::income_verification_status,
+                     completed_at = CASE WHEN $1 = 'completed' THEN NOW() ELSE completed_at END,
+                     verification_data = CASE
+                         WHEN $1 = 'completed' AND verification_data ? 'extracted_fields'
+                         THEN verification_data || jsonb_build_object('extracted_fields_confirmed', true)
+                         ELSE verification_data
+                     END,
+                     updated_at = NOW()
+                 WHERE id = $2 AND status IN ('pending', 'in_progress')",
+            ),
+        }
+        .bind(verification_status)
+        .bind(decided.verification_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::Conflict(
+                "Verification has already been decided".to_string(),
+            ));
+        }
+
+        tx.commit().await?;
+        Ok(Some(decided))
+    }
+}
@@ -0,0 +1,364 @@
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::storage::Storage;
+use crate::identity::model::{IdentityVerification, VerificationStatus};
+use crate::identity::repository::IdentityRepository;
+use crate::income::model::{
+    IncomeDocumentResponse, IncomeVerification, IncomeVerificationResponse, IncomeVerificationStatus,
+};
+use crate::income::repository::IncomeRepository;
+use crate::kyc::service::KycPolicyService;
+use crate::shared::traits::Repository;
+use crate::shared::types::{PaginatedResponse, UserId};
+use super::model::{
+    EvidenceDocumentLink, FlagVerificationRequest, IdentityEvidence, IncomeEvidence, ReviewDecision,
+    ReviewDecisionRequest, ReviewEvidenceResponse, ReviewFilter, ReviewStatus, VerificationKind,
+    VerificationReview,
+};
+use super::repository::ReviewRepository;
+
+/// How long links to evidence documents stay valid
+const EVIDENCE_LINK_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Puts verifications in front of reviewers. Used by the review API and by
+/// modules that flag verifications on their own, such as document uploads.
+#[derive(Clone)]
+pub struct ReviewQueue {
+    repository: ReviewRepository,
+    audit_logger: AuditLogger,
+}
+
+impl ReviewQueue {
+    pub fn new(repository: ReviewRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            audit_logger,
+        }
+    }
+
+    /// Queue a verification for review. A verification already in the queue
+    /// keeps its existing review, which is returned instead.
+    pub async fn flag(
+        &self,
+        kind: VerificationKind,
+        verification_id: Uuid,
+        subject_user_id: UserId,
+        reason: String,
+        flagged_by: Option<Uuid>,
+    ) -> AppResult<VerificationReview> {
+        let now = Utc::now();
+        let review = VerificationReview {
+            id: Uuid::new_v4(),
+            verification_kind: kind,
+            verification_id,
+            subject_user_id,
+            status: ReviewStatus::Pending,
+            flag_reason: reason,
+            flagged_by,
+            claimed_by: None,
+            claimed_at: None,
+            decided_by: None,
+            decision_reason: None,
+            decided_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let Some(created) = self.repository.enqueue(&review).await? else {
+            return self
+                .repository
+                .find_open(kind, verification_id)
+                .await?
+                .ok_or_else(|| AppError::Conflict("Verification review changed concurrently".to_string()));
+        };
+
+        let mut event = AuditEvent::new(AuditEventType::VerificationFlaggedForReview)
+            .resource(verification_resource(kind, verification_id))
+            .action("flag_for_review".to_string())
+            .metadata("review_id".to_string(), serde_json::json!(created.id))
+            .metadata("reason".to_string(), serde_json::json!(created.flag_reason))
+            .metadata("automatic".to_string(), serde_json::json!(flagged_by.is_none()))
+            .compliance_tag("KYC".to_string());
+        if let Some(flagged_by) = flagged_by {
+            event = event.user_id(flagged_by);
+        }
+        self.audit_logger.log(event).await;
+
+        Ok(created)
+    }
+}
+
+/// Manual review of flagged identity and income verifications
+pub struct ReviewService {
+    repository: ReviewRepository,
+    queue: ReviewQueue,
+    identity_repository: IdentityRepository,
+    income_repository: IncomeRepository,
+    storage: Arc<dyn Storage>,
+    kyc_policy: KycPolicyService,
+    audit_logger: AuditLogger,
+}
+
+impl ReviewService {
+    pub fn new(
+        repository: ReviewRepository,
+        identity_repository: IdentityRepository,
+        income_repository: IncomeRepository,
+        storage: Arc<dyn Storage>,
+        kyc_policy: KycPolicyService,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            queue: ReviewQueue::new(repository.clone(), audit_logger.clone()),
+            repository,
+            identity_repository,
+            income_repository,
+            storage,
+            kyc_policy,
+            audit_logger,
+        }
+    }
+
+    /// Flag an undecided verification for manual review
+    pub async fn flag(&self, request: FlagVerificationRequest, actor_id: Uuid) -> AppResult<VerificationReview> {
+        let (subject_user_id, decided) = match request.verification_kind {
+            VerificationKind::Identity => {
+                let verification = self.find_identity(request.verification_id).await?;
+                let decided = !matches!(verification.status, VerificationStatus::Pending | VerificationStatus::InProgress);
+                (verification.user_id, decided)
+            }
+            VerificationKind::Income => {
+                let verification = self.find_income(request.verification_id).await?;
+                let decided = !matches!(
+                    verification.status,
+                    IncomeVerificationStatus::Pending | IncomeVerificationStatus::InProgress
+                );
+                (verification.user_id, decided)
+            }
+        };
+        if decided {
+            return Err(AppError::BadRequest("Verification has already been decided".to_string()));
+        }
+
+        self.queue
+            .flag(
+                request.verification_kind,
+                request.verification_id,
+                subject_user_id,
+                request.reason,
+                Some(actor_id),
+            )
+            .await
+    }
+
+    /// Reviews in the queue, oldest first. With `filter.mine`, only reviews
+    /// claimed by the reviewer.
+    pub async fn list(
+        &self,
+        filter: ReviewFilter,
+        reviewer_id: Uuid,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<PaginatedResponse<VerificationReview>> {
+        let limit = limit.clamp(1, 100);
+        let page = page.max(1);
+        let claimed_by = filter.mine.then_some(reviewer_id);
+
+        let reviews = self.repository.find_reviews(&filter, claimed_by, page, limit).await?;
+        let total = self.repository.count_reviews(&filter, claimed_by).await?.max(0) as u64;
+
+        Ok(PaginatedResponse {
+            data: reviews,
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit as u64) as u32,
+        })
+    }
+
+    /// Claim a pending review so no other reviewer works on it. Claiming a
+    /// review the reviewer already holds is a no-op.
+    pub async fn claim(&self, review_id: Uuid, reviewer_id: Uuid) -> AppResult<VerificationReview> {
+        let review = self.find_review(review_id).await?;
+        if review.status == ReviewStatus::Claimed && review.claimed_by == Some(reviewer_id) {
+            return Ok(review);
+        }
+
+        let claimed = self.repository.claim(review_id, reviewer_id).await?.ok_or_else(|| {
+            AppError::Conflict(match review.status {
+                ReviewStatus::Claimed => "Review has already been claimed by another reviewer".to_string(),
+                _ => "Review has already been decided".to_string(),
+            })
+        })?;
+
+        self.log_review_event(AuditEventType::VerificationReviewClaimed, "claim_review", &claimed, reviewer_id)
+            .await;
+        Ok(claimed)
+    }
+
+    /// Return a claimed review to the queue
+    pub async fn release(&self, review_id: Uuid, reviewer_id: Uuid) -> AppResult<VerificationReview> {
+        self.find_review(review_id).await?;
+        let released = self
+            .repository
+            .release(review_id, reviewer_id)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Review is not claimed by you".to_string()))?;
+
+        self.log_review_event(AuditEventType::VerificationReviewReleased, "release_review", &released, reviewer_id)
+            .await;
+        Ok(released)
+    }
+
+    /// The review with the verification's details and temporary links to
+    /// its documents. Every view is audited.
+    pub async fn get_evidence(&self, review_id: Uuid, reviewer_id: Uuid) -> AppResult<ReviewEvidenceResponse> {
+        let review = self.find_review(review_id).await?;
+        let subject_name = self.income_repository.find_user_name(review.subject_user_id).await?;
+
+        let (identity, income) = match review.verification_kind {
+            VerificationKind::Identity => {
+                let verification = self.find_identity(review.verification_id).await?;
+                let mut evidence = IdentityEvidence::from(verification);
+                evidence.screening = self
+                    .identity_repository
+                    .find_screening(review.verification_id)
+                    .await?
+                    .filter(|screening| screening.status.is_some());
+                (Some(evidence), None)
+            }
+            VerificationKind::Income => (None, Some(self.income_evidence(review.verification_id).await?)),
+        };
+        let history = self
+            .repository
+            .find_decided(review.verification_kind, review.verification_id)
+            .await?;
+
+        self.log_review_event(AuditEventType::VerificationEvidenceViewed, "view_evidence", &review, reviewer_id)
+            .await;
+
+        Ok(ReviewEvidenceResponse {
+            review,
+            subject_name,
+            identity,
+            income,
+            history,
+        })
+    }
+
+    /// Approve or reject a review the reviewer has claimed, completing or
+    /// failing the verification and refreshing the subject's KYC tier
+    pub async fn decide(
+        &self,
+        review_id: Uuid,
+        reviewer_id: Uuid,
+        request: ReviewDecisionRequest,
+    ) -> AppResult<VerificationReview> {
+        let review = self.find_review(review_id).await?;
+        if !review.status.is_open() {
+            return Err(AppError::Conflict("Review has already been decided".to_string()));
+        }
+        if review.claimed_by != Some(reviewer_id) {
+            return Err(AppError::Conflict("Claim the review before deciding it".to_string()));
+        }
+
+        let decided = self
+            .repository
+            .decide(review_id, reviewer_id, request.decision, &request.reason)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Review is no longer claimed by you".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::VerificationReviewDecided)
+            .severity(match request.decision {
+                ReviewDecision::Approve => AuditSeverity::Info,
+                ReviewDecision::Reject => AuditSeverity::Warning,
+            })
+            .user_id(reviewer_id)
+            .resource(verification_resource(decided.verification_kind, decided.verification_id))
+            .action("decide_review".to_string())
+            .metadata("review_id".to_string(), serde_json::json!(decided.id))
+            .metadata("decision".to_string(), serde_json::json!(request.decision))
+            .metadata("reason".to_string(), serde_json::json!(request.reason))
+            .metadata("subject_user_id".to_string(), serde_json::json!(decided.subject_user_id))
+            .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        // The verification outcome may change the user's KYC tier
+        self.kyc_policy.refresh_user_tier(decided.subject_user_id, reviewer_id).await?;
+
+        Ok(decided)
+    }
+
+    async fn income_evidence(&self, verification_id: Uuid) -> AppResult<IncomeEvidence> {
+        let verification = self.find_income(verification_id).await?;
+        let documents = self.income_repository.find_documents(verification_id).await?;
+
+        let expires_at = Utc::now() + chrono::Duration::from_std(EVIDENCE_LINK_TTL).unwrap_or_default();
+        let mut document_links = Vec::with_capacity(documents.len());
+        for document in &documents {
+            document_links.push(EvidenceDocumentLink {
+                document_id: document.id,
+                file_name: document.file_name.clone(),
+                content_type: document.content_type.clone(),
+                download_url: self.storage.signed_url(&document.storage_key, EVIDENCE_LINK_TTL)?,
+                expires_at,
+            });
+        }
+
+        let mut verification = IncomeVerificationResponse::from(verification);
+        verification.documents = documents.into_iter().map(IncomeDocumentResponse::from).collect();
+        Ok(IncomeEvidence {
+            verification,
+            document_links,
+        })
+    }
+
+    async fn log_review_event(
+        &self,
+        event_type: AuditEventType,
+        action: &str,
+        review: &VerificationReview,
+        reviewer_id: Uuid,
+    ) {
+        let event = AuditEvent::new(event_type)
+            .user_id(reviewer_id)
+            .resource(verification_resource(review.verification_kind, review.verification_id))
+            .action(action.to_string())
+            .metadata("review_id".to_string(), serde_json::json!(review.id))
+            .metadata("subject_user_id".to_string(), serde_json::json!(review.subject_user_id))
+            .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+    }
+
+    async fn find_review(&self, review_id: Uuid) -> AppResult<VerificationReview> {
+        self.repository
+            .find_by_id(review_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Review not found".to_string()))
+    }
+
+    async fn find_identity(&self, verification_id: Uuid) -> AppResult<IdentityVerification> {
+        self.identity_repository
+            .find_by_id(verification_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Identity verification not found".to_string()))
+    }
+
+    async fn find_income(&self, verification_id: Uuid) -> AppResult<IncomeVerification> {
+        self.income_repository
+            .find_by_id(verification_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Income verification not found".to_string()))
+    }
+}
+
+fn verification_resource(kind: VerificationKind, verification_id: Uuid) -> String {
+    match kind {
+        VerificationKind::Identity => format!("identity_verification:{}", verification_id),
+        VerificationKind::Income => format!("income_verification:{}", verification_id),
+    }
+}
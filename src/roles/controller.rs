@@ -0,0 +1,280 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{AssignRoleRequest, CustomRole, CustomRoleRequest, RoleAssignment};
+use super::repository::CustomRoleRepository;
+use super::service::CustomRoleService;
+
+fn custom_role_service(state: &AppState) -> CustomRoleService {
+    CustomRoleService::new(
+        CustomRoleRepository::new(state.postgres.clone()),
+        state.rbac_service.clone(),
+        state.audit_logger.clone(),
+    )
+}
+
+/// List the organization's custom roles (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/roles",
+    tag = "roles",
+    responses(
+        (status = 200, description = "Custom roles", body = [CustomRole]),
+        (status = 403, description = "Caller lacks the role management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_roles(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+) -> AppResult<Json<ApiResponse<Vec<CustomRole>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_roles(), ip, "custom_roles".to_string())
+        .await?;
+
+    let roles = custom_role_service(&state).list_roles(claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Roles retrieved successfully", roles)))
+}
+
+/// Define a custom role (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/roles",
+    tag = "roles",
+    request_body = CustomRoleRequest,
+    responses(
+        (status = 201, description = "Role created", body = CustomRole),
+        (status = 400, description = "Invalid name or permission"),
+        (status = 403, description = "Caller lacks the role management permission or a granted permission"),
+        (status = 409, description = "A role with the name already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_role(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<CustomRoleRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<CustomRole>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_roles(), ip, "custom_roles".to_string())
+        .await?;
+
+    let role = custom_role_service(&state)
+        .create_role(request, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Role created successfully", role)),
+    ))
+}
+
+/// Get a custom role (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/roles/{id}",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    responses(
+        (status = 200, description = "Custom role", body = CustomRole),
+        (status = 403, description = "Caller lacks the role management permission"),
+        (status = 404, description = "Role not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_role(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<CustomRole>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_roles(), ip, format!("custom_role:{}", id))
+        .await?;
+
+    let role = custom_role_service(&state).get_role(id, claims.tenant_id).await?;
+    Ok(Json(ApiResponse::success("Role retrieved successfully", role)))
+}
+
+/// Replace a custom role's name, description and permissions (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/roles/{id}",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    request_body = CustomRoleRequest,
+    responses(
+        (status = 200, description = "Role updated", body = CustomRole),
+        (status = 400, description = "Invalid name or permission"),
+        (status = 403, description = "Caller lacks the role management permission or a granted permission"),
+        (status = 404, description = "Role not found"),
+        (status = 409, description = "A role with the name already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_role(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<CustomRoleRequest>,
+) -> AppResult<Json<ApiResponse<CustomRole>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_roles(), ip, format!("custom_role:{}", id))
+        .await?;
+
+    let role = custom_role_service(&state)
+        .update_role(id, request, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Role updated successfully", role)))
+}
+
+/// Delete a custom role and its assignments (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/roles/{id}",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    responses(
+        (status = 200, description = "Role deleted"),
+        (status = 403, description = "Caller lacks the role management permission"),
+        (status = 404, description = "Role not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_role(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_roles(), ip, format!("custom_role:{}", id))
+        .await?;
+
+    custom_role_service(&state)
+        .delete_role(id, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success_no_data("Role deleted successfully")))
+}
+
+/// List the developers holding a custom role (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/roles/{id}/assignments",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    responses(
+        (status = 200, description = "Role assignments", body = [RoleAssignment]),
+        (status = 403, description = "Caller lacks the role management permission"),
+        (status = 404, description = "Role not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_assignments(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<Vec<RoleAssignment>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_roles(), ip, format!("custom_role:{}", id))
+        .await?;
+
+    let assignments = custom_role_service(&state)
+        .list_assignments(id, claims.tenant_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Role assignments retrieved successfully", assignments)))
+}
+
+/// Assign a custom role to a member of the organization (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/roles/{id}/assignments",
+    tag = "roles",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    request_body = AssignRoleRequest,
+    responses(
+        (status = 201, description = "Role assigned", body = RoleAssignment),
+        (status = 400, description = "Expiry is in the past"),
+        (status = 403, description = "Caller lacks the role management permission or one of the role's permissions"),
+        (status = 404, description = "Role not found or developer is not a member")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn assign_role(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<AssignRoleRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<RoleAssignment>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_roles(), ip, format!("custom_role:{}", id))
+        .await?;
+
+    let assignment = custom_role_service(&state)
+        .assign_role(id, request, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Role assigned successfully", assignment)),
+    ))
+}
+
+/// Revoke a custom role from a developer (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/roles/{id}/assignments/{developer_id}",
+    tag = "roles",
+    params(
+        ("id" = Uuid, Path, description = "Role ID"),
+        ("developer_id" = Uuid, Path, description = "Developer holding the role")
+    ),
+    responses(
+        (status = 200, description = "Role revoked"),
+        (status = 403, description = "Caller lacks the role management permission"),
+        (status = 404, description = "Role or assignment not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn revoke_role(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path((id, developer_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_roles(), ip, format!("custom_role:{}", id))
+        .await?;
+
+    custom_role_service(&state)
+        .revoke_role(id, developer_id, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success_no_data("Role revoked successfully")))
+}
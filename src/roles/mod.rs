@@ -0,0 +1,23 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{delete, get}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/roles", get(controller::list_roles).post(controller::create_role))
+        .route(
+            "/roles/:id",
+            get(controller::get_role)
+                .put(controller::update_role)
+                .delete(controller::delete_role),
+        )
+        .route(
+            "/roles/:id/assignments",
+            get(controller::list_assignments).post(controller::assign_role),
+        )
+        .route("/roles/:id/assignments/:developer_id", delete(controller::revoke_role))
+}
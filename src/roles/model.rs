@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::types::TenantId;
+
+/// A tenant-defined role: a named set of permissions granted on top of the
+/// built-in roles
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct CustomRole {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub tenant_id: TenantId,
+    pub name: String,
+    pub description: Option<String>,
+    /// Granted permissions, as `resource:action`
+    pub permissions: Vec<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create or replace a custom role
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CustomRoleRequest {
+    /// Lowercase letters, digits and underscores; built-in role names are reserved
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    /// Permissions as `resource:action`, e.g. `payments:approve`
+    #[validate(length(min = 1, max = 100))]
+    pub permissions: Vec<String>,
+}
+
+/// A developer holding a custom role
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct RoleAssignment {
+    pub role_id: Uuid,
+    pub developer_id: Uuid,
+    pub assigned_by: Option<Uuid>,
+    /// The role stops applying after this time
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Assign a custom role to a member of the organization
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AssignRoleRequest {
+    pub developer_id: Uuid,
+    pub expires_at: Option<DateTime<Utc>>,
+}
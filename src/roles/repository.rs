@@ -0,0 +1,177 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::shared::types::TenantId;
+use super::model::{CustomRole, RoleAssignment};
+
+const ROLE_COLUMNS: &str = "id, tenant_id, name, description, permissions, created_by, created_at, updated_at";
+
+const ASSIGNMENT_COLUMNS: &str = "role_id, developer_id, assigned_by, expires_at, created_at";
+
+pub struct CustomRoleRepository {
+    pool: PgPool,
+}
+
+impl CustomRoleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self, tenant_id: TenantId) -> AppResult<Vec<CustomRole>> {
+        let roles = sqlx::query_as::<_, CustomRole>(&format!(
+            "SELECT {} FROM custom_roles WHERE tenant_id = $1 ORDER BY name",
+            ROLE_COLUMNS
+        ))
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(roles)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<CustomRole>> {
+        let role = sqlx::query_as::<_, CustomRole>(&format!(
+            "SELECT {} FROM custom_roles WHERE id = $1 AND tenant_id = $2",
+            ROLE_COLUMNS
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(role)
+    }
+
+    /// Another role in the tenant already using `name`
+    pub async fn name_taken(&self, tenant_id: TenantId, name: &str, except: Option<Uuid>) -> AppResult<bool> {
+        let taken: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM custom_roles
+             WHERE tenant_id = $1 AND name = $2 AND ($3::UUID IS NULL OR id <> $3))",
+        )
+        .bind(tenant_id)
+        .bind(name)
+        .bind(except)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(taken)
+    }
+
+    pub async fn create(&self, role: &CustomRole) -> AppResult<CustomRole> {
+        let created = sqlx::query_as::<_, CustomRole>(&format!(
+            "INSERT INTO custom_roles (id, tenant_id, name, description, permissions, created_by, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {}",
+            ROLE_COLUMNS
+        ))
+        .bind(role.id)
+        .bind(role.tenant_id)
+        .bind(&role.name)
+        .bind(&role.description)
+        .bind(&role.permissions)
+        .bind(role.created_by)
+        .bind(role.created_at)
+        .bind(role.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn update(
+        &self,
+        id: Uuid,
+        tenant_id: TenantId,
+        name: &str,
+        description: Option<&str>,
+        permissions: &[String],
+    ) -> AppResult<Option<CustomRole>> {
+        let updated = sqlx::query_as::<_, CustomRole>(&format!(
+            "UPDATE custom_roles SET name = $3, description = $4, permissions = $5, updated_at = NOW()
+             WHERE id = $1 AND tenant_id = $2
+             RETURNING {}",
+            ROLE_COLUMNS
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .bind(name)
+        .bind(description)
+        .bind(permissions)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(updated)
+    }
+
+    /// Delete a role along with its assignments
+    pub async fn delete(&self, id: Uuid, tenant_id: TenantId) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM custom_roles WHERE id = $1 AND tenant_id = $2")
+            .bind(id)
+            .bind(tenant_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn is_member(&self, tenant_id: TenantId, developer_id: Uuid) -> AppResult<bool> {
+        let member: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM organization_members WHERE organization_id = $1 AND developer_id = $2)",
+        )
+        .bind(tenant_id)
+        .bind(developer_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(member)
+    }
+
+    pub async fn list_assignments(&self, role_id: Uuid) -> AppResult<Vec<RoleAssignment>> {
+        let assignments = sqlx::query_as::<_, RoleAssignment>(&format!(
+            "SELECT {} FROM custom_role_assignments WHERE role_id = $1 ORDER BY created_at",
+            ASSIGNMENT_COLUMNS
+        ))
+        .bind(role_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(assignments)
+    }
+
+    /// Assign a role, replacing the expiry of an existing assignment
+    pub async fn assign(
+        &self,
+        role_id: Uuid,
+        developer_id: Uuid,
+        assigned_by: Uuid,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> AppResult<RoleAssignment> {
+        let assignment = sqlx::query_as::<_, RoleAssignment>(&format!(
+            "INSERT INTO custom_role_assignments (role_id, developer_id, assigned_by, expires_at)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (role_id, developer_id)
+             DO UPDATE SET assigned_by = EXCLUDED.assigned_by, expires_at = EXCLUDED.expires_at
+             RETURNING {}",
+            ASSIGNMENT_COLUMNS
+        ))
+        .bind(role_id)
+        .bind(developer_id)
+        .bind(assigned_by)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(assignment)
+    }
+
+    pub async fn revoke(&self, role_id: Uuid, developer_id: Uuid) -> AppResult<bool> {
+        let result = sqlx::query("DELETE FROM custom_role_assignments WHERE role_id = $1 AND developer_id = $2")
+            .bind(role_id)
+            .bind(developer_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
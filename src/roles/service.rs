@@ -0,0 +1,224 @@
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::rbac::{Permission, PermissionContext, RbacService, Role};
+use crate::shared::types::TenantId;
+use super::model::{AssignRoleRequest, CustomRole, CustomRoleRequest, RoleAssignment};
+use super::repository::CustomRoleRepository;
+
+pub struct CustomRoleService {
+    repository: CustomRoleRepository,
+    rbac_service: RbacService,
+    audit_logger: AuditLogger,
+}
+
+impl CustomRoleService {
+    pub fn new(repository: CustomRoleRepository, rbac_service: RbacService, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            rbac_service,
+            audit_logger,
+        }
+    }
+
+    pub async fn list_roles(&self, tenant_id: TenantId) -> AppResult<Vec<CustomRole>> {
+        self.repository.list(tenant_id).await
+    }
+
+    pub async fn get_role(&self, id: Uuid, tenant_id: TenantId) -> AppResult<CustomRole> {
+        self.repository
+            .find_by_id(id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Role not found".to_string()))
+    }
+
+    pub async fn create_role(
+        &self,
+        request: CustomRoleRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<CustomRole> {
+        let permissions = self.validate_role(&request, tenant_id, actor_id, None).await?;
+
+        let now = Utc::now();
+        let role = CustomRole {
+            id: Uuid::new_v4(),
+            tenant_id,
+            name: request.name,
+            description: request.description,
+            permissions,
+            created_by: Some(actor_id),
+            created_at: now,
+            updated_at: now,
+        };
+        let created = self.repository.create(&role).await?;
+
+        self.audit(AuditEventType::CustomRoleCreated, actor_id, &created, "create").await;
+        Ok(created)
+    }
+
+    /// Replace a role's name, description and permissions. Holders pick up
+    /// the change on their next request.
+    pub async fn update_role(
+        &self,
+        id: Uuid,
+        request: CustomRoleRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<CustomRole> {
+        self.get_role(id, tenant_id).await?;
+        let permissions = self.validate_role(&request, tenant_id, actor_id, Some(id)).await?;
+
+        let updated = self
+            .repository
+            .update(id, tenant_id, &request.name, request.description.as_deref(), &permissions)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Role not found".to_string()))?;
+
+        self.audit(AuditEventType::CustomRoleUpdated, actor_id, &updated, "update").await;
+        Ok(updated)
+    }
+
+    pub async fn delete_role(&self, id: Uuid, tenant_id: TenantId, actor_id: Uuid) -> AppResult<()> {
+        let role = self.get_role(id, tenant_id).await?;
+        if !self.repository.delete(id, tenant_id).await? {
+            return Err(AppError::NotFound("Role not found".to_string()));
+        }
+
+        self.audit(AuditEventType::CustomRoleDeleted, actor_id, &role, "delete").await;
+        Ok(())
+    }
+
+    pub async fn list_assignments(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Vec<RoleAssignment>> {
+        self.get_role(id, tenant_id).await?;
+        self.repository.list_assignments(id).await
+    }
+
+    /// Assign a role to a member of the organization. Only permissions the
+    /// caller holds can be handed on.
+    pub async fn assign_role(
+        &self,
+        id: Uuid,
+        request: AssignRoleRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<RoleAssignment> {
+        let role = self.get_role(id, tenant_id).await?;
+        if request.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(AppError::Validation("Role assignment expiry must be in the future".to_string()));
+        }
+        if !self.repository.is_member(tenant_id, request.developer_id).await? {
+            return Err(AppError::NotFound("Developer is not a member of the organization".to_string()));
+        }
+        let permissions = parse_permissions(&role.permissions)?;
+        self.ensure_grantable(actor_id, &permissions)?;
+
+        let assignment = self
+            .repository
+            .assign(id, request.developer_id, actor_id, request.expires_at)
+            .await?;
+
+        let event = AuditEvent::new(AuditEventType::CustomRoleAssigned)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("custom_role:{}", role.id))
+            .action("assign".to_string())
+            .metadata("developer_id".to_string(), json!(assignment.developer_id))
+            .metadata("expires_at".to_string(), json!(assignment.expires_at))
+            .compliance_tag("RBAC".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(assignment)
+    }
+
+    pub async fn revoke_role(
+        &self,
+        id: Uuid,
+        developer_id: Uuid,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+    ) -> AppResult<()> {
+        let role = self.get_role(id, tenant_id).await?;
+        if !self.repository.revoke(id, developer_id).await? {
+            return Err(AppError::NotFound("Role assignment not found".to_string()));
+        }
+
+        let event = AuditEvent::new(AuditEventType::CustomRoleRevoked)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("custom_role:{}", role.id))
+            .action("revoke".to_string())
+            .metadata("developer_id".to_string(), json!(developer_id))
+            .compliance_tag("RBAC".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(())
+    }
+
+    /// Check the name and permissions, returning the permissions in their
+    /// canonical form
+    async fn validate_role(
+        &self,
+        request: &CustomRoleRequest,
+        tenant_id: TenantId,
+        actor_id: Uuid,
+        existing: Option<Uuid>,
+    ) -> AppResult<Vec<String>> {
+        if !request
+            .name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(AppError::Validation(
+                "Role names may only contain lowercase letters, digits and underscores".to_string(),
+            ));
+        }
+        if request.name.parse::<Role>().is_ok() {
+            return Err(AppError::Conflict(format!("'{}' is a built-in role", request.name)));
+        }
+        if self.repository.name_taken(tenant_id, &request.name, existing).await? {
+            return Err(AppError::Conflict(format!("Role '{}' already exists", request.name)));
+        }
+
+        let permissions = parse_permissions(&request.permissions)?;
+        self.ensure_grantable(actor_id, &permissions)?;
+
+        let mut canonical: Vec<String> = permissions.iter().map(Permission::to_string).collect();
+        canonical.sort();
+        canonical.dedup();
+        Ok(canonical)
+    }
+
+    /// Custom roles cannot grant more than their author holds. The caller's
+    /// roles are the ones loaded when the request was authorized.
+    fn ensure_grantable(&self, actor_id: Uuid, permissions: &[Permission]) -> AppResult<()> {
+        let context = PermissionContext::new(actor_id, "unknown".to_string());
+        for permission in permissions {
+            if !self.rbac_service.check_permission(actor_id, permission, &context) {
+                return Err(AppError::Authorization(format!(
+                    "Cannot grant {}, which the caller does not hold",
+                    permission
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn audit(&self, event_type: AuditEventType, actor_id: Uuid, role: &CustomRole, action: &str) {
+        let event = AuditEvent::new(event_type)
+            .severity(AuditSeverity::Warning)
+            .user_id(actor_id)
+            .resource(format!("custom_role:{}", role.id))
+            .action(action.to_string())
+            .metadata("name".to_string(), json!(role.name))
+            .metadata("permissions".to_string(), json!(role.permissions))
+            .compliance_tag("RBAC".to_string());
+        self.audit_logger.log(event).await;
+    }
+}
+
+fn parse_permissions(permissions: &[String]) -> AppResult<Vec<Permission>> {
+    permissions.iter().map(|permission| permission.parse()).collect()
+}
@@ -0,0 +1,153 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::webhooks::repository::WebhookRepository;
+use super::model::{CreateReportSubscriptionRequest, ReportSubscription, UpdateReportSubscriptionRequest};
+use super::repository::ReportSubscriptionRepository;
+use super::service::ReportSubscriptionService;
+
+pub(crate) fn report_subscription_service(state: &AppState) -> ReportSubscriptionService {
+    ReportSubscriptionService::new(
+        ReportSubscriptionRepository::new(state.postgres.clone()),
+        WebhookRepository::new(state.postgres.clone()),
+        state.mailer.clone(),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Subscribe to a periodic report on the caller's organization
+#[utoipa::path(
+    post,
+    path = "/api/v1/report-subscriptions",
+    tag = "report-subscriptions",
+    request_body = CreateReportSubscriptionRequest,
+    responses(
+        (status = 201, description = "Report subscription created", body = ReportSubscription),
+        (status = 400, description = "Invalid subscription or destination")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_report_subscription(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<CreateReportSubscriptionRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<ReportSubscription>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let subscription = report_subscription_service(&state)
+        .create_subscription(request, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Report subscription created successfully", subscription)),
+    ))
+}
+
+/// List the caller's report subscriptions
+#[utoipa::path(
+    get,
+    path = "/api/v1/report-subscriptions",
+    tag = "report-subscriptions",
+    responses(
+        (status = 200, description = "Report subscriptions", body = [ReportSubscription])
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_report_subscriptions(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+) -> AppResult<Json<ApiResponse<Vec<ReportSubscription>>>> {
+    let subscriptions = report_subscription_service(&state)
+        .list_subscriptions(claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Report subscriptions retrieved successfully", subscriptions)))
+}
+
+/// Get a report subscription with its schedule and last delivery result
+#[utoipa::path(
+    get,
+    path = "/api/v1/report-subscriptions/{id}",
+    tag = "report-subscriptions",
+    params(("id" = Uuid, Path, description = "Report subscription ID")),
+    responses(
+        (status = 200, description = "Report subscription", body = ReportSubscription),
+        (status = 404, description = "Report subscription not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_report_subscription(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<ReportSubscription>>> {
+    let subscription = report_subscription_service(&state)
+        .get_subscription(id, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Report subscription retrieved successfully", subscription)))
+}
+
+/// Change where and how a report is delivered, or pause and resume it
+#[utoipa::path(
+    patch,
+    path = "/api/v1/report-subscriptions/{id}",
+    tag = "report-subscriptions",
+    params(("id" = Uuid, Path, description = "Report subscription ID")),
+    request_body = UpdateReportSubscriptionRequest,
+    responses(
+        (status = 200, description = "Report subscription updated", body = ReportSubscription),
+        (status = 400, description = "Invalid update or destination"),
+        (status = 404, description = "Report subscription not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_report_subscription(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<UpdateReportSubscriptionRequest>,
+) -> AppResult<Json<ApiResponse<ReportSubscription>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    let subscription = report_subscription_service(&state)
+        .update_subscription(id, request, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Report subscription updated successfully", subscription)))
+}
+
+/// Unsubscribe from a report
+#[utoipa::path(
+    delete,
+    path = "/api/v1/report-subscriptions/{id}",
+    tag = "report-subscriptions",
+    params(("id" = Uuid, Path, description = "Report subscription ID")),
+    responses(
+        (status = 200, description = "Report subscription deleted"),
+        (status = 404, description = "Report subscription not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_report_subscription(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<()>>> {
+    report_subscription_service(&state)
+        .delete_subscription(id, claims.tenant_id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success_no_data("Report subscription deleted successfully")))
+}
@@ -0,0 +1,34 @@
+use chrono::Utc;
+use crate::core::AppState;
+use super::controller::report_subscription_service;
+
+const REPORT_SCHEDULER_JOB: &str = "report_scheduler";
+
+/// Periodically render and deliver subscribed reports that are due
+pub fn spawn_report_scheduler_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.report_subscription_check_interval_seconds);
+    state.job_monitor.register(REPORT_SCHEDULER_JOB, period);
+
+    tokio::spawn(async move {
+        let service = report_subscription_service(&state);
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match service
+                .run_due(Utc::now(), state.config.report_subscription_batch_size)
+                .await
+            {
+                Ok(run) => {
+                    state.job_monitor.record_success(REPORT_SCHEDULER_JOB);
+                    if run.delivered + run.failed > 0 {
+                        tracing::info!("Scheduled reports: {} delivered, {} failed", run.delivered, run.failed);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(REPORT_SCHEDULER_JOB, e.to_string());
+                    tracing::error!("Report scheduler job failed: {}", e);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,23 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod schedule;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            post(controller::create_report_subscription).get(controller::get_report_subscriptions),
+        )
+        .route(
+            "/:id",
+            get(controller::get_report_subscription)
+                .patch(controller::update_report_subscription)
+                .delete(controller::delete_report_subscription),
+        )
+}
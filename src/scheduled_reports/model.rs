@@ -0,0 +1,216 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::{csv::csv_field, types::TenantId};
+
+/// A report developers can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "scheduled_report_type", rename_all = "snake_case")]
+pub enum ScheduledReportType {
+    /// Transactions created the previous day by currency, type and status;
+    /// delivered shortly after midnight UTC
+    DailyTransactionSummary,
+    /// Payments settled the previous Monday to Sunday by currency and
+    /// method; delivered on Mondays
+    WeeklySettlement,
+    /// API usage of the tenant's projects over the previous calendar month;
+    /// delivered on the 1st
+    MonthlyUsage,
+}
+
+impl ScheduledReportType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScheduledReportType::DailyTransactionSummary => "daily_transaction_summary",
+            ScheduledReportType::WeeklySettlement => "weekly_settlement",
+            ScheduledReportType::MonthlyUsage => "monthly_usage",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ScheduledReportType::DailyTransactionSummary => "Daily transaction summary",
+            ScheduledReportType::WeeklySettlement => "Weekly settlement report",
+            ScheduledReportType::MonthlyUsage => "Monthly usage report",
+        }
+    }
+}
+
+/// How a report is delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "report_delivery_channel", rename_all = "snake_case")]
+pub enum ReportDeliveryChannel {
+    /// Sent to an email address with the report in the message body
+    Email,
+    /// Queued for delivery to a URL as a `report.delivered` webhook
+    Webhook,
+}
+
+/// File format a report is rendered in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "report_file_format", rename_all = "snake_case")]
+pub enum ReportFileFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// A developer's subscription to a periodic report on their tenant
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReportSubscription {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub tenant_id: TenantId,
+    pub developer_id: Uuid,
+    pub report_type: ScheduledReportType,
+    pub channel: ReportDeliveryChannel,
+    /// Email address or webhook URL, depending on the channel
+    pub destination: String,
+    pub format: ReportFileFormat,
+    pub is_active: bool,
+    pub next_run_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Why the last run could not be delivered, cleared by the next success
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Subscribe to a periodic report
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateReportSubscriptionRequest {
+    pub report_type: ScheduledReportType,
+    pub channel: ReportDeliveryChannel,
+    /// Email address for `email`, HTTP(S) URL for `webhook`
+    #[validate(length(min = 1, max = 2048))]
+    pub destination: String,
+    #[serde(default)]
+    pub format: ReportFileFormat,
+}
+
+/// Update a report subscription; omitted fields are left unchanged
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateReportSubscriptionRequest {
+    pub channel: Option<ReportDeliveryChannel>,
+    #[validate(length(min = 1, max = 2048))]
+    pub destination: Option<String>,
+    pub format: Option<ReportFileFormat>,
+    /// Paused subscriptions are not delivered; resuming schedules the next
+    /// run from now rather than catching up
+    pub is_active: Option<bool>,
+}
+
+/// Transactions of one currency, type and status in a daily summary
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TransactionSummaryLine {
+    pub currency: String,
+    pub transaction_type: String,
+    pub status: String,
+    pub transaction_count: i64,
+    pub total_amount: i64,
+}
+
+/// Payments of one currency and method settled in a week
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct SettlementLine {
+    pub currency: String,
+    pub payment_method: String,
+    pub payment_count: i64,
+    pub total_amount: i64,
+    pub total_fees: i64,
+}
+
+/// Calls made by a project to one scope in a month
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct UsageLine {
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub scope: String,
+    pub request_count: i64,
+    pub error_count: i64,
+}
+
+/// Rows of a rendered report
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ReportLines {
+    TransactionSummary(Vec<TransactionSummaryLine>),
+    Settlement(Vec<SettlementLine>),
+    Usage(Vec<UsageLine>),
+}
+
+/// A report generated for one subscription and period
+#[derive(Debug, Serialize)]
+pub struct ScheduledReport {
+    pub subscription_id: Uuid,
+    pub report_type: ScheduledReportType,
+    /// Inclusive
+    pub period_start: DateTime<Utc>,
+    /// Exclusive
+    pub period_end: DateTime<Utc>,
+    pub generated_at: DateTime<Utc>,
+    pub lines: ReportLines,
+}
+
+impl ScheduledReport {
+    /// Render the report's rows as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        match &self.lines {
+            ReportLines::TransactionSummary(lines) => {
+                let mut csv = String::from("currency,transaction_type,status,transaction_count,total_amount\n");
+                for line in lines {
+                    csv.push_str(&format!(
+                        "{},{},{},{},{}\n",
+                        csv_field(&line.currency),
+                        line.transaction_type,
+                        line.status,
+                        line.transaction_count,
+                        line.total_amount
+                    ));
+                }
+                csv
+            }
+            ReportLines::Settlement(lines) => {
+                let mut csv = String::from("currency,payment_method,payment_count,total_amount,total_fees\n");
+                for line in lines {
+                    csv.push_str(&format!(
+                        "{},{},{},{},{}\n",
+                        csv_field(&line.currency),
+                        line.payment_method,
+                        line.payment_count,
+                        line.total_amount,
+                        line.total_fees
+                    ));
+                }
+                csv
+            }
+            ReportLines::Usage(lines) => {
+                let mut csv = String::from("project_id,project_name,scope,request_count,error_count\n");
+                for line in lines {
+                    csv.push_str(&format!(
+                        "{},{},{},{},{}\n",
+                        line.project_id,
+                        csv_field(&line.project_name),
+                        csv_field(&line.scope),
+                        line.request_count,
+                        line.error_count
+                    ));
+                }
+                csv
+            }
+        }
+    }
+}
+
+/// Outcome of one scheduler pass
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReportRun {
+    pub delivered: usize,
+    pub failed: usize,
+}
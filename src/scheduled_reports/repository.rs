@@ -0,0 +1,232 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::shared::types::TenantId;
+use super::model::{ReportSubscription, SettlementLine, TransactionSummaryLine, UsageLine};
+
+const SUBSCRIPTION_COLUMNS: &str = "id, tenant_id, developer_id, report_type, channel, destination, format,
+    is_active, next_run_at, last_run_at, last_error, created_at, updated_at";
+
+pub struct ReportSubscriptionRepository {
+    pool: PgPool,
+}
+
+impl ReportSubscriptionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, subscription: &ReportSubscription) -> AppResult<ReportSubscription> {
+        let created = sqlx::query_as::<_, ReportSubscription>(&format!(
+            "INSERT INTO report_subscriptions ({SUBSCRIPTION_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             RETURNING {SUBSCRIPTION_COLUMNS}"
+        ))
+        .bind(subscription.id)
+        .bind(subscription.tenant_id)
+        .bind(subscription.developer_id)
+        .bind(subscription.report_type)
+        .bind(subscription.channel)
+        .bind(&subscription.destination)
+        .bind(subscription.format)
+        .bind(subscription.is_active)
+        .bind(subscription.next_run_at)
+        .bind(subscription.last_run_at)
+        .bind(&subscription.last_error)
+        .bind(subscription.created_at)
+        .bind(subscription.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    /// A subscription owned by the developer in the tenant
+    pub async fn find_by_id(
+        &self,
+        id: Uuid,
+        developer_id: Uuid,
+        tenant_id: TenantId,
+    ) -> AppResult<Option<ReportSubscription>> {
+        let subscription = sqlx::query_as::<_, ReportSubscription>(&format!(
+            "SELECT {SUBSCRIPTION_COLUMNS} FROM report_subscriptions
+             WHERE id = $1 AND developer_id = $2 AND tenant_id = $3"
+        ))
+        .bind(id)
+        .bind(developer_id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(subscription)
+    }
+
+    pub async fn find_by_developer(
+        &self,
+        developer_id: Uuid,
+        tenant_id: TenantId,
+    ) -> AppResult<Vec<ReportSubscription>> {
+        let subscriptions = sqlx::query_as::<_, ReportSubscription>(&format!(
+            "SELECT {SUBSCRIPTION_COLUMNS} FROM report_subscriptions
+             WHERE developer_id = $1 AND tenant_id = $2
+             ORDER BY created_at"
+        ))
+        .bind(developer_id)
+        .bind(tenant_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(subscriptions)
+    }
+
+    pub async fn update(&self, subscription: &ReportSubscription) -> AppResult<ReportSubscription> {
+        let updated = sqlx::query_as::<_, ReportSubscription>(&format!(
+            "UPDATE report_subscriptions
+             SET channel = $2, destination = $3, format = $4, is_active = $5, next_run_at = $6,
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING {SUBSCRIPTION_COLUMNS}"
+        ))
+        .bind(subscription.id)
+        .bind(subscription.channel)
+        .bind(&subscription.destination)
+        .bind(subscription.format)
+        .bind(subscription.is_active)
+        .bind(subscription.next_run_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(updated)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> AppResult<()> {
+        sqlx::query("DELETE FROM report_subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Active subscriptions whose next run is due, oldest first
+    pub async fn find_due(&self, now: DateTime<Utc>, limit: i64) -> AppResult<Vec<ReportSubscription>> {
+        let subscriptions = sqlx::query_as::<_, ReportSubscription>(&format!(
+            "SELECT {SUBSCRIPTION_COLUMNS} FROM report_subscriptions
+             WHERE is_active AND next_run_at <= $1
+             ORDER BY next_run_at
+             LIMIT $2"
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(subscriptions)
+    }
+
+    /// Move a due subscription on to its next run. Returns false if another
+    /// worker already took this run, so each period is delivered once.
+    pub async fn advance(&self, id: Uuid, due_at: DateTime<Utc>, next_run_at: DateTime<Utc>) -> AppResult<bool> {
+        let result = sqlx::query(
+            "UPDATE report_subscriptions
+             SET next_run_at = $3, last_run_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND next_run_at = $2 AND is_active",
+        )
+        .bind(id)
+        .bind(due_at)
+        .bind(next_run_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn record_result(&self, id: Uuid, error: Option<&str>) -> AppResult<()> {
+        sqlx::query("UPDATE report_subscriptions SET last_error = $2, updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(error)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Transactions created in the tenant within the period, grouped by
+    /// currency, type and status
+    pub async fn transaction_summary(
+        &self,
+        tenant_id: TenantId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> AppResult<Vec<TransactionSummaryLine>> {
+        let lines = sqlx::query_as::<_, TransactionSummaryLine>(
+            "SELECT COALESCE(currency, 'USD') AS currency, transaction_type::TEXT AS transaction_type,
+                    COALESCE(status::TEXT, 'pending') AS status, COUNT(*) AS transaction_count,
+                    SUM(amount)::BIGINT AS total_amount
+             FROM transactions
+             WHERE tenant_id = $1 AND created_at >= $2 AND created_at < $3
+             GROUP BY 1, 2, 3
+             ORDER BY 1, 2, 3",
+        )
+        .bind(tenant_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(lines)
+    }
+
+    /// Payments in the tenant settled within the period, grouped by
+    /// currency and method
+    pub async fn settlement_summary(
+        &self,
+        tenant_id: TenantId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> AppResult<Vec<SettlementLine>> {
+        let lines = sqlx::query_as::<_, SettlementLine>(
+            "SELECT COALESCE(currency, 'USD') AS currency, payment_method::TEXT AS payment_method,
+                    COUNT(*) AS payment_count, SUM(amount)::BIGINT AS total_amount,
+                    SUM(fee_amount)::BIGINT AS total_fees
+             FROM payments
+             WHERE tenant_id = $1 AND settled_at >= $2 AND settled_at < $3
+             GROUP BY 1, 2
+             ORDER BY 1, 2",
+        )
+        .bind(tenant_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(lines)
+    }
+
+    /// API usage of the tenant's projects on days from `from` up to but not
+    /// including `to`, grouped by project and scope
+    pub async fn usage_summary(
+        &self,
+        tenant_id: TenantId,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<UsageLine>> {
+        let lines = sqlx::query_as::<_, UsageLine>(
+            "SELECT p.id AS project_id, p.name AS project_name, u.scope,
+                    SUM(u.request_count)::BIGINT AS request_count, SUM(u.error_count)::BIGINT AS error_count
+             FROM api_usage_daily u
+             JOIN projects p ON p.id = u.project_id
+             WHERE p.organization_id = $1 AND u.usage_date >= $2 AND u.usage_date < $3
+             GROUP BY p.id, p.name, u.scope
+             ORDER BY p.name, u.scope",
+        )
+        .bind(tenant_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(lines)
+    }
+}
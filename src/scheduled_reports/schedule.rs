@@ -0,0 +1,120 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use super::model::ScheduledReportType;
+
+/// Time range a report covers; `start` inclusive, `end` exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportPeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Start of the reporting period `at` falls in: UTC midnight for daily
+/// reports, Monday for weekly ones and the 1st for monthly ones
+fn period_floor(report_type: ScheduledReportType, at: DateTime<Utc>) -> NaiveDate {
+    let date = at.date_naive();
+    match report_type {
+        ScheduledReportType::DailyTransactionSummary => date,
+        ScheduledReportType::WeeklySettlement => {
+            date - Duration::days(date.weekday().num_days_from_monday() as i64)
+        }
+        ScheduledReportType::MonthlyUsage => date.with_day(1).unwrap_or(date),
+    }
+}
+
+fn next_period_start(report_type: ScheduledReportType, start: NaiveDate) -> NaiveDate {
+    match report_type {
+        ScheduledReportType::DailyTransactionSummary => start + Duration::days(1),
+        ScheduledReportType::WeeklySettlement => start + Duration::days(7),
+        ScheduledReportType::MonthlyUsage => {
+            let (year, month) = if start.month() == 12 {
+                (start.year() + 1, 1)
+            } else {
+                (start.year(), start.month() + 1)
+            };
+            NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(start)
+        }
+    }
+}
+
+fn previous_period_start(report_type: ScheduledReportType, start: NaiveDate) -> NaiveDate {
+    match report_type {
+        ScheduledReportType::DailyTransactionSummary => start - Duration::days(1),
+        ScheduledReportType::WeeklySettlement => start - Duration::days(7),
+        ScheduledReportType::MonthlyUsage => {
+            let (year, month) = if start.month() == 1 {
+                (start.year() - 1, 12)
+            } else {
+                (start.year(), start.month() - 1)
+            };
+            NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(start)
+        }
+    }
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// First run strictly after `after`; runs happen at the start of each period
+pub fn next_run_after(report_type: ScheduledReportType, after: DateTime<Utc>) -> DateTime<Utc> {
+    midnight(next_period_start(report_type, period_floor(report_type, after)))
+}
+
+/// The last complete period before a run, so a run on the 1st reports on
+/// the whole previous month
+pub fn period_before(report_type: ScheduledReportType, run_at: DateTime<Utc>) -> ReportPeriod {
+    let end = period_floor(report_type, run_at);
+    ReportPeriod {
+        start: midnight(previous_period_start(report_type, end)),
+        end: midnight(end),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn daily_reports_run_at_the_next_midnight() {
+        let run = next_run_after(ScheduledReportType::DailyTransactionSummary, at(2026, 10, 16, 9));
+        assert_eq!(run, at(2026, 10, 17, 0));
+        // A run exactly at midnight schedules the following one
+        assert_eq!(
+            next_run_after(ScheduledReportType::DailyTransactionSummary, run),
+            at(2026, 10, 18, 0)
+        );
+    }
+
+    #[test]
+    fn weekly_reports_run_on_monday() {
+        // 2026-10-16 is a Friday
+        let run = next_run_after(ScheduledReportType::WeeklySettlement, at(2026, 10, 16, 9));
+        assert_eq!(run, at(2026, 10, 19, 0));
+        assert_eq!(next_run_after(ScheduledReportType::WeeklySettlement, run), at(2026, 10, 26, 0));
+    }
+
+    #[test]
+    fn monthly_reports_run_on_the_first_and_roll_over_the_year() {
+        let run = next_run_after(ScheduledReportType::MonthlyUsage, at(2026, 12, 31, 23));
+        assert_eq!(run, at(2027, 1, 1, 0));
+        assert_eq!(next_run_after(ScheduledReportType::MonthlyUsage, run), at(2027, 2, 1, 0));
+    }
+
+    #[test]
+    fn reports_cover_the_last_complete_period() {
+        let daily = period_before(ScheduledReportType::DailyTransactionSummary, at(2026, 10, 17, 0));
+        assert_eq!(daily, ReportPeriod { start: at(2026, 10, 16, 0), end: at(2026, 10, 17, 0) });
+
+        // A run that starts late still reports on the period before it was due
+        let weekly = period_before(ScheduledReportType::WeeklySettlement, at(2026, 10, 19, 3));
+        assert_eq!(weekly, ReportPeriod { start: at(2026, 10, 12, 0), end: at(2026, 10, 19, 0) });
+
+        let monthly = period_before(ScheduledReportType::MonthlyUsage, at(2027, 1, 1, 0));
+        assert_eq!(monthly, ReportPeriod { start: at(2026, 12, 1, 0), end: at(2027, 1, 1, 0) });
+    }
+}
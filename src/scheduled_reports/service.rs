@@ -0,0 +1,287 @@
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use validator::{ValidateEmail, ValidateUrl};
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::core::mailer::{EmailMessage, Mailer};
+use crate::shared::types::TenantId;
+use crate::webhooks::repository::WebhookRepository;
+use super::model::{
+    CreateReportSubscriptionRequest, ReportDeliveryChannel, ReportFileFormat, ReportLines, ReportRun,
+    ReportSubscription, ScheduledReport, ScheduledReportType, UpdateReportSubscriptionRequest,
+};
+use super::repository::ReportSubscriptionRepository;
+use super::schedule::{next_run_after, period_before};
+
+/// Event type report webhooks are delivered under
+pub const REPORT_DELIVERED_EVENT: &str = "report.delivered";
+
+pub struct ReportSubscriptionService {
+    repository: ReportSubscriptionRepository,
+    webhooks: WebhookRepository,
+    mailer: Arc<dyn Mailer>,
+    audit_logger: AuditLogger,
+}
+
+impl ReportSubscriptionService {
+    pub fn new(
+        repository: ReportSubscriptionRepository,
+        webhooks: WebhookRepository,
+        mailer: Arc<dyn Mailer>,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            webhooks,
+            mailer,
+            audit_logger,
+        }
+    }
+
+    /// Subscribe the developer to a report on their tenant; the first run is
+    /// at the start of the next period
+    pub async fn create_subscription(
+        &self,
+        request: CreateReportSubscriptionRequest,
+        tenant_id: TenantId,
+        developer_id: Uuid,
+    ) -> AppResult<ReportSubscription> {
+        validate_destination(request.channel, &request.destination)?;
+
+        let now = Utc::now();
+        let subscription = ReportSubscription {
+            id: Uuid::new_v4(),
+            tenant_id,
+            developer_id,
+            report_type: request.report_type,
+            channel: request.channel,
+            destination: request.destination,
+            format: request.format,
+            is_active: true,
+            next_run_at: next_run_after(request.report_type, now),
+            last_run_at: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let subscription = self.repository.create(&subscription).await?;
+
+        let event = AuditEvent::new(AuditEventType::ReportSubscriptionCreated)
+            .user_id(developer_id)
+            .resource(format!("report_subscription:{}", subscription.id))
+            .action("create".to_string())
+            .metadata("report_type".to_string(), json!(subscription.report_type))
+            .metadata("channel".to_string(), json!(subscription.channel))
+            .compliance_tag("REPORTING".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(subscription)
+    }
+
+    pub async fn list_subscriptions(
+        &self,
+        tenant_id: TenantId,
+        developer_id: Uuid,
+    ) -> AppResult<Vec<ReportSubscription>> {
+        self.repository.find_by_developer(developer_id, tenant_id).await
+    }
+
+    pub async fn get_subscription(
+        &self,
+        id: Uuid,
+        tenant_id: TenantId,
+        developer_id: Uuid,
+    ) -> AppResult<ReportSubscription> {
+        self.repository
+            .find_by_id(id, developer_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Report subscription not found".to_string()))
+    }
+
+    pub async fn update_subscription(
+        &self,
+        id: Uuid,
+        request: UpdateReportSubscriptionRequest,
+        tenant_id: TenantId,
+        developer_id: Uuid,
+    ) -> AppResult<ReportSubscription> {
+        let mut subscription = self.get_subscription(id, tenant_id, developer_id).await?;
+
+        if let Some(channel) = request.channel {
+            subscription.channel = channel;
+        }
+        if let Some(destination) = request.destination {
+            subscription.destination = destination;
+        }
+        if let Some(format) = request.format {
+            subscription.format = format;
+        }
+        // A channel change without a new destination must still fit the channel
+        validate_destination(subscription.channel, &subscription.destination)?;
+
+        if let Some(is_active) = request.is_active {
+            if is_active && !subscription.is_active {
+                subscription.next_run_at = next_run_after(subscription.report_type, Utc::now());
+            }
+            subscription.is_active = is_active;
+        }
+        let subscription = self.repository.update(&subscription).await?;
+
+        let event = AuditEvent::new(AuditEventType::ReportSubscriptionUpdated)
+            .user_id(developer_id)
+            .resource(format!("report_subscription:{}", subscription.id))
+            .action("update".to_string())
+            .metadata("channel".to_string(), json!(subscription.channel))
+            .metadata("is_active".to_string(), json!(subscription.is_active))
+            .compliance_tag("REPORTING".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(subscription)
+    }
+
+    pub async fn delete_subscription(&self, id: Uuid, tenant_id: TenantId, developer_id: Uuid) -> AppResult<()> {
+        let subscription = self.get_subscription(id, tenant_id, developer_id).await?;
+        self.repository.delete(subscription.id).await?;
+
+        let event = AuditEvent::new(AuditEventType::ReportSubscriptionDeleted)
+            .user_id(developer_id)
+            .resource(format!("report_subscription:{}", subscription.id))
+            .action("delete".to_string())
+            .metadata("report_type".to_string(), json!(subscription.report_type))
+            .compliance_tag("REPORTING".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(())
+    }
+
+    /// Render and deliver every report that is due. Each subscription moves
+    /// on to its next run before delivery, so a failed delivery is recorded
+    /// on the subscription rather than retried; webhook deliveries are
+    /// retried by the webhook queue.
+    pub async fn run_due(&self, now: DateTime<Utc>, limit: i64) -> AppResult<ReportRun> {
+        let mut run = ReportRun::default();
+        for subscription in self.repository.find_due(now, limit).await? {
+            let next_run_at = next_run_after(subscription.report_type, now);
+            if !self
+                .repository
+                .advance(subscription.id, subscription.next_run_at, next_run_at)
+                .await?
+            {
+                continue;
+            }
+
+            match self.deliver(&subscription).await {
+                Ok(()) => {
+                    self.repository.record_result(subscription.id, None).await?;
+                    run.delivered += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(subscription_id = %subscription.id, "Failed to deliver scheduled report: {}", e);
+                    self.repository
+                        .record_result(subscription.id, Some(&e.to_string()))
+                        .await?;
+                    run.failed += 1;
+                }
+            }
+        }
+
+        Ok(run)
+    }
+
+    /// Generate the report for the period before the subscription's due run
+    pub async fn generate(&self, subscription: &ReportSubscription) -> AppResult<ScheduledReport> {
+        let period = period_before(subscription.report_type, subscription.next_run_at);
+        let lines = match subscription.report_type {
+            ScheduledReportType::DailyTransactionSummary => ReportLines::TransactionSummary(
+                self.repository
+                    .transaction_summary(subscription.tenant_id, period.start, period.end)
+                    .await?,
+            ),
+            ScheduledReportType::WeeklySettlement => ReportLines::Settlement(
+                self.repository
+                    .settlement_summary(subscription.tenant_id, period.start, period.end)
+                    .await?,
+            ),
+            ScheduledReportType::MonthlyUsage => ReportLines::Usage(
+                self.repository
+                    .usage_summary(subscription.tenant_id, period.start.date_naive(), period.end.date_naive())
+                    .await?,
+            ),
+        };
+
+        Ok(ScheduledReport {
+            subscription_id: subscription.id,
+            report_type: subscription.report_type,
+            period_start: period.start,
+            period_end: period.end,
+            generated_at: Utc::now(),
+            lines,
+        })
+    }
+
+    async fn deliver(&self, subscription: &ReportSubscription) -> AppResult<()> {
+        let report = self.generate(subscription).await?;
+
+        match subscription.channel {
+            ReportDeliveryChannel::Email => {
+                let body = match subscription.format {
+                    ReportFileFormat::Csv => report.to_csv(),
+                    ReportFileFormat::Json => serde_json::to_string_pretty(&report)
+                        .map_err(|e| AppError::Internal(format!("Failed to render report: {}", e)))?,
+                };
+                self.mailer
+                    .send(EmailMessage {
+                        to: subscription.destination.clone(),
+                        subject: format!(
+                            "{} for {} to {}",
+                            subscription.report_type.title(),
+                            report.period_start.date_naive(),
+                            report.period_end.date_naive()
+                        ),
+                        body,
+                    })
+                    .await
+            }
+            ReportDeliveryChannel::Webhook => {
+                let content = match subscription.format {
+                    ReportFileFormat::Csv => json!(report.to_csv()),
+                    ReportFileFormat::Json => json!(report.lines),
+                };
+                let payload = json!({
+                    "event": REPORT_DELIVERED_EVENT,
+                    "subscription_id": subscription.id,
+                    "report_type": subscription.report_type,
+                    "format": subscription.format,
+                    "period_start": report.period_start,
+                    "period_end": report.period_end,
+                    "generated_at": report.generated_at,
+                    "content": content,
+                });
+                self.webhooks
+                    .enqueue(Some(subscription.tenant_id), REPORT_DELIVERED_EVENT, &subscription.destination, &payload)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Email subscriptions need an email address and webhook ones an HTTP(S) URL
+fn validate_destination(channel: ReportDeliveryChannel, destination: &str) -> AppResult<()> {
+    let valid = match channel {
+        ReportDeliveryChannel::Email => destination.validate_email(),
+        ReportDeliveryChannel::Webhook => {
+            destination.validate_url()
+                && (destination.starts_with("https://") || destination.starts_with("http://"))
+        }
+    };
+    if !valid {
+        return Err(AppError::Validation(match channel {
+            ReportDeliveryChannel::Email => "Email reports need a valid email address".to_string(),
+            ReportDeliveryChannel::Webhook => "Webhook reports need a valid HTTP(S) URL".to_string(),
+        }));
+    }
+    Ok(())
+}
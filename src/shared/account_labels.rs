@@ -0,0 +1,170 @@
+//! Nicknames, labels and custom metadata users give their accounts and
+//! virtual accounts
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use crate::core::error::{AppError, AppResult};
+
+/// Longest nickname, in characters
+pub const MAX_NICKNAME_CHARS: usize = 64;
+
+/// Most labels on one account
+pub const MAX_LABELS: usize = 10;
+
+/// Longest label, in characters
+pub const MAX_LABEL_CHARS: usize = 32;
+
+/// Largest metadata object, serialized
+pub const MAX_METADATA_BYTES: usize = 4096;
+
+/// Changes to an account's nickname, labels and metadata; fields left out
+/// stay as they are
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateAccountDetailsRequest {
+    /// Name shown instead of the account name; an empty nickname clears it
+    pub nickname: Option<String>,
+    /// Replaces the labels; an empty list clears them. Labels are compared
+    /// without case.
+    pub labels: Option<Vec<String>>,
+    /// Replaces the metadata; must be a JSON object
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// An account's nickname and labels, as shown on statements and exports
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AccountLabels {
+    pub nickname: Option<String>,
+    pub labels: Vec<String>,
+}
+
+/// A checked update: `nickname` is `Some(None)` when it is cleared
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccountDetailsChange {
+    pub nickname: Option<Option<String>>,
+    pub labels: Option<Vec<String>>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl UpdateAccountDetailsRequest {
+    /// Trim and check the nickname, normalize the labels and check the
+    /// metadata is a small object
+    pub fn into_change(self) -> AppResult<AccountDetailsChange> {
+        let nickname = self
+            .nickname
+            .map(|nickname| {
+                let nickname = nickname.trim().to_string();
+                if nickname.chars().count() > MAX_NICKNAME_CHARS {
+                    return Err(AppError::Validation(format!(
+                        "A nickname is at most {} characters",
+                        MAX_NICKNAME_CHARS
+                    )));
+                }
+                Ok((!nickname.is_empty()).then_some(nickname))
+            })
+            .transpose()?;
+
+        let labels = self.labels.map(normalize_labels).transpose()?;
+
+        if let Some(metadata) = &self.metadata {
+            if !metadata.is_object() {
+                return Err(AppError::Validation("Account metadata must be a JSON object".to_string()));
+            }
+            if metadata.to_string().len() > MAX_METADATA_BYTES {
+                return Err(AppError::Validation(format!(
+                    "Account metadata is at most {} bytes",
+                    MAX_METADATA_BYTES
+                )));
+            }
+        }
+
+        Ok(AccountDetailsChange {
+            nickname,
+            labels,
+            metadata: self.metadata,
+        })
+    }
+}
+
+/// Lower-case and trim a label, as stored and as searched for
+pub fn normalize_label(label: &str) -> String {
+    label.trim().to_lowercase()
+}
+
+/// Normalize labels, dropping repeats, and reject empty, long or unusual
+/// ones. Labels hold letters, digits, spaces, `-` and `_`.
+pub fn normalize_labels(labels: Vec<String>) -> AppResult<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(labels.len());
+    for label in labels {
+        let label = normalize_label(&label);
+        if label.is_empty() || label.chars().count() > MAX_LABEL_CHARS {
+            return Err(AppError::Validation(format!(
+                "Labels are 1 to {} characters",
+                MAX_LABEL_CHARS
+            )));
+        }
+        if !label.chars().all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_') {
+            return Err(AppError::Validation(format!(
+                "Label '{}' may only hold letters, digits, spaces, '-' and '_'",
+                label
+            )));
+        }
+        if !normalized.contains(&label) {
+            normalized.push(label);
+        }
+    }
+    if normalized.len() > MAX_LABELS {
+        return Err(AppError::Validation(format!("An account has at most {} labels", MAX_LABELS)));
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn labels(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn labels_are_normalized_and_deduplicated() {
+        assert_eq!(
+            normalize_labels(labels(&[" Rent ", "rent", "Holiday-2026"])).unwrap(),
+            labels(&["rent", "holiday-2026"])
+        );
+        assert!(normalize_labels(labels(&[""])).is_err());
+        assert!(normalize_labels(labels(&["a;b"])).is_err());
+        assert!(normalize_labels(labels(&[&"x".repeat(MAX_LABEL_CHARS + 1)])).is_err());
+        let many: Vec<String> = (0..=MAX_LABELS).map(|i| format!("label {}", i)).collect();
+        assert!(normalize_labels(many).is_err());
+    }
+
+    #[test]
+    fn updates_clear_fields_with_empty_values() {
+        let change = UpdateAccountDetailsRequest {
+            nickname: Some("  ".to_string()),
+            labels: Some(Vec::new()),
+            metadata: None,
+        }
+        .into_change()
+        .unwrap();
+        assert_eq!(change.nickname, Some(None));
+        assert_eq!(change.labels, Some(Vec::new()));
+
+        let untouched = UpdateAccountDetailsRequest::default().into_change().unwrap();
+        assert_eq!(untouched, AccountDetailsChange::default());
+    }
+
+    #[test]
+    fn metadata_must_be_a_small_object() {
+        let request = |metadata| UpdateAccountDetailsRequest {
+            metadata: Some(metadata),
+            ..Default::default()
+        };
+        assert!(request(json!({"cost_center": "ops"})).into_change().is_ok());
+        assert!(matches!(request(json!([1, 2])).into_change(), Err(AppError::Validation(_))));
+        assert!(request(json!({"note": "x".repeat(MAX_METADATA_BYTES)})).into_change().is_err());
+    }
+}
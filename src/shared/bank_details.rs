@@ -0,0 +1,444 @@
+//! Format checks for bank account details: IBANs, BICs and the local
+//! account number and bank code formats of countries without IBANs
+
+use std::ops::RangeInclusive;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Layout of a country's IBAN: a bank code, then a numeric account part
+/// filling the rest
+pub struct IbanCountry {
+    pub code: &'static str,
+    pub length: usize,
+    pub bank_code_length: usize,
+    pub bank_code_alphabetic: bool,
+}
+
+/// Countries whose IBAN layout is known. IBANs from other countries are
+/// still checked for length and check digits.
+pub const IBAN_COUNTRIES: &[IbanCountry] = &[
+    IbanCountry { code: "AT", length: 20, bank_code_length: 5, bank_code_alphabetic: false },
+    IbanCountry { code: "CH", length: 21, bank_code_length: 5, bank_code_alphabetic: false },
+    IbanCountry { code: "DE", length: 22, bank_code_length: 8, bank_code_alphabetic: false },
+    IbanCountry { code: "GB", length: 22, bank_code_length: 4, bank_code_alphabetic: true },
+    IbanCountry { code: "IE", length: 22, bank_code_length: 4, bank_code_alphabetic: true },
+    IbanCountry { code: "NL", length: 18, bank_code_length: 4, bank_code_alphabetic: true },
+];
+
+pub fn iban_country(code: &str) -> Option<&'static IbanCountry> {
+    IBAN_COUNTRIES.iter().find(|country| country.code == code)
+}
+
+/// Longest IBAN, and so the longest account number the account tables hold
+pub const MAX_IBAN_LENGTH: usize = 34;
+
+/// Upper-case account details and drop the spaces and dashes they are
+/// often written with
+pub fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+/// Whether a normalized number is written like an IBAN: a country code
+/// then check digits
+pub fn looks_like_iban(number: &str) -> bool {
+    let bytes = number.as_bytes();
+    bytes.len() >= 4 && bytes[..2].iter().all(u8::is_ascii_uppercase) && bytes[2..4].iter().all(u8::is_ascii_digit)
+}
+
+/// Reasons a normalized IBAN is invalid; empty when it is valid
+pub fn validate_iban(iban: &str) -> Vec<String> {
+    if !looks_like_iban(iban) || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return vec!["IBAN must be a country code, two check digits and letters or digits".to_string()];
+    }
+    if let Some(country) = iban_country(&iban[..2]) {
+        if iban.len() != country.length {
+            return vec![format!("{} IBANs are {} characters", country.code, country.length)];
+        }
+    } else if !(15..=MAX_IBAN_LENGTH).contains(&iban.len()) {
+        return vec!["IBAN must be 15 to 34 characters".to_string()];
+    }
+    if mod97(&format!("{}{}", &iban[4..], &iban[..4])) != 1 {
+        return vec!["IBAN check digits do not match".to_string()];
+    }
+    Vec::new()
+}
+
+/// Country and bank code of a valid IBAN; the bank code only for countries
+/// whose layout is known
+pub fn iban_parts(iban: &str) -> Option<(String, Option<String>)> {
+    if !validate_iban(iban).is_empty() {
+        return None;
+    }
+    let bank_code = iban_country(&iban[..2]).map(|country| iban[4..4 + country.bank_code_length].to_string());
+    Some((iban[..2].to_string(), bank_code))
+}
+
+/// The two check digits of an IBAN with this country code and BBAN
+pub fn iban_check_digits(country_code: &str, bban: &str) -> String {
+    format!("{:02}", 98 - mod97(&format!("{}{}00", bban, country_code)))
+}
+
+/// An IBAN in groups of four, as it is printed
+pub fn format_iban(iban: &str) -> String {
+    iban.as_bytes()
+        .chunks(4)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Remainder modulo 97 of a string read as digits, letters counting as
+/// 10 (A) to 35 (Z)
+fn mod97(value: &str) -> u32 {
+    value.chars().fold(0, |remainder, c| {
+        let digit = c.to_digit(36).unwrap_or(0);
+        let base = if digit >= 10 { 100 } else { 10 };
+        (remainder * base + digit) % 97
+    })
+}
+
+/// The parts of a valid BIC (SWIFT code)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct BicDetails {
+    pub bic: String,
+    pub institution_code: String,
+    pub country_code: String,
+    pub location_code: String,
+    /// Branch, absent for a bank's primary office
+    pub branch_code: Option<String>,
+    /// Test and training BICs have a `0` as the second location character
+    pub test: bool,
+}
+
+/// Split a normalized BIC into its parts, or say why it is invalid
+pub fn parse_bic(bic: &str) -> Result<BicDetails, String> {
+    if bic.len() != 8 && bic.len() != 11 {
+        return Err("BIC must be 8 or 11 characters".to_string());
+    }
+    if !bic.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("BIC must be letters and digits".to_string());
+    }
+    let (institution_code, rest) = bic.split_at(4);
+    let (country_code, rest) = rest.split_at(2);
+    let (location_code, branch_code) = rest.split_at(2);
+    if !institution_code.chars().chain(country_code.chars()).all(|c| c.is_ascii_uppercase()) {
+        return Err("BIC must start with a four-letter bank code and a country code".to_string());
+    }
+
+    Ok(BicDetails {
+        bic: bic.to_string(),
+        institution_code: institution_code.to_string(),
+        country_code: country_code.to_string(),
+        location_code: location_code.to_string(),
+        branch_code: (!branch_code.is_empty() && branch_code != "XXX").then(|| branch_code.to_string()),
+        test: location_code.ends_with('0'),
+    })
+}
+
+enum BankCodeFormat {
+    Digits(usize),
+    /// US ABA routing number: nine digits with a weighted check digit
+    AbaRouting,
+    /// Indian Financial System Code: four letters, a zero, six characters
+    Ifsc,
+}
+
+/// How a country writes domestic account numbers and the code of the bank
+/// holding them
+struct LocalFormat {
+    country: &'static str,
+    bank_code_type: &'static str,
+    bank_code: BankCodeFormat,
+    account_lengths: RangeInclusive<usize>,
+}
+
+const LOCAL_FORMATS: &[LocalFormat] = &[
+    LocalFormat { country: "AU", bank_code_type: "bsb", bank_code: BankCodeFormat::Digits(6), account_lengths: 5..=9 },
+    LocalFormat { country: "DE", bank_code_type: "blz", bank_code: BankCodeFormat::Digits(8), account_lengths: 1..=10 },
+    LocalFormat { country: "GB", bank_code_type: "sort_code", bank_code: BankCodeFormat::Digits(6), account_lengths: 8..=8 },
+    LocalFormat { country: "IN", bank_code_type: "ifsc", bank_code: BankCodeFormat::Ifsc, account_lengths: 9..=18 },
+    LocalFormat {
+        country: "US",
+        bank_code_type: "routing_number",
+        bank_code: BankCodeFormat::AbaRouting,
+        account_lengths: 4..=17,
+    },
+];
+
+impl BankCodeFormat {
+    fn matches(&self, bank_code: &str) -> bool {
+        let digits = |len: usize| bank_code.len() == len && bank_code.chars().all(|c| c.is_ascii_digit());
+        match self {
+            BankCodeFormat::Digits(len) => digits(*len),
+            BankCodeFormat::AbaRouting => {
+                digits(9) && {
+                    let weights = [3, 7, 1];
+                    let sum: u32 = bank_code
+                        .chars()
+                        .filter_map(|c| c.to_digit(10))
+                        .enumerate()
+                        .map(|(i, digit)| digit * weights[i % 3])
+                        .sum();
+                    sum.is_multiple_of(10)
+                }
+            }
+            BankCodeFormat::Ifsc => {
+                let bytes = bank_code.as_bytes();
+                bytes.len() == 11
+                    && bytes[..4].iter().all(u8::is_ascii_uppercase)
+                    && bytes[4] == b'0'
+                    && bytes[5..].iter().all(u8::is_ascii_alphanumeric)
+            }
+        }
+    }
+}
+
+/// How a validated account is identified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountFormat {
+    Iban,
+    /// A domestic account number with its bank code
+    Local,
+}
+
+/// Account details to check, as given by the caller
+#[derive(Debug, Clone, Default)]
+pub struct AccountDetails<'a> {
+    pub iban: Option<&'a str>,
+    pub bic: Option<&'a str>,
+    pub account_number: Option<&'a str>,
+    pub bank_code: Option<&'a str>,
+    pub country_code: Option<&'a str>,
+}
+
+impl AccountDetails<'_> {
+    /// Whether there is an account to check at all
+    pub fn is_empty(&self) -> bool {
+        self.iban.is_none() && self.account_number.is_none()
+    }
+}
+
+/// Result of checking account details, with the details in normalized form
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountValidation {
+    pub valid: bool,
+    pub format: AccountFormat,
+    pub country_code: Option<String>,
+    /// Electronic form: upper-case, no spaces
+    pub iban: Option<String>,
+    /// The IBAN in groups of four
+    pub iban_display: Option<String>,
+    pub bank_code: Option<String>,
+    /// What the bank code is called locally, such as `sort_code`
+    pub bank_code_type: Option<String>,
+    pub account_number: Option<String>,
+    pub bic: Option<BicDetails>,
+    pub issues: Vec<String>,
+}
+
+/// Check account details: an IBAN (also accepted as `account_number`), or a
+/// local account number with its bank code and country, plus any BIC
+pub fn validate_account(details: &AccountDetails) -> AccountValidation {
+    let account_number = details.account_number.map(normalize);
+    let iban = details
+        .iban
+        .map(normalize)
+        .or_else(|| account_number.clone().filter(|number| looks_like_iban(number)));
+
+    let mut validation = match iban {
+        Some(iban) => validate_iban_details(iban),
+        None => validate_local_details(
+            account_number,
+            details.bank_code.map(normalize),
+            details.country_code.map(normalize),
+        ),
+    };
+
+    if let Some(bic) = details.bic.map(normalize) {
+        match parse_bic(&bic) {
+            Ok(bic) => {
+                if validation.country_code.as_deref().is_some_and(|country| country != bic.country_code) {
+                    validation.issues.push("BIC is for a bank in another country".to_string());
+                }
+                validation.bic = Some(bic);
+            }
+            Err(issue) => validation.issues.push(issue),
+        }
+    }
+
+    validation.valid = validation.issues.is_empty();
+    validation
+}
+
+fn validate_iban_details(iban: String) -> AccountValidation {
+    let issues = validate_iban(&iban);
+    let layout = issues.is_empty().then(|| iban_country(&iban[..2])).flatten();
+
+    AccountValidation {
+        valid: false,
+        format: AccountFormat::Iban,
+        country_code: looks_like_iban(&iban).then(|| iban[..2].to_string()),
+        iban_display: issues.is_empty().then(|| format_iban(&iban)),
+        bank_code: layout.map(|country| iban[4..4 + country.bank_code_length].to_string()),
+        bank_code_type: None,
+        account_number: layout.map(|country| iban[4 + country.bank_code_length..].to_string()),
+        bic: None,
+        issues,
+        iban: Some(iban),
+    }
+}
+
+fn validate_local_details(
+    account_number: Option<String>,
+    bank_code: Option<String>,
+    country_code: Option<String>,
+) -> AccountValidation {
+    let mut issues = Vec::new();
+    let format = country_code
+        .as_deref()
+        .and_then(|country| LOCAL_FORMATS.iter().find(|format| format.country == country));
+
+    match (&account_number, format) {
+        (None, _) => issues.push("Provide an IBAN, or an account number with its bank code".to_string()),
+        (Some(number), Some(format)) => {
+            if !number.chars().all(|c| c.is_ascii_digit()) || !format.account_lengths.contains(&number.len()) {
+                issues.push(format!(
+                    "{} account numbers are {} to {} digits",
+                    format.country,
+                    format.account_lengths.start(),
+                    format.account_lengths.end()
+                ));
+            }
+        }
+        (Some(number), None) => {
+            if !number.chars().all(|c| c.is_ascii_alphanumeric()) || number.len() > MAX_IBAN_LENGTH {
+                issues.push("Account number must be up to 34 letters or digits".to_string());
+            }
+        }
+    }
+    match (&bank_code, format) {
+        (None, _) => issues.push("A local account number needs its bank code".to_string()),
+        (Some(code), Some(format)) if !format.bank_code.matches(code) => {
+            issues.push(format!("Invalid {} for {}", format.bank_code_type.replace('_', " "), format.country));
+        }
+        _ => {}
+    }
+    if country_code.is_none() {
+        issues.push("A local account number needs its country_code".to_string());
+    }
+
+    // Where an IBAN is just the bank code and padded account number, give it too
+    let iban = match (&account_number, &bank_code, country_code.as_deref().and_then(iban_country)) {
+        (Some(number), Some(code), Some(layout))
+            if issues.is_empty()
+                && !layout.bank_code_alphabetic
+                && code.len() == layout.bank_code_length
+                && number.len() <= layout.length - 4 - layout.bank_code_length =>
+        {
+            let bban = format!("{}{:0>width$}", code, number, width = layout.length - 4 - layout.bank_code_length);
+            Some(format!("{}{}{}", layout.code, iban_check_digits(layout.code, &bban), bban))
+        }
+        _ => None,
+    };
+
+    AccountValidation {
+        valid: false,
+        format: AccountFormat::Local,
+        country_code,
+        iban_display: iban.as_deref().map(format_iban),
+        iban,
+        bank_code,
+        bank_code_type: format.map(|format| format.bank_code_type.to_string()),
+        account_number,
+        bic: None,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_ibans_validate() {
+        assert!(validate_iban("GB82WEST12345698765432").is_empty());
+        assert!(validate_iban(&normalize("de89 3704 0044 0532 0130 00")).is_empty());
+        assert!(!validate_iban("GB82WEST12345698765433").is_empty());
+        assert!(!validate_iban("GB82WEST1234569876543").is_empty());
+        assert_eq!(iban_check_digits("GB", "WEST12345698765432"), "82");
+        assert_eq!(format_iban("GB82WEST12345698765432"), "GB82 WEST 1234 5698 7654 32");
+    }
+
+    #[test]
+    fn bics_are_split_into_their_parts() {
+        let bic = parse_bic("DEUTDEFF500").unwrap();
+        assert_eq!((bic.institution_code.as_str(), bic.country_code.as_str()), ("DEUT", "DE"));
+        assert_eq!(bic.branch_code.as_deref(), Some("500"));
+        assert!(!bic.test);
+        assert!(parse_bic("NEDSZAJ0").unwrap().test);
+        assert_eq!(parse_bic("DEUTDEFFXXX").unwrap().branch_code, None);
+        assert!(parse_bic("DEUTDEF").is_err());
+        assert!(parse_bic("1EUTDEFF").is_err());
+    }
+
+    #[test]
+    fn iban_details_are_normalized_and_checked_against_the_bic() {
+        let validation = validate_account(&AccountDetails {
+            account_number: Some("gb82 west 1234 5698 7654 32"),
+            bic: Some("westgb2l"),
+            ..Default::default()
+        });
+        assert!(validation.valid, "unexpected issues: {:?}", validation.issues);
+        assert_eq!(validation.format, AccountFormat::Iban);
+        assert_eq!(validation.iban.as_deref(), Some("GB82WEST12345698765432"));
+        assert_eq!(validation.bank_code.as_deref(), Some("WEST"));
+
+        let validation = validate_account(&AccountDetails {
+            iban: Some("GB82WEST12345698765432"),
+            bic: Some("DEUTDEFF"),
+            ..Default::default()
+        });
+        assert!(!validation.valid);
+    }
+
+    #[test]
+    fn local_accounts_follow_their_country_format() {
+        let us = |routing: &'static str| {
+            validate_account(&AccountDetails {
+                account_number: Some("123456789"),
+                bank_code: Some(routing),
+                country_code: Some("us"),
+                ..Default::default()
+            })
+        };
+        assert!(us("021000021").valid);
+        assert!(!us("021000022").valid);
+
+        let gb = validate_account(&AccountDetails {
+            account_number: Some("31926819"),
+            bank_code: Some("60-16-13"),
+            country_code: Some("GB"),
+            ..Default::default()
+        });
+        assert!(gb.valid, "unexpected issues: {:?}", gb.issues);
+        assert_eq!(gb.bank_code.as_deref(), Some("601613"));
+        assert_eq!(gb.bank_code_type.as_deref(), Some("sort_code"));
+
+        // German account numbers and bank codes make up the IBAN
+        let de = validate_account(&AccountDetails {
+            account_number: Some("532013000"),
+            bank_code: Some("37040044"),
+            country_code: Some("DE"),
+            ..Default::default()
+        });
+        assert!(de.valid);
+        assert_eq!(de.iban.as_deref(), Some("DE89370400440532013000"));
+
+        assert!(!validate_account(&AccountDetails { account_number: Some("123"), ..Default::default() }).valid);
+    }
+}
@@ -31,6 +31,47 @@ pub const MAX_TRANSACTION_AMOUNT: i64 = 100_000_000;
 /// Minimum transaction amount (in cents) - $0.01
 pub const MIN_TRANSACTION_AMOUNT: i64 = 1;
 
+/// Maximum size of a single dispute evidence file (5 MB)
+pub const MAX_DISPUTE_EVIDENCE_SIZE: usize = 5 * 1024 * 1024;
+
+/// Maximum size of a settlement file submitted for reconciliation (10 MB)
+pub const MAX_SETTLEMENT_FILE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Content types accepted as dispute evidence
+pub const SUPPORTED_EVIDENCE_TYPES: &[&str] = &["application/pdf", "image/jpeg", "image/png"];
+
+/// Maximum size of a single income verification document (10 MB)
+pub const MAX_INCOME_DOCUMENT_SIZE: usize = 10 * 1024 * 1024;
+
+/// Maximum number of documents accepted in one income verification upload
+pub const MAX_INCOME_DOCUMENTS_PER_UPLOAD: usize = 5;
+
+/// Maximum size of an identity image, a document photo or selfie (10 MB)
+pub const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Image formats accepted for identity documents and selfies
+pub const SUPPORTED_IMAGE_FORMATS: &[&str] = &["image/jpeg", "image/png"];
+
+/// Shortest side of an identity image, in pixels, for checks to read it
+pub const MIN_IMAGE_DIMENSION: u32 = 200;
+
+/// Longest side of an identity image, in pixels
+pub const MAX_IMAGE_DIMENSION: u32 = 8000;
+
+/// Maximum size of a single verification session upload; liveness videos
+/// are the largest (25 MB)
+pub const MAX_SESSION_UPLOAD_SIZE: usize = 25 * 1024 * 1024;
+
+/// Maximum size of an object uploaded directly through a signed upload link
+pub const MAX_DIRECT_UPLOAD_SIZE: usize = MAX_SESSION_UPLOAD_SIZE;
+
+/// Content types accepted as income verification documents
+pub const SUPPORTED_INCOME_DOCUMENT_TYPES: &[&str] =
+    &["application/pdf", "text/plain", "image/jpeg", "image/png"];
+
+/// Maximum number of open savings goals per account
+pub const MAX_GOALS_PER_ACCOUNT: usize = 20;
+
 /// Rate limiting
 pub const DEFAULT_RATE_LIMIT: u64 = 60; // requests per minute
 
@@ -44,6 +85,8 @@ pub mod tables {
     pub const PAYMENTS: &str = "payments";
     pub const IDENTITY_VERIFICATIONS: &str = "identity_verifications";
     pub const INCOME_VERIFICATIONS: &str = "income_verifications";
+    pub const DISPUTES: &str = "disputes";
+    pub const SAVINGS_GOALS: &str = "savings_goals";
 }
 
 /// MongoDB collection names
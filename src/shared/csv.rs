@@ -0,0 +1,23 @@
+//! CSV formatting shared by the report writers
+
+/// Quote a CSV field when it contains separators, quotes or newlines
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_fields_that_need_it_are_quoted() {
+        assert_eq!(csv_field("GET /api/v1/accounts"), "GET /api/v1/accounts");
+        assert_eq!(csv_field("Acme, Inc."), "\"Acme, Inc.\"");
+        assert_eq!(csv_field("the \"main\" project"), "\"the \"\"main\"\" project\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+}
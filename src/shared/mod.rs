@@ -1,3 +1,6 @@
+pub mod account_labels;
+pub mod bank_details;
 pub mod constants;
+pub mod csv;
 pub mod traits;
 pub mod types;
\ No newline at end of file
@@ -1,6 +1,14 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use crate::auth::model::LoginRecord;
+use crate::captures::model::DebugCaptureSummary;
+use crate::developers::model::ManagedDeveloperResponse;
+use crate::goals::model::GoalMovement;
+use crate::reviews::model::VerificationReview;
+use crate::virtual_accounts::model::VirtualAccountPosting;
+use crate::webhooks::model::WebhookDeadLetter;
 
 /// Common timestamp fields for entities
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -48,10 +56,13 @@ impl<T> ApiResponse<T> {
 }
 
 /// Pagination parameters
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PaginationParams {
+    /// Page number, starting at 1
     #[serde(default = "default_page")]
     pub page: u32,
+    /// Items per page
     #[serde(default = "default_limit")]
     pub limit: u32,
 }
@@ -65,7 +76,16 @@ fn default_limit() -> u32 {
 }
 
 /// Paginated response wrapper
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[aliases(
+    PaginatedDebugCaptures = PaginatedResponse<DebugCaptureSummary>,
+    PaginatedDevelopers = PaginatedResponse<ManagedDeveloperResponse>,
+    PaginatedGoalMovements = PaginatedResponse<GoalMovement>,
+    PaginatedLogins = PaginatedResponse<LoginRecord>,
+    PaginatedDeadLetters = PaginatedResponse<WebhookDeadLetter>,
+    PaginatedReviews = PaginatedResponse<VerificationReview>,
+    PaginatedVirtualAccountPostings = PaginatedResponse<VirtualAccountPosting>
+)]
 pub struct PaginatedResponse<T> {
     pub data: Vec<T>,
     pub page: u32,
@@ -87,4 +107,6 @@ pub type TransactionId = Uuid;
 pub type Amount = i64;
 
 /// Currency code (ISO 4217)
-pub type Currency = String;
\ No newline at end of file
+pub type Currency = String;
+/// Tenant (organization) ID type alias
+pub type TenantId = Uuid;
@@ -0,0 +1,68 @@
+use std::convert::Infallible;
+use std::time::Duration;
+use axum::{
+    extract::{Query, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    Stream, StreamExt,
+};
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    AppState,
+};
+use super::model::{StreamFilter, StreamQuery};
+use super::repository::StreamRepository;
+
+/// Stream real-time events for the caller's tenant as server-sent events.
+/// Each event's SSE name is its type and its data is the event as JSON.
+#[utoipa::path(
+    get,
+    path = "/api/v1/stream",
+    tag = "stream",
+    params(StreamQuery),
+    responses(
+        (status = 200, description = "Server-sent events named by event type, with the event as JSON data. \
+                                      A `stream.lagged` event carries the number of events the client missed.",
+         content_type = "text/event-stream", body = DomainEvent),
+        (status = 400, description = "Unknown event type or invalid account ID"),
+        (status = 403, description = "Token lacks the scope an event type needs"),
+        (status = 404, description = "Account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn stream_events(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<StreamQuery>,
+) -> AppResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let filter = StreamFilter::from_query(&claims, &query)?;
+    if !filter.account_ids.is_empty()
+        && !StreamRepository::new(state.postgres.clone())
+            .accounts_in_tenant(&filter.account_ids, filter.tenant_id)
+            .await?
+    {
+        return Err(AppError::NotFound("Account not found".to_string()));
+    }
+
+    let events = BroadcastStream::new(state.event_bus.subscribe()).filter_map(move |received| match received {
+        Ok(event) if filter.matches(&event) => Event::default()
+            .id(event.id.to_string())
+            .event(event.event_type.as_str())
+            .json_data(event.as_ref())
+            .ok()
+            .map(Ok),
+        Ok(_) => None,
+        // The subscriber fell behind and missed events; say how many so the
+        // client can refetch state
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            Some(Ok(Event::default().event("stream.lagged").data(skipped.to_string())))
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(
+        KeepAlive::new().interval(Duration::from_secs(state.config.event_stream_keep_alive_seconds)),
+    ))
+}
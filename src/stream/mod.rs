@@ -0,0 +1,10 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+
+use axum::{routing::get, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/", get(controller::stream_events))
+}
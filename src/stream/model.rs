@@ -0,0 +1,94 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+use crate::auth::model::JwtClaims;
+use crate::auth::scopes::{self, Access};
+use crate::core::error::{AppError, AppResult};
+use crate::core::events::{DomainEvent, DomainEventType};
+use crate::shared::types::{AccountId, TenantId};
+
+/// Query parameters for the event stream. Both lists are comma separated.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StreamQuery {
+    /// Event types to receive; defaults to every type the token's scopes allow
+    pub events: Option<String>,
+    /// Accounts to receive events for; defaults to every account in the tenant
+    pub accounts: Option<String>,
+}
+
+/// Which events a subscriber receives
+#[derive(Debug, Clone)]
+pub struct StreamFilter {
+    pub tenant_id: TenantId,
+    pub event_types: Vec<DomainEventType>,
+    pub account_ids: Vec<AccountId>,
+}
+
+impl StreamFilter {
+    /// Build the filter for a token, rejecting event types its scopes do not cover
+    pub fn from_query(claims: &JwtClaims, query: &StreamQuery) -> AppResult<Self> {
+        let event_types = permitted_event_types(claims, query.events.as_deref())?;
+
+        let account_ids = match query.accounts.as_deref() {
+            Some(accounts) => split_list(accounts)
+                .map(|id| {
+                    id.parse::<AccountId>()
+                        .map_err(|_| AppError::Validation(format!("Invalid account id '{}'", id)))
+                })
+                .collect::<AppResult<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            tenant_id: claims.tenant_id,
+            event_types,
+            account_ids,
+        })
+    }
+
+    pub fn matches(&self, event: &DomainEvent) -> bool {
+        event.tenant_id == Some(self.tenant_id)
+            && self.event_types.contains(&event.event_type)
+            && (self.account_ids.is_empty()
+                || event.account_ids.iter().any(|id| self.account_ids.contains(id)))
+    }
+}
+
+/// Event types named in a comma-separated list, or every type the token's
+/// scopes allow when no list is given. Naming a type the scopes do not cover
+/// is an error.
+pub fn permitted_event_types(claims: &JwtClaims, events: Option<&str>) -> AppResult<Vec<DomainEventType>> {
+    let permitted =
+        |event_type: &DomainEventType| scopes::grants(&claims.scopes, event_type.required_scope(), Access::Read);
+
+    let event_types = match events {
+        Some(events) => {
+            let mut event_types = Vec::new();
+            for name in split_list(events) {
+                let event_type = DomainEventType::parse(name)
+                    .ok_or_else(|| AppError::Validation(format!("Unknown event type '{}'", name)))?;
+                if !permitted(&event_type) {
+                    return Err(AppError::Authorization(format!(
+                        "Token is missing the '{}' scope required for {} events",
+                        scopes::module_scope(event_type.required_scope(), Access::Read),
+                        name
+                    )));
+                }
+                event_types.push(event_type);
+            }
+            event_types
+        }
+        None => DomainEventType::ALL.into_iter().filter(permitted).collect(),
+    };
+    if event_types.is_empty() {
+        return Err(AppError::Authorization(
+            "Token has no scope that allows reading events".to_string(),
+        ));
+    }
+
+    Ok(event_types)
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
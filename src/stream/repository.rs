@@ -0,0 +1,26 @@
+use sqlx::PgPool;
+use crate::core::error::AppResult;
+use crate::shared::types::{AccountId, TenantId};
+
+pub struct StreamRepository {
+    pool: PgPool,
+}
+
+impl StreamRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Whether every account belongs to the tenant
+    pub async fn accounts_in_tenant(&self, account_ids: &[AccountId], tenant_id: TenantId) -> AppResult<bool> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM accounts WHERE tenant_id = $1 AND id = ANY($2)",
+        )
+        .bind(tenant_id)
+        .bind(account_ids)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count == account_ids.len() as i64)
+    }
+}
@@ -0,0 +1,377 @@
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Months, NaiveTime, SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+use uuid::Uuid;
+use crate::core::crypto::hex;
+use crate::core::error::{AppError, AppResult};
+use crate::core::storage::Storage;
+use crate::shared::types::{AccountId, TenantId};
+use super::model::{AccountStatement, StatementQuery, TransactionArchive, TransactionRecord};
+use super::repository::TransactionRepository;
+
+/// Tenant months archived per run; the rest wait for the next run
+const PERIODS_PER_RUN: i64 = 10;
+
+/// Longest period a statement may cover
+const MAX_STATEMENT_DAYS: i64 = 366;
+
+const ARCHIVE_FORMAT: &str = "csv";
+
+const CSV_HEADER: &str =
+    "id,from_account_id,to_account_id,amount,currency,transaction_type,status,reference,description,metadata,created_at,updated_at";
+
+/// Moves old transactions to object storage and reads them back for
+/// statements, so callers see one history whichever side a transaction is on
+pub struct TransactionArchiveService {
+    repository: TransactionRepository,
+    storage: Arc<dyn Storage>,
+    batch_size: i64,
+}
+
+impl TransactionArchiveService {
+    pub fn new(repository: TransactionRepository, storage: Arc<dyn Storage>, batch_size: i64) -> Self {
+        Self {
+            repository,
+            storage,
+            batch_size,
+        }
+    }
+
+    /// Archive settled transactions created before `cutoff`, writing at most
+    /// one archive per tenant and month. Returns how many were archived.
+    pub async fn archive_before(&self, cutoff: DateTime<Utc>) -> AppResult<usize> {
+        let periods = self.repository.find_archivable_periods(cutoff, PERIODS_PER_RUN).await?;
+
+        let mut archived = 0;
+        for (tenant_id, month) in periods {
+            let month_end = month
+                .checked_add_months(Months::new(1))
+                .map_or(cutoff, |month_end| month_end.min(cutoff));
+            archived += self.archive_period(tenant_id, month, month_end).await?;
+        }
+        Ok(archived)
+    }
+
+    async fn archive_period(
+        &self,
+        tenant_id: Option<TenantId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> AppResult<usize> {
+        let records = self
+            .repository
+            .find_archivable(tenant_id, start, end, self.batch_size)
+            .await?;
+        let (Some(first), Some(last)) = (records.first(), records.last()) else {
+            return Ok(0);
+        };
+
+        let id = Uuid::new_v4();
+        let body = to_csv(&records).into_bytes();
+        let checksum = hex(&Sha256::digest(&body));
+        let storage_key = format!(
+            "archives/transactions/{}/{}/{}.{}",
+            tenant_id.map_or_else(|| "none".to_string(), |tenant_id| tenant_id.to_string()),
+            start.format("%Y-%m"),
+            id,
+            ARCHIVE_FORMAT
+        );
+        self.storage.put(&storage_key, body).await?;
+
+        let archive = TransactionArchive {
+            id,
+            tenant_id,
+            period_start: first.created_at,
+            period_end: last.created_at + Duration::microseconds(1),
+            storage_key,
+            format: ARCHIVE_FORMAT.to_string(),
+            record_count: records.len() as i32,
+            checksum,
+            created_at: Utc::now(),
+        };
+        if !self.repository.record_archive(&archive, &records).await? {
+            // A transaction changed after it was read; the next run retries
+            warn!(archive_id = %id, "Transactions changed while being archived, discarding the archive");
+            self.storage.delete(&archive.storage_key).await?;
+            return Ok(0);
+        }
+
+        info!(
+            archive_id = %id,
+            tenant_id = ?tenant_id,
+            transactions = records.len(),
+            "Archived transactions to {}",
+            archive.storage_key
+        );
+        Ok(records.len())
+    }
+
+    /// An account's transactions over whole UTC days, fetching the archived
+    /// ones from object storage
+    pub async fn statement(&self, tenant_id: TenantId, query: &StatementQuery) -> AppResult<AccountStatement> {
+        if query.to < query.from {
+            return Err(AppError::BadRequest("Statement end date is before its start date".to_string()));
+        }
+        if (query.to - query.from).num_days() >= MAX_STATEMENT_DAYS {
+            return Err(AppError::BadRequest(format!(
+                "A statement covers at most {} days",
+                MAX_STATEMENT_DAYS
+            )));
+        }
+        let account = self
+            .repository
+            .find_account_labels(query.account_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found".to_string()))?;
+
+        let start = query.from.and_time(NaiveTime::MIN).and_utc();
+        let end = (query.to + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+        let mut transactions = self
+            .repository
+            .find_for_statement(tenant_id, query.account_id, start, end)
+            .await?;
+        for archive in self.repository.find_archives(tenant_id, start, end).await? {
+            let records = self.read_archive(&archive).await?;
+            transactions.extend(
+                records
+                    .into_iter()
+                    .filter(|record| involves(record, query.account_id))
+                    .filter(|record| record.created_at >= start && record.created_at < end),
+            );
+        }
+        transactions.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+        Ok(AccountStatement {
+            account_id: query.account_id,
+            nickname: account.nickname,
+            labels: account.labels,
+            from: query.from,
+            to: query.to,
+            transactions,
+        })
+    }
+
+    async fn read_archive(&self, archive: &TransactionArchive) -> AppResult<Vec<TransactionRecord>> {
+        let body = self.storage.get(&archive.storage_key).await?;
+        if hex(&Sha256::digest(&body)) != archive.checksum {
+            return Err(AppError::Internal(format!(
+                "Transaction archive {} does not match its checksum",
+                archive.id
+            )));
+        }
+        let content = String::from_utf8(body)
+            .map_err(|_| AppError::Internal(format!("Transaction archive {} is not UTF-8", archive.id)))?;
+        from_csv(&content)
+    }
+}
+
+fn involves(record: &TransactionRecord, account_id: AccountId) -> bool {
+    record.from_account_id == Some(account_id) || record.to_account_id == Some(account_id)
+}
+
+/// Render transactions as CSV with a header row. Present optional text is
+/// always quoted, so an empty description stays distinct from none.
+pub fn to_csv(records: &[TransactionRecord]) -> String {
+    let mut csv = csv_header();
+    for record in records {
+        csv.push_str(&csv_line(record));
+    }
+    csv
+}
+
+/// The header row of [`to_csv`], with its line break
+pub fn csv_header() -> String {
+    format!("{}\n", CSV_HEADER)
+}
+
+/// One transaction as a row of [`to_csv`], with its line break
+pub fn csv_line(record: &TransactionRecord) -> String {
+    let fields = [
+        record.id.to_string(),
+        optional_id(record.from_account_id),
+        optional_id(record.to_account_id),
+        record.amount.to_string(),
+        quote(&record.currency),
+        quote(&record.transaction_type),
+        quote(&record.status),
+        quote(&record.reference),
+        record.description.as_deref().map(quote).unwrap_or_default(),
+        record.metadata.as_ref().map(|metadata| quote(&metadata.to_string())).unwrap_or_default(),
+        record.created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+        record
+            .updated_at
+            .map(|updated_at| updated_at.to_rfc3339_opts(SecondsFormat::Micros, true))
+            .unwrap_or_default(),
+    ];
+    format!("{}\n", fields.join(","))
+}
+
+/// Read transactions back from CSV written by [`to_csv`]
+pub fn from_csv(content: &str) -> AppResult<Vec<TransactionRecord>> {
+    let malformed = |reason: String| AppError::Internal(format!("Malformed transaction archive: {}", reason));
+
+    let mut rows = split_records(content).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|field| field.value)
+        .collect();
+    if header.join(",") != CSV_HEADER {
+        return Err(malformed("unexpected header".to_string()));
+    }
+
+    rows.enumerate()
+        .map(|(index, fields)| {
+            let line = index + 2;
+            let [id, from_account_id, to_account_id, amount, currency, transaction_type, status, reference, description, metadata, created_at, updated_at]: [Field; 12] =
+                fields
+                    .try_into()
+                    .map_err(|_| malformed(format!("line {} does not have 12 fields", line)))?;
+            let uuid = |field: &Field| {
+                Uuid::parse_str(&field.value).map_err(|_| malformed(format!("invalid id on line {}", line)))
+            };
+            let optional_uuid = |field: &Field| {
+                if field.value.is_empty() { Ok(None) } else { uuid(field).map(Some) }
+            };
+            let timestamp = |field: &Field| {
+                DateTime::parse_from_rfc3339(&field.value)
+                    .map(|timestamp| timestamp.with_timezone(&Utc))
+                    .map_err(|_| malformed(format!("invalid timestamp on line {}", line)))
+            };
+
+            Ok(TransactionRecord {
+                id: uuid(&id)?,
+                from_account_id: optional_uuid(&from_account_id)?,
+                to_account_id: optional_uuid(&to_account_id)?,
+                amount: amount
+                    .value
+                    .parse()
+                    .map_err(|_| malformed(format!("invalid amount on line {}", line)))?,
+                currency: currency.value,
+                transaction_type: transaction_type.value,
+                status: status.value,
+                reference: reference.value,
+                description: description.quoted.then_some(description.value),
+                metadata: if metadata.quoted {
+                    Some(
+                        serde_json::from_str(&metadata.value)
+                            .map_err(|_| malformed(format!("invalid metadata on line {}", line)))?,
+                    )
+                } else {
+                    None
+                },
+                created_at: timestamp(&created_at)?,
+                updated_at: if updated_at.value.is_empty() { None } else { Some(timestamp(&updated_at)?) },
+            })
+        })
+        .collect()
+}
+
+fn optional_id(id: Option<Uuid>) -> String {
+    id.map(|id| id.to_string()).unwrap_or_default()
+}
+
+pub(crate) fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// A CSV field and whether it was quoted
+struct Field {
+    value: String,
+    quoted: bool,
+}
+
+/// Split CSV into records of fields; quoted fields may hold separators,
+/// doubled quotes and line breaks
+fn split_records(content: &str) -> Vec<Vec<Field>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = Field { value: String::new(), quoted: false };
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    field.value.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.value.is_empty() => {
+                in_quotes = true;
+                field.quoted = true;
+            }
+            ',' if !in_quotes => {
+                record.push(std::mem::replace(&mut field, Field { value: String::new(), quoted: false }));
+            }
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                record.push(std::mem::replace(&mut field, Field { value: String::new(), quoted: false }));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.value.push(c),
+        }
+    }
+    if !record.is_empty() || !field.value.is_empty() || field.quoted {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(description: Option<&str>, metadata: Option<serde_json::Value>) -> TransactionRecord {
+        TransactionRecord {
+            id: Uuid::new_v4(),
+            from_account_id: Some(Uuid::new_v4()),
+            to_account_id: None,
+            amount: 12_345,
+            currency: "EUR".to_string(),
+            transaction_type: "withdrawal".to_string(),
+            status: "completed".to_string(),
+            reference: "TXN_1".to_string(),
+            description: description.map(str::to_string),
+            metadata,
+            created_at: Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn csv_round_trips_awkward_values() {
+        let records = vec![
+            record(Some("Rent, \"May\"\nsecond line"), Some(json!({"note": "a,b", "nested": {"n": 1}}))),
+            record(Some(""), None),
+            record(None, Some(json!(null))),
+        ];
+
+        let parsed = from_csv(&to_csv(&records)).unwrap();
+        assert_eq!(parsed.len(), 3);
+        for (parsed, original) in parsed.iter().zip(&records) {
+            assert_eq!(parsed.id, original.id);
+            assert_eq!(parsed.description, original.description);
+            assert_eq!(parsed.metadata, original.metadata);
+            assert_eq!(parsed.from_account_id, original.from_account_id);
+            assert_eq!(parsed.to_account_id, None);
+            // Archives keep microseconds, as Postgres does
+            assert_eq!(parsed.created_at.timestamp_micros(), original.created_at.timestamp_micros());
+        }
+    }
+
+    #[test]
+    fn csv_rejects_other_files() {
+        assert!(from_csv("currency,amount\nEUR,1\n").is_err());
+        let truncated = format!("{}\n{},,,1\n", CSV_HEADER, Uuid::new_v4());
+        assert!(from_csv(&truncated).is_err());
+        assert!(from_csv(&format!("{}\n", CSV_HEADER)).unwrap().is_empty());
+    }
+}
@@ -1,6 +1,66 @@
-use axum::{extract::State, response::Json};
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
 use serde_json::{json, Value};
-use crate::core::{error::AppResult, AppState};
+use uuid::Uuid;
+use validator::Validate;
+use crate::account_controls::{repository::AccountControlRepository, service::AccountFreezeGuard};
+use crate::auth::middleware::JwtToken;
+use crate::auth::step_up::{SensitiveAction, StepUpPolicy, StepUpSettings};
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::devices::extractor::DeviceSigned;
+use crate::fees::{repository::FeeRepository, service::FeeEngine};
+use crate::fx::controller::fx_quote_service;
+use crate::goals::{repository::GoalRepository, service::GoalBalanceGuard};
+use crate::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use crate::metadata_schemas::{repository::MetadataSchemaRepository, service::MetadataSchemaGuard};
+use crate::usage::model::ExportFormat;
+use crate::user_data::{repository::UserDataRepository, service::UserDataService};
+use crate::webhooks::repository::WebhookRepository;
+use super::archive::{self, TransactionArchiveService};
+use super::export::{TransactionExportService, TransactionExportSettings};
+use super::model::{
+    ExportTransactionsRequest, StatementQuery, TransactionExportResponse, TransactionResponse, TransferPreview,
+    TransferRequest,
+};
+use super::repository::TransactionRepository;
+use super::service::TransactionService;
+
+fn transaction_service(state: &AppState) -> TransactionService {
+    TransactionService::new(
+        TransactionRepository::new(state.postgres.clone()),
+        AccountFreezeGuard::new(
+            AccountControlRepository::new(state.postgres.clone()),
+            state.config.frozen_accounts_allow_credits,
+        ),
+        KycPolicyService::new(
+            KycRepository::new(state.postgres.clone()),
+            KycLimits::from_config(&state.config),
+            state.audit_logger.clone(),
+        ),
+        GoalBalanceGuard::new(GoalRepository::new(state.postgres.clone())),
+        FeeEngine::new(FeeRepository::new(state.postgres.clone())),
+        MetadataSchemaGuard::new(MetadataSchemaRepository::new(state.postgres.clone())),
+    )
+}
+
+pub(crate) fn transaction_export_service(state: &AppState) -> TransactionExportService {
+    TransactionExportService::new(
+        TransactionRepository::new(state.postgres.clone()),
+        state.storage.clone(),
+        WebhookRepository::new(state.postgres.clone()),
+        state.mailer.clone(),
+        state.audit_logger.clone(),
+        TransactionExportSettings::from_config(&state.config),
+    )
+}
 
 /// Create a new transaction
 pub async fn create_transaction(
@@ -41,15 +101,234 @@ pub async fn get_transaction_by_id(
     })))
 }
 
-/// Transfer funds between accounts
+/// Transfer funds between accounts. End users sign the call with a bound
+/// device and must have logged in recently with a one-time code to move
+/// large amounts.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/transfer",
+    tag = "transactions",
+    request_body = TransferRequest,
+    params(
+        ("X-Device-Id" = Option<String>, Header, description = "Bound device that signed the call"),
+        ("X-Device-Timestamp" = Option<i64>, Header, description = "Unix time of the signature in milliseconds"),
+        ("X-Device-Signature" = Option<String>, Header, description = "Base64 Ed25519 signature of method, path, timestamp and body hash")
+    ),
+    responses(
+        (status = 200, description = "Transfer created"),
+        (status = 400, description = "Invalid request, or the FX quote does not price the transfer"),
+        (status = 401, description = "Invalid device assertion, or the user must log in again with a one-time code (see the WWW-Authenticate header)"),
+        (status = 404, description = "Account or FX quote not found"),
+        (status = 409, description = "The FX quote was already used, or has expired and requote was not asked for")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn transfer_funds(
-    State(_state): State<AppState>,
-    // TODO: Add request body for transfer data
-) -> AppResult<Json<Value>> {
-    // TODO: Implement fund transfer logic
-    
-    Ok(Json(json!({
-        "message": "Transfer funds endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
-}
\ No newline at end of file
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    DeviceSigned { body: request, .. }: DeviceSigned<TransferRequest>,
+) -> AppResult<Json<ApiResponse<TransactionResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    // User tokens may only move money out of the user's own accounts
+    if claims.user_id.is_some() {
+        UserDataService::new(UserDataRepository::new(state.postgres.clone()))
+            .ensure_account_access(&claims, request.from_account_id)
+            .await?;
+    }
+
+    StepUpPolicy::new(StepUpSettings::from_config(&state.config), state.audit_logger.clone())
+        .require(
+            &claims,
+            SensitiveAction::Transfer {
+                from_account_id: request.from_account_id,
+                amount: request.amount,
+            },
+        )
+        .await?;
+
+    // The quote is only checked here; posting the transfer uses it up
+    let quote = match request.quote_id {
+        Some(quote_id) => Some(
+            fx_quote_service(&state)?
+                .resolve(quote_id, claims.tenant_id, &request.currency, request.amount, request.requote)
+                .await?,
+        ),
+        None => None,
+    };
+    let transferred = transaction_service(&state)
+        .transfer_funds(request, claims.tenant_id, Some(claims.project_id), quote.as_ref())
+        .await?;
+    Ok(Json(ApiResponse::success("Transfer created successfully", transferred)))
+}
+
+/// Preview a transfer without making it: fees, limits, resulting balances
+/// and any reason it would be rejected
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/transfer/preview",
+    tag = "transactions",
+    request_body = TransferRequest,
+    responses(
+        (status = 200, description = "What the transfer would do; nothing is persisted", body = TransferPreview),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Account or FX quote not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn preview_transfer(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<TransferRequest>,
+) -> AppResult<Json<ApiResponse<TransferPreview>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    // User tokens may only move money out of the user's own accounts
+    if claims.user_id.is_some() {
+        UserDataService::new(UserDataRepository::new(state.postgres.clone()))
+            .ensure_account_access(&claims, request.from_account_id)
+            .await?;
+    }
+
+    let quote = match request.quote_id {
+        Some(quote_id) => Some(fx_quote_service(&state)?.get(quote_id, claims.tenant_id).await?),
+        None => None,
+    };
+    let preview = transaction_service(&state)
+        .preview_transfer(request, claims.tenant_id, Some(claims.project_id), quote.as_ref())
+        .await?;
+    Ok(Json(ApiResponse::success("Transfer preview calculated successfully", preview)))
+}
+
+/// An account's transactions over a period as JSON or CSV, including those
+/// already moved to the archive
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/statement",
+    tag = "transactions",
+    params(StatementQuery),
+    responses(
+        (status = 200, description = "The account's transactions, oldest first", content(
+            ("application/json" = AccountStatement),
+            ("text/csv" = String)
+        )),
+        (status = 400, description = "Invalid or too long period"),
+        (status = 404, description = "Account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_statement(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<StatementQuery>,
+) -> AppResult<Response> {
+    if claims.user_id.is_some() {
+        UserDataService::new(UserDataRepository::new(state.postgres.clone()))
+            .ensure_account_access(&claims, query.account_id)
+            .await?;
+    }
+
+    let statement = TransactionArchiveService::new(
+        TransactionRepository::new(state.postgres.clone()),
+        state.storage.clone(),
+        state.config.transaction_archive_batch_size,
+    )
+    .statement(claims.tenant_id, &query)
+    .await?;
+
+    match query.format {
+        ExportFormat::Json => Ok(Json(ApiResponse::success("Statement generated successfully", statement))
+            .into_response()),
+        ExportFormat::Csv => {
+            let disposition = format!(
+                "attachment; filename=\"statement-{}-{}-{}.csv\"",
+                statement.account_id, statement.from, statement.to
+            );
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (header::CONTENT_DISPOSITION, disposition),
+                ],
+                archive::to_csv(&statement.transactions),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// Queue an export of the organization's transactions matching a filter. The
+/// file is written in the background; poll the export, or ask for a webhook
+/// or email, to get its download link.
+#[utoipa::path(
+    post,
+    path = "/api/v1/transactions/export",
+    tag = "transactions",
+    request_body = ExportTransactionsRequest,
+    responses(
+        (status = 202, description = "Export queued", body = TransactionExportResponse),
+        (status = 400, description = "Invalid filter or notification target"),
+        (status = 404, description = "Account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_transactions(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<ExportTransactionsRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<TransactionExportResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    // User tokens may only export one of the user's own accounts
+    if claims.user_id.is_some() {
+        let account_id = request
+            .filter
+            .account_id
+            .ok_or_else(|| AppError::BadRequest("User exports must name one of the user's accounts".to_string()))?;
+        UserDataService::new(UserDataRepository::new(state.postgres.clone()))
+            .ensure_account_access(&claims, account_id)
+            .await?;
+    }
+
+    let export = transaction_export_service(&state)
+        .request_export(
+            request,
+            claims.tenant_id,
+            Some(claims.project_id),
+            Some(claims.user_id.unwrap_or(claims.developer_id)),
+        )
+        .await?;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ApiResponse::success("Transaction export queued", export)),
+    ))
+}
+
+/// An export's progress, with a time-limited download link once it is ready
+#[utoipa::path(
+    get,
+    path = "/api/v1/transactions/exports/{id}",
+    tag = "transactions",
+    params(("id" = Uuid, Path, description = "Export ID")),
+    responses(
+        (status = 200, description = "Export, with a download link when completed", body = TransactionExportResponse),
+        (status = 404, description = "Transaction export not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_transaction_export(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(export_id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<TransactionExportResponse>>> {
+    let export = transaction_export_service(&state)
+        .get_export(export_id, claims.tenant_id)
+        .await?;
+    // User tokens only see exports the user asked for
+    if claims.user_id.is_some() && export.export.requested_by != claims.user_id {
+        return Err(AppError::NotFound("Transaction export not found".to_string()));
+    }
+    Ok(Json(ApiResponse::success("Transaction export retrieved successfully", export)))
+}
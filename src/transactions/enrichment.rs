@@ -0,0 +1,386 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::Arc;
+use crate::core::circuit_breaker::{CircuitBreaker, CircuitBreakerSettings, CircuitBreakers};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use super::model::{EnrichmentRecord, MerchantCategory, MerchantLocation, MerchantMatch, MerchantRule};
+use super::repository::TransactionRepository;
+
+/// Card network and processor words that say nothing about the merchant
+const NOISE_WORDS: [&str; 9] = [
+    "POS", "PURCHASE", "DEBIT", "CARD", "VISA", "MASTERCARD", "CONTACTLESS", "RECURRING", "PMT",
+];
+
+/// US state codes, read as the region when a description ends with one
+const US_STATES: [&str; 51] = [
+    "AL", "AK", "AZ", "AR", "CA", "CO", "CT", "DE", "DC", "FL", "GA", "HI", "ID", "IL", "IN", "IA", "KS", "KY",
+    "LA", "ME", "MD", "MA", "MI", "MN", "MS", "MO", "MT", "NE", "NV", "NH", "NJ", "NM", "NY", "NC", "ND", "OH",
+    "OK", "OR", "PA", "RI", "SC", "SD", "TN", "TX", "UT", "VT", "VA", "WA", "WV", "WI", "WY",
+];
+
+/// Country codes read as the country when a description ends with one and
+/// it is not also a US state
+const COUNTRIES: [&str; 10] = ["GB", "IE", "FR", "NL", "ES", "IT", "AU", "NZ", "SE", "NO"];
+
+/// Resolves raw transaction descriptions to merchants
+#[async_trait]
+pub trait MerchantProvider: Send + Sync {
+    /// Name stored with each enrichment, matching `MERCHANT_ENRICHMENT_PROVIDER`
+    fn name(&self) -> &'static str;
+
+    /// The merchant behind a description, or `None` if it is not known
+    async fn resolve(&self, description: &str) -> AppResult<Option<MerchantMatch>>;
+}
+
+/// Matches descriptions against the `merchant_rules` lookup table
+pub struct RulesMerchantProvider {
+    rules: Vec<MerchantRule>,
+    logo_base_url: Option<String>,
+}
+
+impl RulesMerchantProvider {
+    /// Rules are tried by priority, then longest pattern first, so `UBER EATS`
+    /// wins over `UBER`
+    pub fn new(mut rules: Vec<MerchantRule>, logo_base_url: Option<String>) -> Self {
+        for rule in &mut rules {
+            rule.pattern = normalize_description(&rule.pattern);
+        }
+        rules.retain(|rule| !rule.pattern.is_empty());
+        rules.sort_by(|a, b| b.priority.cmp(&a.priority).then(b.pattern.len().cmp(&a.pattern.len())));
+        Self { rules, logo_base_url }
+    }
+
+    /// Resolve without waiting on anything; the table is already loaded
+    pub fn resolve_now(&self, description: &str) -> Option<MerchantMatch> {
+        let normalized = normalize_description(description);
+        let (rule, end) = self
+            .rules
+            .iter()
+            .find_map(|rule| word_match_end(&normalized, &rule.pattern).map(|end| (rule, end)))?;
+
+        let location = if rule.city.is_some() || rule.country.is_some() {
+            Some(MerchantLocation {
+                city: rule.city.clone(),
+                region: None,
+                country: rule.country.clone(),
+            })
+        } else {
+            extract_location(&normalized[end..])
+        };
+        let logo_url = rule.logo_url.clone().or_else(|| {
+            let base = self.logo_base_url.as_deref()?;
+            rule.domain.as_ref().map(|domain| format!("{}/{}", base.trim_end_matches('/'), domain))
+        });
+
+        Some(MerchantMatch {
+            merchant_name: rule.merchant_name.clone(),
+            logo_url,
+            category: rule.category,
+            location,
+        })
+    }
+}
+
+#[async_trait]
+impl MerchantProvider for RulesMerchantProvider {
+    fn name(&self) -> &'static str {
+        "rules"
+    }
+
+    async fn resolve(&self, description: &str) -> AppResult<Option<MerchantMatch>> {
+        Ok(self.resolve_now(description))
+    }
+}
+
+/// Name of the enrichment API's circuit breaker
+const MERCHANT_ENRICHMENT_BREAKER: &str = "merchant_enrichment";
+
+/// Queries an HTTP enrichment API accepting `{description}` JSON with a
+/// bearer API key and answering `{name, logo_url, category, city, region,
+/// country}`, or 404 for unknown merchants
+pub struct HttpMerchantProvider {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    breaker: CircuitBreaker,
+}
+
+impl HttpMerchantProvider {
+    pub fn new(api_url: String, api_key: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            breaker: CircuitBreaker::new(MERCHANT_ENRICHMENT_BREAKER, CircuitBreakerSettings::default()),
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    async fn lookup(&self, description: &str) -> AppResult<Option<MerchantMatch>> {
+        let request = self
+            .client
+            .post(&self.api_url)
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({ "description": description }));
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Merchant enrichment request failed: {}", e)))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Merchant enrichment returned {}",
+                response.status()
+            )));
+        }
+
+        let merchant: HttpMerchant = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Invalid merchant enrichment response: {}", e)))?;
+        let category = merchant
+            .category
+            .and_then(|category| serde_json::from_value(serde_json::Value::String(category.to_lowercase())).ok())
+            .unwrap_or(MerchantCategory::Other);
+        let location = (merchant.city.is_some() || merchant.region.is_some() || merchant.country.is_some()).then_some(
+            MerchantLocation {
+                city: merchant.city,
+                region: merchant.region,
+                country: merchant.country,
+            },
+        );
+
+        Ok(Some(MerchantMatch {
+            merchant_name: merchant.name,
+            logo_url: merchant.logo_url,
+            category,
+            location,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpMerchant {
+    name: String,
+    logo_url: Option<String>,
+    category: Option<String>,
+    city: Option<String>,
+    region: Option<String>,
+    country: Option<String>,
+}
+
+#[async_trait]
+impl MerchantProvider for HttpMerchantProvider {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn resolve(&self, description: &str) -> AppResult<Option<MerchantMatch>> {
+        self.breaker.call(self.lookup(description)).await
+    }
+}
+
+/// Build the provider selected by `MERCHANT_ENRICHMENT_PROVIDER`, loading
+/// the lookup table for the rules provider
+pub async fn from_config(
+    config: &Config,
+    repository: &TransactionRepository,
+    breakers: &CircuitBreakers,
+    clients: &HttpClients,
+) -> AppResult<Arc<dyn MerchantProvider>> {
+    match config.merchant_enrichment_provider.as_str() {
+        "rules" => Ok(Arc::new(RulesMerchantProvider::new(
+            repository.find_merchant_rules().await?,
+            config.merchant_logo_base_url.clone(),
+        ))),
+        "http" => {
+            let missing =
+                |name: &str| AppError::Internal(format!("{} is required for the http merchant provider", name));
+            Ok(Arc::new(HttpMerchantProvider::new(
+                config.merchant_enrichment_api_url.clone().ok_or_else(|| missing("MERCHANT_ENRICHMENT_API_URL"))?,
+                config.merchant_enrichment_api_key.clone().ok_or_else(|| missing("MERCHANT_ENRICHMENT_API_KEY"))?,
+            )
+            .with_circuit_breaker(breakers.get(MERCHANT_ENRICHMENT_BREAKER))
+            .with_http_client(clients.get(MERCHANT_ENRICHMENT_BREAKER))))
+        }
+        other => Err(AppError::Internal(format!("Unknown merchant enrichment provider '{}'", other))),
+    }
+}
+
+/// Adds merchant details to transactions after they are created
+pub struct EnrichmentService {
+    repository: TransactionRepository,
+    provider: Arc<dyn MerchantProvider>,
+    batch_size: i64,
+}
+
+impl EnrichmentService {
+    pub fn new(repository: TransactionRepository, provider: Arc<dyn MerchantProvider>, batch_size: i64) -> Self {
+        Self {
+            repository,
+            provider,
+            batch_size,
+        }
+    }
+
+    /// Enrich the oldest transactions not yet enriched, returning how many
+    /// were. Transactions the provider fails on are left for the next run;
+    /// those it does not know are stored as unmatched and not tried again.
+    pub async fn enrich_pending(&self) -> AppResult<usize> {
+        let pending = self.repository.find_unenriched(self.batch_size).await?;
+
+        let mut enriched = 0;
+        for (transaction_id, description) in pending {
+            let merchant = match description.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+                Some(description) => match self.provider.resolve(description).await {
+                    Ok(merchant) => merchant,
+                    Err(e) => {
+                        tracing::warn!(%transaction_id, "Merchant enrichment failed: {}", e);
+                        continue;
+                    }
+                },
+                None => None,
+            };
+            self.repository
+                .save_enrichment(&EnrichmentRecord::new(transaction_id, merchant, self.provider.name()))
+                .await?;
+            enriched += 1;
+        }
+
+        Ok(enriched)
+    }
+}
+
+/// Upper-case a description, split off processor markers such as `*` and
+/// drop reference numbers and card network words
+pub fn normalize_description(description: &str) -> String {
+    description
+        .to_uppercase()
+        .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '/' | '&' | '\'' | '-')))
+        .filter(|word| !word.is_empty())
+        .filter(|word| !word.chars().any(|c| c.is_ascii_digit()))
+        .filter(|word| !NOISE_WORDS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Where the first occurrence of `pattern` at the start of a word in `text`
+/// ends
+fn word_match_end(text: &str, pattern: &str) -> Option<usize> {
+    text.match_indices(pattern)
+        .find(|(index, _)| *index == 0 || text.as_bytes()[index - 1] == b' ')
+        .map(|(index, _)| index + pattern.len())
+}
+
+/// A city followed by a US state or country code at the end of what
+/// follows the merchant in a normalized description, as card networks
+/// print them
+fn extract_location(rest: &str) -> Option<MerchantLocation> {
+    let words: Vec<&str> = rest.split_whitespace().collect();
+    let [.., city, code] = words.as_slice() else {
+        return None;
+    };
+    if city.len() < 3 || !city.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let city = Some(title_case(city));
+    if US_STATES.contains(code) {
+        Some(MerchantLocation { city, region: Some(code.to_string()), country: Some("US".to_string()) })
+    } else if COUNTRIES.contains(code) {
+        Some(MerchantLocation { city, region: None, country: Some(code.to_string()) })
+    } else {
+        None
+    }
+}
+
+fn title_case(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, merchant_name: &str, category: MerchantCategory) -> MerchantRule {
+        MerchantRule {
+            pattern: pattern.to_string(),
+            merchant_name: merchant_name.to_string(),
+            domain: Some(format!("{}.com", merchant_name.to_lowercase().replace(' ', ""))),
+            logo_url: None,
+            category,
+            city: None,
+            country: None,
+            priority: 0,
+        }
+    }
+
+    fn provider() -> RulesMerchantProvider {
+        RulesMerchantProvider::new(
+            vec![
+                rule("UBER", "Uber", MerchantCategory::Transport),
+                rule("uber eats", "Uber Eats", MerchantCategory::Dining),
+                rule("AMZN", "Amazon", MerchantCategory::Shopping),
+                rule("CVS", "CVS Pharmacy", MerchantCategory::Health),
+            ],
+            Some("https://logos.example.com/".to_string()),
+        )
+    }
+
+    #[test]
+    fn descriptions_are_normalized() {
+        assert_eq!(normalize_description("POS PURCHASE AMZN Mktp US*2K4AB1"), "AMZN MKTP US");
+        assert_eq!(normalize_description("  Starbucks #12345  Seattle, WA "), "STARBUCKS SEATTLE WA");
+    }
+
+    #[test]
+    fn the_most_specific_rule_wins() {
+        let provider = provider();
+        let trip = provider.resolve_now("UBER   *TRIP HELP.UBER.COM").unwrap();
+        assert_eq!(trip.merchant_name, "Uber");
+        let eats = provider.resolve_now("Uber *Eats 8005928996 CA").unwrap();
+        assert_eq!(eats.merchant_name, "Uber Eats");
+        assert_eq!(eats.category, MerchantCategory::Dining);
+        assert_eq!(eats.logo_url.as_deref(), Some("https://logos.example.com/ubereats.com"));
+        assert!(eats.location.is_none());
+
+        // Patterns only match at the start of a word
+        assert!(provider.resolve_now("MCVS TRADING").is_none());
+        assert!(provider.resolve_now("Transfer to savings").is_none());
+    }
+
+    #[test]
+    fn trailing_city_and_region_become_the_location() {
+        let amazon = provider().resolve_now("AMZN MKTP SEATTLE WA").unwrap();
+        assert_eq!(
+            amazon.location,
+            Some(MerchantLocation {
+                city: Some("Seattle".to_string()),
+                region: Some("WA".to_string()),
+                country: Some("US".to_string()),
+            })
+        );
+        let cvs = provider().resolve_now("CVS PHARMACY LONDON GB").unwrap();
+        assert_eq!(cvs.location.unwrap().country.as_deref(), Some("GB"));
+        assert!(provider().resolve_now("AMZN MKTP US").unwrap().location.is_none());
+    }
+}
@@ -0,0 +1,633 @@
+use std::sync::Arc;
+use axum::body::Bytes;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde_json::json;
+use sqlx::types::Json;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::io::StreamReader;
+use tracing::{info, warn};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::mailer::{EmailMessage, Mailer};
+use crate::core::storage::Storage;
+use crate::scheduled_reports::model::ReportFileFormat;
+use crate::shared::types::TenantId;
+use crate::webhooks::repository::WebhookRepository;
+use super::archive::{csv_header, csv_line, quote};
+use super::model::{
+    ExportRecord, ExportRun, ExportTransactionsRequest, TransactionExport, TransactionExportFilter,
+    TransactionExportResponse, TransactionExportStatus,
+};
+use super::repository::TransactionRepository;
+
+/// Event type webhooks for finished exports are delivered under
+pub const EXPORT_COMPLETED_EVENT: &str = "transaction_export.completed";
+
+/// Event type webhooks for exports that could not be written are delivered under
+pub const EXPORT_FAILED_EVENT: &str = "transaction_export.failed";
+
+/// Exports written per run; the rest wait for the next run
+const EXPORTS_PER_RUN: i64 = 2;
+
+/// Expired export files removed per run
+const EXPIRED_PER_RUN: i64 = 100;
+
+/// A running export that has not written a page for this long is taken over
+/// by the next run, as its worker has stopped
+const STALE_AFTER_MINUTES: i64 = 10;
+
+/// Chunks buffered between reading transactions and writing the file
+const STREAM_BUFFER_CHUNKS: usize = 4;
+
+const STATUSES: [&str; 4] = ["pending", "completed", "failed", "cancelled"];
+
+const TRANSACTION_TYPES: [&str; 6] = ["deposit", "withdrawal", "transfer", "payment", "refund", "interest"];
+
+/// How exports are written and how long they can be downloaded
+#[derive(Debug, Clone)]
+pub struct TransactionExportSettings {
+    /// Transactions read per query
+    pub batch_size: i64,
+    /// Lifetime of a download link
+    pub link_ttl: std::time::Duration,
+    /// How long a finished file is kept
+    pub retention: Duration,
+}
+
+impl TransactionExportSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            batch_size: config.transaction_export_batch_size.max(1),
+            link_ttl: std::time::Duration::from_secs(config.transaction_export_link_ttl_seconds),
+            retention: Duration::hours(config.transaction_export_retention_hours as i64),
+        }
+    }
+}
+
+/// Queues exports of a tenant's transactions, writes them to object storage
+/// page by page, and hands out signed links to the files
+pub struct TransactionExportService {
+    repository: TransactionRepository,
+    storage: Arc<dyn Storage>,
+    webhooks: WebhookRepository,
+    mailer: Arc<dyn Mailer>,
+    audit_logger: AuditLogger,
+    settings: TransactionExportSettings,
+}
+
+impl TransactionExportService {
+    pub fn new(
+        repository: TransactionRepository,
+        storage: Arc<dyn Storage>,
+        webhooks: WebhookRepository,
+        mailer: Arc<dyn Mailer>,
+        audit_logger: AuditLogger,
+        settings: TransactionExportSettings,
+    ) -> Self {
+        Self {
+            repository,
+            storage,
+            webhooks,
+            mailer,
+            audit_logger,
+            settings,
+        }
+    }
+
+    /// Queue an export of the tenant's transactions matching the filter. Only
+    /// transactions created before the request are included.
+    pub async fn request_export(
+        &self,
+        request: ExportTransactionsRequest,
+        tenant_id: TenantId,
+        project_id: Option<Uuid>,
+        requested_by: Option<Uuid>,
+    ) -> AppResult<TransactionExportResponse> {
+        validate_filter(&request.filter)?;
+        if let Some(url) = &request.webhook_url {
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                return Err(AppError::Validation("Export webhooks need an HTTP(S) URL".to_string()));
+            }
+        }
+        if let Some(account_id) = request.filter.account_id {
+            if !self.repository.account_in_tenant(account_id, tenant_id).await? {
+                return Err(AppError::NotFound("Account not found".to_string()));
+            }
+        }
+
+        let now = Utc::now();
+        let export = self
+            .repository
+            .create_export(&TransactionExport {
+                id: Uuid::new_v4(),
+                tenant_id,
+                project_id,
+                requested_by,
+                filter: Json(request.filter),
+                format: request.format,
+                status: TransactionExportStatus::Queued,
+                notify_email: request.notify_email,
+                webhook_url: request.webhook_url,
+                storage_key: None,
+                row_count: None,
+                size_bytes: None,
+                error: None,
+                started_at: None,
+                completed_at: None,
+                expires_at: None,
+                created_at: now,
+                updated_at: now,
+            })
+            .await?;
+
+        let mut event = AuditEvent::new(AuditEventType::DataExported)
+            .resource(format!("transaction_export:{}", export.id))
+            .action("request".to_string())
+            .metadata("format".to_string(), json!(export.format))
+            .metadata("filter".to_string(), json!(export.filter))
+            .compliance_tag("REPORTING".to_string());
+        if let Some(requested_by) = requested_by {
+            event = event.user_id(requested_by);
+        }
+        if let Some(project_id) = project_id {
+            event = event.project_id(project_id);
+        }
+        self.audit_logger.log(event).await;
+
+        self.with_download_url(export)
+    }
+
+    /// An export of the tenant, with a fresh download link once it is ready
+    pub async fn get_export(&self, export_id: Uuid, tenant_id: TenantId) -> AppResult<TransactionExportResponse> {
+        let export = self
+            .repository
+            .find_export(export_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Transaction export not found".to_string()))?;
+        self.with_download_url(export)
+    }
+
+    /// Remove files past their retention, then write queued exports. A failed
+    /// export is recorded on the export and not retried.
+    pub async fn run(&self, now: DateTime<Utc>) -> AppResult<ExportRun> {
+        let mut run = ExportRun::default();
+
+        for export in self.repository.find_expired_exports(now, EXPIRED_PER_RUN).await? {
+            if let Some(storage_key) = &export.storage_key {
+                self.storage.delete(storage_key).await?;
+            }
+            self.repository.expire_export(export.id).await?;
+            run.expired += 1;
+        }
+
+        let stale_before = now - Duration::minutes(STALE_AFTER_MINUTES);
+        for export in self.repository.claim_exports(now, stale_before, EXPORTS_PER_RUN).await? {
+            match self.write(&export).await {
+                Ok(completed) => {
+                    info!(
+                        export_id = %completed.id,
+                        transactions = completed.row_count.unwrap_or_default(),
+                        "Exported transactions"
+                    );
+                    self.notify(&completed).await;
+                    run.completed += 1;
+                }
+                Err(e) => {
+                    warn!(export_id = %export.id, "Failed to export transactions: {}", e);
+                    self.repository.fail_export(export.id, &e.to_string()).await?;
+                    let failed = TransactionExport {
+                        status: TransactionExportStatus::Failed,
+                        error: Some(e.to_string()),
+                        ..export
+                    };
+                    self.notify(&failed).await;
+                    run.failed += 1;
+                }
+            }
+        }
+
+        Ok(run)
+    }
+
+    /// Stream the export into object storage while its pages are read, so
+    /// memory use does not grow with the number of transactions
+    async fn write(&self, export: &TransactionExport) -> AppResult<TransactionExport> {
+        let storage_key = format!(
+            "exports/transactions/{}/{}.{}",
+            export.tenant_id,
+            export.id,
+            extension(export.format)
+        );
+
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER_CHUNKS);
+        let producer = tokio::spawn(stream_rows(
+            self.repository.clone(),
+            export.clone(),
+            self.settings.batch_size,
+            sender,
+        ));
+        let stored = self
+            .storage
+            .put_stream(&storage_key, Box::pin(StreamReader::new(ReceiverStream::new(receiver))))
+            .await;
+        let written = producer
+            .await
+            .map_err(|e| AppError::Internal(format!("Transaction export task failed: {}", e)))?;
+
+        // A failed read also breaks the upload, so its error explains both
+        let (row_count, size_bytes) = match (written, stored) {
+            (Ok(row_count), Ok(size_bytes)) => (row_count, size_bytes),
+            (Err(e), _) | (Ok(_), Err(e)) => {
+                if let Err(cleanup) = self.storage.delete(&storage_key).await {
+                    warn!(export_id = %export.id, "Failed to remove partial export: {}", cleanup);
+                }
+                return Err(e);
+            }
+        };
+
+        self.repository
+            .complete_export(
+                export.id,
+                &storage_key,
+                row_count,
+                size_bytes as i64,
+                Utc::now() + self.settings.retention,
+            )
+            .await
+    }
+
+    /// Tell the requester the export finished, by webhook and email as asked.
+    /// Failures are only logged; the export can still be fetched.
+    async fn notify(&self, export: &TransactionExport) {
+        let response = match self.with_download_url(export.clone()) {
+            Ok(response) => response,
+            Err(e) => {
+                warn!(export_id = %export.id, "Failed to sign export download link: {}", e);
+                TransactionExportResponse {
+                    export: export.clone(),
+                    download_url: None,
+                    download_url_expires_at: None,
+                }
+            }
+        };
+
+        if let Some(url) = &export.webhook_url {
+            let event_type = match export.status {
+                TransactionExportStatus::Completed => EXPORT_COMPLETED_EVENT,
+                _ => EXPORT_FAILED_EVENT,
+            };
+            let payload = json!({
+                "event": event_type,
+                "export_id": export.id,
+                "status": export.status,
+                "format": export.format,
+                "row_count": export.row_count,
+                "size_bytes": export.size_bytes,
+                "error": export.error,
+                "download_url": response.download_url,
+                "download_url_expires_at": response.download_url_expires_at,
+                "expires_at": export.expires_at,
+            });
+            if let Err(e) = self
+                .webhooks
+                .enqueue(Some(export.tenant_id), event_type, url, &payload)
+                .await
+            {
+                warn!(export_id = %export.id, "Failed to queue export webhook: {}", e);
+            }
+        }
+
+        if let Some(to) = &export.notify_email {
+            let message = match (&response.download_url, response.download_url_expires_at) {
+                (Some(url), Some(link_expires_at)) => EmailMessage {
+                    to: to.clone(),
+                    subject: "Your transaction export is ready".to_string(),
+                    body: format!(
+                        "Your export of {} transactions is ready. Download it before {}:\n\n{}\n\n\
+                         Request a new link from the export afterwards; the file is kept until {}.",
+                        export.row_count.unwrap_or_default(),
+                        link_expires_at.format("%Y-%m-%d %H:%M UTC"),
+                        url,
+                        export.expires_at.unwrap_or(link_expires_at).format("%Y-%m-%d %H:%M UTC"),
+                    ),
+                },
+                _ => EmailMessage {
+                    to: to.clone(),
+                    subject: "Your transaction export failed".to_string(),
+                    body: format!(
+                        "Transaction export {} could not be written: {}",
+                        export.id,
+                        export.error.as_deref().unwrap_or("unknown error")
+                    ),
+                },
+            };
+            if let Err(e) = self.mailer.send(message).await {
+                warn!(export_id = %export.id, "Failed to email export notification: {}", e);
+            }
+        }
+    }
+
+    /// Sign a download link for a completed export, lasting no longer than the file
+    fn with_download_url(&self, export: TransactionExport) -> AppResult<TransactionExportResponse> {
+        let (Some(storage_key), Some(expires_at), TransactionExportStatus::Completed) =
+            (&export.storage_key, export.expires_at, export.status)
+        else {
+            return Ok(TransactionExportResponse {
+                export,
+                download_url: None,
+                download_url_expires_at: None,
+            });
+        };
+
+        let now = Utc::now();
+        let remaining = (expires_at - now).to_std().unwrap_or_default();
+        let link_ttl = self.settings.link_ttl.min(remaining);
+        let download_url = self.storage.signed_url(storage_key, link_ttl)?;
+        let link_expires_at = now + Duration::from_std(link_ttl).unwrap_or_default();
+
+        Ok(TransactionExportResponse {
+            export,
+            download_url: Some(download_url),
+            download_url_expires_at: Some(link_expires_at),
+        })
+    }
+}
+
+/// Reject filters that cannot match anything the way they were meant to
+pub fn validate_filter(filter: &TransactionExportFilter) -> AppResult<()> {
+    if let (Some(from), Some(to)) = (filter.from, filter.to) {
+        if to < from {
+            return Err(AppError::BadRequest("Export end date is before its start date".to_string()));
+        }
+    }
+    if let (Some(min_amount), Some(max_amount)) = (filter.min_amount, filter.max_amount) {
+        if max_amount < min_amount {
+            return Err(AppError::BadRequest("Export maximum amount is below its minimum".to_string()));
+        }
+    }
+    if let Some(status) = &filter.status {
+        if !STATUSES.contains(&status.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown transaction status '{}', expected one of {}",
+                status,
+                STATUSES.join(", ")
+            )));
+        }
+    }
+    if let Some(transaction_type) = &filter.transaction_type {
+        if !TRANSACTION_TYPES.contains(&transaction_type.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unknown transaction type '{}', expected one of {}",
+                transaction_type,
+                TRANSACTION_TYPES.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The `[start, end)` creation times an export covers: the filter's whole
+/// UTC days, ending no later than the request
+pub fn export_period(
+    filter: &TransactionExportFilter,
+    requested_at: DateTime<Utc>,
+) -> (Option<DateTime<Utc>>, DateTime<Utc>) {
+    let start = filter.from.map(|from| from.and_time(NaiveTime::MIN).and_utc());
+    let requested_end = requested_at + Duration::microseconds(1);
+    let end = filter
+        .to
+        .map(|to| (to + Duration::days(1)).and_time(NaiveTime::MIN).and_utc())
+        .map_or(requested_end, |end| end.min(requested_end));
+    (start, end)
+}
+
+/// The start of an export file, before any transaction
+pub fn render_opening(format: ReportFileFormat) -> String {
+    match format {
+        ReportFileFormat::Csv => format!("{},from_account_labels,to_account_labels\n", csv_header().trim_end()),
+        ReportFileFormat::Json => "[".to_string(),
+    }
+}
+
+/// One page of transactions as written to an export file; `first` tells
+/// whether any transaction came before it
+pub fn render_page(format: ReportFileFormat, records: &[ExportRecord], first: bool) -> AppResult<String> {
+    match format {
+        ReportFileFormat::Csv => Ok(records.iter().map(export_csv_line).collect()),
+        ReportFileFormat::Json => {
+            let mut page = String::new();
+            for (index, record) in records.iter().enumerate() {
+                page.push_str(if first && index == 0 { "\n" } else { ",\n" });
+                let line = serde_json::to_string(record)
+                    .map_err(|e| AppError::Internal(format!("Failed to render transaction: {}", e)))?;
+                page.push_str(&line);
+            }
+            Ok(page)
+        }
+    }
+}
+
+/// The end of an export file, after every transaction
+pub fn render_closing(format: ReportFileFormat, empty: bool) -> String {
+    match format {
+        ReportFileFormat::Csv => String::new(),
+        ReportFileFormat::Json if empty => "]\n".to_string(),
+        ReportFileFormat::Json => "\n]\n".to_string(),
+    }
+}
+
+/// An archive CSV row followed by the accounts' labels, `;`-separated
+fn export_csv_line(record: &ExportRecord) -> String {
+    format!(
+        "{},{},{}\n",
+        csv_line(&record.record).trim_end(),
+        quote(&record.from_account_labels.join(";")),
+        quote(&record.to_account_labels.join(";"))
+    )
+}
+
+fn extension(format: ReportFileFormat) -> &'static str {
+    match format {
+        ReportFileFormat::Csv => "csv",
+        ReportFileFormat::Json => "json",
+    }
+}
+
+/// Feed an export file to `sender` page by page. A failed read is passed on
+/// so the upload stops too, and returned so the export records why.
+async fn stream_rows(
+    repository: TransactionRepository,
+    export: TransactionExport,
+    batch_size: i64,
+    sender: mpsc::Sender<std::io::Result<Bytes>>,
+) -> AppResult<i64> {
+    match write_rows(&repository, &export, batch_size, &sender).await {
+        Ok(row_count) => Ok(row_count),
+        Err(e) => {
+            let _ = sender.send(Err(std::io::Error::other(e.to_string()))).await;
+            Err(e)
+        }
+    }
+}
+
+/// Returns how many transactions were written. Stops early without an error
+/// when the upload has gone away, as the upload's error says why.
+async fn write_rows(
+    repository: &TransactionRepository,
+    export: &TransactionExport,
+    batch_size: i64,
+    sender: &mpsc::Sender<std::io::Result<Bytes>>,
+) -> AppResult<i64> {
+    let (start, end) = export_period(&export.filter, export.created_at);
+
+    if sender.send(Ok(Bytes::from(render_opening(export.format)))).await.is_err() {
+        return Ok(0);
+    }
+
+    let mut row_count = 0i64;
+    let mut after = None;
+    loop {
+        let page = repository
+            .find_for_export(export.tenant_id, &export.filter, start, end, after, batch_size)
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        after = Some((last.record.created_at, last.record.id));
+
+        let chunk = render_page(export.format, &page, row_count == 0)?;
+        row_count += page.len() as i64;
+        if sender.send(Ok(Bytes::from(chunk))).await.is_err() {
+            return Ok(row_count);
+        }
+        repository.touch_export(export.id).await?;
+
+        if (page.len() as i64) < batch_size {
+            break;
+        }
+    }
+
+    let closing = render_closing(export.format, row_count == 0);
+    if !closing.is_empty() {
+        let _ = sender.send(Ok(Bytes::from(closing))).await;
+    }
+    Ok(row_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+    use crate::transactions::archive::to_csv;
+    use crate::transactions::model::TransactionRecord;
+
+    fn record(reference: &str, amount: i64) -> ExportRecord {
+        let record = TransactionRecord {
+            id: Uuid::new_v4(),
+            from_account_id: Some(Uuid::new_v4()),
+            to_account_id: None,
+            amount,
+            currency: "USD".to_string(),
+            transaction_type: "payment".to_string(),
+            status: "completed".to_string(),
+            reference: reference.to_string(),
+            description: Some("Coffee, \"large\"".to_string()),
+            metadata: Some(json!({"order": 1})),
+            created_at: Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap(),
+            updated_at: None,
+        };
+        ExportRecord {
+            record,
+            from_account_labels: vec!["rent".to_string(), "joint".to_string()],
+            to_account_labels: Vec::new(),
+        }
+    }
+
+    fn render_all(format: ReportFileFormat, pages: &[Vec<ExportRecord>]) -> String {
+        let mut file = render_opening(format);
+        let mut written = 0;
+        for page in pages {
+            file.push_str(&render_page(format, page, written == 0).unwrap());
+            written += page.len();
+        }
+        file.push_str(&render_closing(format, written == 0));
+        file
+    }
+
+    #[test]
+    fn paged_csv_is_the_archive_format_with_labels() {
+        let records = [record("TX-1", 100), record("TX-2", 250), record("TX-3", 75)];
+        let paged = render_all(ReportFileFormat::Csv, &[records[..2].to_vec(), records[2..].to_vec()]);
+
+        let archive: Vec<TransactionRecord> = records.iter().map(|record| record.record.clone()).collect();
+        let archive = to_csv(&archive);
+        for (paged, archived) in paged.lines().zip(archive.lines()).skip(1) {
+            assert_eq!(paged, format!("{},\"rent;joint\",\"\"", archived));
+        }
+        assert_eq!(paged.lines().count(), archive.lines().count());
+        assert!(paged.starts_with(&format!("{},from_account_labels,to_account_labels\n", archive.lines().next().unwrap())));
+    }
+
+    #[test]
+    fn paged_json_is_one_array() {
+        let records = vec![record("TX-1", 100), record("TX-2", 250), record("TX-3", 75)];
+        let paged = render_all(ReportFileFormat::Json, &[records[..1].to_vec(), records[1..].to_vec()]);
+        let parsed: Vec<ExportRecord> = serde_json::from_str(&paged).unwrap();
+        assert_eq!(parsed, records);
+
+        let empty: Vec<ExportRecord> = serde_json::from_str(&render_all(ReportFileFormat::Json, &[])).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn period_covers_whole_days_up_to_the_request() {
+        let requested_at = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let filter = TransactionExportFilter {
+            from: NaiveDate::from_ymd_opt(2026, 3, 1),
+            to: NaiveDate::from_ymd_opt(2026, 3, 2),
+            ..Default::default()
+        };
+        let (start, end) = export_period(&filter, requested_at);
+        assert_eq!(start, Some(Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()));
+        assert_eq!(end, Utc.with_ymd_and_hms(2026, 3, 3, 0, 0, 0).unwrap());
+
+        // Transactions created after the request are left out
+        let (start, end) = export_period(&TransactionExportFilter::default(), requested_at);
+        assert_eq!(start, None);
+        assert_eq!(end, requested_at + Duration::microseconds(1));
+    }
+
+    #[test]
+    fn rejects_filters_that_cannot_be_meant() {
+        assert!(validate_filter(&TransactionExportFilter::default()).is_ok());
+
+        let backwards = TransactionExportFilter {
+            from: NaiveDate::from_ymd_opt(2026, 3, 2),
+            to: NaiveDate::from_ymd_opt(2026, 3, 1),
+            ..Default::default()
+        };
+        assert!(matches!(validate_filter(&backwards), Err(AppError::BadRequest(_))));
+
+        let amounts = TransactionExportFilter {
+            min_amount: Some(500),
+            max_amount: Some(100),
+            ..Default::default()
+        };
+        assert!(validate_filter(&amounts).is_err());
+
+        let status = TransactionExportFilter {
+            status: Some("settled".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_filter(&status).is_err());
+
+        let transaction_type = TransactionExportFilter {
+            transaction_type: Some("refund".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_filter(&transaction_type).is_ok());
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{Duration, Utc};
+use crate::core::error::AppResult;
+use crate::core::AppState;
+use super::archive::TransactionArchiveService;
+use super::controller::transaction_export_service;
+use super::enrichment::{self, EnrichmentService};
+use super::repository::TransactionRepository;
+
+const ENRICHMENT_JOB: &str = "transaction_enrichment";
+
+const ARCHIVE_JOB: &str = "transaction_archive";
+
+const EXPORT_JOB: &str = "transaction_export";
+
+/// Periodically add merchant details to newly created transactions
+pub fn spawn_enrichment_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.merchant_enrichment_interval_seconds);
+    state.job_monitor.register(ENRICHMENT_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match enrich_transactions(&state).await {
+                Ok(count) => {
+                    state.job_monitor.record_success(ENRICHMENT_JOB);
+                    if count > 0 {
+                        tracing::info!("Enriched {} transactions", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(ENRICHMENT_JOB, e.to_string());
+                    tracing::error!("Transaction enrichment job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Run one batch, reloading the provider so rule changes apply
+async fn enrich_transactions(state: &AppState) -> AppResult<usize> {
+    let repository = TransactionRepository::new(state.postgres.clone());
+    let provider = enrichment::from_config(&state.config, &repository, &state.circuit_breakers, &state.http_clients).await?;
+    EnrichmentService::new(repository, provider, state.config.merchant_enrichment_batch_size)
+        .enrich_pending()
+        .await
+}
+
+/// Periodically move settled transactions past the archive age to object
+/// storage. Not started when archiving is turned off.
+pub fn spawn_archive_job(state: AppState) {
+    if state.config.transaction_archive_after_days == 0 {
+        return;
+    }
+    let period = std::time::Duration::from_secs(state.config.transaction_archive_interval_seconds);
+    state.job_monitor.register(ARCHIVE_JOB, period);
+
+    tokio::spawn(async move {
+        let service = TransactionArchiveService::new(
+            TransactionRepository::new(state.postgres.clone()),
+            state.storage.clone(),
+            state.config.transaction_archive_batch_size,
+        );
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let cutoff = Utc::now() - Duration::days(state.config.transaction_archive_after_days as i64);
+            match service.archive_before(cutoff).await {
+                Ok(count) => {
+                    state.job_monitor.record_success(ARCHIVE_JOB);
+                    if count > 0 {
+                        tracing::info!("Archived {} transactions", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(ARCHIVE_JOB, e.to_string());
+                    tracing::error!("Transaction archive job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+/// Periodically write queued transaction exports and remove expired files
+pub fn spawn_export_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.transaction_export_interval_seconds);
+    state.job_monitor.register(EXPORT_JOB, period);
+
+    tokio::spawn(async move {
+        let service = transaction_export_service(&state);
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match service.run(Utc::now()).await {
+                Ok(run) => {
+                    state.job_monitor.record_success(EXPORT_JOB);
+                    if run.completed + run.failed + run.expired > 0 {
+                        tracing::info!(
+                            "Transaction exports: {} completed, {} failed, {} expired",
+                            run.completed,
+                            run.failed,
+                            run.expired
+                        );
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(EXPORT_JOB, e.to_string());
+                    tracing::error!("Transaction export job failed: {}", e);
+                }
+            }
+        }
+    });
+}
@@ -1,4 +1,8 @@
+pub mod archive;
 pub mod controller;
+pub mod enrichment;
+pub mod export;
+pub mod jobs;
 pub mod model;
 pub mod repository;
 pub mod service;
@@ -10,6 +14,10 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", post(controller::create_transaction))
         .route("/", get(controller::get_transactions))
+        .route("/statement", get(controller::get_statement))
+        .route("/export", post(controller::export_transactions))
+        .route("/exports/:id", get(controller::get_transaction_export))
         .route("/:id", get(controller::get_transaction_by_id))
         .route("/transfer", post(controller::transfer_funds))
+        .route("/transfer/preview", post(controller::preview_transfer))
 }
\ No newline at end of file
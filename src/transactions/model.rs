@@ -1,9 +1,15 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
-
+use sqlx::{types::Json, FromRow};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
 use validator::Validate;
-use crate::shared::types::{AccountId, Amount, Currency, TransactionId};
+use crate::fees::model::FeeBreakdown;
+use crate::fx::model::FxConversion;
+use crate::kyc::model::AccountLimitPosition;
+use crate::scheduled_reports::model::ReportFileFormat;
+use crate::shared::types::{AccountId, Amount, Currency, TenantId, TransactionId};
+use crate::usage::model::ExportFormat;
 
 /// Transaction status enum
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
@@ -24,6 +30,7 @@ pub enum TransactionType {
     Transfer,
     Payment,
     Refund,
+    Interest,
 }
 
 /// Transaction model for database
@@ -44,7 +51,7 @@ pub struct Transaction {
 }
 
 /// Create transaction request
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreateTransactionRequest {
     pub from_account_id: Option<AccountId>,
     pub to_account_id: Option<AccountId>,
@@ -57,18 +64,64 @@ pub struct CreateTransactionRequest {
 }
 
 /// Transfer request
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct TransferRequest {
     pub from_account_id: AccountId,
     pub to_account_id: AccountId,
     #[validate(range(min = 1))]
     pub amount: Amount,
+    #[validate(length(equal = 3))]
     pub currency: Currency,
     pub description: Option<String>,
+    /// FX quote converting the amount into the destination account's
+    /// currency at a locked rate
+    pub quote_id: Option<Uuid>,
+    /// Price an expired quote again at the current rate rather than refuse it
+    #[serde(default)]
+    pub requote: bool,
+}
+
+/// An account's balance before and after a previewed transfer
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BalancePreview {
+    pub account_id: AccountId,
+    pub currency: Currency,
+    pub available_balance: Amount,
+    pub available_balance_after: Amount,
+    /// Available balance less funds locked in savings goals
+    pub spendable_balance: Amount,
+    pub spendable_balance_after: Amount,
+}
+
+/// What a transfer would do if submitted now. Nothing is persisted; the
+/// checks are repeated when the transfer is made.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransferPreview {
+    pub from_account_id: AccountId,
+    pub to_account_id: AccountId,
+    pub amount: Amount,
+    pub currency: Currency,
+    /// Fees the transfer incurs, priced as a bank transfer
+    pub fees: FeeBreakdown,
+    /// Principal plus fees debited from the source account
+    pub total_debit: Amount,
+    /// Amount credited to the destination account, in its currency
+    pub total_credit: Amount,
+    /// The conversion the FX quote prices, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversion: Option<FxConversion>,
+    /// Source account owner's KYC limits and today's usage before the transfer
+    pub limits: AccountLimitPosition,
+    pub source: BalancePreview,
+    pub destination: BalancePreview,
+    /// Whether the transfer would currently be accepted
+    pub allowed: bool,
+    /// Why the transfer would be rejected; empty when allowed
+    pub issues: Vec<String>,
 }
 
 /// Transaction response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TransactionResponse {
     pub id: TransactionId,
     pub from_account_id: Option<AccountId>,
@@ -79,9 +132,20 @@ pub struct TransactionResponse {
     pub status: TransactionStatus,
     pub reference: String,
     pub description: Option<String>,
+    /// Merchant details; absent until the transaction has been enriched
+    #[serde(default)]
+    pub enrichment: Option<TransactionEnrichment>,
+    /// How a transfer converted into the destination account's currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversion: Option<FxConversion>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Transfer metadata recording the conversion an FX quote priced
+pub(crate) fn conversion_metadata(conversion: &FxConversion) -> serde_json::Value {
+    serde_json::json!({ "fx_conversion": conversion })
+}
+
 impl From<Transaction> for TransactionResponse {
     fn from(transaction: Transaction) -> Self {
         Self {
@@ -94,7 +158,316 @@ impl From<Transaction> for TransactionResponse {
             status: transaction.status,
             reference: transaction.reference,
             description: transaction.description,
+            enrichment: None,
+            conversion: transaction
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.get("fx_conversion"))
+                .and_then(|conversion| serde_json::from_value(conversion.clone()).ok()),
             created_at: transaction.created_at,
         }
     }
-}
\ No newline at end of file
+}
+/// Spending category of a merchant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "merchant_category", rename_all = "snake_case")]
+pub enum MerchantCategory {
+    Groceries,
+    Dining,
+    Transport,
+    Shopping,
+    Utilities,
+    Entertainment,
+    Travel,
+    Health,
+    Subscriptions,
+    Cash,
+    Fuel,
+    Other,
+}
+
+/// Whether a merchant was found for a transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "enrichment_status", rename_all = "snake_case")]
+pub enum EnrichmentStatus {
+    Matched,
+    Unmatched,
+}
+
+/// A row of the merchant lookup table
+#[derive(Debug, Clone, FromRow)]
+pub struct MerchantRule {
+    /// Matched against the start of a word in the normalized description
+    pub pattern: String,
+    pub merchant_name: String,
+    pub domain: Option<String>,
+    pub logo_url: Option<String>,
+    pub category: MerchantCategory,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub priority: i32,
+}
+
+/// Where a merchant is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MerchantLocation {
+    pub city: Option<String>,
+    /// State, county or province code
+    pub region: Option<String>,
+    pub country: Option<String>,
+}
+
+/// A merchant resolved from a transaction description
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerchantMatch {
+    pub merchant_name: String,
+    pub logo_url: Option<String>,
+    pub category: MerchantCategory,
+    pub location: Option<MerchantLocation>,
+}
+
+/// Stored enrichment of one transaction
+#[derive(Debug, Clone, FromRow)]
+pub struct EnrichmentRecord {
+    pub transaction_id: TransactionId,
+    pub status: EnrichmentStatus,
+    pub merchant_name: Option<String>,
+    pub logo_url: Option<String>,
+    pub category: Option<MerchantCategory>,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub country: Option<String>,
+    pub provider: String,
+    pub enriched_at: DateTime<Utc>,
+}
+
+impl EnrichmentRecord {
+    pub fn new(transaction_id: TransactionId, merchant: Option<MerchantMatch>, provider: &str) -> Self {
+        let status = if merchant.is_some() {
+            EnrichmentStatus::Matched
+        } else {
+            EnrichmentStatus::Unmatched
+        };
+        let (merchant_name, logo_url, category, location) = match merchant {
+            Some(merchant) => (Some(merchant.merchant_name), merchant.logo_url, Some(merchant.category), merchant.location),
+            None => (None, None, None, None),
+        };
+        let location = location.unwrap_or(MerchantLocation { city: None, region: None, country: None });
+
+        Self {
+            transaction_id,
+            status,
+            merchant_name,
+            logo_url,
+            category,
+            city: location.city,
+            region: location.region,
+            country: location.country,
+            provider: provider.to_string(),
+            enriched_at: Utc::now(),
+        }
+    }
+}
+
+/// Merchant details of a transaction, added shortly after it is created
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransactionEnrichment {
+    pub status: EnrichmentStatus,
+    pub merchant_name: Option<String>,
+    pub logo_url: Option<String>,
+    pub category: Option<MerchantCategory>,
+    pub location: Option<MerchantLocation>,
+    /// Provider that resolved the merchant
+    pub provider: String,
+    pub enriched_at: DateTime<Utc>,
+}
+
+impl From<EnrichmentRecord> for TransactionEnrichment {
+    fn from(record: EnrichmentRecord) -> Self {
+        let location = (record.city.is_some() || record.region.is_some() || record.country.is_some()).then_some(
+            MerchantLocation {
+                city: record.city,
+                region: record.region,
+                country: record.country,
+            },
+        );
+        Self {
+            status: record.status,
+            merchant_name: record.merchant_name,
+            logo_url: record.logo_url,
+            category: record.category,
+            location,
+            provider: record.provider,
+            enriched_at: record.enriched_at,
+        }
+    }
+}
+
+/// A transaction as written to archives and statements, the same whether it
+/// is still in the database or was read back from an archive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TransactionRecord {
+    pub id: TransactionId,
+    pub from_account_id: Option<AccountId>,
+    pub to_account_id: Option<AccountId>,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub transaction_type: String,
+    pub status: String,
+    pub reference: String,
+    pub description: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A transaction as written to export files, with the labels of the
+/// accounts on either side
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct ExportRecord {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub record: TransactionRecord,
+    pub from_account_labels: Vec<String>,
+    pub to_account_labels: Vec<String>,
+}
+
+/// Transactions of one tenant exported to object storage
+#[derive(Debug, Clone, FromRow)]
+pub struct TransactionArchive {
+    pub id: Uuid,
+    pub tenant_id: Option<TenantId>,
+    /// Inclusive
+    pub period_start: DateTime<Utc>,
+    /// Exclusive
+    pub period_end: DateTime<Utc>,
+    pub storage_key: String,
+    pub format: String,
+    pub record_count: i32,
+    /// Hex SHA-256 of the stored object
+    pub checksum: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Statement parameters
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StatementQuery {
+    pub account_id: AccountId,
+    /// First day of the statement (UTC)
+    pub from: NaiveDate,
+    /// Last day of the statement (UTC), inclusive
+    pub to: NaiveDate,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// An account's transactions over a period, archived ones included
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountStatement {
+    pub account_id: AccountId,
+    pub nickname: Option<String>,
+    pub labels: Vec<String>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Oldest first
+    pub transactions: Vec<TransactionRecord>,
+}
+
+/// Which transactions an export holds. Every filter is optional; an empty
+/// filter exports all of the tenant's transactions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransactionExportFilter {
+    /// Transactions to or from this account
+    pub account_id: Option<AccountId>,
+    /// First day to include (UTC)
+    pub from: Option<NaiveDate>,
+    /// Last day to include (UTC), inclusive
+    pub to: Option<NaiveDate>,
+    /// pending, completed, failed or cancelled
+    pub status: Option<String>,
+    /// deposit, withdrawal, transfer, payment, refund or interest
+    pub transaction_type: Option<String>,
+    /// Smallest amount to include, in minor units
+    pub min_amount: Option<Amount>,
+    /// Largest amount to include, in minor units
+    pub max_amount: Option<Amount>,
+}
+
+/// Request to export transactions to a file
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct ExportTransactionsRequest {
+    #[serde(default)]
+    pub filter: TransactionExportFilter,
+    #[serde(default)]
+    pub format: ReportFileFormat,
+    /// Address told where to download the export once it is ready
+    #[validate(email)]
+    pub notify_email: Option<String>,
+    /// HTTP(S) URL sent a `transaction_export.completed` webhook once the
+    /// export is ready
+    #[validate(url, length(max = 2048))]
+    pub webhook_url: Option<String>,
+}
+
+/// Where an export is in its life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "transaction_export_status", rename_all = "snake_case")]
+pub enum TransactionExportStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    /// The file was removed after the retention period
+    Expired,
+}
+
+/// An export of a tenant's transactions to object storage
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TransactionExport {
+    pub id: Uuid,
+    pub tenant_id: TenantId,
+    pub project_id: Option<Uuid>,
+    pub requested_by: Option<Uuid>,
+    #[schema(value_type = TransactionExportFilter)]
+    pub filter: Json<TransactionExportFilter>,
+    pub format: ReportFileFormat,
+    pub status: TransactionExportStatus,
+    pub notify_email: Option<String>,
+    pub webhook_url: Option<String>,
+    #[serde(skip)]
+    pub storage_key: Option<String>,
+    /// Transactions written, once completed
+    pub row_count: Option<i64>,
+    /// File size in bytes, once completed
+    pub size_bytes: Option<i64>,
+    /// Why the export failed
+    pub error: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the file is removed
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An export with a link to download it while it is ready
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransactionExportResponse {
+    #[serde(flatten)]
+    pub export: TransactionExport,
+    /// Time-limited link to the file; present only for completed exports
+    pub download_url: Option<String>,
+    pub download_url_expires_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of one export job pass
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ExportRun {
+    pub completed: usize,
+    pub failed: usize,
+    pub expired: usize,
+}
@@ -1,10 +1,39 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
+use uuid::Uuid;
 
-use crate::core::error::AppResult;
-use crate::shared::{traits::Repository, types::{AccountId, TransactionId}};
-use super::model::{Transaction, TransactionStatus};
+use crate::core::{database::lock_balances, deadline};
+use crate::core::error::{AppError, AppResult};
+use crate::fees::{model::FeeBreakdown, repository::post_transfer_charges_in};
+use crate::fx::{model::FxQuote, repository::use_quote_in, service::QUOTE_TAKEN};
+use crate::goals::repository::locked_goal_balance_in;
+use crate::shared::{account_labels::AccountLabels, traits::Repository, types::{AccountId, TenantId, TransactionId}};
+use super::model::{
+    EnrichmentRecord, ExportRecord, MerchantRule, Transaction, TransactionArchive, TransactionExport,
+    TransactionExportFilter, TransactionRecord, TransactionStatus,
+};
 
+const TRANSACTION_COLUMNS: &str = "id, from_account_id, to_account_id, amount, currency, transaction_type, status,
+    reference, description, metadata, created_at, updated_at";
+
+const ENRICHMENT_COLUMNS: &str = "transaction_id, status, merchant_name, logo_url, category, city, region, country,
+    provider, enriched_at";
+
+const RECORD_COLUMNS: &str = "id, from_account_id, to_account_id, amount, COALESCE(currency, 'USD') AS currency,
+    transaction_type::TEXT AS transaction_type, COALESCE(status::TEXT, 'pending') AS status, reference, description,
+    metadata, created_at, updated_at";
+
+const ARCHIVE_COLUMNS: &str = "id, tenant_id, period_start, period_end, storage_key, format, record_count, checksum,
+    created_at";
+
+const EXPORT_COLUMNS: &str = "id, tenant_id, project_id, requested_by, filter, format, status, notify_email, webhook_url,
+    storage_key, row_count, size_bytes, error, started_at, completed_at, expires_at, created_at, updated_at";
+
+/// Only transactions that can no longer change are archived
+const ARCHIVABLE: &str = "status IN ('completed', 'failed', 'cancelled')";
+
+#[derive(Clone)]
 pub struct TransactionRepository {
     pool: PgPool,
 }
@@ -40,6 +69,554 @@ impl TransactionRepository {
         Ok(Vec::new())
     }
 
+    /// Whether an account belongs to a tenant
+    pub async fn account_in_tenant(&self, account_id: AccountId, tenant_id: TenantId) -> AppResult<bool> {
+        let exists = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND tenant_id = $2)",
+        )
+        .bind(account_id)
+        .bind(tenant_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// An account's nickname and labels, if it belongs to the tenant
+    pub async fn find_account_labels(
+        &self,
+        account_id: AccountId,
+        tenant_id: TenantId,
+    ) -> AppResult<Option<AccountLabels>> {
+        let labels = sqlx::query_as::<_, AccountLabels>(
+            "SELECT nickname, labels FROM accounts WHERE id = $1 AND tenant_id = $2",
+        )
+        .bind(account_id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(labels)
+    }
+
+    /// The merchant lookup table
+    pub async fn find_merchant_rules(&self) -> AppResult<Vec<MerchantRule>> {
+        let rules = sqlx::query_as::<_, MerchantRule>(
+            "SELECT pattern, merchant_name, domain, logo_url, category, city, country, priority FROM merchant_rules",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    /// The oldest transactions without an enrichment, with their descriptions
+    pub async fn find_unenriched(&self, limit: i64) -> AppResult<Vec<(TransactionId, Option<String>)>> {
+        let transactions = sqlx::query_as(
+            "SELECT t.id, t.description FROM transactions t
+             WHERE NOT EXISTS (SELECT 1 FROM transaction_enrichments e WHERE e.transaction_id = t.id)
+             ORDER BY t.created_at
+             LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(transactions)
+    }
+
+    /// Store a transaction's enrichment, replacing any earlier one
+    pub async fn save_enrichment(&self, record: &EnrichmentRecord) -> AppResult<()> {
+        sqlx::query(&format!(
+            "INSERT INTO transaction_enrichments ({ENRICHMENT_COLUMNS})
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             ON CONFLICT (transaction_id) DO UPDATE SET
+                 status = EXCLUDED.status, merchant_name = EXCLUDED.merchant_name, logo_url = EXCLUDED.logo_url,
+                 category = EXCLUDED.category, city = EXCLUDED.city, region = EXCLUDED.region,
+                 country = EXCLUDED.country, provider = EXCLUDED.provider, enriched_at = EXCLUDED.enriched_at"
+        ))
+        .bind(record.transaction_id)
+        .bind(record.status)
+        .bind(&record.merchant_name)
+        .bind(&record.logo_url)
+        .bind(record.category)
+        .bind(&record.city)
+        .bind(&record.region)
+        .bind(&record.country)
+        .bind(&record.provider)
+        .bind(record.enriched_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Enrichments of the given transactions, for those that have one
+    pub async fn find_enrichments(&self, transaction_ids: &[TransactionId]) -> AppResult<Vec<EnrichmentRecord>> {
+        let records = sqlx::query_as::<_, EnrichmentRecord>(&format!(
+            "SELECT {ENRICHMENT_COLUMNS} FROM transaction_enrichments WHERE transaction_id = ANY($1)"
+        ))
+        .bind(transaction_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Tenants and UTC months holding transactions created before `cutoff`
+    /// that can be archived, oldest month first
+    pub async fn find_archivable_periods(
+        &self,
+        cutoff: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<(Option<TenantId>, DateTime<Utc>)>> {
+        let periods = sqlx::query_as(&format!(
+            "SELECT tenant_id, date_trunc('month', created_at AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS month
+             FROM transactions
+             WHERE created_at < $1 AND {ARCHIVABLE}
+             GROUP BY 1, 2
+             ORDER BY 2, 1
+             LIMIT $2"
+        ))
+        .bind(cutoff)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(periods)
+    }
+
+    /// The oldest archivable transactions of a tenant created in `[start, end)`
+    pub async fn find_archivable(
+        &self,
+        tenant_id: Option<TenantId>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<TransactionRecord>> {
+        let records = sqlx::query_as::<_, TransactionRecord>(&format!(
+            "SELECT {RECORD_COLUMNS} FROM transactions
+             WHERE tenant_id IS NOT DISTINCT FROM $1 AND created_at >= $2 AND created_at < $3 AND {ARCHIVABLE}
+             ORDER BY created_at, id
+             LIMIT $4"
+        ))
+        .bind(tenant_id)
+        .bind(start)
+        .bind(end)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Record an archive and remove its transactions from the table. Nothing
+    /// is changed, and `false` returned, when any of them was updated or
+    /// removed since it was read, as the archive no longer matches.
+    pub async fn record_archive(&self, archive: &TransactionArchive, records: &[TransactionRecord]) -> AppResult<bool> {
+        let ids: Vec<TransactionId> = records.iter().map(|record| record.id).collect();
+        let updated_at: Vec<Option<DateTime<Utc>>> = records.iter().map(|record| record.updated_at).collect();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(&format!(
+            "INSERT INTO transaction_archives ({ARCHIVE_COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+        ))
+        .bind(archive.id)
+        .bind(archive.tenant_id)
+        .bind(archive.period_start)
+        .bind(archive.period_end)
+        .bind(&archive.storage_key)
+        .bind(&archive.format)
+        .bind(archive.record_count)
+        .bind(&archive.checksum)
+        .bind(archive.created_at)
+        .execute(&mut *tx)
+        .await?;
+
+        let deleted = sqlx::query(
+            "DELETE FROM transactions t
+             USING UNNEST($1::UUID[], $2::TIMESTAMPTZ[]) AS archived(id, updated_at)
+             WHERE t.id = archived.id AND t.updated_at IS NOT DISTINCT FROM archived.updated_at
+               AND t.created_at >= $3 AND t.created_at < $4",
+        )
+        .bind(&ids)
+        .bind(&updated_at)
+        .bind(archive.period_start)
+        .bind(archive.period_end)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if deleted != ids.len() as u64 {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query("UPDATE transaction_keys SET archive_id = $1 WHERE id = ANY($2)")
+            .bind(archive.id)
+            .bind(&ids)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// A tenant's archives that may hold transactions created in `[start, end)`
+    pub async fn find_archives(
+        &self,
+        tenant_id: TenantId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> AppResult<Vec<TransactionArchive>> {
+        let archives = sqlx::query_as::<_, TransactionArchive>(&format!(
+            "SELECT {ARCHIVE_COLUMNS} FROM transaction_archives
+             WHERE tenant_id = $1 AND period_start < $3 AND period_end > $2
+             ORDER BY period_start"
+        ))
+        .bind(tenant_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(archives)
+    }
+
+    /// A tenant's transactions to or from an account created in `[start, end)`
+    /// that have not been archived, oldest first
+    pub async fn find_for_statement(
+        &self,
+        tenant_id: TenantId,
+        account_id: AccountId,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> AppResult<Vec<TransactionRecord>> {
+        let records = sqlx::query_as::<_, TransactionRecord>(&format!(
+            "SELECT {RECORD_COLUMNS} FROM transactions
+             WHERE tenant_id = $1 AND (from_account_id = $2 OR to_account_id = $2)
+               AND created_at >= $3 AND created_at < $4
+             ORDER BY created_at, id"
+        ))
+        .bind(tenant_id)
+        .bind(account_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// One page of a tenant's transactions matching an export filter, created
+    /// in `[start, end)` and after the `after` position, oldest first
+    pub async fn find_for_export(
+        &self,
+        tenant_id: TenantId,
+        filter: &TransactionExportFilter,
+        start: Option<DateTime<Utc>>,
+        end: DateTime<Utc>,
+        after: Option<(DateTime<Utc>, TransactionId)>,
+        limit: i64,
+    ) -> AppResult<Vec<ExportRecord>> {
+        let records = sqlx::query_as::<_, ExportRecord>(&format!(
+            "SELECT {RECORD_COLUMNS},
+                 COALESCE((SELECT a.labels FROM accounts a
+                           WHERE a.id = t.from_account_id AND a.tenant_id = t.tenant_id), '{{}}') AS from_account_labels,
+                 COALESCE((SELECT a.labels FROM accounts a
+                           WHERE a.id = t.to_account_id AND a.tenant_id = t.tenant_id), '{{}}') AS to_account_labels
+             FROM transactions t
+             WHERE tenant_id = $1
+               AND ($2::UUID IS NULL OR from_account_id = $2 OR to_account_id = $2)
+               AND ($3::TIMESTAMPTZ IS NULL OR created_at >= $3) AND created_at < $4
+               AND ($5::TEXT IS NULL OR COALESCE(status::TEXT, 'pending') = $5)
+               AND ($6::TEXT IS NULL OR transaction_type::TEXT = $6)
+               AND ($7::BIGINT IS NULL OR amount >= $7)
+               AND ($8::BIGINT IS NULL OR amount <= $8)
+               AND ($9::TIMESTAMPTZ IS NULL OR (created_at, id) > ($9, $10))
+             ORDER BY created_at, id
+             LIMIT $11"
+        ))
+        .bind(tenant_id)
+        .bind(filter.account_id)
+        .bind(start)
+        .bind(end)
+        .bind(&filter.status)
+        .bind(&filter.transaction_type)
+        .bind(filter.min_amount)
+        .bind(filter.max_amount)
+        .bind(after.map(|(created_at, _)| created_at))
+        .bind(after.map(|(_, id)| id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn create_export(&self, export: &TransactionExport) -> AppResult<TransactionExport> {
+        let created = sqlx::query_as::<_, TransactionExport>(&format!(
+            "INSERT INTO transaction_exports (id, tenant_id, project_id, requested_by, filter, format, notify_email,
+                 webhook_url)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {EXPORT_COLUMNS}"
+        ))
+        .bind(export.id)
+        .bind(export.tenant_id)
+        .bind(export.project_id)
+        .bind(export.requested_by)
+        .bind(&export.filter)
+        .bind(export.format)
+        .bind(&export.notify_email)
+        .bind(&export.webhook_url)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn find_export(&self, export_id: Uuid, tenant_id: TenantId) -> AppResult<Option<TransactionExport>> {
+        let export = sqlx::query_as::<_, TransactionExport>(&format!(
+            "SELECT {EXPORT_COLUMNS} FROM transaction_exports WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(export_id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(export)
+    }
+
+    /// Take queued exports, and running ones whose worker has not reported
+    /// progress since `stale_before`, marking them running
+    pub async fn claim_exports(
+        &self,
+        now: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<TransactionExport>> {
+        let exports = sqlx::query_as::<_, TransactionExport>(&format!(
+            "UPDATE transaction_exports
+             SET status = 'running', started_at = $1, updated_at = $1
+             WHERE id IN (
+                 SELECT id FROM transaction_exports
+                 WHERE status = 'queued' OR (status = 'running' AND updated_at < $2)
+                 ORDER BY created_at
+                 LIMIT $3
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {EXPORT_COLUMNS}"
+        ))
+        .bind(now)
+        .bind(stale_before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(exports)
+    }
+
+    /// Record that a running export is still making progress
+    pub async fn touch_export(&self, export_id: Uuid) -> AppResult<()> {
+        sqlx::query("UPDATE transaction_exports SET updated_at = NOW() WHERE id = $1 AND status = 'running'")
+            .bind(export_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn complete_export(
+        &self,
+        export_id: Uuid,
+        storage_key: &str,
+        row_count: i64,
+        size_bytes: i64,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<TransactionExport> {
+        let export = sqlx::query_as::<_, TransactionExport>(&format!(
+            "UPDATE transaction_exports
+             SET status = 'completed', storage_key = $2, row_count = $3, size_bytes = $4, expires_at = $5,
+                 error = NULL, completed_at = NOW(), updated_at = NOW()
+             WHERE id = $1
+             RETURNING {EXPORT_COLUMNS}"
+        ))
+        .bind(export_id)
+        .bind(storage_key)
+        .bind(row_count)
+        .bind(size_bytes)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(export)
+    }
+
+    pub async fn fail_export(&self, export_id: Uuid, error: &str) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE transaction_exports
+             SET status = 'failed', error = $2, completed_at = NOW(), updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(export_id)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Completed exports whose file is past its retention
+    pub async fn find_expired_exports(&self, now: DateTime<Utc>, limit: i64) -> AppResult<Vec<TransactionExport>> {
+        let exports = sqlx::query_as::<_, TransactionExport>(&format!(
+            "SELECT {EXPORT_COLUMNS} FROM transaction_exports
+             WHERE status = 'completed' AND expires_at <= $1
+             ORDER BY expires_at
+             LIMIT $2"
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(exports)
+    }
+
+    pub async fn expire_export(&self, export_id: Uuid) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE transaction_exports SET status = 'expired', storage_key = NULL, updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(export_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Post a transfer: record it as completed and move the money between
+    /// the two accounts in one database transaction. Both balances are
+    /// locked first and the source's available balance, less what its
+    /// locked savings goals hold, checked again, so transfers racing for the
+    /// same funds cannot overdraw it. With an FX
+    /// quote the destination is credited the quote's converted amount, and
+    /// the quote is used up in the same transaction. The fees are debited
+    /// from the source on top of the amount and booked with the transfer.
+    pub async fn post_transfer(
+        &self,
+        transaction: &Transaction,
+        tenant_id: TenantId,
+        quote: Option<&FxQuote>,
+        fees: &FeeBreakdown,
+    ) -> AppResult<Transaction> {
+        let (Some(from_account_id), Some(to_account_id)) = (transaction.from_account_id, transaction.to_account_id)
+        else {
+            return Err(AppError::Validation("A transfer needs a source and a destination account".to_string()));
+        };
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let available = lock_balances(&mut tx, &[from_account_id, to_account_id])
+            .await?
+            .into_iter()
+            .find(|(account_id, _)| *account_id == from_account_id)
+            .map(|(_, available)| available)
+            .ok_or_else(|| AppError::NotFound("Source balance not found".to_string()))?;
+        let total_debit = transaction.amount + fees.total;
+        if available < total_debit {
+            return Err(AppError::BadRequest("Insufficient funds".to_string()));
+        }
+        let locked = locked_goal_balance_in(&mut tx, from_account_id).await?;
+        if available - locked < total_debit {
+            return Err(AppError::BadRequest(format!(
+                "Insufficient spendable balance: {} of the available balance is locked in savings goals",
+                locked
+            )));
+        }
+        let credit = match quote {
+            Some(quote) => {
+                use_quote_in(&mut tx, quote.id)
+                    .await?
+                    .ok_or_else(|| AppError::Conflict(QUOTE_TAKEN.to_string()))?
+                    .target_amount
+            }
+            None => transaction.amount,
+        };
+
+        let posted = sqlx::query_as::<_, Transaction>(&format!(
+            "INSERT INTO transactions (id, from_account_id, to_account_id, amount, currency, transaction_type, status,
+                                       reference, description, metadata, tenant_id, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+             RETURNING {TRANSACTION_COLUMNS}"
+        ))
+        .bind(transaction.id)
+        .bind(from_account_id)
+        .bind(to_account_id)
+        .bind(transaction.amount)
+        .bind(&transaction.currency)
+        .bind(&transaction.transaction_type)
+        .bind(TransactionStatus::Completed)
+        .bind(&transaction.reference)
+        .bind(&transaction.description)
+        .bind(&transaction.metadata)
+        .bind(tenant_id)
+        .bind(transaction.created_at)
+        .bind(transaction.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let source_ledger = sqlx::query_scalar::<_, i64>(
+            "UPDATE balances
+             SET available_balance = available_balance - $1, ledger_balance = ledger_balance - $1, updated_at = NOW()
+             WHERE account_id = $2
+             RETURNING ledger_balance",
+        )
+        .bind(total_debit)
+        .bind(from_account_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let destination_ledger = sqlx::query_scalar::<_, i64>(
+            "INSERT INTO balances (account_id, available_balance, ledger_balance, currency)
+             VALUES ($1, $2, $2, (SELECT currency FROM accounts WHERE id = $1))
+             ON CONFLICT (account_id) DO UPDATE
+             SET available_balance = balances.available_balance + EXCLUDED.available_balance,
+                 ledger_balance = balances.ledger_balance + EXCLUDED.ledger_balance,
+                 updated_at = NOW()
+             RETURNING ledger_balance",
+        )
+        .bind(to_account_id)
+        .bind(credit)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let description = format!("Transfer {}", posted.reference);
+        let fee_description = format!("Fees on transfer {}", posted.reference);
+        let mut postings = vec![
+            (from_account_id, source_ledger + total_debit, -posted.amount, &description),
+            (to_account_id, destination_ledger - credit, credit, &description),
+        ];
+        if fees.total > 0 {
+            postings.push((from_account_id, source_ledger + fees.total, -fees.total, &fee_description));
+        }
+        for (account_id, balance_before, amount_changed, description) in postings {
+            sqlx::query(
+                "INSERT INTO balance_history
+                    (account_id, balance_before, balance_after, amount_changed, transaction_id, description)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(account_id)
+            .bind(balance_before)
+            .bind(balance_before + amount_changed)
+            .bind(amount_changed)
+            .bind(posted.id)
+            .bind(description)
+            .execute(&mut *tx)
+            .await?;
+        }
+        post_transfer_charges_in(&mut tx, posted.id, from_account_id, fees).await?;
+
+        tx.commit().await?;
+        Ok(posted)
+    }
+
     /// Update transaction status
     pub async fn update_status(
         &self,
@@ -61,9 +638,32 @@ impl TransactionRepository {
 
 #[async_trait]
 impl Repository<Transaction, TransactionId> for TransactionRepository {
+    /// Record a transaction without moving any money, under the tenant of
+    /// the accounts it names
     async fn create(&self, transaction: Transaction) -> AppResult<Transaction> {
-        // TODO: Implement transaction creation
-        Ok(transaction)
+        let created = sqlx::query_as::<_, Transaction>(&format!(
+            "INSERT INTO transactions (id, from_account_id, to_account_id, amount, currency, transaction_type, status,
+                                       reference, description, metadata, tenant_id, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+                     (SELECT tenant_id FROM accounts WHERE id = COALESCE($2, $3)), $11, $12)
+             RETURNING {TRANSACTION_COLUMNS}"
+        ))
+        .bind(transaction.id)
+        .bind(transaction.from_account_id)
+        .bind(transaction.to_account_id)
+        .bind(transaction.amount)
+        .bind(&transaction.currency)
+        .bind(&transaction.transaction_type)
+        .bind(&transaction.status)
+        .bind(&transaction.reference)
+        .bind(&transaction.description)
+        .bind(&transaction.metadata)
+        .bind(transaction.created_at)
+        .bind(transaction.updated_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
     }
 
     async fn find_by_id(&self, id: TransactionId) -> AppResult<Option<Transaction>> {
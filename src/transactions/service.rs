@@ -1,26 +1,55 @@
 use uuid::Uuid;
 use chrono::Utc;
+use crate::account_controls::{model::AccountKind, service::AccountFreezeGuard};
+use crate::core::database::retry_transaction;
 use crate::core::error::{AppError, AppResult};
-use crate::shared::{traits::Repository, types::{AccountId, TransactionId}};
+use crate::fees::service::FeeEngine;
+use crate::fx::model::{FxConversion, FxQuote};
+use crate::goals::{model::GoalBalanceSummary, service::GoalBalanceGuard};
+use crate::kyc::service::KycPolicyService;
+use crate::metadata_schemas::{model::MetadataTarget, service::MetadataSchemaGuard};
+use crate::payments::model::PaymentMethod;
+use crate::shared::{traits::Repository, types::{AccountId, Amount, TenantId, TransactionId}};
 use super::model::{
     Transaction, TransactionResponse, CreateTransactionRequest, 
-    TransferRequest, TransactionStatus, TransactionType
+    TransferRequest, TransactionStatus, TransactionType, BalancePreview, TransferPreview, conversion_metadata
 };
 use super::repository::TransactionRepository;
 
 pub struct TransactionService {
     repository: TransactionRepository,
+    freeze_guard: AccountFreezeGuard,
+    kyc_policy: KycPolicyService,
+    goal_guard: GoalBalanceGuard,
+    fee_engine: FeeEngine,
+    metadata_guard: MetadataSchemaGuard,
 }
 
 impl TransactionService {
-    pub fn new(repository: TransactionRepository) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: TransactionRepository,
+        freeze_guard: AccountFreezeGuard,
+        kyc_policy: KycPolicyService,
+        goal_guard: GoalBalanceGuard,
+        fee_engine: FeeEngine,
+        metadata_guard: MetadataSchemaGuard,
+    ) -> Self {
+        Self {
+            repository,
+            freeze_guard,
+            kyc_policy,
+            goal_guard,
+            fee_engine,
+            metadata_guard,
+        }
     }
 
-    /// Create a new transaction
+    /// Create a new transaction. Metadata must match the project's
+    /// transaction metadata schema, if it has one.
     pub async fn create_transaction(
         &self,
         request: CreateTransactionRequest,
+        project_id: Option<Uuid>,
     ) -> AppResult<TransactionResponse> {
         // TODO: Implement transaction creation logic
         // 1. Validate request data
@@ -28,7 +57,20 @@ impl TransactionService {
         // 3. Create transaction entity
         // 4. Save to database
         // 5. Process transaction (update balances, etc.)
-        
+
+        if let Some(project_id) = project_id {
+            self.metadata_guard
+                .ensure_valid(project_id, MetadataTarget::Transaction, request.metadata.as_ref())
+                .await?;
+        }
+        self.ensure_can_move(request.from_account_id, request.to_account_id, request.amount)
+            .await?;
+        if let Some(from_account_id) = request.from_account_id {
+            self.goal_guard
+                .ensure_spendable(from_account_id, request.amount)
+                .await?;
+        }
+
         let now = Utc::now();
         let transaction = Transaction {
             id: Uuid::new_v4(),
@@ -49,29 +91,172 @@ impl TransactionService {
         Ok(TransactionResponse::from(created_transaction))
     }
 
-    /// Transfer funds between accounts
+    /// Transfer funds between two of the tenant's accounts: the transfer is
+    /// recorded as completed and both balances move at once. With an FX
+    /// quote, already checked for the amount, the destination account must
+    /// hold the quote's target currency and is credited the converted
+    /// amount; the quote is used up only if the transfer is posted, and the
+    /// transfer records the conversion. The source is charged the project's
+    /// bank transfer fees on top of the amount, as the preview shows.
     pub async fn transfer_funds(
         &self,
         request: TransferRequest,
+        tenant_id: TenantId,
+        project_id: Option<Uuid>,
+        quote: Option<&FxQuote>,
     ) -> AppResult<TransactionResponse> {
-        // TODO: Implement fund transfer logic
-        // 1. Validate accounts exist
-        // 2. Check sufficient balance in source account
-        // 3. Create transfer transaction
-        // 4. Update account balances
-        // 5. Return transaction details
-        
-        let create_request = CreateTransactionRequest {
+        if request.from_account_id == request.to_account_id {
+            return Err(AppError::Validation("Cannot transfer to the same account".to_string()));
+        }
+        for account_id in [request.from_account_id, request.to_account_id] {
+            if !self.repository.account_in_tenant(account_id, tenant_id).await? {
+                return Err(AppError::NotFound("Account not found".to_string()));
+            }
+        }
+        let source = self.goal_guard.balance_summary(request.from_account_id).await?;
+        let destination = self.goal_guard.balance_summary(request.to_account_id).await?;
+        if source.currency != request.currency {
+            return Err(AppError::Validation(format!(
+                "Account {} holds {}, not {}",
+                source.account_id, source.currency, request.currency
+            )));
+        }
+        match quote {
+            Some(quote) if destination.currency != quote.target_currency => {
+                return Err(AppError::Validation(format!(
+                    "Account {} holds {}, but the FX quote converts into {}",
+                    destination.account_id, destination.currency, quote.target_currency
+                )));
+            }
+            None if destination.currency != request.currency => {
+                return Err(AppError::Validation(format!(
+                    "Account {} holds {}, not {}; convert with an FX quote",
+                    destination.account_id, destination.currency, request.currency
+                )));
+            }
+            _ => {}
+        }
+        let fees = self
+            .fee_engine
+            .calculate(project_id, &PaymentMethod::BankTransfer, &request.currency, request.amount)
+            .await?;
+        let total_debit = request.amount + fees.total;
+        self.ensure_can_move(Some(request.from_account_id), Some(request.to_account_id), total_debit)
+            .await?;
+
+        let now = Utc::now();
+        let transaction = Transaction {
+            id: Uuid::new_v4(),
             from_account_id: Some(request.from_account_id),
             to_account_id: Some(request.to_account_id),
             amount: request.amount,
             currency: request.currency,
             transaction_type: TransactionType::Transfer,
+            status: TransactionStatus::Pending,
+            reference: format!("TXN_{}", Uuid::new_v4()),
             description: request.description,
-            metadata: None,
+            metadata: quote.map(|quote| conversion_metadata(&FxConversion::from(quote))),
+            created_at: now,
+            updated_at: now,
         };
+        let posted = retry_transaction(|| self.repository.post_transfer(&transaction, tenant_id, quote, &fees)).await?;
+
+        // Completed credits feed the receiving account's savings goal rules
+        let credit = quote.map_or(posted.amount, |quote| quote.target_amount);
+        self.goal_guard
+            .allocate_inbound_credit(request.to_account_id, credit, posted.id)
+            .await?;
+        Ok(TransactionResponse::from(posted))
+    }
 
-        self.create_transaction(create_request).await
+    /// Run a transfer's checks and work out its fees, limits and resulting
+    /// balances without moving any money. Checks that would reject the
+    /// transfer are reported as issues rather than errors, so a client can
+    /// show them on its confirmation screen. An FX quote is checked but not
+    /// used up.
+    pub async fn preview_transfer(
+        &self,
+        request: TransferRequest,
+        tenant_id: TenantId,
+        project_id: Option<Uuid>,
+        quote: Option<&FxQuote>,
+    ) -> AppResult<TransferPreview> {
+        if request.from_account_id == request.to_account_id {
+            return Err(AppError::Validation("Cannot transfer to the same account".to_string()));
+        }
+        for account_id in [request.from_account_id, request.to_account_id] {
+            if !self.repository.account_in_tenant(account_id, tenant_id).await? {
+                return Err(AppError::NotFound("Account not found".to_string()));
+            }
+        }
+
+        let fees = self
+            .fee_engine
+            .calculate(project_id, &PaymentMethod::BankTransfer, &request.currency, request.amount)
+            .await?;
+        let total_debit = request.amount + fees.total;
+        let source = self.goal_guard.balance_summary(request.from_account_id).await?;
+        let destination = self.goal_guard.balance_summary(request.to_account_id).await?;
+        let limits = self.kyc_policy.limit_position(request.from_account_id).await?;
+
+        let mut issues = Vec::new();
+        // Without an FX quote both accounts must hold the transfer currency
+        let credit_currency = quote.map_or(request.currency.as_str(), |quote| quote.target_currency.as_str());
+        for (summary, currency) in [(&source, request.currency.as_str()), (&destination, credit_currency)] {
+            if summary.currency != currency {
+                issues.push(match quote {
+                    Some(_) => format!("Account {} holds {}, not {}", summary.account_id, summary.currency, currency),
+                    None => format!(
+                        "Account {} holds {}, not {}; convert with an FX quote",
+                        summary.account_id, summary.currency, currency
+                    ),
+                });
+            }
+        }
+        if let Some(quote) = quote {
+            issues.extend(quote.mismatch(&request.currency, request.amount));
+            if quote.used_at.is_some() {
+                issues.push("FX quote has already been used".to_string());
+            } else if quote.is_expired(Utc::now()) && !request.requote {
+                issues.push("FX quote has expired".to_string());
+            }
+        }
+        let total_credit = quote.map_or(request.amount, |quote| quote.target_amount);
+        record_issue(&mut issues, self.freeze_guard.ensure_can_debit(request.from_account_id).await)?;
+        record_issue(
+            &mut issues,
+            self.freeze_guard
+                .ensure_can_credit(AccountKind::Account, request.to_account_id)
+                .await,
+        )?;
+        record_issue(
+            &mut issues,
+            self.kyc_policy
+                .ensure_within_limits(request.from_account_id, total_debit)
+                .await,
+        )?;
+        record_issue(
+            &mut issues,
+            self.goal_guard
+                .ensure_spendable(request.from_account_id, total_debit)
+                .await,
+        )?;
+
+        Ok(TransferPreview {
+            from_account_id: request.from_account_id,
+            to_account_id: request.to_account_id,
+            amount: request.amount,
+            currency: request.currency,
+            fees,
+            total_debit,
+            total_credit,
+            conversion: quote.map(FxConversion::from),
+            limits,
+            source: balance_preview(source, -total_debit),
+            destination: balance_preview(destination, total_credit),
+            allowed: issues.is_empty(),
+            issues,
+        })
     }
 
     /// Get transaction by ID
@@ -79,7 +264,8 @@ impl TransactionService {
         let transaction = self.repository.find_by_id(transaction_id).await?
             .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?;
 
-        Ok(TransactionResponse::from(transaction))
+        let mut responses = self.with_enrichments(vec![transaction]).await?;
+        Ok(responses.remove(0))
     }
 
     /// Get transactions for account
@@ -90,7 +276,28 @@ impl TransactionService {
         limit: u32,
     ) -> AppResult<Vec<TransactionResponse>> {
         let transactions = self.repository.find_by_account_id(account_id, page, limit).await?;
-        Ok(transactions.into_iter().map(TransactionResponse::from).collect())
+        self.with_enrichments(transactions).await
+    }
+
+    /// Responses for transactions, with the merchant details of those
+    /// already enriched
+    async fn with_enrichments(&self, transactions: Vec<Transaction>) -> AppResult<Vec<TransactionResponse>> {
+        let ids: Vec<TransactionId> = transactions.iter().map(|transaction| transaction.id).collect();
+        let mut enrichments = self.repository.find_enrichments(&ids).await?;
+
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| {
+                let enrichment = enrichments
+                    .iter()
+                    .position(|record| record.transaction_id == transaction.id)
+                    .map(|index| enrichments.swap_remove(index).into());
+                TransactionResponse {
+                    enrichment,
+                    ..TransactionResponse::from(transaction)
+                }
+            })
+            .collect())
     }
 
     /// Update transaction status
@@ -99,6 +306,64 @@ impl TransactionService {
         transaction_id: TransactionId,
         status: TransactionStatus,
     ) -> AppResult<()> {
-        self.repository.update_status(transaction_id, status).await
+        let completed = matches!(status, TransactionStatus::Completed);
+        self.repository.update_status(transaction_id, status).await?;
+
+        // Completed credits feed the receiving account's savings goal rules
+        if completed {
+            if let Some(transaction) = self.repository.find_by_id(transaction_id).await? {
+                if let Some(to_account_id) = transaction.to_account_id {
+                    self.goal_guard
+                        .allocate_inbound_credit(to_account_id, transaction.amount, transaction.id)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks every movement of money must pass: neither account frozen,
+    /// and the debit within the owner's KYC limits
+    async fn ensure_can_move(
+        &self,
+        from_account_id: Option<AccountId>,
+        to_account_id: Option<AccountId>,
+        amount: Amount,
+    ) -> AppResult<()> {
+        if let Some(from_account_id) = from_account_id {
+            self.freeze_guard.ensure_can_debit(from_account_id).await?;
+            self.kyc_policy
+                .ensure_within_limits(from_account_id, amount)
+                .await?;
+        }
+        if let Some(to_account_id) = to_account_id {
+            self.freeze_guard
+                .ensure_can_credit(AccountKind::Account, to_account_id)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Keep the reason a check rejected a transfer; other errors still fail
+fn record_issue(issues: &mut Vec<String>, result: AppResult<()>) -> AppResult<()> {
+    match result {
+        Ok(()) => Ok(()),
+        Err(AppError::BadRequest(reason) | AppError::Authorization(reason)) => {
+            issues.push(reason);
+            Ok(())
+        }
+        Err(e) => Err(e),
     }
-}
\ No newline at end of file
+}
+
+fn balance_preview(summary: GoalBalanceSummary, change: Amount) -> BalancePreview {
+    BalancePreview {
+        account_id: summary.account_id,
+        currency: summary.currency,
+        available_balance: summary.available_balance,
+        available_balance_after: summary.available_balance + change,
+        spendable_balance: summary.spendable_balance,
+        spendable_balance_after: summary.spendable_balance + change,
+    }
+}
@@ -0,0 +1,141 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::AppResult,
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{FreezePositionsRequest, PositionsQuery, TreasuryPositions};
+use super::repository::TreasuryRepository;
+use super::service::TreasuryService;
+
+pub(crate) fn treasury_service(state: &AppState) -> TreasuryService {
+    TreasuryService::new(
+        TreasuryRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Net flows per currency and movements per GL account for a business date
+/// (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/treasury/positions",
+    tag = "treasury",
+    params(PositionsQuery),
+    responses(
+        (status = 200, description = "Live positions, or the frozen end-of-day snapshot", body = TreasuryPositions),
+        (status = 403, description = "Caller lacks the treasury permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_positions(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<PositionsQuery>,
+) -> AppResult<Json<ApiResponse<TreasuryPositions>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_treasury(), ip, "treasury_positions".to_string())
+        .await?;
+
+    let positions = treasury_service(&state)
+        .positions(query.business_date, query.currency)
+        .await?;
+    Ok(Json(ApiResponse::success("Treasury positions retrieved successfully", positions)))
+}
+
+/// Snapshots taken of a business date's positions (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/treasury/positions/snapshots",
+    tag = "treasury",
+    params(PositionsQuery),
+    responses(
+        (status = 200, description = "Snapshots, oldest first", body = [TreasuryPositions]),
+        (status = 403, description = "Caller lacks the treasury permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_position_snapshots(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<PositionsQuery>,
+) -> AppResult<Json<ApiResponse<Vec<TreasuryPositions>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_treasury(), ip, "treasury_snapshots".to_string())
+        .await?;
+
+    let snapshots = treasury_service(&state)
+        .list_snapshots(query.business_date, query.currency)
+        .await?;
+    Ok(Json(ApiResponse::success("Treasury snapshots retrieved successfully", snapshots)))
+}
+
+/// Snapshot today's positions so far (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/treasury/positions/snapshots",
+    tag = "treasury",
+    responses(
+        (status = 201, description = "Snapshot taken", body = TreasuryPositions),
+        (status = 403, description = "Caller lacks the treasury permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn take_position_snapshot(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+) -> AppResult<(StatusCode, Json<ApiResponse<TreasuryPositions>>)> {
+    state
+        .authorize(claims.developer_id, permissions::manage_treasury(), ip, "treasury_snapshots".to_string())
+        .await?;
+
+    let snapshot = treasury_service(&state).take_snapshot(claims.developer_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Treasury snapshot taken successfully", snapshot)),
+    ))
+}
+
+/// Close a business date with its end-of-day snapshot (admin only). Flows
+/// arriving for it afterwards count towards the next open date.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/treasury/positions/freeze",
+    tag = "treasury",
+    request_body = FreezePositionsRequest,
+    responses(
+        (status = 201, description = "Positions frozen", body = TreasuryPositions),
+        (status = 400, description = "Business date is in the future"),
+        (status = 403, description = "Caller lacks the treasury permission"),
+        (status = 409, description = "Business date already frozen")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn freeze_positions(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<FreezePositionsRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<TreasuryPositions>>)> {
+    state
+        .authorize(claims.developer_id, permissions::manage_treasury(), ip, "treasury_positions".to_string())
+        .await?;
+
+    let snapshot = treasury_service(&state)
+        .freeze(request.business_date, claims.developer_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Treasury positions frozen successfully", snapshot)),
+    ))
+}
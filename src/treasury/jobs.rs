@@ -0,0 +1,27 @@
+use tokio::sync::broadcast::error::RecvError;
+use crate::core::AppState;
+use super::controller::treasury_service;
+
+/// Keep treasury flows up to date with the money domain events move. Runs
+/// for the life of the process; events published while it lags behind are
+/// skipped and logged.
+pub fn spawn_position_job(state: AppState) {
+    let mut events = state.event_bus.subscribe();
+
+    tokio::spawn(async move {
+        let service = treasury_service(&state);
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if let Err(e) = service.record(&event).await {
+                        tracing::error!(event_id = %event.id, "Failed to record treasury flow of {} event: {}", event.event_type.as_str(), e);
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Treasury position job fell behind and skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
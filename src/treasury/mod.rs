@@ -0,0 +1,18 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/treasury/positions", get(controller::get_positions))
+        .route(
+            "/treasury/positions/snapshots",
+            get(controller::list_position_snapshots).post(controller::take_position_snapshot),
+        )
+        .route("/treasury/positions/freeze", post(controller::freeze_positions))
+}
@@ -0,0 +1,192 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use sqlx::types::Json;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use crate::core::events::{DomainEvent, DomainEventType};
+use crate::general_ledger::model::GlAccountType;
+use crate::shared::types::{AccountId, Amount, Currency};
+
+/// Money moved in or out of customer accounts by one event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Flow {
+    pub currency: Currency,
+    pub inflow: Amount,
+    pub outflow: Amount,
+}
+
+/// The fields treasury needs from `payment.status_changed` and
+/// `transaction.created` payloads
+#[derive(Debug, Deserialize)]
+struct Movement {
+    from_account_id: Option<AccountId>,
+    to_account_id: Option<AccountId>,
+    amount: Amount,
+    currency: Currency,
+    status: String,
+}
+
+/// The flow a domain event records, if it moved money. Payments count once
+/// they complete; a payment between two customer accounts is both an inflow
+/// and an outflow, so it nets to zero.
+pub fn flow(event: &DomainEvent) -> Option<Flow> {
+    if !matches!(
+        event.event_type,
+        DomainEventType::PaymentStatusChanged | DomainEventType::TransactionCreated
+    ) {
+        return None;
+    }
+    let movement = Movement::deserialize(&event.data).ok()?;
+    if movement.status != "Completed" || movement.amount <= 0 {
+        return None;
+    }
+
+    Some(Flow {
+        currency: movement.currency.to_uppercase(),
+        inflow: if movement.to_account_id.is_some() { movement.amount } else { 0 },
+        outflow: if movement.from_account_id.is_some() { movement.amount } else { 0 },
+    })
+}
+
+/// Flows of one currency over a business date
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CurrencyPosition {
+    pub currency: Currency,
+    /// Credited to customer accounts
+    pub inflow: Amount,
+    /// Debited from customer accounts
+    pub outflow: Amount,
+    pub net: Amount,
+    pub movement_count: i64,
+}
+
+/// Movements on one GL account and currency over a business date
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GlPosition {
+    pub code: String,
+    pub name: String,
+    pub account_type: GlAccountType,
+    pub currency: Currency,
+    pub debits: Amount,
+    pub credits: Amount,
+    /// Net movement on the account's normal side
+    pub net: Amount,
+}
+
+/// Positions of a business date, live or as of a snapshot
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TreasuryPositions {
+    /// Set when the positions come from a snapshot
+    pub snapshot_id: Option<Uuid>,
+    pub business_date: NaiveDate,
+    /// When the positions were computed
+    pub as_of: DateTime<Utc>,
+    /// Whether the business date is closed by its end-of-day snapshot
+    pub frozen: bool,
+    pub currencies: Vec<CurrencyPosition>,
+    pub gl_accounts: Vec<GlPosition>,
+}
+
+/// Stored snapshot of a business date's positions
+#[derive(Debug, Clone, FromRow)]
+pub struct TreasurySnapshot {
+    pub id: Uuid,
+    pub business_date: NaiveDate,
+    pub is_end_of_day: bool,
+    pub currencies: Json<Vec<CurrencyPosition>>,
+    pub gl_accounts: Json<Vec<GlPosition>>,
+    pub taken_by: Option<Uuid>,
+    pub taken_at: DateTime<Utc>,
+}
+
+impl From<TreasurySnapshot> for TreasuryPositions {
+    fn from(snapshot: TreasurySnapshot) -> Self {
+        Self {
+            snapshot_id: Some(snapshot.id),
+            business_date: snapshot.business_date,
+            as_of: snapshot.taken_at,
+            frozen: snapshot.is_end_of_day,
+            currencies: snapshot.currencies.0,
+            gl_accounts: snapshot.gl_accounts.0,
+        }
+    }
+}
+
+/// Query parameters for positions and snapshots
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PositionsQuery {
+    /// Defaults to today
+    pub business_date: Option<NaiveDate>,
+    pub currency: Option<Currency>,
+}
+
+/// Close a business date with an end-of-day snapshot
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FreezePositionsRequest {
+    /// Defaults to today
+    pub business_date: Option<NaiveDate>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: DomainEventType, data: serde_json::Value) -> DomainEvent {
+        DomainEvent::new(event_type, None, Vec::new(), data)
+    }
+
+    #[test]
+    fn completed_movements_are_flows() {
+        let outgoing = event(
+            DomainEventType::PaymentStatusChanged,
+            serde_json::json!({
+                "from_account_id": Uuid::new_v4(),
+                "to_account_id": null,
+                "amount": 500,
+                "currency": "eur",
+                "status": "Completed",
+            }),
+        );
+        assert_eq!(
+            flow(&outgoing),
+            Some(Flow { currency: "EUR".to_string(), inflow: 0, outflow: 500 })
+        );
+
+        let interest = event(
+            DomainEventType::TransactionCreated,
+            serde_json::json!({
+                "to_account_id": Uuid::new_v4(),
+                "amount": 12,
+                "currency": "EUR",
+                "status": "Completed",
+            }),
+        );
+        assert_eq!(
+            flow(&interest),
+            Some(Flow { currency: "EUR".to_string(), inflow: 12, outflow: 0 })
+        );
+    }
+
+    #[test]
+    fn other_events_are_not_flows() {
+        let pending = event(
+            DomainEventType::PaymentStatusChanged,
+            serde_json::json!({
+                "from_account_id": Uuid::new_v4(),
+                "to_account_id": Uuid::new_v4(),
+                "amount": 500,
+                "currency": "EUR",
+                "status": "Pending",
+            }),
+        );
+        assert_eq!(flow(&pending), None);
+
+        let balance = event(
+            DomainEventType::BalanceUpdated,
+            serde_json::json!({"account_id": Uuid::new_v4(), "currency": "EUR"}),
+        );
+        assert_eq!(flow(&balance), None);
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use super::model::{CurrencyPosition, Flow, GlPosition, TreasurySnapshot};
+
+const SNAPSHOT_COLUMNS: &str = "id, business_date, is_end_of_day, currencies, gl_accounts, taken_by, taken_at";
+
+/// Serializes recording flows with freezing, so that no flow is added to a
+/// business date once its end-of-day snapshot is taken
+async fn lock_positions(conn: &mut PgConnection) -> AppResult<()> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('openbank_treasury_positions'))")
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// When the business date was frozen, if it was
+async fn frozen_at(conn: &mut PgConnection, business_date: NaiveDate) -> AppResult<Option<DateTime<Utc>>> {
+    let taken_at = sqlx::query_scalar(
+        "SELECT taken_at FROM treasury_snapshots WHERE business_date = $1 AND is_end_of_day",
+    )
+    .bind(business_date)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(taken_at)
+}
+
+/// Flows and GL movements of a business date. GL entries booked after a
+/// date was frozen, but before it ended, count towards the next date.
+async fn positions(
+    conn: &mut PgConnection,
+    business_date: NaiveDate,
+    currency: Option<&str>,
+) -> AppResult<(Vec<CurrencyPosition>, Vec<GlPosition>)> {
+    let day_start = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc();
+    let mut starts_at = day_start(business_date);
+    if let Some(previous_frozen_at) = frozen_at(conn, business_date - Duration::days(1)).await? {
+        starts_at = starts_at.min(previous_frozen_at);
+    }
+    let mut ends_at = day_start(business_date + Duration::days(1));
+    if let Some(frozen_at) = frozen_at(conn, business_date).await? {
+        ends_at = ends_at.min(frozen_at);
+    }
+
+    let currencies = sqlx::query_as::<_, CurrencyPosition>(
+        "SELECT currency, inflow, outflow, inflow - outflow AS net, movement_count
+         FROM treasury_flows
+         WHERE business_date = $1 AND ($2::VARCHAR IS NULL OR currency = $2)
+         ORDER BY currency",
+    )
+    .bind(business_date)
+    .bind(currency)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // Net on the account's normal side, as in the trial balance
+    let gl_accounts = sqlx::query_as::<_, GlPosition>(
+        "SELECT a.code, a.name, a.account_type, e.currency,
+                SUM(e.debit)::BIGINT AS debits,
+                SUM(e.credit)::BIGINT AS credits,
+                (CASE WHEN a.account_type IN ('asset', 'expense') THEN SUM(e.debit) - SUM(e.credit)
+                      ELSE SUM(e.credit) - SUM(e.debit) END)::BIGINT AS net
+         FROM gl_entries e
+         JOIN gl_accounts a ON a.id = e.gl_account_id
+         WHERE e.created_at >= $1 AND e.created_at < $2 AND ($3::VARCHAR IS NULL OR e.currency = $3)
+         GROUP BY a.code, a.name, a.account_type, e.currency
+         ORDER BY e.currency, a.code",
+    )
+    .bind(starts_at)
+    .bind(ends_at)
+    .bind(currency)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok((currencies, gl_accounts))
+}
+
+pub struct TreasuryRepository {
+    pool: PgPool,
+}
+
+impl TreasuryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add a flow to its business date, or to the first date after it that
+    /// is not frozen yet. Returns the date the flow was booked to.
+    pub async fn record_flow(&self, business_date: NaiveDate, flow: &Flow) -> AppResult<NaiveDate> {
+        let mut tx = self.pool.begin().await?;
+        lock_positions(&mut tx).await?;
+
+        let mut business_date = business_date;
+        while frozen_at(&mut tx, business_date).await?.is_some() {
+            business_date += Duration::days(1);
+        }
+        sqlx::query(
+            "INSERT INTO treasury_flows (business_date, currency, inflow, outflow, movement_count)
+             VALUES ($1, $2, $3, $4, 1)
+             ON CONFLICT (business_date, currency) DO UPDATE SET
+                 inflow = treasury_flows.inflow + EXCLUDED.inflow,
+                 outflow = treasury_flows.outflow + EXCLUDED.outflow,
+                 movement_count = treasury_flows.movement_count + 1,
+                 updated_at = NOW()",
+        )
+        .bind(business_date)
+        .bind(&flow.currency)
+        .bind(flow.inflow)
+        .bind(flow.outflow)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(business_date)
+    }
+
+    /// Positions of a business date as they stand now
+    pub async fn current_positions(
+        &self,
+        business_date: NaiveDate,
+        currency: Option<&str>,
+    ) -> AppResult<(Vec<CurrencyPosition>, Vec<GlPosition>)> {
+        let mut conn = self.pool.acquire().await?;
+        positions(&mut conn, business_date, currency).await
+    }
+
+    /// Record the positions of a business date. An end-of-day snapshot also
+    /// freezes the date; returns `None` if it is frozen already.
+    pub async fn create_snapshot(
+        &self,
+        business_date: NaiveDate,
+        is_end_of_day: bool,
+        taken_by: Uuid,
+    ) -> AppResult<Option<TreasurySnapshot>> {
+        let mut tx = self.pool.begin().await?;
+        lock_positions(&mut tx).await?;
+
+        if is_end_of_day && frozen_at(&mut tx, business_date).await?.is_some() {
+            return Ok(None);
+        }
+        let (currencies, gl_accounts) = positions(&mut tx, business_date, None).await?;
+        let snapshot = sqlx::query_as::<_, TreasurySnapshot>(&format!(
+            "INSERT INTO treasury_snapshots (business_date, is_end_of_day, currencies, gl_accounts, taken_by)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {SNAPSHOT_COLUMNS}"
+        ))
+        .bind(business_date)
+        .bind(is_end_of_day)
+        .bind(Json(currencies))
+        .bind(Json(gl_accounts))
+        .bind(taken_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(snapshot))
+    }
+
+    pub async fn find_end_of_day(&self, business_date: NaiveDate) -> AppResult<Option<TreasurySnapshot>> {
+        let snapshot = sqlx::query_as::<_, TreasurySnapshot>(&format!(
+            "SELECT {SNAPSHOT_COLUMNS} FROM treasury_snapshots WHERE business_date = $1 AND is_end_of_day"
+        ))
+        .bind(business_date)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+
+    /// Snapshots of a business date, oldest first
+    pub async fn list_snapshots(&self, business_date: NaiveDate) -> AppResult<Vec<TreasurySnapshot>> {
+        let snapshots = sqlx::query_as::<_, TreasurySnapshot>(&format!(
+            "SELECT {SNAPSHOT_COLUMNS} FROM treasury_snapshots WHERE business_date = $1 ORDER BY taken_at"
+        ))
+        .bind(business_date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots)
+    }
+}
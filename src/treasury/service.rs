@@ -0,0 +1,132 @@
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::core::events::DomainEvent;
+use super::model::{flow, TreasuryPositions};
+use super::repository::TreasuryRepository;
+
+pub struct TreasuryService {
+    repository: TreasuryRepository,
+    audit_logger: AuditLogger,
+}
+
+impl TreasuryService {
+    pub fn new(repository: TreasuryRepository, audit_logger: AuditLogger) -> Self {
+        Self {
+            repository,
+            audit_logger,
+        }
+    }
+
+    /// Add the money a domain event moved to its business date's flows.
+    /// Returns the date it was booked to, or `None` if it moved no money.
+    pub async fn record(&self, event: &DomainEvent) -> AppResult<Option<NaiveDate>> {
+        let Some(flow) = flow(event) else {
+            return Ok(None);
+        };
+        let business_date = self
+            .repository
+            .record_flow(event.occurred_at.date_naive(), &flow)
+            .await?;
+        Ok(Some(business_date))
+    }
+
+    /// Positions of a business date: live while it is open, as frozen by
+    /// its end-of-day snapshot once closed
+    pub async fn positions(
+        &self,
+        business_date: Option<NaiveDate>,
+        currency: Option<String>,
+    ) -> AppResult<TreasuryPositions> {
+        let business_date = business_date.unwrap_or_else(|| Utc::now().date_naive());
+        let currency = currency.map(|code| code.to_uppercase());
+
+        if let Some(snapshot) = self.repository.find_end_of_day(business_date).await? {
+            return Ok(filter_currency(snapshot.into(), currency.as_deref()));
+        }
+        let (currencies, gl_accounts) = self
+            .repository
+            .current_positions(business_date, currency.as_deref())
+            .await?;
+        Ok(TreasuryPositions {
+            snapshot_id: None,
+            business_date,
+            as_of: Utc::now(),
+            frozen: false,
+            currencies,
+            gl_accounts,
+        })
+    }
+
+    /// Snapshots taken of a business date, oldest first
+    pub async fn list_snapshots(
+        &self,
+        business_date: Option<NaiveDate>,
+        currency: Option<String>,
+    ) -> AppResult<Vec<TreasuryPositions>> {
+        let business_date = business_date.unwrap_or_else(|| Utc::now().date_naive());
+        let currency = currency.map(|code| code.to_uppercase());
+
+        let snapshots = self.repository.list_snapshots(business_date).await?;
+        Ok(snapshots
+            .into_iter()
+            .map(|snapshot| filter_currency(snapshot.into(), currency.as_deref()))
+            .collect())
+    }
+
+    /// Record today's positions so far
+    pub async fn take_snapshot(&self, actor_id: Uuid) -> AppResult<TreasuryPositions> {
+        let business_date = Utc::now().date_naive();
+        let snapshot = self
+            .repository
+            .create_snapshot(business_date, false, actor_id)
+            .await?
+            .ok_or_else(|| AppError::Internal("Intraday snapshot was not recorded".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::TreasurySnapshotTaken)
+            .user_id(actor_id)
+            .resource(format!("treasury_snapshot:{}", snapshot.id))
+            .action("snapshot".to_string())
+            .metadata("business_date".to_string(), serde_json::json!(business_date))
+            .compliance_tag("TREASURY".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(snapshot.into())
+    }
+
+    /// Close a business date with its end-of-day snapshot. Flows arriving
+    /// for it afterwards are booked to the next open date.
+    pub async fn freeze(&self, business_date: Option<NaiveDate>, actor_id: Uuid) -> AppResult<TreasuryPositions> {
+        let today = Utc::now().date_naive();
+        let business_date = business_date.unwrap_or(today);
+        if business_date > today {
+            return Err(AppError::Validation("Future business dates cannot be frozen".to_string()));
+        }
+
+        let snapshot = self
+            .repository
+            .create_snapshot(business_date, true, actor_id)
+            .await?
+            .ok_or_else(|| AppError::Conflict(format!("Positions for {} are already frozen", business_date)))?;
+
+        let event = AuditEvent::new(AuditEventType::TreasuryPositionsFrozen)
+            .user_id(actor_id)
+            .resource(format!("treasury_snapshot:{}", snapshot.id))
+            .action("freeze".to_string())
+            .metadata("business_date".to_string(), serde_json::json!(business_date))
+            .compliance_tag("TREASURY".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(snapshot.into())
+    }
+}
+
+/// Limit stored positions, which cover every currency, to one
+fn filter_currency(mut positions: TreasuryPositions, currency: Option<&str>) -> TreasuryPositions {
+    if let Some(currency) = currency {
+        positions.currencies.retain(|position| position.currency == currency);
+        positions.gl_accounts.retain(|position| position.currency == currency);
+    }
+    positions
+}
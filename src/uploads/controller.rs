@@ -0,0 +1,52 @@
+use axum::{extract::State, http::StatusCode, response::Json};
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{error::{AppError, AppResult}, extractors::ApiJson, response::ApiResponse, AppState};
+use crate::identity::repository::IdentityRepository;
+use super::model::{CreateUploadRequest, UploadTokenResponse};
+use super::repository::UploadRepository;
+use super::service::UploadService;
+
+pub(crate) fn upload_service(state: &AppState) -> UploadService {
+    UploadService::new(
+        UploadRepository::new(state.postgres.clone()),
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        state.storage.clone(),
+        chrono::Duration::seconds(state.config.direct_upload_ttl_seconds),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Issue a short-lived link to upload an image or video to directly, so SDKs
+/// do not send files through the API. Reference the returned upload id in
+/// verification requests once the file is uploaded.
+#[utoipa::path(
+    post,
+    path = "/api/v1/uploads",
+    tag = "uploads",
+    request_body = CreateUploadRequest,
+    responses(
+        (status = 201, description = "Upload link issued", body = UploadTokenResponse),
+        (status = 400, description = "Unsupported content type"),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_upload(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<CreateUploadRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<UploadTokenResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let upload = upload_service(&state)
+        .create(
+            request,
+            claims.tenant_id,
+            claims.user_id,
+            claims.user_id.unwrap_or(claims.developer_id),
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::success("Upload link issued successfully", upload))))
+}
@@ -0,0 +1,29 @@
+use crate::core::AppState;
+use super::controller::upload_service;
+
+const UPLOAD_CLEANUP_JOB: &str = "direct_upload_cleanup";
+
+/// Periodically delete direct uploads nobody referenced in time
+pub fn spawn_upload_cleanup_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.direct_upload_cleanup_interval_seconds);
+    state.job_monitor.register(UPLOAD_CLEANUP_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match upload_service(&state).expire_due().await {
+                Ok(count) => {
+                    state.job_monitor.record_success(UPLOAD_CLEANUP_JOB);
+                    if count > 0 {
+                        tracing::info!("Deleted {} unreferenced direct uploads", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(UPLOAD_CLEANUP_JOB, e.to_string());
+                    tracing::error!("Direct upload cleanup job failed: {}", e);
+                }
+            }
+        }
+    });
+}
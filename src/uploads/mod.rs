@@ -0,0 +1,12 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::post, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/", post(controller::create_upload))
+}
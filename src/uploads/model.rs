@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::shared::constants::SUPPORTED_IMAGE_FORMATS;
+use crate::shared::types::UserId;
+
+/// What a direct upload is for; it can only be referenced for that
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "direct_upload_purpose", rename_all = "snake_case")]
+pub enum UploadPurpose {
+    /// A photo or scan of an ID document
+    IdentityDocument,
+    /// A photo of the user's face
+    Selfie,
+    /// A short video of the user's face
+    LivenessVideo,
+}
+
+impl UploadPurpose {
+    /// Content types an upload for the purpose may have
+    pub fn accepted_content_types(&self) -> &'static [&'static str] {
+        match self {
+            UploadPurpose::IdentityDocument => &["image/jpeg", "image/png", "application/pdf"],
+            UploadPurpose::Selfie => SUPPORTED_IMAGE_FORMATS,
+            UploadPurpose::LivenessVideo => &["video/mp4", "video/webm"],
+        }
+    }
+}
+
+/// Where a direct upload is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "direct_upload_status", rename_all = "snake_case")]
+pub enum UploadStatus {
+    /// Waiting to be uploaded or referenced
+    Pending,
+    /// Referenced by a request, which took over the file
+    Consumed,
+    /// Not referenced in time; the file is deleted
+    Expired,
+}
+
+/// A direct upload
+#[derive(Debug, Clone, FromRow)]
+pub struct DirectUpload {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub purpose: UploadPurpose,
+    pub content_type: String,
+    pub storage_key: String,
+    pub status: UploadStatus,
+    pub created_by: Uuid,
+    pub link_expires_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Ask for a link to upload a file to directly
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateUploadRequest {
+    /// The user the file belongs to; required with project tokens, and with
+    /// user tokens it must be the token's user
+    pub user_id: Option<UserId>,
+    pub purpose: UploadPurpose,
+    /// Content type of the file, sent as the `Content-Type` of the upload
+    #[validate(length(min = 1, max = 100))]
+    pub content_type: String,
+}
+
+/// A link to upload a file to, and the id to reference it by once uploaded
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UploadTokenResponse {
+    pub upload_id: Uuid,
+    pub purpose: UploadPurpose,
+    /// Send the file as the body of a request with this method to `upload_url`
+    pub method: String,
+    pub upload_url: String,
+    pub content_type: String,
+    pub max_bytes: u64,
+    /// The link stops working at this time
+    pub link_expires_at: DateTime<Utc>,
+    /// The upload has to be referenced by this time
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A file uploaded directly, as handed to the request referencing it
+#[derive(Debug, Clone)]
+pub struct UploadedFile {
+    pub upload: DirectUpload,
+    pub content: Vec<u8>,
+}
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::error::AppResult;
+use crate::shared::types::UserId;
+use super::model::{DirectUpload, UploadPurpose};
+
+const UPLOAD_COLUMNS: &str = "id, user_id, purpose, content_type, storage_key, status, created_by,
+    link_expires_at, expires_at, consumed_at, created_at";
+
+#[derive(Clone)]
+pub struct UploadRepository {
+    pool: PgPool,
+}
+
+impl UploadRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        &self,
+        id: Uuid,
+        user_id: UserId,
+        purpose: UploadPurpose,
+        content_type: &str,
+        storage_key: &str,
+        created_by: Uuid,
+        link_expires_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<DirectUpload> {
+        let upload = sqlx::query_as::<_, DirectUpload>(&format!(
+            "INSERT INTO direct_uploads
+                 (id, user_id, purpose, content_type, storage_key, created_by, link_expires_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+             RETURNING {UPLOAD_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(purpose)
+        .bind(content_type)
+        .bind(storage_key)
+        .bind(created_by)
+        .bind(link_expires_at)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(upload)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<DirectUpload>> {
+        let upload = sqlx::query_as::<_, DirectUpload>(&format!(
+            "SELECT {UPLOAD_COLUMNS} FROM direct_uploads WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(upload)
+    }
+
+    /// Mark an upload as taken over by the request referencing it. Returns
+    /// `None` unless it was pending.
+    pub async fn consume(&self, id: Uuid) -> AppResult<Option<DirectUpload>> {
+        let upload = sqlx::query_as::<_, DirectUpload>(&format!(
+            "UPDATE direct_uploads SET status = 'consumed', consumed_at = NOW()
+             WHERE id = $1 AND status = 'pending'
+             RETURNING {UPLOAD_COLUMNS}"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(upload)
+    }
+
+    /// Expire pending uploads nobody referenced in time
+    pub async fn expire_due(&self, now: DateTime<Utc>) -> AppResult<Vec<DirectUpload>> {
+        let expired = sqlx::query_as::<_, DirectUpload>(&format!(
+            "UPDATE direct_uploads SET status = 'expired'
+             WHERE status = 'pending' AND expires_at <= $1
+             RETURNING {UPLOAD_COLUMNS}"
+        ))
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(expired)
+    }
+}
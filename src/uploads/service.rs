@@ -0,0 +1,178 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::error::{AppError, AppResult};
+use crate::core::storage::Storage;
+use crate::identity::{images, repository::IdentityRepository};
+use crate::shared::constants::{MAX_DIRECT_UPLOAD_SIZE, MAX_IMAGE_SIZE};
+use crate::shared::types::{TenantId, UserId};
+use super::model::{CreateUploadRequest, DirectUpload, UploadPurpose, UploadStatus, UploadTokenResponse, UploadedFile};
+use super::repository::UploadRepository;
+
+/// How long an upload is kept for a request to reference it once its link
+/// has expired
+const UNREFERENCED_UPLOAD_RETENTION_MINUTES: i64 = 60;
+
+/// Issues short-lived links clients upload files to directly, and hands the
+/// files to the requests that reference them
+pub struct UploadService {
+    repository: UploadRepository,
+    identity_repository: IdentityRepository,
+    storage: Arc<dyn Storage>,
+    link_ttl: Duration,
+    audit_logger: AuditLogger,
+}
+
+impl UploadService {
+    pub fn new(
+        repository: UploadRepository,
+        identity_repository: IdentityRepository,
+        storage: Arc<dyn Storage>,
+        link_ttl: Duration,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            identity_repository,
+            storage,
+            link_ttl,
+            audit_logger,
+        }
+    }
+
+    /// Issue a link scoped to one user, purpose and content type
+    pub async fn create(
+        &self,
+        request: CreateUploadRequest,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+        actor_id: Uuid,
+    ) -> AppResult<UploadTokenResponse> {
+        let user_id = match (request.user_id, caller) {
+            (Some(user_id), Some(caller)) if user_id != caller => {
+                return Err(AppError::NotFound("User not found".to_string()))
+            }
+            (Some(user_id), _) | (None, Some(user_id)) => user_id,
+            (None, None) => return Err(AppError::Validation("user_id is required".to_string())),
+        };
+        if !self.identity_repository.user_in_tenant(user_id, tenant_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        let content_type = request.content_type.trim().to_ascii_lowercase();
+        let accepted = request.purpose.accepted_content_types();
+        if !accepted.contains(&content_type.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Unsupported upload type '{}'. Supported types: {}",
+                content_type,
+                accepted.join(", ")
+            )));
+        }
+
+        let id = Uuid::new_v4();
+        let storage_key = format!("direct-uploads/{}/{}", user_id, id);
+        let link_ttl = self
+            .link_ttl
+            .to_std()
+            .map_err(|_| AppError::Internal("Invalid direct upload lifetime".to_string()))?;
+        let upload_url = self.storage.signed_upload_url(&storage_key, link_ttl)?;
+        let link_expires_at = Utc::now() + self.link_ttl;
+        let expires_at = link_expires_at + Duration::minutes(UNREFERENCED_UPLOAD_RETENTION_MINUTES);
+        let upload = self
+            .repository
+            .create(
+                id,
+                user_id,
+                request.purpose,
+                &content_type,
+                &storage_key,
+                actor_id,
+                link_expires_at,
+                expires_at,
+            )
+            .await?;
+
+        let event = AuditEvent::new(AuditEventType::DirectUploadIssued)
+            .user_id(actor_id)
+            .resource(format!("direct_upload:{}", upload.id))
+            .action("issue_upload_link".to_string())
+            .metadata("subject_user_id".to_string(), json!(user_id))
+            .metadata("purpose".to_string(), json!(upload.purpose))
+            .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        let max_bytes = if images::is_image(&upload.content_type) {
+            MAX_IMAGE_SIZE
+        } else {
+            MAX_DIRECT_UPLOAD_SIZE
+        };
+        Ok(UploadTokenResponse {
+            upload_id: upload.id,
+            purpose: upload.purpose,
+            method: "PUT".to_string(),
+            upload_url,
+            content_type: upload.content_type,
+            max_bytes: max_bytes as u64,
+            link_expires_at: upload.link_expires_at,
+            expires_at: upload.expires_at,
+        })
+    }
+
+    /// Read the file of a pending upload of the user for the purpose. The
+    /// upload stays pending until [`consume`](Self::consume) is called, so a
+    /// request that fails can reference it again.
+    pub async fn fetch(&self, upload_id: Uuid, user_id: UserId, purpose: UploadPurpose) -> AppResult<UploadedFile> {
+        let upload = self
+            .repository
+            .find_by_id(upload_id)
+            .await?
+            .filter(|upload| upload.user_id == user_id)
+            .ok_or_else(|| AppError::NotFound("Upload not found".to_string()))?;
+        if upload.purpose != purpose {
+            return Err(AppError::Validation("The upload was issued for another purpose".to_string()));
+        }
+        if upload.status != UploadStatus::Pending || upload.expires_at <= Utc::now() {
+            return Err(AppError::Conflict("The upload has already been used or has expired".to_string()));
+        }
+
+        let content = match self.storage.get(&upload.storage_key).await {
+            Ok(content) => content,
+            Err(AppError::NotFound(_)) => {
+                return Err(AppError::Validation("Nothing has been uploaded yet".to_string()))
+            }
+            Err(e) => return Err(e),
+        };
+        if content.len() > MAX_DIRECT_UPLOAD_SIZE {
+            return Err(AppError::Validation(format!(
+                "Upload exceeds the maximum size of {} bytes",
+                MAX_DIRECT_UPLOAD_SIZE
+            )));
+        }
+        Ok(UploadedFile { upload, content })
+    }
+
+    /// Mark an upload as taken over by the request that referenced it and
+    /// delete its file, which the request stored on its own
+    pub async fn consume(&self, upload: &DirectUpload) -> AppResult<()> {
+        self.repository
+            .consume(upload.id)
+            .await?
+            .ok_or_else(|| AppError::Conflict("The upload has already been used or has expired".to_string()))?;
+        if let Err(e) = self.storage.delete(&upload.storage_key).await {
+            tracing::warn!("Failed to delete consumed upload {}: {}", upload.storage_key, e);
+        }
+        Ok(())
+    }
+
+    /// Expire uploads nobody referenced in time, deleting their files
+    pub async fn expire_due(&self) -> AppResult<usize> {
+        let expired = self.repository.expire_due(Utc::now()).await?;
+        for upload in &expired {
+            if let Err(e) = self.storage.delete(&upload.storage_key).await {
+                tracing::warn!("Failed to delete expired upload {}: {}", upload.storage_key, e);
+            }
+        }
+        Ok(expired.len())
+    }
+}
@@ -0,0 +1,193 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use super::model::{
+    BillingExportQuery, ExportFormat, ProjectUsageResponse, QuotaOverrideRequest, QuotaStatusResponse,
+    UsageQuery,
+};
+use super::quota::quota_service;
+use super::repository::UsageRepository;
+use super::service::UsageService;
+
+fn usage_service(state: &AppState) -> UsageService {
+    UsageService::new(UsageRepository::new(state.postgres.clone()))
+}
+
+/// Get daily API usage for a project
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/projects/{id}/usage",
+    tag = "usage",
+    params(("id" = Uuid, Path, description = "Project ID"), UsageQuery),
+    responses(
+        (status = 200, description = "Daily usage by scope", body = ProjectUsageResponse),
+        (status = 400, description = "Invalid date range"),
+        (status = 403, description = "Caller lacks the project management permission"),
+        (status = 404, description = "Project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_project_usage(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    Query(query): Query<UsageQuery>,
+) -> AppResult<Json<ApiResponse<ProjectUsageResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, format!("project:{}", id))
+        .await?;
+
+    let usage = usage_service(&state).get_project_usage(id, query).await?;
+    Ok(Json(ApiResponse::success("Project usage retrieved successfully", usage)))
+}
+
+/// Export monthly per-endpoint usage for billing as JSON or CSV
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/usage/export",
+    tag = "usage",
+    params(BillingExportQuery),
+    responses(
+        (status = 200, description = "Per-endpoint usage for the month", content(
+            ("application/json" = BillingExport),
+            ("text/csv" = String)
+        )),
+        (status = 400, description = "Invalid month"),
+        (status = 403, description = "Caller lacks the project management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_billing(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(query): Query<BillingExportQuery>,
+) -> AppResult<Response> {
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, "usage:billing".to_string())
+        .await?;
+
+    let export = usage_service(&state)
+        .billing_export(query.month, query.project_id)
+        .await?;
+
+    match query.format {
+        ExportFormat::Json => Ok(Json(ApiResponse::success("Billing export generated successfully", export))
+            .into_response()),
+        ExportFormat::Csv => {
+            let disposition = format!("attachment; filename=\"usage-{}.csv\"", export.month);
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (header::CONTENT_DISPOSITION, disposition),
+                ],
+                export.to_csv(),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// Get a project's monthly quota and current position
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/projects/{id}/quota",
+    tag = "usage",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Monthly quota and current position", body = QuotaStatusResponse),
+        (status = 403, description = "Caller lacks the project management permission"),
+        (status = 404, description = "Project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_project_quota(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<QuotaStatusResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, format!("project:{}", id))
+        .await?;
+
+    let quota = quota_service(&state).get_status(id).await?;
+    Ok(Json(ApiResponse::success("Project quota retrieved successfully", quota)))
+}
+
+/// Override a project's monthly quota
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/projects/{id}/quota",
+    tag = "usage",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    request_body = QuotaOverrideRequest,
+    responses(
+        (status = 200, description = "Quota overridden", body = QuotaStatusResponse),
+        (status = 403, description = "Caller lacks the project management permission"),
+        (status = 404, description = "Project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn override_project_quota(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<QuotaOverrideRequest>,
+) -> AppResult<Json<ApiResponse<QuotaStatusResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, format!("project:{}", id))
+        .await?;
+
+    let quota = quota_service(&state)
+        .set_override(id, request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Project quota overridden successfully", quota)))
+}
+
+/// Remove a project's quota override
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/projects/{id}/quota",
+    tag = "usage",
+    params(("id" = Uuid, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Quota override removed", body = QuotaStatusResponse),
+        (status = 403, description = "Caller lacks the project management permission"),
+        (status = 404, description = "Project not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn clear_project_quota_override(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<QuotaStatusResponse>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_projects(), ip, format!("project:{}", id))
+        .await?;
+
+    let quota = quota_service(&state)
+        .clear_override(id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Project quota override removed successfully", quota)))
+}
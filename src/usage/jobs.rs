@@ -0,0 +1,41 @@
+use crate::core::error::AppResult;
+use crate::core::AppState;
+use super::repository::UsageRepository;
+
+const FLUSH_JOB: &str = "usage_meter_flush";
+
+/// Periodically write buffered API call counts to Postgres
+pub fn spawn_flush_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.usage_flush_interval_seconds);
+    state.job_monitor.register(FLUSH_JOB, period);
+
+    tokio::spawn(async move {
+        let repository = UsageRepository::new(state.postgres.clone());
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match flush_usage(&state, &repository).await {
+                Ok(_) => state.job_monitor.record_success(FLUSH_JOB),
+                Err(e) => {
+                    state.job_monitor.record_failure(FLUSH_JOB, e.to_string());
+                    tracing::error!("Usage flush job failed: {}", e);
+                }
+            }
+        }
+    });
+}
+
+async fn flush_usage(state: &AppState, repository: &UsageRepository) -> AppResult<usize> {
+    let entries = state.usage_meter.drain();
+    if entries.is_empty() {
+        return Ok(0);
+    }
+
+    if let Err(e) = repository.add_counts(&entries).await {
+        // Keep the counts so they are written on the next run
+        state.usage_meter.restore(entries);
+        return Err(e);
+    }
+
+    Ok(entries.len())
+}
@@ -0,0 +1,18 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod quota;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{delete, get, put}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/projects/:id/usage", get(controller::get_project_usage))
+        .route("/projects/:id/quota", get(controller::get_project_quota))
+        .route("/projects/:id/quota", put(controller::override_project_quota))
+        .route("/projects/:id/quota", delete(controller::clear_project_quota_override))
+        .route("/usage/export", get(controller::export_billing))
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::model::ProjectEnvironment;
+use crate::shared::csv::csv_field;
+
+/// Calls made by a project under one scope on one day
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct DailyUsage {
+    pub usage_date: NaiveDate,
+    pub scope: String,
+    pub request_count: i64,
+    pub error_count: i64,
+}
+
+/// Calls made by a project to one endpoint over a billing period
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct EndpointUsage {
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub developer_id: Uuid,
+    pub scope: String,
+    pub endpoint: String,
+    pub request_count: i64,
+    pub error_count: i64,
+}
+
+/// Date range for project usage, inclusive; defaults to the last 30 days
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UsageQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// Output format of billing exports and statements
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// Billing export parameters
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BillingExportQuery {
+    /// Billing month as `YYYY-MM`; defaults to the current month
+    pub month: Option<String>,
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub project_id: Option<Uuid>,
+}
+
+/// Quota settings stored on a project
+#[derive(Debug, Clone, FromRow)]
+pub struct ProjectQuota {
+    pub environment: ProjectEnvironment,
+    pub monthly_quota_override: Option<i64>,
+    pub quota_override_reason: Option<String>,
+    pub quota_override_by: Option<Uuid>,
+    pub quota_override_at: Option<DateTime<Utc>>,
+}
+
+/// Override a project's monthly quota
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct QuotaOverrideRequest {
+    #[validate(range(min = 0))]
+    pub monthly_limit: i64,
+    #[validate(length(min = 1, max = 1000))]
+    pub reason: String,
+}
+
+/// Project quota response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuotaStatusResponse {
+    pub project_id: Uuid,
+    pub environment: ProjectEnvironment,
+    /// Effective monthly limit; `null` means unlimited
+    pub monthly_limit: Option<i64>,
+    pub environment_default: Option<i64>,
+    pub override_limit: Option<i64>,
+    pub override_reason: Option<String>,
+    pub override_by: Option<Uuid>,
+    pub override_at: Option<DateTime<Utc>>,
+    pub used: i64,
+    pub remaining: Option<i64>,
+    pub resets_at: DateTime<Utc>,
+}
+
+/// Project usage response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProjectUsageResponse {
+    pub project_id: Uuid,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total_requests: i64,
+    pub total_errors: i64,
+    pub daily: Vec<DailyUsage>,
+}
+
+/// Monthly per-endpoint usage for billing
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BillingExport {
+    pub month: String,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub lines: Vec<EndpointUsage>,
+}
+
+impl BillingExport {
+    /// Render the export as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "month,project_id,project_name,developer_id,scope,endpoint,request_count,error_count\n",
+        );
+        for line in &self.lines {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                self.month,
+                line.project_id,
+                csv_field(&line.project_name),
+                line.developer_id,
+                csv_field(&line.scope),
+                csv_field(&line.endpoint),
+                line.request_count,
+                line.error_count
+            ));
+        }
+        csv
+    }
+}
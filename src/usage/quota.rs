@@ -0,0 +1,236 @@
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use uuid::Uuid;
+use crate::auth::model::ProjectEnvironment;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::metering::{QuotaCache, QuotaState, UsageMeter};
+use crate::core::AppState;
+use super::model::{ProjectQuota, QuotaOverrideRequest, QuotaStatusResponse};
+use super::repository::UsageRepository;
+
+/// Default monthly quotas per project environment
+#[derive(Debug, Clone)]
+pub struct QuotaDefaults {
+    pub development: i64,
+    pub staging: i64,
+    pub production: i64,
+}
+
+impl QuotaDefaults {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            development: config.quota_development_monthly,
+            staging: config.quota_staging_monthly,
+            production: config.quota_production_monthly,
+        }
+    }
+
+    /// Monthly limit for an environment; `None` when configured as unlimited (0)
+    pub fn for_environment(&self, environment: &ProjectEnvironment) -> Option<i64> {
+        let limit = match environment {
+            ProjectEnvironment::Development => self.development,
+            ProjectEnvironment::Staging => self.staging,
+            ProjectEnvironment::Production => self.production,
+        };
+        (limit > 0).then_some(limit)
+    }
+}
+
+/// Outcome of checking a call against its project's quota
+#[derive(Debug, Clone)]
+pub struct QuotaCheck {
+    pub allowed: bool,
+    pub limit: Option<i64>,
+    pub remaining: Option<i64>,
+    pub resets_at: DateTime<Utc>,
+}
+
+pub struct QuotaService {
+    repository: UsageRepository,
+    meter: UsageMeter,
+    cache: QuotaCache,
+    defaults: QuotaDefaults,
+    audit_logger: AuditLogger,
+}
+
+pub fn quota_service(state: &AppState) -> QuotaService {
+    QuotaService::new(
+        UsageRepository::new(state.postgres.clone()),
+        state.usage_meter.clone(),
+        state.quota_cache.clone(),
+        QuotaDefaults::from_config(&state.config),
+        state.audit_logger.clone(),
+    )
+}
+
+impl QuotaService {
+    pub fn new(
+        repository: UsageRepository,
+        meter: UsageMeter,
+        cache: QuotaCache,
+        defaults: QuotaDefaults,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            meter,
+            cache,
+            defaults,
+            audit_logger,
+        }
+    }
+
+    /// Check a call against the project's monthly quota, counting it when admitted.
+    /// The first rejected call of a period is audited.
+    pub async fn admit(&self, project_id: Uuid) -> AppResult<QuotaCheck> {
+        let state = self.current_state(project_id).await?;
+
+        if state.is_exhausted() {
+            if self.cache.report_exceeded(project_id) {
+                let event = AuditEvent::new(AuditEventType::QuotaExceeded)
+                    .severity(AuditSeverity::Warning)
+                    .resource(format!("project:{}", project_id))
+                    .action("reject".to_string())
+                    .metadata("limit".to_string(), serde_json::json!(state.limit))
+                    .metadata("used".to_string(), serde_json::json!(state.used))
+                    .metadata("period_start".to_string(), serde_json::json!(state.period_start))
+                    .compliance_tag("USAGE".to_string());
+                self.audit_logger.log(event).await;
+            }
+
+            return Ok(QuotaCheck {
+                allowed: false,
+                limit: state.limit,
+                remaining: Some(0),
+                resets_at: state.resets_at,
+            });
+        }
+
+        self.cache.consume(project_id);
+        Ok(QuotaCheck {
+            allowed: true,
+            limit: state.limit,
+            remaining: state.remaining().map(|remaining| remaining - 1),
+            resets_at: state.resets_at,
+        })
+    }
+
+    /// Get a project's quota settings and current position
+    pub async fn get_status(&self, project_id: Uuid) -> AppResult<QuotaStatusResponse> {
+        let quota = self.find_quota(project_id).await?;
+        let state = self.load_state(project_id, &quota).await?;
+        Ok(self.status_response(project_id, quota, state))
+    }
+
+    /// Override a project's monthly quota
+    pub async fn set_override(
+        &self,
+        project_id: Uuid,
+        request: QuotaOverrideRequest,
+        actor_id: Uuid,
+    ) -> AppResult<QuotaStatusResponse> {
+        let previous = self.find_quota(project_id).await?;
+        self.repository
+            .set_quota_override(project_id, Some(request.monthly_limit), Some(&request.reason), Some(actor_id))
+            .await?;
+        self.cache.invalidate(project_id);
+
+        let event = AuditEvent::new(AuditEventType::QuotaOverridden)
+            .user_id(actor_id)
+            .resource(format!("project:{}", project_id))
+            .action("set_override".to_string())
+            .metadata("from".to_string(), serde_json::json!(previous.monthly_quota_override))
+            .metadata("to".to_string(), serde_json::json!(request.monthly_limit))
+            .metadata("reason".to_string(), serde_json::json!(request.reason))
+            .compliance_tag("USAGE".to_string());
+        self.audit_logger.log(event).await;
+
+        self.get_status(project_id).await
+    }
+
+    /// Remove a project's quota override, restoring the environment default
+    pub async fn clear_override(&self, project_id: Uuid, actor_id: Uuid) -> AppResult<QuotaStatusResponse> {
+        let previous = self.find_quota(project_id).await?;
+        if previous.monthly_quota_override.is_none() {
+            return Err(AppError::NotFound("Project has no quota override".to_string()));
+        }
+
+        self.repository
+            .set_quota_override(project_id, None, None, None)
+            .await?;
+        self.cache.invalidate(project_id);
+
+        let event = AuditEvent::new(AuditEventType::QuotaOverridden)
+            .user_id(actor_id)
+            .resource(format!("project:{}", project_id))
+            .action("clear_override".to_string())
+            .metadata("from".to_string(), serde_json::json!(previous.monthly_quota_override))
+            .compliance_tag("USAGE".to_string());
+        self.audit_logger.log(event).await;
+
+        self.get_status(project_id).await
+    }
+
+    async fn current_state(&self, project_id: Uuid) -> AppResult<QuotaState> {
+        let (period_start, _) = current_period();
+        if let Some(state) = self.cache.get(project_id, period_start) {
+            return Ok(state);
+        }
+
+        let quota = self.find_quota(project_id).await?;
+        let state = self.load_state(project_id, &quota).await?;
+        self.cache.store(project_id, state.clone());
+        Ok(state)
+    }
+
+    /// Build the quota position from the flushed aggregates plus buffered calls
+    async fn load_state(&self, project_id: Uuid, quota: &ProjectQuota) -> AppResult<QuotaState> {
+        let (period_start, resets_at) = current_period();
+        let used = self.repository.count_requests_since(project_id, period_start).await?
+            + self.meter.pending_requests(project_id, period_start);
+
+        Ok(QuotaState::new(self.effective_limit(quota), used, period_start, resets_at))
+    }
+
+    fn effective_limit(&self, quota: &ProjectQuota) -> Option<i64> {
+        quota
+            .monthly_quota_override
+            .or_else(|| self.defaults.for_environment(&quota.environment))
+    }
+
+    fn status_response(&self, project_id: Uuid, quota: ProjectQuota, state: QuotaState) -> QuotaStatusResponse {
+        QuotaStatusResponse {
+            project_id,
+            monthly_limit: state.limit,
+            environment_default: self.defaults.for_environment(&quota.environment),
+            environment: quota.environment,
+            override_limit: quota.monthly_quota_override,
+            override_reason: quota.quota_override_reason,
+            override_by: quota.quota_override_by,
+            override_at: quota.quota_override_at,
+            used: state.used,
+            remaining: state.remaining(),
+            resets_at: state.resets_at,
+        }
+    }
+
+    async fn find_quota(&self, project_id: Uuid) -> AppResult<ProjectQuota> {
+        self.repository
+            .find_project_quota(project_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Project not found".to_string()))
+    }
+}
+
+/// Start of the current calendar month (UTC) and the instant the quota resets
+fn current_period() -> (NaiveDate, DateTime<Utc>) {
+    let today = Utc::now().date_naive();
+    let period_start = today.with_day(1).expect("first day of month is valid");
+    let resets_at = period_start
+        .checked_add_months(Months::new(1))
+        .and_then(|next_month| next_month.and_hms_opt(0, 0, 0))
+        .expect("next month is in range")
+        .and_utc();
+    (period_start, resets_at)
+}
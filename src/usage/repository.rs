@@ -0,0 +1,159 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::core::metering::{UsageCount, UsageKey};
+use super::model::{DailyUsage, EndpointUsage, ProjectQuota};
+
+pub struct UsageRepository {
+    pool: PgPool,
+}
+
+impl UsageRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Add buffered counts to the daily aggregates
+    pub async fn add_counts(&self, entries: &[(UsageKey, UsageCount)]) -> AppResult<()> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        for (key, count) in entries {
+            sqlx::query(
+                "INSERT INTO api_usage_daily
+                     (project_id, developer_id, usage_date, scope, endpoint, request_count, error_count)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)
+                 ON CONFLICT (project_id, usage_date, scope, endpoint) DO UPDATE
+                 SET request_count = api_usage_daily.request_count + EXCLUDED.request_count,
+                     error_count = api_usage_daily.error_count + EXCLUDED.error_count,
+                     updated_at = NOW()",
+            )
+            .bind(key.project_id)
+            .bind(key.developer_id)
+            .bind(key.usage_date)
+            .bind(&key.scope)
+            .bind(&key.endpoint)
+            .bind(count.requests)
+            .bind(count.errors)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn project_exists(&self, project_id: Uuid) -> AppResult<bool> {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1)")
+            .bind(project_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(exists)
+    }
+
+    /// Daily usage per scope for a project within an inclusive date range
+    pub async fn find_daily_usage(
+        &self,
+        project_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> AppResult<Vec<DailyUsage>> {
+        let usage = sqlx::query_as::<_, DailyUsage>(
+            "SELECT usage_date, scope, SUM(request_count)::BIGINT AS request_count,
+                    SUM(error_count)::BIGINT AS error_count
+             FROM api_usage_daily
+             WHERE project_id = $1 AND usage_date BETWEEN $2 AND $3
+             GROUP BY usage_date, scope
+             ORDER BY usage_date, scope",
+        )
+        .bind(project_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(usage)
+    }
+
+    /// Per-endpoint usage within an inclusive date range, optionally for one project
+    pub async fn find_endpoint_usage(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        project_id: Option<Uuid>,
+    ) -> AppResult<Vec<EndpointUsage>> {
+        let usage = sqlx::query_as::<_, EndpointUsage>(
+            "SELECT u.project_id, p.name AS project_name, u.developer_id, u.scope, u.endpoint,
+                    SUM(u.request_count)::BIGINT AS request_count,
+                    SUM(u.error_count)::BIGINT AS error_count
+             FROM api_usage_daily u
+             JOIN projects p ON p.id = u.project_id
+             WHERE u.usage_date BETWEEN $1 AND $2
+               AND ($3::UUID IS NULL OR u.project_id = $3)
+             GROUP BY u.project_id, p.name, u.developer_id, u.scope, u.endpoint
+             ORDER BY u.project_id, u.scope, u.endpoint",
+        )
+        .bind(from)
+        .bind(to)
+        .bind(project_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(usage)
+    }
+
+    /// Quota settings for a project
+    pub async fn find_project_quota(&self, project_id: Uuid) -> AppResult<Option<ProjectQuota>> {
+        let quota = sqlx::query_as::<_, ProjectQuota>(
+            "SELECT environment, monthly_quota_override, quota_override_reason, quota_override_by,
+                    quota_override_at
+             FROM projects WHERE id = $1",
+        )
+        .bind(project_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(quota)
+    }
+
+    /// Calls recorded for a project on or after a date
+    pub async fn count_requests_since(&self, project_id: Uuid, since: NaiveDate) -> AppResult<i64> {
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(request_count), 0)::BIGINT FROM api_usage_daily
+             WHERE project_id = $1 AND usage_date >= $2",
+        )
+        .bind(project_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(total)
+    }
+
+    /// Set or clear a project's quota override
+    pub async fn set_quota_override(
+        &self,
+        project_id: Uuid,
+        monthly_limit: Option<i64>,
+        reason: Option<&str>,
+        actor_id: Option<Uuid>,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE projects
+             SET monthly_quota_override = $1, quota_override_reason = $2, quota_override_by = $3,
+                 quota_override_at = CASE WHEN $1::BIGINT IS NULL THEN NULL ELSE NOW() END,
+                 updated_at = NOW()
+             WHERE id = $4",
+        )
+        .bind(monthly_limit)
+        .bind(reason)
+        .bind(actor_id)
+        .bind(project_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, Utc};
+use uuid::Uuid;
+use crate::core::error::{AppError, AppResult};
+use super::model::{BillingExport, ProjectUsageResponse, UsageQuery};
+use super::repository::UsageRepository;
+
+/// Default window for project usage when no range is given
+const DEFAULT_USAGE_DAYS: i64 = 30;
+
+pub struct UsageService {
+    repository: UsageRepository,
+}
+
+impl UsageService {
+    pub fn new(repository: UsageRepository) -> Self {
+        Self { repository }
+    }
+
+    /// Daily usage for a project
+    pub async fn get_project_usage(
+        &self,
+        project_id: Uuid,
+        query: UsageQuery,
+    ) -> AppResult<ProjectUsageResponse> {
+        let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+        let from = query.from.unwrap_or(to - Duration::days(DEFAULT_USAGE_DAYS - 1));
+        if from > to {
+            return Err(AppError::Validation("'from' must not be after 'to'".to_string()));
+        }
+
+        if !self.repository.project_exists(project_id).await? {
+            return Err(AppError::NotFound("Project not found".to_string()));
+        }
+
+        let daily = self.repository.find_daily_usage(project_id, from, to).await?;
+
+        Ok(ProjectUsageResponse {
+            project_id,
+            from,
+            to,
+            total_requests: daily.iter().map(|day| day.request_count).sum(),
+            total_errors: daily.iter().map(|day| day.error_count).sum(),
+            daily,
+        })
+    }
+
+    /// Per-endpoint usage for a billing month (`YYYY-MM`, defaulting to the current month)
+    pub async fn billing_export(
+        &self,
+        month: Option<String>,
+        project_id: Option<Uuid>,
+    ) -> AppResult<BillingExport> {
+        let period_start = match month {
+            Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+                .map_err(|_| AppError::Validation("month must be formatted as YYYY-MM".to_string()))?,
+            None => Utc::now().date_naive().with_day(1).expect("first day of month is valid"),
+        };
+        let period_end = period_start
+            .checked_add_months(Months::new(1))
+            .and_then(|next_month| next_month.pred_opt())
+            .ok_or_else(|| AppError::Validation("month is out of range".to_string()))?;
+
+        let lines = self
+            .repository
+            .find_endpoint_usage(period_start, period_end, project_id)
+            .await?;
+
+        Ok(BillingExport {
+            month: period_start.format("%Y-%m").to_string(),
+            period_start,
+            period_end,
+            lines,
+        })
+    }
+}
@@ -1,69 +1,204 @@
-use axum::{extract::State, response::Json};
-use serde_json::{json, Value};
-use crate::core::{error::AppResult, AppState};
+use axum::extract::{Path, Query, State};
+use axum::response::{Json, Response};
+use crate::auth::{middleware::JwtToken, model::JwtClaims};
+use crate::core::{
+    conditional::{self, Preconditions},
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::account_labels::UpdateAccountDetailsRequest;
+use crate::shared::types::{AccountId, UserId};
+use super::model::{
+    AccountBalance, BalanceHistory, BalanceHistoryQuery, BalanceQuery, BatchBalanceResult, BatchGetBalancesRequest,
+    UserAccountsQuery,
+};
+use super::repository::UserDataRepository;
+use super::service::UserDataService;
 
-/// Get account balance
+/// Balance history entries per page at most
+const MAX_PAGE_SIZE: u32 = 100;
+
+fn user_data_service(state: &AppState) -> UserDataService {
+    UserDataService::new(UserDataRepository::new(state.postgres.clone()))
+}
+
+/// The end user a token acts for; project tokens have none to show
+fn end_user(claims: &JwtClaims) -> AppResult<UserId> {
+    claims
+        .user_id
+        .ok_or_else(|| AppError::Authorization("This endpoint requires a user access token".to_string()))
+}
+
+/// Get account balance, or with `as_of` the ledger balance at that time
+#[utoipa::path(
+    get,
+    path = "/api/v1/user-data/balance",
+    tag = "user-data",
+    params(
+        BalanceQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the balance the client holds")
+    ),
+    responses(
+        (status = 200, description = "Current balance, or the ledger balance as of the given time", body = AccountBalance),
+        (status = 304, description = "The balance is unchanged"),
+        (status = 400, description = "as_of is in the future"),
+        (status = 404, description = "Account not found, or not the user's own")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_balance(
-    State(_state): State<AppState>,
-    // TODO: Add user authentication and account extraction
-) -> AppResult<Json<Value>> {
-    // TODO: Implement balance retrieval logic
-    // 1. Authenticate user
-    // 2. Get account ID from user
-    // 3. Fetch current balance from database
-    // 4. Return balance information
-    
-    Ok(Json(json!({
-        "message": "Get balance endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<BalanceQuery>,
+    preconditions: Preconditions,
+) -> AppResult<Response> {
+    let service = user_data_service(&state);
+    service.ensure_account_access(&claims, query.account_id).await?;
+
+    let balance = match query.as_of {
+        Some(as_of) => AccountBalance::AsOf(service.get_balance_as_of(query.account_id, as_of).await?),
+        None => AccountBalance::Current(service.get_balance(query.account_id).await?),
+    };
+    Ok(conditional::respond(&preconditions, "Balance retrieved successfully", balance))
+}
+
+/// Get the current balances of many accounts at once, each with its
+/// balance or the error reading it
+#[utoipa::path(
+    post,
+    path = "/api/v1/user-data/balances:batchGet",
+    tag = "user-data",
+    request_body = BatchGetBalancesRequest,
+    responses(
+        (status = 200, description = "A result per account, in the order asked for", body = [BatchBalanceResult]),
+        (status = 400, description = "No accounts, or more than 1000")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn batch_get_balances(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(method): Path<String>,
+    ApiJson(request): ApiJson<BatchGetBalancesRequest>,
+) -> AppResult<Json<ApiResponse<Vec<BatchBalanceResult>>>> {
+    if method != ":batchGet" {
+        return Err(AppError::NotFound("Not found".to_string()));
+    }
+    let results = user_data_service(&state)
+        .batch_get_balances(&claims, &request.account_ids)
+        .await?;
+    Ok(Json(ApiResponse::success("Balances retrieved successfully", results)))
 }
 
 /// Get balance history
+#[utoipa::path(
+    get,
+    path = "/api/v1/user-data/balance/history",
+    tag = "user-data",
+    params(BalanceHistoryQuery),
+    responses(
+        (status = 200, description = "Balance changes, newest first", body = [BalanceHistory]),
+        (status = 404, description = "Account not found, or not the user's own")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_balance_history(
-    State(_state): State<AppState>,
-    // TODO: Add pagination parameters and filters
-) -> AppResult<Json<Value>> {
-    // TODO: Implement balance history logic
-    // 1. Authenticate user
-    // 2. Get account ID from user
-    // 3. Fetch balance history with pagination
-    // 4. Return paginated balance history
-    
-    Ok(Json(json!({
-        "message": "Get balance history endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<BalanceHistoryQuery>,
+) -> AppResult<Json<ApiResponse<Vec<BalanceHistory>>>> {
+    let service = user_data_service(&state);
+    service.ensure_account_access(&claims, query.account_id).await?;
+
+    let history = service
+        .get_balance_history(query.account_id, query.page.max(1), query.limit.clamp(1, MAX_PAGE_SIZE))
+        .await?;
+    Ok(Json(ApiResponse::success("Balance history retrieved successfully", history)))
 }
 
-/// Get user profile
+/// Get the profile of the user the token acts for
+#[utoipa::path(
+    get,
+    path = "/api/v1/user-data/profile",
+    tag = "user-data",
+    params(("If-None-Match" = Option<String>, Header, description = "ETag of the profile the client holds")),
+    responses(
+        (status = 200, description = "The user's profile", body = UserProfileResponse),
+        (status = 304, description = "The profile is unchanged"),
+        (status = 403, description = "Not a user access token")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_user_profile(
-    State(_state): State<AppState>,
-    // TODO: Add user authentication
-) -> AppResult<Json<Value>> {
-    // TODO: Implement user profile retrieval logic
-    // 1. Authenticate user
-    // 2. Fetch user profile data
-    // 3. Return user information
-    
-    Ok(Json(json!({
-        "message": "Get user profile endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    preconditions: Preconditions,
+) -> AppResult<Response> {
+    let user_id = end_user(&claims)?;
+    let profile = user_data_service(&state).get_user_profile(user_id).await?;
+    Ok(conditional::respond(&preconditions, "User profile retrieved successfully", profile))
 }
 
-/// Get user accounts
+/// Get the accounts of the user the token acts for
+#[utoipa::path(
+    get,
+    path = "/api/v1/user-data/accounts",
+    tag = "user-data",
+    params(
+        UserAccountsQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of the account list the client holds")
+    ),
+    responses(
+        (status = 200, description = "The user's active accounts", body = [UserAccountResponse]),
+        (status = 304, description = "The accounts are unchanged"),
+        (status = 403, description = "Not a user access token")
+    ),
+    security(("bearer_auth" = []))
+)]
 pub async fn get_user_accounts(
-    State(_state): State<AppState>,
-    // TODO: Add user authentication and pagination
-) -> AppResult<Json<Value>> {
-    // TODO: Implement user accounts retrieval logic
-    // 1. Authenticate user
-    // 2. Fetch all user accounts
-    // 3. Return paginated account list
-    
-    Ok(Json(json!({
-        "message": "Get user accounts endpoint - TODO: Implement",
-        "status": "placeholder"
-    })))
-}
\ No newline at end of file
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Query(query): Query<UserAccountsQuery>,
+    preconditions: Preconditions,
+) -> AppResult<Response> {
+    let user_id = end_user(&claims)?;
+    let accounts = user_data_service(&state)
+        .get_user_accounts(user_id, query.label.as_deref())
+        .await?;
+    Ok(conditional::respond(&preconditions, "User accounts retrieved successfully", accounts))
+}
+
+/// Set an account's nickname, labels or custom metadata; with If-Match,
+/// only if the account is as the client last read it
+#[utoipa::path(
+    patch,
+    path = "/api/v1/user-data/accounts/{id}",
+    tag = "user-data",
+    params(
+        ("id" = Uuid, Path, description = "Account ID"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the account as the client last read it")
+    ),
+    request_body = UpdateAccountDetailsRequest,
+    responses(
+        (status = 200, description = "Account updated", body = UserAccountResponse),
+        (status = 400, description = "Invalid nickname, labels or metadata"),
+        (status = 404, description = "Account not found, or not the user's own"),
+        (status = 412, description = "The account has changed since the client read it")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_account_details(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(account_id): Path<AccountId>,
+    preconditions: Preconditions,
+    ApiJson(request): ApiJson<UpdateAccountDetailsRequest>,
+) -> AppResult<Response> {
+    let service = user_data_service(&state);
+    service.ensure_account_access(&claims, account_id).await?;
+
+    let account = service.update_account_details(account_id, request, &preconditions).await?;
+    Ok(conditional::tagged("Account updated successfully", account))
+}
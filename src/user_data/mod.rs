@@ -4,12 +4,19 @@ pub mod repository;
 pub mod service;
 
 use crate::core::AppState;
-use axum::{routing::get, Router};
+use axum::{routing::{get, patch, post}, Router};
 
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/balance", get(controller::get_balance))
         .route("/balance/history", get(controller::get_balance_history))
+        // The router reads the `:batchGet` of a custom method as a parameter,
+        // so the handler checks the path really ends in it
+        .route("/balances:batchGet", post(controller::batch_get_balances))
         .route("/profile", get(controller::get_user_profile))
         .route("/accounts", get(controller::get_user_accounts))
+        .route("/accounts/:id", patch(controller::update_account_details))
+        .nest("/notifications", crate::notifications::routes())
+        .nest("/devices", crate::devices::routes())
+        .nest("/verification", crate::verification::routes())
 }
@@ -1,7 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
+use crate::account_controls::model::FreezeReason;
+use crate::kyc::model::KycTier;
 use crate::shared::types::{AccountId, Amount, Currency, UserId};
 
 /// Balance model for database
@@ -17,7 +20,7 @@ pub struct Balance {
 }
 
 /// Balance history entry
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct BalanceHistory {
     pub id: Uuid,
     pub account_id: AccountId,
@@ -25,12 +28,12 @@ pub struct BalanceHistory {
     pub balance_after: Amount,
     pub amount_changed: Amount,
     pub transaction_id: Option<Uuid>,
-    pub description: String,
+    pub description: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
 /// Balance response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct BalanceResponse {
     pub account_id: AccountId,
     pub available_balance: Amount,
@@ -60,6 +63,8 @@ pub struct UserProfile {
     pub last_name: String,
     pub phone: Option<String>,
     pub is_verified: bool,
+    pub phone_verified: bool,
+    pub kyc_tier: KycTier,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -74,19 +79,27 @@ pub struct UserAccount {
     pub account_type: String,
     pub currency: Currency,
     pub is_active: bool,
+    pub frozen_at: Option<DateTime<Utc>>,
+    pub freeze_reason: Option<FreezeReason>,
+    pub nickname: Option<String>,
+    pub labels: Vec<String>,
+    pub metadata: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 /// User profile response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserProfileResponse {
     pub id: UserId,
     pub email: String,
     pub first_name: String,
     pub last_name: String,
     pub phone: Option<String>,
+    /// Whether the email address is verified
     pub is_verified: bool,
+    pub phone_verified: bool,
+    pub kyc_tier: KycTier,
     pub created_at: DateTime<Utc>,
 }
 
@@ -99,13 +112,15 @@ impl From<UserProfile> for UserProfileResponse {
             last_name: profile.last_name,
             phone: profile.phone,
             is_verified: profile.is_verified,
+            phone_verified: profile.phone_verified,
+            kyc_tier: profile.kyc_tier,
             created_at: profile.created_at,
         }
     }
 }
 
 /// User account response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserAccountResponse {
     pub id: AccountId,
     pub account_number: String,
@@ -113,6 +128,12 @@ pub struct UserAccountResponse {
     pub account_type: String,
     pub currency: Currency,
     pub is_active: bool,
+    pub is_frozen: bool,
+    pub freeze_reason: Option<FreezeReason>,
+    pub frozen_at: Option<DateTime<Utc>>,
+    pub nickname: Option<String>,
+    pub labels: Vec<String>,
+    pub metadata: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -125,7 +146,103 @@ impl From<UserAccount> for UserAccountResponse {
             account_type: account.account_type,
             currency: account.currency,
             is_active: account.is_active,
+            is_frozen: account.frozen_at.is_some(),
+            freeze_reason: account.freeze_reason,
+            frozen_at: account.frozen_at,
+            nickname: account.nickname,
+            labels: account.labels,
+            metadata: account.metadata,
             created_at: account.created_at,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Filters on the user's accounts
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserAccountsQuery {
+    /// Only accounts with this label or nickname, compared without case
+    pub label: Option<String>,
+}
+
+/// The account a balance is read for
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BalanceQuery {
+    pub account_id: AccountId,
+    /// Ledger balance as of this time instead of the current balance
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+/// An account's ledger balance at a point in time, from its postings
+#[derive(Debug, Clone, FromRow)]
+pub struct LedgerBalanceAsOf {
+    pub ledger_balance: Amount,
+    pub currency: Currency,
+    pub last_posted_at: Option<DateTime<Utc>>,
+}
+
+/// Ledger balance at a point in time, for reconciling statements. Holds are
+/// not kept historically, so only the ledger balance is given.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BalanceAsOfResponse {
+    pub account_id: AccountId,
+    pub ledger_balance: Amount,
+    pub currency: Currency,
+    /// Time of the last posting counted; absent when there was none yet
+    pub last_posted_at: Option<DateTime<Utc>>,
+    pub as_of: DateTime<Utc>,
+}
+
+/// The current balance, or the ledger balance at the requested time
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum AccountBalance {
+    Current(BalanceResponse),
+    AsOf(BalanceAsOfResponse),
+}
+
+/// Accounts to read the current balances of in one call
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchGetBalancesRequest {
+    /// At most 1000 account IDs
+    pub account_ids: Vec<AccountId>,
+}
+
+/// Why an account's balance was not returned
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BalanceError {
+    pub code: String,
+    pub message: String,
+}
+
+/// One account's current balance, or the error reading it
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchBalanceResult {
+    pub account_id: AccountId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<BalanceResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BalanceError>,
+}
+
+/// Balance history parameters
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BalanceHistoryQuery {
+    pub account_id: AccountId,
+    /// Page number, starting at 1
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Entries per page, at most 100 (default 20)
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_limit() -> u32 {
+    20
+}
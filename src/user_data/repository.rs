@@ -1,13 +1,18 @@
-use super::model::{Balance, BalanceHistory, UserAccount, UserProfile};
+use super::model::{Balance, BalanceHistory, LedgerBalanceAsOf, UserAccount, UserProfile};
 use crate::core::error::AppResult;
 use crate::shared::{
+    account_labels::AccountDetailsChange,
     traits::Repository,
-    types::{AccountId, UserId},
+    types::{AccountId, TenantId, UserId},
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+const ACCOUNT_COLUMNS: &str = "id, user_id, account_number, account_name, account_type, currency, is_active,
+    frozen_at, freeze_reason, nickname, labels, metadata, created_at, updated_at";
+
 pub struct UserDataRepository {
     pool: PgPool,
 }
@@ -19,8 +24,7 @@ impl UserDataRepository {
 
     /// Get balance by account ID
     pub async fn find_by_account_id(&self, account_id: AccountId) -> AppResult<Option<Balance>> {
-        // TODO: Implement database query to find balance by account ID
-        let _result = sqlx::query_as::<_, Balance>(
+        let balance = sqlx::query_as::<_, Balance>(
             "SELECT id, account_id, available_balance, ledger_balance, currency, created_at, updated_at 
              FROM balances WHERE account_id = $1"
         )
@@ -28,7 +32,31 @@ impl UserDataRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(None)
+        Ok(balance)
+    }
+
+    /// Current balances of those of the accounts in the tenant, and owned by
+    /// `user_id` when given, in one query
+    pub async fn find_balances(
+        &self,
+        account_ids: &[AccountId],
+        tenant_id: TenantId,
+        user_id: Option<UserId>,
+    ) -> AppResult<Vec<Balance>> {
+        let balances = sqlx::query_as::<_, Balance>(
+            "SELECT b.id, b.account_id, b.available_balance, b.ledger_balance, b.currency, b.created_at, b.updated_at
+             FROM balances b
+             JOIN accounts a ON a.id = b.account_id
+             WHERE b.account_id = ANY($1) AND a.tenant_id = $2
+               AND ($3::UUID IS NULL OR a.user_id = $3)",
+        )
+        .bind(account_ids)
+        .bind(tenant_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(balances)
     }
 
     /// Get balance history for account
@@ -38,10 +66,9 @@ impl UserDataRepository {
         page: u32,
         limit: u32,
     ) -> AppResult<Vec<BalanceHistory>> {
-        // TODO: Implement paginated balance history query
-        let offset = (page - 1) * limit;
+        let offset = (page.max(1) - 1) * limit;
 
-        let _history = sqlx::query_as::<_, BalanceHistory>(
+        let history = sqlx::query_as::<_, BalanceHistory>(
             "SELECT id, account_id, balance_before, balance_after, amount_changed, 
                     transaction_id, description, created_at
              FROM balance_history WHERE account_id = $1 
@@ -53,36 +80,131 @@ impl UserDataRepository {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(Vec::new())
+        Ok(history)
+    }
+
+    /// When an account was opened; accounts without a recorded time count as
+    /// opened at the epoch
+    pub async fn find_account_opened_at(&self, account_id: AccountId) -> AppResult<Option<DateTime<Utc>>> {
+        let opened_at = sqlx::query_scalar("SELECT COALESCE(created_at, 'epoch') FROM accounts WHERE id = $1")
+            .bind(account_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(opened_at)
+    }
+
+    /// An account's ledger balance at `as_of`: the sum of its postings up to
+    /// then, or with `from_current` its current ledger balance less the
+    /// postings since. Both read as many postings as lie on their side of
+    /// `as_of`, and partition pruning skips the months on the other side.
+    pub async fn find_ledger_balance_as_of(
+        &self,
+        account_id: AccountId,
+        as_of: DateTime<Utc>,
+        from_current: bool,
+    ) -> AppResult<Option<LedgerBalanceAsOf>> {
+        let ledger_balance = if from_current {
+            "b.ledger_balance - COALESCE(
+                 (SELECT SUM(amount_changed) FROM balance_history WHERE account_id = $1 AND created_at > $2), 0)"
+        } else {
+            "COALESCE((SELECT SUM(amount_changed) FROM balance_history WHERE account_id = $1 AND created_at <= $2), 0)"
+        };
+        let balance = sqlx::query_as::<_, LedgerBalanceAsOf>(&format!(
+            "SELECT ({ledger_balance})::BIGINT AS ledger_balance, b.currency,
+                    (SELECT MAX(created_at) FROM balance_history
+                     WHERE account_id = $1 AND created_at <= $2) AS last_posted_at
+             FROM balances b WHERE b.account_id = $1"
+        ))
+        .bind(account_id)
+        .bind(as_of)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(balance)
     }
 
     /// Find user profile by ID
     pub async fn find_user_profile(&self, user_id: UserId) -> AppResult<Option<UserProfile>> {
-        // TODO: Implement user profile query
-        let _result = sqlx::query_as::<_, UserProfile>(
-            "SELECT id, email, first_name, last_name, phone, is_verified, created_at, updated_at
+        let profile = sqlx::query_as::<_, UserProfile>(
+            "SELECT id, email, first_name, last_name, phone, is_verified, phone_verified, kyc_tier, created_at, updated_at
              FROM users WHERE id = $1 AND is_active = true",
         )
         .bind(user_id)
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(None)
+        Ok(profile)
     }
 
     /// Find user accounts by user ID
-    pub async fn find_user_accounts(&self, user_id: UserId) -> AppResult<Vec<UserAccount>> {
-        // TODO: Implement user accounts query
-        let _accounts = sqlx::query_as::<_, UserAccount>(
-            "SELECT id, user_id, account_number, account_name, account_type, currency, is_active, created_at, updated_at
+    pub async fn find_user_accounts(&self, user_id: UserId, label: Option<&str>) -> AppResult<Vec<UserAccount>> {
+        let accounts = sqlx::query_as::<_, UserAccount>(&format!(
+            "SELECT {ACCOUNT_COLUMNS}
              FROM accounts WHERE user_id = $1 AND is_active = true
+               AND ($2::TEXT IS NULL OR labels @> ARRAY[$2] OR LOWER(nickname) = $2)
              ORDER BY created_at DESC"
-        )
+        ))
         .bind(user_id)
+        .bind(label)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(Vec::new())
+        Ok(accounts)
+    }
+
+    /// Find an account by ID
+    pub async fn find_user_account(&self, account_id: AccountId) -> AppResult<Option<UserAccount>> {
+        let account = sqlx::query_as::<_, UserAccount>(&format!(
+            "SELECT {ACCOUNT_COLUMNS} FROM accounts WHERE id = $1"
+        ))
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(account)
+    }
+
+    /// Apply a change to an account's nickname, labels and metadata; with
+    /// `read_at`, only if the account is unchanged since it was read
+    pub async fn update_account_details(
+        &self,
+        account_id: AccountId,
+        change: &AccountDetailsChange,
+        read_at: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<UserAccount>> {
+        let account = sqlx::query_as::<_, UserAccount>(&format!(
+            "UPDATE accounts
+             SET nickname = CASE WHEN $2 THEN $3 ELSE nickname END,
+                 labels = COALESCE($4, labels),
+                 metadata = COALESCE($5, metadata),
+                 updated_at = NOW()
+             WHERE id = $1 AND ($6::TIMESTAMPTZ IS NULL OR updated_at = $6)
+             RETURNING {ACCOUNT_COLUMNS}"
+        ))
+        .bind(account_id)
+        .bind(change.nickname.is_some())
+        .bind(change.nickname.clone().flatten())
+        .bind(&change.labels)
+        .bind(&change.metadata)
+        .bind(read_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(account)
+    }
+
+    /// The user owning an account of the tenant
+    pub async fn find_account_owner(&self, account_id: AccountId, tenant_id: TenantId) -> AppResult<Option<UserId>> {
+        let owner = sqlx::query_scalar::<_, UserId>(
+            "SELECT user_id FROM accounts WHERE id = $1 AND tenant_id = $2",
+        )
+        .bind(account_id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(owner)
     }
 }
 
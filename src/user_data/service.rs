@@ -1,8 +1,19 @@
-use super::model::{BalanceHistory, BalanceResponse, UserAccountResponse, UserProfileResponse};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use super::model::{
+    BalanceAsOfResponse, BalanceError, BalanceHistory, BalanceResponse, BatchBalanceResult, UserAccountResponse,
+    UserProfileResponse,
+};
 use super::repository::UserDataRepository;
+use crate::auth::model::JwtClaims;
+use crate::core::conditional::{self, Preconditions};
 use crate::core::error::{AppError, AppResult};
+use crate::shared::account_labels::{normalize_label, UpdateAccountDetailsRequest};
 use crate::shared::types::{AccountId, Amount, UserId};
 
+/// Accounts whose balances can be read in one call at most
+pub const MAX_BATCH_ACCOUNTS: usize = 1000;
+
 pub struct UserDataService {
     repository: UserDataRepository,
 }
@@ -12,6 +23,20 @@ impl UserDataService {
         Self { repository }
     }
 
+    /// Check a token may reach an account: it must be in the token's tenant
+    /// and, for tokens acting for an end user, belong to that user. Accounts
+    /// out of reach are reported as not found.
+    pub async fn ensure_account_access(&self, claims: &JwtClaims, account_id: AccountId) -> AppResult<()> {
+        let owner = self
+            .repository
+            .find_account_owner(account_id, claims.tenant_id)
+            .await?;
+        match owner {
+            Some(owner) if claims.user_id.is_none_or(|user_id| user_id == owner) => Ok(()),
+            _ => Err(AppError::NotFound("Account not found".to_string())),
+        }
+    }
+
     /// Get current balance for account
     pub async fn get_balance(&self, account_id: AccountId) -> AppResult<BalanceResponse> {
         let balance = self
@@ -23,6 +48,80 @@ impl UserDataService {
         Ok(BalanceResponse::from(balance))
     }
 
+    /// Current balances of many accounts, read in one query, in the order
+    /// asked for. Accounts the token may not reach are reported as not found,
+    /// each on its own, rather than failing the whole call.
+    pub async fn batch_get_balances(
+        &self,
+        claims: &JwtClaims,
+        account_ids: &[AccountId],
+    ) -> AppResult<Vec<BatchBalanceResult>> {
+        if account_ids.is_empty() {
+            return Err(AppError::Validation("account_ids must not be empty".to_string()));
+        }
+        if account_ids.len() > MAX_BATCH_ACCOUNTS {
+            return Err(AppError::Validation(format!(
+                "At most {} accounts can be read at once",
+                MAX_BATCH_ACCOUNTS
+            )));
+        }
+
+        let balances: HashMap<AccountId, BalanceResponse> = self
+            .repository
+            .find_balances(account_ids, claims.tenant_id, claims.user_id)
+            .await?
+            .into_iter()
+            .map(|balance| (balance.account_id, BalanceResponse::from(balance)))
+            .collect();
+
+        Ok(account_ids
+            .iter()
+            .map(|&account_id| match balances.get(&account_id) {
+                Some(balance) => BatchBalanceResult {
+                    account_id,
+                    balance: Some(balance.clone()),
+                    error: None,
+                },
+                None => BatchBalanceResult {
+                    account_id,
+                    balance: None,
+                    error: Some(BalanceError {
+                        code: "NOT_FOUND".to_string(),
+                        message: "Account not found".to_string(),
+                    }),
+                },
+            })
+            .collect())
+    }
+
+    /// An account's ledger balance at a past time, counted from whichever
+    /// end of its history is nearer so old and recent dates both stay fast
+    pub async fn get_balance_as_of(&self, account_id: AccountId, as_of: DateTime<Utc>) -> AppResult<BalanceAsOfResponse> {
+        let now = Utc::now();
+        if as_of > now {
+            return Err(AppError::BadRequest("as_of must not be in the future".to_string()));
+        }
+        let opened_at = self
+            .repository
+            .find_account_opened_at(account_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Account not found".to_string()))?;
+
+        let balance = self
+            .repository
+            .find_ledger_balance_as_of(account_id, as_of, count_from_current(opened_at, as_of, now))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Balance not found for account".to_string()))?;
+
+        Ok(BalanceAsOfResponse {
+            account_id,
+            ledger_balance: balance.ledger_balance,
+            currency: balance.currency,
+            last_posted_at: balance.last_posted_at,
+            as_of,
+        })
+    }
+
     /// Get balance history for account
     pub async fn get_balance_history(
         &self,
@@ -55,23 +154,75 @@ impl UserDataService {
 
     /// Get user profile
     pub async fn get_user_profile(&self, user_id: UserId) -> AppResult<UserProfileResponse> {
-        // TODO: Implement user profile retrieval
-        // 1. Fetch user data from database
-        // 2. Return user profile
+        let profile = self
+            .repository
+            .find_user_profile(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User profile not found".to_string()))?;
 
-        // Placeholder implementation
-        let _ = user_id;
-        Err(AppError::NotFound("User profile not found".to_string()))
+        Ok(UserProfileResponse::from(profile))
     }
 
-    /// Get user accounts
-    pub async fn get_user_accounts(&self, user_id: UserId) -> AppResult<Vec<UserAccountResponse>> {
-        // TODO: Implement user accounts retrieval
-        // 1. Fetch all accounts for user
-        // 2. Return account list
+    /// Get user accounts, optionally only those with a label or nickname
+    pub async fn get_user_accounts(&self, user_id: UserId, label: Option<&str>) -> AppResult<Vec<UserAccountResponse>> {
+        let label = label.map(normalize_label).filter(|label| !label.is_empty());
+        let accounts = self.repository.find_user_accounts(user_id, label.as_deref()).await?;
+        Ok(accounts.into_iter().map(UserAccountResponse::from).collect())
+    }
 
-        // Placeholder implementation
-        let _ = user_id;
-        Ok(Vec::new())
+    /// Set an account's nickname, labels or metadata. Callers check access
+    /// with `ensure_account_access` first. With If-Match the change is only
+    /// made to the account as the client last read it.
+    pub async fn update_account_details(
+        &self,
+        account_id: AccountId,
+        request: UpdateAccountDetailsRequest,
+        preconditions: &Preconditions,
+    ) -> AppResult<UserAccountResponse> {
+        let change = request.into_change()?;
+        let read_at = if preconditions.is_conditional() {
+            let current = self
+                .repository
+                .find_user_account(account_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Account not found".to_string()))?;
+            let read_at = current.updated_at;
+            preconditions.ensure_match(&conditional::etag(&UserAccountResponse::from(current)))?;
+            Some(read_at)
+        } else {
+            None
+        };
+
+        match self.repository.update_account_details(account_id, &change, read_at).await? {
+            Some(account) => Ok(UserAccountResponse::from(account)),
+            // Changed by someone else between the check and the update
+            None if read_at.is_some() => Err(AppError::PreconditionFailed(
+                "The resource has changed since it was read".to_string(),
+            )),
+            None => Err(AppError::NotFound("Account not found".to_string())),
+        }
+    }
+}
+
+/// Whether `as_of` is nearer now than the account's opening, so fewer
+/// postings lie between it and the current balance than before it
+fn count_from_current(opened_at: DateTime<Utc>, as_of: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    now - as_of < as_of - opened_at
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn counts_from_the_nearer_end_of_the_history() {
+        let opened_at = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+
+        assert!(!count_from_current(opened_at, Utc.with_ymd_and_hms(2021, 1, 31, 23, 59, 59).unwrap(), now));
+        assert!(count_from_current(opened_at, Utc.with_ymd_and_hms(2025, 6, 30, 0, 0, 0).unwrap(), now));
+        // Before the account was opened there is nothing to count
+        assert!(!count_from_current(opened_at, Utc.with_ymd_and_hms(2019, 1, 1, 0, 0, 0).unwrap(), now));
     }
 }
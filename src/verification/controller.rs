@@ -0,0 +1,109 @@
+use axum::{extract::State, response::Json};
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::auth::model::JwtClaims;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::ApiJson,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::UserId;
+use super::model::{
+    ConfirmVerificationCodeRequest, RequestVerificationCodeRequest, VerificationCodeSent, VerificationSettings,
+    VerificationStatus,
+};
+use super::repository::VerificationRepository;
+use super::service::VerificationService;
+
+fn verification_service(state: &AppState) -> VerificationService {
+    VerificationService::new(
+        VerificationRepository::new(state.postgres.clone()),
+        VerificationSettings::from_config(&state.config),
+        state.mailer.clone(),
+        state.sms_sender.clone(),
+        state.audit_logger.clone(),
+    )
+}
+
+/// Contact details belong to an end user; project tokens have none
+fn end_user(claims: &JwtClaims) -> AppResult<UserId> {
+    claims
+        .user_id
+        .ok_or_else(|| AppError::Authorization("This endpoint requires a user access token".to_string()))
+}
+
+/// Which of the user's contact details are verified
+#[utoipa::path(
+    get,
+    path = "/api/v1/user-data/verification",
+    tag = "verification",
+    responses(
+        (status = 200, description = "The user's contact details", body = VerificationStatus),
+        (status = 403, description = "Not a user access token")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_verification_status(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+) -> AppResult<Json<ApiResponse<VerificationStatus>>> {
+    let user_id = end_user(&claims)?;
+    let status = verification_service(&state).status(user_id).await?;
+    Ok(Json(ApiResponse::success("Verification status retrieved successfully", status)))
+}
+
+/// Send a one-time code to the user's email address or phone number
+#[utoipa::path(
+    post,
+    path = "/api/v1/user-data/verification/codes",
+    tag = "verification",
+    request_body = RequestVerificationCodeRequest,
+    responses(
+        (status = 200, description = "Code sent", body = VerificationCodeSent),
+        (status = 400, description = "No or invalid phone number"),
+        (status = 403, description = "Not a user access token"),
+        (status = 409, description = "Already verified"),
+        (status = 429, description = "Too many codes requested; see the Retry-After header")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn request_verification_code(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<RequestVerificationCodeRequest>,
+) -> AppResult<Json<ApiResponse<VerificationCodeSent>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let user_id = end_user(&claims)?;
+    let sent = verification_service(&state).request_code(user_id, request).await?;
+    Ok(Json(ApiResponse::success("Verification code sent", sent)))
+}
+
+/// Confirm a code, marking the email address or phone number verified
+#[utoipa::path(
+    post,
+    path = "/api/v1/user-data/verification/confirm",
+    tag = "verification",
+    request_body = ConfirmVerificationCodeRequest,
+    responses(
+        (status = 200, description = "Contact detail verified", body = VerificationStatus),
+        (status = 400, description = "Invalid or expired code"),
+        (status = 403, description = "Not a user access token"),
+        (status = 409, description = "The contact details changed after the code was sent")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn confirm_verification_code(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<ConfirmVerificationCodeRequest>,
+) -> AppResult<Json<ApiResponse<VerificationStatus>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let user_id = end_user(&claims)?;
+    let status = verification_service(&state).confirm_code(user_id, request).await?;
+    Ok(Json(ApiResponse::success("Contact details verified successfully", status)))
+}
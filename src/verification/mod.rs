@@ -0,0 +1,14 @@
+pub mod controller;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(controller::get_verification_status))
+        .route("/codes", post(controller::request_verification_code))
+        .route("/confirm", post(controller::confirm_verification_code))
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::core::config::Config;
+
+/// Where a verification code is sent, and which contact detail it proves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "verification_channel", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationChannel {
+    /// The user's email address, shown as `is_verified` on the profile
+    Email,
+    /// The user's phone number, by text message
+    Phone,
+}
+
+/// A code sent to one of the user's contact details
+#[derive(Debug, Clone, FromRow)]
+pub struct VerificationCode {
+    pub id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub channel: VerificationChannel,
+    pub destination: String,
+    pub code_hash: String,
+    pub attempts: i32,
+    pub expires_at: DateTime<Utc>,
+    pub consumed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The user's contact details and which of them are proven
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct VerificationStatus {
+    pub email: String,
+    pub email_verified: bool,
+    pub phone: Option<String>,
+    pub phone_verified: bool,
+}
+
+/// Send a code to the user's email address or phone number
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RequestVerificationCodeRequest {
+    pub channel: VerificationChannel,
+    /// Phone number to verify, in E.164 form (`+15551234567`). Defaults to
+    /// the number on the profile; a new number replaces it once confirmed.
+    #[validate(length(max = 20))]
+    pub phone: Option<String>,
+}
+
+/// Prove the user received a code
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ConfirmVerificationCodeRequest {
+    pub channel: VerificationChannel,
+    #[validate(length(min = 1, max = 12))]
+    pub code: String,
+}
+
+/// Where a code went, and when another may be asked for
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationCodeSent {
+    pub channel: VerificationChannel,
+    /// The destination, partly hidden
+    pub destination: String,
+    pub expires_at: DateTime<Utc>,
+    pub resend_after: DateTime<Utc>,
+}
+
+/// How codes are issued and checked
+#[derive(Debug, Clone)]
+pub struct VerificationSettings {
+    /// How long a code can be confirmed
+    pub code_ttl: Duration,
+    /// Wrong guesses that spend a code
+    pub max_attempts: i32,
+    /// Least time between two codes on one channel
+    pub resend_interval: Duration,
+    /// Most codes sent on one channel in an hour
+    pub codes_per_hour: i64,
+    /// Key codes are hashed with before they are stored
+    pub code_key: String,
+}
+
+impl VerificationSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            code_ttl: Duration::minutes(config.verification_code_ttl_minutes),
+            max_attempts: config.verification_code_max_attempts,
+            resend_interval: Duration::seconds(config.verification_code_resend_seconds),
+            codes_per_hour: config.verification_codes_per_hour,
+            code_key: config.jwt_secret.clone(),
+        }
+    }
+}
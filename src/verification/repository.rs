@@ -0,0 +1,154 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use crate::core::error::AppResult;
+use crate::shared::types::UserId;
+use super::model::{VerificationChannel, VerificationCode, VerificationStatus};
+
+const CODE_COLUMNS: &str =
+    "id, user_id, channel, destination, code_hash, attempts, expires_at, consumed_at, created_at";
+
+const STATUS_COLUMNS: &str =
+    "email, COALESCE(is_verified, FALSE) AS email_verified, phone, phone_verified";
+
+#[derive(Clone)]
+pub struct VerificationRepository {
+    pool: PgPool,
+}
+
+impl VerificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The active user's contact details
+    pub async fn find_status(&self, user_id: UserId) -> AppResult<Option<VerificationStatus>> {
+        let status = sqlx::query_as::<_, VerificationStatus>(&format!(
+            "SELECT {STATUS_COLUMNS} FROM users WHERE id = $1 AND is_active = TRUE"
+        ))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(status)
+    }
+
+    /// When codes were sent on the channel since `since`, newest first
+    pub async fn find_sent_since(
+        &self,
+        user_id: UserId,
+        channel: VerificationChannel,
+        since: DateTime<Utc>,
+    ) -> AppResult<Vec<DateTime<Utc>>> {
+        let sent = sqlx::query_scalar(
+            "SELECT created_at FROM user_verification_codes
+             WHERE user_id = $1 AND channel = $2 AND created_at > $3
+             ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .bind(channel)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sent)
+    }
+
+    /// Store a code, spending any the user still had open on the channel
+    pub async fn create_code(
+        &self,
+        user_id: UserId,
+        channel: VerificationChannel,
+        destination: &str,
+        code_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<VerificationCode> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE user_verification_codes SET consumed_at = NOW()
+             WHERE user_id = $1 AND channel = $2 AND consumed_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(channel)
+        .execute(&mut *tx)
+        .await?;
+        let code = sqlx::query_as::<_, VerificationCode>(&format!(
+            "INSERT INTO user_verification_codes (user_id, channel, destination, code_hash, expires_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING {CODE_COLUMNS}"
+        ))
+        .bind(user_id)
+        .bind(channel)
+        .bind(destination)
+        .bind(code_hash)
+        .bind(expires_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(code)
+    }
+
+    /// Count an attempt against the user's open code on the channel,
+    /// spending it when `code_hash` matches. Returns the destination of a
+    /// matching code; codes that expired or ran out of attempts never match.
+    pub async fn redeem_code(
+        &self,
+        user_id: UserId,
+        channel: VerificationChannel,
+        code_hash: &str,
+        max_attempts: i32,
+    ) -> AppResult<Option<String>> {
+        let redeemed = sqlx::query_as::<_, (bool, String)>(
+            "UPDATE user_verification_codes
+             SET attempts = attempts + 1,
+                 consumed_at = CASE WHEN code_hash = $3 OR attempts + 1 >= $4 THEN NOW() END
+             WHERE id = (
+                 SELECT id FROM user_verification_codes
+                 WHERE user_id = $1 AND channel = $2 AND consumed_at IS NULL AND expires_at > NOW()
+                 ORDER BY created_at DESC
+                 LIMIT 1
+                 FOR UPDATE
+             )
+             RETURNING code_hash = $3, destination",
+        )
+        .bind(user_id)
+        .bind(channel)
+        .bind(code_hash)
+        .bind(max_attempts)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(redeemed.and_then(|(matched, destination)| matched.then_some(destination)))
+    }
+
+    /// Mark the email address verified, unless it changed since the code
+    /// was sent
+    pub async fn mark_email_verified(&self, user_id: UserId, email: &str) -> AppResult<Option<VerificationStatus>> {
+        let status = sqlx::query_as::<_, VerificationStatus>(&format!(
+            "UPDATE users SET is_verified = TRUE, updated_at = NOW()
+             WHERE id = $1 AND LOWER(email) = LOWER($2)
+             RETURNING {STATUS_COLUMNS}"
+        ))
+        .bind(user_id)
+        .bind(email)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(status)
+    }
+
+    /// Set the phone number the code was sent to and mark it verified
+    pub async fn mark_phone_verified(&self, user_id: UserId, phone: &str) -> AppResult<Option<VerificationStatus>> {
+        let status = sqlx::query_as::<_, VerificationStatus>(&format!(
+            "UPDATE users SET phone = $2, phone_verified = TRUE, updated_at = NOW()
+             WHERE id = $1
+             RETURNING {STATUS_COLUMNS}"
+        ))
+        .bind(user_id)
+        .bind(phone)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(status)
+    }
+}
@@ -0,0 +1,313 @@
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde_json::json;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::crypto::{hex, hmac_sha256};
+use crate::core::error::{AppError, AppResult};
+use crate::core::mailer::{EmailMessage, Mailer};
+use crate::core::sms::{SmsMessage, SmsSender};
+use crate::shared::types::UserId;
+use super::model::{
+    ConfirmVerificationCodeRequest, RequestVerificationCodeRequest, VerificationChannel, VerificationCodeSent,
+    VerificationSettings, VerificationStatus,
+};
+use super::repository::VerificationRepository;
+
+/// Proves users own the email address and phone number on their profile by
+/// sending them one-time codes to confirm
+pub struct VerificationService {
+    repository: VerificationRepository,
+    settings: VerificationSettings,
+    mailer: Arc<dyn Mailer>,
+    sms_sender: Arc<dyn SmsSender>,
+    audit_logger: AuditLogger,
+}
+
+impl VerificationService {
+    pub fn new(
+        repository: VerificationRepository,
+        settings: VerificationSettings,
+        mailer: Arc<dyn Mailer>,
+        sms_sender: Arc<dyn SmsSender>,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            settings,
+            mailer,
+            sms_sender,
+            audit_logger,
+        }
+    }
+
+    /// The user's contact details and which of them are proven
+    pub async fn status(&self, user_id: UserId) -> AppResult<VerificationStatus> {
+        self.repository
+            .find_status(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    /// Send a code to the user's email address or phone number, at most
+    /// once per resend interval and a few times an hour
+    pub async fn request_code(
+        &self,
+        user_id: UserId,
+        request: RequestVerificationCodeRequest,
+    ) -> AppResult<VerificationCodeSent> {
+        let status = self.status(user_id).await?;
+        let destination = match request.channel {
+            VerificationChannel::Email if status.email_verified => {
+                return Err(AppError::Conflict("The email address is already verified".to_string()));
+            }
+            VerificationChannel::Email => status.email.clone(),
+            VerificationChannel::Phone => {
+                let phone = request
+                    .phone
+                    .as_deref()
+                    .or(status.phone.as_deref())
+                    .ok_or_else(|| AppError::BadRequest("A phone number is required".to_string()))?;
+                let phone = normalize_phone(phone)?;
+                if status.phone_verified && status.phone.as_deref() == Some(phone.as_str()) {
+                    return Err(AppError::Conflict("The phone number is already verified".to_string()));
+                }
+                phone
+            }
+        };
+
+        let now = Utc::now();
+        let sent = self
+            .repository
+            .find_sent_since(user_id, request.channel, now - Duration::hours(1))
+            .await?;
+        if let Some(retry_after) = throttle(&self.settings, &sent, now) {
+            self.audit_logger
+                .log(
+                    AuditEvent::new(AuditEventType::RateLimitExceeded)
+                        .severity(AuditSeverity::Warning)
+                        .user_id(user_id)
+                        .action("request_verification_code".to_string())
+                        .metadata("channel".to_string(), json!(request.channel))
+                        .success(false),
+                )
+                .await;
+            return Err(AppError::TooManyRequests {
+                reason: "Too many verification codes requested".to_string(),
+                retry_after_seconds: retry_after.num_seconds().max(1),
+            });
+        }
+
+        let code = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+        let expires_at = now + self.settings.code_ttl;
+        let stored = self
+            .repository
+            .create_code(
+                user_id,
+                request.channel,
+                &destination,
+                &self.code_hash(user_id, request.channel, &code),
+                expires_at,
+            )
+            .await?;
+
+        let minutes = self.settings.code_ttl.num_minutes();
+        match request.channel {
+            VerificationChannel::Email => {
+                self.mailer
+                    .send(EmailMessage {
+                        to: destination.clone(),
+                        subject: "Verify your email address".to_string(),
+                        body: format!(
+                            "Your verification code is {}. It expires in {} minutes.\n\n\
+                             If you did not ask for it, you can ignore this email.",
+                            code, minutes
+                        ),
+                    })
+                    .await?
+            }
+            VerificationChannel::Phone => {
+                self.sms_sender
+                    .send(SmsMessage {
+                        to: destination.clone(),
+                        body: format!("Your verification code is {}. It expires in {} minutes.", code, minutes),
+                    })
+                    .await?
+            }
+        }
+
+        let event = AuditEvent::new(AuditEventType::ContactVerificationRequested)
+            .user_id(user_id)
+            .resource(format!("user:{}", user_id))
+            .action("request_verification_code".to_string())
+            .metadata("channel".to_string(), json!(request.channel))
+            .metadata("destination".to_string(), json!(mask_destination(&destination)))
+            .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(VerificationCodeSent {
+            channel: request.channel,
+            destination: mask_destination(&destination),
+            expires_at: stored.expires_at,
+            resend_after: stored.created_at + self.settings.resend_interval,
+        })
+    }
+
+    /// Confirm a code, marking the contact detail it was sent to verified.
+    /// A phone number confirmed this way becomes the one on the profile.
+    pub async fn confirm_code(
+        &self,
+        user_id: UserId,
+        request: ConfirmVerificationCodeRequest,
+    ) -> AppResult<VerificationStatus> {
+        let code = request.code.trim();
+        let destination = self
+            .repository
+            .redeem_code(
+                user_id,
+                request.channel,
+                &self.code_hash(user_id, request.channel, code),
+                self.settings.max_attempts,
+            )
+            .await?;
+
+        let Some(destination) = destination else {
+            let event = AuditEvent::new(AuditEventType::ContactVerificationFailed)
+                .severity(AuditSeverity::Warning)
+                .user_id(user_id)
+                .resource(format!("user:{}", user_id))
+                .action("confirm_verification_code".to_string())
+                .metadata("channel".to_string(), json!(request.channel))
+                .success(false)
+                .compliance_tag("KYC".to_string());
+            self.audit_logger.log(event).await;
+            return Err(AppError::BadRequest("Invalid or expired verification code".to_string()));
+        };
+
+        let status = match request.channel {
+            VerificationChannel::Email => self.repository.mark_email_verified(user_id, &destination).await?,
+            VerificationChannel::Phone => self.repository.mark_phone_verified(user_id, &destination).await?,
+        }
+        .ok_or_else(|| AppError::Conflict("The contact details changed after the code was sent".to_string()))?;
+
+        let event = AuditEvent::new(AuditEventType::ContactVerified)
+            .user_id(user_id)
+            .resource(format!("user:{}", user_id))
+            .action("confirm_verification_code".to_string())
+            .metadata("channel".to_string(), json!(request.channel))
+            .metadata("destination".to_string(), json!(mask_destination(&destination)))
+            .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        Ok(status)
+    }
+
+    fn code_hash(&self, user_id: UserId, channel: VerificationChannel, code: &str) -> String {
+        let channel = match channel {
+            VerificationChannel::Email => "email",
+            VerificationChannel::Phone => "phone",
+        };
+        hex(&hmac_sha256(
+            self.settings.code_key.as_bytes(),
+            format!("verify:{}:{}:{}", user_id, channel, code).as_bytes(),
+        ))
+    }
+}
+
+/// How long until another code may be sent, given when the codes of the
+/// last hour were sent (newest first); `None` when one may be sent now
+pub fn throttle(settings: &VerificationSettings, sent: &[DateTime<Utc>], now: DateTime<Utc>) -> Option<Duration> {
+    let resend = sent
+        .first()
+        .map(|last| *last + settings.resend_interval - now)
+        .filter(|wait| *wait > Duration::zero());
+    let hourly = (sent.len() as i64 >= settings.codes_per_hour)
+        .then(|| sent.get(settings.codes_per_hour.max(1) as usize - 1))
+        .flatten()
+        .map(|oldest| *oldest + Duration::hours(1) - now)
+        .filter(|wait| *wait > Duration::zero());
+    resend.max(hourly)
+}
+
+/// A phone number in E.164 form: `+` and 8 to 15 digits. Spaces, dashes,
+/// dots and brackets people type are dropped.
+pub fn normalize_phone(phone: &str) -> AppResult<String> {
+    let phone: String = phone
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+    let digits = phone.strip_prefix('+').unwrap_or_default();
+    if !(8..=15).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_digit()) || digits.starts_with('0') {
+        return Err(AppError::Validation(
+            "Phone numbers must be in international form, such as +15551234567".to_string(),
+        ));
+    }
+    Ok(phone)
+}
+
+/// A destination with most of it hidden: `j***@example.com`, `*******4567`
+pub fn mask_destination(destination: &str) -> String {
+    match destination.split_once('@') {
+        Some((local, domain)) => {
+            let first: String = local.chars().take(1).collect();
+            format!("{}***@{}", first, domain)
+        }
+        None => {
+            let count = destination.chars().count();
+            destination
+                .chars()
+                .enumerate()
+                .map(|(index, c)| if index + 4 < count { '*' } else { c })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn settings() -> VerificationSettings {
+        VerificationSettings {
+            code_ttl: Duration::minutes(10),
+            max_attempts: 5,
+            resend_interval: Duration::seconds(60),
+            codes_per_hour: 3,
+            code_key: "key".to_string(),
+        }
+    }
+
+    #[test]
+    fn codes_wait_for_the_resend_interval_and_the_hourly_limit() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let settings = settings();
+
+        assert_eq!(throttle(&settings, &[], now), None);
+        assert_eq!(throttle(&settings, &[now - Duration::seconds(20)], now), Some(Duration::seconds(40)));
+        assert_eq!(throttle(&settings, &[now - Duration::seconds(60)], now), None);
+
+        // The third code of the hour was sent 50 minutes ago
+        let sent = [
+            now - Duration::minutes(5),
+            now - Duration::minutes(20),
+            now - Duration::minutes(50),
+        ];
+        assert_eq!(throttle(&settings, &sent, now), Some(Duration::minutes(10)));
+    }
+
+    #[test]
+    fn phone_numbers_must_be_international() {
+        assert_eq!(normalize_phone("+1 (555) 123-4567").unwrap(), "+15551234567");
+        assert!(normalize_phone("5551234567").is_err());
+        assert!(normalize_phone("+0123456789").is_err());
+        assert!(normalize_phone("+1555").is_err());
+        assert!(normalize_phone("+1555123456x").is_err());
+    }
+
+    #[test]
+    fn destinations_are_masked() {
+        assert_eq!(mask_destination("jane@example.com"), "j***@example.com");
+        assert_eq!(mask_destination("+15551234567"), "********4567");
+    }
+}
@@ -0,0 +1,193 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::core::circuit_breaker::{CircuitBreaker, CircuitBreakerSettings, CircuitBreakers};
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::{HttpClient, HttpClients};
+use super::model::{SessionStep, StepUpload};
+
+/// What a provider made of a step's upload
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepCheck {
+    Passed { reference: Option<String> },
+    /// The upload was rejected, e.g. a blurred document or a face that does
+    /// not match; the user may upload again
+    Failed { reference: Option<String>, reason: String },
+    /// The provider could not decide; a reviewer has to
+    NeedsReview { reference: Option<String> },
+}
+
+/// Checks the uploads of a session step: document authenticity, a selfie
+/// matching the document photo, liveness
+#[async_trait]
+pub trait SessionChecks: Send + Sync {
+    /// Name stored with the verifications a session records, matching
+    /// `VERIFICATION_SESSION_PROVIDER`
+    fn name(&self) -> &'static str;
+
+    async fn check(
+        &self,
+        session_id: Uuid,
+        document_type: &str,
+        step: SessionStep,
+        upload: &StepUpload,
+    ) -> AppResult<StepCheck>;
+}
+
+/// Used when no provider is configured: every upload goes to a reviewer
+pub struct ManualSessionChecks;
+
+#[async_trait]
+impl SessionChecks for ManualSessionChecks {
+    fn name(&self) -> &'static str {
+        "manual"
+    }
+
+    async fn check(
+        &self,
+        _session_id: Uuid,
+        _document_type: &str,
+        _step: SessionStep,
+        _upload: &StepUpload,
+    ) -> AppResult<StepCheck> {
+        Ok(StepCheck::NeedsReview { reference: None })
+    }
+}
+
+/// Name of the session provider's circuit breaker
+const SESSION_CHECKS_BREAKER: &str = "verification_session";
+
+/// Talks to an HTTP identity API taking `{reference, document_type,
+/// content_type, content}` JSON (content base64 encoded) at
+/// `/checks/{step}` with a bearer API key and answering `{result, reference,
+/// reason}`, where `result` is `passed`, `failed` or `review`
+pub struct HttpSessionChecks {
+    client: HttpClient,
+    api_url: String,
+    api_key: String,
+    breaker: CircuitBreaker,
+}
+
+impl HttpSessionChecks {
+    pub fn new(api_url: String, api_key: String) -> Self {
+        Self {
+            client: HttpClient::default(),
+            api_url,
+            api_key,
+            breaker: CircuitBreaker::new(SESSION_CHECKS_BREAKER, CircuitBreakerSettings::default()),
+        }
+    }
+
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    async fn send(
+        &self,
+        session_id: Uuid,
+        document_type: &str,
+        step: SessionStep,
+        upload: &StepUpload,
+    ) -> AppResult<StepCheck> {
+        let request = self
+            .client
+            .post(format!("{}/checks/{}", self.api_url.trim_end_matches('/'), step.as_str()))
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({
+                "reference": session_id,
+                "document_type": document_type,
+                "content_type": upload.content_type,
+                "content": STANDARD.encode(&upload.content),
+            }));
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Identity check request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ExternalService(format!(
+                "Identity check provider returned {}",
+                response.status()
+            )));
+        }
+
+        let body: HttpCheck = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalService(format!("Invalid identity check response: {}", e)))?;
+        match body.result.as_str() {
+            "passed" => Ok(StepCheck::Passed { reference: body.reference }),
+            "failed" => Ok(StepCheck::Failed {
+                reference: body.reference,
+                reason: body.reason.unwrap_or_else(|| "The upload was rejected".to_string()),
+            }),
+            "review" => Ok(StepCheck::NeedsReview { reference: body.reference }),
+            other => Err(AppError::ExternalService(format!("Unknown identity check result '{}'", other))),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpCheck {
+    result: String,
+    reference: Option<String>,
+    reason: Option<String>,
+}
+
+#[async_trait]
+impl SessionChecks for HttpSessionChecks {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn check(
+        &self,
+        session_id: Uuid,
+        document_type: &str,
+        step: SessionStep,
+        upload: &StepUpload,
+    ) -> AppResult<StepCheck> {
+        self.breaker.call(self.send(session_id, document_type, step, upload)).await
+    }
+}
+
+/// Build the step checks selected by `VERIFICATION_SESSION_PROVIDER`
+pub fn from_config(
+    config: &Config,
+    breakers: &CircuitBreakers,
+    clients: &HttpClients,
+) -> AppResult<Arc<dyn SessionChecks>> {
+    match config.verification_session_provider.as_str() {
+        "manual" => Ok(Arc::new(ManualSessionChecks)),
+        "http" => {
+            let missing = |name: &str| {
+                AppError::Internal(format!("{} is required for the http verification session provider", name))
+            };
+            Ok(Arc::new(
+                HttpSessionChecks::new(
+                    config
+                        .verification_session_api_url
+                        .clone()
+                        .ok_or_else(|| missing("VERIFICATION_SESSION_API_URL"))?,
+                    config
+                        .verification_session_api_key
+                        .clone()
+                        .ok_or_else(|| missing("VERIFICATION_SESSION_API_KEY"))?,
+                )
+                .with_circuit_breaker(breakers.get(SESSION_CHECKS_BREAKER))
+                .with_http_client(clients.get(SESSION_CHECKS_BREAKER)),
+            ))
+        }
+        other => Err(AppError::Internal(format!("Unknown verification session provider '{}'", other))),
+    }
+}
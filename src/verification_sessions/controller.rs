@@ -0,0 +1,229 @@
+use axum::{
+    extract::{FromRequest, Multipart, Path, Request, State},
+    http::{header, StatusCode},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{error::{AppError, AppResult}, extractors::ApiJson, response::ApiResponse, AppState};
+use crate::identity::repository::IdentityRepository;
+use crate::identity::screening::{self, ScreeningSettings};
+use crate::identity::service::ScreeningService;
+use crate::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use crate::reviews::{repository::ReviewRepository, service::ReviewQueue};
+use crate::uploads::controller::upload_service;
+use super::checks;
+use super::model::{
+    CreateVerificationSessionRequest, SessionSettings, SessionStep, StepUpload, StepUploadReference,
+    VerificationSessionResponse,
+};
+use super::repository::VerificationSessionRepository;
+use super::service::VerificationSessionService;
+
+pub(crate) fn session_service(state: &AppState) -> AppResult<VerificationSessionService> {
+    let review_queue = ReviewQueue::new(ReviewRepository::new(state.postgres.clone()), state.audit_logger.clone());
+    let service = VerificationSessionService::new(
+        VerificationSessionRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+        state.storage.clone(),
+        upload_service(state),
+        checks::from_config(&state.config, &state.circuit_breakers, &state.http_clients)?,
+        SessionSettings::from_config(&state.config),
+        review_queue.clone(),
+        KycPolicyService::new(
+            KycRepository::new(state.postgres.clone()),
+            KycLimits::from_config(&state.config),
+            state.audit_logger.clone(),
+        ),
+        state.audit_logger.clone(),
+    );
+
+    match screening::from_config(&state.config, &state.circuit_breakers, &state.http_clients)? {
+        Some(provider) => Ok(service.with_screening(ScreeningService::new(
+            IdentityRepository::new(state.postgres.clone(), state.user_cipher.clone()),
+            provider,
+            ScreeningSettings::from_config(&state.config),
+            review_queue,
+            state.audit_logger.clone(),
+        ))),
+        None => Ok(service),
+    }
+}
+
+/// Start a verification session, or resume the user's active one
+#[utoipa::path(
+    post,
+    path = "/api/v1/identity/sessions",
+    tag = "identity",
+    request_body = CreateVerificationSessionRequest,
+    responses(
+        (status = 201, description = "Session started or resumed", body = VerificationSessionResponse),
+        (status = 400, description = "Invalid steps"),
+        (status = 404, description = "User not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_session(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ApiJson(request): ApiJson<CreateVerificationSessionRequest>,
+) -> AppResult<(StatusCode, Json<ApiResponse<VerificationSessionResponse>>)> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+    let session = session_service(&state)?
+        .create(
+            request,
+            claims.tenant_id,
+            claims.user_id,
+            claims.user_id.unwrap_or(claims.developer_id),
+        )
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success("Verification session started successfully", session)),
+    ))
+}
+
+/// Get a verification session with the status of each step and the step
+/// to upload next
+#[utoipa::path(
+    get,
+    path = "/api/v1/identity/sessions/{id}",
+    tag = "identity",
+    params(("id" = Uuid, Path, description = "Verification session ID")),
+    responses(
+        (status = 200, description = "The session", body = VerificationSessionResponse),
+        (status = 404, description = "Session not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_session(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(session_id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<VerificationSessionResponse>>> {
+    let session = session_service(&state)?
+        .get(session_id, claims.tenant_id, claims.user_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Verification session retrieved successfully", session)))
+}
+
+/// Upload a step of a verification session: multipart with a `file` part,
+/// or JSON referencing a direct upload, plus the document number with the
+/// document step
+#[utoipa::path(
+    post,
+    path = "/api/v1/identity/sessions/{id}/steps/{step}",
+    tag = "identity",
+    params(
+        ("id" = Uuid, Path, description = "Verification session ID"),
+        ("step" = SessionStep, Path, description = "Step to upload")
+    ),
+    request_body(
+        content = StepUploadReference,
+        description = "JSON, or multipart/form-data with a `file` part and a `document_number` field"
+    ),
+    responses(
+        (status = 200, description = "Step uploaded and checked", body = VerificationSessionResponse),
+        (status = 400, description = "Unsupported upload"),
+        (status = 404, description = "Session or step not found"),
+        (status = 409, description = "Step out of order, already uploaded, or session closed"),
+        (status = 502, description = "The verification provider failed")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn submit_step(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path((session_id, step)): Path<(Uuid, SessionStep)>,
+    request: Request,
+) -> AppResult<Json<ApiResponse<VerificationSessionResponse>>> {
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("multipart/form-data"));
+    let service = session_service(&state)?;
+    let actor_id = claims.user_id.unwrap_or(claims.developer_id);
+
+    let session = if is_multipart {
+        let multipart = Multipart::from_request(request, &state)
+            .await
+            .map_err(|rejection| AppError::BadRequest(rejection.body_text()))?;
+        let upload = read_upload(multipart).await?;
+        service
+            .submit_step(session_id, step, upload, claims.tenant_id, claims.user_id, actor_id)
+            .await?
+    } else {
+        let ApiJson(reference) = ApiJson::<StepUploadReference>::from_request(request, &state).await?;
+        if let Err(validation_errors) = reference.validate() {
+            return Err(AppError::InvalidFields(validation_errors));
+        }
+        service
+            .submit_uploaded_step(session_id, step, reference, claims.tenant_id, claims.user_id, actor_id)
+            .await?
+    };
+    Ok(Json(ApiResponse::success("Verification step uploaded successfully", session)))
+}
+
+/// Decide a verification session once every step is uploaded
+#[utoipa::path(
+    post,
+    path = "/api/v1/identity/sessions/{id}/complete",
+    tag = "identity",
+    params(("id" = Uuid, Path, description = "Verification session ID")),
+    responses(
+        (status = 200, description = "Session approved or sent to review", body = VerificationSessionResponse),
+        (status = 404, description = "Session not found"),
+        (status = 409, description = "Steps still to upload, or session closed")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn complete_session(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(session_id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<VerificationSessionResponse>>> {
+    let session = session_service(&state)?
+        .complete(
+            session_id,
+            claims.tenant_id,
+            claims.user_id,
+            claims.user_id.unwrap_or(claims.developer_id),
+        )
+        .await?;
+    Ok(Json(ApiResponse::success("Verification session completed successfully", session)))
+}
+
+/// Read the `file` part and `document_number` field of a step upload
+async fn read_upload(mut multipart: Multipart) -> AppResult<StepUpload> {
+    let invalid = |error: axum::extract::multipart::MultipartError| {
+        AppError::BadRequest(format!("Invalid multipart upload: {}", error.body_text()))
+    };
+    let mut file = None;
+    let mut document_number = None;
+
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        match field.name().unwrap_or_default() {
+            "file" => {
+                let file_name = field.file_name().unwrap_or("upload").to_string();
+                let content_type = field.content_type().unwrap_or("application/octet-stream").to_string();
+                let content = field.bytes().await.map_err(invalid)?;
+                file = Some((file_name, content_type, content.to_vec()));
+            }
+            "document_number" => document_number = Some(field.text().await.map_err(invalid)?),
+            _ => {}
+        }
+    }
+
+    let (file_name, content_type, content) =
+        file.ok_or_else(|| AppError::Validation("Missing file part 'file'".to_string()))?;
+    Ok(StepUpload {
+        file_name,
+        content_type,
+        content,
+        document_number,
+    })
+}
@@ -0,0 +1,34 @@
+use crate::core::AppState;
+use super::controller::session_service;
+
+const SESSION_EXPIRY_JOB: &str = "verification_session_expiry";
+
+/// Periodically expire verification sessions not decided in time and delete
+/// their uploads
+pub fn spawn_session_expiry_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.verification_session_expiry_check_interval_seconds);
+    state.job_monitor.register(SESSION_EXPIRY_JOB, period);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            let result = match session_service(&state) {
+                Ok(service) => service.expire_due().await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(count) => {
+                    state.job_monitor.record_success(SESSION_EXPIRY_JOB);
+                    if count > 0 {
+                        tracing::info!("Expired {} verification sessions", count);
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(SESSION_EXPIRY_JOB, e.to_string());
+                    tracing::error!("Verification session expiry job failed: {}", e);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,24 @@
+pub mod checks;
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{extract::DefaultBodyLimit, routing::{get, post}, Router};
+use crate::core::AppState;
+use crate::shared::constants::MAX_SESSION_UPLOAD_SIZE;
+
+/// Room for one upload plus the multipart framing and the document number
+const STEP_BODY_LIMIT: usize = MAX_SESSION_UPLOAD_SIZE + 64 * 1024;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(controller::create_session))
+        .route("/:id", get(controller::get_session))
+        .route(
+            "/:id/steps/:step",
+            post(controller::submit_step).layer(DefaultBodyLimit::max(STEP_BODY_LIMIT)),
+        )
+        .route("/:id/complete", post(controller::complete_session))
+}
@@ -0,0 +1,346 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+use crate::core::config::Config;
+use crate::core::error::{AppError, AppResult};
+use crate::shared::types::UserId;
+use crate::uploads::model::UploadPurpose;
+
+/// Where a session is in its life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "verification_session_status", rename_all = "snake_case")]
+pub enum VerificationSessionStatus {
+    /// Taking uploads
+    Active,
+    /// Waiting for a reviewer to decide the verifications it produced
+    InReview,
+    Approved,
+    /// A step failed too many times
+    Rejected,
+    /// Not decided in time; its uploads are deleted
+    Expired,
+}
+
+/// A step of a session, in the order steps are completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "verification_session_step", rename_all = "snake_case")]
+pub enum SessionStep {
+    /// A photo or scan of the ID document
+    Document,
+    /// A photo of the user's face, matched to the document
+    Selfie,
+    /// A short video proving a live person took the selfie
+    Liveness,
+}
+
+impl SessionStep {
+    /// Content types an upload for the step may have
+    pub fn accepted_content_types(&self) -> &'static [&'static str] {
+        self.upload_purpose().accepted_content_types()
+    }
+
+    /// The purpose a direct upload for the step is issued for
+    pub fn upload_purpose(&self) -> UploadPurpose {
+        match self {
+            SessionStep::Document => UploadPurpose::IdentityDocument,
+            SessionStep::Selfie => UploadPurpose::Selfie,
+            SessionStep::Liveness => UploadPurpose::LivenessVideo,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionStep::Document => "document",
+            SessionStep::Selfie => "selfie",
+            SessionStep::Liveness => "liveness",
+        }
+    }
+}
+
+/// Where a step is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "verification_step_status", rename_all = "snake_case")]
+pub enum StepStatus {
+    /// Nothing uploaded yet
+    Pending,
+    /// Uploaded; a reviewer has to check it
+    Submitted,
+    /// Uploaded and checked by the provider
+    Passed,
+    /// The provider rejected the upload; it may be retried
+    Failed,
+}
+
+impl StepStatus {
+    /// The step needs no further uploads
+    pub fn is_done(&self) -> bool {
+        matches!(self, StepStatus::Submitted | StepStatus::Passed)
+    }
+}
+
+/// A verification session
+#[derive(Debug, Clone, FromRow)]
+pub struct VerificationSession {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub document_type: String,
+    /// Decrypted as it is read; `None` until the document step is uploaded
+    /// and once the user's data key is shredded
+    pub document_number: Option<String>,
+    pub status: VerificationSessionStatus,
+    pub decision_reason: Option<String>,
+    pub identity_verification_id: Option<Uuid>,
+    pub face_verification_id: Option<Uuid>,
+    pub created_by: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One step of a session
+#[derive(Debug, Clone, FromRow)]
+pub struct SessionStepRecord {
+    pub session_id: Uuid,
+    pub step: SessionStep,
+    pub position: i16,
+    pub status: StepStatus,
+    pub attempts: i32,
+    pub storage_key: Option<String>,
+    pub content_type: Option<String>,
+    pub provider_reference: Option<String>,
+    pub failure_reason: Option<String>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Start a verification session, or resume the user's active one
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateVerificationSessionRequest {
+    /// The user to verify; required with project tokens, and with user
+    /// tokens it must be the token's user
+    pub user_id: Option<UserId>,
+    /// Kind of ID document, e.g. `passport` or `driving_licence`
+    #[validate(length(min = 1, max = 100))]
+    pub document_type: String,
+    /// Defaults to every step; the document step is always included
+    pub steps: Option<Vec<SessionStep>>,
+}
+
+/// An upload for a step
+#[derive(Debug, Clone)]
+pub struct StepUpload {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+    /// The number on the ID document, sent with the document step
+    pub document_number: Option<String>,
+}
+
+/// A step uploaded directly to storage, referenced instead of sent as
+/// multipart
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct StepUploadReference {
+    /// Id of a direct upload issued for the step's purpose
+    pub upload_id: Uuid,
+    /// The number on the ID document, sent with the document step
+    #[validate(length(min = 1, max = 100))]
+    pub document_number: Option<String>,
+}
+
+/// A step and how far it is
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionStepResponse {
+    pub step: SessionStep,
+    pub status: StepStatus,
+    pub attempts: i32,
+    pub attempts_remaining: i32,
+    pub failure_reason: Option<String>,
+    pub submitted_at: Option<DateTime<Utc>>,
+}
+
+/// A verification session with its steps
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VerificationSessionResponse {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub document_type: String,
+    pub status: VerificationSessionStatus,
+    pub steps: Vec<SessionStepResponse>,
+    /// The step to upload next, where a resumed session picks up
+    pub next_step: Option<SessionStep>,
+    /// Every step is uploaded and the decision can be asked for
+    pub ready_to_complete: bool,
+    pub decision_reason: Option<String>,
+    /// The ID document verification the decision recorded
+    pub identity_verification_id: Option<Uuid>,
+    /// The face enrollment the decision recorded
+    pub face_verification_id: Option<Uuid>,
+    pub expires_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl VerificationSessionResponse {
+    pub fn new(session: VerificationSession, steps: &[SessionStepRecord], max_step_attempts: i32) -> Self {
+        Self {
+            id: session.id,
+            user_id: session.user_id,
+            document_type: session.document_type,
+            status: session.status,
+            next_step: (session.status == VerificationSessionStatus::Active)
+                .then(|| next_step(steps))
+                .flatten(),
+            ready_to_complete: session.status == VerificationSessionStatus::Active
+                && steps.iter().all(|step| step.status.is_done()),
+            steps: steps
+                .iter()
+                .map(|step| SessionStepResponse {
+                    step: step.step,
+                    status: step.status,
+                    attempts: step.attempts,
+                    attempts_remaining: (max_step_attempts - step.attempts).max(0),
+                    failure_reason: step.failure_reason.clone(),
+                    submitted_at: step.submitted_at,
+                })
+                .collect(),
+            decision_reason: session.decision_reason,
+            identity_verification_id: session.identity_verification_id,
+            face_verification_id: session.face_verification_id,
+            expires_at: session.expires_at,
+            decided_at: session.decided_at,
+            created_at: session.created_at,
+        }
+    }
+}
+
+/// How sessions run
+#[derive(Debug, Clone)]
+pub struct SessionSettings {
+    /// How long a session takes uploads before it expires
+    pub ttl: Duration,
+    /// Uploads allowed per step before the session is rejected
+    pub max_step_attempts: i32,
+}
+
+impl SessionSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            ttl: Duration::minutes(config.verification_session_ttl_minutes),
+            max_step_attempts: config.verification_session_max_step_attempts,
+        }
+    }
+}
+
+/// What the steps add up to once all are uploaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionDecision {
+    /// The provider passed every step
+    Approved,
+    /// Some steps were only submitted and need a reviewer
+    Review,
+}
+
+/// The steps a session runs, in order. The document step is required
+/// since the decision is recorded against the ID document.
+pub fn session_steps(requested: Option<Vec<SessionStep>>) -> AppResult<Vec<SessionStep>> {
+    let mut steps =
+        requested.unwrap_or_else(|| vec![SessionStep::Document, SessionStep::Selfie, SessionStep::Liveness]);
+    steps.sort();
+    steps.dedup();
+    if steps.first() != Some(&SessionStep::Document) {
+        return Err(AppError::Validation("A verification session needs the document step".to_string()));
+    }
+    if steps.contains(&SessionStep::Liveness) && !steps.contains(&SessionStep::Selfie) {
+        return Err(AppError::Validation("The liveness step needs the selfie step".to_string()));
+    }
+    Ok(steps)
+}
+
+/// The first step still needing an upload
+pub fn next_step(steps: &[SessionStepRecord]) -> Option<SessionStep> {
+    let mut ordered: Vec<&SessionStepRecord> = steps.iter().collect();
+    ordered.sort_by_key(|step| step.position);
+    ordered.into_iter().find(|step| !step.status.is_done()).map(|step| step.step)
+}
+
+/// The decision for a session whose steps are all uploaded
+pub fn aggregate_decision(steps: &[SessionStepRecord]) -> SessionDecision {
+    if steps.iter().all(|step| step.status == StepStatus::Passed) {
+        SessionDecision::Approved
+    } else {
+        SessionDecision::Review
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(step: SessionStep, position: i16, status: StepStatus) -> SessionStepRecord {
+        SessionStepRecord {
+            session_id: Uuid::nil(),
+            step,
+            position,
+            status,
+            attempts: 0,
+            storage_key: None,
+            content_type: None,
+            provider_reference: None,
+            failure_reason: None,
+            submitted_at: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn steps_are_ordered_and_need_the_document() {
+        assert_eq!(
+            session_steps(None).unwrap(),
+            [SessionStep::Document, SessionStep::Selfie, SessionStep::Liveness]
+        );
+        assert_eq!(
+            session_steps(Some(vec![SessionStep::Selfie, SessionStep::Document, SessionStep::Selfie])).unwrap(),
+            [SessionStep::Document, SessionStep::Selfie]
+        );
+        assert!(session_steps(Some(vec![SessionStep::Selfie])).is_err());
+        assert!(session_steps(Some(vec![SessionStep::Document, SessionStep::Liveness])).is_err());
+    }
+
+    #[test]
+    fn sessions_resume_at_the_first_step_not_uploaded() {
+        let steps = [
+            step(SessionStep::Liveness, 2, StepStatus::Pending),
+            step(SessionStep::Document, 0, StepStatus::Passed),
+            step(SessionStep::Selfie, 1, StepStatus::Failed),
+        ];
+        assert_eq!(next_step(&steps), Some(SessionStep::Selfie));
+
+        let uploaded = [
+            step(SessionStep::Document, 0, StepStatus::Passed),
+            step(SessionStep::Selfie, 1, StepStatus::Submitted),
+        ];
+        assert_eq!(next_step(&uploaded), None);
+    }
+
+    #[test]
+    fn only_steps_the_provider_passed_approve_a_session() {
+        let passed = [
+            step(SessionStep::Document, 0, StepStatus::Passed),
+            step(SessionStep::Selfie, 1, StepStatus::Passed),
+        ];
+        assert_eq!(aggregate_decision(&passed), SessionDecision::Approved);
+
+        let submitted = [
+            step(SessionStep::Document, 0, StepStatus::Passed),
+            step(SessionStep::Selfie, 1, StepStatus::Submitted),
+        ];
+        assert_eq!(aggregate_decision(&submitted), SessionDecision::Review);
+    }
+}
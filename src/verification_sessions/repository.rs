@@ -0,0 +1,263 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::crypto::UserCipher;
+use crate::core::error::AppResult;
+use crate::shared::types::UserId;
+use super::model::{SessionStep, SessionStepRecord, StepStatus, VerificationSession, VerificationSessionStatus};
+
+const SESSION_COLUMNS: &str = "id, user_id, document_type, document_number, status, decision_reason,
+    identity_verification_id, face_verification_id, created_by, expires_at, decided_at, created_at, updated_at";
+
+const STEP_COLUMNS: &str = "session_id, step, position, status, attempts, storage_key, content_type,
+    provider_reference, failure_reason, submitted_at, updated_at";
+
+/// Verification session persistence. The document number is encrypted under
+/// the user's data key.
+#[derive(Clone)]
+pub struct VerificationSessionRepository {
+    pool: PgPool,
+    cipher: UserCipher,
+}
+
+impl VerificationSessionRepository {
+    pub fn new(pool: PgPool, cipher: UserCipher) -> Self {
+        Self { pool, cipher }
+    }
+
+    /// Store a new session with its steps, in order. Returns `None` when the
+    /// user already has an active session.
+    pub async fn create(
+        &self,
+        user_id: UserId,
+        document_type: &str,
+        steps: &[SessionStep],
+        created_by: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> AppResult<Option<VerificationSession>> {
+        let mut tx = self.pool.begin().await?;
+        let session = sqlx::query_as::<_, VerificationSession>(&format!(
+            "INSERT INTO verification_sessions (user_id, document_type, created_by, expires_at)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (user_id) WHERE status = 'active' DO NOTHING
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(user_id)
+        .bind(document_type)
+        .bind(created_by)
+        .bind(expires_at)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(session) = session else {
+            return Ok(None);
+        };
+
+        for (position, step) in steps.iter().enumerate() {
+            sqlx::query("INSERT INTO verification_session_steps (session_id, step, position) VALUES ($1, $2, $3)")
+                .bind(session.id)
+                .bind(step)
+                .bind(position as i16)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(Some(session))
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<VerificationSession>> {
+        let session = sqlx::query_as::<_, VerificationSession>(&format!(
+            "SELECT {SESSION_COLUMNS} FROM verification_sessions WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match session {
+            Some(session) => Ok(Some(self.decrypt(session).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The user's active session, if any
+    pub async fn find_active(&self, user_id: UserId) -> AppResult<Option<VerificationSession>> {
+        let session = sqlx::query_as::<_, VerificationSession>(&format!(
+            "SELECT {SESSION_COLUMNS} FROM verification_sessions WHERE user_id = $1 AND status = 'active'"
+        ))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match session {
+            Some(session) => Ok(Some(self.decrypt(session).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The session's steps, in order
+    pub async fn find_steps(&self, session_id: Uuid) -> AppResult<Vec<SessionStepRecord>> {
+        let steps = sqlx::query_as::<_, SessionStepRecord>(&format!(
+            "SELECT {STEP_COLUMNS} FROM verification_session_steps WHERE session_id = $1 ORDER BY position"
+        ))
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(steps)
+    }
+
+    /// Record an upload for a step and what the provider made of it,
+    /// counting the attempt. Returns `None` unless the step was waiting for
+    /// an upload.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_step(
+        &self,
+        session_id: Uuid,
+        step: SessionStep,
+        status: StepStatus,
+        storage_key: &str,
+        content_type: &str,
+        provider_reference: Option<&str>,
+        failure_reason: Option<&str>,
+    ) -> AppResult<Option<SessionStepRecord>> {
+        let recorded = sqlx::query_as::<_, SessionStepRecord>(&format!(
+            "UPDATE verification_session_steps SET
+                 status = $3,
+                 attempts = attempts + 1,
+                 storage_key = $4,
+                 content_type = $5,
+                 provider_reference = $6,
+                 failure_reason = $7,
+                 submitted_at = NOW(),
+                 updated_at = NOW()
+             WHERE session_id = $1 AND step = $2 AND status IN ('pending', 'failed')
+             RETURNING {STEP_COLUMNS}"
+        ))
+        .bind(session_id)
+        .bind(step)
+        .bind(status)
+        .bind(storage_key)
+        .bind(content_type)
+        .bind(provider_reference)
+        .bind(failure_reason)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(recorded)
+    }
+
+    /// Keep the number read off the ID document
+    pub async fn set_document_number(&self, session: &VerificationSession, document_number: &str) -> AppResult<()> {
+        let encrypted = self.cipher.encrypt_str(session.user_id, document_number).await?;
+        sqlx::query("UPDATE verification_sessions SET document_number = $2, updated_at = NOW() WHERE id = $1")
+            .bind(session.id)
+            .bind(encrypted)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record the session's outcome. Returns `None` unless it was active.
+    pub async fn decide(
+        &self,
+        session_id: Uuid,
+        status: VerificationSessionStatus,
+        reason: Option<&str>,
+        identity_verification_id: Option<Uuid>,
+        face_verification_id: Option<Uuid>,
+    ) -> AppResult<Option<VerificationSession>> {
+        let decided = sqlx::query_as::<_, VerificationSession>(&format!(
+            "UPDATE verification_sessions SET
+                 status = $2,
+                 decision_reason = $3,
+                 identity_verification_id = $4,
+                 face_verification_id = $5,
+                 decided_at = NOW(),
+                 updated_at = NOW()
+             WHERE id = $1 AND status = 'active'
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(session_id)
+        .bind(status)
+        .bind(reason)
+        .bind(identity_verification_id)
+        .bind(face_verification_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match decided {
+            Some(session) => Ok(Some(self.decrypt(session).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Record how the review of a session in review ended. Returns `None`
+    /// unless it was in review.
+    pub async fn settle(
+        &self,
+        session_id: Uuid,
+        status: VerificationSessionStatus,
+        reason: Option<&str>,
+    ) -> AppResult<Option<VerificationSession>> {
+        let settled = sqlx::query_as::<_, VerificationSession>(&format!(
+            "UPDATE verification_sessions SET status = $2, decision_reason = $3, decided_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = 'in_review'
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(session_id)
+        .bind(status)
+        .bind(reason)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match settled {
+            Some(session) => Ok(Some(self.decrypt(session).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Expire active sessions past their expiry, returning them with the
+    /// storage keys of their uploads, which are cleared
+    pub async fn expire_due(&self, now: DateTime<Utc>) -> AppResult<Vec<(VerificationSession, Vec<String>)>> {
+        let mut tx = self.pool.begin().await?;
+        let expired = sqlx::query_as::<_, VerificationSession>(&format!(
+            "UPDATE verification_sessions SET status = 'expired', decided_at = NOW(), updated_at = NOW()
+             WHERE status = 'active' AND expires_at <= $1
+             RETURNING {SESSION_COLUMNS}"
+        ))
+        .bind(now)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut result = Vec::with_capacity(expired.len());
+        for session in expired {
+            let keys: Vec<String> = sqlx::query_scalar(
+                "SELECT storage_key FROM verification_session_steps
+                 WHERE session_id = $1 AND storage_key IS NOT NULL
+                 FOR UPDATE",
+            )
+            .bind(session.id)
+            .fetch_all(&mut *tx)
+            .await?;
+            sqlx::query(
+                "UPDATE verification_session_steps SET storage_key = NULL, updated_at = NOW()
+                 WHERE session_id = $1 AND storage_key IS NOT NULL",
+            )
+            .bind(session.id)
+            .execute(&mut *tx)
+            .await?;
+            result.push((session, keys));
+        }
+        tx.commit().await?;
+
+        Ok(result)
+    }
+
+    async fn decrypt(&self, mut session: VerificationSession) -> AppResult<VerificationSession> {
+        if let Some(document_number) = &session.document_number {
+            session.document_number = self.cipher.decrypt_str(session.user_id, document_number).await?;
+        }
+        Ok(session)
+    }
+}
@@ -0,0 +1,545 @@
+use std::sync::Arc;
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::storage::Storage;
+use crate::identity::images;
+use crate::identity::model::{IdentityVerification, VerificationStatus, FACE_VERIFICATION_TYPE};
+use crate::identity::repository::IdentityRepository;
+use crate::identity::service::ScreeningService;
+use crate::kyc::service::KycPolicyService;
+use crate::reviews::{model::VerificationKind, service::ReviewQueue};
+use crate::shared::constants::MAX_SESSION_UPLOAD_SIZE;
+use crate::shared::{traits::Repository, types::{TenantId, UserId}};
+use crate::uploads::service::UploadService;
+use super::checks::{SessionChecks, StepCheck};
+use super::model::{
+    aggregate_decision, next_step, session_steps, CreateVerificationSessionRequest, SessionDecision, SessionSettings,
+    SessionStep, SessionStepRecord, StepStatus, StepUpload, StepUploadReference, VerificationSession,
+    VerificationSessionResponse, VerificationSessionStatus,
+};
+use super::repository::VerificationSessionRepository;
+
+/// Runs multi-step identity verification sessions: takes each step's upload
+/// in order, has it checked and records the outcome as identity
+/// verifications once every step is in
+pub struct VerificationSessionService {
+    repository: VerificationSessionRepository,
+    identity_repository: IdentityRepository,
+    storage: Arc<dyn Storage>,
+    uploads: UploadService,
+    checks: Arc<dyn SessionChecks>,
+    settings: SessionSettings,
+    review_queue: ReviewQueue,
+    kyc_policy: KycPolicyService,
+    screening: Option<ScreeningService>,
+    audit_logger: AuditLogger,
+}
+
+impl VerificationSessionService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: VerificationSessionRepository,
+        identity_repository: IdentityRepository,
+        storage: Arc<dyn Storage>,
+        uploads: UploadService,
+        checks: Arc<dyn SessionChecks>,
+        settings: SessionSettings,
+        review_queue: ReviewQueue,
+        kyc_policy: KycPolicyService,
+        audit_logger: AuditLogger,
+    ) -> Self {
+        Self {
+            repository,
+            identity_repository,
+            storage,
+            uploads,
+            checks,
+            settings,
+            review_queue,
+            kyc_policy,
+            screening: None,
+            audit_logger,
+        }
+    }
+
+    /// Screen the identity verifications sessions record
+    pub fn with_screening(mut self, screening: ScreeningService) -> Self {
+        self.screening = Some(screening);
+        self
+    }
+
+    /// Start a session, or return the user's active one so an interrupted
+    /// flow resumes where it stopped
+    pub async fn create(
+        &self,
+        request: CreateVerificationSessionRequest,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+        actor_id: Uuid,
+    ) -> AppResult<VerificationSessionResponse> {
+        let user_id = match (request.user_id, caller) {
+            (Some(user_id), Some(caller)) if user_id != caller => {
+                return Err(AppError::NotFound("User not found".to_string()))
+            }
+            (Some(user_id), _) | (None, Some(user_id)) => user_id,
+            (None, None) => return Err(AppError::Validation("user_id is required".to_string())),
+        };
+        if !self.identity_repository.user_in_tenant(user_id, tenant_id).await? {
+            return Err(AppError::NotFound("User not found".to_string()));
+        }
+        let steps = session_steps(request.steps)?;
+
+        if let Some(active) = self.repository.find_active(user_id).await? {
+            if active.expires_at > Utc::now() {
+                return self.response(active).await;
+            }
+        }
+        // An active session past its expiry is left to the expiry job; until
+        // then it blocks a new one
+        let expires_at = Utc::now() + self.settings.ttl;
+        let session = self
+            .repository
+            .create(user_id, request.document_type.trim(), &steps, actor_id, expires_at)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict("The user's previous verification session has not been closed yet".to_string())
+            })?;
+
+        let event = AuditEvent::new(AuditEventType::VerificationSessionCreated)
+            .user_id(actor_id)
+            .resource(format!("verification_session:{}", session.id))
+            .action("create_verification_session".to_string())
+            .metadata("subject_user_id".to_string(), json!(user_id))
+            .metadata("steps".to_string(), json!(steps))
+            .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        self.response(session).await
+    }
+
+    /// A session with its steps. Sessions in review are settled here once
+    /// reviewers have decided their verifications.
+    pub async fn get(
+        &self,
+        session_id: Uuid,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+    ) -> AppResult<VerificationSessionResponse> {
+        let session = self.load(session_id, tenant_id, caller).await?;
+        let session = match session.status {
+            VerificationSessionStatus::InReview => self.settle(session).await?,
+            _ => session,
+        };
+        self.response(session).await
+    }
+
+    /// Upload the session's next step and have it checked. A step the
+    /// provider rejects may be uploaded again until its attempts run out,
+    /// which rejects the session.
+    pub async fn submit_step(
+        &self,
+        session_id: Uuid,
+        step: SessionStep,
+        upload: StepUpload,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+        actor_id: Uuid,
+    ) -> AppResult<VerificationSessionResponse> {
+        let session = self.load(session_id, tenant_id, caller).await?;
+        self.record_upload(session, step, upload, actor_id).await
+    }
+
+    /// Take the session's next step from a direct upload of the user issued
+    /// for the step. The upload is used up once the step is recorded.
+    pub async fn submit_uploaded_step(
+        &self,
+        session_id: Uuid,
+        step: SessionStep,
+        reference: StepUploadReference,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+        actor_id: Uuid,
+    ) -> AppResult<VerificationSessionResponse> {
+        let session = self.load(session_id, tenant_id, caller).await?;
+        ensure_open(&session)?;
+        let file = self
+            .uploads
+            .fetch(reference.upload_id, session.user_id, step.upload_purpose())
+            .await?;
+        let direct_upload = file.upload;
+        let upload = StepUpload {
+            file_name: direct_upload.id.to_string(),
+            content_type: direct_upload.content_type.clone(),
+            content: file.content,
+            document_number: reference.document_number,
+        };
+
+        let response = self.record_upload(session, step, upload, actor_id).await?;
+        if let Err(e) = self.uploads.consume(&direct_upload).await {
+            tracing::warn!("Failed to mark upload {} as used: {}", direct_upload.id, e);
+        }
+        Ok(response)
+    }
+
+    async fn record_upload(
+        &self,
+        session: VerificationSession,
+        step: SessionStep,
+        mut upload: StepUpload,
+        actor_id: Uuid,
+    ) -> AppResult<VerificationSessionResponse> {
+        ensure_open(&session)?;
+        let steps = self.repository.find_steps(session.id).await?;
+        let record = steps
+            .iter()
+            .find(|record| record.step == step)
+            .ok_or_else(|| AppError::NotFound("The session has no such step".to_string()))?;
+        if record.status.is_done() {
+            return Err(AppError::Conflict(format!("The {} step is already uploaded", step.as_str())));
+        }
+        if let Some(next) = next_step(&steps).filter(|next| *next != step) {
+            return Err(AppError::Conflict(format!("Upload the {} step first", next.as_str())));
+        }
+        if record.attempts >= self.settings.max_step_attempts {
+            return Err(AppError::Conflict(format!("The {} step has no attempts left", step.as_str())));
+        }
+        check_upload(step, &upload)?;
+        // Checks see the image upright, and nothing stored keeps its EXIF
+        if images::is_image(&upload.content_type) {
+            let (content_type, content) = (upload.content_type.clone(), std::mem::take(&mut upload.content));
+            let normalized = tokio::task::spawn_blocking(move || images::normalize(&content_type, &content))
+                .await
+                .map_err(|e| AppError::Internal(format!("Image processing failed: {}", e)))??;
+            upload.content = normalized.content;
+        }
+        let document_number = match step {
+            SessionStep::Document => Some(
+                upload
+                    .document_number
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|number| !number.is_empty())
+                    .ok_or_else(|| AppError::Validation("The document step needs the document number".to_string()))?
+                    .to_string(),
+            ),
+            _ => None,
+        };
+
+        let storage_key = format!("verification-sessions/{}/{}-{}", session.id, step.as_str(), record.attempts + 1);
+        self.storage.put(&storage_key, upload.content.clone()).await?;
+        let check = match self.checks.check(session.id, &session.document_type, step, &upload).await {
+            Ok(check) => check,
+            Err(e) => {
+                if let Err(delete_error) = self.storage.delete(&storage_key).await {
+                    tracing::warn!("Failed to delete unchecked upload {}: {}", storage_key, delete_error);
+                }
+                return Err(e);
+            }
+        };
+        let (status, reference, reason) = match check {
+            StepCheck::Passed { reference } => (StepStatus::Passed, reference, None),
+            StepCheck::Failed { reference, reason } => (StepStatus::Failed, reference, Some(reason)),
+            StepCheck::NeedsReview { reference } => (StepStatus::Submitted, reference, None),
+        };
+
+        let recorded = self
+            .repository
+            .record_step(
+                session.id,
+                step,
+                status,
+                &storage_key,
+                &upload.content_type,
+                reference.as_deref(),
+                reason.as_deref(),
+            )
+            .await?
+            .ok_or_else(|| AppError::Conflict("The step changed concurrently".to_string()))?;
+        // Only the latest upload of a step is kept
+        if let Some(previous) = &record.storage_key {
+            if let Err(e) = self.storage.delete(previous).await {
+                tracing::warn!("Failed to delete replaced upload {}: {}", previous, e);
+            }
+        }
+        if let Some(document_number) = document_number.filter(|_| status != StepStatus::Failed) {
+            self.repository.set_document_number(&session, &document_number).await?;
+        }
+
+        let event = if status == StepStatus::Failed {
+            AuditEvent::new(AuditEventType::VerificationSessionStepSubmitted)
+                .severity(AuditSeverity::Warning)
+                .success(false)
+        } else {
+            AuditEvent::new(AuditEventType::VerificationSessionStepSubmitted)
+        }
+        .user_id(actor_id)
+        .resource(format!("verification_session:{}", session.id))
+        .action("submit_verification_step".to_string())
+        .metadata("step".to_string(), json!(step))
+        .metadata("status".to_string(), json!(status))
+        .metadata("attempt".to_string(), json!(recorded.attempts))
+        .metadata("provider".to_string(), json!(self.checks.name()))
+        .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+
+        if status == StepStatus::Failed && recorded.attempts >= self.settings.max_step_attempts {
+            let reason = format!(
+                "The {} step failed {} times: {}",
+                step.as_str(),
+                recorded.attempts,
+                reason.unwrap_or_default()
+            );
+            let rejected = self
+                .repository
+                .decide(session.id, VerificationSessionStatus::Rejected, Some(&reason), None, None)
+                .await?
+                .ok_or_else(|| AppError::Conflict("The verification session changed concurrently".to_string()))?;
+            self.log_decision(&rejected, actor_id).await;
+            return self.response(rejected).await;
+        }
+
+        self.response(session).await
+    }
+
+    /// Decide a session whose steps are all uploaded, recording the ID
+    /// document (and the face, with a selfie step) as identity
+    /// verifications. Steps the provider passed approve the session; any
+    /// left to a reviewer put it in review.
+    pub async fn complete(
+        &self,
+        session_id: Uuid,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+        actor_id: Uuid,
+    ) -> AppResult<VerificationSessionResponse> {
+        let session = self.load(session_id, tenant_id, caller).await?;
+        ensure_open(&session)?;
+        let steps = self.repository.find_steps(session.id).await?;
+        let missing: Vec<&str> = steps
+            .iter()
+            .filter(|record| !record.status.is_done())
+            .map(|record| record.step.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(AppError::Conflict(format!("Steps still to upload: {}", missing.join(", "))));
+        }
+        let decision = aggregate_decision(&steps);
+
+        let identity = self
+            .record_verification(&session, &steps, SessionStep::Document.as_str(), decision)
+            .await?;
+        let face = match steps.iter().any(|record| record.step == SessionStep::Selfie) {
+            true => Some(self.record_verification(&session, &steps, FACE_VERIFICATION_TYPE, decision).await?),
+            false => None,
+        };
+
+        let (status, reason) = match decision {
+            SessionDecision::Approved => (VerificationSessionStatus::Approved, None),
+            SessionDecision::Review => (
+                VerificationSessionStatus::InReview,
+                Some("Some steps need a reviewer".to_string()),
+            ),
+        };
+        let decided = self
+            .repository
+            .decide(
+                session.id,
+                status,
+                reason.as_deref(),
+                Some(identity.id),
+                face.as_ref().map(|face| face.id),
+            )
+            .await?
+            .ok_or_else(|| AppError::Conflict("The verification session changed concurrently".to_string()))?;
+
+        match decision {
+            SessionDecision::Approved => {
+                self.kyc_policy.refresh_user_tier(session.user_id, actor_id).await?;
+            }
+            SessionDecision::Review => {
+                let reason = format!("Verification session {} has steps no provider checked", session.id);
+                for verification in std::iter::once(&identity).chain(face.as_ref()) {
+                    self.review_queue
+                        .flag(VerificationKind::Identity, verification.id, session.user_id, reason.clone(), None)
+                        .await?;
+                }
+            }
+        }
+
+        // A provider outage must not block the decision; the re-screening
+        // job picks up verifications never screened
+        if let Some(screening) = &self.screening {
+            if let Err(e) = screening.screen(&identity).await {
+                tracing::warn!("Screening of verification {} failed: {}", identity.id, e);
+            }
+        }
+
+        self.log_decision(&decided, actor_id).await;
+        self.response(decided).await
+    }
+
+    /// Expire sessions not decided in time, deleting their uploads
+    pub async fn expire_due(&self) -> AppResult<usize> {
+        let expired = self.repository.expire_due(Utc::now()).await?;
+        for (session, storage_keys) in &expired {
+            for key in storage_keys {
+                if let Err(e) = self.storage.delete(key).await {
+                    tracing::warn!("Failed to delete upload {} of expired session {}: {}", key, session.id, e);
+                }
+            }
+
+            let event = AuditEvent::new(AuditEventType::VerificationSessionExpired)
+                .resource(format!("verification_session:{}", session.id))
+                .action("expire_verification_session".to_string())
+                .metadata("subject_user_id".to_string(), json!(session.user_id))
+                .metadata("uploads_deleted".to_string(), json!(storage_keys.len()))
+                .compliance_tag("KYC".to_string());
+            self.audit_logger.log(event).await;
+        }
+        Ok(expired.len())
+    }
+
+    /// A session of a user in the tenant. User tokens reach only their own
+    /// sessions.
+    async fn load(
+        &self,
+        session_id: Uuid,
+        tenant_id: TenantId,
+        caller: Option<UserId>,
+    ) -> AppResult<VerificationSession> {
+        let not_found = || AppError::NotFound("Verification session not found".to_string());
+        let session = self.repository.find_by_id(session_id).await?.ok_or_else(not_found)?;
+        let foreign_user = caller.is_some_and(|caller| caller != session.user_id);
+        if foreign_user || !self.identity_repository.user_in_tenant(session.user_id, tenant_id).await? {
+            return Err(not_found());
+        }
+        Ok(session)
+    }
+
+    async fn record_verification(
+        &self,
+        session: &VerificationSession,
+        steps: &[SessionStepRecord],
+        verification_type: &str,
+        decision: SessionDecision,
+    ) -> AppResult<IdentityVerification> {
+        let now = Utc::now();
+        let is_face = verification_type == FACE_VERIFICATION_TYPE;
+        let approved = decision == SessionDecision::Approved;
+        let verification = IdentityVerification {
+            id: Uuid::new_v4(),
+            user_id: session.user_id,
+            verification_type: verification_type.to_string(),
+            status: if approved { VerificationStatus::Completed } else { VerificationStatus::Pending },
+            document_type: (!is_face).then(|| session.document_type.clone()),
+            document_number: session.document_number.clone().filter(|_| !is_face),
+            verification_data: Some(json!({
+                "session_id": session.id,
+                "steps": steps
+                    .iter()
+                    .filter(|record| !is_face || record.step != SessionStep::Document)
+                    .map(|record| json!({
+                        "step": record.step,
+                        "status": record.status,
+                        "attempts": record.attempts,
+                        "storage_key": record.storage_key,
+                        "provider_reference": record.provider_reference,
+                    }))
+                    .collect::<Vec<_>>(),
+            })),
+            provider: Some(self.checks.name().to_string()),
+            provider_reference: Some(session.id.to_string()),
+            completed_at: approved.then_some(now),
+            created_at: now,
+            updated_at: now,
+        };
+        self.identity_repository.create(verification).await
+    }
+
+    /// Close a session in review once reviewers have decided its
+    /// verifications: approved when all were approved, rejected when any
+    /// was rejected
+    async fn settle(&self, session: VerificationSession) -> AppResult<VerificationSession> {
+        let mut statuses = Vec::new();
+        for id in [session.identity_verification_id, session.face_verification_id].into_iter().flatten() {
+            if let Some(verification) = self.identity_repository.find_by_id(id).await? {
+                statuses.push(verification.status);
+            }
+        }
+        let approved = !statuses.is_empty() && statuses.iter().all(|status| matches!(status, VerificationStatus::Completed));
+        let (status, reason) = if statuses.iter().any(|status| matches!(status, VerificationStatus::Failed)) {
+            (VerificationSessionStatus::Rejected, "A reviewer rejected the verification")
+        } else if approved {
+            (VerificationSessionStatus::Approved, "A reviewer approved the verification")
+        } else {
+            return Ok(session);
+        };
+
+        let Some(settled) = self.repository.settle(session.id, status, Some(reason)).await? else {
+            return Ok(session);
+        };
+        self.log_decision(&settled, Uuid::nil()).await;
+        Ok(settled)
+    }
+
+    async fn log_decision(&self, session: &VerificationSession, actor_id: Uuid) {
+        let event = match session.status {
+            VerificationSessionStatus::Rejected => AuditEvent::new(AuditEventType::VerificationSessionDecided)
+                .severity(AuditSeverity::Warning)
+                .success(false),
+            _ => AuditEvent::new(AuditEventType::VerificationSessionDecided),
+        }
+        .user_id(actor_id)
+        .resource(format!("verification_session:{}", session.id))
+        .action("decide_verification_session".to_string())
+        .metadata("subject_user_id".to_string(), json!(session.user_id))
+        .metadata("status".to_string(), json!(session.status))
+        .metadata("reason".to_string(), json!(session.decision_reason))
+        .compliance_tag("KYC".to_string());
+        self.audit_logger.log(event).await;
+    }
+
+    async fn response(&self, mut session: VerificationSession) -> AppResult<VerificationSessionResponse> {
+        let steps = self.repository.find_steps(session.id).await?;
+        // Reported as expired before the expiry job gets to it
+        if session.status == VerificationSessionStatus::Active && session.expires_at <= Utc::now() {
+            session.status = VerificationSessionStatus::Expired;
+        }
+        Ok(VerificationSessionResponse::new(session, &steps, self.settings.max_step_attempts))
+    }
+}
+
+fn ensure_open(session: &VerificationSession) -> AppResult<()> {
+    if session.status != VerificationSessionStatus::Active {
+        return Err(AppError::Conflict("The verification session is closed".to_string()));
+    }
+    if session.expires_at <= Utc::now() {
+        return Err(AppError::Conflict("The verification session has expired".to_string()));
+    }
+    Ok(())
+}
+
+fn check_upload(step: SessionStep, upload: &StepUpload) -> AppResult<()> {
+    let accepted = step.accepted_content_types();
+    if !accepted.contains(&upload.content_type.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Unsupported {} upload type '{}'. Supported types: {}",
+            step.as_str(),
+            upload.content_type,
+            accepted.join(", ")
+        )));
+    }
+    if upload.content.is_empty() {
+        return Err(AppError::Validation(format!("Upload '{}' is empty", upload.file_name)));
+    }
+    if upload.content.len() > MAX_SESSION_UPLOAD_SIZE {
+        return Err(AppError::Validation(format!(
+            "Upload exceeds the maximum size of {} bytes",
+            MAX_SESSION_UPLOAD_SIZE
+        )));
+    }
+    Ok(())
+}
@@ -1,6 +1,40 @@
-use axum::{extract::State, response::Json};
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Json, Response},
+};
 use serde_json::{json, Value};
-use crate::core::{error::AppResult, AppState};
+use uuid::Uuid;
+use crate::account_controls::{
+    repository::AccountControlRepository, service::AccountFreezeGuard,
+};
+use crate::account_numbers::{
+    repository::AccountNumberRepository, service::{default_scheme, AccountNumberGenerator},
+};
+use crate::auth::middleware::JwtToken;
+use crate::core::{error::AppResult, extractors::ApiJson, qr::QrQuery, response::ApiResponse, AppState};
+use crate::shared::account_labels::UpdateAccountDetailsRequest;
+use crate::shared::types::PaginatedResponse;
+use super::model::{
+    VirtualAccountBalance, VirtualAccountBalanceQuery, VirtualAccountPosting, VirtualAccountResponse,
+    VirtualAccountTransactionsQuery,
+};
+use super::repository::VirtualAccountRepository;
+use super::service::VirtualAccountService;
+
+fn virtual_account_service(state: &AppState) -> VirtualAccountService {
+    VirtualAccountService::new(
+        VirtualAccountRepository::new(state.postgres.clone()),
+        AccountFreezeGuard::new(
+            AccountControlRepository::new(state.postgres.clone()),
+            state.config.frozen_accounts_allow_credits,
+        ),
+        AccountNumberGenerator::new(
+            AccountNumberRepository::new(state.postgres.clone()),
+            default_scheme(&state.config),
+        ),
+    )
+}
 
 /// Create a new virtual account
 pub async fn create_virtual_account(
@@ -52,4 +86,93 @@ pub async fn deactivate_virtual_account(
         "message": "Deactivate virtual account endpoint - TODO: Implement",
         "status": "placeholder"
     })))
-}
\ No newline at end of file
+}
+
+/// Render a funding QR code for a virtual account
+pub async fn get_virtual_account_qr(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<QrQuery>,
+) -> AppResult<Response> {
+    let payload = virtual_account_service(&state).get_funding_qr_payload(id).await?;
+
+    let options = state.qr_renderer.options(&query).await?;
+    let (content_type, body) = state.qr_renderer.render(&payload, &options)?;
+
+    Ok(([(header::CONTENT_TYPE, content_type)], body).into_response())
+}
+
+/// List the ledger postings attributed to a virtual account, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/virtual-accounts/{id}/transactions",
+    tag = "virtual-accounts",
+    params(("id" = Uuid, Path, description = "Virtual account ID"), VirtualAccountTransactionsQuery),
+    responses(
+        (status = 200, description = "Page of postings", body = PaginatedVirtualAccountPostings),
+        (status = 400, description = "Invalid date range"),
+        (status = 404, description = "Virtual account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_virtual_account_transactions(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    Query(query): Query<VirtualAccountTransactionsQuery>,
+) -> AppResult<Json<ApiResponse<PaginatedResponse<VirtualAccountPosting>>>> {
+    let postings = virtual_account_service(&state)
+        .get_transactions(id, claims.tenant_id, query)
+        .await?;
+    Ok(Json(ApiResponse::success("Virtual account transactions retrieved successfully", postings)))
+}
+
+/// Get a virtual account's own balance, separate from its parent account's
+#[utoipa::path(
+    get,
+    path = "/api/v1/virtual-accounts/{id}/balance",
+    tag = "virtual-accounts",
+    params(("id" = Uuid, Path, description = "Virtual account ID"), VirtualAccountBalanceQuery),
+    responses(
+        (status = 200, description = "Virtual account balance", body = VirtualAccountBalance),
+        (status = 404, description = "Virtual account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_virtual_account_balance(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    Query(query): Query<VirtualAccountBalanceQuery>,
+) -> AppResult<Json<ApiResponse<VirtualAccountBalance>>> {
+    let balance = virtual_account_service(&state)
+        .get_balance(id, claims.tenant_id, query)
+        .await?;
+    Ok(Json(ApiResponse::success("Virtual account balance retrieved successfully", balance)))
+}
+
+/// Set a virtual account's nickname, labels or custom metadata
+#[utoipa::path(
+    patch,
+    path = "/api/v1/virtual-accounts/{id}",
+    tag = "virtual-accounts",
+    params(("id" = Uuid, Path, description = "Virtual account ID")),
+    request_body = UpdateAccountDetailsRequest,
+    responses(
+        (status = 200, description = "Virtual account updated", body = VirtualAccountResponse),
+        (status = 400, description = "Invalid nickname, labels or metadata"),
+        (status = 404, description = "Virtual account not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_virtual_account_details(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    Path(id): Path<Uuid>,
+    ApiJson(request): ApiJson<UpdateAccountDetailsRequest>,
+) -> AppResult<Json<ApiResponse<VirtualAccountResponse>>> {
+    let account = virtual_account_service(&state)
+        .update_details(id, claims.tenant_id, claims.user_id, request)
+        .await?;
+    Ok(Json(ApiResponse::success("Virtual account updated successfully", account)))
+}
@@ -3,7 +3,7 @@ pub mod model;
 pub mod repository;
 pub mod service;
 
-use axum::{routing::{get, post}, Router};
+use axum::{routing::{get, patch, post}, Router};
 use crate::core::AppState;
 
 pub fn routes() -> Router<AppState> {
@@ -11,5 +11,9 @@ pub fn routes() -> Router<AppState> {
         .route("/", post(controller::create_virtual_account))
         .route("/", get(controller::get_virtual_accounts))
         .route("/:id", get(controller::get_virtual_account_by_id))
+        .route("/:id", patch(controller::update_virtual_account_details))
         .route("/:id/deactivate", post(controller::deactivate_virtual_account))
+        .route("/:id/qr", get(controller::get_virtual_account_qr))
+        .route("/:id/transactions", get(controller::get_virtual_account_transactions))
+        .route("/:id/balance", get(controller::get_virtual_account_balance))
 }
\ No newline at end of file
@@ -1,12 +1,17 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
-use crate::shared::types::{AccountId, UserId, Currency};
+use crate::account_controls::model::FreezeReason;
+use crate::shared::types::{AccountId, Amount, UserId, Currency};
+
+/// Largest page of postings a caller may ask for
+pub const MAX_PAGE_SIZE: u32 = 100;
 
 /// Virtual account status enum
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "virtual_account_status", rename_all = "lowercase")]
 pub enum VirtualAccountStatus {
     Active,
@@ -27,6 +32,10 @@ pub struct VirtualAccount {
     pub status: VirtualAccountStatus,
     pub purpose: Option<String>,
     pub metadata: Option<serde_json::Value>,
+    pub frozen_at: Option<DateTime<Utc>>,
+    pub freeze_reason: Option<FreezeReason>,
+    pub nickname: Option<String>,
+    pub labels: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -43,7 +52,7 @@ pub struct CreateVirtualAccountRequest {
 }
 
 /// Virtual account response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct VirtualAccountResponse {
     pub id: Uuid,
     pub account_number: String,
@@ -51,6 +60,12 @@ pub struct VirtualAccountResponse {
     pub currency: Currency,
     pub status: VirtualAccountStatus,
     pub purpose: Option<String>,
+    pub is_frozen: bool,
+    pub freeze_reason: Option<FreezeReason>,
+    pub frozen_at: Option<DateTime<Utc>>,
+    pub nickname: Option<String>,
+    pub labels: Vec<String>,
+    pub metadata: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -63,7 +78,83 @@ impl From<VirtualAccount> for VirtualAccountResponse {
             currency: account.currency,
             status: account.status,
             purpose: account.purpose,
+            is_frozen: account.frozen_at.is_some(),
+            freeze_reason: account.freeze_reason,
+            frozen_at: account.frozen_at,
+            nickname: account.nickname,
+            labels: account.labels,
+            metadata: account.metadata,
             created_at: account.created_at,
         }
     }
-}
\ No newline at end of file
+}
+
+/// A ledger posting on the parent account attributed to a virtual account
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct VirtualAccountPosting {
+    pub id: Uuid,
+    pub transaction_id: Option<Uuid>,
+    /// Positive for credits, negative for debits
+    pub amount: Amount,
+    /// The virtual account's balance once this posting applied
+    pub balance_after: Amount,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Query parameters for a virtual account's postings
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VirtualAccountTransactionsQuery {
+    /// Page number, starting at 1
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Postings per page, at most 100 (default 20)
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    /// Only postings made at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only postings made before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_limit() -> u32 {
+    20
+}
+
+/// Query parameters for a virtual account's balance
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VirtualAccountBalanceQuery {
+    /// Balance as of this time instead of now
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+/// Totals of a virtual account's postings
+#[derive(Debug, Clone, FromRow)]
+pub struct PostingTotals {
+    pub balance: Amount,
+    pub total_credits: Amount,
+    pub total_debits: Amount,
+    pub posting_count: i64,
+    pub last_posted_at: Option<DateTime<Utc>>,
+}
+
+/// A virtual account's own balance, derived from the postings attributed to
+/// it. The parent account's balance also includes these funds.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VirtualAccountBalance {
+    pub virtual_account_id: Uuid,
+    pub parent_account_id: AccountId,
+    pub currency: Currency,
+    pub balance: Amount,
+    pub total_credits: Amount,
+    pub total_debits: Amount,
+    pub posting_count: i64,
+    pub last_posted_at: Option<DateTime<Utc>>,
+    pub as_of: DateTime<Utc>,
+}
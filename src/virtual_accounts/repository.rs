@@ -1,9 +1,13 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 use crate::core::error::AppResult;
-use crate::shared::{traits::Repository, types::{UserId, AccountId}};
-use super::model::{VirtualAccount, VirtualAccountStatus};
+use crate::shared::{account_labels::AccountDetailsChange, traits::Repository, types::{UserId, AccountId, TenantId}};
+use super::model::{PostingTotals, VirtualAccount, VirtualAccountPosting, VirtualAccountStatus};
+
+const VIRTUAL_ACCOUNT_COLUMNS: &str = "id, user_id, parent_account_id, account_number, account_name, currency, status,
+    purpose, metadata, frozen_at, freeze_reason, nickname, labels, created_at, updated_at";
 
 pub struct VirtualAccountRepository {
     pool: PgPool,
@@ -26,6 +30,118 @@ impl VirtualAccountRepository {
         Ok(Vec::new())
     }
 
+    pub async fn find_by_id_for_tenant(&self, id: Uuid, tenant_id: TenantId) -> AppResult<Option<VirtualAccount>> {
+        let account = sqlx::query_as::<_, VirtualAccount>(&format!(
+            "SELECT {VIRTUAL_ACCOUNT_COLUMNS} FROM virtual_accounts WHERE id = $1 AND tenant_id = $2"
+        ))
+        .bind(id)
+        .bind(tenant_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(account)
+    }
+
+    /// Postings attributed to a virtual account, newest first, each with the
+    /// running balance over all of the account's postings
+    pub async fn find_postings(
+        &self,
+        virtual_account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<Vec<VirtualAccountPosting>> {
+        let offset = (page.saturating_sub(1) * limit) as i64;
+        // The running balance needs every earlier posting, so only the upper
+        // bound can be applied before the window to prune partitions
+        let postings = sqlx::query_as::<_, VirtualAccountPosting>(
+            "SELECT id, transaction_id, amount, balance_after, description, created_at
+             FROM (
+                 SELECT id, transaction_id, amount_changed AS amount,
+                        SUM(amount_changed) OVER (ORDER BY created_at, id)::BIGINT AS balance_after,
+                        description, created_at
+                 FROM balance_history
+                 WHERE virtual_account_id = $1
+                   AND created_at < COALESCE($3::TIMESTAMPTZ, 'infinity')
+             ) postings
+             WHERE created_at >= COALESCE($2::TIMESTAMPTZ, '-infinity')
+             ORDER BY created_at DESC, id DESC
+             LIMIT $4 OFFSET $5",
+        )
+        .bind(virtual_account_id)
+        .bind(from)
+        .bind(to)
+        .bind(limit as i64)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(postings)
+    }
+
+    pub async fn count_postings(
+        &self,
+        virtual_account_id: Uuid,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> AppResult<i64> {
+        let count = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM balance_history
+             WHERE virtual_account_id = $1
+               AND created_at >= COALESCE($2::TIMESTAMPTZ, '-infinity')
+               AND created_at < COALESCE($3::TIMESTAMPTZ, 'infinity')",
+        )
+        .bind(virtual_account_id)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Totals of the postings attributed to a virtual account up to `as_of`
+    pub async fn posting_totals(&self, virtual_account_id: Uuid, as_of: DateTime<Utc>) -> AppResult<PostingTotals> {
+        let totals = sqlx::query_as::<_, PostingTotals>(
+            "SELECT COALESCE(SUM(amount_changed), 0)::BIGINT AS balance,
+                    COALESCE(SUM(amount_changed) FILTER (WHERE amount_changed > 0), 0)::BIGINT AS total_credits,
+                    COALESCE(-SUM(amount_changed) FILTER (WHERE amount_changed < 0), 0)::BIGINT AS total_debits,
+                    COUNT(*) AS posting_count,
+                    MAX(created_at) AS last_posted_at
+             FROM balance_history
+             WHERE virtual_account_id = $1 AND created_at <= $2",
+        )
+        .bind(virtual_account_id)
+        .bind(as_of)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(totals)
+    }
+
+    /// Apply a change to a virtual account's nickname, labels and metadata
+    pub async fn update_details(&self, id: Uuid, change: &AccountDetailsChange) -> AppResult<Option<VirtualAccount>> {
+        let account = sqlx::query_as::<_, VirtualAccount>(&format!(
+            "UPDATE virtual_accounts
+             SET nickname = CASE WHEN $2 THEN $3 ELSE nickname END,
+                 labels = COALESCE($4, labels),
+                 metadata = COALESCE($5, metadata),
+                 updated_at = NOW()
+             WHERE id = $1
+             RETURNING {VIRTUAL_ACCOUNT_COLUMNS}"
+        ))
+        .bind(id)
+        .bind(change.nickname.is_some())
+        .bind(change.nickname.clone().flatten())
+        .bind(&change.labels)
+        .bind(&change.metadata)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(account)
+    }
+
     /// Update account status
     pub async fn update_status(
         &self,
@@ -35,12 +151,6 @@ impl VirtualAccountRepository {
         // TODO: Implement status update
         Ok(())
     }
-
-    /// Generate unique account number
-    pub async fn generate_account_number(&self) -> AppResult<String> {
-        // TODO: Implement account number generation
-        Ok(format!("VA{}", Uuid::new_v4().to_string().replace("-", "")[..8].to_uppercase()))
-    }
 }
 
 #[async_trait]
@@ -50,9 +160,15 @@ impl Repository<VirtualAccount, Uuid> for VirtualAccountRepository {
         Ok(account)
     }
 
-    async fn find_by_id(&self, _id: Uuid) -> AppResult<Option<VirtualAccount>> {
-        // TODO: Implement find by ID
-        Ok(None)
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<VirtualAccount>> {
+        let account = sqlx::query_as::<_, VirtualAccount>(&format!(
+            "SELECT {VIRTUAL_ACCOUNT_COLUMNS} FROM virtual_accounts WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(account)
     }
 
     async fn update(&self, _id: Uuid, account: VirtualAccount) -> AppResult<VirtualAccount> {
@@ -1,29 +1,46 @@
 use uuid::Uuid;
 use chrono::Utc;
+use crate::account_controls::{model::AccountKind, service::AccountFreezeGuard};
+use crate::account_numbers::service::AccountNumberGenerator;
 use crate::core::error::{AppError, AppResult};
-use crate::shared::{traits::Repository, types::UserId};
+use crate::shared::{
+    account_labels::UpdateAccountDetailsRequest, traits::Repository, types::{PaginatedResponse, TenantId, UserId},
+};
 use super::model::{
-    VirtualAccount, VirtualAccountResponse, CreateVirtualAccountRequest, VirtualAccountStatus
+    VirtualAccount, VirtualAccountResponse, CreateVirtualAccountRequest, VirtualAccountStatus, VirtualAccountBalance,
+    VirtualAccountBalanceQuery, VirtualAccountPosting, VirtualAccountTransactionsQuery, MAX_PAGE_SIZE
 };
 use super::repository::VirtualAccountRepository;
 
 pub struct VirtualAccountService {
     repository: VirtualAccountRepository,
+    freeze_guard: AccountFreezeGuard,
+    account_numbers: AccountNumberGenerator,
 }
 
 impl VirtualAccountService {
-    pub fn new(repository: VirtualAccountRepository) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: VirtualAccountRepository,
+        freeze_guard: AccountFreezeGuard,
+        account_numbers: AccountNumberGenerator,
+    ) -> Self {
+        Self {
+            repository,
+            freeze_guard,
+            account_numbers,
+        }
     }
 
-    /// Create a new virtual account
+    /// Create a new virtual account, numbered in the project's scheme for
+    /// its currency
     pub async fn create_virtual_account(
         &self,
         user_id: UserId,
+        project_id: Uuid,
         request: CreateVirtualAccountRequest,
     ) -> AppResult<VirtualAccountResponse> {
         // TODO: Implement virtual account creation logic
-        let account_number = self.repository.generate_account_number().await?;
+        let account_number = self.account_numbers.generate(project_id, &request.currency).await?;
         let now = Utc::now();
         
         let virtual_account = VirtualAccount {
@@ -36,6 +53,10 @@ impl VirtualAccountService {
             status: VirtualAccountStatus::Active,
             purpose: request.purpose,
             metadata: request.metadata,
+            frozen_at: None,
+            freeze_reason: None,
+            nickname: None,
+            labels: Vec::new(),
             created_at: now,
             updated_at: now,
         };
@@ -58,6 +79,117 @@ impl VirtualAccountService {
         Ok(accounts.into_iter().map(VirtualAccountResponse::from).collect())
     }
 
+    /// Page through the postings attributed to a virtual account, newest first
+    pub async fn get_transactions(
+        &self,
+        account_id: Uuid,
+        tenant_id: TenantId,
+        query: VirtualAccountTransactionsQuery,
+    ) -> AppResult<PaginatedResponse<VirtualAccountPosting>> {
+        if let (Some(from), Some(to)) = (query.from, query.to) {
+            if from >= to {
+                return Err(AppError::BadRequest("'from' must be before 'to'".to_string()));
+            }
+        }
+        let account = self.find_for_tenant(account_id, tenant_id).await?;
+        let page = query.page.max(1);
+        let limit = query.limit.clamp(1, MAX_PAGE_SIZE);
+
+        let postings = self
+            .repository
+            .find_postings(account.id, query.from, query.to, page, limit)
+            .await?;
+        let total = self.repository.count_postings(account.id, query.from, query.to).await?.max(0) as u64;
+
+        Ok(PaginatedResponse {
+            data: postings,
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit as u64) as u32,
+        })
+    }
+
+    /// A virtual account's own balance from its attributed postings, apart
+    /// from the rest of its parent account's funds
+    pub async fn get_balance(
+        &self,
+        account_id: Uuid,
+        tenant_id: TenantId,
+        query: VirtualAccountBalanceQuery,
+    ) -> AppResult<VirtualAccountBalance> {
+        let account = self.find_for_tenant(account_id, tenant_id).await?;
+        let as_of = query.as_of.unwrap_or_else(Utc::now);
+        let totals = self.repository.posting_totals(account.id, as_of).await?;
+
+        Ok(VirtualAccountBalance {
+            virtual_account_id: account.id,
+            parent_account_id: account.parent_account_id,
+            currency: account.currency,
+            balance: totals.balance,
+            total_credits: totals.total_credits,
+            total_debits: totals.total_debits,
+            posting_count: totals.posting_count,
+            last_posted_at: totals.last_posted_at,
+            as_of,
+        })
+    }
+
+    /// Set a virtual account's nickname, labels or metadata. User tokens may
+    /// only change the user's own virtual accounts.
+    pub async fn update_details(
+        &self,
+        account_id: Uuid,
+        tenant_id: TenantId,
+        user_id: Option<UserId>,
+        request: UpdateAccountDetailsRequest,
+    ) -> AppResult<VirtualAccountResponse> {
+        let account = self.find_for_tenant(account_id, tenant_id).await?;
+        if user_id.is_some_and(|user_id| user_id != account.user_id) {
+            return Err(AppError::NotFound("Virtual account not found".to_string()));
+        }
+
+        let change = request.into_change()?;
+        let updated = self
+            .repository
+            .update_details(account.id, &change)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Virtual account not found".to_string()))?;
+        Ok(VirtualAccountResponse::from(updated))
+    }
+
+    async fn find_for_tenant(&self, account_id: Uuid, tenant_id: TenantId) -> AppResult<VirtualAccount> {
+        self.repository
+            .find_by_id_for_tenant(account_id, tenant_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Virtual account not found".to_string()))
+    }
+
+    /// Build the funding payload encoded into a virtual account's QR code
+    pub async fn get_funding_qr_payload(&self, account_id: Uuid) -> AppResult<String> {
+        let account = self.repository.find_by_id(account_id).await?
+            .ok_or_else(|| AppError::NotFound("Virtual account not found".to_string()))?;
+
+        if !matches!(account.status, VirtualAccountStatus::Active) {
+            return Err(AppError::BadRequest("Virtual account is not active".to_string()));
+        }
+        self.freeze_guard
+            .ensure_can_credit(AccountKind::VirtualAccount, account.id)
+            .await?;
+
+        let uri = reqwest::Url::parse_with_params(
+            "openbank://fund",
+            &[
+                ("account_number", account.account_number.as_str()),
+                ("account_name", account.account_name.as_str()),
+                ("currency", account.currency.as_str()),
+            ],
+        )
+        .map_err(|e| AppError::Internal(format!("Failed to build funding URI: {}", e)))?;
+
+        Ok(uri.to_string())
+    }
+
     /// Deactivate virtual account
     pub async fn deactivate_virtual_account(&self, account_id: Uuid) -> AppResult<()> {
         self.repository.update_status(account_id, VirtualAccountStatus::Inactive).await
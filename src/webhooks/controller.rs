@@ -0,0 +1,175 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use uuid::Uuid;
+use validator::Validate;
+use crate::auth::middleware::JwtToken;
+use crate::core::{
+    error::{AppError, AppResult},
+    extractors::{ApiJson, ClientIp},
+    rbac::permissions,
+    response::ApiResponse,
+    AppState,
+};
+use crate::shared::types::{PaginatedResponse, PaginationParams};
+use super::model::{
+    DeadLetterFilter, DeliverySettings, ReplayDeadLettersRequest, ReplayDeadLettersResponse, WebhookDeadLetter,
+    WebhookDelivery, WebhookQueueStats,
+};
+use super::repository::WebhookRepository;
+use super::service::WebhookService;
+
+/// Name of the webhook deliveries' outbound client
+const WEBHOOKS_CLIENT: &str = "webhooks";
+
+pub(crate) fn webhook_service(state: &AppState) -> WebhookService {
+    WebhookService::new(
+        WebhookRepository::new(state.postgres.clone()),
+        state.audit_logger.clone(),
+        DeliverySettings::from_config(&state.config),
+    )
+    .with_http_client(state.http_clients.get(WEBHOOKS_CLIENT))
+}
+
+/// List dead-lettered webhook deliveries, newest first (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/dlq",
+    tag = "webhooks",
+    params(DeadLetterFilter, PaginationParams),
+    responses(
+        (status = 200, description = "Page of dead letters", body = PaginatedDeadLetters),
+        (status = 403, description = "Caller lacks the webhook management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_dead_letters(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Query(filter): Query<DeadLetterFilter>,
+    Query(pagination): Query<PaginationParams>,
+) -> AppResult<Json<ApiResponse<PaginatedResponse<WebhookDeadLetter>>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_webhooks(), ip, "webhook_dead_letters".to_string())
+        .await?;
+
+    let dead_letters = webhook_service(&state)
+        .list_dead_letters(filter, pagination.page, pagination.limit)
+        .await?;
+    Ok(Json(ApiResponse::success("Dead letters retrieved successfully", dead_letters)))
+}
+
+/// Delivery and dead-letter queue depth (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/dlq/stats",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Webhook queue statistics", body = WebhookQueueStats),
+        (status = 403, description = "Caller lacks the webhook management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_queue_stats(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+) -> AppResult<Json<ApiResponse<WebhookQueueStats>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_webhooks(), ip, "webhook_dead_letters".to_string())
+        .await?;
+
+    let stats = webhook_service(&state).queue_stats().await?;
+    Ok(Json(ApiResponse::success("Webhook queue statistics retrieved successfully", stats)))
+}
+
+/// Get a dead letter with its payload and last error (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/dlq/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Dead letter ID")),
+    responses(
+        (status = 200, description = "Dead letter", body = WebhookDeadLetter),
+        (status = 403, description = "Caller lacks the webhook management permission"),
+        (status = 404, description = "Dead letter not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_dead_letter(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<WebhookDeadLetter>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_webhooks(), ip, format!("webhook_dead_letter:{}", id))
+        .await?;
+
+    let dead_letter = webhook_service(&state).get_dead_letter(id).await?;
+    Ok(Json(ApiResponse::success("Dead letter retrieved successfully", dead_letter)))
+}
+
+/// Queue a dead letter for delivery again (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks/dlq/{id}/replay",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Dead letter ID")),
+    responses(
+        (status = 200, description = "Delivery queued", body = WebhookDelivery),
+        (status = 403, description = "Caller lacks the webhook management permission"),
+        (status = 404, description = "Dead letter not found"),
+        (status = 409, description = "Dead letter has already been replayed")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn replay_dead_letter(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<ApiResponse<WebhookDelivery>>> {
+    state
+        .authorize(claims.developer_id, permissions::manage_webhooks(), ip, format!("webhook_dead_letter:{}", id))
+        .await?;
+
+    let delivery = webhook_service(&state)
+        .replay_dead_letter(id, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Dead letter queued for replay", delivery)))
+}
+
+/// Queue several dead letters for delivery again (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks/dlq/replay",
+    tag = "webhooks",
+    request_body = ReplayDeadLettersRequest,
+    responses(
+        (status = 200, description = "Deliveries queued", body = ReplayDeadLettersResponse),
+        (status = 403, description = "Caller lacks the webhook management permission")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn replay_dead_letters(
+    State(state): State<AppState>,
+    JwtToken(claims): JwtToken,
+    ClientIp(ip): ClientIp,
+    ApiJson(request): ApiJson<ReplayDeadLettersRequest>,
+) -> AppResult<Json<ApiResponse<ReplayDeadLettersResponse>>> {
+    if let Err(validation_errors) = request.validate() {
+        return Err(AppError::InvalidFields(validation_errors));
+    }
+
+    state
+        .authorize(claims.developer_id, permissions::manage_webhooks(), ip, "webhook_dead_letters".to_string())
+        .await?;
+
+    let replayed = webhook_service(&state)
+        .replay_dead_letters(request, claims.developer_id)
+        .await?;
+    Ok(Json(ApiResponse::success("Dead letters queued for replay", replayed)))
+}
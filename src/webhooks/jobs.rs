@@ -0,0 +1,35 @@
+use crate::core::AppState;
+use super::controller::webhook_service;
+
+const WEBHOOK_DELIVERY_JOB: &str = "webhook_delivery";
+
+/// Periodically deliver queued webhooks and retry failed ones
+pub fn spawn_delivery_job(state: AppState) {
+    let period = std::time::Duration::from_secs(state.config.webhook_delivery_interval_seconds);
+    state.job_monitor.register(WEBHOOK_DELIVERY_JOB, period);
+
+    tokio::spawn(async move {
+        let service = webhook_service(&state);
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match service.deliver_due().await {
+                Ok(run) => {
+                    state.job_monitor.record_success(WEBHOOK_DELIVERY_JOB);
+                    if run.delivered + run.retrying + run.dead_lettered > 0 {
+                        tracing::info!(
+                            "Webhooks: {} delivered, {} to retry, {} dead-lettered",
+                            run.delivered,
+                            run.retrying,
+                            run.dead_lettered
+                        );
+                    }
+                }
+                Err(e) => {
+                    state.job_monitor.record_failure(WEBHOOK_DELIVERY_JOB, e.to_string());
+                    tracing::error!("Webhook delivery job failed: {}", e);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,17 @@
+pub mod controller;
+pub mod jobs;
+pub mod model;
+pub mod repository;
+pub mod service;
+
+use axum::{routing::{get, post}, Router};
+use crate::core::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/dlq", get(controller::list_dead_letters))
+        .route("/dlq/stats", get(controller::get_queue_stats))
+        .route("/dlq/replay", post(controller::replay_dead_letters))
+        .route("/dlq/:id", get(controller::get_dead_letter))
+        .route("/dlq/:id/replay", post(controller::replay_dead_letter))
+}
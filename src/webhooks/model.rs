@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+use crate::core::config::Config;
+use crate::shared::types::TenantId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its next attempt
+    Pending,
+    Delivered,
+    /// Retries exhausted; moved to the dead-letter queue
+    DeadLettered,
+}
+
+/// One outgoing webhook and its delivery progress
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub tenant_id: Option<TenantId>,
+    /// Project whose webhook URL receives the delivery
+    pub project_id: Option<Uuid>,
+    /// Domain event the delivery carries
+    pub event_id: Option<Uuid>,
+    pub event_type: String,
+    pub url: String,
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    /// Dead letter this delivery replays
+    pub replay_of: Option<Uuid>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A delivery that exhausted its retries
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct WebhookDeadLetter {
+    pub id: Uuid,
+    pub delivery_id: Uuid,
+    pub tenant_id: Option<TenantId>,
+    pub project_id: Option<Uuid>,
+    pub event_id: Option<Uuid>,
+    pub event_type: String,
+    pub url: String,
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub failed_at: DateTime<Utc>,
+    pub replayed_at: Option<DateTime<Utc>>,
+    pub replayed_by: Option<Uuid>,
+    /// New delivery created by the replay
+    pub replay_delivery_id: Option<Uuid>,
+}
+
+/// Why a delivery attempt failed
+#[derive(Debug, Clone)]
+pub struct AttemptFailure {
+    pub status_code: Option<i32>,
+    pub error: String,
+}
+
+/// Retry policy for outgoing webhooks
+#[derive(Debug, Clone)]
+pub struct DeliverySettings {
+    pub max_attempts: i32,
+    /// Delay before the first retry; doubled for every further one
+    pub retry_backoff_seconds: i64,
+    pub timeout_seconds: u64,
+    pub batch_size: i64,
+}
+
+impl DeliverySettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_attempts: config.webhook_max_attempts.max(1),
+            retry_backoff_seconds: config.webhook_retry_backoff_seconds,
+            timeout_seconds: config.webhook_timeout_seconds,
+            batch_size: config.webhook_delivery_batch_size,
+        }
+    }
+
+    /// Delay before retrying a delivery that has failed `attempts` times
+    pub fn retry_delay(&self, attempts: i32) -> chrono::Duration {
+        let exponent = (attempts - 1).clamp(0, 16) as u32;
+        chrono::Duration::seconds(self.retry_backoff_seconds.saturating_mul(1 << exponent))
+    }
+}
+
+/// Query parameters for listing dead letters
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeadLetterFilter {
+    pub event_type: Option<String>,
+    /// Defaults to dead letters that have not been replayed
+    pub replayed: Option<bool>,
+}
+
+/// Replay several dead letters at once. Without `ids`, outstanding dead
+/// letters are replayed oldest first, optionally limited to one event type.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReplayDeadLettersRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub ids: Option<Vec<Uuid>>,
+    #[validate(length(min = 1, max = 100))]
+    pub event_type: Option<String>,
+    /// At most this many dead letters are replayed (default 100)
+    #[validate(range(min = 1, max = 500))]
+    pub limit: Option<i64>,
+}
+
+/// Deliveries queued by a bulk replay
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReplayDeadLettersResponse {
+    pub replayed: usize,
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct DeadLetterCount {
+    pub event_type: String,
+    pub count: i64,
+}
+
+/// Size of the delivery and dead-letter queues
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookQueueStats {
+    /// Dead letters not yet replayed
+    pub dead_letter_depth: i64,
+    pub oldest_dead_letter_at: Option<DateTime<Utc>>,
+    pub dead_letters_by_event_type: Vec<DeadLetterCount>,
+    /// Deliveries waiting for their first attempt or a retry
+    pub pending_deliveries: i64,
+}
@@ -0,0 +1,360 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::core::deadline;
+use crate::core::error::AppResult;
+use crate::core::events::DomainEvent;
+use crate::shared::types::TenantId;
+use super::model::{
+    AttemptFailure, DeadLetterCount, DeadLetterFilter, WebhookDeadLetter, WebhookDelivery, WebhookQueueStats,
+};
+
+const DELIVERY_COLUMNS: &str = "id, tenant_id, project_id, event_id, event_type, url, payload, status, attempts,
+    next_attempt_at, last_status_code, last_error, replay_of, delivered_at, created_at, updated_at";
+
+const DEAD_LETTER_COLUMNS: &str = "id, delivery_id, tenant_id, project_id, event_id, event_type, url, payload,
+    attempts, last_status_code, last_error, failed_at, replayed_at, replayed_by, replay_delivery_id";
+
+/// Matches dead letters against `DeadLetterFilter` bound as $1 (event type)
+/// and $2 (replayed)
+const DEAD_LETTER_FILTER: &str = "($1::TEXT IS NULL OR event_type = $1)
+    AND (replayed_at IS NOT NULL) = COALESCE($2, FALSE)";
+
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue a webhook for delivery by the background job
+    pub async fn enqueue(
+        &self,
+        tenant_id: Option<TenantId>,
+        event_type: &str,
+        url: &str,
+        payload: &serde_json::Value,
+    ) -> AppResult<WebhookDelivery> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "INSERT INTO webhook_deliveries (tenant_id, event_type, url, payload)
+             VALUES ($1, $2, $3, $4)
+             RETURNING {DELIVERY_COLUMNS}"
+        ))
+        .bind(tenant_id)
+        .bind(event_type)
+        .bind(url)
+        .bind(payload)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    /// Queue a domain event for every active project in its tenant that has a
+    /// webhook URL and read access to the event's module; returns how many
+    /// were queued
+    pub async fn enqueue_for_projects(
+        &self,
+        event: &DomainEvent,
+        payload: &serde_json::Value,
+    ) -> AppResult<u64> {
+        let queued = sqlx::query(
+            "INSERT INTO webhook_deliveries (tenant_id, project_id, event_id, event_type, url, payload)
+             SELECT organization_id, id, $1, $2, webhook_url, $3 FROM projects
+             WHERE organization_id = $4
+               AND is_active
+               AND COALESCE(webhook_url, '') <> ''
+               AND ($5 = ANY(scopes) OR $5 || ':read' = ANY(scopes))",
+        )
+        .bind(event.id)
+        .bind(event.event_type.as_str())
+        .bind(payload)
+        .bind(event.tenant_id)
+        .bind(event.event_type.required_scope())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(queued.rows_affected())
+    }
+
+    /// Queue a domain event for one project's webhook URL
+    pub async fn enqueue_for_project(
+        &self,
+        project_id: Uuid,
+        event: &DomainEvent,
+        url: &str,
+        payload: &serde_json::Value,
+    ) -> AppResult<WebhookDelivery> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "INSERT INTO webhook_deliveries (tenant_id, project_id, event_id, event_type, url, payload)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING {DELIVERY_COLUMNS}"
+        ))
+        .bind(event.tenant_id)
+        .bind(project_id)
+        .bind(event.id)
+        .bind(event.event_type.as_str())
+        .bind(url)
+        .bind(payload)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    /// Take pending deliveries that are due, pushing their next attempt out
+    /// to `lease_until` so no other worker picks them up meanwhile
+    pub async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "UPDATE webhook_deliveries
+             SET next_attempt_at = $2, updated_at = NOW()
+             WHERE id IN (
+                 SELECT id FROM webhook_deliveries
+                 WHERE status = 'pending' AND next_attempt_at <= $1
+                 ORDER BY next_attempt_at
+                 LIMIT $3
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING {DELIVERY_COLUMNS}"
+        ))
+        .bind(now)
+        .bind(lease_until)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    pub async fn mark_delivered(&self, delivery_id: Uuid, status_code: i32) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET status = 'delivered', attempts = attempts + 1, last_status_code = $1,
+                 last_error = NULL, delivered_at = NOW(), updated_at = NOW()
+             WHERE id = $2 AND status = 'pending'",
+        )
+        .bind(status_code)
+        .bind(delivery_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn schedule_retry(
+        &self,
+        delivery_id: Uuid,
+        failure: &AttemptFailure,
+        next_attempt_at: DateTime<Utc>,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET attempts = attempts + 1, last_status_code = $1, last_error = $2,
+                 next_attempt_at = $3, updated_at = NOW()
+             WHERE id = $4 AND status = 'pending'",
+        )
+        .bind(failure.status_code)
+        .bind(&failure.error)
+        .bind(next_attempt_at)
+        .bind(delivery_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record the final failed attempt and move the delivery to the
+    /// dead-letter queue; `None` if it is no longer pending
+    pub async fn dead_letter(
+        &self,
+        delivery_id: Uuid,
+        failure: &AttemptFailure,
+    ) -> AppResult<Option<WebhookDeadLetter>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let Some(delivery) = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "UPDATE webhook_deliveries
+             SET status = 'dead_lettered', attempts = attempts + 1, last_status_code = $1,
+                 last_error = $2, updated_at = NOW()
+             WHERE id = $3 AND status = 'pending'
+             RETURNING {DELIVERY_COLUMNS}"
+        ))
+        .bind(failure.status_code)
+        .bind(&failure.error)
+        .bind(delivery_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let dead_letter = sqlx::query_as::<_, WebhookDeadLetter>(&format!(
+            "INSERT INTO webhook_dead_letters (delivery_id, tenant_id, project_id, event_id, event_type, url,
+                 payload, attempts, last_status_code, last_error)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             RETURNING {DEAD_LETTER_COLUMNS}"
+        ))
+        .bind(delivery.id)
+        .bind(delivery.tenant_id)
+        .bind(delivery.project_id)
+        .bind(delivery.event_id)
+        .bind(&delivery.event_type)
+        .bind(&delivery.url)
+        .bind(&delivery.payload)
+        .bind(delivery.attempts)
+        .bind(delivery.last_status_code)
+        .bind(&delivery.last_error)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(dead_letter))
+    }
+
+    pub async fn find_dead_letters(
+        &self,
+        filter: &DeadLetterFilter,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<Vec<WebhookDeadLetter>> {
+        let offset = (page.max(1) - 1) * limit;
+
+        let dead_letters = sqlx::query_as::<_, WebhookDeadLetter>(&format!(
+            "SELECT {DEAD_LETTER_COLUMNS} FROM webhook_dead_letters
+             WHERE {DEAD_LETTER_FILTER}
+             ORDER BY failed_at DESC, id
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind(&filter.event_type)
+        .bind(filter.replayed)
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(dead_letters)
+    }
+
+    pub async fn count_dead_letters(&self, filter: &DeadLetterFilter) -> AppResult<i64> {
+        let count: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM webhook_dead_letters WHERE {DEAD_LETTER_FILTER}"
+        ))
+        .bind(&filter.event_type)
+        .bind(filter.replayed)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn find_dead_letter(&self, id: Uuid) -> AppResult<Option<WebhookDeadLetter>> {
+        let dead_letter = sqlx::query_as::<_, WebhookDeadLetter>(&format!(
+            "SELECT {DEAD_LETTER_COLUMNS} FROM webhook_dead_letters WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(dead_letter)
+    }
+
+    /// Queue a new delivery for each outstanding dead letter in `ids`, or when
+    /// `ids` is `None` for the oldest outstanding ones (optionally of one
+    /// event type), and mark them replayed. Dead letters already replayed
+    /// are skipped.
+    pub async fn replay(
+        &self,
+        ids: Option<&[Uuid]>,
+        event_type: Option<&str>,
+        limit: i64,
+        actor_id: Uuid,
+    ) -> AppResult<Vec<WebhookDelivery>> {
+        let mut tx = deadline::begin(&self.pool).await?;
+
+        let dead_letters = sqlx::query_as::<_, WebhookDeadLetter>(&format!(
+            "SELECT {DEAD_LETTER_COLUMNS} FROM webhook_dead_letters
+             WHERE replayed_at IS NULL
+               AND ($1::UUID[] IS NULL OR id = ANY($1))
+               AND ($2::TEXT IS NULL OR event_type = $2)
+             ORDER BY failed_at, id
+             LIMIT $3
+             FOR UPDATE SKIP LOCKED"
+        ))
+        .bind(ids)
+        .bind(event_type)
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut deliveries = Vec::with_capacity(dead_letters.len());
+        for dead_letter in dead_letters {
+            let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+                "INSERT INTO webhook_deliveries (tenant_id, project_id, event_id, event_type, url, payload,
+                     replay_of)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)
+                 RETURNING {DELIVERY_COLUMNS}"
+            ))
+            .bind(dead_letter.tenant_id)
+            .bind(dead_letter.project_id)
+            .bind(dead_letter.event_id)
+            .bind(&dead_letter.event_type)
+            .bind(&dead_letter.url)
+            .bind(&dead_letter.payload)
+            .bind(dead_letter.id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                "UPDATE webhook_dead_letters
+                 SET replayed_at = NOW(), replayed_by = $1, replay_delivery_id = $2
+                 WHERE id = $3",
+            )
+            .bind(actor_id)
+            .bind(delivery.id)
+            .bind(dead_letter.id)
+            .execute(&mut *tx)
+            .await?;
+
+            deliveries.push(delivery);
+        }
+
+        tx.commit().await?;
+        Ok(deliveries)
+    }
+
+    pub async fn queue_stats(&self) -> AppResult<WebhookQueueStats> {
+        let (dead_letter_depth, oldest_dead_letter_at): (i64, Option<DateTime<Utc>>) = sqlx::query_as(
+            "SELECT COUNT(*), MIN(failed_at) FROM webhook_dead_letters WHERE replayed_at IS NULL",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let dead_letters_by_event_type = sqlx::query_as::<_, DeadLetterCount>(
+            "SELECT event_type, COUNT(*) AS count FROM webhook_dead_letters
+             WHERE replayed_at IS NULL
+             GROUP BY event_type
+             ORDER BY count DESC, event_type",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let pending_deliveries: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM webhook_deliveries WHERE status = 'pending'")
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(WebhookQueueStats {
+            dead_letter_depth,
+            oldest_dead_letter_at,
+            dead_letters_by_event_type,
+            pending_deliveries,
+        })
+    }
+}
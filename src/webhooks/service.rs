@@ -0,0 +1,219 @@
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use crate::core::audit::{AuditEvent, AuditEventType, AuditLogger, AuditSeverity};
+use crate::core::error::{AppError, AppResult};
+use crate::core::http_client::HttpClient;
+use crate::shared::types::PaginatedResponse;
+use super::model::{
+    AttemptFailure, DeadLetterFilter, DeliverySettings, ReplayDeadLettersRequest, ReplayDeadLettersResponse,
+    WebhookDeadLetter, WebhookDelivery, WebhookQueueStats,
+};
+use super::repository::WebhookRepository;
+
+/// Dead letters replayed by a bulk replay without an explicit limit
+const DEFAULT_REPLAY_LIMIT: i64 = 100;
+
+/// Outcome of one pass over the due deliveries
+#[derive(Debug, Default)]
+pub struct DeliveryRun {
+    pub delivered: usize,
+    pub retrying: usize,
+    pub dead_lettered: usize,
+}
+
+pub struct WebhookService {
+    repository: WebhookRepository,
+    audit_logger: AuditLogger,
+    settings: DeliverySettings,
+    client: HttpClient,
+}
+
+impl WebhookService {
+    pub fn new(repository: WebhookRepository, audit_logger: AuditLogger, settings: DeliverySettings) -> Self {
+        Self {
+            repository,
+            audit_logger,
+            settings,
+            client: HttpClient::default(),
+        }
+    }
+
+    /// Send deliveries through a preconfigured HTTP client (proxies, pinned
+    /// CAs). Failed deliveries follow the queue's backoff rather than the
+    /// client's retries.
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.client = client.without_retries();
+        self
+    }
+
+    /// Attempt every due delivery once. Failures are retried with backoff
+    /// until the attempt limit, then moved to the dead-letter queue.
+    pub async fn deliver_due(&self) -> AppResult<DeliveryRun> {
+        let now = Utc::now();
+        // Deliveries are sent one after another, so the lease covers the whole batch
+        let lease = Duration::seconds(self.settings.timeout_seconds as i64 * (self.settings.batch_size + 1));
+        let due = self
+            .repository
+            .claim_due(now, now + lease, self.settings.batch_size)
+            .await?;
+
+        let mut run = DeliveryRun::default();
+        for delivery in &due {
+            let failure = match self.attempt(delivery).await {
+                Ok(status_code) => {
+                    self.repository.mark_delivered(delivery.id, status_code).await?;
+                    run.delivered += 1;
+                    continue;
+                }
+                Err(failure) => failure,
+            };
+
+            let attempts = delivery.attempts + 1;
+            if attempts < self.settings.max_attempts {
+                let next_attempt_at = Utc::now() + self.settings.retry_delay(attempts);
+                self.repository
+                    .schedule_retry(delivery.id, &failure, next_attempt_at)
+                    .await?;
+                run.retrying += 1;
+            } else if let Some(dead_letter) = self.repository.dead_letter(delivery.id, &failure).await? {
+                tracing::warn!(
+                    delivery_id = %delivery.id,
+                    "Webhook {} moved to the dead-letter queue after {} attempts: {}",
+                    delivery.event_type,
+                    attempts,
+                    failure.error
+                );
+                self.log_dead_lettered(&dead_letter).await;
+                run.dead_lettered += 1;
+            }
+        }
+
+        Ok(run)
+    }
+
+    async fn attempt(&self, delivery: &WebhookDelivery) -> Result<i32, AttemptFailure> {
+        let request = self
+            .client
+            .post(&delivery.url)
+            .timeout(std::time::Duration::from_secs(self.settings.timeout_seconds))
+            .header("X-Webhook-Id", delivery.id.to_string())
+            .header("X-Webhook-Event", &delivery.event_type)
+            .json(&delivery.payload);
+        let response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|e| AttemptFailure {
+                status_code: None,
+                error: e.to_string(),
+            })?;
+
+        let status_code = i32::from(response.status().as_u16());
+        if response.status().is_success() {
+            Ok(status_code)
+        } else {
+            Err(AttemptFailure {
+                status_code: Some(status_code),
+                error: format!("Endpoint returned {}", response.status()),
+            })
+        }
+    }
+
+    pub async fn list_dead_letters(
+        &self,
+        filter: DeadLetterFilter,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<PaginatedResponse<WebhookDeadLetter>> {
+        let limit = limit.clamp(1, 100);
+        let page = page.max(1);
+
+        let dead_letters = self.repository.find_dead_letters(&filter, page, limit).await?;
+        let total = self.repository.count_dead_letters(&filter).await?.max(0) as u64;
+
+        Ok(PaginatedResponse {
+            data: dead_letters,
+            page,
+            limit,
+            total,
+            total_pages: total.div_ceil(limit as u64) as u32,
+        })
+    }
+
+    pub async fn get_dead_letter(&self, id: Uuid) -> AppResult<WebhookDeadLetter> {
+        self.repository
+            .find_dead_letter(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Dead letter not found".to_string()))
+    }
+
+    /// Queue a dead letter for delivery again, with a fresh set of attempts
+    pub async fn replay_dead_letter(&self, id: Uuid, actor_id: Uuid) -> AppResult<WebhookDelivery> {
+        let dead_letter = self.get_dead_letter(id).await?;
+        if dead_letter.replayed_at.is_some() {
+            return Err(AppError::Conflict("Dead letter has already been replayed".to_string()));
+        }
+
+        let delivery = self
+            .repository
+            .replay(Some(&[id]), None, 1, actor_id)
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::Conflict("Dead letter has already been replayed".to_string()))?;
+
+        self.log_replayed(std::slice::from_ref(&delivery), actor_id).await;
+        Ok(delivery)
+    }
+
+    pub async fn replay_dead_letters(
+        &self,
+        request: ReplayDeadLettersRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ReplayDeadLettersResponse> {
+        let limit = request.limit.unwrap_or(DEFAULT_REPLAY_LIMIT);
+        let deliveries = self
+            .repository
+            .replay(request.ids.as_deref(), request.event_type.as_deref(), limit, actor_id)
+            .await?;
+
+        if !deliveries.is_empty() {
+            self.log_replayed(&deliveries, actor_id).await;
+        }
+        Ok(ReplayDeadLettersResponse {
+            replayed: deliveries.len(),
+            deliveries,
+        })
+    }
+
+    pub async fn queue_stats(&self) -> AppResult<WebhookQueueStats> {
+        self.repository.queue_stats().await
+    }
+
+    async fn log_dead_lettered(&self, dead_letter: &WebhookDeadLetter) {
+        let event = AuditEvent::new(AuditEventType::WebhookDeadLettered)
+            .severity(AuditSeverity::Warning)
+            .resource(format!("webhook_dead_letter:{}", dead_letter.id))
+            .action("dead_letter".to_string())
+            .success(false)
+            .metadata("delivery_id".to_string(), json!(dead_letter.delivery_id))
+            .metadata("event_type".to_string(), json!(dead_letter.event_type))
+            .metadata("attempts".to_string(), json!(dead_letter.attempts))
+            .metadata("last_status_code".to_string(), json!(dead_letter.last_status_code))
+            .metadata("last_error".to_string(), json!(dead_letter.last_error));
+        self.audit_logger.log(event).await;
+    }
+
+    async fn log_replayed(&self, deliveries: &[WebhookDelivery], actor_id: Uuid) {
+        let dead_letter_ids: Vec<_> = deliveries.iter().filter_map(|delivery| delivery.replay_of).collect();
+        let delivery_ids: Vec<_> = deliveries.iter().map(|delivery| delivery.id).collect();
+        let event = AuditEvent::new(AuditEventType::WebhookReplayed)
+            .severity(AuditSeverity::Info)
+            .user_id(actor_id)
+            .resource("webhook_dead_letters".to_string())
+            .action("replay".to_string())
+            .metadata("dead_letter_ids".to_string(), json!(dead_letter_ids))
+            .metadata("delivery_ids".to_string(), json!(delivery_ids));
+        self.audit_logger.log(event).await;
+    }
+}
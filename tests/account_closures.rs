@@ -0,0 +1,99 @@
+use openbank::account_closures::model::{CloseAccountRequest, ClosureReason, ClosureSettings};
+use openbank::account_closures::repository::AccountClosureRepository;
+use openbank::account_closures::service::AccountClosureService;
+use openbank::account_controls::{model::AccountKind, repository::AccountControlRepository, service::AccountFreezeGuard};
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::AppError;
+use openbank::core::events::{DomainEventType, EventBus};
+use openbank::transactions::repository::TransactionRepository;
+use openbank::webhooks::repository::WebhookRepository;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use uuid::Uuid;
+
+
+fn close_request(account_id: Uuid, transfer_to_account_id: Option<Uuid>) -> CloseAccountRequest {
+    CloseAccountRequest {
+        account_id,
+        reason: ClosureReason::CustomerRequest,
+        note: None,
+        transfer_to_account_id,
+    }
+}
+
+#[tokio::test]
+async fn closing_sweeps_the_balance_and_blocks_the_account() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let tenant_id: Uuid = sqlx::query_scalar("INSERT INTO organizations (name) VALUES ('Test') RETURNING id")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let closing = seeder.account(Some(tenant_id), "USD", 2_500).await.id;
+    let destination = seeder.account(Some(tenant_id), "USD", 100).await.id;
+
+    let audit_logger = AuditLogger::in_memory();
+    let event_bus = EventBus::new(16);
+    let mut events = event_bus.subscribe();
+    let service = AccountClosureService::new(
+        AccountClosureRepository::new(pool.clone()),
+        TransactionRepository::new(pool.clone()),
+        AccountFreezeGuard::new(AccountControlRepository::new(pool.clone()), false),
+        audit_logger.clone(),
+        event_bus,
+        WebhookRepository::new(pool.clone()),
+        ClosureSettings {
+            retention_days: 365,
+            webhook_url: None,
+        },
+    );
+    let actor = Uuid::new_v4();
+
+    // A balance cannot be left behind
+    let error = service
+        .close_account(close_request(closing, None), tenant_id, actor)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::BadRequest(_)));
+
+    let closure = service
+        .close_account(close_request(closing, Some(destination)), tenant_id, actor)
+        .await
+        .unwrap();
+    assert_eq!(closure.transferred_amount, 2_500);
+    assert!(closure.transaction_id.is_some());
+    assert_eq!(closure.retain_until, (closure.closed_at + chrono::Duration::days(365)).date_naive());
+
+    let balances: Vec<(Uuid, i64, i64)> = sqlx::query_as(
+        "SELECT account_id, available_balance, ledger_balance FROM balances
+         WHERE account_id = ANY($1) ORDER BY ledger_balance",
+    )
+    .bind(vec![closing, destination])
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(balances, vec![(closing, 0, 0), (destination, 2_600, 2_600)]);
+
+    // The closed account no longer moves money and cannot be closed twice
+    let guard = AccountFreezeGuard::new(AccountControlRepository::new(pool.clone()), true);
+    assert!(guard.ensure_can_debit(closing).await.is_err());
+    assert!(guard.ensure_can_credit(AccountKind::Account, closing).await.is_err());
+    assert!(service
+        .close_account(close_request(closing, None), tenant_id, actor)
+        .await
+        .is_err());
+
+    let event = events.try_recv().unwrap();
+    assert_eq!(event.event_type, DomainEventType::AccountClosed);
+    assert_eq!(event.account_ids, vec![closing, destination]);
+    assert!(audit_logger
+        .recorded_events()
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::AccountClosed)));
+
+    assert_eq!(service.get_closure(closure.id, tenant_id).await.unwrap().account_id, closing);
+
+    database.cleanup().await;
+}
@@ -0,0 +1,176 @@
+use openbank::account_numbers::model::{SchemeKind, SetAccountNumberSchemeRequest, ValidateAccountNumberRequest};
+use openbank::account_numbers::repository::AccountNumberRepository;
+use openbank::account_numbers::service::{default_scheme, AccountNumberGenerator, AccountNumberSchemeService};
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::AppError;
+use openbank::shared::bank_details::validate_iban;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use uuid::Uuid;
+
+fn scheme_request(currency: Option<&str>, kind: SchemeKind) -> SetAccountNumberSchemeRequest {
+    SetAccountNumberSchemeRequest {
+        currency: currency.map(str::to_string),
+        kind,
+        prefix: None,
+        bin_start: None,
+        bin_end: None,
+        country_code: None,
+        bank_code: None,
+        length: None,
+    }
+}
+
+#[tokio::test]
+async fn numbers_follow_the_scheme_for_the_project_and_currency() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let config = test_config();
+    let seeded = Seeder::new(pool.clone(), &config).project(&[]).await;
+    let project_id = seeded.project.id;
+    let developer_id = seeded.developer.id;
+
+    let audit_logger = AuditLogger::in_memory();
+    let schemes = AccountNumberSchemeService::new(
+        AccountNumberRepository::new(pool.clone()),
+        default_scheme(&config),
+        audit_logger.clone(),
+    );
+    let generator = AccountNumberGenerator::new(AccountNumberRepository::new(pool.clone()), default_scheme(&config));
+
+    // Without a scheme of its own the project uses the configured prefix
+    let number = generator.generate(project_id, "USD").await.unwrap();
+    assert!(number.starts_with(&config.account_number_prefix));
+
+    // EUR accounts get IBANs, everything else a BIN range
+    schemes
+        .set_scheme(
+            project_id,
+            SetAccountNumberSchemeRequest {
+                country_code: Some("de".to_string()),
+                bank_code: Some("37040044".to_string()),
+                ..scheme_request(Some("eur"), SchemeKind::Iban)
+            },
+            developer_id,
+        )
+        .await
+        .unwrap();
+    schemes
+        .set_scheme(
+            project_id,
+            SetAccountNumberSchemeRequest {
+                bin_start: Some("520000".to_string()),
+                bin_end: Some("520099".to_string()),
+                length: Some(16),
+                ..scheme_request(None, SchemeKind::BinRange)
+            },
+            developer_id,
+        )
+        .await
+        .unwrap();
+    assert!(audit_logger
+        .recorded_events()
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::AccountNumberSchemeChanged)));
+
+    let iban = generator.generate(project_id, "EUR").await.unwrap();
+    assert!(iban.starts_with("DE") && iban[4..].starts_with("37040044"));
+    assert!(validate_iban(&iban).is_empty());
+    let number = generator.generate(project_id, "GBP").await.unwrap();
+    assert_eq!(number.len(), 16);
+    assert!(number.starts_with("5200"));
+
+    let validation = schemes
+        .validate(
+            project_id,
+            ValidateAccountNumberRequest {
+                account_number: iban.to_lowercase(),
+                currency: Some("EUR".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(validation.valid, "unexpected issues: {:?}", validation.issues);
+    assert_eq!(validation.country_code.as_deref(), Some("DE"));
+    assert_eq!(validation.bank_code.as_deref(), Some("37040044"));
+    assert!(!validation.in_use);
+
+    // A number an account already holds is reported as in use
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name, tenant_id)
+         VALUES ($1, 'x', 'Test', 'User', $2) RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .bind(seeded.project.organization_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO accounts (user_id, account_number, account_name, account_type, tenant_id)
+         VALUES ($1, $2, 'Checking', 'checking', $3)",
+    )
+    .bind(user_id)
+    .bind(&iban)
+    .bind(seeded.project.organization_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let validation = schemes
+        .validate(
+            project_id,
+            ValidateAccountNumberRequest {
+                account_number: iban.clone(),
+                currency: Some("EUR".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(validation.in_use);
+
+    // Numbers are checked against the default scheme when no currency is given
+    let validation = schemes
+        .validate(
+            project_id,
+            ValidateAccountNumberRequest {
+                account_number: "5300001234567890".to_string(),
+                currency: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(validation.kind, SchemeKind::BinRange);
+    assert!(!validation.valid);
+
+    // Unusable schemes are rejected
+    let result = schemes
+        .set_scheme(
+            project_id,
+            SetAccountNumberSchemeRequest {
+                country_code: Some("DE".to_string()),
+                bank_code: Some("WEST".to_string()),
+                ..scheme_request(Some("EUR"), SchemeKind::Iban)
+            },
+            developer_id,
+        )
+        .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    // Removing the EUR scheme falls back to the project's default
+    let listed = schemes.list_schemes(project_id).await.unwrap();
+    assert_eq!(listed.len(), 2);
+    assert!(listed[0].currency.is_none());
+    let eur = listed.iter().find(|scheme| scheme.currency.as_deref() == Some("EUR")).unwrap();
+    schemes.delete_scheme(eur.id, project_id, developer_id).await.unwrap();
+    let number = generator.generate(project_id, "EUR").await.unwrap();
+    assert!(number.starts_with("5200"));
+
+    // Other projects cannot remove the scheme
+    let other = Seeder::new(pool.clone(), &config).project(&[]).await;
+    assert!(matches!(
+        schemes.delete_scheme(listed[0].id, other.project.id, other.developer.id).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    database.cleanup().await;
+}
@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant};
+use openbank::core::audit::{AuditEvent, AuditEventType, AuditLogger};
+use openbank::core::audit_chain::{verify_stream, AuditChainRepository, ChainFinding};
+use openbank::core::audit_writer::AuditWriterSettings;
+use openbank::core::circuit_breaker::{CircuitBreaker, CircuitBreakerSettings, CircuitState};
+use openbank_test_support::TestDatabase;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn audit_events_are_spilled_to_disk_while_mongodb_is_down() {
+    // Nothing listens on port 1, so every write fails quickly
+    let client = mongodb::Client::with_uri_str("mongodb://127.0.0.1:1/?serverSelectionTimeoutMS=100")
+        .await
+        .unwrap();
+    let breaker = CircuitBreaker::new(
+        "audit_store",
+        CircuitBreakerSettings {
+            failure_rate_threshold: 0.5,
+            minimum_calls: 1,
+            window: Duration::from_secs(60),
+            open_duration: Duration::from_secs(60),
+            half_open_calls: 1,
+        },
+    );
+    let spill_path = std::env::temp_dir().join(format!("audit-spill-{}.jsonl", Uuid::new_v4()));
+    let logger = AuditLogger::new(
+        client,
+        breaker.clone(),
+        AuditWriterSettings {
+            queue_capacity: 1,
+            batch_size: 10,
+            spill_path: spill_path.clone(),
+        },
+    );
+
+    // Logging returns without waiting on MongoDB
+    let started = Instant::now();
+    for _ in 0..3 {
+        logger.log(AuditEvent::new(AuditEventType::LoginAttempt)).await;
+    }
+    assert!(started.elapsed() < Duration::from_millis(100));
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while logger.writer_stats().unwrap().spilled < 3 && Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    let stats = logger.writer_stats().unwrap();
+    assert_eq!(stats.spilled, 3);
+    assert_eq!(stats.written, 0);
+    assert!(stats.backpressured > 0, "a queue of one cannot hold a burst of three");
+    assert_eq!(breaker.state(), CircuitState::Open);
+
+    // Nothing is lost: every event is on disk until MongoDB takes it back
+    let spilled = std::fs::read_to_string(&spill_path).unwrap();
+    assert_eq!(spilled.lines().count(), 3);
+    assert_eq!(logger.replay_spilled().await.unwrap(), 0);
+    assert_eq!(std::fs::read_to_string(&spill_path).unwrap(), spilled);
+
+    std::fs::remove_file(&spill_path).unwrap();
+}
+
+#[tokio::test]
+async fn anchored_audit_chains_reveal_deleted_events() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let repository = AuditChainRepository::new(database.pool());
+    let logger = AuditLogger::in_memory();
+    for _ in 0..3 {
+        logger.log(AuditEvent::new(AuditEventType::LoginAttempt)).await;
+    }
+    let head = logger.chain_head().unwrap();
+    assert_eq!(head.sequence, 2);
+    assert!(repository.anchor(&head).await.unwrap());
+    assert!(!repository.anchor(&head).await.unwrap());
+
+    let verification = verify_stream(&logger, &repository, logger.chain_stream_id()).await.unwrap();
+    assert!(verification.intact);
+    assert_eq!((verification.events_checked, verification.anchors_checked), (3, 1));
+
+    // Anchors cannot be rewritten to match a doctored store
+    assert!(sqlx::query("DELETE FROM audit_chain_anchors").execute(&database.pool()).await.is_err());
+
+    // A stream the store holds nothing of still has its anchored head
+    let other = AuditLogger::in_memory();
+    other.log(AuditEvent::new(AuditEventType::LoginAttempt)).await;
+    repository.anchor(&other.chain_head().unwrap()).await.unwrap();
+    let verification = verify_stream(&logger, &repository, other.chain_stream_id()).await.unwrap();
+    assert!(matches!(
+        verification.findings.as_slice(),
+        [ChainFinding::AnchorMismatch { sequence: 0, stored_hash: None, .. }]
+    ));
+
+    database.cleanup().await;
+}
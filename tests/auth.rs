@@ -0,0 +1,425 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use axum::http::{
+    header::{AUTHORIZATION, WWW_AUTHENTICATE},
+    HeaderMap, StatusCode,
+};
+use axum::response::IntoResponse;
+use openbank::auth::login_history::LoginContext;
+use openbank::auth::middleware::decode_bearer_claims;
+use openbank::auth::model::{JwtClaims, LoginCodeRequest, RefreshTokenRequest, ReportLoginRequest, TokenRequest};
+use openbank::auth::repository::AuthRepository;
+use openbank::auth::scopes;
+use openbank::auth::service::AuthService;
+use openbank::auth::step_up::{SensitiveAction, StepUpPolicy, StepUpSettings, ACR_OTP, ACR_PASSWORD};
+use openbank::core::alerts::{Alert, AlertSink};
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::mailer::{EmailMessage, Mailer};
+use openbank::core::security::{AccountSecurityService, SecurityConfig};
+use openbank::user_data::{repository::UserDataRepository, service::UserDataService};
+use openbank_test_support::{test_config, Seeder, SeededProject, TestDatabase, TestStateBuilder};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Default)]
+struct RecordingAlertSink {
+    alerts: Mutex<Vec<Alert>>,
+}
+
+#[async_trait]
+impl AlertSink for RecordingAlertSink {
+    async fn send(&self, alert: &Alert) -> AppResult<()> {
+        self.alerts.lock().unwrap().push(alert.clone());
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, message: EmailMessage) -> AppResult<()> {
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+/// A customer of the tenant with one account; returns the user and account ids
+async fn seed_customer(pool: &PgPool, tenant_id: Uuid, email: &str, password: &str) -> (Uuid, Uuid) {
+    let password_hash = bcrypt::hash(password, 4).unwrap();
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name, tenant_id)
+         VALUES ($1, $2, 'Test', 'Customer', $3) RETURNING id",
+    )
+    .bind(email)
+    .bind(password_hash)
+    .bind(tenant_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    let account_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO accounts (user_id, account_number, account_name, account_type, tenant_id)
+         VALUES ($1, $2, 'Checking', 'checking', $3) RETURNING id",
+    )
+    .bind(user_id)
+    .bind(&Uuid::new_v4().simple().to_string()[..20])
+    .bind(tenant_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    (user_id, account_id)
+}
+
+fn user_grant(project: &SeededProject, grant_type: &str, username: &str, secret: &str) -> TokenRequest {
+    TokenRequest {
+        grant_type: grant_type.to_string(),
+        client_id: project.client_id.clone(),
+        client_secret: project.client_secret.clone(),
+        scope: None,
+        username: Some(username.to_string()),
+        password: (grant_type == "password").then(|| secret.to_string()),
+        otp: (grant_type == "otp").then(|| secret.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn issued_token_verifies_with_project_scopes() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let config = test_config();
+    let seeder = Seeder::new(database.pool(), &config);
+
+    let project = seeder.project(&[scopes::PAYMENTS, scopes::TRANSACTIONS]).await;
+    let token = seeder.token(&project).await;
+
+    let auth = AuthService::new(AuthRepository::new(database.pool()), config.jwt_secret.clone());
+    let me = auth.verify_access_token(&token.access_token).await.unwrap();
+    assert_eq!(me.developer_id, project.developer.id);
+    assert_eq!(me.project_id, project.project.id);
+    assert_eq!(me.scopes, vec![scopes::PAYMENTS, scopes::TRANSACTIONS]);
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn test_state_records_audit_events_in_memory() {
+    let audit_logger = AuditLogger::in_memory();
+    let state = TestStateBuilder::new().audit_logger(audit_logger.clone()).build().await;
+
+    state
+        .audit_logger
+        .log_access_denied("payments".to_string(), "Missing scope".to_string(), "127.0.0.1".to_string())
+        .await;
+
+    let events = audit_logger.recorded_events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event_type, AuditEventType::AccessDenied));
+}
+
+#[tokio::test]
+async fn user_tokens_reach_only_the_users_own_accounts() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let config = test_config();
+    let seeder = Seeder::new(database.pool(), &config);
+    let project = seeder.project(&[scopes::PAYMENTS, scopes::TRANSACTIONS, scopes::USER_DATA_READ]).await;
+    let tenant_id = project.project.organization_id;
+    let email = format!("customer-{}@example.com", Uuid::new_v4().simple());
+    let (user_id, account_id) = seed_customer(&database.pool(), tenant_id, &email, "hunter2-hunter2").await;
+    let other_email = format!("customer-{}@example.com", Uuid::new_v4().simple());
+    let (_, other_account_id) = seed_customer(&database.pool(), tenant_id, &other_email, "other-password").await;
+
+    let auth = AuthService::new(AuthRepository::new(database.pool()), config.jwt_secret.clone());
+    let wrong = auth
+        .issue_token(user_grant(&project, "password", &email, "wrong-password"), &LoginContext::default())
+        .await;
+    assert!(matches!(wrong, Err(AppError::Authentication(_))));
+
+    // User tokens get only the project's scopes that serve end users
+    let token = auth
+        .issue_token(user_grant(&project, "password", &email, "hunter2-hunter2"), &LoginContext::default())
+        .await
+        .unwrap();
+    assert_eq!(token.scope, "transactions user-data:read");
+    let me = auth.verify_access_token(&token.access_token).await.unwrap();
+    assert_eq!(me.user_id, Some(user_id));
+
+    let mut payments = user_grant(&project, "password", &email, "hunter2-hunter2");
+    payments.scope = Some(scopes::PAYMENTS_WRITE.to_string());
+    assert!(matches!(auth.issue_token(payments, &LoginContext::default()).await, Err(AppError::Validation(_))));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, format!("Bearer {}", token.access_token).parse().unwrap());
+    let claims = decode_bearer_claims(&headers, &config.jwt_secret).unwrap();
+    assert_eq!(claims.user_id, Some(user_id));
+
+    let user_data = UserDataService::new(UserDataRepository::new(database.pool()));
+    user_data.ensure_account_access(&claims, account_id).await.unwrap();
+    assert!(matches!(
+        user_data.ensure_account_access(&claims, other_account_id).await,
+        Err(AppError::NotFound(_))
+    ));
+    // The project itself still reaches every account of its tenant
+    let project_claims = JwtClaims { user_id: None, ..claims };
+    user_data.ensure_account_access(&project_claims, other_account_id).await.unwrap();
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn login_codes_work_once() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let config = test_config();
+    let seeder = Seeder::new(database.pool(), &config);
+    let project = seeder.project(&[scopes::TRANSACTIONS_READ]).await;
+    let email = format!("customer-{}@example.com", Uuid::new_v4().simple());
+    let (user_id, _) = seed_customer(&database.pool(), project.project.organization_id, &email, "unused-password").await;
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let auth = AuthService::new(AuthRepository::new(database.pool()), config.jwt_secret.clone())
+        .with_mailer(mailer.clone());
+    let request = |username: &str| LoginCodeRequest {
+        client_id: project.client_id.clone(),
+        client_secret: project.client_secret.clone(),
+        username: username.to_string(),
+    };
+
+    // Unknown users get the same answer and no email
+    auth.request_login_code(request("nobody@example.com"), &LoginContext::default()).await.unwrap();
+    assert!(mailer.sent.lock().unwrap().is_empty());
+
+    auth.request_login_code(request(&email), &LoginContext::default()).await.unwrap();
+    let body = mailer.sent.lock().unwrap()[0].body.clone();
+    let code: String = body.chars().filter(char::is_ascii_digit).take(6).collect();
+
+    let token = auth.issue_token(user_grant(&project, "otp", &email, &code), &LoginContext::default()).await.unwrap();
+    assert_eq!(token.scope, scopes::TRANSACTIONS_READ);
+    assert_eq!(auth.verify_access_token(&token.access_token).await.unwrap().user_id, Some(user_id));
+
+    let replayed = auth.issue_token(user_grant(&project, "otp", &email, &code), &LoginContext::default()).await;
+    assert!(matches!(replayed, Err(AppError::Authentication(_))));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn large_transfers_need_a_recent_one_time_code_login() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let config = test_config();
+    let seeder = Seeder::new(database.pool(), &config);
+    let project = seeder.project(&[scopes::TRANSACTIONS]).await;
+    let email = format!("customer-{}@example.com", Uuid::new_v4().simple());
+    let (_, account_id) = seed_customer(&database.pool(), project.project.organization_id, &email, "hunter2-hunter2").await;
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let auth = AuthService::new(AuthRepository::new(database.pool()), config.jwt_secret.clone())
+        .with_mailer(mailer.clone());
+    let audit_logger = AuditLogger::in_memory();
+    let policy = StepUpPolicy::new(StepUpSettings::from_config(&config), audit_logger.clone());
+    let claims_of = |access_token: &str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, format!("Bearer {}", access_token).parse().unwrap());
+        decode_bearer_claims(&headers, &config.jwt_secret).unwrap()
+    };
+    let large = SensitiveAction::Transfer {
+        from_account_id: account_id,
+        amount: config.step_up_transfer_threshold,
+    };
+    let small = SensitiveAction::Transfer {
+        from_account_id: account_id,
+        amount: config.step_up_transfer_threshold - 1,
+    };
+
+    let password = auth
+        .issue_token(user_grant(&project, "password", &email, "hunter2-hunter2"), &LoginContext::default())
+        .await
+        .unwrap();
+    let claims = claims_of(&password.access_token);
+    assert_eq!(claims.acr.as_deref(), Some(ACR_PASSWORD));
+    policy.require(&claims, small).await.unwrap();
+    let challenged = policy.require(&claims, large).await.unwrap_err();
+    assert!(matches!(challenged, AppError::StepUpRequired { .. }));
+    let response = challenged.into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let challenge = response.headers()[WWW_AUTHENTICATE].to_str().unwrap();
+    assert!(challenge.contains("insufficient_user_authentication"));
+    assert!(challenge.contains("acr_values=\"otp\""));
+
+    let events = audit_logger.recorded_events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event_type, AuditEventType::StepUpChallenged));
+
+    // Project tokens act for no user and are never challenged
+    policy.require(&JwtClaims { user_id: None, ..claims }, large).await.unwrap();
+
+    auth.request_login_code(
+        LoginCodeRequest {
+            client_id: project.client_id.clone(),
+            client_secret: project.client_secret.clone(),
+            username: email.clone(),
+        },
+        &LoginContext::default(),
+    )
+    .await
+    .unwrap();
+    let body = mailer.sent.lock().unwrap()[0].body.clone();
+    let code: String = body.chars().filter(char::is_ascii_digit).take(6).collect();
+    let otp = auth.issue_token(user_grant(&project, "otp", &email, &code), &LoginContext::default()).await.unwrap();
+    let otp_claims = claims_of(&otp.access_token);
+    assert_eq!(otp_claims.acr.as_deref(), Some(ACR_OTP));
+    policy.require(&otp_claims, large).await.unwrap();
+
+    // Refreshing is not a new login
+    let refreshed = auth
+        .refresh_access_token(
+            RefreshTokenRequest {
+                client_id: project.client_id.clone(),
+                client_secret: project.client_secret.clone(),
+                jti: otp_claims.jti.clone(),
+            },
+            &LoginContext::default(),
+        )
+        .await
+        .unwrap();
+    let refreshed_claims = claims_of(&refreshed.access_token);
+    assert_eq!(refreshed_claims.auth_time, otp_claims.auth_time);
+    assert_eq!(refreshed_claims.acr, otp_claims.acr);
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn developers_see_their_logins_and_report_ones_not_theirs() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let config = test_config();
+    let seeder = Seeder::new(database.pool(), &config);
+    let project = seeder.project(&[scopes::PAYMENTS]).await;
+    let alerts = Arc::new(RecordingAlertSink::default());
+    let auth = AuthService::new(AuthRepository::new(database.pool()), config.jwt_secret.clone())
+        .with_audit_logger(AuditLogger::in_memory())
+        .with_alert_sink(alerts.clone());
+
+    let grant = |client_secret: &str| TokenRequest {
+        grant_type: "client_credentials".to_string(),
+        client_id: project.client_id.clone(),
+        client_secret: client_secret.to_string(),
+        scope: None,
+        username: None,
+        password: None,
+        otp: None,
+    };
+    let mut headers = HeaderMap::new();
+    headers.insert("user-agent", "curl/8.4.0".parse().unwrap());
+    headers.insert("cf-ipcountry", "NL".parse().unwrap());
+    let context = LoginContext::from_headers("198.51.100.20".to_string(), &headers);
+
+    let refused = auth.issue_token(grant("not-the-secret"), &context).await;
+    assert!(matches!(refused, Err(AppError::Authentication(_))));
+    auth.issue_token(grant(&project.client_secret), &context).await.unwrap();
+
+    let history = auth.login_history(project.developer.id, 1, 20).await.unwrap();
+    assert_eq!(history.total, 2);
+    let (latest, failed) = (&history.data[0], &history.data[1]);
+    assert!(latest.success);
+    assert!(!failed.success);
+    assert_eq!(failed.ip_address, "198.51.100.20");
+    assert_eq!(failed.country.as_deref(), Some("NL"));
+    assert_eq!(failed.device.as_deref(), Some("curl 8.4.0"));
+    assert_eq!(latest.project_id, Some(project.project.id));
+
+    // Other developers cannot see or report the logins
+    let other = seeder.project(&[scopes::PAYMENTS]).await;
+    assert_eq!(auth.login_history(other.developer.id, 1, 20).await.unwrap().total, 0);
+    let report = |developer_id| {
+        auth.report_login(
+            developer_id,
+            latest.id,
+            ReportLoginRequest {
+                note: Some("Not from our office".to_string()),
+            },
+            "127.0.0.1".to_string(),
+        )
+    };
+    assert!(matches!(report(other.developer.id).await, Err(AppError::NotFound(_))));
+
+    report(project.developer.id).await.unwrap();
+    assert!(matches!(report(project.developer.id).await, Err(AppError::Conflict(_))));
+    assert_eq!(alerts.alerts.lock().unwrap().len(), 1);
+
+    let history = auth.login_history(project.developer.id, 1, 20).await.unwrap();
+    assert!(history.data[0].reported);
+    assert!(!history.data[1].reported);
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn clients_are_locked_out_after_repeated_wrong_secrets() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let config = test_config();
+    let seeder = Seeder::new(database.pool(), &config);
+    let project = seeder.project(&[scopes::PAYMENTS]).await;
+    let audit_logger = AuditLogger::in_memory();
+    let auth = AuthService::new(AuthRepository::new(database.pool()), config.jwt_secret.clone())
+        .with_audit_logger(audit_logger.clone())
+        .with_security_service(AccountSecurityService::new(SecurityConfig {
+            client_max_failed_attempts: 3,
+            ..SecurityConfig::default()
+        }));
+
+    let grant = |client_secret: &str| TokenRequest {
+        grant_type: "client_credentials".to_string(),
+        client_id: project.client_id.clone(),
+        client_secret: client_secret.to_string(),
+        scope: None,
+        username: None,
+        password: None,
+        otp: None,
+    };
+    let context = LoginContext {
+        ip_address: "198.51.100.20".to_string(),
+        ..LoginContext::default()
+    };
+
+    // A success clears earlier failures
+    auth.issue_token(grant("wrong-secret"), &context).await.unwrap_err();
+    auth.issue_token(grant(&project.client_secret), &context).await.unwrap();
+    for _ in 0..2 {
+        auth.issue_token(grant("wrong-secret"), &context).await.unwrap_err();
+    }
+    auth.issue_token(grant(&project.client_secret), &context).await.unwrap();
+
+    for _ in 0..3 {
+        auth.issue_token(grant("wrong-secret"), &context).await.unwrap_err();
+    }
+    // Locked, even with the right secret
+    match auth.issue_token(grant(&project.client_secret), &context).await {
+        Err(AppError::Authentication(message)) => assert!(message.contains("locked")),
+        other => panic!("expected a lockout, got {:?}", other.map(|token| token.scope)),
+    }
+
+    let locks: Vec<_> = audit_logger
+        .recorded_events()
+        .into_iter()
+        .filter(|event| matches!(event.event_type, AuditEventType::AccountLocked))
+        .collect();
+    assert_eq!(locks.len(), 1);
+    assert_eq!(locks[0].user_id, Some(project.developer.id));
+    assert_eq!(locks[0].ip_address, "198.51.100.20");
+
+    database.cleanup().await;
+}
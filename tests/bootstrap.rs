@@ -0,0 +1,73 @@
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::bootstrap;
+use openbank::core::rbac::permissions;
+use openbank_test_support::{TestDatabase, TestStateBuilder};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn bootstrap_creates_the_first_admin_once() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let email = format!("admin-{}@example.com", &Uuid::new_v4().simple().to_string()[..12]);
+    let state = TestStateBuilder::new()
+        .postgres(database.pool())
+        .audit_logger(AuditLogger::in_memory())
+        .config(|config| {
+            config.bootstrap_enabled = true;
+            config.bootstrap_admin_email = Some(email.clone());
+            config.bootstrap_admin_password = Some("correct-horse-battery".to_string());
+            config.bcrypt_cost = 4;
+        })
+        .build()
+        .await;
+
+    let created = bootstrap::run(&state).await.unwrap().unwrap();
+    assert!(created.admin_created);
+    assert_eq!(created.roles_granted, vec!["super_admin".to_string()]);
+    state
+        .authorize(created.admin_id, permissions::system_admin(), "127.0.0.1".to_string(), "system".to_string())
+        .await
+        .unwrap();
+
+    let owner: (String,) = sqlx::query_as(
+        "SELECT role::text FROM organization_members WHERE organization_id = $1 AND developer_id = $2",
+    )
+    .bind(created.organization_id)
+    .bind(created.admin_id)
+    .fetch_one(&database.pool())
+    .await
+    .unwrap();
+    assert_eq!(owner.0, "owner");
+
+    // A second start finds everything in place
+    let again = bootstrap::run(&state).await.unwrap().unwrap();
+    assert_eq!(again.admin_id, created.admin_id);
+    assert!(!again.admin_created);
+    assert!(again.roles_granted.is_empty());
+
+    // ...and restores the role if it was taken away
+    sqlx::query("DELETE FROM user_roles WHERE developer_id = $1")
+        .bind(created.admin_id)
+        .execute(&database.pool())
+        .await
+        .unwrap();
+    let restored = bootstrap::run(&state).await.unwrap().unwrap();
+    assert_eq!(restored.roles_granted, vec!["super_admin".to_string()]);
+
+    let granted = state
+        .audit_logger
+        .recorded_events()
+        .iter()
+        .filter(|event| matches!(event.event_type, AuditEventType::SystemRoleGranted))
+        .count();
+    assert_eq!(granted, 2);
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn bootstrap_is_off_unless_enabled() {
+    let state = TestStateBuilder::new().build().await;
+    assert!(bootstrap::run(&state).await.unwrap().is_none());
+}
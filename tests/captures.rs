@@ -0,0 +1,108 @@
+use openbank::captures::model::{CaptureFilter, NewDebugCapture, UpdateCaptureSettingsRequest};
+use openbank::captures::service::capture_service;
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::AppError;
+use openbank_test_support::{test_config, Seeder, TestDatabase, TestStateBuilder};
+use uuid::Uuid;
+
+fn capture(project_id: Uuid, status_code: i32) -> NewDebugCapture {
+    NewDebugCapture {
+        project_id,
+        method: "POST".to_string(),
+        path: "/api/v1/payments".to_string(),
+        query: None,
+        status_code,
+        request_headers: serde_json::json!({"authorization": "[REDACTED]"}),
+        request_body: Some("{\"amount\":100}".to_string()),
+        response_headers: serde_json::json!({}),
+        response_body: Some("{\"success\":false}".to_string()),
+        duration_ms: 12,
+        retention_hours: 24,
+    }
+}
+
+#[tokio::test]
+async fn captures_are_opt_in_and_kept_for_the_retention() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let seeder = Seeder::new(database.pool(), &test_config());
+    let seeded = seeder.project(&[]).await;
+    let project_id = seeded.project.id;
+    let state = TestStateBuilder::new()
+        .postgres(database.pool())
+        .audit_logger(AuditLogger::in_memory())
+        .build()
+        .await;
+    let captures = capture_service(&state);
+
+    // Off until the project opts in
+    assert!(!captures.settings_for(project_id).await.unwrap().enabled);
+    assert!(!captures.get_settings(project_id).await.unwrap().enabled);
+
+    let settings = captures
+        .update_settings(
+            project_id,
+            UpdateCaptureSettingsRequest {
+                enabled: true,
+                sample_rate: Some(0.25),
+                retention_hours: Some(2),
+            },
+            seeded.developer.id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(settings.sample_rate, 0.25);
+    assert_eq!(settings.updated_by, Some(seeded.developer.id));
+
+    // Saving drops the cached settings, so sampling starts at once
+    let sampling = captures.settings_for(project_id).await.unwrap();
+    assert!(sampling.enabled);
+    assert_eq!(sampling.retention_hours, 2);
+
+    captures.record(capture(project_id, 201)).await.unwrap();
+    let failed = captures.record(capture(project_id, 401)).await.unwrap();
+    let mut expired = capture(project_id, 500);
+    expired.retention_hours = 0;
+    captures.record(expired).await.unwrap();
+
+    let page = captures
+        .list_captures(project_id, CaptureFilter::default(), 1, 20)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 2, "expired captures are not listed");
+
+    let errors = captures
+        .list_captures(
+            project_id,
+            CaptureFilter {
+                status_code: None,
+                errors_only: true,
+            },
+            1,
+            20,
+        )
+        .await
+        .unwrap();
+    assert_eq!(errors.data.len(), 1);
+    assert_eq!(errors.data[0].id, failed);
+
+    let detail = captures.get_capture(project_id, failed, seeded.developer.id).await.unwrap();
+    assert_eq!(detail.request_body.as_deref(), Some("{\"amount\":100}"));
+    assert!(matches!(
+        captures.get_capture(Uuid::new_v4(), failed, seeded.developer.id).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    let events = state.audit_logger.recorded_events();
+    assert!(events
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::ProjectDebugCaptureChanged)));
+    assert!(events
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::DebugCaptureViewed)));
+
+    assert_eq!(captures.purge_expired().await.unwrap(), 1);
+
+    database.cleanup().await;
+}
@@ -0,0 +1,146 @@
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::http_client::{HttpClient, RetryPolicy};
+use wiremock::{MockBuilder, MockServer, ResponseTemplate};
+
+/// Timeout of the HTTP client handed to adapters under test
+pub const CLIENT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Retry policy of the HTTP client handed to adapters under test
+pub const CLIENT_RETRY: RetryPolicy = RetryPolicy {
+    max_retries: 2,
+    base_delay: Duration::from_millis(10),
+};
+
+/// A provider adapter exercised against a fake provider.
+///
+/// Every adapter talking to an external HTTP provider implements this and
+/// runs [`run_matrix`], so they all agree on timeouts, retries and how
+/// provider failures surface as `AppError`s.
+#[async_trait]
+pub trait ProviderConformance: Sized + Send + Sync {
+    /// Name used in assertion messages
+    const NAME: &'static str;
+
+    /// Whether the adapter reads the response body on success
+    const READS_BODY: bool = true;
+
+    /// Requests the adapter may make for one call when the provider keeps
+    /// failing; the client's first attempt plus its retries
+    const MAX_ATTEMPTS: u64 = 1 + CLIENT_RETRY.max_retries as u64;
+
+    /// Build the adapter against the fake provider
+    fn adapter(base_url: &str, http: HttpClient) -> Self;
+
+    /// Matches the request the adapter sends for one call
+    fn request() -> MockBuilder;
+
+    /// A recorded successful response
+    fn success() -> ResponseTemplate;
+
+    /// Make one call to the provider
+    async fn call(&self) -> AppResult<()>;
+}
+
+/// Run the behavioral test matrix every provider adapter must pass
+pub async fn run_matrix<P: ProviderConformance>() {
+    succeeds_on_recorded_response::<P>().await;
+    maps_client_errors_without_retrying::<P>().await;
+    maps_server_errors_after_bounded_retries::<P>().await;
+    maps_timeouts::<P>().await;
+    maps_unreadable_bodies::<P>().await;
+}
+
+pub async fn start<P: ProviderConformance>(response: ResponseTemplate) -> (MockServer, P) {
+    let server = MockServer::start().await;
+    P::request().respond_with(response).mount(&server).await;
+    let client = reqwest::Client::builder()
+        .timeout(CLIENT_TIMEOUT)
+        .build()
+        .expect("Failed to build HTTP client");
+    let http = HttpClient::new(client, CLIENT_RETRY);
+    let adapter = P::adapter(&server.uri(), http);
+    (server, adapter)
+}
+
+pub async fn received(server: &MockServer) -> u64 {
+    server.received_requests().await.map_or(0, |requests| requests.len() as u64)
+}
+
+fn assert_external<T: std::fmt::Debug>(name: &str, case: &str, result: AppResult<T>) {
+    match result {
+        Err(AppError::ExternalService(_)) => {}
+        other => panic!("{}: {} should map to ExternalService, got {:?}", name, case, other),
+    }
+}
+
+async fn succeeds_on_recorded_response<P: ProviderConformance>() {
+    let (server, adapter) = start::<P>(P::success()).await;
+
+    let result = adapter.call().await;
+    assert!(result.is_ok(), "{}: recorded response should succeed, got {:?}", P::NAME, result);
+    assert_eq!(received(&server).await, 1, "{}: a successful call should be sent once", P::NAME);
+}
+
+async fn maps_client_errors_without_retrying<P: ProviderConformance>() {
+    let body = include_str!("fixtures/provider_error.json");
+    for status in [400, 401, 403, 422] {
+        let (server, adapter) =
+            start::<P>(ResponseTemplate::new(status).set_body_raw(body, "application/json")).await;
+
+        assert_external(P::NAME, &format!("HTTP {}", status), adapter.call().await);
+        assert_eq!(
+            received(&server).await,
+            1,
+            "{}: HTTP {} must not be retried",
+            P::NAME,
+            status
+        );
+    }
+}
+
+async fn maps_server_errors_after_bounded_retries<P: ProviderConformance>() {
+    let body = include_str!("fixtures/provider_error.json");
+    for status in [500, 502, 503] {
+        let (server, adapter) =
+            start::<P>(ResponseTemplate::new(status).set_body_raw(body, "application/json")).await;
+
+        assert_external(P::NAME, &format!("HTTP {}", status), adapter.call().await);
+        let attempts = received(&server).await;
+        assert!(
+            (1..=P::MAX_ATTEMPTS).contains(&attempts),
+            "{}: HTTP {} was attempted {} times, at most {} allowed",
+            P::NAME,
+            status,
+            attempts,
+            P::MAX_ATTEMPTS
+        );
+    }
+}
+
+async fn maps_timeouts<P: ProviderConformance>() {
+    let (_server, adapter) = start::<P>(P::success().set_delay(CLIENT_TIMEOUT * 4)).await;
+
+    let started = Instant::now();
+    assert_external(P::NAME, "a timeout", adapter.call().await);
+    // Every attempt may use the full client timeout, plus some slack
+    let bound = CLIENT_TIMEOUT * (P::MAX_ATTEMPTS as u32 + 1);
+    assert!(
+        started.elapsed() < bound,
+        "{}: timed out call took {:?}, expected under {:?}",
+        P::NAME,
+        started.elapsed(),
+        bound
+    );
+}
+
+async fn maps_unreadable_bodies<P: ProviderConformance>() {
+    if !P::READS_BODY {
+        return;
+    }
+    let (_server, adapter) =
+        start::<P>(ResponseTemplate::new(200).set_body_raw("<html>Bad gateway</html>", "text/html")).await;
+
+    assert_external(P::NAME, "an unreadable body", adapter.call().await);
+}
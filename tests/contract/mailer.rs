@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use openbank::core::error::AppResult;
+use openbank::core::http_client::HttpClient;
+use openbank::core::mailer::{EmailMessage, HttpMailer, Mailer};
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockBuilder, ResponseTemplate};
+use crate::conformance::{self, ProviderConformance};
+
+#[async_trait]
+impl ProviderConformance for HttpMailer {
+    const NAME: &'static str = "http mailer";
+    const READS_BODY: bool = false;
+
+    fn adapter(base_url: &str, http: HttpClient) -> Self {
+        HttpMailer::new(
+            format!("{}/v1/emails", base_url),
+            "mail-api-key".to_string(),
+            "noreply@openbank.dev".to_string(),
+        )
+        .with_http_client(http)
+    }
+
+    fn request() -> MockBuilder {
+        Mock::given(method("POST"))
+            .and(path("/v1/emails"))
+            .and(header("authorization", "Bearer mail-api-key"))
+            .and(body_partial_json(serde_json::json!({
+                "from": "noreply@openbank.dev",
+                "to": "ada@example.com",
+                "subject": "Confirm employment",
+            })))
+    }
+
+    fn success() -> ResponseTemplate {
+        ResponseTemplate::new(202)
+            .set_body_raw(include_str!("fixtures/mail_accepted.json"), "application/json")
+    }
+
+    async fn call(&self) -> AppResult<()> {
+        self.send(EmailMessage {
+            to: "ada@example.com".to_string(),
+            subject: "Confirm employment".to_string(),
+            body: "Please confirm the employment of Ada Lovelace.".to_string(),
+        })
+        .await
+    }
+}
+
+#[tokio::test]
+async fn http_mailer_conforms() {
+    conformance::run_matrix::<HttpMailer>().await;
+}
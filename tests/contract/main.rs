@@ -0,0 +1,10 @@
+//! Contract tests for provider adapters.
+//!
+//! Each adapter runs against a wiremock fake of its provider, replaying
+//! responses recorded in `fixtures/`, and must pass the shared matrix in
+//! [`conformance`].
+
+mod conformance;
+mod mailer;
+mod secrets;
+mod storage;
@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use openbank::core::error::AppResult;
+use openbank::core::http_client::HttpClient;
+use openbank::core::secrets::{SecretsProvider, VaultSecretsProvider};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockBuilder, ResponseTemplate};
+use crate::conformance::{self, ProviderConformance};
+
+#[async_trait]
+impl ProviderConformance for VaultSecretsProvider {
+    const NAME: &'static str = "vault secrets provider";
+
+    fn adapter(base_url: &str, http: HttpClient) -> Self {
+        VaultSecretsProvider::new(
+            base_url.to_string(),
+            "vault-token".to_string(),
+            "secret".to_string(),
+            "openbank".to_string(),
+        )
+        .with_http_client(http)
+    }
+
+    fn request() -> MockBuilder {
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/openbank"))
+            .and(header("x-vault-token", "vault-token"))
+    }
+
+    fn success() -> ResponseTemplate {
+        ResponseTemplate::new(200)
+            .set_body_raw(include_str!("fixtures/vault_kv_read.json"), "application/json")
+    }
+
+    async fn call(&self) -> AppResult<()> {
+        self.get_secret("JWT_SECRET").await.map(|_| ())
+    }
+}
+
+#[tokio::test]
+async fn vault_secrets_provider_conforms() {
+    conformance::run_matrix::<VaultSecretsProvider>().await;
+}
+
+#[tokio::test]
+async fn vault_reads_secret_from_recorded_response() {
+    let (_server, vault) =
+        conformance::start::<VaultSecretsProvider>(VaultSecretsProvider::success()).await;
+
+    assert_eq!(
+        vault.get_secret("JWT_SECRET").await.unwrap().as_deref(),
+        Some("vault-jwt-secret")
+    );
+    assert_eq!(vault.get_secret("MAIL_API_KEY").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn vault_missing_secret_path_is_not_an_error() {
+    let (server, vault) =
+        conformance::start::<VaultSecretsProvider>(ResponseTemplate::new(404)).await;
+
+    assert_eq!(vault.get_secret("JWT_SECRET").await.unwrap(), None);
+    assert_eq!(conformance::received(&server).await, 1);
+}
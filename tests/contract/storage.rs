@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::http_client::HttpClient;
+use openbank::core::storage::{S3Settings, S3Storage, Storage};
+use tokio::io::AsyncReadExt;
+use wiremock::matchers::{header, header_exists, method, path};
+use wiremock::{Mock, MockBuilder, MockServer, ResponseTemplate};
+use crate::conformance::{self, ProviderConformance};
+
+const OBJECT_PATH: &str = "/openbank-documents/disputes/receipt%201.pdf";
+
+fn s3_storage(base_url: &str) -> S3Storage {
+    S3Storage::new(S3Settings {
+        endpoint: base_url.to_string(),
+        bucket: "openbank-documents".to_string(),
+        region: "us-east-1".to_string(),
+        path_style: true,
+        access_key_id: "AKIDEXAMPLE".to_string(),
+        secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+        session_token: None,
+    })
+    .expect("Failed to build S3 storage")
+}
+
+#[async_trait]
+impl ProviderConformance for S3Storage {
+    const NAME: &'static str = "s3 storage";
+    const READS_BODY: bool = false;
+
+    fn adapter(base_url: &str, http: HttpClient) -> Self {
+        s3_storage(base_url).with_http_client(http)
+    }
+
+    fn request() -> MockBuilder {
+        Mock::given(method("PUT"))
+            .and(path(OBJECT_PATH))
+            .and(header_exists("authorization"))
+            .and(header_exists("x-amz-date"))
+            .and(header_exists("x-amz-content-sha256"))
+    }
+
+    fn success() -> ResponseTemplate {
+        ResponseTemplate::new(200)
+    }
+
+    async fn call(&self) -> AppResult<()> {
+        self.put("disputes/receipt 1.pdf", b"%PDF-1.7".to_vec()).await
+    }
+}
+
+#[tokio::test]
+async fn s3_storage_conforms() {
+    conformance::run_matrix::<S3Storage>().await;
+}
+
+#[tokio::test]
+async fn s3_streams_objects_and_treats_missing_ones_as_not_found() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(OBJECT_PATH))
+        .and(header(
+            "x-amz-content-sha256",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.7".to_vec()))
+        .mount(&server)
+        .await;
+    let storage = s3_storage(&server.uri());
+
+    let mut content = Vec::new();
+    storage
+        .get_stream("disputes/receipt 1.pdf")
+        .await
+        .unwrap()
+        .read_to_end(&mut content)
+        .await
+        .unwrap();
+    assert_eq!(content, b"%PDF-1.7");
+
+    // Anything else is a 404 from the fake
+    assert!(matches!(storage.get("disputes/missing.pdf").await, Err(AppError::NotFound(_))));
+    storage.delete("disputes/missing.pdf").await.unwrap();
+    assert!(matches!(storage.get("../escape").await, Err(AppError::Validation(_))));
+}
+
+#[tokio::test]
+async fn s3_signed_urls_point_at_the_object() {
+    let storage = s3_storage("http://127.0.0.1:9000");
+
+    let url = storage
+        .signed_url("disputes/receipt 1.pdf", std::time::Duration::from_secs(900))
+        .unwrap();
+    assert!(url.starts_with(&format!("http://127.0.0.1:9000{}?X-Amz-Algorithm=AWS4-HMAC-SHA256", OBJECT_PATH)));
+    assert!(url.contains("&X-Amz-Expires=900&"));
+    assert!(url.contains("&X-Amz-Signature="));
+}
@@ -0,0 +1,180 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::events::{DomainEventType, EventBus};
+use openbank::income::bureau::{BureauCallback, BureauOutcome, CreditBureau, CreditReportRequest};
+use openbank::income::credit_check::CreditCheckService;
+use openbank::income::model::{
+    CreditCheckConsent, CreditCheckRequest, CreditCheckSettings, CreditCheckStatus, CreditReport,
+};
+use openbank::income::repository::IncomeRepository;
+use openbank_test_support::TestDatabase;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Accepts every pull and treats a callback body as the reference of a
+/// completed pull, signed when the signature is `valid`
+#[derive(Default)]
+struct FakeBureau {
+    requests: Mutex<Vec<CreditReportRequest>>,
+}
+
+#[async_trait]
+impl CreditBureau for FakeBureau {
+    fn name(&self) -> &'static str {
+        "fake"
+    }
+
+    async fn request_report(&self, request: &CreditReportRequest) -> AppResult<String> {
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(format!("ref-{}", request.check_id))
+    }
+
+    fn verify_callback(&self, signature: Option<&str>, _body: &[u8]) -> bool {
+        signature == Some("valid")
+    }
+
+    fn parse_callback(&self, body: &[u8]) -> AppResult<BureauCallback> {
+        Ok(BureauCallback {
+            reference: String::from_utf8(body.to_vec()).unwrap(),
+            outcome: BureauOutcome::Completed(CreditReport {
+                score: Some(705),
+                score_model: Some("FICO8".to_string()),
+                currency: "USD".to_string(),
+                accounts: Vec::new(),
+                total_balance: 0,
+                total_monthly_payments: 0,
+                credit_utilization: None,
+                delinquent_accounts: 0,
+                recent_inquiries: 1,
+                public_records: 0,
+                generated_at: Utc::now(),
+            }),
+        })
+    }
+}
+
+async fn seed_user(pool: &PgPool, tenant_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name, tenant_id)
+         VALUES ($1, 'x', 'Test', 'User', $2) RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .bind(tenant_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+fn credit_check_request(user_id: Uuid, granted: bool, days_ago: i64) -> CreditCheckRequest {
+    CreditCheckRequest {
+        user_id,
+        purpose: "loan_application".to_string(),
+        consent: CreditCheckConsent {
+            granted,
+            granted_at: Utc::now() - Duration::days(days_ago),
+            reference: "consent-form-1".to_string(),
+        },
+    }
+}
+
+#[tokio::test]
+async fn credit_checks_need_consent_and_complete_through_the_callback() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let tenant_id: Uuid = sqlx::query_scalar("INSERT INTO organizations (name) VALUES ('Test') RETURNING id")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let user_id = seed_user(&pool, tenant_id).await;
+
+    let bureau = Arc::new(FakeBureau::default());
+    let audit_logger = AuditLogger::in_memory();
+    let event_bus = EventBus::new(16);
+    let mut events = event_bus.subscribe();
+    let service = CreditCheckService::new(
+        IncomeRepository::new(pool.clone()),
+        bureau.clone(),
+        audit_logger.clone(),
+        event_bus,
+        CreditCheckSettings {
+            consent_max_age_days: 30,
+            retention_days: 90,
+            callback_url: "https://openbank.example.com/api/v1/income/credit-check/callback".to_string(),
+        },
+    );
+    let actor = Uuid::new_v4();
+
+    // Without consent, or with stale consent, nothing is pulled
+    for request in [credit_check_request(user_id, false, 0), credit_check_request(user_id, true, 31)] {
+        assert!(matches!(
+            service.initiate(request, tenant_id, actor).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+    assert!(matches!(
+        service.initiate(credit_check_request(user_id, true, 0), Uuid::new_v4(), actor).await,
+        Err(AppError::NotFound(_))
+    ));
+    assert!(bureau.requests.lock().unwrap().is_empty());
+
+    let check = service
+        .initiate(credit_check_request(user_id, true, 1), tenant_id, actor)
+        .await
+        .unwrap();
+    assert_eq!(check.status, CreditCheckStatus::Pending);
+    assert_eq!(check.provider, "fake");
+    assert_eq!(bureau.requests.lock().unwrap()[0].subject.first_name, "Test");
+
+    // One pull at a time per user
+    assert!(matches!(
+        service.initiate(credit_check_request(user_id, true, 0), tenant_id, actor).await,
+        Err(AppError::Conflict(_))
+    ));
+
+    // Unsigned callbacks are refused; signed ones complete the check once
+    let reference = format!("ref-{}", check.id);
+    assert!(matches!(
+        service.handle_callback(Some("forged"), reference.as_bytes()).await,
+        Err(AppError::Authentication(_))
+    ));
+    service.handle_callback(Some("valid"), reference.as_bytes()).await.unwrap();
+    service.handle_callback(Some("valid"), reference.as_bytes()).await.unwrap();
+
+    let completed = service.get_check(check.id, tenant_id).await.unwrap();
+    assert_eq!(completed.status, CreditCheckStatus::Completed);
+    assert_eq!(completed.report.as_ref().and_then(|report| report.score), Some(705));
+    assert!(completed.retain_until.unwrap() > Utc::now() + Duration::days(89));
+
+    let event = events.try_recv().unwrap();
+    assert_eq!(event.event_type, DomainEventType::CreditCheckCompleted);
+    assert_eq!(event.data["credit_check_id"], serde_json::json!(check.id));
+    assert!(events.try_recv().is_err());
+
+    // Reports past retention are purged, keeping the record of the pull
+    let purged = IncomeRepository::new(pool.clone())
+        .purge_expired_credit_reports(Utc::now() + Duration::days(91))
+        .await
+        .unwrap();
+    assert_eq!(purged.len(), 1);
+    let check = service.get_check(check.id, tenant_id).await.unwrap();
+    assert!(check.report.is_none());
+    assert!(check.purged_at.is_some());
+    assert_eq!(check.consent_reference, "consent-form-1");
+
+    // Other tenants cannot see or purge it
+    assert!(matches!(
+        service.purge_report(check.id, Uuid::new_v4(), actor).await,
+        Err(AppError::NotFound(_))
+    ));
+    assert!(audit_logger
+        .recorded_events()
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::CreditCheckRequested)));
+
+    database.cleanup().await;
+}
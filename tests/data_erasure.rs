@@ -0,0 +1,98 @@
+use chrono::Utc;
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::AppError;
+use openbank::data_erasure::model::EraseUserDataRequest;
+use openbank::data_erasure::repository::DataErasureRepository;
+use openbank::data_erasure::service::DataErasureService;
+use openbank::identity::model::{IdentityVerification, VerificationStatus};
+use openbank::identity::repository::IdentityRepository;
+use openbank::shared::traits::Repository;
+use openbank_test_support::{TestDatabase, TestStateBuilder};
+use uuid::Uuid;
+
+fn verification(user_id: Uuid) -> IdentityVerification {
+    let now = Utc::now();
+    IdentityVerification {
+        id: Uuid::new_v4(),
+        user_id,
+        verification_type: "passport".to_string(),
+        status: VerificationStatus::Completed,
+        document_type: Some("passport".to_string()),
+        document_number: Some("P1234567".to_string()),
+        verification_data: Some(serde_json::json!({ "date_of_birth": "1990-01-01" })),
+        provider: None,
+        provider_reference: None,
+        completed_at: Some(now),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[tokio::test]
+async fn shredding_a_users_key_erases_their_personal_data() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let state = TestStateBuilder::new().postgres(pool.clone()).build().await;
+    let identity = IdentityRepository::new(pool.clone(), state.user_cipher.clone());
+    let erasure = DataErasureService::new(
+        DataErasureRepository::new(pool.clone()),
+        state.user_cipher.key_store().clone(),
+        IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+        AuditLogger::in_memory(),
+    );
+
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name)
+         VALUES ($1, 'x', 'Test', 'User') RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let created = identity.create(verification(user_id)).await.unwrap();
+    assert_eq!(created.document_number.as_deref(), Some("P1234567"));
+    let stored: String = sqlx::query_scalar("SELECT document_number FROM identity_verifications WHERE id = $1")
+        .bind(created.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(stored.starts_with("enc:u1:"));
+
+    // A value written before per-user keys, which shredding cannot reach
+    let legacy = verification(user_id);
+    sqlx::query(
+        "INSERT INTO identity_verifications (id, user_id, verification_type, document_number)
+         VALUES ($1, $2, 'passport', 'P7654321')",
+    )
+    .bind(legacy.id)
+    .bind(user_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert!(!erasure.get(user_id).await.unwrap().erased);
+    let erased = erasure
+        .erase(user_id, EraseUserDataRequest { reason: "GDPR-42".to_string() }, Uuid::new_v4())
+        .await
+        .unwrap();
+    assert!(erased.erased);
+
+    let erased_verification = identity.find_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(erased_verification.document_number, None);
+    assert_eq!(erased_verification.verification_data, None);
+    let legacy = identity.find_by_id(legacy.id).await.unwrap().unwrap();
+    assert_eq!(legacy.document_number, None);
+
+    assert!(matches!(
+        erasure
+            .erase(user_id, EraseUserDataRequest { reason: "GDPR-42".to_string() }, Uuid::new_v4())
+            .await,
+        Err(AppError::Conflict(_))
+    ));
+    assert!(matches!(identity.create(verification(user_id)).await, Err(AppError::Conflict(_))));
+
+    database.cleanup().await;
+}
@@ -0,0 +1,139 @@
+use axum::http::HeaderMap;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{Duration, Utc};
+use openbank::auth::model::JwtClaims;
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::AppError;
+use openbank::devices::assertion::{
+    signing_input, DeviceAssertion, DEVICE_ID_HEADER, DEVICE_SIGNATURE_HEADER, DEVICE_TIMESTAMP_HEADER,
+};
+use openbank::devices::model::{DeviceRisk, DeviceSettings, RegisterDeviceRequest};
+use openbank::devices::repository::DeviceRepository;
+use openbank::devices::service::{DeviceService, SignedCall};
+use openbank::payments::model::PaymentSettings;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use uuid::Uuid;
+
+const PATH: &str = "/api/v1/transactions/transfer";
+
+fn key_pair() -> Ed25519KeyPair {
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+    Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+}
+
+/// The device headers for a call signed by `key`
+fn sign(key: &Ed25519KeyPair, device_id: Uuid, timestamp_ms: i64, body: &[u8]) -> Option<DeviceAssertion> {
+    let signature = key.sign(&signing_input("POST", PATH, timestamp_ms, body));
+    let mut headers = HeaderMap::new();
+    headers.insert(DEVICE_ID_HEADER, device_id.to_string().parse().unwrap());
+    headers.insert(DEVICE_TIMESTAMP_HEADER, timestamp_ms.to_string().parse().unwrap());
+    headers.insert(DEVICE_SIGNATURE_HEADER, STANDARD.encode(signature.as_ref()).parse().unwrap());
+    DeviceAssertion::from_headers(&headers).unwrap()
+}
+
+fn call(body: &[u8]) -> SignedCall<'_> {
+    SignedCall {
+        method: "POST",
+        path_and_query: PATH,
+        body,
+    }
+}
+
+#[test]
+fn untrusted_devices_hold_large_payments() {
+    let settings = PaymentSettings::from_config(&test_config());
+    let threshold = settings.device_review_threshold;
+
+    assert!(settings.requires_device_review(Some(DeviceRisk::New), threshold));
+    assert!(settings.requires_device_review(Some(DeviceRisk::Unbound), threshold));
+    assert!(!settings.requires_device_review(Some(DeviceRisk::New), threshold - 1));
+    assert!(!settings.requires_device_review(Some(DeviceRisk::Trusted), threshold));
+    assert!(!settings.requires_device_review(None, threshold));
+}
+
+#[tokio::test]
+async fn bound_devices_sign_calls_until_revoked() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let config = test_config();
+    let project = Seeder::new(database.pool(), &config).project(&[]).await;
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name, tenant_id)
+         VALUES ($1, 'unused', 'Test', 'Customer', $2) RETURNING id",
+    )
+    .bind(format!("customer-{}@example.com", Uuid::new_v4().simple()))
+    .bind(project.project.organization_id)
+    .fetch_one(&database.pool())
+    .await
+    .unwrap();
+    let now = Utc::now();
+    let claims = JwtClaims {
+        iss: "openbank-auth".to_string(),
+        aud: "openbank-api".to_string(),
+        sub: user_id.to_string(),
+        exp: (now + Duration::hours(1)).timestamp(),
+        iat: now.timestamp(),
+        jti: Uuid::new_v4().to_string(),
+        developer_id: project.developer.id,
+        project_id: project.project.id,
+        tenant_id: project.project.organization_id,
+        scopes: Vec::new(),
+        user_id: Some(user_id),
+        auth_time: None,
+        acr: None,
+    };
+
+    let audit_logger = AuditLogger::in_memory();
+    let devices = DeviceService::new(
+        DeviceRepository::new(database.pool()),
+        DeviceSettings::from_config(&config),
+        audit_logger.clone(),
+    );
+    let key = key_pair();
+    let request = || RegisterDeviceRequest {
+        name: "Phone".to_string(),
+        public_key: STANDARD.encode(key.public_key().as_ref()),
+    };
+    let device = devices.register(user_id, request()).await.unwrap();
+    assert!(!device.trusted);
+    assert!(matches!(devices.register(user_id, request()).await, Err(AppError::Conflict(_))));
+
+    // Unsigned calls are allowed but unbound while binding is optional
+    let body = br#"{"amount":100}"#;
+    assert_eq!(devices.assess(&claims, None, call(body)).await.unwrap(), Some(DeviceRisk::Unbound));
+
+    let timestamp_ms = now.timestamp_millis();
+    let risk = devices.assess(&claims, sign(&key, device.id, timestamp_ms, body), call(body)).await.unwrap();
+    assert_eq!(risk, Some(DeviceRisk::New));
+
+    // Replayed, tampered and stale assertions are refused
+    let replayed = devices.assess(&claims, sign(&key, device.id, timestamp_ms, body), call(body)).await;
+    assert!(matches!(replayed, Err(AppError::Authentication(_))));
+    let tampered = devices
+        .assess(&claims, sign(&key, device.id, timestamp_ms + 1, body), call(br#"{"amount":900}"#))
+        .await;
+    assert!(matches!(tampered, Err(AppError::Authentication(_))));
+    let stale = devices
+        .assess(&claims, sign(&key, device.id, timestamp_ms - 3_600_000, body), call(body))
+        .await;
+    assert!(matches!(stale, Err(AppError::Authentication(_))));
+
+    assert_eq!(devices.list(user_id).await.unwrap().len(), 1);
+    devices.revoke(user_id, device.id).await.unwrap();
+    assert!(devices.list(user_id).await.unwrap().is_empty());
+    let revoked = devices.assess(&claims, sign(&key, device.id, timestamp_ms + 2, body), call(body)).await;
+    assert!(matches!(revoked, Err(AppError::Authentication(_))));
+    assert!(matches!(devices.revoke(user_id, device.id).await, Err(AppError::NotFound(_))));
+
+    let rejections = audit_logger
+        .recorded_events()
+        .into_iter()
+        .filter(|event| matches!(event.event_type, AuditEventType::DeviceAssertionRejected))
+        .count();
+    assert_eq!(rejections, 4);
+
+    database.cleanup().await;
+}
@@ -0,0 +1,121 @@
+use openbank::auth::scopes;
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::AppError;
+use openbank::core::events::{DomainEvent, DomainEventType};
+use openbank::events::model::EventHistoryQuery;
+use openbank::events::repository::EventRepository;
+use openbank::events::service::EventService;
+use openbank::webhooks::model::WebhookDeliveryStatus;
+use openbank::webhooks::repository::WebhookRepository;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use serde_json::json;
+use uuid::Uuid;
+
+fn page_query(cursor: Option<String>, limit: u32) -> EventHistoryQuery {
+    EventHistoryQuery {
+        events: None,
+        from: None,
+        to: None,
+        cursor,
+        limit: Some(limit),
+    }
+}
+
+#[tokio::test]
+async fn events_are_journaled_paged_and_redelivered() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let audit_logger = AuditLogger::in_memory();
+    let service = EventService::new(
+        EventRepository::new(pool.clone()),
+        WebhookRepository::new(pool.clone()),
+        audit_logger.clone(),
+    );
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let seeded = seeder.project(&[scopes::USER_DATA]).await;
+    let other_tenant_id = seeder.developer().await.organization_id;
+    let tenant_id = seeded.developer.organization_id;
+    sqlx::query("UPDATE projects SET webhook_url = 'https://partner.example.com/hooks' WHERE id = $1")
+        .bind(seeded.project.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let closed = DomainEvent::new(DomainEventType::AccountClosed, Some(tenant_id), vec![], json!({ "n": 1 }));
+    let updated = DomainEvent::new(DomainEventType::BalanceUpdated, Some(tenant_id), vec![], json!({ "n": 2 }));
+    let created = DomainEvent::new(DomainEventType::TransactionCreated, Some(tenant_id), vec![], json!({ "n": 3 }));
+    let elsewhere = DomainEvent::new(DomainEventType::AccountClosed, Some(other_tenant_id), vec![], json!({}));
+    for event in [&closed, &updated, &created, &elsewhere] {
+        service.record(event).await.unwrap();
+    }
+    // Recording an event again neither duplicates it nor delivers it twice
+    service.record(&closed).await.unwrap();
+
+    // Only events the project's scopes cover fan out to its webhook
+    let queued: Vec<(Option<Uuid>, String)> = sqlx::query_as(
+        "SELECT event_id, event_type FROM webhook_deliveries WHERE project_id = $1 ORDER BY created_at",
+    )
+    .bind(seeded.project.id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        queued,
+        vec![
+            (Some(closed.id), "account.closed".to_string()),
+            (Some(updated.id), "balance.updated".to_string()),
+        ]
+    );
+
+    // Pages follow the cursor in publish order and skip other tenants
+    let permitted = [DomainEventType::AccountClosed, DomainEventType::BalanceUpdated];
+    let first = service.list_events(tenant_id, &permitted, page_query(None, 1)).await.unwrap();
+    assert_eq!(first.data.len(), 1);
+    assert_eq!(first.data[0].id, closed.id);
+    assert!(first.has_more);
+    let second = service
+        .list_events(tenant_id, &permitted, page_query(first.next_cursor.clone(), 1))
+        .await
+        .unwrap();
+    assert_eq!(second.data[0].id, updated.id);
+    assert!(!second.has_more);
+    let empty = service
+        .list_events(tenant_id, &permitted, page_query(second.next_cursor.clone(), 1))
+        .await
+        .unwrap();
+    assert!(empty.data.is_empty());
+    assert_eq!(empty.next_cursor, second.next_cursor);
+    assert!(matches!(
+        service.list_events(tenant_id, &permitted, page_query(Some("abc".to_string()), 1)).await,
+        Err(AppError::Validation(_))
+    ));
+
+    // Redelivery queues a fresh delivery tied to the event and project
+    let actor_id = seeded.developer.id;
+    let delivery = service
+        .redeliver(closed.id, tenant_id, seeded.project.id, &permitted, actor_id)
+        .await
+        .unwrap();
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(delivery.event_id, Some(closed.id));
+    assert_eq!(delivery.project_id, Some(seeded.project.id));
+    assert_eq!(delivery.url, "https://partner.example.com/hooks");
+    assert!(audit_logger
+        .recorded_events()
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::EventRedelivered)));
+
+    // Events outside the token's scopes or tenant are not found
+    assert!(matches!(
+        service.redeliver(created.id, tenant_id, seeded.project.id, &permitted, actor_id).await,
+        Err(AppError::NotFound(_))
+    ));
+    assert!(matches!(
+        service.redeliver(elsewhere.id, tenant_id, seeded.project.id, &permitted, actor_id).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    database.cleanup().await;
+}
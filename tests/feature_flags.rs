@@ -0,0 +1,105 @@
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::AppError;
+use openbank::core::feature_flags::{Feature, FlagContext, FlagTargetType};
+use openbank::feature_flags::model::{CreateFeatureFlagRequest, UpdateFeatureFlagRequest};
+use openbank::feature_flags::service::feature_flag_service;
+use openbank_test_support::{test_config, Seeder, TestDatabase, TestStateBuilder};
+
+#[tokio::test]
+async fn flags_follow_targets_then_the_rollout() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let seeder = Seeder::new(database.pool(), &test_config());
+    let seeded = seeder.project(&[]).await;
+    let tenant_id = seeded.developer.organization_id;
+    let project_id = seeded.project.id;
+    let actor_id = seeded.developer.id;
+    let state = TestStateBuilder::new()
+        .postgres(database.pool())
+        .audit_logger(AuditLogger::in_memory())
+        .build()
+        .await;
+    let flags = feature_flag_service(&state);
+    let context = FlagContext::new(Some(tenant_id), Some(project_id));
+
+    // Features keep their default until their flag exists
+    assert!(state.feature_flags.is_enabled(Feature::ExternalPayeeDirectory, context).await);
+
+    let key = Feature::ExternalPayeeDirectory.key().to_string();
+    let created = flags
+        .create_flag(
+            CreateFeatureFlagRequest {
+                key: key.clone(),
+                description: Some("Cross-bank confirmation of payee".to_string()),
+                enabled: true,
+                rollout_percentage: 0,
+            },
+            actor_id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.created_by, Some(actor_id));
+    assert!(!state.feature_flags.is_enabled(Feature::ExternalPayeeDirectory, context).await);
+
+    let duplicate = flags
+        .create_flag(
+            CreateFeatureFlagRequest {
+                key: key.clone(),
+                description: None,
+                enabled: false,
+                rollout_percentage: 0,
+            },
+            actor_id,
+        )
+        .await;
+    assert!(matches!(duplicate, Err(AppError::Conflict(_))));
+
+    // A tenant target turns it on for every project in the tenant
+    let targeted = flags
+        .set_target(&key, FlagTargetType::Tenant, tenant_id, true, actor_id)
+        .await
+        .unwrap();
+    assert_eq!(targeted.targets.len(), 1);
+    assert!(state.feature_flags.is_enabled(Feature::ExternalPayeeDirectory, context).await);
+
+    // ...unless the project is targeted off
+    flags
+        .set_target(&key, FlagTargetType::Project, project_id, false, actor_id)
+        .await
+        .unwrap();
+    assert!(!state.feature_flags.is_enabled(Feature::ExternalPayeeDirectory, context).await);
+    assert!(flags.evaluate(&key, FlagContext::tenant(tenant_id)).await.unwrap().enabled);
+
+    flags
+        .remove_target(&key, FlagTargetType::Project, project_id, actor_id)
+        .await
+        .unwrap();
+    flags
+        .remove_target(&key, FlagTargetType::Tenant, tenant_id, actor_id)
+        .await
+        .unwrap();
+    flags
+        .update_flag(
+            &key,
+            UpdateFeatureFlagRequest {
+                description: None,
+                enabled: None,
+                rollout_percentage: Some(100),
+            },
+            actor_id,
+        )
+        .await
+        .unwrap();
+    assert!(state.feature_flags.is_enabled(Feature::ExternalPayeeDirectory, context).await);
+
+    let missing = flags
+        .set_target(&key, FlagTargetType::Project, uuid::Uuid::new_v4(), true, actor_id)
+        .await;
+    assert!(matches!(missing, Err(AppError::NotFound(_))));
+
+    flags.delete_flag(&key, actor_id).await.unwrap();
+    assert!(matches!(flags.get_flag(&key).await, Err(AppError::NotFound(_))));
+
+    database.cleanup().await;
+}
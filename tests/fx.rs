@@ -0,0 +1,337 @@
+use axum::extract::{Path, State};
+use chrono::{Duration, Utc};
+use openbank::account_controls::{repository::AccountControlRepository, service::AccountFreezeGuard};
+use openbank::auth::{middleware::JwtToken, model::JwtClaims};
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::AppError;
+use openbank::core::extractors::ApiJson;
+use openbank::core::versioning::ApiVersion;
+use openbank::fees::{repository::FeeRepository, service::FeeEngine};
+use openbank::fx::model::{CreateFxQuoteRequest, FxQuoteResponse};
+use openbank::fx::rates::StaticRateSource;
+use openbank::fx::repository::FxRepository;
+use openbank::fx::service::FxQuoteService;
+use openbank::goals::{repository::GoalRepository, service::GoalBalanceGuard};
+use openbank::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use openbank::metadata_schemas::{repository::MetadataSchemaRepository, service::MetadataSchemaGuard};
+use openbank::payments::controller::{confirm_payment_intent, create_payment_intent};
+use openbank::payments::model::{ConfirmPaymentIntentRequest, CreatePaymentIntentRequest, PaymentMethod};
+use openbank::payments::repository::PaymentRepository;
+use openbank::transactions::model::TransferRequest;
+use openbank::transactions::repository::TransactionRepository;
+use openbank::transactions::service::TransactionService;
+use openbank_test_support::{test_config, SeededProject, Seeder, TestDatabase, TestStateBuilder};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+
+async fn available_balance(pool: &PgPool, account_id: Uuid) -> i64 {
+    sqlx::query_scalar("SELECT available_balance FROM balances WHERE account_id = $1")
+        .bind(account_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn expire(pool: &PgPool, quote_id: Uuid) {
+    sqlx::query("UPDATE fx_quotes SET expires_at = NOW() - INTERVAL '1 second' WHERE id = $1")
+        .bind(quote_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+fn quote_service(pool: &PgPool) -> FxQuoteService {
+    FxQuoteService::new(
+        FxRepository::new(pool.clone()),
+        Arc::new(StaticRateSource::parse("EUR/USD=1.25").unwrap()),
+        Duration::seconds(30),
+    )
+}
+
+fn eur_to_usd(amount: i64) -> CreateFxQuoteRequest {
+    CreateFxQuoteRequest {
+        source_currency: "eur".to_string(),
+        target_currency: "USD".to_string(),
+        source_amount: amount,
+    }
+}
+
+fn transfer(from: Uuid, to: Uuid, amount: i64, quote_id: Uuid) -> TransferRequest {
+    TransferRequest {
+        from_account_id: from,
+        to_account_id: to,
+        amount,
+        currency: "EUR".to_string(),
+        description: None,
+        quote_id: Some(quote_id),
+        requote: false,
+    }
+}
+
+fn project_claims(project: &SeededProject) -> JwtClaims {
+    let now = Utc::now();
+    JwtClaims {
+        iss: "openbank-auth".to_string(),
+        aud: "openbank-api".to_string(),
+        sub: project.project.id.to_string(),
+        exp: (now + Duration::hours(1)).timestamp(),
+        iat: now.timestamp(),
+        jti: Uuid::new_v4().to_string(),
+        developer_id: project.developer.id,
+        project_id: project.project.id,
+        tenant_id: project.project.organization_id,
+        scopes: Vec::new(),
+        user_id: None,
+        auth_time: None,
+        acr: None,
+    }
+}
+
+#[tokio::test]
+async fn quotes_convert_one_transfer_at_their_locked_rate() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let config = test_config();
+    let tenant_id = Seeder::new(pool.clone(), &config).developer().await.organization_id;
+    let euros = seeder.account(Some(tenant_id), "EUR", 100_000).await.id;
+    let dollars = seeder.account(Some(tenant_id), "USD", 0).await.id;
+    let quotes = quote_service(&pool);
+    let transactions = TransactionService::new(
+        TransactionRepository::new(pool.clone()),
+        AccountFreezeGuard::new(AccountControlRepository::new(pool.clone()), false),
+        KycPolicyService::new(
+            KycRepository::new(pool.clone()),
+            KycLimits::from_config(&config),
+            AuditLogger::in_memory(),
+        ),
+        GoalBalanceGuard::new(GoalRepository::new(pool.clone())),
+        FeeEngine::new(FeeRepository::new(pool.clone())),
+        MetadataSchemaGuard::new(MetadataSchemaRepository::new(pool.clone())),
+    );
+
+    let quote = quotes.create_quote(tenant_id, None, eur_to_usd(10_000)).await.unwrap();
+    assert_eq!(quote.source_currency, "EUR");
+    assert_eq!(quote.target_amount, 12_500);
+    assert_eq!(FxQuoteResponse::from(quote.clone()).rate, "1.25");
+
+    // The preview prices the conversion without using the quote up
+    let preview = transactions
+        .preview_transfer(transfer(euros, dollars, 10_000, quote.id), tenant_id, None, Some(&quote))
+        .await
+        .unwrap();
+    assert!(preview.allowed, "unexpected issues: {:?}", preview.issues);
+    assert_eq!(preview.total_credit, 12_500);
+    assert_eq!(preview.destination.available_balance_after, 12_500);
+
+    // A quote only prices its own amount
+    let other_amount = quotes.resolve(quote.id, tenant_id, "EUR", 9_999, false).await;
+    assert!(matches!(other_amount, Err(AppError::Validation(_))));
+
+    let resolved = quotes.resolve(quote.id, tenant_id, "EUR", 10_000, false).await.unwrap();
+    assert!(resolved.used_at.is_none());
+    let transferred = transactions
+        .transfer_funds(transfer(euros, dollars, 10_000, quote.id), tenant_id, None, Some(&resolved))
+        .await
+        .unwrap();
+    let conversion = transferred.conversion.unwrap();
+    assert_eq!(conversion.quote_id, quote.id);
+    assert_eq!((conversion.target_currency.as_str(), conversion.target_amount), ("USD", 12_500));
+    // The source is debited in euros and the destination credited the converted dollars
+    assert_eq!(available_balance(&pool, euros).await, 90_000);
+    assert_eq!(available_balance(&pool, dollars).await, 12_500);
+    assert!(quotes.get(quote.id, tenant_id).await.unwrap().used_at.is_some());
+
+    let again = quotes.resolve(quote.id, tenant_id, "EUR", 10_000, false).await;
+    assert!(matches!(again, Err(AppError::Conflict(_))));
+    // nor does a copy checked before it was used convert a second transfer
+    let twice = transactions
+        .transfer_funds(transfer(euros, dollars, 10_000, quote.id), tenant_id, None, Some(&resolved))
+        .await;
+    assert!(matches!(twice, Err(AppError::Conflict(_))));
+    assert_eq!(available_balance(&pool, euros).await, 90_000);
+    assert_eq!(available_balance(&pool, dollars).await, 12_500);
+
+    // The destination must hold the currency converted into, and a refused
+    // transfer leaves the quote unused
+    let quote = quotes.create_quote(tenant_id, None, eur_to_usd(10_000)).await.unwrap();
+    let resolved = quotes.resolve(quote.id, tenant_id, "EUR", 10_000, false).await.unwrap();
+    let other_euros = seeder.account(Some(tenant_id), "EUR", 0).await.id;
+    let refused = transactions
+        .transfer_funds(transfer(euros, other_euros, 10_000, quote.id), tenant_id, None, Some(&resolved))
+        .await;
+    assert!(matches!(refused, Err(AppError::Validation(_))));
+    assert!(quotes.get(quote.id, tenant_id).await.unwrap().used_at.is_none());
+
+    // Expired quotes are refused unless priced again
+    let quote = quotes.create_quote(tenant_id, None, eur_to_usd(10_000)).await.unwrap();
+    expire(&pool, quote.id).await;
+    let quote = quotes.get(quote.id, tenant_id).await.unwrap();
+    let preview = transactions
+        .preview_transfer(transfer(euros, dollars, 10_000, quote.id), tenant_id, None, Some(&quote))
+        .await
+        .unwrap();
+    assert!(preview.issues.iter().any(|issue| issue.contains("expired")));
+    let expired = quotes.resolve(quote.id, tenant_id, "EUR", 10_000, false).await;
+    assert!(matches!(expired, Err(AppError::Conflict(_))));
+
+    let requoted = quotes.resolve(quote.id, tenant_id, "EUR", 10_000, true).await.unwrap();
+    assert_ne!(requoted.id, quote.id);
+    assert_eq!(requoted.requoted_from, Some(quote.id));
+    assert_eq!(requoted.target_amount, 12_500);
+    assert!(requoted.expires_at > Utc::now());
+    transactions
+        .transfer_funds(transfer(euros, dollars, 10_000, requoted.id), tenant_id, None, Some(&requoted))
+        .await
+        .unwrap();
+    assert_eq!(available_balance(&pool, dollars).await, 25_000);
+
+    // Quotes of another tenant are not found
+    let other_tenant_id = Seeder::new(pool.clone(), &config).developer().await.organization_id;
+    let stranger = quotes.resolve(requoted.id, other_tenant_id, "EUR", 10_000, false).await;
+    assert!(matches!(stranger, Err(AppError::NotFound(_))));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn payment_intents_expire_with_their_quote() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let state = TestStateBuilder::new()
+        .config(|config| {
+            config.fx_rates_provider = "static".to_string();
+            config.fx_rates = "EUR/USD=1.25".to_string();
+        })
+        .postgres(pool.clone())
+        .build()
+        .await;
+    let project = seeder.project(&[]).await;
+    let claims = project_claims(&project);
+    let tenant_id = claims.tenant_id;
+    let payer = seeder.account(Some(tenant_id), "EUR", 100_000).await.id;
+    let payee = seeder.account(Some(tenant_id), "USD", 0).await.id;
+    let quote = quote_service(&pool)
+        .create_quote(tenant_id, Some(claims.project_id), eur_to_usd(10_000))
+        .await
+        .unwrap();
+
+    let request = |amount| CreatePaymentIntentRequest {
+        from_account_id: payer,
+        to_account_id: Some(payee),
+        to_virtual_account_id: None,
+        amount,
+        currency: "EUR".to_string(),
+        payment_method: PaymentMethod::BankTransfer,
+        description: None,
+        recipient_info: None,
+        metadata: None,
+        quote_id: Some(quote.id),
+        requote: false,
+        execute_at: None,
+        timezone: None,
+        force_override: false,
+    };
+    let create = |amount| {
+        create_payment_intent(State(state.clone()), JwtToken(claims.clone()), ApiVersion::V1, ApiJson(request(amount)))
+    };
+
+    let mismatched = create(5_000).await;
+    assert!(matches!(mismatched, Err(AppError::Validation(_))));
+
+    let (_, response) = create(10_000).await.unwrap();
+    let intent = response.0.data.unwrap().1;
+    assert_eq!(intent.fx_quote_id, Some(quote.id));
+    assert!(intent.expires_at <= quote.expires_at);
+
+    let reused = create(10_000).await;
+    assert!(matches!(reused, Err(AppError::Conflict(_))));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn payment_intents_credit_the_converted_amount() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let state = TestStateBuilder::new()
+        .config(|config| {
+            config.fx_rates_provider = "static".to_string();
+            config.fx_rates = "EUR/USD=1.25".to_string();
+            config.payment_cutoff_bank_transfer = None;
+        })
+        .postgres(pool.clone())
+        .build()
+        .await;
+    let project = seeder.project(&[]).await;
+    let claims = project_claims(&project);
+    let tenant_id = claims.tenant_id;
+    let payer = seeder.account(Some(tenant_id), "EUR", 100_000).await.id;
+    let dollars = seeder.account(Some(tenant_id), "USD", 0).await.id;
+    let euros = seeder.account(Some(tenant_id), "EUR", 0).await.id;
+    let quote = quote_service(&pool)
+        .create_quote(tenant_id, Some(claims.project_id), eur_to_usd(10_000))
+        .await
+        .unwrap();
+
+    let request = |payee| CreatePaymentIntentRequest {
+        from_account_id: payer,
+        to_account_id: Some(payee),
+        to_virtual_account_id: None,
+        amount: 10_000,
+        currency: "EUR".to_string(),
+        payment_method: PaymentMethod::BankTransfer,
+        description: None,
+        recipient_info: None,
+        metadata: None,
+        quote_id: Some(quote.id),
+        requote: false,
+        execute_at: None,
+        timezone: None,
+        force_override: false,
+    };
+    let create = |payee| {
+        create_payment_intent(State(state.clone()), JwtToken(claims.clone()), ApiVersion::V1, ApiJson(request(payee)))
+    };
+
+    // The payee must hold the currency the quote converts into; the quote
+    // stays usable
+    let mismatched = create(euros).await;
+    assert!(matches!(mismatched, Err(AppError::Validation(_))));
+
+    let (_, response) = create(dollars).await.unwrap();
+    let intent = response.0.data.unwrap().1;
+    assert_eq!(intent.target_amount, Some(12_500));
+    assert_eq!(intent.target_currency.as_deref(), Some("USD"));
+
+    let confirmation = ConfirmPaymentIntentRequest {
+        confirmation_token: intent.confirmation_token.clone().unwrap(),
+    };
+    let response = confirm_payment_intent(
+        State(state.clone()),
+        JwtToken(claims.clone()),
+        ApiVersion::V1,
+        Path(intent.id),
+        ApiJson(confirmation),
+    )
+    .await
+    .unwrap();
+    let payment_id = response.0.data.unwrap().1.payment_id.unwrap();
+    PaymentRepository::new(pool.clone()).settle(payment_id).await.unwrap().unwrap();
+
+    // The payer is debited euros, the payee credited the converted dollars
+    assert_eq!(available_balance(&pool, payer).await, 90_000);
+    assert_eq!(available_balance(&pool, dollars).await, 12_500);
+
+    database.cleanup().await;
+}
@@ -0,0 +1,75 @@
+use openbank::core::audit::AuditLogger;
+use openbank::general_ledger::model::{CreateGlAccountRequest, GlAccountType, PostingEvent, SetPostingRuleRequest};
+use openbank::general_ledger::repository::{post_journal, GeneralLedgerRepository, JournalRequest};
+use openbank::general_ledger::service::GeneralLedgerService;
+use openbank_test_support::TestDatabase;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn journals_follow_posting_rules_and_balance() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let service = GeneralLedgerService::new(GeneralLedgerRepository::new(database.pool()), AuditLogger::in_memory());
+    let admin = Uuid::new_v4();
+
+    // Route FX fees to their own income account
+    service
+        .create_account(
+            CreateGlAccountRequest {
+                code: "4010".to_string(),
+                name: "FX fee income".to_string(),
+                account_type: GlAccountType::Income,
+                description: None,
+            },
+            admin,
+        )
+        .await
+        .unwrap();
+    service
+        .set_rule(
+            SetPostingRuleRequest {
+                event: PostingEvent::FeeCharged,
+                fee_code: Some("fx".to_string()),
+                debit_account_code: "2000".to_string(),
+                credit_account_code: "4010".to_string(),
+            },
+            admin,
+        )
+        .await
+        .unwrap();
+
+    let mut tx = database.begin().await;
+    for (event, fee_code, amount) in [
+        (PostingEvent::FeeCharged, Some("transfer"), 150),
+        (PostingEvent::FeeCharged, Some("fx"), 75),
+        (PostingEvent::InterestCapitalized, None, 40),
+    ] {
+        post_journal(
+            &mut tx,
+            JournalRequest {
+                event,
+                fee_code,
+                amount,
+                currency: "USD",
+                source_id: Uuid::new_v4(),
+                description: "test".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+    tx.commit().await.unwrap();
+
+    let trial_balance = service.trial_balance(None, None).await.unwrap();
+    let balances: Vec<_> = trial_balance
+        .lines
+        .iter()
+        .map(|line| (line.code.as_str(), line.balance))
+        .collect();
+    assert_eq!(balances, vec![("2000", -185), ("4000", 150), ("4010", 75), ("5000", 40)]);
+    assert_eq!(trial_balance.total_debits, 265);
+    assert!(trial_balance.balanced);
+
+    database.cleanup().await;
+}
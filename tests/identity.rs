@@ -0,0 +1,318 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::{AppError, AppResult};
+use openbank::identity::address::{AddressCheck, AddressVerifier};
+use openbank::identity::model::{
+    AddressVerificationStatus, IdentityVerification, KycStep, PostalAddress, ScreeningCategory, ScreeningMatch,
+    ScreeningStatus, VerificationRequest, VerificationStatus,
+};
+use openbank::identity::repository::IdentityRepository;
+use openbank::identity::screening::{ScreeningOutcome, ScreeningProvider, ScreeningSettings, ScreeningSubject};
+use openbank::identity::service::{IdentityService, KycProfileService, ScreeningService};
+use openbank::kyc::repository::KycRepository;
+use openbank::reviews::model::{ReviewDecision, VerificationKind};
+use openbank::reviews::repository::ReviewRepository;
+use openbank::reviews::service::ReviewQueue;
+use openbank::shared::traits::Repository;
+use openbank::shared::types::UserId;
+use openbank_test_support::{TestDatabase, TestStateBuilder};
+use uuid::Uuid;
+
+/// Verifies every address outside one postal code
+struct StubAddressVerifier;
+
+#[async_trait]
+impl AddressVerifier for StubAddressVerifier {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    async fn verify(
+        &self,
+        _user_id: UserId,
+        _first_name: &str,
+        _last_name: &str,
+        address: &PostalAddress,
+    ) -> AppResult<AddressCheck> {
+        let verified = address.postal_code != "00000";
+        Ok(AddressCheck {
+            verified,
+            reference: Some("CHK-1".to_string()),
+            reason: (!verified).then(|| "No record of the resident".to_string()),
+        })
+    }
+}
+
+fn address(postal_code: &str) -> PostalAddress {
+    PostalAddress {
+        line1: "1 Main Street".to_string(),
+        line2: None,
+        city: "Springfield".to_string(),
+        region: Some("IL".to_string()),
+        postal_code: postal_code.to_string(),
+        country: "us".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn addresses_are_verified_and_counted_towards_the_kyc_status() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let state = TestStateBuilder::new().postgres(pool.clone()).build().await;
+    let tenant_id: Uuid =
+        sqlx::query_scalar("INSERT INTO organizations (name) VALUES ('Acme') RETURNING id")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name, tenant_id, is_verified)
+         VALUES ($1, 'x', 'Test', 'User', $2, TRUE) RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .bind(tenant_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let identity = IdentityRepository::new(pool.clone(), state.user_cipher.clone());
+    let now = Utc::now();
+    identity
+        .create(IdentityVerification {
+            id: Uuid::new_v4(),
+            user_id,
+            verification_type: "passport".to_string(),
+            status: VerificationStatus::Completed,
+            document_type: Some("passport".to_string()),
+            document_number: Some("P1234567".to_string()),
+            verification_data: None,
+            provider: None,
+            provider_reference: None,
+            completed_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        })
+        .await
+        .unwrap();
+
+    let service = KycProfileService::new(
+        IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+        KycRepository::new(pool.clone()),
+        Arc::new(StubAddressVerifier),
+        AuditLogger::in_memory(),
+    );
+
+    let status = service.kyc_status(user_id, tenant_id, None).await.unwrap();
+    assert_eq!(
+        status.missing_steps,
+        vec![
+            KycStep::PhoneVerified,
+            KycStep::FaceEnrolled,
+            KycStep::AddressVerified,
+            KycStep::IncomeVerified
+        ]
+    );
+
+    // A failed check is recorded, and a new address starts over
+    service
+        .set_address(user_id, tenant_id, None, user_id, address("00000"))
+        .await
+        .unwrap();
+    let failed = service
+        .verify_address(user_id, tenant_id, None, user_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        failed.verification_status,
+        AddressVerificationStatus::Failed
+    );
+    let changed = service
+        .set_address(user_id, tenant_id, None, user_id, address("62701"))
+        .await
+        .unwrap();
+    assert_eq!(
+        changed.verification_status,
+        AddressVerificationStatus::Unverified
+    );
+    assert!(changed.failure_reason.is_none());
+
+    let verified = service
+        .verify_address(user_id, tenant_id, Some(user_id), user_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        verified.verification_status,
+        AddressVerificationStatus::Verified
+    );
+    assert_eq!(verified.address.unwrap().country, "US");
+    let stored: serde_json::Value =
+        sqlx::query_scalar("SELECT address FROM user_addresses WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(stored.as_str().unwrap().starts_with("enc:u1:"));
+
+    // Resubmitting the same address keeps its verification
+    let same = service
+        .set_address(user_id, tenant_id, None, user_id, address("62701"))
+        .await
+        .unwrap();
+    assert_eq!(
+        same.verification_status,
+        AddressVerificationStatus::Verified
+    );
+
+    let status = service.kyc_status(user_id, tenant_id, None).await.unwrap();
+    assert!(!status.missing_steps.contains(&KycStep::AddressVerified));
+
+    // Other tenants and other users' tokens see nothing
+    let other_tenant = service.kyc_status(user_id, Uuid::new_v4(), None).await;
+    assert!(matches!(other_tenant, Err(AppError::NotFound(_))));
+    let other_user = service
+        .get_address(user_id, tenant_id, Some(Uuid::new_v4()))
+        .await;
+    assert!(matches!(other_user, Err(AppError::NotFound(_))));
+
+    database.cleanup().await;
+}
+
+/// Finds one PEP entry for everybody
+struct StubScreeningProvider;
+
+#[async_trait]
+impl ScreeningProvider for StubScreeningProvider {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    async fn screen(
+        &self,
+        subject: &ScreeningSubject,
+        _categories: &[ScreeningCategory],
+    ) -> AppResult<ScreeningOutcome> {
+        Ok(ScreeningOutcome {
+            reference: Some("SCR-1".to_string()),
+            matches: vec![
+                ScreeningMatch {
+                    category: ScreeningCategory::Pep,
+                    name: format!("{} {}", subject.first_name, subject.last_name),
+                    score: 0.9,
+                    source: Some("National PEP list".to_string()),
+                    details: Some("Member of parliament".to_string()),
+                },
+                ScreeningMatch {
+                    category: ScreeningCategory::Pep,
+                    name: "Someone Else".to_string(),
+                    score: 0.3,
+                    source: None,
+                    details: None,
+                },
+            ],
+        })
+    }
+}
+
+#[tokio::test]
+async fn screening_matches_are_reviewed_once_and_dismissed_on_approval() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let state = TestStateBuilder::new().postgres(pool.clone()).build().await;
+    let audit_logger = AuditLogger::in_memory();
+    let reviews = ReviewRepository::new(pool.clone());
+    let screening = ScreeningService::new(
+        IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+        Arc::new(StubScreeningProvider),
+        ScreeningSettings {
+            match_threshold: 0.85,
+            adverse_media: true,
+            rescreen_after: Duration::zero(),
+            rescreen_batch_size: 100,
+        },
+        ReviewQueue::new(reviews.clone(), audit_logger.clone()),
+        audit_logger.clone(),
+    );
+    let identity = IdentityService::new(IdentityRepository::new(pool.clone(), state.user_cipher.clone()))
+        .with_screening(screening);
+
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name)
+         VALUES ($1, 'x', 'Jane', 'Doe') RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    // Screening runs as the verification starts, keeping the strong match
+    let verification = identity
+        .initiate_verification(
+            user_id,
+            VerificationRequest {
+                verification_type: "passport".to_string(),
+                document_type: "passport".to_string(),
+                document_number: "P1234567".to_string(),
+                additional_data: Some(serde_json::json!({ "date_of_birth": "1970-01-01" })),
+            },
+        )
+        .await
+        .unwrap();
+    let repository = IdentityRepository::new(pool.clone(), state.user_cipher.clone());
+    let screened = repository.find_screening(verification.id).await.unwrap().unwrap();
+    assert_eq!(screened.status, Some(ScreeningStatus::PotentialMatch));
+    assert_eq!(screened.matches.len(), 1);
+    assert_eq!(screened.risk_score, Some(54));
+    assert_eq!(screened.reference.as_deref(), Some("SCR-1"));
+    let review = reviews
+        .find_open(VerificationKind::Identity, verification.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(review.flagged_by.is_none());
+
+    // Approving the review completes the verification and dismisses the match
+    let reviewer = Uuid::new_v4();
+    reviews.claim(review.id, reviewer).await.unwrap().unwrap();
+    reviews
+        .decide(review.id, reviewer, ReviewDecision::Approve, "Known PEP, enhanced due diligence done")
+        .await
+        .unwrap()
+        .unwrap();
+    let dismissed = repository.find_screening(verification.id).await.unwrap().unwrap();
+    assert_eq!(dismissed.status, Some(ScreeningStatus::Dismissed));
+
+    // Re-screening finds the same match and leaves it dismissed
+    let screening = ScreeningService::new(
+        IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+        Arc::new(StubScreeningProvider),
+        ScreeningSettings {
+            match_threshold: 0.85,
+            adverse_media: true,
+            rescreen_after: Duration::zero(),
+            rescreen_batch_size: 100,
+        },
+        ReviewQueue::new(reviews.clone(), audit_logger.clone()),
+        audit_logger.clone(),
+    );
+    assert!(screening.rescreen_due().await.unwrap() >= 1);
+    let rescreened = repository.find_screening(verification.id).await.unwrap().unwrap();
+    assert_eq!(rescreened.status, Some(ScreeningStatus::Dismissed));
+    assert!(reviews
+        .find_open(VerificationKind::Identity, verification.id)
+        .await
+        .unwrap()
+        .is_none());
+    let found = audit_logger
+        .recorded_events()
+        .iter()
+        .filter(|event| matches!(event.event_type, AuditEventType::ScreeningMatchFound))
+        .count();
+    assert_eq!(found, 1);
+
+    database.cleanup().await;
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use openbank::core::audit::AuditLogger;
+use openbank::core::mailer::LogMailer;
+use openbank::ledger::model::{DiscrepancyType, IntegrityStatus};
+use openbank::ledger::repository::LedgerRepository;
+use openbank::ledger::service::LedgerService;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+
+async fn post(pool: &PgPool, account_id: Uuid, before: i64, after: i64, amount: i64, transaction_id: Uuid) {
+    sqlx::query(
+        "INSERT INTO balance_history (account_id, balance_before, balance_after, amount_changed, transaction_id)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(account_id)
+    .bind(before)
+    .bind(after)
+    .bind(amount)
+    .bind(transaction_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn transfer(pool: &PgPool, from: Uuid, to: Uuid, amount: i64) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO transactions (from_account_id, to_account_id, amount, transaction_type, status, reference)
+         VALUES ($1, $2, $3, 'transfer', 'completed', $4) RETURNING id",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(amount)
+    .bind(format!("TXN_{}", Uuid::new_v4()))
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn integrity_check_reports_broken_invariants() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let service = LedgerService::new(
+        LedgerRepository::new(pool.clone()),
+        AuditLogger::in_memory(),
+        Arc::new(LogMailer),
+        None,
+    );
+
+    // A balanced transfer
+    let payer = seeder.account(None, "USD", -500).await.id;
+    let payee = seeder.account(None, "USD", 500).await.id;
+    let balanced = transfer(&pool, payer, payee, 500).await;
+    post(&pool, payer, 0, -500, -500, balanced).await;
+    post(&pool, payee, 0, 500, 500, balanced).await;
+
+    let clean = service.run_integrity_check().await.unwrap();
+    assert_eq!(clean.run.status, IntegrityStatus::Passed);
+    assert_eq!(clean.run.accounts_checked, 2);
+    assert!(clean.discrepancies.is_empty());
+
+    // Only the payer leg is posted, with wrong arithmetic, and the payee
+    // balance is left untouched
+    let unbalanced = transfer(&pool, payer, payee, 200).await;
+    post(&pool, payer, -500, -750, -200, unbalanced).await;
+    sqlx::query("UPDATE balances SET ledger_balance = -700 WHERE account_id = $1")
+        .bind(payer)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let failed = service.run_integrity_check().await.unwrap();
+    assert_eq!(failed.run.status, IntegrityStatus::Failed);
+    let mut found: Vec<_> = failed
+        .discrepancies
+        .iter()
+        .map(|d| (d.discrepancy_type, d.expected, d.actual))
+        .collect();
+    found.sort_by_key(|(kind, _, _)| *kind as u8);
+    assert_eq!(
+        found,
+        vec![
+            (DiscrepancyType::PostingArithmetic, -700, -750),
+            (DiscrepancyType::UnbalancedTransaction, 0, -200),
+        ]
+    );
+
+    let stored = service.get_run(failed.run.id).await.unwrap();
+    assert_eq!(stored.discrepancies.len(), 2);
+
+    database.cleanup().await;
+}
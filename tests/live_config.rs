@@ -0,0 +1,51 @@
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::AppError;
+use openbank::core::live_config::reload_config;
+use openbank_test_support::TestStateBuilder;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn reloads_apply_tunables_and_hold_back_the_rest() {
+    let config_file = std::env::temp_dir().join(format!("openbank-{}.env", Uuid::new_v4()));
+    std::fs::write(
+        &config_file,
+        "RATE_LIMIT_BURST_SIZE=7\nMAX_FAILED_ATTEMPTS=2\nPORT=9999\nJWT_SECRET=from-the-file\n",
+    )
+    .unwrap();
+    let state = TestStateBuilder::new()
+        .config(|config| config.config_file = config_file.to_string_lossy().into_owned())
+        .audit_logger(AuditLogger::in_memory())
+        .build()
+        .await;
+    let jwt_secret = state.config.jwt_secret.clone();
+
+    let reload = reload_config(&state, Some(Uuid::new_v4())).await.unwrap();
+    assert!(reload.applied.contains(&"rate_limit_burst_size".to_string()));
+    assert!(reload.applied.contains(&"max_failed_attempts".to_string()));
+    assert!(reload.pending_restart.contains(&"port".to_string()));
+    assert!(!reload.pending_restart.contains(&"jwt_secret".to_string()));
+
+    assert_eq!(state.rate_limiter.config().burst_size, 7);
+    assert_eq!(state.security_service.config().max_failed_attempts, 2);
+    let current = state.live_config.current();
+    assert_eq!(current.port, state.config.port, "the port waits for a restart");
+    assert_eq!(current.jwt_secret, jwt_secret, "secrets are not reloaded");
+
+    let view = state.live_config.view();
+    assert_eq!(view.reload_count, 1);
+    assert_eq!(view.settings["jwt_secret"], "[REDACTED]");
+    assert_eq!(view.settings["rate_limit_burst_size"], 7);
+
+    assert!(state
+        .audit_logger
+        .recorded_events()
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::ConfigurationChanged)));
+
+    // A missing file leaves the running settings alone
+    std::fs::remove_file(&config_file).unwrap();
+    let failed = reload_config(&state, None).await;
+    assert!(matches!(failed, Err(AppError::BadRequest(_))));
+    assert_eq!(state.rate_limiter.config().burst_size, 7);
+    assert!(state.live_config.view().last_reload_error.is_some());
+}
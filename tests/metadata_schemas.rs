@@ -0,0 +1,96 @@
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::AppError;
+use openbank::metadata_schemas::model::{MetadataTarget, SetMetadataSchemaRequest};
+use openbank::metadata_schemas::repository::MetadataSchemaRepository;
+use openbank::metadata_schemas::service::{MetadataSchemaGuard, MetadataSchemaService};
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use serde_json::json;
+
+#[tokio::test]
+async fn metadata_is_held_to_the_project_schema_for_its_target() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let config = test_config();
+    let seeded = Seeder::new(pool.clone(), &config).project(&["payments"]).await;
+    let project_id = seeded.project.id;
+    let developer_id = seeded.developer.id;
+
+    let audit_logger = AuditLogger::in_memory();
+    let schemas = MetadataSchemaService::new(MetadataSchemaRepository::new(pool.clone()), audit_logger.clone());
+    let guard = MetadataSchemaGuard::new(MetadataSchemaRepository::new(pool.clone()));
+
+    // Free-form until a schema is registered
+    guard
+        .ensure_valid(project_id, MetadataTarget::Payment, Some(&json!({"anything": 1})))
+        .await
+        .unwrap();
+
+    let invalid = schemas
+        .set_schema(
+            project_id,
+            MetadataTarget::Payment,
+            SetMetadataSchemaRequest { schema: json!({"type": 12}) },
+            developer_id,
+        )
+        .await;
+    assert!(matches!(invalid, Err(AppError::Validation(_))));
+
+    let request = SetMetadataSchemaRequest {
+        schema: json!({
+            "type": "object",
+            "required": ["order_id"],
+            "properties": {"order_id": {"type": "string"}}
+        }),
+    };
+    let first = schemas
+        .set_schema(project_id, MetadataTarget::Payment, request.clone(), developer_id)
+        .await
+        .unwrap();
+    let replaced = schemas
+        .set_schema(project_id, MetadataTarget::Payment, request, developer_id)
+        .await
+        .unwrap();
+    assert_eq!((first.version, replaced.version), (1, 2));
+    assert_eq!(replaced.id, first.id);
+
+    guard
+        .ensure_valid(project_id, MetadataTarget::Payment, Some(&json!({"order_id": "ORD-1"})))
+        .await
+        .unwrap();
+    let rejected = guard
+        .ensure_valid(project_id, MetadataTarget::Payment, Some(&json!({"order_id": 7})))
+        .await;
+    let Err(AppError::InvalidFields(errors)) = rejected else {
+        panic!("expected metadata field errors, got {:?}", rejected);
+    };
+    assert_eq!(errors.field_errors()["metadata"][0].params["path"], json!("/order_id"));
+    // Transactions have no schema of their own
+    guard
+        .ensure_valid(project_id, MetadataTarget::Transaction, Some(&json!({"order_id": 7})))
+        .await
+        .unwrap();
+
+    let validation = schemas.validate(project_id, MetadataTarget::Payment, None).await.unwrap();
+    assert!(!validation.valid);
+    assert_eq!(validation.schema_version, Some(2));
+    assert_eq!(validation.violations[0].keyword, "required");
+
+    schemas
+        .delete_schema(project_id, MetadataTarget::Payment, developer_id)
+        .await
+        .unwrap();
+    assert!(schemas.list_schemas(project_id).await.unwrap().is_empty());
+    let missing = schemas.delete_schema(project_id, MetadataTarget::Payment, developer_id).await;
+    assert!(matches!(missing, Err(AppError::NotFound(_))));
+
+    let changes = audit_logger
+        .recorded_events()
+        .iter()
+        .filter(|event| matches!(event.event_type, AuditEventType::MetadataSchemaChanged))
+        .count();
+    assert_eq!(changes, 3);
+
+    database.cleanup().await;
+}
@@ -0,0 +1,185 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use chrono::Utc;
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::events::{DomainEvent, DomainEventType, EventBus};
+use openbank::core::mailer::{EmailMessage, Mailer};
+use openbank::identity::repository::IdentityRepository;
+use openbank::notifications::model::{
+    CategoryPreferencesUpdate, NotificationCategory, NotificationFilter, NotificationKind,
+    UpdateNotificationPreferencesRequest,
+};
+use openbank::notifications::repository::NotificationRepository;
+use openbank::notifications::service::NotificationService;
+use openbank::payments::model::{PaymentMethod, PaymentResponse, PaymentStatus};
+use openbank_test_support::{test_config, SeededAccount, Seeder, TestDatabase, TestStateBuilder};
+use serde_json::json;
+use uuid::Uuid;
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, message: EmailMessage) -> AppResult<()> {
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+
+fn completed_payment(tenant_id: Uuid, from: Uuid, to: Uuid) -> DomainEvent {
+    let payment = PaymentResponse {
+        id: Uuid::new_v4(),
+        from_account_id: from,
+        to_account_id: Some(to),
+        amount: 12_550,
+        currency: "USD".to_string(),
+        payment_method: PaymentMethod::BankTransfer,
+        status: PaymentStatus::Completed,
+        reference: "INV-42".to_string(),
+        description: None,
+        fee_amount: 0,
+        fees: None,
+        execute_at: None,
+        execution_timezone: None,
+        executed_at: Some(Utc::now()),
+        execution_error: None,
+        expected_settlement_at: None,
+        settled_at: Some(Utc::now()),
+        duplicate_of: None,
+        to_virtual_account_id: None,
+        created_at: Utc::now(),
+    };
+    DomainEvent::new(
+        DomainEventType::PaymentStatusChanged,
+        Some(tenant_id),
+        vec![from, to],
+        json!(payment),
+    )
+}
+
+#[tokio::test]
+async fn payment_events_reach_the_feed_on_the_chosen_channels() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let state = TestStateBuilder::new().postgres(pool.clone()).build().await;
+    let tenant_id = seeder.developer().await.organization_id;
+    let SeededAccount { user_id: payer, id: payer_account } = seeder.account(Some(tenant_id), "USD", 0).await;
+    let SeededAccount { user_id: payee, id: payee_account } = seeder.account(Some(tenant_id), "USD", 0).await;
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let event_bus = EventBus::new(16);
+    let mut published = event_bus.subscribe();
+    let audit_logger = AuditLogger::in_memory();
+    let service = NotificationService::new(
+        NotificationRepository::new(pool.clone()),
+        IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+        mailer.clone(),
+        event_bus.clone(),
+        audit_logger.clone(),
+    );
+
+    // Handling the same payment twice notifies each party once
+    let event = completed_payment(tenant_id, payer_account, payee_account);
+    service.handle_event(&event).await.unwrap();
+    service.handle_event(&event).await.unwrap();
+
+    let feed_filter = |user_id| NotificationFilter {
+        user_id,
+        category: None,
+        unread_only: false,
+    };
+    let payer_feed = service.get_feed(feed_filter(payer), tenant_id, 1, 20).await.unwrap();
+    assert_eq!(payer_feed.total, 1);
+    assert_eq!(payer_feed.unread_count, 1);
+    assert_eq!(payer_feed.data[0].kind, NotificationKind::PaymentCompleted);
+    assert!(payer_feed.data[0].body.contains("125.50 USD"));
+    let payee_feed = service.get_feed(feed_filter(payee), tenant_id, 1, 20).await.unwrap();
+    assert_eq!(payee_feed.data[0].kind, NotificationKind::PaymentReceived);
+
+    // Payment notifications are not emailed by default, but reach the webhooks
+    assert!(mailer.sent.lock().unwrap().is_empty());
+    let webhook_event = published.try_recv().unwrap();
+    assert_eq!(webhook_event.event_type, DomainEventType::NotificationCreated);
+    assert_eq!(webhook_event.tenant_id, Some(tenant_id));
+
+    // Reading one notification leaves nothing unread
+    let read = service.mark_read(payer_feed.data[0].id, payer, tenant_id).await.unwrap();
+    assert!(read.read_at.is_some());
+    let unread = NotificationFilter {
+        unread_only: true,
+        ..feed_filter(payer)
+    };
+    assert_eq!(service.get_feed(unread, tenant_id, 1, 20).await.unwrap().total, 0);
+    assert!(matches!(
+        service.mark_read(payer_feed.data[0].id, payee, tenant_id).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    // Opting into email and out of the feed changes where the next one goes
+    let preferences = service
+        .update_preferences(
+            UpdateNotificationPreferencesRequest {
+                user_id: payer,
+                preferences: vec![CategoryPreferencesUpdate {
+                    category: NotificationCategory::Payments,
+                    email: Some(true),
+                    webhook: None,
+                    in_app: Some(false),
+                }],
+            },
+            tenant_id,
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+    let payments = &preferences.preferences[0];
+    assert_eq!(payments.category, NotificationCategory::Payments);
+    assert!(payments.channels.email && payments.channels.webhook && !payments.channels.in_app);
+    assert!(audit_logger
+        .recorded_events()
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::NotificationPreferencesUpdated)));
+
+    service
+        .handle_event(&completed_payment(tenant_id, payer_account, payee_account))
+        .await
+        .unwrap();
+    assert_eq!(service.get_feed(feed_filter(payer), tenant_id, 1, 20).await.unwrap().total, 1);
+    assert_eq!(service.get_feed(feed_filter(payee), tenant_id, 1, 20).await.unwrap().total, 2);
+    assert_eq!(mailer.sent.lock().unwrap().len(), 1);
+
+    // Security alerts always stay in the feed
+    let result = service
+        .update_preferences(
+            UpdateNotificationPreferencesRequest {
+                user_id: payer,
+                preferences: vec![CategoryPreferencesUpdate {
+                    category: NotificationCategory::Security,
+                    email: None,
+                    webhook: None,
+                    in_app: Some(false),
+                }],
+            },
+            tenant_id,
+            Uuid::new_v4(),
+        )
+        .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    // Another tenant cannot read the user's notifications
+    let other_tenant_id = seeder.developer().await.organization_id;
+    assert!(matches!(
+        service.get_feed(feed_filter(payer), other_tenant_id, 1, 20).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    database.cleanup().await;
+}
@@ -0,0 +1,255 @@
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use chrono::{Duration, Utc};
+use openbank::auth::model::JwtClaims;
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::AppError;
+use openbank::core::i18n::Locale;
+use openbank::core::ip_filter::IpRejection;
+use openbank::core::middleware::security_middleware;
+use openbank::core::versioning::{ApiVersion, VersionStatus};
+use openbank::organizations::members::organization_member_service;
+use openbank::organizations::model::{
+    SetProjectApiVersionPolicyRequest, SetProjectIpRulesRequest, SetProjectLocaleRequest,
+};
+use openbank::organizations::service::organization_service;
+use openbank_test_support::{test_config, SeededProject, Seeder, TestDatabase, TestStateBuilder};
+use std::net::SocketAddr;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn project_ip_rules_are_normalized_and_enforced() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let seeder = Seeder::new(database.pool(), &test_config());
+    let seeded = seeder.project(&[]).await;
+    let organization_id = seeded.developer.organization_id;
+    let project_id = seeded.project.id;
+    let state = TestStateBuilder::new()
+        .postgres(database.pool())
+        .audit_logger(AuditLogger::in_memory())
+        .build()
+        .await;
+    let members = organization_member_service(&state);
+
+    // Unrestricted until rules are set
+    let policy = organization_service(&state).ip_policy(project_id).await.unwrap();
+    assert!(policy.is_unrestricted());
+
+    let error = members
+        .set_project_ip_rules(
+            organization_id,
+            project_id,
+            SetProjectIpRulesRequest {
+                allow: vec!["10.0.0.0/33".to_string()],
+                deny: vec![],
+            },
+            seeded.developer.id,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::Validation(_)));
+
+    let rules = members
+        .set_project_ip_rules(
+            organization_id,
+            project_id,
+            SetProjectIpRulesRequest {
+                allow: vec!["203.0.113.0/24".to_string(), "2001:db8::1".to_string(), "203.0.113.0/24".to_string()],
+                deny: vec!["203.0.113.66".to_string()],
+            },
+            seeded.developer.id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(rules.allow, vec!["2001:db8::1/128", "203.0.113.0/24"]);
+    assert_eq!(rules.deny, vec!["203.0.113.66/32"]);
+    assert_eq!(rules.updated_by, Some(seeded.developer.id));
+
+    // Saving drops the cached policy, so the new rules apply at once
+    let policy = organization_service(&state).ip_policy(project_id).await.unwrap();
+    assert_eq!(policy.check(Some("203.0.113.10".parse().unwrap())), Ok(()));
+    assert!(matches!(policy.check(Some("203.0.113.66".parse().unwrap())), Err(IpRejection::Denied(_))));
+    assert_eq!(policy.check(Some("198.51.100.1".parse().unwrap())), Err(IpRejection::NotAllowed));
+
+    // Members of other organizations cannot see the project
+    let outsider = seeder.developer().await;
+    assert!(members
+        .get_project_ip_rules(organization_id, project_id, outsider.id)
+        .await
+        .is_err());
+
+    database.cleanup().await;
+}
+
+fn project_claims(project: &SeededProject) -> JwtClaims {
+    let now = Utc::now();
+    JwtClaims {
+        iss: "openbank-auth".to_string(),
+        aud: "openbank-api".to_string(),
+        sub: project.project.id.to_string(),
+        exp: (now + Duration::hours(1)).timestamp(),
+        iat: now.timestamp(),
+        jti: Uuid::new_v4().to_string(),
+        developer_id: project.developer.id,
+        project_id: project.project.id,
+        tenant_id: project.project.organization_id,
+        scopes: Vec::new(),
+        user_id: None,
+        auth_time: None,
+        acr: None,
+    }
+}
+
+#[tokio::test]
+async fn spoofed_forwarded_addresses_do_not_pass_project_ip_rules() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let seeded = Seeder::new(database.pool(), &test_config()).project(&[]).await;
+    let state = TestStateBuilder::new()
+        .config(|config| config.trusted_proxies = vec!["10.0.0.0/8".parse().unwrap()])
+        .postgres(database.pool())
+        .audit_logger(AuditLogger::in_memory())
+        .build()
+        .await;
+    organization_member_service(&state)
+        .set_project_ip_rules(
+            seeded.developer.organization_id,
+            seeded.project.id,
+            SetProjectIpRulesRequest {
+                allow: vec!["203.0.113.0/24".to_string()],
+                deny: vec![],
+            },
+            seeded.developer.id,
+        )
+        .await
+        .unwrap();
+
+    let app = Router::new()
+        .route("/ping", get(|| async { "pong" }))
+        .layer(axum::middleware::from_fn_with_state(state.clone(), security_middleware));
+    let claims = project_claims(&seeded);
+    let status = |peer: &str, forwarded_for: Option<&str>| {
+        let mut request = Request::get("/ping");
+        if let Some(forwarded_for) = forwarded_for {
+            request = request.header("x-forwarded-for", forwarded_for);
+        }
+        let mut request = request.body(Body::empty()).unwrap();
+        request.extensions_mut().insert(ConnectInfo(SocketAddr::new(peer.parse().unwrap(), 443)));
+        request.extensions_mut().insert(claims.clone());
+        let app = app.clone();
+        async move { app.oneshot(request).await.unwrap().status() }
+    };
+
+    // Callers reaching the API directly are judged by their own address,
+    // whatever they claim to forward for
+    assert_eq!(status("203.0.113.5", None).await, StatusCode::OK);
+    assert_eq!(status("198.51.100.7", None).await, StatusCode::FORBIDDEN);
+    assert_eq!(status("198.51.100.7", Some("203.0.113.5")).await, StatusCode::FORBIDDEN);
+
+    // Behind a trusted proxy, only the hop the proxy added counts
+    assert_eq!(status("10.0.0.1", Some("203.0.113.5")).await, StatusCode::OK);
+    assert_eq!(status("10.0.0.1", Some("203.0.113.5, 198.51.100.7")).await, StatusCode::FORBIDDEN);
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn project_default_locale_applies_once_changed() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let seeder = Seeder::new(database.pool(), &test_config());
+    let seeded = seeder.project(&[]).await;
+    let organization_id = seeded.developer.organization_id;
+    let project_id = seeded.project.id;
+    let state = TestStateBuilder::new()
+        .postgres(database.pool())
+        .audit_logger(AuditLogger::in_memory())
+        .build()
+        .await;
+    let members = organization_member_service(&state);
+    let organizations = organization_service(&state);
+
+    assert_eq!(organizations.default_locale(project_id).await.unwrap(), Some(Locale::En));
+
+    let locale = members
+        .set_project_locale(
+            organization_id,
+            project_id,
+            SetProjectLocaleRequest { default_locale: Locale::Fr },
+            seeded.developer.id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(locale.default_locale, Locale::Fr);
+    assert_eq!(locale.updated_by, Some(seeded.developer.id));
+
+    // Saving drops the cached locale
+    assert_eq!(organizations.default_locale(project_id).await.unwrap(), Some(Locale::Fr));
+
+    let outsider = seeder.developer().await;
+    assert!(members
+        .get_project_locale(organization_id, project_id, outsider.id)
+        .await
+        .is_err());
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn project_api_version_policy_retires_older_versions() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let seeder = Seeder::new(database.pool(), &test_config());
+    let seeded = seeder.project(&[]).await;
+    let organization_id = seeded.developer.organization_id;
+    let project_id = seeded.project.id;
+    let state = TestStateBuilder::new()
+        .postgres(database.pool())
+        .audit_logger(AuditLogger::in_memory())
+        .build()
+        .await;
+    let members = organization_member_service(&state);
+    let organizations = organization_service(&state);
+    let today = chrono::Utc::now().date_naive();
+
+    let rules = organizations.version_rules(project_id).await.unwrap().unwrap();
+    assert_eq!(rules.min_api_version, ApiVersion::V1);
+    assert_eq!(state.version_policy.status(ApiVersion::V1, Some(&rules), today), VersionStatus::Current);
+
+    let policy = members
+        .set_project_api_version_policy(
+            organization_id,
+            project_id,
+            SetProjectApiVersionPolicyRequest { min_api_version: ApiVersion::V2, api_sunset_on: None },
+            seeded.developer.id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(policy.min_api_version, ApiVersion::V2);
+    assert_eq!(policy.updated_by, Some(seeded.developer.id));
+
+    // Saving drops the cached rules, so v1 is refused at once
+    let rules = organizations.version_rules(project_id).await.unwrap().unwrap();
+    assert!(matches!(
+        state.version_policy.status(ApiVersion::V1, Some(&rules), today),
+        VersionStatus::Retired(_)
+    ));
+    assert_eq!(state.version_policy.status(ApiVersion::V2, Some(&rules), today), VersionStatus::Current);
+
+    let outsider = seeder.developer().await;
+    assert!(members
+        .get_project_api_version_policy(organization_id, project_id, outsider.id)
+        .await
+        .is_err());
+
+    database.cleanup().await;
+}
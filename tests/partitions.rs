@@ -0,0 +1,72 @@
+use chrono::{Datelike, Duration, Utc};
+use openbank::core::partitions::ensure_partitions;
+use openbank_test_support::TestDatabase;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn insert_transaction(pool: &PgPool, reference: &str, created_at: chrono::DateTime<Utc>) -> Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar(
+        "INSERT INTO transactions (amount, transaction_type, status, reference, created_at)
+         VALUES (100, 'deposit', 'completed', $1, $2) RETURNING id",
+    )
+    .bind(reference)
+    .bind(created_at)
+    .fetch_one(pool)
+    .await
+}
+
+async fn partition_of(pool: &PgPool, id: Uuid) -> String {
+    sqlx::query_scalar("SELECT tableoid::regclass::TEXT FROM transactions WHERE id = $1")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn maintenance_creates_future_partitions_and_moves_early_rows() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+
+    // The migration already prepared the months the default configuration asks for
+    assert_eq!(ensure_partitions(&pool, 3).await.unwrap(), 0);
+
+    let current = Utc::now();
+    let current_partition = format!("transactions_{:04}_{:02}", current.year(), current.month());
+    let id = insert_transaction(&pool, &format!("TXN_{}", Uuid::new_v4()), current).await.unwrap();
+    assert!(partition_of(&pool, id).await.ends_with(&current_partition));
+
+    // A row dated beyond the prepared months waits in the default partition...
+    let later = current + Duration::days(200);
+    let later_id = insert_transaction(&pool, &format!("TXN_{}", Uuid::new_v4()), later).await.unwrap();
+    assert!(partition_of(&pool, later_id).await.ends_with("transactions_default"));
+
+    // ...until its month is created
+    assert!(ensure_partitions(&pool, 8).await.unwrap() > 0);
+    let later_partition = format!("transactions_{:04}_{:02}", later.year(), later.month());
+    assert!(partition_of(&pool, later_id).await.ends_with(&later_partition));
+    assert_eq!(ensure_partitions(&pool, 8).await.unwrap(), 0);
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn references_stay_unique_across_partitions() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+
+    let reference = format!("TXN_{}", Uuid::new_v4());
+    insert_transaction(&pool, &reference, Utc::now()).await.unwrap();
+    let duplicate = insert_transaction(&pool, &reference, Utc::now() + Duration::days(45)).await;
+    let error = duplicate.unwrap_err();
+    assert_eq!(
+        error.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("23505")
+    );
+
+    database.cleanup().await;
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Duration, Utc};
+use openbank::core::events::{DomainEvent, DomainEventType};
+use openbank::payment_analytics::model::{PaymentAnalyticsQuery, INTERNAL_PROVIDER};
+use openbank::payment_analytics::repository::PaymentAnalyticsRepository;
+use openbank::payment_analytics::service::PaymentAnalyticsService;
+use openbank::payments::model::PaymentMethod;
+use openbank_test_support::TestDatabase;
+use uuid::Uuid;
+
+/// A card payment moving to `status` `after_seconds` after it was created
+fn status_changed(
+    id: Uuid,
+    created_at: DateTime<Utc>,
+    status: &str,
+    error: Option<&str>,
+    after_seconds: i64,
+) -> DomainEvent {
+    let mut event = DomainEvent::new(
+        DomainEventType::PaymentStatusChanged,
+        None,
+        Vec::new(),
+        serde_json::json!({
+            "id": id,
+            "payment_method": "Card",
+            "currency": "XTS",
+            "amount": 1000,
+            "status": status,
+            "execution_error": error,
+            "created_at": created_at,
+        }),
+    );
+    event.occurred_at = created_at + Duration::seconds(after_seconds);
+    event
+}
+
+#[tokio::test]
+async fn analytics_break_payments_down_by_outcome() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let service = PaymentAnalyticsService::new(PaymentAnalyticsRepository::new(database.pool()));
+    let created_at = Utc::now() - Duration::hours(1);
+
+    let (fast, slow, failed, pending) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    for event in [
+        status_changed(fast, created_at, "Pending", None, 0),
+        status_changed(fast, created_at, "Completed", None, 10),
+        status_changed(slow, created_at, "Completed", None, 30),
+        // A refund keeps the time the payment completed
+        status_changed(slow, created_at, "Refunded", None, 600),
+        status_changed(failed, created_at, "Failed", Some("Insufficient funds"), 5),
+        status_changed(pending, created_at, "Pending", None, 0),
+    ] {
+        assert!(service.record(&event).await.unwrap());
+    }
+
+    let analytics = service
+        .analytics(PaymentAnalyticsQuery {
+            currency: Some("xts".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(analytics.breakdown.len(), 1);
+    let group = &analytics.breakdown[0];
+    assert_eq!(group.provider, INTERNAL_PROVIDER);
+    assert_eq!(group.payment_method, PaymentMethod::Card);
+    assert_eq!((group.payment_count, group.volume), (4, 4000));
+    assert_eq!((group.succeeded, group.failed, group.in_progress), (2, 1, 1));
+    assert!((group.success_rate.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+    assert_eq!(group.average_latency_seconds, Some(20.0));
+    assert_eq!(group.errors.len(), 1);
+    assert_eq!((group.errors[0].error.as_str(), group.errors[0].count), ("Insufficient funds", 1));
+
+    let elsewhere = service
+        .analytics(PaymentAnalyticsQuery {
+            provider: Some("stripe".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(elsewhere.breakdown.is_empty());
+
+    database.cleanup().await;
+}
@@ -0,0 +1,724 @@
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, Request, StatusCode};
+use chrono::{Duration, NaiveDateTime, Utc};
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::crypto::{hex, hmac_sha256};
+use openbank::core::database::retry_transaction;
+use openbank::core::error::AppError;
+use openbank::auth::{middleware::JwtToken, model::JwtClaims};
+use openbank::core::extractors::ApiJson;
+use openbank::core::versioning::ApiVersion;
+use openbank::core::AppState;
+use openbank::payments::controller::{
+    cancel_payment_intent, confirm_payment_intent, create_payment_intent, payment_callback,
+};
+use openbank::payments::model::{
+    ApprovalDecision, ConfirmPaymentIntentRequest, CreatePaymentIntentRequest, CreatePaymentRequest, ExecuteAt, Payment,
+    PaymentCallbackOutcome, PaymentCallbackResponse, PaymentIntentResponse, PaymentIntentStatus, PaymentMethod,
+    PaymentStatus,
+};
+use openbank::payments::repository::PaymentRepository;
+use openbank::shared::traits::Repository;
+use openbank_test_support::{test_config, SeededProject, Seeder, TestDatabase, TestStateBuilder};
+use sqlx::PgPool;
+use tokio::task::JoinSet;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+
+async fn balances(pool: &PgPool, account_id: Uuid) -> (i64, i64) {
+    sqlx::query_as("SELECT available_balance, ledger_balance FROM balances WHERE account_id = $1")
+        .bind(account_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn pending_payment(repository: &PaymentRepository, from: Uuid, to: Uuid, amount: i64) -> Payment {
+    create_payment(repository, from, to, amount, PaymentStatus::Pending).await
+}
+
+async fn create_payment(
+    repository: &PaymentRepository,
+    from: Uuid,
+    to: Uuid,
+    amount: i64,
+    status: PaymentStatus,
+) -> Payment {
+    let now = Utc::now();
+    repository
+        .create(Payment {
+            id: Uuid::new_v4(),
+            from_account_id: from,
+            to_account_id: Some(to),
+            amount,
+            currency: "USD".to_string(),
+            payment_method: PaymentMethod::BankTransfer,
+            status,
+            reference: format!("PAY_{}", Uuid::new_v4()),
+            description: None,
+            recipient_info: None,
+            metadata: None,
+            external_reference: None,
+            project_id: None,
+            tenant_id: None,
+            fee_amount: 0,
+            fee_breakdown: None,
+            execute_at: None,
+            execution_timezone: None,
+            executed_at: None,
+            execution_error: None,
+            transaction_id: None,
+            expected_settlement_at: None,
+            settled_at: None,
+            created_by: Some(Uuid::new_v4()),
+            duplicate_of: None,
+            to_virtual_account_id: None,
+            created_at: now,
+            updated_at: now,
+            target_amount: None,
+            target_currency: None,
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn pending_payments_hold_available_balance_until_settled() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let repository = PaymentRepository::new(pool.clone());
+    let payer = seeder.account(None, "USD", 1_000).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+
+    // Posting holds the amount without moving either ledger balance
+    let payment = pending_payment(&repository, payer, payee, 300).await;
+    let settles_at = Utc::now() - Duration::minutes(1);
+    let posted = repository.post_pending(&payment, settles_at).await.unwrap().unwrap();
+    assert!(posted.transaction_id.is_some());
+    assert!(repository.post_pending(&payment, settles_at).await.unwrap().is_none());
+    assert_eq!(balances(&pool, payer).await, (700, 1_000));
+    assert_eq!(balances(&pool, payee).await, (0, 0));
+
+    let due = repository.find_due_settlement(Utc::now(), 10).await.unwrap();
+    assert_eq!(due.iter().map(|payment| payment.id).collect::<Vec<_>>(), vec![payment.id]);
+
+    // Settling moves the held amount between ledger balances, once
+    let settled = repository.settle(payment.id).await.unwrap().unwrap();
+    assert!(matches!(settled.status, PaymentStatus::Completed));
+    assert!(settled.settled_at.is_some());
+    assert!(repository.settle(payment.id).await.unwrap().is_none());
+    assert_eq!(balances(&pool, payer).await, (700, 700));
+    assert_eq!(balances(&pool, payee).await, (300, 300));
+
+    let postings: i64 = sqlx::query_scalar(
+        "SELECT SUM(amount_changed)::BIGINT FROM balance_history WHERE transaction_id = $1",
+    )
+    .bind(settled.transaction_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(postings, 0);
+
+    // Cancelling before settlement releases the hold
+    let payment = pending_payment(&repository, payer, payee, 200).await;
+    repository
+        .post_pending(&payment, Utc::now() + Duration::hours(24))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(balances(&pool, payer).await, (500, 700));
+    repository.cancel(payment.id).await.unwrap().unwrap();
+    assert_eq!(balances(&pool, payer).await, (700, 700));
+    assert!(repository.settle(payment.id).await.unwrap().is_none());
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn approval_decisions_are_recorded_once() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let repository = PaymentRepository::new(pool.clone());
+    let payer = seeder.account(None, "USD", 1_000_000).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+    let checker = Uuid::new_v4();
+
+    let payment = create_payment(&repository, payer, payee, 500_000, PaymentStatus::PendingApproval).await;
+    assert!(matches!(payment.status, PaymentStatus::PendingApproval));
+
+    let (approved, approval) = repository
+        .decide_approval(payment.id, ApprovalDecision::Approved, PaymentStatus::Pending, checker, Some("ok"))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(approved.status, PaymentStatus::Pending));
+    assert!(approved.executed_at.is_some());
+    assert_eq!(approval.decision, ApprovalDecision::Approved);
+    assert_eq!(approval.decided_by, checker);
+
+    // A decided payment cannot be decided again
+    assert!(repository
+        .decide_approval(payment.id, ApprovalDecision::Rejected, PaymentStatus::Cancelled, checker, None)
+        .await
+        .unwrap()
+        .is_none());
+
+    let rejected = create_payment(&repository, payer, payee, 500_000, PaymentStatus::PendingApproval).await;
+    let (rejected, _) = repository
+        .decide_approval(rejected.id, ApprovalDecision::Rejected, PaymentStatus::Cancelled, checker, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(rejected.status, PaymentStatus::Cancelled));
+    assert!(rejected.executed_at.is_none());
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn recent_matching_payments_are_found_as_duplicates() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let repository = PaymentRepository::new(pool.clone());
+    let payer = seeder.account(None, "USD", 1_000).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+    let original = pending_payment(&repository, payer, payee, 250).await;
+
+    let request = |amount| CreatePaymentRequest {
+        to_account_id: Some(payee),
+        to_virtual_account_id: None,
+        amount,
+        currency: "USD".to_string(),
+        payment_method: PaymentMethod::BankTransfer,
+        description: None,
+        recipient_info: None,
+        metadata: None,
+        execute_at: None,
+        timezone: None,
+        force_override: false,
+    };
+    let since = Utc::now() - Duration::minutes(10);
+
+    let matched = repository.find_recent_duplicate(payer, &request(250), since).await.unwrap();
+    assert_eq!(matched, Some(original.id));
+
+    // A different amount or an older window does not match
+    assert!(repository.find_recent_duplicate(payer, &request(251), since).await.unwrap().is_none());
+    let later = Utc::now() + Duration::minutes(1);
+    assert!(repository.find_recent_duplicate(payer, &request(250), later).await.unwrap().is_none());
+
+    // Cancelled payments are not duplicates
+    repository.cancel(original.id).await.unwrap().unwrap();
+    assert!(repository.find_recent_duplicate(payer, &request(250), since).await.unwrap().is_none());
+
+    database.cleanup().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn racing_payments_cannot_overdraw_the_payer() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let repository = PaymentRepository::new(pool.clone());
+    let payer = seeder.account(None, "USD", 100).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+
+    let mut posts = JoinSet::new();
+    for _ in 0..5 {
+        let payment = pending_payment(&repository, payer, payee, 30).await;
+        let repository = PaymentRepository::new(pool.clone());
+        posts.spawn(async move {
+            let settles_at = Utc::now() + Duration::hours(1);
+            retry_transaction(|| repository.post_pending(&payment, settles_at)).await
+        });
+    }
+
+    let mut posted = 0;
+    let mut failed = 0;
+    while let Some(result) = posts.join_next().await {
+        let payment = result.unwrap().unwrap().unwrap();
+        match payment.status {
+            PaymentStatus::Failed => {
+                assert_eq!(payment.execution_error.as_deref(), Some("Insufficient funds"));
+                assert!(payment.transaction_id.is_none());
+                failed += 1;
+            }
+            _ => posted += 1,
+        }
+    }
+    assert_eq!((posted, failed), (3, 2));
+    assert_eq!(balances(&pool, payer).await, (10, 100));
+
+    database.cleanup().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn opposite_settlements_do_not_deadlock() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let repository = PaymentRepository::new(pool.clone());
+    let first = seeder.account(None, "USD", 1_000).await.id;
+    let second = seeder.account(None, "USD", 1_000).await.id;
+
+    let mut payments = Vec::new();
+    for index in 0..20 {
+        let (from, to) = if index % 2 == 0 { (first, second) } else { (second, first) };
+        let payment = pending_payment(&repository, from, to, 10 + index).await;
+        payments.push(repository.post_pending(&payment, Utc::now()).await.unwrap().unwrap());
+    }
+
+    let mut settlements = JoinSet::new();
+    for payment in payments {
+        let repository = PaymentRepository::new(pool.clone());
+        settlements.spawn(async move { retry_transaction(|| repository.settle(payment.id)).await });
+    }
+    while let Some(result) = settlements.join_next().await {
+        let settled = result.unwrap().unwrap().unwrap();
+        assert!(matches!(settled.status, PaymentStatus::Completed));
+    }
+
+    // The first account sent 10, 12, ..., 28 and received 11, 13, ..., 29
+    assert_eq!(balances(&pool, first).await, (1_010, 1_010));
+    assert_eq!(balances(&pool, second).await, (990, 990));
+
+    database.cleanup().await;
+}
+
+const CALLBACK_SECRET: &str = "callback-secret";
+
+async fn post_callback(state: &AppState, body: serde_json::Value, secret: &str) -> Result<PaymentCallbackResponse, AppError> {
+    let body = Bytes::from(body.to_string());
+    let mut headers = HeaderMap::new();
+    headers.insert("x-callback-signature", hex(&hmac_sha256(secret.as_bytes(), &body)).parse().unwrap());
+    let response = payment_callback(State(state.clone()), Path("generic".to_string()), headers, body).await?;
+    Ok(response.0.data.unwrap())
+}
+
+#[tokio::test]
+async fn unsigned_callbacks_are_refused_and_audited() {
+    let audit_logger = AuditLogger::in_memory();
+    let state = TestStateBuilder::new()
+        .config(|config| config.payment_callback_secret = Some(CALLBACK_SECRET.to_string()))
+        .audit_logger(audit_logger.clone())
+        .build()
+        .await;
+
+    let body = serde_json::json!({"event_id": "evt_1", "reference": "PAY_1", "status": "settled"});
+    let forged = post_callback(&state, body.clone(), "guessed").await;
+    assert!(matches!(forged, Err(AppError::Authentication(_))));
+    let events = audit_logger.recorded_events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event_type, AuditEventType::PaymentCallbackRejected));
+
+    // Rails without a secret configured are unknown
+    let stripe = payment_callback(State(state), Path("stripe".to_string()), HeaderMap::new(), Bytes::new()).await;
+    assert!(matches!(stripe, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn replayed_stripe_callbacks_are_refused_and_audited() {
+    let audit_logger = AuditLogger::in_memory();
+    let state = TestStateBuilder::new()
+        .config(|config| config.stripe_webhook_secret = Some("whsec".to_string()))
+        .audit_logger(audit_logger.clone())
+        .build()
+        .await;
+
+    let body = Bytes::from_static(br#"{"id":"evt_1","type":"payment_intent.succeeded","data":{"object":{"id":"pi_1"}}}"#);
+    let now = Utc::now();
+    let mut signed = format!("{}.", now.timestamp()).into_bytes();
+    signed.extend_from_slice(&body);
+    let signature = hex(&hmac_sha256(b"whsec", &signed));
+    // The delivery was already accepted once
+    state.replay_cache.check("stripe", &signature, now.timestamp(), now).await.unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("stripe-signature", format!("t={},v1={}", now.timestamp(), signature).parse().unwrap());
+    let replayed = payment_callback(State(state.clone()), Path("stripe".to_string()), headers, body).await;
+    assert!(matches!(replayed, Err(AppError::Authentication(_))));
+
+    let events = audit_logger.recorded_events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event_type, AuditEventType::PaymentCallbackRejected));
+    assert_eq!(events[0].metadata["reason"], "replayed");
+    assert_eq!(state.replay_cache.stats().replays_rejected, 1);
+}
+
+#[tokio::test]
+async fn rail_callbacks_move_posted_payments_once() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let state = TestStateBuilder::new()
+        .config(|config| config.payment_callback_secret = Some(CALLBACK_SECRET.to_string()))
+        .postgres(pool.clone())
+        .build()
+        .await;
+    let repository = PaymentRepository::new(pool.clone());
+    let payer = seeder.account(None, "USD", 1_000).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+
+    let payment = pending_payment(&repository, payer, payee, 300).await;
+    repository.post_pending(&payment, Utc::now() + Duration::hours(24)).await.unwrap().unwrap();
+    let callback = |event_id: &str, status: &str| {
+        serde_json::json!({
+            "event_id": event_id,
+            "reference": payment.reference,
+            "provider_reference": "rail-42",
+            "status": status,
+        })
+    };
+
+    // Accepted by the rail: processing, and no longer cancellable
+    let accepted = post_callback(&state, callback("evt_1", "accepted"), CALLBACK_SECRET).await.unwrap();
+    assert_eq!(accepted.outcome, PaymentCallbackOutcome::Applied);
+    assert_eq!(accepted.payment_id, Some(payment.id));
+    let processing = repository.find_by_id(payment.id).await.unwrap().unwrap();
+    assert!(matches!(processing.status, PaymentStatus::Processing));
+    assert_eq!(processing.external_reference.as_deref(), Some("rail-42"));
+    assert!(repository.cancel(payment.id).await.unwrap().is_none());
+
+    // Redelivery changes nothing
+    let again = post_callback(&state, callback("evt_1", "accepted"), CALLBACK_SECRET).await.unwrap();
+    assert!(again.duplicate);
+    assert_eq!(again.callback_id, accepted.callback_id);
+
+    let settled = post_callback(&state, callback("evt_2", "settled"), CALLBACK_SECRET).await.unwrap();
+    assert_eq!(settled.outcome, PaymentCallbackOutcome::Applied);
+    assert_eq!(balances(&pool, payer).await, (700, 700));
+    assert_eq!(balances(&pool, payee).await, (300, 300));
+
+    // A late failure cannot undo the settlement
+    let late = post_callback(&state, callback("evt_3", "failed"), CALLBACK_SECRET).await.unwrap();
+    assert_eq!(late.outcome, PaymentCallbackOutcome::Ignored);
+    assert_eq!(balances(&pool, payer).await, (700, 700));
+
+    // A rejected payment fails and releases its hold
+    let rejected = pending_payment(&repository, payer, payee, 200).await;
+    repository.post_pending(&rejected, Utc::now() + Duration::hours(24)).await.unwrap().unwrap();
+    assert_eq!(balances(&pool, payer).await, (500, 700));
+    let body = serde_json::json!({
+        "event_id": "evt_4",
+        "reference": rejected.reference,
+        "status": "returned",
+        "reason": "Account closed",
+    });
+    post_callback(&state, body, CALLBACK_SECRET).await.unwrap();
+    let failed = repository.find_by_id(rejected.id).await.unwrap().unwrap();
+    assert!(matches!(failed.status, PaymentStatus::Failed));
+    assert_eq!(failed.execution_error.as_deref(), Some("Account closed"));
+    assert_eq!(balances(&pool, payer).await, (700, 700));
+
+    let unknown = serde_json::json!({"event_id": "evt_5", "reference": "PAY_unknown", "status": "settled"});
+    let unmatched = post_callback(&state, unknown, CALLBACK_SECRET).await.unwrap();
+    assert_eq!(unmatched.outcome, PaymentCallbackOutcome::Unmatched);
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM payment_callbacks WHERE raw_body IS NOT NULL")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 5);
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn only_scheduled_payments_are_rescheduled() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let repository = PaymentRepository::new(pool.clone());
+    let payer = seeder.account(None, "USD", 1_000).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+
+    // Deferred past the cut-off to the next processing window
+    let scheduled = create_payment(&repository, payer, payee, 100, PaymentStatus::Scheduled).await;
+    let window = Utc::now() + Duration::hours(12);
+    let settles_at = window + Duration::hours(24);
+    let deferred = repository.reschedule(scheduled.id, window, settles_at).await.unwrap().unwrap();
+    assert!(matches!(deferred.status, PaymentStatus::Scheduled));
+    assert_eq!(deferred.execution_timezone.as_deref(), Some("UTC"));
+    assert!(deferred.execute_at.is_some_and(|at| (at - window).num_milliseconds().abs() < 1));
+    assert!(deferred.expected_settlement_at.is_some());
+    assert!(repository.find_due_scheduled(Utc::now(), 10).await.unwrap().iter().all(|due| due.id != scheduled.id));
+
+    let pending = pending_payment(&repository, payer, payee, 100).await;
+    assert!(repository.reschedule(pending.id, window, settles_at).await.unwrap().is_none());
+
+    database.cleanup().await;
+}
+
+fn project_claims(project: &SeededProject) -> JwtClaims {
+    let now = Utc::now();
+    JwtClaims {
+        iss: "openbank-auth".to_string(),
+        aud: "openbank-api".to_string(),
+        sub: project.project.id.to_string(),
+        exp: (now + Duration::hours(1)).timestamp(),
+        iat: now.timestamp(),
+        jti: Uuid::new_v4().to_string(),
+        developer_id: project.developer.id,
+        project_id: project.project.id,
+        tenant_id: project.project.organization_id,
+        scopes: Vec::new(),
+        user_id: None,
+        auth_time: None,
+        acr: None,
+    }
+}
+
+fn intent_request(from: Uuid, to: Uuid, amount: i64) -> CreatePaymentIntentRequest {
+    CreatePaymentIntentRequest {
+        from_account_id: from,
+        to_account_id: Some(to),
+        to_virtual_account_id: None,
+        amount,
+        currency: "USD".to_string(),
+        payment_method: PaymentMethod::BankTransfer,
+        description: None,
+        recipient_info: None,
+        metadata: None,
+        quote_id: None,
+        requote: false,
+        execute_at: None,
+        timezone: None,
+        force_override: false,
+    }
+}
+
+async fn create_intent(state: &AppState, claims: &JwtClaims, from: Uuid, to: Uuid, amount: i64) -> PaymentIntentResponse {
+    let request = intent_request(from, to, amount);
+    let (_, response) = create_payment_intent(State(state.clone()), JwtToken(claims.clone()), ApiVersion::V1, ApiJson(request))
+        .await
+        .unwrap();
+    response.0.data.unwrap().1
+}
+
+async fn confirm_intent(
+    state: &AppState,
+    claims: &JwtClaims,
+    intent_id: Uuid,
+    token: &str,
+) -> Result<PaymentIntentResponse, AppError> {
+    let request = ConfirmPaymentIntentRequest { confirmation_token: token.to_string() };
+    confirm_payment_intent(State(state.clone()), JwtToken(claims.clone()), ApiVersion::V1, Path(intent_id), ApiJson(request))
+        .await
+        .map(|response| response.0.data.unwrap().1)
+}
+
+#[tokio::test]
+async fn payment_intents_are_confirmed_once_with_their_token() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let audit_logger = AuditLogger::in_memory();
+    let state = TestStateBuilder::new()
+        .postgres(pool.clone())
+        .audit_logger(audit_logger.clone())
+        .build()
+        .await;
+    let project = seeder.project(&[]).await;
+    let claims = project_claims(&project);
+    let payer = seeder.account(None, "USD", 1_000).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+
+    let intent = create_intent(&state, &claims, payer, payee, 300).await;
+    assert_eq!(intent.status, PaymentIntentStatus::RequiresConfirmation);
+    assert_eq!(intent.total_amount, 300 + intent.fee_amount);
+    let token = intent.confirmation_token.clone().unwrap();
+    // Nothing is held until the intent is confirmed
+    assert_eq!(balances(&pool, payer).await, (1_000, 1_000));
+
+    let guessed = confirm_intent(&state, &claims, intent.id, "guessed").await;
+    assert!(matches!(guessed, Err(AppError::Authentication(_))));
+    let events = audit_logger.recorded_events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event_type, AuditEventType::AccessDenied));
+
+    let confirmed = confirm_intent(&state, &claims, intent.id, &token).await.unwrap();
+    assert_eq!(confirmed.status, PaymentIntentStatus::Processing);
+    assert!(confirmed.confirmation_token.is_none());
+    let payment = PaymentRepository::new(pool.clone())
+        .find_by_id(confirmed.payment_id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(payment.amount, 300);
+    assert_eq!(payment.tenant_id, Some(claims.tenant_id));
+
+    let again = confirm_intent(&state, &claims, intent.id, &token).await;
+    assert!(matches!(again, Err(AppError::Conflict(_))));
+
+    // Cancelled intents cannot be confirmed
+    let cancelled = create_intent(&state, &claims, payer, payee, 100).await;
+    let response = cancel_payment_intent(State(state.clone()), JwtToken(claims.clone()), ApiVersion::V1, Path(cancelled.id))
+        .await
+        .unwrap();
+    assert_eq!(response.0.data.unwrap().1.status, PaymentIntentStatus::Cancelled);
+    let late = confirm_intent(&state, &claims, cancelled.id, cancelled.confirmation_token.as_deref().unwrap()).await;
+    assert!(matches!(late, Err(AppError::Conflict(_))));
+
+    // Nor can expired ones, which are marked expired
+    let expired = create_intent(&state, &claims, payer, payee, 100).await;
+    sqlx::query("UPDATE payment_intents SET expires_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+        .bind(expired.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let late = confirm_intent(&state, &claims, expired.id, expired.confirmation_token.as_deref().unwrap()).await;
+    assert!(matches!(late, Err(AppError::Conflict(_))));
+    let status: PaymentIntentStatus = sqlx::query_scalar("SELECT status FROM payment_intents WHERE id = $1")
+        .bind(expired.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, PaymentIntentStatus::Expired);
+
+    // Intents of another tenant are not found
+    let other = seeder.project(&[]).await;
+    let stranger = confirm_intent(&state, &project_claims(&other), intent.id, &token).await;
+    assert!(matches!(stranger, Err(AppError::NotFound(_))));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn payment_intents_schedule_their_payment() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let state = TestStateBuilder::new()
+        .config(|config| config.payment_cutoff_bank_transfer = None)
+        .postgres(pool.clone())
+        .build()
+        .await;
+    let project = seeder.project(&[]).await;
+    let claims = project_claims(&project);
+    let payer = seeder.account(None, "USD", 1_000).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+    let local = (Utc::now() + Duration::days(3)).date_naive().and_hms_opt(9, 0, 0).unwrap();
+
+    let create = |timezone: &str| {
+        let request = CreatePaymentIntentRequest {
+            execute_at: Some(ExecuteAt::Local(local)),
+            timezone: Some(timezone.to_string()),
+            ..intent_request(payer, payee, 300)
+        };
+        create_payment_intent(State(state.clone()), JwtToken(claims.clone()), ApiVersion::V1, ApiJson(request))
+    };
+
+    let unknown = create("Mars/Olympus_Mons").await;
+    assert!(matches!(unknown, Err(AppError::Validation(_))));
+
+    let (_, response) = create("Europe/London").await.unwrap();
+    let intent = response.0.data.unwrap().1;
+    let execute_at = intent.execute_at.unwrap();
+    assert_eq!(intent.execution_timezone.as_deref(), Some("Europe/London"));
+    let in_london: NaiveDateTime = sqlx::query_scalar("SELECT $1::TIMESTAMPTZ AT TIME ZONE 'Europe/London'")
+        .bind(execute_at)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(in_london, local);
+
+    // Confirming schedules the payment instead of posting it
+    let confirmed = confirm_intent(&state, &claims, intent.id, intent.confirmation_token.as_deref().unwrap())
+        .await
+        .unwrap();
+    let payment = PaymentRepository::new(pool.clone())
+        .find_by_id(confirmed.payment_id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(payment.status, PaymentStatus::Scheduled));
+    assert_eq!(payment.execute_at, Some(execute_at));
+    assert_eq!(payment.execution_timezone.as_deref(), Some("Europe/London"));
+    assert_eq!(balances(&pool, payer).await, (1_000, 1_000));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn blocked_duplicate_intents_can_be_forced_through() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let state = TestStateBuilder::new().postgres(pool.clone()).build().await;
+    let project = seeder.project(&[]).await;
+    sqlx::query("UPDATE projects SET duplicate_payment_action = 'block' WHERE id = $1")
+        .bind(project.project.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let claims = project_claims(&project);
+    let payer = seeder.account(None, "USD", 1_000).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+
+    let app = openbank::payments::routes().with_state(state.clone());
+    let post = |path: String, body: serde_json::Value| {
+        let mut request = Request::post(path)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        request.extensions_mut().insert(claims.clone());
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+        }
+    };
+    let pay = |force_override: bool| async move {
+        let mut request = serde_json::to_value(intent_request(payer, payee, 300)).unwrap();
+        request["force_override"] = serde_json::json!(force_override);
+        let (status, created) = post("/intents".to_string(), request).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let intent = &created["data"];
+        let confirmation = serde_json::json!({ "confirmation_token": intent["confirmation_token"] });
+        post(format!("/intents/{}/confirm", intent["id"].as_str().unwrap()), confirmation).await
+    };
+
+    let (status, first) = pay(false).await;
+    assert_eq!(status, StatusCode::OK);
+    let original: Uuid = serde_json::from_value(first["data"]["payment_id"].clone()).unwrap();
+
+    // The same payment again is refused as a duplicate...
+    let (status, refused) = pay(false).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(refused["data"]["error_code"], "DUPLICATE_PAYMENT");
+
+    // ...unless the client overrides the block
+    let (status, forced) = pay(true).await;
+    assert_eq!(status, StatusCode::OK);
+    let forced: Uuid = serde_json::from_value(forced["data"]["payment_id"].clone()).unwrap();
+    let payment = PaymentRepository::new(pool.clone()).find_by_id(forced).await.unwrap().unwrap();
+    assert_eq!(payment.duplicate_of, Some(original));
+
+    database.cleanup().await;
+}
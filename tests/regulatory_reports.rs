@@ -0,0 +1,116 @@
+use chrono::{DateTime, Duration, Utc};
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::AppError;
+use openbank::regulatory_reports::model::{
+    GenerateRegulatoryReportRequest, Jurisdiction, RegulatoryReportFormat, RegulatoryReportSettings,
+    RegulatoryReportType, ReportableReason, SubmissionStatus, UpdateSubmissionRequest,
+};
+use openbank::regulatory_reports::repository::RegulatoryReportRepository;
+use openbank::regulatory_reports::service::RegulatoryReportService;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+
+async fn completed_payment(pool: &PgPool, from: Uuid, to: Uuid, amount: i64, executed_at: DateTime<Utc>) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO payments (from_account_id, to_account_id, amount, currency, payment_method, status,
+             reference, executed_at)
+         VALUES ($1, $2, $3, 'XTS', 'bank_transfer', 'completed', $4, $5) RETURNING id",
+    )
+    .bind(from)
+    .bind(to)
+    .bind(amount)
+    .bind(format!("PAY_{}", Uuid::new_v4()))
+    .bind(executed_at)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn reports_list_flagged_payments_and_track_submission() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let settings = RegulatoryReportSettings {
+        jurisdictions: vec![Jurisdiction {
+            code: "XT".to_string(),
+            currency: "XTS".to_string(),
+            format: RegulatoryReportFormat::Csv,
+            large_transaction_threshold: 10_000,
+        }],
+    };
+    let service =
+        RegulatoryReportService::new(RegulatoryReportRepository::new(pool.clone()), settings, AuditLogger::in_memory());
+    let actor_id = Uuid::new_v4();
+    // A past date, so other tests' payments do not land on it
+    let day = (Utc::now() - Duration::days(400)).date_naive();
+    let at = day.and_hms_opt(10, 0, 0).unwrap().and_utc();
+
+    let payer = seeder.account(None, "USD", 0).await.id;
+    let payee = seeder.account(None, "USD", 0).await.id;
+    let large = completed_payment(&pool, payer, payee, 25_000, at).await;
+    let structurer = seeder.account(None, "USD", 0).await.id;
+    for hour in 0..3 {
+        completed_payment(&pool, structurer, payee, 4_000, at + Duration::hours(hour)).await;
+    }
+
+    let large_transactions = service
+        .reportable("xt", RegulatoryReportType::LargeTransactions, day, day)
+        .await
+        .unwrap();
+    assert_eq!(large_transactions.len(), 1);
+    assert_eq!(large_transactions[0].payment_id, large);
+
+    let suspicious = service
+        .reportable("XT", RegulatoryReportType::SuspiciousActivity, day, day)
+        .await
+        .unwrap();
+    assert_eq!(suspicious.len(), 3);
+    assert!(suspicious.iter().all(|transaction| transaction.reasons == vec![ReportableReason::Structuring]));
+
+    assert!(matches!(
+        service.reportable("ZZ", RegulatoryReportType::SuspiciousActivity, day, day).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    let report = service
+        .generate(
+            GenerateRegulatoryReportRequest {
+                jurisdiction: "XT".to_string(),
+                report_type: RegulatoryReportType::SuspiciousActivity,
+                period_start: day,
+                period_end: day,
+            },
+            actor_id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(report.transaction_count, 3);
+    assert_eq!(report.content.lines().count(), 4);
+
+    let submit = |submission_reference: Option<&str>| UpdateSubmissionRequest {
+        status: SubmissionStatus::Submitted,
+        submission_reference: submission_reference.map(str::to_string),
+        note: None,
+    };
+    assert!(matches!(
+        service.update_submission(report.id, submit(None), actor_id).await,
+        Err(AppError::Validation(_))
+    ));
+    let submitted = service
+        .update_submission(report.id, submit(Some("FIL-1")), actor_id)
+        .await
+        .unwrap();
+    assert_eq!(submitted.status, SubmissionStatus::Submitted);
+    assert!(submitted.submitted_at.is_some());
+    assert!(matches!(
+        service.update_submission(report.id, submit(Some("FIL-2")), actor_id).await,
+        Err(AppError::Conflict(_))
+    ));
+
+    database.cleanup().await;
+}
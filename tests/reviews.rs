@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::storage::LocalStorage;
+use openbank::income::model::{
+    DocumentUpload, IncomeDocumentType, IncomeVerificationRequest, IncomeVerificationStatus,
+};
+use openbank::income::repository::IncomeRepository;
+use openbank::income::service::IncomeService;
+use openbank::reviews::model::{ReviewDecision, ReviewFilter, ReviewStatus, VerificationKind};
+use openbank::reviews::repository::ReviewRepository;
+use openbank::reviews::service::ReviewQueue;
+use openbank::shared::traits::Repository;
+use openbank_test_support::TestDatabase;
+use uuid::Uuid;
+
+const PAYSLIP: &str = "Employer: Acme Ltd\nPay period: Monthly\nGross pay: 4,000.00 USD\n";
+
+#[tokio::test]
+async fn uploaded_documents_are_queued_and_decided_by_one_reviewer() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let audit_logger = AuditLogger::in_memory();
+    let storage_root = std::env::temp_dir().join(format!("openbank-reviews-{}", Uuid::new_v4()));
+    let repository = ReviewRepository::new(pool.clone());
+    let queue = ReviewQueue::new(repository.clone(), audit_logger.clone());
+    let income_service = IncomeService::new(
+        IncomeRepository::new(pool.clone()),
+        Arc::new(LocalStorage::new(&storage_root)),
+        queue.clone(),
+        audit_logger.clone(),
+    );
+
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name)
+         VALUES ($1, 'x', 'Test', 'User') RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let request = IncomeVerificationRequest {
+        user_id,
+        verification_type: "payslip".to_string(),
+        employer_name: None,
+        job_title: None,
+        expected_annual_income: None,
+        currency: "usd".to_string(),
+        document_type: IncomeDocumentType::Payslip,
+        additional_data: None,
+    };
+    let upload = || DocumentUpload {
+        file_name: "payslip.txt".to_string(),
+        content_type: "text/plain".to_string(),
+        content: PAYSLIP.as_bytes().to_vec(),
+    };
+    let verification = income_service
+        .initiate_verification(request, vec![upload(), upload()], user_id)
+        .await
+        .unwrap();
+
+    // Both uploads land on the same open review
+    let page = repository
+        .find_reviews(&ReviewFilter::default(), None, 1, 20)
+        .await
+        .unwrap();
+    assert_eq!(page.len(), 1);
+    let review = &page[0];
+    assert_eq!(review.verification_kind, VerificationKind::Income);
+    assert_eq!(review.verification_id, verification.id);
+    assert_eq!(review.subject_user_id, user_id);
+    assert_eq!(review.status, ReviewStatus::Pending);
+    assert!(review.flagged_by.is_none());
+    let flagged = audit_logger
+        .recorded_events()
+        .iter()
+        .filter(|event| matches!(event.event_type, AuditEventType::VerificationFlaggedForReview))
+        .count();
+    assert_eq!(flagged, 1);
+
+    // A claimed review belongs to its reviewer until released or decided
+    let reviewer = Uuid::new_v4();
+    let other_reviewer = Uuid::new_v4();
+    let claimed = repository.claim(review.id, reviewer).await.unwrap().unwrap();
+    assert_eq!(claimed.claimed_by, Some(reviewer));
+    assert!(repository.claim(review.id, other_reviewer).await.unwrap().is_none());
+    assert!(repository.release(review.id, other_reviewer).await.unwrap().is_none());
+    assert!(repository
+        .decide(review.id, other_reviewer, ReviewDecision::Approve, "looks fine")
+        .await
+        .unwrap()
+        .is_none());
+
+    let decided = repository
+        .decide(review.id, reviewer, ReviewDecision::Approve, "Payslip matches stated employer")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(decided.status, ReviewStatus::Approved);
+    assert_eq!(decided.decided_by, Some(reviewer));
+    assert_eq!(decided.decision_reason.as_deref(), Some("Payslip matches stated employer"));
+
+    let income = IncomeRepository::new(pool.clone())
+        .find_by_id(verification.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(income.status, IncomeVerificationStatus::Completed));
+    assert_eq!(
+        income.verification_data.unwrap()["extracted_fields_confirmed"],
+        serde_json::json!(true)
+    );
+
+    // The decided review moves out of the queue into the history
+    let history = repository
+        .find_decided(VerificationKind::Income, verification.id)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 1);
+    assert!(repository
+        .find_open(VerificationKind::Income, verification.id)
+        .await
+        .unwrap()
+        .is_none());
+
+    let _ = std::fs::remove_dir_all(&storage_root);
+    database.cleanup().await;
+}
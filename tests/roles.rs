@@ -0,0 +1,95 @@
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::AppError;
+use openbank::core::rbac::{permissions, PermissionContext, RbacService};
+use openbank::roles::model::{AssignRoleRequest, CustomRoleRequest};
+use openbank::roles::repository::CustomRoleRepository;
+use openbank::roles::service::CustomRoleService;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+
+fn role_request(name: &str, permissions: &[&str]) -> CustomRoleRequest {
+    CustomRoleRequest {
+        name: name.to_string(),
+        description: None,
+        permissions: permissions.iter().map(|permission| permission.to_string()).collect(),
+    }
+}
+
+#[tokio::test]
+async fn custom_role_permissions_resolve_for_assigned_members() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let admin = seeder.developer().await;
+    let member = seeder.developer().await;
+
+    let tenant_id = admin.organization_id;
+    sqlx::query("INSERT INTO organization_members (organization_id, developer_id) VALUES ($1, $2)")
+        .bind(tenant_id)
+        .bind(member.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO user_roles (developer_id, role) VALUES ($1, 'admin')")
+        .bind(admin.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let rbac = RbacService::new();
+    rbac.load_user_roles(&pool, admin.id).await.unwrap();
+    let service = CustomRoleService::new(
+        CustomRoleRepository::new(pool.clone()),
+        rbac.clone(),
+        AuditLogger::in_memory(),
+    );
+
+    let role = service
+        .create_role(
+            role_request("treasury_ops", &["fees:manage", "accounts:freeze", "fees:manage"]),
+            tenant_id,
+            admin.id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(role.permissions, vec!["accounts:freeze", "fees:manage"]);
+
+    // Built-in names are reserved, and no one can grant what they do not hold
+    let error = service
+        .create_role(role_request("admin", &["fees:manage"]), tenant_id, admin.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::Conflict(_)));
+    let error = service
+        .create_role(role_request("operators", &["system:manage"]), tenant_id, admin.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, AppError::Authorization(_)));
+
+    let context = PermissionContext::new(member.id, "127.0.0.1".to_string());
+    rbac.load_user_roles(&pool, member.id).await.unwrap();
+    assert!(!rbac.check_permission(member.id, &permissions::freeze_accounts(), &context));
+
+    service
+        .assign_role(
+            role.id,
+            AssignRoleRequest {
+                developer_id: member.id,
+                expires_at: None,
+            },
+            tenant_id,
+            admin.id,
+        )
+        .await
+        .unwrap();
+    rbac.load_user_roles(&pool, member.id).await.unwrap();
+    assert!(rbac.check_permission(member.id, &permissions::freeze_accounts(), &context));
+    assert!(!rbac.check_permission(member.id, &permissions::manage_roles(), &context));
+
+    service.revoke_role(role.id, member.id, tenant_id, admin.id).await.unwrap();
+    rbac.load_user_roles(&pool, member.id).await.unwrap();
+    assert!(!rbac.check_permission(member.id, &permissions::freeze_accounts(), &context));
+
+    database.cleanup().await;
+}
@@ -0,0 +1,181 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::mailer::{EmailMessage, Mailer};
+use openbank::scheduled_reports::model::{
+    CreateReportSubscriptionRequest, ReportDeliveryChannel, ReportFileFormat, ScheduledReportType,
+    UpdateReportSubscriptionRequest,
+};
+use openbank::scheduled_reports::repository::ReportSubscriptionRepository;
+use openbank::scheduled_reports::service::{ReportSubscriptionService, REPORT_DELIVERED_EVENT};
+use openbank::webhooks::repository::WebhookRepository;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, message: EmailMessage) -> AppResult<()> {
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+/// Make a subscription due now, so the next run reports on yesterday
+async fn make_due(pool: &PgPool, subscription_id: Uuid) {
+    sqlx::query("UPDATE report_subscriptions SET next_run_at = date_trunc('day', NOW()) WHERE id = $1")
+        .bind(subscription_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn due_reports_are_rendered_and_delivered_once_per_period() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let developer = Seeder::new(pool.clone(), &test_config()).developer().await;
+    let tenant_id = developer.organization_id;
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let audit_logger = AuditLogger::in_memory();
+    let service = ReportSubscriptionService::new(
+        ReportSubscriptionRepository::new(pool.clone()),
+        WebhookRepository::new(pool.clone()),
+        mailer.clone(),
+        audit_logger.clone(),
+    );
+
+    // Yesterday's transfer in the tenant shows up in the daily summary
+    sqlx::query(
+        "INSERT INTO transactions (amount, currency, transaction_type, status, reference, tenant_id, created_at)
+         VALUES (12550, 'USD', 'transfer', 'completed', $1, $2, date_trunc('day', NOW()) - INTERVAL '12 hours')",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(tenant_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let email = service
+        .create_subscription(
+            CreateReportSubscriptionRequest {
+                report_type: ScheduledReportType::DailyTransactionSummary,
+                channel: ReportDeliveryChannel::Email,
+                destination: "finance@example.com".to_string(),
+                format: ReportFileFormat::Csv,
+            },
+            tenant_id,
+            developer.id,
+        )
+        .await
+        .unwrap();
+    assert!(email.next_run_at > Utc::now());
+    assert!(audit_logger
+        .recorded_events()
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::ReportSubscriptionCreated)));
+
+    let webhook = service
+        .create_subscription(
+            CreateReportSubscriptionRequest {
+                report_type: ScheduledReportType::DailyTransactionSummary,
+                channel: ReportDeliveryChannel::Webhook,
+                destination: "https://example.com/reports".to_string(),
+                format: ReportFileFormat::Json,
+            },
+            tenant_id,
+            developer.id,
+        )
+        .await
+        .unwrap();
+
+    // Nothing is due yet
+    let run = service.run_due(Utc::now(), 50).await.unwrap();
+    assert_eq!(run.delivered, 0);
+
+    make_due(&pool, email.id).await;
+    make_due(&pool, webhook.id).await;
+    let run = service.run_due(Utc::now(), 50).await.unwrap();
+    assert_eq!((run.delivered, run.failed), (2, 0));
+
+    let sent = mailer.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "finance@example.com");
+    assert!(sent[0].subject.starts_with("Daily transaction summary"));
+    assert!(sent[0].body.contains("USD,transfer,completed,1,12550"));
+
+    let payload: serde_json::Value = sqlx::query_scalar(
+        "SELECT payload FROM webhook_deliveries WHERE event_type = $1 AND url = $2",
+    )
+    .bind(REPORT_DELIVERED_EVENT)
+    .bind("https://example.com/reports")
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(payload["subscription_id"], serde_json::json!(webhook.id));
+    assert_eq!(payload["content"][0]["total_amount"], 12550);
+
+    // The same period is not delivered twice
+    let delivered = service.get_subscription(email.id, tenant_id, developer.id).await.unwrap();
+    assert!(delivered.next_run_at > Utc::now());
+    assert!(delivered.last_run_at.is_some());
+    let run = service.run_due(Utc::now(), 50).await.unwrap();
+    assert_eq!(run.delivered, 0);
+
+    // A new channel must come with a destination that suits it
+    let result = service
+        .update_subscription(
+            email.id,
+            UpdateReportSubscriptionRequest {
+                channel: Some(ReportDeliveryChannel::Webhook),
+                destination: None,
+                format: None,
+                is_active: None,
+            },
+            tenant_id,
+            developer.id,
+        )
+        .await;
+    assert!(matches!(result, Err(AppError::Validation(_))));
+
+    // Paused subscriptions are skipped
+    service
+        .update_subscription(
+            email.id,
+            UpdateReportSubscriptionRequest {
+                channel: None,
+                destination: None,
+                format: None,
+                is_active: Some(false),
+            },
+            tenant_id,
+            developer.id,
+        )
+        .await
+        .unwrap();
+    make_due(&pool, email.id).await;
+    assert_eq!(service.run_due(Utc::now() + Duration::seconds(1), 50).await.unwrap().delivered, 0);
+
+    // Other developers cannot see or remove the subscription
+    let other = Seeder::new(pool.clone(), &test_config()).developer().await;
+    assert!(matches!(
+        service.delete_subscription(email.id, other.organization_id, other.id).await,
+        Err(AppError::NotFound(_))
+    ));
+    service.delete_subscription(email.id, tenant_id, developer.id).await.unwrap();
+    let remaining = service.list_subscriptions(tenant_id, developer.id).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, webhook.id);
+
+    database.cleanup().await;
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use openbank::core::error::AppError;
+use openbank::core::storage::LocalStorage;
+use openbank::transactions::archive::TransactionArchiveService;
+use openbank::transactions::model::StatementQuery;
+use openbank::transactions::repository::TransactionRepository;
+use openbank::usage::model::ExportFormat;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+
+async fn deposit(pool: &PgPool, tenant_id: Uuid, account_id: Uuid, status: &str, created_at: DateTime<Utc>) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO transactions (to_account_id, amount, transaction_type, status, reference, description,
+                                   tenant_id, created_at)
+         VALUES ($1, 100, 'deposit', $2::transaction_status, $3, 'Salary, \"March\"', $4, $5) RETURNING id",
+    )
+    .bind(account_id)
+    .bind(status)
+    .bind(format!("TXN_{}", Uuid::new_v4()))
+    .bind(tenant_id)
+    .bind(created_at)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn old_transactions_move_to_storage_and_stay_on_statements() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let tenant_id = seeder.developer().await.organization_id;
+    let account_id = seeder.account(Some(tenant_id), "USD", 0).await.id;
+    let storage_root = std::env::temp_dir().join(format!("openbank-archive-{}", Uuid::new_v4()));
+    let service = TransactionArchiveService::new(
+        TransactionRepository::new(pool.clone()),
+        Arc::new(LocalStorage::new(&storage_root)),
+        100,
+    );
+
+    let now = Utc::now();
+    let old = now - Duration::days(300);
+    let settled = deposit(&pool, tenant_id, account_id, "completed", old).await;
+    let pending = deposit(&pool, tenant_id, account_id, "pending", old + Duration::minutes(1)).await;
+    let recent = deposit(&pool, tenant_id, account_id, "completed", now - Duration::days(1)).await;
+
+    // Only the settled transaction past the cutoff is archived
+    assert_eq!(service.archive_before(now - Duration::days(180)).await.unwrap(), 1);
+    assert_eq!(service.archive_before(now - Duration::days(180)).await.unwrap(), 0);
+    let remaining: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM transactions ORDER BY created_at")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, vec![pending, recent]);
+    let archive_id: Option<Uuid> = sqlx::query_scalar("SELECT archive_id FROM transaction_keys WHERE id = $1")
+        .bind(settled)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(archive_id.is_some());
+
+    // A statement over the old period reads the archive back
+    let query = StatementQuery {
+        account_id,
+        from: old.date_naive(),
+        to: now.date_naive(),
+        format: ExportFormat::Json,
+    };
+    let statement = service.statement(tenant_id, &query).await.unwrap();
+    let ids: Vec<Uuid> = statement.transactions.iter().map(|record| record.id).collect();
+    assert_eq!(ids, vec![settled, pending, recent]);
+    assert_eq!(statement.transactions[0].description.as_deref(), Some("Salary, \"March\""));
+    assert_eq!(statement.transactions[0].status, "completed");
+
+    let too_long = StatementQuery {
+        from: (now - Duration::days(800)).date_naive(),
+        ..query
+    };
+    assert!(matches!(service.statement(tenant_id, &too_long).await, Err(AppError::BadRequest(_))));
+    let other_tenant = StatementQuery {
+        from: old.date_naive(),
+        ..too_long
+    };
+    assert!(matches!(service.statement(Uuid::new_v4(), &other_tenant).await, Err(AppError::NotFound(_))));
+
+    let _ = std::fs::remove_dir_all(&storage_root);
+    database.cleanup().await;
+}
@@ -0,0 +1,166 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::mailer::{EmailMessage, Mailer};
+use openbank::core::storage::{LocalStorage, Storage, UrlSigner};
+use openbank::scheduled_reports::model::ReportFileFormat;
+use openbank::transactions::export::{TransactionExportService, TransactionExportSettings, EXPORT_COMPLETED_EVENT};
+use openbank::transactions::model::{
+    ExportTransactionsRequest, TransactionExportFilter, TransactionExportStatus,
+};
+use openbank::transactions::repository::TransactionRepository;
+use openbank::webhooks::repository::WebhookRepository;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, message: EmailMessage) -> AppResult<()> {
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+async fn seed_transaction(pool: &PgPool, tenant_id: Uuid, status: &str, amount: i64) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO transactions (amount, currency, transaction_type, status, reference, description, tenant_id)
+         VALUES ($1, 'USD', 'deposit', $2::transaction_status, $3, 'Salary, \"March\"', $4) RETURNING id",
+    )
+    .bind(amount)
+    .bind(status)
+    .bind(format!("TXN_{}", Uuid::new_v4()))
+    .bind(tenant_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn exports_are_written_in_pages_and_handed_out_by_signed_link() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let config = test_config();
+    let tenant_id = Seeder::new(pool.clone(), &config).developer().await.organization_id;
+
+    let mut completed = Vec::new();
+    for amount in 1..=5 {
+        completed.push(seed_transaction(&pool, tenant_id, "completed", amount * 100).await);
+    }
+    seed_transaction(&pool, tenant_id, "pending", 100).await;
+
+    let storage_root = std::env::temp_dir().join(format!("openbank-export-{}", Uuid::new_v4()));
+    let storage: Arc<dyn Storage> = Arc::new(
+        LocalStorage::new(&storage_root)
+            .with_url_signer(UrlSigner::new("https://api.example.com".to_string(), "test-key".to_string())),
+    );
+    let mailer = Arc::new(RecordingMailer::default());
+    let settings = TransactionExportSettings {
+        // Smaller than the export, so it is written over several pages
+        batch_size: 2,
+        link_ttl: std::time::Duration::from_secs(600),
+        retention: Duration::hours(1),
+    };
+    let service = TransactionExportService::new(
+        TransactionRepository::new(pool.clone()),
+        storage.clone(),
+        WebhookRepository::new(pool.clone()),
+        mailer.clone(),
+        AuditLogger::in_memory(),
+        settings,
+    );
+
+    let backwards = service
+        .request_export(
+            ExportTransactionsRequest {
+                filter: TransactionExportFilter {
+                    min_amount: Some(500),
+                    max_amount: Some(100),
+                    ..Default::default()
+                },
+                format: ReportFileFormat::Csv,
+                notify_email: None,
+                webhook_url: None,
+            },
+            tenant_id,
+            None,
+            None,
+        )
+        .await;
+    assert!(matches!(backwards, Err(AppError::BadRequest(_))));
+
+    let requested = service
+        .request_export(
+            ExportTransactionsRequest {
+                filter: TransactionExportFilter {
+                    status: Some("completed".to_string()),
+                    ..Default::default()
+                },
+                format: ReportFileFormat::Csv,
+                notify_email: Some("finance@example.com".to_string()),
+                webhook_url: Some("https://hooks.example.com/exports".to_string()),
+            },
+            tenant_id,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(requested.export.status, TransactionExportStatus::Queued);
+    assert!(requested.download_url.is_none());
+    // Transactions created after the request are not exported
+    seed_transaction(&pool, tenant_id, "completed", 100).await;
+
+    let run = service.run(Utc::now()).await.unwrap();
+    assert_eq!((run.completed, run.failed), (1, 0));
+
+    let ready = service.get_export(requested.export.id, tenant_id).await.unwrap();
+    assert_eq!(ready.export.status, TransactionExportStatus::Completed);
+    assert_eq!(ready.export.row_count, Some(5));
+    assert!(ready.download_url.unwrap().starts_with("https://api.example.com/"));
+
+    let storage_key = ready.export.storage_key.clone().unwrap();
+    let content = String::from_utf8(storage.get(&storage_key).await.unwrap()).unwrap();
+    let mut lines = content.lines();
+    assert!(lines.next().unwrap().ends_with(",from_account_labels,to_account_labels"));
+    let mut ids: Vec<Uuid> = lines
+        .map(|line| Uuid::parse_str(line.split(',').next().unwrap()).unwrap())
+        .collect();
+    ids.sort();
+    completed.sort();
+    assert_eq!(ids, completed);
+
+    let webhooks: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM webhook_deliveries WHERE event_type = $1 AND url = 'https://hooks.example.com/exports'",
+    )
+    .bind(EXPORT_COMPLETED_EVENT)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(webhooks, 1);
+    assert!(mailer.sent.lock().unwrap()[0].body.contains("https://api.example.com/"));
+
+    // Other tenants cannot see the export
+    let missing = service.get_export(requested.export.id, Uuid::new_v4()).await;
+    assert!(matches!(missing, Err(AppError::NotFound(_))));
+
+    // The file is removed once its retention has passed
+    let run = service.run(Utc::now() + Duration::hours(2)).await.unwrap();
+    assert_eq!(run.expired, 1);
+    let expired = service.get_export(requested.export.id, tenant_id).await.unwrap();
+    assert_eq!(expired.export.status, TransactionExportStatus::Expired);
+    assert!(expired.download_url.is_none());
+    assert!(matches!(storage.get(&storage_key).await, Err(AppError::NotFound(_))));
+
+    let _ = std::fs::remove_dir_all(&storage_root);
+    database.cleanup().await;
+}
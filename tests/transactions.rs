@@ -0,0 +1,309 @@
+use chrono::Utc;
+use openbank::account_controls::{repository::AccountControlRepository, service::AccountFreezeGuard};
+use openbank::core::audit::AuditLogger;
+use openbank::core::circuit_breaker::{CircuitBreakerSettings, CircuitBreakers};
+use openbank::core::config::Config;
+use openbank::core::database::retry_transaction;
+use openbank::core::error::AppError;
+use openbank::core::http_client::HttpClients;
+use openbank::fees::model::{CreateFeeScheduleRequest, FeeBreakdown, FeeType};
+use openbank::fees::{repository::FeeRepository, service::{FeeEngine, FeeScheduleService}};
+use openbank::payments::model::PaymentMethod;
+use openbank::goals::{repository::GoalRepository, service::GoalBalanceGuard};
+use openbank::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use openbank::metadata_schemas::{repository::MetadataSchemaRepository, service::MetadataSchemaGuard};
+use openbank::transactions::enrichment::{self, EnrichmentService};
+use openbank::transactions::model::{
+    EnrichmentStatus, MerchantCategory, Transaction, TransactionStatus, TransactionType, TransferRequest,
+};
+use openbank::transactions::repository::TransactionRepository;
+use openbank::transactions::service::TransactionService;
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use sqlx::PgPool;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+
+fn transfer(from: Uuid, to: Uuid, amount: i64) -> TransferRequest {
+    TransferRequest {
+        from_account_id: from,
+        to_account_id: to,
+        amount,
+        currency: "USD".to_string(),
+        description: None,
+        quote_id: None,
+        requote: false,
+    }
+}
+
+fn transaction_service(pool: &PgPool, config: &Config) -> TransactionService {
+    TransactionService::new(
+        TransactionRepository::new(pool.clone()),
+        AccountFreezeGuard::new(AccountControlRepository::new(pool.clone()), false),
+        KycPolicyService::new(
+            KycRepository::new(pool.clone()),
+            KycLimits::from_config(config),
+            AuditLogger::in_memory(),
+        ),
+        GoalBalanceGuard::new(GoalRepository::new(pool.clone())),
+        FeeEngine::new(FeeRepository::new(pool.clone())),
+        MetadataSchemaGuard::new(MetadataSchemaRepository::new(pool.clone())),
+    )
+}
+
+async fn balances(pool: &PgPool, account_id: Uuid) -> (i64, i64) {
+    sqlx::query_as("SELECT available_balance, ledger_balance FROM balances WHERE account_id = $1")
+        .bind(account_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn transfer_preview_reports_outcome_without_moving_money() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let config = test_config();
+    let tenant_id = Seeder::new(pool.clone(), &config).developer().await.organization_id;
+    let from = seeder.account(Some(tenant_id), "USD", 10_000).await.id;
+    let to = seeder.account(Some(tenant_id), "USD", 500).await.id;
+
+    FeeScheduleService::new(FeeRepository::new(pool.clone()), AuditLogger::in_memory())
+        .create_schedule(
+            CreateFeeScheduleRequest {
+                fee_code: "transfer".to_string(),
+                name: "Transfer fee".to_string(),
+                project_id: None,
+                payment_method: Some(PaymentMethod::BankTransfer),
+                currency: Some("USD".to_string()),
+                fee_type: FeeType::Flat,
+                flat_amount: 150,
+                percentage_bps: 0,
+                tiers: None,
+                min_fee: None,
+                max_fee: None,
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+
+    let service = transaction_service(&pool, &config);
+
+    let preview = service.preview_transfer(transfer(from, to, 2_500), tenant_id, None, None).await.unwrap();
+    assert!(preview.allowed, "unexpected issues: {:?}", preview.issues);
+    assert_eq!(preview.fees.total, 150);
+    assert_eq!(preview.total_debit, 2_650);
+    assert_eq!(preview.source.available_balance_after, 7_350);
+    let expected_balance = preview.source.available_balance_after;
+    assert_eq!(preview.destination.available_balance_after, 3_000);
+    assert_eq!(preview.limits.daily_used, 0);
+
+    // Overdrawing is reported as an issue rather than an error
+    let preview = service.preview_transfer(transfer(from, to, 50_000), tenant_id, None, None).await.unwrap();
+    assert!(!preview.allowed);
+    assert!(preview.issues.iter().any(|issue| issue.contains("Insufficient spendable balance")));
+
+    // Nothing was persisted
+    let transactions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transactions WHERE from_account_id = $1")
+        .bind(from)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(transactions, 0);
+    let balance: i64 = sqlx::query_scalar("SELECT available_balance FROM balances WHERE account_id = $1")
+        .bind(from)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(balance, 10_000);
+
+    // Accounts in another tenant are not found
+    let other_tenant_id = Seeder::new(pool.clone(), &config).developer().await.organization_id;
+    assert!(matches!(
+        service.preview_transfer(transfer(from, to, 2_500), other_tenant_id, None, None).await,
+        Err(AppError::NotFound(_))
+    ));
+
+    // The transfer itself charges the fee the preview showed
+    let transferred = service.transfer_funds(transfer(from, to, 2_500), tenant_id, None, None).await.unwrap();
+    assert_eq!(balances(&pool, from).await, (expected_balance, expected_balance));
+    assert_eq!(balances(&pool, to).await, (3_000, 3_000));
+    let charged: Vec<(String, i64)> =
+        sqlx::query_as("SELECT fee_code, amount FROM fee_postings WHERE transaction_id = $1")
+            .bind(transferred.id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(charged, vec![("transfer".to_string(), 150)]);
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn transfers_move_money_between_both_accounts() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let config = test_config();
+    let tenant_id = Seeder::new(pool.clone(), &config).developer().await.organization_id;
+    let from = seeder.account(Some(tenant_id), "USD", 10_000).await.id;
+    let to = seeder.account(Some(tenant_id), "USD", 500).await.id;
+    let service = transaction_service(&pool, &config);
+
+    let transferred = service.transfer_funds(transfer(from, to, 2_500), tenant_id, None, None).await.unwrap();
+    assert!(matches!(transferred.status, TransactionStatus::Completed));
+    assert_eq!(balances(&pool, from).await, (7_500, 7_500));
+    assert_eq!(balances(&pool, to).await, (3_000, 3_000));
+
+    let postings: Vec<(Uuid, i64, i64)> = sqlx::query_as(
+        "SELECT account_id, amount_changed, balance_after FROM balance_history
+         WHERE transaction_id = $1 ORDER BY amount_changed",
+    )
+    .bind(transferred.id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(postings, vec![(from, -2_500, 7_500), (to, 2_500, 3_000)]);
+
+    // A refused transfer leaves both balances alone
+    let overdrawn = service.transfer_funds(transfer(from, to, 50_000), tenant_id, None, None).await;
+    assert!(matches!(overdrawn, Err(AppError::BadRequest(_))));
+    assert_eq!(balances(&pool, from).await, (7_500, 7_500));
+    assert_eq!(balances(&pool, to).await, (3_000, 3_000));
+    let transactions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transactions WHERE from_account_id = $1")
+        .bind(from)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(transactions, 1);
+
+    // Funds in a locked savings goal cannot be transferred out
+    sqlx::query(
+        "INSERT INTO savings_goals (account_id, tenant_id, name, target_amount, balance, currency, locked)
+         VALUES ($1, $2, 'Holiday', 10000, 7000, 'USD', TRUE)",
+    )
+    .bind(from)
+    .bind(tenant_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    match service.transfer_funds(transfer(from, to, 1_000), tenant_id, None, None).await {
+        Err(AppError::BadRequest(reason)) => assert!(reason.contains("7000 of the available balance is locked")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert_eq!(balances(&pool, from).await, (7_500, 7_500));
+
+    database.cleanup().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn racing_transfers_cannot_overdraw_the_source() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let tenant_id = seeder.developer().await.organization_id;
+    let source = seeder.account(Some(tenant_id), "USD", 100).await.id;
+    let destination = seeder.account(Some(tenant_id), "USD", 0).await.id;
+
+    let mut transfers = JoinSet::new();
+    for _ in 0..5 {
+        let repository = TransactionRepository::new(pool.clone());
+        let now = Utc::now();
+        let transaction = Transaction {
+            id: Uuid::new_v4(),
+            from_account_id: Some(source),
+            to_account_id: Some(destination),
+            amount: 30,
+            currency: "USD".to_string(),
+            transaction_type: TransactionType::Transfer,
+            status: TransactionStatus::Pending,
+            reference: format!("TXN_{}", Uuid::new_v4()),
+            description: None,
+            metadata: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let fees = FeeBreakdown {
+            total: 0,
+            currency: "USD".to_string(),
+            lines: Vec::new(),
+        };
+        transfers.spawn(async move {
+            retry_transaction(|| repository.post_transfer(&transaction, tenant_id, None, &fees)).await
+        });
+    }
+
+    let mut posted = 0;
+    let mut refused = 0;
+    while let Some(result) = transfers.join_next().await {
+        match result.unwrap() {
+            Ok(transaction) => {
+                assert!(matches!(transaction.status, TransactionStatus::Completed));
+                posted += 1;
+            }
+            Err(AppError::BadRequest(reason)) => {
+                assert_eq!(reason, "Insufficient funds");
+                refused += 1;
+            }
+            Err(error) => panic!("unexpected error: {error:?}"),
+        }
+    }
+    assert_eq!((posted, refused), (3, 2));
+    assert_eq!(balances(&pool, source).await, (10, 10));
+    assert_eq!(balances(&pool, destination).await, (90, 90));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn enrichment_resolves_merchants_once_per_transaction() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let config = test_config();
+    let tenant_id = Seeder::new(pool.clone(), &config).developer().await.organization_id;
+
+    let mut ids = Vec::new();
+    for description in ["STARBUCKS STORE 1234 SEATTLE WA", "Transfer to savings"] {
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO transactions (amount, currency, transaction_type, status, reference, description, tenant_id)
+             VALUES (450, 'USD', 'transfer', 'completed', $1, $2, $3) RETURNING id",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(description)
+        .bind(tenant_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        ids.push(id);
+    }
+
+    let repository = TransactionRepository::new(pool.clone());
+    let breakers = CircuitBreakers::new(CircuitBreakerSettings::from_config(&config));
+    let clients = HttpClients::from_config(&config).unwrap();
+    let provider = enrichment::from_config(&config, &repository, &breakers, &clients).await.unwrap();
+    let service = EnrichmentService::new(TransactionRepository::new(pool.clone()), provider, 100);
+    assert_eq!(service.enrich_pending().await.unwrap(), 2);
+    // Already enriched transactions are not looked up again
+    assert_eq!(service.enrich_pending().await.unwrap(), 0);
+
+    let mut records = repository.find_enrichments(&ids).await.unwrap();
+    records.sort_by_key(|record| ids.iter().position(|id| *id == record.transaction_id));
+    assert_eq!(records[0].status, EnrichmentStatus::Matched);
+    assert_eq!(records[0].merchant_name.as_deref(), Some("Starbucks"));
+    assert_eq!(records[0].category, Some(MerchantCategory::Dining));
+    assert_eq!(records[0].city.as_deref(), Some("Seattle"));
+    assert_eq!(records[1].status, EnrichmentStatus::Unmatched);
+    assert!(records[1].merchant_name.is_none());
+
+    database.cleanup().await;
+}
@@ -0,0 +1,69 @@
+use chrono::{Duration, Utc};
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::AppError;
+use openbank::core::events::{DomainEvent, DomainEventType};
+use openbank::treasury::repository::TreasuryRepository;
+use openbank::treasury::service::TreasuryService;
+use openbank_test_support::TestDatabase;
+use uuid::Uuid;
+
+/// A completed payment; internal ones credit another customer account
+fn payment_completed(currency: &str, amount: i64, internal: bool) -> DomainEvent {
+    DomainEvent::new(
+        DomainEventType::PaymentStatusChanged,
+        None,
+        Vec::new(),
+        serde_json::json!({
+            "from_account_id": Uuid::new_v4(),
+            "to_account_id": internal.then(Uuid::new_v4),
+            "amount": amount,
+            "currency": currency,
+            "status": "Completed",
+        }),
+    )
+}
+
+#[tokio::test]
+async fn frozen_positions_stop_taking_flows() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let service = TreasuryService::new(TreasuryRepository::new(database.pool()), AuditLogger::in_memory());
+    let actor_id = Uuid::new_v4();
+    // A past date, so other tests' flows do not land on it
+    let business_date = (Utc::now() - Duration::days(400)).date_naive();
+    let currency = "XTS";
+
+    let mut outgoing = payment_completed(currency, 500, false);
+    outgoing.occurred_at = business_date.and_hms_opt(10, 0, 0).unwrap().and_utc();
+    let mut internal = payment_completed(currency, 200, true);
+    internal.occurred_at = outgoing.occurred_at;
+    assert_eq!(service.record(&outgoing).await.unwrap(), Some(business_date));
+    assert_eq!(service.record(&internal).await.unwrap(), Some(business_date));
+
+    let live = service.positions(Some(business_date), Some(currency.to_string())).await.unwrap();
+    assert!(!live.frozen);
+    let position = &live.currencies[0];
+    assert_eq!((position.inflow, position.outflow, position.net, position.movement_count), (200, 700, -500, 2));
+
+    let frozen = service.freeze(Some(business_date), actor_id).await.unwrap();
+    assert!(frozen.frozen);
+    assert!(matches!(
+        service.freeze(Some(business_date), actor_id).await,
+        Err(AppError::Conflict(_))
+    ));
+
+    // A late flow rolls into the next business date; the frozen one is unchanged
+    let next_date = business_date + Duration::days(1);
+    assert_eq!(service.record(&outgoing).await.unwrap(), Some(next_date));
+    let positions = service.positions(Some(business_date), Some(currency.to_string())).await.unwrap();
+    assert_eq!(positions.snapshot_id, frozen.snapshot_id);
+    assert_eq!(positions.currencies[0].outflow, 700);
+    let next = service.positions(Some(next_date), Some(currency.to_string())).await.unwrap();
+    assert_eq!(next.currencies[0].outflow, 500);
+
+    let snapshots = service.list_snapshots(Some(business_date), None).await.unwrap();
+    assert_eq!(snapshots.len(), 1);
+
+    database.cleanup().await;
+}
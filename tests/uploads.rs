@@ -0,0 +1,186 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Duration;
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::storage::{LocalStorage, Storage, UrlSigner};
+use openbank::identity::repository::IdentityRepository;
+use openbank::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use openbank::reviews::repository::ReviewRepository;
+use openbank::reviews::service::ReviewQueue;
+use openbank::uploads::model::{CreateUploadRequest, UploadPurpose};
+use openbank::uploads::repository::UploadRepository;
+use openbank::uploads::service::UploadService;
+use openbank::verification_sessions::checks::{SessionChecks, StepCheck};
+use openbank::verification_sessions::model::{
+    CreateVerificationSessionRequest, SessionSettings, SessionStep, StepStatus, StepUpload, StepUploadReference,
+};
+use openbank::verification_sessions::repository::VerificationSessionRepository;
+use openbank::verification_sessions::service::VerificationSessionService;
+use openbank_test_support::{test_config, test_image, TestDatabase, TestStateBuilder};
+use uuid::Uuid;
+
+struct PassingChecks;
+
+#[async_trait]
+impl SessionChecks for PassingChecks {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    async fn check(
+        &self,
+        _session_id: Uuid,
+        _document_type: &str,
+        _step: SessionStep,
+        _upload: &StepUpload,
+    ) -> AppResult<StepCheck> {
+        Ok(StepCheck::Passed { reference: None })
+    }
+}
+
+#[tokio::test]
+async fn direct_uploads_are_scoped_and_used_once() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let state = TestStateBuilder::new().postgres(pool.clone()).build().await;
+    let tenant_id: Uuid = sqlx::query_scalar("INSERT INTO organizations (name) VALUES ('Acme') RETURNING id")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name, tenant_id)
+         VALUES ($1, 'x', 'Test', 'User', $2) RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .bind(tenant_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let audit_logger = AuditLogger::in_memory();
+    let storage_root = std::env::temp_dir().join(format!("openbank-uploads-{}", Uuid::new_v4()));
+    let storage: Arc<dyn Storage> = Arc::new(
+        LocalStorage::new(&storage_root)
+            .with_url_signer(UrlSigner::new("https://api.example.com".to_string(), "signing-key".to_string())),
+    );
+    let uploads = || {
+        UploadService::new(
+            UploadRepository::new(pool.clone()),
+            IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+            storage.clone(),
+            Duration::minutes(5),
+            audit_logger.clone(),
+        )
+    };
+    let sessions = VerificationSessionService::new(
+        VerificationSessionRepository::new(pool.clone(), state.user_cipher.clone()),
+        IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+        storage.clone(),
+        uploads(),
+        Arc::new(PassingChecks),
+        SessionSettings::from_config(&test_config()),
+        ReviewQueue::new(ReviewRepository::new(pool.clone()), audit_logger.clone()),
+        KycPolicyService::new(
+            KycRepository::new(pool.clone()),
+            KycLimits::from_config(&test_config()),
+            audit_logger.clone(),
+        ),
+        audit_logger.clone(),
+    );
+
+    // Links are only issued for content types the purpose accepts
+    let request = |purpose, content_type: &str| CreateUploadRequest {
+        user_id: None,
+        purpose,
+        content_type: content_type.to_string(),
+    };
+    let video_selfie = uploads()
+        .create(request(UploadPurpose::Selfie, "video/mp4"), tenant_id, Some(user_id), user_id)
+        .await;
+    assert!(matches!(video_selfie, Err(AppError::Validation(_))));
+
+    let token = uploads()
+        .create(request(UploadPurpose::IdentityDocument, "image/PNG"), tenant_id, Some(user_id), user_id)
+        .await
+        .unwrap();
+    assert_eq!(token.method, "PUT");
+    assert_eq!(token.content_type, "image/png");
+    assert!(token.upload_url.contains(&format!("direct-uploads/{}/{}", user_id, token.upload_id)));
+
+    let session = sessions
+        .create(
+            CreateVerificationSessionRequest {
+                user_id: None,
+                document_type: "passport".to_string(),
+                steps: Some(vec![SessionStep::Document, SessionStep::Selfie]),
+            },
+            tenant_id,
+            Some(user_id),
+            user_id,
+        )
+        .await
+        .unwrap();
+    let reference = || StepUploadReference {
+        upload_id: token.upload_id,
+        document_number: Some("P1234567".to_string()),
+    };
+
+    // Nothing uploaded yet, then the wrong step
+    let early = sessions
+        .submit_uploaded_step(session.id, SessionStep::Document, reference(), tenant_id, Some(user_id), user_id)
+        .await;
+    assert!(matches!(early, Err(AppError::Validation(_))));
+    storage
+        .put(&format!("direct-uploads/{}/{}", user_id, token.upload_id), test_image("image/png"))
+        .await
+        .unwrap();
+    sessions
+        .submit_step(
+            session.id,
+            SessionStep::Document,
+            StepUpload {
+                file_name: "id.png".to_string(),
+                content_type: "image/png".to_string(),
+                content: test_image("image/png"),
+                document_number: Some("P1234567".to_string()),
+            },
+            tenant_id,
+            Some(user_id),
+            user_id,
+        )
+        .await
+        .unwrap();
+    let wrong_purpose = sessions
+        .submit_uploaded_step(session.id, SessionStep::Selfie, reference(), tenant_id, Some(user_id), user_id)
+        .await;
+    assert!(matches!(wrong_purpose, Err(AppError::Validation(_))));
+
+    // A selfie uploaded directly is taken over by the session once
+    let selfie = uploads()
+        .create(request(UploadPurpose::Selfie, "image/jpeg"), tenant_id, Some(user_id), user_id)
+        .await
+        .unwrap();
+    storage
+        .put(&format!("direct-uploads/{}/{}", user_id, selfie.upload_id), test_image("image/jpeg"))
+        .await
+        .unwrap();
+    let selfie_reference = || StepUploadReference {
+        upload_id: selfie.upload_id,
+        document_number: None,
+    };
+    let uploaded = sessions
+        .submit_uploaded_step(session.id, SessionStep::Selfie, selfie_reference(), tenant_id, Some(user_id), user_id)
+        .await
+        .unwrap();
+    assert_eq!(uploaded.steps[1].status, StepStatus::Passed);
+    assert!(uploaded.ready_to_complete);
+
+    let reused = uploads().fetch(selfie.upload_id, user_id, UploadPurpose::Selfie).await;
+    assert!(matches!(reused, Err(AppError::Conflict(_))));
+    let stranger = uploads().fetch(token.upload_id, Uuid::new_v4(), UploadPurpose::IdentityDocument).await;
+    assert!(matches!(stranger, Err(AppError::NotFound(_))));
+    let _ = std::fs::remove_dir_all(&storage_root);
+}
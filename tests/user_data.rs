@@ -0,0 +1,271 @@
+use axum::http::{header, HeaderMap, HeaderValue};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use openbank::auth::model::JwtClaims;
+use openbank::core::conditional::{self, Preconditions};
+use openbank::core::error::AppError;
+use openbank::shared::account_labels::UpdateAccountDetailsRequest;
+use openbank::user_data::repository::UserDataRepository;
+use openbank::user_data::service::{UserDataService, MAX_BATCH_ACCOUNTS};
+use openbank_test_support::{test_config, Seeder, TestDatabase};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+
+async fn post(pool: &PgPool, account_id: Uuid, before: i64, amount: i64, created_at: DateTime<Utc>) {
+    sqlx::query(
+        "INSERT INTO balance_history (account_id, balance_before, balance_after, amount_changed, created_at)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(account_id)
+    .bind(before)
+    .bind(before + amount)
+    .bind(amount)
+    .bind(created_at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn balance_as_of_matches_the_postings_up_to_that_time() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+
+    let now = Utc::now();
+    let opened_at = now - Duration::days(720);
+    let account_id = seeder.account(None, "USD", 650).await.id;
+    sqlx::query("UPDATE accounts SET created_at = $2 WHERE id = $1")
+        .bind(account_id)
+        .bind(opened_at)
+        .execute(&pool)
+        .await
+        .unwrap();
+    post(&pool, account_id, 0, 1000, opened_at + Duration::days(1)).await;
+    post(&pool, account_id, 1000, -400, opened_at + Duration::days(30)).await;
+    // Two postings of one transaction share its timestamp
+    let recent = now - Duration::days(2);
+    post(&pool, account_id, 600, 100, recent).await;
+    post(&pool, account_id, 700, -50, recent).await;
+
+    let service = UserDataService::new(UserDataRepository::new(pool.clone()));
+
+    // Near the opening the postings before are summed, near now the ones
+    // since are taken off the current balance; both agree with the ledger
+    let early = service
+        .get_balance_as_of(account_id, opened_at + Duration::days(10))
+        .await
+        .unwrap();
+    assert_eq!(early.ledger_balance, 1000);
+    assert_eq!(early.last_posted_at.unwrap().timestamp(), (opened_at + Duration::days(1)).timestamp());
+
+    let before_recent = service
+        .get_balance_as_of(account_id, recent - Duration::seconds(1))
+        .await
+        .unwrap();
+    assert_eq!(before_recent.ledger_balance, 600);
+    let at_recent = service.get_balance_as_of(account_id, recent).await.unwrap();
+    assert_eq!(at_recent.ledger_balance, 650);
+
+    let before_opening = service
+        .get_balance_as_of(account_id, Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(before_opening.ledger_balance, 0);
+    assert!(before_opening.last_posted_at.is_none());
+
+    let future = service.get_balance_as_of(account_id, now + Duration::days(1)).await;
+    assert!(matches!(future, Err(AppError::BadRequest(_))));
+    let missing = service.get_balance_as_of(Uuid::new_v4(), now).await;
+    assert!(matches!(missing, Err(AppError::NotFound(_))));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn accounts_are_found_by_label_or_nickname() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+
+    let account_id = seeder.account(None, "USD", 0).await.id;
+    let user_id: Uuid = sqlx::query_scalar("SELECT user_id FROM accounts WHERE id = $1")
+        .bind(account_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let service = UserDataService::new(UserDataRepository::new(pool.clone()));
+
+    let updated = service
+        .update_account_details(
+            account_id,
+            UpdateAccountDetailsRequest {
+                nickname: Some(" Bills ".to_string()),
+                labels: Some(vec!["Rent".to_string(), "Joint".to_string()]),
+                metadata: Some(json!({"cost_center": "home"})),
+            },
+            &Preconditions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.nickname.as_deref(), Some("Bills"));
+    assert_eq!(updated.labels, vec!["rent", "joint"]);
+
+    for search in ["RENT", "bills"] {
+        let found = service.get_user_accounts(user_id, Some(search)).await.unwrap();
+        assert_eq!(found.len(), 1, "searching for {}", search);
+    }
+    assert!(service.get_user_accounts(user_id, Some("savings")).await.unwrap().is_empty());
+
+    // Fields left out are kept; an empty nickname clears it
+    let cleared = service
+        .update_account_details(
+            account_id,
+            UpdateAccountDetailsRequest {
+                nickname: Some(String::new()),
+                ..Default::default()
+            },
+            &Preconditions::default(),
+        )
+        .await
+        .unwrap();
+    assert!(cleared.nickname.is_none());
+    assert_eq!(cleared.labels, vec!["rent", "joint"]);
+    assert_eq!(cleared.metadata, Some(json!({"cost_center": "home"})));
+
+    let invalid = service
+        .update_account_details(
+            account_id,
+            UpdateAccountDetailsRequest {
+                metadata: Some(json!("note")),
+                ..Default::default()
+            },
+            &Preconditions::default(),
+        )
+        .await;
+    assert!(matches!(invalid, Err(AppError::Validation(_))));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn account_changes_need_the_current_etag() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+
+    let account_id = seeder.account(None, "USD", 0).await.id;
+    let user_id: Uuid = sqlx::query_scalar("SELECT user_id FROM accounts WHERE id = $1")
+        .bind(account_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let service = UserDataService::new(UserDataRepository::new(pool.clone()));
+    let if_match = |etag: &str| {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(etag).unwrap());
+        Preconditions::from_headers(&headers)
+    };
+    let nickname = |nickname: &str| UpdateAccountDetailsRequest {
+        nickname: Some(nickname.to_string()),
+        ..Default::default()
+    };
+
+    let read = service.get_user_accounts(user_id, None).await.unwrap();
+    let etag = conditional::etag(&read[0]);
+
+    let updated = service
+        .update_account_details(account_id, nickname("Bills"), &if_match(&etag))
+        .await
+        .unwrap();
+    assert_eq!(updated.nickname.as_deref(), Some("Bills"));
+
+    // A second writer holding the same read loses
+    let stale = service
+        .update_account_details(account_id, nickname("Savings"), &if_match(&etag))
+        .await;
+    assert!(matches!(stale, Err(AppError::PreconditionFailed(_))));
+
+    let current = service
+        .update_account_details(account_id, nickname("Savings"), &if_match(&conditional::etag(&updated)))
+        .await
+        .unwrap();
+    assert_eq!(current.nickname.as_deref(), Some("Savings"));
+
+    database.cleanup().await;
+}
+
+#[tokio::test]
+async fn balances_are_read_in_bulk_with_an_error_per_account() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let project = seeder.project(&[]).await;
+    let tenant_id = project.project.organization_id;
+
+    let first = seeder.account(None, "USD", 100).await.id;
+    let second = seeder.account(None, "USD", 200).await.id;
+    let elsewhere = seeder.account(None, "USD", 300).await.id;
+    // The first two belong to one user of the tenant
+    let user_id: Uuid = sqlx::query_scalar(
+        "UPDATE accounts SET tenant_id = $2, user_id = (SELECT user_id FROM accounts WHERE id = $1)
+         WHERE id = ANY($3) RETURNING user_id",
+    )
+    .bind(first)
+    .bind(tenant_id)
+    .bind(vec![first, second])
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let now = Utc::now();
+    let claims = JwtClaims {
+        iss: "openbank-auth".to_string(),
+        aud: "openbank-api".to_string(),
+        sub: user_id.to_string(),
+        exp: (now + Duration::hours(1)).timestamp(),
+        iat: now.timestamp(),
+        jti: Uuid::new_v4().to_string(),
+        developer_id: project.developer.id,
+        project_id: project.project.id,
+        tenant_id,
+        scopes: Vec::new(),
+        user_id: Some(user_id),
+        auth_time: None,
+        acr: None,
+    };
+    let service = UserDataService::new(UserDataRepository::new(pool.clone()));
+
+    let missing = Uuid::new_v4();
+    let results = service
+        .batch_get_balances(&claims, &[second, elsewhere, missing, first])
+        .await
+        .unwrap();
+    let ids: Vec<Uuid> = results.iter().map(|result| result.account_id).collect();
+    assert_eq!(ids, vec![second, elsewhere, missing, first]);
+    assert_eq!(results[0].balance.as_ref().unwrap().ledger_balance, 200);
+    assert_eq!(results[3].balance.as_ref().unwrap().ledger_balance, 100);
+    // Another tenant's account looks the same as one that does not exist
+    for result in &results[1..3] {
+        assert!(result.balance.is_none());
+        assert_eq!(result.error.as_ref().unwrap().code, "NOT_FOUND");
+    }
+
+    let empty = service.batch_get_balances(&claims, &[]).await;
+    assert!(matches!(empty, Err(AppError::Validation(_))));
+    let too_many = service
+        .batch_get_balances(&claims, &vec![first; MAX_BATCH_ACCOUNTS + 1])
+        .await;
+    assert!(matches!(too_many, Err(AppError::Validation(_))));
+
+    database.cleanup().await;
+}
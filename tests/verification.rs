@@ -0,0 +1,184 @@
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use chrono::Duration;
+use openbank::core::audit::AuditLogger;
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::mailer::{EmailMessage, Mailer};
+use openbank::core::sms::{SmsMessage, SmsSender};
+use openbank::verification::model::{
+    ConfirmVerificationCodeRequest, RequestVerificationCodeRequest, VerificationChannel, VerificationSettings,
+};
+use openbank::verification::repository::VerificationRepository;
+use openbank::verification::service::VerificationService;
+use openbank_test_support::TestDatabase;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, message: EmailMessage) -> AppResult<()> {
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct RecordingSmsSender {
+    sent: Mutex<Vec<SmsMessage>>,
+}
+
+#[async_trait]
+impl SmsSender for RecordingSmsSender {
+    async fn send(&self, message: SmsMessage) -> AppResult<()> {
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+async fn seed_user(pool: &PgPool) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name)
+         VALUES ($1, 'x', 'Test', 'User') RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+/// The six-digit code in the last message
+fn code_in(body: &str) -> String {
+    body.split_whitespace()
+        .map(|word| word.trim_end_matches('.'))
+        .find(|word| word.len() == 6 && word.chars().all(|c| c.is_ascii_digit()))
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn codes_verify_the_email_address_and_phone_number() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let user_id = seed_user(&pool).await;
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let sms = Arc::new(RecordingSmsSender::default());
+    let service = VerificationService::new(
+        VerificationRepository::new(pool.clone()),
+        VerificationSettings {
+            code_ttl: Duration::minutes(10),
+            max_attempts: 3,
+            resend_interval: Duration::zero(),
+            codes_per_hour: 3,
+            code_key: "test-key".to_string(),
+        },
+        mailer.clone(),
+        sms.clone(),
+        AuditLogger::in_memory(),
+    );
+
+    // Email
+    let sent = service
+        .request_code(
+            user_id,
+            RequestVerificationCodeRequest {
+                channel: VerificationChannel::Email,
+                phone: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(sent.destination.contains("***@example.com"));
+    let code = code_in(&mailer.sent.lock().unwrap()[0].body);
+
+    let wrong = service
+        .confirm_code(
+            user_id,
+            ConfirmVerificationCodeRequest {
+                channel: VerificationChannel::Email,
+                code: "not-it".to_string(),
+            },
+        )
+        .await;
+    assert!(matches!(wrong, Err(AppError::BadRequest(_))));
+    let status = service
+        .confirm_code(
+            user_id,
+            ConfirmVerificationCodeRequest {
+                channel: VerificationChannel::Email,
+                code: code.clone(),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(status.email_verified);
+    assert!(!status.phone_verified);
+
+    // A code is spent by its first use
+    let again = service
+        .confirm_code(
+            user_id,
+            ConfirmVerificationCodeRequest {
+                channel: VerificationChannel::Email,
+                code,
+            },
+        )
+        .await;
+    assert!(matches!(again, Err(AppError::BadRequest(_))));
+
+    // Phone: the confirmed number becomes the profile's
+    let phone_request = || RequestVerificationCodeRequest {
+        channel: VerificationChannel::Phone,
+        phone: Some("+44 7700 900123".to_string()),
+    };
+    service.request_code(user_id, phone_request()).await.unwrap();
+    let sent = sms.sent.lock().unwrap()[0].clone();
+    assert_eq!(sent.to, "+447700900123");
+    let status = service
+        .confirm_code(
+            user_id,
+            ConfirmVerificationCodeRequest {
+                channel: VerificationChannel::Phone,
+                code: code_in(&sent.body),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(status.phone_verified);
+    assert_eq!(status.phone.as_deref(), Some("+447700900123"));
+    let verified = service.request_code(user_id, phone_request()).await;
+    assert!(matches!(verified, Err(AppError::Conflict(_))));
+
+    // Only a few codes are sent an hour
+    for _ in 0..2 {
+        service
+            .request_code(
+                user_id,
+                RequestVerificationCodeRequest {
+                    channel: VerificationChannel::Phone,
+                    phone: Some("+447700900999".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+    }
+    let limited = service
+        .request_code(
+            user_id,
+            RequestVerificationCodeRequest {
+                channel: VerificationChannel::Phone,
+                phone: Some("+447700900999".to_string()),
+            },
+        )
+        .await;
+    assert!(matches!(limited, Err(AppError::TooManyRequests { .. })));
+
+    database.cleanup().await;
+}
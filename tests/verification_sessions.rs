@@ -0,0 +1,309 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::{AppError, AppResult};
+use openbank::core::storage::{LocalStorage, Storage};
+use openbank::identity::model::VerificationStatus;
+use openbank::identity::repository::IdentityRepository;
+use openbank::kyc::{model::KycLimits, repository::KycRepository, service::KycPolicyService};
+use openbank::reviews::repository::ReviewRepository;
+use openbank::reviews::service::ReviewQueue;
+use openbank::shared::traits::Repository;
+use openbank::uploads::repository::UploadRepository;
+use openbank::uploads::service::UploadService;
+use openbank::verification_sessions::checks::{SessionChecks, StepCheck};
+use openbank::verification_sessions::model::{
+    CreateVerificationSessionRequest, SessionSettings, SessionStep, StepStatus, StepUpload, VerificationSessionStatus,
+};
+use openbank::verification_sessions::repository::VerificationSessionRepository;
+use openbank::verification_sessions::service::VerificationSessionService;
+use openbank_test_support::{test_config, test_image, TestDatabase, TestStateBuilder};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Rejects uploads named "blurred.png" and leaves liveness videos to a reviewer
+struct StubChecks;
+
+#[async_trait]
+impl SessionChecks for StubChecks {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    async fn check(
+        &self,
+        _session_id: Uuid,
+        _document_type: &str,
+        step: SessionStep,
+        upload: &StepUpload,
+    ) -> AppResult<StepCheck> {
+        let reference = Some(format!("{}-ref", step.as_str()));
+        if upload.file_name == "blurred.png" {
+            return Ok(StepCheck::Failed {
+                reference,
+                reason: "The document is not legible".to_string(),
+            });
+        }
+        match step {
+            SessionStep::Liveness => Ok(StepCheck::NeedsReview { reference }),
+            _ => Ok(StepCheck::Passed { reference }),
+        }
+    }
+}
+
+fn upload(content_type: &str, document_number: Option<&str>) -> StepUpload {
+    let content = match content_type {
+        "image/png" | "image/jpeg" => test_image(content_type),
+        _ => b"ok".to_vec(),
+    };
+    StepUpload {
+        file_name: "upload".to_string(),
+        content_type: content_type.to_string(),
+        content,
+        document_number: document_number.map(str::to_string),
+    }
+}
+
+async fn user_in_tenant(pool: &PgPool) -> (Uuid, Uuid) {
+    let tenant_id: Uuid = sqlx::query_scalar("INSERT INTO organizations (name) VALUES ('Acme') RETURNING id")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (email, password_hash, first_name, last_name, tenant_id)
+         VALUES ($1, 'x', 'Test', 'User', $2) RETURNING id",
+    )
+    .bind(format!("{}@example.com", Uuid::new_v4()))
+    .bind(tenant_id)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    (tenant_id, user_id)
+}
+
+#[tokio::test]
+async fn sessions_resume_and_approve_once_every_step_passes() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let state = TestStateBuilder::new().postgres(pool.clone()).build().await;
+    let (tenant_id, user_id) = user_in_tenant(&pool).await;
+    let audit_logger = AuditLogger::in_memory();
+    let storage_root = std::env::temp_dir().join(format!("openbank-sessions-{}", Uuid::new_v4()));
+    let service = VerificationSessionService::new(
+        VerificationSessionRepository::new(pool.clone(), state.user_cipher.clone()),
+        IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+        Arc::new(LocalStorage::new(&storage_root)),
+        UploadService::new(
+            UploadRepository::new(pool.clone()),
+            IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+            Arc::new(LocalStorage::new(&storage_root)),
+            Duration::minutes(5),
+            audit_logger.clone(),
+        ),
+        Arc::new(StubChecks),
+        SessionSettings::from_config(&test_config()),
+        ReviewQueue::new(ReviewRepository::new(pool.clone()), audit_logger.clone()),
+        KycPolicyService::new(
+            KycRepository::new(pool.clone()),
+            KycLimits::from_config(&test_config()),
+            audit_logger.clone(),
+        ),
+        audit_logger.clone(),
+    );
+    let request = || CreateVerificationSessionRequest {
+        user_id: None,
+        document_type: "passport".to_string(),
+        steps: Some(vec![SessionStep::Selfie, SessionStep::Document]),
+    };
+
+    let session = service.create(request(), tenant_id, Some(user_id), user_id).await.unwrap();
+    assert_eq!(session.next_step, Some(SessionStep::Document));
+
+    // Steps are taken in order, and the document needs its number
+    let out_of_order = service
+        .submit_step(
+            session.id,
+            SessionStep::Selfie,
+            upload("image/png", None),
+            tenant_id,
+            Some(user_id),
+            user_id,
+        )
+        .await;
+    assert!(matches!(out_of_order, Err(AppError::Conflict(_))));
+    let without_number = service
+        .submit_step(
+            session.id,
+            SessionStep::Document,
+            upload("image/png", None),
+            tenant_id,
+            Some(user_id),
+            user_id,
+        )
+        .await;
+    assert!(matches!(without_number, Err(AppError::Validation(_))));
+
+    // A rejected upload can be retried; an interrupted flow resumes
+    let failed = service
+        .submit_step(
+            session.id,
+            SessionStep::Document,
+            StepUpload {
+                file_name: "blurred.png".to_string(),
+                ..upload("image/png", Some("P1234567"))
+            },
+            tenant_id,
+            Some(user_id),
+            user_id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(failed.steps[0].status, StepStatus::Failed);
+    assert_eq!(failed.steps[0].attempts_remaining, 2);
+    let resumed = service.create(request(), tenant_id, Some(user_id), user_id).await.unwrap();
+    assert_eq!(resumed.id, session.id);
+    assert_eq!(resumed.next_step, Some(SessionStep::Document));
+
+    service
+        .submit_step(
+            session.id,
+            SessionStep::Document,
+            upload("image/jpeg", Some("P1234567")),
+            tenant_id,
+            Some(user_id),
+            user_id,
+        )
+        .await
+        .unwrap();
+    let uploaded = service
+        .submit_step(
+            session.id,
+            SessionStep::Selfie,
+            upload("image/png", None),
+            tenant_id,
+            Some(user_id),
+            user_id,
+        )
+        .await
+        .unwrap();
+    assert!(uploaded.ready_to_complete);
+
+    let decided = service.complete(session.id, tenant_id, Some(user_id), user_id).await.unwrap();
+    assert_eq!(decided.status, VerificationSessionStatus::Approved);
+    let identity = IdentityRepository::new(pool.clone(), state.user_cipher.clone());
+    let document = identity.find_by_id(decided.identity_verification_id.unwrap()).await.unwrap().unwrap();
+    assert!(matches!(document.status, VerificationStatus::Completed));
+    assert_eq!(document.document_number.as_deref(), Some("P1234567"));
+    assert!(decided.face_verification_id.is_some());
+
+    // Other users cannot see the session
+    let stranger = service.get(session.id, tenant_id, Some(Uuid::new_v4())).await;
+    assert!(matches!(stranger, Err(AppError::NotFound(_))));
+
+    let events = audit_logger.recorded_events();
+    assert!(events
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::VerificationSessionDecided)));
+    let _ = std::fs::remove_dir_all(&storage_root);
+}
+
+#[tokio::test]
+async fn unchecked_steps_go_to_review_and_stale_sessions_expire() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let state = TestStateBuilder::new().postgres(pool.clone()).build().await;
+    let (tenant_id, user_id) = user_in_tenant(&pool).await;
+    let (_, other_user_id) = user_in_tenant(&pool).await;
+    let audit_logger = AuditLogger::in_memory();
+    let storage_root = std::env::temp_dir().join(format!("openbank-sessions-{}", Uuid::new_v4()));
+    let storage: Arc<dyn Storage> = Arc::new(LocalStorage::new(&storage_root));
+    let review_repository = ReviewRepository::new(pool.clone());
+    let service = VerificationSessionService::new(
+        VerificationSessionRepository::new(pool.clone(), state.user_cipher.clone()),
+        IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+        storage.clone(),
+        UploadService::new(
+            UploadRepository::new(pool.clone()),
+            IdentityRepository::new(pool.clone(), state.user_cipher.clone()),
+            storage.clone(),
+            Duration::minutes(5),
+            audit_logger.clone(),
+        ),
+        Arc::new(StubChecks),
+        SessionSettings::from_config(&test_config()),
+        ReviewQueue::new(review_repository.clone(), audit_logger.clone()),
+        KycPolicyService::new(
+            KycRepository::new(pool.clone()),
+            KycLimits::from_config(&test_config()),
+            audit_logger.clone(),
+        ),
+        audit_logger.clone(),
+    );
+    let request = |user_id| CreateVerificationSessionRequest {
+        user_id: Some(user_id),
+        document_type: "driving_licence".to_string(),
+        steps: None,
+    };
+
+    let session = service.create(request(user_id), tenant_id, None, user_id).await.unwrap();
+    for (step, content_type) in [
+        (SessionStep::Document, "application/pdf"),
+        (SessionStep::Selfie, "image/jpeg"),
+        (SessionStep::Liveness, "video/mp4"),
+    ] {
+        service
+            .submit_step(session.id, step, upload(content_type, Some("D-42")), tenant_id, None, user_id)
+            .await
+            .unwrap();
+    }
+    let decided = service.complete(session.id, tenant_id, None, user_id).await.unwrap();
+    assert_eq!(decided.status, VerificationSessionStatus::InReview);
+    let flagged: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM verification_reviews WHERE subject_user_id = $1")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(flagged, 2);
+
+    // Sessions of other tenants are not reachable
+    let foreign = service.create(request(other_user_id), tenant_id, None, user_id).await;
+    assert!(matches!(foreign, Err(AppError::NotFound(_))));
+
+    // A session past its expiry is expired and its uploads deleted
+    let other_tenant: Uuid = sqlx::query_scalar("SELECT tenant_id FROM users WHERE id = $1")
+        .bind(other_user_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let abandoned = service.create(request(other_user_id), other_tenant, None, other_user_id).await.unwrap();
+    service
+        .submit_step(
+            abandoned.id,
+            SessionStep::Document,
+            upload("image/png", Some("X-1")),
+            other_tenant,
+            None,
+            other_user_id,
+        )
+        .await
+        .unwrap();
+    sqlx::query("UPDATE verification_sessions SET expires_at = $2 WHERE id = $1")
+        .bind(abandoned.id)
+        .bind(Utc::now() - Duration::minutes(1))
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(service.expire_due().await.unwrap(), 1);
+    let expired = service.get(abandoned.id, other_tenant, None).await.unwrap();
+    assert_eq!(expired.status, VerificationSessionStatus::Expired);
+    assert!(storage
+        .get(&format!("verification-sessions/{}/document-1", abandoned.id))
+        .await
+        .is_err());
+    let _ = std::fs::remove_dir_all(&storage_root);
+}
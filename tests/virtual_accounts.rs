@@ -0,0 +1,137 @@
+use chrono::{Duration, Utc};
+use openbank::account_controls::{repository::AccountControlRepository, service::AccountFreezeGuard};
+use openbank::account_numbers::{
+    repository::AccountNumberRepository, service::{default_scheme, AccountNumberGenerator},
+};
+use openbank::core::error::AppError;
+use openbank::payments::model::{Payment, PaymentMethod, PaymentStatus};
+use openbank::payments::repository::PaymentRepository;
+use openbank::shared::traits::Repository;
+use openbank::virtual_accounts::model::{VirtualAccountBalanceQuery, VirtualAccountTransactionsQuery};
+use openbank::virtual_accounts::repository::VirtualAccountRepository;
+use openbank::virtual_accounts::service::VirtualAccountService;
+use openbank_test_support::{test_config, SeededAccount, Seeder, TestDatabase};
+use uuid::Uuid;
+
+/// Create a payment into the virtual account and settle it
+async fn pay_in(repository: &PaymentRepository, from: Uuid, to: Uuid, virtual_account_id: Uuid, amount: i64) {
+    let now = Utc::now();
+    let payment = repository
+        .create(Payment {
+            id: Uuid::new_v4(),
+            from_account_id: from,
+            to_account_id: Some(to),
+            amount,
+            currency: "USD".to_string(),
+            payment_method: PaymentMethod::BankTransfer,
+            status: PaymentStatus::Pending,
+            reference: format!("PAY_{}", Uuid::new_v4()),
+            description: None,
+            recipient_info: None,
+            metadata: None,
+            external_reference: None,
+            project_id: None,
+            tenant_id: None,
+            fee_amount: 0,
+            fee_breakdown: None,
+            execute_at: None,
+            execution_timezone: None,
+            executed_at: None,
+            execution_error: None,
+            transaction_id: None,
+            expected_settlement_at: None,
+            settled_at: None,
+            created_by: None,
+            duplicate_of: None,
+            to_virtual_account_id: Some(virtual_account_id),
+            created_at: now,
+            updated_at: now,
+            target_amount: None,
+            target_currency: None,
+        })
+        .await
+        .unwrap();
+    repository
+        .post_pending(&payment, Utc::now() - Duration::minutes(1))
+        .await
+        .unwrap()
+        .unwrap();
+    repository.settle(payment.id).await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn virtual_account_balance_comes_from_its_own_postings() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let seeder = Seeder::new(pool.clone(), &test_config());
+    let tenant_id = seeder.developer().await.organization_id;
+    let payer = seeder.account(Some(tenant_id), "USD", 10_000).await.id;
+    let SeededAccount { user_id: owner, id: parent } = seeder.account(Some(tenant_id), "USD", 500).await;
+    let virtual_account_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO virtual_accounts (user_id, parent_account_id, account_number, account_name, tenant_id)
+         VALUES ($1, $2, $3, 'Rent collection', $4) RETURNING id",
+    )
+    .bind(owner)
+    .bind(parent)
+    .bind(&Uuid::new_v4().simple().to_string()[..20])
+    .bind(tenant_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let payments = PaymentRepository::new(pool.clone());
+    pay_in(&payments, payer, parent, virtual_account_id, 1_200).await;
+    pay_in(&payments, payer, parent, virtual_account_id, 300).await;
+
+    let service = VirtualAccountService::new(
+        VirtualAccountRepository::new(pool.clone()),
+        AccountFreezeGuard::new(AccountControlRepository::new(pool.clone()), false),
+        AccountNumberGenerator::new(AccountNumberRepository::new(pool.clone()), default_scheme(&test_config())),
+    );
+
+    // The parent holds its own funds too; the virtual account only what was paid into it
+    let balance = service
+        .get_balance(virtual_account_id, tenant_id, VirtualAccountBalanceQuery { as_of: None })
+        .await
+        .unwrap();
+    assert_eq!(balance.parent_account_id, parent);
+    assert_eq!(balance.balance, 1_500);
+    assert_eq!((balance.total_credits, balance.total_debits), (1_500, 0));
+    assert_eq!(balance.posting_count, 2);
+
+    let page = service
+        .get_transactions(
+            virtual_account_id,
+            tenant_id,
+            VirtualAccountTransactionsQuery { page: 1, limit: 1, from: None, to: None },
+        )
+        .await
+        .unwrap();
+    assert_eq!((page.total, page.total_pages), (2, 2));
+    assert_eq!(page.data[0].amount, 300);
+    assert_eq!(page.data[0].balance_after, 1_500);
+
+    let future = Utc::now() + Duration::hours(1);
+    let page = service
+        .get_transactions(
+            virtual_account_id,
+            tenant_id,
+            VirtualAccountTransactionsQuery { page: 1, limit: 20, from: Some(future), to: None },
+        )
+        .await
+        .unwrap();
+    assert!(page.data.is_empty());
+
+    // Other tenants cannot see the virtual account
+    let other_tenant_id = seeder.developer().await.organization_id;
+    assert!(matches!(
+        service
+            .get_balance(virtual_account_id, other_tenant_id, VirtualAccountBalanceQuery { as_of: None })
+            .await,
+        Err(AppError::NotFound(_))
+    ));
+
+    database.cleanup().await;
+}
@@ -0,0 +1,107 @@
+use openbank::core::audit::{AuditEventType, AuditLogger};
+use openbank::core::error::AppError;
+use openbank::webhooks::model::{DeadLetterFilter, DeliverySettings, ReplayDeadLettersRequest, WebhookDeliveryStatus};
+use openbank::webhooks::repository::WebhookRepository;
+use openbank::webhooks::service::WebhookService;
+use openbank_test_support::TestDatabase;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Nothing listens here, so every attempt fails to connect
+const UNREACHABLE_URL: &str = "http://127.0.0.1:9/webhook";
+
+#[tokio::test]
+async fn failed_deliveries_are_dead_lettered_and_replayed() {
+    let Some(database) = TestDatabase::create().await else {
+        return;
+    };
+    let pool = database.pool();
+    let audit_logger = AuditLogger::in_memory();
+    let settings = DeliverySettings {
+        max_attempts: 2,
+        retry_backoff_seconds: 0,
+        timeout_seconds: 2,
+        batch_size: 10,
+    };
+    let service = WebhookService::new(WebhookRepository::new(pool.clone()), audit_logger.clone(), settings);
+    let repository = WebhookRepository::new(pool.clone());
+
+    let queued = repository
+        .enqueue(None, "account.closed", UNREACHABLE_URL, &json!({ "event": "account.closed" }))
+        .await
+        .unwrap();
+    assert_eq!(queued.status, WebhookDeliveryStatus::Pending);
+
+    // The first failure is retried, the second exhausts the attempts
+    let run = service.deliver_due().await.unwrap();
+    assert_eq!((run.delivered, run.retrying, run.dead_lettered), (0, 1, 0));
+    let run = service.deliver_due().await.unwrap();
+    assert_eq!((run.delivered, run.retrying, run.dead_lettered), (0, 0, 1));
+
+    let stats = service.queue_stats().await.unwrap();
+    assert_eq!(stats.dead_letter_depth, 1);
+    assert_eq!(stats.pending_deliveries, 0);
+    assert_eq!(stats.dead_letters_by_event_type[0].event_type, "account.closed");
+
+    let page = service.list_dead_letters(DeadLetterFilter::default(), 1, 20).await.unwrap();
+    assert_eq!(page.total, 1);
+    let dead_letter = &page.data[0];
+    assert_eq!(dead_letter.delivery_id, queued.id);
+    assert_eq!(dead_letter.attempts, 2);
+    assert_eq!(dead_letter.payload, json!({ "event": "account.closed" }));
+    assert!(dead_letter.last_error.is_some());
+    assert!(audit_logger
+        .recorded_events()
+        .iter()
+        .any(|event| matches!(event.event_type, AuditEventType::WebhookDeadLettered)));
+
+    // Replaying queues a fresh delivery, once
+    let actor_id = Uuid::new_v4();
+    let replay = service.replay_dead_letter(dead_letter.id, actor_id).await.unwrap();
+    assert_eq!(replay.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(replay.attempts, 0);
+    assert_eq!(replay.replay_of, Some(dead_letter.id));
+    assert!(matches!(
+        service.replay_dead_letter(dead_letter.id, actor_id).await,
+        Err(AppError::Conflict(_))
+    ));
+
+    let replayed = service.get_dead_letter(dead_letter.id).await.unwrap();
+    assert_eq!(replayed.replay_delivery_id, Some(replay.id));
+    assert_eq!(replayed.replayed_by, Some(actor_id));
+    assert_eq!(service.queue_stats().await.unwrap().dead_letter_depth, 0);
+    let replayed_filter = DeadLetterFilter {
+        event_type: None,
+        replayed: Some(true),
+    };
+    assert_eq!(service.list_dead_letters(replayed_filter, 1, 20).await.unwrap().total, 1);
+
+    // Bulk replay picks up whatever is outstanding for the event type
+    service.deliver_due().await.unwrap();
+    service.deliver_due().await.unwrap();
+    let other = repository
+        .enqueue(None, "identity_verification.expired", UNREACHABLE_URL, &json!({}))
+        .await
+        .unwrap();
+    service.deliver_due().await.unwrap();
+    service.deliver_due().await.unwrap();
+    assert_eq!(service.queue_stats().await.unwrap().dead_letter_depth, 2);
+
+    let response = service
+        .replay_dead_letters(
+            ReplayDeadLettersRequest {
+                ids: None,
+                event_type: Some("identity_verification.expired".to_string()),
+                limit: None,
+            },
+            actor_id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.replayed, 1);
+    let replayed_from = service.get_dead_letter(response.deliveries[0].replay_of.unwrap()).await.unwrap();
+    assert_eq!(replayed_from.delivery_id, other.id);
+    assert_eq!(service.queue_stats().await.unwrap().dead_letter_depth, 1);
+
+    database.cleanup().await;
+}